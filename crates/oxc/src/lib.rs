@@ -17,14 +17,14 @@ pub mod ast_lower {
     pub use oxc_ast_lower::*;
 }
 
-pub mod diagnostics {
+pub mod codegen {
     #[doc(inline)]
-    pub use oxc_diagnostics::*;
+    pub use oxc_codegen::*;
 }
 
-pub mod formatter {
+pub mod diagnostics {
     #[doc(inline)]
-    pub use oxc_formatter::*;
+    pub use oxc_diagnostics::*;
 }
 
 pub mod hir {
@@ -62,12 +62,18 @@ pub mod syntax {
     pub use oxc_syntax::*;
 }
 
+pub mod transformer {
+    #[doc(inline)]
+    pub use oxc_transformer::*;
+}
+
 #[allow(unused_imports)]
 #[test]
 fn import_smoke_test() {
     use crate::{
         allocator::Allocator, ast::ast::Program as AstProgram, ast_lower::AstLower,
-        diagnostics::Error, formatter::Formatter, hir::hir::Program as HirProgram, index::IndexVec,
+        codegen::Codegen, diagnostics::Error, hir::hir::Program as HirProgram, index::IndexVec,
         minifier::Minifier, parser::Parser, semantic::Semantic, span::Span, syntax::NumberBase,
+        transformer::Transformer,
     };
 }
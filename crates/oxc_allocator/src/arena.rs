@@ -5,12 +5,17 @@ use std::{
     self,
     fmt::{self, Debug, Formatter},
     hash::{Hash, Hasher},
+    marker::PhantomData,
     ops, ptr,
 };
 
 use bumpalo::collections;
 pub use bumpalo::collections::String;
-use serde::{ser::SerializeSeq, Serialize, Serializer};
+use serde::{
+    de::{SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
 
 use crate::Allocator;
 
@@ -70,6 +75,19 @@ where
     }
 }
 
+impl<'de, 'alloc, T> Deserialize<'de> for Box<'alloc, T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = T::deserialize(d)?;
+        Ok(Self(Allocator::deserialization().alloc(value)))
+    }
+}
+
 impl<'alloc, T: Hash> Hash for Box<'alloc, T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.0.hash(state);
@@ -167,6 +185,49 @@ where
     }
 }
 
+impl<'de, 'alloc, T> Deserialize<'de> for Vec<'alloc, T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VecVisitor<'alloc, T>(&'alloc Allocator, PhantomData<T>);
+
+        impl<'de, 'alloc, T: Deserialize<'de> + 'alloc> Visitor<'de> for VecVisitor<'alloc, T> {
+            type Value = Vec<'alloc, T>;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut v = Vec::with_capacity_in(seq.size_hint().unwrap_or(0), self.0);
+                while let Some(e) = seq.next_element()? {
+                    v.push(e);
+                }
+                Ok(v)
+            }
+        }
+
+        d.deserialize_seq(VecVisitor(Allocator::deserialization(), PhantomData))
+    }
+}
+
+/// Deserializes a string into the allocator of the current [`Allocator::deserialize_in`]
+///
+/// # Errors
+///
+/// If the value is not a string
+pub fn deserialize_str<'de, 'alloc, D>(d: D) -> Result<&'alloc str, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = std::string::String::deserialize(d)?;
+    Ok(Allocator::deserialization().alloc_str(&s))
+}
+
 impl<'alloc, T: Hash> Hash for Vec<'alloc, T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for e in &self.0 {
@@ -221,4 +282,12 @@ mod test {
         let v = serde_json::to_string(&v).unwrap();
         assert_eq!(v, "[\"x\"]");
     }
+
+    #[test]
+    fn deserialize() {
+        let allocator = Allocator::default();
+        let v: Vec<Box<u32>> =
+            unsafe { allocator.deserialize_in(|| serde_json::from_str("[1, 2]").unwrap()) };
+        assert_eq!(v.iter().map(|b| **b).collect::<std::vec::Vec<_>>(), [1, 2]);
+    }
 }
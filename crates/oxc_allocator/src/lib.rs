@@ -1,8 +1,8 @@
-use std::ops::Deref;
+use std::{cell::Cell, ops::Deref, ptr};
 
 mod arena;
 
-pub use arena::{Box, String, Vec};
+pub use arena::{deserialize_str, Box, String, Vec};
 use bumpalo::Bump;
 
 #[derive(Default)]
@@ -17,3 +17,44 @@ impl Deref for Allocator {
         &self.bump
     }
 }
+
+thread_local! {
+    /// The allocator of the `Box`es and `Vec`s which are being deserialized on this thread
+    static DESERIALIZATION_ALLOCATOR: Cell<*const Allocator> = const { Cell::new(ptr::null()) };
+}
+
+impl Allocator {
+    /// Runs `f`, allocating the [`Box`]es, [`Vec`]s and strings which it deserializes in `self`
+    ///
+    /// # Safety
+    ///
+    /// The values which `f` deserializes must not outlive `self`, i.e. the `'alloc` of the
+    /// deserialized `Box<'alloc, T>`s and `Vec<'alloc, T>`s must be the lifetime of `&self`.
+    pub unsafe fn deserialize_in<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        struct Restore(*const Allocator);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                DESERIALIZATION_ALLOCATOR.with(|allocator| allocator.set(self.0));
+            }
+        }
+
+        let _restore = Restore(DESERIALIZATION_ALLOCATOR.with(|allocator| allocator.replace(self)));
+        f()
+    }
+
+    /// The allocator of the current [`Allocator::deserialize_in`]
+    ///
+    /// # Panics
+    ///
+    /// If nothing is being deserialized on this thread
+    fn deserialization<'alloc>() -> &'alloc Self {
+        let allocator = DESERIALIZATION_ALLOCATOR.with(Cell::get);
+        assert!(
+            !allocator.is_null(),
+            "arena values can only be deserialized in `Allocator::deserialize_in`"
+        );
+        // SAFETY: `deserialize_in` is running, whose caller guarantees that `'alloc` does not
+        // outlive the allocator
+        unsafe { &*allocator }
+    }
+}
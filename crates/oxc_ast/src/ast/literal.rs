@@ -8,12 +8,15 @@ use std::{
 use bitflags::bitflags;
 use num_bigint::BigInt;
 use oxc_span::{Atom, Span};
-use oxc_syntax::NumberBase;
+use oxc_syntax::{
+    escape::{self, EscapeContext, InvalidEscape},
+    number::{parse_bigint_digits, NumberBase},
+};
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
 pub struct BooleanLiteral {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub span: Span,
@@ -31,7 +34,7 @@ impl BooleanLiteral {
 }
 
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
 pub struct NullLiteral {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub span: Span,
@@ -44,7 +47,7 @@ impl Hash for NullLiteral {
 }
 
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
 pub struct NumberLiteral<'a> {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub span: Span,
@@ -69,16 +72,36 @@ impl<'a> Hash for NumberLiteral<'a> {
 }
 
 #[derive(Debug, Clone, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
 pub struct BigintLiteral {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub span: Span,
-    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::serialize::serialize_bigint"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::serialize::serialize_bigint",
+            deserialize_with = "crate::serialize::deserialize_bigint"
+        )
+    )]
     pub value: BigInt,
+    /// Source text including the `n` suffix, e.g. `0x1F_FFn`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Atom,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub base: NumberBase,
+}
+
+impl BigintLiteral {
+    /// The digits of `raw` without the base prefix, separators and `n` suffix, e.g. `1FFF` for `0x1F_FFn`.
+    /// Use `base` for their radix.
+    pub fn digits(&self) -> String {
+        parse_bigint_digits(&self.raw)
+            .map_or_else(|| self.value.to_str_radix(10), |(digits, _)| digits)
+    }
 }
 
 #[derive(Debug, Clone, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
 pub struct RegExpLiteral {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub span: Span,
@@ -89,7 +112,7 @@ pub struct RegExpLiteral {
 }
 
 #[derive(Debug, Clone, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RegExp {
     pub pattern: Atom,
     pub flags: RegExpFlags,
@@ -167,11 +190,11 @@ impl fmt::Display for RegExpFlags {
 }
 
 #[derive(Debug, Clone, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EmptyObject;
 
 #[derive(Debug, Clone, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
 pub struct StringLiteral {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub span: Span,
@@ -179,6 +202,23 @@ pub struct StringLiteral {
 }
 
 impl StringLiteral {
+    /// The text between the quotes, with escape sequences as written.
+    /// Only for string literals parsed from `source_text`, and not for JSX attribute values,
+    /// which have no escape sequences.
+    pub fn raw<'a>(&self, source_text: &'a str) -> &'a str {
+        &source_text[self.span.start as usize + 1..self.span.end as usize - 1]
+    }
+
+    /// The value decoded from the source text as UTF-16 code units.
+    /// Unlike `value`, lone surrogates such as `"\uD800"` are kept as is.
+    ///
+    /// # Errors
+    ///
+    /// The invalid escape sequence, with a span relative to [`StringLiteral::raw`]
+    pub fn cooked_utf16(&self, source_text: &str) -> Result<Vec<u16>, InvalidEscape> {
+        escape::cook_utf16(self.raw(source_text), EscapeContext::String)
+    }
+
     /// Static Semantics: `IsStringWellFormedUnicode`
     /// test for \uD800-\uDFFF
     pub fn is_string_well_formed_unicode(&self) -> bool {
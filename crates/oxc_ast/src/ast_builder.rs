@@ -6,7 +6,7 @@
 )]
 
 use oxc_allocator::{Allocator, Box, String, Vec};
-use oxc_span::{Atom, SourceType, Span};
+use oxc_span::{Atom, SourceType, Span, SPAN};
 use oxc_syntax::{
     operator::{
         AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator, UpdateOperator,
@@ -152,6 +152,10 @@ impl<'a> AstBuilder<'a> {
         Statement::EmptyStatement(self.alloc(EmptyStatement { span }))
     }
 
+    pub fn error_statement(&self, span: Span) -> Statement<'a> {
+        Statement::ErrorStatement(self.alloc(ErrorStatement { span }))
+    }
+
     pub fn expression_statement(&self, span: Span, expression: Expression<'a>) -> Statement<'a> {
         Statement::ExpressionStatement(self.alloc(ExpressionStatement { span, expression }))
     }
@@ -353,6 +357,7 @@ impl<'a> AstBuilder<'a> {
         arguments: Vec<'a, Argument<'a>>,
         optional: bool, // for optional chaining
         type_parameters: Option<Box<'a, TSTypeParameterInstantiation<'a>>>,
+        pure: bool,
     ) -> Expression<'a> {
         Expression::CallExpression(self.alloc(CallExpression {
             span,
@@ -360,6 +365,7 @@ impl<'a> AstBuilder<'a> {
             arguments,
             optional,
             type_parameters,
+            pure,
         }))
     }
 
@@ -466,12 +472,14 @@ impl<'a> AstBuilder<'a> {
         callee: Expression<'a>,
         arguments: Vec<'a, Argument<'a>>,
         type_parameters: Option<Box<'a, TSTypeParameterInstantiation<'a>>>,
+        pure: bool,
     ) -> Expression<'a> {
         Expression::NewExpression(self.alloc(NewExpression {
             span,
             callee,
             arguments,
             type_parameters,
+            pure,
         }))
     }
 
@@ -546,6 +554,10 @@ impl<'a> AstBuilder<'a> {
         Expression::ThisExpression(self.alloc(ThisExpression { span }))
     }
 
+    pub fn error_expression(&self, span: Span) -> Expression<'a> {
+        Expression::ErrorExpression(self.alloc(ErrorExpression { span }))
+    }
+
     pub fn unary_expression(
         &self,
         span: Span,
@@ -600,9 +612,10 @@ impl<'a> AstBuilder<'a> {
         pattern: BindingPattern<'a>,
         accessibility: Option<TSAccessibility>,
         readonly: bool,
+        r#override: bool,
         decorators: Vec<'a, Decorator<'a>>,
     ) -> FormalParameter<'a> {
-        FormalParameter { span, pattern, accessibility, readonly, decorators }
+        FormalParameter { span, pattern, accessibility, readonly, r#override, decorators }
     }
 
     pub fn function(
@@ -695,6 +708,7 @@ impl<'a> AstBuilder<'a> {
         value: Option<Expression<'a>>,
         computed: bool,
         r#static: bool,
+        decorators: Vec<'a, Decorator<'a>>,
     ) -> ClassElement<'a> {
         ClassElement::AccessorProperty(self.alloc(AccessorProperty {
             span,
@@ -702,6 +716,7 @@ impl<'a> AstBuilder<'a> {
             value,
             computed,
             r#static,
+            decorators,
         }))
     }
 
@@ -813,10 +828,10 @@ impl<'a> AstBuilder<'a> {
         span: Span,
         specifiers: Vec<'a, ImportDeclarationSpecifier>,
         source: StringLiteral,
-        assertions: Option<Vec<'a, ImportAttribute>>,
+        with_clause: Option<WithClause<'a>>,
         import_kind: ImportOrExportKind,
     ) -> Box<'a, ImportDeclaration<'a>> {
-        self.alloc(ImportDeclaration { span, specifiers, source, assertions, import_kind })
+        self.alloc(ImportDeclaration { span, specifiers, source, with_clause, import_kind })
     }
 
     pub fn export_all_declaration(
@@ -824,10 +839,10 @@ impl<'a> AstBuilder<'a> {
         span: Span,
         exported: Option<ModuleExportName>,
         source: StringLiteral,
-        assertions: Option<Vec<'a, ImportAttribute>>,
+        with_clause: Option<WithClause<'a>>,
         export_kind: ImportOrExportKind,
     ) -> Box<'a, ExportAllDeclaration<'a>> {
-        self.alloc(ExportAllDeclaration { span, exported, source, assertions, export_kind })
+        self.alloc(ExportAllDeclaration { span, exported, source, with_clause, export_kind })
     }
 
     pub fn export_default_declaration(
@@ -845,9 +860,17 @@ impl<'a> AstBuilder<'a> {
         declaration: Option<Declaration<'a>>,
         specifiers: Vec<'a, ExportSpecifier>,
         source: Option<StringLiteral>,
+        with_clause: Option<WithClause<'a>>,
         export_kind: ImportOrExportKind,
     ) -> Box<'a, ExportNamedDeclaration<'a>> {
-        self.alloc(ExportNamedDeclaration { span, declaration, specifiers, source, export_kind })
+        self.alloc(ExportNamedDeclaration {
+            span,
+            declaration,
+            specifiers,
+            source,
+            with_clause,
+            export_kind,
+        })
     }
 
     /* ---------- JSX ----------------- */
@@ -1492,4 +1515,64 @@ impl<'a> AstBuilder<'a> {
     pub fn js_doc_unknown_type(&self, span: Span) -> TSType<'a> {
         TSType::JSDocUnknownType(self.alloc(JSDocUnknownType { span }))
     }
+
+    /* ---------- Synthesized ---------- */
+    // Shorthands for nodes created by transforms and fixers, which have no source text
+    // and use the empty `SPAN`.
+
+    pub fn string_literal_expression(&self, value: &str) -> Expression<'a> {
+        self.literal_string_expression(StringLiteral { span: SPAN, value: Atom::from(value) })
+    }
+
+    /// A number, printed from its value
+    ///
+    /// The value is expected to be non-negative, negative numbers are unary `-` expressions.
+    /// `Infinity` and `NaN` have no literal, so the codegen prints them as `(1 / 0)` and
+    /// `(0 / 0)`, which unlike the globals cannot be shadowed.
+    pub fn number_literal_expression(&self, value: f64) -> Expression<'a> {
+        let base = if value.fract() == 0.0 { NumberBase::Decimal } else { NumberBase::Float };
+        let raw = self.new_str(&value.to_string());
+        self.literal_number_expression(NumberLiteral::new(SPAN, value, raw, base))
+    }
+
+    pub fn boolean_literal_expression(&self, value: bool) -> Expression<'a> {
+        self.literal_boolean_expression(BooleanLiteral { span: SPAN, value })
+    }
+
+    pub fn null_literal_expression(&self) -> Expression<'a> {
+        self.literal_null_expression(NullLiteral { span: SPAN })
+    }
+
+    pub fn identifier_reference_expression(&self, name: &str) -> Expression<'a> {
+        self.identifier_expression(IdentifierReference { span: SPAN, name: Atom::from(name) })
+    }
+
+    /// `void 0`, which is `undefined` even where the global is shadowed
+    pub fn void_0(&self) -> Expression<'a> {
+        self.unary_expression(SPAN, UnaryOperator::Void, self.number_literal_expression(0.0))
+    }
+
+    /// `object.property`
+    pub fn member_expression_by_name(
+        &self,
+        object: Expression<'a>,
+        property: &str,
+    ) -> Expression<'a> {
+        let property = IdentifierName { span: SPAN, name: Atom::from(property) };
+        self.static_member_expression(SPAN, object, property, false)
+    }
+
+    /// `callee(arguments)`
+    pub fn call_expression_with_arguments<I>(
+        &self,
+        callee: Expression<'a>,
+        arguments: I,
+    ) -> Expression<'a>
+    where
+        I: IntoIterator<Item = Expression<'a>>,
+    {
+        let mut args = self.new_vec();
+        args.extend(arguments.into_iter().map(Argument::Expression));
+        self.call_expression(SPAN, callee, args, false, None, false)
+    }
 }
@@ -16,6 +16,7 @@ pub enum AstKind<'a> {
     DebuggerStatement(&'a DebuggerStatement),
     DoWhileStatement(&'a DoWhileStatement<'a>),
     EmptyStatement(&'a EmptyStatement),
+    ErrorStatement(&'a ErrorStatement),
     ExpressionStatement(&'a ExpressionStatement<'a>),
     ForInStatement(&'a ForInStatement<'a>),
     ForOfStatement(&'a ForOfStatement<'a>),
@@ -72,6 +73,7 @@ pub enum AstKind<'a> {
     UnaryExpression(&'a UnaryExpression<'a>),
     UpdateExpression(&'a UpdateExpression<'a>),
     YieldExpression(&'a YieldExpression<'a>),
+    ErrorExpression(&'a ErrorExpression),
 
     ObjectProperty(&'a ObjectProperty<'a>),
     PropertyKey(&'a PropertyKey<'a>),
@@ -156,8 +158,8 @@ impl<'a> AstKind<'a> {
     pub fn is_statement(self) -> bool {
         self.is_iteration_statement()
             || matches!(self, Self::BlockStatement(_) | Self::BreakStatement(_) | Self::ContinueStatement(_)
-                    | Self::DebuggerStatement(_) | Self::EmptyStatement(_) | Self::ExpressionStatement(_)
-                    | Self::LabeledStatement(_) | Self::ReturnStatement(_) | Self::SwitchStatement(_)
+                    | Self::DebuggerStatement(_) | Self::EmptyStatement(_) | Self::ErrorStatement(_)
+                    | Self::ExpressionStatement(_) | Self::LabeledStatement(_) | Self::ReturnStatement(_) | Self::SwitchStatement(_)
                     | Self::ThrowStatement(_) | Self::TryStatement(_) | Self::WithStatement(_)
                     | Self::IfStatement(_) | Self::VariableDeclaration(_))
     }
@@ -232,6 +234,131 @@ impl<'a> AstKind<'a> {
             _ => false,
         }
     }
+
+    /// The address of the node, which tells apart nodes of the same kind and span, e.g. for
+    /// looking up nodes which were not moved since they were visited. `None` for `Elision`,
+    /// which holds no node.
+    #[allow(clippy::match_same_arms, clippy::too_many_lines)]
+    pub fn address(self) -> Option<usize> {
+        let address = match self {
+            Self::Program(x) => x as *const _ as usize,
+            Self::Directive(x) => x as *const _ as usize,
+            Self::Hashbang(x) => x as *const _ as usize,
+            Self::BlockStatement(x) => x as *const _ as usize,
+            Self::BreakStatement(x) => x as *const _ as usize,
+            Self::ContinueStatement(x) => x as *const _ as usize,
+            Self::DebuggerStatement(x) => x as *const _ as usize,
+            Self::DoWhileStatement(x) => x as *const _ as usize,
+            Self::EmptyStatement(x) => x as *const _ as usize,
+            Self::ErrorStatement(x) => x as *const _ as usize,
+            Self::ExpressionStatement(x) => x as *const _ as usize,
+            Self::ForInStatement(x) => x as *const _ as usize,
+            Self::ForOfStatement(x) => x as *const _ as usize,
+            Self::ForStatement(x) => x as *const _ as usize,
+            Self::ForStatementInit(x) => x as *const _ as usize,
+            Self::IfStatement(x) => x as *const _ as usize,
+            Self::LabeledStatement(x) => x as *const _ as usize,
+            Self::ReturnStatement(x) => x as *const _ as usize,
+            Self::SwitchStatement(x) => x as *const _ as usize,
+            Self::ThrowStatement(x) => x as *const _ as usize,
+            Self::TryStatement(x) => x as *const _ as usize,
+            Self::WhileStatement(x) => x as *const _ as usize,
+            Self::WithStatement(x) => x as *const _ as usize,
+            Self::SwitchCase(x) => x as *const _ as usize,
+            Self::CatchClause(x) => x as *const _ as usize,
+            Self::FinallyClause(x) => x as *const _ as usize,
+            Self::VariableDeclaration(x) => x as *const _ as usize,
+            Self::VariableDeclarator(x) => x as *const _ as usize,
+            Self::IdentifierName(x) => x as *const _ as usize,
+            Self::IdentifierReference(x) => x as *const _ as usize,
+            Self::BindingIdentifier(x) => x as *const _ as usize,
+            Self::LabelIdentifier(x) => x as *const _ as usize,
+            Self::PrivateIdentifier(x) => x as *const _ as usize,
+            Self::NumberLiteral(x) => x as *const _ as usize,
+            Self::StringLiteral(x) => x as *const _ as usize,
+            Self::BooleanLiteral(x) => x as *const _ as usize,
+            Self::NullLiteral(x) => x as *const _ as usize,
+            Self::BigintLiteral(x) => x as *const _ as usize,
+            Self::RegExpLiteral(x) => x as *const _ as usize,
+            Self::TemplateLiteral(x) => x as *const _ as usize,
+            Self::MetaProperty(x) => x as *const _ as usize,
+            Self::Super(x) => x as *const _ as usize,
+            Self::ArrayExpression(x) => x as *const _ as usize,
+            Self::ArrowExpression(x) => x as *const _ as usize,
+            Self::AssignmentExpression(x) => x as *const _ as usize,
+            Self::AwaitExpression(x) => x as *const _ as usize,
+            Self::BinaryExpression(x) => x as *const _ as usize,
+            Self::CallExpression(x) => x as *const _ as usize,
+            Self::ConditionalExpression(x) => x as *const _ as usize,
+            Self::LogicalExpression(x) => x as *const _ as usize,
+            Self::MemberExpression(x) => x as *const _ as usize,
+            Self::NewExpression(x) => x as *const _ as usize,
+            Self::ObjectExpression(x) => x as *const _ as usize,
+            Self::ParenthesizedExpression(x) => x as *const _ as usize,
+            Self::SequenceExpression(x) => x as *const _ as usize,
+            Self::TaggedTemplateExpression(x) => x as *const _ as usize,
+            Self::ThisExpression(x) => x as *const _ as usize,
+            Self::UnaryExpression(x) => x as *const _ as usize,
+            Self::UpdateExpression(x) => x as *const _ as usize,
+            Self::YieldExpression(x) => x as *const _ as usize,
+            Self::ErrorExpression(x) => x as *const _ as usize,
+            Self::ObjectProperty(x) => x as *const _ as usize,
+            Self::PropertyKey(x) => x as *const _ as usize,
+            Self::Argument(x) => x as *const _ as usize,
+            Self::AssignmentTarget(x) => x as *const _ as usize,
+            Self::SimpleAssignmentTarget(x) => x as *const _ as usize,
+            Self::AssignmentTargetWithDefault(x) => x as *const _ as usize,
+            Self::ArrayExpressionElement(x) => x as *const _ as usize,
+            Self::SpreadElement(x) => x as *const _ as usize,
+            Self::RestElement(x) => x as *const _ as usize,
+            Self::Function(x) => x as *const _ as usize,
+            Self::FunctionBody(x) => x as *const _ as usize,
+            Self::FormalParameters(x) => x as *const _ as usize,
+            Self::FormalParameter(x) => x as *const _ as usize,
+            Self::Class(x) => x as *const _ as usize,
+            Self::ClassHeritage(x) => x as *const _ as usize,
+            Self::StaticBlock(x) => x as *const _ as usize,
+            Self::PropertyDefinition(x) => x as *const _ as usize,
+            Self::MethodDefinition(x) => x as *const _ as usize,
+            Self::ArrayPattern(x) => x as *const _ as usize,
+            Self::ObjectPattern(x) => x as *const _ as usize,
+            Self::AssignmentPattern(x) => x as *const _ as usize,
+            Self::Decorator(x) => x as *const _ as usize,
+            Self::ModuleDeclaration(x) => x as *const _ as usize,
+            Self::JSXElement(x) => x as *const _ as usize,
+            Self::JSXOpeningElement(x) => x as *const _ as usize,
+            Self::JSXElementName(x) => x as *const _ as usize,
+            Self::TSModuleBlock(x) => x as *const _ as usize,
+            Self::TSAnyKeyword(x) => x as *const _ as usize,
+            Self::TSIntersectionType(x) => x as *const _ as usize,
+            Self::TSLiteralType(x) => x as *const _ as usize,
+            Self::TSMethodSignature(x) => x as *const _ as usize,
+            Self::TSNullKeyword(x) => x as *const _ as usize,
+            Self::TSTypeLiteral(x) => x as *const _ as usize,
+            Self::TSTypeReference(x) => x as *const _ as usize,
+            Self::TSUnionType(x) => x as *const _ as usize,
+            Self::TSVoidKeyword(x) => x as *const _ as usize,
+            Self::TSIndexedAccessType(x) => x as *const _ as usize,
+            Self::TSAsExpression(x) => x as *const _ as usize,
+            Self::TSSatisfiesExpression(x) => x as *const _ as usize,
+            Self::TSNonNullExpression(x) => x as *const _ as usize,
+            Self::TSEnumDeclaration(x) => x as *const _ as usize,
+            Self::TSEnumMember(x) => x as *const _ as usize,
+            Self::TSEnumBody(x) => x as *const _ as usize,
+            Self::TSImportEqualsDeclaration(x) => x as *const _ as usize,
+            Self::TSInterfaceDeclaration(x) => x as *const _ as usize,
+            Self::TSModuleDeclaration(x) => x as *const _ as usize,
+            Self::TSTypeAliasDeclaration(x) => x as *const _ as usize,
+            Self::TSTypeAnnotation(x) => x as *const _ as usize,
+            Self::TSTypeAssertion(x) => x as *const _ as usize,
+            Self::TSTypeParameter(x) => x as *const _ as usize,
+            Self::TSTypeParameterDeclaration(x) => x as *const _ as usize,
+            Self::TSTypeParameterInstantiation(x) => x as *const _ as usize,
+            Self::TSPropertySignature(x) => x as *const _ as usize,
+            Self::Elision(_) => return None,
+        };
+        Some(address)
+    }
 }
 
 impl<'a> GetSpan for AstKind<'a> {
@@ -248,6 +375,7 @@ impl<'a> GetSpan for AstKind<'a> {
             Self::DebuggerStatement(x) => x.span,
             Self::DoWhileStatement(x) => x.span,
             Self::EmptyStatement(x) => x.span,
+            Self::ErrorStatement(x) => x.span,
             Self::ExpressionStatement(x) => x.span,
             Self::ForInStatement(x) => x.span,
             Self::ForOfStatement(x) => x.span,
@@ -304,6 +432,7 @@ impl<'a> GetSpan for AstKind<'a> {
             Self::UnaryExpression(x) => x.span,
             Self::UpdateExpression(x) => x.span,
             Self::YieldExpression(x) => x.span,
+            Self::ErrorExpression(x) => x.span,
 
             Self::ObjectProperty(x) => x.span,
             Self::PropertyKey(x) => x.span(),
@@ -396,6 +525,7 @@ impl<'a> AstKind<'a> {
             Self::DebuggerStatement(_) => "DebuggerStatement".into(),
             Self::DoWhileStatement(_) => "DoWhileStatement".into(),
             Self::EmptyStatement(_) => "EmptyStatement".into(),
+            Self::ErrorStatement(_) => "ErrorStatement".into(),
             Self::ExpressionStatement(_) => "ExpressionStatement".into(),
             Self::ForInStatement(_) => "ForInStatement".into(),
             Self::ForOfStatement(_) => "ForOfStatement".into(),
@@ -456,6 +586,7 @@ impl<'a> AstKind<'a> {
             Self::UnaryExpression(expr) => format!("UnaryExpression({:?})", expr.operator).into(),
             Self::UpdateExpression(_) => "UpdateExpression".into(),
             Self::YieldExpression(_) => "YieldExpression".into(),
+            Self::ErrorExpression(_) => "ErrorExpression".into(),
 
             Self::ObjectProperty(_) => "ObjectProperty".into(),
             Self::PropertyKey(_) => "PropertyKey".into(),
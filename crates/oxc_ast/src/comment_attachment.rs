@@ -0,0 +1,182 @@
+//! Attach comments to AST nodes
+//!
+//! Used by tools which need to know which node a comment belongs to,
+//! e.g. a formatter or code generator preserving comments when the nodes around them move.
+
+use std::collections::BTreeMap;
+
+use oxc_span::{GetSpan, Span};
+
+use crate::{
+    ast::Program,
+    trivia::{CommentKind, Trivias},
+    AstKind, Visit,
+};
+
+/// Position of a comment relative to the node it is attached to
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CommentPosition {
+    /// Before the node, e.g. `/* c */ a`
+    Leading,
+    /// After the node, e.g. `a // c`
+    Trailing,
+    /// Inside the node with no child node to attach to, e.g. `function foo(/* c */) {}`
+    Dangling,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct AttachedComment {
+    /// Span of the comment text, without `//`, `/*` and `*/`, same as the keys of [Trivias]
+    pub span: Span,
+    pub kind: CommentKind,
+    pub position: CommentPosition,
+}
+
+/// Comments attached to AST nodes, keyed by the span of the node.
+///
+/// Each comment is attached to exactly one node, found by descending into the innermost node
+/// enclosing the comment and taking its children right before and after the comment:
+/// * a comment on its own line leads the following node
+/// * a comment at the end of a line trails the preceding node
+/// * a comment between nodes on the same line leads the following node if only whitespace
+///   separates them, otherwise it trails the preceding node
+/// * a comment without a preceding or following node dangles inside the enclosing node
+///
+/// The outermost node starting or ending at the comment wins,
+/// e.g. `/* c */` in `/* c */ foo();` leads the expression statement rather than `foo`.
+/// Nodes with the same span, such as `foo` and its expression statement in `foo`,
+/// share their comments.
+#[derive(Debug, Default)]
+pub struct CommentAttachments {
+    comments: BTreeMap<Span, Vec<AttachedComment>>,
+}
+
+impl CommentAttachments {
+    pub fn new<'a>(source_text: &str, trivias: &Trivias, program: &'a Program<'a>) -> Self {
+        let mut collector = NodeCollector::default();
+        collector.visit_program(program);
+        let nodes = collector.nodes;
+
+        let mut comments: BTreeMap<Span, Vec<AttachedComment>> = BTreeMap::new();
+        for (start, comment) in trivias.comments() {
+            let span = Span::new(*start, comment.end());
+            // Include `//`, `/*` and `*/`
+            let range = Span::new(
+                span.start.saturating_sub(2),
+                if comment.is_multi_line() { span.end + 2 } else { span.end },
+            );
+            let (enclosing, preceding, following) = Self::neighbours(&nodes, range);
+
+            let before = Span::new(0, range.start).source_text(source_text);
+            let own_line = before.rsplit('\n').next().map_or(true, |line| line.trim().is_empty());
+            let touches_following = following.map_or(false, |following| {
+                let between = Span::new(range.end, nodes[following].span.start);
+                let between = between.source_text(source_text);
+                !between.contains('\n') && between.trim().is_empty()
+            });
+
+            let (node, position) = match (preceding, following) {
+                (_, Some(following)) if own_line || touches_following => {
+                    (following, CommentPosition::Leading)
+                }
+                (Some(preceding), _) => (preceding, CommentPosition::Trailing),
+                (None, Some(following)) => (following, CommentPosition::Leading),
+                _ => (enclosing, CommentPosition::Dangling),
+            };
+            comments.entry(nodes[node].span).or_default().push(AttachedComment {
+                span,
+                kind: comment.kind(),
+                position,
+            });
+        }
+        Self { comments }
+    }
+
+    /// The innermost node enclosing `range`, and its children right before and after `range`.
+    fn neighbours(nodes: &[Node], range: Span) -> (usize, Option<usize>, Option<usize>) {
+        let mut enclosing = 0;
+        'descend: loop {
+            let mut preceding: Option<usize> = None;
+            let mut following: Option<usize> = None;
+            for &child in &nodes[enclosing].children {
+                let span = nodes[child].span;
+                // Nodes without source text cannot have comments attached
+                if span.start >= span.end {
+                    continue;
+                }
+                if span.start <= range.start && range.end <= span.end {
+                    enclosing = child;
+                    continue 'descend;
+                }
+                if span.end <= range.start
+                    && preceding.map_or(true, |preceding| nodes[preceding].span.end < span.end)
+                {
+                    preceding = Some(child);
+                }
+                if range.end <= span.start
+                    && following.map_or(true, |following| span.start < nodes[following].span.start)
+                {
+                    following = Some(child);
+                }
+            }
+            return (enclosing, preceding, following);
+        }
+    }
+
+    /// All comments attached to the node with `span`, in source order.
+    pub fn get(&self, span: Span) -> &[AttachedComment] {
+        self.comments.get(&span).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn leading(&self, span: Span) -> impl Iterator<Item = &AttachedComment> + '_ {
+        self.with_position(span, CommentPosition::Leading)
+    }
+
+    pub fn trailing(&self, span: Span) -> impl Iterator<Item = &AttachedComment> + '_ {
+        self.with_position(span, CommentPosition::Trailing)
+    }
+
+    pub fn dangling(&self, span: Span) -> impl Iterator<Item = &AttachedComment> + '_ {
+        self.with_position(span, CommentPosition::Dangling)
+    }
+
+    fn with_position(
+        &self,
+        span: Span,
+        position: CommentPosition,
+    ) -> impl Iterator<Item = &AttachedComment> + '_ {
+        self.get(span).iter().filter(move |comment| comment.position == position)
+    }
+
+    /// Spans of the nodes with comments attached and their comments, in source order.
+    pub fn iter(&self) -> impl Iterator<Item = (Span, &[AttachedComment])> + '_ {
+        self.comments.iter().map(|(span, comments)| (*span, comments.as_slice()))
+    }
+}
+
+struct Node {
+    span: Span,
+    children: Vec<usize>,
+}
+
+/// Collects the span and children of every node, the program being the first node.
+#[derive(Default)]
+struct NodeCollector {
+    nodes: Vec<Node>,
+    stack: Vec<usize>,
+}
+
+impl<'a> Visit<'a> for NodeCollector {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        let id = self.nodes.len();
+        self.nodes.push(Node { span: kind.span(), children: vec![] });
+        if let Some(parent) = self.stack.last() {
+            self.nodes[*parent].children.push(id);
+        }
+        self.stack.push(id);
+    }
+
+    fn leave_node(&mut self, _kind: AstKind<'a>) {
+        self.stack.pop();
+    }
+}
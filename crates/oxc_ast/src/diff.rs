@@ -0,0 +1,171 @@
+//! Structural diff of ASTs, ignoring spans and formatting
+//!
+//! Used for asserting that two programs are the same, e.g. the output of a transform
+//! against the output of Babel, or the code before and after applying lint fixes.
+
+use std::fmt::{self, Write};
+
+use serde_json::{Map, Value};
+
+use crate::ast::Program;
+
+/// Fields which depend on the position of a node in the source text
+const SPAN_FIELDS: [&str; 2] = ["start", "end"];
+
+/// A difference between two ASTs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstDifference {
+    /// Path of the differing node or value from the root, e.g. `body[0].expression.callee.name`
+    pub path: String,
+    /// `None` if the value is only in the right AST
+    pub left: Option<Value>,
+    /// `None` if the value is only in the left AST
+    pub right: Option<Value>,
+}
+
+impl fmt::Display for AstDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let print =
+            |value: &Option<Value>| value.as_ref().map_or_else(|| "missing".to_string(), summary);
+        write!(f, "{}: {} != {}", self.path, print(&self.left), print(&self.right))
+    }
+}
+
+/// The node type of nodes, and the value of other values
+fn summary(value: &Value) -> String {
+    value.get("type").and_then(Value::as_str).map_or_else(|| value.to_string(), str::to_string)
+}
+
+impl<'a> Program<'a> {
+    /// The differences between this program and `other`, ignoring spans and formatting such as
+    /// whitespace, comments, quotes and number representation.
+    /// Empty if the programs are structurally the same.
+    ///
+    /// # Panics
+    ///
+    /// * Serde JSON serialization
+    pub fn diff(&self, other: &Program<'_>) -> Vec<AstDifference> {
+        let left = serde_json::to_value(self).unwrap();
+        let right = serde_json::to_value(other).unwrap();
+        diff_values(&left, &right)
+    }
+}
+
+/// The differences between two serialized ASTs, at the shallowest paths they differ
+pub fn diff_values(left: &Value, right: &Value) -> Vec<AstDifference> {
+    let mut differences = vec![];
+    diff_value(&mut String::new(), left, right, &mut differences);
+    differences
+}
+
+fn diff_value(
+    path: &mut String,
+    left: &Value,
+    right: &Value,
+    differences: &mut Vec<AstDifference>,
+) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) if left.get("type") == right.get("type") => {
+            diff_object(path, left, right, differences);
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            let len = path.len();
+            for i in 0..left.len().max(right.len()) {
+                write!(path, "[{i}]").unwrap();
+                match (left.get(i), right.get(i)) {
+                    (Some(left), Some(right)) => diff_value(path, left, right, differences),
+                    (left, right) => differences.push(AstDifference {
+                        path: path.clone(),
+                        left: left.cloned(),
+                        right: right.cloned(),
+                    }),
+                }
+                path.truncate(len);
+            }
+        }
+        (Value::Number(left), Value::Number(right)) if left.as_f64() == right.as_f64() => {}
+        _ if left == right => {}
+        _ => differences.push(AstDifference {
+            path: path.clone(),
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+    }
+}
+
+fn diff_object(
+    path: &mut String,
+    left: &Map<String, Value>,
+    right: &Map<String, Value>,
+    differences: &mut Vec<AstDifference>,
+) {
+    let len = path.len();
+    let keys = left.keys().chain(right.keys().filter(|key| !left.contains_key(*key)));
+    for key in keys.filter(|key| !SPAN_FIELDS.contains(&key.as_str())) {
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(key);
+        match (left.get(key), right.get(key)) {
+            (Some(left), Some(right)) => diff_value(path, left, right, differences),
+            // A missing field is the same as `null`, e.g. skipped optional fields
+            (Some(Value::Null), None) | (None, Some(Value::Null)) => {}
+            (left, right) => differences.push(AstDifference {
+                path: path.clone(),
+                left: left.cloned(),
+                right: right.cloned(),
+            }),
+        }
+        path.truncate(len);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::diff_values;
+
+    fn call(callee: &str, start: u32, arguments: &serde_json::Value) -> serde_json::Value {
+        json!({
+            "type": "Program",
+            "start": 0,
+            "end": start + 10,
+            "body": [{
+                "type": "ExpressionStatement",
+                "start": start,
+                "expression": {
+                    "type": "CallExpression",
+                    "callee": { "type": "IdentifierReference", "name": callee, "start": start },
+                    "arguments": arguments
+                }
+            }]
+        })
+    }
+
+    #[test]
+    fn diff() {
+        let a = call("foo", 0, &json!([{ "type": "NumberLiteral", "value": 1.0 }]));
+        let moved = call("foo", 5, &json!([{ "type": "NumberLiteral", "value": 1 }]));
+        assert!(diff_values(&a, &moved).is_empty());
+
+        let differences = diff_values(&a, &call("bar", 0, &json!([])));
+        let mut differences = differences.iter().map(ToString::to_string).collect::<Vec<_>>();
+        differences.sort();
+        assert_eq!(
+            differences,
+            [
+                "body[0].expression.arguments[0]: NumberLiteral != missing",
+                "body[0].expression.callee.name: \"foo\" != \"bar\"",
+            ]
+        );
+
+        let b = call("foo", 0, &json!([{ "type": "StringLiteral", "value": "1" }]));
+        let differences = diff_values(&a, &b);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(
+            differences[0].to_string(),
+            "body[0].expression.arguments[0]: NumberLiteral != StringLiteral"
+        );
+    }
+}
@@ -5,16 +5,24 @@
 //! * `AssignmentExpression`.`left` `Pattern` is replaced with `AssignmentTarget`
 //!
 //! ## Cargo Features
-//! * `"serde"` enables support for serde serialization
+//! * `"serde"` enables support for serde serialization, esquery selectors in [`query`]
+//!   and structural diffs in [`diff`]
 
+#[cfg(feature = "serde")]
+pub mod diff;
+#[cfg(feature = "serde")]
+pub mod query;
 #[cfg(feature = "serde")]
 mod serialize;
 
 pub mod ast;
 mod ast_builder;
 mod ast_kind;
+mod comment_attachment;
 mod span;
+pub mod stats;
 pub mod syntax_directed_operations;
+mod token_span;
 mod trivia;
 mod visit;
 mod visit_mut;
@@ -22,7 +30,13 @@ mod visit_mut;
 pub use num_bigint::BigUint;
 
 pub use crate::{
-    ast_builder::AstBuilder, ast_kind::AstKind, trivia::Trivias, visit::Visit, visit_mut::VisitMut,
+    ast_builder::AstBuilder,
+    ast_kind::AstKind,
+    comment_attachment::{AttachedComment, CommentAttachments, CommentPosition},
+    token_span::{TokenSpan, TokenSpanKind, TokenSpans},
+    trivia::{Comment, CommentKind, Trivias},
+    visit::Visit,
+    visit_mut::VisitMut,
 };
 
 // After experimenting with two types of boxed enum variants:
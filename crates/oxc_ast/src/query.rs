@@ -0,0 +1,404 @@
+//! [esquery](https://github.com/estools/esquery) compatible node querying over the serialized AST
+//!
+//! Supported selectors:
+//! * node type `CallExpression` and wildcard `*`
+//! * attribute existence `[callee]`, and comparison `[callee.name="require"]`, `[arguments.length!=0]`
+//!   against strings, numbers, `true`, `false` and `null`
+//! * descendant `A B` and child `A > B` combinators
+//! * negation `:not(A, B)`
+//! * selector lists `A, B`
+//!
+//! Node types are the names of the oxc AST nodes, e.g. `IdentifierReference` instead of `Identifier`.
+
+use std::{borrow::Cow, fmt};
+
+use serde_json::Value;
+
+use crate::ast::Program;
+
+impl<'a> Program<'a> {
+    /// Serialize the AST and return every node matching the esquery `selector`, in source order.
+    ///
+    /// ```ignore
+    /// let requires = program.query("CallExpression[callee.name='require']")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * The selector is invalid
+    ///
+    /// # Panics
+    ///
+    /// * Serde JSON serialization
+    pub fn query(&self, selector: &str) -> Result<Vec<Value>, QueryError> {
+        let selector = Selector::parse(selector)?;
+        let program = serde_json::to_value(self).unwrap();
+        Ok(selector.query(&program).into_iter().cloned().collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub message: String,
+    /// Byte offset into the selector
+    pub offset: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid selector at {}: {}", self.offset, self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// A parsed esquery selector list
+#[derive(Debug, Clone)]
+pub struct Selector {
+    alternatives: Vec<Complex>,
+}
+
+/// Compound selectors joined with combinators, e.g. `A > B C`
+#[derive(Debug, Clone)]
+struct Complex {
+    /// The leftmost compound selector
+    head: Compound,
+    tail: Vec<(Combinator, Compound)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Compound {
+    /// `None` for `*`
+    node_type: Option<String>,
+    attributes: Vec<Attribute>,
+    not: Vec<Selector>,
+}
+
+#[derive(Debug, Clone)]
+struct Attribute {
+    path: Vec<String>,
+    comparison: Option<(Operator, Value)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+}
+
+impl Selector {
+    /// # Errors
+    ///
+    /// * The selector is invalid
+    pub fn parse(source: &str) -> Result<Self, QueryError> {
+        let mut parser = SelectorParser { source, pos: 0 };
+        let selector = parser.parse_selector()?;
+        parser.skip_whitespace();
+        if parser.pos < source.len() {
+            return Err(parser.error("unexpected character"));
+        }
+        Ok(selector)
+    }
+
+    /// Every node in the JSON AST `root` matching the selector, in source order
+    pub fn query<'v>(&self, root: &'v Value) -> Vec<&'v Value> {
+        let mut matches = vec![];
+        let mut ancestors = vec![];
+        self.walk(root, &mut ancestors, &mut matches);
+        // Object fields are not visited in source order
+        matches.sort_by_key(|node| node.get("start").and_then(Value::as_u64));
+        matches
+    }
+
+    /// Whether `node` matches the selector, given its ancestors from the root down to its parent
+    pub fn matches(&self, node: &Value, ancestors: &[&Value]) -> bool {
+        self.alternatives.iter().any(|complex| complex.matches(node, ancestors))
+    }
+
+    fn walk<'v>(
+        &self,
+        value: &'v Value,
+        ancestors: &mut Vec<&'v Value>,
+        matches: &mut Vec<&'v Value>,
+    ) {
+        let is_node = node_type(value).is_some();
+        if is_node {
+            if self.matches(value, ancestors) {
+                matches.push(value);
+            }
+            ancestors.push(value);
+        }
+        match value {
+            Value::Object(object) => {
+                for child in object.values() {
+                    self.walk(child, ancestors, matches);
+                }
+            }
+            Value::Array(array) => {
+                for child in array {
+                    self.walk(child, ancestors, matches);
+                }
+            }
+            _ => {}
+        }
+        if is_node {
+            ancestors.pop();
+        }
+    }
+}
+
+impl Complex {
+    fn matches(&self, node: &Value, ancestors: &[&Value]) -> bool {
+        // Match from right to left, backtracking over the ancestors for descendant combinators
+        fn matches_from(
+            complex: &Complex,
+            index: usize,
+            node: &Value,
+            ancestors: &[&Value],
+        ) -> bool {
+            let (compound, combinator) = if index == 0 {
+                (&complex.head, None)
+            } else {
+                let (combinator, compound) = &complex.tail[index - 1];
+                (compound, Some(*combinator))
+            };
+            if !compound.matches(node, ancestors) {
+                return false;
+            }
+            match combinator {
+                None => true,
+                Some(Combinator::Child) => ancestors
+                    .split_last()
+                    .map_or(false, |(parent, rest)| matches_from(complex, index - 1, parent, rest)),
+                Some(Combinator::Descendant) => (0..ancestors.len())
+                    .rev()
+                    .any(|i| matches_from(complex, index - 1, ancestors[i], &ancestors[..i])),
+            }
+        }
+        matches_from(self, self.tail.len(), node, ancestors)
+    }
+}
+
+impl Compound {
+    fn matches(&self, node: &Value, ancestors: &[&Value]) -> bool {
+        if let Some(expected) = &self.node_type {
+            if node_type(node) != Some(expected.as_str()) {
+                return false;
+            }
+        }
+        self.attributes.iter().all(|attribute| attribute.matches(node))
+            && !self.not.iter().any(|selector| selector.matches(node, ancestors))
+    }
+}
+
+impl Attribute {
+    fn matches(&self, node: &Value) -> bool {
+        let value = resolve(node, &self.path);
+        match &self.comparison {
+            None => value.map_or(false, |value| !value.is_null()),
+            Some((operator, expected)) => {
+                let equal = value
+                    .map_or_else(|| expected.is_null(), |value| values_equal(&value, expected));
+                (*operator == Operator::Equal) == equal
+            }
+        }
+    }
+}
+
+/// Follow the attribute `path` from `node`, where arrays have a computed `length`
+fn resolve<'v>(node: &'v Value, path: &[String]) -> Option<Cow<'v, Value>> {
+    let (last, init) = path.split_last()?;
+    let parent = init.iter().try_fold(node, |value, key| match value {
+        Value::Array(array) => key.parse::<usize>().ok().and_then(|i| array.get(i)),
+        _ => value.get(key),
+    })?;
+    match parent {
+        Value::Array(array) if last == "length" => Some(Cow::Owned(Value::from(array.len()))),
+        Value::Array(array) => {
+            last.parse::<usize>().ok().and_then(|i| array.get(i)).map(Cow::Borrowed)
+        }
+        _ => parent.get(last).map(Cow::Borrowed),
+    }
+}
+
+fn node_type(value: &Value) -> Option<&str> {
+    value.get("type").and_then(Value::as_str)
+}
+
+fn values_equal(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => value == expected,
+    }
+}
+
+struct SelectorParser<'s> {
+    source: &'s str,
+    pos: usize,
+}
+
+impl<'s> SelectorParser<'s> {
+    fn error(&self, message: &str) -> QueryError {
+        QueryError { message: message.to_string(), offset: self.pos }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), QueryError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{c}`")))
+        }
+    }
+
+    /// Skip whitespace, returning whether there was any
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+        self.pos > start
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, QueryError> {
+        let mut alternatives = vec![self.parse_complex()?];
+        while self.eat(',') {
+            alternatives.push(self.parse_complex()?);
+        }
+        Ok(Selector { alternatives })
+    }
+
+    fn parse_complex(&mut self) -> Result<Complex, QueryError> {
+        self.skip_whitespace();
+        let head = self.parse_compound()?;
+        let mut tail = vec![];
+        loop {
+            let has_whitespace = self.skip_whitespace();
+            let combinator = if self.eat('>') {
+                self.skip_whitespace();
+                Combinator::Child
+            } else if has_whitespace && !matches!(self.peek(), None | Some(',' | ')')) {
+                Combinator::Descendant
+            } else {
+                break;
+            };
+            tail.push((combinator, self.parse_compound()?));
+        }
+        Ok(Complex { head, tail })
+    }
+
+    fn parse_compound(&mut self) -> Result<Compound, QueryError> {
+        let start = self.pos;
+        let mut compound = Compound::default();
+        if !self.eat('*') {
+            let name = self.parse_identifier();
+            if !name.is_empty() {
+                compound.node_type = Some(name.to_string());
+            }
+        }
+        loop {
+            if self.eat('[') {
+                compound.attributes.push(self.parse_attribute()?);
+            } else if self.source[self.pos..].starts_with(":not(") {
+                self.pos += ":not(".len();
+                compound.not.push(self.parse_selector()?);
+                self.skip_whitespace();
+                self.expect(')')?;
+            } else {
+                break;
+            }
+        }
+        if self.pos == start {
+            return Err(self.error("expected a selector"));
+        }
+        Ok(compound)
+    }
+
+    fn parse_identifier(&mut self) -> &'s str {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+            self.pos += 1;
+        }
+        &self.source[start..self.pos]
+    }
+
+    fn parse_attribute(&mut self) -> Result<Attribute, QueryError> {
+        self.skip_whitespace();
+        let mut path = vec![];
+        loop {
+            let name = self.parse_identifier();
+            if name.is_empty() {
+                return Err(self.error("expected an attribute name"));
+            }
+            path.push(name.to_string());
+            if !self.eat('.') {
+                break;
+            }
+        }
+        self.skip_whitespace();
+        let operator = if self.eat('=') {
+            Some(Operator::Equal)
+        } else if self.eat('!') {
+            self.expect('=')?;
+            Some(Operator::NotEqual)
+        } else {
+            None
+        };
+        let comparison = match operator {
+            Some(operator) => {
+                self.skip_whitespace();
+                Some((operator, self.parse_value()?))
+            }
+            None => None,
+        };
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Attribute { path, comparison })
+    }
+
+    fn parse_value(&mut self) -> Result<Value, QueryError> {
+        if let Some(quote @ ('"' | '\'')) = self.peek() {
+            self.pos += 1;
+            let start = self.pos;
+            let Some(len) = self.source[start..].find(quote) else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += len + 1;
+            Ok(Value::from(&self.source[start..start + len]))
+        } else {
+            let start = self.pos;
+            while self.peek().map_or(false, |c| !c.is_whitespace() && c != ']') {
+                self.pos += self.peek().map_or(1, char::len_utf8);
+            }
+            let raw = &self.source[start..self.pos];
+            if raw.is_empty() {
+                return Err(self.error("expected a value"));
+            }
+            Ok(match raw {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "null" => Value::Null,
+                // Unquoted values which are not numbers are strings, as in esquery
+                _ => raw.parse::<f64>().ok().map_or_else(|| Value::from(raw), Value::from),
+            })
+        }
+    }
+}
@@ -1,8 +1,16 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
-use serde::{ser::Serializer, Serialize};
+use num_bigint::BigInt;
+use oxc_allocator::Allocator;
+use oxc_span::Atom;
+use serde::{
+    de::{self, Deserializer},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
 
-use crate::ast::{Program, RegExpFlags};
+#[allow(clippy::wildcard_imports)]
+use crate::{ast::*, VisitMut};
 
 pub struct EcmaFormatter;
 
@@ -19,6 +27,8 @@ impl serde_json::ser::Formatter for EcmaFormatter {
 }
 
 impl<'a> Program<'a> {
+    /// Serialize the AST to ESTree compatible JSON.
+    ///
     /// # Panics
     pub fn to_json(&self) -> String {
         let buf = std::vec::Vec::new();
@@ -26,6 +36,44 @@ impl<'a> Program<'a> {
         self.serialize(&mut ser).unwrap();
         String::from_utf8(ser.into_inner()).unwrap()
     }
+
+    /// Deserialize the JSON of [`Program::to_json`], allocating the AST in `allocator`.
+    ///
+    /// The fields which are not serialized are rebuilt from the others, e.g. the raw text of
+    /// number literals is printed from their value.
+    ///
+    /// # Errors
+    ///
+    /// If `json` is not the JSON of a program
+    pub fn from_json(allocator: &'a Allocator, json: &str) -> serde_json::Result<Self> {
+        // SAFETY: the program borrows `allocator` for `'a`
+        let mut program: Self = unsafe { allocator.deserialize_in(|| serde_json::from_str(json)) }?;
+        SkippedFields { allocator }.visit_program(&mut program);
+        Ok(program)
+    }
+}
+
+/// Rebuilds the fields which are skipped in serialization
+struct SkippedFields<'a> {
+    allocator: &'a Allocator,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for SkippedFields<'a> {
+    fn visit_variable_declaration(&mut self, decl: &'b mut VariableDeclaration<'a>) {
+        for declarator in decl.declarations.iter_mut() {
+            declarator.kind = decl.kind;
+            self.visit_variable_declarator(declarator);
+        }
+    }
+
+    fn visit_number_literal(&mut self, lit: &'b mut NumberLiteral<'a>) {
+        let mut buffer = ryu_js::Buffer::new();
+        lit.raw = self.allocator.alloc_str(buffer.format(lit.value));
+    }
+
+    fn visit_bigint_literal(&mut self, lit: &'b mut BigintLiteral) {
+        lit.raw = Atom::from(format!("{}n", lit.value));
+    }
 }
 
 pub fn serialize_bigint<T, S>(value: &T, s: S) -> Result<S::Ok, S::Error>
@@ -36,6 +84,16 @@ where
     s.collect_str(&format_args!("{value}n"))
 }
 
+pub fn deserialize_bigint<'de, D>(d: D) -> Result<BigInt, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(d)?;
+    s.strip_suffix('n')
+        .and_then(|digits| BigInt::from_str(digits).ok())
+        .ok_or_else(|| de::Error::invalid_value(de::Unexpected::Str(&s), &"a bigint, e.g. `1n`"))
+}
+
 impl Serialize for RegExpFlags {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -44,3 +102,422 @@ impl Serialize for RegExpFlags {
         serializer.serialize_str(&self.to_string())
     }
 }
+
+impl<'de> Deserialize<'de> for RegExpFlags {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.chars().try_fold(Self::empty(), |flags, c| {
+            Self::try_from(c).map(|flag| flags | flag).map_err(|_| {
+                de::Error::invalid_value(de::Unexpected::Char(c), &"a regular expression flag")
+            })
+        })
+    }
+}
+
+/// The `type` of a serialized node, or `""` for the values which are not nodes
+fn node_type(value: &serde_json::Value) -> &str {
+    value.get("type").and_then(serde_json::Value::as_str).unwrap_or_default()
+}
+
+/// Deserializes an untagged enum by the `type` of its node, with `Variant => "Type"` for the
+/// variants of nodes and `Variant => Enum` for the variants of enums of nodes.
+///
+/// The derived `Deserialize` of a struct ignores its `type` tag, so trying each variant like the
+/// derived `Deserialize` of an untagged enum would pick the first node with the same fields.
+macro_rules! deserialize_untagged {
+    ($name:ident $(<$lt:lifetime>)? {
+        $($variant:ident => $($ty:literal)|+,)*
+    } $({
+        $($enum_variant:ident => $enum:ident,)*
+    })?) => {
+        impl<'de $(, $lt)?> Deserialize<'de> for $name $(<$lt>)? {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = serde_json::Value::deserialize(deserializer)?;
+                match node_type(&value) {
+                    $($($ty)|+ => Deserialize::deserialize(value).map(Self::$variant),)*
+                    $($(
+                        ty if $enum::has_type(ty) => {
+                            Deserialize::deserialize(value).map(Self::$enum_variant)
+                        }
+                    )*)?
+                    ty => {
+                        return Err(de::Error::custom(format_args!(
+                            "unexpected node `{ty}` for {}",
+                            stringify!($name)
+                        )))
+                    }
+                }
+                .map_err(de::Error::custom)
+            }
+        }
+
+        // Unused for the enums which are not variants of other enums
+        #[allow(dead_code)]
+        impl $(<$lt>)? $name $(<$lt>)? {
+            fn has_type(ty: &str) -> bool {
+                [$($($ty),+),*].contains(&ty) $($(|| $enum::has_type(ty))*)?
+            }
+        }
+    };
+}
+
+deserialize_untagged!(Expression<'a> {
+    BooleanLiteral => "BooleanLiteral",
+    NullLiteral => "NullLiteral",
+    NumberLiteral => "NumberLiteral",
+    BigintLiteral => "BigintLiteral",
+    RegExpLiteral => "RegExpLiteral",
+    StringLiteral => "StringLiteral",
+    TemplateLiteral => "TemplateLiteral",
+    Identifier => "IdentifierReference",
+    MetaProperty => "MetaProperty",
+    Super => "Super",
+    ArrayExpression => "ArrayExpression",
+    ArrowExpression => "ArrowExpression",
+    AssignmentExpression => "AssignmentExpression",
+    AwaitExpression => "AwaitExpression",
+    BinaryExpression => "BinaryExpression",
+    CallExpression => "CallExpression",
+    ChainExpression => "ChainExpression",
+    ClassExpression => "ClassExpression",
+    ConditionalExpression => "ConditionalExpression",
+    FunctionExpression => "FunctionExpression",
+    ImportExpression => "ImportExpression",
+    LogicalExpression => "LogicalExpression",
+    NewExpression => "NewExpression",
+    ObjectExpression => "ObjectExpression",
+    ParenthesizedExpression => "ParenthesizedExpression",
+    SequenceExpression => "SequenceExpression",
+    TaggedTemplateExpression => "TaggedTemplateExpression",
+    ThisExpression => "ThisExpression",
+    UnaryExpression => "UnaryExpression",
+    UpdateExpression => "UpdateExpression",
+    YieldExpression => "YieldExpression",
+    PrivateInExpression => "PrivateInExpression",
+    JSXElement => "JSXElement",
+    JSXFragment => "JSXFragment",
+    TSAsExpression => "TSAsExpression",
+    TSSatisfiesExpression => "TSSatisfiesExpression",
+    TSTypeAssertion => "TSTypeAssertion",
+    TSNonNullExpression => "TSNonNullExpression",
+    TSInstantiationExpression => "TSInstantiationExpression",
+} {
+    MemberExpression => MemberExpression,
+});
+
+deserialize_untagged!(ObjectPropertyKind<'a> {
+    ObjectProperty => "ObjectProperty",
+    SpreadProperty => "SpreadElement",
+});
+
+deserialize_untagged!(PropertyKey<'a> {
+    Identifier => "IdentifierName",
+    PrivateIdentifier => "PrivateIdentifier",
+} {
+    Expression => Expression,
+});
+
+deserialize_untagged!(MemberExpression<'a> {
+    ComputedMemberExpression => "ComputedMemberExpression",
+    StaticMemberExpression => "StaticMemberExpression",
+    PrivateFieldExpression => "PrivateFieldExpression",
+});
+
+deserialize_untagged!(Argument<'a> {
+    SpreadElement => "SpreadElement",
+} {
+    Expression => Expression,
+});
+
+deserialize_untagged!(AssignmentTarget<'a> {
+} {
+    SimpleAssignmentTarget => SimpleAssignmentTarget,
+    AssignmentTargetPattern => AssignmentTargetPattern,
+});
+
+deserialize_untagged!(SimpleAssignmentTarget<'a> {
+    AssignmentTargetIdentifier => "IdentifierReference",
+    TSAsExpression => "TSAsExpression",
+    TSSatisfiesExpression => "TSSatisfiesExpression",
+    TSNonNullExpression => "TSNonNullExpression",
+    TSTypeAssertion => "TSTypeAssertion",
+} {
+    MemberAssignmentTarget => MemberExpression,
+});
+
+deserialize_untagged!(AssignmentTargetPattern<'a> {
+    ArrayAssignmentTarget => "ArrayAssignmentTarget",
+    ObjectAssignmentTarget => "ObjectAssignmentTarget",
+});
+
+deserialize_untagged!(AssignmentTargetMaybeDefault<'a> {
+    AssignmentTargetWithDefault => "AssignmentTargetWithDefault",
+} {
+    AssignmentTarget => AssignmentTarget,
+});
+
+deserialize_untagged!(AssignmentTargetProperty<'a> {
+    AssignmentTargetPropertyIdentifier => "AssignmentTargetPropertyIdentifier",
+    AssignmentTargetPropertyProperty => "AssignmentTargetPropertyProperty",
+});
+
+deserialize_untagged!(ChainElement<'a> {
+    CallExpression => "CallExpression",
+} {
+    MemberExpression => MemberExpression,
+});
+
+deserialize_untagged!(Statement<'a> {
+    BlockStatement => "BlockStatement",
+    BreakStatement => "BreakStatement",
+    ContinueStatement => "ContinueStatement",
+    DebuggerStatement => "DebuggerStatement",
+    DoWhileStatement => "DoWhileStatement",
+    EmptyStatement => "EmptyStatement",
+    ErrorStatement => "ErrorStatement",
+    ExpressionStatement => "ExpressionStatement",
+    ForInStatement => "ForInStatement",
+    ForOfStatement => "ForOfStatement",
+    ForStatement => "ForStatement",
+    IfStatement => "IfStatement",
+    LabeledStatement => "LabeledStatement",
+    ReturnStatement => "ReturnStatement",
+    SwitchStatement => "SwitchStatement",
+    ThrowStatement => "ThrowStatement",
+    TryStatement => "TryStatement",
+    WhileStatement => "WhileStatement",
+    WithStatement => "WithStatement",
+} {
+    ModuleDeclaration => ModuleDeclaration,
+    Declaration => Declaration,
+});
+
+deserialize_untagged!(Declaration<'a> {
+    VariableDeclaration => "VariableDeclaration",
+    FunctionDeclaration => "FunctionDeclaration" | "TSDeclareFunction",
+    ClassDeclaration => "ClassDeclaration",
+    TSTypeAliasDeclaration => "TSTypeAliasDeclaration",
+    TSInterfaceDeclaration => "TSInterfaceDeclaration",
+    TSEnumDeclaration => "TSEnumDeclaration",
+    TSModuleDeclaration => "TSModuleDeclaration",
+    TSImportEqualsDeclaration => "TSImportEqualsDeclaration",
+});
+
+deserialize_untagged!(ForStatementInit<'a> {
+    VariableDeclaration => "VariableDeclaration",
+} {
+    Expression => Expression,
+});
+
+deserialize_untagged!(ForStatementLeft<'a> {
+    VariableDeclaration => "VariableDeclaration",
+} {
+    AssignmentTarget => AssignmentTarget,
+});
+
+deserialize_untagged!(BindingPatternKind<'a> {
+    BindingIdentifier => "BindingIdentifier",
+    ObjectPattern => "ObjectPattern",
+    ArrayPattern => "ArrayPattern",
+    AssignmentPattern => "AssignmentPattern",
+});
+
+deserialize_untagged!(ClassElement<'a> {
+    StaticBlock => "StaticBlock",
+    MethodDefinition => "MethodDefinition",
+    PropertyDefinition => "PropertyDefinition",
+    AccessorProperty => "AccessorProperty",
+    TSAbstractMethodDefinition => "TSAbstractMethodDefinition",
+    TSAbstractPropertyDefinition => "TSAbstractPropertyDefinition",
+    TSIndexSignature => "TSIndexSignature",
+});
+
+deserialize_untagged!(ModuleDeclaration<'a> {
+    ImportDeclaration => "ImportDeclaration",
+    ExportAllDeclaration => "ExportAllDeclaration",
+    ExportDefaultDeclaration => "ExportDefaultDeclaration",
+    ExportNamedDeclaration => "ExportNamedDeclaration",
+    TSExportAssignment => "TSExportAssignment",
+    TSNamespaceExportDeclaration => "TSNamespaceExportDeclaration",
+});
+
+deserialize_untagged!(ImportDeclarationSpecifier {
+    ImportSpecifier => "ImportSpecifier",
+    ImportDefaultSpecifier => "ImportDefaultSpecifier",
+    ImportNamespaceSpecifier => "ImportNamespaceSpecifier",
+});
+
+deserialize_untagged!(ImportAttributeKey {
+    Identifier => "IdentifierName",
+    StringLiteral => "StringLiteral",
+});
+
+deserialize_untagged!(ExportDefaultDeclarationKind<'a> {
+    FunctionDeclaration => "FunctionDeclaration" | "TSDeclareFunction",
+    ClassDeclaration => "ClassDeclaration",
+    TSInterfaceDeclaration => "TSInterfaceDeclaration",
+    TSEnumDeclaration => "TSEnumDeclaration",
+} {
+    Expression => Expression,
+});
+
+deserialize_untagged!(ModuleExportName {
+    Identifier => "IdentifierName",
+    StringLiteral => "StringLiteral",
+});
+
+deserialize_untagged!(JSXElementName<'a> {
+    Identifier => "JSXIdentifier",
+    NamespacedName => "JSXNamespacedName",
+    MemberExpression => "JSXMemberExpression",
+});
+
+deserialize_untagged!(JSXMemberExpressionObject<'a> {
+    Identifier => "JSXIdentifier",
+    MemberExpression => "JSXMemberExpression",
+});
+
+deserialize_untagged!(JSXExpression<'a> {
+    EmptyExpression => "JSXEmptyExpression",
+} {
+    Expression => Expression,
+});
+
+deserialize_untagged!(JSXAttributeItem<'a> {
+    Attribute => "JSXAttribute",
+    SpreadAttribute => "JSXSpreadAttribute",
+});
+
+deserialize_untagged!(JSXAttributeName<'a> {
+    Identifier => "JSXIdentifier",
+    NamespacedName => "JSXNamespacedName",
+});
+
+deserialize_untagged!(JSXAttributeValue<'a> {
+    StringLiteral => "StringLiteral",
+    ExpressionContainer => "JSXExpressionContainer",
+    Element => "JSXElement",
+    Fragment => "JSXFragment",
+});
+
+deserialize_untagged!(JSXChild<'a> {
+    Text => "JSXText",
+    Element => "JSXElement",
+    Fragment => "JSXFragment",
+    ExpressionContainer => "JSXExpressionContainer",
+    Spread => "JSXSpreadChild",
+});
+
+deserialize_untagged!(TSEnumMemberName<'a> {
+    Identifier => "IdentifierName",
+    StringLiteral => "StringLiteral",
+    NumberLiteral => "NumberLiteral",
+} {
+    ComputedPropertyName => Expression,
+});
+
+deserialize_untagged!(TSLiteral<'a> {
+    BooleanLiteral => "BooleanLiteral",
+    NullLiteral => "NullLiteral",
+    NumberLiteral => "NumberLiteral",
+    BigintLiteral => "BigintLiteral",
+    RegExpLiteral => "RegExpLiteral",
+    StringLiteral => "StringLiteral",
+    TemplateLiteral => "TemplateLiteral",
+    UnaryExpression => "UnaryExpression",
+});
+
+deserialize_untagged!(TSType<'a> {
+    TSAnyKeyword => "TSAnyKeyword",
+    TSBigIntKeyword => "TSBigIntKeyword",
+    TSBooleanKeyword => "TSBooleanKeyword",
+    TSNeverKeyword => "TSNeverKeyword",
+    TSNullKeyword => "TSNullKeyword",
+    TSNumberKeyword => "TSNumberKeyword",
+    TSObjectKeyword => "TSObjectKeyword",
+    TSStringKeyword => "TSStringKeyword",
+    TSSymbolKeyword => "TSSymbolKeyword",
+    TSThisKeyword => "TSThisKeyword",
+    TSUndefinedKeyword => "TSUndefinedKeyword",
+    TSUnknownKeyword => "TSUnknownKeyword",
+    TSVoidKeyword => "TSVoidKeyword",
+    TSArrayType => "TSArrayType",
+    TSConditionalType => "TSConditionalType",
+    TSConstructorType => "TSConstructorType",
+    TSFunctionType => "TSFunctionType",
+    TSImportType => "TSImportType",
+    TSIndexedAccessType => "TSIndexedAccessType",
+    TSInferType => "TSInferType",
+    TSIntersectionType => "TSIntersectionType",
+    TSLiteralType => "TSLiteralType",
+    TSMappedType => "TSMappedType",
+    TSQualifiedName => "TSQualifiedName",
+    TSTemplateLiteralType => "TSTemplateLiteralType",
+    TSTupleType => "TSTupleType",
+    TSTypeLiteral => "TSTypeLiteral",
+    TSTypeOperatorType => "TSTypeOperator",
+    TSTypePredicate => "TSTypePredicate",
+    TSTypeQuery => "TSTypeQuery",
+    TSTypeReference => "TSTypeReference",
+    TSUnionType => "TSUnionType",
+    JSDocNullableType => "JSDocNullableType",
+    JSDocUnknownType => "JSDocUnknownType",
+});
+
+deserialize_untagged!(TSTupleElement<'a> {
+    TSOptionalType => "TSOptionalType",
+    TSRestType => "TSRestType",
+    TSNamedTupleMember => "TSNamedTupleMember",
+} {
+    TSType => TSType,
+});
+
+deserialize_untagged!(TSTypeName<'a> {
+    IdentifierName => "IdentifierName",
+    QualifiedName => "TSQualifiedName",
+});
+
+deserialize_untagged!(TSSignature<'a> {
+    TSIndexSignature => "TSIndexSignature",
+    TSPropertySignature => "TSPropertySignature",
+    TSCallSignatureDeclaration => "TSCallSignatureDeclaration",
+    TSConstructSignatureDeclaration => "TSConstructSignatureDeclaration",
+    TSMethodSignature => "TSMethodSignature",
+});
+
+deserialize_untagged!(TSTypePredicateName {
+    Identifier => "IdentifierName",
+    This => "TSThisKeyword",
+});
+
+deserialize_untagged!(TSModuleDeclarationName {
+    Identifier => "IdentifierName",
+    StringLiteral => "StringLiteral",
+});
+
+deserialize_untagged!(TSModuleDeclarationBody<'a> {
+    TSModuleDeclaration => "TSModuleDeclaration",
+    TSModuleBlock => "TSModuleBlock",
+});
+
+deserialize_untagged!(TSModuleReference<'a> {
+    ExternalModuleReference => "TSExternalModuleReference",
+} {
+    TypeName => TSTypeName,
+});
+
+/// Holes are serialized as their span, without a `type`
+impl<'de, 'a> Deserialize<'de> for ArrayExpressionElement<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match node_type(&value) {
+            "SpreadElement" => Deserialize::deserialize(value).map(Self::SpreadElement),
+            "" => Deserialize::deserialize(value).map(Self::Elision),
+            _ => Deserialize::deserialize(value).map(Self::Expression),
+        }
+        .map_err(de::Error::custom)
+    }
+}
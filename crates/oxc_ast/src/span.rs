@@ -12,6 +12,7 @@ impl<'a> GetSpan for Statement<'a> {
             Self::DebuggerStatement(stmt) => stmt.span,
             Self::DoWhileStatement(stmt) => stmt.span,
             Self::EmptyStatement(stmt) => stmt.span,
+            Self::ErrorStatement(stmt) => stmt.span,
             Self::ExpressionStatement(stmt) => stmt.span,
             Self::ForInStatement(stmt) => stmt.span,
             Self::ForOfStatement(stmt) => stmt.span,
@@ -66,6 +67,7 @@ impl<'a> GetSpan for Expression<'a> {
             Self::UnaryExpression(e) => e.span,
             Self::UpdateExpression(e) => e.span,
             Self::YieldExpression(e) => e.span,
+            Self::ErrorExpression(e) => e.span,
             Self::JSXElement(e) => e.span,
             Self::JSXFragment(e) => e.span,
             Self::TSAsExpression(e) => e.span,
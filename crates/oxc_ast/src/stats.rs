@@ -0,0 +1,169 @@
+//! Statistics about the size and shape of an AST
+//!
+//! Used for finding files which are expensive to process, e.g. generated or bundled code.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+};
+
+use oxc_span::{Atom, Span};
+use rustc_hash::FxHashSet;
+
+use crate::{ast::Program, AstKind, Visit};
+
+#[derive(Debug, Default)]
+pub struct AstStats {
+    /// Number of nodes of each kind, e.g. `CallExpression`
+    pub node_counts: BTreeMap<String, usize>,
+    /// Depth of the most deeply nested node, the program being at depth 1
+    pub max_depth: usize,
+    /// Functions, including arrow functions, in source order
+    pub functions: Vec<FunctionStats>,
+    /// Number of string literals
+    pub string_count: usize,
+    /// Total length of the string literals in bytes
+    pub string_bytes: usize,
+    /// Number of identifiers, including property names and labels
+    pub identifier_count: usize,
+    /// Number of distinct identifier names
+    pub unique_identifier_count: usize,
+}
+
+#[derive(Debug)]
+pub struct FunctionStats {
+    /// `None` for anonymous functions
+    pub name: Option<Atom>,
+    pub span: Span,
+    /// Number of nodes in the function, including the function itself and nested functions
+    pub node_count: usize,
+}
+
+impl AstStats {
+    pub fn new<'a>(program: &'a Program<'a>) -> Self {
+        let mut collector = StatsCollector::default();
+        collector.visit_program(program);
+        let StatsCollector { mut stats, identifiers, .. } = collector;
+        stats.unique_identifier_count = identifiers.len();
+        stats
+    }
+
+    /// Total number of nodes
+    pub fn node_count(&self) -> usize {
+        self.node_counts.values().sum()
+    }
+
+    /// The function with the most nodes
+    pub fn largest_function(&self) -> Option<&FunctionStats> {
+        self.functions.iter().max_by_key(|function| function.node_count)
+    }
+}
+
+#[derive(Default)]
+struct StatsCollector {
+    stats: AstStats,
+    depth: usize,
+    identifiers: FxHashSet<Atom>,
+    /// Indexes into `stats.functions` of the functions being visited
+    function_stack: Vec<usize>,
+}
+
+impl<'a> Visit<'a> for StatsCollector {
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        self.depth += 1;
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+        *self.stats.node_counts.entry(kind_name(kind)).or_default() += 1;
+
+        match kind {
+            AstKind::Function(func) => {
+                let name = func.id.as_ref().map(|id| id.name.clone());
+                self.enter_function(name, func.span);
+            }
+            AstKind::ArrowExpression(arrow) => self.enter_function(None, arrow.span),
+            AstKind::StringLiteral(lit) => {
+                self.stats.string_count += 1;
+                self.stats.string_bytes += lit.value.len();
+            }
+            _ => {}
+        }
+        if let Some(name) = kind.identifier_name() {
+            self.stats.identifier_count += 1;
+            self.identifiers.insert(name);
+        }
+        for &index in &self.function_stack {
+            self.stats.functions[index].node_count += 1;
+        }
+    }
+
+    fn leave_node(&mut self, kind: AstKind<'a>) {
+        self.depth -= 1;
+        if matches!(kind, AstKind::Function(_) | AstKind::ArrowExpression(_)) {
+            self.function_stack.pop();
+        }
+    }
+}
+
+impl StatsCollector {
+    fn enter_function(&mut self, name: Option<Atom>, span: Span) {
+        self.function_stack.push(self.stats.functions.len());
+        self.stats.functions.push(FunctionStats { name, span, node_count: 0 });
+    }
+}
+
+/// Name of the kind of node, e.g. `CallExpression`, which is the start of its `Debug` output.
+/// `AstKind::debug_name` only exists in debug builds.
+fn kind_name(kind: AstKind) -> String {
+    /// Stops formatting at the `(` after the name of the variant
+    struct VariantName(String);
+
+    impl fmt::Write for VariantName {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if let Some(end) = s.find('(') {
+                self.0.push_str(&s[..end]);
+                return Err(fmt::Error);
+            }
+            self.0.push_str(s);
+            Ok(())
+        }
+    }
+
+    let mut name = VariantName(String::new());
+    let _ = write!(name, "{kind:?}");
+    name.0
+}
+
+#[cfg(test)]
+mod test {
+    // The parser depends on this crate, so the AST is built by hand
+    use oxc_allocator::Allocator;
+    use oxc_span::{SourceType, Span};
+
+    use super::AstStats;
+    use crate::AstBuilder;
+
+    #[test]
+    fn stats() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        // foo("ab", foo)
+        let call = ast.call_expression_with_arguments(
+            ast.identifier_reference_expression("foo"),
+            [ast.string_literal_expression("ab"), ast.identifier_reference_expression("foo")],
+        );
+        let body = ast.new_vec_single(ast.expression_statement(Span::default(), call));
+        let program =
+            ast.program(Span::default(), SourceType::default(), ast.new_vec(), None, body);
+        let program = allocator.alloc(program);
+
+        let stats = AstStats::new(program);
+        assert_eq!(stats.node_counts["IdentifierReference"], 2);
+        assert_eq!(stats.node_counts["CallExpression"], 1);
+        // Program > ExpressionStatement > CallExpression > Argument > StringLiteral
+        assert_eq!(stats.max_depth, 5);
+        assert_eq!(stats.string_count, 1);
+        assert_eq!(stats.string_bytes, 2);
+        assert_eq!(stats.identifier_count, 2);
+        assert_eq!(stats.unique_identifier_count, 1);
+        assert!(stats.functions.is_empty());
+    }
+}
@@ -0,0 +1,174 @@
+//! Spans of significant keywords and punctuators
+//!
+//! The AST only keeps the spans of nodes, so the position of a token between two child nodes,
+//! such as the `else` of an `if` statement, would have to be recomputed by scanning the source text,
+//! which must skip comments and strings. This table records them as they are parsed,
+//! for formatters and fixers to target exactly the token to edit.
+
+use oxc_span::{GetSpan, Span};
+
+use crate::ast::{
+    ArrowExpression, Class, ConditionalExpression, DoWhileStatement, ForInStatement,
+    ForOfStatement, IfStatement, TryStatement,
+};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TokenSpanKind {
+    /// `=>`
+    Arrow,
+    /// `?`
+    Question,
+    /// `:`
+    Colon,
+    Catch,
+    Else,
+    Extends,
+    Finally,
+    Implements,
+    In,
+    Of,
+    While,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TokenSpan {
+    pub kind: TokenSpanKind,
+    pub span: Span,
+}
+
+/// Spans of the tokens of [`TokenSpanKind`] in source order
+///
+/// Tokens are recorded wherever they appear, e.g. `extends` in `<T extends U>` as well as in a
+/// class heritage, so they are looked up between the child nodes surrounding them.
+#[derive(Debug, Default)]
+pub struct TokenSpans {
+    tokens: Vec<TokenSpan>,
+}
+
+impl TokenSpans {
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &TokenSpan> + '_ {
+        self.tokens.iter()
+    }
+
+    /// Tokens starting within `range`
+    pub fn range(&self, range: Span) -> &[TokenSpan] {
+        let start = self.tokens.partition_point(|token| token.span.start < range.start);
+        let end = self.tokens.partition_point(|token| token.span.start < range.end);
+        &self.tokens[start..end.max(start)]
+    }
+
+    /// First token of `kind` starting within `range`
+    pub fn find(&self, kind: TokenSpanKind, range: Span) -> Option<Span> {
+        self.range(range).iter().find(|token| token.kind == kind).map(|token| token.span)
+    }
+
+    /// Last token of `kind` starting within `range`
+    pub fn rfind(&self, kind: TokenSpanKind, range: Span) -> Option<Span> {
+        self.range(range).iter().rev().find(|token| token.kind == kind).map(|token| token.span)
+    }
+
+    /// Tokens must be added in source order
+    pub fn add(&mut self, kind: TokenSpanKind, span: Span) {
+        debug_assert!(self.tokens.last().map_or(true, |token| token.span.end <= span.start));
+        self.tokens.push(TokenSpan { kind, span });
+    }
+
+    /// Remove the tokens starting at or after `offset`
+    pub fn truncate(&mut self, offset: u32) {
+        let len = self.tokens.partition_point(|token| token.span.start < offset);
+        self.tokens.truncate(len);
+    }
+
+    /// Remove the tokens starting at or after `offset` and return them
+    #[must_use]
+    pub fn split_off(&mut self, offset: u32) -> Self {
+        let len = self.tokens.partition_point(|token| token.span.start < offset);
+        Self { tokens: self.tokens.split_off(len) }
+    }
+
+    /// Move the tokens, which must start at or after `from`, by `to - from`,
+    /// e.g. after an edit ending at `from` in the previous source text and at `to` in the edited one
+    pub fn shift(&mut self, from: u32, to: u32) {
+        for token in &mut self.tokens {
+            token.span = Span::new(token.span.start - from + to, token.span.end - from + to);
+        }
+    }
+
+    /// Add the tokens of `other`, which must start after the last token
+    pub fn append(&mut self, mut other: Self) {
+        debug_assert!(match (self.tokens.last(), other.tokens.first()) {
+            (Some(last), Some(first)) => last.span.end <= first.span.start,
+            _ => true,
+        });
+        self.tokens.append(&mut other.tokens);
+    }
+
+    /// `else` of an `if` statement with an alternate
+    pub fn if_else(&self, stmt: &IfStatement) -> Option<Span> {
+        let alternate = stmt.alternate.as_ref()?;
+        let range = Span::new(stmt.consequent.span().end, alternate.span().start);
+        self.find(TokenSpanKind::Else, range)
+    }
+
+    /// `while` of a `do ... while` statement
+    pub fn do_while(&self, stmt: &DoWhileStatement) -> Option<Span> {
+        let range = Span::new(stmt.body.span().end, stmt.test.span().start);
+        self.find(TokenSpanKind::While, range)
+    }
+
+    /// `catch` of a `try` statement with a handler
+    pub fn try_catch(&self, stmt: &TryStatement) -> Option<Span> {
+        let handler = stmt.handler.as_ref()?;
+        let range = Span::new(stmt.block.span.end, handler.span.end);
+        self.find(TokenSpanKind::Catch, range)
+    }
+
+    /// `finally` of a `try` statement with a finalizer
+    pub fn try_finally(&self, stmt: &TryStatement) -> Option<Span> {
+        let finalizer = stmt.finalizer.as_ref()?;
+        let start = stmt.handler.as_ref().map_or(stmt.block.span.end, |handler| handler.span.end);
+        self.find(TokenSpanKind::Finally, Span::new(start, finalizer.span.start))
+    }
+
+    /// `in` of a `for ... in` statement
+    pub fn for_in(&self, stmt: &ForInStatement) -> Option<Span> {
+        let range = Span::new(stmt.left.span().end, stmt.right.span().start);
+        self.find(TokenSpanKind::In, range)
+    }
+
+    /// `of` of a `for ... of` statement
+    pub fn for_of(&self, stmt: &ForOfStatement) -> Option<Span> {
+        let range = Span::new(stmt.left.span().end, stmt.right.span().start);
+        self.find(TokenSpanKind::Of, range)
+    }
+
+    /// `=>` of an arrow function, after the return type if any
+    pub fn arrow(&self, expr: &ArrowExpression) -> Option<Span> {
+        let range = Span::new(expr.params.span.end, expr.body.span.start);
+        self.rfind(TokenSpanKind::Arrow, range)
+    }
+
+    /// `?` and `:` of a conditional expression
+    pub fn conditional(&self, expr: &ConditionalExpression) -> Option<(Span, Span)> {
+        let question = Span::new(expr.test.span().end, expr.consequent.span().start);
+        let colon = Span::new(expr.consequent.span().end, expr.alternate.span().start);
+        Some((
+            self.find(TokenSpanKind::Question, question)?,
+            self.find(TokenSpanKind::Colon, colon)?,
+        ))
+    }
+
+    /// `extends` of a class with a super class, after the type parameters if any
+    pub fn class_extends(&self, class: &Class) -> Option<Span> {
+        let super_class = class.super_class.as_ref()?;
+        let range = Span::new(class.span.start, super_class.span().start);
+        self.rfind(TokenSpanKind::Extends, range)
+    }
+
+    /// `implements` of a class implementing interfaces
+    pub fn class_implements(&self, class: &Class) -> Option<Span> {
+        let first = class.implements.as_ref()?.first()?;
+        let range = Span::new(class.span.start, first.span.start);
+        self.rfind(TokenSpanKind::Implements, range)
+    }
+}
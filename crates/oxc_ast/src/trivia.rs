@@ -16,7 +16,6 @@ pub struct Trivias {
 
 /// Single or multiline comment
 #[derive(Debug, Clone, Copy)]
-#[allow(unused)]
 pub struct Comment {
     kind: CommentKind,
     end: u32,
@@ -37,6 +36,10 @@ impl Comment {
         self.end
     }
 
+    pub fn kind(self) -> CommentKind {
+        self.kind
+    }
+
     pub fn is_single_line(self) -> bool {
         matches!(self.kind, CommentKind::SingleLine)
     }
@@ -55,6 +58,47 @@ impl Trivias {
         self.comments.range(span.start..span.end).count() > 0
     }
 
+    /// Whether the call or `new` expression starting at `start` is annotated with
+    /// `/* #__PURE__ */` or `/* @__PURE__ */`, i.e. the last comment before it is the annotation,
+    /// with only whitespace and opening parentheses in between
+    pub fn is_pure_annotated(&self, source_text: &str, start: u32) -> bool {
+        let Some((comment_start, comment)) = self.comments.range(..start).next_back() else {
+            return false;
+        };
+        // Comment spans exclude the `//`, `/*` and `*/`
+        let comment_end = if comment.is_multi_line() { comment.end + 2 } else { comment.end };
+        let Some(between) = source_text.get(comment_end as usize..start as usize) else {
+            return false;
+        };
+        if !between.chars().all(|c| c.is_whitespace() || c == '(') {
+            return false;
+        }
+        let text = &source_text[*comment_start as usize..comment.end as usize];
+        matches!(text.trim(), "#__PURE__" | "@__PURE__")
+    }
+
+    /// Remove the comments starting at or after `offset` and return them
+    #[must_use]
+    pub fn split_off(&mut self, offset: u32) -> Self {
+        Self { comments: self.comments.split_off(&offset) }
+    }
+
+    /// Move the comments, which must start at or after `from`, by `to - from`,
+    /// e.g. after an edit ending at `from` in the previous source text and at `to` in the edited one
+    pub fn shift(&mut self, from: u32, to: u32) {
+        self.comments = std::mem::take(&mut self.comments)
+            .into_iter()
+            .map(|(start, comment)| {
+                (start - from + to, Comment::new(comment.end - from + to, comment.kind))
+            })
+            .collect();
+    }
+
+    /// Add the comments of `other`
+    pub fn append(&mut self, mut other: Self) {
+        self.comments.append(&mut other.comments);
+    }
+
     pub fn add_single_line_comment(&mut self, span: Span) {
         let comment = Comment::new(span.end, CommentKind::SingleLine);
         self.comments.insert(span.start, comment);
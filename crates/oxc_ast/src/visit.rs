@@ -18,6 +18,9 @@ pub trait Visit<'a>: Sized {
     fn visit_program(&mut self, program: &'a Program<'a>) {
         let kind = AstKind::Program(program);
         self.enter_node(kind);
+        if let Some(hashbang) = &program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
         for directive in &program.directives {
             self.visit_directive(directive);
         }
@@ -45,6 +48,7 @@ pub trait Visit<'a>: Sized {
             Statement::DebuggerStatement(stmt) => self.visit_debugger_statement(stmt),
             Statement::DoWhileStatement(stmt) => self.visit_do_while_statement(stmt),
             Statement::EmptyStatement(stmt) => self.visit_empty_statement(stmt),
+            Statement::ErrorStatement(stmt) => self.visit_error_statement(stmt),
             Statement::ExpressionStatement(stmt) => self.visit_expression_statement(stmt),
             Statement::ForInStatement(stmt) => self.visit_for_in_statement(stmt),
             Statement::ForOfStatement(stmt) => self.visit_for_of_statement(stmt),
@@ -108,6 +112,12 @@ pub trait Visit<'a>: Sized {
         self.leave_node(kind);
     }
 
+    fn visit_error_statement(&mut self, stmt: &'a ErrorStatement) {
+        let kind = AstKind::ErrorStatement(stmt);
+        self.enter_node(kind);
+        self.leave_node(kind);
+    }
+
     fn visit_expression_statement(&mut self, stmt: &'a ExpressionStatement<'a>) {
         let kind = AstKind::ExpressionStatement(stmt);
         self.enter_node(kind);
@@ -271,6 +281,12 @@ pub trait Visit<'a>: Sized {
         self.leave_node(kind);
     }
 
+    fn visit_hashbang(&mut self, hashbang: &'a Hashbang) {
+        let kind = AstKind::Hashbang(hashbang);
+        self.enter_node(kind);
+        self.leave_node(kind);
+    }
+
     fn visit_directive(&mut self, directive: &'a Directive) {
         let kind = AstKind::Directive(directive);
         self.enter_node(kind);
@@ -405,7 +421,7 @@ pub trait Visit<'a>: Sized {
             ClassElement::StaticBlock(block) => self.visit_static_block(block),
             ClassElement::MethodDefinition(def) => self.visit_method_definition(def),
             ClassElement::PropertyDefinition(def) => self.visit_property_definition(def),
-            ClassElement::AccessorProperty(_def) => { /* TODO */ }
+            ClassElement::AccessorProperty(def) => self.visit_accessor_property(def),
             ClassElement::TSAbstractMethodDefinition(def) => {
                 self.visit_method_definition(&def.method_definition);
             }
@@ -450,6 +466,16 @@ pub trait Visit<'a>: Sized {
         self.leave_node(kind);
     }
 
+    fn visit_accessor_property(&mut self, def: &'a AccessorProperty<'a>) {
+        for decorator in &def.decorators {
+            self.visit_decorator(decorator);
+        }
+        self.visit_property_key(&def.key);
+        if let Some(value) = &def.value {
+            self.visit_expression(value);
+        }
+    }
+
     /* ----------  Expression ---------- */
 
     fn visit_expression(&mut self, expr: &'a Expression<'a>) {
@@ -496,6 +522,7 @@ pub trait Visit<'a>: Sized {
             Expression::UnaryExpression(expr) => self.visit_unary_expression(expr),
             Expression::UpdateExpression(expr) => self.visit_update_expression(expr),
             Expression::YieldExpression(expr) => self.visit_yield_expression(expr),
+            Expression::ErrorExpression(expr) => self.visit_error_expression(expr),
             Expression::Super(expr) => self.visit_super(expr),
             Expression::JSXElement(elem) => self.visit_jsx_element(elem),
             Expression::JSXFragment(elem) => self.visit_jsx_fragment(elem),
@@ -756,6 +783,12 @@ pub trait Visit<'a>: Sized {
         self.leave_node(kind);
     }
 
+    fn visit_error_expression(&mut self, expr: &'a ErrorExpression) {
+        let kind = AstKind::ErrorExpression(expr);
+        self.enter_node(kind);
+        self.leave_node(kind);
+    }
+
     fn visit_unary_expression(&mut self, expr: &'a UnaryExpression<'a>) {
         let kind = AstKind::UnaryExpression(expr);
         self.enter_node(kind);
@@ -1172,7 +1205,7 @@ pub trait Visit<'a>: Sized {
             self.visit_import_declaration_specifier(specifier);
         }
         // TODO: source
-        // TODO: assertions
+        // TODO: with_clause
     }
 
     fn visit_import_declaration_specifier(&mut self, specifier: &'a ImportDeclarationSpecifier) {
@@ -7,8 +7,15 @@ use oxc_span::Span;
 use crate::ast::*;
 
 /// Syntax tree traversal to mutate an exclusive borrow of a syntax tree in place.
+///
+/// Visits the same nodes in the same order as [`Visit`](crate::Visit), so that a transform can
+/// rewrite the nodes in the arena without cloning them, e.g. by replacing `*expr` in
+/// `visit_expression`.
 pub trait VisitMut<'a, 'b>: Sized {
     fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
         for directive in program.directives.iter_mut() {
             self.visit_directive(directive);
         }
@@ -35,6 +42,7 @@ pub trait VisitMut<'a, 'b>: Sized {
             Statement::DebuggerStatement(stmt) => self.visit_debugger_statement(stmt),
             Statement::DoWhileStatement(stmt) => self.visit_do_while_statement(stmt),
             Statement::EmptyStatement(stmt) => self.visit_empty_statement(stmt),
+            Statement::ErrorStatement(stmt) => self.visit_error_statement(stmt),
             Statement::ExpressionStatement(stmt) => self.visit_expression_statement(stmt),
             Statement::ForInStatement(stmt) => self.visit_for_in_statement(stmt),
             Statement::ForOfStatement(stmt) => self.visit_for_of_statement(stmt),
@@ -78,6 +86,8 @@ pub trait VisitMut<'a, 'b>: Sized {
 
     fn visit_empty_statement(&mut self, _stmt: &'b mut EmptyStatement) {}
 
+    fn visit_error_statement(&mut self, _stmt: &'b mut ErrorStatement) {}
+
     fn visit_expression_statement(&mut self, stmt: &'b mut ExpressionStatement<'a>) {
         self.visit_expression(&mut stmt.expression);
     }
@@ -193,6 +203,8 @@ pub trait VisitMut<'a, 'b>: Sized {
         self.visit_statement(&mut stmt.body);
     }
 
+    fn visit_hashbang(&mut self, _hashbang: &'b mut Hashbang) {}
+
     fn visit_directive(&mut self, directive: &'b mut Directive) {
         self.visit_string_literal(&mut directive.expression);
     }
@@ -294,7 +306,7 @@ pub trait VisitMut<'a, 'b>: Sized {
             ClassElement::StaticBlock(block) => self.visit_static_block(block),
             ClassElement::MethodDefinition(def) => self.visit_method_definition(def),
             ClassElement::PropertyDefinition(def) => self.visit_property_definition(def),
-            ClassElement::AccessorProperty(_def) => { /* TODO */ }
+            ClassElement::AccessorProperty(def) => self.visit_accessor_property(def),
             ClassElement::TSAbstractMethodDefinition(def) => {
                 self.visit_method_definition(&mut def.method_definition);
             }
@@ -330,6 +342,16 @@ pub trait VisitMut<'a, 'b>: Sized {
         }
     }
 
+    fn visit_accessor_property(&mut self, def: &'b mut AccessorProperty<'a>) {
+        for decorator in def.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        self.visit_property_key(&mut def.key);
+        if let Some(value) = &mut def.value {
+            self.visit_expression(value);
+        }
+    }
+
     /* ----------  Expression ---------- */
 
     fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
@@ -376,6 +398,7 @@ pub trait VisitMut<'a, 'b>: Sized {
             Expression::UnaryExpression(expr) => self.visit_unary_expression(expr),
             Expression::UpdateExpression(expr) => self.visit_update_expression(expr),
             Expression::YieldExpression(expr) => self.visit_yield_expression(expr),
+            Expression::ErrorExpression(expr) => self.visit_error_expression(expr),
             Expression::Super(expr) => self.visit_super(expr),
             Expression::JSXElement(elem) => self.visit_jsx_element(elem),
             Expression::JSXFragment(elem) => self.visit_jsx_fragment(elem),
@@ -567,6 +590,8 @@ pub trait VisitMut<'a, 'b>: Sized {
 
     fn visit_this_expression(&mut self, _expr: &'b mut ThisExpression) {}
 
+    fn visit_error_expression(&mut self, _expr: &'b mut ErrorExpression) {}
+
     fn visit_unary_expression(&mut self, expr: &'b mut UnaryExpression<'a>) {
         self.visit_expression(&mut expr.argument);
     }
@@ -798,6 +823,9 @@ pub trait VisitMut<'a, 'b>: Sized {
         for prop in pat.properties.iter_mut() {
             self.visit_binding_property(prop);
         }
+        if let Some(rest) = &mut pat.rest {
+            self.visit_rest_element(rest);
+        }
     }
 
     fn visit_binding_property(&mut self, prop: &'b mut BindingProperty<'a>) {
@@ -886,7 +914,7 @@ pub trait VisitMut<'a, 'b>: Sized {
             self.visit_import_declaration_specifier(specifier);
         }
         // TODO: source
-        // TODO: assertions
+        // TODO: with_clause
     }
 
     fn visit_import_declaration_specifier(
@@ -946,7 +974,11 @@ pub trait VisitMut<'a, 'b>: Sized {
 
     fn visit_enum(&mut self, decl: &'b mut TSEnumDeclaration<'a>) {
         self.visit_binding_identifier(&mut decl.id);
-        for member in decl.body.members.iter_mut() {
+        self.visit_enum_body(&mut decl.body);
+    }
+
+    fn visit_enum_body(&mut self, body: &'b mut TSEnumBody<'a>) {
+        for member in body.members.iter_mut() {
             self.visit_enum_member(member);
         }
     }
@@ -0,0 +1,51 @@
+use oxc_allocator::Allocator;
+use oxc_ast::query::Selector;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+/// The type and name, or the name of the callee, of each node matching the selector
+fn query(source_text: &str, selector: &str) -> Vec<String> {
+    let allocator = Allocator::default();
+    let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+    program
+        .query(selector)
+        .unwrap()
+        .iter()
+        .map(|node| {
+            let name = node.get("name").or_else(|| node.pointer("/callee/name"));
+            format!(
+                "{}:{}",
+                node["type"].as_str().unwrap(),
+                name.map_or("", |n| n.as_str().unwrap())
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn selectors() {
+    let source_text = "let a = require('a'); foo(b.c);";
+    let query = |selector| query(source_text, selector);
+    assert_eq!(query("CallExpression"), ["CallExpression:require", "CallExpression:foo"]);
+    assert_eq!(query("CallExpression[callee.name='require']"), ["CallExpression:require"]);
+    assert_eq!(query("CallExpression[callee.name!=\"require\"]"), ["CallExpression:foo"]);
+    assert_eq!(query("[arguments.length=1] > StaticMemberExpression"), ["StaticMemberExpression:"]);
+    assert_eq!(query("VariableDeclaration IdentifierReference"), ["IdentifierReference:require"]);
+    assert_eq!(query("ExpressionStatement > IdentifierReference"), Vec::<String>::new());
+    assert_eq!(
+        query("IdentifierReference:not([name=b]), BindingIdentifier"),
+        ["BindingIdentifier:a", "IdentifierReference:require", "IdentifierReference:foo"]
+    );
+    assert_eq!(query("* > IdentifierName"), ["IdentifierName:c"]);
+    // Whitespace which is not ASCII
+    assert_eq!(query("VariableDeclaration\u{3000}>\u{a0}VariableDeclarator").len(), 1);
+}
+
+#[test]
+fn errors() {
+    assert_eq!(Selector::parse("").unwrap_err().offset, 0);
+    assert_eq!(Selector::parse("A[b").unwrap_err().offset, 3);
+    assert_eq!(Selector::parse("A[b='c]").unwrap_err().message, "unterminated string");
+    assert!(Selector::parse("A >").is_err());
+    assert_eq!(Selector::parse("A\u{3000}[").unwrap_err().offset, 5);
+}
@@ -8,6 +8,7 @@ use oxc_semantic::{
     SymbolId,
 };
 use oxc_span::{Atom, GetSpan, SourceType, Span};
+use rustc_hash::FxHashSet;
 
 // <https://github.com/rust-lang/rust/blob/master/compiler/rustc_data_structures/src/stack.rs>
 #[cfg(not(target_arch = "wasm32"))]
@@ -26,11 +27,16 @@ pub fn ensure_sufficient_stack<R, F: FnOnce() -> R>(f: F) -> R {
 pub struct AstLowerReturn<'a> {
     pub program: hir::Program<'a>,
     pub semantic: Semantic<'a>,
+    /// Spans of the expressions wrapped in parentheses in the source,
+    /// parenthesized expressions are not kept in the HIR.
+    /// e.g. the span of `a, b` for `(a, b)`, and of `a + b` for `((a + b))`
+    pub parenthesized: FxHashSet<Span>,
 }
 
 pub struct AstLower<'a> {
     hir: HirBuilder<'a>,
     semantic: SemanticBuilder<'a>,
+    parenthesized: FxHashSet<Span>,
 }
 
 impl<'a> AstLower<'a> {
@@ -120,13 +126,14 @@ impl<'a> AstLower<'a> {
         Self {
             hir: HirBuilder::new(allocator),
             semantic: SemanticBuilder::new(source_text, source_type),
+            parenthesized: FxHashSet::default(),
         }
     }
 
     pub fn build(mut self, program: &ast::Program<'a>) -> AstLowerReturn<'a> {
         let program = self.lower_program(program);
         let semantic = self.semantic.build2();
-        AstLowerReturn { program, semantic }
+        AstLowerReturn { program, semantic, parenthesized: self.parenthesized }
     }
 
     pub fn lower_vec<T, R, F>(&mut self, items: &Vec<'a, T>, cb: F) -> Vec<'a, R>
@@ -179,7 +186,7 @@ impl<'a> AstLower<'a> {
             ast::Statement::ContinueStatement(stmt) => Some(self.lower_continue_statement(stmt)),
             ast::Statement::DebuggerStatement(stmt) => Some(self.lower_debugger_statement(stmt)),
             ast::Statement::DoWhileStatement(stmt) => Some(self.lower_do_while_statement(stmt)),
-            ast::Statement::EmptyStatement(_) => None,
+            ast::Statement::EmptyStatement(_) | ast::Statement::ErrorStatement(_) => None,
             ast::Statement::ExpressionStatement(stmt) => {
                 Some(self.lower_expression_statement(stmt))
             }
@@ -371,6 +378,15 @@ impl<'a> AstLower<'a> {
         self.hir.with_statement(stmt.span, object, body)
     }
 
+    /// `undefined` in place of an expression which cannot be lowered
+    fn lower_undefined(&mut self, span: Span) -> hir::Expression<'a> {
+        let ident = self.lower_identifier_reference(
+            &ast::IdentifierReference { span, name: "undefined".into() },
+            ReferenceFlag::Read,
+        );
+        self.hir.identifier_reference_expression(ident)
+    }
+
     fn lower_expression(&mut self, expr: &ast::Expression<'a>) -> hir::Expression<'a> {
         ensure_sufficient_stack(|| {
             match expr {
@@ -441,22 +457,11 @@ impl<'a> AstLower<'a> {
                 ast::Expression::UpdateExpression(expr) => self.lower_update_expression(expr),
                 ast::Expression::YieldExpression(expr) => self.lower_yield_expression(expr),
                 ast::Expression::Super(expr) => self.lower_super(expr),
-                ast::Expression::JSXElement(elem) => {
-                    // TODO: implement JSX
-                    let ident = self.lower_identifier_reference(
-                        &ast::IdentifierReference { span: elem.span, name: "undefined".into() },
-                        ReferenceFlag::Read,
-                    );
-                    self.hir.identifier_reference_expression(ident)
-                }
-                ast::Expression::JSXFragment(elem) => {
-                    // TODO: implement JSX
-                    let ident = self.lower_identifier_reference(
-                        &ast::IdentifierReference { span: elem.span, name: "undefined".into() },
-                        ReferenceFlag::Read,
-                    );
-                    self.hir.identifier_reference_expression(ident)
-                }
+                // Nothing is known about a missing expression
+                ast::Expression::ErrorExpression(expr) => self.lower_undefined(expr.span),
+                // TODO: implement JSX
+                ast::Expression::JSXElement(elem) => self.lower_undefined(elem.span),
+                ast::Expression::JSXFragment(elem) => self.lower_undefined(elem.span),
                 // Syntax trimmed for the following expressions
                 ast::Expression::TSAsExpression(expr) => self.lower_expression(&expr.expression),
                 ast::Expression::TSSatisfiesExpression(expr) => {
@@ -561,7 +566,7 @@ impl<'a> AstLower<'a> {
     fn lower_call_expression(&mut self, expr: &ast::CallExpression<'a>) -> hir::Expression<'a> {
         let callee = self.lower_expression(&expr.callee);
         let arguments = self.lower_vec(&expr.arguments, Self::lower_argument);
-        self.hir.call_expression(expr.span, callee, arguments, expr.optional)
+        self.hir.call_expression(expr.span, callee, arguments, expr.optional, expr.pure)
     }
 
     fn lower_chain_expression(&mut self, expr: &ast::ChainExpression<'a>) -> hir::Expression<'a> {
@@ -670,7 +675,7 @@ impl<'a> AstLower<'a> {
     fn lower_new_expression(&mut self, expr: &ast::NewExpression<'a>) -> hir::Expression<'a> {
         let callee = self.lower_expression(&expr.callee);
         let arguments = self.lower_vec(&expr.arguments, Self::lower_argument);
-        self.hir.new_expression(expr.span, callee, arguments)
+        self.hir.new_expression(expr.span, callee, arguments, expr.pure)
     }
 
     fn lower_object_expression(&mut self, expr: &ast::ObjectExpression<'a>) -> hir::Expression<'a> {
@@ -682,6 +687,7 @@ impl<'a> AstLower<'a> {
         &mut self,
         expr: &ast::ParenthesizedExpression<'a>,
     ) -> hir::Expression<'a> {
+        self.parenthesized.insert(expr.expression.span());
         self.lower_expression(&expr.expression)
     }
 
@@ -1183,6 +1189,10 @@ impl<'a> AstLower<'a> {
                 let decl = self.lower_import_declaration(decl);
                 hir::ModuleDeclaration::ImportDeclaration(decl)
             }
+            // `export type * from "mod"`
+            ast::ModuleDeclaration::ExportAllDeclaration(decl) if decl.export_kind.is_type() => {
+                return None;
+            }
             ast::ModuleDeclaration::ExportAllDeclaration(decl) => {
                 let decl = self.lower_export_all_declaration(decl);
                 hir::ModuleDeclaration::ExportAllDeclaration(decl)
@@ -1209,15 +1219,18 @@ impl<'a> AstLower<'a> {
     ) -> Box<'a, hir::ImportDeclaration<'a>> {
         let specifiers = self.lower_vec(&decl.specifiers, Self::lower_import_declaration_specifier);
         let source = self.lower_string_literal(&decl.source);
-        let assertions = decl
-            .assertions
-            .as_ref()
-            .map(|attributes| self.lower_vec(attributes, Self::lower_import_attribute));
+        let with_clause = decl.with_clause.as_ref().map(|clause| self.lower_with_clause(clause));
         let import_kind = match decl.import_kind {
             ast::ImportOrExportKind::Value => hir::ImportOrExportKind::Value,
             ast::ImportOrExportKind::Type => hir::ImportOrExportKind::Type,
         };
-        self.hir.import_declaration(decl.span, specifiers, source, assertions, import_kind)
+        self.hir.import_declaration(decl.span, specifiers, source, with_clause, import_kind)
+    }
+
+    fn lower_with_clause(&mut self, clause: &ast::WithClause<'a>) -> hir::WithClause<'a> {
+        let attributes_keyword = self.lower_identifier_name(&clause.attributes_keyword);
+        let with_entries = self.lower_vec(&clause.with_entries, Self::lower_import_attribute);
+        self.hir.with_clause(clause.span, attributes_keyword, with_entries)
     }
 
     fn lower_import_attribute(&mut self, attribute: &ast::ImportAttribute) -> hir::ImportAttribute {
@@ -1317,12 +1330,9 @@ impl<'a> AstLower<'a> {
     ) -> Box<'a, hir::ExportAllDeclaration<'a>> {
         let exported = decl.exported.as_ref().map(|name| self.lower_module_export_name(name));
         let source = self.lower_string_literal(&decl.source);
-        let assertions = decl
-            .assertions
-            .as_ref()
-            .map(|attributes| self.lower_vec(attributes, Self::lower_import_attribute));
+        let with_clause = decl.with_clause.as_ref().map(|clause| self.lower_with_clause(clause));
         let export_kind = self.lower_import_export_type_or_value(decl.export_kind);
-        self.hir.export_all_declaration(decl.span, exported, source, assertions, export_kind)
+        self.hir.export_all_declaration(decl.span, exported, source, with_clause, export_kind)
     }
 
     fn lower_export_default_declaration(
@@ -1359,11 +1369,19 @@ impl<'a> AstLower<'a> {
         let declaration = decl.declaration.as_ref().and_then(|decl| self.lower_declaration(decl));
         let specifiers = self.lower_vec(&decl.specifiers, Self::lower_export_specifier);
         let source = decl.source.as_ref().map(|source| self.lower_string_literal(source));
+        let with_clause = decl.with_clause.as_ref().map(|clause| self.lower_with_clause(clause));
         let export_kind = match decl.export_kind {
             ast::ImportOrExportKind::Value => hir::ImportOrExportKind::Value,
             ast::ImportOrExportKind::Type => hir::ImportOrExportKind::Type,
         };
-        self.hir.export_named_declaration(decl.span, declaration, specifiers, source, export_kind)
+        self.hir.export_named_declaration(
+            decl.span,
+            declaration,
+            specifiers,
+            source,
+            with_clause,
+            export_kind,
+        )
     }
 
     fn lower_export_specifier(&mut self, specifier: &ast::ExportSpecifier) -> hir::ExportSpecifier {
@@ -1402,6 +1420,8 @@ impl<'a> AstLower<'a> {
             ast::VariableDeclarationKind::Var => hir::VariableDeclarationKind::Var,
             ast::VariableDeclarationKind::Const => hir::VariableDeclarationKind::Const,
             ast::VariableDeclarationKind::Let => hir::VariableDeclarationKind::Let,
+            ast::VariableDeclarationKind::Using => hir::VariableDeclarationKind::Using,
+            ast::VariableDeclarationKind::AwaitUsing => hir::VariableDeclarationKind::AwaitUsing,
         };
         let declarations = self.lower_vec(&decl.declarations, Self::lower_variable_declarator);
         self.hir.variable_declaration(decl.span, kind, declarations)
@@ -1415,6 +1435,8 @@ impl<'a> AstLower<'a> {
             ast::VariableDeclarationKind::Var => hir::VariableDeclarationKind::Var,
             ast::VariableDeclarationKind::Const => hir::VariableDeclarationKind::Const,
             ast::VariableDeclarationKind::Let => hir::VariableDeclarationKind::Let,
+            ast::VariableDeclarationKind::Using => hir::VariableDeclarationKind::Using,
+            ast::VariableDeclarationKind::AwaitUsing => hir::VariableDeclarationKind::AwaitUsing,
         };
 
         let (includes, excludes) = if decl.kind.is_lexical() {
@@ -1623,7 +1645,8 @@ impl<'a> AstLower<'a> {
     ) -> Box<'a, hir::AccessorProperty<'a>> {
         let key = self.lower_property_key(&def.key);
         let value = def.value.as_ref().map(|expr| self.lower_expression(expr));
-        self.hir.accessor_property(def.span, key, value, def.computed, def.r#static)
+        let decorators = self.lower_vec(&def.decorators, Self::lower_decorator);
+        self.hir.accessor_property(def.span, key, value, def.computed, def.r#static, decorators)
     }
 
     fn lower_ts_enum_declaration(
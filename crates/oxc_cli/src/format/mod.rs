@@ -0,0 +1,196 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::{builder::ValueParser, Arg, ArgAction, ArgMatches, Command};
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_prettier::{Prettier, PrettierOptions, TrailingComma};
+use oxc_span::SourceType;
+
+use crate::{
+    runner::{Runner, RunnerOptions},
+    CliRunResult, Walk,
+};
+
+#[derive(Debug)]
+pub struct FormatOptions {
+    pub paths: Vec<PathBuf>,
+    /// Overwrite the files with their formatted output instead of printing it
+    pub write: bool,
+    /// Only list the files which are not formatted
+    pub check: bool,
+    pub prettier: PrettierOptions,
+}
+
+#[allow(clippy::fallible_impl_from)]
+impl<'a> From<&'a ArgMatches> for FormatOptions {
+    fn from(matches: &'a ArgMatches) -> Self {
+        let mut prettier = PrettierOptions::default();
+        if let Some(print_width) = matches.get_one::<usize>("print-width") {
+            prettier.print_width = *print_width;
+        }
+        if let Some(tab_width) = matches.get_one::<usize>("tab-width") {
+            prettier.tab_width = *tab_width;
+        }
+        prettier.use_tabs = matches.get_flag("use-tabs");
+        prettier.single_quote = matches.get_flag("single-quote");
+        if let Some(trailing_comma) = matches.get_one::<String>("trailing-comma") {
+            prettier.trailing_comma = match trailing_comma.as_str() {
+                "es5" => TrailingComma::Es5,
+                "none" => TrailingComma::None,
+                _ => TrailingComma::All,
+            };
+        }
+        Self {
+            paths: matches
+                .get_many::<PathBuf>("path")
+                .map_or_else(Vec::new, |paths| paths.cloned().collect()),
+            write: matches.get_flag("write"),
+            check: matches.get_flag("check"),
+            prettier,
+        }
+    }
+}
+
+impl RunnerOptions for FormatOptions {
+    fn build_args(cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("path")
+                .value_name("PATH")
+                .num_args(1..)
+                .required(true)
+                .value_parser(ValueParser::path_buf())
+                .help("File or Directory paths to format. Directories are formatted recursively."),
+        )
+        .arg(
+            Arg::new("write")
+                .long("write")
+                .short('w')
+                .action(ArgAction::SetTrue)
+                .conflicts_with("check")
+                .help("Edit the files in place instead of printing the formatted code"),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .short('c')
+                .action(ArgAction::SetTrue)
+                .help("List the files which are not formatted, and fail if there is any"),
+        )
+        .arg(
+            Arg::new("print-width")
+                .long("print-width")
+                .value_parser(clap::value_parser!(usize))
+                .help(
+                "The line length which the formatter will try to fit the code in, defaults to 80",
+            ),
+        )
+        .arg(
+            Arg::new("tab-width")
+                .long("tab-width")
+                .value_parser(clap::value_parser!(usize))
+                .help("Number of spaces per indentation level, defaults to 2"),
+        )
+        .arg(
+            Arg::new("use-tabs")
+                .long("use-tabs")
+                .action(ArgAction::SetTrue)
+                .help("Indent with tabs instead of spaces"),
+        )
+        .arg(
+            Arg::new("single-quote")
+                .long("single-quote")
+                .action(ArgAction::SetTrue)
+                .help("Use single quotes instead of double quotes, except in JSX"),
+        )
+        .arg(
+            Arg::new("trailing-comma")
+                .long("trailing-comma")
+                .value_parser(["all", "es5", "none"])
+                .help(
+                    "Print trailing commas wherever possible when multi-line, defaults to \"all\"",
+                ),
+        )
+    }
+}
+
+/// Formats JavaScript and TypeScript files the same way as Prettier
+pub struct FormatRunner {
+    options: FormatOptions,
+}
+
+impl Runner for FormatRunner {
+    type Options = FormatOptions;
+
+    const ABOUT: &'static str = "Format files the same way as Prettier";
+    const NAME: &'static str = "format";
+
+    fn new(options: FormatOptions) -> Self {
+        Self { options }
+    }
+
+    fn run(&self) -> CliRunResult {
+        let now = std::time::Instant::now();
+
+        let paths =
+            self.options.paths.iter().filter(|path| !path.exists()).cloned().collect::<Vec<_>>();
+        if !paths.is_empty() {
+            return CliRunResult::PathNotFound { paths };
+        }
+
+        let mut number_of_files = 0;
+        let mut number_of_unformatted_files = 0;
+        let mut number_of_parse_errors = 0;
+        for path in Walk::source_files(&self.options.paths) {
+            number_of_files += 1;
+            let Ok(source_text) = fs::read_to_string(&path) else {
+                eprintln!("Failed to read {}", path.display());
+                number_of_parse_errors += 1;
+                continue;
+            };
+            let Some(formatted) = self.format(&path, &source_text) else {
+                number_of_parse_errors += 1;
+                continue;
+            };
+            if self.options.check {
+                if formatted != source_text {
+                    println!("{}", path.display());
+                    number_of_unformatted_files += 1;
+                }
+            } else if self.options.write {
+                if formatted != source_text && fs::write(&path, formatted).is_err() {
+                    eprintln!("Failed to write {}", path.display());
+                }
+            } else {
+                print!("{formatted}");
+            }
+        }
+
+        CliRunResult::FormatResult {
+            duration: now.elapsed(),
+            number_of_files,
+            number_of_unformatted_files,
+            number_of_parse_errors,
+        }
+    }
+}
+
+impl FormatRunner {
+    /// The formatted code, or `None` if the file has syntax errors, which are printed
+    fn format(&self, path: &Path, source_text: &str) -> Option<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        if !ret.errors.is_empty() {
+            for error in ret.errors {
+                let error = error.with_source_code(source_text.to_string());
+                eprintln!("{error:?}");
+            }
+            return None;
+        }
+        let program = allocator.alloc(ret.program);
+        Some(Prettier::new(source_text, &ret.trivias, self.options.prettier).build(program))
+    }
+}
@@ -1,51 +1,90 @@
-use std::path::Path;
+use std::{
+    env,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
 
-use git2::Repository;
-use miette::Diagnostic;
-use oxc_diagnostics::{thiserror::Error, Error};
+use git2::{Commit, DiffOptions, Repository, Status, StatusOptions};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+    Error,
+};
+use rustc_hash::FxHashMap;
+
+use crate::Walk;
+
+/// Number of uncommitted files listed in [UncommittedChanges].
+const MAX_LISTED_FILES: usize = 10;
 
 #[derive(Debug, Error, Diagnostic)]
 #[error("No repository found")]
-#[diagnostic(severity(warning), help("Ensure target path(s) belong to a Git repository"))]
+#[diagnostic(help(
+    "Ensure target path(s) belong to a Git repository, or pass --fix-dirty to fix them anyway"
+))]
 struct NoRepositoryFound;
 
 #[derive(Debug, Error, Diagnostic)]
 #[error("Multiple repositories found")]
-#[diagnostic(severity(warning), help("Ensure all paths belong to a single repository"))]
+#[diagnostic(help("Ensure all paths belong to a single repository"))]
 struct MultipleRepositoriesFound;
 
 #[derive(Debug, Error, Diagnostic)]
-#[error("Uncommitted changes")]
-#[diagnostic(severity(warning), help("Commit any changes before linting"))]
-struct UncommittedChanges;
+#[error("Uncommitted changes in:\n{0}")]
+#[diagnostic(help("Commit or stash the changes before fixing, or pass --fix-dirty to fix anyway"))]
+struct UncommittedChanges(String);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Failed to find the changed files: {0}")]
+#[diagnostic(help("--changed takes a branch, tag or commit, e.g. --changed=origin/main"))]
+struct ChangedFilesError(String);
 
-pub struct Git<'a> {
-    // paths: &'a Vec<Box<Path>>,
+/// `--changed` and `--changed=<REF>`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ChangedSince {
+    /// The merge base of `HEAD` and the default branch.
+    DefaultBranch,
+    Ref(String),
+}
+
+/// Checks that the files to be fixed in place are committed, so `--fix` cannot overwrite
+/// changes which cannot be restored.
+pub struct Git {
+    /// The directories walked for the lint paths.
+    paths: Vec<PathBuf>,
     repos: Vec<Repository>,
 }
 
-impl<'a> Git<'a> {
-    pub fn new() -> Self {
-        // let repos: Vec<Repository> =
-        // paths.iter().filter_map(|path| Repository::discover(path).ok()).collect();
-        Self { repos }
+impl Git {
+    pub fn new(paths: &[PathBuf]) -> Self {
+        let paths = paths
+            .iter()
+            .map(|path| Walk::glob_base(path).unwrap_or_else(|| path.clone()))
+            .collect::<Vec<_>>();
+        let repos = paths.iter().filter_map(|path| Repository::discover(path).ok()).collect();
+        Self { paths, repos }
     }
 
-    pub fn verify(&'a self) -> Result<&'a Repository, Error> {
-        if self.repos.is_empty() {
+    /// # Errors
+    ///
+    /// * A path is not inside a Git repository
+    /// * The paths belong to different repositories
+    /// * A file inside the paths is modified or untracked
+    pub fn verify(&self) -> Result<(), Error> {
+        if self.repos.is_empty() || self.repos.len() < self.paths.len() {
             return Err(NoRepositoryFound.into());
         }
-        match self.is_same_repo() {
-            Ok(repo) => {
-                for path in self.paths {
-                    if Self::is_uncommitted(repo, path) {
-                        return Err(UncommittedChanges.into());
-                    }
-                }
-                Ok(repo)
-            }
-            err => err,
+        let repo = self.is_same_repo()?;
+        let files = self.uncommitted_files(repo);
+        if files.is_empty() {
+            return Ok(());
         }
+        let mut list =
+            files.iter().take(MAX_LISTED_FILES).map(|file| format!("  {file}")).collect::<Vec<_>>();
+        if files.len() > MAX_LISTED_FILES {
+            list.push(format!("  and {} more", files.len() - MAX_LISTED_FILES));
+        }
+        Err(UncommittedChanges(list.join("\n")).into())
     }
 
     /// Given a list of repositories, verify they're all the same repository.
@@ -60,7 +99,147 @@ impl<'a> Git<'a> {
         Ok(first_repo)
     }
 
-    fn is_uncommitted(repo: &Repository, path: &Path) -> bool {
-        repo.status_file(path).map_or(true, |status| !matches!(status, git2::Status::CURRENT))
+    /// Modified, staged and untracked files inside the paths, relative to the repository root.
+    /// Untracked files count as uncommitted because git cannot restore them.
+    fn uncommitted_files(&self, repo: &Repository) -> Vec<String> {
+        let Some(workdir) = repo.workdir().and_then(|dir| dir.canonicalize().ok()) else {
+            return vec![];
+        };
+        let mut options = StatusOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true).include_ignored(false);
+        // A path at the repository root matches all files, so no pathspec is needed.
+        let pathspecs = self
+            .paths
+            .iter()
+            .map(|path| Self::pathspec(&workdir, path))
+            .collect::<Option<Vec<_>>>();
+        for pathspec in pathspecs.unwrap_or_default() {
+            options.pathspec(pathspec);
+        }
+        let Ok(statuses) = repo.statuses(Some(&mut options)) else { return vec![] };
+        statuses
+            .iter()
+            .filter(|entry| entry.status() != Status::CURRENT && !entry.status().is_ignored())
+            .filter_map(|entry| entry.path().map(ToString::to_string))
+            .collect()
+    }
+
+    /// `path` relative to the repository root, `None` for the root itself which matches all files.
+    fn pathspec(workdir: &Path, path: &Path) -> Option<String> {
+        let path = path.canonicalize().ok()?;
+        let relative = path.strip_prefix(workdir).ok()?;
+        if relative.as_os_str().is_empty() {
+            return None;
+        }
+        Some(relative.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+/// The files changed since a commit, including staged, unstaged and untracked changes,
+/// with the line numbers of the added or modified lines.
+#[derive(Debug)]
+pub struct ChangedFiles {
+    cwd: PathBuf,
+    /// Keyed by absolute path.
+    lines: FxHashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedFiles {
+    /// # Errors
+    ///
+    /// * The current working directory is not inside a Git repository
+    /// * The ref or the default branch cannot be found
+    pub fn new(since: &ChangedSince) -> Result<Self, Error> {
+        let git_error = |err: git2::Error| ChangedFilesError(err.message().to_string());
+        let cwd = env::current_dir().and_then(|cwd| cwd.canonicalize()).map_err(|err| {
+            ChangedFilesError(format!("cannot access the current directory: {err}"))
+        })?;
+        let repo = Repository::discover(&cwd).map_err(|_| NoRepositoryFound)?;
+        let workdir =
+            repo.workdir().and_then(|dir| dir.canonicalize().ok()).ok_or(NoRepositoryFound)?;
+        let tree = Self::base_commit(&repo, since)?.tree().map_err(git_error)?;
+
+        let mut options = DiffOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true)
+            .context_lines(0);
+        let diff = repo
+            .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+            .map_err(git_error)?;
+
+        let mut lines: FxHashMap<PathBuf, Vec<RangeInclusive<usize>>> = FxHashMap::default();
+        // Files with deletions only have no added lines to report on, so they are skipped.
+        diff.foreach(
+            &mut |_, _| true,
+            None,
+            Some(&mut |delta, hunk| {
+                if let (Some(path), true) = (delta.new_file().path(), hunk.new_lines() > 0) {
+                    let start = hunk.new_start() as usize;
+                    let end = start + hunk.new_lines() as usize - 1;
+                    lines.entry(workdir.join(path)).or_default().push(start..=end);
+                }
+                true
+            }),
+            None,
+        )
+        .map_err(git_error)?;
+
+        Ok(Self { cwd, lines })
+    }
+
+    fn base_commit<'a>(repo: &'a Repository, since: &ChangedSince) -> Result<Commit<'a>, Error> {
+        let find_commit =
+            |name: &str| repo.revparse_single(name).and_then(|object| object.peel_to_commit());
+        match since {
+            ChangedSince::Ref(name) => find_commit(name)
+                .map_err(|err| ChangedFilesError(format!("`{name}`: {}", err.message())).into()),
+            ChangedSince::DefaultBranch => {
+                let default_branch = [
+                    "refs/remotes/origin/HEAD",
+                    "refs/heads/main",
+                    "refs/heads/master",
+                    "refs/remotes/origin/main",
+                    "refs/remotes/origin/master",
+                ]
+                .iter()
+                .find_map(|name| find_commit(name).ok())
+                .ok_or_else(|| ChangedFilesError("no default branch found".into()))?;
+                let head = find_commit("HEAD")
+                    .map_err(|err| ChangedFilesError(err.message().to_string()))?;
+                let merge_base = repo
+                    .merge_base(head.id(), default_branch.id())
+                    .and_then(|oid| repo.find_commit(oid))
+                    .map_err(|err| ChangedFilesError(err.message().to_string()))?;
+                Ok(merge_base)
+            }
+        }
+    }
+
+    /// The changed line ranges of `path`, or `None` if the file is not changed.
+    fn changed_lines(&self, path: &Path) -> Option<&[RangeInclusive<usize>]> {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.lines.get(&self.cwd.join(path)).map(Vec::as_slice)
+    }
+
+    pub fn is_changed_file(&self, path: &Path) -> bool {
+        self.changed_lines(path).is_some()
+    }
+
+    /// Whether a label of `diagnostic` points to a changed line.
+    /// Diagnostics without labels apply to the whole file and are always reported.
+    pub fn is_changed_diagnostic(&self, path: &Path, diagnostic: &dyn Diagnostic) -> bool {
+        let Some(lines) = self.changed_lines(path) else { return false };
+        let (Some(source), Some(mut labels)) = (diagnostic.source_code(), diagnostic.labels())
+        else {
+            return true;
+        };
+        labels.any(|label| {
+            source.read_span(label.inner(), 0, 0).map_or(false, |span| {
+                let line = span.line() + 1;
+                lines.iter().any(|range| range.contains(&line))
+            })
+        })
     }
 }
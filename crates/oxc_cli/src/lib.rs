@@ -1,14 +1,18 @@
-// mod git;
+mod format;
+mod git;
 mod lint;
 mod runner;
+mod stats;
 mod type_check;
 mod walk;
 
 use clap::{Arg, Command};
 
 pub use crate::{
-    lint::{LintOptions, LintRunner},
-    runner::{CliRunResult, Runner, RunnerOptions},
+    format::{FormatOptions, FormatRunner},
+    lint::{doctor, generate_completions, BaselineAction, LintOptions, LintRunner},
+    runner::{exit_code, install_panic_hook, CliRunResult, Runner, RunnerOptions},
+    stats::{StatsOptions, StatsRunner},
     type_check::{TypeCheckOptions, TypeCheckRunner},
     walk::Walk,
 };
@@ -23,6 +27,8 @@ pub fn command() -> Command {
         .arg_required_else_help(true)
         .subcommand(LintRunner::command())
         .subcommand(TypeCheckRunner::command())
+        .subcommand(FormatRunner::command())
+        .subcommand(StatsRunner::command())
         .arg(
             Arg::new("threads")
                 .long("threads")
@@ -0,0 +1,145 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use rustc_hash::FxHashMap;
+
+use super::error::{ErrorWithPath, Result};
+
+/// The baseline file read from the current working directory unless `--baseline` is given.
+pub const BASELINE_FILE_NAME: &str = ".oxlint-baseline.json";
+
+/// `oxlint baseline create` and `oxlint baseline prune`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BaselineAction {
+    /// Record all current findings.
+    Create,
+    /// Drop the findings of the baseline which are no longer reported.
+    Prune,
+}
+
+/// Known findings which are not reported, e.g.
+///
+/// ```json
+/// { "src/index.js": { "no-debugger": 2 } }
+/// ```
+///
+/// Findings are counted per file and rule rather than recorded by position,
+/// so that they remain suppressed when the code around them changes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Baseline {
+    files: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl Baseline {
+    /// Returns `None` when the baseline file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// * The baseline file cannot be read or parsed
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(path).with_path(path)?;
+        let files = serde_json::from_str(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            .with_path(path)?;
+        Ok(Some(Self { files }))
+    }
+
+    /// # Errors
+    ///
+    /// * The baseline file cannot be written
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let mut text = serde_json::to_string_pretty(&self.files).unwrap();
+        text.push('\n');
+        fs::write(path, text).with_path(path)
+    }
+
+    /// Baseline paths are relative to the current working directory, with `/` as separator.
+    pub fn key(path: &Path) -> String {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        path.components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// The rule a finding is counted under: the name of the rule reporting it, or `syntax` for
+    /// parser and semantic errors.
+    pub fn rule(rule_name: Option<&str>) -> &str {
+        rule_name.unwrap_or("syntax")
+    }
+
+    pub fn add(&mut self, key: &str, rule: &str) {
+        *self.files.entry(key.to_string()).or_default().entry(rule.to_string()).or_default() += 1;
+    }
+
+    /// The number of findings of each rule suppressed in the file.
+    pub fn allowances(&self, key: &str) -> FxHashMap<String, usize> {
+        self.files
+            .get(key)
+            .map(|rules| rules.iter().map(|(rule, count)| (rule.clone(), *count)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Keep at most the number of `current` findings of each file and rule.
+    #[must_use]
+    pub fn prune(&self, current: &Self) -> Self {
+        let files = self
+            .files
+            .iter()
+            .filter_map(|(key, rules)| {
+                let current = current.files.get(key)?;
+                let rules = rules
+                    .iter()
+                    .filter_map(|(rule, count)| {
+                        let count = (*count).min(*current.get(rule)?);
+                        (count > 0).then(|| (rule.clone(), count))
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                (!rules.is_empty()).then(|| (key.clone(), rules))
+            })
+            .collect();
+        Self { files }
+    }
+
+    pub fn number_of_files(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn number_of_findings(&self) -> usize {
+        self.files.values().flat_map(BTreeMap::values).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::Baseline;
+
+    #[test]
+    fn key() {
+        assert_eq!(Baseline::key(Path::new("./src/index.js")), "src/index.js");
+        assert_eq!(Baseline::key(Path::new("src/index.js")), "src/index.js");
+    }
+
+    #[test]
+    fn prune() {
+        let mut baseline = Baseline::default();
+        baseline.add("a.js", "no-debugger");
+        baseline.add("a.js", "no-debugger");
+        baseline.add("a.js", "no-empty");
+        baseline.add("b.js", "no-debugger");
+
+        let mut current = Baseline::default();
+        current.add("a.js", "no-debugger");
+        current.add("a.js", "eqeqeq");
+
+        let mut expected = Baseline::default();
+        expected.add("a.js", "no-debugger");
+        assert_eq!(baseline.prune(&current), expected);
+        assert_eq!(expected.number_of_findings(), 1);
+        assert_eq!(expected.number_of_files(), 1);
+    }
+}
@@ -1,14 +1,23 @@
-use clap::{builder::ValueParser, Arg, ArgAction, Command};
+use clap::{
+    builder::{PossibleValuesParser, ValueParser},
+    Arg, ArgAction, Command,
+};
+use oxc_linter::{RuleEnum, RULES};
 
+use crate::walk::Shard;
+
+#[allow(clippy::too_many_lines)]
 pub(super) fn lint_command(command: Command) -> Command {
     command
             .arg_required_else_help(true)
             .after_help(
                 "# Rule Selection
 
-To allow or deny a rule, multiple -A <NAME> or -D <NAME>.
+To allow, warn or deny a rule, multiple -A <NAME>, -W <NAME> or -D <NAME>.
+Flags are applied in order, so later flags override earlier ones.
+Warned rules report warnings and denied rules report errors.
 
-For example: -D correctness -A no-debugger.
+For example: -D correctness -W no-debugger -A no-empty.
 
 The categories are:
   * correctness - code that is outright wrong or useless
@@ -17,6 +26,23 @@ The categories are:
 
 The default category is -D correctness.
 
+# Configuration File
+
+Rules can also be configured in a .oxlintrc.json file in the current working directory,
+or in the file given by --config. The -A, -W and -D flags are applied after the config file.
+
+{
+  \"plugins\": [\"eslint\", \"typescript\"],
+  \"categories\": { \"correctness\": \"deny\", \"suspicious\": \"warn\" },
+  \"rules\": { \"no-debugger\": \"allow\", \"eqeqeq\": [\"deny\", \"smart\"] }
+}
+
+Configs can extend other configs with \"extends\": [\"./base.json\"], and configure rules for a
+subset of files with \"overrides\": [{ \"files\": [\"*.test.ts\"], \"rules\": { ... } }].
+
+Run --init to generate a starter configuration for the current project,
+and --print-config <PATH> to show the rules that apply to a file.
+
 # Profile Rule Performance
 
 Setting the TIMING environment variable will display the execution time of each rule.
@@ -27,9 +53,17 @@ TIMING=1 \"the lint command\"
                 Arg::new("path")
                     .value_name("PATH")
                     .num_args(1..)
-                    .required_unless_present("rules")
+                    .required_unless_present_any(["rules", "init", "print-config"])
                     .value_parser(ValueParser::path_buf())
-                    .help("File or Directory paths to scan. Directories are scanned recursively.")
+                    .help("File or Directory paths to scan. Directories are scanned recursively. Glob patterns such as \"src/**/*.ts\" are also accepted, quote them to prevent shell expansion. Extglobs such as \"@(a|b)\" are not supported. Give the paths after -- when the first one is named like a subcommand, such as \"doctor\".")
+            )
+            .arg(
+                Arg::new("config")
+                .long("config")
+                .short('c')
+                .required(false)
+                .value_parser(ValueParser::path_buf())
+                .help("Use this configuration file instead of .oxlintrc.json")
             )
             .arg(
                 Arg::new("allow")
@@ -39,6 +73,14 @@ TIMING=1 \"the lint command\"
                 .action(ArgAction::Append)
                 .help("Allow a rule or a category")
             )
+            .arg(
+                Arg::new("warn")
+                .long("warn")
+                .short('W')
+                .required(false)
+                .action(ArgAction::Append)
+                .help("Warn on a rule or a category")
+            )
             .arg(
                 Arg::new("deny")
                 .long("deny")
@@ -47,6 +89,15 @@ TIMING=1 \"the lint command\"
                 .action(ArgAction::Append)
                 .help("Deny a rule or a category")
             )
+            .arg(
+                Arg::new("rule")
+                .long("rule")
+                .required(false)
+                .action(ArgAction::Append)
+                .value_parser(PossibleValuesParser::new(RULES.iter().map(RuleEnum::name)))
+                .hide_possible_values(true)
+                .help("Only run this rule, even if it is not enabled. Can be repeated. The rules are denied unless they are enabled as warnings.")
+            )
             .arg(
                 Arg::new("fix")
                 .long("fix")
@@ -54,6 +105,43 @@ TIMING=1 \"the lint command\"
                 .action(ArgAction::SetTrue)
                 .help("This option allows you to enable oxc to fix as many issues as possible. If enabled, only unfixed issues are reported in the output")
             )
+            .arg(
+                Arg::new("fix-dry-run")
+                .long("fix-dry-run")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("fix")
+                .help("This option has the same effect as --fix with one difference: the fixes are not saved to the file system. Instead, the fixes are printed to stdout as unified diffs.")
+            )
+            .arg(
+                Arg::new("changed")
+                .long("changed")
+                .required(false)
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("")
+                .value_name("REF")
+                .help("Only lint the files changed since REF, and only report the diagnostics on the changed lines. Staged, unstaged and untracked changes are included. Defaults to the merge base with the default branch.")
+            )
+            .arg(
+                Arg::new("fix-dirty")
+                .long("fix-dirty")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .help("Fix files even if they have uncommitted changes or are not inside a Git repository. By default, fixes are only written to committed files so they can be reviewed and reverted.")
+            )
+            .arg(
+                Arg::new("fix-suggestions")
+                .long("fix-suggestions")
+                .value_name("RULES")
+                .required(false)
+                .num_args(0..=1)
+                .require_equals(true)
+                .value_delimiter(',')
+                .default_missing_value("all")
+                .action(ArgAction::Append)
+                .help("Apply suggestions in addition to safe fixes. Suggestions may change the semantics of your code. Optionally restrict them to a comma separated list of rules, e.g. --fix-suggestions=no-return-await")
+            )
             .arg(
               Arg::new("quiet")
                 .long("quiet")
@@ -61,19 +149,27 @@ TIMING=1 \"the lint command\"
                 .action(ArgAction::SetTrue)
                 .help("This option allows you to disable reporting on warnings. If you enable this option, only errors are reported by oxc_lint.")
             )
+            .arg(
+                Arg::new("shard")
+                .long("shard")
+                .required(false)
+                .value_name("INDEX/COUNT")
+                .value_parser(Shard::parse)
+                .help("Split the files into COUNT shards by directory and only lint the INDEX-th one, starting at 1, e.g. --shard 2/8. The split is the same on every machine, so CI jobs can lint the shards in parallel.")
+            )
             .arg(
                 Arg::new("ignore-path")
                 .long("ignore-path")
                 .required(false)
                 .value_parser(ValueParser::path_buf())
-                .help("This option allows you to specify the file to use as your .eslintignore.")
+                .help("This option allows you to specify the file to use as your .eslintignore. Files ignored by .gitignore are always excluded.")
             )
             .arg(
                 Arg::new("no-ignore")
                 .long("no-ignore")
                 .required(false)
                 .action(ArgAction::SetTrue)
-                .help("Disables excluding of files from .eslintignore files, --ignore-path flags, --ignore-pattern flags.")
+                .help("Disables excluding of files from .gitignore and .eslintignore files, --ignore-path flags, --ignore-pattern flags.")
             )
             .arg(
                 Arg::new("ignore-pattern")
@@ -90,8 +186,98 @@ TIMING=1 \"the lint command\"
                   .required(false)
                   .help("This option allows you to specify a warning threshold, which can be used to force oxc_lint to exit with an error status if there are too many warning-level rule violations in your project.")
               )
+              .arg(
+                Arg::new("max-parse-errors")
+                  .long("max-parse-errors")
+                  .value_parser(clap::value_parser!(usize))
+                  .required(false)
+                  .help("Keep parsing after syntax errors to report up to this many syntax errors per file, instead of stopping at the first unrecoverable one.")
+              )
+              .arg(
+                Arg::new("isolated-modules")
+                  .long("isolated-modules")
+                  .required(false)
+                  .action(ArgAction::SetTrue)
+                  .help("Report the TypeScript errors of `tsc --isolatedModules`, such as re-exporting a type without `export type`, which break tools that transpile one file at a time.")
+              )
+              .arg(
+                Arg::new("baseline")
+                  .long("baseline")
+                  .required(false)
+                  .value_parser(ValueParser::path_buf())
+                  .help("Path of the baseline file, whose known findings are not reported. Defaults to .oxlint-baseline.json, create it with `oxlint baseline create`.")
+              )
+              .arg(
+                Arg::new("statistics")
+                  .long("statistics")
+                  .required(false)
+                  .action(ArgAction::SetTrue)
+                  .help("Print the number of violations and fixable violations per rule, the files with the most violations and the total time after the run.")
+              )
+              .arg(
+                Arg::new("no-progress")
+                  .long("no-progress")
+                  .required(false)
+                  .action(ArgAction::SetTrue)
+                  .help("Do not show the progress bar, which is only shown on stderr when it is a terminal.")
+              )
+              .arg(
+                Arg::new("output-file")
+                  .long("output-file")
+                  .short('o')
+                  .required(false)
+                  .value_parser(ValueParser::path_buf())
+                  .help("Write the lint report to this file instead of stdout. The summary is still printed on stderr.")
+              )
+              .arg(
+                Arg::new("exit-zero-even-if-error")
+                  .long("exit-zero-even-if-error")
+                  .required(false)
+                  .action(ArgAction::SetTrue)
+                  .help("Report lint violations but always exit with status 0. Failures of the tool itself, such as a missing path or an invalid config, still exit with status 2.")
+              )
               .arg(
                 Arg::new("rules")
                     .long("rules")
                     .required(false).action(ArgAction::SetTrue).help("This option allows you to list all the rules that are currently registered."))
+            .arg(
+                Arg::new("category")
+                .long("category")
+                .required(false)
+                .requires("rules")
+                .value_parser(["correctness", "suspicious", "pedantic", "style", "restriction", "nursery"])
+                .help("Only list the rules of this category, used with --rules")
+            )
+            .arg(
+                Arg::new("fixable")
+                .long("fixable")
+                .required(false)
+                .requires("rules")
+                .action(ArgAction::SetTrue)
+                .help("Only list the rules which provide fixes or suggestions, used with --rules")
+            )
+            .arg(
+                Arg::new("format")
+                .long("format")
+                .required(false)
+                .value_parser(["default", "json"])
+                .help("Output format of the diagnostics and of --rules. With json, the diagnostics are printed as an array sorted by file, so the reports of --shard runs can be merged by concatenating them")
+            )
+            .arg(
+                Arg::new("init")
+                .long("init")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("path")
+                .help("Inspect the project in the current working directory and write a starter .oxlintrc.json")
+            )
+            .arg(
+                Arg::new("print-config")
+                .long("print-config")
+                .value_name("PATH")
+                .required(false)
+                .conflicts_with("path")
+                .value_parser(ValueParser::path_buf())
+                .help("Print the configuration that applies to the given file")
+            )
 }
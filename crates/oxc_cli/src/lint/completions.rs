@@ -0,0 +1,41 @@
+use std::io::Write;
+
+use clap::{builder::PossibleValuesParser, Command};
+use clap_complete::{generate, Shell};
+use oxc_linter::{RuleEnum, RULES};
+
+/// Values accepted by -A / -W / -D besides the rule names.
+const CATEGORIES: [&str; 7] =
+    ["all", "correctness", "suspicious", "pedantic", "style", "restriction", "nursery"];
+
+/// Write the completion script of `shell` for `command`.
+/// The values of -A / -W / -D complete to the categories and the names of all registered rules.
+pub fn generate_completions(command: Command, shell: Shell, buf: &mut dyn Write) {
+    let values = CATEGORIES.into_iter().chain(RULES.iter().map(RuleEnum::name)).collect::<Vec<_>>();
+    // The possible values are only used for the completions, the arguments are not restricted
+    // when linting so that unknown rule names are reported by the linter.
+    let mut command = ["allow", "warn", "deny"].into_iter().fold(command, |command, id| {
+        command.mut_arg(id, |arg| arg.value_parser(PossibleValuesParser::new(values.clone())))
+    });
+    let name = command.get_name().to_string();
+    generate(shell, &mut command, name, buf);
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Command;
+    use clap_complete::Shell;
+
+    use super::generate_completions;
+    use crate::lint::command::lint_command;
+
+    #[test]
+    fn rule_names() {
+        let mut buf = vec![];
+        generate_completions(lint_command(Command::new("oxlint")), Shell::Bash, &mut buf);
+        let script = String::from_utf8(buf).unwrap();
+        assert!(script.contains("oxlint"));
+        assert!(script.contains("no-debugger"));
+        assert!(script.contains("correctness"));
+    }
+}
@@ -0,0 +1,544 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use miette::NamedSource;
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_json::{JsonKind, JsonParser, JsonProperty, JsonValue, JsonValueKind};
+use oxc_linter::Environment;
+use oxc_span::{GetSpan, Span};
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+
+use super::options::AllowWarnDeny;
+
+/// The configuration file looked up in the current working directory.
+pub const CONFIG_FILE_NAME: &str = ".oxlintrc.json";
+
+/// Configuration read from a `.oxlintrc.json` file, e.g.
+///
+/// ```json
+/// {
+///   "extends": ["./base.json"],
+///   "plugins": ["eslint", "typescript"],
+///   "env": { "browser": true },
+///   "categories": { "correctness": "deny", "suspicious": "warn" },
+///   "rules": { "no-debugger": "allow", "eqeqeq": ["deny", "smart"] },
+///   "overrides": [{ "files": ["*.test.ts"], "rules": { "no-empty": "allow" } }]
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct LintConfig {
+    /// Enabled plugins, all plugins are enabled when not specified.
+    pub plugins: Option<Vec<String>>,
+    /// Environments whose global variables are defined, ECMAScript intrinsics are always defined.
+    pub env: Environment,
+    /// Categories and rules in the order they are applied, same as the `-A` / `-W` / `-D` flags.
+    /// Categories are applied before rules.
+    pub rules: Vec<(AllowWarnDeny, String)>,
+    /// Rule options, i.e. everything after the level in `"eqeqeq": ["deny", "smart"]`.
+    pub rule_options: FxHashMap<String, Value>,
+    /// Rules applied on top of `rules` for the files matching their patterns, in order.
+    pub overrides: Vec<ConfigOverride>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigOverride {
+    /// Glob patterns relative to the current working directory.
+    /// Patterns without a `/` match the file name in any directory, e.g. `*.test.ts`.
+    pub files: Vec<String>,
+    globs: GlobSet,
+    pub rules: Vec<(AllowWarnDeny, String)>,
+    pub rule_options: FxHashMap<String, Value>,
+}
+
+impl ConfigOverride {
+    fn is_match(&self, path: &Path) -> bool {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.globs.is_match(path)
+    }
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum ConfigError {
+    #[error("Failed to read config file {0:?}: {1}")]
+    Read(PathBuf, std::io::Error),
+    #[error("Failed to parse config file {path:?}: {message}")]
+    #[diagnostic(help(
+        "The config file must be valid JSON, comments and trailing commas are allowed"
+    ))]
+    Parse {
+        path: PathBuf,
+        message: &'static str,
+        #[source_code]
+        source_code: NamedSource,
+        #[label]
+        span: Span,
+    },
+    #[error("Invalid config file {path:?}: {message}")]
+    #[diagnostic(help("See `oxlint --help` for the config file format"))]
+    Invalid {
+        path: PathBuf,
+        message: String,
+        #[source_code]
+        source_code: NamedSource,
+        #[label]
+        span: Span,
+    },
+}
+
+/// An invalid config value, labeled by its span in the config file.
+type InvalidConfig = (String, Span);
+
+impl LintConfig {
+    /// Find the config file from `--config`, or `.oxlintrc.json` in the current working directory.
+    ///
+    /// # Errors
+    ///
+    /// * The config file given by `--config` does not exist
+    /// * The config file cannot be read or parsed
+    pub fn find(config: Option<&Path>) -> Result<Option<(PathBuf, Self)>, ConfigError> {
+        let path = if let Some(path) = config {
+            path.to_path_buf()
+        } else {
+            let path = PathBuf::from(CONFIG_FILE_NAME);
+            if !path.is_file() {
+                return Ok(None);
+            }
+            path
+        };
+        let config = Self::from_file(&path)?;
+        Ok(Some((path, config)))
+    }
+
+    /// # Errors
+    ///
+    /// * The config file or one of the config files it extends cannot be read or parsed
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        Self::from_file_impl(path, &mut vec![])
+    }
+
+    /// `visited` holds the config files being loaded to detect circular `extends`.
+    fn from_file_impl(path: &Path, visited: &mut Vec<PathBuf>) -> Result<Self, ConfigError> {
+        let text =
+            fs::read_to_string(path).map_err(|err| ConfigError::Read(path.to_path_buf(), err))?;
+        let source_code = || NamedSource::new(path.to_string_lossy(), text.clone());
+        let invalid = |(message, span): InvalidConfig| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            message,
+            source_code: source_code(),
+            span,
+        };
+        let value = JsonParser::new(&text, JsonKind::from_path(path)).parse().map_err(|err| {
+            ConfigError::Parse {
+                path: path.to_path_buf(),
+                message: err.0,
+                source_code: source_code(),
+                span: err.1,
+            }
+        })?;
+        let config = Self::from_value(&value).map_err(invalid)?;
+
+        let extends = match value.get("extends") {
+            None => vec![],
+            Some(extends) => match &extends.kind {
+                JsonValueKind::String(extends) => vec![extends.as_str()],
+                JsonValueKind::Array(paths) => paths.iter().filter_map(JsonValue::as_str).collect(),
+                _ => {
+                    return Err(invalid((
+                        format!(
+                            "expected `extends` to be a path or a list of paths, found {}",
+                            extends.type_name()
+                        ),
+                        extends.span,
+                    )))
+                }
+            },
+        };
+        if extends.is_empty() {
+            return Ok(config);
+        }
+
+        let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if visited.contains(&canonical_path) {
+            let span = value.get("extends").map_or(value.span, GetSpan::span);
+            return Err(invalid(("circular `extends`".into(), span)));
+        }
+        visited.push(canonical_path);
+
+        // Paths in `extends` are relative to the config file that declares them.
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut base = Self::default();
+        for extends in extends {
+            base = Self::from_file_impl(&dir.join(extends), visited)?.extend(base);
+        }
+        visited.pop();
+
+        Ok(config.extend(base))
+    }
+
+    /// Apply `self` on top of `base`.
+    fn extend(self, base: Self) -> Self {
+        let mut rules = base.rules;
+        rules.extend(self.rules);
+        let mut rule_options = base.rule_options;
+        rule_options.extend(self.rule_options);
+        let mut overrides = base.overrides;
+        overrides.extend(self.overrides);
+        Self {
+            plugins: self.plugins.or(base.plugins),
+            env: self.env | base.env,
+            rules,
+            rule_options,
+            overrides,
+        }
+    }
+
+    /// Indices of the overrides matching `path`.
+    pub fn matching_overrides(&self, path: &Path) -> Vec<usize> {
+        self.overrides
+            .iter()
+            .enumerate()
+            .filter(|(_, r#override)| r#override.is_match(path))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The configuration for files matching the given overrides.
+    pub fn with_overrides(&self, overrides: &[usize]) -> Self {
+        let mut config = Self { overrides: vec![], ..self.clone() };
+        for r#override in overrides.iter().map(|i| &self.overrides[*i]) {
+            config.rules.extend(r#override.rules.iter().cloned());
+            config
+                .rule_options
+                .extend(r#override.rule_options.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        config
+    }
+
+    /// The configuration that applies to `path`.
+    pub fn resolve(&self, path: &Path) -> Self {
+        self.with_overrides(&self.matching_overrides(path))
+    }
+
+    pub(super) fn from_value(value: &JsonValue) -> Result<Self, InvalidConfig> {
+        if value.as_object().is_none() {
+            return Err((format!("expected an object, found {}", value.type_name()), value.span));
+        }
+
+        let plugins = match value.get("plugins") {
+            None => None,
+            Some(plugins) => {
+                let expected = |value: &JsonValue| {
+                    (
+                        format!(
+                            "expected `plugins` to be a list of strings, found {}",
+                            value.type_name()
+                        ),
+                        value.span,
+                    )
+                };
+                let Some(plugins) = plugins.as_array() else {
+                    return Err(expected(plugins));
+                };
+                Some(
+                    plugins
+                        .iter()
+                        .map(|plugin| {
+                            plugin.as_str().map(ToString::to_string).ok_or_else(|| expected(plugin))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            }
+        };
+
+        let env = Self::parse_env(value)?;
+
+        let (rules, rule_options) = Self::parse_rules(value)?;
+
+        let overrides = match value.get("overrides") {
+            None => vec![],
+            Some(overrides) => {
+                let Some(overrides) = overrides.as_array() else {
+                    return Err((
+                        format!(
+                            "expected `overrides` to be a list, found {}",
+                            overrides.type_name()
+                        ),
+                        overrides.span,
+                    ));
+                };
+                overrides.iter().map(Self::parse_override).collect::<Result<Vec<_>, _>>()?
+            }
+        };
+
+        Ok(Self { plugins, env, rules, rule_options, overrides })
+    }
+
+    /// Parse the `env` field, e.g. `{ "browser": true, "node": false }`.
+    /// Environments without known globals, such as `jest`, are ignored.
+    fn parse_env(value: &JsonValue) -> Result<Environment, InvalidConfig> {
+        let mut env = Environment::default();
+        let Some(envs) = value.get("env") else { return Ok(env) };
+        let Some(envs) = envs.as_object() else {
+            return Err((
+                format!("expected `env` to be an object, found {}", envs.type_name()),
+                envs.span,
+            ));
+        };
+        for JsonProperty { key: name, value: enabled, .. } in envs {
+            let Some(enabled) = enabled.as_bool() else {
+                return Err((format!("expected a boolean for environment `{name}`"), enabled.span));
+            };
+            if let Some(environment) = Environment::from_env_name(name).filter(|_| enabled) {
+                env |= environment;
+            }
+        }
+        Ok(env)
+    }
+
+    fn parse_override(value: &JsonValue) -> Result<ConfigOverride, InvalidConfig> {
+        if value.as_object().is_none() {
+            return Err((
+                format!("expected an override to be an object, found {}", value.type_name()),
+                value.span,
+            ));
+        }
+        let files = match value.get("files").map(|files| (files, &files.kind)) {
+            Some((_, JsonValueKind::String(file))) => vec![file.clone()],
+            Some((_, JsonValueKind::Array(files))) => {
+                files.iter().filter_map(JsonValue::as_str).map(ToString::to_string).collect()
+            }
+            files => {
+                let span = files.map_or(value.span, |(files, _)| files.span);
+                return Err((
+                    "expected `files` in override to be a list of glob patterns".into(),
+                    span,
+                ));
+            }
+        };
+        let mut globs = GlobSetBuilder::new();
+        for file in &files {
+            let pattern = if file.contains('/') {
+                file.strip_prefix("./").unwrap_or(file).to_string()
+            } else {
+                format!("**/{file}")
+            };
+            let glob =
+                GlobBuilder::new(&pattern).literal_separator(true).build().map_err(|err| {
+                    let span = value.get("files").map_or(value.span, GetSpan::span);
+                    (format!("invalid pattern `{file}` in override: {err}"), span)
+                })?;
+            globs.add(glob);
+        }
+        let globs = globs.build().map_err(|err| (err.to_string(), value.span))?;
+        let (rules, rule_options) = Self::parse_rules(value)?;
+        Ok(ConfigOverride { files, globs, rules, rule_options })
+    }
+
+    /// Parse the `categories` and `rules` fields.
+    #[allow(clippy::type_complexity)]
+    fn parse_rules(
+        value: &JsonValue,
+    ) -> Result<(Vec<(AllowWarnDeny, String)>, FxHashMap<String, Value>), InvalidConfig> {
+        let mut rules = vec![];
+        let mut rule_options = FxHashMap::default();
+
+        if let Some(categories) = value.get("categories") {
+            let Some(categories) = categories.as_object() else {
+                return Err((
+                    format!(
+                        "expected `categories` to be an object, found {}",
+                        categories.type_name()
+                    ),
+                    categories.span,
+                ));
+            };
+            for JsonProperty { key: category, value: level, .. } in categories {
+                rules.push((Self::parse_level(category, level)?, category.clone()));
+            }
+            // `all` is applied first so it can be refined by the other categories.
+            rules.sort_by_key(|(_, category)| category != "all");
+        }
+
+        if let Some(rules_config) = value.get("rules") {
+            let Some(rules_config) = rules_config.as_object() else {
+                return Err((
+                    format!("expected `rules` to be an object, found {}", rules_config.type_name()),
+                    rules_config.span,
+                ));
+            };
+            for JsonProperty { key: name, value: config, .. } in rules_config {
+                let level = match &config.kind {
+                    JsonValueKind::Array(config_values) => {
+                        let Some(level) = config_values.first() else {
+                            return Err((format!("missing level for rule `{name}`"), config.span));
+                        };
+                        if config_values.len() > 1 {
+                            let options = config_values[1..].iter().map(to_serde_json).collect();
+                            rule_options.insert(name.clone(), Value::Array(options));
+                        }
+                        level
+                    }
+                    _ => config,
+                };
+                rules.push((Self::parse_level(name, level)?, name.clone()));
+            }
+        }
+
+        Ok((rules, rule_options))
+    }
+
+    /// Accepts the levels of the `-A` / `-W` / `-D` flags as well as ESLint's levels.
+    fn parse_level(name: &str, level: &JsonValue) -> Result<AllowWarnDeny, InvalidConfig> {
+        let parsed = match &level.kind {
+            JsonValueKind::String(s) => match s.as_str() {
+                "allow" | "off" => Some(AllowWarnDeny::Allow),
+                "warn" => Some(AllowWarnDeny::Warn),
+                "deny" | "error" => Some(AllowWarnDeny::Deny),
+                _ => None,
+            },
+            JsonValueKind::Number(_) => match level.as_u64() {
+                Some(0) => Some(AllowWarnDeny::Allow),
+                Some(1) => Some(AllowWarnDeny::Warn),
+                Some(2) => Some(AllowWarnDeny::Deny),
+                _ => None,
+            },
+            _ => None,
+        };
+        parsed.ok_or_else(|| (format!("invalid level for `{name}`"), level.span))
+    }
+
+    pub fn is_plugin_enabled(&self, plugin_name: &str) -> bool {
+        self.plugins.as_ref().map_or(true, |plugins| plugins.iter().any(|p| p == plugin_name))
+    }
+}
+
+/// Rule options are passed to the rules as `serde_json` values.
+/// Numbers which are integers stay integers, e.g. for `"max-params": ["deny", 3]`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::float_cmp)]
+fn to_serde_json(value: &JsonValue) -> Value {
+    match &value.kind {
+        JsonValueKind::Null => Value::Null,
+        JsonValueKind::Boolean(value) => Value::Bool(*value),
+        JsonValueKind::Number(number) => {
+            if number.trunc() == *number && number.abs() < i64::MAX as f64 {
+                Value::from(*number as i64)
+            } else {
+                serde_json::Number::from_f64(*number).map_or(Value::Null, Value::Number)
+            }
+        }
+        JsonValueKind::String(value) => Value::String(value.clone()),
+        JsonValueKind::Array(elements) => {
+            Value::Array(elements.iter().map(to_serde_json).collect())
+        }
+        JsonValueKind::Object(properties) => Value::Object(
+            properties
+                .iter()
+                .map(|property| (property.key.clone(), to_serde_json(&property.value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use oxc_json::{JsonKind, JsonParser, JsonValue};
+    use oxc_linter::Environment;
+    use serde_json::json;
+
+    use super::{AllowWarnDeny, LintConfig};
+
+    fn parse(source_text: &str) -> JsonValue {
+        JsonParser::new(source_text, JsonKind::Jsonc).parse().unwrap()
+    }
+
+    #[test]
+    fn from_value() {
+        let config = LintConfig::from_value(&parse(
+            r#"{
+            "plugins": ["eslint"],
+            "categories": { "suspicious": "warn", "all": "allow" },
+            "rules": { "no-debugger": 2, "eqeqeq": ["warn", "smart"] }
+        }"#,
+        ))
+        .unwrap();
+        assert_eq!(config.plugins, Some(vec!["eslint".to_string()]));
+        assert_eq!(config.rules.len(), 4);
+        assert_eq!(
+            config.rules[..2],
+            [(AllowWarnDeny::Allow, "all".into()), (AllowWarnDeny::Warn, "suspicious".into())]
+        );
+        assert!(config.rules.contains(&(AllowWarnDeny::Warn, "eqeqeq".into())));
+        assert!(config.rules.contains(&(AllowWarnDeny::Deny, "no-debugger".into())));
+        assert_eq!(config.rule_options.get("eqeqeq"), Some(&json!(["smart"])));
+        assert!(config.is_plugin_enabled("eslint"));
+        assert!(!config.is_plugin_enabled("jest"));
+        assert_eq!(config.env, Environment::default());
+    }
+
+    #[test]
+    fn env() {
+        let config = LintConfig::from_value(&parse(
+            r#"{ "env": { "browser": true, "node": false, "jest": true } }"#,
+        ))
+        .unwrap();
+        assert_eq!(config.env, Environment::BROWSER);
+
+        let source_text = r#"{ "env": { "node": "yes" } }"#;
+        let (message, span) = LintConfig::from_value(&parse(source_text)).unwrap_err();
+        assert_eq!(message, "expected a boolean for environment `node`");
+        assert_eq!(span.source_text(source_text), r#""yes""#);
+    }
+
+    #[test]
+    fn overrides() {
+        let config = LintConfig::from_value(&parse(
+            r#"{
+            "rules": { "no-debugger": "deny" },
+            "overrides": [
+                { "files": ["*.test.ts"], "rules": { "no-debugger": "allow" } },
+                { "files": "src/**/*.ts", "rules": { "eqeqeq": ["warn", "smart"] } }
+            ]
+        }"#,
+        ))
+        .unwrap();
+        assert!(config.matching_overrides(Path::new("index.ts")).is_empty());
+        assert_eq!(config.matching_overrides(Path::new("./test/a.test.ts")), vec![0]);
+        assert_eq!(config.matching_overrides(Path::new("src/a/b.test.ts")), vec![0, 1]);
+
+        let resolved = config.resolve(Path::new("src/a/b.test.ts"));
+        assert!(resolved.overrides.is_empty());
+        assert_eq!(
+            resolved.rules,
+            vec![
+                (AllowWarnDeny::Deny, "no-debugger".into()),
+                (AllowWarnDeny::Allow, "no-debugger".into()),
+                (AllowWarnDeny::Warn, "eqeqeq".into()),
+            ]
+        );
+        assert_eq!(resolved.rule_options.get("eqeqeq"), Some(&json!(["smart"])));
+    }
+
+    #[test]
+    fn invalid_level() {
+        let source_text = r#"{ "rules": { "no-debugger": "on" } }"#;
+        let (message, span) = LintConfig::from_value(&parse(source_text)).unwrap_err();
+        assert_eq!(message, "invalid level for `no-debugger`");
+        assert_eq!(span.source_text(source_text), r#""on""#);
+
+        let source_text = r#"{ "categories": ["correctness"] }"#;
+        let (message, span) = LintConfig::from_value(&parse(source_text)).unwrap_err();
+        assert_eq!(message, "expected `categories` to be an object, found an array");
+        assert_eq!(span.source_text(source_text), r#"["correctness"]"#);
+
+        assert!(LintConfig::from_value(&parse("[]")).is_err());
+    }
+}
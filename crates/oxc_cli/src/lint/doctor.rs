@@ -0,0 +1,223 @@
+use std::{fs, path::Path};
+
+use ignore::gitignore::GitignoreBuilder;
+use oxc_json::{JsonKind, JsonParser, JsonValue};
+use oxc_linter::{RuleCategory, RULES};
+use rustc_hash::FxHashMap;
+
+use super::{
+    config::{ConfigOverride, LintConfig},
+    init::ProjectInfo,
+    options::AllowWarnDeny,
+};
+use crate::CliRunResult;
+
+/// Checks the setup of the project in the current working directory and prints the problems found:
+/// * the config file cannot be read or parsed
+/// * unknown plugins and rule names, and rules whose plugin is not enabled
+/// * rule options which have no effect, because the rule does not accept them
+/// * overrides with the same file pattern setting a rule to different levels
+/// * ignore files that cannot be read or parsed
+pub fn doctor(config_path: Option<&Path>, ignore_path: &Path) -> CliRunResult {
+    print_environment();
+
+    let mut problems = vec![];
+    match LintConfig::find(config_path) {
+        Ok(Some((path, config))) => {
+            println!("Config file: {}", path.display());
+            problems.extend(check_config(&config));
+        }
+        Ok(None) => println!("Config file: none, using -D correctness"),
+        Err(error) => problems.push(error.to_string()),
+    }
+    for path in [ignore_path, Path::new(".gitignore")] {
+        if path.is_file() {
+            println!("Ignore file: {}", path.display());
+            problems.extend(check_ignore_file(path));
+        }
+    }
+
+    if !problems.is_empty() {
+        println!();
+    }
+    for problem in &problems {
+        println!("  × {problem}");
+    }
+
+    CliRunResult::DoctorResult { number_of_problems: problems.len() }
+}
+
+fn print_environment() {
+    let info = ProjectInfo::detect(Path::new("."));
+    println!("oxlint {}", env!("CARGO_PKG_VERSION"));
+    println!("Platform: {} {}", std::env::consts::OS, std::env::consts::ARCH);
+    println!("Threads: {}", rayon::current_num_threads());
+    println!("TypeScript: {}", if info.uses_typescript() { "yes" } else { "no" });
+    println!("JSX: {}", tsconfig_jsx().as_deref().unwrap_or("not configured"));
+    if let Some(framework) = info.framework() {
+        println!("Framework: {framework}");
+    }
+}
+
+/// `compilerOptions.jsx` of `tsconfig.json`, which may contain comments and trailing commas.
+fn tsconfig_jsx() -> Option<String> {
+    let tsconfig = fs::read_to_string("tsconfig.json").ok()?;
+    let tsconfig = JsonParser::new(&tsconfig, JsonKind::Jsonc).parse().ok()?;
+    tsconfig.pointer("/compilerOptions/jsx").and_then(JsonValue::as_str).map(ToString::to_string)
+}
+
+fn check_config(config: &LintConfig) -> Vec<String> {
+    let mut problems = vec![];
+
+    if let Some(plugins) = &config.plugins {
+        for plugin in plugins {
+            if !RULES.iter().any(|rule| rule.plugin_name() == plugin) {
+                problems.push(format!("unknown plugin `{plugin}`"));
+            }
+        }
+    }
+
+    let sections = std::iter::once(("rules".to_string(), &config.rules, &config.rule_options))
+        .chain(config.overrides.iter().enumerate().map(|(i, r#override)| {
+            (format!("overrides[{i}]"), &r#override.rules, &r#override.rule_options)
+        }));
+    for (section, rules, rule_options) in sections {
+        let names = rules.iter().map(|(_, name)| name).chain(rule_options.keys());
+        for name in names {
+            if name == "all" || RuleCategory::from(name).is_some() {
+                continue;
+            }
+            match RULES.iter().find(|rule| rule.name() == name) {
+                None => problems.push(format!("unknown rule `{name}` in {section}")),
+                Some(rule) if !config.is_plugin_enabled(rule.plugin_name()) => {
+                    problems.push(format!(
+                        "rule `{name}` in {section} belongs to the `{}` plugin, which is not enabled",
+                        rule.plugin_name()
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut rule_options = rule_options.iter().collect::<Vec<_>>();
+        rule_options.sort_unstable_by_key(|(name, _)| *name);
+        for (name, options) in rule_options {
+            let Some(rule) = RULES.iter().find(|rule| rule.name() == name) else { continue };
+            // Rules ignore the options they do not accept, so invalid options leave the rule
+            // configured as its default
+            if format!("{:?}", rule.read_json(Some(options.clone()))) == format!("{rule:?}") {
+                problems.push(format!(
+                    "the options of rule `{name}` in {section} have no effect, they are invalid or the defaults"
+                ));
+            }
+        }
+    }
+
+    for (i, a) in config.overrides.iter().enumerate() {
+        for (j, b) in config.overrides.iter().enumerate().skip(i + 1) {
+            let Some(pattern) = a.files.iter().find(|pattern| b.files.contains(pattern)) else {
+                continue;
+            };
+            let (levels_a, levels_b) = (override_levels(a), override_levels(b));
+            let mut conflicts = levels_a
+                .iter()
+                .filter_map(|(name, level_a)| {
+                    levels_b.get(name).filter(|level_b| *level_b != level_a).map(|level_b| {
+                        format!(
+                            "overrides[{i}] and overrides[{j}] both match `{pattern}` and set `{name}` to {level_a} and {level_b}, overrides[{j}] wins"
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+            conflicts.sort_unstable();
+            problems.extend(conflicts);
+        }
+    }
+
+    problems
+}
+
+/// The last level of each rule or category in an override.
+fn override_levels(r#override: &ConfigOverride) -> FxHashMap<&str, AllowWarnDeny> {
+    r#override.rules.iter().map(|(level, name)| (name.as_str(), *level)).collect()
+}
+
+fn check_ignore_file(path: &Path) -> Option<String> {
+    if let Err(error) = fs::read_to_string(path) {
+        return Some(format!("cannot read ignore file {}: {error}", path.display()));
+    }
+    GitignoreBuilder::new(".")
+        .add(path)
+        .map(|error| format!("invalid ignore file {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_json::{JsonKind, JsonParser, JsonValue};
+
+    use super::{check_config, LintConfig};
+
+    fn parse(source_text: &str) -> JsonValue {
+        JsonParser::new(source_text, JsonKind::Jsonc).parse().unwrap()
+    }
+
+    #[test]
+    fn unknown_names() {
+        let config = LintConfig::from_value(&parse(
+            r#"{
+            "plugins": ["eslint", "foo"],
+            "rules": { "no-debugger": "deny", "no-foo": "deny", "no-focused-tests": "warn" }
+        }"#,
+        ))
+        .unwrap();
+        let mut problems = check_config(&config);
+        problems.sort_unstable();
+        assert_eq!(
+            problems,
+            vec![
+                "rule `no-focused-tests` in rules belongs to the `jest` plugin, which is not enabled",
+                "unknown plugin `foo`",
+                "unknown rule `no-foo` in rules",
+            ]
+        );
+    }
+
+    #[test]
+    fn ineffective_options() {
+        let config = LintConfig::from_value(&parse(
+            r#"{
+            "rules": {
+                "no-debugger": ["deny", { "foo": true }],
+                "no-global-assign": ["deny", { "exceptions": "Object" }],
+                "no-restricted-globals": ["deny", "event"]
+            }
+        }"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            check_config(&config),
+            vec![
+                "the options of rule `no-debugger` in rules have no effect, they are invalid or the defaults",
+                "the options of rule `no-global-assign` in rules have no effect, they are invalid or the defaults",
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicting_overrides() {
+        let config = LintConfig::from_value(&parse(
+            r#"{
+            "overrides": [
+                { "files": ["*.test.ts"], "rules": { "no-debugger": "allow", "no-empty": "warn" } },
+                { "files": ["*.test.ts"], "rules": { "no-debugger": "deny", "no-empty": "warn" } },
+                { "files": ["*.js"], "rules": { "no-debugger": "warn" } },
+            ]
+        }"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            check_config(&config),
+            vec!["overrides[0] and overrides[1] both match `*.test.ts` and set `no-debugger` to allow and deny, overrides[1] wins"]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::Path,
+};
+
+use oxc_json::{JsonKind, JsonParser, JsonValue};
+use rustc_hash::FxHashSet;
+use serde_json::{json, Value};
+
+use super::{config::CONFIG_FILE_NAME, error::ErrorWithPath};
+use crate::CliRunResult;
+
+/// What `--init` learned about the project in the current working directory.
+#[derive(Debug, Default)]
+pub(super) struct ProjectInfo {
+    /// Names of all dependencies declared in `package.json`.
+    dependencies: FxHashSet<String>,
+    has_tsconfig: bool,
+}
+
+impl ProjectInfo {
+    pub(super) fn detect(cwd: &Path) -> Self {
+        let dependencies = fs::read_to_string(cwd.join("package.json"))
+            .ok()
+            .and_then(|s| JsonParser::new(&s, JsonKind::Json).parse().ok())
+            .map(|package_json| {
+                ["dependencies", "devDependencies", "peerDependencies"]
+                    .iter()
+                    .filter_map(|key| package_json.get(key).and_then(JsonValue::as_object))
+                    .flat_map(|deps| deps.iter().map(|dep| dep.key.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let has_tsconfig = cwd.join("tsconfig.json").is_file();
+        Self { dependencies, has_tsconfig }
+    }
+
+    fn has_dependency(&self, name: &str) -> bool {
+        self.dependencies.contains(name)
+    }
+
+    pub(super) fn uses_typescript(&self) -> bool {
+        self.has_tsconfig || self.has_dependency("typescript")
+    }
+
+    pub(super) fn uses_jest(&self) -> bool {
+        ["jest", "vitest", "@jest/globals"].iter().any(|name| self.has_dependency(name))
+    }
+
+    pub(super) fn framework(&self) -> Option<&'static str> {
+        [
+            ("next", "Next.js"),
+            ("react", "React"),
+            ("vue", "Vue"),
+            ("svelte", "Svelte"),
+            ("@angular/core", "Angular"),
+            ("solid-js", "Solid"),
+        ]
+        .iter()
+        .find(|(name, _)| self.has_dependency(name))
+        .map(|(_, framework)| *framework)
+    }
+
+    /// Plugins to enable, `eslint` and `deepscan` rules apply to all JavaScript code.
+    fn plugins(&self) -> Vec<&'static str> {
+        let mut plugins = vec!["eslint", "deepscan"];
+        if self.uses_typescript() {
+            plugins.push("typescript");
+        }
+        if self.uses_jest() {
+            plugins.push("jest");
+        }
+        plugins
+    }
+
+    fn config(&self) -> Value {
+        json!({
+            "plugins": self.plugins(),
+            "categories": {
+                "correctness": "deny",
+                "suspicious": "warn",
+            },
+            "rules": {},
+        })
+    }
+}
+
+/// Writes a starter `.oxlintrc.json` for the project in the current working directory.
+pub fn init() -> CliRunResult {
+    let cwd = Path::new(".");
+    let config_path = cwd.join(CONFIG_FILE_NAME);
+
+    if config_path.exists() && !confirm_overwrite() {
+        println!("{CONFIG_FILE_NAME} already exists, nothing was written.");
+        return CliRunResult::None;
+    }
+
+    let info = ProjectInfo::detect(cwd);
+    println!("Detected project settings:");
+    println!("  TypeScript: {}", if info.uses_typescript() { "yes" } else { "no" });
+    println!("  Test framework: {}", if info.uses_jest() { "yes" } else { "no" });
+    if let Some(framework) = info.framework() {
+        println!("  Framework: {framework}");
+    }
+
+    let mut config = serde_json::to_string_pretty(&info.config()).unwrap();
+    config.push('\n');
+    if let Err(err) = fs::write(&config_path, config).with_path(&config_path) {
+        return CliRunResult::IOError(err);
+    }
+
+    println!("Created {CONFIG_FILE_NAME} with plugins: {}.", info.plugins().join(", "));
+    CliRunResult::None
+}
+
+/// Asks before overwriting an existing config file.
+/// Never overwrites when stdin is not a terminal, e.g. on CI.
+fn confirm_overwrite() -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{CONFIG_FILE_NAME} already exists. Overwrite it? [y/N] ");
+    io::stdout().flush().unwrap();
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+#[cfg(test)]
+mod test {
+    use super::ProjectInfo;
+
+    #[test]
+    fn plugins() {
+        let info = ProjectInfo::default();
+        assert_eq!(info.plugins(), vec!["eslint", "deepscan"]);
+        assert_eq!(info.framework(), None);
+
+        let info = ProjectInfo {
+            dependencies: ["react", "jest"].iter().map(ToString::to_string).collect(),
+            has_tsconfig: true,
+        };
+        assert_eq!(info.plugins(), vec!["eslint", "deepscan", "typescript", "jest"]);
+        assert_eq!(info.framework(), Some("React"));
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     fs,
-    io::{BufWriter, Write},
+    io::{self, BufWriter, IsTerminal, Write},
     path::{Path, PathBuf},
     rc::Rc,
     sync::{
@@ -14,20 +14,63 @@ use oxc_allocator::Allocator;
 use oxc_diagnostics::{
     miette::{self, Diagnostic},
     thiserror::Error,
-    Error, GraphicalReportHandler, Severity,
+    Error, GraphicalReportHandler, GraphicalTheme, Severity,
 };
-use oxc_linter::{Fixer, LintContext, Linter};
+use oxc_linter::{Fixer, LintContext, Linter, Message};
 use oxc_parser::Parser;
 use oxc_semantic::SemanticBuilder;
 use oxc_span::SourceType;
+use similar::TextDiff;
 
-use super::options::LintOptions;
-use crate::{CliRunResult, Walk};
+use super::{
+    baseline::{Baseline, BaselineAction},
+    error::{self as lint_error, ErrorWithPath},
+    json_report::JsonReport,
+    options::{LintOptions, OutputFormat},
+    progress::Progress,
+    resolver::LinterResolver,
+    statistics::Statistics,
+};
+use crate::{git::ChangedFiles, CliRunResult, Walk};
 
 pub struct IsolatedLintHandler {
     options: Arc<LintOptions>,
 
-    linter: Arc<Linter>,
+    resolver: Arc<LinterResolver>,
+
+    /// Set by `--changed`, only the changed files are linted and only diagnostics on changed
+    /// lines are reported.
+    changed: Option<Arc<ChangedFiles>>,
+}
+
+/// The outcome of linting a single file.
+struct LintedFile {
+    path: PathBuf,
+    diagnostics: Vec<LintedDiagnostic>,
+    /// Unified diff of the applied fixes, only computed for `--fix-dry-run`.
+    diff: Option<String>,
+    applied_suggestions: usize,
+}
+
+/// A diagnostic with the rule which reported it.
+struct LintedDiagnostic {
+    error: Error,
+    /// `None` for parser and semantic errors.
+    rule_name: Option<&'static str>,
+    /// Whether the rule has a fix for the diagnostic which was not applied.
+    fixable: bool,
+}
+
+impl LintedDiagnostic {
+    fn syntax(error: Error) -> Self {
+        Self { error, rule_name: None, fixable: false }
+    }
+}
+
+impl From<Message<'_>> for LintedDiagnostic {
+    fn from(message: Message<'_>) -> Self {
+        Self { error: message.error, rule_name: Some(message.rule_name), fixable: message.fixable }
+    }
 }
 
 #[derive(Debug, Error, Diagnostic)]
@@ -36,8 +79,12 @@ pub struct IsolatedLintHandler {
 pub struct MinifiedFileError(pub PathBuf);
 
 impl IsolatedLintHandler {
-    pub(super) fn new(options: Arc<LintOptions>, linter: Arc<Linter>) -> Self {
-        Self { options, linter }
+    pub(super) fn new(
+        options: Arc<LintOptions>,
+        resolver: Arc<LinterResolver>,
+        changed: Option<Arc<ChangedFiles>>,
+    ) -> Self {
+        Self { options, resolver, changed }
     }
 
     /// # Panics
@@ -46,92 +93,248 @@ impl IsolatedLintHandler {
     pub(super) fn run(&self) -> CliRunResult {
         let now = std::time::Instant::now();
 
+        let report_writer = match self.report_writer() {
+            Ok(writer) => writer,
+            Err(error) => return CliRunResult::IOError(error),
+        };
+
+        let baseline_path = &self.options.baseline;
+        let baseline = match self.options.baseline_action {
+            Some(BaselineAction::Create) => None,
+            _ => match Baseline::load(baseline_path) {
+                Ok(baseline) => baseline,
+                Err(error) => return CliRunResult::IOError(error),
+            },
+        };
+        if self.options.baseline_action == Some(BaselineAction::Prune) && baseline.is_none() {
+            return CliRunResult::PathNotFound { paths: vec![baseline_path.clone()] };
+        }
+        // The current findings, recorded instead of reported by `oxlint baseline`.
+        let mut found = self.options.baseline_action.map(|_| Baseline::default());
+
+        let walk = match Walk::new(&self.options) {
+            Ok(walk) => walk,
+            Err(error) => return CliRunResult::ConfigError(error),
+        };
+
         let number_of_files = Arc::new(AtomicUsize::new(0));
-        let (tx_error, rx_error) = mpsc::channel::<(PathBuf, Vec<Error>)>();
+        let number_of_processed_files = Arc::new(AtomicUsize::new(0));
+        let (tx_error, rx_error) = mpsc::channel::<LintedFile>();
 
-        self.process_paths(&number_of_files, tx_error);
-        let (number_of_warnings, number_of_errors) = self.process_diagnostics(&rx_error);
+        let progress = (!self.options.no_progress && io::stderr().is_terminal()).then(|| {
+            Progress::start(Arc::clone(&number_of_files), Arc::clone(&number_of_processed_files))
+        });
+
+        self.process_paths(walk, &number_of_files, &number_of_processed_files, tx_error);
+        let mut statistics = self.options.statistics.then(Statistics::default);
+        let (number_of_warnings, number_of_errors, number_of_applied_suggestions) = self
+            .process_diagnostics(
+                &rx_error,
+                report_writer,
+                progress.as_ref(),
+                statistics.as_mut(),
+                baseline.as_ref(),
+                found.as_mut(),
+            );
+
+        if let Some(progress) = progress {
+            progress.finish();
+        }
+
+        if let Some(found) = found {
+            let baseline = match baseline {
+                Some(baseline) => baseline.prune(&found),
+                None => found,
+            };
+            if let Err(error) = baseline.write(baseline_path) {
+                return CliRunResult::IOError(error);
+            }
+            println!(
+                "Wrote {} findings in {} files to {}.",
+                baseline.number_of_findings(),
+                baseline.number_of_files(),
+                baseline_path.display()
+            );
+            return CliRunResult::None;
+        }
+
+        let duration = now.elapsed();
+        // Keep stdout free for the report when it is printed there as JSON.
+        let summary_to_stderr =
+            self.options.output_file.is_some() || self.options.format == OutputFormat::Json;
+        if let Some(statistics) = statistics {
+            // Printed next to the summary.
+            if summary_to_stderr {
+                statistics.print(&mut io::stderr(), duration).unwrap();
+            } else {
+                statistics.print(&mut io::stdout(), duration).unwrap();
+            }
+        }
 
         CliRunResult::LintResult {
-            duration: now.elapsed(),
-            number_of_rules: self.linter.number_of_rules(),
+            duration,
+            number_of_rules: self.resolver.linter().number_of_rules(),
             number_of_files: number_of_files.load(Ordering::Relaxed),
             number_of_warnings,
             number_of_errors,
-            max_warnings_exceeded: self
-                .options
-                .max_warnings
-                .map_or(false, |max_warnings| number_of_warnings > max_warnings),
+            number_of_applied_suggestions,
+            max_warnings: self.options.max_warnings,
+            exit_zero: self.options.exit_zero_even_if_error,
+            summary_to_stderr,
         }
     }
 
     fn process_paths(
         &self,
+        walk: Walk,
         number_of_files: &Arc<AtomicUsize>,
-        tx_error: mpsc::Sender<(PathBuf, Vec<Error>)>,
+        number_of_processed_files: &Arc<AtomicUsize>,
+        tx_error: mpsc::Sender<LintedFile>,
     ) {
         let (tx_path, rx_path) = mpsc::channel::<Box<Path>>();
 
-        let walk = Walk::new(&self.options);
         let number_of_files = Arc::clone(number_of_files);
+        let changed = self.changed.clone();
         rayon::spawn(move || {
-            let mut count = 0;
-            walk.iter().for_each(|path| {
-                count += 1;
-                tx_path.send(path).unwrap();
-            });
-            number_of_files.store(count, Ordering::Relaxed);
+            walk.iter()
+                .filter(|path| {
+                    changed.as_ref().map_or(true, |changed| changed.is_changed_file(path))
+                })
+                .for_each(|path| {
+                    number_of_files.fetch_add(1, Ordering::Relaxed);
+                    tx_path.send(path).unwrap();
+                });
         });
 
-        let linter = Arc::clone(&self.linter);
+        let resolver = Arc::clone(&self.resolver);
+        let fix_dry_run = self.options.fix_dry_run;
+        let max_parse_errors = self.options.max_parse_errors;
+        let isolated_modules = self.options.isolated_modules;
+        let number_of_processed_files = Arc::clone(number_of_processed_files);
         rayon::spawn(move || {
             while let Ok(path) = rx_path.recv() {
                 let tx_error = tx_error.clone();
-                let linter = Arc::clone(&linter);
+                let linter = resolver.linter_for(&path);
+                let number_of_processed_files = Arc::clone(&number_of_processed_files);
                 rayon::spawn(move || {
-                    if let Some(diagnostics) = Self::lint_path(&linter, &path) {
+                    if let Some(diagnostics) = Self::lint_path(
+                        &linter,
+                        &path,
+                        fix_dry_run,
+                        max_parse_errors,
+                        isolated_modules,
+                    ) {
                         tx_error.send(diagnostics).unwrap();
                     }
+                    number_of_processed_files.fetch_add(1, Ordering::Relaxed);
                     drop(tx_error);
                 });
             }
         });
     }
 
+    fn report_handler(&self) -> GraphicalReportHandler {
+        if self.options.output_file.is_some() {
+            // Escape codes are noise in a file.
+            GraphicalReportHandler::new().with_theme(GraphicalTheme::unicode_nocolor())
+        } else {
+            GraphicalReportHandler::new()
+        }
+    }
+
+    /// Where the diagnostics are reported:
+    /// * the `--output-file`, created or truncated before linting starts
+    /// * stderr with `--fix-dry-run`, as stdout is reserved for the diffs so the output can be
+    ///   piped into patch tools
+    /// * stdout otherwise
+    fn report_writer(&self) -> lint_error::Result<Box<dyn Write>> {
+        if let Some(path) = &self.options.output_file {
+            let file = fs::File::create(path).with_path(path)?;
+            return Ok(Box::new(file));
+        }
+        if self.options.fix_dry_run {
+            return Ok(Box::new(std::io::stderr()));
+        }
+        Ok(Box::new(std::io::stdout()))
+    }
+
     fn process_diagnostics(
         &self,
-        rx_error: &mpsc::Receiver<(PathBuf, Vec<Error>)>,
-    ) -> (usize, usize) {
+        rx_error: &mpsc::Receiver<LintedFile>,
+        report_writer: Box<dyn Write>,
+        progress: Option<&Progress>,
+        mut statistics: Option<&mut Statistics>,
+        baseline: Option<&Baseline>,
+        mut found: Option<&mut Baseline>,
+    ) -> (usize, usize, usize) {
         let mut number_of_warnings = 0;
         let mut number_of_errors = 0;
-        let mut buf_writer = BufWriter::new(std::io::stdout());
-        let handler = GraphicalReportHandler::new();
+        let mut number_of_applied_suggestions = 0;
+        let wrap = |writer: Box<dyn Write>| -> Box<dyn Write> {
+            match progress {
+                Some(progress) => Box::new(progress.wrap(writer)),
+                None => writer,
+            }
+        };
+        let mut buf_writer = BufWriter::new(wrap(report_writer));
+        let mut diff_writer = BufWriter::new(wrap(Box::new(std::io::stdout())));
+        let handler = self.report_handler();
+        let mut json_report = (self.options.format == OutputFormat::Json).then(JsonReport::default);
+
+        while let Ok(LintedFile { path, diagnostics, diff, applied_suggestions }) = rx_error.recv()
+        {
+            number_of_applied_suggestions += applied_suggestions;
+            if let Some(diff) = diff {
+                diff_writer.write_all(diff.as_bytes()).unwrap();
+            }
+
+            let key = (baseline.is_some() || found.is_some()).then(|| Baseline::key(&path));
+            let mut allowances = baseline
+                .zip(key.as_deref())
+                .map(|(baseline, key)| baseline.allowances(key))
+                .unwrap_or_default();
 
-        while let Ok((path, diagnostics)) = rx_error.recv() {
             let mut output = String::new();
-            for diagnostic in diagnostics {
+            for LintedDiagnostic { error: diagnostic, rule_name, fixable } in diagnostics {
                 let severity = diagnostic.severity();
                 let is_warning = severity == Some(Severity::Warning);
                 let is_error = severity.is_none() || severity == Some(Severity::Error);
-                if is_warning || is_error {
-                    if is_warning {
-                        number_of_warnings += 1;
-                    }
-                    if is_error {
-                        number_of_errors += 1;
+                // The --quiet flag follows ESLint's --quiet behavior as documented here: https://eslint.org/docs/latest/use/command-line-interface#--quiet
+                // Note that it does not disable ALL diagnostics, only Warning diagnostics,
+                // which are neither reported nor counted.
+                if is_warning && self.options.quiet {
+                    continue;
+                }
+                if let Some(changed) = &self.changed {
+                    if !changed.is_changed_diagnostic(&path, diagnostic.as_ref()) {
+                        continue;
                     }
-                    // The --quiet flag follows ESLint's --quiet behavior as documented here: https://eslint.org/docs/latest/use/command-line-interface#--quiet
-                    // Note that it does not disable ALL diagnostics, only Warning diagnostics
-                    if self.options.quiet {
+                }
+                if let Some(key) = &key {
+                    let rule = Baseline::rule(rule_name);
+                    if let Some(found) = found.as_deref_mut() {
+                        found.add(key, rule);
                         continue;
                     }
-
-                    if let Some(max_warnings) = self.options.max_warnings {
-                        if number_of_warnings > max_warnings {
-                            continue;
-                        }
+                    // Findings of the baseline are neither reported nor counted.
+                    if let Some(allowance) = allowances.get_mut(rule).filter(|n| **n > 0) {
+                        *allowance -= 1;
+                        continue;
                     }
                 }
+                if is_warning {
+                    number_of_warnings += 1;
+                }
+                if is_error {
+                    number_of_errors += 1;
+                }
+                if let Some(statistics) = statistics.as_deref_mut() {
+                    statistics.add(&path, rule_name, fixable);
+                }
+                if let Some(json_report) = json_report.as_mut() {
+                    json_report.add(&path, diagnostic.as_ref(), rule_name, fixable);
+                    continue;
+                }
 
                 let mut err = String::new();
                 handler.render_report(&mut err, diagnostic.as_ref()).unwrap();
@@ -146,23 +349,37 @@ impl IsolatedLintHandler {
             }
             buf_writer.write_all(output.as_bytes()).unwrap();
         }
+        if let Some(json_report) = json_report {
+            writeln!(buf_writer, "{}", json_report.into_json()).unwrap();
+        }
 
         buf_writer.flush().unwrap();
-        (number_of_warnings, number_of_errors)
+        diff_writer.flush().unwrap();
+        (number_of_warnings, number_of_errors, number_of_applied_suggestions)
     }
 
-    fn lint_path(linter: &Linter, path: &Path) -> Option<(PathBuf, Vec<Error>)> {
+    fn lint_path(
+        linter: &Linter,
+        path: &Path,
+        fix_dry_run: bool,
+        max_parse_errors: Option<usize>,
+        isolated_modules: bool,
+    ) -> Option<LintedFile> {
         let source_text =
             fs::read_to_string(path).unwrap_or_else(|_| panic!("Failed to read {path:?}"));
         let allocator = Allocator::default();
         let source_type =
             SourceType::from_path(path).unwrap_or_else(|_| panic!("Incorrect {path:?}"));
-        let ret = Parser::new(&allocator, &source_text, source_type)
-            .allow_return_outside_function(true)
-            .parse();
+        let mut parser =
+            Parser::new(&allocator, &source_text, source_type).allow_return_outside_function(true);
+        if let Some(max_parse_errors) = max_parse_errors {
+            parser = parser.recover_from_errors(true).max_errors(max_parse_errors);
+        }
+        let ret = parser.parse();
 
         if !ret.errors.is_empty() {
-            return Some(Self::wrap_diagnostics(path, &source_text, ret.errors));
+            let diagnostics = ret.errors.into_iter().map(LintedDiagnostic::syntax).collect();
+            return Some(Self::wrap_diagnostics(path, &source_text, diagnostics));
         };
 
         let program = allocator.alloc(ret.program);
@@ -170,10 +387,13 @@ impl IsolatedLintHandler {
             .with_trivias(ret.trivias)
             .with_check_syntax_error(true)
             .with_module_record_builder(true)
+            .with_isolated_modules(isolated_modules)
             .build(program);
 
         if !semantic_ret.errors.is_empty() {
-            return Some(Self::wrap_diagnostics(path, &source_text, semantic_ret.errors));
+            let diagnostics =
+                semantic_ret.errors.into_iter().map(LintedDiagnostic::syntax).collect();
+            return Some(Self::wrap_diagnostics(path, &source_text, diagnostics));
         };
 
         let lint_ctx = LintContext::new(&Rc::new(semantic_ret.semantic));
@@ -185,25 +405,48 @@ impl IsolatedLintHandler {
 
         if linter.has_fix() {
             let fix_result = Fixer::new(&source_text, result).fix();
-            fs::write(path, fix_result.fixed_code.as_bytes()).unwrap();
-            let errors = fix_result.messages.into_iter().map(|m| m.error).collect();
-            return Some(Self::wrap_diagnostics(path, &source_text, errors));
+            let diagnostics = fix_result.messages.into_iter().map(LintedDiagnostic::from).collect();
+            let mut linted_file = Self::wrap_diagnostics(path, &source_text, diagnostics);
+            linted_file.applied_suggestions = fix_result.applied_suggestions;
+            if fix_dry_run {
+                if fix_result.fixed {
+                    linted_file.diff =
+                        Some(Self::unified_diff(path, &source_text, &fix_result.fixed_code));
+                }
+            } else {
+                fs::write(path, fix_result.fixed_code.as_bytes()).unwrap();
+            }
+            return Some(linted_file);
         }
 
-        let errors = result.into_iter().map(|diagnostic| diagnostic.error).collect();
-        Some(Self::wrap_diagnostics(path, &source_text, errors))
+        let diagnostics = result.into_iter().map(LintedDiagnostic::from).collect();
+        Some(Self::wrap_diagnostics(path, &source_text, diagnostics))
     }
 
     fn wrap_diagnostics(
         path: &Path,
         source_text: &str,
-        diagnostics: Vec<Error>,
-    ) -> (PathBuf, Vec<Error>) {
+        diagnostics: Vec<LintedDiagnostic>,
+    ) -> LintedFile {
         let source = Arc::new(NamedSource::new(path.to_string_lossy(), source_text.to_owned()));
         let diagnostics = diagnostics
             .into_iter()
-            .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+            .map(|diagnostic| LintedDiagnostic {
+                error: diagnostic.error.with_source_code(Arc::clone(&source)),
+                ..diagnostic
+            })
             .collect();
-        (path.to_path_buf(), diagnostics)
+        LintedFile { path: path.to_path_buf(), diagnostics, diff: None, applied_suggestions: 0 }
+    }
+
+    /// Render the changes between `source_text` and `fixed_code` as a unified diff,
+    /// using git's `a/` and `b/` path prefixes so it can be applied with `git apply` or `patch -p1`.
+    fn unified_diff(path: &Path, source_text: &str, fixed_code: &str) -> String {
+        let path = path.strip_prefix("./").unwrap_or(path).to_string_lossy();
+        TextDiff::from_lines(source_text, fixed_code)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+            .to_string()
     }
 }
@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use oxc_diagnostics::{
+    miette::{Diagnostic, LabeledSpan},
+    Severity,
+};
+use serde_json::{json, Value};
+
+use super::baseline::Baseline;
+
+/// The diagnostics printed as a JSON array with `--format json`, e.g.
+///
+/// ```json
+/// [
+///   {
+///     "file": "src/index.js",
+///     "rule": "no-debugger",
+///     "severity": "warning",
+///     "message": "eslint(no-debugger): `debugger` statement is not allowed",
+///     "help": "Delete this code.",
+///     "labels": [{ "label": null, "start": 10, "end": 19, "line": 2, "column": 1 }],
+///     "fixable": true
+///   }
+/// ]
+/// ```
+///
+/// The diagnostics are sorted by file and position and the report has no totals, so the reports
+/// of `--shard` runs, which never share a file, are merged by concatenating their arrays and
+/// sorting by file, e.g. with `jq -s 'add | sort_by(.file)'`.
+#[derive(Debug, Default)]
+pub struct JsonReport {
+    diagnostics: Vec<(String, usize, Value)>,
+}
+
+impl JsonReport {
+    /// `rule_name` is `None` for parser and semantic errors, which are reported with a `null` rule.
+    pub fn add(
+        &mut self,
+        path: &Path,
+        diagnostic: &dyn Diagnostic,
+        rule_name: Option<&str>,
+        fixable: bool,
+    ) {
+        let file = Baseline::key(path);
+        let severity = match diagnostic.severity() {
+            Some(Severity::Warning) => "warning",
+            Some(Severity::Advice) => "advice",
+            Some(Severity::Error) | None => "error",
+        };
+        let labels = diagnostic
+            .labels()
+            .map(|labels| labels.map(|label| Self::label(diagnostic, &label)).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let offset = diagnostic
+            .labels()
+            .and_then(|mut labels| labels.next())
+            .map_or(0, |label| label.offset());
+        let value = json!({
+            "file": file,
+            "rule": rule_name,
+            "severity": severity,
+            "message": diagnostic.to_string(),
+            "help": diagnostic.help().map(|help| help.to_string()),
+            "labels": labels,
+            "fixable": fixable,
+        });
+        self.diagnostics.push((file, offset, value));
+    }
+
+    /// `line` and `column` start at 1, and are `null` when the source text is not attached.
+    fn label(diagnostic: &dyn Diagnostic, label: &LabeledSpan) -> Value {
+        let position = diagnostic
+            .source_code()
+            .and_then(|source| source.read_span(label.inner(), 0, 0).ok())
+            .map(|contents| (contents.line() + 1, contents.column() + 1));
+        json!({
+            "label": label.label(),
+            "start": label.offset(),
+            "end": label.offset() + label.len(),
+            "line": position.map(|(line, _)| line),
+            "column": position.map(|(_, column)| column),
+        })
+    }
+
+    /// The files are linted in parallel, the diagnostics are sorted to make the report stable.
+    pub fn into_json(mut self) -> String {
+        self.diagnostics.sort_by(|(a_file, a_offset, _), (b_file, b_offset, _)| {
+            a_file.cmp(b_file).then_with(|| a_offset.cmp(b_offset))
+        });
+        let diagnostics =
+            self.diagnostics.into_iter().map(|(_, _, value)| value).collect::<Vec<_>>();
+        serde_json::to_string_pretty(&diagnostics).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use oxc_diagnostics::{
+        miette::{miette, LabeledSpan},
+        Severity,
+    };
+    use serde_json::{json, Value};
+
+    use super::JsonReport;
+
+    const SOURCE: &str = "let a = 1;\ndebugger;\n";
+
+    fn report(files: &[(&str, usize)]) -> Value {
+        let mut report = JsonReport::default();
+        for (file, offset) in files {
+            let diagnostic = miette!(
+                severity = Severity::Warning,
+                help = "Delete this code.",
+                labels = vec![LabeledSpan::at(*offset..*offset + 9, "here")],
+                "`debugger` statement is not allowed"
+            )
+            .with_source_code(SOURCE);
+            report.add(Path::new(file), diagnostic.as_ref(), Some("no-debugger"), true);
+        }
+        serde_json::from_str(&report.into_json()).unwrap()
+    }
+
+    #[test]
+    fn diagnostic() {
+        assert_eq!(
+            report(&[("./src/index.js", 11)]),
+            json!([{
+                "file": "src/index.js",
+                "rule": "no-debugger",
+                "severity": "warning",
+                "message": "`debugger` statement is not allowed",
+                "help": "Delete this code.",
+                "labels": [{ "label": "here", "start": 11, "end": 20, "line": 2, "column": 1 }],
+                "fixable": true,
+            }])
+        );
+    }
+
+    #[test]
+    fn syntax_error() {
+        let mut report = JsonReport::default();
+        let diagnostic = miette!("Unexpected token");
+        report.add(Path::new("a.js"), diagnostic.as_ref(), None, false);
+        let report: Value = serde_json::from_str(&report.into_json()).unwrap();
+        assert_eq!(report[0]["rule"], Value::Null);
+        assert_eq!(report[0]["severity"], "error");
+        assert_eq!(report[0]["labels"], json!([]));
+    }
+
+    #[test]
+    fn sorted() {
+        let files = |report: &Value| {
+            report
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|diagnostic| {
+                    (diagnostic["file"].clone(), diagnostic["labels"][0]["start"].clone())
+                })
+                .collect::<Vec<_>>()
+        };
+        let report = report(&[("b/b.js", 0), ("a/a.js", 11), ("b/a.js", 0), ("a/a.js", 0)]);
+        assert_eq!(
+            files(&report),
+            [
+                (json!("a/a.js"), json!(0)),
+                (json!("a/a.js"), json!(11)),
+                (json!("b/a.js"), json!(0)),
+                (json!("b/b.js"), json!(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_shards() {
+        let single = report(&[("a/a.js", 11), ("b/b.js", 0), ("a/a.js", 0), ("c/c.js", 0)]);
+        let first = report(&[("c/c.js", 0), ("a/a.js", 11), ("a/a.js", 0)]);
+        let second = report(&[("b/b.js", 0)]);
+        let mut merged = [first, second]
+            .iter()
+            .flat_map(|report| report.as_array().unwrap().clone())
+            .collect::<Vec<_>>();
+        merged.sort_by(|a, b| a["file"].as_str().cmp(&b["file"].as_str()));
+        assert_eq!(Value::Array(merged), single);
+    }
+}
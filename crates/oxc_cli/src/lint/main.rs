@@ -8,8 +8,17 @@ static GLOBAL: jemallocator::Jemalloc = jemallocator::Jemalloc;
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
-use clap::{Arg, Command};
-use oxc_cli::{CliRunResult, LintOptions, LintRunner, Runner, RunnerOptions};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use clap::{Arg, ArgMatches, Command};
+use clap_complete::Shell;
+use oxc_cli::{
+    doctor, generate_completions, install_panic_hook, BaselineAction, CliRunResult, LintOptions,
+    LintRunner, Runner, RunnerOptions,
+};
 
 pub fn command() -> Command {
     LintOptions::build_args(
@@ -23,16 +32,104 @@ pub fn command() -> Command {
                 Arg::new("threads")
                     .long("threads")
                     .value_parser(clap::value_parser!(usize))
-                    .help("Number of threads to use. Set to 1 for using only 1 CPU core."),
+                    .help("Number of threads to use. Set to 1 for using only 1 CPU core. Defaults to the OXLINT_THREADS environment variable, or all CPU cores."),
+            )
+            .subcommand_negates_reqs(true)
+            .args_conflicts_with_subcommands(true)
+            .subcommand(
+                Command::new("completions")
+                    .about("Generate shell completions, including the rule names for -A, -W and -D")
+                    .arg(
+                        Arg::new("shell")
+                            .required(true)
+                            .value_parser(["bash", "zsh", "fish", "powershell"]),
+                    ),
+            )
+            .subcommand(
+                Command::new("doctor")
+                    .about("Check the config file and ignore files, and print the environment")
+                    .arg(
+                        Arg::new("config")
+                            .long("config")
+                            .short('c')
+                            .value_parser(clap::value_parser!(PathBuf))
+                            .help("Path of the config file, defaults to .oxlintrc.json"),
+                    )
+                    .arg(
+                        Arg::new("ignore-path")
+                            .long("ignore-path")
+                            .value_parser(clap::value_parser!(PathBuf))
+                            .default_value(".eslintignore")
+                            .help("Path of the ignore file"),
+                    ),
+            )
+            .subcommand(
+                Command::new("baseline")
+                    .about("Record the current findings in a baseline file, which are then no longer reported")
+                    .subcommand_required(true)
+                    .subcommand(LintOptions::build_args(
+                        Command::new("create").about("Write all current findings to the baseline file"),
+                    ))
+                    .subcommand(LintOptions::build_args(
+                        Command::new("prune")
+                            .about("Remove the findings which are no longer reported from the baseline file"),
+                    )),
             ),
     )
 }
 
+/// `--threads` takes precedence over `OXLINT_THREADS`, an invalid `OXLINT_THREADS` is ignored.
+fn threads(matches: &ArgMatches) -> Option<usize> {
+    matches
+        .get_one::<usize>("threads")
+        .copied()
+        .or_else(|| env::var("OXLINT_THREADS").ok().and_then(|threads| threads.trim().parse().ok()))
+}
+
+/// The subcommands take precedence over a path with the same name, which is linted with
+/// `oxlint -- <PATH>` instead.
+fn warn_if_shadowing_path(subcommand: &str) {
+    if Path::new(subcommand).exists() {
+        eprintln!(
+            "Running the `{subcommand}` subcommand, use `oxlint -- {subcommand}` to lint the path `{subcommand}` instead."
+        );
+    }
+}
+
 fn main() -> CliRunResult {
+    install_panic_hook();
+
     let matches = command().get_matches();
 
-    if let Some(threads) = matches.get_one::<usize>("threads") {
-        rayon::ThreadPoolBuilder::new().num_threads(*threads).build_global().unwrap();
+    if let Some((name, _)) = matches.subcommand() {
+        warn_if_shadowing_path(name);
+    }
+
+    if let Some(("completions", matches)) = matches.subcommand() {
+        let shell = matches.get_one::<String>("shell").unwrap().parse::<Shell>().unwrap();
+        generate_completions(command(), shell, &mut std::io::stdout());
+        return CliRunResult::None;
+    }
+
+    if let Some(("doctor", matches)) = matches.subcommand() {
+        let config = matches.get_one::<PathBuf>("config");
+        let ignore_path = matches.get_one::<PathBuf>("ignore-path").unwrap();
+        return doctor(config.map(PathBuf::as_path), ignore_path);
+    }
+
+    if let Some(threads) = threads(&matches) {
+        rayon::ThreadPoolBuilder::new().num_threads(threads).build_global().unwrap();
+    }
+
+    if let Some(("baseline", matches)) = matches.subcommand() {
+        let (action, matches) = match matches.subcommand() {
+            Some(("create", matches)) => (BaselineAction::Create, matches),
+            Some(("prune", matches)) => (BaselineAction::Prune, matches),
+            _ => unreachable!("subcommand is required"),
+        };
+        let mut options = LintOptions::from(matches);
+        options.baseline_action = Some(action);
+        return LintRunner::new(options).run();
     }
 
     let options = LintOptions::from(&matches);
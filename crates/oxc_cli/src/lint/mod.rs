@@ -1,21 +1,46 @@
+mod baseline;
 mod command;
+mod completions;
+mod config;
+mod doctor;
 mod error;
+mod init;
 mod isolated_handler;
+mod json_report;
 mod options;
+mod progress;
+mod resolver;
+mod statistics;
 
-use std::{io::BufWriter, sync::Arc, time::Duration};
+use std::{
+    io::{BufWriter, Write},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
 
+use oxc_diagnostics::Severity;
 use oxc_index::assert_impl_all;
-use oxc_linter::{Linter, RuleCategory, RuleEnum, RULES};
-use rustc_hash::FxHashSet;
+use oxc_linter::{Environment, FixKind, Linter, RuleCategory, RuleEnum, RULES};
+use rustc_hash::FxHashMap;
 
-pub use self::{error::Error, options::LintOptions};
-use self::{isolated_handler::IsolatedLintHandler, options::AllowWarnDeny};
-use crate::{CliRunResult, Runner};
+pub use self::{
+    baseline::BaselineAction, completions::generate_completions, doctor::doctor, error::Error,
+    options::LintOptions,
+};
+use self::{
+    config::LintConfig,
+    isolated_handler::IsolatedLintHandler,
+    options::{AllowWarnDeny, OutputFormat},
+    resolver::LinterResolver,
+};
+use crate::{
+    git::{ChangedFiles, Git},
+    CliRunResult, Runner,
+};
 
 pub struct LintRunner {
     options: Arc<LintOptions>,
-    linter: Arc<Linter>,
 }
 assert_impl_all!(LintRunner: Send, Sync);
 
@@ -32,23 +57,49 @@ impl Runner for LintRunner {
     const NAME: &'static str = "lint";
 
     fn new(options: LintOptions) -> Self {
-        let linter = Linter::from_rules(Self::derive_rules(&options))
-            .with_fix(options.fix)
-            .with_print_execution_times(options.print_execution_times);
-        Self { options: Arc::new(options), linter: Arc::new(linter) }
+        Self { options: Arc::new(options) }
     }
 
     fn run(&self) -> CliRunResult {
         if self.options.list_rules {
-            Self::print_rules();
+            Self::print_rules(&self.options);
             return CliRunResult::None;
         }
 
+        if self.options.init {
+            return init::init();
+        }
+
+        if self.options.fix_in_place() && !self.options.fix_dirty {
+            if let Err(error) = Git::new(&self.options.paths).verify() {
+                return CliRunResult::VcsError(error);
+            }
+        }
+
+        let resolver = match LinterResolver::new(Arc::clone(&self.options)) {
+            Ok(resolver) => Arc::new(resolver),
+            Err(error) => return CliRunResult::ConfigError(error.into()),
+        };
+
+        if let Some(path) = &self.options.print_config {
+            Self::print_config(&self.options, resolver.config(), path);
+            return CliRunResult::None;
+        }
+
+        let changed = match &self.options.changed {
+            Some(since) => match ChangedFiles::new(since) {
+                Ok(changed) => Some(Arc::new(changed)),
+                Err(error) => return CliRunResult::VcsError(error),
+            },
+            None => None,
+        };
+
         let result =
-            IsolatedLintHandler::new(Arc::clone(&self.options), Arc::clone(&self.linter)).run();
+            IsolatedLintHandler::new(Arc::clone(&self.options), Arc::clone(&resolver), changed)
+                .run();
 
         if self.options.print_execution_times {
-            self.print_execution_times();
+            Self::print_execution_times(resolver.linter());
         }
 
         result
@@ -56,60 +107,210 @@ impl Runner for LintRunner {
 }
 
 impl LintRunner {
-    fn print_rules() {
+    /// Print the registered rules, filtered by `--category` and `--fixable`.
+    fn print_rules(options: &LintOptions) {
+        let rules = RULES
+            .iter()
+            .filter(|rule| options.rules_category.map_or(true, |c| rule.category() == c))
+            .filter(|rule| !options.rules_fixable || rule.fix() != FixKind::None)
+            .collect::<Vec<_>>();
         let mut stdout = BufWriter::new(std::io::stdout());
-        Linter::print_rules(&mut stdout);
+
+        match options.format {
+            OutputFormat::Default => {
+                for category in [
+                    RuleCategory::Correctness,
+                    RuleCategory::Suspicious,
+                    RuleCategory::Pedantic,
+                    RuleCategory::Style,
+                    RuleCategory::Restriction,
+                    RuleCategory::Nursery,
+                ] {
+                    let rules =
+                        rules.iter().filter(|rule| rule.category() == category).collect::<Vec<_>>();
+                    if rules.is_empty() {
+                        continue;
+                    }
+                    writeln!(stdout, "{category} ({}):", rules.len()).unwrap();
+                    for rule in rules {
+                        let default_level = Self::default_level(rule);
+                        let fix = match rule.fix() {
+                            FixKind::None => String::new(),
+                            fix => format!(", {fix}"),
+                        };
+                        writeln!(
+                            stdout,
+                            "  {}/{} ({default_level}{fix})",
+                            rule.plugin_name(),
+                            rule.name()
+                        )
+                        .unwrap();
+                    }
+                }
+                writeln!(stdout, "Total: {}", rules.len()).unwrap();
+            }
+            OutputFormat::Json => {
+                let rules = rules
+                    .iter()
+                    .map(|rule| {
+                        serde_json::json!({
+                            "name": rule.name(),
+                            "plugin": rule.plugin_name(),
+                            "category": rule.category().to_string().to_lowercase(),
+                            "default": Self::default_level(rule),
+                            "fix": rule.fix().to_string(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                writeln!(stdout, "{}", serde_json::to_string_pretty(&rules).unwrap()).unwrap();
+            }
+        }
+        stdout.flush().unwrap();
     }
 
-    fn derive_rules(options: &LintOptions) -> Vec<RuleEnum> {
-        let mut rules: FxHashSet<RuleEnum> = FxHashSet::default();
+    /// Rules of the correctness category are denied when no config file is present.
+    fn default_level(rule: &RuleEnum) -> &'static str {
+        if rule.category() == RuleCategory::Correctness {
+            "deny"
+        } else {
+            "allow"
+        }
+    }
+
+    /// Print the environments and rules that apply to `path` as JSON, after applying the
+    /// `extends` and `overrides` of the config file and the `-A` / `-W` / `-D` flags.
+    fn print_config(options: &LintOptions, config: Option<&LintConfig>, path: &Path) {
+        let config = config.map(|config| config.resolve(path));
+        let (rules, rule_severities) = Self::derive_rules(options, config.as_ref());
+
+        let mut plugins = RULES
+            .iter()
+            .map(RuleEnum::plugin_name)
+            .filter(|plugin| {
+                config.as_ref().map_or(true, |config| config.is_plugin_enabled(plugin))
+            })
+            .collect::<Vec<_>>();
+        plugins.sort_unstable();
+        plugins.dedup();
 
-        for (allow_warn_deny, name_or_category) in &options.rules {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let level = match rule_severities.get(rule.name()) {
+                    Some(Severity::Warning) => "warn",
+                    _ => "deny",
+                };
+                let value = match config.as_ref().and_then(|c| c.rule_options.get(rule.name())) {
+                    Some(serde_json::Value::Array(rule_options)) => {
+                        let mut value = vec![serde_json::Value::from(level)];
+                        value.extend(rule_options.iter().cloned());
+                        serde_json::Value::Array(value)
+                    }
+                    _ => serde_json::Value::from(level),
+                };
+                (rule.name().to_string(), value)
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        let env = config
+            .as_ref()
+            .map_or_else(Environment::default, |config| config.env)
+            .iter_names()
+            .map(|(name, _)| (name.to_lowercase(), serde_json::Value::Bool(true)))
+            .collect::<serde_json::Map<_, _>>();
+
+        let resolved = serde_json::json!({ "plugins": plugins, "env": env, "rules": rules });
+        println!("{}", serde_json::to_string_pretty(&resolved).unwrap());
+    }
+
+    /// Returns the enabled rules together with the severity of their diagnostics:
+    /// `-W` reports warnings and `-D` reports errors.
+    ///
+    /// The rules of the config file are applied first, or `-D correctness` without a config file,
+    /// followed by the `-A` / `-W` / `-D` flags. `--rule` then selects the rules to run, which are
+    /// denied unless they are already enabled.
+    pub(super) fn derive_rules(
+        options: &LintOptions,
+        config: Option<&LintConfig>,
+    ) -> (Vec<RuleEnum>, FxHashMap<&'static str, Severity>) {
+        let mut rules: FxHashMap<RuleEnum, Severity> = FxHashMap::default();
+
+        let default_rules = [(AllowWarnDeny::Deny, "correctness".to_string())];
+        let config_rules =
+            config.map_or(default_rules.as_slice(), |config| config.rules.as_slice());
+        let all_rules = RULES
+            .iter()
+            .filter(|rule| {
+                config.map_or(true, |config| config.is_plugin_enabled(rule.plugin_name()))
+            })
+            .collect::<Vec<_>>();
+
+        for (allow_warn_deny, name_or_category) in config_rules.iter().chain(&options.rules) {
             let maybe_category = RuleCategory::from(name_or_category.as_str());
-            match allow_warn_deny {
-                AllowWarnDeny::Deny => {
-                    match maybe_category {
-                        Some(category) => rules.extend(
-                            RULES.iter().filter(|rule| rule.category() == category).cloned(),
-                        ),
-                        None => {
-                            if name_or_category == "all" {
-                                rules.extend(RULES.iter().cloned());
-                            } else {
-                                rules.extend(
-                                    RULES
-                                        .iter()
-                                        .filter(|rule| rule.name() == name_or_category)
-                                        .cloned(),
-                                );
-                            }
-                        }
-                    };
-                }
+            let severity = match allow_warn_deny {
                 AllowWarnDeny::Allow => {
                     match maybe_category {
-                        Some(category) => rules.retain(|rule| rule.category() != category),
+                        Some(category) => rules.retain(|rule, _| rule.category() != category),
                         None => {
                             if name_or_category == "all" {
                                 rules.clear();
                             } else {
-                                rules.retain(|rule| rule.name() == name_or_category);
+                                rules.retain(|rule, _| rule.name() != name_or_category);
                             }
                         }
                     };
+                    continue;
                 }
-            }
+                AllowWarnDeny::Warn => Severity::Warning,
+                AllowWarnDeny::Deny => Severity::Error,
+            };
+            match maybe_category {
+                Some(category) => rules.extend(
+                    all_rules
+                        .iter()
+                        .filter(|rule| rule.category() == category)
+                        .map(|rule| ((*rule).clone(), severity)),
+                ),
+                None => {
+                    if name_or_category == "all" {
+                        rules.extend(all_rules.iter().map(|rule| ((*rule).clone(), severity)));
+                    } else {
+                        rules.extend(
+                            all_rules
+                                .iter()
+                                .filter(|rule| rule.name() == name_or_category)
+                                .map(|rule| ((*rule).clone(), severity)),
+                        );
+                    }
+                }
+            };
         }
 
-        let mut rules = rules.into_iter().collect::<Vec<_>>();
+        // `--rule` runs exactly the given rules, whether they are enabled or not.
+        if !options.only_rules.is_empty() {
+            rules = RULES
+                .iter()
+                .filter(|rule| options.only_rules.iter().any(|name| name == rule.name()))
+                .map(|rule| (rule.clone(), rules.get(rule).copied().unwrap_or(Severity::Error)))
+                .collect();
+        }
+
+        let rule_severities =
+            rules.iter().map(|(rule, severity)| (rule.name(), *severity)).collect();
+        let mut rules = rules
+            .into_keys()
+            .map(|rule| match config.and_then(|config| config.rule_options.get(rule.name())) {
+                Some(rule_options) => rule.read_json(Some(rule_options.clone())),
+                None => rule,
+            })
+            .collect::<Vec<_>>();
         // for stable diagnostics output ordering
         rules.sort_unstable_by_key(|rule| rule.name());
-        rules
+        (rules, rule_severities)
     }
 
-    fn print_execution_times(&self) {
-        let mut timings = self
-            .linter
+    fn print_execution_times(linter: &Linter) {
+        let mut timings = linter
             .rules()
             .iter()
             .map(|rule| (rule.name(), rule.execute_time()))
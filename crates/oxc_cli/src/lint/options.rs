@@ -1,25 +1,71 @@
-use std::{collections::BTreeMap, env, path::PathBuf};
+use std::{collections::BTreeMap, env, fmt, path::PathBuf};
 
 use clap::ArgMatches;
+use oxc_linter::{FixSuggestions, RuleCategory};
+use rustc_hash::FxHashSet;
 
-use super::command::lint_command;
+use super::{
+    baseline::{BaselineAction, BASELINE_FILE_NAME},
+    command::lint_command,
+};
 pub use super::{error::Error, isolated_handler::IsolatedLintHandler};
-use crate::runner::RunnerOptions;
+use crate::{git::ChangedSince, runner::RunnerOptions, walk::Shard};
 
 #[derive(Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct LintOptions {
     pub paths: Vec<PathBuf>,
-    /// Allow / Deny rules in order. [("allow" / "deny", rule name)]
-    /// Defaults to [("deny", "correctness")]
+    /// Allow / Warn / Deny rules in order. [("allow" / "warn" / "deny", rule name)]
+    /// Applied after the rules of the config file, which defaults to [("deny", "correctness")]
     pub rules: Vec<(AllowWarnDeny, String)>,
+    /// Only lint the files of this shard, set by `--shard <INDEX>/<COUNT>`
+    pub shard: Option<Shard>,
+    /// Only run these rules, set by `--rule`
+    pub only_rules: Vec<String>,
+    /// Path of the config file, defaults to `.oxlintrc.json` when it exists
+    pub config: Option<PathBuf>,
     pub list_rules: bool,
+    /// Only list the rules of this category
+    pub rules_category: Option<RuleCategory>,
+    /// Only list the rules that provide fixes or suggestions
+    pub rules_fixable: bool,
+    pub format: OutputFormat,
+    /// Write a starter config file
+    pub init: bool,
+    /// Print the configuration that applies to this file
+    pub print_config: Option<PathBuf>,
     pub fix: bool,
+    /// Compute fixes but print them as unified diffs instead of writing them to disk
+    pub fix_dry_run: bool,
+    /// Rules whose suggestions are applied in addition to safe fixes
+    pub fix_suggestions: FixSuggestions,
+    /// Fix files with uncommitted changes, or outside of a Git repository
+    pub fix_dirty: bool,
+    /// Only lint the files changed since a Git ref, and only report diagnostics on changed lines
+    pub changed: Option<ChangedSince>,
     pub quiet: bool,
     pub ignore_path: PathBuf,
     pub no_ignore: bool,
     pub ignore_pattern: Vec<String>,
     pub max_warnings: Option<usize>,
+    /// Recover from syntax errors and report up to this many of them per file,
+    /// instead of stopping at the first unrecoverable one
+    pub max_parse_errors: Option<usize>,
+    /// Report the errors of `tsc --isolatedModules`
+    pub isolated_modules: bool,
+    /// Write the report to this file instead of stdout, the summary is printed on stderr
+    pub output_file: Option<PathBuf>,
+    /// Findings of the baseline file are not reported
+    pub baseline: PathBuf,
+    /// Set by `oxlint baseline create` and `oxlint baseline prune`, which write the baseline file
+    /// instead of reporting the findings
+    pub baseline_action: Option<BaselineAction>,
+    /// Print a per-rule and per-file breakdown of the diagnostics after the run
+    pub statistics: bool,
+    /// Do not show the progress bar on stderr
+    pub no_progress: bool,
+    /// Exit with status 0 even if lint violations are found
+    pub exit_zero_even_if_error: bool,
     pub print_execution_times: bool,
 }
 
@@ -30,10 +76,26 @@ impl Default for LintOptions {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Default,
+    Json,
+}
+
+impl From<&str> for OutputFormat {
+    fn from(s: &str) -> Self {
+        match s {
+            "json" => Self::Json,
+            _ => Self::Default,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum AllowWarnDeny {
     Allow,
-    // Warn,
+    Warn,
     Deny,
 }
 
@@ -41,23 +103,58 @@ impl From<&'static str> for AllowWarnDeny {
     fn from(s: &'static str) -> Self {
         match s {
             "allow" => Self::Allow,
+            "warn" => Self::Warn,
             "deny" => Self::Deny,
             _ => unreachable!(),
         }
     }
 }
 
+impl fmt::Display for AllowWarnDeny {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => write!(f, "allow"),
+            Self::Warn => write!(f, "warn"),
+            Self::Deny => write!(f, "deny"),
+        }
+    }
+}
+
 impl<'a> From<&'a ArgMatches> for LintOptions {
     fn from(matches: &'a ArgMatches) -> Self {
         let list_rules = matches.get_flag("rules");
+        let init = matches.get_flag("init");
+        let print_config = matches.get_one::<PathBuf>("print-config").cloned();
 
         Self {
             paths: matches.get_many("path").map_or_else(
-                || if list_rules { vec![] } else { vec![PathBuf::from(".")] },
+                || {
+                    if list_rules || init || print_config.is_some() {
+                        vec![]
+                    } else {
+                        vec![PathBuf::from(".")]
+                    }
+                },
                 |paths| paths.into_iter().cloned().collect(),
             ),
             rules: Self::get_rules(matches),
+            shard: matches.get_one::<Shard>("shard").copied(),
+            only_rules: matches
+                .get_many::<String>("rule")
+                .map(|rules| rules.cloned().collect())
+                .unwrap_or_default(),
+            config: matches.get_one::<PathBuf>("config").cloned(),
             fix: matches.get_flag("fix"),
+            fix_dry_run: matches.get_flag("fix-dry-run"),
+            fix_suggestions: Self::get_fix_suggestions(matches),
+            fix_dirty: matches.get_flag("fix-dirty"),
+            changed: matches.get_one::<String>("changed").map(|since| {
+                if since.is_empty() {
+                    ChangedSince::DefaultBranch
+                } else {
+                    ChangedSince::Ref(since.clone())
+                }
+            }),
             quiet: matches.get_flag("quiet"),
             ignore_path: matches
                 .get_one::<PathBuf>("ignore-path")
@@ -68,7 +165,27 @@ impl<'a> From<&'a ArgMatches> for LintOptions {
                 .map(|patterns| patterns.into_iter().cloned().collect())
                 .unwrap_or_default(),
             max_warnings: matches.get_one("max-warnings").copied(),
+            max_parse_errors: matches.get_one("max-parse-errors").copied(),
+            isolated_modules: matches.get_flag("isolated-modules"),
+            exit_zero_even_if_error: matches.get_flag("exit-zero-even-if-error"),
+            baseline: matches
+                .get_one::<PathBuf>("baseline")
+                .map_or_else(|| PathBuf::from(BASELINE_FILE_NAME), Clone::clone),
+            baseline_action: None,
+            statistics: matches.get_flag("statistics"),
+            no_progress: matches.get_flag("no-progress"),
+            output_file: matches.get_one::<PathBuf>("output-file").cloned(),
             list_rules,
+            rules_category: matches
+                .get_one::<String>("category")
+                .and_then(|category| RuleCategory::from(category)),
+            rules_fixable: matches.get_flag("fixable"),
+            format: matches
+                .get_one::<String>("format")
+                .map(|format| OutputFormat::from(format.as_str()))
+                .unwrap_or_default(),
+            init,
+            print_config,
             print_execution_times: matches!(env::var("TIMING"), Ok(x) if x == "true" || x == "1"),
         }
     }
@@ -76,11 +193,10 @@ impl<'a> From<&'a ArgMatches> for LintOptions {
 
 impl LintOptions {
     /// Get all rules in order, e.g.
-    /// `-A all -D no-var -D -eqeqeq` => [("allow", "all"), ("deny", "no-var"), ("deny", "eqeqeq")]
-    /// Defaults to [("deny", "correctness")];
+    /// `-A all -D no-var -W eqeqeq` => [("allow", "all"), ("deny", "no-var"), ("warn", "eqeqeq")]
     fn get_rules(matches: &ArgMatches) -> Vec<(AllowWarnDeny, String)> {
         let mut map: BTreeMap<usize, (AllowWarnDeny, String)> = BTreeMap::new();
-        for key in ["allow", "deny"] {
+        for key in ["allow", "warn", "deny"] {
             let allow_warn_deny = AllowWarnDeny::from(key);
             if let Some(values) = matches.get_many::<String>(key) {
                 let indices = matches.indices_of(key).unwrap();
@@ -89,10 +205,33 @@ impl LintOptions {
                 map.extend(zipped);
             }
         }
-        if map.is_empty() {
-            vec![(AllowWarnDeny::Deny, "correctness".into())]
+        map.into_values().collect()
+    }
+}
+
+impl LintOptions {
+    /// Whether any kind of fix is requested, i.e. `--fix`, `--fix-dry-run` or `--fix-suggestions`.
+    pub fn has_fix(&self) -> bool {
+        self.fix || self.fix_dry_run || !matches!(self.fix_suggestions, FixSuggestions::None)
+    }
+
+    /// Whether fixes are written to the files, i.e. `--fix` or `--fix-suggestions` without
+    /// `--fix-dry-run`.
+    pub fn fix_in_place(&self) -> bool {
+        self.has_fix() && !self.fix_dry_run
+    }
+
+    /// `--fix-suggestions` => `All`
+    /// `--fix-suggestions=no-var,eqeqeq` => `Rules(["no-var", "eqeqeq"])`
+    fn get_fix_suggestions(matches: &ArgMatches) -> FixSuggestions {
+        let Some(rules) = matches.get_many::<String>("fix-suggestions") else {
+            return FixSuggestions::None;
+        };
+        let rules = rules.cloned().collect::<FxHashSet<_>>();
+        if rules.contains("all") {
+            FixSuggestions::All
         } else {
-            map.into_values().collect()
+            FixSuggestions::Rules(rules)
         }
     }
 }
@@ -109,9 +248,10 @@ mod test {
     use std::path::PathBuf;
 
     use clap::Command;
+    use oxc_linter::{FixSuggestions, RuleCategory};
 
-    use super::{AllowWarnDeny, LintOptions};
-    use crate::runner::RunnerOptions;
+    use super::{AllowWarnDeny, LintOptions, OutputFormat};
+    use crate::{git::ChangedSince, runner::RunnerOptions, walk::Shard};
 
     #[test]
     fn verify_command() {
@@ -130,11 +270,17 @@ mod test {
         let options = get_lint_options("lint .");
         assert_eq!(options.paths, vec![PathBuf::from(".")]);
         assert!(!options.fix);
+        assert!(!options.fix_dry_run);
+        assert!(matches!(options.fix_suggestions, FixSuggestions::None));
         assert!(!options.quiet);
         assert_eq!(options.ignore_path, PathBuf::from(".eslintignore"));
         assert!(!options.no_ignore);
         assert!(options.ignore_pattern.is_empty());
         assert_eq!(options.max_warnings, None);
+        assert_eq!(options.max_parse_errors, None);
+        assert!(!options.isolated_modules);
+        assert!(options.rules.is_empty());
+        assert_eq!(options.config, None);
     }
 
     #[test]
@@ -162,6 +308,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn rules_with_warn() {
+        let options = get_lint_options("lint src -W correctness -D no-debugger --warn eqeqeq");
+        assert_eq!(
+            options.rules,
+            vec![
+                (AllowWarnDeny::Warn, "correctness".into()),
+                (AllowWarnDeny::Deny, "no-debugger".into()),
+                (AllowWarnDeny::Warn, "eqeqeq".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn only_rules() {
+        let options = get_lint_options("lint --rule no-debugger --rule no-empty foo.js");
+        assert_eq!(options.only_rules, vec!["no-debugger", "no-empty"]);
+        let result = LintOptions::build_args(Command::new("oxc"))
+            .try_get_matches_from("lint --rule no-such-rule foo.js".split(' '));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn shard() {
+        let options = get_lint_options("lint --shard 2/8 foo.js");
+        assert_eq!(options.shard, Some(Shard::parse("2/8").unwrap()));
+        let result = LintOptions::build_args(Command::new("oxc"))
+            .try_get_matches_from("lint --shard 9/8 foo.js".split(' '));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn shard_json_report() {
+        let options = get_lint_options("lint --shard 1/2 --format json src");
+        assert_eq!(options.shard, Some(Shard::parse("1/2").unwrap()));
+        assert_eq!(options.format, OutputFormat::Json);
+        assert!(options.rules.is_empty());
+    }
+
     #[test]
     fn quiet_true() {
         let options = get_lint_options("lint foo.js --quiet");
@@ -174,12 +359,116 @@ mod test {
         assert!(options.fix);
     }
 
+    #[test]
+    fn fix_dry_run_true() {
+        let options = get_lint_options("lint foo.js --fix-dry-run");
+        assert!(options.fix_dry_run);
+        assert!(!options.fix);
+    }
+
+    #[test]
+    fn fix_and_fix_dry_run_conflict() {
+        let result = LintOptions::build_args(Command::new("oxc"))
+            .try_get_matches_from("lint foo.js --fix --fix-dry-run".split(' '));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn changed() {
+        let options = get_lint_options("lint foo.js");
+        assert_eq!(options.changed, None);
+        let options = get_lint_options("lint --changed foo.js");
+        assert_eq!(options.changed, Some(ChangedSince::DefaultBranch));
+        assert_eq!(options.paths, vec![PathBuf::from("foo.js")]);
+        let options = get_lint_options("lint --changed=HEAD~1 foo.js");
+        assert_eq!(options.changed, Some(ChangedSince::Ref("HEAD~1".to_string())));
+    }
+
+    #[test]
+    fn fix_dirty() {
+        let options = get_lint_options("lint --fix foo.js");
+        assert!(options.fix_in_place());
+        assert!(!options.fix_dirty);
+        let options = get_lint_options("lint --fix --fix-dirty foo.js");
+        assert!(options.fix_dirty);
+        let options = get_lint_options("lint --fix-dry-run foo.js");
+        assert!(!options.fix_in_place());
+    }
+
+    #[test]
+    fn fix_suggestions_all() {
+        let options = get_lint_options("lint --fix-suggestions foo.js");
+        assert!(matches!(options.fix_suggestions, FixSuggestions::All));
+        assert_eq!(options.paths, vec![PathBuf::from("foo.js")]);
+        assert!(options.has_fix());
+    }
+
+    #[test]
+    fn fix_suggestions_rules() {
+        let options = get_lint_options("lint --fix-suggestions=no-var,eqeqeq foo.js");
+        let FixSuggestions::Rules(rules) = options.fix_suggestions else {
+            panic!("expected a list of rules");
+        };
+        assert_eq!(rules.len(), 2);
+        assert!(rules.contains("no-var"));
+        assert!(rules.contains("eqeqeq"));
+    }
+
     #[test]
     fn max_warnings() {
         let options = get_lint_options("lint --max-warnings 10 foo.js");
         assert_eq!(options.max_warnings, Some(10));
     }
 
+    #[test]
+    fn max_parse_errors() {
+        let options = get_lint_options("lint --max-parse-errors 20 foo.js");
+        assert_eq!(options.max_parse_errors, Some(20));
+    }
+
+    #[test]
+    fn isolated_modules() {
+        let options = get_lint_options("lint --isolated-modules foo.ts");
+        assert!(options.isolated_modules);
+    }
+
+    #[test]
+    fn baseline() {
+        let options = get_lint_options("lint foo.js");
+        assert_eq!(options.baseline, PathBuf::from(".oxlint-baseline.json"));
+        let options = get_lint_options("lint --baseline baseline.json foo.js");
+        assert_eq!(options.baseline, PathBuf::from("baseline.json"));
+        assert_eq!(options.baseline_action, None);
+    }
+
+    #[test]
+    fn statistics() {
+        let options = get_lint_options("lint --statistics foo.js");
+        assert!(options.statistics);
+    }
+
+    #[test]
+    fn no_progress() {
+        let options = get_lint_options("lint foo.js");
+        assert!(!options.no_progress);
+        let options = get_lint_options("lint --no-progress foo.js");
+        assert!(options.no_progress);
+    }
+
+    #[test]
+    fn output_file() {
+        let options = get_lint_options("lint -o report.txt foo.js");
+        assert_eq!(options.output_file, Some(PathBuf::from("report.txt")));
+        let options = get_lint_options("lint --output-file report.txt foo.js");
+        assert_eq!(options.output_file, Some(PathBuf::from("report.txt")));
+    }
+
+    #[test]
+    fn exit_zero_even_if_error() {
+        let options = get_lint_options("lint --exit-zero-even-if-error foo.js");
+        assert!(options.exit_zero_even_if_error);
+    }
+
     #[test]
     fn ignore_path() {
         let options = get_lint_options("lint --ignore-path .xxx foo.js");
@@ -205,6 +494,36 @@ mod test {
         assert_eq!(options.ignore_pattern, vec![String::from("./test"), String::from("bar.js")]);
     }
 
+    #[test]
+    fn config() {
+        let options = get_lint_options("lint -c oxlintrc.json foo.js");
+        assert_eq!(options.config, Some(PathBuf::from("oxlintrc.json")));
+    }
+
+    #[test]
+    fn init_true() {
+        let options = get_lint_options("lint --init");
+        assert!(options.paths.is_empty());
+        assert!(options.init);
+    }
+
+    #[test]
+    fn print_config() {
+        let options = get_lint_options("lint --print-config src/index.ts");
+        assert!(options.paths.is_empty());
+        assert_eq!(options.print_config, Some(PathBuf::from("src/index.ts")));
+    }
+
+    #[test]
+    fn list_rules_with_filters() {
+        let options =
+            get_lint_options("lint --rules --format json --category correctness --fixable");
+        assert!(options.list_rules);
+        assert_eq!(options.format, OutputFormat::Json);
+        assert_eq!(options.rules_category, Some(RuleCategory::Correctness));
+        assert!(options.rules_fixable);
+    }
+
     #[test]
     fn list_rules_true() {
         let options = get_lint_options("lint --rules");
@@ -0,0 +1,115 @@
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Runs shorter than this finish before anything is drawn, so small scans stay silent.
+const DELAY: Duration = Duration::from_millis(500);
+const TICK: Duration = Duration::from_millis(100);
+
+/// Renders `processed / found files` and the throughput on stderr while linting.
+pub struct Progress {
+    done: Arc<AtomicBool>,
+    line: Arc<Mutex<Line>>,
+    renderer: thread::JoinHandle<()>,
+}
+
+/// The state of the terminal shared by the progress line and the other output,
+/// locked while either of them writes.
+#[derive(Default)]
+struct Line {
+    /// Whether the progress line is drawn
+    drawn: bool,
+    /// Whether the other output is in the middle of a line, which must not be overwritten
+    in_line: bool,
+}
+
+impl Line {
+    fn clear(&mut self) {
+        if self.drawn {
+            let mut stderr = io::stderr().lock();
+            write!(stderr, "\r\x1b[2K").unwrap();
+            stderr.flush().unwrap();
+            self.drawn = false;
+        }
+    }
+}
+
+impl Progress {
+    /// `found` is still growing while the directories are being walked.
+    pub fn start(found: Arc<AtomicUsize>, processed: Arc<AtomicUsize>) -> Self {
+        let done = Arc::new(AtomicBool::new(false));
+        let line = Arc::new(Mutex::new(Line::default()));
+        let renderer = {
+            let done = Arc::clone(&done);
+            let line = Arc::clone(&line);
+            thread::spawn(move || {
+                let start = Instant::now();
+                while !done.load(Ordering::Relaxed) {
+                    thread::sleep(TICK);
+                    let elapsed = start.elapsed();
+                    if elapsed < DELAY {
+                        continue;
+                    }
+                    let mut line = line.lock().unwrap();
+                    if line.in_line {
+                        continue;
+                    }
+                    let processed = processed.load(Ordering::Relaxed);
+                    let found = found.load(Ordering::Relaxed);
+                    #[allow(clippy::cast_precision_loss)]
+                    let rate = processed as f64 / elapsed.as_secs_f64();
+                    let mut stderr = io::stderr().lock();
+                    write!(stderr, "\r\x1b[2K{processed}/{found} files ({rate:.0} files/s)")
+                        .unwrap();
+                    stderr.flush().unwrap();
+                    line.drawn = true;
+                }
+                line.lock().unwrap().clear();
+            })
+        };
+        Self { done, line, renderer }
+    }
+
+    /// A writer which clears the progress line before writing to `writer`, so that the output,
+    /// e.g. diagnostics on stdout in the same terminal, does not interleave with it.
+    pub fn wrap<W: Write>(&self, writer: W) -> ProgressWriter<W> {
+        ProgressWriter { line: Arc::clone(&self.line), writer }
+    }
+
+    /// Stops rendering and clears the progress line.
+    pub fn finish(self) {
+        self.done.store(true, Ordering::Relaxed);
+        self.renderer.join().unwrap();
+    }
+}
+
+/// See [`Progress::wrap`]
+pub struct ProgressWriter<W> {
+    line: Arc<Mutex<Line>>,
+    writer: W,
+}
+
+impl<W: Write> Write for ProgressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut line = self.line.lock().unwrap();
+        line.clear();
+        let written = self.writer.write(buf)?;
+        // Written through before the progress line is drawn again
+        self.writer.flush()?;
+        if written > 0 {
+            line.in_line = buf[written - 1] != b'\n';
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let _line = self.line.lock().unwrap();
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,66 @@
+use std::{path::Path, sync::Arc};
+
+use dashmap::DashMap;
+use oxc_linter::{Environment, Linter};
+
+use super::{
+    config::{ConfigError, LintConfig},
+    options::LintOptions,
+    LintRunner,
+};
+
+/// Resolves the linter of each file, as `overrides` in the config file may configure
+/// different rules for different files.
+pub struct LinterResolver {
+    options: Arc<LintOptions>,
+    config: Option<LintConfig>,
+    /// The linter for files not matched by any override.
+    linter: Arc<Linter>,
+    /// Linters keyed by the indices of the matched overrides.
+    override_linters: DashMap<Vec<usize>, Arc<Linter>>,
+}
+
+impl LinterResolver {
+    /// # Errors
+    ///
+    /// * The config file cannot be read or parsed
+    pub fn new(options: Arc<LintOptions>) -> Result<Self, ConfigError> {
+        let config = LintConfig::find(options.config.as_deref())?.map(|(_, config)| config);
+        let linter = Arc::new(Self::build_linter(&options, config.as_ref()));
+        Ok(Self { options, config, linter, override_linters: DashMap::default() })
+    }
+
+    pub fn config(&self) -> Option<&LintConfig> {
+        self.config.as_ref()
+    }
+
+    pub fn linter(&self) -> &Arc<Linter> {
+        &self.linter
+    }
+
+    pub fn linter_for(&self, path: &Path) -> Arc<Linter> {
+        let Some(config) = &self.config else { return Arc::clone(&self.linter) };
+        let overrides = config.matching_overrides(path);
+        if overrides.is_empty() {
+            return Arc::clone(&self.linter);
+        }
+        if let Some(linter) = self.override_linters.get(&overrides) {
+            return Arc::clone(&linter);
+        }
+        let config = config.with_overrides(&overrides);
+        let linter = Arc::new(Self::build_linter(&self.options, Some(&config)));
+        self.override_linters.insert(overrides, Arc::clone(&linter));
+        linter
+    }
+
+    fn build_linter(options: &LintOptions, config: Option<&LintConfig>) -> Linter {
+        let (rules, rule_severities) = LintRunner::derive_rules(options, config);
+        let environment = config.map_or_else(Environment::default, |config| config.env);
+        Linter::from_rules(rules)
+            .with_rule_severities(rule_severities)
+            .with_environment(environment)
+            .with_fix(options.has_fix())
+            .with_fix_suggestions(options.fix_suggestions.clone())
+            .with_print_execution_times(options.print_execution_times)
+    }
+}
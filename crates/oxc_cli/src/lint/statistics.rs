@@ -0,0 +1,66 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use rustc_hash::FxHashMap;
+
+/// Number of files listed under "Top files".
+const TOP_FILES: usize = 10;
+
+/// Per-rule and per-file breakdown of the reported diagnostics, printed with `--statistics`.
+#[derive(Debug, Default)]
+pub struct Statistics {
+    rules: FxHashMap<String, RuleStatistics>,
+    files: FxHashMap<PathBuf, usize>,
+}
+
+#[derive(Debug, Default)]
+struct RuleStatistics {
+    violations: usize,
+    fixable: usize,
+}
+
+impl Statistics {
+    /// `rule_name` is `None` for parser and semantic errors, which are counted as `syntax`.
+    pub fn add(&mut self, path: &Path, rule_name: Option<&str>, fixable: bool) {
+        let rule = self.rules.entry(rule_name.unwrap_or("syntax").to_string()).or_default();
+        rule.violations += 1;
+        if fixable {
+            rule.fixable += 1;
+        }
+        *self.files.entry(path.to_path_buf()).or_default() += 1;
+    }
+
+    /// # Errors
+    ///
+    /// * Writing to `writer` fails
+    pub fn print<W: Write>(&self, writer: &mut W, duration: Duration) -> std::io::Result<()> {
+        let mut rules = self.rules.iter().collect::<Vec<_>>();
+        rules.sort_unstable_by(|(a_name, a), (b_name, b)| {
+            b.violations.cmp(&a.violations).then_with(|| a_name.cmp(b_name))
+        });
+        let mut files = self.files.iter().collect::<Vec<_>>();
+        files
+            .sort_unstable_by(|(a_path, a), (b_path, b)| b.cmp(a).then_with(|| a_path.cmp(b_path)));
+
+        writeln!(writer)?;
+        writeln!(writer, "Rule statistics:")?;
+        writeln!(writer, "{:>8} {:>8}  rule", "count", "fixable")?;
+        for (name, rule) in rules {
+            writeln!(writer, "{:>8} {:>8}  {name}", rule.violations, rule.fixable)?;
+        }
+
+        writeln!(writer)?;
+        writeln!(writer, "Top files:")?;
+        writeln!(writer, "{:>8}  file", "count")?;
+        for (path, count) in files.into_iter().take(TOP_FILES) {
+            writeln!(writer, "{count:>8}  {}", path.display())?;
+        }
+
+        writeln!(writer)?;
+        writeln!(writer, "Total time: {}ms", duration.as_millis())?;
+        Ok(())
+    }
+}
@@ -9,10 +9,13 @@ static GLOBAL: jemallocator::Jemalloc = jemallocator::Jemalloc;
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 use oxc_cli::{
-    command, CliRunResult, LintOptions, LintRunner, Runner, TypeCheckOptions, TypeCheckRunner,
+    command, install_panic_hook, CliRunResult, FormatOptions, FormatRunner, LintOptions,
+    LintRunner, Runner, StatsOptions, StatsRunner, TypeCheckOptions, TypeCheckRunner,
 };
 
 fn main() -> CliRunResult {
+    install_panic_hook();
+
     let matches = command().get_matches();
 
     if let Some(threads) = matches.get_one::<usize>("threads") {
@@ -31,6 +34,14 @@ fn main() -> CliRunResult {
             let options = TypeCheckOptions::from(matches);
             TypeCheckRunner::new(options).run()
         }
+        FormatRunner::NAME => {
+            let options = FormatOptions::from(matches);
+            FormatRunner::new(options).run()
+        }
+        StatsRunner::NAME => {
+            let options = StatsOptions::from(matches);
+            StatsRunner::new(options).run()
+        }
         _ => CliRunResult::None,
     }
 }
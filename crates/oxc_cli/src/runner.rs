@@ -1,4 +1,5 @@
 use std::{
+    io::{self, Write},
     path::PathBuf,
     process::{ExitCode, Termination},
 };
@@ -28,10 +29,33 @@ pub trait Runner: Send + Sync {
     fn run(&self) -> CliRunResult;
 }
 
+/// Exit codes of the CLI, so that scripts can tell lint violations apart from failures of the tool.
+pub mod exit_code {
+    /// No lint violations were found.
+    pub const SUCCESS: u8 = 0;
+    /// Lint violations were found, or the number of warnings exceeded `--max-warnings`.
+    pub const LINT_VIOLATIONS: u8 = 1;
+    /// The tool itself failed, e.g. a path does not exist, a config file is invalid, or a panic.
+    pub const TOOL_ERROR: u8 = 2;
+}
+
+/// Exit with [`exit_code::TOOL_ERROR`] on panic, so that a crash is not mistaken for lint
+/// violations.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        std::process::exit(i32::from(exit_code::TOOL_ERROR));
+    }));
+}
+
 #[derive(Debug)]
 pub enum CliRunResult {
     None,
     IOError(crate::lint::Error),
+    ConfigError(oxc_diagnostics::Error),
+    /// `--fix` refused to modify files which are not committed, or `--changed` failed.
+    VcsError(oxc_diagnostics::Error),
     PathNotFound {
         paths: Vec<PathBuf>,
     },
@@ -41,25 +65,53 @@ pub enum CliRunResult {
         number_of_files: usize,
         number_of_warnings: usize,
         number_of_errors: usize,
-        max_warnings_exceeded: bool,
+        number_of_applied_suggestions: usize,
+        /// The `--max-warnings` threshold, if any.
+        max_warnings: Option<usize>,
+        /// `--exit-zero-even-if-error`, report lint violations without failing.
+        exit_zero: bool,
+        /// Print the summary on stderr, when the report is written to `--output-file`.
+        summary_to_stderr: bool,
     },
     TypeCheckResult {
         duration: std::time::Duration,
         number_of_diagnostics: usize,
     },
+    DoctorResult {
+        number_of_problems: usize,
+    },
+    FormatResult {
+        duration: std::time::Duration,
+        number_of_files: usize,
+        /// Files which differ from their formatted output, with `--check`
+        number_of_unformatted_files: usize,
+        /// Files which could not be read or parsed
+        number_of_parse_errors: usize,
+    },
+    StatsResult {
+        duration: std::time::Duration,
+        number_of_files: usize,
+        /// Files which could not be read or parsed
+        number_of_parse_errors: usize,
+    },
 }
 
 impl Termination for CliRunResult {
+    #[allow(clippy::too_many_lines)]
     fn report(self) -> ExitCode {
         match self {
-            Self::None => ExitCode::from(0),
+            Self::None => ExitCode::from(exit_code::SUCCESS),
             Self::PathNotFound { paths } => {
                 println!("Path {paths:?} does not exist.");
-                ExitCode::from(1)
+                ExitCode::from(exit_code::TOOL_ERROR)
             }
             Self::IOError(e) => {
                 println!("IO Error: {e}");
-                ExitCode::from(1)
+                ExitCode::from(exit_code::TOOL_ERROR)
+            }
+            Self::ConfigError(e) | Self::VcsError(e) => {
+                println!("{e:?}");
+                ExitCode::from(exit_code::TOOL_ERROR)
             }
             Self::LintResult {
                 duration,
@@ -67,37 +119,72 @@ impl Termination for CliRunResult {
                 number_of_files,
                 number_of_warnings,
                 number_of_errors,
-                max_warnings_exceeded,
+                number_of_applied_suggestions,
+                max_warnings,
+                exit_zero,
+                summary_to_stderr,
             } => {
+                let mut out: Box<dyn Write> =
+                    if summary_to_stderr { Box::new(io::stderr()) } else { Box::new(io::stdout()) };
                 let ms = duration.as_millis();
                 let threads = rayon::current_num_threads();
                 let number_of_diagnostics = number_of_warnings + number_of_errors;
 
                 if number_of_diagnostics > 0 {
-                    println!();
+                    writeln!(out).unwrap();
                 }
 
-                println!(
+                writeln!(
+                    out,
                     "Finished in {ms}ms on {number_of_files} files with {number_of_rules} rules using {threads} threads."
-                );
+                )
+                .unwrap();
 
-                if max_warnings_exceeded {
-                    println!("Exceeded maximum number of warnings. Found {number_of_warnings}.");
-                    return ExitCode::from(1);
+                if number_of_applied_suggestions > 0 {
+                    let suggestions = if number_of_applied_suggestions == 1 {
+                        "suggestion"
+                    } else {
+                        "suggestions"
+                    };
+                    writeln!(
+                        out,
+                        "Applied {number_of_applied_suggestions} {suggestions}, which may change the semantics of your code."
+                    )
+                    .unwrap();
                 }
 
                 if number_of_diagnostics > 0 {
                     let warnings = if number_of_warnings == 1 { "warning" } else { "warnings" };
                     let errors = if number_of_errors == 1 { "error" } else { "errors" };
-                    println!(
+                    writeln!(
+                        out,
                         "Found {number_of_warnings} {warnings} and {number_of_errors} {errors}."
-                    );
-                    return ExitCode::from(1);
+                    )
+                    .unwrap();
                 }
 
+                let failed = match max_warnings {
+                    Some(max_warnings) if number_of_warnings > max_warnings => {
+                        writeln!(
+                            out,
+                            "Exceeded maximum number of warnings ({max_warnings}). Found {number_of_warnings}."
+                        )
+                        .unwrap();
+                        true
+                    }
+                    // Warnings within the threshold are tolerated, so that the threshold can be
+                    // ratcheted down over time.
+                    Some(_) => number_of_errors > 0,
+                    None => number_of_diagnostics > 0,
+                };
+
                 // eslint does not print anything after success, so we do the same.
                 // It is also standard to not print anything after success in the *nix world.
-                ExitCode::from(0)
+                if failed && !exit_zero {
+                    ExitCode::from(exit_code::LINT_VIOLATIONS)
+                } else {
+                    ExitCode::from(exit_code::SUCCESS)
+                }
             }
             Self::TypeCheckResult { duration, number_of_diagnostics } => {
                 let ms = duration.as_millis();
@@ -105,10 +192,49 @@ impl Termination for CliRunResult {
 
                 if number_of_diagnostics > 0 {
                     println!("Found {number_of_diagnostics} errors.");
-                    return ExitCode::from(1);
+                    return ExitCode::from(exit_code::LINT_VIOLATIONS);
                 }
 
-                ExitCode::from(0)
+                ExitCode::from(exit_code::SUCCESS)
+            }
+            Self::DoctorResult { number_of_problems } => {
+                if number_of_problems > 0 {
+                    let problems = if number_of_problems == 1 { "problem" } else { "problems" };
+                    println!("\nFound {number_of_problems} {problems}.");
+                    return ExitCode::from(exit_code::LINT_VIOLATIONS);
+                }
+                println!("\nNo problems found.");
+                ExitCode::from(exit_code::SUCCESS)
+            }
+            Self::FormatResult {
+                duration,
+                number_of_files,
+                number_of_unformatted_files,
+                number_of_parse_errors,
+            } => {
+                // The formatted code may be printed on stdout, so the summary goes to stderr.
+                let ms = duration.as_millis();
+                eprintln!("Finished in {ms}ms on {number_of_files} files.");
+
+                if number_of_parse_errors > 0 {
+                    eprintln!("Failed to format {number_of_parse_errors} files.");
+                    return ExitCode::from(exit_code::TOOL_ERROR);
+                }
+                if number_of_unformatted_files > 0 {
+                    eprintln!("Found {number_of_unformatted_files} unformatted files.");
+                    return ExitCode::from(exit_code::LINT_VIOLATIONS);
+                }
+                ExitCode::from(exit_code::SUCCESS)
+            }
+            Self::StatsResult { duration, number_of_files, number_of_parse_errors } => {
+                let ms = duration.as_millis();
+                eprintln!("Finished in {ms}ms on {number_of_files} files.");
+
+                if number_of_parse_errors > 0 {
+                    eprintln!("Failed to analyze {number_of_parse_errors} files.");
+                    return ExitCode::from(exit_code::TOOL_ERROR);
+                }
+                ExitCode::from(exit_code::SUCCESS)
             }
         }
     }
@@ -158,6 +284,42 @@ mod tests {
         }
     }
 
+    fn lint_result(
+        number_of_warnings: usize,
+        number_of_errors: usize,
+        max_warnings: Option<usize>,
+        exit_zero: bool,
+    ) -> CliRunResult {
+        CliRunResult::LintResult {
+            duration: std::time::Duration::default(),
+            number_of_rules: 0,
+            number_of_files: 0,
+            number_of_warnings,
+            number_of_errors,
+            number_of_applied_suggestions: 0,
+            max_warnings,
+            exit_zero,
+            summary_to_stderr: false,
+        }
+    }
+
+    #[test]
+    fn max_warnings_exit_code() {
+        assert_eq!(lint_result(0, 0, None, false).report(), ExitCode::from(0));
+        assert_eq!(lint_result(1, 0, None, false).report(), ExitCode::from(1));
+        assert_eq!(lint_result(2, 0, Some(2), false).report(), ExitCode::from(0));
+        assert_eq!(lint_result(3, 0, Some(2), false).report(), ExitCode::from(1));
+        assert_eq!(lint_result(0, 1, Some(2), false).report(), ExitCode::from(1));
+    }
+
+    #[test]
+    fn exit_zero_even_if_error() {
+        assert_eq!(lint_result(1, 1, None, true).report(), ExitCode::from(0));
+        assert_eq!(lint_result(3, 0, Some(2), true).report(), ExitCode::from(0));
+        let result = CliRunResult::PathNotFound { paths: vec![PathBuf::from("foo")] };
+        assert_eq!(result.report(), ExitCode::from(exit_code::TOOL_ERROR));
+    }
+
     #[test]
     fn check_cmd_validity() {
         TestRunner::command().debug_assert();
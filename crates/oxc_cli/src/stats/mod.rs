@@ -0,0 +1,141 @@
+use std::{fmt::Write, fs, path::PathBuf};
+
+use clap::{builder::ValueParser, Arg, ArgMatches, Command};
+use oxc_allocator::Allocator;
+use oxc_ast::stats::AstStats;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::{
+    runner::{Runner, RunnerOptions},
+    CliRunResult, Walk,
+};
+
+#[derive(Debug)]
+pub struct StatsOptions {
+    pub paths: Vec<PathBuf>,
+    /// Only print the files with the most nodes
+    pub top: Option<usize>,
+}
+
+#[allow(clippy::fallible_impl_from)]
+impl<'a> From<&'a ArgMatches> for StatsOptions {
+    fn from(matches: &'a ArgMatches) -> Self {
+        Self {
+            paths: matches
+                .get_many::<PathBuf>("path")
+                .map_or_else(Vec::new, |paths| paths.cloned().collect()),
+            top: matches.get_one::<usize>("top").copied(),
+        }
+    }
+}
+
+impl RunnerOptions for StatsOptions {
+    fn build_args(cmd: Command) -> Command {
+        cmd.arg(
+            Arg::new("path")
+                .value_name("PATH")
+                .num_args(1..)
+                .required(true)
+                .value_parser(ValueParser::path_buf())
+                .help("File or Directory paths to analyze. Directories are analyzed recursively."),
+        )
+        .arg(
+            Arg::new("top")
+                .long("top")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .help("Only print the N files with the most AST nodes"),
+        )
+    }
+}
+
+/// Prints statistics about the AST of each file, largest files first,
+/// for finding the files which dominate the time of the other commands
+pub struct StatsRunner {
+    options: StatsOptions,
+}
+
+impl Runner for StatsRunner {
+    type Options = StatsOptions;
+
+    const ABOUT: &'static str =
+        "Print AST statistics of files, such as node counts and nesting depth";
+    const NAME: &'static str = "stats";
+
+    fn new(options: StatsOptions) -> Self {
+        Self { options }
+    }
+
+    fn run(&self) -> CliRunResult {
+        let now = std::time::Instant::now();
+
+        let paths =
+            self.options.paths.iter().filter(|path| !path.exists()).cloned().collect::<Vec<_>>();
+        if !paths.is_empty() {
+            return CliRunResult::PathNotFound { paths };
+        }
+
+        let files = Walk::source_files(&self.options.paths);
+        let number_of_files = files.len();
+        let mut number_of_parse_errors = 0;
+        let mut summaries = vec![];
+        for path in files {
+            let Ok(source_text) = fs::read_to_string(&path) else {
+                eprintln!("Failed to read {}", path.display());
+                number_of_parse_errors += 1;
+                continue;
+            };
+            let allocator = Allocator::default();
+            let source_type = SourceType::from_path(&path).unwrap_or_default();
+            let ret = Parser::new(&allocator, &source_text, source_type).parse();
+            if !ret.errors.is_empty() {
+                eprintln!("Failed to parse {}", path.display());
+                number_of_parse_errors += 1;
+                continue;
+            }
+            let program = allocator.alloc(ret.program);
+            let stats = AstStats::new(program);
+            summaries.push((stats.node_count(), summary(&path, source_text.len(), &stats)));
+        }
+
+        summaries.sort_by_key(|(node_count, _)| std::cmp::Reverse(*node_count));
+        let top = self.options.top.unwrap_or(summaries.len());
+        for (_, summary) in summaries.iter().take(top) {
+            println!("{summary}");
+        }
+
+        CliRunResult::StatsResult {
+            duration: now.elapsed(),
+            number_of_files,
+            number_of_parse_errors,
+        }
+    }
+}
+
+fn summary(path: &std::path::Path, source_len: usize, stats: &AstStats) -> String {
+    let mut summary = format!(
+        "{}: {source_len} bytes, {} nodes, depth {}, {} functions",
+        path.display(),
+        stats.node_count(),
+        stats.max_depth,
+        stats.functions.len(),
+    );
+    if let Some(function) = stats.largest_function() {
+        let name = function.name.as_ref().map_or("<anonymous>", |name| name.as_str());
+        let _ = write!(
+            summary,
+            " (largest {name} at {} with {} nodes)",
+            function.span.start, function.node_count
+        );
+    }
+    let _ = write!(
+        summary,
+        ", {} strings ({} bytes), {} identifiers ({} unique)",
+        stats.string_count,
+        stats.string_bytes,
+        stats.identifier_count,
+        stats.unique_identifier_count
+    );
+    summary
+}
@@ -1,52 +1,228 @@
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use ignore::{overrides::OverrideBuilder, DirEntry, WalkBuilder};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
 use oxc_span::VALID_EXTENSIONS;
 
 use crate::LintOptions;
 
+/// Characters which turn a path argument into a glob pattern, e.g. `"src/**/*.ts"`.
+///
+/// Extglob patterns, e.g. `"src/@(a|b)/*.ts"`, are not supported.
+const GLOB_CHARS: [char; 4] = ['*', '?', '[', '{'];
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid glob pattern: {0}")]
+#[diagnostic(help(
+    "Glob patterns support `*`, `**`, `?`, `[...]` and `{{a,b}}`, but not extglobs such as `@(a|b)`"
+))]
+struct InvalidGlob(globset::Error);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Failed to read the ignore file {0:?}: {1}")]
+struct IgnoreFileError(PathBuf, ignore::Error);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid --ignore-pattern {0:?}: {1}")]
+struct InvalidIgnorePattern(String, ignore::Error);
+
 pub struct Walk {
     inner: ignore::Walk,
+    /// Set when some of the paths are glob patterns.
+    filter: Option<PathFilter>,
+    shard: Option<Shard>,
+}
+
+/// Selects the files matching the glob patterns or located in the plain paths.
+struct PathFilter {
+    globs: GlobSet,
+    paths: Vec<PathBuf>,
+}
+
+impl PathFilter {
+    fn is_match(&self, path: &Path) -> bool {
+        self.globs.is_match(path) || self.paths.iter().any(|p| path.starts_with(p))
+    }
+}
+
+/// `--shard <INDEX>/<COUNT>` splits the files into COUNT shards and lints the INDEX-th one.
+///
+/// Files are assigned by a hash of their directory, so that every file is linted by exactly one
+/// shard on any machine and the files of a directory stay together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    /// Starts at 1.
+    index: usize,
+    count: usize,
+}
+
+impl Shard {
+    /// # Errors
+    ///
+    /// * `s` is not `<INDEX>/<COUNT>` with `1 <= INDEX <= COUNT`
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let error = || format!("expected <INDEX>/<COUNT> with 1 <= INDEX <= COUNT, found `{s}`");
+        let (index, count) = s.split_once('/').ok_or_else(error)?;
+        let index = index.trim().parse::<usize>().map_err(|_| error())?;
+        let count = count.trim().parse::<usize>().map_err(|_| error())?;
+        if index == 0 || index > count {
+            return Err(error());
+        }
+        Ok(Self { index, count })
+    }
+
+    fn contains(self, path: &Path) -> bool {
+        let dir = path.parent().unwrap_or(path);
+        let dir = dir
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        // FNV-1a, which unlike the std hashers is guaranteed to be stable across builds.
+        let hash = dir.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        #[allow(clippy::cast_possible_truncation)]
+        let shard = (hash % self.count as u64) as usize;
+        shard == self.index - 1
+    }
 }
 
 impl Walk {
+    /// # Errors
+    ///
+    /// * A path is an invalid glob pattern
+    /// * The ignore file cannot be read, or an `--ignore-pattern` is invalid
+    ///
     /// # Panics
-    pub fn new(options: &LintOptions) -> Self {
-        let mut inner = WalkBuilder::new(&options.paths[0]);
+    pub fn new(options: &LintOptions) -> Result<Self, oxc_diagnostics::Error> {
+        let mut roots = vec![];
+        let mut paths = vec![];
+        let mut globs = GlobSetBuilder::new();
+        let mut has_globs = false;
+        for path in &options.paths {
+            if let Some(base) = Self::glob_base(path) {
+                let glob = GlobBuilder::new(&path.to_string_lossy())
+                    .literal_separator(true)
+                    .build()
+                    .map_err(InvalidGlob)?;
+                globs.add(glob);
+                has_globs = true;
+                roots.push(base);
+            } else {
+                roots.push(path.clone());
+                paths.push(path.clone());
+            }
+        }
+        // Multiple patterns may share the same base directory.
+        roots.sort_unstable();
+        roots.dedup();
+
+        let filter = if has_globs {
+            let globs = globs.build().map_err(InvalidGlob)?;
+            Some(PathFilter { globs, paths })
+        } else {
+            None
+        };
+
+        let mut inner = WalkBuilder::new(&roots[0]);
 
-        if let Some(paths) = options.paths.get(1..) {
-            for path in paths {
-                inner.add(path);
+        if let Some(roots) = roots.get(1..) {
+            for root in roots {
+                inner.add(root);
             }
         }
 
-        if !options.no_ignore {
-            inner.add_custom_ignore_filename(&options.ignore_path);
+        if options.no_ignore {
+            inner.git_ignore(false).git_exclude(false);
+        } else {
+            // The ignore file is optional, e.g. the default `.eslintignore` may not exist.
+            if options.ignore_path.is_file() {
+                if let Some(error) = inner.add_ignore(&options.ignore_path) {
+                    return Err(IgnoreFileError(options.ignore_path.clone(), error).into());
+                }
+            }
 
             if !options.ignore_pattern.is_empty() {
-                let mut override_builder = OverrideBuilder::new(Path::new("/"));
+                let mut override_builder = OverrideBuilder::new(Path::new("."));
                 for pattern in &options.ignore_pattern {
-                    // TODO: check this command arg parser
-                    override_builder.add(pattern).unwrap();
+                    // Overrides are whitelists by default, `!` turns the pattern into an ignore.
+                    override_builder
+                        .add(&format!("!{pattern}"))
+                        .map_err(|error| InvalidIgnorePattern(pattern.clone(), error))?;
                 }
-                let r#override = override_builder.build().unwrap();
+                let r#override = override_builder.build().map_err(|error| {
+                    InvalidIgnorePattern(options.ignore_pattern.join(" "), error)
+                })?;
                 inner.overrides(r#override);
             }
         }
         // Turning off `follow_links` because:
         // * following symlinks is a really slow syscall
         // * it is super rare to have symlinked source code
-        let inner = inner.ignore(false).git_global(false).follow_links(false).build();
-        Self { inner }
+        // Turning off `require_git` so `.gitignore` files are respected outside of git repositories.
+        let inner =
+            inner.ignore(false).git_global(false).require_git(false).follow_links(false).build();
+        Ok(Self { inner, filter, shard: options.shard })
     }
 
     pub fn iter(self) -> impl Iterator<Item = Box<Path>> {
+        let filter = self.filter;
+        let shard = self.shard;
         self.inner
             .filter_map(Result::ok)
             .filter(Self::is_wanted_entry)
+            .filter(move |entry| filter.as_ref().map_or(true, |f| f.is_match(entry.path())))
+            .filter(move |entry| shard.map_or(true, |shard| shard.contains(entry.path())))
             .map(|entry| entry.path().to_path_buf().into_boxed_path())
     }
 
+    /// Returns the directory to walk for a glob pattern, i.e. the path components before the
+    /// first component containing a glob character, or `None` if `path` is not a glob pattern.
+    /// Existing paths are not glob patterns, e.g. `pages/[id].tsx`.
+    pub(crate) fn glob_base(path: &Path) -> Option<PathBuf> {
+        if path.exists() {
+            return None;
+        }
+        let is_glob = |component: &Component| {
+            component.as_os_str().to_string_lossy().contains(GLOB_CHARS.as_slice())
+        };
+        if !path.components().any(|c| is_glob(&c)) {
+            return None;
+        }
+        let base = path.components().take_while(|c| !is_glob(c)).collect::<PathBuf>();
+        Some(if base.as_os_str().is_empty() { PathBuf::from(".") } else { base })
+    }
+
+    /// Files with a JavaScript or TypeScript extension in the paths, respecting `.gitignore`,
+    /// for the commands other than lint which take plain paths.
+    pub(crate) fn source_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+        let Some((first, rest)) = paths.split_first() else { return vec![] };
+        let mut builder = WalkBuilder::new(first);
+        for path in rest {
+            builder.add(path);
+        }
+        let mut files = builder
+            .build()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().map_or(false, |file_type| !file_type.is_dir()))
+            .map(DirEntry::into_path)
+            .filter(|path| {
+                path.extension().map_or(false, |extension| {
+                    VALID_EXTENSIONS.contains(&extension.to_string_lossy().as_ref())
+                })
+            })
+            .collect::<Vec<_>>();
+        files.sort_unstable();
+        files.dedup();
+        files
+    }
+
     fn is_wanted_entry(dir_entry: &DirEntry) -> bool {
         let Some(file_type) = dir_entry.file_type() else { return false };
         if file_type.is_dir() {
@@ -60,3 +236,41 @@ impl Walk {
         VALID_EXTENSIONS.contains(&extension.to_string_lossy().as_ref())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use super::{Shard, Walk};
+
+    #[test]
+    fn glob_base() {
+        assert_eq!(Walk::glob_base(Path::new("src/foo.js")), None);
+        assert_eq!(Walk::glob_base(Path::new("src/**/*.ts")), Some(PathBuf::from("src")));
+        assert_eq!(Walk::glob_base(Path::new("./src/*.{js,ts}")), Some(PathBuf::from("./src")));
+        assert_eq!(Walk::glob_base(Path::new("*.js")), Some(PathBuf::from(".")));
+
+        let dir = std::env::temp_dir().join("oxc_cli_glob_base");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("[id].js");
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(Walk::glob_base(&path), None);
+        assert_eq!(Walk::glob_base(&dir.join("[a-z]*.js")), Some(dir));
+    }
+
+    #[test]
+    fn shard() {
+        assert_eq!(Shard::parse("2/8"), Ok(Shard { index: 2, count: 8 }));
+        assert!(Shard::parse("0/8").is_err());
+        assert!(Shard::parse("9/8").is_err());
+        assert!(Shard::parse("2").is_err());
+
+        let paths = ["a/x.js", "./a/y.js", "b/x.js", "c/d/x.js", "x.js"].map(Path::new);
+        let shards = (1..=3).map(|index| Shard { index, count: 3 }).collect::<Vec<_>>();
+        for path in paths {
+            assert_eq!(shards.iter().filter(|shard| shard.contains(path)).count(), 1);
+        }
+        let shard = shards.iter().find(|shard| shard.contains(Path::new("a/x.js"))).unwrap();
+        assert!(shard.contains(Path::new("./a/y.js")));
+    }
+}
@@ -1,14 +1,14 @@
 use std::{env, path::Path};
 
 use oxc_allocator::Allocator;
-use oxc_formatter::{Formatter, FormatterOptions};
+use oxc_codegen::{Codegen, CodegenOptions};
 use oxc_parser::Parser;
 use oxc_span::SourceType;
 
 // Instruction:
 // create a `test.js`,
-// run `cargo run -p oxc_formatter --example formatter`
-// or `cargo watch -x "run -p oxc_formatter --example formatter"`
+// run `cargo run -p oxc_codegen --example codegen`
+// or `cargo watch -x "run -p oxc_codegen --example codegen"`
 
 fn main() {
     let name = env::args().nth(1).unwrap_or_else(|| "test.js".to_string());
@@ -26,7 +26,8 @@ fn main() {
         return;
     }
 
-    let formatter_options = FormatterOptions::default();
-    let printed = Formatter::new(source_text.len(), formatter_options).build(&ret.program);
+    let codegen_options =
+        CodegenOptions { typescript: source_type.is_typescript(), ..CodegenOptions::default() };
+    let printed = Codegen::new(source_text.len(), codegen_options).build(&ret.program);
     println!("{printed}");
 }
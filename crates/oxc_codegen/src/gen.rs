@@ -1,25 +1,36 @@
-use oxc_allocator::{Box, Vec};
+use oxc_allocator::Box;
 #[allow(clippy::wildcard_imports)]
 use oxc_ast::ast::*;
-use oxc_syntax::operator::BinaryOperator;
-
-use crate::{Formatter, Separator};
+use oxc_ast::CommentPosition;
+use oxc_span::GetSpan;
+use oxc_syntax::{operator::BinaryOperator, NumberBase};
+
+use crate::{
+    gen_ts::{
+        print_accessibility, print_declare, print_type_annotation, print_type_arguments,
+        print_type_parameters,
+    },
+    ArrowParens, Codegen, Separator,
+};
 
 pub trait Gen {
-    fn gen(&self, p: &mut Formatter);
+    fn gen(&self, p: &mut Codegen);
 }
 
 impl<'a, T> Gen for Box<'a, T>
 where
     T: Gen,
 {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         (**self).gen(p);
     }
 }
 
 impl<'a> Gen for Program<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        if let Some(hashbang) = &self.hashbang {
+            hashbang.gen(p);
+        }
         for directive in &self.directives {
             directive.gen(p);
         }
@@ -27,11 +38,20 @@ impl<'a> Gen for Program<'a> {
             p.print_semicolon_if_needed();
             stmt.gen(p);
         }
+        p.print_dangling_comments_on_own_line(self.span);
+    }
+}
+
+impl Gen for Hashbang {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_str(b"#!");
+        p.print_str(self.value.as_bytes());
+        p.print_hard_newline();
     }
 }
 
 impl Gen for Directive {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print(b'"');
         p.print_str(self.directive.as_bytes());
@@ -42,7 +62,12 @@ impl Gen for Directive {
 }
 
 impl<'a> Gen for Statement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_leading_comments_on_own_line(self.span());
+        // Taken before printing the statement, so they are not printed by an expression
+        // with the same span, e.g. `foo()` in `foo() // c`
+        let trailing_comments = p.take_comments(self.span(), CommentPosition::Trailing);
+        p.add_pending_source_mapping(self.span());
         match self {
             Self::BlockStatement(stmt) => stmt.gen(p),
             Self::BreakStatement(stmt) => stmt.gen(p),
@@ -50,6 +75,8 @@ impl<'a> Gen for Statement<'a> {
             Self::DebuggerStatement(stmt) => stmt.gen(p),
             Self::DoWhileStatement(stmt) => stmt.gen(p),
             Self::EmptyStatement(stmt) => stmt.gen(p),
+            // The source text is not available to print it as is
+            Self::ErrorStatement(_) => {}
             Self::ExpressionStatement(stmt) => stmt.gen(p),
             Self::ForInStatement(stmt) => stmt.gen(p),
             Self::ForOfStatement(stmt) => stmt.gen(p),
@@ -65,11 +92,12 @@ impl<'a> Gen for Statement<'a> {
             Self::WithStatement(stmt) => stmt.gen(p),
             Self::Declaration(decl) => decl.gen(p),
         }
+        p.print_trailing_comments_at_end_of_line(trailing_comments);
     }
 }
 
 impl<'a> Gen for ExpressionStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         self.expression.gen(p);
         if self.expression.is_specific_id("let") {
@@ -81,7 +109,7 @@ impl<'a> Gen for ExpressionStatement<'a> {
 }
 
 impl Gen for EmptyStatement {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_semicolon();
         p.print_newline();
@@ -89,13 +117,13 @@ impl Gen for EmptyStatement {
 }
 
 impl<'a> Gen for IfStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         print_if(self, p);
     }
 }
 
-fn print_if(if_stmt: &IfStatement<'_>, p: &mut Formatter) {
+fn print_if(if_stmt: &IfStatement<'_>, p: &mut Codegen) {
     p.print_str(b"if");
     p.print_space();
     p.print(b'(');
@@ -121,7 +149,6 @@ fn print_if(if_stmt: &IfStatement<'_>, p: &mut Formatter) {
     }
     if let Some(alternate) = if_stmt.alternate.as_ref() {
         p.print_semicolon_if_needed();
-        p.print_space();
         p.print_str(b"else");
         p.print_space();
         match alternate {
@@ -143,7 +170,7 @@ fn print_if(if_stmt: &IfStatement<'_>, p: &mut Formatter) {
 }
 
 impl<'a> Gen for BlockStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_block1(self);
         p.print_newline();
@@ -151,7 +178,7 @@ impl<'a> Gen for BlockStatement<'a> {
 }
 
 impl<'a> Gen for ForStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"for");
         p.print_space();
@@ -184,7 +211,7 @@ impl<'a> Gen for ForStatement<'a> {
 }
 
 impl<'a> Gen for ForInStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"for");
         gen_for_statement_brack_content(&self.left, &self.right, &self.body, b"in", p);
@@ -192,7 +219,7 @@ impl<'a> Gen for ForInStatement<'a> {
 }
 
 impl<'a> Gen for ForOfStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"for");
         if self.r#await {
@@ -207,7 +234,7 @@ fn gen_for_statement_brack_content<'a>(
     right: &Expression<'a>,
     body: &Statement,
     key: &[u8],
-    p: &mut Formatter,
+    p: &mut Codegen,
 ) {
     p.print_space();
     p.print(b'(');
@@ -221,7 +248,7 @@ fn gen_for_statement_brack_content<'a>(
 }
 
 impl<'a> Gen for ForStatementLeft<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match &self {
             ForStatementLeft::VariableDeclaration(var) => var.gen(p),
             ForStatementLeft::AssignmentTarget(target) => target.gen(p),
@@ -230,7 +257,7 @@ impl<'a> Gen for ForStatementLeft<'a> {
 }
 
 impl<'a> Gen for WhileStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"while");
         p.print_space();
@@ -242,7 +269,7 @@ impl<'a> Gen for WhileStatement<'a> {
 }
 
 impl<'a> Gen for DoWhileStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"do");
         p.print_space();
@@ -268,7 +295,7 @@ impl<'a> Gen for DoWhileStatement<'a> {
 }
 
 impl Gen for ContinueStatement {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"continue");
         if let Some(label) = &self.label {
@@ -280,7 +307,7 @@ impl Gen for ContinueStatement {
 }
 
 impl Gen for BreakStatement {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"break");
         if let Some(label) = &self.label {
@@ -292,7 +319,7 @@ impl Gen for BreakStatement {
 }
 
 impl<'a> Gen for SwitchStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"switch");
         p.print_space();
@@ -315,7 +342,7 @@ impl<'a> Gen for SwitchStatement<'a> {
 }
 
 impl<'a> Gen for SwitchCase<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_semicolon_if_needed();
         p.print_indent();
         match &self.test {
@@ -338,7 +365,7 @@ impl<'a> Gen for SwitchCase<'a> {
 }
 
 impl<'a> Gen for ReturnStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"return");
         if let Some(arg) = &self.argument {
@@ -350,7 +377,7 @@ impl<'a> Gen for ReturnStatement<'a> {
 }
 
 impl<'a> Gen for LabeledStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         self.label.gen(p);
         p.print_colon();
@@ -360,7 +387,7 @@ impl<'a> Gen for LabeledStatement<'a> {
 }
 
 impl<'a> Gen for TryStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"try");
         p.print_space();
@@ -388,7 +415,7 @@ impl<'a> Gen for TryStatement<'a> {
 }
 
 impl<'a> Gen for ThrowStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"throw");
         p.print_space();
@@ -398,7 +425,7 @@ impl<'a> Gen for ThrowStatement<'a> {
 }
 
 impl<'a> Gen for WithStatement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"with");
         p.print_space();
@@ -411,7 +438,7 @@ impl<'a> Gen for WithStatement<'a> {
 }
 
 impl Gen for DebuggerStatement {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_indent();
         p.print_str(b"debugger");
         p.print_semicolon_after_statement();
@@ -419,19 +446,23 @@ impl Gen for DebuggerStatement {
 }
 
 impl<'a> Gen for ModuleDeclaration<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        // Indented in `namespace a { export const b = 1; }`
+        p.print_indent();
         match self {
             Self::ImportDeclaration(decl) => decl.gen(p),
             Self::ExportAllDeclaration(decl) => decl.gen(p),
             Self::ExportDefaultDeclaration(decl) => decl.gen(p),
             Self::ExportNamedDeclaration(decl) => decl.gen(p),
+            Self::TSExportAssignment(decl) if p.options.typescript => decl.gen(p),
+            Self::TSNamespaceExportDeclaration(decl) if p.options.typescript => decl.gen(p),
             Self::TSExportAssignment(_) | Self::TSNamespaceExportDeclaration(_) => {}
         }
     }
 }
 
 impl<'a> Gen for Declaration<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::VariableDeclaration(stmt) => {
                 p.print_indent();
@@ -444,24 +475,44 @@ impl<'a> Gen for Declaration<'a> {
                 p.print_newline();
             }
             Self::ClassDeclaration(declaration) => {
+                p.print_indent();
                 declaration.gen(p);
                 p.print_newline();
             }
-            Self::TSTypeAliasDeclaration(_)
-            | Self::TSInterfaceDeclaration(_)
-            | Self::TSEnumDeclaration(_)
-            | Self::TSModuleDeclaration(_)
-            | Self::TSImportEqualsDeclaration(_) => {}
+            _ if !p.options.typescript => {}
+            Self::TSTypeAliasDeclaration(decl) => {
+                p.print_indent();
+                decl.gen(p);
+            }
+            Self::TSInterfaceDeclaration(decl) => {
+                p.print_indent();
+                decl.gen(p);
+            }
+            Self::TSEnumDeclaration(decl) => {
+                p.print_indent();
+                decl.gen(p);
+            }
+            Self::TSModuleDeclaration(decl) => {
+                p.print_indent();
+                decl.gen(p);
+            }
+            Self::TSImportEqualsDeclaration(decl) => {
+                p.print_indent();
+                decl.gen(p);
+            }
         }
     }
 }
 
 impl<'a> Gen for VariableDeclaration<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        print_declare(&self.modifiers, p);
         p.print_str(match self.kind {
             VariableDeclarationKind::Const => b"const",
             VariableDeclarationKind::Let => b"let",
             VariableDeclarationKind::Var => b"var",
+            VariableDeclarationKind::Using => b"using",
+            VariableDeclarationKind::AwaitUsing => b"await using",
         });
         p.print_space();
         p.print_list(&self.declarations);
@@ -469,8 +520,15 @@ impl<'a> Gen for VariableDeclaration<'a> {
 }
 
 impl<'a> Gen for VariableDeclarator<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        self.id.gen(p);
+    fn gen(&self, p: &mut Codegen) {
+        if self.definite && p.options.typescript {
+            // `let a!: T`
+            self.id.kind.gen(p);
+            p.print(b'!');
+            print_type_annotation(self.id.type_annotation.as_deref(), p);
+        } else {
+            self.id.gen(p);
+        }
         if let Some(init) = &self.init {
             p.print_space();
             p.print_equal();
@@ -481,7 +539,8 @@ impl<'a> Gen for VariableDeclarator<'a> {
 }
 
 impl<'a> Gen for Function<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        print_declare(&self.modifiers, p);
         if self.r#async {
             p.print_str(b"async");
             p.print_space();
@@ -495,20 +554,31 @@ impl<'a> Gen for Function<'a> {
                 p.print_space();
             }
             id.gen(p);
+            print_type_parameters(self.type_parameters.as_deref(), p);
             p.print_space();
+        } else {
+            print_type_parameters(self.type_parameters.as_deref(), p);
         }
         p.print(b'(');
         self.params.gen(p);
         p.print(b')');
+        print_type_annotation(self.return_type.as_deref(), p);
+        print_function_body(self.body.as_deref(), p);
+    }
+}
+
+/// The body of functions and methods, or the `;` of overloads and ambient declarations
+fn print_function_body(body: Option<&FunctionBody<'_>>, p: &mut Codegen) {
+    if let Some(body) = body {
         p.print_space();
-        if let Some(body) = &self.body {
-            body.gen(p);
-        }
+        body.gen(p);
+    } else if p.options.typescript {
+        p.print_semicolon();
     }
 }
 
 impl<'a> Gen for FunctionBody<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'{');
         p.indent();
         p.print_newline();
@@ -520,6 +590,7 @@ impl<'a> Gen for FunctionBody<'a> {
             p.print_semicolon_if_needed();
             stmt.gen(p);
         }
+        p.print_dangling_comments_on_own_line(self.span);
         p.dedent();
         p.print_indent();
         p.print(b'}');
@@ -528,17 +599,27 @@ impl<'a> Gen for FunctionBody<'a> {
 }
 
 impl<'a> Gen for FormalParameter<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        print_accessibility(self.accessibility, p);
+        if p.options.typescript {
+            if self.r#override {
+                p.print_str(b"override ");
+            }
+            if self.readonly {
+                p.print_str(b"readonly ");
+            }
+        }
         self.pattern.gen(p);
     }
 }
 
 impl<'a> Gen for FormalParameters<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_list(&self.items);
         if let Some(rest) = &self.rest {
             if !self.items.is_empty() {
                 p.print_comma();
+                p.print_space();
             }
             rest.gen(p);
         }
@@ -546,13 +627,16 @@ impl<'a> Gen for FormalParameters<'a> {
 }
 
 impl<'a> Gen for ImportDeclaration<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"import ");
+        if p.options.typescript && self.import_kind.is_type() {
+            p.print_str(b"type ");
+        }
         if self.specifiers.is_empty() {
             p.print(b'\'');
             p.print_str(self.source.value.as_bytes());
             p.print(b'\'');
-            self.assertions.gen(p);
+            self.with_clause.gen(p);
             p.print_semicolon_after_statement();
             return;
         }
@@ -597,6 +681,9 @@ impl<'a> Gen for ImportDeclaration<'a> {
                         p.print(b'{');
                     }
                     p.print_space();
+                    if p.options.typescript && spec.import_kind.is_type() {
+                        p.print_str(b"type ");
+                    }
 
                     let imported_name = match &spec.imported {
                         ModuleExportName::Identifier(identifier) => {
@@ -624,24 +711,30 @@ impl<'a> Gen for ImportDeclaration<'a> {
         }
         p.print_str(b" from ");
         self.source.gen(p);
-        self.assertions.gen(p);
+        self.with_clause.gen(p);
         p.print_semicolon_after_statement();
     }
 }
 
-impl<'a> Gen for Option<Vec<'a, ImportAttribute>> {
-    fn gen(&self, p: &mut Formatter) {
-        if let Some(assertions) = &self {
-            p.print_space();
-            p.print_str(b"assert");
+impl<'a> Gen for Option<WithClause<'a>> {
+    fn gen(&self, p: &mut Codegen) {
+        if let Some(with_clause) = self {
             p.print_space();
-            p.print_block(assertions, Separator::Comma);
-        };
+            with_clause.gen(p);
+        }
+    }
+}
+
+impl<'a> Gen for WithClause<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_str(self.attributes_keyword.name.as_bytes());
+        p.print_space();
+        p.print_block(&self.with_entries, Separator::Comma);
     }
 }
 
 impl Gen for ImportAttribute {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match &self.key {
             ImportAttributeKey::Identifier(identifier) => {
                 p.print_str(identifier.name.as_bytes());
@@ -655,11 +748,14 @@ impl Gen for ImportAttribute {
 }
 
 impl<'a> Gen for ExportNamedDeclaration<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"export ");
         match &self.declaration {
             Some(decl) => decl.gen(p),
             None => {
+                if p.options.typescript && self.export_kind.is_type() {
+                    p.print_str(b"type ");
+                }
                 p.print(b'{');
                 if !self.specifiers.is_empty() {
                     p.print_space();
@@ -673,6 +769,7 @@ impl<'a> Gen for ExportNamedDeclaration<'a> {
                     p.print_space();
                     source.gen(p);
                 }
+                self.with_clause.gen(p);
                 p.print_semicolon_after_statement();
             }
         }
@@ -680,7 +777,10 @@ impl<'a> Gen for ExportNamedDeclaration<'a> {
 }
 
 impl Gen for ExportSpecifier {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        if p.options.typescript && self.export_kind.is_type() {
+            p.print_str(b"type ");
+        }
         self.local.gen(p);
         if self.local.name() != self.exported.name() {
             p.print_str(b" as ");
@@ -690,7 +790,7 @@ impl Gen for ExportSpecifier {
 }
 
 impl Gen for ModuleExportName {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Identifier(identifier) => {
                 p.print_str(identifier.name.as_bytes());
@@ -701,9 +801,12 @@ impl Gen for ModuleExportName {
 }
 
 impl<'a> Gen for ExportAllDeclaration<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"export");
         p.print_space();
+        if p.options.typescript && self.export_kind.is_type() {
+            p.print_str(b"type ");
+        }
         p.print(b'*');
 
         if let Some(exported) = &self.exported {
@@ -715,20 +818,20 @@ impl<'a> Gen for ExportAllDeclaration<'a> {
         p.print_str(b" from");
         p.print_space();
         self.source.gen(p);
-        self.assertions.gen(p);
+        self.with_clause.gen(p);
 
         p.print_semicolon_after_statement();
     }
 }
 
 impl<'a> Gen for ExportDefaultDeclaration<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"export default ");
         self.declaration.gen(p);
     }
 }
 impl<'a> Gen for ExportDefaultDeclarationKind<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Expression(expr) => {
                 expr.gen(p);
@@ -736,13 +839,17 @@ impl<'a> Gen for ExportDefaultDeclarationKind<'a> {
             }
             Self::FunctionDeclaration(fun) => fun.gen(p),
             Self::ClassDeclaration(value) => value.gen(p),
+            Self::TSInterfaceDeclaration(decl) if p.options.typescript => decl.gen(p),
+            Self::TSEnumDeclaration(decl) if p.options.typescript => decl.gen(p),
             Self::TSInterfaceDeclaration(_) | Self::TSEnumDeclaration(_) => {}
         }
     }
 }
 
 impl<'a> Gen for Expression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_inline_comments(self.span(), CommentPosition::Leading);
+        p.add_source_mapping(self.span());
         match self {
             Self::BooleanLiteral(lit) => lit.gen(p),
             Self::NullLiteral(lit) => lit.gen(p),
@@ -777,93 +884,144 @@ impl<'a> Gen for Expression<'a> {
             Self::NewExpression(expr) => expr.gen(p),
             Self::MetaProperty(expr) => expr.gen(p),
             Self::ClassExpression(expr) => expr.gen(p),
+            // A missing expression has no source text
+            Self::ErrorExpression(_) => {}
             Self::JSXElement(el) => el.gen(p),
             Self::JSXFragment(fragment) => fragment.gen(p),
-            Self::TSAsExpression(expr) => expr.expression.gen(p),
-            Self::TSSatisfiesExpression(expr) => expr.expression.gen(p),
-            Self::TSTypeAssertion(expr) => expr.expression.gen(p),
-            Self::TSNonNullExpression(expr) => expr.expression.gen(p),
-            Self::TSInstantiationExpression(expr) => expr.expression.gen(p),
+            Self::TSAsExpression(expr) => expr.gen(p),
+            Self::TSSatisfiesExpression(expr) => expr.gen(p),
+            Self::TSTypeAssertion(expr) => expr.gen(p),
+            Self::TSNonNullExpression(expr) => expr.gen(p),
+            Self::TSInstantiationExpression(expr) => {
+                expr.expression.gen(p);
+                print_type_arguments(Some(&expr.type_parameters), p);
+            }
         }
+        p.print_inline_comments(self.span(), CommentPosition::Trailing);
     }
 }
 
 impl Gen for IdentifierReference {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        p.add_source_mapping_for_name(self.span, self.name.as_str());
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl Gen for IdentifierName {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl Gen for BindingIdentifier {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        p.add_source_mapping_for_name(self.span, self.name.as_str());
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl Gen for LabelIdentifier {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl Gen for BooleanLiteral {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(self.as_str().as_bytes());
     }
 }
 
 impl Gen for NullLiteral {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"null");
     }
 }
 
 impl<'a> Gen for NumberLiteral<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        p.print_str(self.raw.as_bytes());
+    fn gen(&self, p: &mut Codegen) {
+        // The raw text is stale if a transform changed the value
+        if raw_number_value(self.raw, self.base) == Some(self.value) {
+            p.print_str(self.raw.as_bytes());
+        } else if self.value.is_finite() {
+            p.print_str(self.value.to_string().as_bytes());
+        } else {
+            // `Infinity` and `NaN` are globals which may be shadowed
+            let numerator: &[u8] = if self.value.is_nan() {
+                b"0"
+            } else if self.value.is_sign_positive() {
+                b"1"
+            } else {
+                b"-1"
+            };
+            p.print(b'(');
+            p.print_str(numerator);
+            BinaryOperator::Division.gen(p);
+            p.print(b'0');
+            p.print(b')');
+        }
     }
 }
 
+fn raw_number_value(raw: &str, base: NumberBase) -> Option<f64> {
+    let raw = raw.replace('_', "").to_ascii_lowercase();
+    let (digits, radix) = match base {
+        NumberBase::Decimal | NumberBase::Float => return raw.parse::<f64>().ok(),
+        NumberBase::Hex => (raw.strip_prefix("0x")?, 16),
+        NumberBase::Binary => (raw.strip_prefix("0b")?, 2),
+        // Legacy octal literals such as `012`
+        NumberBase::Octal => (raw.strip_prefix("0o").or_else(|| raw.strip_prefix('0'))?, 8),
+    };
+    #[allow(clippy::cast_precision_loss)]
+    u64::from_str_radix(digits, radix).ok().map(|value| value as f64)
+}
+
 impl Gen for BigintLiteral {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(self.value.to_string().as_bytes());
         p.print(b'n');
     }
 }
 
 impl Gen for RegExpLiteral {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'/');
         p.print_str(self.regex.pattern.as_bytes());
         p.print(b'/');
+        p.prev_reg_exp_end = p.code.len();
         p.print_str(self.regex.flags.to_string().as_bytes());
     }
 }
 
 impl Gen for StringLiteral {
-    fn gen(&self, p: &mut Formatter) {
-        p.print(b'\'');
+    fn gen(&self, p: &mut Codegen) {
+        if let Some(raw) = p.raw_string_literal(self.span, &self.value) {
+            p.print_str(raw.as_bytes());
+            return;
+        }
+        let quote = p.options.quote.as_byte();
+        p.print(quote);
         for c in self.value.chars() {
-            p.print_str(c.escape_default().to_string().as_bytes());
+            match c {
+                // Only the quote in use needs escaping
+                '\'' if quote != b'\'' => p.print(b'\''),
+                '"' if quote != b'"' => p.print(b'"'),
+                _ => p.print_str(c.escape_default().to_string().as_bytes()),
+            }
         }
-        p.print(b'\'');
+        p.print(quote);
     }
 }
 
 impl Gen for ThisExpression {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"this");
     }
 }
 
 impl<'a> Gen for MemberExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::ComputedMemberExpression(expr) => expr.gen(p),
             Self::StaticMemberExpression(expr) => expr.gen(p),
@@ -873,7 +1031,7 @@ impl<'a> Gen for MemberExpression<'a> {
 }
 
 impl<'a> Gen for ComputedMemberExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.object.gen(p);
         if self.optional {
             p.print_str(b"?.");
@@ -885,10 +1043,17 @@ impl<'a> Gen for ComputedMemberExpression<'a> {
 }
 
 impl<'a> Gen for StaticMemberExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        let start = p.code.len();
         self.object.gen(p);
+        // The `.` would be read as the decimal point of an integer, e.g. `1 .toString()`
+        let is_integer = |code: &[u8]| code.iter().all(|b| b.is_ascii_digit() || *b == b'_');
         if self.optional {
             p.print(b'?');
+        } else if matches!(self.object, Expression::NumberLiteral(_))
+            && is_integer(&p.code[start..])
+        {
+            p.print(b' ');
         }
         p.print(b'.');
         self.property.gen(p);
@@ -896,7 +1061,7 @@ impl<'a> Gen for StaticMemberExpression<'a> {
 }
 
 impl<'a> Gen for PrivateFieldExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.object.gen(p);
         if self.optional {
             p.print_str(b"?");
@@ -907,12 +1072,16 @@ impl<'a> Gen for PrivateFieldExpression<'a> {
 }
 
 impl<'a> Gen for CallExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        if self.pure {
+            p.print_pure_annotation();
+        }
         self.callee.gen(p);
 
         if self.optional {
             p.print_str(b"?.");
         }
+        print_type_arguments(self.type_parameters.as_deref(), p);
         p.print(b'(');
         p.print_list(&self.arguments);
         p.print(b')');
@@ -920,7 +1089,7 @@ impl<'a> Gen for CallExpression<'a> {
 }
 
 impl<'a> Gen for Argument<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::SpreadElement(elem) => elem.gen(p),
             Self::Expression(elem) => elem.gen(p),
@@ -929,7 +1098,7 @@ impl<'a> Gen for Argument<'a> {
 }
 
 impl<'a> Gen for ArrayExpressionElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Expression(expr) => expr.gen(p),
             Self::SpreadElement(elem) => elem.gen(p),
@@ -939,14 +1108,14 @@ impl<'a> Gen for ArrayExpressionElement<'a> {
 }
 
 impl<'a> Gen for SpreadElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_ellipsis();
         self.argument.gen(p);
     }
 }
 
 impl<'a> Gen for ArrayExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'[');
         p.print_list(&self.elements);
         if self.trailing_comma.is_some() {
@@ -957,7 +1126,7 @@ impl<'a> Gen for ArrayExpression<'a> {
 }
 
 impl<'a> Gen for ObjectExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'{');
         p.indent();
         for (i, item) in self.properties.iter().enumerate() {
@@ -968,6 +1137,9 @@ impl<'a> Gen for ObjectExpression<'a> {
             p.print_indent();
             item.gen(p);
         }
+        if !self.properties.is_empty() {
+            p.print_trailing_comma();
+        }
         p.print_newline();
         p.dedent();
         p.print_indent();
@@ -976,16 +1148,18 @@ impl<'a> Gen for ObjectExpression<'a> {
 }
 
 impl<'a> Gen for ObjectPropertyKind<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_inline_comments(self.span(), CommentPosition::Leading);
         match self {
             Self::ObjectProperty(prop) => prop.gen(p),
             Self::SpreadProperty(elem) => elem.gen(p),
         }
+        p.print_inline_comments(self.span(), CommentPosition::Trailing);
     }
 }
 
 impl<'a> Gen for ObjectProperty<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         if let Expression::FunctionExpression(func) = &self.value {
             let is_accessor = match &self.kind {
                 PropertyKind::Init => false,
@@ -1012,13 +1186,12 @@ impl<'a> Gen for ObjectProperty<'a> {
                 if self.computed {
                     p.print(b']');
                 }
+                print_type_parameters(func.type_parameters.as_deref(), p);
                 p.print(b'(');
                 func.params.gen(p);
                 p.print(b')');
-                p.print_space();
-                if let Some(body) = &func.body {
-                    body.gen(p);
-                }
+                print_type_annotation(func.return_type.as_deref(), p);
+                print_function_body(func.body.as_deref(), p);
                 return;
             }
         }
@@ -1036,7 +1209,7 @@ impl<'a> Gen for ObjectProperty<'a> {
 }
 
 impl<'a> Gen for PropertyKey<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Identifier(ident) => {
                 if p.quote_property_with_double_quotes {
@@ -1054,14 +1227,37 @@ impl<'a> Gen for PropertyKey<'a> {
 }
 
 impl<'a> Gen for ArrowExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         if self.r#async {
             p.print_str(b"async");
             p.print_space();
         }
-        p.print(b'(');
-        self.params.gen(p);
-        p.print(b')');
+        match self.type_parameters.as_deref() {
+            // `<T,>`, which is not a JSX element in TSX files
+            Some(parameters)
+                if p.options.typescript
+                    && matches!(parameters.params.as_slice(), [param]
+                        if param.constraint.is_none()) =>
+            {
+                p.print(b'<');
+                parameters.params[0].gen(p);
+                p.print_str(b",>");
+            }
+            parameters => print_type_parameters(parameters, p),
+        }
+        let has_types =
+            p.options.typescript && (self.type_parameters.is_some() || self.return_type.is_some());
+        if p.options.arrow_parens == ArrowParens::Avoid
+            && !has_types
+            && is_single_identifier(&self.params, p)
+        {
+            self.params.gen(p);
+        } else {
+            p.print(b'(');
+            self.params.gen(p);
+            p.print(b')');
+        }
+        print_type_annotation(self.return_type.as_deref(), p);
         p.print_space();
         p.print_str(b"=>");
         p.print_space();
@@ -1075,8 +1271,18 @@ impl<'a> Gen for ArrowExpression<'a> {
     }
 }
 
+/// `x` in `(x) => x`, which can be printed without parentheses
+fn is_single_identifier(params: &FormalParameters<'_>, p: &Codegen) -> bool {
+    params.rest.is_none()
+        && matches!(params.items.as_slice(), [param] if
+            param.decorators.is_empty()
+                && matches!(param.pattern.kind, BindingPatternKind::BindingIdentifier(_))
+                && !(p.options.typescript
+                    && (param.pattern.optional || param.pattern.type_annotation.is_some())))
+}
+
 impl<'a> Gen for YieldExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"yield");
         if self.delegate {
             p.print_space();
@@ -1091,7 +1297,7 @@ impl<'a> Gen for YieldExpression<'a> {
 }
 
 impl<'a> Gen for UpdateExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         let operator = self.operator.as_str().as_bytes();
         if self.prefix {
             p.print_space();
@@ -1105,7 +1311,7 @@ impl<'a> Gen for UpdateExpression<'a> {
 }
 
 impl<'a> Gen for UnaryExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         let operator = self.operator.as_str().as_bytes();
         if self.operator.is_keyword() {
             p.print_str(operator);
@@ -1119,7 +1325,7 @@ impl<'a> Gen for UnaryExpression<'a> {
 }
 
 impl<'a> Gen for BinaryExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.left.gen(p);
         self.operator.gen(p);
         self.right.gen(p);
@@ -1127,7 +1333,7 @@ impl<'a> Gen for BinaryExpression<'a> {
 }
 
 impl Gen for BinaryOperator {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         let operator = self.as_str().as_bytes();
         if self.is_keyword() {
             p.print_space();
@@ -1138,7 +1344,7 @@ impl Gen for BinaryOperator {
 }
 
 impl<'a> Gen for PrivateInExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.left.gen(p);
         p.print_space();
         p.print_str(self.operator.as_str().as_bytes());
@@ -1148,7 +1354,7 @@ impl<'a> Gen for PrivateInExpression<'a> {
 }
 
 impl<'a> Gen for LogicalExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.left.gen(p);
         p.print_space();
         p.print_str(self.operator.as_str().as_bytes());
@@ -1158,7 +1364,7 @@ impl<'a> Gen for LogicalExpression<'a> {
 }
 
 impl<'a> Gen for ConditionalExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.test.gen(p);
         p.print_space();
         p.print(b'?');
@@ -1172,7 +1378,7 @@ impl<'a> Gen for ConditionalExpression<'a> {
 }
 
 impl<'a> Gen for AssignmentExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.left.gen(p);
         p.print_space();
         p.print_str(self.operator.as_str().as_bytes());
@@ -1182,7 +1388,7 @@ impl<'a> Gen for AssignmentExpression<'a> {
 }
 
 impl<'a> Gen for AssignmentTarget<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::SimpleAssignmentTarget(target) => target.gen(p),
             Self::AssignmentTargetPattern(pat) => pat.gen(p),
@@ -1190,21 +1396,61 @@ impl<'a> Gen for AssignmentTarget<'a> {
     }
 }
 
+impl<'a> Gen for TSAsExpression<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        self.expression.gen(p);
+        if p.options.typescript {
+            p.print_str(b" as ");
+            self.type_annotation.gen(p);
+        }
+    }
+}
+
+impl<'a> Gen for TSSatisfiesExpression<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        self.expression.gen(p);
+        if p.options.typescript {
+            p.print_str(b" satisfies ");
+            self.type_annotation.gen(p);
+        }
+    }
+}
+
+impl<'a> Gen for TSTypeAssertion<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        if p.options.typescript {
+            p.print(b'<');
+            self.type_annotation.gen(p);
+            p.print(b'>');
+        }
+        self.expression.gen(p);
+    }
+}
+
+impl<'a> Gen for TSNonNullExpression<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        self.expression.gen(p);
+        if p.options.typescript {
+            p.print(b'!');
+        }
+    }
+}
+
 impl<'a> Gen for SimpleAssignmentTarget<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::AssignmentTargetIdentifier(ident) => ident.gen(p),
             Self::MemberAssignmentTarget(member_expr) => member_expr.gen(p),
-            Self::TSAsExpression(expr) => expr.expression.gen(p),
-            Self::TSSatisfiesExpression(expr) => expr.expression.gen(p),
-            Self::TSNonNullExpression(expr) => expr.expression.gen(p),
-            Self::TSTypeAssertion(expr) => expr.expression.gen(p),
+            Self::TSAsExpression(expr) => expr.gen(p),
+            Self::TSSatisfiesExpression(expr) => expr.gen(p),
+            Self::TSNonNullExpression(expr) => expr.gen(p),
+            Self::TSTypeAssertion(expr) => expr.gen(p),
         }
     }
 }
 
 impl<'a> Gen for AssignmentTargetPattern<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::ArrayAssignmentTarget(target) => target.gen(p),
             Self::ObjectAssignmentTarget(target) => target.gen(p),
@@ -1213,7 +1459,7 @@ impl<'a> Gen for AssignmentTargetPattern<'a> {
 }
 
 impl<'a> Gen for ArrayAssignmentTarget<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'[');
         p.print_list(&self.elements);
         if let Some(target) = &self.rest {
@@ -1229,7 +1475,7 @@ impl<'a> Gen for ArrayAssignmentTarget<'a> {
 }
 
 impl<'a> Gen for Option<AssignmentTargetMaybeDefault<'a>> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         if let Some(arg) = self {
             arg.gen(p);
         }
@@ -1237,7 +1483,7 @@ impl<'a> Gen for Option<AssignmentTargetMaybeDefault<'a>> {
 }
 
 impl<'a> Gen for ObjectAssignmentTarget<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'{');
         p.print_list(&self.properties);
         if let Some(target) = &self.rest {
@@ -1252,7 +1498,7 @@ impl<'a> Gen for ObjectAssignmentTarget<'a> {
 }
 
 impl<'a> Gen for AssignmentTargetMaybeDefault<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::AssignmentTarget(target) => target.gen(p),
             Self::AssignmentTargetWithDefault(target) => target.gen(p),
@@ -1261,7 +1507,7 @@ impl<'a> Gen for AssignmentTargetMaybeDefault<'a> {
 }
 
 impl<'a> Gen for AssignmentTargetWithDefault<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.binding.gen(p);
         p.print_equal();
         self.init.gen(p);
@@ -1269,7 +1515,7 @@ impl<'a> Gen for AssignmentTargetWithDefault<'a> {
 }
 
 impl<'a> Gen for AssignmentTargetProperty<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::AssignmentTargetPropertyIdentifier(ident) => ident.gen(p),
             Self::AssignmentTargetPropertyProperty(prop) => prop.gen(p),
@@ -1278,7 +1524,7 @@ impl<'a> Gen for AssignmentTargetProperty<'a> {
 }
 
 impl<'a> Gen for AssignmentTargetPropertyIdentifier<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.binding.gen(p);
         if let Some(expr) = &self.init {
             p.print_space();
@@ -1290,7 +1536,7 @@ impl<'a> Gen for AssignmentTargetPropertyIdentifier<'a> {
 }
 
 impl<'a> Gen for AssignmentTargetPropertyProperty<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match &self.name {
             PropertyKey::Identifier(ident) => {
                 ident.gen(p);
@@ -1311,21 +1557,43 @@ impl<'a> Gen for AssignmentTargetPropertyProperty<'a> {
 }
 
 impl<'a> Gen for SequenceExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_list(&self.expressions);
     }
 }
 
 impl<'a> Gen for ParenthesizedExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        if p.options.compact && is_primary_without_parens(&self.expression) {
+            self.expression.gen(p);
+            return;
+        }
         p.print(b'(');
         self.expression.gen(p);
         p.print(b')');
     }
 }
 
+/// Expressions which are parsed the same without parentheses in any position
+fn is_primary_without_parens(expr: &Expression<'_>) -> bool {
+    match expr {
+        // `(let)[a] = b` is not a declaration, `for ((async) of a)` is not a syntax error
+        Expression::Identifier(ident) => !matches!(ident.name.as_str(), "let" | "async"),
+        Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::BigintLiteral(_)
+        | Expression::RegExpLiteral(_)
+        | Expression::TemplateLiteral(_)
+        | Expression::ThisExpression(_)
+        | Expression::ArrayExpression(_)
+        | Expression::ParenthesizedExpression(_) => true,
+        // `("use strict")` is not a directive, and `(1).toString()` is not `1.toString()`
+        _ => false,
+    }
+}
+
 impl<'a> Gen for ImportExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"import(");
         self.source.gen(p);
         if !self.arguments.is_empty() {
@@ -1338,7 +1606,7 @@ impl<'a> Gen for ImportExpression<'a> {
 }
 
 impl<'a> Gen for TemplateLiteral<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'`');
         let mut expressions = self.expressions.iter();
 
@@ -1357,27 +1625,28 @@ impl<'a> Gen for TemplateLiteral<'a> {
 }
 
 impl<'a> Gen for TaggedTemplateExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.tag.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
         self.quasi.gen(p);
     }
 }
 
 impl Gen for Super {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"super");
     }
 }
 
 impl<'a> Gen for AwaitExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"await ");
         self.argument.gen(p);
     }
 }
 
 impl<'a> Gen for ChainExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match &self.expression {
             ChainElement::CallExpression(expr) => expr.gen(p),
             ChainElement::MemberExpression(expr) => expr.gen(p),
@@ -1386,9 +1655,13 @@ impl<'a> Gen for ChainExpression<'a> {
 }
 
 impl<'a> Gen for NewExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        if self.pure {
+            p.print_pure_annotation();
+        }
         p.print_str(b"new ");
         self.callee.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
         p.print(b'(');
         p.print_list(&self.arguments);
         p.print(b')');
@@ -1396,7 +1669,7 @@ impl<'a> Gen for NewExpression<'a> {
 }
 
 impl Gen for MetaProperty {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.meta.gen(p);
         p.print(b'.');
         self.property.gen(p);
@@ -1404,15 +1677,32 @@ impl Gen for MetaProperty {
 }
 
 impl<'a> Gen for Class<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        print_declare(&self.modifiers, p);
+        if p.options.typescript && self.modifiers.contains(ModifierKind::Abstract) {
+            p.print_str(b"abstract ");
+        }
         p.print_str(b"class");
         if let Some(id) = &self.id {
             p.print_space();
             id.gen(p);
         }
+        print_type_parameters(self.type_parameters.as_deref(), p);
         if let Some(super_class) = self.super_class.as_ref() {
             p.print_str(b" extends ");
             super_class.gen(p);
+            print_type_arguments(self.super_type_parameters.as_deref(), p);
+        }
+        if let Some(implements) = self.implements.as_ref().filter(|_| p.options.typescript) {
+            p.print_str(b" implements ");
+            for (index, implement) in implements.iter().enumerate() {
+                if index != 0 {
+                    p.print_comma();
+                    p.print_space();
+                }
+                implement.expression.gen(p);
+                print_type_arguments(implement.type_parameters.as_deref(), p);
+            }
         }
         p.print_space();
         p.print(b'{');
@@ -1422,10 +1712,13 @@ impl<'a> Gen for Class<'a> {
             p.print_semicolon_if_needed();
             p.print_indent();
             item.gen(p);
-            if matches!(
-                item,
-                ClassElement::PropertyDefinition(_) | ClassElement::AccessorProperty(_)
-            ) {
+            let ends_with_semicolon = match item {
+                ClassElement::PropertyDefinition(_) | ClassElement::AccessorProperty(_) => true,
+                ClassElement::TSAbstractPropertyDefinition(_)
+                | ClassElement::TSIndexSignature(_) => p.options.typescript,
+                _ => false,
+            };
+            if ends_with_semicolon {
                 p.print_semicolon_after_statement();
             } else {
                 p.print_newline();
@@ -1439,27 +1732,34 @@ impl<'a> Gen for Class<'a> {
 }
 
 impl<'a> Gen for ClassElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_inline_comments(self.span(), CommentPosition::Leading);
         match self {
             Self::StaticBlock(elem) => elem.gen(p),
             Self::MethodDefinition(elem) => elem.gen(p),
             Self::PropertyDefinition(elem) => elem.gen(p),
             Self::AccessorProperty(elem) => elem.gen(p),
-            Self::TSAbstractMethodDefinition(_)
-            | Self::TSAbstractPropertyDefinition(_)
-            | Self::TSIndexSignature(_) => {}
+            _ if !p.options.typescript => {}
+            Self::TSAbstractMethodDefinition(elem) => {
+                print_method_definition(&elem.method_definition, true, p);
+            }
+            Self::TSAbstractPropertyDefinition(elem) => {
+                print_property_definition(&elem.property_definition, true, p);
+            }
+            Self::TSIndexSignature(elem) => elem.gen(p),
         }
+        p.print_inline_comments(self.span(), CommentPosition::Trailing);
     }
 }
 
 impl Gen for JSXIdentifier {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl<'a> Gen for JSXMemberExpressionObject<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Identifier(ident) => ident.gen(p),
             Self::MemberExpression(member_expr) => member_expr.gen(p),
@@ -1468,7 +1768,7 @@ impl<'a> Gen for JSXMemberExpressionObject<'a> {
 }
 
 impl<'a> Gen for JSXMemberExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.object.gen(p);
         p.print(b'.');
         self.property.gen(p);
@@ -1476,7 +1776,7 @@ impl<'a> Gen for JSXMemberExpression<'a> {
 }
 
 impl<'a> Gen for JSXElementName<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Identifier(identifier) => identifier.gen(p),
             Self::NamespacedName(namespaced_name) => namespaced_name.gen(p),
@@ -1486,7 +1786,7 @@ impl<'a> Gen for JSXElementName<'a> {
 }
 
 impl Gen for JSXNamespacedName {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.namespace.gen(p);
         p.print(b'.');
         self.property.gen(p);
@@ -1494,7 +1794,7 @@ impl Gen for JSXNamespacedName {
 }
 
 impl<'a> Gen for JSXAttributeName<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Identifier(ident) => ident.gen(p),
             Self::NamespacedName(namespaced_name) => namespaced_name.gen(p),
@@ -1503,7 +1803,7 @@ impl<'a> Gen for JSXAttributeName<'a> {
 }
 
 impl<'a> Gen for JSXAttribute<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.name.gen(p);
         p.print(b'=');
         if let Some(value) = &self.value {
@@ -1513,11 +1813,11 @@ impl<'a> Gen for JSXAttribute<'a> {
 }
 
 impl Gen for JSXEmptyExpression {
-    fn gen(&self, _: &mut Formatter) {}
+    fn gen(&self, _: &mut Codegen) {}
 }
 
 impl<'a> Gen for JSXExpression<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Expression(expr) => expr.gen(p),
             Self::EmptyExpression(expr) => expr.gen(p),
@@ -1526,7 +1826,7 @@ impl<'a> Gen for JSXExpression<'a> {
 }
 
 impl<'a> Gen for JSXExpressionContainer<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'{');
         self.expression.gen(p);
         p.print(b'}');
@@ -1534,7 +1834,7 @@ impl<'a> Gen for JSXExpressionContainer<'a> {
 }
 
 impl<'a> Gen for JSXAttributeValue<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Fragment(fragment) => fragment.gen(p),
             Self::Element(el) => el.gen(p),
@@ -1545,13 +1845,13 @@ impl<'a> Gen for JSXAttributeValue<'a> {
 }
 
 impl<'a> Gen for JSXSpreadAttribute<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.argument.gen(p);
     }
 }
 
 impl<'a> Gen for JSXAttributeItem<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Attribute(attr) => attr.gen(p),
             Self::SpreadAttribute(spread_attr) => spread_attr.gen(p),
@@ -1560,9 +1860,10 @@ impl<'a> Gen for JSXAttributeItem<'a> {
 }
 
 impl<'a> Gen for JSXOpeningElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"<");
         self.name.gen(p);
+        print_type_arguments(self.type_parameters.as_deref(), p);
         for attr in &self.attributes {
             attr.gen(p);
         }
@@ -1576,7 +1877,7 @@ impl<'a> Gen for JSXOpeningElement<'a> {
 }
 
 impl<'a> Gen for JSXClosingElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"</");
         self.name.gen(p);
         p.print(b'>');
@@ -1584,7 +1885,7 @@ impl<'a> Gen for JSXClosingElement<'a> {
 }
 
 impl<'a> Gen for JSXElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.opening_element.gen(p);
         for child in &self.children {
             child.gen(p);
@@ -1596,32 +1897,32 @@ impl<'a> Gen for JSXElement<'a> {
 }
 
 impl Gen for JSXOpeningFragment {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"<>");
     }
 }
 
 impl Gen for JSXClosingFragment {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"</>");
     }
 }
 
 impl Gen for JSXText {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(self.value.as_bytes());
     }
 }
 
 impl<'a> Gen for JSXSpreadChild<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"...");
         self.expression.gen(p);
     }
 }
 
 impl<'a> Gen for JSXChild<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         match self {
             Self::Fragment(fragment) => fragment.gen(p),
             Self::Element(el) => el.gen(p),
@@ -1633,7 +1934,7 @@ impl<'a> Gen for JSXChild<'a> {
 }
 
 impl<'a> Gen for JSXFragment<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.opening_fragment.gen(p);
         for child in &self.children {
             child.gen(p);
@@ -1643,7 +1944,7 @@ impl<'a> Gen for JSXFragment<'a> {
 }
 
 impl<'a> Gen for StaticBlock<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_str(b"static");
         p.print_space();
         p.print(b'{');
@@ -1661,65 +1962,108 @@ impl<'a> Gen for StaticBlock<'a> {
 }
 
 impl<'a> Gen for MethodDefinition<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        if self.r#static {
-            p.print_str(b"static ");
-        }
+    fn gen(&self, p: &mut Codegen) {
+        print_method_definition(self, false, p);
+    }
+}
 
-        match &self.kind {
-            MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => {}
-            MethodDefinitionKind::Get => p.print_str(b"get "),
-            MethodDefinitionKind::Set => p.print_str(b"set "),
+fn print_method_definition(method: &MethodDefinition<'_>, r#abstract: bool, p: &mut Codegen) {
+    print_accessibility(method.accessibility, p);
+    if method.r#static {
+        p.print_str(b"static ");
+    }
+    if p.options.typescript {
+        if r#abstract {
+            p.print_str(b"abstract ");
         }
-
-        if self.value.r#async {
-            p.print_str(b"async ");
+        if method.r#override {
+            p.print_str(b"override ");
         }
+    }
 
-        if self.value.generator {
-            p.print_str(b"*");
-        }
+    match &method.kind {
+        MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => {}
+        MethodDefinitionKind::Get => p.print_str(b"get "),
+        MethodDefinitionKind::Set => p.print_str(b"set "),
+    }
 
-        if self.computed {
-            p.print(b'[');
-        }
-        self.key.gen(p);
-        if self.computed {
-            p.print(b']');
-        }
-        p.print(b'(');
-        self.value.params.gen(p);
-        p.print(b')');
-        p.print_space();
-        if let Some(body) = &self.value.body {
-            body.gen(p);
-        }
+    if method.value.r#async {
+        p.print_str(b"async ");
+    }
+
+    if method.value.generator {
+        p.print_str(b"*");
     }
+
+    if method.computed {
+        p.print(b'[');
+    }
+    method.key.gen(p);
+    if method.computed {
+        p.print(b']');
+    }
+    if p.options.typescript && method.optional {
+        p.print(b'?');
+    }
+    print_type_parameters(method.value.type_parameters.as_deref(), p);
+    p.print(b'(');
+    method.value.params.gen(p);
+    p.print(b')');
+    print_type_annotation(method.value.return_type.as_deref(), p);
+    print_function_body(method.value.body.as_deref(), p);
 }
 
 impl<'a> Gen for PropertyDefinition<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        if self.r#static {
-            p.print_str(b"static ");
+    fn gen(&self, p: &mut Codegen) {
+        print_property_definition(self, false, p);
+    }
+}
+
+fn print_property_definition(property: &PropertyDefinition<'_>, r#abstract: bool, p: &mut Codegen) {
+    if p.options.typescript && property.declare {
+        p.print_str(b"declare ");
+    }
+    print_accessibility(property.accessibility, p);
+    if property.r#static {
+        p.print_str(b"static ");
+    }
+    if p.options.typescript {
+        if r#abstract {
+            p.print_str(b"abstract ");
         }
-        if self.computed {
-            p.print(b'[');
+        if property.r#override {
+            p.print_str(b"override ");
         }
-        self.key.gen(p);
-        if self.computed {
-            p.print(b']');
+        if property.readonly {
+            p.print_str(b"readonly ");
         }
-        if let Some(value) = &self.value {
-            p.print_space();
-            p.print_equal();
-            p.print_space();
-            value.gen(p);
+    }
+    if property.computed {
+        p.print(b'[');
+    }
+    property.key.gen(p);
+    if property.computed {
+        p.print(b']');
+    }
+    if p.options.typescript {
+        if property.optional {
+            p.print(b'?');
+        }
+        if property.definite {
+            p.print(b'!');
         }
     }
+    print_type_annotation(property.type_annotation.as_deref(), p);
+    if let Some(value) = &property.value {
+        p.print_space();
+        p.print_equal();
+        p.print_space();
+        value.gen(p);
+    }
 }
 
 impl<'a> Gen for AccessorProperty<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         if self.r#static {
             p.print_str(b"static ");
         }
@@ -1741,25 +2085,35 @@ impl<'a> Gen for AccessorProperty<'a> {
 }
 
 impl Gen for PrivateIdentifier {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'#');
         p.print_str(self.name.as_bytes());
     }
 }
 
 impl<'a> Gen for BindingPattern<'a> {
-    fn gen(&self, p: &mut Formatter) {
-        match &self.kind {
-            BindingPatternKind::BindingIdentifier(ident) => ident.gen(p),
-            BindingPatternKind::ObjectPattern(pattern) => pattern.gen(p),
-            BindingPatternKind::ArrayPattern(pattern) => pattern.gen(p),
-            BindingPatternKind::AssignmentPattern(pattern) => pattern.gen(p),
+    fn gen(&self, p: &mut Codegen) {
+        self.kind.gen(p);
+        if p.options.typescript && self.optional {
+            p.print(b'?');
+        }
+        print_type_annotation(self.type_annotation.as_deref(), p);
+    }
+}
+
+impl<'a> Gen for BindingPatternKind<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        match self {
+            Self::BindingIdentifier(ident) => ident.gen(p),
+            Self::ObjectPattern(pattern) => pattern.gen(p),
+            Self::ArrayPattern(pattern) => pattern.gen(p),
+            Self::AssignmentPattern(pattern) => pattern.gen(p),
         }
     }
 }
 
 impl<'a> Gen for ObjectPattern<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'{');
         p.print_space();
         p.print_list(&self.properties);
@@ -1775,7 +2129,7 @@ impl<'a> Gen for ObjectPattern<'a> {
 }
 
 impl<'a> Gen for BindingProperty<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         if self.computed {
             p.print(b'[');
         }
@@ -1790,14 +2144,14 @@ impl<'a> Gen for BindingProperty<'a> {
 }
 
 impl<'a> Gen for RestElement<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print_ellipsis();
         self.argument.gen(p);
     }
 }
 
 impl<'a> Gen for ArrayPattern<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         p.print(b'[');
         for (index, item) in self.elements.iter().enumerate() {
             if index != 0 {
@@ -1818,7 +2172,7 @@ impl<'a> Gen for ArrayPattern<'a> {
 }
 
 impl<'a> Gen for AssignmentPattern<'a> {
-    fn gen(&self, p: &mut Formatter) {
+    fn gen(&self, p: &mut Codegen) {
         self.left.gen(p);
         p.print_space();
         p.print_equal();
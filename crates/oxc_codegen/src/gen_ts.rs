@@ -0,0 +1,683 @@
+//! TypeScript syntax, which is only printed with [`CodegenOptions::typescript`]
+//!
+//! [`CodegenOptions::typescript`]: crate::CodegenOptions::typescript
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+
+use crate::{gen::Gen, Codegen};
+
+/// `: T` of bindings, parameters, properties and return types
+pub fn print_type_annotation(annotation: Option<&TSTypeAnnotation<'_>>, p: &mut Codegen) {
+    if let Some(annotation) = annotation {
+        if p.options.typescript {
+            p.print_colon();
+            p.print_space();
+            annotation.type_annotation.gen(p);
+        }
+    }
+}
+
+/// `<T extends U = V>` of functions, classes and type declarations
+pub fn print_type_parameters(parameters: Option<&TSTypeParameterDeclaration<'_>>, p: &mut Codegen) {
+    if let Some(parameters) = parameters {
+        if p.options.typescript {
+            parameters.gen(p);
+        }
+    }
+}
+
+/// `<T>` of calls, `new` expressions, tagged templates and JSX elements
+pub fn print_type_arguments(arguments: Option<&TSTypeParameterInstantiation<'_>>, p: &mut Codegen) {
+    if let Some(arguments) = arguments {
+        if p.options.typescript {
+            arguments.gen(p);
+        }
+    }
+}
+
+pub fn print_accessibility(accessibility: Option<TSAccessibility>, p: &mut Codegen) {
+    if !p.options.typescript {
+        return;
+    }
+    match accessibility {
+        Some(TSAccessibility::Private) => p.print_str(b"private "),
+        Some(TSAccessibility::Protected) => p.print_str(b"protected "),
+        Some(TSAccessibility::Public) => p.print_str(b"public "),
+        None => {}
+    }
+}
+
+/// `declare` of ambient declarations
+pub fn print_declare(modifiers: &Modifiers<'_>, p: &mut Codegen) {
+    if p.options.typescript && modifiers.contains(ModifierKind::Declare) {
+        p.print_str(b"declare ");
+    }
+}
+
+impl<'a> Gen for TSTypeParameterDeclaration<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        p.print(b'<');
+        p.print_list(&self.params);
+        p.print(b'>');
+    }
+}
+
+impl<'a> Gen for TSTypeParameter<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        if self.r#const {
+            p.print_str(b"const ");
+        }
+        if self.r#in {
+            p.print_str(b"in ");
+        }
+        if self.out {
+            p.print_str(b"out ");
+        }
+        self.name.gen(p);
+        if let Some(constraint) = &self.constraint {
+            p.print_str(b" extends ");
+            constraint.gen(p);
+        }
+        if let Some(default) = &self.default {
+            p.print_space();
+            p.print_equal();
+            p.print_space();
+            default.gen(p);
+        }
+    }
+}
+
+impl<'a> Gen for TSTypeParameterInstantiation<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        p.print(b'<');
+        p.print_list(&self.params);
+        p.print(b'>');
+    }
+}
+
+impl<'a> Gen for TSTypeName<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        match self {
+            Self::IdentifierName(ident) => ident.gen(p),
+            Self::QualifiedName(name) => {
+                name.left.gen(p);
+                p.print(b'.');
+                name.right.gen(p);
+            }
+        }
+    }
+}
+
+impl<'a> Gen for TSType<'a> {
+    #[allow(clippy::too_many_lines)]
+    fn gen(&self, p: &mut Codegen) {
+        match self {
+            Self::TSAnyKeyword(_) => p.print_str(b"any"),
+            Self::TSBigIntKeyword(_) => p.print_str(b"bigint"),
+            Self::TSBooleanKeyword(_) => p.print_str(b"boolean"),
+            Self::TSNeverKeyword(_) => p.print_str(b"never"),
+            Self::TSNullKeyword(_) => p.print_str(b"null"),
+            Self::TSNumberKeyword(_) => p.print_str(b"number"),
+            Self::TSObjectKeyword(_) => p.print_str(b"object"),
+            Self::TSStringKeyword(_) => p.print_str(b"string"),
+            Self::TSSymbolKeyword(_) => p.print_str(b"symbol"),
+            Self::TSThisKeyword(_) => p.print_str(b"this"),
+            Self::TSUndefinedKeyword(_) => p.print_str(b"undefined"),
+            Self::TSUnknownKeyword(_) => p.print_str(b"unknown"),
+            Self::TSVoidKeyword(_) => p.print_str(b"void"),
+            Self::TSArrayType(ty) => {
+                print_wrapped(&ty.element_type, binds_looser_than_postfix(&ty.element_type), p);
+                p.print_str(b"[]");
+            }
+            Self::TSConditionalType(ty) => {
+                let wrap_check = is_function_like(&ty.check_type)
+                    || matches!(ty.check_type, TSType::TSConditionalType(_));
+                print_wrapped(&ty.check_type, wrap_check, p);
+                p.print_str(b" extends ");
+                let wrap_extends = matches!(ty.extends_type, TSType::TSConditionalType(_));
+                print_wrapped(&ty.extends_type, wrap_extends, p);
+                p.print_str(b" ? ");
+                ty.true_type.gen(p);
+                p.print_str(b" : ");
+                ty.false_type.gen(p);
+            }
+            Self::TSConstructorType(ty) => {
+                if ty.r#abstract {
+                    p.print_str(b"abstract ");
+                }
+                p.print_str(b"new ");
+                print_function_type(
+                    ty.type_parameters.as_deref(),
+                    &ty.params,
+                    &ty.return_type.type_annotation,
+                    p,
+                );
+            }
+            Self::TSFunctionType(ty) => print_function_type(
+                ty.type_parameters.as_deref(),
+                &ty.params,
+                &ty.return_type.type_annotation,
+                p,
+            ),
+            Self::TSImportType(ty) => {
+                if ty.is_type_of {
+                    p.print_str(b"typeof ");
+                }
+                p.print_str(b"import(");
+                ty.parameter.gen(p);
+                p.print(b')');
+                if let Some(qualifier) = &ty.qualifier {
+                    p.print(b'.');
+                    qualifier.gen(p);
+                }
+                print_type_arguments(ty.type_parameters.as_deref(), p);
+            }
+            Self::TSIndexedAccessType(ty) => {
+                print_wrapped(&ty.object_type, binds_looser_than_postfix(&ty.object_type), p);
+                p.print(b'[');
+                ty.index_type.gen(p);
+                p.print(b']');
+            }
+            Self::TSInferType(ty) => {
+                p.print_str(b"infer ");
+                ty.type_parameter.gen(p);
+            }
+            Self::TSIntersectionType(ty) => {
+                for (index, ty) in ty.types.iter().enumerate() {
+                    if index != 0 {
+                        p.print_str(b" & ");
+                    }
+                    let wrap = is_function_like(ty)
+                        || matches!(ty, TSType::TSUnionType(_) | TSType::TSConditionalType(_));
+                    print_wrapped(ty, wrap, p);
+                }
+            }
+            Self::TSLiteralType(ty) => match &ty.literal {
+                TSLiteral::BooleanLiteral(lit) => lit.gen(p),
+                TSLiteral::NullLiteral(lit) => lit.gen(p),
+                TSLiteral::NumberLiteral(lit) => lit.gen(p),
+                TSLiteral::BigintLiteral(lit) => lit.gen(p),
+                TSLiteral::RegExpLiteral(lit) => lit.gen(p),
+                TSLiteral::StringLiteral(lit) => lit.gen(p),
+                TSLiteral::TemplateLiteral(lit) => lit.gen(p),
+                // `-1`, without the space before the operator of unary expressions
+                TSLiteral::UnaryExpression(expr) => {
+                    p.print_str(expr.operator.as_str().as_bytes());
+                    expr.argument.gen(p);
+                }
+            },
+            Self::TSMappedType(ty) => ty.gen(p),
+            Self::TSQualifiedName(name) => {
+                name.left.gen(p);
+                p.print(b'.');
+                name.right.gen(p);
+            }
+            Self::TSTemplateLiteralType(ty) => {
+                p.print(b'`');
+                let mut types = ty.types.iter();
+                for quasi in &ty.quasis {
+                    p.print_str(quasi.value.raw.as_bytes());
+                    if let Some(ty) = types.next() {
+                        p.print_str(b"${");
+                        ty.gen(p);
+                        p.print(b'}');
+                    }
+                }
+                p.print(b'`');
+            }
+            Self::TSTupleType(ty) => {
+                p.print(b'[');
+                p.print_list(&ty.element_types);
+                p.print(b']');
+            }
+            Self::TSTypeLiteral(ty) => print_signatures(&ty.members, p),
+            Self::TSTypeOperatorType(ty) => {
+                p.print_str(match ty.operator {
+                    TSTypeOperator::Keyof => b"keyof ",
+                    TSTypeOperator::Unique => b"unique ",
+                    TSTypeOperator::Readonly => b"readonly ",
+                });
+                let wrap = is_function_like(&ty.type_annotation)
+                    || matches!(
+                        ty.type_annotation,
+                        TSType::TSUnionType(_)
+                            | TSType::TSIntersectionType(_)
+                            | TSType::TSConditionalType(_)
+                    );
+                print_wrapped(&ty.type_annotation, wrap, p);
+            }
+            Self::TSTypePredicate(ty) => {
+                if ty.asserts {
+                    p.print_str(b"asserts ");
+                }
+                match &ty.parameter_name {
+                    TSTypePredicateName::Identifier(ident) => ident.gen(p),
+                    TSTypePredicateName::This(_) => p.print_str(b"this"),
+                }
+                if let Some(annotation) = &ty.type_annotation {
+                    p.print_str(b" is ");
+                    annotation.type_annotation.gen(p);
+                }
+            }
+            Self::TSTypeQuery(ty) => {
+                p.print_str(b"typeof ");
+                ty.expr_name.gen(p);
+                print_type_arguments(ty.type_parameters.as_deref(), p);
+            }
+            Self::TSTypeReference(ty) => {
+                ty.type_name.gen(p);
+                print_type_arguments(ty.type_parameters.as_deref(), p);
+            }
+            Self::TSUnionType(ty) => {
+                for (index, ty) in ty.types.iter().enumerate() {
+                    if index != 0 {
+                        p.print_str(b" | ");
+                    }
+                    let wrap = is_function_like(ty)
+                        || matches!(ty, TSType::TSConditionalType(_))
+                        || matches!(ty, TSType::TSInferType(infer)
+                            if infer.type_parameter.constraint.is_some());
+                    print_wrapped(ty, wrap, p);
+                }
+            }
+            Self::JSDocNullableType(ty) => {
+                if ty.postfix {
+                    ty.type_annotation.gen(p);
+                    p.print(b'?');
+                } else {
+                    p.print(b'?');
+                    ty.type_annotation.gen(p);
+                }
+            }
+            Self::JSDocUnknownType(_) => p.print(b'?'),
+        }
+    }
+}
+
+/// Parentheses are not kept in the AST of types, so they are printed where the type would
+/// otherwise be parsed differently, e.g. `(A | B)[]`
+fn print_wrapped(ty: &TSType<'_>, wrap: bool, p: &mut Codegen) {
+    if wrap {
+        p.print(b'(');
+        ty.gen(p);
+        p.print(b')');
+    } else {
+        ty.gen(p);
+    }
+}
+
+/// Types which need parentheses as the operand of `[]` and `[K]`
+fn binds_looser_than_postfix(ty: &TSType<'_>) -> bool {
+    is_function_like(ty)
+        || matches!(
+            ty,
+            TSType::TSUnionType(_)
+                | TSType::TSIntersectionType(_)
+                | TSType::TSConditionalType(_)
+                | TSType::TSTypeOperatorType(_)
+                | TSType::TSInferType(_)
+        )
+}
+
+/// Types whose return type would take the rest of a union or intersection
+fn is_function_like(ty: &TSType<'_>) -> bool {
+    matches!(ty, TSType::TSFunctionType(_) | TSType::TSConstructorType(_))
+}
+
+fn print_function_type(
+    type_parameters: Option<&TSTypeParameterDeclaration<'_>>,
+    params: &FormalParameters<'_>,
+    return_type: &TSType<'_>,
+    p: &mut Codegen,
+) {
+    print_type_parameters(type_parameters, p);
+    p.print(b'(');
+    params.gen(p);
+    p.print(b')');
+    p.print_str(b" => ");
+    return_type.gen(p);
+}
+
+impl<'a> Gen for TSMappedType<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        p.print(b'{');
+        p.print_space();
+        match self.readonly {
+            TSMappedTypeModifierOperator::True => p.print_str(b"readonly "),
+            TSMappedTypeModifierOperator::Plus => p.print_str(b"+readonly "),
+            TSMappedTypeModifierOperator::Minus => p.print_str(b"-readonly "),
+            TSMappedTypeModifierOperator::None => {}
+        }
+        p.print(b'[');
+        self.type_parameter.name.gen(p);
+        if let Some(constraint) = &self.type_parameter.constraint {
+            p.print_str(b" in ");
+            constraint.gen(p);
+        }
+        if let Some(name_type) = &self.name_type {
+            p.print_str(b" as ");
+            name_type.gen(p);
+        }
+        p.print(b']');
+        match self.optional {
+            TSMappedTypeModifierOperator::True => p.print(b'?'),
+            TSMappedTypeModifierOperator::Plus => p.print_str(b"+?"),
+            TSMappedTypeModifierOperator::Minus => p.print_str(b"-?"),
+            TSMappedTypeModifierOperator::None => {}
+        }
+        p.print_colon();
+        p.print_space();
+        self.type_annotation.gen(p);
+        p.print_space();
+        p.print(b'}');
+    }
+}
+
+impl<'a> Gen for TSTupleElement<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        match self {
+            Self::TSType(ty) => ty.gen(p),
+            Self::TSOptionalType(ty) => {
+                let ty = &ty.type_annotation;
+                print_wrapped(ty, binds_looser_than_postfix(ty), p);
+                p.print(b'?');
+            }
+            Self::TSRestType(ty) => {
+                p.print_ellipsis();
+                ty.type_annotation.gen(p);
+            }
+            Self::TSNamedTupleMember(member) => {
+                if member.rest {
+                    p.print_ellipsis();
+                }
+                member.label.gen(p);
+                if member.optional {
+                    p.print(b'?');
+                }
+                p.print_colon();
+                p.print_space();
+                member.element_type.gen(p);
+            }
+        }
+    }
+}
+
+/// The members of interfaces and type literals, one per line
+fn print_signatures(signatures: &[TSSignature<'_>], p: &mut Codegen) {
+    p.print(b'{');
+    p.indent();
+    for signature in signatures {
+        p.print_newline();
+        p.print_indent();
+        signature.gen(p);
+        p.print_semicolon();
+    }
+    p.dedent();
+    if !signatures.is_empty() {
+        p.print_newline();
+        p.print_indent();
+    }
+    p.print(b'}');
+}
+
+impl<'a> Gen for TSSignature<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        match self {
+            Self::TSIndexSignature(signature) => signature.gen(p),
+            Self::TSPropertySignature(signature) => {
+                if signature.readonly {
+                    p.print_str(b"readonly ");
+                }
+                print_key(&signature.key, signature.computed, p);
+                if signature.optional {
+                    p.print(b'?');
+                }
+                print_type_annotation(signature.type_annotation.as_deref(), p);
+            }
+            Self::TSCallSignatureDeclaration(signature) => print_signature(
+                signature.type_parameters.as_deref(),
+                &signature.params,
+                signature.return_type.as_deref(),
+                p,
+            ),
+            Self::TSConstructSignatureDeclaration(signature) => {
+                p.print_str(b"new ");
+                print_signature(
+                    signature.type_parameters.as_deref(),
+                    &signature.params,
+                    signature.return_type.as_deref(),
+                    p,
+                );
+            }
+            Self::TSMethodSignature(signature) => {
+                match signature.kind {
+                    TSMethodSignatureKind::Method => {}
+                    TSMethodSignatureKind::Get => p.print_str(b"get "),
+                    TSMethodSignatureKind::Set => p.print_str(b"set "),
+                }
+                print_key(&signature.key, signature.computed, p);
+                if signature.optional {
+                    p.print(b'?');
+                }
+                print_signature(
+                    signature.type_parameters.as_deref(),
+                    &signature.params,
+                    signature.return_type.as_deref(),
+                    p,
+                );
+            }
+        }
+    }
+}
+
+fn print_key(key: &PropertyKey<'_>, computed: bool, p: &mut Codegen) {
+    if computed {
+        p.print(b'[');
+    }
+    key.gen(p);
+    if computed {
+        p.print(b']');
+    }
+}
+
+fn print_signature(
+    type_parameters: Option<&TSTypeParameterDeclaration<'_>>,
+    params: &FormalParameters<'_>,
+    return_type: Option<&TSTypeAnnotation<'_>>,
+    p: &mut Codegen,
+) {
+    print_type_parameters(type_parameters, p);
+    p.print(b'(');
+    params.gen(p);
+    p.print(b')');
+    print_type_annotation(return_type, p);
+}
+
+impl<'a> Gen for TSIndexSignature<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        p.print(b'[');
+        for (index, parameter) in self.parameters.iter().enumerate() {
+            if index != 0 {
+                p.print_comma();
+                p.print_space();
+            }
+            p.print_str(parameter.name.as_bytes());
+            print_type_annotation(Some(&parameter.type_annotation), p);
+        }
+        p.print(b']');
+        print_type_annotation(Some(&self.type_annotation), p);
+    }
+}
+
+impl<'a> Gen for TSTypeAliasDeclaration<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        print_declare(&self.modifiers, p);
+        p.print_str(b"type ");
+        self.id.gen(p);
+        print_type_parameters(self.type_parameters.as_deref(), p);
+        p.print_space();
+        p.print_equal();
+        p.print_space();
+        self.type_annotation.gen(p);
+        p.print_semicolon_after_statement();
+    }
+}
+
+impl<'a> Gen for TSInterfaceDeclaration<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        print_declare(&self.modifiers, p);
+        p.print_str(b"interface ");
+        self.id.gen(p);
+        print_type_parameters(self.type_parameters.as_deref(), p);
+        if let Some(extends) = &self.extends {
+            p.print_str(b" extends ");
+            for (index, heritage) in extends.iter().enumerate() {
+                if index != 0 {
+                    p.print_comma();
+                    p.print_space();
+                }
+                heritage.expression.gen(p);
+                print_type_arguments(heritage.type_parameters.as_deref(), p);
+            }
+        }
+        p.print_space();
+        print_signatures(&self.body.body, p);
+        p.print_newline();
+    }
+}
+
+impl<'a> Gen for TSEnumDeclaration<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        print_declare(&self.modifiers, p);
+        if self.modifiers.contains(ModifierKind::Const) {
+            p.print_str(b"const ");
+        }
+        p.print_str(b"enum ");
+        self.id.gen(p);
+        p.print_space();
+        p.print(b'{');
+        p.indent();
+        for (index, member) in self.body.members.iter().enumerate() {
+            if index != 0 {
+                p.print_comma();
+            }
+            p.print_newline();
+            p.print_indent();
+            match &member.id {
+                TSEnumMemberName::Identifier(ident) => ident.gen(p),
+                TSEnumMemberName::StringLiteral(lit) => lit.gen(p),
+                TSEnumMemberName::ComputedPropertyName(expr) => {
+                    p.print(b'[');
+                    expr.gen(p);
+                    p.print(b']');
+                }
+                TSEnumMemberName::NumberLiteral(lit) => lit.gen(p),
+            }
+            if let Some(init) = &member.initializer {
+                p.print_space();
+                p.print_equal();
+                p.print_space();
+                init.gen(p);
+            }
+        }
+        if !self.body.members.is_empty() {
+            p.print_trailing_comma();
+        }
+        p.dedent();
+        if !self.body.members.is_empty() {
+            p.print_newline();
+            p.print_indent();
+        }
+        p.print(b'}');
+        p.print_newline();
+    }
+}
+
+impl<'a> Gen for TSModuleDeclaration<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        print_declare(&self.modifiers, p);
+        match &self.id {
+            // `declare global { }`
+            TSModuleDeclarationName::Identifier(ident) if ident.name == "global" => {}
+            TSModuleDeclarationName::Identifier(_) => p.print_str(b"namespace "),
+            TSModuleDeclarationName::StringLiteral(_) => p.print_str(b"module "),
+        }
+        let mut decl = self;
+        loop {
+            match &decl.id {
+                TSModuleDeclarationName::Identifier(ident) => ident.gen(p),
+                TSModuleDeclarationName::StringLiteral(lit) => lit.gen(p),
+            }
+            match &decl.body {
+                // `a.b` of `namespace a.b { }`
+                TSModuleDeclarationBody::TSModuleDeclaration(body) => {
+                    p.print(b'.');
+                    decl = body;
+                }
+                TSModuleDeclarationBody::TSModuleBlock(block) => {
+                    // `declare module 'a';` has no block in the source text
+                    if block.body.is_empty() && block.span.end <= block.span.start {
+                        p.print_semicolon_after_statement();
+                        return;
+                    }
+                    p.print_space();
+                    p.print(b'{');
+                    p.print_newline();
+                    p.indent();
+                    for stmt in &block.body {
+                        p.print_semicolon_if_needed();
+                        stmt.gen(p);
+                    }
+                    p.dedent();
+                    p.needs_semicolon = false;
+                    p.print_indent();
+                    p.print(b'}');
+                    p.print_newline();
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Gen for TSImportEqualsDeclaration<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_str(b"import ");
+        if self.import_kind.is_type() {
+            p.print_str(b"type ");
+        }
+        self.id.gen(p);
+        p.print_space();
+        p.print_equal();
+        p.print_space();
+        match &*self.module_reference {
+            TSModuleReference::TypeName(name) => name.gen(p),
+            TSModuleReference::ExternalModuleReference(reference) => {
+                p.print_str(b"require(");
+                reference.expression.gen(p);
+                p.print(b')');
+            }
+        }
+        p.print_semicolon_after_statement();
+    }
+}
+
+impl<'a> Gen for TSExportAssignment<'a> {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_str(b"export");
+        p.print_space();
+        p.print_equal();
+        p.print_space();
+        self.expression.gen(p);
+        p.print_semicolon_after_statement();
+    }
+}
+
+impl Gen for TSNamespaceExportDeclaration {
+    fn gen(&self, p: &mut Codegen) {
+        p.print_str(b"export as namespace ");
+        self.id.gen(p);
+        p.print_semicolon_after_statement();
+    }
+}
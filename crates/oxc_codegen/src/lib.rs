@@ -0,0 +1,710 @@
+//! Oxc Codegen
+//!
+//! Prints the AST back into JavaScript source text.
+//! TypeScript syntax such as type annotations and interfaces is only printed with
+//! [`CodegenOptions::typescript`].
+//! Code adapted from [esbuild](https://github.com/evanw/esbuild/blob/main/internal/js_printer/js_printer.go)
+
+mod gen;
+mod gen_ts;
+mod sourcemap;
+
+use std::collections::BTreeMap;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_ast::{CommentAttachments, CommentKind, CommentPosition, Trivias};
+use oxc_span::Span;
+
+use crate::sourcemap::SourceMapBuilder;
+pub use crate::{gen::Gen, sourcemap::SourceMap};
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy)]
+pub struct CodegenOptions {
+    /// Number of spaces per indentation level, ignored when indenting with tabs
+    pub indentation: u8,
+    pub indent_style: IndentStyle,
+    pub line_ending: LineEnding,
+    /// Print without whitespace, semicolons and parentheses which are not needed,
+    /// e.g. `if(a){b=(c)}` for `if (a) { b = (c); }`, as a whitespace-only minifier
+    pub compact: bool,
+    /// Which comments to print, see [`Codegen::with_comments`]
+    pub comments: Comments,
+    /// Quotes of string literals which are not printed as written
+    pub quote: Quote,
+    /// End statements with semicolons, otherwise only print the semicolons needed to
+    /// separate statements, e.g. before a statement starting with `(`
+    pub semicolons: bool,
+    /// Print a comma after the last item of lists printed on multiple lines, i.e. the
+    /// properties of objects, the members of enums and the attributes of imports
+    pub trailing_commas: bool,
+    pub arrow_parens: ArrowParens,
+    /// Print TypeScript syntax such as type annotations, interfaces and enums, which is
+    /// otherwise omitted
+    pub typescript: bool,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            indentation: 4,
+            indent_style: IndentStyle::Space,
+            line_ending: LineEnding::Lf,
+            compact: false,
+            comments: Comments::None,
+            quote: Quote::Single,
+            semicolons: true,
+            trailing_commas: false,
+            arrow_parens: ArrowParens::Always,
+            typescript: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Quote {
+    /// `'a'`
+    #[default]
+    Single,
+    /// `"a"`
+    Double,
+}
+
+impl Quote {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            Self::Single => b'\'',
+            Self::Double => b'"',
+        }
+    }
+}
+
+/// Parentheses around the parameter of arrow functions with a single identifier parameter
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum ArrowParens {
+    /// `(x) => x`
+    #[default]
+    Always,
+    /// `x => x`
+    Avoid,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Comments {
+    /// No comments, except the `/* #__PURE__ */` annotations of calls, which are printed from
+    /// the `pure` flag of the call rather than from the comments
+    #[default]
+    None,
+    /// License banners, i.e. `/*! ... */` and comments containing `@license` or `@preserve`,
+    /// and annotations such as `/* #__NO_SIDE_EFFECTS__ */` which change how bundlers treat the
+    /// code
+    License,
+    All,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum IndentStyle {
+    #[default]
+    Space,
+    Tab,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+        }
+    }
+}
+
+#[allow(clippy::struct_excessive_bools)]
+pub struct Codegen {
+    options: CodegenOptions,
+
+    /// Output Code
+    code: Vec<u8>,
+
+    /// Current indentation level
+    indentation: u32,
+
+    // states
+    needs_semicolon: bool,
+
+    /// A semicolon omitted at the end of the previous statement,
+    /// printed only if the next statement would otherwise continue it
+    needs_asi_semicolon: bool,
+
+    /// A space requested in compact mode,
+    /// printed only if the next token would otherwise be joined to the previous one
+    needs_space: bool,
+
+    /// Length of the code after the closing `/` of a regular expression without flags,
+    /// where an identifier character would be read as a flag, e.g. `/a/instanceof b`
+    prev_reg_exp_end: usize,
+
+    // Quote property with double quotes
+    quote_property_with_double_quotes: bool,
+
+    sourcemap: Option<SourceMapBuilder>,
+
+    /// Mapping for the statement being printed, added once its indentation is printed
+    pending_source_mapping: Option<Span>,
+
+    /// Comments to print, keyed by the span of the node they are attached to,
+    /// removed once printed
+    comments: BTreeMap<Span, Vec<PrintedComment>>,
+
+    /// Source text to print string literals from as written, see [`Codegen::with_raw_literals`]
+    raw_source_text: Option<String>,
+}
+
+struct PrintedComment {
+    /// Text of the comment, including `//`, `/*` and `*/`
+    text: String,
+    kind: CommentKind,
+    position: CommentPosition,
+}
+
+pub struct CodegenReturn {
+    pub source_text: String,
+    /// `None` unless enabled by [`Codegen::with_source_map`]
+    pub source_map: Option<SourceMap>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Separator {
+    Comma,
+    Semicolon,
+    None,
+}
+
+/// Codegen interface for pretty print or minification
+impl Codegen {
+    pub fn new(source_len: usize, options: CodegenOptions) -> Self {
+        Self {
+            options,
+            code: Vec::with_capacity(source_len),
+            indentation: 0,
+            needs_semicolon: false,
+            needs_asi_semicolon: false,
+            needs_space: false,
+            prev_reg_exp_end: 0,
+            quote_property_with_double_quotes: false,
+            sourcemap: None,
+            pending_source_mapping: None,
+            comments: BTreeMap::new(),
+            raw_source_text: None,
+        }
+    }
+
+    /// Print string literals as written in `source_text`, keeping their quotes and escapes,
+    /// unless a transform changed their value.
+    ///
+    /// Number literals are always printed as written if their value is unchanged.
+    #[must_use]
+    pub fn with_raw_literals(mut self, source_text: &str) -> Self {
+        self.raw_source_text = Some(source_text.to_string());
+        self
+    }
+
+    /// The source text of the string literal at `span`, if it is still the literal of `value`
+    fn raw_string_literal(&self, span: Span, value: &str) -> Option<String> {
+        let raw = self.raw_source_text.as_ref()?.get(span.start as usize..span.end as usize)?;
+        let quote = raw.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+        let content = raw.strip_prefix(quote)?.strip_suffix(quote)?;
+        (unescape_simple(content)? == value).then(|| raw.to_string())
+    }
+
+    /// Print the comments of `program` selected by [`CodegenOptions::comments`],
+    /// next to the nodes they are attached to.
+    ///
+    /// Comments on their own line before or at the end of the line after a statement stay there.
+    /// Other comments are printed inline as block comments. Dangling comments
+    /// (e.g. in an empty function body) are printed on their own line in the program,
+    /// blocks and function bodies, and dropped elsewhere.
+    #[must_use]
+    pub fn with_comments<'a>(
+        mut self,
+        source_text: &str,
+        trivias: &Trivias,
+        program: &'a Program<'a>,
+    ) -> Self {
+        if self.options.comments == Comments::None {
+            return self;
+        }
+        let attachments = CommentAttachments::new(source_text, trivias, program);
+        for (span, comments) in attachments.iter() {
+            let comments = comments
+                .iter()
+                .filter_map(|comment| {
+                    let text = comment.span.source_text(source_text);
+                    // Printed for the calls marked as pure in all modes
+                    if is_pure_annotation(text) {
+                        return None;
+                    }
+                    let keep = match self.options.comments {
+                        Comments::None => false,
+                        Comments::License => is_license_or_annotation(text, comment.kind),
+                        Comments::All => true,
+                    };
+                    keep.then(|| {
+                        let text = match comment.kind {
+                            CommentKind::SingleLine => format!("//{text}"),
+                            CommentKind::MultiLine => format!("/*{text}*/"),
+                        };
+                        PrintedComment { text, kind: comment.kind, position: comment.position }
+                    })
+                })
+                .collect::<Vec<_>>();
+            if !comments.is_empty() {
+                self.comments.insert(span, comments);
+            }
+        }
+        self
+    }
+
+    /// Generate a source map from the printed code to `source_text`,
+    /// which is the source text of the program named `source_name`.
+    #[must_use]
+    pub fn with_source_map(mut self, source_name: &str, source_text: &str) -> Self {
+        self.sourcemap = Some(SourceMapBuilder::new(source_name, source_text));
+        self
+    }
+
+    pub fn build(mut self, program: &Program<'_>) -> String {
+        program.gen(&mut self);
+        self.into_code()
+    }
+
+    pub fn build_with_source_map(mut self, program: &Program<'_>) -> CodegenReturn {
+        program.gen(&mut self);
+        let source_map = self.sourcemap.take().map(|builder| builder.build(None));
+        CodegenReturn { source_text: self.into_code(), source_map }
+    }
+
+    /// Map the current position of the printed code to the start of `span`
+    #[inline]
+    pub fn add_source_mapping(&mut self, span: Span) {
+        if let Some(sourcemap) = &mut self.sourcemap {
+            self.pending_source_mapping = None;
+            sourcemap.add_mapping(&self.code, span, None);
+        }
+    }
+
+    /// Same as [`Codegen::add_source_mapping`] for an identifier, recording its original `name`
+    #[inline]
+    pub fn add_source_mapping_for_name(&mut self, span: Span, name: &str) {
+        if let Some(sourcemap) = &mut self.sourcemap {
+            self.pending_source_mapping = None;
+            sourcemap.add_mapping(&self.code, span, Some(name));
+        }
+    }
+
+    /// Map the start of a statement, after its indentation
+    #[inline]
+    fn add_pending_source_mapping(&mut self, span: Span) {
+        if self.sourcemap.is_some() {
+            self.pending_source_mapping = Some(span);
+        }
+    }
+
+    /// Take the comments in `position` attached to the node with `span`
+    fn take_comments(&mut self, span: Span, position: CommentPosition) -> Vec<PrintedComment> {
+        if self.comments.is_empty() {
+            return vec![];
+        }
+        let Some(comments) = self.comments.get_mut(&span) else { return vec![] };
+        let (taken, rest): (Vec<_>, Vec<_>) =
+            comments.drain(..).partition(|comment| comment.position == position);
+        *comments = rest;
+        taken
+    }
+
+    /// Print the leading comments of a statement, each on its own line
+    fn print_leading_comments_on_own_line(&mut self, span: Span) {
+        for comment in self.take_comments(span, CommentPosition::Leading) {
+            self.print_semicolon_if_needed();
+            self.print_indent();
+            self.print_str(comment.text.as_bytes());
+            self.print_comment_line_break(comment.kind);
+        }
+    }
+
+    /// Print the trailing comments of a statement at the end of its last line
+    fn print_trailing_comments_at_end_of_line(&mut self, comments: Vec<PrintedComment>) {
+        if comments.is_empty() {
+            return;
+        }
+        let line_ending = self.options.line_ending.as_str().as_bytes();
+        let ended_line = self.code.ends_with(line_ending);
+        if ended_line {
+            self.code.truncate(self.code.len() - line_ending.len());
+        }
+        self.print_semicolon_if_needed();
+        for comment in comments {
+            self.print_space();
+            self.print_str(comment.text.as_bytes());
+            if comment.kind == CommentKind::SingleLine {
+                self.print_hard_newline();
+                return;
+            }
+        }
+        if ended_line {
+            self.print_newline();
+        }
+    }
+
+    /// Print the leading or trailing comments of an expression, as block comments
+    fn print_inline_comments(&mut self, span: Span, position: CommentPosition) {
+        for comment in self.take_comments(span, position) {
+            let text = match comment.kind {
+                CommentKind::MultiLine => comment.text,
+                // `*/` would end the block comment early
+                CommentKind::SingleLine if comment.text.contains("*/") => continue,
+                CommentKind::SingleLine => format!("/*{}*/", &comment.text[2..]),
+            };
+            if position == CommentPosition::Trailing {
+                self.print_space();
+            }
+            self.print_str(text.as_bytes());
+            if position == CommentPosition::Leading {
+                self.print_space();
+            }
+        }
+    }
+
+    /// Print `/* #__PURE__ */` before a call or `new` expression
+    fn print_pure_annotation(&mut self) {
+        self.print_str(b"/* #__PURE__ */");
+        self.print_space();
+    }
+
+    /// Print the dangling comments of the program, a block or a function body,
+    /// e.g. a file with only comments
+    fn print_dangling_comments_on_own_line(&mut self, span: Span) {
+        for comment in self.take_comments(span, CommentPosition::Dangling) {
+            self.print_semicolon_if_needed();
+            self.print_indent();
+            self.print_str(comment.text.as_bytes());
+            self.print_comment_line_break(comment.kind);
+        }
+    }
+
+    fn print_comment_line_break(&mut self, kind: CommentKind) {
+        match kind {
+            CommentKind::SingleLine => self.print_hard_newline(),
+            CommentKind::MultiLine => self.print_newline(),
+        }
+    }
+
+    #[inline]
+    pub fn into_code(self) -> String {
+        unsafe { String::from_utf8_unchecked(self.code) }
+    }
+
+    pub fn code(&self) -> &Vec<u8> {
+        &self.code
+    }
+
+    /// Push a single character into the buffer
+    #[inline]
+    pub fn print(&mut self, ch: u8) {
+        self.print_asi_semicolon_if_needed(&[ch]);
+        self.print_space_if_needed(ch);
+        self.code.push(ch);
+    }
+
+    /// Push a string into the buffer
+    #[inline]
+    pub fn print_str(&mut self, s: &[u8]) {
+        self.print_asi_semicolon_if_needed(s);
+        if let Some(&first) = s.first() {
+            self.print_space_if_needed(first);
+        }
+        self.code.extend_from_slice(s);
+    }
+
+    #[inline]
+    pub fn print_space(&mut self) {
+        if self.options.compact {
+            self.needs_space = true;
+        } else {
+            self.code.push(b' ');
+        }
+    }
+
+    /// A line break, which is only a separator between tokens in compact mode
+    #[inline]
+    pub fn print_newline(&mut self) {
+        if self.options.compact {
+            self.needs_space = true;
+        } else {
+            self.print_hard_newline();
+        }
+    }
+
+    /// A line break in all modes, e.g. ending a hashbang
+    #[inline]
+    pub fn print_hard_newline(&mut self) {
+        self.needs_space = false;
+        self.code.extend_from_slice(self.options.line_ending.as_str().as_bytes());
+    }
+
+    /// Print the semicolon omitted at the end of the previous statement if the next statement
+    /// starts with `next`, which would otherwise continue it, e.g. `a` and `(b)` as `a(b)`
+    #[inline]
+    fn print_asi_semicolon_if_needed(&mut self, next: &[u8]) {
+        if self.needs_asi_semicolon {
+            self.needs_asi_semicolon = false;
+            let continues = match next {
+                // Comments
+                [b'/', b'/' | b'*', ..] => false,
+                // `*` continues a class property before a generator method
+                [first, ..] => matches!(first, b'(' | b'[' | b'`' | b'+' | b'-' | b'/' | b'*'),
+                [] => {
+                    self.needs_asi_semicolon = true;
+                    false
+                }
+            };
+            if continues {
+                self.code.push(b';');
+            }
+        }
+    }
+
+    #[inline]
+    fn print_space_if_needed(&mut self, next: u8) {
+        if self.needs_space {
+            self.needs_space = false;
+            let after_reg_exp = self.code.len() == self.prev_reg_exp_end && is_word(next);
+            if after_reg_exp || self.code.last().map_or(false, |&last| is_joined(last, next)) {
+                self.code.push(b' ');
+            }
+        }
+    }
+
+    #[inline]
+    pub fn indent(&mut self) {
+        self.indentation += 1;
+    }
+
+    #[inline]
+    pub fn dedent(&mut self) {
+        self.indentation -= 1;
+    }
+
+    #[inline]
+    pub fn print_semicolon(&mut self) {
+        self.print(b';');
+    }
+
+    #[inline]
+    pub fn print_comma(&mut self) {
+        self.print(b',');
+    }
+
+    /// The comma after the last item of a list printed on multiple lines
+    #[inline]
+    pub fn print_trailing_comma(&mut self) {
+        if self.options.trailing_commas && !self.options.compact {
+            self.print_comma();
+        }
+    }
+
+    fn print_semicolon_after_statement(&mut self) {
+        // The semicolon before a `}` or the end of the program is omitted in compact mode
+        if self.options.compact {
+            self.needs_semicolon = true;
+        } else if !self.options.semicolons {
+            self.needs_asi_semicolon = true;
+            self.print_newline();
+        } else {
+            self.print_semicolon();
+            self.print_newline();
+        }
+    }
+
+    fn print_semicolon_if_needed(&mut self) {
+        if self.needs_semicolon {
+            self.print_semicolon();
+            self.needs_semicolon = false;
+        }
+    }
+
+    #[inline]
+    pub fn print_ellipsis(&mut self) {
+        self.print_str(b"...");
+    }
+
+    #[inline]
+    pub fn print_colon(&mut self) {
+        self.print(b':');
+    }
+
+    #[inline]
+    pub fn print_equal(&mut self) {
+        self.print(b'=');
+    }
+
+    pub fn print_indent(&mut self) {
+        // Not after `export` in `export const a = 1;`, which is on the line of the declaration
+        let at_line_start = self.code.last().map_or(true, |&last| last == b'\n');
+        match self.options.indent_style {
+            _ if self.options.compact || !at_line_start => {}
+            IndentStyle::Space => {
+                let width = self.indentation * u32::from(self.options.indentation);
+                self.code.extend(std::iter::repeat(b' ').take(width as usize));
+            }
+            IndentStyle::Tab => {
+                self.code.extend(std::iter::repeat(b'\t').take(self.indentation as usize));
+            }
+        }
+        if let Some(span) = self.pending_source_mapping.take() {
+            self.add_source_mapping(span);
+        }
+    }
+
+    #[inline]
+    pub fn print_sequence<T: Gen>(&mut self, items: &[T], separator: Separator) {
+        let len = items.len();
+        for (index, item) in items.iter().enumerate() {
+            self.print_indent();
+            item.gen(self);
+            match separator {
+                Separator::Semicolon => self.print_semicolon(),
+                Separator::Comma if index != len - 1 => self.print_comma(),
+                Separator::Comma => self.print_trailing_comma(),
+                Separator::None => {}
+            }
+            if index != len - 1 {
+                self.print_newline();
+            }
+        }
+    }
+
+    #[inline]
+    pub fn print_body(&mut self, stmt: &Statement<'_>) {
+        if let Statement::BlockStatement(block) = stmt {
+            self.print_space();
+            self.print_block1(block);
+            self.print_newline();
+        } else {
+            self.print_newline();
+            self.indent();
+            stmt.gen(self);
+            self.dedent();
+        }
+    }
+
+    #[inline]
+    pub fn print_block1(&mut self, stmt: &BlockStatement<'_>) {
+        // Comments not printed on their own line, e.g. `else /* c */ {`
+        self.print_inline_comments(stmt.span, CommentPosition::Leading);
+        self.print(b'{');
+        self.print_newline();
+        self.indent();
+        for item in &stmt.body {
+            self.print_semicolon_if_needed();
+            item.gen(self);
+        }
+        self.print_dangling_comments_on_own_line(stmt.span);
+        self.dedent();
+        self.needs_semicolon = false;
+        self.print_indent();
+        self.print(b'}');
+    }
+
+    #[inline]
+    pub fn print_block<T: Gen>(&mut self, items: &[T], separator: Separator) {
+        self.print(b'{');
+        self.indent();
+        if !items.is_empty() {
+            self.print_newline();
+        }
+        self.print_sequence(items, separator);
+        self.dedent();
+        if !items.is_empty() {
+            self.print_newline();
+            self.print_indent();
+        }
+        self.print(b'}');
+    }
+
+    #[inline]
+    pub fn print_list<T: Gen>(&mut self, items: &[T]) {
+        for (index, item) in items.iter().enumerate() {
+            if index != 0 {
+                self.print_comma();
+                self.print_space();
+            }
+            item.gen(self);
+        }
+    }
+
+    pub fn last_char(&self) -> Option<&u8> {
+        self.code.last()
+    }
+}
+
+/// Whether printing `next` right after `last` would change the tokens,
+/// e.g. `let a` as `leta`, `a - -b` as `a--b`, `a / /b/` as `a//b/`, or `a < !b` as `a<!b`,
+/// where `<!--` starts an HTML-like comment
+fn is_joined(last: u8, next: u8) -> bool {
+    (is_word(last) && is_word(next))
+        || (last == next && matches!(next, b'+' | b'-'))
+        || (last == b'/' && matches!(next, b'/' | b'*'))
+        || (last == b'<' && next == b'!')
+}
+
+/// Whether the byte can be part of an identifier, keyword or number
+fn is_word(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$' | b'\\') || b >= 0x80
+}
+
+/// Whether a comment is kept by [`Comments::License`]
+fn is_license_or_annotation(text: &str, kind: CommentKind) -> bool {
+    (kind == CommentKind::MultiLine && text.starts_with('!'))
+        || text.contains("@license")
+        || text.contains("@preserve")
+        || matches!(text.trim(), "#__NO_SIDE_EFFECTS__" | "@__NO_SIDE_EFFECTS__")
+}
+
+/// `/* #__PURE__ */`, which is printed from [`CallExpression::pure`] and [`NewExpression::pure`]
+fn is_pure_annotation(text: &str) -> bool {
+    matches!(text.trim(), "#__PURE__" | "@__PURE__")
+}
+
+/// The value of string literal content with only single character escapes such as `\n` and `\'`,
+/// or `None` if it has other escapes
+fn unescape_simple(content: &str) -> Option<String> {
+    let mut value = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        value.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            c @ ('\'' | '"' | '\\') => c,
+            _ => return None,
+        });
+    }
+    Some(value)
+}
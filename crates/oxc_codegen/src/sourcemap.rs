@@ -0,0 +1,238 @@
+//! [Source Map Revision 3](https://sourcemaps.info/spec.html)
+//!
+//! Mappings are recorded while printing, from the current position in the output code
+//! to the start of the span of the node being printed in the original source text.
+
+use oxc_span::Span;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A generated source map, see [`crate::Codegen::with_source_map`]
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// Name of the generated file
+    pub file: Option<String>,
+    pub sources: Vec<String>,
+    pub sources_content: Vec<String>,
+    pub names: Vec<String>,
+    /// Base64 VLQ encoded mappings
+    pub mappings: String,
+}
+
+impl SourceMap {
+    pub fn to_json_string(&self) -> String {
+        let mut json = serde_json::json!({
+            "version": 3,
+            "sources": self.sources,
+            "sourcesContent": self.sources_content,
+            "names": self.names,
+            "mappings": self.mappings,
+        });
+        if let Some(file) = &self.file {
+            json["file"] = serde_json::Value::from(file.as_str());
+        }
+        json.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Mapping {
+    generated_line: u32,
+    generated_column: u32,
+    original_line: u32,
+    original_column: u32,
+    name: Option<u32>,
+}
+
+pub struct SourceMapBuilder {
+    source_name: String,
+    source_text: String,
+    /// Byte offsets of the start of each line in the source text
+    line_offsets: Vec<u32>,
+    names: Vec<String>,
+    mappings: Vec<Mapping>,
+    /// Position in the output code up to `scanned`, columns are in UTF-16 code units
+    generated_line: u32,
+    generated_column: u32,
+    scanned: usize,
+}
+
+impl SourceMapBuilder {
+    pub fn new(source_name: &str, source_text: &str) -> Self {
+        Self {
+            source_name: source_name.to_string(),
+            source_text: source_text.to_string(),
+            line_offsets: line_offsets(source_text),
+            names: vec![],
+            mappings: vec![],
+            generated_line: 0,
+            generated_column: 0,
+            scanned: 0,
+        }
+    }
+
+    /// Map the end of `code` to the start of `span` in the original source text
+    pub fn add_mapping(&mut self, code: &[u8], span: Span, name: Option<&str>) {
+        self.scan(code);
+        let (original_line, original_column) = self.original_position(span.start);
+        let name = name.map(|name| self.add_name(name));
+        let mapping = Mapping {
+            generated_line: self.generated_line,
+            generated_column: self.generated_column,
+            original_line,
+            original_column,
+            name,
+        };
+        match self.mappings.last_mut() {
+            // A node printed at the same position as its parent, e.g. the callee of a call,
+            // is more precise than its parent
+            Some(last)
+                if last.generated_line == mapping.generated_line
+                    && last.generated_column == mapping.generated_column =>
+            {
+                *last = mapping;
+            }
+            _ => self.mappings.push(mapping),
+        }
+    }
+
+    pub fn build(self, file: Option<String>) -> SourceMap {
+        SourceMap {
+            file,
+            mappings: encode_mappings(&self.mappings),
+            sources: vec![self.source_name],
+            sources_content: vec![self.source_text],
+            names: self.names,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn add_name(&mut self, name: &str) -> u32 {
+        if let Some(index) = self.names.iter().position(|n| n == name) {
+            index as u32
+        } else {
+            self.names.push(name.to_string());
+            self.names.len() as u32 - 1
+        }
+    }
+
+    /// Advance the generated position over the code printed since the last mapping
+    #[allow(clippy::cast_possible_truncation)]
+    fn scan(&mut self, code: &[u8]) {
+        // SAFETY: mappings are added between printed tokens, which are always complete chars
+        let text = unsafe { std::str::from_utf8_unchecked(&code[self.scanned..]) };
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\r' | '\n' => {
+                    if c == '\r' {
+                        chars.next_if_eq(&'\n');
+                    }
+                    self.generated_line += 1;
+                    self.generated_column = 0;
+                }
+                c => self.generated_column += c.len_utf16() as u32,
+            }
+        }
+        self.scanned = code.len();
+    }
+
+    /// Line and UTF-16 column of the byte `offset` in the source text
+    #[allow(clippy::cast_possible_truncation)]
+    fn original_position(&self, offset: u32) -> (u32, u32) {
+        let line = self.line_offsets.partition_point(|&start| start <= offset) - 1;
+        let start = self.line_offsets[line] as usize;
+        let end = (offset as usize).min(self.source_text.len());
+        let column = self.source_text.get(start..end).map_or(0, |text| text.encode_utf16().count());
+        (line as u32, column as u32)
+    }
+}
+
+/// Byte offsets of the start of each line, lines are ended by `\n`, `\r\n`, `\r`, `<LS>` and `<PS>`
+#[allow(clippy::cast_possible_truncation)]
+fn line_offsets(source_text: &str) -> Vec<u32> {
+    let mut offsets = vec![0];
+    let mut chars = source_text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\r' if chars.next_if(|(_, c)| *c == '\n').is_some() => {
+                offsets.push(index as u32 + 2);
+            }
+            '\r' | '\n' | '\u{2028}' | '\u{2029}' => offsets.push((index + c.len_utf8()) as u32),
+            _ => {}
+        }
+    }
+    offsets
+}
+
+fn encode_mappings(mappings: &[Mapping]) -> String {
+    let mut encoded = String::new();
+    let mut line = 0;
+    let mut previous_column = 0;
+    let mut previous_original_line = 0;
+    let mut previous_original_column = 0;
+    let mut previous_name = 0;
+    for (index, mapping) in mappings.iter().enumerate() {
+        if mapping.generated_line != line {
+            for _ in line..mapping.generated_line {
+                encoded.push(';');
+            }
+            line = mapping.generated_line;
+            previous_column = 0;
+        } else if index > 0 {
+            encoded.push(',');
+        }
+        encode_vlq(&mut encoded, i64::from(mapping.generated_column) - previous_column);
+        // There is only one source
+        encode_vlq(&mut encoded, 0);
+        encode_vlq(&mut encoded, i64::from(mapping.original_line) - previous_original_line);
+        encode_vlq(&mut encoded, i64::from(mapping.original_column) - previous_original_column);
+        if let Some(name) = mapping.name {
+            encode_vlq(&mut encoded, i64::from(name) - previous_name);
+            previous_name = i64::from(name);
+        }
+        previous_column = i64::from(mapping.generated_column);
+        previous_original_line = i64::from(mapping.original_line);
+        previous_original_column = i64::from(mapping.original_column);
+    }
+    encoded
+}
+
+/// Base64 VLQ: 5 bits per digit with a continuation bit, the lowest bit of the value is the sign
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn encode_vlq(encoded: &mut String, value: i64) {
+    let mut vlq = (if value < 0 { ((-value) << 1) | 1 } else { value << 1 }) as u64;
+    loop {
+        let mut digit = (vlq & 0b1_1111) as usize;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b10_0000;
+        }
+        encoded.push(BASE64[digit] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{encode_vlq, line_offsets};
+
+    #[test]
+    fn vlq() {
+        let cases =
+            [(0, "A"), (1, "C"), (-1, "D"), (15, "e"), (16, "gB"), (-16, "hB"), (1000, "w+B")];
+        for (value, expected) in cases {
+            let mut encoded = String::new();
+            encode_vlq(&mut encoded, value);
+            assert_eq!(encoded, expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(line_offsets(""), vec![0]);
+        assert_eq!(line_offsets("a\nb\r\nc\rd\u{2028}e"), vec![0, 2, 5, 7, 11]);
+    }
+}
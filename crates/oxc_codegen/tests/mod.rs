@@ -0,0 +1,280 @@
+use oxc_allocator::Allocator;
+use oxc_ast::{ast::NumberLiteral, VisitMut};
+use oxc_codegen::{ArrowParens, Codegen, CodegenOptions, Comments, IndentStyle, LineEnding, Quote};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test(source_text: &str, expected: &str, options: CodegenOptions) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default();
+    let program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let printed = Codegen::new(source_text.len(), options).build(&program);
+    assert_eq!(expected, printed, "for source {source_text}");
+}
+
+#[test]
+fn indentation() {
+    let source_text = "if (a) { if (b) { c; } }";
+    test(source_text, "if (a) {\n    if (b) {\n        c;\n    }\n}\n", CodegenOptions::default());
+    test(
+        source_text,
+        "if (a) {\n  if (b) {\n    c;\n  }\n}\n",
+        CodegenOptions { indentation: 2, ..CodegenOptions::default() },
+    );
+    test(
+        source_text,
+        "if (a) {\n\tif (b) {\n\t\tc;\n\t}\n}\n",
+        CodegenOptions { indent_style: IndentStyle::Tab, ..CodegenOptions::default() },
+    );
+}
+
+#[test]
+fn line_ending() {
+    test(
+        "if (a) { b; }",
+        "if (a) {\r\n    b;\r\n}\r\n",
+        CodegenOptions { line_ending: LineEnding::Crlf, ..CodegenOptions::default() },
+    );
+}
+
+#[test]
+fn source_map() {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default();
+    let source_text = "let a = 1;\n  foo(a);";
+    let program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let ret = Codegen::new(source_text.len(), CodegenOptions::default())
+        .with_source_map("test.js", source_text)
+        .build_with_source_map(&program);
+    assert_eq!(ret.source_text, "let a = 1;\nfoo(a);\n");
+
+    let source_map = ret.source_map.unwrap();
+    assert_eq!(source_map.names, vec!["a", "foo"]);
+    // `let` `a` `1`, then `foo` `a` which are indented by 2 in the source text
+    assert_eq!(source_map.mappings, "AAAA,IAAIA,IAAI;AACNC,IAAID");
+
+    let json: serde_json::Value = serde_json::from_str(&source_map.to_json_string()).unwrap();
+    assert_eq!(json["version"], 3);
+    assert_eq!(json["sources"][0], "test.js");
+    assert_eq!(json["sourcesContent"][0], source_text);
+
+    let ret =
+        Codegen::new(source_text.len(), CodegenOptions::default()).build_with_source_map(&program);
+    assert!(ret.source_map.is_none());
+}
+
+#[test]
+fn compact() {
+    let options = CodegenOptions { compact: true, ..CodegenOptions::default() };
+    test(
+        "if (a) {\n  let b = (c);\n  b += -d;\n} else {\n  e(a - -b, typeof (f));\n}\nfunction g(h) {\n  return (h);\n}\n",
+        "if(a){let b=c;b+=-d}else{e(a- -b,typeof f)}function g(h){return h}",
+        options,
+    );
+    // Parentheses which may be needed are kept
+    test("(function () {})();\n(1).toString();", "(function(){})();(1).toString()", options);
+    test("#!/usr/bin/env node\na;", "#!/usr/bin/env node\na", options);
+    // An identifier after a regular expression would be read as its flags
+    test("/a/ instanceof b; /a/g in c;", "/a/ instanceof b;/a/g in c", options);
+    // The `.` after an integer would be read as its decimal point
+    test("x = 1 .toString();", "x=1 .toString()", options);
+    test("x = 1_000 .toString();", "x=1_000 .toString()", options);
+    test("x = 1.5.toFixed(); y = 1..toString();", "x=1.5.toFixed();y=1..toString()", options);
+    test("x = 1e3.toString(); y = 0x10 .a;", "x=1e3.toString();y=0x10.a", options);
+    test("x = 1?.toString();", "x=1?.toString()", options);
+}
+
+fn test_comments(source_text: &str, expected: &str, comments: Comments) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let program = &*allocator.alloc(ret.program);
+    let options = CodegenOptions { comments, ..CodegenOptions::default() };
+    let printed = Codegen::new(source_text.len(), options)
+        .with_comments(source_text, &ret.trivias, program)
+        .build(program);
+    assert_eq!(expected, printed, "for source {source_text}");
+}
+
+#[test]
+fn comments() {
+    let source_text = "/*! license */\n// a\nlet a = 1; // b\nconst c = /* #__PURE__ */ foo();";
+    test_comments(
+        source_text,
+        "/*! license */\n// a\nlet a = 1; // b\nconst c = /* #__PURE__ */ foo();\n",
+        Comments::All,
+    );
+    test_comments(
+        source_text,
+        "/*! license */\nlet a = 1;\nconst c = /* #__PURE__ */ foo();\n",
+        Comments::License,
+    );
+    test_comments(source_text, "let a = 1;\nconst c = /* #__PURE__ */ foo();\n", Comments::None);
+
+    test_comments("foo(a /* b */, c);", "foo(a /* b */, c);\n", Comments::All);
+    test_comments("// a", "// a\n", Comments::All);
+}
+
+#[test]
+fn dangling_comments() {
+    let source_text = "function f() {\n    // inside\n}\n";
+    test_comments(source_text, "function f () {\n    // inside\n}\n", Comments::All);
+    test_comments(source_text, "function f () {\n}\n", Comments::License);
+    let source_text = "class A {\n    m() {\n        /* c */\n    }\n}\n";
+    test_comments(source_text, source_text, Comments::All);
+
+    let source_text = "{\n    /*! license */\n}\n";
+    test_comments(source_text, source_text, Comments::All);
+    test_comments(source_text, source_text, Comments::License);
+    test_comments(source_text, "{\n}\n", Comments::None);
+    let source_text = "if (a) {\n    b();\n} else /* c2 */ {\n    // c3\n}\n";
+    test_comments(source_text, source_text, Comments::All);
+}
+
+/// Replaces `1` by `Infinity`, `2` by `-Infinity` and other numbers by `NaN`
+struct NonFinite;
+
+impl<'a, 'b> VisitMut<'a, 'b> for NonFinite {
+    fn visit_number_literal(&mut self, lit: &'b mut NumberLiteral<'a>) {
+        lit.value = match lit.value {
+            1.0 => f64::INFINITY,
+            2.0 => f64::NEG_INFINITY,
+            _ => f64::NAN,
+        };
+    }
+}
+
+#[test]
+fn raw_literals() {
+    struct Double;
+    impl<'a, 'b> VisitMut<'a, 'b> for Double {
+        fn visit_number_literal(&mut self, lit: &'b mut NumberLiteral<'a>) {
+            lit.value *= 2.0;
+        }
+    }
+
+    let source_text = "x = [1e3, 0xFF, 1_000];\ny = [\"a\\n\", 'b', \"\\x63\"];";
+    let allocator = Allocator::default();
+    let source_type = SourceType::default();
+    let mut program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let printed = Codegen::new(source_text.len(), CodegenOptions::default())
+        .with_raw_literals(source_text)
+        .build(&program);
+    // `\x63` is printed from its value
+    assert_eq!(printed, "x = [1e3, 0xFF, 1_000];\ny = [\"a\\n\", 'b', 'c'];\n");
+
+    Double.visit_program(&mut program);
+    let printed = Codegen::new(source_text.len(), CodegenOptions::default()).build(&program);
+    assert_eq!(printed, "x = [2000, 510, 2000];\ny = ['a\\n', 'b', 'c'];\n");
+
+    let source_text = "x = 1 ** 2 + 3;";
+    let mut program = Parser::new(&allocator, source_text, source_type).parse().program;
+    NonFinite.visit_program(&mut program);
+    let printed = Codegen::new(source_text.len(), CodegenOptions::default()).build(&program);
+    assert_eq!(printed, "x = (1/ 0)** (-1/ 0)+ (0/ 0);\n");
+}
+
+#[test]
+fn style() {
+    test(
+        "x = { a: 'b\"', c: (d) => d };",
+        "x = {\n    a: \"b\\\"\",\n    c: d => d,\n};\n",
+        CodegenOptions {
+            quote: Quote::Double,
+            trailing_commas: true,
+            arrow_parens: ArrowParens::Avoid,
+            ..CodegenOptions::default()
+        },
+    );
+    let options = CodegenOptions { trailing_commas: true, ..CodegenOptions::default() };
+    test(
+        "import a from 'a' with { type: 'json' };",
+        "import a from 'a' with {\n    type: 'json',\n};\n",
+        options,
+    );
+    test(
+        "import a from 'a' with { type: 'json' };",
+        "import a from 'a' with {\n    type: 'json'\n};\n",
+        CodegenOptions::default(),
+    );
+    test_typescript(
+        "enum A { B, C }",
+        "enum A {\n    B,\n    C,\n}\n",
+        CodegenOptions { typescript: true, ..options },
+    );
+    test(
+        "a = 1;\n(b);\nif (c) { d; } else { [e] = f; }\n`g`;",
+        "a = 1\n;(b)\nif (c) {\n    d\n} else {\n    [e] = f\n}\n`g`\n",
+        CodegenOptions { semicolons: false, ..CodegenOptions::default() },
+    );
+}
+
+fn test_typescript(source_text: &str, expected: &str, options: CodegenOptions) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true).with_typescript(true);
+    let program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let printed = Codegen::new(source_text.len(), options).build(&program);
+    assert_eq!(expected, printed, "for source {source_text}");
+}
+
+#[test]
+fn typescript() {
+    let options = CodegenOptions { typescript: true, ..CodegenOptions::default() };
+    test_typescript(
+        "let a!: number; const b: (A | B)[] = [];",
+        "let a!: number;\nconst b: (A | B)[] = [];\n",
+        options,
+    );
+    test_typescript(
+        "type A<T extends object = {}> = keyof T extends infer U ? U : never;",
+        "type A<T extends object = {}> = keyof T extends infer U ? U : never;\n",
+        options,
+    );
+    test_typescript(
+        "type A = [a: string, b?: number, ...c: boolean[]] | (() => void);",
+        "type A = [a: string, b?: number, ...c: boolean[]] | (() => void);\n",
+        options,
+    );
+    test_typescript(
+        "type A = { readonly [K in keyof T]-?: T[K] };",
+        "type A = { readonly [K in keyof T]-?: T[K] };\n",
+        options,
+    );
+    test_typescript(
+        "interface A<T> extends B<T> { a?: T; b(c: number): void }",
+        "interface A<T> extends B<T> {\n    a?: T;\n    b(c: number): void;\n}\n",
+        options,
+    );
+    test_typescript(
+        "declare const enum A { B = 1, C }",
+        "declare const enum A {\n    B = 1,\n    C\n}\n",
+        options,
+    );
+    test_typescript(
+        "namespace A.B { export const c = 1; } declare module 'd';",
+        "namespace A.B {\n    export const c = 1;\n}\ndeclare module 'd';\n",
+        options,
+    );
+    test_typescript(
+        "function f<T>(a?: T, ...b: T[]): a is T { return a! as any; } function g(): void;",
+        "function f<T> (a?: T, ...b: T[]): a is T {\n    return a! as any;\n}\nfunction g (): void;\n",
+        options,
+    );
+    test_typescript("const f = <T,>(a: T): T => a;", "const f = <T,>(a: T): T => a;\n", options);
+    test_typescript(
+        "abstract class A<T> extends B<T> implements C { private readonly a?: T; protected abstract b(): void; constructor(public c: number) { super(); } }",
+        "abstract class A<T> extends B<T> implements C {\n    private readonly a?: T;\n    protected abstract b(): void;\n    constructor(public c: number) {\n        super();\n    }\n}\n",
+        options,
+    );
+    test_typescript(
+        "import type { A } from 'a'; import { type B } from 'b'; export type { C }; import d = require('d'); export = d;",
+        "import type { A } from 'a';\nimport { type B } from 'b';\nexport type { C };\nimport d = require('d');\nexport = d;\n",
+        options,
+    );
+    // Types are omitted without the option
+    test_typescript(
+        "let a: number = f<string>(b as C);",
+        "let a = f(b);\n",
+        CodegenOptions::default(),
+    );
+}
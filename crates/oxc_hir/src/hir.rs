@@ -706,6 +706,9 @@ pub struct CallExpression<'a> {
     pub callee: Expression<'a>,
     pub arguments: Vec<'a, Argument<'a>>,
     pub optional: bool, // for optional chaining
+    /// Annotated with `/* #__PURE__ */`, so the call can be removed if its value is unused
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub pure: bool,
 }
 
 impl<'a> CallExpression<'a> {
@@ -757,6 +760,9 @@ pub struct NewExpression<'a> {
     pub span: Span,
     pub callee: Expression<'a>,
     pub arguments: Vec<'a, Argument<'a>>,
+    /// Annotated with `/* #__PURE__ */`, so the expression can be removed if its value is unused
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub pure: bool,
 }
 
 /// Meta Property `new.target` | `import.meta`
@@ -1096,6 +1102,10 @@ pub enum VariableDeclarationKind {
     Var,
     Const,
     Let,
+    /// [Explicit Resource Management](https://github.com/tc39/proposal-explicit-resource-management)
+    Using,
+    #[cfg_attr(feature = "serde", serde(rename = "await using"))]
+    AwaitUsing,
 }
 
 impl VariableDeclarationKind {
@@ -1104,7 +1114,12 @@ impl VariableDeclarationKind {
     }
 
     pub fn is_lexical(&self) -> bool {
-        matches!(self, Self::Const | Self::Let)
+        matches!(self, Self::Const | Self::Let | Self::Using | Self::AwaitUsing)
+    }
+
+    /// `using` or `await using`
+    pub fn is_using(&self) -> bool {
+        matches!(self, Self::Using | Self::AwaitUsing)
     }
 }
 
@@ -1114,6 +1129,8 @@ impl fmt::Display for VariableDeclarationKind {
             Self::Var => "var",
             Self::Const => "const",
             Self::Let => "let",
+            Self::Using => "using",
+            Self::AwaitUsing => "await using",
         };
         write!(f, "{s}")
     }
@@ -1722,6 +1739,7 @@ pub struct AccessorProperty<'a> {
     pub value: Option<Expression<'a>>,
     pub computed: bool,
     pub r#static: bool,
+    pub decorators: Vec<'a, Decorator<'a>>,
 }
 
 #[derive(Debug, Hash)]
@@ -1740,8 +1758,8 @@ pub struct ImportDeclaration<'a> {
     pub span: Span,
     pub specifiers: Vec<'a, ImportDeclarationSpecifier>,
     pub source: StringLiteral,
-    pub assertions: Option<Vec<'a, ImportAttribute>>, // Some(vec![]) for empty assertion
-    pub import_kind: ImportOrExportKind,              // `import type { foo } from 'bar'`
+    pub with_clause: Option<WithClause<'a>>,
+    pub import_kind: ImportOrExportKind, // `import type { foo } from 'bar'`
 }
 
 #[derive(Debug, Hash)]
@@ -1781,6 +1799,24 @@ pub struct ImportNamespaceSpecifier {
     pub local: BindingIdentifier,
 }
 
+/// `with { type: "json" }` of `import data from "./data.json" with { type: "json" }`,
+/// or the legacy `assert { type: "json" }`
+#[derive(Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type", rename_all = "camelCase"))]
+pub struct WithClause<'a> {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub span: Span,
+    pub attributes_keyword: IdentifierName, // `with` or `assert`
+    pub with_entries: Vec<'a, ImportAttribute>,
+}
+
+impl<'a> WithClause<'a> {
+    /// Whether this is the deprecated `assert { ... }` form
+    pub fn is_assert(&self) -> bool {
+        self.attributes_keyword.name == "assert"
+    }
+}
+
 #[derive(Debug, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize), serde(tag = "type"))]
 pub struct ImportAttribute {
@@ -1814,6 +1850,7 @@ pub struct ExportNamedDeclaration<'a> {
     pub declaration: Option<Declaration<'a>>,
     pub specifiers: Vec<'a, ExportSpecifier>,
     pub source: Option<StringLiteral>,
+    pub with_clause: Option<WithClause<'a>>,
     pub export_kind: ImportOrExportKind, // `export type { foo }`
 }
 
@@ -1833,8 +1870,8 @@ pub struct ExportAllDeclaration<'a> {
     pub span: Span,
     pub exported: Option<ModuleExportName>,
     pub source: StringLiteral,
-    pub assertions: Option<Vec<'a, ImportAttribute>>, // Some(vec![]) for empty assertion
-    pub export_kind: ImportOrExportKind,              // `export type *`
+    pub with_clause: Option<WithClause<'a>>,
+    pub export_kind: ImportOrExportKind, // `export type *`
 }
 
 #[derive(Debug, Hash)]
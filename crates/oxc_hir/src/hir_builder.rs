@@ -459,8 +459,15 @@ impl<'a> HirBuilder<'a> {
         callee: Expression<'a>,
         arguments: Vec<'a, Argument<'a>>,
         optional: bool, // for optional chaining
+        pure: bool,
     ) -> Expression<'a> {
-        Expression::CallExpression(self.alloc(CallExpression { span, callee, arguments, optional }))
+        Expression::CallExpression(self.alloc(CallExpression {
+            span,
+            callee,
+            arguments,
+            optional,
+            pure,
+        }))
     }
 
     pub fn chain_expression(&mut self, span: Span, expression: ChainElement<'a>) -> Expression<'a> {
@@ -563,8 +570,9 @@ impl<'a> HirBuilder<'a> {
         span: Span,
         callee: Expression<'a>,
         arguments: Vec<'a, Argument<'a>>,
+        pure: bool,
     ) -> Expression<'a> {
-        Expression::NewExpression(self.alloc(NewExpression { span, callee, arguments }))
+        Expression::NewExpression(self.alloc(NewExpression { span, callee, arguments, pure }))
     }
 
     pub fn object_expression(
@@ -866,8 +874,9 @@ impl<'a> HirBuilder<'a> {
         value: Option<Expression<'a>>,
         computed: bool,
         r#static: bool,
+        decorators: Vec<'a, Decorator<'a>>,
     ) -> Box<'a, AccessorProperty<'a>> {
-        self.alloc(AccessorProperty { span, key, value, computed, r#static })
+        self.alloc(AccessorProperty { span, key, value, computed, r#static, decorators })
     }
 
     /* ---------- Declarations ---------- */
@@ -979,10 +988,19 @@ impl<'a> HirBuilder<'a> {
         span: Span,
         specifiers: Vec<'a, ImportDeclarationSpecifier>,
         source: StringLiteral,
-        assertions: Option<Vec<'a, ImportAttribute>>,
+        with_clause: Option<WithClause<'a>>,
         import_kind: ImportOrExportKind,
     ) -> Box<'a, ImportDeclaration<'a>> {
-        self.alloc(ImportDeclaration { span, specifiers, source, assertions, import_kind })
+        self.alloc(ImportDeclaration { span, specifiers, source, with_clause, import_kind })
+    }
+
+    pub fn with_clause(
+        &mut self,
+        span: Span,
+        attributes_keyword: IdentifierName,
+        with_entries: Vec<'a, ImportAttribute>,
+    ) -> WithClause<'a> {
+        WithClause { span, attributes_keyword, with_entries }
     }
 
     pub fn import_attribute(
@@ -1024,10 +1042,10 @@ impl<'a> HirBuilder<'a> {
         span: Span,
         exported: Option<ModuleExportName>,
         source: StringLiteral,
-        assertions: Option<Vec<'a, ImportAttribute>>, // Some(vec![]) for empty assertion
+        with_clause: Option<WithClause<'a>>,
         export_kind: ImportOrExportKind,
     ) -> Box<'a, ExportAllDeclaration<'a>> {
-        self.alloc(ExportAllDeclaration { span, exported, source, assertions, export_kind })
+        self.alloc(ExportAllDeclaration { span, exported, source, with_clause, export_kind })
     }
 
     pub fn export_default_declaration(
@@ -1045,9 +1063,17 @@ impl<'a> HirBuilder<'a> {
         declaration: Option<Declaration<'a>>,
         specifiers: Vec<'a, ExportSpecifier>,
         source: Option<StringLiteral>,
+        with_clause: Option<WithClause<'a>>,
         export_kind: ImportOrExportKind, // `export type { foo }`
     ) -> Box<'a, ExportNamedDeclaration<'a>> {
-        self.alloc(ExportNamedDeclaration { span, declaration, specifiers, source, export_kind })
+        self.alloc(ExportNamedDeclaration {
+            span,
+            declaration,
+            specifiers,
+            source,
+            with_clause,
+            export_kind,
+        })
     }
 
     pub fn export_specifier(
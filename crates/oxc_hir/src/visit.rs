@@ -1212,7 +1212,7 @@ pub trait Visit<'a>: Sized {
             self.visit_import_declaration_specifier(specifier);
         }
         // TODO: source
-        // TODO: assertions
+        // TODO: with_clause
     }
 
     fn visit_import_declaration_specifier(&mut self, specifier: &'a ImportDeclarationSpecifier) {
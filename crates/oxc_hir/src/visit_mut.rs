@@ -828,7 +828,7 @@ pub trait VisitMut<'a, 'b>: Sized {
             self.visit_import_declaration_specifier(specifier);
         }
         // TODO: source
-        // TODO: assertions
+        // TODO: with_clause
     }
 
     fn visit_import_declaration_specifier(
@@ -0,0 +1,128 @@
+//! JSON values with spans
+//!
+//! Spans are byte offsets into the source text.
+
+use oxc_span::{GetSpan, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonValue {
+    pub span: Span,
+    pub kind: JsonValueKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValueKind {
+    Null,
+    Boolean(bool),
+    /// Including `Infinity` and `NaN` of JSON5
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// Properties in source order, duplicated keys are kept
+    Object(Vec<JsonProperty>),
+}
+
+/// `"key": value` of an object
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonProperty {
+    pub key: String,
+    pub key_span: Span,
+    pub value: JsonValue,
+}
+
+impl GetSpan for JsonValue {
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+impl JsonValue {
+    /// The value of `key` when this is an object.
+    /// The last one wins for duplicated keys, same as `JSON.parse`.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        self.as_object()?.iter().rev().find(|property| property.key == key).map(|p| &p.value)
+    }
+
+    /// Look up a value by a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901),
+    /// e.g. `/compilerOptions/jsx` or `/overrides/0/files`
+    pub fn pointer(&self, pointer: &str) -> Option<&Self> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        let pointer = pointer.strip_prefix('/')?;
+        pointer.split('/').map(|token| token.replace("~1", "/").replace("~0", "~")).try_fold(
+            self,
+            |value, token| match &value.kind {
+                JsonValueKind::Object(_) => value.get(&token),
+                JsonValueKind::Array(elements) => {
+                    token.parse::<usize>().ok().and_then(|index| elements.get(index))
+                }
+                _ => None,
+            },
+        )
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self.kind, JsonValueKind::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.kind {
+            JsonValueKind::Boolean(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.kind {
+            JsonValueKind::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The number when it is a non-negative integer
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss,
+        clippy::float_cmp
+    )]
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_f64()
+            .filter(|value| *value >= 0.0 && *value <= u64::MAX as f64 && value.trunc() == *value)
+            .map(|value| value as u64)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.kind {
+            JsonValueKind::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Self]> {
+        match &self.kind {
+            JsonValueKind::Array(elements) => Some(elements.as_slice()),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[JsonProperty]> {
+        match &self.kind {
+            JsonValueKind::Object(properties) => Some(properties.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Name of the type for error messages, e.g. `expected a string, found an array`
+    pub fn type_name(&self) -> &'static str {
+        match self.kind {
+            JsonValueKind::Null => "null",
+            JsonValueKind::Boolean(_) => "a boolean",
+            JsonValueKind::Number(_) => "a number",
+            JsonValueKind::String(_) => "a string",
+            JsonValueKind::Array(_) => "an array",
+            JsonValueKind::Object(_) => "an object",
+        }
+    }
+}
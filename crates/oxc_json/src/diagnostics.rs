@@ -0,0 +1,10 @@
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_span::Span;
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Invalid JSON: {0}")]
+#[diagnostic()]
+pub struct InvalidJson(pub &'static str, #[label] pub Span);
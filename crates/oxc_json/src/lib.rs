@@ -0,0 +1,25 @@
+//! # Oxc JSON
+//!
+//! A small JSON parser keeping the span of every value,
+//! so errors in config files such as `.oxlintrc.json`, `package.json` and `tsconfig.json`
+//! can be reported with labels pointing into the file.
+//!
+//! Three flavors are supported, see [JsonKind]:
+//! * [JSON](https://www.json.org)
+//! * JSONC, JSON with comments and trailing commas as accepted by `tsconfig.json`
+//! * [JSON5](https://spec.json5.org)
+//!
+//! ```rust
+//! let value = JsonParser::new(r#"{ "rules": { "eqeqeq": "deny" } }"#, JsonKind::Jsonc).parse()?;
+//! let level = value.pointer("/rules/eqeqeq").and_then(JsonValue::as_str);
+//! ```
+
+mod ast;
+mod diagnostics;
+mod parser;
+
+pub use crate::{
+    ast::{JsonProperty, JsonValue, JsonValueKind},
+    diagnostics::InvalidJson,
+    parser::{JsonKind, JsonParser},
+};
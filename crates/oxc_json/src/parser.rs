@@ -0,0 +1,597 @@
+use oxc_span::Span;
+
+use crate::{
+    ast::{JsonProperty, JsonValue, JsonValueKind},
+    diagnostics::InvalidJson,
+};
+
+/// The flavor of JSON accepted by [JsonParser]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum JsonKind {
+    /// [RFC 8259](https://datatracker.ietf.org/doc/html/rfc8259)
+    #[default]
+    Json,
+    /// JSON with `//` and `/* */` comments and trailing commas
+    Jsonc,
+    /// [JSON5](https://spec.json5.org), a superset of JSONC
+    Json5,
+}
+
+impl JsonKind {
+    /// `.json5` files are JSON5, everything else is JSONC.
+    /// Most JSON config files, such as `tsconfig.json`, allow comments.
+    pub fn from_path(path: &std::path::Path) -> Self {
+        if path.extension().map_or(false, |ext| ext == "json5") {
+            Self::Json5
+        } else {
+            Self::Jsonc
+        }
+    }
+
+    fn allows_comments(self) -> bool {
+        matches!(self, Self::Jsonc | Self::Json5)
+    }
+
+    fn is_json5(self) -> bool {
+        self == Self::Json5
+    }
+}
+
+pub struct JsonParser<'a> {
+    source_text: &'a str,
+    kind: JsonKind,
+    /// Byte offset of the current position
+    pos: usize,
+}
+
+type Result<T> = std::result::Result<T, InvalidJson>;
+
+impl<'a> JsonParser<'a> {
+    pub fn new(source_text: &'a str, kind: JsonKind) -> Self {
+        Self { source_text, kind, pos: 0 }
+    }
+
+    /// # Errors
+    ///
+    /// The first syntax error, parsing stops at the first error.
+    pub fn parse(mut self) -> Result<JsonValue> {
+        if self.source_text.starts_with('\u{feff}') {
+            self.pos = '\u{feff}'.len_utf8();
+        }
+        self.skip_trivia()?;
+        let value = self.parse_value()?;
+        self.skip_trivia()?;
+        if self.pos < self.source_text.len() {
+            return Err(self.error_at_char("Unexpected content after the value"));
+        }
+        Ok(value)
+    }
+
+    /* ---------- Cursor ---------- */
+
+    fn peek(&self) -> Option<u8> {
+        self.source_text.as_bytes().get(self.pos).copied()
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.source_text[self.pos..].chars().next()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source_text[self.pos..]
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let matched = self.peek() == Some(b);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn span(&self, start: usize) -> Span {
+        Span::new(start as u32, self.pos as u32)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn error_at_char(&self, message: &'static str) -> InvalidJson {
+        let len = self.peek_char().map_or(0, char::len_utf8);
+        InvalidJson(message, Span::new(self.pos as u32, (self.pos + len) as u32))
+    }
+
+    /* ---------- Trivia ---------- */
+
+    fn skip_trivia(&mut self) -> Result<()> {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n' | b'\r') => self.pos += 1,
+                Some(b'/') => {
+                    if !self.kind.allows_comments() {
+                        return Err(self.error_at_char("Comments are not allowed in JSON"));
+                    }
+                    self.skip_comment()?;
+                }
+                Some(_) if self.kind.is_json5() => match self.peek_char() {
+                    Some(c) if is_json5_whitespace(c) => self.pos += c.len_utf8(),
+                    _ => return Ok(()),
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) -> Result<()> {
+        let start = self.pos;
+        if self.rest().starts_with("//") {
+            let end = self.rest().find(['\n', '\r']).unwrap_or_else(|| self.rest().len());
+            self.pos += end;
+        } else if self.rest().starts_with("/*") {
+            let Some(end) = self.rest()[2..].find("*/") else {
+                self.pos = self.source_text.len();
+                return Err(InvalidJson("Unterminated comment", self.span(start)));
+            };
+            self.pos += end + 4;
+        } else {
+            return Err(self.error_at_char("Unexpected character"));
+        }
+        Ok(())
+    }
+
+    /* ---------- Values ---------- */
+
+    fn parse_value(&mut self) -> Result<JsonValue> {
+        let start = self.pos;
+        let kind = match self.peek() {
+            Some(b'{') => self.parse_object()?,
+            Some(b'[') => self.parse_array()?,
+            Some(b'"') => JsonValueKind::String(self.parse_string()?),
+            Some(b'\'') if self.kind.is_json5() => JsonValueKind::String(self.parse_string()?),
+            Some(b'-' | b'0'..=b'9') => JsonValueKind::Number(self.parse_number()?),
+            Some(b'+' | b'.' | b'I' | b'N') if self.kind.is_json5() => {
+                JsonValueKind::Number(self.parse_number()?)
+            }
+            _ if self.eat_keyword("null") => JsonValueKind::Null,
+            _ if self.eat_keyword("true") => JsonValueKind::Boolean(true),
+            _ if self.eat_keyword("false") => JsonValueKind::Boolean(false),
+            None => return Err(self.error_at_char("Unexpected end of input, expected a value")),
+            _ => return Err(self.error_at_char("Expected a value")),
+        };
+        Ok(JsonValue { span: self.span(start), kind })
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let rest = self.rest();
+        let matched = rest.starts_with(keyword)
+            && !rest[keyword.len()..].chars().next().map_or(false, is_identifier_part);
+        if matched {
+            self.pos += keyword.len();
+        }
+        matched
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValueKind> {
+        self.pos += 1; // `{`
+        let mut properties = vec![];
+        self.skip_trivia()?;
+        while !self.eat(b'}') {
+            let key_start = self.pos;
+            let key = match self.peek() {
+                Some(b'"') => self.parse_string()?,
+                Some(b'\'') if self.kind.is_json5() => self.parse_string()?,
+                Some(_) if self.kind.is_json5() => self.parse_identifier()?,
+                None => return Err(self.error_at_char("Unterminated object, expected '}'")),
+                Some(_) => return Err(self.error_at_char("Expected a property name in quotes")),
+            };
+            let key_span = self.span(key_start);
+            self.skip_trivia()?;
+            if !self.eat(b':') {
+                return Err(self.error_at_char("Expected ':' after the property name"));
+            }
+            self.skip_trivia()?;
+            let value = self.parse_value()?;
+            properties.push(JsonProperty { key, key_span, value });
+            self.skip_trivia()?;
+            if !self.parse_separator(b'}')? {
+                return Err(self.error_at_char("Expected ',' or '}' after the property"));
+            }
+        }
+        Ok(JsonValueKind::Object(properties))
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValueKind> {
+        self.pos += 1; // `[`
+        let mut elements = vec![];
+        self.skip_trivia()?;
+        while !self.eat(b']') {
+            if self.peek().is_none() {
+                return Err(self.error_at_char("Unterminated array, expected ']'"));
+            }
+            elements.push(self.parse_value()?);
+            self.skip_trivia()?;
+            if !self.parse_separator(b']')? {
+                return Err(self.error_at_char("Expected ',' or ']' after the element"));
+            }
+        }
+        Ok(JsonValueKind::Array(elements))
+    }
+
+    /// Parse the `,` after an element or property, returns `false` if neither `,` nor `close` follows.
+    /// `close` itself is left for the caller.
+    fn parse_separator(&mut self, close: u8) -> Result<bool> {
+        if self.peek() == Some(close) {
+            return Ok(true);
+        }
+        let comma = self.pos;
+        if !self.eat(b',') {
+            return Ok(false);
+        }
+        self.skip_trivia()?;
+        if self.peek() == Some(close) && self.kind == JsonKind::Json {
+            return Err(InvalidJson("Trailing commas are not allowed in JSON", self.span(comma)));
+        }
+        Ok(true)
+    }
+
+    /// `"..."`, or `'...'` in JSON5
+    fn parse_string(&mut self) -> Result<String> {
+        let start = self.pos;
+        let quote = self.peek_char().unwrap();
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            let Some(c) = self.peek_char() else {
+                return Err(InvalidJson("Unterminated string", self.span(start)));
+            };
+            match c {
+                c if c == quote => {
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                '\\' => {
+                    self.pos += 1;
+                    self.parse_escape(&mut value)?;
+                }
+                '\n' | '\r' => return Err(InvalidJson("Unterminated string", self.span(start))),
+                '\0'..='\u{1f}' => {
+                    return Err(self.error_at_char("Control characters must be escaped in strings"))
+                }
+                c => {
+                    value.push(c);
+                    self.pos += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    /// The escape sequence after `\`
+    fn parse_escape(&mut self, value: &mut String) -> Result<()> {
+        let Some(c) = self.peek_char() else {
+            return Err(self.error_at_char("Unterminated string"));
+        };
+        self.pos += c.len_utf8();
+        let escaped = match c {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => self.parse_unicode_escape()?,
+            _ if self.kind.is_json5() => match c {
+                '\'' => '\'',
+                'v' => '\u{b}',
+                '0' if !self.peek().map_or(false, |b| b.is_ascii_digit()) => '\0',
+                'x' => {
+                    let code = self.parse_hex_digits(2)?;
+                    char::from_u32(code).unwrap()
+                }
+                // Line continuations
+                '\r' => {
+                    self.eat(b'\n');
+                    return Ok(());
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => return Ok(()),
+                '1'..='9' => {
+                    self.pos -= 1;
+                    return Err(self.error_at_char("Invalid escape sequence"));
+                }
+                c => c,
+            },
+            _ => {
+                self.pos -= c.len_utf8();
+                return Err(self.error_at_char("Invalid escape sequence"));
+            }
+        };
+        value.push(escaped);
+        Ok(())
+    }
+
+    /// `XXXX` of `\uXXXX`, combining surrogate pairs.
+    /// Lone surrogates cannot be represented in Rust strings and are replaced by U+FFFD.
+    fn parse_unicode_escape(&mut self) -> Result<char> {
+        let code = self.parse_hex_digits(4)?;
+        if (0xD800..0xDC00).contains(&code) && self.rest().starts_with("\\u") {
+            let pos = self.pos;
+            self.pos += 2;
+            let low = self.parse_hex_digits(4)?;
+            if (0xDC00..0xE000).contains(&low) {
+                let code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                return Ok(char::from_u32(code).unwrap());
+            }
+            self.pos = pos;
+        }
+        Ok(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn parse_hex_digits(&mut self, len: usize) -> Result<u32> {
+        let digits = self.rest().get(..len).filter(|s| s.bytes().all(|b| b.is_ascii_hexdigit()));
+        let Some(digits) = digits else {
+            return Err(self.error_at_char("Invalid escape sequence, expected hex digits"));
+        };
+        self.pos += len;
+        Ok(u32::from_str_radix(digits, 16).unwrap())
+    }
+
+    /// Unquoted property names of JSON5
+    fn parse_identifier(&mut self) -> Result<String> {
+        let mut name = String::new();
+        while let Some(c) = self.peek_char() {
+            match c {
+                '\\' if self.rest().starts_with("\\u") => {
+                    self.pos += 2;
+                    name.push(self.parse_unicode_escape()?);
+                }
+                c if (name.is_empty() && is_identifier_start(c))
+                    || (!name.is_empty() && is_identifier_part(c)) =>
+                {
+                    name.push(c);
+                    self.pos += c.len_utf8();
+                }
+                _ => break,
+            }
+        }
+        if name.is_empty() {
+            return Err(self.error_at_char("Expected a property name"));
+        }
+        Ok(name)
+    }
+
+    fn parse_number(&mut self) -> Result<f64> {
+        let start = self.pos;
+        let json5 = self.kind.is_json5();
+        let negative = self.eat(b'-');
+        if json5 && !negative {
+            self.eat(b'+');
+        }
+
+        if json5 {
+            let value = if self.eat_keyword("Infinity") {
+                Some(f64::INFINITY)
+            } else if self.eat_keyword("NaN") {
+                Some(f64::NAN)
+            } else if self.rest().starts_with("0x") || self.rest().starts_with("0X") {
+                self.pos += 2;
+                let digits_start = self.pos;
+                while self.peek().map_or(false, |b| b.is_ascii_hexdigit()) {
+                    self.pos += 1;
+                }
+                let digits = &self.source_text[digits_start..self.pos];
+                if digits.is_empty() {
+                    return Err(self.error_at_char("Expected hex digits"));
+                }
+                Some(digits.bytes().fold(0.0, |acc: f64, b| {
+                    acc.mul_add(16.0, f64::from(char::from(b).to_digit(16).unwrap()))
+                }))
+            } else {
+                None
+            };
+            if let Some(value) = value {
+                self.check_number_end()?;
+                return Ok(if negative { -value } else { value });
+            }
+        }
+
+        let int_start = self.pos;
+        if self.eat(b'0') {
+            if self.peek().map_or(false, |b| b.is_ascii_digit()) {
+                return Err(self.error_at_char("Leading zeros are not allowed in numbers"));
+            }
+        } else {
+            self.eat_digits();
+        }
+        let has_int = self.pos > int_start;
+        if !(has_int || (json5 && self.peek() == Some(b'.'))) {
+            return Err(self.error_at_char("Expected a digit"));
+        }
+        if self.eat(b'.') {
+            let has_fraction = self.eat_digits();
+            if !(has_fraction || (json5 && has_int)) {
+                return Err(self.error_at_char("Expected a digit after the decimal point"));
+            }
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            if !self.eat(b'+') {
+                self.eat(b'-');
+            }
+            if !self.eat_digits() {
+                return Err(self.error_at_char("Expected a digit in the exponent"));
+            }
+        }
+        self.check_number_end()?;
+
+        let raw = &self.source_text[int_start..self.pos];
+        let value =
+            raw.parse::<f64>().map_err(|_| InvalidJson("Invalid number", self.span(start)))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn eat_digits(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().map_or(false, |b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    /// A number cannot be followed by an identifier, e.g. `1x` or `3in`
+    fn check_number_end(&self) -> Result<()> {
+        if self.peek_char().map_or(false, is_identifier_part) {
+            return Err(self.error_at_char("Unexpected character after the number"));
+        }
+        Ok(())
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c == '$' || c == '_' || c.is_alphabetic()
+}
+
+fn is_identifier_part(c: char) -> bool {
+    is_identifier_start(c) || c.is_alphanumeric() || matches!(c, '\u{200c}' | '\u{200d}')
+}
+
+/// <https://spec.json5.org/#white-space>
+fn is_json5_whitespace(c: char) -> bool {
+    matches!(
+        c,
+        '\t' | '\n'
+            | '\u{b}'
+            | '\u{c}'
+            | '\r'
+            | ' '
+            | '\u{a0}'
+            | '\u{2028}'
+            | '\u{2029}'
+            | '\u{feff}'
+    ) || (c.is_whitespace() && !c.is_control())
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::Span;
+
+    use super::{JsonKind, JsonParser};
+    use crate::{JsonValue, JsonValueKind};
+
+    fn parse(source_text: &str, kind: JsonKind) -> JsonValue {
+        JsonParser::new(source_text, kind).parse().unwrap()
+    }
+
+    fn error(source_text: &str, kind: JsonKind) -> (&'static str, Span) {
+        let error = JsonParser::new(source_text, kind).parse().unwrap_err();
+        (error.0, error.1)
+    }
+
+    #[test]
+    fn values() {
+        let value = parse(
+            r#"{ "a": [1, -2.5e3, true, false, null], "b": "c\né😀", "": {} }"#,
+            JsonKind::Json,
+        );
+        let a = value.get("a").unwrap().as_array().unwrap();
+        assert_eq!(a[0].as_u64(), Some(1));
+        assert_eq!(a[1].as_f64(), Some(-2500.0));
+        assert_eq!(a[2].as_bool(), Some(true));
+        assert_eq!(a[3].as_bool(), Some(false));
+        assert!(a[4].is_null());
+        assert_eq!(value.get("b").and_then(JsonValue::as_str), Some("c\né😀"));
+        assert_eq!(value.get("").and_then(JsonValue::as_object).map(<[_]>::len), Some(0));
+        assert_eq!(parse("\u{feff} 0 ", JsonKind::Json).kind, JsonValueKind::Number(0.0));
+    }
+
+    #[test]
+    fn spans() {
+        let source_text = r#"{ "rules": { "eqeqeq": ["deny", "smart"] } }"#;
+        let value = parse(source_text, JsonKind::Json);
+        let eqeqeq = value.pointer("/rules/eqeqeq").unwrap();
+        assert_eq!(eqeqeq.span.source_text(source_text), r#"["deny", "smart"]"#);
+        let level = value.pointer("/rules/eqeqeq/0").unwrap();
+        assert_eq!(level.span.source_text(source_text), r#""deny""#);
+        let property = &value.get("rules").unwrap().as_object().unwrap()[0];
+        assert_eq!(property.key_span.source_text(source_text), r#""eqeqeq""#);
+        assert_eq!(value.span.source_text(source_text), source_text);
+    }
+
+    #[test]
+    fn duplicated_keys() {
+        let value = parse(r#"{ "a": 1, "a": 2 }"#, JsonKind::Json);
+        assert_eq!(value.as_object().unwrap().len(), 2);
+        assert_eq!(value.get("a").and_then(JsonValue::as_u64), Some(2));
+    }
+
+    #[test]
+    fn jsonc() {
+        let source_text = r#"
+            // comment
+            {
+                /* comment */ "compilerOptions": { "jsx": "react", },
+                "files": ["a.ts",],
+            }
+        "#;
+        let value = parse(source_text, JsonKind::Jsonc);
+        assert_eq!(
+            value.pointer("/compilerOptions/jsx").and_then(JsonValue::as_str),
+            Some("react")
+        );
+        assert_eq!(value.pointer("/files/0").and_then(JsonValue::as_str), Some("a.ts"));
+
+        assert_eq!(error(source_text, JsonKind::Json).0, "Comments are not allowed in JSON");
+        assert_eq!(
+            error("[1,]", JsonKind::Json),
+            ("Trailing commas are not allowed in JSON", Span::new(2, 3))
+        );
+        assert_eq!(error("{ 'a': 1 }", JsonKind::Jsonc).0, "Expected a property name in quotes");
+        assert_eq!(error("[1] /* a", JsonKind::Jsonc), ("Unterminated comment", Span::new(4, 8)));
+    }
+
+    #[test]
+    fn json5() {
+        let source_text = r"{
+            // comment
+            unquoted: 'single \' quotes',
+            $_id2: 0x1F,
+            leading: .5, trailing: 5., positive: +1,
+            infinity: -Infinity, nan: NaN,
+            multiline: 'a\
+b',
+        }";
+        let value = parse(source_text, JsonKind::Json5);
+        assert_eq!(value.get("unquoted").and_then(JsonValue::as_str), Some("single ' quotes"));
+        assert_eq!(value.get("$_id2").and_then(JsonValue::as_u64), Some(31));
+        assert_eq!(value.get("leading").and_then(JsonValue::as_f64), Some(0.5));
+        assert_eq!(value.get("trailing").and_then(JsonValue::as_f64), Some(5.0));
+        assert_eq!(value.get("positive").and_then(JsonValue::as_f64), Some(1.0));
+        assert_eq!(value.get("infinity").and_then(JsonValue::as_f64), Some(f64::NEG_INFINITY));
+        assert!(value.get("nan").and_then(JsonValue::as_f64).unwrap().is_nan());
+        assert_eq!(value.get("multiline").and_then(JsonValue::as_str), Some("ab"));
+    }
+
+    #[test]
+    fn errors() {
+        let cases = [
+            ("", "Unexpected end of input, expected a value", Span::new(0, 0)),
+            ("{", "Unterminated object, expected '}'", Span::new(1, 1)),
+            ("[1", "Expected ',' or ']' after the element", Span::new(2, 2)),
+            (r#"{"a" 1}"#, "Expected ':' after the property name", Span::new(5, 6)),
+            (r#"{"a": 1 "b": 2}"#, "Expected ',' or '}' after the property", Span::new(8, 9)),
+            (r#""abc"#, "Unterminated string", Span::new(0, 4)),
+            ("\"a\tb\"", "Control characters must be escaped in strings", Span::new(2, 3)),
+            (r#""\x41""#, "Invalid escape sequence", Span::new(2, 3)),
+            (r#""\u12""#, "Invalid escape sequence, expected hex digits", Span::new(3, 4)),
+            ("01", "Leading zeros are not allowed in numbers", Span::new(1, 2)),
+            ("1.", "Expected a digit after the decimal point", Span::new(2, 2)),
+            ("1e", "Expected a digit in the exponent", Span::new(2, 2)),
+            ("1x", "Unexpected character after the number", Span::new(1, 2)),
+            ("tru", "Expected a value", Span::new(0, 1)),
+            ("truex", "Expected a value", Span::new(0, 1)),
+            ("1 2", "Unexpected content after the value", Span::new(2, 3)),
+            ("NaN", "Expected a value", Span::new(0, 1)),
+        ];
+        for (source_text, message, span) in cases {
+            assert_eq!(error(source_text, JsonKind::Json), (message, span), "{source_text}");
+        }
+    }
+}
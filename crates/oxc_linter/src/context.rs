@@ -1,13 +1,20 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    rc::Rc,
+};
 
-use oxc_diagnostics::Error;
-use oxc_formatter::{Formatter, FormatterOptions};
-use oxc_semantic::{AstNodes, JSDocComment, ScopeTree, Semantic, SymbolTable};
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_diagnostics::{
+    miette::{Diagnostic, LabeledSpan, SourceCode},
+    Error, Severity,
+};
+use oxc_semantic::{AstNodes, Environment, JSDocComment, ScopeTree, Semantic, SymbolTable};
 use oxc_span::SourceType;
 
 use crate::{
     disable_directives::{DisableDirectives, DisableDirectivesBuilder},
-    fixer::{Fix, Message},
+    fixer::{Fix, FixSuggestions, Message},
     AstNode,
 };
 
@@ -21,7 +28,16 @@ pub struct LintContext<'a> {
     /// Whether or not to apply code fixes during linting.
     fix: bool,
 
+    /// Rules whose suggestions are applied alongside fixes.
+    fix_suggestions: FixSuggestions,
+
+    /// Environments whose global variables are defined.
+    environment: Environment,
+
     current_rule_name: &'static str,
+
+    /// Severity configured for the current rule, overriding the severity declared by its diagnostics.
+    current_rule_severity: Option<Severity>,
 }
 
 impl<'a> LintContext<'a> {
@@ -33,7 +49,10 @@ impl<'a> LintContext<'a> {
             diagnostics: RefCell::new(vec![]),
             disable_directives,
             fix: false,
+            fix_suggestions: FixSuggestions::None,
+            environment: Environment::default(),
             current_rule_name: "",
+            current_rule_severity: None,
         }
     }
 
@@ -43,6 +62,18 @@ impl<'a> LintContext<'a> {
         self
     }
 
+    #[must_use]
+    pub fn with_fix_suggestions(mut self, fix_suggestions: FixSuggestions) -> Self {
+        self.fix_suggestions = fix_suggestions;
+        self
+    }
+
+    #[must_use]
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self
+    }
+
     pub fn semantic(&self) -> &Rc<Semantic<'a>> {
         &self.semantic
     }
@@ -55,24 +86,42 @@ impl<'a> LintContext<'a> {
         self.semantic().source_type()
     }
 
+    /// Environments whose global variables are defined, e.g. `window` in browsers.
+    pub fn environment(&self) -> Environment {
+        self.environment
+    }
+
     pub fn with_rule_name(&mut self, name: &'static str) {
         self.current_rule_name = name;
     }
 
+    pub fn with_rule_severity(&mut self, severity: Option<Severity>) {
+        self.current_rule_severity = severity;
+    }
+
     /* Diagnostics */
 
     pub fn into_message(self) -> Vec<Message<'a>> {
         self.diagnostics.into_inner()
     }
 
-    fn add_diagnostic(&self, message: Message<'a>) {
+    fn with_severity<T: Into<Error>>(&self, diagnostic: T) -> Error {
+        let error = diagnostic.into();
+        match self.current_rule_severity {
+            Some(severity) => Error::new(DiagnosticWithSeverity { error, severity }),
+            None => error,
+        }
+    }
+
+    fn add_diagnostic(&self, mut message: Message<'a>) {
         if !self.disable_directives.contains(self.current_rule_name, message.start()) {
+            message.rule_name = self.current_rule_name;
             self.diagnostics.borrow_mut().push(message);
         }
     }
 
     pub fn diagnostic<T: Into<Error>>(&self, diagnostic: T) {
-        self.add_diagnostic(Message::new(diagnostic.into(), None));
+        self.add_diagnostic(Message::new(self.with_severity(diagnostic), None));
     }
 
     pub fn diagnostic_with_fix<T, F>(&self, diagnostic: T, fix: F)
@@ -81,9 +130,28 @@ impl<'a> LintContext<'a> {
         F: FnOnce() -> Fix<'a>,
     {
         if self.fix {
-            self.add_diagnostic(Message::new(diagnostic.into(), Some(fix())));
+            self.add_diagnostic(Message::new(self.with_severity(diagnostic), Some(fix())));
         } else {
-            self.diagnostic(diagnostic);
+            let mut message = Message::new(self.with_severity(diagnostic), None);
+            message.fixable = true;
+            self.add_diagnostic(message);
+        }
+    }
+
+    /// Report a diagnostic with a fix that may change the semantics of the program.
+    /// The fix is only applied when suggestions are enabled for the current rule.
+    pub fn diagnostic_with_suggestion<T, F>(&self, diagnostic: T, fix: F)
+    where
+        T: Into<Error>,
+        F: FnOnce() -> Fix<'a>,
+    {
+        let enabled = self.fix_suggestions.is_enabled_for(self.current_rule_name);
+        if self.fix && enabled {
+            self.add_diagnostic(Message::new_suggestion(self.with_severity(diagnostic), fix()));
+        } else {
+            let mut message = Message::new(self.with_severity(diagnostic), None);
+            message.fixable = enabled;
+            self.add_diagnostic(message);
         }
     }
 
@@ -99,9 +167,9 @@ impl<'a> LintContext<'a> {
         self.semantic().symbols()
     }
 
-    #[allow(clippy::unused_self)]
-    pub fn formatter(&self) -> Formatter {
-        Formatter::new(0, FormatterOptions::default())
+    pub fn codegen(&self) -> Codegen {
+        let typescript = self.source_type().is_typescript();
+        Codegen::new(0, CodegenOptions { typescript, ..CodegenOptions::default() })
     }
 
     /* JSDoc */
@@ -109,3 +177,56 @@ impl<'a> LintContext<'a> {
         self.semantic().jsdoc().get_by_node(node)
     }
 }
+
+/// A diagnostic with its severity replaced by the one configured for its rule.
+#[derive(Debug)]
+struct DiagnosticWithSeverity {
+    error: Error,
+    severity: Severity,
+}
+
+impl Display for DiagnosticWithSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for DiagnosticWithSeverity {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&*self.error)
+    }
+}
+
+impl Diagnostic for DiagnosticWithSeverity {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Some(self.severity)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.error.source_code()
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.error.labels()
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        self.error.related()
+    }
+
+    fn diagnostic_source(&self) -> Option<&dyn Diagnostic> {
+        self.error.diagnostic_source()
+    }
+}
@@ -77,12 +77,13 @@ impl<'a, 'b> DisableDirectivesBuilder<'a, 'b> {
 
                 // `eslint-disable-next-line`
                 if let Some(text) = text.strip_prefix("-next-line") {
-                    // Get the span up to the next new line
+                    // Get the span up to the end of the next line
                     let stop = self.source_text[span.end as usize..]
                         .lines()
-                        .take(if comment.is_single_line() { 1 } else { 2 })
-                        .map(|line| span.end + line.len() as u32)
-                        .sum();
+                        .take(2)
+                        .map(|line| line.len() as u32 + 1)
+                        .sum::<u32>()
+                        + span.end;
                     if text.trim().is_empty() {
                         self.add_interval(span.end, stop, DisabledRule::All);
                     } else {
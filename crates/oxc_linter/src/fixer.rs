@@ -2,6 +2,7 @@ use std::borrow::Cow;
 
 use oxc_diagnostics::Error;
 use oxc_span::Span;
+use rustc_hash::FxHashSet;
 
 #[derive(Debug, Default)]
 pub struct Fix<'a> {
@@ -19,11 +20,35 @@ impl<'a> Fix<'a> {
     }
 }
 
+/// Which rules are allowed to apply their suggestions.
+///
+/// Suggestions are fixes that may change the semantics of the program,
+/// so they are only applied when explicitly requested.
+#[derive(Debug, Default, Clone)]
+pub enum FixSuggestions {
+    #[default]
+    None,
+    All,
+    Rules(FxHashSet<String>),
+}
+
+impl FixSuggestions {
+    pub fn is_enabled_for(&self, rule_name: &str) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Rules(rules) => rules.contains(rule_name),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct FixResult<'a> {
     pub fixed: bool,
     pub fixed_code: Cow<'a, str>,
     pub messages: Vec<Message<'a>>,
+    /// Number of applied fixes that came from suggestions.
+    pub applied_suggestions: usize,
 }
 
 #[derive(Debug)]
@@ -32,6 +57,13 @@ pub struct Message<'a> {
     start: u32,
     end: u32,
     pub fix: Option<Fix<'a>>,
+    /// The rule which reported the message, set by the [`crate::LintContext`].
+    pub rule_name: &'static str,
+    /// Whether the rule has a fix for the message, also when the fix is not computed because
+    /// fixing is disabled.
+    pub fixable: bool,
+    /// Whether `fix` is a suggestion, which may change the semantics of the program.
+    suggestion: bool,
     fixed: bool,
 }
 
@@ -45,7 +77,16 @@ impl<'a> Message<'a> {
             .iter()
             .max_by_key(|span| span.offset() + span.len())
             .map_or(0, |span| (span.offset() + span.len()) as u32);
-        Self { error, start, end, fix, fixed: false }
+        let fixable = fix.is_some();
+        Self { error, start, end, fix, rule_name: "", fixable, suggestion: false, fixed: false }
+    }
+
+    pub fn new_suggestion(error: Error, fix: Fix<'a>) -> Self {
+        Self { suggestion: true, ..Self::new(error, Some(fix)) }
+    }
+
+    pub fn is_suggestion(&self) -> bool {
+        self.suggestion
     }
 
     pub fn start(&self) -> u32 {
@@ -77,11 +118,13 @@ impl<'a> Fixer<'a> {
                 fixed: false,
                 fixed_code: Cow::Borrowed(source_text),
                 messages: self.messages,
+                applied_suggestions: 0,
             };
         }
 
         self.messages.sort_by_key(|m| m.fix.as_ref().unwrap_or(&Fix::default()).span);
         let mut fixed = false;
+        let mut applied_suggestions = 0;
         let mut output = String::with_capacity(source_text.len());
         let mut last_pos: i64 = -1;
         self.messages.iter_mut().filter(|m| m.fix.is_some()).for_each(|m| {
@@ -97,6 +140,9 @@ impl<'a> Fixer<'a> {
 
             m.fixed = true;
             fixed = true;
+            if m.suggestion {
+                applied_suggestions += 1;
+            }
             let offset = usize::try_from(last_pos.max(0)).ok().unwrap();
             output.push_str(&source_text[offset..start as usize]);
             output.push_str(content);
@@ -108,7 +154,7 @@ impl<'a> Fixer<'a> {
 
         let mut messages = self.messages.into_iter().filter(|m| !m.fixed).collect::<Vec<_>>();
         messages.sort_by_key(|m| (m.start, m.end));
-        return FixResult { fixed, fixed_code: Cow::Owned(output), messages };
+        return FixResult { fixed, fixed_code: Cow::Owned(output), messages, applied_suggestions };
     }
 }
 
@@ -379,6 +425,17 @@ mod test {
         assert!(!result.fixed);
     }
 
+    #[test]
+    fn count_applied_suggestions() {
+        let result = get_fix_result(vec![
+            create_message(ReplaceVar, Some(REPLACE_VAR)),
+            Message::new_suggestion(ReplaceId.into(), REPLACE_ID),
+        ]);
+        assert_eq!(result.fixed_code, "let foo = 6 * 7;");
+        assert_eq!(result.applied_suggestions, 1);
+        assert!(result.fixed);
+    }
+
     #[test]
     fn sort_no_fix_messages_correctly() {
         let result = get_fix_result(vec![
@@ -15,20 +15,27 @@ mod rules;
 
 use std::{self, fs, io::Write, rc::Rc};
 
-pub use fixer::{FixResult, Fixer, Message};
+pub use fixer::{FixResult, FixSuggestions, Fixer, Message};
+use oxc_diagnostics::Severity;
 pub(crate) use oxc_semantic::AstNode;
+pub use oxc_semantic::Environment;
 use rustc_hash::FxHashMap;
 
 pub use crate::{
     context::LintContext,
-    rule::RuleCategory,
+    rule::{FixKind, RuleCategory},
     rules::{RuleEnum, RULES},
 };
 
 #[derive(Debug)]
 pub struct Linter {
     rules: Vec<RuleEnum>,
+    /// Severity of each rule's diagnostics, defaults to the severity declared by the rule.
+    rule_severities: FxHashMap<&'static str, Severity>,
     fix: bool,
+    fix_suggestions: FixSuggestions,
+    /// Environments whose global variables are defined, ECMAScript intrinsics by default.
+    environment: Environment,
     print_execution_times: bool,
 }
 
@@ -43,7 +50,14 @@ impl Linter {
     }
 
     pub fn from_rules(rules: Vec<RuleEnum>) -> Self {
-        Self { rules, fix: false, print_execution_times: false }
+        Self {
+            rules,
+            rule_severities: FxHashMap::default(),
+            fix: false,
+            fix_suggestions: FixSuggestions::None,
+            environment: Environment::default(),
+            print_execution_times: false,
+        }
     }
 
     pub fn rules(&self) -> &Vec<RuleEnum> {
@@ -64,15 +78,51 @@ impl Linter {
         self
     }
 
+    /// Override the severity of the given rules' diagnostics.
+    #[must_use]
+    pub fn with_rule_severities(
+        mut self,
+        rule_severities: FxHashMap<&'static str, Severity>,
+    ) -> Self {
+        self.rule_severities = rule_severities;
+        self
+    }
+
+    /// Also apply the suggestions of the given rules when fixing.
+    #[must_use]
+    pub fn with_fix_suggestions(mut self, fix_suggestions: FixSuggestions) -> Self {
+        self.fix_suggestions = fix_suggestions;
+        self
+    }
+
+    /// Define the global variables of the environments, e.g. `Environment::BROWSER`.
+    #[must_use]
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self
+    }
+
     #[must_use]
     pub fn with_print_execution_times(mut self, yes: bool) -> Self {
         self.print_execution_times = yes;
         self
     }
 
+    /// Rules and environments from an ESLint config, e.g.
+    /// `{ "env": { "browser": true }, "rules": { "no-undef": "error" } }`
     pub fn from_json_str(s: &str) -> Self {
-        let rules = serde_json::from_str(s)
-            .ok()
+        let config = serde_json::from_str::<serde_json::Value>(s).ok();
+        let environment = config
+            .as_ref()
+            .and_then(|v| v.get("env"))
+            .and_then(serde_json::Value::as_object)
+            .map_or_else(Environment::default, |env| {
+                env.iter()
+                    .filter(|(_, enabled)| enabled.as_bool() == Some(true))
+                    .filter_map(|(name, _)| Environment::from_env_name(name))
+                    .fold(Environment::default(), |environment, env| environment | env)
+            });
+        let rules = config
             .and_then(|v: serde_json::Value| v.get("rules").cloned())
             .and_then(|v| v.as_object().cloned())
             .map_or_else(
@@ -88,28 +138,39 @@ impl Linter {
                 },
             );
 
-        Self::from_rules(rules)
+        Self::from_rules(rules).with_environment(environment)
     }
 
     pub fn run<'a>(&self, ctx: LintContext<'a>) -> Vec<Message<'a>> {
         let semantic = Rc::clone(ctx.semantic());
-        let mut ctx = ctx.with_fix(self.fix);
+        let mut ctx = ctx
+            .with_fix(self.fix)
+            .with_fix_suggestions(self.fix_suggestions.clone())
+            .with_environment(self.environment);
+        let rules = self
+            .rules
+            .iter()
+            .map(|rule| (rule, self.rule_severities.get(rule.name()).copied()))
+            .collect::<Vec<_>>();
 
-        for rule in &self.rules {
+        for (rule, severity) in &rules {
             ctx.with_rule_name(rule.name());
+            ctx.with_rule_severity(*severity);
             rule.run_once(&ctx, self.print_execution_times);
         }
 
         for node in semantic.nodes().iter() {
-            for rule in &self.rules {
+            for (rule, severity) in &rules {
                 ctx.with_rule_name(rule.name());
+                ctx.with_rule_severity(*severity);
                 rule.run(node, &ctx, self.print_execution_times);
             }
         }
 
         for symbol in semantic.symbols().iter() {
-            for rule in &self.rules {
+            for (rule, severity) in &rules {
                 ctx.with_rule_name(rule.name());
+                ctx.with_rule_severity(*severity);
                 rule.run_on_symbol(symbol, &ctx, self.print_execution_times);
             }
         }
@@ -25,6 +25,8 @@ pub trait RuleMeta {
 
     const CATEGORY: RuleCategory;
 
+    const FIX: FixKind = FixKind::None;
+
     fn documentation() -> Option<&'static str> {
         None
     }
@@ -78,6 +80,27 @@ impl fmt::Display for RuleCategory {
     }
 }
 
+/// The kind of automatic fixes provided by a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixKind {
+    /// No automatic fixes
+    None,
+    /// Safe fixes, applied by `--fix`
+    Fix,
+    /// Fixes which may change the semantics of the program, applied by `--fix-suggestions`
+    Suggestion,
+}
+
+impl fmt::Display for FixKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Fix => write!(f, "fix"),
+            Self::Suggestion => write!(f, "suggestion"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::RULES;
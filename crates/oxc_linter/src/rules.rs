@@ -50,11 +50,13 @@ mod eslint {
     pub mod no_global_assign;
     pub mod no_import_assign;
     pub mod no_inner_declarations;
+    pub mod no_invalid_regexp;
     pub mod no_loss_of_precision;
     pub mod no_mixed_operators;
     pub mod no_new_symbol;
     pub mod no_obj_calls;
     pub mod no_prototype_builtins;
+    pub mod no_restricted_globals;
     pub mod no_return_await;
     pub mod no_self_assign;
     pub mod no_self_compare;
@@ -66,6 +68,7 @@ mod eslint {
     pub mod no_unsafe_negation;
     pub mod no_unsafe_optional_chaining;
     pub mod no_unused_labels;
+    pub mod no_useless_backreference;
     pub mod no_useless_catch;
     pub mod no_useless_escape;
     pub mod require_yield;
@@ -140,11 +143,13 @@ oxc_macros::declare_all_lint_rules! {
     eslint::no_global_assign,
     eslint::no_import_assign,
     eslint::no_inner_declarations,
+    eslint::no_invalid_regexp,
     eslint::no_loss_of_precision,
     eslint::no_mixed_operators,
     eslint::no_new_symbol,
     eslint::no_obj_calls,
     eslint::no_prototype_builtins,
+    eslint::no_restricted_globals,
     eslint::no_return_await,
     eslint::no_self_assign,
     eslint::no_self_compare,
@@ -157,6 +162,7 @@ oxc_macros::declare_all_lint_rules! {
     eslint::no_unsafe_optional_chaining,
     eslint::no_unused_labels,
     eslint::no_useless_catch,
+    eslint::no_useless_backreference,
     eslint::no_useless_escape,
     eslint::require_yield,
     eslint::use_isnan,
@@ -70,8 +70,11 @@ impl Rule for NoCaseDeclarations {
                             let start = var.span.start;
                             let end = match var.kind {
                                 VariableDeclarationKind::Var => unreachable!(),
-                                VariableDeclarationKind::Const => 5,
+                                VariableDeclarationKind::Const | VariableDeclarationKind::Using => {
+                                    5
+                                }
                                 VariableDeclarationKind::Let => 3,
+                                VariableDeclarationKind::AwaitUsing => 11,
                             };
                             let end = start + end;
                             ctx.diagnostic(NoCaseDeclarationsDiagnostic(Span::new(start, end)));
@@ -1,13 +1,16 @@
 // Ported from https://github.com/eslint/eslint/blob/main/lib/rules/no-empty-character-class.js
-use lazy_static::lazy_static;
 use oxc_ast::AstKind;
 use oxc_diagnostics::{
     miette::{self, Diagnostic},
     thiserror::Error,
 };
 use oxc_macros::declare_oxc_lint;
+use oxc_regexp::{
+    ast::CharacterClass,
+    visit::{walk_character_class, Visit},
+    PatternParser,
+};
 use oxc_span::Span;
-use regex::Regex;
 
 use crate::{context::LintContext, rule::Rule, AstNode};
 
@@ -36,25 +39,31 @@ declare_oxc_lint!(
 
 impl Rule for NoEmptyCharacterClass {
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
-        lazy_static! {
-            /*
-            * plain-English description of the following regexp:
-            * 0. `^` fix the match at the beginning of the string
-            * 1. `([^\\[]|\\.|\[([^\\\]]|\\.)+\])*`: regexp contents; 0 or more of the following
-            * 1.0. `[^\\[]`: any character that's not a `\` or a `[` (anything but escape sequences and character classes)
-            * 1.1. `\\.`: an escape sequence
-            * 1.2. `\[([^\\\]]|\\.)+\]`: a character class that isn't empty
-            * 2. `$`: fix the match at the end of the string
-            */
-            static ref NO_EMPTY_CLASS_REGEX_PATTERN: Regex =
-                Regex::new(r"^([^\\\[]|\\.|\[([^\\\]]|\\.)+\])*$").unwrap();
+        let AstKind::RegExpLiteral(lit) = node.kind() else { return };
+        // Invalid patterns are reported as syntax errors
+        let Ok(pattern) = PatternParser::new(lit.regex.pattern.as_str(), lit.regex.flags).parse()
+        else {
+            return;
+        };
+        let mut finder = EmptyClassFinder { found: false };
+        finder.visit_pattern(&pattern);
+        if finder.found {
+            ctx.diagnostic(NoEmptyCharacterClassDiagnostic(lit.span));
         }
+    }
+}
+
+struct EmptyClassFinder {
+    found: bool,
+}
 
-        if let AstKind::RegExpLiteral(lit) = node.kind() {
-            if !NO_EMPTY_CLASS_REGEX_PATTERN.is_match(&lit.regex.pattern) {
-                ctx.diagnostic(NoEmptyCharacterClassDiagnostic(lit.span));
-            }
+impl Visit for EmptyClassFinder {
+    fn visit_character_class(&mut self, class: &CharacterClass) {
+        // `[^]` matches any character
+        if class.elements.is_empty() && !class.negate {
+            self.found = true;
         }
+        walk_character_class(self, class);
     }
 }
 
@@ -77,6 +86,8 @@ fn test() {
         ("var foo = /[\\]]/s;", None),
         ("var foo = /[\\]]/d;", None),
         ("var foo = /\\[]/", None),
+        ("var foo = /[^]/;", None),
+        ("var foo = /[[a]]/v;", None),
     ];
 
     let fail = vec![
@@ -88,6 +99,8 @@ fn test() {
         ("var foo = /\\[[]/;", None),
         ("var foo = /\\[\\[\\]a-z[]/;", None),
         ("var foo = /[]]/d;", None),
+        ("var foo = /[[]]/v;", None),
+        ("var foo = /[a--[]]/v;", None),
     ];
 
     Tester::new(NoEmptyCharacterClass::NAME, pass, fail).test_and_snapshot();
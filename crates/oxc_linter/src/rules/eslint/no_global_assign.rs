@@ -5,7 +5,7 @@ use oxc_diagnostics::{
 use oxc_macros::declare_oxc_lint;
 use oxc_span::{Atom, Span};
 
-use crate::{context::LintContext, globals::BUILTINS, rule::Rule};
+use crate::{context::LintContext, rule::Rule};
 
 #[derive(Debug, Error, Diagnostic)]
 #[error("eslint(no-global-assign): Read-only global '{0}' should not be modified.")]
@@ -57,12 +57,13 @@ impl Rule for NoGlobalAssign {
         for reference_id_list in ctx.scopes().root_unresolved_references().values() {
             for &reference_id in reference_id_list {
                 let reference = symbol_table.get_reference(reference_id);
-                if reference.is_write() && symbol_table.is_global_reference(reference_id) {
-                    let name = reference.name();
-
-                    if !self.excludes.contains(name) && BUILTINS.contains_key(name) {
-                        ctx.diagnostic(NoGlobalAssignDiagnostic(name.clone(), reference.span()));
-                    }
+                if !reference.is_write() || self.excludes.contains(reference.name()) {
+                    continue;
+                }
+                let global = ctx.semantic().global_variable(reference, ctx.environment());
+                if global.is_some_and(|global| !global.writable) {
+                    let name = reference.name().clone();
+                    ctx.diagnostic(NoGlobalAssignDiagnostic(name, reference.span()));
                 }
             }
         }
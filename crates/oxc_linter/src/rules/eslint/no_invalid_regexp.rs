@@ -0,0 +1,194 @@
+use oxc_ast::{
+    ast::{Argument, Expression, RegExpFlags},
+    AstKind,
+};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_macros::declare_oxc_lint;
+use oxc_regexp::{parse_flags, PatternParser};
+use oxc_span::{Atom, Span};
+
+use crate::{context::LintContext, rule::Rule, AstNode};
+
+#[derive(Debug, Error, Diagnostic)]
+enum NoInvalidRegexpDiagnostic {
+    #[error("eslint(no-invalid-regexp): Invalid regular expression: /{0}/: {1}")]
+    #[diagnostic(severity(warning))]
+    Pattern(Atom, &'static str, #[label] Span),
+
+    #[error("eslint(no-invalid-regexp): Invalid flags supplied to RegExp constructor '{0}'")]
+    #[diagnostic(severity(warning))]
+    Flags(Atom, #[label] Span),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NoInvalidRegexp {
+    /// Non-standard flags allowed in `RegExp` constructors
+    allow_constructor_flags: Vec<char>,
+}
+
+declare_oxc_lint!(
+    /// ### What it does
+    ///
+    /// Disallow invalid regular expression strings in `RegExp` constructors
+    ///
+    /// ### Why is this bad?
+    ///
+    /// An invalid pattern in a regular expression literal is a SyntaxError when the code is parsed,
+    /// but an invalid string in `RegExp` constructors throws a SyntaxError
+    /// only when the code is executed.
+    ///
+    /// ### Example
+    ///
+    /// ```javascript
+    /// RegExp('[');
+    /// RegExp('.', 'z');
+    /// new RegExp('\\');
+    /// ```
+    NoInvalidRegexp,
+    correctness
+);
+
+impl Rule for NoInvalidRegexp {
+    fn from_configuration(value: serde_json::Value) -> Self {
+        Self {
+            allow_constructor_flags: value
+                .get(0)
+                .and_then(|v| v.get("allowConstructorFlags"))
+                .and_then(serde_json::Value::as_array)
+                .map(|v| {
+                    v.iter().filter_map(serde_json::Value::as_str).flat_map(str::chars).collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
+        let (callee, arguments, span) = match node.kind() {
+            AstKind::NewExpression(expr) => (&expr.callee, &expr.arguments, expr.span),
+            AstKind::CallExpression(expr) => (&expr.callee, &expr.arguments, expr.span),
+            _ => return,
+        };
+        if !callee.is_specific_id("RegExp") {
+            return;
+        }
+
+        // `None` for flags which are not a string literal
+        let flags = match arguments.get(1) {
+            None => Some(RegExpFlags::empty()),
+            Some(Argument::Expression(Expression::StringLiteral(lit))) => {
+                let Some(flags) = self.parse_flags(lit.value.as_str()) else {
+                    ctx.diagnostic(NoInvalidRegexpDiagnostic::Flags(lit.value.clone(), span));
+                    return;
+                };
+                Some(flags)
+            }
+            _ => None,
+        };
+
+        let pattern = match arguments.first() {
+            Some(Argument::Expression(Expression::StringLiteral(lit))) => &lit.value,
+            _ => return,
+        };
+
+        // Without known flags, the pattern is valid if it is valid with any of the flags
+        // changing the syntax
+        let candidates = flags.map_or_else(
+            || vec![RegExpFlags::empty(), RegExpFlags::U, RegExpFlags::V],
+            |flags| vec![flags],
+        );
+        let mut first_error = None;
+        for flags in candidates {
+            match PatternParser::new(pattern.as_str(), flags).parse() {
+                Ok(_) => return,
+                Err(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        if let Some(error) = first_error {
+            ctx.diagnostic(NoInvalidRegexpDiagnostic::Pattern(pattern.clone(), error.0, span));
+        }
+    }
+}
+
+impl NoInvalidRegexp {
+    fn parse_flags(&self, flags: &str) -> Option<RegExpFlags> {
+        let mut allowed = vec![];
+        let mut standard = String::new();
+        for c in flags.chars() {
+            if RegExpFlags::try_from(c).is_err() && self.allow_constructor_flags.contains(&c) {
+                if allowed.contains(&c) {
+                    return None;
+                }
+                allowed.push(c);
+            } else {
+                standard.push(c);
+            }
+        }
+        parse_flags(&standard).ok()
+    }
+}
+
+#[test]
+fn test() {
+    use serde_json::json;
+
+    use crate::tester::Tester;
+
+    let pass = vec![
+        ("RegExp('')", None),
+        ("RegExp()", None),
+        ("RegExp('.', 'g')", None),
+        ("new RegExp('.')", None),
+        ("new RegExp", None),
+        ("new RegExp('.', 'im')", None),
+        ("global.RegExp('\\\\')", None),
+        ("new RegExp('.', y)", None),
+        ("new RegExp('.', 'y')", None),
+        ("new RegExp('.', 'u')", None),
+        ("new RegExp('.', 'yu')", None),
+        ("new RegExp('/', 'yu')", None),
+        ("new RegExp('\\/', 'yu')", None),
+        ("new RegExp('\\\\u{65}', 'u')", None),
+        ("new RegExp('\\\\u{65}*', 'u')", None),
+        ("new RegExp('[\\\\u{0}-\\\\u{1F}]', 'u')", None),
+        ("new RegExp('.', 's')", None),
+        ("new RegExp('(?<=a)b')", None),
+        ("new RegExp('(?<!a)b')", None),
+        ("new RegExp('(?<a>b)\\\\k<a>')", None),
+        ("new RegExp('(?<a>b)\\\\k<a>', 'u')", None),
+        ("new RegExp('\\\\p{Letter}', 'u')", None),
+        ("new RegExp('.', 'd')", None),
+        ("new RegExp('a+(?<Z>z)?', 'd')", None),
+        ("new RegExp('[\\\\p{Basic_Emoji}--\\\\q{a|bc|def}]', 'v')", None),
+        ("new RegExp('[[]', flags)", None),
+        ("new RegExp('\\\\p{Letter}', flags)", None),
+        ("new RegExp(pattern, 'g')", None),
+        ("new RegExp('.', 'a')", Some(json!([{ "allowConstructorFlags": ["a"] }]))),
+        ("new RegExp('.', 'ag')", Some(json!([{ "allowConstructorFlags": ["a"] }]))),
+        ("new RegExp('.', 'az')", Some(json!([{ "allowConstructorFlags": ["a", "z"] }]))),
+        ("new RegExp('.', 'az')", Some(json!([{ "allowConstructorFlags": ["az"] }]))),
+    ];
+
+    let fail = vec![
+        ("RegExp('[');", None),
+        ("RegExp('.', 'z');", None),
+        ("new RegExp(')');", None),
+        ("new RegExp('\\\\a', 'u');", None),
+        ("RegExp('\\\\u{0}*');", None),
+        ("new RegExp('(?<a>.)\\\\k<b>');", None),
+        ("new RegExp('(?<a>.)(?<a>.)');", None),
+        ("new RegExp('\\\\p{Foo}', 'u');", None),
+        ("new RegExp('.', 'gg');", None),
+        ("new RegExp('.', 'uv');", None),
+        ("new RegExp('[(]', 'v');", None),
+        ("new RegExp('(', flags);", None),
+        ("new RegExp('.', 'aa');", Some(json!([{ "allowConstructorFlags": ["a"] }]))),
+        ("new RegExp('.', 'z');", Some(json!([{ "allowConstructorFlags": ["a"] }]))),
+    ];
+
+    Tester::new(NoInvalidRegexp::NAME, pass, fail).test_and_snapshot();
+}
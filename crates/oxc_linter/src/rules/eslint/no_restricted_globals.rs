@@ -0,0 +1,128 @@
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_macros::declare_oxc_lint;
+use oxc_span::{Atom, Span};
+use rustc_hash::FxHashMap;
+
+use crate::{context::LintContext, rule::Rule};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("eslint(no-restricted-globals): Unexpected use of '{0}'.")]
+#[diagnostic(severity(warning))]
+struct NoRestrictedGlobalsDiagnostic(Atom, #[help] Option<String>, #[label] Span);
+
+#[derive(Debug, Default, Clone)]
+pub struct NoRestrictedGlobals {
+    /// Restricted global names and their custom messages
+    restricted: FxHashMap<String, Option<String>>,
+}
+
+declare_oxc_lint!(
+    /// ### What it does
+    ///
+    /// Disallow specified global variables
+    ///
+    /// ### Why is this bad?
+    ///
+    /// Some globals are easily mistaken for local variables, e.g. the browser globals `event`
+    /// and `name`, which are defined whenever a parameter or variable is misspelled.
+    ///
+    /// ### Example
+    ///
+    /// ```javascript
+    /// // "no-restricted-globals": ["error", "event", { "name": "fdescribe", "message": "Do not commit fdescribe." }]
+    /// function onClick() {
+    ///     console.log(event);
+    /// }
+    /// ```
+    NoRestrictedGlobals,
+    restriction
+);
+
+impl Rule for NoRestrictedGlobals {
+    fn from_configuration(value: serde_json::Value) -> Self {
+        let restricted = value
+            .as_array()
+            .map(|globals| {
+                globals
+                    .iter()
+                    .filter_map(|global| match global {
+                        serde_json::Value::String(name) => Some((name.clone(), None)),
+                        serde_json::Value::Object(global) => {
+                            let name = global.get("name")?.as_str()?.to_string();
+                            let message = global
+                                .get("message")
+                                .and_then(serde_json::Value::as_str)
+                                .map(ToString::to_string);
+                            Some((name, message))
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { restricted }
+    }
+
+    fn run_once(&self, ctx: &LintContext) {
+        if self.restricted.is_empty() {
+            return;
+        }
+        let symbol_table = ctx.symbols();
+        // References which are not resolved to a declaration are references to globals
+        let mut references = ctx
+            .scopes()
+            .root_unresolved_references()
+            .iter()
+            .filter_map(|(name, reference_ids)| {
+                let message = self.restricted.get(name.as_str())?;
+                Some(reference_ids.iter().map(move |&reference_id| (reference_id, message)))
+            })
+            .flatten()
+            .map(|(reference_id, message)| (symbol_table.get_reference(reference_id), message))
+            .collect::<Vec<_>>();
+        references.sort_unstable_by_key(|(reference, _)| reference.span().start);
+        for (reference, message) in references {
+            ctx.diagnostic(NoRestrictedGlobalsDiagnostic(
+                reference.name().clone(),
+                message.clone(),
+                reference.span(),
+            ));
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::tester::Tester;
+
+    let pass = vec![
+        ("foo", None),
+        ("foo", Some(serde_json::json!(["bar"]))),
+        ("var foo = 1;", Some(serde_json::json!(["foo"]))),
+        ("event", Some(serde_json::json!(["bar"]))),
+        ("import foo from 'bar';", Some(serde_json::json!(["foo"]))),
+        ("function foo() {}", Some(serde_json::json!(["foo"]))),
+        ("function fn() { var foo; }", Some(serde_json::json!(["foo"]))),
+        ("foo.bar", Some(serde_json::json!(["bar"]))),
+        ("foo", Some(serde_json::json!([{ "name": "bar", "message": "Use baz instead." }]))),
+        ("function fn(event) { event; }", Some(serde_json::json!(["event"]))),
+    ];
+
+    let fail = vec![
+        ("foo", Some(serde_json::json!(["foo"]))),
+        ("function fn() { foo; }", Some(serde_json::json!(["foo"]))),
+        ("foo = 1; foo++;", Some(serde_json::json!(["foo"]))),
+        ("event", Some(serde_json::json!(["foo", "event"]))),
+        ("window.foo()", Some(serde_json::json!(["window"]))),
+        ("typeof foo", Some(serde_json::json!(["foo"]))),
+        (
+            "function onClick() { console.log(event); }",
+            Some(serde_json::json!([{ "name": "event", "message": "Use local event parameter." }])),
+        ),
+    ];
+
+    Tester::new(NoRestrictedGlobals::NAME, pass, fail).test_and_snapshot();
+}
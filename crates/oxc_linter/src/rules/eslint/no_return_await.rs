@@ -31,7 +31,8 @@ declare_oxc_lint!(
     /// }
     /// ```
     NoReturnAwait,
-    pedantic
+    pedantic,
+    suggestion
 );
 
 impl Rule for NoReturnAwait {
@@ -41,7 +42,7 @@ impl Rule for NoReturnAwait {
                 let start = await_expr.span.start;
                 let end = start + 5;
                 let await_keyword_span = Span::new(start, end);
-                ctx.diagnostic_with_fix(NoReturnAwaitDiagnostic(await_keyword_span), || {
+                ctx.diagnostic_with_suggestion(NoReturnAwaitDiagnostic(await_keyword_span), || {
                     Fix::new("", await_keyword_span)
                 });
             }
@@ -7,7 +7,7 @@ use oxc_macros::declare_oxc_lint;
 use oxc_span::{Atom, Span};
 use oxc_syntax::operator::UnaryOperator;
 
-use crate::{context::LintContext, globals::BUILTINS, rule::Rule, AstNode};
+use crate::{context::LintContext, rule::Rule, AstNode};
 
 #[derive(Debug, Error, Diagnostic)]
 #[error("eslint(no-undef): Disallow the use of undeclared variables")]
@@ -50,20 +50,14 @@ impl Rule for NoUndef {
     fn run_once(&self, ctx: &LintContext) {
         let symbol_table = ctx.symbols();
 
-        for reference_id_list in ctx.scopes().root_unresolved_references().values() {
-            for &reference_id in reference_id_list {
-                let reference = symbol_table.get_reference(reference_id);
-                if BUILTINS.contains_key(reference.name().as_str()) {
-                    return;
-                }
-
-                let node = ctx.nodes().get_node(reference.node_id());
-                if !self.type_of && has_typeof_operator(node, ctx) {
-                    return;
-                }
-
-                ctx.diagnostic(NoUndefDiagnostic(reference.name().clone(), reference.span()));
+        for reference_id in ctx.semantic().undefined_references(ctx.environment()) {
+            let reference = symbol_table.get_reference(reference_id);
+            let node = ctx.nodes().get_node(reference.node_id());
+            if !self.type_of && has_typeof_operator(node, ctx) {
+                continue;
             }
+
+            ctx.diagnostic(NoUndefDiagnostic(reference.name().clone(), reference.span()));
         }
     }
 }
@@ -78,7 +72,7 @@ fn has_typeof_operator(node: &AstNode<'_>, ctx: &LintContext<'_>) -> bool {
 
 #[test]
 fn test() {
-    use crate::tester::Tester;
+    use crate::{tester::Tester, Environment};
 
     let pass = vec![
         ("var a = 1, b = 2; a;", None),
@@ -114,7 +108,7 @@ fn test() {
         ("var a; ({b: a} = {});", None),
         ("var obj; [obj.a, obj.b] = [0, 1];", None),
         // ("URLSearchParams;", None),
-        // ("Intl;", None),
+        ("Intl;", None),
         // ("IntersectionObserver;", None),
         // ("Credential;", None),
         // ("requestIdleCallback;", None),
@@ -186,4 +180,12 @@ fn test() {
     ];
 
     Tester::new(NoUndef::NAME, pass, fail).test_and_snapshot();
+
+    let pass = vec![("window; document;", None), ("Object; isNaN();", None)];
+    let fail = vec![("require(\"a\");", None), ("process;", None)];
+    Tester::new(NoUndef::NAME, pass, fail).with_environment(Environment::BROWSER).test();
+
+    let pass = vec![("require(\"a\"); process;", None)];
+    let fail = vec![("window;", None)];
+    Tester::new(NoUndef::NAME, pass, fail).with_environment(Environment::NODE).test();
 }
@@ -2,11 +2,11 @@ use oxc_ast::{
     ast::{BinaryExpression, Expression},
     AstKind,
 };
+use oxc_codegen::Gen;
 use oxc_diagnostics::{
     miette::{self, Diagnostic},
     thiserror::{self, Error},
 };
-use oxc_formatter::Gen;
 use oxc_macros::declare_oxc_lint;
 use oxc_span::{GetSpan, Span};
 use oxc_syntax::operator::{BinaryOperator, UnaryOperator};
@@ -47,7 +47,8 @@ declare_oxc_lint!(
     /// }
     /// ```
     NoUnsafeNegation,
-    correctness
+    correctness,
+    suggestion
 );
 
 impl Rule for NoUnsafeNegation {
@@ -85,20 +86,20 @@ impl NoUnsafeNegation {
         let fix_producer = || {
             // modify `!a instance of B` to `!(a instanceof B)`
             let modified_code = {
-                let mut formatter = ctx.formatter();
-                formatter.print(b'!');
+                let mut codegen = ctx.codegen();
+                codegen.print(b'!');
                 let Expression::UnaryExpression(left) = &expr.left else { unreachable!() };
-                formatter.print(b'(');
-                left.argument.gen(&mut formatter);
-                expr.operator.gen(&mut formatter);
-                expr.right.gen(&mut formatter);
-                formatter.print(b')');
-                formatter.into_code()
+                codegen.print(b'(');
+                left.argument.gen(&mut codegen);
+                expr.operator.gen(&mut codegen);
+                expr.right.gen(&mut codegen);
+                codegen.print(b')');
+                codegen.into_code()
             };
             Fix::new(modified_code, expr.span)
         };
 
-        ctx.diagnostic_with_fix(diagnostic, fix_producer);
+        ctx.diagnostic_with_suggestion(diagnostic, fix_producer);
     }
 }
 
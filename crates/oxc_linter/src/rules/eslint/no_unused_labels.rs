@@ -37,7 +37,8 @@ declare_oxc_lint!(
     /// }
     /// ```
     NoUnusedLabels,
-    correctness
+    correctness,
+    fix
 );
 
 impl Rule for NoUnusedLabels {
@@ -0,0 +1,321 @@
+// Ported from https://github.com/eslint/eslint/blob/main/lib/rules/no-useless-backreference.js
+use oxc_ast::{
+    ast::{Argument, Expression, RegExpFlags},
+    AstKind,
+};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_macros::declare_oxc_lint;
+use oxc_regexp::{
+    ast::{Alternative, Assertion, AssertionKind, Backreference, CapturingGroup, Group, Reference},
+    parse_flags,
+    visit::{walk_alternative, walk_alternatives, Visit},
+    PatternParser,
+};
+use oxc_span::Span;
+
+use crate::{context::LintContext, rule::Rule, AstNode};
+
+#[derive(Debug, Error, Diagnostic)]
+enum NoUselessBackreferenceDiagnostic {
+    #[error("eslint(no-useless-backreference): Backreference '{0}' will be ignored. It references group '{1}' from within that group.")]
+    #[diagnostic(severity(warning))]
+    Nested(String, String, #[label] Span),
+
+    #[error("eslint(no-useless-backreference): Backreference '{0}' will be ignored. It references group '{1}' which appears later in the pattern.")]
+    #[diagnostic(severity(warning))]
+    Forward(String, String, #[label] Span),
+
+    #[error("eslint(no-useless-backreference): Backreference '{0}' will be ignored. It references group '{1}' which appears before in the same lookbehind.")]
+    #[diagnostic(severity(warning))]
+    Backward(String, String, #[label] Span),
+
+    #[error("eslint(no-useless-backreference): Backreference '{0}' will be ignored. It references group '{1}' which is in another alternative.")]
+    #[diagnostic(severity(warning))]
+    Disjunctive(String, String, #[label] Span),
+
+    #[error("eslint(no-useless-backreference): Backreference '{0}' will be ignored. It references group '{1}' which is in a negative lookaround.")]
+    #[diagnostic(severity(warning))]
+    IntoNegativeLookaround(String, String, #[label] Span),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NoUselessBackreference;
+
+declare_oxc_lint!(
+    /// ### What it does
+    ///
+    /// Disallow useless backreferences in regular expressions
+    ///
+    /// ### Why is this bad?
+    ///
+    /// A backreference to a group which has not matched, or cannot have matched at the point
+    /// of the backreference, always matches the empty string, which is likely a mistake.
+    ///
+    /// ### Example
+    ///
+    /// ```javascript
+    /// /\1(a)/;       // the group appears later
+    /// /(a\1)/;       // from within the group
+    /// /(a)|\1b/;     // in another alternative
+    /// /(?<=(a)\1)b/; // lookbehinds match backwards
+    /// /(?!(a))\1b/;  // in a negative lookaround
+    /// ```
+    NoUselessBackreference,
+    correctness
+);
+
+impl Rule for NoUselessBackreference {
+    fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
+        let (pattern, flags, span) = match node.kind() {
+            AstKind::RegExpLiteral(lit) => (lit.regex.pattern.as_str(), lit.regex.flags, lit.span),
+            AstKind::NewExpression(expr) if expr.callee.is_specific_id("RegExp") => {
+                let Some(pattern) = regexp_constructor_pattern(&expr.arguments) else { return };
+                (pattern.0, pattern.1, expr.span)
+            }
+            AstKind::CallExpression(expr) if expr.callee.is_specific_id("RegExp") => {
+                let Some(pattern) = regexp_constructor_pattern(&expr.arguments) else { return };
+                (pattern.0, pattern.1, expr.span)
+            }
+            _ => return,
+        };
+        let Ok(parsed) = PatternParser::new(pattern, flags).parse() else { return };
+
+        let mut collector = Collector::default();
+        collector.visit_pattern(&parsed);
+        let raw = |span: Span| pattern[span.start as usize..span.end as usize].to_string();
+        for reference in &collector.references {
+            let groups = collector.groups.iter().filter(|group| match &reference.reference {
+                Reference::Index(index) => group.index == *index,
+                Reference::Name(name) => group.name.as_ref() == Some(name),
+            });
+            // Duplicate named groups are useful if any of them is
+            let problems = groups
+                .map(|group| Problem::of(reference, group).map(|problem| (problem, group)))
+                .collect::<Option<Vec<_>>>();
+            let Some(&(problem, group)) = problems.as_ref().and_then(|problems| problems.first())
+            else {
+                continue;
+            };
+            let (bref, group) = (raw(reference.span), raw(group.span));
+            ctx.diagnostic(match problem {
+                Problem::Nested => NoUselessBackreferenceDiagnostic::Nested(bref, group, span),
+                Problem::Forward => NoUselessBackreferenceDiagnostic::Forward(bref, group, span),
+                Problem::Backward => NoUselessBackreferenceDiagnostic::Backward(bref, group, span),
+                Problem::Disjunctive => {
+                    NoUselessBackreferenceDiagnostic::Disjunctive(bref, group, span)
+                }
+                Problem::IntoNegativeLookaround => {
+                    NoUselessBackreferenceDiagnostic::IntoNegativeLookaround(bref, group, span)
+                }
+            });
+        }
+    }
+}
+
+/// The pattern and flags of `RegExp("pattern", "flags")`, without flags when they are not a
+/// string literal
+fn regexp_constructor_pattern<'b>(arguments: &'b [Argument<'_>]) -> Option<(&'b str, RegExpFlags)> {
+    let Some(Argument::Expression(Expression::StringLiteral(pattern))) = arguments.first() else {
+        return None;
+    };
+    let flags = match arguments.get(1) {
+        Some(Argument::Expression(Expression::StringLiteral(flags))) => {
+            parse_flags(flags.value.as_str()).ok()?
+        }
+        _ => RegExpFlags::empty(),
+    };
+    Some((pattern.value.as_str(), flags))
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Problem {
+    Nested,
+    Forward,
+    Backward,
+    Disjunctive,
+    IntoNegativeLookaround,
+}
+
+impl Problem {
+    fn of(reference: &ReferenceInfo, group: &GroupInfo) -> Option<Self> {
+        if reference.path.iter().any(|frame| frame.address == group.address) {
+            return Some(Self::Nested);
+        }
+        // The path from the root to the group splits from the path to the reference below
+        // their lowest common ancestor
+        let common = reference
+            .path
+            .iter()
+            .zip(&group.path)
+            .take_while(|(a, b)| a.address == b.address)
+            .count();
+        let (above, below) = group.path.split_at(common);
+        let is_matching_backward = above
+            .iter()
+            .rev()
+            .find_map(|frame| match frame.kind {
+                FrameKind::Lookaround { behind, .. } => Some(behind),
+                _ => None,
+            })
+            .unwrap_or(false);
+        if matches!(below.first().map(|frame| frame.kind), Some(FrameKind::Alternative)) {
+            Some(Self::Disjunctive)
+        } else if !is_matching_backward && reference.span.end <= group.span.start {
+            Some(Self::Forward)
+        } else if is_matching_backward && group.span.end <= reference.span.start {
+            Some(Self::Backward)
+        } else if below
+            .iter()
+            .any(|frame| matches!(frame.kind, FrameKind::Lookaround { negative: true, .. }))
+        {
+            Some(Self::IntoNegativeLookaround)
+        } else {
+            None
+        }
+    }
+}
+
+/// A node containing groups and backreferences, identified by its address
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    address: usize,
+    kind: FrameKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FrameKind {
+    Alternative,
+    Group,
+    Lookaround { behind: bool, negative: bool },
+}
+
+struct GroupInfo {
+    address: usize,
+    index: u32,
+    name: Option<String>,
+    span: Span,
+    /// From the root to the group itself
+    path: Vec<Frame>,
+}
+
+struct ReferenceInfo {
+    reference: Reference,
+    span: Span,
+    /// From the root to the innermost node containing the backreference
+    path: Vec<Frame>,
+}
+
+#[derive(Default)]
+struct Collector {
+    stack: Vec<Frame>,
+    groups: Vec<GroupInfo>,
+    references: Vec<ReferenceInfo>,
+}
+
+impl Collector {
+    fn with_frame<T>(&mut self, node: &T, kind: FrameKind, visit: impl FnOnce(&mut Self)) {
+        self.stack.push(Frame { address: std::ptr::addr_of!(*node) as usize, kind });
+        visit(self);
+        self.stack.pop();
+    }
+}
+
+impl Visit for Collector {
+    fn visit_alternative(&mut self, alternative: &Alternative) {
+        self.with_frame(alternative, FrameKind::Alternative, |v| walk_alternative(v, alternative));
+    }
+
+    fn visit_assertion(&mut self, assertion: &Assertion) {
+        let (behind, negative) = match assertion.kind {
+            AssertionKind::Lookahead => (false, false),
+            AssertionKind::NegativeLookahead => (false, true),
+            AssertionKind::Lookbehind => (true, false),
+            AssertionKind::NegativeLookbehind => (true, true),
+            _ => return,
+        };
+        self.with_frame(assertion, FrameKind::Lookaround { behind, negative }, |v| {
+            walk_alternatives(v, &assertion.alternatives);
+        });
+    }
+
+    fn visit_group(&mut self, group: &Group) {
+        self.with_frame(group, FrameKind::Group, |v| walk_alternatives(v, &group.alternatives));
+    }
+
+    fn visit_capturing_group(&mut self, group: &CapturingGroup) {
+        self.with_frame(group, FrameKind::Group, |v| {
+            v.groups.push(GroupInfo {
+                address: std::ptr::addr_of!(*group) as usize,
+                index: group.index,
+                name: group.name.clone(),
+                span: group.span,
+                path: v.stack.clone(),
+            });
+            walk_alternatives(v, &group.alternatives);
+        });
+    }
+
+    fn visit_backreference(&mut self, backreference: &Backreference) {
+        self.references.push(ReferenceInfo {
+            reference: backreference.reference.clone(),
+            span: backreference.span,
+            path: self.stack.clone(),
+        });
+    }
+}
+
+#[test]
+fn test() {
+    use crate::tester::Tester;
+
+    let pass = vec![
+        ("'\\\\1(a)'", None),
+        ("regExp('\\\\1(a)')", None),
+        ("new Regexp('\\\\1(a)', 'u')", None),
+        ("RegExp.foo('\\\\1(a)', 'u')", None),
+        ("new foo.RegExp('\\\\1(a)')", None),
+        ("RegExp(p)", None),
+        ("new RegExp(p, 'u')", None),
+        ("RegExp('\\\\1(a)' + suffix)", None),
+        ("/a/", None),
+        ("/\\1/", None),
+        ("/(a)\\1/", None),
+        ("/(a)(b)\\2\\1/", None),
+        ("/(?<foo>a)\\k<foo>/", None),
+        ("/(a)|(b)\\2/", None),
+        ("/(?:(a)|b)\\1/", None),
+        ("/(a)(?:b|\\1)/", None),
+        ("/(?:(a)|b)*\\1/", None),
+        ("/(a)?\\1/", None),
+        ("/((a)\\2)/", None),
+        ("/(?=(a))\\1/", None),
+        ("/(?<=\\1(a))b/", None),
+        ("/(?!(a)\\1)b/", None),
+        ("/(?<!\\1(a))b/", None),
+        ("new RegExp('(a)\\\\1')", None),
+        ("RegExp('(?<=\\\\1(a))b')", None),
+        ("/[\\1](a)/", None),
+        ("/(?:a|(b))\\1/u", None),
+    ];
+
+    let fail = vec![
+        ("/(a\\1)/", None),
+        ("/(a(b)\\1)/", None),
+        ("/(?<foo>a\\k<foo>)/", None),
+        ("/\\1(a)/u", None),
+        ("/\\k<foo>(?<foo>a)/", None),
+        ("/(a)|\\1b/", None),
+        ("/(?<=(a)\\1)b/", None),
+        ("/(?!(a))\\1b/", None),
+        ("/(?<!(a))b\\1/", None),
+        ("new RegExp('(a)|\\\\1b')", None),
+        ("RegExp('\\\\1(a)', 'u')", None),
+        ("/(?:(a)|b)|\\1/", None),
+        ("/(a)(b)|(c)\\2/", None),
+    ];
+
+    Tester::new(NoUselessBackreference::NAME, pass, fail).test_and_snapshot();
+}
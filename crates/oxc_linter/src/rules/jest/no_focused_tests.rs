@@ -54,7 +54,8 @@ declare_oxc_lint!(
     /// `();
     /// ```
     NoFocusedTests,
-    suspicious
+    suspicious,
+    fix
 );
 
 impl Rule for NoFocusedTests {
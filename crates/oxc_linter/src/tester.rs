@@ -7,12 +7,13 @@ use oxc_semantic::SemanticBuilder;
 use oxc_span::SourceType;
 use serde_json::Value;
 
-use crate::{rules::RULES, Fixer, LintContext, Linter, Message};
+use crate::{rules::RULES, Environment, FixSuggestions, Fixer, LintContext, Linter, Message};
 
 pub struct Tester {
     rule_name: &'static str,
     expect_pass: Vec<(String, Option<Value>)>,
     expect_fail: Vec<(String, Option<Value>)>,
+    environment: Environment,
     snapshot: String,
 }
 
@@ -24,7 +25,13 @@ impl Tester {
     ) -> Self {
         let expect_pass = expect_pass.into_iter().map(|(s, r)| (s.into(), r)).collect::<Vec<_>>();
         let expect_fail = expect_fail.into_iter().map(|(s, r)| (s.into(), r)).collect::<Vec<_>>();
-        Self { rule_name, expect_pass, expect_fail, snapshot: String::new() }
+        Self {
+            rule_name,
+            expect_pass,
+            expect_fail,
+            environment: Environment::default(),
+            snapshot: String::new(),
+        }
     }
 
     pub fn new_without_config<S: Into<String>>(
@@ -34,7 +41,20 @@ impl Tester {
     ) -> Self {
         let expect_pass = expect_pass.into_iter().map(|s| (s.into(), None)).collect::<Vec<_>>();
         let expect_fail = expect_fail.into_iter().map(|s| (s.into(), None)).collect::<Vec<_>>();
-        Self { rule_name, expect_pass, expect_fail, snapshot: String::new() }
+        Self {
+            rule_name,
+            expect_pass,
+            expect_fail,
+            environment: Environment::default(),
+            snapshot: String::new(),
+        }
+    }
+
+    /// Lint the code as running in the environments, e.g. `Environment::BROWSER`
+    #[must_use]
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self
     }
 
     pub fn test(&mut self) {
@@ -145,6 +165,11 @@ impl Tester {
             .unwrap_or_else(|| panic!("Rule not found: {}", &self.rule_name));
         let rule = rule.read_json(config);
         let lint_context = LintContext::new(&Rc::new(semantic_ret.semantic));
-        Linter::from_rules(vec![rule]).with_fix(is_fix).run(lint_context)
+        let fix_suggestions = if is_fix { FixSuggestions::All } else { FixSuggestions::None };
+        Linter::from_rules(vec![rule])
+            .with_fix(is_fix)
+            .with_fix_suggestions(fix_suggestions)
+            .with_environment(self.environment)
+            .run(lint_context)
     }
 }
@@ -23,6 +23,8 @@ fn parse_attr<const LEN: usize>(path: [&'static str; LEN], attr: &Attribute) ->
 pub struct LintRuleMeta {
     name: Ident,
     category: Ident,
+    /// `fix` or `suggestion`, when the rule provides automatic fixes
+    fix: Option<Ident>,
     documentation: String,
     pub used_in_test: bool,
 }
@@ -48,15 +50,22 @@ impl Parse for LintRuleMeta {
         input.parse::<Token!(,)>()?;
         let category = input.parse()?;
 
+        let fix = if input.peek(Token!(,)) && input.peek2(Ident) {
+            input.parse::<Token!(,)>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
         // Ignore the rest
         input.parse::<TokenStream>()?;
 
-        Ok(Self { name: struct_name, category, documentation, used_in_test: false })
+        Ok(Self { name: struct_name, category, fix, documentation, used_in_test: false })
     }
 }
 
 pub fn declare_oxc_lint(metadata: LintRuleMeta) -> TokenStream {
-    let LintRuleMeta { name, category, documentation, used_in_test } = metadata;
+    let LintRuleMeta { name, category, fix, documentation, used_in_test } = metadata;
     let canonical_name = name.to_string().to_case(Case::Kebab);
     let category = match category.to_string().as_str() {
         "correctness" => quote! { RuleCategory::Correctness },
@@ -68,8 +77,16 @@ pub fn declare_oxc_lint(metadata: LintRuleMeta) -> TokenStream {
         _ => panic!("invalid rule category"),
     };
 
+    let fix = fix.map(|fix| match fix.to_string().as_str() {
+        "fix" => quote! { const FIX: FixKind = FixKind::Fix; },
+        "suggestion" => quote! { const FIX: FixKind = FixKind::Suggestion; },
+        _ => panic!("invalid fix kind, expected `fix` or `suggestion`"),
+    });
+
     let import_statement = if used_in_test {
         None
+    } else if fix.is_some() {
+        Some(quote! { use crate::rule::{FixKind, RuleCategory, RuleMeta}; })
     } else {
         Some(quote! { use crate::rule::{RuleCategory, RuleMeta}; })
     };
@@ -82,6 +99,8 @@ pub fn declare_oxc_lint(metadata: LintRuleMeta) -> TokenStream {
 
             const CATEGORY: RuleCategory = #category;
 
+            #fix
+
             fn documentation() -> Option<&'static str> {
                 Some(#documentation)
             }
@@ -1,8 +1,9 @@
 use std::path::Path;
 
-use oxc_minifier::{Minifier, MinifierOptions};
+use oxc_minifier::{LicenseComments, Minifier, MinifierOptions, NameCache, PropertyManglerOptions};
 use oxc_span::SourceType;
 use pico_args::Arguments;
+use regex::Regex;
 
 #[cfg(not(target_env = "msvc"))]
 #[global_allocator]
@@ -16,6 +17,8 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 // create a `test.js`,
 // run `cargo run -p oxc_minifier --example minifier`
 // or `cargo watch -x "run -p oxc_minifier --example minifier"`
+// add `--mangle-props --mangle-props-regex '^_' --name-cache cache.json` to mangle properties
+// add `--extract-license-comments` to move license comments to `test.js.LICENSE.txt`
 
 fn main() {
     let mut args = Arguments::from_env();
@@ -23,18 +26,53 @@ fn main() {
     let name = args.subcommand().ok().flatten().unwrap_or_else(|| String::from("test.js"));
     let mangle = args.contains("--mangle");
     let twice = args.contains("--twice");
+    let mangle_props = args.contains("--mangle-props");
+    let mangle_props_builtins = args.contains("--mangle-props-builtins");
+    let mangle_props_regex: Option<String> =
+        args.opt_value_from_str("--mangle-props-regex").unwrap();
+    let mangle_props_reserved: Option<String> =
+        args.opt_value_from_str("--mangle-props-reserved").unwrap();
+    let name_cache_path: Option<String> = args.opt_value_from_str("--name-cache").unwrap();
+    let extract_license_comments = args.contains("--extract-license-comments");
 
     let path = Path::new(&name);
     let source_text = std::fs::read_to_string(path).unwrap_or_else(|_| panic!("{name} not found"));
     let source_type = SourceType::from_path(path).unwrap();
 
-    let options = MinifierOptions { mangle, ..MinifierOptions::default() };
-    let printed = Minifier::new(&source_text, source_type, options).build();
-    println!("{printed}");
+    let mangle_props = mangle_props.then(|| PropertyManglerOptions {
+        regex: mangle_props_regex.map(|regex| Regex::new(&regex).unwrap()),
+        reserved: mangle_props_reserved
+            .map(|reserved| reserved.split(',').map(ToString::to_string).collect())
+            .unwrap_or_default(),
+        builtins: mangle_props_builtins,
+    });
+    let mut name_cache = name_cache_path
+        .as_ref()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|json| NameCache::from_json(&json).unwrap())
+        .unwrap_or_default();
+
+    let license_comments =
+        if extract_license_comments { LicenseComments::Extract } else { LicenseComments::Inline };
+    let options =
+        MinifierOptions { mangle, mangle_props, license_comments, ..MinifierOptions::default() };
+    let ret = Minifier::new(&source_text, source_type, options.clone()).minify(&mut name_cache);
+    let printed = ret.source_text;
+    if ret.license_comments.is_empty() {
+        println!("{printed}");
+    } else {
+        let license_file = format!("{name}.LICENSE.txt");
+        std::fs::write(&license_file, ret.license_comments.join("\n\n")).unwrap();
+        println!("/*! For license information please see {license_file} */\n{printed}");
+    }
 
     if twice {
-        let options = MinifierOptions { mangle, ..MinifierOptions::default() };
-        let printed = Minifier::new(&printed, source_type, options).build();
+        let printed =
+            Minifier::new(&printed, source_type, options).build_with_name_cache(&mut name_cache);
         println!("{printed}");
     }
+
+    if let Some(path) = name_cache_path {
+        std::fs::write(path, name_cache.to_json()).unwrap();
+    }
 }
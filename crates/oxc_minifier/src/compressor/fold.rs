@@ -2,7 +2,7 @@
 //!
 //! <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeFoldConstants.java>
 
-use std::{cmp::Ordering, mem, ops::Not};
+use std::{borrow::Cow, cmp::Ordering, mem, ops::Not};
 
 use num_bigint::BigInt;
 #[allow(clippy::wildcard_imports)]
@@ -175,6 +175,41 @@ impl<'a> From<&Expression<'a>> for Ty {
     }
 }
 
+impl Ty {
+    /// The types which `+` adds as numbers
+    fn is_numeric(self) -> bool {
+        matches!(self, Self::Number | Self::Boolean | Self::Null)
+    }
+}
+
+/// Integers beyond 2^53 lose precision, so arithmetic results beyond it are not folded.
+const MAX_FOLD_NUMBER: f64 = 9_007_199_254_740_992.0;
+
+/// `Number.prototype.toString` of the value, if it is printed without an exponent.
+fn number_to_string(value: f64) -> Option<String> {
+    if value == 0.0 {
+        // Including `-0`
+        return Some("0".to_string());
+    }
+    (1e-6..1e21).contains(&value.abs()).then(|| value.to_string())
+}
+
+/// The string which the value of a literal is converted to by `+`.
+fn get_concatenated_string_value<'a>(expr: &'a Expression) -> Option<Cow<'a, str>> {
+    match expr {
+        Expression::NumberLiteral(number_literal) => {
+            number_to_string(number_literal.value).map(Cow::Owned)
+        }
+        Expression::StringLiteral(_)
+        | Expression::TemplateLiteral(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::BigintLiteral(_)
+        | Expression::UnaryExpression(_) => get_side_free_string_value(expr),
+        _ => None,
+    }
+}
+
 impl<'a> Compressor<'a> {
     pub(crate) fn fold_expression<'b>(&mut self, expr: &'b mut Expression<'a>) {
         let folded_expr = match expr {
@@ -200,6 +235,17 @@ impl<'a> Compressor<'a> {
                     &binary_expr.left,
                     &binary_expr.right,
                 ),
+                BinaryOperator::Addition => self.try_fold_addition(binary_expr),
+                BinaryOperator::Subtraction
+                | BinaryOperator::Multiplication
+                | BinaryOperator::Division
+                | BinaryOperator::Remainder
+                | BinaryOperator::Exponential => self.try_fold_arithmetic(
+                    binary_expr.span,
+                    binary_expr.operator,
+                    &binary_expr.left,
+                    &binary_expr.right,
+                ),
                 _ => None,
             },
             Expression::UnaryExpression(unary_expr) => match unary_expr.operator {
@@ -580,7 +626,7 @@ impl<'a> Compressor<'a> {
                         return Some(self.hir.literal_number_expression(literal));
                     }
                     Expression::Identifier(ident) => {
-                        if matches!(ident.name.as_str(), "NaN" | "Infinity") {
+                        if self.is_builtin(ident, "NaN") || self.is_builtin(ident, "Infinity") {
                             return self.try_detach_unary_op(unary_expr);
                         }
                     }
@@ -628,7 +674,7 @@ impl<'a> Compressor<'a> {
                         return Some(self.hir.literal_bigint_expression(literal));
                     }
                     Expression::Identifier(ident) => {
-                        if ident.name == "NaN" {
+                        if self.is_builtin(ident, "NaN") {
                             return self.try_detach_unary_op(unary_expr);
                         }
                     }
@@ -656,7 +702,7 @@ impl<'a> Compressor<'a> {
                         return Some(self.hir.literal_bigint_expression(leteral));
                     }
                     Expression::Identifier(ident) => {
-                        if ident.name == "NaN" {
+                        if self.is_builtin(ident, "NaN") {
                             let value = -1_f64;
                             let raw = self.hir.new_str("-1");
                             let literal = self.hir.number_literal(
@@ -774,6 +820,107 @@ impl<'a> Compressor<'a> {
         None
     }
 
+    /// port of `tryFoldAdd` from [closure-compiler](https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeFoldConstants.java)
+    /// 'a' + 1 -> 'a1'
+    /// x + 'a' + 'b' -> x + 'ab'
+    /// 1 + true -> 2
+    fn try_fold_addition<'b>(
+        &mut self,
+        binary_expr: &'b mut BinaryExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        let left_ty = Ty::from(&binary_expr.left);
+        let right_ty = Ty::from(&binary_expr.right);
+        if left_ty.is_numeric() && right_ty.is_numeric() {
+            return self.try_fold_arithmetic(
+                binary_expr.span,
+                binary_expr.operator,
+                &binary_expr.left,
+                &binary_expr.right,
+            );
+        }
+        if left_ty != Ty::Str && right_ty != Ty::Str {
+            return None;
+        }
+
+        let right = get_concatenated_string_value(&binary_expr.right)?;
+        if let Some(left) = get_concatenated_string_value(&binary_expr.left) {
+            let value = Atom::from(left.into_owned() + &right);
+            let string_literal = self.hir.string_literal(binary_expr.span, value);
+            return Some(self.hir.literal_string_expression(string_literal));
+        }
+
+        // `x + 'a'` is a string whatever `x` is, so the constants can be concatenated first
+        let Expression::BinaryExpression(left_expr) = &mut binary_expr.left else { return None };
+        if left_expr.operator != BinaryOperator::Addition {
+            return None;
+        }
+        let Expression::StringLiteral(string_literal) = &left_expr.right else { return None };
+        let value = Atom::from(string_literal.value.to_string() + &right);
+        let string_literal = self.hir.string_literal(left_expr.right.span(), value);
+        let right = self.hir.literal_string_expression(string_literal);
+        let left = self.move_out_expression(&mut left_expr.left);
+        Some(self.hir.binary_expression(binary_expr.span, left, BinaryOperator::Addition, right))
+    }
+
+    /// port of `performArithmeticOp` from [closure-compiler](https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeFoldConstants.java)
+    /// 1 + 2 -> 3
+    /// 2 ** -1 -> .5
+    ///
+    /// Results which are longer than the expression, like `1 / 3`, are not folded.
+    fn try_fold_arithmetic<'b>(
+        &mut self,
+        span: Span,
+        op: BinaryOperator,
+        left: &'b Expression<'a>,
+        right: &'b Expression<'a>,
+    ) -> Option<Expression<'a>> {
+        if !Ty::from(left).is_numeric() || !Ty::from(right).is_numeric() {
+            return None;
+        }
+        let (Some(NumberValue::Number(left_val)), Some(NumberValue::Number(right_val))) =
+            (get_side_free_number_value(left), get_side_free_number_value(right))
+        else {
+            return None;
+        };
+
+        let result = match op {
+            BinaryOperator::Addition => left_val + right_val,
+            BinaryOperator::Subtraction => left_val - right_val,
+            BinaryOperator::Multiplication => left_val * right_val,
+            BinaryOperator::Division if right_val != 0.0 => left_val / right_val,
+            BinaryOperator::Remainder if right_val != 0.0 => left_val % right_val,
+            BinaryOperator::Exponential => left_val.powf(right_val),
+            _ => return None,
+        };
+        if !result.is_finite() || result.abs() > MAX_FOLD_NUMBER {
+            return None;
+        }
+
+        // Numbers are printed without a leading zero, `.5`
+        let printed_len = |value| number_to_string(value).map(|s| s.trim_start_matches('0').len());
+        let result_len = printed_len(result)?;
+        // The operands and the operator
+        if result_len > printed_len(left_val)? + printed_len(right_val)? + 1 {
+            return None;
+        }
+
+        Some(self.create_number_expression(span, result))
+    }
+
+    /// A number literal, negated when the number is negative: `-1` -> `-(1)`
+    fn create_number_expression(&mut self, span: Span, value: f64) -> Expression<'a> {
+        let abs_value = value.abs();
+        let raw = self.hir.new_str(abs_value.to_string().as_str());
+        let base = if abs_value.fract() == 0.0 { NumberBase::Decimal } else { NumberBase::Float };
+        let number_literal = self.hir.number_literal(span, abs_value, raw, base);
+        let expr = self.hir.literal_number_expression(number_literal);
+        if value.is_sign_negative() {
+            self.hir.unary_expression(span, UnaryOperator::UnaryNegation, expr)
+        } else {
+            expr
+        }
+    }
+
     /// port from [closure-compiler](https://github.com/google/closure-compiler/blob/09094b551915a6487a980a783831cba58b5739d1/src/com/google/javascript/jscomp/PeepholeFoldConstants.java#L587)
     /// Try to fold a AND/OR node.
     fn try_fold_and_or(
@@ -880,7 +1027,7 @@ impl<'a> Compressor<'a> {
         folded_expr
     }
 
-    fn move_out_expression(&mut self, expr: &mut Expression<'a>) -> Expression<'a> {
+    pub(crate) fn move_out_expression(&mut self, expr: &mut Expression<'a>) -> Expression<'a> {
         let null_literal = self.hir.null_literal(expr.span());
         let null_expr = self.hir.literal_null_expression(null_literal);
         mem::replace(expr, null_expr)
@@ -0,0 +1,249 @@
+//! Minimize Conditions
+//!
+//! <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeMinimizeConditions.java>
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_span::SPAN;
+use oxc_syntax::operator::{BinaryOperator, LogicalOperator, UnaryOperator};
+
+use super::Compressor;
+
+/// Whether the value of the expression is always a boolean.
+fn is_boolean_value(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::BooleanLiteral(_) => true,
+        Expression::UnaryExpression(unary_expr) => {
+            matches!(unary_expr.operator, UnaryOperator::LogicalNot | UnaryOperator::Delete)
+        }
+        Expression::BinaryExpression(binary_expr) => {
+            binary_expr.operator.is_equality()
+                || binary_expr.operator.is_compare()
+                || matches!(binary_expr.operator, BinaryOperator::In | BinaryOperator::Instanceof)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the branch of an `if` statement does nothing.
+fn is_empty_branch(stmt: Option<&Statement<'_>>) -> bool {
+    match stmt {
+        None => true,
+        Some(Statement::BlockStatement(block)) => block.body.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_expression_branch(stmt: Option<&Statement<'_>>) -> bool {
+    matches!(stmt, Some(Statement::ExpressionStatement(_)))
+}
+
+impl<'a> Compressor<'a> {
+    /// Simplifies the test of an `if` statement or a loop, of which only the truthiness is used.
+    /// Enabled by `compress.booleans`
+    pub(crate) fn minimize_statement_condition<'b>(&mut self, stmt: &'b mut Statement<'a>) {
+        if !self.options.booleans {
+            return;
+        }
+        match stmt {
+            Statement::IfStatement(if_stmt) => self.minimize_condition(&mut if_stmt.test),
+            Statement::WhileStatement(while_stmt) => self.minimize_condition(&mut while_stmt.test),
+            Statement::DoWhileStatement(do_while_stmt) => {
+                self.minimize_condition(&mut do_while_stmt.test);
+            }
+            Statement::ForStatement(for_stmt) => {
+                if let Some(test) = &mut for_stmt.test {
+                    self.minimize_condition(test);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Removes double negations from an expression of which only the truthiness is used.
+    ///
+    /// `!!a && !!b` -> `a && b`
+    fn minimize_condition<'b>(&mut self, expr: &'b mut Expression<'a>) {
+        match expr {
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::LogicalNot =>
+            {
+                if let Expression::UnaryExpression(argument) = &mut unary_expr.argument {
+                    if argument.operator == UnaryOperator::LogicalNot {
+                        *expr = self.move_out_expression(&mut argument.argument);
+                        self.minimize_condition(expr);
+                    }
+                }
+            }
+            // The value of `a ?? b` is `a` when it is not nullish, which `!!a` never is
+            Expression::LogicalExpression(logical_expr)
+                if logical_expr.operator != LogicalOperator::Coalesce =>
+            {
+                self.minimize_condition(&mut logical_expr.left);
+                self.minimize_condition(&mut logical_expr.right);
+            }
+            Expression::SequenceExpression(sequence_expr) => {
+                if let Some(last) = sequence_expr.expressions.last_mut() {
+                    self.minimize_condition(last);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `!!(a > b)` -> `a > b`
+    /// `!(a == b)` -> `a != b`
+    pub(crate) fn minimize_not<'b>(&mut self, expr: &'b mut Expression<'a>) {
+        let Expression::UnaryExpression(unary_expr) = expr else { return };
+        if unary_expr.operator != UnaryOperator::LogicalNot {
+            return;
+        }
+        match &mut unary_expr.argument {
+            Expression::UnaryExpression(argument)
+                if argument.operator == UnaryOperator::LogicalNot
+                    && is_boolean_value(&argument.argument) =>
+            {
+                *expr = self.move_out_expression(&mut argument.argument);
+            }
+            Expression::BinaryExpression(argument) => {
+                if let Some(operator) = argument.operator.equality_inverse_operator() {
+                    argument.operator = operator;
+                    *expr = self.move_out_expression(&mut unary_expr.argument);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `!!a ? b : c` -> `a ? b : c`
+    /// `!a ? b : c` -> `a ? c : b`
+    /// Enabled by `compress.booleans`
+    pub(crate) fn minimize_conditional<'b>(&mut self, expr: &'b mut Expression<'a>) {
+        if !self.options.booleans {
+            return;
+        }
+        let Expression::ConditionalExpression(conditional_expr) = expr else { return };
+        self.minimize_condition(&mut conditional_expr.test);
+        let Expression::UnaryExpression(test) = &mut conditional_expr.test else { return };
+        if test.operator == UnaryOperator::LogicalNot {
+            let argument = self.move_out_expression(&mut test.argument);
+            let ConditionalExpression { test, consequent, alternate, .. } = &mut **conditional_expr;
+            *test = argument;
+            std::mem::swap(consequent, alternate);
+        }
+    }
+
+    /// `if (a) b(); else c()` -> `a ? b() : c()`
+    /// `if (a) b()` -> `a && b()`
+    /// `if (!a) b()` -> `a || b()`
+    /// Enabled by `compress.conditionals`
+    pub(crate) fn minimize_if<'b>(&mut self, stmt: &'b mut Statement<'a>) {
+        if !self.options.conditionals {
+            return;
+        }
+        let Statement::IfStatement(if_stmt) = stmt else { return };
+        let consequent_is_empty = is_empty_branch(if_stmt.consequent.as_ref());
+        let alternate_is_empty = is_empty_branch(if_stmt.alternate.as_ref());
+        if !(consequent_is_empty || is_expression_branch(if_stmt.consequent.as_ref()))
+            || !(alternate_is_empty || is_expression_branch(if_stmt.alternate.as_ref()))
+        {
+            return;
+        }
+
+        let span = if_stmt.span;
+        let test = self.move_out_expression(&mut if_stmt.test);
+        let consequent = if_stmt.consequent.take().filter(|_| !consequent_is_empty);
+        let alternate = if_stmt.alternate.take().filter(|_| !alternate_is_empty);
+        let expr = match (consequent.map(into_expression), alternate.map(into_expression)) {
+            (Some(consequent), Some(alternate)) => {
+                let mut expr = self.hir.conditional_expression(SPAN, test, consequent, alternate);
+                self.minimize_conditional(&mut expr);
+                expr
+            }
+            (Some(consequent), None) => self.join_condition(test, LogicalOperator::And, consequent),
+            (None, Some(alternate)) => self.join_condition(test, LogicalOperator::Or, alternate),
+            (None, None) => test,
+        };
+        *stmt = self.hir.expression_statement(span, expr);
+    }
+
+    /// `a && b`, or `a || b` for `!a && b`
+    fn join_condition(
+        &mut self,
+        mut test: Expression<'a>,
+        operator: LogicalOperator,
+        expr: Expression<'a>,
+    ) -> Expression<'a> {
+        if let Expression::UnaryExpression(unary_expr) = &mut test {
+            if unary_expr.operator == UnaryOperator::LogicalNot {
+                let test = self.move_out_expression(&mut unary_expr.argument);
+                let operator = match operator {
+                    LogicalOperator::And => LogicalOperator::Or,
+                    _ => LogicalOperator::And,
+                };
+                return self.hir.logical_expression(SPAN, test, operator, expr);
+            }
+        }
+        self.hir.logical_expression(SPAN, test, operator, expr)
+    }
+
+    /// `if (a) return b; else return c` -> `return a ? b : c`
+    /// Enabled by `compress.if_return`
+    pub(crate) fn minimize_if_return<'b>(&mut self, stmt: &'b mut Statement<'a>) {
+        if !self.options.if_return {
+            return;
+        }
+        let Statement::IfStatement(if_stmt) = stmt else { return };
+        let IfStatement { span, test, consequent, alternate } = &mut **if_stmt;
+        let (
+            Some(Statement::ReturnStatement(consequent)),
+            Some(Statement::ReturnStatement(alternate)),
+        ) = (consequent, alternate)
+        else {
+            return;
+        };
+        // `return a ? void 0 : void 0` is not shorter
+        if consequent.argument.is_none() && alternate.argument.is_none() {
+            return;
+        }
+        let consequent = consequent.argument.take().unwrap_or_else(|| self.create_void_0());
+        let alternate = alternate.argument.take().unwrap_or_else(|| self.create_void_0());
+        let test = self.move_out_expression(test);
+        let mut argument = self.hir.conditional_expression(SPAN, test, consequent, alternate);
+        self.minimize_conditional(&mut argument);
+        *stmt = self.hir.return_statement(*span, Some(argument));
+    }
+
+    /// `if (a) return b; return c` -> `return a ? b : c`
+    /// Enabled by `compress.if_return`
+    pub(crate) fn minimize_if_returns<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !self.options.if_return {
+            return;
+        }
+        let mut i = 0;
+        while i + 1 < stmts.len() {
+            if let (Statement::IfStatement(if_stmt), Statement::ReturnStatement(return_stmt)) =
+                (&stmts[i], &stmts[i + 1])
+            {
+                if let (Some(Statement::ReturnStatement(consequent)), None) =
+                    (&if_stmt.consequent, &if_stmt.alternate)
+                {
+                    if consequent.argument.is_some() || return_stmt.argument.is_some() {
+                        let alternate = stmts.remove(i + 1);
+                        if let Statement::IfStatement(if_stmt) = &mut stmts[i] {
+                            if_stmt.alternate = Some(alternate);
+                        }
+                        self.minimize_if_return(&mut stmts[i]);
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+}
+
+fn into_expression(stmt: Statement<'_>) -> Expression<'_> {
+    let Statement::ExpressionStatement(expr_stmt) = stmt else { unreachable!() };
+    expr_stmt.unbox().expression
+}
@@ -1,27 +1,50 @@
 #![allow(clippy::unused_self)]
 
 mod fold;
+mod minimize_conditions;
+mod sequences;
 mod util;
 
 use oxc_allocator::{Allocator, Vec};
 #[allow(clippy::wildcard_imports)]
 use oxc_hir::{hir::*, HirBuilder, VisitMut};
-use oxc_semantic::Semantic;
-use oxc_span::Span;
+use oxc_semantic::{Environment, Semantic};
+use oxc_span::SPAN;
 use oxc_syntax::{
     operator::{BinaryOperator, UnaryOperator},
     precedence::GetPrecedence,
     NumberBase,
 };
+#[cfg(feature = "serde")]
+use serde::Deserialize;
 
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(default))]
 pub struct CompressOptions {
     /// Various optimizations for boolean context, for example `!!a ? b : c` → `a ? b : c`.
     ///
     /// Default `true`
     pub booleans: bool,
 
+    /// Turn `if` statements whose branches are expressions into `&&`, `||` and conditional
+    /// expressions, for example `if (a) b()` → `a && b()`.
+    ///
+    /// Default `true`
+    pub conditionals: bool,
+
+    /// Remove unreachable code, and the branches of `if` statements, `for` and `while` loops
+    /// and conditional expressions which are never taken. See [`crate::DeadCodeElimination`].
+    ///
+    /// Default `true`
+    pub dead_code: bool,
+
+    /// Replace the expressions whose value is a constant by the value when it is not longer, for
+    /// example `1 + 2` → `3` and `"a" + "b"` → `"ab"`. See [`crate::DeadCodeElimination`].
+    ///
+    /// Default `true`
+    pub evaluate: bool,
+
     /// Remove `debugger;` statements.
     ///
     /// Default `true`
@@ -32,6 +55,12 @@ pub struct CompressOptions {
     /// Default `false`
     pub drop_console: bool,
 
+    /// Join `if` statements which return in both branches into a single `return`, for example
+    /// `if (a) return b; return c` → `return a ? b : c`.
+    ///
+    /// Default `true`
+    pub if_return: bool,
+
     /// Join consecutive var statements.
     ///
     /// Default `true`
@@ -42,21 +71,47 @@ pub struct CompressOptions {
     /// Default `true`
     pub loops: bool,
 
+    /// Join consecutive expression statements with the comma operator, including into the
+    /// following `return`, `throw` or `if` statement, for example `a(); return b` → `return a(), b`.
+    ///
+    /// Default `true`
+    pub sequences: bool,
+
+    /// Drop the calls annotated with `/* #__PURE__ */` whose values are unused, keeping their
+    /// arguments with side effects, for example `/* #__PURE__ */ f(a())` → `a()`. See
+    /// [`crate::DeadCodeElimination`].
+    ///
+    /// Default `true`
+    pub side_effects: bool,
+
     /// Transforms `typeof foo == "undefined" into `foo === void 0`
     ///
     /// Default `true`
     pub typeofs: bool,
+
+    /// Drop the unreferenced functions and variables of functions, when their initializers have
+    /// no side effects, and the initializers which are overwritten before they are read.
+    ///
+    /// Default `true`
+    pub unused: bool,
 }
 
 impl Default for CompressOptions {
     fn default() -> Self {
         Self {
             booleans: true,
+            conditionals: true,
+            dead_code: true,
+            evaluate: true,
             drop_debugger: true,
             drop_console: false,
+            if_return: true,
             join_vars: true,
             loops: true,
+            sequences: true,
+            side_effects: true,
             typeofs: true,
+            unused: true,
         }
     }
 }
@@ -67,8 +122,6 @@ pub struct Compressor<'a> {
     options: CompressOptions,
 }
 
-const SPAN: Span = Span::new(0, 0);
-
 impl<'a> Compressor<'a> {
     pub fn new(allocator: &'a Allocator, semantic: Semantic<'a>, options: CompressOptions) -> Self {
         Self { hir: HirBuilder::new(allocator), semantic, options }
@@ -81,6 +134,23 @@ impl<'a> Compressor<'a> {
 
     /* Utilities */
 
+    /// Whether the identifier is the ECMAScript intrinsic `name`, rather than a binding shadowing it
+    fn is_builtin(&self, ident: &IdentifierReference, name: &str) -> bool {
+        if ident.name != name {
+            return false;
+        }
+        let reference = self.semantic.symbols().get_reference(ident.reference_id.get());
+        self.semantic.global_variable(reference, Environment::Builtin).is_some()
+    }
+
+    /// `undefined` or `void 0`
+    fn is_undefined(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Identifier(ident) => self.is_builtin(ident, "undefined"),
+            _ => expr.is_void_0(),
+        }
+    }
+
     /// `void 0`
     fn create_void_0(&mut self) -> Expression<'a> {
         let left = self.hir.number_literal(SPAN, 0.0, "0", NumberBase::Decimal);
@@ -136,7 +206,11 @@ impl<'a> Compressor<'a> {
     }
 
     /// Join consecutive var statements
+    /// Enabled by `compress.join_vars`
     fn join_vars<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !self.options.join_vars {
+            return;
+        }
         // Collect all the consecutive ranges that contain joinable vars.
         // This is required because Rust prevents in-place vec mutation.
         let mut ranges = vec![];
@@ -205,9 +279,7 @@ impl<'a> Compressor<'a> {
     /// Transforms `undefined` => `void 0`
     fn compress_undefined<'b>(&mut self, expr: &'b mut Expression<'a>) -> bool {
         let Expression::Identifier(ident) = expr else { return false };
-        if ident.name == "undefined"
-            && self.semantic.symbols().is_global_reference(ident.reference_id.clone().into_inner())
-        {
+        if self.is_builtin(ident, "undefined") {
             *expr = self.create_void_0();
             return true;
         }
@@ -218,9 +290,7 @@ impl<'a> Compressor<'a> {
     #[allow(unused)]
     fn compress_infinity<'b>(&mut self, expr: &'b mut Expression<'a>) -> bool {
         let Expression::Identifier(ident) = expr else { return false };
-        if ident.name == "Infinity"
-            && self.semantic.symbols().is_global_reference(ident.reference_id.clone().into_inner())
-        {
+        if self.is_builtin(ident, "Infinity") {
             *expr = self.create_one_div_zero();
             return true;
         }
@@ -269,16 +339,17 @@ impl<'a> Compressor<'a> {
     /// `return undefined` -> `return`
     /// `return void 0` -> `return`
     fn compress_return_statement<'b>(&mut self, stmt: &'b mut ReturnStatement<'a>) {
-        if stmt.argument.as_ref().is_some_and(|expr| expr.is_undefined() || expr.is_void_0()) {
+        if stmt.argument.as_ref().is_some_and(|expr| self.is_undefined(expr)) {
             stmt.argument = None;
         }
     }
 
     fn compress_variable_declarator<'b>(&mut self, decl: &'b mut VariableDeclarator<'a>) {
-        if decl.kind.is_const() {
+        // `const` and `using` declarations require an initializer
+        if decl.kind.is_const() || decl.kind.is_using() {
             return;
         }
-        if decl.init.as_ref().is_some_and(|init| init.is_undefined() || init.is_void_0()) {
+        if decl.init.as_ref().is_some_and(|init| self.is_undefined(init)) {
             decl.init = None;
         }
     }
@@ -318,6 +389,9 @@ impl<'a, 'b> VisitMut<'a, 'b> for Compressor<'a> {
         for stmt in stmts.iter_mut() {
             self.visit_statement(stmt);
         }
+
+        self.minimize_if_returns(stmts);
+        self.join_sequences(stmts);
     }
 
     fn visit_statement(&mut self, stmt: &'b mut Statement<'a>) {
@@ -325,6 +399,11 @@ impl<'a, 'b> VisitMut<'a, 'b> for Compressor<'a> {
         self.compress_while(stmt);
         self.fold_condition(stmt);
         self.visit_statement_match(stmt);
+        // Blocks may be left with a single statement by joining sequences
+        self.compress_block(stmt);
+        self.minimize_statement_condition(stmt);
+        self.minimize_if_return(stmt);
+        self.minimize_if(stmt);
     }
 
     fn visit_return_statement(&mut self, stmt: &'b mut ReturnStatement<'a>) {
@@ -346,6 +425,8 @@ impl<'a, 'b> VisitMut<'a, 'b> for Compressor<'a> {
         self.visit_expression_match(expr);
         self.compress_console(expr);
         self.fold_expression(expr);
+        self.minimize_not(expr);
+        self.minimize_conditional(expr);
         if !self.compress_undefined(expr) {
             self.compress_boolean(expr);
         }
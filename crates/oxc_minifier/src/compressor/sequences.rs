@@ -0,0 +1,99 @@
+//! Sequences
+//!
+//! Joins consecutive statements with the comma operator.
+//! See terser's [`sequences`](https://terser.org/docs/options/#compress-options) option.
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_span::SPAN;
+
+use super::Compressor;
+
+/// Whether the expressions of the statements before `stmt` can be joined into it.
+fn joins_preceding_expressions(stmt: &Statement<'_>) -> bool {
+    match stmt {
+        Statement::ExpressionStatement(_)
+        | Statement::IfStatement(_)
+        | Statement::ThrowStatement(_) => true,
+        Statement::ReturnStatement(return_stmt) => return_stmt.argument.is_some(),
+        _ => false,
+    }
+}
+
+impl<'a> Compressor<'a> {
+    /// `a(); b(); return c` -> `return a(), b(), c`
+    /// `a(); if (b) c()` -> `if (a(), b) c()`
+    /// Enabled by `compress.sequences`
+    pub(crate) fn join_sequences<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !self.options.sequences
+            || !stmts.windows(2).any(|window| {
+                matches!(window[0], Statement::ExpressionStatement(_))
+                    && joins_preceding_expressions(&window[1])
+            })
+        {
+            return;
+        }
+
+        let mut new_stmts = self.hir.new_vec_with_capacity(stmts.len());
+        let mut expressions = vec![];
+        // The span of the first joined expression statement, where comments before it are printed
+        let mut span = SPAN;
+        for mut stmt in stmts.drain(..) {
+            let joined = match &mut stmt {
+                Statement::ExpressionStatement(expr_stmt) => {
+                    if expressions.is_empty() {
+                        span = expr_stmt.span;
+                    }
+                    let expr = self.move_out_expression(&mut expr_stmt.expression);
+                    Self::push_expression(&mut expressions, expr);
+                    continue;
+                }
+                _ if expressions.is_empty() => None,
+                Statement::IfStatement(if_stmt) => Some(&mut if_stmt.test),
+                Statement::ThrowStatement(throw_stmt) => Some(&mut throw_stmt.argument),
+                Statement::ReturnStatement(return_stmt) => return_stmt.argument.as_mut(),
+                _ => None,
+            };
+            if let Some(joined) = joined {
+                let expr = self.move_out_expression(joined);
+                Self::push_expression(&mut expressions, expr);
+                *joined = self.create_sequence(&mut expressions);
+            } else if !expressions.is_empty() {
+                let expr = self.create_sequence(&mut expressions);
+                new_stmts.push(self.hir.expression_statement(span, expr));
+            }
+            new_stmts.push(stmt);
+        }
+        if !expressions.is_empty() {
+            let expr = self.create_sequence(&mut expressions);
+            new_stmts.push(self.hir.expression_statement(span, expr));
+        }
+        *stmts = new_stmts;
+    }
+
+    /// Adds the expression to the sequence, flattening `(a, b)`.
+    pub(super) fn push_expression(
+        expressions: &mut std::vec::Vec<Expression<'a>>,
+        expr: Expression<'a>,
+    ) {
+        if let Expression::SequenceExpression(sequence_expr) = expr {
+            expressions.extend(sequence_expr.unbox().expressions);
+        } else {
+            expressions.push(expr);
+        }
+    }
+
+    /// `a, b, c`, or `a` for a single expression. Drains the expressions.
+    pub(super) fn create_sequence(
+        &mut self,
+        expressions: &mut std::vec::Vec<Expression<'a>>,
+    ) -> Expression<'a> {
+        if expressions.len() == 1 {
+            return expressions.pop().unwrap();
+        }
+        let mut sequence = self.hir.new_vec_with_capacity(expressions.len());
+        sequence.extend(expressions.drain(..));
+        self.hir.sequence_expression(SPAN, sequence)
+    }
+}
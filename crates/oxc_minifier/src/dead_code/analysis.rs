@@ -0,0 +1,425 @@
+//! What dead code elimination removes, found with the control flow graph, the symbol table,
+//! liveness over the control flow graph, and the side effects and constant values of expressions
+
+use std::collections::{HashMap, HashSet};
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstKind, Visit};
+use oxc_semantic::{
+    Analysis, AstNode, AstNodeId, ConstantValue, DataflowResults, Direction, Reference, Semantic,
+    SymbolId,
+};
+use oxc_span::GetSpan;
+use oxc_syntax::operator::AssignmentOperator;
+
+use super::operator_address;
+use crate::CompressOptions;
+
+/// The nodes to remove or rewrite, keyed by their addresses as given by [`AstKind::address`],
+/// because the program can only be mutated once the semantic borrowing it is dropped
+#[derive(Debug, Default)]
+pub(super) struct DeadCode {
+    /// Statements which are never executed
+    pub unreachable: HashSet<usize>,
+    /// The values of the tests of `if` statements, loops and conditional expressions which are
+    /// constant and have no side effects
+    pub tests: HashMap<usize, bool>,
+    /// Variable declarators and function declarations which are never referenced
+    pub unused: HashSet<usize>,
+    /// Variable declarators whose initializers are overwritten before they are read
+    pub dead_stores: HashSet<usize>,
+    /// Expression statements which are calls annotated with `/* #__PURE__ */`, with whether
+    /// each argument has side effects, in the order [`pure_call_arguments`] visits them
+    pub pure_calls: HashMap<usize, Vec<bool>>,
+    /// Operator expressions and template literals whose value is a constant which is not longer
+    /// than the expression, and which have no side effects
+    pub constants: HashMap<usize, ConstantValue>,
+}
+
+impl DeadCode {
+    pub fn collect(semantic: &Semantic<'_>, options: CompressOptions) -> Self {
+        let mut dead_code = Self::default();
+        if options.dead_code {
+            dead_code.collect_unreachable_statements(semantic);
+            dead_code.collect_constant_tests(semantic);
+        }
+        if options.unused {
+            Unused::new(semantic, options).collect(&mut dead_code);
+        }
+        if options.side_effects {
+            dead_code.collect_pure_calls(semantic);
+        }
+        if options.evaluate {
+            dead_code.collect_constants(semantic);
+        }
+        dead_code
+    }
+
+    fn collect_constants(&mut self, semantic: &Semantic<'_>) {
+        let Some(AstKind::Program(program)) = semantic.nodes().iter().next().map(AstNode::kind)
+        else {
+            return;
+        };
+        Constants { semantic, constants: &mut self.constants }.visit_program(program);
+    }
+
+    fn collect_pure_calls(&mut self, semantic: &Semantic<'_>) {
+        for node in semantic.nodes().iter() {
+            let AstKind::ExpressionStatement(stmt) = node.kind() else { continue };
+            if !is_removable_pure_call(&stmt.expression) {
+                continue;
+            }
+            let mut side_effects = vec![];
+            pure_call_arguments(&stmt.expression, &mut |argument| {
+                let has_side_effects = !semantic.side_effects(argument).is_pure();
+                side_effects.push(has_side_effects);
+                has_side_effects
+            });
+            self.pure_calls.extend(node.kind().address().map(|address| (address, side_effects)));
+        }
+    }
+
+    fn collect_unreachable_statements(&mut self, semantic: &Semantic<'_>) {
+        let cfg = semantic.cfg();
+        for node in semantic.nodes().iter() {
+            if !cfg.is_reachable(node.id()) {
+                self.unreachable.extend(node.kind().address());
+            }
+        }
+    }
+
+    fn collect_constant_tests<'a>(&mut self, semantic: &Semantic<'a>) {
+        let constant_test = |test: &Expression<'a>| {
+            if semantic.side_effects(test).is_pure() {
+                semantic.evaluate_boolean(test)
+            } else {
+                None
+            }
+        };
+        for node in semantic.nodes().iter() {
+            let value = match node.kind() {
+                AstKind::IfStatement(stmt) => constant_test(&stmt.test),
+                AstKind::ConditionalExpression(expr) => constant_test(&expr.test),
+                AstKind::WhileStatement(stmt) => constant_test(&stmt.test).filter(|value| !value),
+                AstKind::ForStatement(stmt) => {
+                    // The bindings of `for (let i = 0; false;)` are scoped to the loop
+                    if matches!(&stmt.init, Some(ForStatementInit::VariableDeclaration(decl)) if decl.kind.is_lexical())
+                    {
+                        continue;
+                    }
+                    stmt.test.as_ref().and_then(constant_test).filter(|value| !value)
+                }
+                _ => continue,
+            };
+            if let (Some(value), Some(address)) = (value, node.kind().address()) {
+                self.tests.insert(address, value);
+            }
+        }
+    }
+}
+
+/// Finds the operator expressions and template literals whose value is a constant primitive
+/// which can be printed as a literal not longer than the expression
+struct Constants<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    constants: &'s mut HashMap<usize, ConstantValue>,
+}
+
+impl<'s, 'a> Visit<'a> for Constants<'s, 'a> {
+    fn visit_expression(&mut self, expr: &'a Expression<'a>) {
+        let Some(address) = operator_address(expr) else {
+            return self.visit_expression_match(expr);
+        };
+        let Some(value) = self.semantic.evaluate(expr) else {
+            return self.visit_expression_match(expr);
+        };
+        // Negative numbers and `undefined` are not literals
+        let len = match &value {
+            ConstantValue::Number(n) if n.is_finite() && n.is_sign_positive() => {
+                value.to_js_string().len()
+            }
+            ConstantValue::String(s) => s.len() + 2,
+            ConstantValue::Boolean(_) | ConstantValue::Null => value.to_js_string().len(),
+            _ => return self.visit_expression_match(expr),
+        };
+        if len <= expr.span().size() as usize && self.semantic.side_effects(expr).is_pure() {
+            self.constants.insert(address, value);
+        } else {
+            self.visit_expression_match(expr);
+        }
+    }
+}
+
+/// Finds the declarations inside functions which are never referenced, ignoring the references
+/// in unreachable code when it is removed, and the initializers which are never read. Top level
+/// declarations are globals and always kept.
+struct Unused<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    options: CompressOptions,
+    liveness: Liveness,
+    /// Functions containing `try` statements, where a statement which throws skips the
+    /// assignments after it
+    functions_with_try: HashSet<AstNodeId>,
+    /// The liveness of the variables of each function
+    results: HashMap<AstNodeId, Option<DataflowResults<HashSet<SymbolId>>>>,
+}
+
+impl<'s, 'a> Unused<'s, 'a> {
+    fn new(semantic: &'s Semantic<'a>, options: CompressOptions) -> Self {
+        let functions_with_try = semantic
+            .nodes()
+            .iter()
+            .filter(|node| matches!(node.kind(), AstKind::TryStatement(_)))
+            .map(|node| enclosing_function(semantic, node.id()))
+            .collect();
+        Self {
+            semantic,
+            options,
+            liveness: Liveness::new(semantic),
+            functions_with_try,
+            results: HashMap::new(),
+        }
+    }
+
+    fn collect(mut self, dead_code: &mut DeadCode) {
+        // `eval` can reference any variable
+        if self.semantic.scopes().root_unresolved_references().contains_key("eval") {
+            return;
+        }
+        let symbols = self.semantic.symbols();
+        let nodes = self.semantic.nodes();
+        for symbol_id in symbols.iter() {
+            let declaration_id = symbols.get_declaration(symbol_id);
+            let kind = nodes.kind(declaration_id);
+            let (statement_id, init) = match kind {
+                // `using` declarations dispose of their values
+                AstKind::VariableDeclarator(declarator)
+                    if matches!(declarator.id.kind, BindingPatternKind::BindingIdentifier(_))
+                        && matches!(
+                            declarator.kind,
+                            VariableDeclarationKind::Var
+                                | VariableDeclarationKind::Let
+                                | VariableDeclarationKind::Const
+                        ) =>
+                {
+                    let Some(statement_id) = nodes.parent_id(declaration_id) else { continue };
+                    (statement_id, declarator.init.as_ref())
+                }
+                AstKind::Function(func) if func.is_declaration() => (declaration_id, None),
+                _ => continue,
+            };
+            // Only declarations in statement lists, and not `for (var i = 0;;)`
+            if self.semantic.cfg().statement_block(statement_id).is_none() {
+                continue;
+            }
+            let function_id = enclosing_function(self.semantic, declaration_id);
+            if matches!(nodes.kind(function_id), AstKind::Program(_)) {
+                continue;
+            }
+            if init.is_some_and(|init| !self.semantic.side_effects(init).is_pure()) {
+                continue;
+            }
+            let Some(address) = kind.address() else { continue };
+
+            let references = symbols
+                .get_resolved_reference_ids(symbol_id)
+                .iter()
+                .map(|&reference_id| symbols.get_reference(reference_id))
+                .filter(|reference| !self.options.dead_code || self.is_reachable(reference))
+                .collect::<Vec<_>>();
+            if references.is_empty() {
+                dead_code.unused.insert(address);
+            } else if let AstKind::VariableDeclarator(declarator) = kind {
+                if declarator.init.is_some()
+                    && declarator.kind != VariableDeclarationKind::Const
+                    && self.is_dead_store(symbol_id, statement_id, function_id, &references)
+                {
+                    dead_code.dead_stores.insert(address);
+                }
+            }
+        }
+    }
+
+    /// Whether the reference is in a statement which can be executed
+    fn is_reachable(&self, reference: &Reference) -> bool {
+        let cfg = self.semantic.cfg();
+        self.semantic
+            .nodes()
+            .ancestors(reference.node_id())
+            .find_map(|node_id| cfg.statement_block(node_id))
+            .map_or(true, |block_id| cfg.basic_block(block_id).is_reachable())
+    }
+
+    /// Whether the value assigned by the declaration statement to the variable is never read,
+    /// because every path after it writes the variable before reading it
+    fn is_dead_store(
+        &mut self,
+        symbol_id: SymbolId,
+        statement_id: AstNodeId,
+        function_id: AstNodeId,
+        references: &[&Reference],
+    ) -> bool {
+        let nodes = self.semantic.nodes();
+        // Variables of closures can be read whenever the closures are called, and `var a = 1,
+        // b = a` reads the variable in its own declaration
+        let is_local = references.iter().all(|reference| {
+            let node_id = reference.node_id();
+            enclosing_function(self.semantic, node_id) == function_id
+                && !nodes.ancestors(node_id).any(|id| id == statement_id)
+        });
+        if !is_local || self.functions_with_try.contains(&function_id) {
+            return false;
+        }
+
+        let cfg = self.semantic.cfg();
+        let results = self
+            .results
+            .entry(function_id)
+            .or_insert_with(|| cfg.analyze(function_id, &self.liveness));
+        let Some(results) = results else { return false };
+        let Some(block_id) = cfg.statement_block(statement_id) else { return false };
+        let Some(live) = results.exit(block_id) else { return false };
+        let mut live = live.clone();
+        for &id in cfg.basic_block(block_id).statements().iter().rev() {
+            if id == statement_id {
+                break;
+            }
+            self.liveness.transfer_statement(&mut live, id);
+        }
+        !live.contains(&symbol_id)
+    }
+}
+
+/// The variables which may be read before they are written again, computed backwards from the
+/// exit of a function.
+///
+/// The reads of a compound statement include the reads of the statements it contains, which
+/// makes more variables live than needed, but never fewer.
+struct Liveness {
+    /// The variables read by each statement
+    uses: HashMap<AstNodeId, Vec<SymbolId>>,
+    /// The variables which each statement writes before it can throw, by `a = 1` or `var a = 1`
+    defs: HashMap<AstNodeId, Vec<SymbolId>>,
+}
+
+impl Liveness {
+    fn new(semantic: &Semantic<'_>) -> Self {
+        let nodes = semantic.nodes();
+        let symbols = semantic.symbols();
+        let cfg = semantic.cfg();
+        let mut uses: HashMap<AstNodeId, Vec<SymbolId>> = HashMap::new();
+        let mut defs: HashMap<AstNodeId, Vec<SymbolId>> = HashMap::new();
+        let mut identifiers = HashMap::new();
+
+        for symbol_id in symbols.iter() {
+            for &reference_id in symbols.get_resolved_reference_ids(symbol_id) {
+                let reference = symbols.get_reference(reference_id);
+                identifiers
+                    .extend(nodes.kind(reference.node_id()).address().map(|a| (a, symbol_id)));
+                if !reference.is_read() {
+                    continue;
+                }
+                for node_id in nodes.ancestors(reference.node_id()) {
+                    if nodes.kind(node_id).is_function_like() {
+                        break;
+                    }
+                    if cfg.statement_block(node_id).is_some() {
+                        uses.entry(node_id).or_default().push(symbol_id);
+                    }
+                }
+            }
+            let declaration_id = symbols.get_declaration(symbol_id);
+            if let AstKind::VariableDeclarator(declarator) = nodes.kind(declaration_id) {
+                if declarator
+                    .init
+                    .as_ref()
+                    .is_some_and(|init| semantic.side_effects(init).is_pure())
+                {
+                    if let Some(statement_id) = nodes.parent_id(declaration_id) {
+                        defs.entry(statement_id).or_default().push(symbol_id);
+                    }
+                }
+            }
+        }
+
+        for node in nodes.iter() {
+            let AstKind::ExpressionStatement(stmt) = node.kind() else { continue };
+            let Expression::AssignmentExpression(expr) = &stmt.expression else { continue };
+            let AssignmentTarget::SimpleAssignmentTarget(
+                SimpleAssignmentTarget::AssignmentTargetIdentifier(ident),
+            ) = &expr.left
+            else {
+                continue;
+            };
+            if expr.operator != AssignmentOperator::Assign
+                || !semantic.side_effects(&expr.right).is_pure()
+            {
+                continue;
+            }
+            let address = AstKind::IdentifierReference(ident).address();
+            if let Some(&symbol_id) = address.and_then(|address| identifiers.get(&address)) {
+                defs.entry(node.id()).or_default().push(symbol_id);
+            }
+        }
+
+        Self { uses, defs }
+    }
+}
+
+impl Analysis for Liveness {
+    type Domain = HashSet<SymbolId>;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn boundary(&self) -> Self::Domain {
+        HashSet::new()
+    }
+
+    fn transfer_statement(&self, state: &mut Self::Domain, statement_id: AstNodeId) {
+        for symbol_id in self.defs.get(&statement_id).into_iter().flatten() {
+            state.remove(symbol_id);
+        }
+        state.extend(self.uses.get(&statement_id).into_iter().flatten());
+    }
+}
+
+/// Whether the expression is a call annotated with `/* #__PURE__ */` which can be replaced by its
+/// arguments, i.e. without spread arguments, which run iterators
+pub(super) fn is_removable_pure_call(expr: &Expression<'_>) -> bool {
+    let (pure, arguments) = match expr {
+        Expression::CallExpression(call_expr) => (call_expr.pure, &call_expr.arguments),
+        Expression::NewExpression(new_expr) => (new_expr.pure, &new_expr.arguments),
+        _ => return false,
+    };
+    pure && arguments.iter().all(|argument| matches!(argument, Argument::Expression(_)))
+}
+
+/// Visits the arguments of a removable pure call, and the arguments of the removable pure calls
+/// among them for which `visit` returns true, i.e. which have side effects
+fn pure_call_arguments<'a>(expr: &Expression<'a>, visit: &mut impl FnMut(&Expression<'a>) -> bool) {
+    let arguments = match expr {
+        Expression::CallExpression(call_expr) => &call_expr.arguments,
+        Expression::NewExpression(new_expr) => &new_expr.arguments,
+        _ => return,
+    };
+    for argument in arguments {
+        if let Argument::Expression(argument) = argument {
+            if visit(argument) && is_removable_pure_call(argument) {
+                pure_call_arguments(argument, visit);
+            }
+        }
+    }
+}
+
+/// The `Program`, `Function` or `ArrowExpression` whose control flow graph contains the node,
+/// which is not the node itself for function declarations
+fn enclosing_function(semantic: &Semantic<'_>, node_id: AstNodeId) -> AstNodeId {
+    let nodes = semantic.nodes();
+    nodes
+        .ancestors(node_id)
+        .skip(1)
+        .find(|&id| {
+            matches!(nodes.kind(id), AstKind::Program(_)) || nodes.kind(id).is_function_like()
+        })
+        .unwrap_or(node_id)
+}
@@ -0,0 +1,444 @@
+//! Dead Code Elimination
+//!
+//! Removes unreachable statements, the branches of `if` statements, loops and conditional
+//! expressions which are never taken, and the unused declarations of functions, before the
+//! program is lowered and compressed.
+//!
+//! <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeRemoveDeadCode.java>
+
+mod analysis;
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, VisitMut};
+use oxc_semantic::{ConstantValue, SemanticBuilder};
+use oxc_span::SPAN;
+
+use self::analysis::{is_removable_pure_call, DeadCode};
+use crate::CompressOptions;
+
+/// Removes the dead code found by the analyses of [`oxc_semantic`].
+///
+/// Unreachable statements are found by the control flow graph, unused declarations by the
+/// symbol table and liveness, and dead branches by constant evaluation, keeping the tests which
+/// have side effects. Unused calls annotated with `/* #__PURE__ */` are replaced by their
+/// arguments which have side effects, and constant expressions by their values.
+///
+/// Enabled by `compress.dead_code`, `compress.unused`, `compress.side_effects` and
+/// `compress.evaluate`
+pub struct DeadCodeElimination<'a> {
+    ast: AstBuilder<'a>,
+    source_text: &'a str,
+    options: CompressOptions,
+    dead_code: DeadCode,
+}
+
+impl<'a> DeadCodeElimination<'a> {
+    pub fn new(allocator: &'a Allocator, source_text: &'a str, options: CompressOptions) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            source_text,
+            options,
+            dead_code: DeadCode::default(),
+        }
+    }
+
+    pub fn build<'b>(mut self, program: &'b mut Program<'a>) {
+        let options = self.options;
+        if !options.dead_code && !options.unused && !options.side_effects && !options.evaluate {
+            return;
+        }
+        // SAFETY: The semantic is dropped before the program is mutated, the facts collected
+        // from it only keep the addresses of the nodes.
+        let borrowed = unsafe { &*(program as *const Program<'a>) };
+        let semantic = SemanticBuilder::new(self.source_text, program.source_type).build(borrowed);
+        self.dead_code = DeadCode::collect(&semantic.semantic, self.options);
+        drop(semantic);
+        self.visit_program(program);
+    }
+
+    /// Removes the statements which are never executed, e.g. after a `return`, `throw`, `break`
+    /// or `continue`.
+    ///
+    /// Function declarations are kept, as they are hoisted. The other names declared by the
+    /// removed statements are still declared without their initializers: `var` bindings are
+    /// hoisted, and `let`, `const` and `class` bindings can be referenced by the functions.
+    ///
+    /// `return; var a = 1; let b = 2; foo()` -> `return; var a; let b`
+    fn remove_unreachable_statements<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if !stmts.iter().any(|stmt| self.is_unreachable(stmt)) {
+            return;
+        }
+        let mut kept = self.ast.new_vec_with_capacity(stmts.len());
+        for mut stmt in stmts.drain(..) {
+            if !self.is_unreachable(&stmt) {
+                kept.push(stmt);
+                continue;
+            }
+            let (kind, names) = match &stmt {
+                Statement::Declaration(Declaration::FunctionDeclaration(_))
+                | Statement::ModuleDeclaration(_) => {
+                    kept.push(stmt);
+                    continue;
+                }
+                Statement::Declaration(Declaration::VariableDeclaration(decl))
+                    if decl.kind.is_lexical() =>
+                {
+                    let mut names = vec![];
+                    decl.bound_names(&mut |ident| names.push(ident.clone()));
+                    (VariableDeclarationKind::Let, names)
+                }
+                Statement::Declaration(Declaration::ClassDeclaration(class)) => {
+                    (VariableDeclarationKind::Let, class.id.iter().cloned().collect())
+                }
+                _ => {
+                    let mut vars = HoistedVars::new(&self.dead_code);
+                    vars.visit_statement(&mut stmt);
+                    (VariableDeclarationKind::Var, vars.names)
+                }
+            };
+            kept.extend(self.declare(kind, names));
+        }
+        *stmts = kept;
+    }
+
+    fn is_unreachable(&self, stmt: &Statement<'a>) -> bool {
+        statement_address(stmt).is_some_and(|address| self.dead_code.unreachable.contains(&address))
+    }
+
+    /// Replaces a statement whose test has a constant value by the branch which is taken,
+    /// keeping the `var` bindings of the branch which is not. Returns whether it was replaced.
+    ///
+    /// `if (!0) a(); else b()` -> `a()`
+    /// `if (!1) { var x = 1 }` -> `var x`
+    /// `for (a(); !1;) b()` -> `a()`
+    fn remove_dead_branch<'b>(&mut self, stmt: &'b mut Statement<'a>) -> bool {
+        let Some(&value) = statement_address(stmt).and_then(|a| self.dead_code.tests.get(&a))
+        else {
+            return false;
+        };
+        let mut vars = HoistedVars::new(&self.dead_code);
+        let taken = match stmt {
+            Statement::IfStatement(if_stmt) => {
+                let (taken, removed) = if value {
+                    (Some(self.take_statement(&mut if_stmt.consequent)), if_stmt.alternate.take())
+                } else {
+                    (if_stmt.alternate.take(), Some(self.take_statement(&mut if_stmt.consequent)))
+                };
+                if let Some(mut removed) = removed {
+                    vars.visit_statement(&mut removed);
+                }
+                taken
+            }
+            Statement::WhileStatement(while_stmt) => {
+                vars.visit_statement(&mut while_stmt.body);
+                None
+            }
+            Statement::ForStatement(for_stmt) => {
+                vars.visit_statement(&mut for_stmt.body);
+                for_stmt.init.take().map(|init| match init {
+                    ForStatementInit::VariableDeclaration(decl) => {
+                        Statement::Declaration(Declaration::VariableDeclaration(decl))
+                    }
+                    ForStatementInit::Expression(expr) => self.ast.expression_statement(SPAN, expr),
+                })
+            }
+            _ => return false,
+        };
+
+        *stmt = match (taken, self.declare(VariableDeclarationKind::Var, vars.names)) {
+            (Some(stmt), None) | (None, Some(stmt)) => stmt,
+            (Some(stmt), Some(declaration)) => {
+                let mut body = self.ast.new_vec_with_capacity(2);
+                body.push(stmt);
+                body.push(declaration);
+                self.ast.block_statement(self.ast.block(SPAN, body))
+            }
+            (None, None) => self.ast.block_statement(self.ast.block(SPAN, self.ast.new_vec())),
+        };
+        true
+    }
+
+    /// Replaces a conditional expression whose test has a constant value by the branch which is
+    /// taken. Returns whether it was replaced.
+    ///
+    /// `!0 ? a : b` -> `a`
+    fn remove_dead_conditional<'b>(&mut self, expr: &'b mut Expression<'a>) -> bool {
+        let Expression::ConditionalExpression(conditional_expr) = expr else { return false };
+        let Some(&value) = self.dead_code.tests.get(&address(&**conditional_expr)) else {
+            return false;
+        };
+        let taken =
+            if value { &mut conditional_expr.consequent } else { &mut conditional_expr.alternate };
+        *expr = std::mem::replace(taken, self.ast.null_literal_expression());
+        true
+    }
+
+    /// Replaces an expression whose value is a constant by the value. Returns whether it was
+    /// replaced.
+    ///
+    /// `1 + 2` -> `3`
+    /// `"a" + "b"` -> `"ab"`
+    fn replace_constant<'b>(&mut self, expr: &'b mut Expression<'a>) -> bool {
+        let Some(value) = operator_address(expr).and_then(|a| self.dead_code.constants.remove(&a))
+        else {
+            return false;
+        };
+        *expr = match value {
+            ConstantValue::Number(n) => self.ast.number_literal_expression(n),
+            ConstantValue::String(s) => self.ast.string_literal_expression(&s),
+            ConstantValue::Boolean(b) => self.ast.boolean_literal_expression(b),
+            ConstantValue::Null => self.ast.null_literal_expression(),
+            ConstantValue::BigInt(_) | ConstantValue::Undefined => return false,
+        };
+        true
+    }
+
+    /// Removes the declarations which are never referenced and whose initializers have no side
+    /// effects, and the initializers which are overwritten before they are read.
+    ///
+    /// `function f() { var a = 1, b = g(); function h() {} return b }`
+    /// -> `function f() { var b = g(); return b }`
+    /// `function f() { var a = 1; a = 2; return a }` -> `function f() { var a; a = 2; return a }`
+    fn remove_unused_declarations<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        let dead_code = &self.dead_code;
+        for stmt in stmts.iter_mut() {
+            let Statement::Declaration(Declaration::VariableDeclaration(decl)) = stmt else {
+                continue;
+            };
+            // The addresses are read before the declarators are moved
+            let mut unused = vec![];
+            for declarator in decl.declarations.iter_mut() {
+                unused.push(dead_code.unused.contains(&address(declarator)));
+                if dead_code.dead_stores.contains(&address(declarator)) {
+                    declarator.init = None;
+                }
+            }
+            let mut unused = unused.into_iter();
+            decl.declarations.retain(|_| !unused.next().unwrap_or(false));
+        }
+        stmts.retain(|stmt| match stmt {
+            Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                !decl.declarations.is_empty()
+            }
+            Statement::Declaration(Declaration::FunctionDeclaration(func)) => {
+                !dead_code.unused.contains(&address(&**func))
+            }
+            _ => true,
+        });
+    }
+
+    /// Removes the calls annotated with `/* #__PURE__ */` whose values are unused, keeping their
+    /// arguments which have side effects.
+    ///
+    /// `/* #__PURE__ */ f(a(), 1)` -> `a()`
+    fn remove_unused_pure_calls<'b>(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        let mut i = 0;
+        while i < stmts.len() {
+            let Statement::ExpressionStatement(expr_stmt) = &mut stmts[i] else {
+                i += 1;
+                continue;
+            };
+            let Some(side_effects) = self.dead_code.pure_calls.remove(&address(&**expr_stmt))
+            else {
+                i += 1;
+                continue;
+            };
+            let expr =
+                std::mem::replace(&mut expr_stmt.expression, self.ast.null_literal_expression());
+            let mut expressions = self.ast.new_vec();
+            Self::unused_side_effects(expr, &mut side_effects.into_iter(), &mut expressions);
+            if expressions.is_empty() {
+                stmts.remove(i);
+            } else {
+                expr_stmt.expression = if expressions.len() == 1 {
+                    expressions.pop().unwrap()
+                } else {
+                    self.ast.sequence_expression(SPAN, expressions)
+                };
+                i += 1;
+            }
+        }
+    }
+
+    /// The parts of an expression whose value is unused which have side effects, given whether
+    /// the arguments of the pure calls have side effects
+    fn unused_side_effects(
+        expr: Expression<'a>,
+        side_effects: &mut impl Iterator<Item = bool>,
+        expressions: &mut Vec<'a, Expression<'a>>,
+    ) {
+        if !is_removable_pure_call(&expr) {
+            match expr {
+                Expression::SequenceExpression(sequence_expr) => {
+                    expressions.extend(sequence_expr.unbox().expressions);
+                }
+                expr => expressions.push(expr),
+            }
+            return;
+        }
+        let arguments = match expr {
+            Expression::CallExpression(call_expr) => call_expr.unbox().arguments,
+            Expression::NewExpression(new_expr) => new_expr.unbox().arguments,
+            _ => unreachable!(),
+        };
+        for argument in arguments {
+            if let Argument::Expression(argument) = argument {
+                if side_effects.next().unwrap_or(true) {
+                    Self::unused_side_effects(argument, side_effects, expressions);
+                }
+            }
+        }
+    }
+
+    fn take_statement(&self, stmt: &mut Statement<'a>) -> Statement<'a> {
+        std::mem::replace(stmt, self.ast.empty_statement(SPAN))
+    }
+
+    /// `var a, b` for the given bindings
+    fn declare(
+        &self,
+        kind: VariableDeclarationKind,
+        idents: std::vec::Vec<BindingIdentifier>,
+    ) -> Option<Statement<'a>> {
+        if idents.is_empty() {
+            return None;
+        }
+        let mut declarations = self.ast.new_vec_with_capacity(idents.len());
+        for ident in idents {
+            let id = self.ast.binding_pattern(self.ast.binding_identifier(ident), None, false);
+            declarations.push(self.ast.variable_declarator(SPAN, kind, id, None, false));
+        }
+        let decl = self.ast.variable_declaration(SPAN, kind, declarations, Modifiers::empty());
+        Some(Statement::Declaration(Declaration::VariableDeclaration(decl)))
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for DeadCodeElimination<'a> {
+    fn visit_statements(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        if self.options.dead_code {
+            self.remove_unreachable_statements(stmts);
+        }
+        if self.options.unused {
+            self.remove_unused_declarations(stmts);
+        }
+        if self.options.side_effects {
+            self.remove_unused_pure_calls(stmts);
+        }
+        for stmt in stmts.iter_mut() {
+            self.visit_statement(stmt);
+        }
+        if self.options.dead_code {
+            // Branches which are never taken become empty blocks
+            stmts.retain(
+                |stmt| !matches!(stmt, Statement::BlockStatement(block) if block.body.is_empty()),
+            );
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &'b mut Statement<'a>) {
+        while self.remove_dead_branch(stmt) {}
+        self.visit_statement_match(stmt);
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        while self.remove_dead_conditional(expr) {}
+        if self.options.evaluate && self.replace_constant(expr) {
+            return;
+        }
+        self.visit_expression_match(expr);
+    }
+}
+
+/// The `var` bindings of removed code, which are hoisted out of it and must still be declared,
+/// unless they are unused.
+///
+/// Function declarations nested in blocks are `var` bindings as well (Annex B).
+struct HoistedVars<'d> {
+    names: std::vec::Vec<BindingIdentifier>,
+    dead_code: &'d DeadCode,
+}
+
+impl<'d> HoistedVars<'d> {
+    fn new(dead_code: &'d DeadCode) -> Self {
+        Self { names: vec![], dead_code }
+    }
+
+    fn add(&mut self, ident: &BindingIdentifier) {
+        if !self.names.iter().any(|hoisted| hoisted.name == ident.name) {
+            self.names.push(ident.clone());
+        }
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for HoistedVars<'_> {
+    fn visit_variable_declaration(&mut self, decl: &'b mut VariableDeclaration<'a>) {
+        if decl.kind == VariableDeclarationKind::Var {
+            for declarator in &decl.declarations {
+                if !self.dead_code.unused.contains(&address(declarator)) {
+                    declarator.id.bound_names(&mut |ident| self.add(ident));
+                }
+            }
+        }
+    }
+
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        if func.is_declaration() {
+            if let Some(ident) = &func.id {
+                self.add(ident);
+            }
+        }
+    }
+
+    fn visit_arrow_expression(&mut self, _expr: &'b mut ArrowExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &'b mut Class<'a>) {}
+}
+
+/// The address of a node, the same as [`oxc_ast::AstKind::address`]
+fn address<T>(node: &T) -> usize {
+    node as *const T as usize
+}
+
+/// The address of the node of an operator expression or a template literal, which may be
+/// replaced by its constant value
+fn operator_address(expr: &Expression<'_>) -> Option<usize> {
+    let address = match expr {
+        Expression::BinaryExpression(expr) => address(&**expr),
+        Expression::LogicalExpression(expr) => address(&**expr),
+        Expression::UnaryExpression(expr) => address(&**expr),
+        Expression::TemplateLiteral(lit) => address(&**lit),
+        _ => return None,
+    };
+    Some(address)
+}
+
+/// The address of the node of a statement, which is the function of a function declaration
+fn statement_address(stmt: &Statement<'_>) -> Option<usize> {
+    let address = match stmt {
+        Statement::BlockStatement(stmt) => address(&**stmt),
+        Statement::BreakStatement(stmt) => address(&**stmt),
+        Statement::ContinueStatement(stmt) => address(&**stmt),
+        Statement::DebuggerStatement(stmt) => address(&**stmt),
+        Statement::DoWhileStatement(stmt) => address(&**stmt),
+        Statement::EmptyStatement(stmt) => address(&**stmt),
+        Statement::ExpressionStatement(stmt) => address(&**stmt),
+        Statement::ForInStatement(stmt) => address(&**stmt),
+        Statement::ForOfStatement(stmt) => address(&**stmt),
+        Statement::ForStatement(stmt) => address(&**stmt),
+        Statement::IfStatement(stmt) => address(&**stmt),
+        Statement::LabeledStatement(stmt) => address(&**stmt),
+        Statement::ReturnStatement(stmt) => address(&**stmt),
+        Statement::SwitchStatement(stmt) => address(&**stmt),
+        Statement::ThrowStatement(stmt) => address(&**stmt),
+        Statement::TryStatement(stmt) => address(&**stmt),
+        Statement::WhileStatement(stmt) => address(&**stmt),
+        Statement::WithStatement(stmt) => address(&**stmt),
+        Statement::ModuleDeclaration(decl) => address(&**decl),
+        Statement::Declaration(Declaration::VariableDeclaration(decl)) => address(&**decl),
+        Statement::Declaration(Declaration::FunctionDeclaration(func)) => address(&**func),
+        Statement::Declaration(Declaration::ClassDeclaration(class)) => address(&**class),
+        _ => return None,
+    };
+    Some(address)
+}
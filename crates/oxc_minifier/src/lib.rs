@@ -1,30 +1,64 @@
 //! ECMAScript Minifier
 
 mod compressor;
+mod dead_code;
 mod mangler;
 mod printer;
 
 use oxc_allocator::Allocator;
+use oxc_ast::{CommentKind, Trivias};
 use oxc_ast_lower::AstLower;
 use oxc_parser::Parser;
-use oxc_span::SourceType;
+use oxc_span::{SourceType, Span};
 
 pub use crate::{
     compressor::{CompressOptions, Compressor},
-    mangler::ManglerBuilder,
+    dead_code::DeadCodeElimination,
+    mangler::{ManglerBuilder, ManglerOptions, NameCache, PropertyMangler, PropertyManglerOptions},
     printer::{Printer, PrinterOptions},
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct MinifierOptions {
     pub mangle: bool,
+    pub mangler: ManglerOptions,
+    /// Mangle the names of properties, off by default
+    pub mangle_props: Option<PropertyManglerOptions>,
     pub compress: CompressOptions,
     pub print: PrinterOptions,
+    pub license_comments: LicenseComments,
+}
+
+/// What to do with license comments, i.e. `/*! ... */` and comments containing `@license` or
+/// `@preserve`. Other comments are always removed.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum LicenseComments {
+    /// Remove them like other comments
+    None,
+    /// Keep them in the code, each on its own line before the statement following it
+    #[default]
+    Inline,
+    /// Remove them from the code and return them in [`MinifierReturn::license_comments`],
+    /// for writing to a separate `.LICENSE.txt` file
+    Extract,
+}
+
+pub struct MinifierReturn {
+    pub source_text: String,
+    /// The license comments removed from the code by [`LicenseComments::Extract`]
+    pub license_comments: Vec<String>,
 }
 
 impl Default for MinifierOptions {
     fn default() -> Self {
-        Self { mangle: true, compress: CompressOptions::default(), print: PrinterOptions }
+        Self {
+            mangle: true,
+            mangler: ManglerOptions::default(),
+            mangle_props: None,
+            compress: CompressOptions::default(),
+            print: PrinterOptions,
+            license_comments: LicenseComments::default(),
+        }
     }
 }
 
@@ -40,17 +74,72 @@ impl<'a> Minifier<'a> {
     }
 
     pub fn build(self) -> String {
+        self.build_with_name_cache(&mut NameCache::default())
+    }
+
+    /// Minifies with the property names of the cache, and adds the newly mangled ones to it.
+    pub fn build_with_name_cache(self, name_cache: &mut NameCache) -> String {
+        self.minify(name_cache).source_text
+    }
+
+    /// Same as [`Minifier::build_with_name_cache`], returning the extracted license comments
+    /// as well.
+    pub fn minify(self, name_cache: &mut NameCache) -> MinifierReturn {
         let allocator = Allocator::default();
         let ret = Parser::new(&allocator, self.source_text, self.source_type).parse();
-        let ret = AstLower::new(&allocator, self.source_text, self.source_type).build(&ret.program);
+        let license_comments = match self.options.license_comments {
+            LicenseComments::None => vec![],
+            LicenseComments::Inline | LicenseComments::Extract => {
+                collect_license_comments(self.source_text, &ret.trivias)
+            }
+        };
+        let program = allocator.alloc(ret.program);
+        DeadCodeElimination::new(&allocator, self.source_text, self.options.compress)
+            .build(program);
+        let ret = AstLower::new(&allocator, self.source_text, self.source_type).build(program);
         let program = allocator.alloc(ret.program);
         let semantic = ret.semantic;
+        let parenthesized = ret.parenthesized;
         let _semantic = Compressor::new(&allocator, semantic, self.options.compress).build(program);
+        if let Some(options) = self.options.mangle_props {
+            PropertyMangler::new(options).build(program, name_cache);
+        }
         let mut printer = Printer::new(self.source_text.len(), self.options.print);
+        printer.with_parenthesized(parenthesized);
         if self.options.mangle {
-            let mangler = ManglerBuilder::new(self.source_text, self.source_type).build(program);
+            let mangler = ManglerBuilder::new(self.source_text, self.source_type)
+                .with_options(self.options.mangler)
+                .build(program);
             printer.with_mangler(mangler);
         }
-        printer.build(program)
+        let license_comments = if self.options.license_comments == LicenseComments::Inline {
+            printer.with_comments(license_comments);
+            vec![]
+        } else {
+            license_comments.into_iter().map(|(_, text)| text).collect()
+        };
+        MinifierReturn { source_text: printer.build(program), license_comments }
     }
 }
+
+/// The license comments of the source text, as their start and their text including `//`, `/*`
+/// and `*/`
+fn collect_license_comments(source_text: &str, trivias: &Trivias) -> Vec<(u32, String)> {
+    trivias
+        .comments()
+        .iter()
+        .filter_map(|(start, comment)| {
+            let text = Span::new(*start, comment.end()).source_text(source_text);
+            let is_license = (comment.is_multi_line() && text.starts_with('!'))
+                || text.contains("@license")
+                || text.contains("@preserve");
+            is_license.then(|| {
+                let text = match comment.kind() {
+                    CommentKind::SingleLine => format!("//{}", text.trim_end()),
+                    CommentKind::MultiLine => format!("/*{text}*/"),
+                };
+                (*start, text)
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,661 @@
+//! The properties of the built-in objects of JavaScript and of the DOM, which the property
+//! mangler keeps unless `builtins` is set, like terser's `domprops`
+
+use phf::{phf_set, Set};
+
+pub(super) const BUILTINS: Set<&'static str> = phf_set! {
+    "BYTES_PER_ELEMENT",
+    "E",
+    "EPSILON",
+    "LN10",
+    "LN2",
+    "LOG10E",
+    "LOG2E",
+    "MAX_SAFE_INTEGER",
+    "MAX_VALUE",
+    "MIN_SAFE_INTEGER",
+    "MIN_VALUE",
+    "NEGATIVE_INFINITY",
+    "NaN",
+    "PI",
+    "POSITIVE_INFINITY",
+    "SQRT1_2",
+    "SQRT2",
+    "UTC",
+    "__defineGetter__",
+    "__defineSetter__",
+    "__lookupGetter__",
+    "__lookupSetter__",
+    "__proto__",
+    "abort",
+    "aborted",
+    "abs",
+    "accept",
+    "acos",
+    "acosh",
+    "action",
+    "activeElement",
+    "add",
+    "addEventListener",
+    "after",
+    "alert",
+    "all",
+    "allSettled",
+    "alt",
+    "altKey",
+    "anchor",
+    "animate",
+    "any",
+    "append",
+    "appendChild",
+    "apply",
+    "arguments",
+    "arrayBuffer",
+    "asIntN",
+    "asUintN",
+    "asin",
+    "asinh",
+    "assert",
+    "assign",
+    "asyncIterator",
+    "at",
+    "atan",
+    "atan2",
+    "atanh",
+    "attachShadow",
+    "attributes",
+    "autofocus",
+    "back",
+    "background",
+    "backgroundColor",
+    "before",
+    "big",
+    "bind",
+    "blink",
+    "blob",
+    "blur",
+    "body",
+    "bodyUsed",
+    "bold",
+    "border",
+    "bottom",
+    "bubbles",
+    "buffer",
+    "button",
+    "buttons",
+    "byteLength",
+    "byteOffset",
+    "call",
+    "caller",
+    "cancelAnimationFrame",
+    "cancelable",
+    "captureStackTrace",
+    "catch",
+    "cause",
+    "cbrt",
+    "ceil",
+    "changedTouches",
+    "charAt",
+    "charCode",
+    "charCodeAt",
+    "checked",
+    "childNodes",
+    "children",
+    "classList",
+    "className",
+    "clear",
+    "clearInterval",
+    "clearTimeout",
+    "click",
+    "clientHeight",
+    "clientLeft",
+    "clientTop",
+    "clientWidth",
+    "clientX",
+    "clientY",
+    "clipboard",
+    "clone",
+    "cloneNode",
+    "close",
+    "closest",
+    "clz32",
+    "code",
+    "codePointAt",
+    "color",
+    "compare",
+    "composedPath",
+    "concat",
+    "confirm",
+    "console",
+    "construct",
+    "constructor",
+    "contains",
+    "contentEditable",
+    "cookie",
+    "copyWithin",
+    "cos",
+    "cosh",
+    "count",
+    "countReset",
+    "create",
+    "createComment",
+    "createDocumentFragment",
+    "createElement",
+    "createElementNS",
+    "createEvent",
+    "createRange",
+    "createTextNode",
+    "crypto",
+    "cssText",
+    "ctrlKey",
+    "currentTarget",
+    "cursor",
+    "data",
+    "dataset",
+    "debug",
+    "defaultPrevented",
+    "defineProperties",
+    "defineProperty",
+    "delete",
+    "deleteProperty",
+    "deltaMode",
+    "deltaX",
+    "deltaY",
+    "deltaZ",
+    "deref",
+    "description",
+    "detail",
+    "dir",
+    "dirxml",
+    "disabled",
+    "dispatchEvent",
+    "display",
+    "document",
+    "documentElement",
+    "domain",
+    "done",
+    "dotAll",
+    "draggable",
+    "elementFromPoint",
+    "endsWith",
+    "entries",
+    "error",
+    "errors",
+    "event",
+    "eventPhase",
+    "every",
+    "exec",
+    "exp",
+    "expm1",
+    "fetch",
+    "files",
+    "fill",
+    "filter",
+    "finally",
+    "find",
+    "findIndex",
+    "findLast",
+    "findLastIndex",
+    "firstChild",
+    "firstElementChild",
+    "fixed",
+    "flags",
+    "flat",
+    "flatMap",
+    "floor",
+    "focus",
+    "font",
+    "fontSize",
+    "fontWeight",
+    "fontcolor",
+    "fontsize",
+    "for",
+    "forEach",
+    "form",
+    "formData",
+    "format",
+    "formatToParts",
+    "forms",
+    "forward",
+    "freeze",
+    "from",
+    "fromCharCode",
+    "fromCodePoint",
+    "fromEntries",
+    "fround",
+    "get",
+    "getAllResponseHeaders",
+    "getAttribute",
+    "getAttributeNames",
+    "getBigInt64",
+    "getBigUint64",
+    "getBoundingClientRect",
+    "getClientRects",
+    "getDate",
+    "getDay",
+    "getElementById",
+    "getElementsByClassName",
+    "getElementsByName",
+    "getElementsByTagName",
+    "getFloat32",
+    "getFloat64",
+    "getFullYear",
+    "getHours",
+    "getInt16",
+    "getInt32",
+    "getInt8",
+    "getItem",
+    "getMilliseconds",
+    "getMinutes",
+    "getMonth",
+    "getOwnPropertyDescriptor",
+    "getOwnPropertyDescriptors",
+    "getOwnPropertyNames",
+    "getOwnPropertySymbols",
+    "getPropertyValue",
+    "getPrototypeOf",
+    "getResponseHeader",
+    "getSeconds",
+    "getTime",
+    "getTimezoneOffset",
+    "getUTCDate",
+    "getUTCDay",
+    "getUTCFullYear",
+    "getUTCHours",
+    "getUTCMilliseconds",
+    "getUTCMinutes",
+    "getUTCMonth",
+    "getUTCSeconds",
+    "getUint16",
+    "getUint32",
+    "getUint8",
+    "getYear",
+    "global",
+    "globalThis",
+    "go",
+    "group",
+    "groupBy",
+    "groupCollapsed",
+    "groupEnd",
+    "groups",
+    "has",
+    "hasAttribute",
+    "hasChildNodes",
+    "hasIndices",
+    "hasInstance",
+    "hasOwn",
+    "hasOwnProperty",
+    "hash",
+    "head",
+    "headers",
+    "height",
+    "hidden",
+    "history",
+    "host",
+    "hostname",
+    "href",
+    "hypot",
+    "id",
+    "ignoreCase",
+    "images",
+    "imul",
+    "includes",
+    "index",
+    "indexOf",
+    "indices",
+    "info",
+    "innerHTML",
+    "innerText",
+    "input",
+    "insertAdjacentElement",
+    "insertAdjacentHTML",
+    "insertAdjacentText",
+    "insertBefore",
+    "is",
+    "isArray",
+    "isConcatSpreadable",
+    "isConnected",
+    "isContentEditable",
+    "isEqualNode",
+    "isExtensible",
+    "isFinite",
+    "isFrozen",
+    "isInteger",
+    "isNaN",
+    "isPrototypeOf",
+    "isSafeInteger",
+    "isSealed",
+    "isTrusted",
+    "isWellFormed",
+    "italics",
+    "item",
+    "iterator",
+    "join",
+    "json",
+    "key",
+    "keyCode",
+    "keyFor",
+    "keys",
+    "lang",
+    "language",
+    "languages",
+    "lastChild",
+    "lastElementChild",
+    "lastEventId",
+    "lastIndex",
+    "lastIndexOf",
+    "left",
+    "length",
+    "link",
+    "links",
+    "localStorage",
+    "localeCompare",
+    "location",
+    "log",
+    "log10",
+    "log1p",
+    "log2",
+    "map",
+    "margin",
+    "match",
+    "matchAll",
+    "matches",
+    "max",
+    "maxByteLength",
+    "message",
+    "metaKey",
+    "method",
+    "min",
+    "movementX",
+    "movementY",
+    "multiline",
+    "multiple",
+    "name",
+    "navigator",
+    "next",
+    "nextElementSibling",
+    "nextSibling",
+    "nodeName",
+    "nodeType",
+    "nodeValue",
+    "normalize",
+    "now",
+    "of",
+    "offsetHeight",
+    "offsetLeft",
+    "offsetParent",
+    "offsetTop",
+    "offsetWidth",
+    "offsetX",
+    "offsetY",
+    "ok",
+    "onLine",
+    "onchange",
+    "onclick",
+    "onerror",
+    "oninput",
+    "onkeydown",
+    "onkeyup",
+    "onload",
+    "onmessage",
+    "onreadystatechange",
+    "onsubmit",
+    "opacity",
+    "open",
+    "origin",
+    "outerHTML",
+    "outerText",
+    "overflow",
+    "ownKeys",
+    "ownerDocument",
+    "padEnd",
+    "padStart",
+    "padding",
+    "pageX",
+    "pageY",
+    "parentElement",
+    "parentNode",
+    "parse",
+    "parseFloat",
+    "parseInt",
+    "pathname",
+    "performance",
+    "placeholder",
+    "platform",
+    "pointerId",
+    "pointerType",
+    "pop",
+    "port",
+    "ports",
+    "position",
+    "postMessage",
+    "pow",
+    "prepend",
+    "preventDefault",
+    "preventExtensions",
+    "previousElementSibling",
+    "previousSibling",
+    "prompt",
+    "propertyIsEnumerable",
+    "protocol",
+    "prototype",
+    "push",
+    "pushState",
+    "querySelector",
+    "querySelectorAll",
+    "queueMicrotask",
+    "race",
+    "random",
+    "raw",
+    "readOnly",
+    "readyState",
+    "reason",
+    "redirected",
+    "reduce",
+    "reduceRight",
+    "referrer",
+    "register",
+    "reject",
+    "rel",
+    "relatedTarget",
+    "reload",
+    "remove",
+    "removeAttribute",
+    "removeChild",
+    "removeEventListener",
+    "removeItem",
+    "removeProperty",
+    "repeat",
+    "replace",
+    "replaceAll",
+    "replaceChild",
+    "replaceChildren",
+    "replaceState",
+    "replaceWith",
+    "requestAnimationFrame",
+    "required",
+    "resizable",
+    "resize",
+    "resolve",
+    "resolvedOptions",
+    "response",
+    "responseText",
+    "responseType",
+    "responseXML",
+    "return",
+    "reverse",
+    "right",
+    "round",
+    "screen",
+    "screenX",
+    "screenY",
+    "scripts",
+    "scroll",
+    "scrollBy",
+    "scrollHeight",
+    "scrollIntoView",
+    "scrollLeft",
+    "scrollTo",
+    "scrollTop",
+    "scrollWidth",
+    "seal",
+    "search",
+    "select",
+    "selected",
+    "self",
+    "send",
+    "serviceWorker",
+    "sessionStorage",
+    "set",
+    "setAttribute",
+    "setBigInt64",
+    "setBigUint64",
+    "setDate",
+    "setFloat32",
+    "setFloat64",
+    "setFullYear",
+    "setHours",
+    "setInt16",
+    "setInt32",
+    "setInt8",
+    "setInterval",
+    "setItem",
+    "setMilliseconds",
+    "setMinutes",
+    "setMonth",
+    "setProperty",
+    "setPrototypeOf",
+    "setRequestHeader",
+    "setSeconds",
+    "setTime",
+    "setTimeout",
+    "setUTCDate",
+    "setUTCFullYear",
+    "setUTCHours",
+    "setUTCMilliseconds",
+    "setUTCMinutes",
+    "setUTCMonth",
+    "setUTCSeconds",
+    "setUint16",
+    "setUint32",
+    "setUint8",
+    "setYear",
+    "shadowRoot",
+    "shift",
+    "shiftKey",
+    "sign",
+    "signal",
+    "sin",
+    "sinh",
+    "size",
+    "slice",
+    "small",
+    "some",
+    "sort",
+    "source",
+    "species",
+    "splice",
+    "split",
+    "sqrt",
+    "src",
+    "srcElement",
+    "stack",
+    "stackTraceLimit",
+    "startsWith",
+    "state",
+    "status",
+    "statusText",
+    "sticky",
+    "stopImmediatePropagation",
+    "stopPropagation",
+    "strike",
+    "stringify",
+    "structuredClone",
+    "style",
+    "sub",
+    "subarray",
+    "substr",
+    "substring",
+    "sup",
+    "supportedLocalesOf",
+    "tabIndex",
+    "table",
+    "tagName",
+    "tan",
+    "tanh",
+    "target",
+    "targetTouches",
+    "test",
+    "text",
+    "textContent",
+    "then",
+    "throw",
+    "time",
+    "timeEnd",
+    "timeLog",
+    "timeStamp",
+    "timeout",
+    "title",
+    "toDateString",
+    "toExponential",
+    "toFixed",
+    "toGMTString",
+    "toISOString",
+    "toJSON",
+    "toLocaleDateString",
+    "toLocaleLowerCase",
+    "toLocaleString",
+    "toLocaleTimeString",
+    "toLocaleUpperCase",
+    "toLowerCase",
+    "toPrecision",
+    "toPrimitive",
+    "toReversed",
+    "toSorted",
+    "toSpliced",
+    "toString",
+    "toStringTag",
+    "toTimeString",
+    "toUTCString",
+    "toUpperCase",
+    "toWellFormed",
+    "toggle",
+    "toggleAttribute",
+    "top",
+    "touches",
+    "trace",
+    "transfer",
+    "transform",
+    "transition",
+    "trim",
+    "trimEnd",
+    "trimLeft",
+    "trimRight",
+    "trimStart",
+    "trunc",
+    "type",
+    "unicode",
+    "unicodeSets",
+    "unregister",
+    "unscopables",
+    "unshift",
+    "upload",
+    "url",
+    "userAgent",
+    "value",
+    "valueOf",
+    "values",
+    "visibility",
+    "w",
+    "warn",
+    "which",
+    "width",
+    "window",
+    "with",
+    "withCredentials",
+    "withResolvers",
+    "x",
+    "y",
+    "z",
+    "zIndex",
+};
@@ -1,14 +1,48 @@
+mod builtins;
+mod properties;
+
+use std::collections::HashSet;
+
 use itertools::Itertools;
 #[allow(clippy::wildcard_imports)]
 use oxc_hir::hir::*;
 use oxc_hir::Visit;
 use oxc_index::{index_vec, IndexVec};
-use oxc_semantic::{Reference, ReferenceFlag, ReferenceId, SemanticBuilder, SymbolId, SymbolTable};
+use oxc_semantic::{
+    Reference, ReferenceFlag, ReferenceId, ScopeId, SemanticBuilder, SymbolId, SymbolTable,
+};
 use oxc_span::{Atom, SourceType};
 use oxc_syntax::{scope::ScopeFlags, symbol::SymbolFlags};
 
+pub use self::properties::{NameCache, PropertyMangler, PropertyManglerOptions};
+
 type Slot = usize;
 
+#[derive(Debug, Default, Clone)]
+pub struct ManglerOptions {
+    /// Names which are not mangled, and which mangled names do not use, e.g. `["$", "exports"]`
+    pub reserved: Vec<String>,
+
+    /// Keep the names of function declarations, for code which reads `Function.prototype.name`.
+    ///
+    /// Default `false`
+    pub keep_fnames: bool,
+
+    /// Keep the names of class declarations.
+    ///
+    /// Default `false`
+    pub keep_classnames: bool,
+}
+
+impl ManglerOptions {
+    /// Whether the symbol `name` keeps its name
+    fn keeps_name(&self, name: &Atom, flags: SymbolFlags) -> bool {
+        (self.keep_fnames && flags.is_function())
+            || (self.keep_classnames && flags.is_class())
+            || self.reserved.iter().any(|reserved| reserved == name.as_str())
+    }
+}
+
 pub struct Mangler {
     symbol_table: SymbolTable,
 }
@@ -65,11 +99,35 @@ impl Mangler {
 ///     }
 /// }
 /// ```
+///
+/// Some bindings keep their names, and mangled names do not use the names they keep:
+/// * the bindings of the top level scope of scripts, which are globals, and the exported
+///   bindings of modules
+/// * the bindings of scopes which contain a direct `eval`, as the code it evaluates can
+///   reference them by name, and of their parent scopes
+/// * the names reserved by [`ManglerOptions`], and functions and classes it keeps the names of
 pub struct ManglerBuilder<'a> {
     semantic: SemanticBuilder<'a>,
+    options: ManglerOptions,
+    source_type: SourceType,
+    /// The scopes which reference `eval`
+    eval_scopes: Vec<ScopeId>,
+    /// The local names of the exported bindings of a module, e.g. `a` for `export { a as b }`
+    exported_names: HashSet<Atom>,
+    /// Whether the bindings are declared by `export const a = 1` and the like
+    in_export_declaration: bool,
 }
 
 impl<'a> Visit<'a> for ManglerBuilder<'a> {
+    fn visit_program(&mut self, program: &'a Program<'a>) {
+        for directive in &program.directives {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&program.body);
+        // Resolves the references to the top level bindings, which module scopes mangle
+        self.semantic.leave_scope();
+    }
+
     fn enter_scope(&mut self, flags: ScopeFlags) {
         self.semantic.enter_scope(flags);
     }
@@ -87,9 +145,32 @@ impl<'a> Visit<'a> for ManglerBuilder<'a> {
         let symbol_id =
             self.semantic.declare_symbol_for_mangler(ident.span, &ident.name, includes, excludes);
         ident.symbol_id.replace(symbol_id);
+        if self.in_export_declaration
+            && self.semantic.current_scope_id == self.semantic.scope.root_scope_id()
+        {
+            self.exported_names.insert(ident.name.clone());
+        }
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &'a ExportNamedDeclaration<'a>) {
+        if let Some(decl) = &decl.declaration {
+            self.in_export_declaration = true;
+            self.visit_declaration(decl);
+            self.in_export_declaration = false;
+        }
+        if decl.source.is_none() {
+            for specifier in &decl.specifiers {
+                if let ModuleExportName::Identifier(ident) = &specifier.local {
+                    self.exported_names.insert(ident.name.clone());
+                }
+            }
+        }
     }
 
     fn visit_identifier_reference(&mut self, ident: &'a IdentifierReference) {
+        if ident.name == "eval" {
+            self.eval_scopes.push(self.semantic.current_scope_id);
+        }
         let reference = Reference::new(
             ident.span,
             ident.name.clone(),
@@ -103,7 +184,20 @@ impl<'a> Visit<'a> for ManglerBuilder<'a> {
 
 impl<'a> ManglerBuilder<'a> {
     pub fn new(source_text: &'a str, source_type: SourceType) -> Self {
-        Self { semantic: SemanticBuilder::new(source_text, source_type) }
+        Self {
+            semantic: SemanticBuilder::new(source_text, source_type),
+            options: ManglerOptions::default(),
+            source_type,
+            eval_scopes: vec![],
+            exported_names: HashSet::new(),
+            in_export_declaration: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_options(mut self, options: ManglerOptions) -> Self {
+        self.options = options;
+        self
     }
 
     #[must_use]
@@ -121,8 +215,23 @@ impl<'a> ManglerBuilder<'a> {
         // Total number of slots for all scopes
         let mut total_number_of_slots: Slot = 0;
 
-        // All symbols with their assigned slots
-        let mut slots: IndexVec<SymbolId, Slot> = index_vec![0; symbol_table.len()];
+        // All symbols with their assigned slots, `None` for the symbols which keep their names
+        let mut slots: IndexVec<SymbolId, Option<Slot>> = index_vec![None; symbol_table.len()];
+
+        // The scopes whose bindings a direct `eval` can reference
+        let eval_scopes = self
+            .eval_scopes
+            .iter()
+            .flat_map(|scope_id| scope_tree.ancestors(*scope_id))
+            .collect::<HashSet<_>>();
+
+        // The names which are kept, which mangled names must not shadow
+        let mut kept_names = self
+            .options
+            .reserved
+            .iter()
+            .map(|name| Atom::from(name.as_str()))
+            .collect::<HashSet<_>>();
 
         // Keep track of the maximum slot number for each scope
         let mut max_slot_for_scope = vec![0; scope_tree.len()];
@@ -137,9 +246,20 @@ impl<'a> ManglerBuilder<'a> {
 
             let mut slot = parent_max_slot;
 
+            let is_root_scope = scope_id == scope_tree.root_scope_id();
+            let keeps_names =
+                (is_root_scope && !self.source_type.is_module()) || eval_scopes.contains(&scope_id);
+
             // `bindings` are stored in order, traverse and increment slot
-            for symbol_id in bindings.values() {
-                slots[*symbol_id] = slot;
+            for (name, symbol_id) in bindings {
+                if keeps_names
+                    || (is_root_scope && self.exported_names.contains(name))
+                    || self.options.keeps_name(name, symbol_table.get_flag(*symbol_id))
+                {
+                    kept_names.insert(name.clone());
+                    continue;
+                }
+                slots[*symbol_id] = Some(slot);
                 slot += 1;
             }
 
@@ -167,8 +287,11 @@ impl<'a> ManglerBuilder<'a> {
             names.push(loop {
                 let name = Atom::base54(count);
                 count += 1;
-                // Do not mangle keywords and unresolved references
-                if !is_keyword(&name) && !unresolved_references.iter().any(|n| **n == name) {
+                // Do not mangle keywords, unresolved references and kept names
+                if !is_keyword(&name)
+                    && !unresolved_references.iter().any(|n| **n == name)
+                    && !kept_names.contains(&name)
+                {
                     break name;
                 }
             });
@@ -224,15 +347,12 @@ impl<'a> ManglerBuilder<'a> {
     fn tally_slot_frequencies(
         symbol_table: &SymbolTable,
         total_number_of_slots: usize,
-        slots: &IndexVec<SymbolId, Slot>,
+        slots: &IndexVec<SymbolId, Option<Slot>>,
     ) -> Vec<SlotFrequency> {
         let mut frequencies = vec![SlotFrequency::default(); total_number_of_slots];
         for (symbol_id, slot) in slots.iter_enumerated() {
-            if !symbol_table.get_flag(symbol_id).is_variable() {
-                continue;
-            }
-            let index = *slot;
-            frequencies[index].slot = *slot;
+            let Some(index) = *slot else { continue };
+            frequencies[index].slot = index;
             frequencies[index].frequency +=
                 symbol_table.get_resolved_reference_ids(symbol_id).len();
             frequencies[index].symbol_ids.push(symbol_id);
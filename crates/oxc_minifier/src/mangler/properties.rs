@@ -0,0 +1,245 @@
+//! Property Mangling
+//!
+//! Renames the properties of objects and classes, e.g. `this._count` -> `this.a`.
+//!
+//! This is only correct when every access of a mangled property is minified with the same names,
+//! so it is not enabled by default.
+//! See terser's [mangle properties](https://terser.org/docs/options/#mangle-properties-options).
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[allow(clippy::wildcard_imports)]
+use oxc_hir::hir::*;
+use oxc_hir::VisitMut;
+use oxc_span::Atom;
+use regex::Regex;
+use serde_json::{Map, Value};
+
+use super::builtins::BUILTINS;
+
+/// Properties which have a meaning to the language
+const ALWAYS_RESERVED: [&str; 3] = ["constructor", "prototype", "__proto__"];
+
+#[derive(Debug, Default, Clone)]
+pub struct PropertyManglerOptions {
+    /// Only mangle the property names matching the regex, e.g. `^_`.
+    ///
+    /// Without it, every property which is not reserved is mangled.
+    pub regex: Option<Regex>,
+
+    /// Property names which are not mangled, and which mangled names do not use, e.g. `["_id"]`
+    pub reserved: Vec<String>,
+
+    /// Mangle the properties of the built-in objects and of the DOM as well, e.g. `length` and
+    /// `addEventListener`, which are otherwise reserved.
+    ///
+    /// Default `false`
+    pub builtins: bool,
+}
+
+impl PropertyManglerOptions {
+    fn mangles(&self, name: &str) -> bool {
+        !self.is_reserved(name) && self.regex.as_ref().map_or(true, |regex| regex.is_match(name))
+    }
+
+    fn is_reserved(&self, name: &str) -> bool {
+        ALWAYS_RESERVED.contains(&name)
+            || (!self.builtins && BUILTINS.contains(name))
+            || self.reserved.iter().any(|reserved| reserved == name)
+    }
+}
+
+/// The names given to mangled properties.
+///
+/// Saving the cache after a build and loading it for the next one keeps the names stable across
+/// builds, and lets separately minified files which share objects agree on them.
+/// The JSON format is the `props` part of terser's `nameCache`: `{"props":{"props":{"$_count":"a"}}}`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NameCache {
+    props: BTreeMap<String, String>,
+}
+
+impl NameCache {
+    /// A cache without `props`, e.g. with only the `vars` of terser, is empty.
+    ///
+    /// # Errors
+    ///
+    /// * The JSON is invalid
+    /// * The JSON is not an object, or its `props` are not `{"props":{"$name":"mangled"}}`
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let value = serde_json::from_str::<Map<String, Value>>(json)?;
+        let Some(props) = value.get("props") else { return Ok(Self::default()) };
+        let props = props.get("props").cloned().unwrap_or(Value::Null);
+        let props = serde_json::from_value::<BTreeMap<String, String>>(props)?
+            .into_iter()
+            .map(|(name, mangled)| match name.strip_prefix('$') {
+                Some(name) => (name.to_string(), mangled),
+                None => (name, mangled),
+            })
+            .collect();
+        Ok(Self { props })
+    }
+
+    pub fn to_json(&self) -> String {
+        let props = self
+            .props
+            .iter()
+            .map(|(name, mangled)| (format!("${name}"), Value::String(mangled.clone())))
+            .collect::<Map<_, _>>();
+        serde_json::json!({ "props": { "props": props } }).to_string()
+    }
+
+    /// The mangled name of the property `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.props.get(name).map(String::as_str)
+    }
+}
+
+pub struct PropertyMangler {
+    options: PropertyManglerOptions,
+}
+
+impl PropertyMangler {
+    pub fn new(options: PropertyManglerOptions) -> Self {
+        Self { options }
+    }
+
+    /// Renames the properties of the program, reusing the names of the cache and adding the new
+    /// ones to it.
+    pub fn build(&self, program: &mut Program<'_>, name_cache: &mut NameCache) {
+        let mut properties = PropertyNames::default();
+        properties.visit_program(program);
+
+        let mut mangled = properties
+            .names
+            .iter()
+            .filter(|name| !properties.quoted.contains(*name) && self.options.mangles(name))
+            .collect::<Vec<_>>();
+        if mangled.is_empty() {
+            return;
+        }
+
+        // A mangled name must not be a property which keeps its name, including the names of
+        // the cache
+        let kept_names = properties
+            .names
+            .iter()
+            .filter(|name| !mangled.contains(name))
+            .chain(&properties.quoted)
+            .map(ToString::to_string)
+            .collect::<HashSet<_>>();
+        let is_kept = |name: &str| kept_names.contains(name) || self.options.is_reserved(name);
+        let mut used_names = name_cache.props.values().cloned().collect::<HashSet<_>>();
+
+        // The most frequent properties get the shorter names, the sort is stable so ties are
+        // named in the order they first appear in the source
+        mangled.sort_by_key(|name| std::cmp::Reverse(properties.counts[*name]));
+
+        let mut renames = HashMap::with_capacity(mangled.len());
+        let mut count = 0;
+        for name in mangled {
+            let new_name = if let Some(new_name) = name_cache.get(name).filter(|n| !is_kept(n)) {
+                new_name.to_string()
+            } else {
+                let new_name = loop {
+                    let new_name = Atom::base54(count);
+                    count += 1;
+                    if !used_names.contains(new_name.as_str()) && !is_kept(&new_name) {
+                        break new_name.to_string();
+                    }
+                };
+                used_names.insert(new_name.clone());
+                name_cache.props.insert(name.to_string(), new_name.clone());
+                new_name
+            };
+            renames.insert(name.clone(), Atom::from(new_name));
+        }
+
+        PropertyRenamer { renames }.visit_program(program);
+    }
+}
+
+/// The property names of the program in the order they first appear.
+#[derive(Default)]
+struct PropertyNames {
+    names: Vec<Atom>,
+    counts: HashMap<Atom, usize>,
+    /// Properties which keep their names: quoted properties, which are the way to access a
+    /// property by its original name, and shorthand assignment targets, which can not be renamed
+    quoted: HashSet<Atom>,
+}
+
+impl PropertyNames {
+    fn add(&mut self, name: &Atom) {
+        let count = self.counts.entry(name.clone()).or_insert_with(|| {
+            self.names.push(name.clone());
+            0
+        });
+        *count += 1;
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for PropertyNames {
+    fn visit_property_key(&mut self, key: &'b mut PropertyKey<'a>) {
+        match key {
+            PropertyKey::Identifier(ident) => self.add(&ident.name),
+            PropertyKey::PrivateIdentifier(_) => {}
+            PropertyKey::Expression(expr) => {
+                if let Expression::StringLiteral(lit) = expr {
+                    self.quoted.insert(lit.value.clone());
+                }
+                self.visit_expression(expr);
+            }
+        }
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &'b mut StaticMemberExpression<'a>) {
+        self.visit_expression(&mut expr.object);
+        self.add(&expr.property.name);
+    }
+
+    fn visit_computed_member_expression(&mut self, expr: &'b mut ComputedMemberExpression<'a>) {
+        if let Expression::StringLiteral(lit) = &expr.expression {
+            self.quoted.insert(lit.value.clone());
+        }
+        self.visit_expression(&mut expr.object);
+        self.visit_expression(&mut expr.expression);
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        ident: &'b mut AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.quoted.insert(ident.binding.name.clone());
+        if let Some(expr) = &mut ident.init {
+            self.visit_expression(expr);
+        }
+    }
+}
+
+struct PropertyRenamer {
+    renames: HashMap<Atom, Atom>,
+}
+
+impl PropertyRenamer {
+    fn rename(&self, ident: &mut IdentifierName) {
+        if let Some(name) = self.renames.get(&ident.name) {
+            ident.name = name.clone();
+        }
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for PropertyRenamer {
+    fn visit_property_key(&mut self, key: &'b mut PropertyKey<'a>) {
+        match key {
+            PropertyKey::Identifier(ident) => self.rename(ident),
+            PropertyKey::PrivateIdentifier(_) => {}
+            PropertyKey::Expression(expr) => self.visit_expression(expr),
+        }
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &'b mut StaticMemberExpression<'a>) {
+        self.visit_expression(&mut expr.object);
+        self.rename(&mut expr.property);
+    }
+}
@@ -2,6 +2,7 @@ use oxc_allocator::{Box, Vec};
 #[allow(clippy::wildcard_imports)]
 use oxc_hir::hir::*;
 use oxc_hir::precedence;
+use oxc_span::GetSpan;
 use oxc_syntax::{
     operator::{
         AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator, UpdateOperator,
@@ -42,14 +43,19 @@ impl<'a> Gen for Program<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
         if let Some(hashbang) = &self.hashbang {
             hashbang.gen(p, ctx);
+            if !self.directives.is_empty() || !self.body.is_empty() {
+                p.print(b'\n');
+            }
         }
         for directive in &self.directives {
+            p.print_comments_before(directive.span.start);
             directive.gen(p, ctx);
         }
         for stmt in &self.body {
             p.print_semicolon_if_needed();
             stmt.gen(p, ctx);
         }
+        p.print_comments_before(u32::MAX);
     }
 }
 
@@ -71,6 +77,7 @@ impl Gen for Directive {
 
 impl<'a> Gen for Statement<'a> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
+        p.print_comments_before(self.span().start);
         match self {
             Self::BlockStatement(stmt) => stmt.gen(p, ctx),
             Self::BreakStatement(stmt) => stmt.gen(p, ctx),
@@ -457,6 +464,8 @@ impl<'a> Gen for VariableDeclaration<'a> {
             VariableDeclarationKind::Const => b"const",
             VariableDeclarationKind::Let => b"let",
             VariableDeclarationKind::Var => b"var",
+            VariableDeclarationKind::Using => b"using",
+            VariableDeclarationKind::AwaitUsing => b"await using",
         });
         p.print(b' ');
         p.print_list(&self.declarations, ctx);
@@ -549,7 +558,7 @@ impl<'a> Gen for ImportDeclaration<'a> {
             p.print(b'\'');
             p.print_str(self.source.value.as_bytes());
             p.print(b'\'');
-            self.assertions.gen(p, ctx);
+            self.with_clause.gen(p, ctx);
             p.print_semicolon_after_statement();
             return;
         }
@@ -598,7 +607,10 @@ impl<'a> Gen for ImportDeclaration<'a> {
                         }
                     };
 
-                    let local_name = spec.local.name.as_bytes();
+                    let local_name = p.mangler.as_ref().map_or_else(
+                        || spec.local.name.as_bytes(),
+                        |mangler| mangler.get_symbol_name(spec.local.symbol_id.get()).as_bytes(),
+                    );
 
                     if imported_name != local_name {
                         p.print_str(b" as ");
@@ -612,17 +624,23 @@ impl<'a> Gen for ImportDeclaration<'a> {
         }
         p.print_str(b" from ");
         self.source.gen(p, ctx);
-        self.assertions.gen(p, ctx);
+        self.with_clause.gen(p, ctx);
         p.print_semicolon_after_statement();
     }
 }
 
-impl<'a> Gen for Option<Vec<'a, ImportAttribute>> {
+impl<'a> Gen for Option<WithClause<'a>> {
     fn gen(&self, p: &mut Printer, ctx: Context) {
-        if let Some(assertions) = &self {
-            p.print_str(b"assert");
-            p.print_block(assertions, Separator::Comma, ctx);
-        };
+        if let Some(with_clause) = self {
+            with_clause.gen(p, ctx);
+        }
+    }
+}
+
+impl<'a> Gen for WithClause<'a> {
+    fn gen(&self, p: &mut Printer, ctx: Context) {
+        p.print_str(self.attributes_keyword.name.as_bytes());
+        p.print_block(&self.with_entries, Separator::Comma, ctx);
     }
 }
 
@@ -654,6 +672,7 @@ impl<'a> Gen for ExportNamedDeclaration<'a> {
                     p.print_str(b"from");
                     source.gen(p, ctx);
                 }
+                self.with_clause.gen(p, ctx);
                 p.print_semicolon_after_statement();
             }
         }
@@ -693,7 +712,7 @@ impl<'a> Gen for ExportAllDeclaration<'a> {
 
         p.print_str(b" from");
         self.source.gen(p, ctx);
-        self.assertions.gen(p, ctx);
+        self.with_clause.gen(p, ctx);
 
         p.print_semicolon_after_statement();
     }
@@ -999,6 +1018,9 @@ impl<'a> GenExpr for PrivateFieldExpression<'a> {
 impl<'a> GenExpr for CallExpression<'a> {
     fn gen_expr(&self, p: &mut Printer, precedence: Precedence, ctx: Context) {
         p.wrap(precedence > self.precedence(), |p| {
+            if self.pure {
+                p.print_pure_annotation();
+            }
             self.callee.gen_expr(p, self.precedence(), ctx);
             if self.optional {
                 p.print_str(b"?.");
@@ -1506,16 +1528,21 @@ impl<'a> GenExpr for AwaitExpression<'a> {
 
 impl<'a> GenExpr for ChainExpression<'a> {
     fn gen_expr(&self, p: &mut Printer, precedence: Precedence, ctx: Context) {
-        match &self.expression {
+        // `(a?.b).c` evaluates `.c` even when `a` is nullish, `a?.b.c` does not
+        let wrap = precedence >= Precedence::Postfix && p.parenthesized.contains(&self.span);
+        p.wrap(wrap, |p| match &self.expression {
             ChainElement::CallExpression(expr) => expr.gen_expr(p, precedence, ctx),
             ChainElement::MemberExpression(expr) => expr.gen_expr(p, precedence, ctx),
-        }
+        });
     }
 }
 
 impl<'a> GenExpr for NewExpression<'a> {
     fn gen_expr(&self, p: &mut Printer, precedence: Precedence, ctx: Context) {
         p.wrap(precedence > self.precedence(), |p| {
+            if self.pure {
+                p.print_pure_annotation();
+            }
             p.print_str(b"new ");
             self.callee.gen_expr(p, self.precedence(), ctx);
             p.wrap(true, |p| {
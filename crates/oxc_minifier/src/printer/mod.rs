@@ -21,6 +21,7 @@ use oxc_syntax::{
     },
     precedence::Precedence,
 };
+use rustc_hash::FxHashSet;
 
 use self::{
     context::Context,
@@ -37,6 +38,15 @@ pub struct Printer {
 
     mangler: Option<Mangler>,
 
+    /// Comments to keep, as their start in the source text and their text, sorted by start
+    comments: Vec<(u32, String)>,
+
+    /// Index of the next comment to print
+    next_comment: usize,
+
+    /// Spans of the expressions wrapped in parentheses in the source
+    parenthesized: FxHashSet<Span>,
+
     /// Output Code
     code: Vec<u8>,
 
@@ -72,6 +82,9 @@ impl Printer {
         Self {
             options,
             mangler: None,
+            comments: vec![],
+            next_comment: 0,
+            parenthesized: FxHashSet::default(),
             code: Vec::with_capacity(capacity),
             needs_semicolon: false,
             need_space_before_dot: 0,
@@ -88,6 +101,23 @@ impl Printer {
         self.mangler = Some(mangler);
     }
 
+    /// Keep the comments, given as their start in the source text and their text including
+    /// `//`, `/*` and `*/`.
+    ///
+    /// Each comment is printed on its own line before the first statement after it.
+    pub fn with_comments(&mut self, mut comments: Vec<(u32, String)>) {
+        comments.sort_by_key(|(start, _)| *start);
+        self.comments = comments;
+    }
+
+    /// Spans of the expressions wrapped in parentheses in the source, see
+    /// [`oxc_ast_lower::AstLowerReturn::parenthesized`].
+    ///
+    /// Needed where the parentheses change the meaning of the code, such as `(a?.b).c`.
+    pub fn with_parenthesized(&mut self, parenthesized: FxHashSet<Span>) {
+        self.parenthesized = parenthesized;
+    }
+
     pub fn build(mut self, program: &Program<'_>) -> String {
         program.gen(&mut self, Context::default());
         self.into_code()
@@ -179,6 +209,44 @@ impl Printer {
         }
     }
 
+    /// Print the comments which start before `start` in the source text
+    fn print_comments_before(&mut self, start: u32) {
+        while let Some((comment_start, text)) = self.comments.get(self.next_comment) {
+            if *comment_start >= start {
+                return;
+            }
+            let text = text.clone();
+            self.next_comment += 1;
+            self.print_semicolon_if_needed();
+            if self.code.last().is_some_and(|last| *last != b'\n') {
+                self.print(b'\n');
+            }
+            self.print_str(text.as_bytes());
+            self.print(b'\n');
+        }
+    }
+
+    /// Print `/*#__PURE__*/` before a call or `new` expression, which is still at the start of
+    /// the statement, arrow function body or default export it starts
+    fn print_pure_annotation(&mut self) {
+        // `a/ /*#__PURE__*/f()`, not a line comment
+        if self.code.last() == Some(&b'/') {
+            self.print(b' ');
+        }
+        let n = self.code_len();
+        self.print_str(b"/*#__PURE__*/");
+        let end = self.code_len();
+        for start in [
+            &mut self.start_of_stmt,
+            &mut self.start_of_arrow_expr,
+            &mut self.start_of_default_export,
+        ] {
+            if *start == n {
+                *start = end;
+            }
+        }
+    }
+
     fn print_ellipsis(&mut self) {
         self.print_str(b"...");
     }
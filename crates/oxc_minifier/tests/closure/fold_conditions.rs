@@ -1,4 +1,4 @@
-use crate::test;
+use crate::{test, test_without_compress_statements};
 
 #[test]
 fn test_fold_not() {
@@ -19,3 +19,26 @@ fn test_fold_not() {
     // This will be further optimized by PeepholeFoldConstants.
     // test("x = !true", "x=!1");
 }
+
+#[test]
+fn test_minimize_not() {
+    test("x = !(a == b)", "x=a!=b");
+    test("x = !(a !== b)", "x=a===b");
+    test("x = !!(a > b)", "x=a>b");
+    test("x = !!!a", "x=!a");
+    test("x = !!a", "x=!!a");
+    test("x = !(a > b)", "x=!(a>b)");
+}
+
+#[test]
+fn test_minimize_boolean_context() {
+    test_without_compress_statements("if (!!a) b()", "if(a)b()");
+    test_without_compress_statements("if (!!a && !!b) c()", "if(a&&b)c()");
+    test_without_compress_statements("if ((x, !!a)) b()", "if(x,a)b()");
+    test_without_compress_statements("if (!!a ?? b) c()", "if(!!a??b)c()");
+    test("while (!!a) b()", "for(;a;)b()");
+    test("do b(); while (!!a)", "do b();while(a)");
+    test("x = !!a ? b : c", "x=a?b:c");
+    test("x = !a ? b : c", "x=a?c:b");
+    test("x = !(a == b) ? c : d", "x=a!=b?c:d");
+}
@@ -469,9 +469,9 @@ fn unary_ops() {
     // test_same("-foo()");
 
     // These cases are handled here.
-    test("a=!true", "a=!!0");
+    test("a=!true", "a=!1");
     test("a=!10", "a=!1");
-    test("a=!false", "a=!!1");
+    test("a=!false", "a=!0");
     test_same("a=!foo()");
     test("a=-0", "a=-0");
     test("a=-(0)", "a=-0");
@@ -628,7 +628,53 @@ fn test_fold_bit_shift() {
 
     test("x = 0xffffffff << 0", "x=-1");
     test("x = 0xffffffff << 4", "x=-16");
-    test("1 << 32", "1<<32");
+    test("1 << 32", "1");
     test("1 << -1", "1<<-1");
-    test("1 >> 32", "1>>32");
+    test("1 >> 32", "1");
+}
+
+#[test]
+fn test_fold_arithmetic() {
+    test("x = 10 + 20", "x=30");
+    test("x = 2 / 4", "x=.5");
+    test("x = 2.25 * 3", "x=6.75");
+    test_same("z=x*y");
+    test_same("x=y*5");
+    test("x = 1 / 3", "x=1/3");
+    test("x = 3 % 2", "x=1");
+    test("x = 3 % -2", "x=1");
+    test("x = -1 % 3", "x=-1");
+    test_same("x=1%0");
+    test_same("x=1/0");
+    test("x = 2 ** 3", "x=8");
+    test("x = 2 ** -3", "x=.125");
+    test_same("x=2**55");
+    test("x = 3 ** -1", "x=3**-1");
+    test("x = 1 - 2", "x=-1");
+    test("x = 0 * -1", "x=-0");
+    test("x = 1 + true", "x=2");
+    test("x = 1 - null", "x=1");
+    test("x=1-'1'", "x=0");
+    test_same("x=y+1+2");
+    test("x = 1 + 2 + y", "x=3+y");
+}
+
+#[test]
+fn test_fold_string_add() {
+    test("x = 'a' + 'b'", "x='ab'");
+    test("x = 'a' + 5", "x='a5'");
+    test("x = 5 + 'a'", "x='5a'");
+    test("x = 'a' + 5n", "x='a5'");
+    test("x = 'a' + 0.5", "x='a0.5'");
+    test("x = 'a' + -0", "x='a0'");
+    test("x = 'a' + 1e21", "x='a1e+21'");
+    test("x = 'a' + ''", "x='a'");
+    test("x = 'a' + true", "x='atrue'");
+    test("x = 'a' + null", "x='anull'");
+    test("x = 'a' + void 0", "x='aundefined'");
+    test_same("x='a'+foo()");
+    test_same("x='a'+{}");
+    test("x = foo() + 'a' + 'b'", "x=foo()+'ab'");
+    test("x = foo() + 'a' + 1", "x=foo()+'a1'");
+    test_same("x=foo()+1+'a'");
 }
@@ -1,6 +1,6 @@
 //! <https://github.com/google/closure-compiler/blob/master/test/com/google/javascript/jscomp/PeepholeSubstituteAlternateSyntaxTest.java>
 
-use crate::test;
+use crate::{test, test_with_options, CompressOptions, MinifierOptions};
 
 #[test]
 fn fold_return_result() {
@@ -15,13 +15,22 @@ fn fold_return_result() {
 #[test]
 fn undefined() {
     test("var x = undefined", "var x");
-    test(
+    let options = MinifierOptions {
+        mangle: false,
+        compress: CompressOptions { unused: false, ..CompressOptions::default() },
+        ..MinifierOptions::default()
+    };
+    test_with_options(
         "var undefined = 1;function f() {var undefined=2;var x;}",
         "var undefined=1;function f(){var undefined=2,x}",
+        options,
     );
     test("function f(undefined) {}", "function f(undefined){}");
     test("try {} catch(undefined) {}", "try{}catch(undefined){}");
     test("for (undefined in {}) {}", "for(undefined in {}){}");
     test("undefined++", "undefined++");
     test("undefined += undefined;", "undefined+=void 0");
+    test("function f(undefined) { return undefined }", "function f(undefined){return undefined}");
+    test("function f(NaN) { return ~NaN }", "function f(NaN){return ~NaN}");
+    test("~NaN", "-1");
 }
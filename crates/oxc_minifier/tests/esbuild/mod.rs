@@ -313,8 +313,8 @@ fn r#for() {
 fn function() {
     test("function foo(a = (b, c), ...d) {}", "function foo(a=(b,c),...d){}");
     test(
-        "function foo({[1 + 2]: a = 3} = {[1 + 2]: 3}) {}",
-        "function foo({[1+2]:a=3}={[1+2]:3}){}",
+        "function foo({[1 + x]: a = 3} = {[1 + x]: 3}) {}",
+        "function foo({[1+x]:a=3}={[1+x]:3}){}",
     );
     test(
         "function foo([a = (1, 2), ...[b, ...c]] = [1, [2, 3]]) {}",
@@ -397,7 +397,7 @@ fn arrow() {
     test("x => (x, 0)", "x=>(x,0)");
     test("x => {y}", "x=>{y}");
     test("(a = (b, c), ...d) => {}", "(a=(b,c),...d)=>{}");
-    test("({[1 + 2]: a = 3} = {[1 + 2]: 3}) => {}", "({[1+2]:a=3}={[1+2]:3})=>{}");
+    test("({[1 + x]: a = 3} = {[1 + x]: 3}) => {}", "({[1+x]:a=3}={[1+x]:3})=>{}");
     test(
         "([a = (1, 2), ...[b, ...c]] = [1, [2, 3]]) => {}",
         "([a=(1,2),...[b,...c]]=[1,[2,3]])=>{}",
@@ -560,8 +560,10 @@ fn whitespace() {
     test("x > !--y", "x>!--y");
     test("!--y", "!--y");
 
-    test("1 + -0", "1+-0");
-    test("1 - -0", "1- -0");
+    test("1 + -0", "1");
+    test("1 - -0", "1");
+    test("x + -0", "x+-0");
+    test("x - -0", "x- -0");
     // test("1 + -Infinity", "1+-1/0");
     // test("1 - -Infinity", "1- -1/0");
 
@@ -611,8 +613,8 @@ fn minify() {
     test("(a = 0) => {}", "(a=0)=>{}");
     test("(a, b) => {}", "(a,b)=>{}");
 
-    test("true ** 2", "(!0)**2");
-    test("false ** 2", "(!1)**2");
+    test("true ** x", "(!0)**x");
+    test("false ** x", "(!1)**x");
 
     // test("import a from 'path'", "import a from'path'");
     // test("import * as ns from 'path'", "import*as ns from'path'");
@@ -27,7 +27,7 @@ pub(crate) fn test_same(source_text: &str) {
 pub(crate) fn test_reparse(source_text: &str) {
     let source_type = SourceType::default();
     let options = MinifierOptions { mangle: false, ..MinifierOptions::default() };
-    let minified = Minifier::new(source_text, source_type, options).build();
+    let minified = Minifier::new(source_text, source_type, options.clone()).build();
     let minified2 = Minifier::new(&minified, source_type, options).build();
     assert_eq!(minified, minified2, "for source {source_text}");
 }
@@ -35,8 +35,26 @@ pub(crate) fn test_reparse(source_text: &str) {
 pub(crate) fn test_without_compress_booleans(source_text: &str, expected: &str) {
     let source_type = SourceType::default();
     let compress_options = CompressOptions { booleans: false, ..CompressOptions::default() };
-    let options =
-        MinifierOptions { mangle: false, compress: compress_options, print: PrinterOptions };
+    let options = MinifierOptions {
+        mangle: false,
+        compress: compress_options,
+        print: PrinterOptions,
+        ..MinifierOptions::default()
+    };
     let minified = Minifier::new(source_text, source_type, options).build();
     assert_eq!(expected, minified, "for source {source_text}");
 }
+
+/// Keeps statements which the `conditionals`, `sequences` and `if_return` options would join into
+/// expressions
+pub(crate) fn test_without_compress_statements(source_text: &str, expected: &str) {
+    let compress_options = CompressOptions {
+        conditionals: false,
+        sequences: false,
+        if_return: false,
+        ..CompressOptions::default()
+    };
+    let options =
+        MinifierOptions { mangle: false, compress: compress_options, ..MinifierOptions::default() };
+    test_with_options(source_text, expected, options);
+}
@@ -25,8 +25,8 @@ fn console_removal() {
         compress: CompressOptions { drop_console: true, ..CompressOptions::default() },
         ..MinifierOptions::default()
     };
-    test_with_options("console.log('hi')", "", options);
-    test_with_options("let x = console.error('oops')", "let x", options);
+    test_with_options("console.log('hi')", "", options.clone());
+    test_with_options("let x = console.error('oops')", "let x", options.clone());
     test_with_options(
         "function f() { return console.warn('problem') }",
         "function f(){return}",
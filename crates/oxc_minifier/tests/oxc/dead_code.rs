@@ -0,0 +1,128 @@
+use crate::{
+    test, test_same, test_with_options, test_without_compress_statements, CompressOptions,
+    MinifierOptions,
+};
+
+#[test]
+fn unreachable_statements() {
+    test("function f(){return 1;foo()}", "function f(){return 1}");
+    test("function f(){throw 1;foo()}", "function f(){throw 1}");
+    test("for(;;){break;foo()}", "for(;;)break");
+    test("for(;;){continue;foo()}", "for(;;)continue");
+    test_without_compress_statements(
+        "function f(){if(a)return 1;else return 2;foo()}",
+        "function f(){if(a)return 1; else return 2}",
+    );
+    test("function f(){{return}foo()}", "function f(){return}");
+    test_same("function f(){if(a)return;foo()}");
+    test("a:{break a}foo()", "a:break a;foo()");
+    test("function f(){for(;;){}foo()}", "function f(){for(;;){}}");
+    test(
+        "function f(){switch(a){case 1:return;foo();case 2:bar()}}",
+        "function f(){switch(a){case 1:return;case 2:bar()}}",
+    );
+    test_same("function f(){for(;!0;)if(a)break;foo()}");
+
+    // Declarations are kept
+    test("function f(){return g;function g(){}}", "function f(){return g;function g(){}}");
+    test("function f(){return x;var x=1}", "function f(){return x;var x}");
+    test("function f(){return x;if(a){var x=1}}", "function f(){return x;var x}");
+    test("function f(){return x;let x=1}", "function f(){return x;let x}");
+    test("function f(){return x;const{x,y:[z]}=a}", "function f(){return x;let x,z}");
+    test("function f(){return X;class X{}}", "function f(){return X;let X}");
+    // Unless they are only referenced by unreachable code
+    test("function f(){if(a)return 1;else return 2;var x=1;foo(x)}", "function f(){return a?1:2}");
+}
+
+#[test]
+fn dead_branches() {
+    test("if(true)a();else b()", "a()");
+    test("if(false)a();else b()", "b()");
+    test("if(false)a()", "");
+    test_without_compress_statements("if(!0){a();b()}", "{a();b()}");
+    test("if(1===2)a()", "");
+    test("if(typeof 1=='number')a()", "a()");
+    test("if(false){var x=1}", "var x");
+    test("if(false){function f(){}}", "var f");
+    test("if(true)a();else{var x=1}", "{a();var x}");
+    test("if(true){let x=1}", "{let x=1}");
+    test_without_compress_statements("if(a())b()", "if(a())b()");
+    test_without_compress_statements("if(x=false)a()", "if(x=!1)a()");
+
+    test("for(;false;)a()", "");
+    test("while(false)a()", "");
+    test("for(a();false;)b()", "a()");
+    test("for(var i=0;false;i++)a()", "var i=0");
+    test_same("for(let i=0;!1;i++)a()");
+
+    test("x=true?a:b", "x=a");
+    test("x=false?a:b", "x=b");
+    test("x=a()?b:c", "x=a()?b:c");
+    test("x=1+1===2?a:b", "x=a");
+}
+
+#[test]
+fn unused_declarations() {
+    test("function f(){var a=1,b=g();return b}", "function f(){var b=g();return b}");
+    test("function f(){var a=g()}", "function f(){var a=g()}");
+    test("function f(){let a={b:1};const c=function(){}}", "function f(){}");
+    test("function f(){function g(){}}", "function f(){}");
+    test(
+        "function f(){function g(){}return function(){return g}}",
+        "function f(){function g(){}return function(){return g}}",
+    );
+    test("function f(){var a;a=1}", "function f(){var a;a=1}");
+    test("function f(){var a=1;eval('a')}", "function f(){var a=1;eval('a')}");
+    test("function f(){var {a}=b}", "function f(){var {a:a}=b}");
+    // References are resolved to their bindings
+    test(
+        "function f(){var a=1;function g(a){return a}return g}",
+        "function f(){function g(a){return a}return g}",
+    );
+
+    // Initializers which are overwritten before they are read
+    test("function f(){var a=1;a=2;return a}", "function f(){var a;return a=2,a}");
+    test_same("function f(){var a=1;return b&&(a=2),a}");
+    test_same("function f(){var a=1;for(;;)g(a),a=2}");
+    test_same("function f(){var a=1;try{g(),a=2}catch(e){}return a}");
+    test_same("function f(){var a=1;return function(){return a}}");
+    test_same("function f(){var a=1,b=a;return a=2,a+b}");
+    test_same("function f(){const a=1;return a}");
+
+    // Top level bindings are globals
+    test_same("var a=1");
+    test_same("function f(){}");
+
+    let options = MinifierOptions {
+        mangle: false,
+        compress: CompressOptions { unused: false, ..CompressOptions::default() },
+        ..MinifierOptions::default()
+    };
+    test_with_options("function f(){var a=1}", "function f(){var a=1}", options);
+}
+
+#[test]
+fn pure_calls() {
+    test("/* #__PURE__ */ f()", "");
+    test("/* @__PURE__ */ new A", "");
+    test("/*#__PURE__*/f(a(),1)", "a()");
+    // Reading an undeclared variable throws
+    test("/*#__PURE__*/f(a(),/*#__PURE__*/g(b()),c)", "a(),b(),c");
+    test("function h(c){/*#__PURE__*/f(a(),c,Math)}", "function h(c){a()}");
+    test("function f(){var a=/*#__PURE__*/g(1)}", "function f(){}");
+    test_same("/*#__PURE__*/f(...a)");
+    test("/*#__PURE__*/f()()", "");
+    test_same("x=/*#__PURE__*/f()()");
+    test_same("x=/*#__PURE__*/f()");
+    test_same("x=/*#__PURE__*/new A()");
+    test("x=/*#__PURE__*/(function(){})()", "x=/*#__PURE__*/function(){}()");
+    test_same("x=a/ /*#__PURE__*/f()");
+
+    let options = MinifierOptions {
+        mangle: false,
+        compress: CompressOptions { side_effects: false, ..CompressOptions::default() },
+        ..MinifierOptions::default()
+    };
+    test_with_options("/*#__PURE__*/f(a())", "/*#__PURE__*/f(a())", options.clone());
+    test_with_options("/*#__PURE__*/(function(){})()", "/*#__PURE__*/(function(){})()", options);
+}
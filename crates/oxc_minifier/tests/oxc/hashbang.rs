@@ -0,0 +1,8 @@
+use crate::test;
+
+#[test]
+fn hashbang() {
+    test("#!/usr/bin/env node\nfoo()", "#!/usr/bin/env node\nfoo()");
+    test("#!/usr/bin/env node\r\nfoo()", "#!/usr/bin/env node\nfoo()");
+    test("#!/usr/bin/env node", "#!/usr/bin/env node");
+}
@@ -0,0 +1,35 @@
+use oxc_minifier::{LicenseComments, Minifier, MinifierOptions, NameCache};
+use oxc_span::SourceType;
+
+use crate::{test, test_with_options};
+
+#[test]
+fn inline() {
+    test("/*! Copyright */ a(); // b\n/* c */ b()", "/*! Copyright */\na(),b()");
+    test("// @license MIT\nvar a = 1", "// @license MIT\nvar a=1");
+    test("/** @preserve */\nfunction f() {}", "/** @preserve */\nfunction f(){}");
+    test("a();\n/*! b */\nfunction f() {}", "a();\n/*! b */\nfunction f(){}");
+    test("function f() { a(); /*! b */ for (;;) c() }", "function f(){a();\n/*! b */\nfor(;;)c()}");
+    test("a(); /*! b */", "a();\n/*! b */\n");
+
+    let options = MinifierOptions {
+        mangle: false,
+        license_comments: LicenseComments::None,
+        ..MinifierOptions::default()
+    };
+    test_with_options("/*! Copyright */ a()", "a()", options);
+}
+
+#[test]
+fn extract() {
+    let options = MinifierOptions {
+        mangle: false,
+        license_comments: LicenseComments::Extract,
+        ..MinifierOptions::default()
+    };
+    let source_text = "/*! a */ a(); // @license b\n/* c */ b()";
+    let ret = Minifier::new(source_text, SourceType::default(), options)
+        .minify(&mut NameCache::default());
+    assert_eq!(ret.source_text, "a(),b()");
+    assert_eq!(ret.license_comments, vec!["/*! a */", "// @license b"]);
+}
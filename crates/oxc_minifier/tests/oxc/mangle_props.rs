@@ -0,0 +1,132 @@
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions, NameCache, PropertyManglerOptions};
+use oxc_span::SourceType;
+use regex::Regex;
+
+use crate::test_with_options;
+
+fn mangle_props(source_text: &str, expected: &str, options: PropertyManglerOptions) {
+    let options = MinifierOptions {
+        mangle: false,
+        // Keep the statements apart
+        compress: CompressOptions { sequences: false, ..CompressOptions::default() },
+        mangle_props: Some(options),
+        ..MinifierOptions::default()
+    };
+    test_with_options(source_text, expected, options);
+}
+
+fn private_props() -> PropertyManglerOptions {
+    PropertyManglerOptions {
+        regex: Some(Regex::new("^_[a-z]").unwrap()),
+        ..PropertyManglerOptions::default()
+    }
+}
+
+#[test]
+fn properties() {
+    mangle_props(
+        "var foo = { bar: 1, baz() {} }; foo.bar = foo.baz(); class Foo { qux = 1; constructor() { this.bar = 2; } }",
+        "var foo={a:1,b(){}};foo.a=foo.b();class Foo{c=1;constructor(){this.a=2}}",
+        PropertyManglerOptions::default(),
+    );
+    // The most frequent properties get the shortest names
+    mangle_props(
+        "foo.bar; foo.baz; foo.baz",
+        "foo.b;foo.a;foo.a",
+        PropertyManglerOptions::default(),
+    );
+    // Shorthand assignment targets keep their names
+    mangle_props(
+        "var { bar, baz: qux } = foo; ({ bar } = foo)",
+        "var {bar:bar,a:qux}=foo;({bar}=foo)",
+        PropertyManglerOptions::default(),
+    );
+}
+
+#[test]
+fn regex() {
+    mangle_props(
+        "class Foo { _count = 0; increment() { return ++this._count; } } foo._bar = foo.bar",
+        "class Foo{a=0;increment(){return ++this.a}}foo.b=foo.bar",
+        private_props(),
+    );
+}
+
+#[test]
+fn reserved() {
+    // Reserved names are neither mangled nor used as mangled names
+    mangle_props(
+        "foo.a = foo.b + foo.bar",
+        "foo.a=foo.b+foo.c",
+        PropertyManglerOptions {
+            reserved: vec!["a".to_string(), "b".to_string()],
+            ..PropertyManglerOptions::default()
+        },
+    );
+    // Quoted properties keep their names everywhere
+    mangle_props(
+        "foo._bar = foo['_baz'] + foo._baz + { '_qux': 1 }._qux",
+        "foo.a=foo['_baz']+foo._baz+{'_qux':1}._qux",
+        private_props(),
+    );
+    mangle_props(
+        "foo.constructor.prototype.__proto__ = foo._bar",
+        "foo.constructor.prototype.__proto__=foo.a",
+        PropertyManglerOptions::default(),
+    );
+}
+
+#[test]
+fn builtins() {
+    // The properties of built-in objects and of the DOM are reserved, and `x` is skipped as a
+    // mangled name
+    mangle_props(
+        "console.log(foo.bar.length); document.body.addEventListener('click', foo.x)",
+        "console.log(foo.a.length);document.body.addEventListener('click',foo.x)",
+        PropertyManglerOptions::default(),
+    );
+    mangle_props(
+        "foo.bar.length = foo.length",
+        "foo.b.a=foo.a",
+        PropertyManglerOptions { builtins: true, ..PropertyManglerOptions::default() },
+    );
+}
+
+#[test]
+fn name_cache() {
+    let source_type = SourceType::default();
+    let options = MinifierOptions {
+        mangle: false,
+        mangle_props: Some(private_props()),
+        ..MinifierOptions::default()
+    };
+
+    let mut name_cache = NameCache::default();
+    let printed = Minifier::new("foo._bar = foo._baz", source_type, options.clone())
+        .build_with_name_cache(&mut name_cache);
+    assert_eq!(printed, "foo.a=foo.b");
+    let json = name_cache.to_json();
+    assert_eq!(json, r#"{"props":{"props":{"$_bar":"a","$_baz":"b"}}}"#);
+
+    // Another file keeps the names of the cache and does not reuse them for new properties
+    let mut name_cache = NameCache::from_json(&json).unwrap();
+    let printed = Minifier::new("foo._qux = foo._baz + foo._baz", source_type, options.clone())
+        .build_with_name_cache(&mut name_cache);
+    assert_eq!(printed, "foo.c=foo.b+foo.b");
+    assert_eq!(name_cache.get("_qux"), Some("c"));
+
+    // A name of the cache which is a property keeping its name here is replaced
+    let mut name_cache = NameCache::from_json(&json).unwrap();
+    let printed = Minifier::new("foo._bar = foo.a", source_type, options)
+        .build_with_name_cache(&mut name_cache);
+    assert_eq!(printed, "foo.c=foo.a");
+    assert_eq!(name_cache.get("_bar"), Some("c"));
+}
+
+#[test]
+fn name_cache_json() {
+    assert_eq!(NameCache::from_json(r#"{"vars":{"props":{}}}"#).unwrap(), NameCache::default());
+    assert!(NameCache::from_json("[]").is_err());
+    assert!(NameCache::from_json(r#"{"props":{"$_bar":"a"}}"#).is_err());
+    assert!(NameCache::from_json(r#"{"props":{"props":{"$_bar":1}}}"#).is_err());
+}
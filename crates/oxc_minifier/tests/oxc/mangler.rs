@@ -0,0 +1,89 @@
+use oxc_minifier::{ManglerOptions, Minifier, MinifierOptions};
+use oxc_span::SourceType;
+
+use crate::test_with_options;
+
+fn mangle(source_text: &str, expected: &str) {
+    test_with_options(source_text, expected, MinifierOptions::default());
+}
+
+fn mangle_with_options(source_text: &str, expected: &str, options: ManglerOptions) {
+    let options = MinifierOptions { mangler: options, ..MinifierOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn locals() {
+    mangle(
+        "function foo(first, second) { var sum = first + second; return sum * sum; }",
+        "function foo(a,b){var c=a+b;return c*c}",
+    );
+    // The top level scope keeps its names, which are globals in scripts
+    mangle(
+        "var foo = 1; function bar(baz) { return foo + baz; }",
+        "var foo=1;function bar(a){return foo+a}",
+    );
+    mangle(
+        "function foo(bar) { return function baz(qux) { return bar(qux, baz); }; }",
+        "function foo(a){return function baz(b){return a(b,baz)}}",
+    );
+    mangle(
+        "function foo() { try { bar(); } catch (error) { baz(error); } }",
+        "function foo(){try{bar()}catch(a){baz(a)}}",
+    );
+}
+
+#[test]
+fn eval() {
+    // Code evaluated by a direct `eval` can reference the bindings of its scopes by name
+    mangle(
+        "function foo(bar) { var baz = 1; function qux(quux) { return eval('bar + quux'); } return qux; }",
+        "function foo(bar){var baz=1;function qux(quux){return eval('bar + quux')}return qux}",
+    );
+    mangle(
+        "function foo(bar) { return bar; } function baz(qux) { return eval('qux'); }",
+        "function foo(a){return a}function baz(qux){return eval('qux')}",
+    );
+}
+
+#[test]
+fn reserved() {
+    mangle_with_options(
+        "function foo(a, $, bar) { return a + $ + bar; }",
+        "function foo(a,$,b){return a+$+b}",
+        ManglerOptions {
+            reserved: vec!["$".to_string(), "a".to_string()],
+            ..ManglerOptions::default()
+        },
+    );
+}
+
+#[test]
+fn keep_names() {
+    let source_text = "function foo() { function bar() {} class Baz {} return [bar, Baz]; }";
+    mangle(source_text, "function foo(){function a(){}class b{}return [a,b]}");
+    mangle_with_options(
+        source_text,
+        "function foo(){function bar(){}class a{}return [bar,a]}",
+        ManglerOptions { keep_fnames: true, ..ManglerOptions::default() },
+    );
+    mangle_with_options(
+        source_text,
+        "function foo(){function a(){}class Baz{}return [a,Baz]}",
+        ManglerOptions { keep_classnames: true, ..ManglerOptions::default() },
+    );
+}
+
+#[test]
+fn module() {
+    // The top level bindings of modules are local, except the exported ones
+    let source_text = "import { foo } from 'a'; import bar, * as baz from 'b';
+        const qux = foo(bar, baz); export const quux = qux;
+        function corge(grault) { return qux + grault; } export { corge as default };";
+    let source_type = SourceType::default().with_module(true);
+    let minified = Minifier::new(source_text, source_type, MinifierOptions::default()).build();
+    assert_eq!(
+        minified,
+        "import {foo as a} from 'a';import b,* as c from 'b';const d=a(b,c);export const quux=d;function corge(e){return d+e}export {corge as default}"
+    );
+}
@@ -1,2 +1,7 @@
 mod code_removal;
+mod dead_code;
+mod hashbang;
+mod license_comments;
+mod mangle_props;
+mod mangler;
 mod precedence;
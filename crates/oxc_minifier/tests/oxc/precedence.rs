@@ -161,3 +161,12 @@ fn equality() {
     test("a, b == c , d", "a,b==c,d");
     test("(a, b) == (c , d)", "(a,b)==(c,d)");
 }
+
+#[test]
+fn chain() {
+    test("a?.b.c", "a?.b.c");
+    test("(a?.b)", "a?.b");
+    test("(a?.b).c", "(a?.b).c");
+    test("(a?.b)()", "(a?.b)()");
+    test("(a?.())[b]", "(a?.())[b]");
+}
@@ -91,7 +91,7 @@ fn tdewolff() {
     test("export default a = b", "export default a=b");
     test("export default a = b;c=d", "export default a=b;c=d");
     // expect("export default function a(){};c=d", "export default function(){}c=d");
-    test("!class {}", "!class{}");
+    test("!class extends a {}", "!class extends a{}");
     test("class a {}", "class a{}");
     test("class a extends b {}", "class a extends b{}");
     // expect("class a extends(!b){}", "class a extends(!b){}");
@@ -0,0 +1,46 @@
+booleans_literals: {
+    options = {
+        booleans: true,
+        sequences: false,
+    }
+    input: {
+        x = true;
+        x = false;
+    }
+    expect: {
+        x = !0;
+        x = !1
+    }
+}
+
+booleans_double_negation: {
+    options = {
+        booleans: true,
+        sequences: false,
+    }
+    input: {
+        if (!!a) b();
+        x = !!!a;
+    }
+    expect: {
+        a && b();
+        x = !a
+    }
+}
+
+booleans_undefined: {
+    options = {
+        booleans: true,
+        sequences: false,
+    }
+    input: {
+        x = undefined;
+        x = void 0;
+        x = void 1;
+    }
+    expect: {
+        x = void 0;
+        x = void 0;
+        x = void 0
+    }
+}
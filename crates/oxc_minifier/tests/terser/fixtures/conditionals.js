@@ -0,0 +1,43 @@
+if_to_conditional: {
+    options = {
+        conditionals: true,
+    }
+    input: {
+        if (a) b(); else c();
+        if (!a) b(); else c();
+    }
+    expect: {
+        a ? b() : c(), a ? c() : b()
+    }
+}
+
+if_to_logical: {
+    options = {
+        conditionals: true,
+        sequences: false,
+    }
+    input: {
+        if (a) b();
+        if (!a) b();
+        if (a) {} else b();
+        if (a) {}
+    }
+    expect: {
+        a && b();
+        a || b();
+        a || b();
+        a
+    }
+}
+
+keep_if: {
+    options = {
+        conditionals: false,
+    }
+    input: {
+        if (a) b(); else c();
+    }
+    expect: {
+        if (a) b(); else c()
+    }
+}
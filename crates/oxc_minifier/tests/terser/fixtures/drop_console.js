@@ -0,0 +1,27 @@
+drop_console: {
+    options = {
+        drop_console: true,
+    }
+    input: {
+        console.log(a);
+        b();
+    }
+    expect: {
+        b()
+    }
+}
+
+keep_console: {
+    options = {
+        drop_console: false,
+        sequences: false,
+    }
+    input: {
+        console.log(a);
+        b();
+    }
+    expect: {
+        console.log(a);
+        b()
+    }
+}
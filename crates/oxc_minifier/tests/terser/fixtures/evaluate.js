@@ -0,0 +1,75 @@
+evaluate_arithmetic: {
+    options = {
+        evaluate: true,
+        sequences: false,
+    }
+    input: {
+        x = 1 + 2;
+        x = 2 * 3 - 1;
+        x = 10 / 4;
+        x = 7 % 3;
+        x = 2 ** 10;
+        x = 1 << 4;
+        x = -1 >>> 28;
+    }
+    expect: {
+        x = 3;
+        x = 5;
+        x = 2.5;
+        x = 1;
+        x = 1024;
+        x = 16;
+        x = 15
+    }
+}
+
+evaluate_string_concatenation: {
+    options = {
+        evaluate: true,
+        sequences: false,
+    }
+    input: {
+        x = "a" + "b";
+        x = "a" + 1;
+        x = 1 + "a";
+        x = "a" + true + null;
+        x = `a${1}b`;
+    }
+    expect: {
+        x = 'ab';
+        x = 'a1';
+        x = '1a';
+        x = 'atruenull';
+        x = 'a1b'
+    }
+}
+
+evaluate_keep_longer: {
+    options = {
+        evaluate: true,
+        sequences: false,
+    }
+    input: {
+        x = 1 / 3;
+        x = 1 + y;
+    }
+    expect: {
+        x = 1 / 3;
+        x = 1 + y
+    }
+}
+
+evaluate_side_effects: {
+    options = {
+        evaluate: true,
+        sequences: false,
+    }
+    input: {
+        x = f() + 1 + 2;
+        x = 1 + 2 + f();
+    }
+    expect: {
+        x = f() + 1 + 2;
+        x = 3 + f()
+    }
+}
@@ -0,0 +1,40 @@
+if_else_return: {
+    options = {
+        if_return: true,
+    }
+    input: {
+        function f() {
+            if (a) return b; else return c;
+        }
+        function g() {
+            if (a) return;
+            return c;
+        }
+    }
+    expect: {
+        function f() {
+            return a ? b : c
+        }
+        function g() {
+            return a ? void 0 : c
+        }
+    }
+}
+
+keep_if_return: {
+    options = {
+        if_return: false,
+    }
+    input: {
+        function f() {
+            if (a) return b;
+            return c;
+        }
+    }
+    expect: {
+        function f() {
+            if (a) return b;
+            return c
+        }
+    }
+}
@@ -0,0 +1,29 @@
+join_vars: {
+    options = {
+        join_vars: true,
+    }
+    input: {
+        var a = 1;
+        var b = 2;
+        let c = 3;
+        let d = 4;
+    }
+    expect: {
+        var a = 1, b = 2;
+        let c = 3, d = 4
+    }
+}
+
+keep_vars: {
+    options = {
+        join_vars: false,
+    }
+    input: {
+        var a = 1;
+        var b = 2;
+    }
+    expect: {
+        var a = 1;
+        var b = 2
+    }
+}
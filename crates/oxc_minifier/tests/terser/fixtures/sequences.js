@@ -0,0 +1,51 @@
+join_expressions: {
+    options = {
+        sequences: true,
+    }
+    input: {
+        a();
+        b();
+        c();
+    }
+    expect: {
+        a(), b(), c()
+    }
+}
+
+join_into_statements: {
+    options = {
+        conditionals: false,
+        sequences: true,
+    }
+    input: {
+        function f() {
+            a();
+            if (b) c();
+            d();
+            return e;
+        }
+        g();
+        throw h;
+    }
+    expect: {
+        function f() {
+            if (a(), b) c();
+            return d(), e
+        }
+        throw g(), h
+    }
+}
+
+keep_sequences: {
+    options = {
+        sequences: false,
+    }
+    input: {
+        a();
+        b();
+    }
+    expect: {
+        a();
+        b()
+    }
+}
@@ -69,6 +69,7 @@ impl TestCase {
             mangle: false,
             compress: self.compress_options,
             print: PrinterOptions,
+            ..MinifierOptions::default()
         };
         let minified_source_text = Minifier::new(self.input.as_ref(), source_type, options).build();
         assert_eq!(
@@ -134,14 +135,28 @@ impl TestCase {
         Self { name, compress_options: options, input, expect }
     }
 
-    #[allow(clippy::single_match)]
     fn parse_options<'a>(object_expr: &'a ObjectExpression<'a>) -> CompressOptions {
         let mut options = CompressOptions::default();
         for object_property in &object_expr.properties {
             if let ObjectPropertyKind::ObjectProperty(property) = object_property {
-                if property.key.is_specific_static_name("drop_debugger") {
-                    options.drop_debugger = Self::get_boolean(&property.value);
-                }
+                let Some(name) = property.key.static_name() else { continue };
+                let option = match name.as_str() {
+                    "booleans" => &mut options.booleans,
+                    "conditionals" => &mut options.conditionals,
+                    "dead_code" => &mut options.dead_code,
+                    "drop_console" => &mut options.drop_console,
+                    "drop_debugger" => &mut options.drop_debugger,
+                    "evaluate" => &mut options.evaluate,
+                    "if_return" => &mut options.if_return,
+                    "join_vars" => &mut options.join_vars,
+                    "loops" => &mut options.loops,
+                    "sequences" => &mut options.sequences,
+                    "side_effects" => &mut options.side_effects,
+                    "typeofs" => &mut options.typeofs,
+                    "unused" => &mut options.unused,
+                    _ => panic!("unknown compress option {name}"),
+                };
+                *option = Self::get_boolean(&property.value);
             }
         }
         options
@@ -38,3 +38,17 @@ export function parseSync(sourceText: string, options?: ParserOptions | undefine
  * * Tokio crashes
  */
 export function parseAsync(sourceText: string, options?: ParserOptions | undefined | null): Promise<ParseResult>
+/**
+ * Find the nodes matching an [esquery](https://github.com/estools/esquery) selector,
+ * e.g. `CallExpression[callee.name="require"]`, returned as JSON strings in source order.
+ *
+ * # Errors
+ *
+ * * The selector is invalid
+ *
+ * # Panics
+ *
+ * * File extension is invalid
+ * * Serde JSON serialization
+ */
+export function querySync(sourceText: string, selector: string, options?: ParserOptions | undefined | null): Array<string>
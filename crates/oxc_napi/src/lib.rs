@@ -95,3 +95,31 @@ pub fn parse_sync(source_text: String, options: Option<ParserOptions>) -> ParseR
 pub async fn parse_async(source_text: String, options: Option<ParserOptions>) -> ParseResult {
     tokio::spawn(async move { parse_sync(source_text, options) }).await.unwrap()
 }
+
+/// Find the nodes matching an [esquery](https://github.com/estools/esquery) selector,
+/// e.g. `CallExpression[callee.name="require"]`, returned as JSON strings in source order.
+///
+/// # Errors
+///
+/// * The selector is invalid
+///
+/// # Panics
+///
+/// * File extension is invalid
+/// * Serde JSON serialization
+#[allow(clippy::needless_pass_by_value)]
+#[napi]
+pub fn query_sync(
+    source_text: String,
+    selector: String,
+    options: Option<ParserOptions>,
+) -> napi::Result<Vec<String>> {
+    let options = options.unwrap_or_default();
+    let allocator = Allocator::default();
+    let ret = parse(&allocator, &source_text, &options);
+    let nodes = ret
+        .program
+        .query(&selector)
+        .map_err(|error| napi::Error::from_reason(error.to_string()))?;
+    Ok(nodes.iter().map(|node| serde_json::to_string(node).unwrap()).collect())
+}
@@ -12,6 +12,10 @@ function test(ret) {
 
 test(oxc.parseSync("foo"));
 
+const requires = oxc.querySync("require('a'); foo('b')", "CallExpression[callee.name='require']");
+assert(requires.length == 1);
+assert(JSON.parse(requires[0]).arguments[0].value == "a");
+
 async function main() {
   test(await oxc.parseAsync("foo"));
 }
@@ -95,6 +95,11 @@ impl Context {
         self.union_if(Self::Yield, include)
     }
 
+    #[inline]
+    pub(crate) fn union_ambient_if(self, include: bool) -> Self {
+        self.union_if(Self::Ambient, include)
+    }
+
     #[inline]
     fn union_if(self, other: Self, include: bool) -> Self {
         if include {
@@ -1,5 +1,6 @@
 //! Code related to navigating `Token`s from the lexer
 
+use oxc_ast::TokenSpanKind;
 use oxc_diagnostics::Result;
 use oxc_span::Span;
 
@@ -98,10 +99,31 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Record the span of the current token in [`oxc_ast::TokenSpans`] if it is one of its kinds
+    #[inline]
+    fn record_token_span(&mut self, kind: Kind) {
+        let kind = match kind {
+            Kind::Arrow => TokenSpanKind::Arrow,
+            Kind::Question => TokenSpanKind::Question,
+            Kind::Colon => TokenSpanKind::Colon,
+            Kind::Catch => TokenSpanKind::Catch,
+            Kind::Else => TokenSpanKind::Else,
+            Kind::Extends => TokenSpanKind::Extends,
+            Kind::Finally => TokenSpanKind::Finally,
+            Kind::Implements => TokenSpanKind::Implements,
+            Kind::In => TokenSpanKind::In,
+            Kind::Of => TokenSpanKind::Of,
+            Kind::While => TokenSpanKind::While,
+            _ => return,
+        };
+        self.token_spans.add(kind, self.cur_token().span());
+    }
+
     /// Move to the next token
     /// Checks if the current token is escaped if it is a keyword
     fn advance(&mut self, kind: Kind) {
         self.test_escaped_keyword(kind);
+        self.record_token_span(kind);
         self.prev_token_end = self.token.end;
         self.token = self.lexer.next_token();
     }
@@ -110,6 +132,7 @@ impl<'a> Parser<'a> {
     /// Checks if the current token is escaped if it is a keyword
     fn advance_for_jsx_child(&mut self, kind: Kind) {
         self.test_escaped_keyword(kind);
+        self.record_token_span(kind);
         self.prev_token_end = self.token.end;
         self.token = self.lexer.next_jsx_child();
     }
@@ -245,6 +268,7 @@ impl<'a> Parser<'a> {
             checkpoint;
 
         self.lexer.rewind(lexer);
+        self.token_spans.truncate(cur_token.start);
         self.token = cur_token;
         self.prev_token_end = prev_span_end;
         self.errors.truncate(errors_lens);
@@ -257,7 +281,10 @@ impl<'a> Parser<'a> {
     ) -> Result<T> {
         let checkpoint = self.checkpoint();
         let ctx = self.ctx;
+        // A failed attempt must return its error for the caller to try something else
+        let recover = std::mem::replace(&mut self.recover, false);
         let result = func(self);
+        self.recover = recover;
         if result.is_err() {
             self.ctx = ctx;
             self.rewind(checkpoint);
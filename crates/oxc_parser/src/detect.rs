@@ -0,0 +1,162 @@
+//! Module or script detection from the content of a source text
+
+use oxc_allocator::Allocator;
+use oxc_ast::{
+    ast::{Expression, Program, Statement},
+    AstKind, Visit,
+};
+use oxc_span::{ModuleKind, SourceType};
+
+use crate::Parser;
+
+/// Detect whether `source_text` is an ES module or a script from its content,
+/// for files whose extension does not tell, such as `.js` and `.jsx`.
+///
+/// * `import` and `export` declarations, `import.meta` and top-level `await` make a module.
+/// * CommonJS markers make a script: `require(...)`, `module` and `exports`, and top-level `return`.
+/// * Syntax which is not allowed in the strict mode of modules makes a script,
+///   e.g. `await` as an identifier, `with` statements and legacy octal literals such as `017`.
+/// * Otherwise it is a module, same as [`SourceType::from_path`].
+///
+/// ```
+/// use oxc_parser::detect_module_kind;
+/// use oxc_span::{ModuleKind, SourceType};
+///
+/// let source_type = SourceType::from_path("index.js").unwrap();
+/// assert_eq!(detect_module_kind("const fs = require('fs');", source_type), ModuleKind::Script);
+/// assert_eq!(detect_module_kind("import fs from 'fs';", source_type), ModuleKind::Module);
+/// ```
+pub fn detect_module_kind(source_text: &str, source_type: SourceType) -> ModuleKind {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type.with_module(true)).parse();
+    if ret.errors.is_empty() {
+        let mut finder = ModuleSyntaxFinder::default();
+        finder.visit_program(allocator.alloc(ret.program));
+        return if !finder.has_module_syntax
+            && (finder.has_commonjs_markers || finder.has_sloppy_mode_syntax)
+        {
+            ModuleKind::Script
+        } else {
+            ModuleKind::Module
+        };
+    }
+
+    let ret = Parser::new(&allocator, source_text, source_type.with_script(true))
+        .allow_return_outside_function(true)
+        .parse();
+    if ret.errors.is_empty() {
+        ModuleKind::Script
+    } else {
+        ModuleKind::Module
+    }
+}
+
+#[derive(Default)]
+struct ModuleSyntaxFinder {
+    /// Number of functions around the current node
+    function_depth: usize,
+    has_module_syntax: bool,
+    has_commonjs_markers: bool,
+    /// Syntax errors in strict mode, which are reported by semantic analysis instead of the parser
+    has_sloppy_mode_syntax: bool,
+}
+
+impl<'a> Visit<'a> for ModuleSyntaxFinder {
+    fn visit_program(&mut self, program: &'a Program<'a>) {
+        // `export` and `import` declarations are only allowed at the top level
+        self.has_module_syntax =
+            program.body.iter().any(|stmt| matches!(stmt, Statement::ModuleDeclaration(_)));
+        if !self.has_module_syntax {
+            self.visit_statements(&program.body);
+        }
+    }
+
+    fn enter_node(&mut self, kind: AstKind<'a>) {
+        match kind {
+            AstKind::Function(_) | AstKind::ArrowExpression(_) => self.function_depth += 1,
+            AstKind::MetaProperty(meta) if meta.meta.name == "import" => {
+                self.has_module_syntax = true;
+            }
+            AstKind::AwaitExpression(_) if self.function_depth == 0 => {
+                self.has_module_syntax = true;
+            }
+            AstKind::ForOfStatement(stmt) if stmt.r#await && self.function_depth == 0 => {
+                self.has_module_syntax = true;
+            }
+            AstKind::CallExpression(call) => {
+                if matches!(&call.callee, Expression::Identifier(ident) if ident.name == "require")
+                {
+                    self.has_commonjs_markers = true;
+                }
+            }
+            AstKind::WithStatement(_) => self.has_sloppy_mode_syntax = true,
+            // LegacyOctalIntegerLiteral and NonOctalDecimalIntegerLiteral
+            AstKind::NumberLiteral(lit)
+                if lit.raw.len() > 1
+                    && lit.raw.starts_with('0')
+                    && lit.raw.as_bytes()[1].is_ascii_digit() =>
+            {
+                self.has_sloppy_mode_syntax = true;
+            }
+            AstKind::IdentifierReference(ident) => {
+                if matches!(ident.name.as_str(), "module" | "exports") {
+                    self.has_commonjs_markers = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn leave_node(&mut self, kind: AstKind<'a>) {
+        if matches!(kind, AstKind::Function(_) | AstKind::ArrowExpression(_)) {
+            self.function_depth -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::{ModuleKind, SourceType};
+
+    use super::detect_module_kind;
+
+    #[test]
+    fn module() {
+        let source_type = SourceType::from_path("index.js").unwrap();
+        let sources = [
+            "import a from 'a';",
+            "export const a = 1;",
+            "export default function () {}",
+            "console.log(import.meta.url);",
+            "const data = await fetch(url);",
+            "for await (const chunk of stream) {}",
+            // Module syntax wins over CommonJS markers
+            "import { createRequire } from 'module'; const require = createRequire(import.meta.url); require('a');",
+            // Without any markers
+            "const a = 1;",
+            "",
+        ];
+        for source in sources {
+            assert_eq!(detect_module_kind(source, source_type), ModuleKind::Module, "{source}");
+        }
+    }
+
+    #[test]
+    fn script() {
+        let source_type = SourceType::from_path("index.js").unwrap();
+        let sources = [
+            "const fs = require('fs');",
+            "module.exports = { a: 1 };",
+            "exports.a = 1;",
+            "if (!process.env.A) { return; }",
+            "var await = 1;",
+            "var a = 017;",
+            "with (Math) { max(a, b); }",
+            // `await` inside a function is not top-level await
+            "async function f() { await g(); } module.exports = f;",
+        ];
+        for source in sources {
+            assert_eq!(detect_module_kind(source, source_type), ModuleKind::Script, "{source}");
+        }
+    }
+}
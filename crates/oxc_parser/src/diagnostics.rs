@@ -9,6 +9,18 @@ use oxc_span::{Atom, Span};
 #[diagnostic()]
 pub struct Flow(#[label] pub Span);
 
+#[derive(Debug, Error, Diagnostic)]
+#[error("Expected a closing tag for this JSX element")]
+#[diagnostic(help(
+    "Type parameters of arrow functions are parsed as JSX in a .tsx file, write `<T,>` or `<T extends unknown>` instead"
+))]
+pub struct GenericArrowInJsx(#[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Too many errors, parsing stopped after {0} errors")]
+#[diagnostic()]
+pub struct TooManyErrors(pub usize, #[label] pub Span);
+
 #[derive(Debug, Error, Diagnostic)]
 #[error("Unexpected token")]
 #[diagnostic()]
@@ -88,6 +100,16 @@ pub struct InvalidDestrucuringDeclaration(#[label] pub Span);
 #[diagnostic()]
 pub struct MissinginitializerInConst(#[label] pub Span);
 
+#[derive(Debug, Error, Diagnostic)]
+#[error("Missing initializer in using declaration")]
+#[diagnostic()]
+pub struct MissingInitializerInUsing(#[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Using declarations may not have binding patterns")]
+#[diagnostic()]
+pub struct UsingDeclarationBindingPattern(#[label] pub Span);
+
 #[derive(Debug, Error, Diagnostic)]
 #[error("Lexical declaration cannot appear in a single-statement context")]
 #[diagnostic(help("Wrap this declaration in a block statement"))]
@@ -281,3 +303,15 @@ pub struct ReturnStatementOnlyInFunctionBody(#[label] pub Span);
 #[error("TS18007: JSX expressions may not use the comma operator.")]
 #[diagnostic(help("Did you mean to write an array?"))]
 pub struct JSXExpressionsMayNotUseTheCommaOperator(#[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Decorators are not valid here")]
+#[diagnostic(help(
+    "Parameter decorators are only supported by TypeScript experimental decorators"
+))]
+pub struct ParameterDecorators(#[label] pub Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Decorators may not appear after `export` or `export default` if they also appear before `export`")]
+#[diagnostic()]
+pub struct DecoratorsBeforeAndAfterExport(#[label] pub Span);
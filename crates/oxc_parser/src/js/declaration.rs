@@ -52,6 +52,11 @@ impl<'a> Parser<'a> {
             Kind::Var => VariableDeclarationKind::Var,
             Kind::Const => VariableDeclarationKind::Const,
             Kind::Let => VariableDeclarationKind::Let,
+            Kind::Using => VariableDeclarationKind::Using,
+            Kind::Await if self.peek_at(Kind::Using) => {
+                self.bump_any(); // bump `await`
+                VariableDeclarationKind::AwaitUsing
+            }
             _ => return Err(self.unexpected()),
         };
         self.bump_any();
@@ -87,6 +92,12 @@ impl<'a> Parser<'a> {
         let init =
             self.eat(Kind::Eq).then(|| self.parse_assignment_expression_base()).transpose()?;
 
+        // UsingDeclaration : using BindingList
+        // the grammar only allows `BindingIdentifier`s in the `BindingList`
+        if kind.is_using() && !matches!(id.kind, BindingPatternKind::BindingIdentifier(_)) {
+            self.error(diagnostics::UsingDeclarationBindingPattern(id.span()));
+        }
+
         if init.is_none() && decl_ctx.parent == VariableDeclarationParent::Statement {
             // LexicalBinding[In, Yield, Await] :
             //   BindingIdentifier[?Yield, ?Await] Initializer[?In, ?Yield, ?Await] opt
@@ -97,6 +108,8 @@ impl<'a> Parser<'a> {
             } else if kind == VariableDeclarationKind::Const && !self.ctx.has_ambient() {
                 // It is a Syntax Error if Initializer is not present and IsConstantDeclaration of the LexicalDeclaration containing this LexicalBinding is true.
                 self.error(diagnostics::MissinginitializerInConst(id.span()));
+            } else if kind.is_using() {
+                self.error(diagnostics::MissingInitializerInUsing(id.span()));
             }
         }
 
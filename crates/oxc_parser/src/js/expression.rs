@@ -186,11 +186,39 @@ impl<'a> Parser<'a> {
                     .map(|literal| self.ast.literal_regexp_expression(literal))
             }
             // JSXElement, JSXFragment
-            Kind::LAngle if self.source_type.is_jsx() => self.parse_jsx_expression(),
+            Kind::LAngle if self.source_type.is_jsx() => {
+                // `<T>(x) => x` meant as a generic arrow function
+                let generic_arrow = (self.ts_enabled()
+                    && self.nth_kind(1).is_identifier()
+                    && self.nth_at(2, Kind::RAngle)
+                    && self.nth_at(3, Kind::LParen))
+                .then(|| Span::new(span.start, self.nth(2).end));
+                self.parse_jsx_expression().map_err(|error| {
+                    generic_arrow.map_or(error, |span| diagnostics::GenericArrowInJsx(span).into())
+                })
+            }
+            _ if self.recover && self.at_expression_end() => self.parse_missing_expression(),
             _ => self.parse_identifier_expression(),
         }
     }
 
+    /// Whether the current token ends an expression, so an expression expected before it is
+    /// missing rather than malformed, e.g. `let a = ;` and `f(a, b +)`
+    fn at_expression_end(&self) -> bool {
+        matches!(
+            self.cur_kind(),
+            Kind::Semicolon | Kind::Comma | Kind::RParen | Kind::RBrack | Kind::RCurly | Kind::Eof
+        )
+    }
+
+    /// An `ErrorExpression` in place of a missing expression, when recovering from errors
+    fn parse_missing_expression(&mut self) -> Result<Expression<'a>> {
+        let error = self.unexpected();
+        self.recover_error(error)?;
+        let span = Span::new(self.cur_token().start, self.cur_token().start);
+        Ok(self.ast.error_expression(span))
+    }
+
     fn parse_parenthesized_expression(&mut self, span: Span) -> Result<Expression<'a>> {
         let has_in = self.ctx.has_in();
         self.ctx = self.ctx.and_in(true);
@@ -292,12 +320,17 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_literal_bigint(&mut self) -> Result<BigintLiteral> {
         let span = self.start_span();
-        let value = match self.cur_kind() {
-            kind if kind.is_number() => self.cur_token().value.as_bigint(),
+        let base = match self.cur_kind() {
+            Kind::Decimal => NumberBase::Decimal,
+            Kind::Binary => NumberBase::Binary,
+            Kind::Octal => NumberBase::Octal,
+            Kind::Hex => NumberBase::Hex,
             _ => return Err(self.unexpected()),
         };
+        let value = self.cur_token().value.as_bigint();
+        let raw = Atom::from(self.cur_src());
         self.bump_any();
-        Ok(BigintLiteral { span: self.end_span(span), value })
+        Ok(BigintLiteral { span: self.end_span(span), value, raw, base })
     }
 
     pub(crate) fn parse_literal_regexp(&mut self) -> Result<RegExpLiteral> {
@@ -670,7 +703,8 @@ impl<'a> Parser<'a> {
             self.error(diagnostics::NewOptionalChain(span));
         }
 
-        Ok(self.ast.new_expression(span, callee, arguments, type_parameter))
+        let pure = self.is_pure_annotated(span);
+        Ok(self.ast.new_expression(span, callee, arguments, type_parameter, pure))
     }
 
     /// Section 13.3 Call Expression
@@ -710,9 +744,20 @@ impl<'a> Parser<'a> {
             break;
         }
 
+        // The annotation belongs to the outermost call, e.g. `/* #__PURE__ */ a()()`
+        if let Expression::CallExpression(call) = &mut lhs {
+            call.pure = self.is_pure_annotated(lhs_span);
+        }
+
         Ok(lhs)
     }
 
+    /// Whether the call or `new` expression starting at `span` is annotated with
+    /// `/* #__PURE__ */`
+    fn is_pure_annotated(&self, span: Span) -> bool {
+        self.lexer.trivia_builder.trivias().is_pure_annotated(self.source_text, span.start)
+    }
+
     /// `DecoratorCallExpression`[Yield, Await] :
     ///   `DecoratorMemberExpression`[?Yield, ?Await] `Arguments`[?Yield, ?Await]
     /// This is different from `CallExpression` in that it only has one level. `@a()()` is not valid Decorator.
@@ -772,6 +817,7 @@ impl<'a> Parser<'a> {
             call_arguments.elements,
             optional,
             type_parameters,
+            false,
         ))
     }
 
@@ -842,7 +888,14 @@ impl<'a> Parser<'a> {
         let lhs = if self.ctx.has_in() && self.at(Kind::PrivateIdentifier) {
             let left = self.parse_private_identifier();
             self.expect(Kind::In)?;
-            let right = self.parse_unary_expression_base(lhs_span)?;
+            // The right operand is a ShiftExpression, e.g. `#x in a + b` is `#x in (a + b)`
+            let rhs_span = self.start_span();
+            let right = self.parse_unary_expression_base(rhs_span)?;
+            let right = self.parse_binary_or_logical_expression_recursive(
+                rhs_span,
+                right,
+                Precedence::Relational,
+            )?;
             Expression::PrivateInExpression(self.ast.alloc(PrivateInExpression {
                 span: self.end_span(lhs_span),
                 left,
@@ -1066,14 +1119,27 @@ impl<'a> Parser<'a> {
     ///   `DecoratorMemberExpression`[?Yield, ?Await]
     ///   ( `Expression`[+In, ?Yield, ?Await] )
     ///   `DecoratorCallExpression`
+    ///
+    /// Legacy decorators also allow member accesses and calls on a call, e.g. `@a().b()`.
     pub(crate) fn parse_decorator(&mut self) -> Result<Decorator<'a>> {
         let span = self.start_span();
         self.bump_any(); // bump @
+        let expr_span = self.start_span();
         let expr = if self.cur_kind() == Kind::LParen {
             self.parse_paren_expression()?
         } else {
             let lhs = self.parse_decorator_member_expression()?;
-            self.parse_decorator_call_expression(span, lhs)?
+            let mut expr = self.parse_decorator_call_expression(expr_span, lhs)?;
+            if self.legacy_decorators {
+                while matches!(self.cur_kind(), Kind::Dot | Kind::LParen) {
+                    expr = if self.at(Kind::Dot) {
+                        self.parse_static_member_expression(expr_span, expr, false)?
+                    } else {
+                        self.parse_call_arguments(expr_span, expr, false, None)?
+                    };
+                }
+            }
+            expr
         };
         Ok(self.ast.decorator(self.end_span(span), expr))
     }
@@ -221,6 +221,7 @@ impl<'a> Parser<'a> {
             pattern,
             None,
             false,
+            false,
             AstBuilder::new_vec(&self.ast),
         );
         let params = self.ast.formal_parameters(
@@ -424,6 +425,14 @@ impl<'a> Parser<'a> {
                             }
                         }
                         Kind::Eq | Kind::Comma => IsParenthesizedArrowFunction::True,
+                        // `<T>(` is a JSX opening element unless enabled otherwise
+                        Kind::RAngle
+                            if self.generic_arrows_in_jsx
+                                && self.ts_enabled()
+                                && self.nth_at(offset + 3, Kind::LParen) =>
+                        {
+                            IsParenthesizedArrowFunction::Maybe
+                        }
                         _ => IsParenthesizedArrowFunction::False,
                     };
                 }
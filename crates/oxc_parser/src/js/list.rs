@@ -253,10 +253,21 @@ impl<'a> SeparatedList<'a> for FormalParameterList<'a> {
     fn parse_element(&mut self, p: &mut Parser<'a>) -> Result<()> {
         let span = p.start_span();
         p.eat_decorators()?;
+        if !p.legacy_decorators {
+            if let (Some(first), Some(last)) =
+                (p.state.decorators.first(), p.state.decorators.last())
+            {
+                p.error(diagnostics::ParameterDecorators(Span::new(
+                    first.span.start,
+                    last.span.end,
+                )));
+            }
+        }
 
         let modifiers = p.parse_class_element_modifiers(true);
         let accessibility = modifiers.accessibility();
         let readonly = modifiers.readonly();
+        let r#override = modifiers.r#override();
 
         match p.cur_kind() {
             Kind::This if p.ts_enabled() => {
@@ -277,6 +288,7 @@ impl<'a> SeparatedList<'a> for FormalParameterList<'a> {
                     pattern,
                     accessibility,
                     readonly,
+                    r#override,
                     decorators,
                 );
                 self.elements.push(formal_parameter);
@@ -287,7 +299,7 @@ impl<'a> SeparatedList<'a> for FormalParameterList<'a> {
     }
 }
 
-/// [Assert Entries](https://tc39.es/proposal-import-assertions)
+/// [With Entries](https://tc39.es/proposal-import-attributes)
 pub struct AssertEntries<'a> {
     pub elements: Vec<'a, ImportAttribute>,
     keys: FxHashMap<Atom, Span>,
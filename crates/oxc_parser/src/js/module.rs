@@ -2,6 +2,7 @@ use oxc_allocator::{Box, Vec};
 use oxc_ast::ast::*;
 use oxc_diagnostics::Result;
 use oxc_span::Span;
+use oxc_syntax::escape;
 
 use super::{
     function::FunctionKind,
@@ -58,14 +59,14 @@ impl<'a> Parser<'a> {
         };
 
         let source = self.parse_literal_string()?;
-        let assertions = self.parse_import_attributes()?;
+        let with_clause = self.parse_import_attributes()?;
         self.asi()?;
         let span = self.end_span(span);
         let decl = ModuleDeclaration::ImportDeclaration(self.ast.import_declaration(
             span,
             specifiers,
             source,
-            assertions,
+            with_clause,
             import_kind,
         ));
         Ok(self.ast.module_declaration(decl))
@@ -135,19 +136,25 @@ impl<'a> Parser<'a> {
         Ok(specifiers)
     }
 
-    /// [Import assertion](https://tc39.es/proposal-import-assertions)
-    fn parse_import_attributes(&mut self) -> Result<Option<Vec<'a, ImportAttribute>>> {
-        if !self.at(Kind::Assert) || self.cur_token().is_on_new_line {
-            return Ok(None);
-        }
-        self.bump_any();
+    /// [Import Attributes](https://tc39.es/proposal-import-attributes)
+    /// `with { type: "json" }`, or the legacy `assert { type: "json" }`
+    fn parse_import_attributes(&mut self) -> Result<Option<WithClause<'a>>> {
+        let attributes_keyword = match self.cur_kind() {
+            Kind::With => self.parse_keyword_identifier(Kind::With),
+            // `assert` is a contextual keyword, which can not be preceded by a line terminator
+            Kind::Assert if !self.cur_token().is_on_new_line => {
+                self.parse_keyword_identifier(Kind::Assert)
+            }
+            _ => return Ok(None),
+        };
+        let span = attributes_keyword.span;
 
         let ctx = self.ctx;
         self.ctx = Context::default();
-        let entries = AssertEntries::parse(self)?.elements;
+        let with_entries = AssertEntries::parse(self)?.elements;
         self.ctx = ctx;
 
-        Ok(Some(entries))
+        Ok(Some(WithClause { span: self.end_span(span), attributes_keyword, with_entries }))
     }
 
     pub(crate) fn parse_ts_export_assignment_declaration(
@@ -199,7 +206,7 @@ impl<'a> Parser<'a> {
             Kind::Type if self.peek_at(Kind::LCurly) && self.ts_enabled() => self
                 .parse_export_named_specifiers(span)
                 .map(ModuleDeclaration::ExportNamedDeclaration),
-            Kind::Type if self.peek_at(Kind::Star) => {
+            Kind::Type if self.peek_at(Kind::Star) && self.ts_enabled() => {
                 self.parse_export_all_declaration(span).map(ModuleDeclaration::ExportAllDeclaration)
             }
             _ => self
@@ -237,6 +244,7 @@ impl<'a> Parser<'a> {
         } else {
             None
         };
+        let with_clause = if source.is_some() { self.parse_import_attributes()? } else { None };
 
         // ExportDeclaration : export NamedExports ;
         // * It is a Syntax Error if ReferencedBindings of NamedExports contains any StringLiterals.
@@ -254,7 +262,30 @@ impl<'a> Parser<'a> {
 
         self.asi()?;
         let span = self.end_span(span);
-        Ok(self.ast.export_named_declaration(span, None, specifiers, source, export_kind))
+        Ok(self.ast.export_named_declaration(
+            span,
+            None,
+            specifiers,
+            source,
+            with_clause,
+            export_kind,
+        ))
+    }
+
+    /// Decorators may be either before or after `export`, e.g. `@dec export class C {}`
+    /// or `export @dec class C {}`
+    fn eat_decorators_after_export(&mut self) -> Result<()> {
+        let before_export = self.state.consume_decorators();
+        self.eat_decorators()?;
+        if before_export.is_empty() {
+            return Ok(());
+        }
+        if let Some(decorator) = self.state.decorators.first() {
+            self.error(diagnostics::DecoratorsBeforeAndAfterExport(decorator.span));
+        } else {
+            self.state.decorators = before_export;
+        }
+        Ok(())
     }
 
     // export Declaration
@@ -265,20 +296,27 @@ impl<'a> Parser<'a> {
         let decl_span = self.start_span();
         // For tc39/proposal-decorators
         // For more information, please refer to <https://babeljs.io/docs/babel-plugin-proposal-decorators#decoratorsbeforeexport>
-        self.eat_decorators()?;
+        self.eat_decorators_after_export()?;
+        let reserved_ctx = self.ctx;
         let modifiers = if self.ts_enabled() {
-            self.eat_modifiers_before_declaration().1
+            let (flags, modifiers) = self.eat_modifiers_before_declaration();
+            // `export declare const a: number;`
+            self.ctx = self.ctx.union_ambient_if(flags.declare());
+            modifiers
         } else {
             Modifiers::empty()
         };
 
-        let declaration = self.parse_declaration(decl_span, modifiers)?;
+        let declaration = self.parse_declaration(decl_span, modifiers);
+        self.ctx = reserved_ctx;
+        let declaration = declaration?;
         let span = self.end_span(span);
         Ok(self.ast.export_named_declaration(
             span,
             Some(declaration),
             self.ast.new_vec(),
             None,
+            None,
             ImportOrExportKind::Value,
         ))
     }
@@ -294,7 +332,7 @@ impl<'a> Parser<'a> {
         let decl_span = self.start_span();
         // For tc39/proposal-decorators
         // For more information, please refer to <https://babeljs.io/docs/babel-plugin-proposal-decorators#decoratorsbeforeexport>
-        self.eat_decorators()?;
+        self.eat_decorators_after_export()?;
         let declaration = match self.cur_kind() {
             Kind::Class => self
                 .parse_class_declaration(decl_span, /* modifiers */ Modifiers::empty())
@@ -348,10 +386,10 @@ impl<'a> Parser<'a> {
         let exported = self.eat(Kind::As).then(|| self.parse_module_export_name()).transpose()?;
         self.expect(Kind::From)?;
         let source = self.parse_literal_string()?;
-        let assertions = self.parse_import_attributes()?;
+        let with_clause = self.parse_import_attributes()?;
         self.asi()?;
         let span = self.end_span(span);
-        Ok(self.ast.export_all_declaration(span, exported, source, assertions, export_kind))
+        Ok(self.ast.export_all_declaration(span, exported, source, with_clause, export_kind))
     }
 
     // ImportSpecifier :
@@ -388,7 +426,7 @@ impl<'a> Parser<'a> {
             let imported = IdentifierName { span: local.span, name: local.name.clone() };
             (ModuleExportName::Identifier(imported), local)
         };
-        Ok(ImportSpecifier { span: self.end_span(specifier_span), imported, local })
+        Ok(ImportSpecifier { span: self.end_span(specifier_span), imported, local, import_kind })
     }
 
     // ModuleExportName :
@@ -400,7 +438,10 @@ impl<'a> Parser<'a> {
                 let literal = self.parse_literal_string()?;
                 // ModuleExportName : StringLiteral
                 // It is a Syntax Error if IsStringWellFormedUnicode(the SV of StringLiteral) is false.
-                if !literal.is_string_well_formed_unicode() {
+                let is_well_formed = literal
+                    .cooked_utf16(self.source_text)
+                    .map_or(true, |value| escape::is_well_formed_unicode(&value));
+                if !is_well_formed {
                     self.error(diagnostics::ExportLoneSurrogate(literal.span));
                 };
                 Ok(ModuleExportName::StringLiteral(literal))
@@ -36,26 +36,42 @@ impl<'a> Parser<'a> {
     ) -> Result<(Vec<'a, Directive>, Vec<'a, Statement<'a>>)> {
         let mut directives = self.ast.new_vec();
         let mut statements = self.ast.new_vec();
+        self.parse_statement_list_into(
+            is_top_level,
+            &mut directives,
+            &mut statements,
+            /* expecting_diretives */ true,
+            |_| false,
+        )?;
+        Ok((directives, statements))
+    }
 
-        let mut expecting_diretives = true;
+    /// Parse a statement list, appending to `directives` and `statements`.
+    /// `expecting_diretives` is false when continuing after a statement.
+    /// Stops before the statement starting at an offset for which `stop_at` returns true.
+    pub(crate) fn parse_statement_list_into(
+        &mut self,
+        is_top_level: bool,
+        directives: &mut Vec<'a, Directive>,
+        statements: &mut Vec<'a, Statement<'a>>,
+        mut expecting_diretives: bool,
+        mut stop_at: impl FnMut(u32) -> bool,
+    ) -> Result<()> {
         while !self.at(Kind::Eof) {
-            match self.cur_kind() {
+            let start = self.cur_token().start;
+            // Decorators before `start` belong to the statement starting there
+            if self.state.decorators.is_empty() && stop_at(start) {
+                break;
+            }
+            let ctx = self.ctx;
+            let result = match self.cur_kind() {
                 Kind::RCurly if !is_top_level => break,
                 Kind::Import if !matches!(self.peek_kind(), Kind::Dot | Kind::LParen) => {
-                    let stmt = self.parse_import_declaration()?;
-                    statements.push(stmt);
+                    self.parse_import_declaration().map(|stmt| statements.push(stmt))
                 }
-                Kind::Export => {
-                    let stmt = self.parse_export_declaration()?;
-                    statements.push(stmt);
-                }
-                Kind::At => {
-                    self.eat_decorators()?;
-                    continue;
-                }
-                _ => {
-                    let stmt = self.parse_statement_list_item(StatementContext::StatementList)?;
-
+                Kind::Export => self.parse_export_declaration().map(|stmt| statements.push(stmt)),
+                Kind::At => self.eat_decorators(),
+                _ => self.parse_statement_list_item(StatementContext::StatementList).map(|stmt| {
                     // Section 11.2.1 Directive Prologue
                     // The only way to get a correct directive is to parse the statement first and check if it is a string literal.
                     // All other method are flawed, see test cases in [babel](https://github.com/babel/babel/blob/main/packages/babel-parser/test/fixtures/core/categorized/not-directive/input.js)
@@ -70,18 +86,80 @@ impl<'a> Parser<'a> {
                                     Atom::from(src),
                                 );
                                 directives.push(directive);
-                                continue;
+                                return;
                             }
                         }
                         expecting_diretives = false;
                     }
 
                     statements.push(stmt);
-                }
+                }),
             };
+
+            if let Err(error) = result {
+                if !self.recover {
+                    return Err(error);
+                }
+                self.ctx = ctx;
+                self.state.consume_decorators();
+                self.recover_error(error)?;
+                self.skip_to_next_statement(start, is_top_level);
+                let span = self.end_span(Span::new(start, start));
+                statements.push(self.ast.error_statement(span));
+            }
         }
 
-        Ok((directives, statements))
+        Ok(())
+    }
+
+    /// Skip the tokens of a statement which failed to parse, up to the start of the next statement:
+    /// after a `;`, before the `}` closing the statement list,
+    /// or before a keyword starting a statement on a new line.
+    /// Brackets opened while skipping are skipped up to their closing bracket.
+    fn skip_to_next_statement(&mut self, start: u32, is_top_level: bool) {
+        // Always make progress, the statement may have failed on its first token.
+        if self.cur_token().start == start {
+            self.bump_any();
+        }
+        let mut depth = 0u32;
+        loop {
+            match self.cur_kind() {
+                Kind::Eof => return,
+                Kind::LCurly | Kind::LParen | Kind::LBrack => depth += 1,
+                Kind::RParen | Kind::RBrack => depth = depth.saturating_sub(1),
+                Kind::RCurly if depth > 0 => depth -= 1,
+                Kind::RCurly if !is_top_level => return,
+                Kind::Semicolon if depth == 0 => {
+                    self.bump_any();
+                    return;
+                }
+                kind if depth == 0
+                    && self.cur_token().is_on_new_line
+                    && matches!(
+                        kind,
+                        Kind::Var
+                            | Kind::Let
+                            | Kind::Const
+                            | Kind::Function
+                            | Kind::Class
+                            | Kind::If
+                            | Kind::For
+                            | Kind::While
+                            | Kind::Do
+                            | Kind::Return
+                            | Kind::Switch
+                            | Kind::Throw
+                            | Kind::Try
+                            | Kind::Import
+                            | Kind::Export
+                    ) =>
+                {
+                    return;
+                }
+                _ => {}
+            }
+            self.bump_any();
+        }
     }
 
     /// `StatementListItem`[Yield, Await, Return] :
@@ -122,6 +200,7 @@ impl<'a> Parser<'a> {
                 self.parse_variable_statement(stmt_ctx)
             }
             Kind::Let if !self.cur_token().escaped => self.parse_let(stmt_ctx),
+            _ if self.at_using_declaration() => self.parse_variable_statement(stmt_ctx),
             _ if self.at_function_with_async() => self.parse_function_declaration(stmt_ctx),
             _ if self.ts_enabled() && self.at_start_of_ts_declaration() => {
                 self.parse_ts_declaration_statement(start_span)
@@ -163,6 +242,25 @@ impl<'a> Parser<'a> {
         Ok(self.ast.block_statement(block))
     }
 
+    /// [Explicit Resource Management](https://github.com/tc39/proposal-explicit-resource-management)
+    /// `using` [no LineTerminator here] `BindingIdentifier`
+    /// `await` [no LineTerminator here] `using` [no LineTerminator here] `BindingIdentifier`
+    pub(crate) fn at_using_declaration(&mut self) -> bool {
+        let n = match self.cur_kind() {
+            Kind::Using if !self.cur_token().escaped => 1,
+            Kind::Await
+                if self.ctx.has_await()
+                    && self.peek_at(Kind::Using)
+                    && !self.peek_token().is_on_new_line =>
+            {
+                2
+            }
+            _ => return false,
+        };
+        let token = self.nth(n);
+        token.kind.is_binding_identifier() && !token.is_on_new_line
+    }
+
     /// Section 14.3.2 Variable Statement
     pub(crate) fn parse_variable_statement(
         &mut self,
@@ -249,9 +347,16 @@ impl<'a> Parser<'a> {
 
         // for (let | for (const | for (var
         // disallow for (let in ..)
+        // for (using x of ..
+        // `for (using of x)` is a for-of loop over `x` with `using` as the assignment target
+        let is_using = self.at_using_declaration()
+            && !(self.at(Kind::Using)
+                && self.peek_at(Kind::Of)
+                && !matches!(self.nth_kind(2), Kind::Of | Kind::Eq));
         if self.at(Kind::Const)
             || self.at(Kind::Var)
             || (self.at(Kind::Let) && self.peek_kind().is_after_let())
+            || is_using
         {
             let start_span = self.start_span();
             let init_declaration = self.without_context(Context::In, |p| {
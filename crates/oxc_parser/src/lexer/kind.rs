@@ -60,6 +60,7 @@ pub enum Kind {
     Set,
     Target, // new.target
     Accessor,
+    Using,
     // TypeScript Contextual Keywords
     Abstract,
     As,
@@ -342,7 +343,7 @@ impl Kind {
 
     #[rustfmt::skip]
     pub fn is_contextual_keyword(self) -> bool {
-        matches!(self, Async | From | Get | Meta | Of | Set | Target | Accessor | Abstract | As | Asserts
+        matches!(self, Async | From | Get | Meta | Of | Set | Target | Accessor | Using | Abstract | As | Asserts
             | Assert | Any | Boolean | Constructor | Declare | Infer | Intrinsic | Is | KeyOf | Module
             | Namespace | Never | Out | Readonly | Require | Number | Object | Satisfies | String
             | Symbol | Type | Undefined | Unique | Unknown | Global | BigInt | Override)
@@ -424,6 +425,7 @@ impl Kind {
             "never" => Never,
             "super" => Super,
             "throw" => Throw,
+            "using" => Using,
             "while" => While,
             "yield" => Yield,
 
@@ -534,6 +536,7 @@ impl Kind {
             Set => "set",
             Asserts => "asserts",
             Accessor => "accessor",
+            Using => "using",
             Abstract => "abstract",
             Readonly => "readonly",
             Declare => "declare",
@@ -8,6 +8,7 @@
 mod constants;
 mod kind;
 mod number;
+mod search;
 mod string_builder;
 mod token;
 mod trivia_builder;
@@ -115,6 +116,12 @@ impl<'a> Lexer<'a> {
         self.lookahead.clear();
     }
 
+    /// Continue lexing from `offset`, for parsing the source text again from there
+    pub fn seek(&mut self, offset: u32) {
+        self.current.chars = self.source[offset as usize..].chars();
+        self.lookahead.clear();
+    }
+
     /// Find the nth lookahead token lazily
     pub fn lookahead(&mut self, n: u8) -> &Token<'a> {
         let n = n as usize;
@@ -167,6 +174,7 @@ impl<'a> Lexer<'a> {
     }
 
     pub fn next_jsx_child(&mut self) -> Token<'a> {
+        self.current.token.start = self.offset();
         let kind = self.read_jsx_child();
         self.finish_next(kind)
     }
@@ -244,6 +252,11 @@ impl<'a> Lexer<'a> {
         (self.source.len() - self.current.chars.as_str().len()) as u32
     }
 
+    /// Advance by `n` bytes, which must end on a char boundary
+    fn advance(&mut self, n: usize) {
+        self.current.chars = self.remaining()[n..].chars();
+    }
+
     /// Get the current unterminated token range
     fn unterminated_range(&self) -> Span {
         Span::new(self.current.token.start, self.offset())
@@ -361,7 +374,10 @@ impl<'a> Lexer<'a> {
         // > the rough order of frequency for different token kinds is as follows:
         // identifiers/keywords, ‘.’, ‘=’, strings, decimal numbers, ‘:’, ‘+’, hex/octal numbers, and then everything else
         match c {
-            ' ' | '\t' => Kind::WhiteSpace,
+            ' ' | '\t' => {
+                self.advance(search::skip_spaces_and_tabs(self.remaining().as_bytes()));
+                Kind::WhiteSpace
+            }
             '\r' | '\n' => {
                 self.current.token.is_on_new_line = true;
                 Kind::NewLine
@@ -453,19 +469,40 @@ impl<'a> Lexer<'a> {
 
     /// Section 12.4 Single Line Comment
     fn skip_single_line_comment(&mut self) -> Kind {
-        while let Some(c) = self.current.chars.next().as_ref() {
-            if is_line_terminator(*c) {
+        // The line terminator is not part of the comment
+        loop {
+            let remaining = self.remaining();
+            let Some(index) = search::find_line_terminator(remaining.as_bytes()) else {
+                self.advance(remaining.len());
+                break;
+            };
+            self.advance(index);
+            // `0xE2` also starts other chars than `<LS>` and `<PS>`
+            if self.current.chars.clone().next().map_or(true, is_line_terminator) {
                 break;
             }
+            self.current.chars.next();
         }
-        self.current.token.is_on_new_line = true;
         self.trivia_builder.add_single_line_comment(self.current.token.start, self.offset());
         Kind::Comment
     }
 
     /// Section 12.4 Multi Line Comment
     fn skip_multi_line_comment(&mut self) -> Kind {
-        while let Some(c) = self.current.chars.next() {
+        loop {
+            let remaining = self.remaining().as_bytes();
+            // Only the end of the comment matters once it is known to contain a line terminator
+            let index = if self.current.token.is_on_new_line {
+                search::find_star(remaining)
+            } else {
+                search::find_multi_line_comment_stop(remaining)
+            };
+            let Some(index) = index else {
+                self.advance(remaining.len());
+                break;
+            };
+            self.advance(index);
+            let Some(c) = self.current.chars.next() else { break };
             if c == '*' && self.next_eq('/') {
                 self.trivia_builder.add_multi_line_comment(self.current.token.start, self.offset());
                 return Kind::MultiLineComment;
@@ -480,12 +517,13 @@ impl<'a> Lexer<'a> {
 
     /// Section 12.5 Hashbang Comments
     fn read_hashbang_comment(&mut self) -> Kind {
-        while let Some(c) = self.current.chars.next().as_ref() {
-            if is_line_terminator(*c) {
+        // The line terminator is not part of the hashbang
+        while let Some(c) = self.current.chars.clone().next() {
+            if is_line_terminator(c) {
                 break;
             }
+            self.current.chars.next();
         }
-        self.current.token.is_on_new_line = true;
         Kind::HashbangComment
     }
 
@@ -966,6 +1004,12 @@ impl<'a> Lexer<'a> {
     fn read_string_literal(&mut self, delimiter: char) -> Kind {
         let mut builder = AutoCow::new(self);
         loop {
+            // Skip to the next delimiter, escape or line terminator
+            let remaining = self.remaining();
+            let index = search::find_string_stop(remaining.as_bytes(), delimiter as u8)
+                .unwrap_or(remaining.len());
+            builder.push_matching_str(&remaining[..index]);
+            self.advance(index);
             match self.current.chars.next() {
                 None | Some('\r' | '\n') => {
                     self.error(diagnostics::UnterminatedString(self.unterminated_range()));
@@ -0,0 +1,153 @@
+//! Word-at-a-time byte searches for the hot loops of the lexer
+//!
+//! Comments, string literals and indentation are scanned 8 bytes at a time with
+//! [SWAR](https://en.wikipedia.org/wiki/SWAR) bit tricks, the same idea as `memchr`'s fallback,
+//! without pulling in platform specific intrinsics.
+//!
+//! All searches stop on ASCII bytes or on `0xE2`, the first byte of `<LS>` and `<PS>` in UTF-8,
+//! which are always char boundaries, so the lexer can slice the remaining source at the result.
+
+const LO7: u64 = 0x7F7F_7F7F_7F7F_7F7F;
+const HI: u64 = 0x8080_8080_8080_8080;
+
+/// First byte of `<LS>` (`E2 80 A8`) and `<PS>` (`E2 80 A9`)
+const LS_PS_LEAD: u8 = 0xE2;
+
+#[inline]
+const fn repeat(byte: u8) -> u64 {
+    u64::from_ne_bytes([byte; 8])
+}
+
+/// Sets the high bit of every zero byte in `word`, and only those
+#[inline]
+const fn zero_bytes(word: u64) -> u64 {
+    !(((word & LO7) + LO7) | word) & HI
+}
+
+/// Sets the high bit of every byte equal to `byte`
+#[inline]
+const fn eq_bytes(word: u64, byte: u8) -> u64 {
+    zero_bytes(word ^ repeat(byte))
+}
+
+/// Index of the first byte whose high bit is set in `mask`
+#[inline]
+fn first_byte(mask: u64) -> usize {
+    let index =
+        if cfg!(target_endian = "little") { mask.trailing_zeros() } else { mask.leading_zeros() };
+    (index / 8) as usize
+}
+
+/// Index of the first byte of `bytes` selected by `mask`, checking one word at a time,
+/// `matches` is the same test for a single byte, used for the remainder
+#[inline]
+fn find(bytes: &[u8], mask: impl Fn(u64) -> u64, matches: impl Fn(u8) -> bool) -> Option<usize> {
+    let mut chunks = bytes.chunks_exact(8);
+    let mut offset = 0;
+    for chunk in &mut chunks {
+        let word = u64::from_ne_bytes([
+            chunk[0], chunk[1], chunk[2], chunk[3], chunk[4], chunk[5], chunk[6], chunk[7],
+        ]);
+        let found = mask(word);
+        if found != 0 {
+            return Some(offset + first_byte(found));
+        }
+        offset += 8;
+    }
+    chunks.remainder().iter().position(|&b| matches(b)).map(|index| offset + index)
+}
+
+/// Index of the first `\r`, `\n` or possible `<LS>` / `<PS>`, ending a single line comment
+pub fn find_line_terminator(bytes: &[u8]) -> Option<usize> {
+    find(
+        bytes,
+        |word| eq_bytes(word, b'\n') | eq_bytes(word, b'\r') | eq_bytes(word, LS_PS_LEAD),
+        |b| matches!(b, b'\n' | b'\r' | LS_PS_LEAD),
+    )
+}
+
+/// Index of the first `*`, which may end a multi line comment, or the first line terminator
+pub fn find_multi_line_comment_stop(bytes: &[u8]) -> Option<usize> {
+    find(
+        bytes,
+        |word| {
+            eq_bytes(word, b'*')
+                | eq_bytes(word, b'\n')
+                | eq_bytes(word, b'\r')
+                | eq_bytes(word, LS_PS_LEAD)
+        },
+        |b| matches!(b, b'*' | b'\n' | b'\r' | LS_PS_LEAD),
+    )
+}
+
+/// Index of the first `*`, once a multi line comment is known to span multiple lines
+pub fn find_star(bytes: &[u8]) -> Option<usize> {
+    find(bytes, |word| eq_bytes(word, b'*'), |b| b == b'*')
+}
+
+/// Index of the first byte which ends the plain part of a string literal:
+/// the `delimiter`, `\`, `\r` or `\n`
+pub fn find_string_stop(bytes: &[u8], delimiter: u8) -> Option<usize> {
+    find(
+        bytes,
+        |word| {
+            eq_bytes(word, delimiter)
+                | eq_bytes(word, b'\\')
+                | eq_bytes(word, b'\n')
+                | eq_bytes(word, b'\r')
+        },
+        |b| b == delimiter || matches!(b, b'\\' | b'\n' | b'\r'),
+    )
+}
+
+/// Number of leading spaces and tabs, e.g. the indentation of a line
+pub fn skip_spaces_and_tabs(bytes: &[u8]) -> usize {
+    find(
+        bytes,
+        |word| !(eq_bytes(word, b' ') | eq_bytes(word, b'\t')) & HI,
+        |b| !matches!(b, b' ' | b'\t'),
+    )
+    .unwrap_or(bytes.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_terminator() {
+        assert_eq!(find_line_terminator(b""), None);
+        assert_eq!(find_line_terminator(b"// comment"), None);
+        assert_eq!(find_line_terminator(b"// a long comment\nfoo"), Some(17));
+        assert_eq!(find_line_terminator(b"abc\r\n"), Some(3));
+        assert_eq!(find_line_terminator("0123456789\u{2028}".as_bytes()), Some(10));
+        // Bytes with the high bit set around the searched byte do not cause false positives
+        assert_eq!(find_line_terminator("éééééééé\n".as_bytes()), Some(16));
+    }
+
+    #[test]
+    fn multi_line_comment_stop() {
+        assert_eq!(find_multi_line_comment_stop(b" comment without end "), None);
+        assert_eq!(find_multi_line_comment_stop(b" a long comment */"), Some(16));
+        assert_eq!(find_multi_line_comment_stop(b"abcdefghij\nk*/"), Some(10));
+        assert_eq!(find_star(b"abcdefghij\nk*/"), Some(12));
+    }
+
+    #[test]
+    fn string_stop() {
+        assert_eq!(find_string_stop(b"hello world\"", b'"'), Some(11));
+        assert_eq!(find_string_stop(b"it's \"quoted\"'", b'\''), Some(2));
+        assert_eq!(find_string_stop(b"a long string \\n with escapes'", b'\''), Some(14));
+        assert_eq!(find_string_stop(b"unterminated\n'", b'\''), Some(12));
+        assert_eq!(find_string_stop(b"unterminated", b'\''), None);
+    }
+
+    #[test]
+    fn spaces_and_tabs() {
+        assert_eq!(skip_spaces_and_tabs(b""), 0);
+        assert_eq!(skip_spaces_and_tabs(b"foo"), 0);
+        assert_eq!(skip_spaces_and_tabs(b"    \t\t    foo"), 10);
+        assert_eq!(skip_spaces_and_tabs(b"                "), 16);
+        assert_eq!(skip_spaces_and_tabs(b"        \n"), 8);
+    }
+}
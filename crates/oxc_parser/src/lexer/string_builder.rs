@@ -22,6 +22,13 @@ impl<'a> AutoCow<'a> {
         }
     }
 
+    // Push a str that matches the next chars of lexer.chars()
+    pub fn push_matching_str(&mut self, s: &str) {
+        if let Some(text) = &mut self.value {
+            text.push_str(s);
+        }
+    }
+
     // Push a different character than lexer.chars().next().
     // force_allocation_without_current_ascii_char must be called before this.
     pub fn push_different(&mut self, c: char) {
@@ -60,6 +60,9 @@ impl<'a> Default for TokenValue<'a> {
 }
 
 impl<'a> TokenValue<'a> {
+    /// # Panics
+    ///
+    /// Panics if the value is not a number
     pub fn as_number(&self) -> f64 {
         match self {
             Self::Number(s) => *s,
@@ -67,6 +70,9 @@ impl<'a> TokenValue<'a> {
         }
     }
 
+    /// # Panics
+    ///
+    /// Panics if the value is not a bigint
     pub fn as_bigint(&self) -> num_bigint::BigInt {
         match self {
             Self::BigInt(s) => s.clone(),
@@ -74,6 +80,9 @@ impl<'a> TokenValue<'a> {
         }
     }
 
+    /// # Panics
+    ///
+    /// Panics if the value is not a regex
     pub fn as_regex(&self) -> &RegExp<'a> {
         match self {
             Self::RegExp(regex) => regex,
@@ -6,7 +6,17 @@ pub struct TriviaBuilder {
     trivias: Trivias,
 }
 
+impl From<Trivias> for TriviaBuilder {
+    fn from(trivias: Trivias) -> Self {
+        Self { trivias }
+    }
+}
+
 impl TriviaBuilder {
+    pub fn trivias(&self) -> &Trivias {
+        &self.trivias
+    }
+
     pub fn build(self) -> Trivias {
         self.trivias
     }
@@ -40,6 +40,11 @@
 #![doc = include_str!("../examples/parser.rs")]
 //! ```
 //!
+//! # Tokens
+//!
+//! Tools which do not need an AST, such as syntax highlighters, can use [Tokenizer] for the tokens
+//! and comments of the source text.
+//!
 //! # Visitor
 //!
 //! See [oxc_ast::Visit] and [oxc_ast::VisitMut]
@@ -70,19 +75,24 @@ mod js;
 mod jsx;
 mod ts;
 
+mod detect;
 mod diagnostics;
 mod lexer;
+mod shift_span;
+mod tokenizer;
 
 use context::{Context, StatementContext};
 use oxc_allocator::Allocator;
-use oxc_ast::{ast::Program, AstBuilder, Trivias};
+use oxc_ast::{ast::Program, AstBuilder, TokenSpans, Trivias};
 use oxc_diagnostics::{Error, Result};
-use oxc_span::{ModuleKind, SourceType, Span};
+use oxc_span::{GetSpan, ModuleKind, SourceType, Span};
 
-use crate::{
-    lexer::{Kind, Lexer, Token},
-    state::ParserState,
+pub use crate::{
+    detect::detect_module_kind,
+    lexer::{Kind, RegExp, Token, TokenValue},
+    tokenizer::Tokenizer,
 };
+use crate::{lexer::Lexer, shift_span::ShiftSpan, state::ParserState};
 
 /// Return value of parser consisting of AST, errors and comments
 ///
@@ -93,6 +103,8 @@ pub struct ParserReturn<'a> {
     pub program: Program<'a>,
     pub errors: Vec<Error>,
     pub trivias: Trivias,
+    /// Spans of keywords and punctuators such as `else` and `=>`, see [`TokenSpans`]
+    pub token_spans: TokenSpans,
     pub panicked: bool,
 }
 
@@ -118,6 +130,9 @@ pub struct Parser<'a> {
     /// The end range of the previous token
     prev_token_end: u32,
 
+    /// Spans of the consumed tokens which are significant for formatting
+    token_spans: TokenSpans,
+
     /// Parser state
     state: ParserState<'a>,
 
@@ -126,6 +141,18 @@ pub struct Parser<'a> {
 
     /// Ast builder for creating AST spans
     ast: AstBuilder<'a>,
+
+    /// Skip statements which fail to parse instead of returning an empty program
+    recover: bool,
+
+    /// Stop recovering once this many errors have been reported
+    max_errors: Option<usize>,
+
+    /// TypeScript experimental decorators instead of the ECMAScript decorators
+    legacy_decorators: bool,
+
+    /// Try `<T>(x) => x` as a generic arrow function before JSX
+    generic_arrows_in_jsx: bool,
 }
 
 impl<'a> Parser<'a> {
@@ -138,9 +165,14 @@ impl<'a> Parser<'a> {
             errors: vec![],
             token: Token::default(),
             prev_token_end: 0,
+            token_spans: TokenSpans::default(),
             state: ParserState::new(allocator),
             ctx: Self::default_context(source_type),
             ast: AstBuilder::new(allocator),
+            recover: false,
+            max_errors: None,
+            legacy_decorators: source_type.is_typescript(),
+            generic_arrows_in_jsx: false,
         }
     }
 
@@ -154,12 +186,153 @@ impl<'a> Parser<'a> {
         self
     }
 
+    /// Recover from syntax errors
+    ///
+    /// By default, a syntax error which the parser cannot recover from returns an empty program.
+    /// Set this to true to report the statement containing the error and skip it up to the next
+    /// statement instead, so that the program contains all the other statements.
+    /// The skipped source text is kept as an `ErrorStatement` in place of the statement,
+    /// and an expression missing before a token such as `;` or `)` as an `ErrorExpression`.
+    /// This is intended for editors, which need a partial AST of code in the middle of an edit.
+    #[must_use]
+    pub fn recover_from_errors(mut self, yes: bool) -> Self {
+        self.recover = yes;
+        self
+    }
+
+    /// Stop recovering from syntax errors after `max` errors
+    ///
+    /// Only applies with [`Parser::recover_from_errors`], which collects as many errors as possible.
+    /// Once `max` errors have been reported, parsing stops as if the last error were not recoverable:
+    /// the program is empty and a final error reports that parsing was stopped.
+    /// This keeps files with a lot of broken code from flooding the output of batch runs.
+    #[must_use]
+    pub fn max_errors(mut self, max: usize) -> Self {
+        self.max_errors = Some(max);
+        self
+    }
+
+    /// Parse decorators as TypeScript experimental decorators
+    ///
+    /// By default, decorators follow the [ECMAScript decorators proposal](https://github.com/tc39/proposal-decorators)
+    /// in JavaScript, and the TypeScript `experimentalDecorators` in TypeScript.
+    /// Legacy decorators are also allowed on parameters,
+    /// and their expressions may be chained calls such as `@a()()`.
+    #[must_use]
+    pub fn legacy_decorators(mut self, yes: bool) -> Self {
+        self.legacy_decorators = yes;
+        self
+    }
+
+    /// Parse JSX, overriding the source type
+    ///
+    /// JSX is enabled by [`SourceType::from_path`] for `.js`, `.jsx` and `.tsx` files.
+    /// Set this to false for TypeScript snippets which are not known to come from a `.tsx` file,
+    /// so that `<T>x` is a type assertion and `<T>(x) => x` a generic arrow function.
+    #[must_use]
+    pub fn jsx(mut self, yes: bool) -> Self {
+        self.source_type = if yes {
+            self.source_type.with_jsx(true)
+        } else {
+            self.source_type.with_standard(true)
+        };
+        self
+    }
+
+    /// Parse `<T>(x) => x` as a generic arrow function in TypeScript with JSX
+    ///
+    /// By default, `<T>` is a JSX opening element in a `.tsx` file as in TypeScript,
+    /// where type parameters of arrow functions are written `<T,>` or `<T extends unknown>`.
+    /// Set this to true to try a generic arrow function first, and JSX when it does not parse as one.
+    #[must_use]
+    pub fn generic_arrows_in_jsx(mut self, yes: bool) -> Self {
+        self.generic_arrows_in_jsx = yes;
+        self
+    }
+
     /// Main entry point
     ///
     /// Returns an empty `Program` on unrecoverable error,
     /// Recoverable errors are stored inside `errors`.
     pub fn parse(mut self) -> ParserReturn<'a> {
-        let (program, panicked) = match self.parse_program() {
+        let result = self.parse_program();
+        self.finish(result)
+    }
+
+    /// Parse the source text again after an edit, reusing the statements of `previous`
+    /// which are not affected by the edit.
+    ///
+    /// `previous` must be returned by a parser using the same allocator on the source text before
+    /// the edit, and `edit` is the span of the previous source text which has been replaced.
+    ///
+    /// The top level statements ending before the edit are reused, except for the last one,
+    /// which is parsed again because the edit may continue it, e.g. appending `.foo()` to `bar`.
+    /// The source text is parsed from there up to a top level statement of `previous` starting
+    /// after the edit, from which on the statements are reused again, with their spans moved by
+    /// the difference in length when the edit inserts or deletes text.
+    /// Falls back to a full parse when `previous` has errors.
+    pub fn reparse(mut self, previous: ParserReturn<'a>, edit: Span) -> ParserReturn<'a> {
+        if previous.panicked || !previous.errors.is_empty() {
+            return self.parse();
+        }
+        let ParserReturn { program, mut trivias, mut token_spans, .. } = previous;
+        let Program { span: previous_span, mut directives, hashbang, mut body, .. } = program;
+        let reused = body.iter().take_while(|stmt| stmt.span().end < edit.start).count();
+        let reused = reused.saturating_sub(1);
+        if reused == 0 {
+            return self.parse();
+        }
+        // The end of the edit in the edited source text, the source text after it is unchanged
+        #[allow(clippy::cast_possible_truncation)]
+        let edit_end = edit.end + self.source_text.len() as u32 - previous_span.end;
+        let mut after = body.split_off(reused);
+        after.retain(|stmt| stmt.span().start >= edit.end);
+        for stmt in &mut after {
+            stmt.shift(edit.end, edit_end);
+        }
+
+        // Lex again from the end of the last reused statement, including the comments after it
+        let offset = body[reused - 1].span().end;
+        let mut trivias_after = trivias.split_off(offset).split_off(edit.end);
+        trivias_after.shift(edit.end, edit_end);
+        self.lexer.trivia_builder = trivias.into();
+        let mut token_spans_after = token_spans.split_off(offset).split_off(edit.end);
+        token_spans_after.shift(edit.end, edit_end);
+        self.token_spans = token_spans;
+        self.lexer.seek(offset);
+        // Continue as if the last token of the reused statement had just been consumed
+        self.token.end = offset;
+        self.bump_any();
+
+        let result = self.parse_statement_list_into(
+            /* is_top_level */ true,
+            &mut directives,
+            &mut body,
+            /* expecting_diretives */ false,
+            |start| after.binary_search_by_key(&start, |stmt| stmt.span().start).is_ok(),
+        );
+        if result.is_ok() && !self.at(Kind::Eof) {
+            // Stopped before a statement of `previous`, the source text from there is unchanged
+            let start = self.cur_token().start;
+            let index = after.partition_point(|stmt| stmt.span().start < start);
+            body.extend(after.drain(index..));
+            let mut trivias = std::mem::take(&mut self.lexer.trivia_builder).build();
+            // Comments lexed ahead of the current token are also in `previous`
+            let _ = trivias.split_off(start);
+            trivias.append(trivias_after.split_off(start));
+            self.lexer.trivia_builder = trivias.into();
+            self.token_spans.append(token_spans_after.split_off(start));
+        }
+        let result = result.map(|()| {
+            #[allow(clippy::cast_possible_truncation)]
+            let span = Span::new(0, self.source_text.len() as u32);
+            self.ast.program(span, self.source_type, directives, hashbang, body)
+        });
+        self.finish(result)
+    }
+
+    fn finish(mut self, result: Result<Program<'a>>) -> ParserReturn<'a> {
+        let (program, panicked) = match result {
             Ok(program) => (program, false),
             Err(error) => {
                 self.error(self.flow_error().unwrap_or(error));
@@ -175,7 +348,7 @@ impl<'a> Parser<'a> {
         };
         let errors = self.lexer.errors.into_iter().chain(self.errors).collect();
         let trivias = self.lexer.trivia_builder.build();
-        ParserReturn { program, errors, trivias, panicked }
+        ParserReturn { program, errors, trivias, token_spans: self.token_spans, panicked }
     }
 
     #[allow(clippy::cast_possible_truncation)]
@@ -231,6 +404,26 @@ impl<'a> Parser<'a> {
         self.errors.push(error.into());
     }
 
+    /// Report an error recovered from, or stop recovering when [`Parser::max_errors`] is reached
+    fn recover_error(&mut self, error: Error) -> Result<()> {
+        self.error(error);
+        if self.too_many_errors() {
+            // Stop recovering in the enclosing statement lists as well
+            self.recover = false;
+            return Err(diagnostics::TooManyErrors(
+                self.max_errors.unwrap_or_default(),
+                self.cur_token().span(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Whether [`Parser::max_errors`] has been reached by the parser and lexer errors
+    fn too_many_errors(&self) -> bool {
+        self.max_errors.map_or(false, |max| self.errors.len() + self.lexer.errors.len() >= max)
+    }
+
     fn ts_enabled(&self) -> bool {
         self.source_type.is_typescript()
     }
@@ -238,6 +431,10 @@ impl<'a> Parser<'a> {
 
 #[cfg(test)]
 mod test {
+    use oxc_ast::ast::{
+        ClassElement, Declaration, Expression, ExpressionStatement, ForStatementLeft, IfStatement,
+        ModuleDeclaration, Statement, VariableDeclarationKind,
+    };
 
     use super::*;
 
@@ -265,4 +462,505 @@ mod test {
         assert!(ret.program.is_empty());
         assert_eq!(ret.errors.first().unwrap().to_string(), "Flow is not supported");
     }
+
+    #[test]
+    fn recover_from_errors() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "let a = 1;\nlet b = ;\nfunction foo() {\n  if (\n  return a;\n}\nlet c = 3;";
+
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.panicked);
+        assert!(ret.program.is_empty());
+
+        let ret = Parser::new(&allocator, source, source_type).recover_from_errors(true).parse();
+        assert!(!ret.panicked);
+        assert_eq!(ret.errors.len(), 2);
+        assert_eq!(ret.program.body.len(), 4);
+        // The missing initializer is an error expression at the `;`
+        let Statement::Declaration(Declaration::VariableDeclaration(declaration)) =
+            &ret.program.body[1]
+        else {
+            panic!("expected a variable declaration");
+        };
+        let Some(Expression::ErrorExpression(error)) = &declaration.declarations[0].init else {
+            panic!("expected an error expression");
+        };
+        let offset = u32::try_from(source.find(" ;").unwrap()).unwrap() + 1;
+        assert_eq!(error.span, Span::new(offset, offset));
+        let Statement::Declaration(Declaration::FunctionDeclaration(function)) =
+            &ret.program.body[2]
+        else {
+            panic!("expected a function declaration");
+        };
+        let statements = &function.body.as_ref().unwrap().statements;
+        assert_eq!(statements.len(), 2);
+        let Statement::ErrorStatement(error) = &statements[0] else {
+            panic!("expected an error statement");
+        };
+        assert_eq!(&source[error.span.start as usize..error.span.end as usize], "if (");
+        assert!(matches!(statements[1], Statement::ReturnStatement(_)));
+    }
+
+    #[test]
+    fn recover_from_missing_expressions() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "if (a && ) {}\nfoo(a, b +);\nlet c = { d: };";
+        let ret = Parser::new(&allocator, source, source_type).recover_from_errors(true).parse();
+        assert!(!ret.panicked);
+        assert_eq!(ret.errors.len(), 3);
+        assert!(matches!(ret.program.body[0], Statement::IfStatement(_)));
+        assert!(matches!(ret.program.body[1], Statement::ExpressionStatement(_)));
+        assert!(matches!(
+            ret.program.body[2],
+            Statement::Declaration(Declaration::VariableDeclaration(_))
+        ));
+    }
+
+    #[test]
+    fn max_errors() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "let a = ;\nlet b = ;\nfunction foo() {\n  let c = ;\n}\nlet d = ;";
+
+        let ret = Parser::new(&allocator, source, source_type).recover_from_errors(true).parse();
+        assert!(!ret.panicked);
+        assert_eq!(ret.errors.len(), 4);
+
+        let ret = Parser::new(&allocator, source, source_type)
+            .recover_from_errors(true)
+            .max_errors(3)
+            .parse();
+        assert!(ret.panicked);
+        assert!(ret.program.is_empty());
+        assert_eq!(ret.errors.len(), 4);
+        assert_eq!(
+            ret.errors.last().unwrap().to_string(),
+            "Too many errors, parsing stopped after 3 errors"
+        );
+    }
+
+    #[test]
+    fn reparse() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "let a = 1;\n// b\nlet b = 2;\nfoo\nlet c = 3;";
+        let edited = "let a = 1;\n// b\nlet b = 2;\nfoo\n.bar()\nlet c = 3;";
+        let edit = Span::new(30, 30);
+
+        let previous = Parser::new(&allocator, source, source_type).parse();
+        let first: *const Statement = &previous.program.body[0];
+        let ret = Parser::new(&allocator, edited, source_type).reparse(previous, edit);
+        let expected = Parser::new(&allocator, edited, source_type).parse();
+
+        assert!(ret.errors.is_empty());
+        assert!(std::ptr::eq(&ret.program.body[0], first));
+        assert_eq!(
+            serde_json::to_string(&ret.program).unwrap(),
+            serde_json::to_string(&expected.program).unwrap()
+        );
+        assert_eq!(ret.trivias.comments().len(), 1);
+    }
+
+    #[test]
+    fn reparse_after_edit() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "let a = 1;\nlet b = 2;\nfoo;\nbar;\n// c\nif (c) d; else e;";
+        // The `;` after `foo` replaced by `,` continues it with `bar`
+        let edited = "let a = 1;\nlet b = 2;\nfoo,\nbar;\n// c\nif (c) d; else e;";
+        let edit = Span::new(25, 26);
+
+        let previous = Parser::new(&allocator, source, source_type).parse();
+        let Statement::IfStatement(last) = &previous.program.body[4] else { unreachable!() };
+        let last: *const IfStatement = &**last;
+        let ret = Parser::new(&allocator, edited, source_type).reparse(previous, edit);
+        let expected = Parser::new(&allocator, edited, source_type).parse();
+
+        assert!(ret.errors.is_empty());
+        assert_eq!(ret.program.body.len(), 4);
+        let Statement::IfStatement(if_stmt) = &ret.program.body[3] else { unreachable!() };
+        assert!(std::ptr::eq(&**if_stmt, last));
+        assert_eq!(
+            serde_json::to_string(&ret.program).unwrap(),
+            serde_json::to_string(&expected.program).unwrap()
+        );
+        assert_eq!(ret.trivias.comments().len(), 1);
+        assert_eq!(ret.token_spans.iter().count(), 1);
+    }
+
+    #[test]
+    fn reparse_insert() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "let a = 1;\nlet b = 2;\nfoo(b);\n// c\nif (c) d; else e;";
+        let edited = "let a = 1;\nlet b = 2;\nfoo(b, 3 + 4);\n// c\nif (c) d; else e;";
+        let edit = Span::new(27, 27);
+
+        let previous = Parser::new(&allocator, source, source_type).parse();
+        let Statement::IfStatement(last) = &previous.program.body[3] else { unreachable!() };
+        let last: *const IfStatement = &**last;
+        let ret = Parser::new(&allocator, edited, source_type).reparse(previous, edit);
+        let expected = Parser::new(&allocator, edited, source_type).parse();
+
+        assert!(ret.errors.is_empty());
+        let Statement::IfStatement(if_stmt) = &ret.program.body[3] else { unreachable!() };
+        assert!(std::ptr::eq(&**if_stmt, last));
+        assert_eq!(if_stmt.span.source_text(edited), "if (c) d; else e;");
+        assert_eq!(
+            serde_json::to_string(&ret.program).unwrap(),
+            serde_json::to_string(&expected.program).unwrap()
+        );
+        let comments = |trivias: &Trivias| trivias.comments().keys().copied().collect::<Vec<_>>();
+        assert_eq!(comments(&ret.trivias), comments(&expected.trivias));
+        assert_eq!(ret.token_spans.if_else(if_stmt), expected.token_spans.if_else(if_stmt));
+    }
+
+    #[test]
+    fn reparse_delete() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let source = "let a = 1;\nlet b = 2;\nfoo(b, 3 + 4);\n/* c */\nx ? y : z;";
+        let edited = "let a = 1;\nlet b = 2;\nfoo(b);\n/* c */\nx ? y : z;";
+        let edit = Span::new(27, 34);
+
+        let previous = Parser::new(&allocator, source, source_type).parse();
+        let Statement::ExpressionStatement(last) = &previous.program.body[3] else {
+            unreachable!()
+        };
+        let last: *const ExpressionStatement = &**last;
+        let ret = Parser::new(&allocator, edited, source_type).reparse(previous, edit);
+        let expected = Parser::new(&allocator, edited, source_type).parse();
+
+        assert!(ret.errors.is_empty());
+        let Statement::ExpressionStatement(stmt) = &ret.program.body[3] else { unreachable!() };
+        assert!(std::ptr::eq(&**stmt, last));
+        assert_eq!(stmt.span.source_text(edited), "x ? y : z;");
+        assert_eq!(
+            serde_json::to_string(&ret.program).unwrap(),
+            serde_json::to_string(&expected.program).unwrap()
+        );
+        let comments = |trivias: &Trivias| trivias.comments().keys().copied().collect::<Vec<_>>();
+        assert_eq!(comments(&ret.trivias), comments(&expected.trivias));
+        let Expression::ConditionalExpression(expr) = &stmt.expression else { unreachable!() };
+        assert_eq!(ret.token_spans.conditional(expr), expected.token_spans.conditional(expr));
+    }
+
+    #[test]
+    fn decorators() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let source = "@a.b() class C { @c accessor #d = 1; @(e) f() {} }";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        let Statement::Declaration(Declaration::ClassDeclaration(class)) = &ret.program.body[0]
+        else {
+            panic!("expected a class declaration");
+        };
+        assert_eq!(class.decorators[0].expression.span().source_text(source), "a.b()");
+        let ClassElement::AccessorProperty(accessor) = &class.body.body[0] else {
+            panic!("expected an accessor property");
+        };
+        assert_eq!(accessor.decorators[0].span.source_text(source), "@c");
+        let ClassElement::MethodDefinition(method) = &class.body.body[1] else {
+            panic!("expected a method definition");
+        };
+        assert_eq!(method.decorators[0].expression.span().source_text(source), "e");
+
+        let source = "class C { m(@a x) {} }";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert_eq!(ret.errors[0].to_string(), "Decorators are not valid here");
+        let ret = Parser::new(&allocator, source, source_type).legacy_decorators(true).parse();
+        assert!(ret.errors.is_empty());
+
+        let source = "@a()() class C {}";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(!ret.errors.is_empty());
+        let ret = Parser::new(&allocator, source, source_type.with_typescript(true)).parse();
+        assert!(ret.errors.is_empty());
+
+        let source = "@a export @b class C {}";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert_eq!(ret.errors.len(), 1);
+    }
+
+    #[test]
+    fn using_declarations() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let source = "using a = b; await using c = d, e = f; for (using g of h); using\ni = j;";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        let kinds = ret
+            .program
+            .body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::Declaration(Declaration::VariableDeclaration(decl)) => Some(decl.kind),
+                Statement::ForOfStatement(stmt) => match &stmt.left {
+                    ForStatementLeft::VariableDeclaration(decl) => Some(decl.kind),
+                    ForStatementLeft::AssignmentTarget(_) => None,
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                VariableDeclarationKind::Using,
+                VariableDeclarationKind::AwaitUsing,
+                VariableDeclarationKind::Using
+            ]
+        );
+
+        // `using` is still an identifier
+        let source = "using[0]; using.x; using(x); for (using of x);";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+
+        for source in ["using a;", "using a = b, { c } = d;"] {
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert_eq!(ret.errors.len(), 1, "{source}");
+        }
+    }
+
+    #[test]
+    fn typescript_5() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true).with_typescript(true);
+        let source = "function f<const T, in out U, const in V>() {}";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        let Statement::Declaration(Declaration::FunctionDeclaration(func)) = &ret.program.body[0]
+        else {
+            panic!("expected a function declaration");
+        };
+        let params = &func.type_parameters.as_ref().unwrap().params;
+        let modifiers = params.iter().map(|p| (p.r#const, p.r#in, p.out)).collect::<Vec<_>>();
+        assert_eq!(modifiers, [(true, false, false), (false, true, true), (true, true, false)]);
+
+        let source =
+            "let a = { b: 1 } satisfies Record<string, number>; export type * as ns from 'c';";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+
+        let source = "export type * from 'c';";
+        let ret = Parser::new(&allocator, source, SourceType::default().with_module(true)).parse();
+        assert!(!ret.errors.is_empty());
+    }
+
+    #[test]
+    fn definition_file() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true).with_typescript_definition(true);
+        let source = "namespace N { const a: number; }
+            declare module 'm';
+            export default function f(): void;
+            export function g(a: string): void;
+            export function g(a: number): void;";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+
+        // `declare` makes the declaration ambient in a TypeScript file
+        let source_type = SourceType::default().with_module(true).with_typescript(true);
+        let source = "export declare const a: number; declare namespace N { const b: number; }";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        let source = "namespace N { const b: number; }";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert_eq!(ret.errors.len(), 1);
+    }
+
+    #[test]
+    fn export_name_lone_surrogate() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let sources = [r#"export { a as "\uD83D\uDE00" }"#, r#"export { a as "\\uD800" }"#];
+        for source in sources {
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert!(ret.errors.is_empty(), "{source}");
+        }
+        let sources = [r#"export { a as "\uD800" }"#, r#"export { a as "\u{DC00}" }"#];
+        for source in sources {
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert_eq!(
+                ret.errors.first().map(ToString::to_string).as_deref(),
+                Some("An export name cannot include a unicode lone surrogate"),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn generic_arrows_in_jsx() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path("foo.tsx").unwrap();
+        let source = "const id = <T>(x: T) => x;";
+
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert_eq!(
+            ret.errors.first().unwrap().to_string(),
+            "Expected a closing tag for this JSX element"
+        );
+
+        let ret = Parser::new(&allocator, source, source_type).generic_arrows_in_jsx(true).parse();
+        assert!(ret.errors.is_empty());
+        let ret = Parser::new(&allocator, source, source_type).jsx(false).parse();
+        assert!(ret.errors.is_empty());
+
+        // JSX is still parsed when it is not a generic arrow function
+        let source = "const a = <T>(x)</T>;";
+        let ret = Parser::new(&allocator, source, source_type).generic_arrows_in_jsx(true).parse();
+        assert!(ret.errors.is_empty());
+        let ret = Parser::new(&allocator, "const a = <T>x;", source_type).jsx(false).parse();
+        assert!(ret.errors.is_empty());
+    }
+
+    #[test]
+    fn import_attributes() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let source = r#"import a from "a" with { type: "json" };
+            import b from "b" assert { type: "json" };
+            export * from "c" with { "type": "json" };
+            export { d } from "d" with { type: "json" };"#;
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        let keywords = ret
+            .program
+            .body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::ModuleDeclaration(decl) => match &**decl {
+                    ModuleDeclaration::ImportDeclaration(decl) => decl.with_clause.as_ref(),
+                    ModuleDeclaration::ExportAllDeclaration(decl) => decl.with_clause.as_ref(),
+                    ModuleDeclaration::ExportNamedDeclaration(decl) => decl.with_clause.as_ref(),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .map(|clause| clause.attributes_keyword.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keywords, ["with", "assert", "with", "with"]);
+
+        let source = "import a from 'a'\nassert { type: 'json' }";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(!ret.errors.is_empty());
+
+        let source = "import a from 'a' with { type: 'json', type: 'json' }";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert_eq!(ret.errors.len(), 1);
+    }
+
+    #[test]
+    fn hashbang() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        for source in ["#!/usr/bin/env node\nfoo()", "#!/usr/bin/env node\r\nfoo()"] {
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert!(ret.errors.is_empty());
+            let hashbang = ret.program.hashbang.as_ref().unwrap();
+            assert_eq!(hashbang.value, "/usr/bin/env node");
+            assert_eq!(hashbang.span, Span::new(0, 19));
+            assert_eq!(ret.program.body.len(), 1);
+        }
+
+        let ret = Parser::new(&allocator, "#!/usr/bin/env node", source_type).parse();
+        assert!(ret.errors.is_empty());
+        assert!(ret.program.body.is_empty());
+
+        let ret = Parser::new(&allocator, "foo()\n#!/usr/bin/env node", source_type).parse();
+        assert!(!ret.errors.is_empty());
+    }
+
+    #[test]
+    fn token_spans() {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_typescript(true);
+        let source = "if (a) /* else */ b; else c;
+            class A<T extends U> extends B implements I {}
+            const f = (x): ((y: T) => T) => x;
+            let d = a ? { b: 1 } : c;";
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        assert!(ret.errors.is_empty());
+        let tokens = &ret.token_spans;
+        let text = |span: Span| &source[span.start as usize..span.end as usize];
+
+        let Statement::IfStatement(stmt) = &ret.program.body[0] else { unreachable!() };
+        let span = tokens.if_else(stmt).unwrap();
+        assert_eq!(span.start as usize, source.find("; else").unwrap() + 2);
+        assert_eq!(text(span), "else");
+
+        let Statement::Declaration(Declaration::ClassDeclaration(class)) = &ret.program.body[1]
+        else {
+            unreachable!()
+        };
+        let span = tokens.class_extends(class).unwrap();
+        assert_eq!(span.start as usize, source.find("extends B").unwrap());
+        let span = tokens.class_implements(class).unwrap();
+        assert_eq!(text(span), "implements");
+
+        let Statement::Declaration(Declaration::VariableDeclaration(decl)) = &ret.program.body[2]
+        else {
+            unreachable!()
+        };
+        let Some(Expression::ArrowExpression(arrow)) = &decl.declarations[0].init else {
+            unreachable!()
+        };
+        let span = tokens.arrow(arrow).unwrap();
+        assert_eq!(span.start as usize, source.find("=> x").unwrap());
+
+        let Statement::Declaration(Declaration::VariableDeclaration(decl)) = &ret.program.body[3]
+        else {
+            unreachable!()
+        };
+        let Some(Expression::ConditionalExpression(expr)) = &decl.declarations[0].init else {
+            unreachable!()
+        };
+        let (question, colon) = tokens.conditional(expr).unwrap();
+        assert_eq!(text(question), "?");
+        assert_eq!(colon.start as usize, source.find(": c").unwrap());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let source_type = SourceType::default().with_module(true).with_typescript(true);
+        let sources = [
+            "import a, { b as c } from 'a'; export default class A extends B { #x = 1n; static { this.#x; } }",
+            "for (let [a, ...b] of c) { label: if (a) break label; else continue; }",
+            "const f = async ({ a = 1, ...b }, c) => { await a?.[b](...c); return /x/gu.test(`${a}`); };",
+            "var x = 0x1F, y = 1e3; function* g() { yield* [x, y]; } try { x++ } catch { } finally { }",
+            "enum E { A = 1 } namespace N { export type T<U> = keyof U | U[]; }\nlet z: string | number = <any>x!;",
+        ];
+        for source in sources {
+            let allocator = Allocator::default();
+            let ret = Parser::new(&allocator, source, source_type).parse();
+            assert!(ret.errors.is_empty(), "failed to parse {source}");
+            let json = ret.program.to_json();
+
+            let program = Program::from_json(&allocator, &json).unwrap();
+            assert_eq!(program.to_json(), json, "for source {source}");
+        }
+
+        let source = "let a = 1, b = 0x10n;";
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::default()).parse();
+        let program = Program::from_json(&allocator, &ret.program.to_json()).unwrap();
+        let Statement::Declaration(Declaration::VariableDeclaration(decl)) = &program.body[0]
+        else {
+            unreachable!()
+        };
+        assert_eq!(decl.declarations[1].kind, VariableDeclarationKind::Let);
+        let Some(Expression::NumberLiteral(lit)) = &decl.declarations[0].init else {
+            unreachable!()
+        };
+        assert_eq!(lit.raw, "1");
+        let Some(Expression::BigintLiteral(lit)) = &decl.declarations[1].init else {
+            unreachable!()
+        };
+        assert_eq!(lit.raw.as_str(), "16n");
+
+        assert!(Program::from_json(&allocator, r#"{ "type": "Statement" }"#).is_err());
+    }
 }
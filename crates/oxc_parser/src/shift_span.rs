@@ -0,0 +1,376 @@
+//! Moving the spans of the statements reused by [`Parser::reparse`](crate::Parser::reparse)
+//!
+//! Statements after an edit are unchanged in the edited source text, but they start at a
+//! different offset when the edit inserts or deletes text, so each span in them is moved by the
+//! difference in length.
+
+use oxc_allocator::{Box, Vec};
+use oxc_ast::ast::*;
+use oxc_span::Span;
+
+/// Move the spans of a node after an edit, from the offsets of the previous source text to the
+/// offsets of the edited source text
+pub trait ShiftSpan {
+    /// `from` is the end of the edit in the previous source text, and `to` in the edited one.
+    /// The node must start at or after `from`.
+    fn shift(&mut self, from: u32, to: u32);
+}
+
+impl ShiftSpan for Span {
+    fn shift(&mut self, from: u32, to: u32) {
+        self.start = self.start - from + to;
+        self.end = self.end - from + to;
+    }
+}
+
+impl<T: ShiftSpan> ShiftSpan for Option<T> {
+    fn shift(&mut self, from: u32, to: u32) {
+        if let Some(node) = self {
+            node.shift(from, to);
+        }
+    }
+}
+
+impl<T: ShiftSpan> ShiftSpan for Box<'_, T> {
+    fn shift(&mut self, from: u32, to: u32) {
+        (**self).shift(from, to);
+    }
+}
+
+impl<T: ShiftSpan> ShiftSpan for Vec<'_, T> {
+    fn shift(&mut self, from: u32, to: u32) {
+        for node in self.iter_mut() {
+            node.shift(from, to);
+        }
+    }
+}
+
+impl ShiftSpan for Modifiers<'_> {
+    fn shift(&mut self, from: u32, to: u32) {
+        for modifier in self {
+            modifier.shift(from, to);
+        }
+    }
+}
+
+/// Shift the spans in the listed fields of structs
+macro_rules! shift_fields {
+    ($($ty:ty { $($field:ident),* })*) => {
+        $(
+            impl ShiftSpan for $ty {
+                fn shift(&mut self, from: u32, to: u32) {
+                    $(self.$field.shift(from, to);)*
+                }
+            }
+        )*
+    };
+}
+
+/// Shift the spans of the nodes in the listed variants of enums
+macro_rules! shift_variants {
+    ($($ty:ty { $($variant:ident),* })*) => {
+        $(
+            impl ShiftSpan for $ty {
+                fn shift(&mut self, from: u32, to: u32) {
+                    match self {
+                        $(Self::$variant(node) => node.shift(from, to),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+shift_fields! {
+    // JavaScript
+    IdentifierName { span }
+    IdentifierReference { span }
+    BindingIdentifier { span }
+    LabelIdentifier { span }
+    ThisExpression { span }
+    ErrorExpression { span }
+    ArrayExpression<'_> { span, elements, trailing_comma }
+    ObjectExpression<'_> { span, properties, trailing_comma }
+    ObjectProperty<'_> { span, key, value, init }
+    TemplateLiteral<'_> { span, quasis, expressions }
+    TaggedTemplateExpression<'_> { span, tag, quasi, type_parameters }
+    TemplateElement { span }
+    ComputedMemberExpression<'_> { span, object, expression }
+    StaticMemberExpression<'_> { span, object, property }
+    PrivateFieldExpression<'_> { span, object, field }
+    CallExpression<'_> { span, callee, arguments, type_parameters }
+    NewExpression<'_> { span, callee, arguments, type_parameters }
+    MetaProperty { span, meta, property }
+    SpreadElement<'_> { span, argument }
+    UpdateExpression<'_> { span, argument }
+    UnaryExpression<'_> { span, argument }
+    BinaryExpression<'_> { span, left, right }
+    PrivateInExpression<'_> { span, left, right }
+    LogicalExpression<'_> { span, left, right }
+    ConditionalExpression<'_> { span, test, consequent, alternate }
+    AssignmentExpression<'_> { span, left, right }
+    ArrayAssignmentTarget<'_> { span, elements, rest, trailing_comma }
+    ObjectAssignmentTarget<'_> { span, properties, rest }
+    AssignmentTargetWithDefault<'_> { span, binding, init }
+    AssignmentTargetPropertyIdentifier<'_> { span, binding, init }
+    AssignmentTargetPropertyProperty<'_> { span, name, binding }
+    SequenceExpression<'_> { span, expressions }
+    Super { span }
+    AwaitExpression<'_> { span, argument }
+    ChainExpression<'_> { span, expression }
+    ParenthesizedExpression<'_> { span, expression }
+    Directive { span, expression }
+    Hashbang { span }
+    BlockStatement<'_> { span, body }
+    VariableDeclaration<'_> { span, declarations, modifiers }
+    VariableDeclarator<'_> { span, id, init }
+    EmptyStatement { span }
+    ErrorStatement { span }
+    ExpressionStatement<'_> { span, expression }
+    IfStatement<'_> { span, test, consequent, alternate }
+    DoWhileStatement<'_> { span, body, test }
+    WhileStatement<'_> { span, test, body }
+    ForStatement<'_> { span, init, test, update, body }
+    ForInStatement<'_> { span, left, right, body }
+    ForOfStatement<'_> { span, left, right, body }
+    ContinueStatement { span, label }
+    BreakStatement { span, label }
+    ReturnStatement<'_> { span, argument }
+    WithStatement<'_> { span, object, body }
+    SwitchStatement<'_> { span, discriminant, cases }
+    SwitchCase<'_> { span, test, consequent }
+    LabeledStatement<'_> { span, label, body }
+    ThrowStatement<'_> { span, argument }
+    TryStatement<'_> { span, block, handler, finalizer }
+    CatchClause<'_> { span, param, body }
+    DebuggerStatement { span }
+    BindingPattern<'_> { kind, type_annotation }
+    AssignmentPattern<'_> { span, left, right }
+    ObjectPattern<'_> { span, properties, rest }
+    BindingProperty<'_> { span, key, value }
+    ArrayPattern<'_> { span, elements, rest }
+    RestElement<'_> { span, argument }
+    Function<'_> { span, id, params, body, type_parameters, return_type, modifiers }
+    FormalParameters<'_> { span, items, rest }
+    FormalParameter<'_> { span, pattern, decorators }
+    FunctionBody<'_> { span, directives, statements }
+    ArrowExpression<'_> { span, params, body, type_parameters, return_type }
+    YieldExpression<'_> { span, argument }
+    Class<'_> {
+        span, id, super_class, body, type_parameters, super_type_parameters, implements,
+        decorators, modifiers
+    }
+    ClassBody<'_> { span, body }
+    MethodDefinition<'_> { span, key, value, decorators }
+    PropertyDefinition<'_> { span, key, value, type_annotation, decorators }
+    PrivateIdentifier { span }
+    StaticBlock<'_> { span, body }
+    AccessorProperty<'_> { span, key, value, decorators }
+    ImportExpression<'_> { span, source, arguments }
+    ImportDeclaration<'_> { span, specifiers, source, with_clause }
+    ImportSpecifier { span, imported, local }
+    ImportDefaultSpecifier { span, local }
+    ImportNamespaceSpecifier { span, local }
+    WithClause<'_> { span, attributes_keyword, with_entries }
+    ImportAttribute { span, key, value }
+    ExportNamedDeclaration<'_> { span, declaration, specifiers, source, with_clause }
+    ExportDefaultDeclaration<'_> { span, declaration, exported }
+    ExportAllDeclaration<'_> { span, exported, source, with_clause }
+    ExportSpecifier { span, local, exported }
+
+    // Literal
+    BooleanLiteral { span }
+    NullLiteral { span }
+    NumberLiteral<'_> { span }
+    BigintLiteral { span }
+    RegExpLiteral { span }
+    StringLiteral { span }
+
+    // JSX
+    JSXElement<'_> { span, opening_element, closing_element, children }
+    JSXOpeningElement<'_> { span, name, attributes, type_parameters }
+    JSXClosingElement<'_> { span, name }
+    JSXFragment<'_> { span, opening_fragment, closing_fragment, children }
+    JSXOpeningFragment { span }
+    JSXClosingFragment { span }
+    JSXNamespacedName { span, namespace, property }
+    JSXMemberExpression<'_> { span, object, property }
+    JSXExpressionContainer<'_> { span, expression }
+    JSXEmptyExpression { span }
+    JSXAttribute<'_> { span, name, value }
+    JSXSpreadAttribute<'_> { span, argument }
+    JSXIdentifier { span }
+    JSXSpreadChild<'_> { span, expression }
+    JSXText { span }
+
+    // TypeScript
+    TSEnumDeclaration<'_> { span, id, body, modifiers }
+    TSEnumBody<'_> { span, members }
+    TSEnumMember<'_> { span, id, initializer }
+    TSTypeAnnotation<'_> { span, type_annotation }
+    TSLiteralType<'_> { span, literal }
+    TSConditionalType<'_> { span, check_type, extends_type, true_type, false_type }
+    TSUnionType<'_> { span, types }
+    TSIntersectionType<'_> { span, types }
+    TSTypeOperatorType<'_> { span, type_annotation }
+    TSArrayType<'_> { span, element_type }
+    TSIndexedAccessType<'_> { span, object_type, index_type }
+    TSTupleType<'_> { span, element_types }
+    TSNamedTupleMember<'_> { span, element_type, label }
+    TSOptionalType<'_> { span, type_annotation }
+    TSRestType<'_> { span, type_annotation }
+    TSAnyKeyword { span }
+    TSStringKeyword { span }
+    TSBooleanKeyword { span }
+    TSNumberKeyword { span }
+    TSNeverKeyword { span }
+    TSUnknownKeyword { span }
+    TSNullKeyword { span }
+    TSUndefinedKeyword { span }
+    TSVoidKeyword { span }
+    TSSymbolKeyword { span }
+    TSThisKeyword { span }
+    TSObjectKeyword { span }
+    TSBigIntKeyword { span }
+    TSTypeReference<'_> { span, type_name, type_parameters }
+    TSQualifiedName<'_> { span, left, right }
+    TSTypeParameterInstantiation<'_> { span, params }
+    TSTypeParameter<'_> { span, name, constraint, default }
+    TSTypeParameterDeclaration<'_> { span, params }
+    TSTypeAliasDeclaration<'_> { span, id, type_annotation, type_parameters, modifiers }
+    TSAbstractMethodDefinition<'_> { method_definition }
+    TSAbstractPropertyDefinition<'_> { property_definition }
+    TSClassImplements<'_> { span, expression, type_parameters }
+    TSInterfaceDeclaration<'_> { span, id, body, type_parameters, extends, modifiers }
+    TSInterfaceBody<'_> { span, body }
+    TSPropertySignature<'_> { span, key, type_annotation }
+    TSIndexSignature<'_> { span, parameters, type_annotation }
+    TSCallSignatureDeclaration<'_> { span, params, return_type, type_parameters }
+    TSMethodSignature<'_> { span, key, params, return_type, type_parameters }
+    TSConstructSignatureDeclaration<'_> { span, params, return_type, type_parameters }
+    TSIndexSignatureName<'_> { span, type_annotation }
+    TSInterfaceHeritage<'_> { span, expression, type_parameters }
+    TSTypePredicate<'_> { span, parameter_name, type_annotation }
+    TSModuleDeclaration<'_> { span, id, body, modifiers }
+    TSModuleBlock<'_> { span, body }
+    TSTypeLiteral<'_> { span, members }
+    TSInferType<'_> { span, type_parameter }
+    TSTypeQuery<'_> { span, expr_name, type_parameters }
+    TSImportType<'_> { span, parameter, qualifier, type_parameters }
+    TSFunctionType<'_> { span, params, return_type, type_parameters }
+    TSConstructorType<'_> { span, params, return_type, type_parameters }
+    TSMappedType<'_> { span, type_parameter, name_type, type_annotation }
+    TSTemplateLiteralType<'_> { span, quasis, types }
+    TSAsExpression<'_> { span, expression, type_annotation }
+    TSSatisfiesExpression<'_> { span, expression, type_annotation }
+    TSTypeAssertion<'_> { span, expression, type_annotation }
+    TSImportEqualsDeclaration<'_> { span, id, module_reference }
+    TSExternalModuleReference { span, expression }
+    TSNonNullExpression<'_> { span, expression }
+    Decorator<'_> { span, expression }
+    Modifier { span }
+    TSExportAssignment<'_> { span, expression }
+    TSNamespaceExportDeclaration { span, id }
+    TSInstantiationExpression<'_> { span, expression, type_parameters }
+
+    // JSDoc
+    JSDocNullableType<'_> { span, type_annotation }
+    JSDocUnknownType { span }
+}
+
+shift_variants! {
+    // JavaScript
+    Expression<'_> {
+        BooleanLiteral, NullLiteral, NumberLiteral, BigintLiteral, RegExpLiteral, StringLiteral,
+        TemplateLiteral, Identifier, MetaProperty, Super, ArrayExpression, ArrowExpression,
+        AssignmentExpression, AwaitExpression, BinaryExpression, CallExpression, ChainExpression,
+        ClassExpression, ConditionalExpression, FunctionExpression, ImportExpression,
+        LogicalExpression, MemberExpression, NewExpression, ObjectExpression,
+        ParenthesizedExpression, SequenceExpression, TaggedTemplateExpression, ThisExpression,
+        UnaryExpression, UpdateExpression, YieldExpression, PrivateInExpression, ErrorExpression,
+        JSXElement, JSXFragment, TSAsExpression, TSSatisfiesExpression, TSTypeAssertion,
+        TSNonNullExpression, TSInstantiationExpression
+    }
+    ArrayExpressionElement<'_> { SpreadElement, Expression, Elision }
+    ObjectPropertyKind<'_> { ObjectProperty, SpreadProperty }
+    PropertyKey<'_> { Identifier, PrivateIdentifier, Expression }
+    MemberExpression<'_> {
+        ComputedMemberExpression, StaticMemberExpression, PrivateFieldExpression
+    }
+    Argument<'_> { SpreadElement, Expression }
+    AssignmentTarget<'_> { SimpleAssignmentTarget, AssignmentTargetPattern }
+    SimpleAssignmentTarget<'_> {
+        AssignmentTargetIdentifier, MemberAssignmentTarget, TSAsExpression, TSSatisfiesExpression,
+        TSNonNullExpression, TSTypeAssertion
+    }
+    AssignmentTargetPattern<'_> { ArrayAssignmentTarget, ObjectAssignmentTarget }
+    AssignmentTargetMaybeDefault<'_> { AssignmentTarget, AssignmentTargetWithDefault }
+    AssignmentTargetProperty<'_> {
+        AssignmentTargetPropertyIdentifier, AssignmentTargetPropertyProperty
+    }
+    ChainElement<'_> { CallExpression, MemberExpression }
+    Statement<'_> {
+        BlockStatement, BreakStatement, ContinueStatement, DebuggerStatement, DoWhileStatement,
+        EmptyStatement, ErrorStatement, ExpressionStatement, ForInStatement, ForOfStatement,
+        ForStatement, IfStatement, LabeledStatement, ReturnStatement, SwitchStatement,
+        ThrowStatement, TryStatement, WhileStatement, WithStatement, ModuleDeclaration,
+        Declaration
+    }
+    Declaration<'_> {
+        VariableDeclaration, FunctionDeclaration, ClassDeclaration, TSTypeAliasDeclaration,
+        TSInterfaceDeclaration, TSEnumDeclaration, TSModuleDeclaration, TSImportEqualsDeclaration
+    }
+    ForStatementInit<'_> { VariableDeclaration, Expression }
+    ForStatementLeft<'_> { VariableDeclaration, AssignmentTarget }
+    BindingPatternKind<'_> { BindingIdentifier, ObjectPattern, ArrayPattern, AssignmentPattern }
+    ClassElement<'_> {
+        StaticBlock, MethodDefinition, PropertyDefinition, AccessorProperty,
+        TSAbstractMethodDefinition, TSAbstractPropertyDefinition, TSIndexSignature
+    }
+    ModuleDeclaration<'_> {
+        ImportDeclaration, ExportAllDeclaration, ExportDefaultDeclaration, ExportNamedDeclaration,
+        TSExportAssignment, TSNamespaceExportDeclaration
+    }
+    ImportDeclarationSpecifier { ImportSpecifier, ImportDefaultSpecifier, ImportNamespaceSpecifier }
+    ImportAttributeKey { Identifier, StringLiteral }
+    ExportDefaultDeclarationKind<'_> {
+        Expression, FunctionDeclaration, ClassDeclaration, TSInterfaceDeclaration,
+        TSEnumDeclaration
+    }
+    ModuleExportName { Identifier, StringLiteral }
+
+    // JSX
+    JSXElementName<'_> { Identifier, NamespacedName, MemberExpression }
+    JSXMemberExpressionObject<'_> { Identifier, MemberExpression }
+    JSXExpression<'_> { Expression, EmptyExpression }
+    JSXAttributeItem<'_> { Attribute, SpreadAttribute }
+    JSXAttributeName<'_> { Identifier, NamespacedName }
+    JSXAttributeValue<'_> { StringLiteral, ExpressionContainer, Element, Fragment }
+    JSXChild<'_> { Text, Element, Fragment, ExpressionContainer, Spread }
+
+    // TypeScript
+    TSEnumMemberName<'_> { Identifier, StringLiteral, ComputedPropertyName, NumberLiteral }
+    TSLiteral<'_> {
+        BooleanLiteral, NullLiteral, NumberLiteral, BigintLiteral, RegExpLiteral, StringLiteral,
+        TemplateLiteral, UnaryExpression
+    }
+    TSType<'_> {
+        TSAnyKeyword, TSBigIntKeyword, TSBooleanKeyword, TSNeverKeyword, TSNullKeyword,
+        TSNumberKeyword, TSObjectKeyword, TSStringKeyword, TSSymbolKeyword, TSThisKeyword,
+        TSUndefinedKeyword, TSUnknownKeyword, TSVoidKeyword, TSArrayType, TSConditionalType,
+        TSConstructorType, TSFunctionType, TSImportType, TSIndexedAccessType, TSInferType,
+        TSIntersectionType, TSLiteralType, TSMappedType, TSQualifiedName, TSTemplateLiteralType,
+        TSTupleType, TSTypeLiteral, TSTypeOperatorType, TSTypePredicate, TSTypeQuery,
+        TSTypeReference, TSUnionType, JSDocNullableType, JSDocUnknownType
+    }
+    TSTupleElement<'_> { TSType, TSOptionalType, TSRestType, TSNamedTupleMember }
+    TSTypeName<'_> { IdentifierName, QualifiedName }
+    TSSignature<'_> {
+        TSIndexSignature, TSPropertySignature, TSCallSignatureDeclaration,
+        TSConstructSignatureDeclaration, TSMethodSignature
+    }
+    TSTypePredicateName { Identifier, This }
+    TSModuleDeclarationName { Identifier, StringLiteral }
+    TSModuleDeclarationBody<'_> { TSModuleDeclaration, TSModuleBlock }
+    TSModuleReference<'_> { TypeName, ExternalModuleReference }
+}
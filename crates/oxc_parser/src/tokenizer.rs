@@ -0,0 +1,210 @@
+//! Token stream without building an AST
+
+use std::collections::VecDeque;
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::Error;
+use oxc_span::SourceType;
+
+use crate::lexer::{Kind, Lexer, Token};
+
+/// Tokens of a source text with their spans, including comments,
+/// for tools which do not need an AST such as syntax highlighters.
+///
+/// Comments are returned as `Kind::Comment` and `Kind::MultiLineComment` tokens
+/// spanning the `//`, `/*` and `*/` delimiters. Whitespace is not returned.
+///
+/// Without a parser, whether `/` starts a regular expression is decided by the previous token,
+/// e.g. `a / b` is a division and `= /b/` a regular expression,
+/// and `}` continues a template literal when it closes a `${` substitution.
+/// JSX is not supported, JSX text is returned as JavaScript tokens.
+///
+/// ```
+/// use oxc_allocator::Allocator;
+/// use oxc_parser::{Kind, Tokenizer};
+/// use oxc_span::SourceType;
+///
+/// let allocator = Allocator::default();
+/// let kinds = Tokenizer::new(&allocator, "let a = 1;", SourceType::default())
+///     .map(|token| token.kind)
+///     .collect::<Vec<_>>();
+/// assert_eq!(kinds, vec![Kind::Let, Kind::Ident, Kind::Eq, Kind::Decimal, Kind::Semicolon]);
+/// ```
+pub struct Tokenizer<'a> {
+    lexer: Lexer<'a>,
+
+    /// Tokens lexed together with the comments before them
+    pending: VecDeque<Token<'a>>,
+
+    prev_kind: Kind,
+
+    prev_end: u32,
+
+    /// Number of `{` which are not closed yet
+    braces: u32,
+
+    /// The values of `braces` when each open template substitution started
+    templates: Vec<u32>,
+
+    finished: bool,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(allocator: &'a Allocator, source_text: &'a str, source_type: SourceType) -> Self {
+        Self {
+            lexer: Lexer::new(allocator, source_text, source_type),
+            pending: VecDeque::new(),
+            prev_kind: Kind::Eof,
+            prev_end: 0,
+            braces: 0,
+            templates: vec![],
+            finished: false,
+        }
+    }
+
+    /// Syntax errors found so far, such as unterminated strings
+    pub fn errors(&self) -> &[Error] {
+        &self.lexer.errors
+    }
+
+    fn next_significant_token(&mut self) -> Token<'a> {
+        let token = self.lexer.next_token();
+        let token = match token.kind {
+            Kind::Slash | Kind::SlashEq if self.regex_allowed() => {
+                self.lexer.next_regex(token.kind)
+            }
+            Kind::RCurly if self.templates.last() == Some(&self.braces) => {
+                self.templates.pop();
+                self.lexer.next_template_substitution_tail()
+            }
+            _ => token,
+        };
+        match token.kind {
+            Kind::LCurly => self.braces += 1,
+            Kind::RCurly => self.braces = self.braces.saturating_sub(1),
+            Kind::TemplateHead | Kind::TemplateMiddle => self.templates.push(self.braces),
+            _ => {}
+        }
+        token
+    }
+
+    /// A `/` after a token ending an expression is a division,
+    /// otherwise it starts a regular expression.
+    fn regex_allowed(&self) -> bool {
+        !(self.prev_kind.is_literal()
+            || matches!(
+                self.prev_kind,
+                Kind::Ident
+                    | Kind::PrivateIdentifier
+                    | Kind::This
+                    | Kind::Super
+                    | Kind::RParen
+                    | Kind::RBrack
+                    | Kind::RCurly
+                    | Kind::Plus2
+                    | Kind::Minus2
+                    | Kind::NoSubstitutionTemplate
+                    | Kind::TemplateTail
+            ))
+    }
+
+    /// The comments between the previous token and `end`
+    fn comments_before(&self, end: u32) -> impl Iterator<Item = Token<'a>> + '_ {
+        self.lexer.trivia_builder.trivias().comments().range(self.prev_end..end).map(
+            |(start, comment)| {
+                let (kind, end) = if comment.is_single_line() {
+                    (Kind::Comment, comment.end())
+                } else {
+                    (Kind::MultiLineComment, comment.end() + 2)
+                };
+                // `start` is after the `//` or `/*`
+                Token { kind, start: start - 2, end, ..Token::default() }
+            },
+        )
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(token) = self.pending.pop_front() {
+            return Some(token);
+        }
+        if self.finished {
+            return None;
+        }
+        let token = self.next_significant_token();
+        let comments = self.comments_before(token.start).collect::<Vec<_>>();
+        self.pending.extend(comments);
+        self.prev_kind = token.kind;
+        self.prev_end = token.end;
+        if token.kind == Kind::Eof {
+            self.finished = true;
+        } else {
+            self.pending.push_back(token);
+        }
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::SourceType;
+
+    use super::Tokenizer;
+    use crate::Kind;
+
+    fn tokens(source: &str) -> Vec<(Kind, &str)> {
+        let allocator = Allocator::default();
+        Tokenizer::new(&allocator, source, SourceType::default())
+            .map(|token| (token.kind, &source[token.start as usize..token.end as usize]))
+            .collect()
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(
+            tokens("/* a */ b // c\nd"),
+            vec![
+                (Kind::MultiLineComment, "/* a */"),
+                (Kind::Ident, "b"),
+                (Kind::Comment, "// c"),
+                (Kind::Ident, "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn regex_or_division() {
+        assert_eq!(
+            tokens("a / b; c = /d/g"),
+            vec![
+                (Kind::Ident, "a"),
+                (Kind::Slash, "/"),
+                (Kind::Ident, "b"),
+                (Kind::Semicolon, ";"),
+                (Kind::Ident, "c"),
+                (Kind::Eq, "="),
+                (Kind::RegExp, "/d/g"),
+            ]
+        );
+    }
+
+    #[test]
+    fn template() {
+        assert_eq!(
+            tokens("`a${ {b} }c${d}e`"),
+            vec![
+                (Kind::TemplateHead, "`a${"),
+                (Kind::LCurly, "{"),
+                (Kind::Ident, "b"),
+                (Kind::RCurly, "}"),
+                (Kind::TemplateMiddle, "}c${"),
+                (Kind::Ident, "d"),
+                (Kind::TemplateTail, "}e`"),
+            ]
+        );
+    }
+}
@@ -52,14 +52,14 @@ impl<'a> SeparatedList<'a> for TSTupleElementList<'a> {
     fn parse_element(&mut self, p: &mut Parser<'a>) -> Result<()> {
         let span = p.start_span();
         if p.is_at_named_tuple_element() {
-            let _is_rest = p.eat(Kind::Dot3);
+            let rest = p.eat(Kind::Dot3);
             let label = p.parse_identifier_name()?;
             let optional = p.eat(Kind::Question);
             p.expect(Kind::Colon)?;
 
             let element_type = p.parse_ts_type()?;
             self.elements.push(TSTupleElement::TSNamedTupleMember(p.ast.alloc(
-                TSNamedTupleMember { span: p.end_span(span), element_type, label, optional },
+                TSNamedTupleMember { span: p.end_span(span), element_type, label, optional, rest },
             )));
 
             return Ok(());
@@ -34,7 +34,7 @@ impl<'a> Parser<'a> {
 
         let id = self.parse_binding_identifier()?;
         let members = TSEnumMemberList::parse(self)?.members;
-        Ok(self.ast.ts_enum_declaration(span, id, members, modifiers))
+        Ok(self.ast.ts_enum_declaration(self.end_span(span), id, members, modifiers))
     }
 
     pub(crate) fn parse_ts_enum_member(&mut self) -> Result<TSEnumMember<'a>> {
@@ -111,7 +111,13 @@ impl<'a> Parser<'a> {
         let annotation = self.parse_ts_type()?;
 
         self.asi()?;
-        Ok(self.ast.ts_type_alias_declaration(span, id, annotation, params, modifiers))
+        Ok(self.ast.ts_type_alias_declaration(
+            self.end_span(span),
+            id,
+            annotation,
+            params,
+            modifiers,
+        ))
     }
 
     /** ---------------------  Interface  ------------------------ */
@@ -262,8 +268,12 @@ impl<'a> Parser<'a> {
                 self.parse_ts_namespace_or_module_declaration_body(span, Modifiers::empty())?;
             TSModuleDeclarationBody::TSModuleDeclaration(decl)
         } else {
+            // `declare module 'a';` has no block
+            let has_block = self.at(Kind::LCurly);
             let block = self.parse_ts_module_block()?;
-            self.asi()?;
+            if !has_block {
+                self.asi()?;
+            }
             TSModuleDeclarationBody::TSModuleBlock(block)
         };
 
@@ -272,11 +282,11 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn parse_ts_namespace_or_module_declaration(
         &mut self,
+        start_span: Span,
         modifiers: Modifiers<'a>,
     ) -> Result<Box<'a, TSModuleDeclaration<'a>>> {
-        let span = self.start_span();
         self.expect(Kind::Namespace).or_else(|_| self.expect(Kind::Module))?;
-        self.parse_ts_namespace_or_module_declaration_body(span, modifiers)
+        self.parse_ts_namespace_or_module_declaration_body(start_span, modifiers)
     }
 
     pub(crate) fn parse_ts_global_declaration(
@@ -298,7 +308,8 @@ impl<'a> Parser<'a> {
         let (flags, modifiers) = self.eat_modifiers_before_declaration();
         let declare = flags.declare();
         let r#async = flags.r#async();
-        self.ctx = self.ctx.and_ambient(declare).and_await(r#async);
+        // Declarations are already ambient in a definition file or inside `declare namespace`
+        self.ctx = self.ctx.union_ambient_if(declare).and_await(r#async);
 
         let result = self.parse_declaration(start_span, modifiers);
 
@@ -313,7 +324,7 @@ impl<'a> Parser<'a> {
     ) -> Result<Declaration<'a>> {
         match self.cur_kind() {
             Kind::Namespace | Kind::Module => self
-                .parse_ts_namespace_or_module_declaration(modifiers)
+                .parse_ts_namespace_or_module_declaration(start_span, modifiers)
                 .map(Declaration::TSModuleDeclaration),
             Kind::Global => {
                 let decl = if self.peek_at(Kind::LCurly) {
@@ -394,9 +405,9 @@ impl<'a> Parser<'a> {
         is_export: bool,
     ) -> Result<Declaration<'a>> {
         let import_kind = if !self.peek_at(Kind::Eq) && self.eat(Kind::Type) {
-            ImportOrExportKind::Value
-        } else {
             ImportOrExportKind::Type
+        } else {
+            ImportOrExportKind::Value
         };
 
         let id = self.parse_binding_identifier()?;
@@ -438,6 +449,7 @@ impl<'a> Parser<'a> {
             binding,
             /* accessibility */ None,
             /* readonly */ false,
+            /* override */ false,
             /* decorators */ self.ast.new_vec(),
         ))
     }
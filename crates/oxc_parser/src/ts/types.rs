@@ -156,25 +156,19 @@ impl<'a> Parser<'a> {
         let mut out = false;
         let mut r#const = false;
 
-        match self.cur_kind() {
-            Kind::In if self.peek_kind().is_identifier_name() => {
-                self.bump_any();
-                r#in = true;
-                if self.at(Kind::Out) && self.peek_kind().is_identifier_name() {
-                    // `<in out T>`
-                    self.bump_any();
-                    out = true;
-                }
-            }
-            Kind::Out if self.peek_kind().is_identifier_name() => {
-                self.bump_any();
-                out = true;
+        // `<in T>`, `<out T>`, `<in out T>`, `<const T>`
+        loop {
+            let peek_kind = self.peek_kind();
+            if !(peek_kind.is_identifier() || matches!(peek_kind, Kind::In | Kind::Const)) {
+                break;
             }
-            Kind::Const if self.peek_kind().is_identifier_name() => {
-                self.bump_any();
-                r#const = true;
+            match self.cur_kind() {
+                Kind::In if !r#in => r#in = true,
+                Kind::Out if !out => out = true,
+                Kind::Const if !r#const => r#const = true,
+                _ => break,
             }
-            _ => {}
+            self.bump_any();
         }
 
         let name = self.parse_binding_identifier()?;
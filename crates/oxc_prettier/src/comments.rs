@@ -0,0 +1,205 @@
+//! Printing the comments attached to the nodes
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/main/comments/print.js)
+
+use oxc_ast::{AttachedComment, CommentKind, CommentPosition};
+use oxc_span::Span;
+
+use crate::{
+    doc::{array, hardline, indent, join, line, line_suffix, text, Doc},
+    Prettier,
+};
+
+impl<'a> Prettier<'a> {
+    /// `doc` of the node with `span` and its leading and trailing comments
+    pub(crate) fn with_comments(&mut self, span: Span, doc: Doc<'a>) -> Doc<'a> {
+        let comments = self.take_comments(span, |position| position != CommentPosition::Dangling);
+        if comments.is_empty() {
+            return doc;
+        }
+        let mut parts = vec![];
+        for comment in comments.iter().filter(|c| c.position == CommentPosition::Leading) {
+            parts.push(self.print_leading_comment(comment));
+        }
+        parts.push(doc);
+        let mut previous: Option<&AttachedComment> = None;
+        let mut has_line_suffix = false;
+        for comment in comments.iter().filter(|c| c.position == CommentPosition::Trailing) {
+            let (printed, line_suffix) =
+                self.print_trailing_comment(comment, previous, has_line_suffix);
+            parts.push(printed);
+            has_line_suffix = line_suffix;
+            previous = Some(comment);
+        }
+        array(parts)
+    }
+
+    /// Comments inside the node with `span` which are not attached to any child node,
+    /// each on its own line, indented unless `same_indent`
+    pub(crate) fn print_dangling_comments(&mut self, span: Span, same_indent: bool) -> Doc<'a> {
+        let comments = self.take_comments(span, |position| position == CommentPosition::Dangling);
+        if comments.is_empty() {
+            return text("");
+        }
+        let ends_with_line_comment =
+            comments.last().map_or(false, |comment| comment.kind == CommentKind::SingleLine);
+        let printed = comments.iter().map(|comment| self.print_comment(comment)).collect();
+        let mut parts = join(&hardline(), printed);
+        if same_indent && ends_with_line_comment {
+            parts.push(hardline());
+        }
+        if same_indent {
+            array(parts)
+        } else {
+            indent(vec![hardline(), array(parts)])
+        }
+    }
+
+    pub(crate) fn has_dangling_comments(&self, span: Span) -> bool {
+        self.comments
+            .dangling(span)
+            .any(|comment| !self.printed_comments.contains(&comment.span.start))
+    }
+
+    /// Whether the node with `span` has comments not printed yet
+    pub(crate) fn has_comments(&self, span: Span) -> bool {
+        self.comments
+            .get(span)
+            .iter()
+            .any(|comment| !self.printed_comments.contains(&comment.span.start))
+    }
+
+    /// Mark the comments within `span` as printed, for source text printed as is
+    pub(crate) fn mark_comments_printed(&mut self, span: Span) {
+        for start in self.trivias.comments().range(span.start..span.end).map(|(start, _)| *start) {
+            self.printed_comments.insert(start);
+        }
+    }
+
+    /// Comments which were not printed by any node, appended at the end of the program
+    pub(crate) fn print_leftover_comments(&self) -> Option<Doc<'a>> {
+        let mut leftover: Vec<AttachedComment> = self
+            .comments
+            .iter()
+            .flat_map(|(_, comments)| comments.iter().copied())
+            .filter(|comment| !self.printed_comments.contains(&comment.span.start))
+            .collect();
+        if leftover.is_empty() {
+            return None;
+        }
+        leftover.sort_by_key(|comment| comment.span.start);
+        let printed = leftover.iter().map(|comment| self.print_comment(comment)).collect();
+        Some(array(vec![hardline(), array(join(&hardline(), printed))]))
+    }
+
+    fn take_comments(
+        &mut self,
+        span: Span,
+        filter: impl Fn(CommentPosition) -> bool,
+    ) -> Vec<AttachedComment> {
+        let comments: Vec<AttachedComment> = self
+            .comments
+            .get(span)
+            .iter()
+            .filter(|comment| filter(comment.position))
+            .filter(|comment| !self.printed_comments.contains(&comment.span.start))
+            .copied()
+            .collect();
+        for comment in &comments {
+            self.printed_comments.insert(comment.span.start);
+        }
+        comments
+    }
+
+    fn print_leading_comment(&self, comment: &AttachedComment) -> Doc<'a> {
+        let range = comment_range(comment);
+        let mut parts = vec![self.print_comment(comment)];
+        if comment.kind == CommentKind::MultiLine {
+            parts.push(if self.has_newline(range.end) {
+                if self.has_newline_backwards(range.start) {
+                    hardline()
+                } else {
+                    line()
+                }
+            } else {
+                text(" ")
+            });
+        } else {
+            parts.push(hardline());
+        }
+        if self.is_next_line_empty_after_newline(range.end) {
+            parts.push(hardline());
+        }
+        array(parts)
+    }
+
+    /// Returns the printed comment and whether it is deferred to the end of the line
+    fn print_trailing_comment(
+        &self,
+        comment: &AttachedComment,
+        previous: Option<&AttachedComment>,
+        previous_has_line_suffix: bool,
+    ) -> (Doc<'a>, bool) {
+        let range = comment_range(comment);
+        let printed = self.print_comment(comment);
+        let is_block = comment.kind == CommentKind::MultiLine;
+        let previous_is_block = previous.map_or(false, |c| c.kind == CommentKind::MultiLine);
+        if (previous_has_line_suffix && !previous_is_block)
+            || self.has_newline_backwards(range.start)
+        {
+            let mut parts = vec![hardline()];
+            if self.is_previous_line_empty(range.start) {
+                parts.push(hardline());
+            }
+            parts.push(printed);
+            return (line_suffix(parts), true);
+        }
+        if !is_block || previous_has_line_suffix {
+            let mut parts = vec![line_suffix(vec![text(" "), printed])];
+            if !is_block {
+                parts.push(Doc::BreakParent);
+            }
+            return (array(parts), true);
+        }
+        (array(vec![text(" "), printed]), false)
+    }
+
+    fn print_comment(&self, comment: &AttachedComment) -> Doc<'a> {
+        let range = comment_range(comment);
+        let source = self.source(range);
+        if comment.kind == CommentKind::SingleLine {
+            return text(source.trim_end());
+        }
+        // Re-indent the lines of JSDoc style comments, which all start with `*`
+        let value = self.source(comment.span);
+        let lines: Vec<&str> = value.split('\n').collect();
+        let is_indentable = lines.len() > 1
+            && lines[1..].iter().enumerate().all(|(index, line)| {
+                let line = line.trim_start();
+                // The last line is followed by the `*` of `*/`
+                line.starts_with('*') || (index == lines.len() - 2 && line.is_empty())
+            });
+        if is_indentable {
+            let last = lines.len() - 1;
+            let lines = lines
+                .iter()
+                .enumerate()
+                .map(|(index, line)| match index {
+                    0 => text(line.trim_end()),
+                    _ if index == last => text(format!(" {}", line.trim_start())),
+                    _ => text(format!(" {}", line.trim())),
+                })
+                .collect();
+            return array(vec![text("/*"), array(join(&hardline(), lines)), text("*/")]);
+        }
+        text(source)
+    }
+}
+
+/// Span of the comment including `//`, `/*` and `*/`
+pub fn comment_range(comment: &AttachedComment) -> Span {
+    match comment.kind {
+        CommentKind::SingleLine => Span::new(comment.span.start - 2, comment.span.end),
+        CommentKind::MultiLine => Span::new(comment.span.start - 2, comment.span.end + 2),
+    }
+}
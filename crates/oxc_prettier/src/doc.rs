@@ -0,0 +1,248 @@
+//! Document IR, the layouts of the formatted code before it is printed
+//!
+//! Same as the [commands](https://github.com/prettier/prettier/blob/main/commands.md) of Prettier.
+
+use std::borrow::Cow;
+
+#[derive(Debug, Clone)]
+pub enum Doc<'a> {
+    Str(Cow<'a, str>),
+    Array(Vec<Self>),
+    /// Contents with one more level of indentation after the line breaks
+    Indent(Vec<Self>),
+    /// Contents printed on a single line if they fit, otherwise all lines directly in the group
+    /// are broken. The `id` allows an [`Doc::IfBreak`] outside of the group to depend on it.
+    Group {
+        contents: Vec<Self>,
+        should_break: bool,
+        id: Option<GroupId>,
+    },
+    /// The first state which fits on the line is printed flat, tried in order,
+    /// otherwise the last state is printed broken
+    ConditionalGroup(Vec<Self>),
+    /// Contents alternating with separators, where a separator is broken only if the content
+    /// after it does not fit on the line, e.g. words of a paragraph
+    Fill(Vec<Self>),
+    /// Printed depending on whether the enclosing group, or the group with `group_id`, is broken
+    IfBreak {
+        break_contents: Box<Self>,
+        flat_contents: Box<Self>,
+        group_id: Option<GroupId>,
+    },
+    Line(Line),
+    /// Deferred until the next line break, e.g. for trailing line comments
+    LineSuffix(Vec<Self>),
+    /// Breaks all enclosing groups
+    BreakParent,
+}
+
+/// Identifies a group printed before an [`Doc::IfBreak`] depending on it
+pub type GroupId = usize;
+
+/// A space or nothing if the enclosing group is flat, otherwise a line break
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Line {
+    /// Always broken, and breaks the enclosing groups
+    pub hard: bool,
+    /// Nothing instead of a space if the enclosing group is flat
+    pub soft: bool,
+    /// A line break without indentation after it, e.g. in template literals
+    pub literal: bool,
+}
+
+impl<'a> Doc<'a> {
+    /// Whether the doc contains a forced break, which will break the enclosing group,
+    /// same as `willBreak` of Prettier
+    pub fn will_break(&self) -> bool {
+        match self {
+            Self::Str(_) => false,
+            Self::BreakParent => true,
+            Self::Line(line) => line.hard,
+            Self::Group { contents, should_break, .. } => {
+                *should_break || contents.iter().any(Doc::will_break)
+            }
+            Self::ConditionalGroup(states) => states.first().map_or(false, Doc::will_break),
+            Self::Array(docs) | Self::Indent(docs) | Self::Fill(docs) | Self::LineSuffix(docs) => {
+                docs.iter().any(Doc::will_break)
+            }
+            Self::IfBreak { break_contents, flat_contents, .. } => {
+                break_contents.will_break() || flat_contents.will_break()
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Str(s) => s.is_empty(),
+            Self::Array(docs) => docs.iter().all(Doc::is_empty),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Doc<'a> {
+    fn from(s: &'a str) -> Self {
+        Self::Str(Cow::Borrowed(s))
+    }
+}
+
+impl<'a> From<String> for Doc<'a> {
+    fn from(s: String) -> Self {
+        Self::Str(Cow::Owned(s))
+    }
+}
+
+impl<'a> From<Vec<Self>> for Doc<'a> {
+    fn from(docs: Vec<Self>) -> Self {
+        Self::Array(docs)
+    }
+}
+
+pub fn text<'a, S: Into<Cow<'a, str>>>(s: S) -> Doc<'a> {
+    Doc::Str(s.into())
+}
+
+pub fn array(docs: Vec<Doc<'_>>) -> Doc<'_> {
+    Doc::Array(docs)
+}
+
+pub fn indent(docs: Vec<Doc<'_>>) -> Doc<'_> {
+    Doc::Indent(docs)
+}
+
+pub fn group(docs: Vec<Doc<'_>>) -> Doc<'_> {
+    Doc::Group { contents: docs, should_break: false, id: None }
+}
+
+pub fn group_with_break(docs: Vec<Doc<'_>>, should_break: bool) -> Doc<'_> {
+    Doc::Group { contents: docs, should_break, id: None }
+}
+
+pub fn group_with_id(docs: Vec<Doc<'_>>, id: GroupId) -> Doc<'_> {
+    Doc::Group { contents: docs, should_break: false, id: Some(id) }
+}
+
+pub fn conditional_group(states: Vec<Doc<'_>>) -> Doc<'_> {
+    Doc::ConditionalGroup(states)
+}
+
+pub fn fill(docs: Vec<Doc<'_>>) -> Doc<'_> {
+    Doc::Fill(docs)
+}
+
+pub fn if_break<'a>(break_contents: Doc<'a>, flat_contents: Doc<'a>) -> Doc<'a> {
+    Doc::IfBreak {
+        break_contents: Box::new(break_contents),
+        flat_contents: Box::new(flat_contents),
+        group_id: None,
+    }
+}
+
+/// Same as [`if_break`], depending on the group with `group_id` instead of the enclosing group
+pub fn if_break_with_id<'a>(
+    break_contents: Doc<'a>,
+    flat_contents: Doc<'a>,
+    group_id: GroupId,
+) -> Doc<'a> {
+    Doc::IfBreak {
+        break_contents: Box::new(break_contents),
+        flat_contents: Box::new(flat_contents),
+        group_id: Some(group_id),
+    }
+}
+
+/// Contents indented if the group with `group_id` is broken, same as `indentIfBreak` of Prettier
+pub fn indent_if_break(docs: Vec<Doc<'_>>, group_id: GroupId) -> Doc<'_> {
+    Doc::IfBreak {
+        break_contents: Box::new(Doc::Indent(docs.clone())),
+        flat_contents: Box::new(Doc::Array(docs)),
+        group_id: Some(group_id),
+    }
+}
+
+pub fn line_suffix(docs: Vec<Doc<'_>>) -> Doc<'_> {
+    Doc::LineSuffix(docs)
+}
+
+/// A space, or a line break if the enclosing group is broken
+pub fn line<'a>() -> Doc<'a> {
+    Doc::Line(Line::default())
+}
+
+/// Nothing, or a line break if the enclosing group is broken
+pub fn softline<'a>() -> Doc<'a> {
+    Doc::Line(Line { soft: true, ..Line::default() })
+}
+
+pub fn hardline<'a>() -> Doc<'a> {
+    Doc::Line(Line { hard: true, ..Line::default() })
+}
+
+pub fn literalline<'a>() -> Doc<'a> {
+    Doc::Line(Line { hard: true, literal: true, ..Line::default() })
+}
+
+pub fn join<'a>(separator: &Doc<'a>, docs: Vec<Doc<'a>>) -> Vec<Doc<'a>> {
+    let mut joined = Vec::with_capacity(docs.len() * 2);
+    for (index, doc) in docs.into_iter().enumerate() {
+        if index > 0 {
+            joined.push(separator.clone());
+        }
+        joined.push(doc);
+    }
+    joined
+}
+
+/// Lines of `doc` as if its groups were flat, e.g. for expressions inside template literals,
+/// same as `cleanDoc(removeLines(doc))` of Prettier
+pub fn remove_lines<'a>(doc: Doc<'a>) -> Doc<'a> {
+    let remove = |docs: Vec<Doc<'a>>| docs.into_iter().map(remove_lines).collect::<Vec<_>>();
+    match doc {
+        Doc::Line(line) if !line.hard => text(if line.soft { "" } else { " " }),
+        Doc::Group { contents, .. } => Doc::Array(remove(contents)),
+        Doc::ConditionalGroup(mut states) => {
+            if states.is_empty() {
+                Doc::Array(vec![])
+            } else {
+                remove_lines(states.swap_remove(0))
+            }
+        }
+        Doc::IfBreak { flat_contents, .. } => remove_lines(*flat_contents),
+        Doc::Array(docs) => Doc::Array(remove(docs)),
+        Doc::Indent(docs) => Doc::Indent(remove(docs)),
+        Doc::Fill(docs) => Doc::Fill(remove(docs)),
+        Doc::LineSuffix(docs) => Doc::LineSuffix(remove(docs)),
+        doc @ (Doc::Str(_) | Doc::Line(_) | Doc::BreakParent) => doc,
+    }
+}
+
+/// Break the groups which contain a forced break, same as `propagateBreaks` of Prettier.
+///
+/// Returns whether `doc` breaks the group around it.
+/// Conditional groups are not broken, and stop the propagation.
+pub fn propagate_breaks(doc: &mut Doc<'_>) -> bool {
+    let propagate = |docs: &mut Vec<Doc<'_>>| {
+        docs.iter_mut().fold(false, |acc, doc| propagate_breaks(doc) | acc)
+    };
+    match doc {
+        Doc::Str(_) => false,
+        Doc::BreakParent => true,
+        Doc::Line(line) => line.hard,
+        Doc::Group { contents, should_break, .. } => {
+            if propagate(contents) {
+                *should_break = true;
+            }
+            *should_break
+        }
+        Doc::ConditionalGroup(states) => {
+            propagate(states);
+            false
+        }
+        Doc::Array(docs) | Doc::Indent(docs) | Doc::Fill(docs) | Doc::LineSuffix(docs) => {
+            propagate(docs)
+        }
+        Doc::IfBreak { break_contents, flat_contents, .. } => {
+            propagate_breaks(break_contents) | propagate_breaks(flat_contents)
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Assignments, variable declarators, class properties and object properties
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/language-js/print/assignment.js)
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::GetSpan;
+
+use super::{
+    binaryish::{is_binaryish, print_binaryish, BinaryishParent},
+    strip_parens, Format,
+};
+use crate::{
+    doc::{group, group_with_id, indent, indent_if_break, line, text, Doc},
+    Prettier,
+};
+
+/// What is on the left side of the operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AssignmentLeft {
+    /// Object property key narrower than the indentation, the value is never moved to
+    /// the next line
+    ShortKey,
+    /// Object pattern with more than two properties, some of which are not shorthand
+    ComplexDestructuring,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    BreakAfterOperator,
+    NeverBreakAfterOperator,
+    BreakLhs,
+    Fluid,
+}
+
+/// `left`, `operator` and `right`, where the right side is moved to the next line
+/// depending on what it is
+pub(super) fn print_assignment<'a>(
+    p: &mut Prettier<'a>,
+    left: Doc<'a>,
+    operator: Doc<'a>,
+    right: Option<&Expression<'_>>,
+    left_kind: AssignmentLeft,
+) -> Doc<'a> {
+    let Some(right) = right else { return left };
+    let right = strip_parens(right);
+    let layout = choose_layout(right, left_kind);
+    let right_doc = if is_binaryish(right) {
+        let doc = print_binaryish(p, right, BinaryishParent::Assignment);
+        p.with_comments(right.span(), doc)
+    } else {
+        right.format(p)
+    };
+    match layout {
+        Layout::BreakAfterOperator => {
+            group(vec![group(vec![left]), operator, group(vec![indent(vec![line(), right_doc])])])
+        }
+        Layout::NeverBreakAfterOperator => {
+            group(vec![group(vec![left]), operator, text(" "), right_doc])
+        }
+        Layout::BreakLhs => group(vec![left, operator, text(" "), group(vec![right_doc])]),
+        Layout::Fluid => {
+            let id = p.next_group_id();
+            group(vec![
+                group(vec![left]),
+                operator,
+                group_with_id(vec![indent(vec![line()])], id),
+                indent_if_break(vec![right_doc], id),
+            ])
+        }
+    }
+}
+
+fn choose_layout(right: &Expression<'_>, left_kind: AssignmentLeft) -> Layout {
+    if left_kind == AssignmentLeft::ComplexDestructuring {
+        return Layout::BreakLhs;
+    }
+    if should_break_after_operator(right, left_kind) {
+        return Layout::BreakAfterOperator;
+    }
+    if left_kind == AssignmentLeft::ShortKey
+        || matches!(
+            right,
+            Expression::TemplateLiteral(_)
+                | Expression::TaggedTemplateExpression(_)
+                | Expression::BooleanLiteral(_)
+                | Expression::NumberLiteral(_)
+                | Expression::ClassExpression(_)
+        )
+        || matches!(right, Expression::CallExpression(call) if call.callee.is_specific_id("require"))
+    {
+        return Layout::NeverBreakAfterOperator;
+    }
+    Layout::Fluid
+}
+
+fn should_break_after_operator(right: &Expression<'_>, left_kind: AssignmentLeft) -> bool {
+    if is_binaryish(right) && !should_inline_right(right) {
+        return true;
+    }
+    match right {
+        Expression::SequenceExpression(_) | Expression::ParenthesizedExpression(_) => return true,
+        Expression::ConditionalExpression(conditional) => {
+            let test = strip_parens(&conditional.test);
+            if is_binaryish(test) && !should_inline_right(test) {
+                return true;
+            }
+        }
+        Expression::ClassExpression(class) if !class.decorators.is_empty() => return true,
+        _ => {}
+    }
+    if left_kind == AssignmentLeft::ShortKey {
+        return false;
+    }
+    let mut node = right;
+    loop {
+        match node {
+            Expression::UnaryExpression(unary) => node = &unary.argument,
+            Expression::TSNonNullExpression(non_null) => node = &non_null.expression,
+            _ => break,
+        }
+    }
+    node.is_string_literal() || is_member_expression_chain(node)
+}
+
+/// Logical expressions inlined after the operator, e.g. `a = b || {`
+fn should_inline_right(expr: &Expression<'_>) -> bool {
+    let Expression::LogicalExpression(logical) = expr else { return false };
+    match &logical.right {
+        Expression::ObjectExpression(object) => !object.properties.is_empty(),
+        Expression::ArrayExpression(array) => !array.elements.is_empty(),
+        Expression::JSXElement(_) | Expression::JSXFragment(_) => true,
+        _ => false,
+    }
+}
+
+/// Member expressions whose objects are member expressions down to an identifier,
+/// e.g. `a.b.c`
+fn is_member_expression_chain(expr: &Expression<'_>) -> bool {
+    let Expression::MemberExpression(member) = expr else { return false };
+    match member.object() {
+        Expression::Identifier(_) => true,
+        object => is_member_expression_chain(object),
+    }
+}
@@ -0,0 +1,238 @@
+//! Binary and logical expressions
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/language-js/print/binaryish.js)
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::GetSpan;
+
+use super::{jsx::is_jsx, Format};
+use crate::{
+    doc::{array, group, group_with_id, indent, indent_if_break, line, text, Doc},
+    Prettier,
+};
+
+/// Where a binary expression is printed, which decides how its operands are indented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BinaryishParent {
+    /// Tests of `if`, `while` and `do while`, which are inside parentheses already
+    InsideParens,
+    /// Indented by the parent already, e.g. `return` arguments and arrow function bodies
+    NoIndent,
+    /// Right side of assignments and object properties
+    Assignment,
+    /// Right operand of another binary expression
+    Binaryish {
+        is_logical: bool,
+    },
+    Other,
+}
+
+struct Binaryish<'e, 'b> {
+    left: &'e Expression<'b>,
+    operator: &'static str,
+    right: &'e Expression<'b>,
+    is_logical: bool,
+}
+
+fn as_binaryish<'e, 'b>(expr: &'e Expression<'b>) -> Option<Binaryish<'e, 'b>> {
+    match expr {
+        Expression::BinaryExpression(expr) => Some(Binaryish {
+            left: &expr.left,
+            operator: expr.operator.as_str(),
+            right: &expr.right,
+            is_logical: false,
+        }),
+        Expression::LogicalExpression(expr) => Some(Binaryish {
+            left: &expr.left,
+            operator: expr.operator.as_str(),
+            right: &expr.right,
+            is_logical: true,
+        }),
+        _ => None,
+    }
+}
+
+pub(super) fn is_binaryish(expr: &Expression<'_>) -> bool {
+    as_binaryish(expr).is_some()
+}
+
+fn precedence(operator: &str) -> u8 {
+    match operator {
+        "??" => 1,
+        "||" => 2,
+        "&&" => 3,
+        "|" => 4,
+        "^" => 5,
+        "&" => 6,
+        "==" | "!=" | "===" | "!==" => 7,
+        "<" | ">" | "<=" | ">=" | "in" | "instanceof" => 8,
+        ">>" | "<<" | ">>>" => 9,
+        "+" | "-" => 10,
+        "*" | "/" | "%" => 11,
+        _ => 12,
+    }
+}
+
+fn is_bitwise_operator(operator: &str) -> bool {
+    matches!(operator, ">>" | ">>>" | "<<" | "&" | "|" | "^")
+}
+
+/// Whether `a op1 b op2 c` is printed as a flat chain, same as `shouldFlatten` of Prettier
+fn should_flatten(parent_operator: &str, node_operator: &str) -> bool {
+    if precedence(node_operator) != precedence(parent_operator) {
+        return false;
+    }
+    // x ** y ** z --> x ** (y ** z)
+    if parent_operator == "**" {
+        return false;
+    }
+    // x == y == z --> (x == y) == z
+    if precedence(parent_operator) == 7 {
+        return false;
+    }
+    // x * y % z --> (x * y) % z
+    if (node_operator == "%" && matches!(parent_operator, "*" | "/"))
+        || (parent_operator == "%" && matches!(node_operator, "*" | "/"))
+    {
+        return false;
+    }
+    // x * y / z --> (x * y) / z
+    // x / y * z --> (x / y) * z
+    if node_operator != parent_operator
+        && matches!(node_operator, "*" | "/")
+        && matches!(parent_operator, "*" | "/")
+    {
+        return false;
+    }
+    // x << y << z --> (x << y) << z
+    if is_bitwise_operator(parent_operator) && is_bitwise_operator(node_operator) {
+        return false;
+    }
+    true
+}
+
+/// Parentheses which are not needed but clarify the precedence, e.g. `(a && b) || c`,
+/// `(a % b) + c` and `(a == b) == c`, same as the binary cases of `needsParens` of Prettier
+fn needs_parens(parent_operator: &str, operand: &Expression<'_>) -> bool {
+    let Some(operand) = as_binaryish(operand) else { return false };
+    let (parent_precedence, precedence) =
+        (precedence(parent_operator), precedence(operand.operator));
+    if parent_precedence == precedence {
+        return !should_flatten(parent_operator, operand.operator);
+    }
+    if parent_precedence < precedence && operand.operator == "%" {
+        return matches!(parent_operator, "+" | "-");
+    }
+    is_bitwise_operator(parent_operator) || (parent_operator == "||" && operand.operator == "&&")
+}
+
+fn wrap_parens(doc: Doc<'_>, needs_parens: bool) -> Doc<'_> {
+    if needs_parens {
+        array(vec![text("("), doc, text(")")])
+    } else {
+        doc
+    }
+}
+
+/// Logical expressions with an object or array on the right, which is printed
+/// on the same line as the operator, e.g. `a || {`
+fn should_inline_logical(expr: &Expression<'_>) -> bool {
+    let Expression::LogicalExpression(expr) = expr else { return false };
+    match &expr.right {
+        Expression::ObjectExpression(object) => !object.properties.is_empty(),
+        Expression::ArrayExpression(array) => !array.elements.is_empty(),
+        right => is_jsx(right),
+    }
+}
+
+pub(super) fn print_binaryish<'a>(
+    p: &mut Prettier<'a>,
+    expr: &Expression<'_>,
+    parent: BinaryishParent,
+) -> Doc<'a> {
+    let Some(binaryish) = as_binaryish(expr) else { return expr.format(p) };
+    let is_inside_parens = parent == BinaryishParent::InsideParens;
+    let parent_is_same_type = matches!(
+        parent,
+        BinaryishParent::Binaryish { is_logical } if is_logical == binaryish.is_logical
+    );
+    let mut parts = print_binaryish_parts(p, expr, is_inside_parens, parent_is_same_type);
+    if is_inside_parens {
+        return array(parts);
+    }
+
+    let should_inline = should_inline_logical(expr);
+    let same_precedence_sub_expression = as_binaryish(binaryish.left)
+        .map_or(false, |left| should_flatten(binaryish.operator, left.operator));
+    if parent == BinaryishParent::NoIndent
+        || (should_inline && !same_precedence_sub_expression)
+        || (!should_inline && parent == BinaryishParent::Assignment)
+    {
+        return group(parts);
+    }
+
+    // The operands after the first group are indented
+    let head_len =
+        parts.iter().position(|part| matches!(part, Doc::Group { .. })).map_or(1, |i| i + 1);
+    let mut rest = parts.split_off(head_len.min(parts.len()));
+    if is_jsx(binaryish.right) && rest.len() > 1 {
+        let jsx = rest.pop().unwrap();
+        let id = p.next_group_id();
+        parts.push(indent(rest));
+        return group(vec![group_with_id(parts, id), indent_if_break(vec![jsx], id)]);
+    }
+    parts.push(indent(rest));
+    group(parts)
+}
+
+/// The operands and operators of `expr`, flattening the left operands with the same precedence,
+/// e.g. `a + b + c` is `[a, " ", ["+", line, b], " ", ["+", line, c]]`
+fn print_binaryish_parts<'a>(
+    p: &mut Prettier<'a>,
+    expr: &Expression<'_>,
+    is_inside_parens: bool,
+    parent_is_same_type: bool,
+) -> Vec<Doc<'a>> {
+    let Some(binaryish) = as_binaryish(expr) else { return vec![group(vec![expr.format(p)])] };
+    let mut parts = match as_binaryish(binaryish.left) {
+        Some(left) if should_flatten(binaryish.operator, left.operator) => {
+            let has_comments = p.has_comments(binaryish.left.span());
+            let left_parts = print_binaryish_parts(p, binaryish.left, is_inside_parens, true);
+            if has_comments {
+                vec![p.with_comments(binaryish.left.span(), array(left_parts))]
+            } else {
+                left_parts
+            }
+        }
+        _ => {
+            let left = binaryish.left.format(p);
+            vec![group(vec![wrap_parens(left, needs_parens(binaryish.operator, binaryish.left))])]
+        }
+    };
+
+    let right_doc = if is_binaryish(binaryish.right) {
+        let parent = BinaryishParent::Binaryish { is_logical: binaryish.is_logical };
+        let doc = print_binaryish(p, binaryish.right, parent);
+        let doc = p.with_comments(binaryish.right.span(), doc);
+        wrap_parens(doc, needs_parens(binaryish.operator, binaryish.right))
+    } else {
+        binaryish.right.format(p)
+    };
+    let right = if should_inline_logical(expr) {
+        vec![text(binaryish.operator), text(" "), right_doc]
+    } else {
+        vec![text(binaryish.operator), line(), right_doc]
+    };
+
+    let is_same_type = |other: &Expression<'_>| {
+        as_binaryish(other).map_or(false, |other| other.is_logical == binaryish.is_logical)
+    };
+    let should_group = !(parent_is_same_type
+        || is_same_type(binaryish.left)
+        || is_same_type(binaryish.right)
+        || (is_inside_parens && binaryish.is_logical));
+    parts.push(text(" "));
+    parts.push(if should_group { group(right) } else { array(right) });
+    parts
+}
@@ -0,0 +1,599 @@
+//! Call, `new` and `import()` expressions, with their arguments and member chains
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/language-js/print/call-arguments.js)
+//! and [member-chain.js](https://github.com/prettier/prettier/blob/main/src/language-js/print/member-chain.js)
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+
+use super::{
+    jsx::is_jsx, print_expression_no_wrap, print_member_lookup, print_member_object,
+    print_type_arguments, strip_parens, Format,
+};
+use crate::{
+    doc::{
+        array, conditional_group, group, group_with_break, hardline, if_break, indent, join, line,
+        softline, text, Doc,
+    },
+    Prettier,
+};
+
+#[derive(Clone, Copy)]
+enum CallArgument<'e, 'b> {
+    Expression(&'e Expression<'b>),
+    Spread(&'e SpreadElement<'b>),
+}
+
+impl<'e, 'b> CallArgument<'e, 'b> {
+    fn expression(self) -> Option<&'e Expression<'b>> {
+        match self {
+            Self::Expression(expr) => Some(strip_parens(expr)),
+            Self::Spread(_) => None,
+        }
+    }
+
+    fn span(self) -> Span {
+        match self {
+            Self::Expression(expr) => expr.span(),
+            Self::Spread(spread) => spread.span,
+        }
+    }
+}
+
+impl<'e, 'b> From<&'e Argument<'b>> for CallArgument<'e, 'b> {
+    fn from(argument: &'e Argument<'b>) -> Self {
+        match argument {
+            Argument::Expression(expr) => Self::Expression(expr),
+            Argument::SpreadElement(spread) => Self::Spread(spread),
+        }
+    }
+}
+
+pub(super) fn print_call_expression<'a>(
+    p: &mut Prettier<'a>,
+    call: &CallExpression<'_>,
+) -> Doc<'a> {
+    if is_memberish(&call.callee) {
+        return print_member_chain(p, call);
+    }
+    let mut parts = vec![call.callee.format(p)];
+    if call.optional {
+        parts.push(text("?."));
+    }
+    if let Some(type_parameters) = &call.type_parameters {
+        parts.push(print_type_arguments(p, type_parameters));
+    }
+    let arguments: Vec<CallArgument<'_, '_>> = call.arguments.iter().map(Into::into).collect();
+    parts.push(print_call_arguments(p, call.span, &arguments, false));
+    array(parts)
+}
+
+pub(super) fn print_new_expression<'a>(p: &mut Prettier<'a>, new: &NewExpression<'_>) -> Doc<'a> {
+    let mut parts = vec![text("new "), new.callee.format(p)];
+    if let Some(type_parameters) = &new.type_parameters {
+        parts.push(print_type_arguments(p, type_parameters));
+    }
+    let arguments: Vec<CallArgument<'_, '_>> = new.arguments.iter().map(Into::into).collect();
+    parts.push(print_call_arguments(p, new.span, &arguments, false));
+    array(parts)
+}
+
+pub(super) fn print_import_expression<'a>(
+    p: &mut Prettier<'a>,
+    import: &ImportExpression<'_>,
+) -> Doc<'a> {
+    let arguments: Vec<CallArgument<'_, '_>> = std::iter::once(&import.source)
+        .chain(import.arguments.iter())
+        .map(CallArgument::Expression)
+        .collect();
+    array(vec![text("import"), print_call_arguments(p, import.span, &arguments, true)])
+}
+
+fn print_argument<'a>(p: &mut Prettier<'a>, argument: CallArgument<'_, '_>) -> Doc<'a> {
+    match argument {
+        CallArgument::Expression(expr) => print_expression_no_wrap(p, expr),
+        CallArgument::Spread(spread) => {
+            let argument = strip_parens(&spread.argument).format(p);
+            p.with_comments(spread.span, array(vec![text("..."), argument]))
+        }
+    }
+}
+
+/// `(a, b)`, hugging the first or last argument if it is a function or an object,
+/// e.g. `foo(a, () => {` instead of breaking all arguments
+fn print_call_arguments<'a>(
+    p: &mut Prettier<'a>,
+    span: Span,
+    arguments: &[CallArgument<'_, '_>],
+    is_dynamic_import: bool,
+) -> Doc<'a> {
+    if arguments.is_empty() {
+        return array(vec![text("("), p.print_dangling_comments(span, true), text(")")]);
+    }
+
+    // useEffect(() => { ... }, [foo, bar, baz])
+    if is_react_hook_with_deps_array(p, arguments) {
+        let first = print_argument(p, arguments[0]);
+        let second = print_argument(p, arguments[1]);
+        return array(vec![text("("), first, text(", "), second, text(")")]);
+    }
+
+    let last_index = arguments.len() - 1;
+    let mut any_argument_empty_line = false;
+    let mut printed = vec![];
+    for (index, argument) in arguments.iter().enumerate() {
+        let doc = print_argument(p, *argument);
+        if index == last_index {
+            printed.push(doc);
+        } else if p.is_next_line_empty(argument.span().end) {
+            any_argument_empty_line = true;
+            printed.push(array(vec![doc, text(","), hardline(), hardline()]));
+        } else {
+            printed.push(array(vec![doc, text(","), line()]));
+        }
+    }
+
+    let trailing_comma =
+        if p.options.trailing_comma.is_all() && !is_dynamic_import { "," } else { "" };
+    let all_arguments_broken_out = |printed: Vec<Doc<'a>>| {
+        group_with_break(
+            vec![
+                text("("),
+                indent(vec![line(), array(printed), text(trailing_comma)]),
+                line(),
+                text(")"),
+            ],
+            true,
+        )
+    };
+
+    if any_argument_empty_line || is_function_composition_args(arguments) {
+        return all_arguments_broken_out(printed);
+    }
+
+    if should_group_first(arguments) {
+        if printed[1..].iter().any(Doc::will_break) {
+            return all_arguments_broken_out(printed);
+        }
+        let mut hugged = vec![text("("), group_with_break(vec![printed[0].clone()], true)];
+        hugged.extend(printed[1..].iter().cloned());
+        hugged.push(text(")"));
+        let mut flat = vec![text("(")];
+        flat.extend(printed.iter().cloned());
+        flat.push(text(")"));
+        return array(vec![
+            if printed[0].will_break() { Doc::BreakParent } else { text("") },
+            conditional_group(vec![array(flat), array(hugged), all_arguments_broken_out(printed)]),
+        ]);
+    }
+
+    if should_group_last(arguments) {
+        if printed[..last_index].iter().any(Doc::will_break) {
+            return all_arguments_broken_out(printed);
+        }
+        let mut hugged = vec![text("(")];
+        hugged.extend(printed[..last_index].iter().cloned());
+        hugged.push(group_with_break(vec![printed[last_index].clone()], true));
+        hugged.push(text(")"));
+        let mut flat = vec![text("(")];
+        flat.extend(printed.iter().cloned());
+        flat.push(text(")"));
+        return array(vec![
+            if printed.iter().any(Doc::will_break) { Doc::BreakParent } else { text("") },
+            conditional_group(vec![array(flat), array(hugged), all_arguments_broken_out(printed)]),
+        ]);
+    }
+
+    let should_break = printed.iter().any(Doc::will_break);
+    group_with_break(
+        vec![
+            text("("),
+            indent(vec![softline(), array(printed)]),
+            if_break(text(trailing_comma), text("")),
+            softline(),
+            text(")"),
+        ],
+        should_break,
+    )
+}
+
+fn is_react_hook_with_deps_array(p: &Prettier<'_>, arguments: &[CallArgument<'_, '_>]) -> bool {
+    let [first, second] = arguments else { return false };
+    let (Some(Expression::ArrowExpression(arrow)), Some(Expression::ArrayExpression(_))) =
+        (first.expression(), second.expression())
+    else {
+        return false;
+    };
+    arrow.params.items.is_empty()
+        && arrow.params.rest.is_none()
+        && !arrow.expression
+        && !arguments.iter().any(|argument| p.has_comments(argument.span()))
+}
+
+/// Functions passed to functions composing them, e.g. `compose(a => b, c => d)`
+fn is_function_composition_args(arguments: &[CallArgument<'_, '_>]) -> bool {
+    if arguments.len() <= 1 {
+        return false;
+    }
+    let mut count = 0;
+    for argument in arguments {
+        match argument.expression() {
+            Some(Expression::FunctionExpression(_)) => {
+                count += 1;
+                if count > 1 {
+                    return true;
+                }
+            }
+            Some(Expression::ArrowExpression(arrow)) if !arrow.expression => {
+                count += 1;
+                if count > 1 {
+                    return true;
+                }
+            }
+            Some(Expression::CallExpression(call))
+                if call.arguments.iter().any(|argument| {
+                    matches!(
+                        argument,
+                        Argument::Expression(
+                            Expression::FunctionExpression(_) | Expression::ArrowExpression(_)
+                        )
+                    )
+                }) =>
+            {
+                return true;
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+fn should_group_first(arguments: &[CallArgument<'_, '_>]) -> bool {
+    let [first, second] = arguments else { return false };
+    let (Some(first), Some(second)) = (first.expression(), second.expression()) else {
+        return false;
+    };
+    let first_is_function = match first {
+        Expression::FunctionExpression(_) => true,
+        Expression::ArrowExpression(arrow) => !arrow.expression,
+        _ => false,
+    };
+    first_is_function
+        && !matches!(
+            second,
+            Expression::FunctionExpression(_)
+                | Expression::ArrowExpression(_)
+                | Expression::ConditionalExpression(_)
+        )
+        && is_hopefully_short_call_argument(second)
+        && !could_expand_argument(second)
+}
+
+fn should_group_last(arguments: &[CallArgument<'_, '_>]) -> bool {
+    let Some(last) = arguments.last().and_then(|last| last.expression()) else { return false };
+    let penultimate =
+        arguments.len().checked_sub(2).and_then(|index| arguments[index].expression());
+    could_expand_argument(last)
+        // If the last two arguments are of the same type, disregard hugging neither of them
+        && penultimate.map_or(true, |penultimate| {
+            std::mem::discriminant(penultimate) != std::mem::discriminant(last)
+        })
+        && (arguments.len() != 2
+            || !matches!(penultimate, Some(Expression::ArrowExpression(_)))
+            || !matches!(last, Expression::ArrayExpression(_)))
+        && !(arguments.len() > 1
+            && matches!(last, Expression::ArrayExpression(array) if is_concisely_printed_array(array)))
+}
+
+fn is_concisely_printed_array(array: &ArrayExpression<'_>) -> bool {
+    array.elements.len() > 1
+        && array.elements.iter().all(|element| {
+            matches!(element, ArrayExpressionElement::Expression(Expression::NumberLiteral(_)))
+        })
+}
+
+/// Objects, arrays and functions, which can be hugged by the parentheses of the call
+fn could_expand_argument(argument: &Expression<'_>) -> bool {
+    match argument {
+        Expression::ObjectExpression(object) => !object.properties.is_empty(),
+        Expression::ArrayExpression(array) => !array.elements.is_empty(),
+        Expression::TSTypeAssertion(expr) => could_expand_argument(&expr.expression),
+        Expression::TSAsExpression(expr) => could_expand_argument(&expr.expression),
+        Expression::FunctionExpression(_) => true,
+        Expression::ArrowExpression(arrow) => {
+            !arrow.expression
+                || arrow.body.statements.first().map_or(false, |stmt| {
+                    let Statement::ExpressionStatement(stmt) = stmt else { return false };
+                    match strip_parens(&stmt.expression) {
+                        Expression::ArrayExpression(_)
+                        | Expression::ObjectExpression(_)
+                        | Expression::ArrowExpression(_)
+                        | Expression::CallExpression(_)
+                        | Expression::ConditionalExpression(_)
+                        | Expression::TemplateLiteral(_)
+                        | Expression::TaggedTemplateExpression(_) => true,
+                        body => is_jsx(body),
+                    }
+                })
+        }
+        _ => false,
+    }
+}
+
+fn is_hopefully_short_call_argument(expr: &Expression<'_>) -> bool {
+    match strip_parens(expr) {
+        Expression::BinaryExpression(binary) => {
+            is_hopefully_short_call_argument(&binary.left)
+                && is_hopefully_short_call_argument(&binary.right)
+        }
+        Expression::LogicalExpression(logical) => {
+            is_hopefully_short_call_argument(&logical.left)
+                && is_hopefully_short_call_argument(&logical.right)
+        }
+        Expression::RegExpLiteral(_) => true,
+        expr => is_simple_call_argument(expr, 0),
+    }
+}
+
+/// Same as `isSimpleCallArgument` of Prettier
+fn is_simple_call_argument(expr: &Expression<'_>, depth: usize) -> bool {
+    let is_child_simple = |child: &Expression<'_>| is_simple_call_argument(child, depth + 1);
+    match strip_parens(expr) {
+        Expression::RegExpLiteral(regexp) => regexp.regex.pattern.len() <= 5,
+        Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::NumberLiteral(_)
+        | Expression::BigintLiteral(_)
+        | Expression::StringLiteral(_)
+        | Expression::ThisExpression(_)
+        | Expression::MetaProperty(_)
+        | Expression::Identifier(_)
+        | Expression::Super(_)
+        | Expression::UpdateExpression(_)
+        | Expression::FunctionExpression(_) => true,
+        Expression::TemplateLiteral(template) => {
+            template.quasis.iter().all(|quasi| !quasi.value.raw.contains('\n'))
+                && template.expressions.iter().all(is_child_simple)
+        }
+        Expression::ObjectExpression(object) => object.properties.iter().all(|property| {
+            let ObjectPropertyKind::ObjectProperty(property) = property else { return false };
+            !property.computed && (property.shorthand || is_child_simple(&property.value))
+        }),
+        Expression::ArrayExpression(array) => array.elements.iter().all(|element| match element {
+            ArrayExpressionElement::Expression(expr) => is_child_simple(expr),
+            ArrayExpressionElement::Elision(_) => true,
+            ArrayExpressionElement::SpreadElement(_) => false,
+        }),
+        Expression::ImportExpression(import) => depth < 2 && is_child_simple(&import.source),
+        Expression::CallExpression(call) => {
+            depth < 2
+                && is_simple_call_argument(&call.callee, depth)
+                && call.arguments.iter().all(|argument| match argument {
+                    Argument::Expression(expr) => is_child_simple(expr),
+                    Argument::SpreadElement(_) => false,
+                })
+        }
+        Expression::NewExpression(new) => {
+            depth < 2
+                && is_simple_call_argument(&new.callee, depth)
+                && new.arguments.iter().all(|argument| match argument {
+                    Argument::Expression(expr) => is_child_simple(expr),
+                    Argument::SpreadElement(_) => false,
+                })
+        }
+        Expression::TSNonNullExpression(expr) => is_simple_call_argument(&expr.expression, depth),
+        Expression::MemberExpression(member) => {
+            let is_property_simple = match &**member {
+                MemberExpression::ComputedMemberExpression(expr) => {
+                    is_simple_call_argument(&expr.expression, depth)
+                }
+                _ => true,
+            };
+            is_property_simple && is_simple_call_argument(member.object(), depth)
+        }
+        Expression::UnaryExpression(unary) => {
+            matches!(unary.operator.as_str(), "!" | "-" | "+" | "~")
+                && is_simple_call_argument(&unary.argument, depth)
+        }
+        Expression::ArrowExpression(arrow) => {
+            !arrow.expression
+                || arrow.body.statements.first().map_or(false, |stmt| match stmt {
+                    Statement::ExpressionStatement(stmt) => {
+                        is_simple_call_argument(&stmt.expression, depth)
+                    }
+                    _ => false,
+                })
+        }
+        _ => false,
+    }
+}
+
+fn is_memberish(expr: &Expression<'_>) -> bool {
+    matches!(expr, Expression::MemberExpression(_) | Expression::TSNonNullExpression(_))
+}
+
+/// A node of a member chain after its head, e.g. `.b`, `[0]` or `(c)` in `a.b[0](c)`
+enum ChainNode<'e, 'b> {
+    Call(&'e CallExpression<'b>),
+    Member(&'e MemberExpression<'b>),
+    NonNull,
+}
+
+struct PrintedNode<'a, 'e, 'b> {
+    node: ChainNode<'e, 'b>,
+    doc: Doc<'a>,
+}
+
+/// Chains of member accesses and calls, e.g. `a.b().c().d()`, kept on one line if they are short,
+/// otherwise broken before each `.` after the first group.
+/// Same as `printMemberChain` of Prettier.
+#[allow(clippy::too_many_lines)]
+fn print_member_chain<'a>(p: &mut Prettier<'a>, call: &CallExpression<'_>) -> Doc<'a> {
+    // The nodes of the chain, from the outermost call
+    let mut nodes: Vec<ChainNode<'_, '_>> = vec![ChainNode::Call(call)];
+    let mut head = &call.callee;
+    loop {
+        match head {
+            Expression::CallExpression(call)
+                if is_memberish(&call.callee)
+                    || matches!(call.callee, Expression::CallExpression(_)) =>
+            {
+                nodes.push(ChainNode::Call(call));
+                head = &call.callee;
+            }
+            Expression::MemberExpression(member) => {
+                nodes.push(ChainNode::Member(member));
+                head = member.object();
+            }
+            Expression::TSNonNullExpression(expr) => {
+                nodes.push(ChainNode::NonNull);
+                head = &expr.expression;
+            }
+            _ => break,
+        }
+    }
+    nodes.reverse();
+
+    let head_doc = print_member_object(p, head);
+    let mut has_comment = false;
+    let mut printed_nodes = vec![];
+    for node in nodes {
+        let doc = match &node {
+            ChainNode::Call(call) => {
+                let mut parts = vec![];
+                if call.optional {
+                    parts.push(text("?."));
+                }
+                if let Some(type_parameters) = &call.type_parameters {
+                    parts.push(print_type_arguments(p, type_parameters));
+                }
+                let arguments: Vec<CallArgument<'_, '_>> =
+                    call.arguments.iter().map(Into::into).collect();
+                parts.push(print_call_arguments(p, call.span, &arguments, false));
+                array(parts)
+            }
+            ChainNode::Member(member) => {
+                if let MemberExpression::StaticMemberExpression(expr) = member {
+                    has_comment |= p.has_comments(expr.property.span);
+                }
+                print_member_lookup(p, member)
+            }
+            ChainNode::NonNull => text("!"),
+        };
+        printed_nodes.push(PrintedNode { node, doc });
+    }
+
+    // The first group is the head, followed by the calls and computed accesses directly on it,
+    // e.g. `a()()` or `a[0]`, and by the member accesses before the last one if the head is not
+    // a call, e.g. `a.b.c` in `a.b.c.d()`
+    let is_first_group_node = |node: &ChainNode<'_, '_>| match node {
+        ChainNode::Call(_) | ChainNode::NonNull => true,
+        ChainNode::Member(MemberExpression::ComputedMemberExpression(expr)) => matches!(
+            strip_parens(&expr.expression),
+            Expression::NumberLiteral(_) | Expression::StringLiteral(_)
+        ),
+        ChainNode::Member(_) => false,
+    };
+    let is_member_node =
+        |node: &ChainNode<'_, '_>| matches!(node, ChainNode::Member(_) | ChainNode::NonNull);
+    let mut index = 0;
+    while index < printed_nodes.len() && is_first_group_node(&printed_nodes[index].node) {
+        index += 1;
+    }
+    if !matches!(head, Expression::CallExpression(_) | Expression::ImportExpression(_)) {
+        while index + 1 < printed_nodes.len()
+            && is_member_node(&printed_nodes[index].node)
+            && is_member_node(&printed_nodes[index + 1].node)
+        {
+            index += 1;
+        }
+    }
+    let first_group: Vec<usize> = (0..index).collect();
+
+    // The rest is grouped as member accesses followed by calls, e.g. `.b()` or `.c.d()`
+    let mut groups: Vec<Vec<usize>> = vec![];
+    let mut current = vec![];
+    let mut has_seen_call = false;
+    while index < printed_nodes.len() {
+        let node = &printed_nodes[index].node;
+        if has_seen_call && is_member_node(node) {
+            groups.push(std::mem::take(&mut current));
+            has_seen_call = false;
+        }
+        if matches!(node, ChainNode::Call(_)) {
+            has_seen_call = true;
+        }
+        current.push(index);
+        index += 1;
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+
+    // Chains on `this` and on factories, e.g. `z.object()` or `$(el)`, merge the first
+    // member access and call into the first group
+    let is_factory = |name: &str| {
+        name.chars().next().map_or(false, |c| c.is_ascii_uppercase())
+            || name.chars().all(|c| c == '$' || c == '_')
+    };
+    let should_merge = first_group.is_empty()
+        && groups.len() >= 2
+        && match head {
+            Expression::ThisExpression(_) => true,
+            Expression::Identifier(ident) => is_factory(&ident.name),
+            _ => false,
+        };
+
+    let print_group = |indices: &[usize]| {
+        array(indices.iter().map(|index| printed_nodes[*index].doc.clone()).collect())
+    };
+    let mut printed_first = vec![head_doc, print_group(&first_group)];
+    let mut printed_groups: Vec<Doc<'a>> = groups.iter().map(|group| print_group(group)).collect();
+    if should_merge {
+        printed_first.push(printed_groups.remove(0));
+    }
+    let printed_first = array(printed_first);
+    let mut one_line = vec![printed_first.clone()];
+    one_line.extend(printed_groups.iter().cloned());
+    let one_line = array(one_line);
+
+    // Short chains are printed on one line, e.g. `a.b()` or `this.a.b()`
+    let cutoff = if should_merge { 3 } else { 2 };
+    if groups.len() < cutoff && !has_comment {
+        return group(vec![one_line]);
+    }
+
+    let expanded = array(vec![
+        printed_first.clone(),
+        indent(vec![group(vec![hardline(), array(join(&hardline(), printed_groups.clone()))])]),
+    ]);
+
+    // Chains with more than two calls with complex arguments, e.g. functions, are expanded
+    let calls: Vec<&CallExpression<'_>> = printed_nodes
+        .iter()
+        .filter_map(|node| match node.node {
+            ChainNode::Call(call) => Some(call),
+            _ => None,
+        })
+        .collect();
+    let has_complex_arguments = calls.iter().any(|call| {
+        !call.arguments.iter().all(|argument| match argument {
+            Argument::Expression(expr) => is_simple_call_argument(expr, 0),
+            Argument::SpreadElement(_) => false,
+        })
+    });
+    let last_group_index = printed_groups.len().saturating_sub(1);
+    if has_comment
+        || (calls.len() > 2 && has_complex_arguments)
+        || printed_first.will_break()
+        || printed_groups[..last_group_index].iter().any(Doc::will_break)
+    {
+        return group(vec![expanded]);
+    }
+
+    array(vec![
+        if one_line.will_break() { Doc::BreakParent } else { text("") },
+        conditional_group(vec![one_line, expanded]),
+    ])
+}
@@ -0,0 +1,568 @@
+//! JSX elements, fragments and their attributes and children
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/language-js/print/jsx.js)
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+
+use super::{
+    binaryish::{is_binaryish, print_binaryish, BinaryishParent},
+    literal::preferred_quote,
+    print_expression_no_wrap, print_type_arguments, print_verbatim, strip_parens, Format,
+};
+use crate::{
+    doc::{
+        array, conditional_group, fill, group, group_with_break, hardline, if_break, indent, join,
+        line, literalline, softline, text, Doc,
+    },
+    Prettier,
+};
+
+pub(super) fn is_jsx(expr: &Expression<'_>) -> bool {
+    matches!(strip_parens(expr), Expression::JSXElement(_) | Expression::JSXFragment(_))
+}
+
+/// Parentheses around multiline JSX, e.g. in `return (` and `const a = (`
+pub(super) fn wrap_jsx(doc: Doc<'_>) -> Doc<'_> {
+    group(vec![
+        if_break(text("("), text("")),
+        indent(vec![softline(), doc]),
+        softline(),
+        if_break(text(")"), text("")),
+    ])
+}
+
+pub(super) fn print_jsx_element<'a>(p: &mut Prettier<'a>, element: &JSXElement<'_>) -> Doc<'a> {
+    let opening = print_opening_element(p, &element.opening_element);
+    let Some(closing) = &element.closing_element else { return opening };
+    let closing = array(vec![text("</"), print_element_name(p, &closing.name), text(">")]);
+    let contains_multiple_attributes = element.opening_element.attributes.len() > 1;
+    print_element_with_children(
+        p,
+        opening,
+        closing,
+        &element.children,
+        contains_multiple_attributes,
+    )
+}
+
+pub(super) fn print_jsx_fragment<'a>(p: &mut Prettier<'a>, fragment: &JSXFragment<'_>) -> Doc<'a> {
+    print_element_with_children(p, text("<>"), text("</>"), &fragment.children, false)
+}
+
+fn print_element_name<'a>(p: &Prettier<'a>, name: &JSXElementName<'_>) -> Doc<'a> {
+    text(p.source(name.span()))
+}
+
+/// `<a b="c">`, where the attributes are broken onto their own lines if they do not fit,
+/// unless there is only one string attribute
+fn print_opening_element<'a>(p: &mut Prettier<'a>, element: &JSXOpeningElement<'_>) -> Doc<'a> {
+    let mut parts = vec![text("<"), print_element_name(p, &element.name)];
+    if let Some(type_parameters) = &element.type_parameters {
+        parts.push(print_type_arguments(p, type_parameters));
+    }
+    if element.self_closing && element.attributes.is_empty() {
+        parts.push(text(" />"));
+        return array(parts);
+    }
+
+    let is_string_attribute = |item: &JSXAttributeItem<'_>| {
+        matches!(
+            item,
+            JSXAttributeItem::Attribute(attribute)
+                if matches!(attribute.value, Some(JSXAttributeValue::StringLiteral(_)))
+        )
+    };
+    let is_multiline_string = |item: &JSXAttributeItem<'_>| {
+        matches!(
+            item,
+            JSXAttributeItem::Attribute(attribute)
+                if matches!(
+                    &attribute.value,
+                    Some(JSXAttributeValue::StringLiteral(literal)) if literal.value.contains('\n')
+                )
+        )
+    };
+    if let [attribute] = element.attributes.as_slice() {
+        if is_string_attribute(attribute)
+            && !is_multiline_string(attribute)
+            && !p.has_comments(attribute.span())
+        {
+            parts.push(text(" "));
+            parts.push(print_attribute_item(p, attribute));
+            parts.push(text(if element.self_closing { " />" } else { ">" }));
+            return group(parts);
+        }
+    }
+
+    let should_break = element.attributes.iter().any(is_multiline_string);
+    let attributes = element
+        .attributes
+        .iter()
+        .map(|attribute| array(vec![line(), print_attribute_item(p, attribute)]))
+        .collect();
+    parts.push(indent(attributes));
+    if element.self_closing {
+        parts.extend([line(), text("/>")]);
+    } else {
+        parts.extend([softline(), text(">")]);
+    }
+    group_with_break(parts, should_break)
+}
+
+fn print_attribute_item<'a>(p: &mut Prettier<'a>, item: &JSXAttributeItem<'_>) -> Doc<'a> {
+    let doc = match item {
+        JSXAttributeItem::Attribute(attribute) => print_attribute(p, attribute),
+        JSXAttributeItem::SpreadAttribute(spread) => {
+            let argument = strip_parens(&spread.argument).format(p);
+            array(vec![text("{..."), argument, text("}")])
+        }
+    };
+    p.with_comments(item.span(), doc)
+}
+
+fn print_attribute<'a>(p: &mut Prettier<'a>, attribute: &JSXAttribute<'_>) -> Doc<'a> {
+    let name_span = match &attribute.name {
+        JSXAttributeName::Identifier(ident) => ident.span,
+        JSXAttributeName::NamespacedName(name) => name.span,
+    };
+    let mut parts = vec![text(p.source(name_span))];
+    let Some(value) = &attribute.value else { return array(parts) };
+    parts.push(text("="));
+    parts.push(match value {
+        JSXAttributeValue::StringLiteral(literal) => print_attribute_string(p, literal.span),
+        JSXAttributeValue::ExpressionContainer(container) => {
+            print_expression_container(p, container, false)
+        }
+        JSXAttributeValue::Element(element) => print_jsx_element(p, element),
+        JSXAttributeValue::Fragment(fragment) => print_jsx_fragment(p, fragment),
+    });
+    array(parts)
+}
+
+/// Attribute strings prefer double quotes, and escape quotes as HTML entities since
+/// backslashes are not escapes in JSX
+fn print_attribute_string<'a>(p: &Prettier<'a>, span: Span) -> Doc<'a> {
+    let raw = p.source(span);
+    let content = raw[1..raw.len() - 1].replace("&apos;", "'").replace("&quot;", "\"");
+    let quote = preferred_quote(&content, '"');
+    let escaped = if quote == '"' { "&quot;" } else { "&apos;" };
+    let value = format!("{quote}{}{quote}", content.replace(quote, escaped));
+    if !value.contains('\n') {
+        return text(value);
+    }
+    let lines = value.split('\n').map(|line| text(line.to_string())).collect();
+    array(join(&literalline(), lines))
+}
+
+/// `{expression}`, where the expression is moved to its own line if it does not fit,
+/// unless it is an object, array, function or call which breaks by itself
+fn print_expression_container<'a>(
+    p: &mut Prettier<'a>,
+    container: &JSXExpressionContainer<'_>,
+    is_child: bool,
+) -> Doc<'a> {
+    let expr = match &container.expression {
+        JSXExpression::Expression(expr) => strip_parens(expr),
+        JSXExpression::EmptyExpression(_) => {
+            // Only comments, e.g. `{/* comment */}`
+            return print_verbatim(p, container.span);
+        }
+    };
+    let should_inline = !p.has_comments(expr.span())
+        && (matches!(
+            expr,
+            Expression::ArrayExpression(_)
+                | Expression::ObjectExpression(_)
+                | Expression::ArrowExpression(_)
+                | Expression::CallExpression(_)
+                | Expression::FunctionExpression(_)
+                | Expression::TemplateLiteral(_)
+                | Expression::TaggedTemplateExpression(_)
+        ) || (is_child
+            && (matches!(expr, Expression::ConditionalExpression(_)) || is_binaryish(expr))));
+    let doc = if is_binaryish(expr) {
+        let parent = if is_child { BinaryishParent::Other } else { BinaryishParent::NoIndent };
+        let doc = print_binaryish(p, expr, parent);
+        p.with_comments(expr.span(), doc)
+    } else {
+        print_expression_no_wrap(p, expr)
+    };
+    if should_inline {
+        group(vec![text("{"), doc, text("}")])
+    } else {
+        group(vec![text("{"), indent(vec![softline(), doc]), softline(), text("}")])
+    }
+}
+
+/// Separators and contents of the children, alternating as in [`fill`]. The separators are
+/// compared with each other to clean up redundant lines, so they are kept apart from other docs.
+enum Part<'a> {
+    Empty,
+    Hardline,
+    Softline,
+    Line,
+    JsxWhitespace,
+    Doc(Doc<'a>),
+}
+
+impl<'a> Part<'a> {
+    fn is_line(&self) -> bool {
+        matches!(self, Self::Hardline | Self::Softline | Self::Line)
+    }
+
+    fn will_break(&self) -> bool {
+        match self {
+            Self::Hardline => true,
+            Self::Doc(doc) => doc.will_break(),
+            _ => false,
+        }
+    }
+
+    fn to_doc(&self, raw_jsx_whitespace: &'static str) -> Doc<'a> {
+        match self {
+            Self::Empty => text(""),
+            Self::Hardline => hardline(),
+            Self::Softline => softline(),
+            Self::Line => line(),
+            Self::JsxWhitespace => {
+                if_break(array(vec![text(raw_jsx_whitespace), softline()]), text(" "))
+            }
+            Self::Doc(doc) => doc.clone(),
+        }
+    }
+}
+
+const JSX_WHITESPACE_CHARS: [char; 4] = [' ', '\n', '\r', '\t'];
+
+/// Text with other characters than whitespace, or whitespace without newlines
+fn is_meaningful_text(text: &str) -> bool {
+    text.contains(|c| !JSX_WHITESPACE_CHARS.contains(&c)) || !text.contains('\n')
+}
+
+/// `{" "}`, which is printed as a space in the text around it
+fn is_whitespace_expression(p: &Prettier<'_>, child: &JSXChild<'_>) -> bool {
+    let JSXChild::ExpressionContainer(container) = child else { return false };
+    let JSXExpression::Expression(Expression::StringLiteral(literal)) = &container.expression
+    else {
+        return false;
+    };
+    literal.value == " " && !p.has_comments(literal.span)
+}
+
+/// Text split at whitespace, e.g. `" a  b"` is `["", " ", "a", "  ", "b"]`
+fn split_words(text: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut start = 0;
+    let mut was_whitespace = false;
+    for (index, c) in text.char_indices() {
+        let is_whitespace = JSX_WHITESPACE_CHARS.contains(&c);
+        if is_whitespace != was_whitespace {
+            if index > 0 || is_whitespace {
+                words.push(&text[start..index]);
+            }
+            start = index;
+            was_whitespace = is_whitespace;
+        }
+    }
+    words.push(&text[start..]);
+    if was_whitespace {
+        words.push("");
+    }
+    words
+}
+
+/// The children are printed on one line if they fit, otherwise each element and expression is
+/// printed on its own line and the text between them is filled
+fn print_element_with_children<'a>(
+    p: &mut Prettier<'a>,
+    opening: Doc<'a>,
+    closing: Doc<'a>,
+    children: &[JSXChild<'_>],
+    contains_multiple_attributes: bool,
+) -> Doc<'a> {
+    let is_empty = match children {
+        [] => true,
+        [JSXChild::Text(child)] => !is_meaningful_text(p.source(child.span)),
+        _ => false,
+    };
+    if is_empty {
+        return array(vec![opening, closing]);
+    }
+    if let [JSXChild::ExpressionContainer(container)] = children {
+        if let JSXExpression::Expression(
+            Expression::TemplateLiteral(_) | Expression::TaggedTemplateExpression(_),
+        ) = &container.expression
+        {
+            let child = print_expression_container(p, container, true);
+            return array(vec![opening, child, closing]);
+        }
+    }
+
+    // `{" "}` is treated as a space in the text
+    let children: Vec<Child<'a, '_, '_>> = children
+        .iter()
+        .map(|child| {
+            if is_whitespace_expression(p, child) {
+                Child::Text(" ")
+            } else if let JSXChild::Text(text) = child {
+                Child::Text(p.source(text.span))
+            } else {
+                Child::Node(child)
+            }
+        })
+        .collect();
+    let contains_tag = children
+        .iter()
+        .any(|child| matches!(child, Child::Node(JSXChild::Element(_) | JSXChild::Fragment(_))));
+    let contains_multiple_expressions = children
+        .iter()
+        .filter(|child| matches!(child, Child::Node(JSXChild::ExpressionContainer(_))))
+        .count()
+        > 1;
+    let contains_text =
+        children.iter().any(|child| matches!(child, Child::Text(text) if is_meaningful_text(text)));
+    let mut force_break = opening.will_break()
+        || contains_tag
+        || contains_multiple_attributes
+        || contains_multiple_expressions;
+
+    let raw_jsx_whitespace = if p.options.single_quote { "{' '}" } else { "{\" \"}" };
+    let mut parts = print_children(p, &children);
+    remove_redundant_parts(&mut parts, contains_text);
+    if parts.is_empty() {
+        return array(vec![opening, closing]);
+    }
+
+    let multiline_children = print_multiline_children(&parts, raw_jsx_whitespace);
+    force_break |= parts.iter().any(Part::will_break);
+    let content = if contains_text {
+        fill(multiline_children)
+    } else {
+        group_with_break(multiline_children, true)
+    };
+    let multiline = group(vec![
+        opening.clone(),
+        indent(vec![hardline(), content]),
+        hardline(),
+        closing.clone(),
+    ]);
+    if force_break {
+        return multiline;
+    }
+    let mut one_line = vec![opening];
+    one_line.extend(parts.iter().map(|part| part.to_doc(raw_jsx_whitespace)));
+    one_line.push(closing);
+    conditional_group(vec![group(one_line), multiline])
+}
+
+/// Remove the empty contents and the lines next to JSX whitespace which are redundant, and the
+/// lines around the children
+fn remove_redundant_parts(parts: &mut Vec<Part<'_>>, contains_text: bool) {
+    let mut index = parts.len().saturating_sub(1);
+    while index > 0 {
+        index -= 1;
+        let separated_by_empty = matches!(parts.get(index + 1), Some(Part::Empty));
+        let (current, after) = (parts.get(index), parts.get(index + 2));
+        let is_pair_of_empty = matches!(current, Some(Part::Empty)) && separated_by_empty;
+        let is_pair_of_hardlines = separated_by_empty
+            && matches!((current, after), (Some(Part::Hardline), Some(Part::Hardline)));
+        let is_line_followed_by_jsx_whitespace = separated_by_empty
+            && matches!(
+                (current, after),
+                (Some(Part::Hardline | Part::Softline), Some(Part::JsxWhitespace))
+            );
+        let is_jsx_whitespace_followed_by_line = separated_by_empty
+            && matches!(
+                (current, after),
+                (Some(Part::JsxWhitespace), Some(Part::Hardline | Part::Softline))
+            );
+        let is_double_jsx_whitespace = separated_by_empty
+            && matches!((current, after), (Some(Part::JsxWhitespace), Some(Part::JsxWhitespace)));
+        let is_pair_of_hard_or_softlines = separated_by_empty
+            && matches!(
+                (current, after),
+                (Some(Part::Softline), Some(Part::Hardline))
+                    | (Some(Part::Hardline), Some(Part::Softline))
+            );
+
+        if (is_pair_of_hardlines && contains_text)
+            || is_pair_of_empty
+            || is_line_followed_by_jsx_whitespace
+            || is_double_jsx_whitespace
+        {
+            parts.drain(index..index + 2);
+        } else if is_jsx_whitespace_followed_by_line || is_pair_of_hard_or_softlines {
+            parts.drain(index + 1..index + 3);
+        }
+    }
+
+    // Trim the lines around the children
+    while parts.last().map_or(false, |part| part.is_line() || matches!(part, Part::Empty)) {
+        parts.pop();
+    }
+    while parts.len() > 1 && parts[0].is_line() && matches!(parts[1], Part::Empty) {
+        parts.drain(..2);
+    }
+}
+
+/// The children printed on multiple lines, where JSX whitespace at the start or the end of the
+/// lines is printed as `{" "}`
+fn print_multiline_children<'a>(
+    parts: &[Part<'a>],
+    raw_jsx_whitespace: &'static str,
+) -> Vec<Doc<'a>> {
+    let len = parts.len();
+    let mut multiline_children = vec![];
+    for index in 0..len {
+        if matches!(parts[index], Part::JsxWhitespace) {
+            let raw = text(raw_jsx_whitespace);
+            let after_empty = index >= 1 && matches!(parts[index - 1], Part::Empty);
+            if index == 1 && after_empty {
+                // Solitary or leading whitespace
+                multiline_children.push(if len == 2 { raw } else { array(vec![raw, hardline()]) });
+                continue;
+            }
+            let after_hardline = index >= 2 && matches!(parts[index - 2], Part::Hardline);
+            if index == len - 1 || (after_empty && after_hardline) {
+                // Trailing whitespace, or whitespace after a line break
+                multiline_children.push(raw);
+                continue;
+            }
+        }
+        multiline_children.push(parts[index].to_doc(raw_jsx_whitespace));
+    }
+    multiline_children
+}
+
+enum Child<'a, 'e, 'b> {
+    Text(&'a str),
+    Node(&'e JSXChild<'b>),
+}
+
+/// Same as `printJsxChildren` of Prettier
+fn print_children<'a>(p: &mut Prettier<'a>, children: &[Child<'a, '_, '_>]) -> Vec<Part<'a>> {
+    let mut parts = vec![];
+    for (index, child) in children.iter().enumerate() {
+        let next = children.get(index + 1);
+        match child {
+            Child::Text(content) if is_meaningful_text(content) => {
+                let mut words = split_words(content);
+                let mut words = &mut words[..];
+                if words[0].is_empty() {
+                    parts.push(Part::Empty);
+                    if words[1].contains('\n') {
+                        let first_word = words.get(2).copied().unwrap_or_default();
+                        parts.push(separator_with_whitespace(first_word, child, next));
+                    } else {
+                        parts.push(Part::JsxWhitespace);
+                    }
+                    words = &mut words[2..];
+                }
+                let mut end_whitespace = None;
+                if words.last().map_or(false, |word| word.is_empty()) {
+                    let len = words.len();
+                    end_whitespace = len.checked_sub(2).map(|index| words[index]);
+                    words = &mut words[..len.saturating_sub(2)];
+                }
+                // Whitespace without newlines only
+                if words.is_empty() {
+                    continue;
+                }
+                for (index, word) in words.iter().enumerate() {
+                    parts.push(if index % 2 == 1 { Part::Line } else { Part::Doc(text(*word)) });
+                }
+                let last_word = words[words.len() - 1];
+                parts.push(match end_whitespace {
+                    Some(whitespace) if whitespace.contains('\n') => {
+                        separator_with_whitespace(last_word, child, next)
+                    }
+                    Some(_) => Part::JsxWhitespace,
+                    None => separator_no_whitespace(last_word, child, next),
+                });
+            }
+            Child::Text(content) => {
+                // Blank lines between tags and expressions are kept
+                if content.matches('\n').count() > 1 {
+                    parts.push(Part::Empty);
+                    parts.push(Part::Hardline);
+                }
+            }
+            Child::Node(node) => {
+                let doc = match node {
+                    JSXChild::Element(element) => print_jsx_element(p, element),
+                    JSXChild::Fragment(fragment) => print_jsx_fragment(p, fragment),
+                    JSXChild::ExpressionContainer(container) => {
+                        print_expression_container(p, container, true)
+                    }
+                    JSXChild::Spread(spread) => {
+                        let expression = strip_parens(&spread.expression).format(p);
+                        array(vec![text("{..."), expression, text("}")])
+                    }
+                    JSXChild::Text(_) => unreachable!(),
+                };
+                parts.push(Part::Doc(p.with_comments(node.span(), doc)));
+                match next {
+                    Some(Child::Text(content)) if is_meaningful_text(content) => {
+                        let trimmed = content.trim_matches(&JSX_WHITESPACE_CHARS[..]);
+                        let first_word = split_words(trimmed)[0];
+                        parts.push(separator_no_whitespace(first_word, child, next));
+                    }
+                    _ => parts.push(Part::Hardline),
+                }
+            }
+        }
+    }
+    parts
+}
+
+fn is_self_closing(child: Option<&Child<'_, '_, '_>>) -> bool {
+    matches!(
+        child,
+        Some(Child::Node(JSXChild::Element(element))) if element.closing_element.is_none()
+    )
+}
+
+fn separator_no_whitespace<'a>(
+    word: &str,
+    child: &Child<'_, '_, '_>,
+    next: Option<&Child<'_, '_, '_>>,
+) -> Part<'a> {
+    if is_self_closing(Some(child)) || is_self_closing(next) {
+        if word.chars().count() == 1 {
+            Part::Softline
+        } else {
+            Part::Hardline
+        }
+    } else {
+        Part::Softline
+    }
+}
+
+fn separator_with_whitespace<'a>(
+    word: &str,
+    child: &Child<'_, '_, '_>,
+    _next: Option<&Child<'_, '_, '_>>,
+) -> Part<'a> {
+    if word.chars().count() == 1 {
+        match child {
+            Child::Text(text) if !text.contains('\n') => Part::Hardline,
+            _ => Part::Softline,
+        }
+    } else {
+        Part::Hardline
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::split_words;
+
+    #[test]
+    fn words() {
+        assert_eq!(split_words("a b"), vec!["a", " ", "b"]);
+        assert_eq!(split_words(" a\n b "), vec!["", " ", "a", "\n ", "b", " ", ""]);
+        assert_eq!(split_words(" "), vec!["", " ", ""]);
+    }
+}
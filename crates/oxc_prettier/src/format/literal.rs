@@ -0,0 +1,214 @@
+//! Strings, numbers, template literals and property keys
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/utils/print-string.js)
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+
+use super::{print_text_lines, print_without_parens, Format};
+use crate::{
+    doc::{array, remove_lines, text, Doc},
+    Prettier,
+};
+
+/// String literal with `span` including its quotes, using the preferred quotes
+/// unless the other quotes need fewer escapes
+pub(super) fn print_string<'a>(p: &Prettier<'a>, span: Span) -> Doc<'a> {
+    let raw = p.source(span);
+    let content = &raw[1..raw.len() - 1];
+    let quote = preferred_quote(content, if p.options.single_quote { '\'' } else { '"' });
+    text(make_string(content, quote))
+}
+
+pub(super) fn preferred_quote(content: &str, preferred: char) -> char {
+    let alternate = if preferred == '"' { '\'' } else { '"' };
+    let count = |quote: char| content.chars().filter(|c| *c == quote).count();
+    if count(preferred) > count(alternate) {
+        alternate
+    } else {
+        preferred
+    }
+}
+
+/// `content` enclosed in `quote`, escaping the enclosing quotes only
+/// and removing the escapes which are not needed, e.g. `\d` becomes `d`
+pub(super) fn make_string(content: &str, quote: char) -> String {
+    let mut result = String::with_capacity(content.len() + 2);
+    result.push(quote);
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped == quote => {
+                    result.push('\\');
+                    result.push(escaped);
+                }
+                Some(escaped @ ('"' | '\'')) => result.push(escaped),
+                Some(escaped) if is_unnecessary_escape(escaped) => result.push(escaped),
+                Some(escaped) => {
+                    result.push('\\');
+                    result.push(escaped);
+                }
+                None => result.push('\\'),
+            },
+            c if c == quote => {
+                result.push('\\');
+                result.push(c);
+            }
+            c => result.push(c),
+        }
+    }
+    result.push(quote);
+    result
+}
+
+fn is_unnecessary_escape(c: char) -> bool {
+    !matches!(
+        c,
+        '\n' | '\r'
+            | '0'..='7'
+            | '\\'
+            | 'b'
+            | 'f'
+            | 'n'
+            | 'r'
+            | 't'..='v'
+            | 'x'
+            | '\u{2028}'
+            | '\u{2029}'
+    )
+}
+
+/// Lowercase numbers without redundant zeros and dots, e.g. `0XAB` becomes `0xab`,
+/// `1.50E+10` becomes `1.5e10`, `.5` becomes `0.5` and `5.` becomes `5`
+pub(super) fn print_number(raw: &str) -> String {
+    let lowercase = raw.to_ascii_lowercase();
+    if lowercase.starts_with("0x") || lowercase.starts_with("0b") || lowercase.starts_with("0o") {
+        return lowercase;
+    }
+    let (mantissa, exponent) = match lowercase.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (lowercase.as_str(), None),
+    };
+    let mut mantissa = mantissa.to_string();
+    if mantissa.starts_with('.') {
+        mantissa.insert(0, '0');
+    }
+    if let Some(dot) = mantissa.find('.') {
+        // One zero is kept after the dot, `1.00` becomes `1.0`
+        let fraction = mantissa[dot + 1..].trim_end_matches('0');
+        let len = if fraction.is_empty() && mantissa.len() > dot + 1 {
+            dot + 2
+        } else if fraction.is_empty() {
+            dot
+        } else {
+            dot + 1 + fraction.len()
+        };
+        mantissa.truncate(len);
+    }
+    let Some(exponent) = exponent else { return mantissa };
+    let (sign, digits) = exponent.strip_prefix('-').map_or_else(
+        || ("", exponent.strip_prefix('+').unwrap_or(exponent)),
+        |digits| ("-", digits),
+    );
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        mantissa
+    } else {
+        format!("{mantissa}e{sign}{digits}")
+    }
+}
+
+/// Regular expressions with their flags sorted
+pub(super) fn print_regexp(raw: &str) -> String {
+    let Some(end) = raw.rfind('/') else { return raw.to_string() };
+    let mut flags: Vec<char> = raw[end + 1..].chars().collect();
+    flags.sort_unstable();
+    let mut result = raw[..=end].to_string();
+    result.extend(flags);
+    result
+}
+
+/// Quasis are printed as written, expressions inside `${}` are never broken
+pub(super) fn print_template_literal<'a>(
+    p: &mut Prettier<'a>,
+    literal: &TemplateLiteral<'_>,
+) -> Doc<'a> {
+    let mut parts = vec![text("`")];
+    for (index, quasi) in literal.quasis.iter().enumerate() {
+        parts.push(print_text_lines(p.source(quasi.span)));
+        if let Some(expr) = literal.expressions.get(index) {
+            let doc = print_without_parens(p, expr);
+            parts.extend([text("${"), remove_lines(doc), text("}")]);
+        }
+    }
+    parts.push(text("`"));
+    array(parts)
+}
+
+/// Keys of properties, methods and patterns. Quotes are removed from string keys which are
+/// identifiers if `can_unquote`, i.e. outside of classes.
+pub(super) fn print_property_key<'a>(
+    p: &mut Prettier<'a>,
+    key: &PropertyKey<'_>,
+    computed: bool,
+    can_unquote: bool,
+) -> Doc<'a> {
+    let doc = match key {
+        PropertyKey::Expression(expr) if computed => {
+            return array(vec![text("["), print_without_parens(p, expr), text("]")]);
+        }
+        PropertyKey::Identifier(ident) => text(p.source(ident.span)),
+        PropertyKey::PrivateIdentifier(ident) => text(p.source(ident.span)),
+        PropertyKey::Expression(Expression::StringLiteral(literal)) => {
+            let raw = p.source(literal.span);
+            let content = &raw[1..raw.len() - 1];
+            if can_unquote && is_es5_identifier_name(content) {
+                text(content)
+            } else {
+                print_string(p, literal.span)
+            }
+        }
+        PropertyKey::Expression(expr) => return expr.format(p),
+    };
+    p.with_comments(key.span(), doc)
+}
+
+fn is_es5_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().map_or(false, |c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+#[cfg(test)]
+mod test {
+    use super::{make_string, print_number, print_regexp};
+
+    #[test]
+    fn numbers() {
+        assert_eq!(print_number("1.50E+10"), "1.5e10");
+        assert_eq!(print_number(".5"), "0.5");
+        assert_eq!(print_number("5."), "5");
+        assert_eq!(print_number("0.0"), "0.0");
+        assert_eq!(print_number("1.500"), "1.5");
+        assert_eq!(print_number("1e-05"), "1e-5");
+        assert_eq!(print_number("2e0"), "2");
+        assert_eq!(print_number("0XAB"), "0xab");
+        assert_eq!(print_number("10"), "10");
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(make_string("a'b", '"'), "\"a'b\"");
+        assert_eq!(make_string("a\\'b", '"'), "\"a'b\"");
+        assert_eq!(make_string("a\"b", '\''), "'a\"b'");
+        assert_eq!(make_string("\\d\\n", '"'), "\"d\\n\"");
+    }
+
+    #[test]
+    fn regexp() {
+        assert_eq!(print_regexp("/a/gim"), "/a/gim");
+        assert_eq!(print_regexp("/a\\//ymg"), "/a\\//gmy");
+    }
+}
@@ -0,0 +1,1812 @@
+//! Conversion of the AST into a [`Doc`]
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/tree/main/src/language-js/print)
+
+mod assignment;
+mod binaryish;
+mod call;
+mod jsx;
+mod literal;
+mod typescript;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+
+use self::{
+    assignment::{print_assignment, AssignmentLeft},
+    binaryish::{is_binaryish, print_binaryish, BinaryishParent},
+    literal::{print_property_key, print_string, print_template_literal},
+    typescript::{
+        print_arrow_type_parameters, print_index_signature, print_type_annotation,
+        print_type_arguments, print_type_name, print_type_parameters,
+    },
+};
+use crate::{
+    doc::{
+        array, fill, group, group_with_break, hardline, if_break, if_break_with_id, indent, join,
+        line, literalline, softline, text, Doc,
+    },
+    Prettier,
+};
+
+pub trait Format<'a> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a>;
+}
+
+impl<'a> Format<'a> for Program<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let mut parts = vec![];
+        if let Some(hashbang) = &self.hashbang {
+            parts.push(text(format!("#!{}", hashbang.value.trim_end())));
+            parts.push(hardline());
+            if p.is_next_line_empty(hashbang.span.end) {
+                parts.push(hardline());
+            }
+        }
+        let body = print_block_body(p, &self.directives, &self.body);
+        if !body.is_empty() {
+            parts.push(body);
+            parts.push(hardline());
+        }
+        if p.has_dangling_comments(self.span) {
+            let dangling = p.print_dangling_comments(self.span, true);
+            let ends_with_hardline = ends_with_hardline(&dangling);
+            parts.push(dangling);
+            if !ends_with_hardline {
+                parts.push(hardline());
+            }
+        }
+        array(parts)
+    }
+}
+
+fn ends_with_hardline(doc: &Doc<'_>) -> bool {
+    match doc {
+        Doc::Line(line) => line.hard,
+        Doc::Array(docs) => docs.last().map_or(false, ends_with_hardline),
+        _ => false,
+    }
+}
+
+/// Directives and statements of programs, blocks and function bodies, one per line,
+/// keeping single blank lines between them
+fn print_block_body<'a>(
+    p: &mut Prettier<'a>,
+    directives: &[Directive],
+    statements: &[Statement<'_>],
+) -> Doc<'a> {
+    let mut parts = vec![];
+    for (index, directive) in directives.iter().enumerate() {
+        let doc = print_directive(p, directive);
+        parts.push(p.with_comments(directive.span, doc));
+        if index + 1 < directives.len() || has_statements(statements) {
+            parts.push(hardline());
+            if p.is_next_line_empty(directive.span.end) {
+                parts.push(hardline());
+            }
+        }
+    }
+    parts.extend(print_statement_sequence(p, statements));
+    array(parts)
+}
+
+fn has_statements(statements: &[Statement<'_>]) -> bool {
+    statements.iter().any(|stmt| !matches!(stmt, Statement::EmptyStatement(_)))
+}
+
+fn print_statement_sequence<'a>(
+    p: &mut Prettier<'a>,
+    statements: &[Statement<'_>],
+) -> Vec<Doc<'a>> {
+    let statements: Vec<&Statement<'_>> =
+        statements.iter().filter(|stmt| !matches!(stmt, Statement::EmptyStatement(_))).collect();
+    let mut parts = vec![];
+    for (index, stmt) in statements.iter().enumerate() {
+        parts.push(stmt.format(p));
+        if index + 1 < statements.len() {
+            parts.push(hardline());
+            if p.is_next_line_empty(stmt.span().end) {
+                parts.push(hardline());
+            }
+        }
+    }
+    parts
+}
+
+/// Directives keep their quotes unless they contain none, as changing them changes the meaning
+fn print_directive<'a>(p: &Prettier<'a>, directive: &Directive) -> Doc<'a> {
+    let raw = p.source(directive.expression.span);
+    let content = &raw[1..raw.len() - 1];
+    if content.contains(['"', '\'']) {
+        return array(vec![text(raw), text(";")]);
+    }
+    let quote = if p.options.single_quote { '\'' } else { '"' };
+    text(format!("{quote}{content}{quote};"))
+}
+
+fn print_block<'a>(
+    p: &mut Prettier<'a>,
+    span: Span,
+    directives: &[Directive],
+    statements: &[Statement<'_>],
+) -> Doc<'a> {
+    if directives.is_empty() && !has_statements(statements) {
+        if p.has_dangling_comments(span) {
+            let dangling = p.print_dangling_comments(span, false);
+            return array(vec![text("{"), dangling, hardline(), text("}")]);
+        }
+        return text("{}");
+    }
+    let body = print_block_body(p, directives, statements);
+    array(vec![text("{"), indent(vec![hardline(), body]), hardline(), text("}")])
+}
+
+/// Source text printed as is, e.g. for decorators and error recovery, with the comments inside it
+fn print_verbatim<'a>(p: &mut Prettier<'a>, span: Span) -> Doc<'a> {
+    p.mark_comments_printed(span);
+    print_text_lines(p.source(span))
+}
+
+/// Text which may span multiple lines, printed without changing the indentation of its lines
+fn print_text_lines(s: &str) -> Doc<'_> {
+    if !s.contains('\n') {
+        return text(s);
+    }
+    let lines = s.split('\n').map(|line| text(line.strip_suffix('\r').unwrap_or(line))).collect();
+    array(join(&literalline(), lines))
+}
+
+impl<'a> Format<'a> for Statement<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let doc = match self {
+            Self::BlockStatement(block) => print_block(p, block.span, &[], &block.body),
+            Self::BreakStatement(stmt) => print_jump(p, "break", stmt.label.as_ref()),
+            Self::ContinueStatement(stmt) => print_jump(p, "continue", stmt.label.as_ref()),
+            Self::DebuggerStatement(_) => text("debugger;"),
+            Self::DoWhileStatement(stmt) => {
+                let body = adjust_clause(p, &stmt.body, false);
+                let mut parts = vec![group(vec![text("do"), body])];
+                parts.push(if matches!(stmt.body, Self::BlockStatement(_)) {
+                    text(" ")
+                } else {
+                    hardline()
+                });
+                parts.push(text("while ("));
+                let test = print_test(p, &stmt.test);
+                parts.push(group(vec![indent(vec![softline(), test]), softline()]));
+                parts.push(text(");"));
+                array(parts)
+            }
+            Self::EmptyStatement(_) => text(""),
+            Self::ErrorStatement(stmt) => print_verbatim(p, stmt.span),
+            Self::ExpressionStatement(stmt) => {
+                array(vec![print_expression_no_wrap(p, &stmt.expression), text(";")])
+            }
+            Self::ForInStatement(stmt) => {
+                print_for_in_of(p, " in ", false, &stmt.left, &stmt.right, &stmt.body)
+            }
+            Self::ForOfStatement(stmt) => {
+                print_for_in_of(p, " of ", stmt.r#await, &stmt.left, &stmt.right, &stmt.body)
+            }
+            Self::ForStatement(stmt) => print_for(p, stmt),
+            Self::IfStatement(stmt) => print_if(p, stmt),
+            Self::LabeledStatement(stmt) => {
+                let label = text(p.source(stmt.label.span));
+                if matches!(stmt.body, Self::EmptyStatement(_)) {
+                    array(vec![label, text(":;")])
+                } else {
+                    array(vec![label, text(": "), stmt.body.format(p)])
+                }
+            }
+            Self::ReturnStatement(stmt) => {
+                print_return_or_throw(p, "return", stmt.span, stmt.argument.as_ref())
+            }
+            Self::SwitchStatement(stmt) => print_switch(p, stmt),
+            Self::ThrowStatement(stmt) => {
+                print_return_or_throw(p, "throw", stmt.span, Some(&stmt.argument))
+            }
+            Self::TryStatement(stmt) => print_try(p, stmt),
+            Self::WhileStatement(stmt) => {
+                let test = print_test(p, &stmt.test);
+                let body = adjust_clause(p, &stmt.body, false);
+                group(vec![
+                    text("while ("),
+                    group(vec![indent(vec![softline(), test]), softline()]),
+                    text(")"),
+                    body,
+                ])
+            }
+            Self::WithStatement(stmt) => {
+                let object = print_without_parens(p, &stmt.object);
+                let body = adjust_clause(p, &stmt.body, false);
+                group(vec![text("with ("), object, text(")"), body])
+            }
+            Self::ModuleDeclaration(decl) => print_module_declaration(p, decl),
+            Self::Declaration(decl) => decl.format(p),
+        };
+        p.with_comments(self.span(), doc)
+    }
+}
+
+/// The body of `if`, `for` and `while` statements, on the next line unless it is a block
+fn adjust_clause<'a>(p: &mut Prettier<'a>, body: &Statement<'_>, force_space: bool) -> Doc<'a> {
+    if matches!(body, Statement::EmptyStatement(_)) {
+        return text(";");
+    }
+    let doc = body.format(p);
+    if force_space || matches!(body, Statement::BlockStatement(_)) {
+        array(vec![text(" "), doc])
+    } else {
+        indent(vec![line(), doc])
+    }
+}
+
+/// Tests of `if`, `while` and `switch`, where binary expressions are not indented
+fn print_test<'a>(p: &mut Prettier<'a>, test: &Expression<'_>) -> Doc<'a> {
+    let test = strip_parens(test);
+    if is_binaryish(test) {
+        let doc = print_binaryish(p, test, BinaryishParent::InsideParens);
+        p.with_comments(test.span(), doc)
+    } else {
+        test.format(p)
+    }
+}
+
+fn print_jump<'a>(
+    p: &Prettier<'a>,
+    keyword: &'static str,
+    label: Option<&LabelIdentifier>,
+) -> Doc<'a> {
+    label.map_or_else(
+        || array(vec![text(keyword), text(";")]),
+        |label| array(vec![text(keyword), text(" "), text(p.source(label.span)), text(";")]),
+    )
+}
+
+fn print_if<'a>(p: &mut Prettier<'a>, stmt: &IfStatement<'_>) -> Doc<'a> {
+    let test = print_test(p, &stmt.test);
+    let consequent = adjust_clause(p, &stmt.consequent, false);
+    let opening = group(vec![
+        text("if ("),
+        group(vec![indent(vec![softline(), test]), softline()]),
+        text(")"),
+        consequent,
+    ]);
+    let mut parts = vec![opening];
+    if let Some(alternate) = &stmt.alternate {
+        let else_on_same_line = matches!(stmt.consequent, Statement::BlockStatement(_));
+        parts.push(if else_on_same_line { text(" ") } else { hardline() });
+        if p.has_dangling_comments(stmt.span) {
+            let dangling = p.print_dangling_comments(stmt.span, true);
+            let ends_with_hardline = ends_with_hardline(&dangling);
+            parts.push(dangling);
+            if !ends_with_hardline {
+                parts.push(text(" "));
+            }
+        }
+        parts.push(text("else"));
+        let is_else_if = matches!(alternate, Statement::IfStatement(_));
+        parts.push(group(vec![adjust_clause(p, alternate, is_else_if)]));
+    }
+    array(parts)
+}
+
+fn print_for<'a>(p: &mut Prettier<'a>, stmt: &ForStatement<'_>) -> Doc<'a> {
+    let body = adjust_clause(p, &stmt.body, false);
+    if stmt.init.is_none() && stmt.test.is_none() && stmt.update.is_none() {
+        return group(vec![text("for (;;)"), body]);
+    }
+    let init = match &stmt.init {
+        Some(ForStatementInit::VariableDeclaration(decl)) => {
+            print_variable_declaration(p, decl, true)
+        }
+        Some(ForStatementInit::Expression(expr)) => expr.format(p),
+        None => text(""),
+    };
+    let test = stmt.test.as_ref().map_or_else(|| text(""), |test| print_without_parens(p, test));
+    let update =
+        stmt.update.as_ref().map_or_else(|| text(""), |update| print_without_parens(p, update));
+    group(vec![
+        text("for ("),
+        group(vec![
+            indent(vec![softline(), init, text(";"), line(), test, text(";"), line(), update]),
+            softline(),
+        ]),
+        text(")"),
+        body,
+    ])
+}
+
+fn print_for_in_of<'a>(
+    p: &mut Prettier<'a>,
+    operator: &'static str,
+    r#await: bool,
+    left: &ForStatementLeft<'_>,
+    right: &Expression<'_>,
+    body: &Statement<'_>,
+) -> Doc<'a> {
+    let left = match left {
+        ForStatementLeft::VariableDeclaration(decl) => print_variable_declaration(p, decl, true),
+        ForStatementLeft::AssignmentTarget(target) => print_assignment_target(p, target),
+    };
+    let right = right.format(p);
+    let body = adjust_clause(p, body, false);
+    group(vec![
+        text(if r#await { "for await (" } else { "for (" }),
+        left,
+        text(operator),
+        right,
+        text(")"),
+        body,
+    ])
+}
+
+fn print_return_or_throw<'a>(
+    p: &mut Prettier<'a>,
+    keyword: &'static str,
+    span: Span,
+    argument: Option<&Expression<'_>>,
+) -> Doc<'a> {
+    let mut parts = vec![text(keyword)];
+    if let Some(argument) = argument {
+        let argument = strip_parens(argument);
+        if is_binaryish(argument) {
+            let doc = print_binaryish(p, argument, BinaryishParent::NoIndent);
+            let doc = p.with_comments(argument.span(), doc);
+            parts.push(group(vec![
+                text(" "),
+                if_break(text("("), text("")),
+                indent(vec![softline(), doc]),
+                softline(),
+                if_break(text(")"), text("")),
+            ]));
+        } else {
+            parts.push(text(" "));
+            parts.push(argument.format(p));
+        }
+    }
+    if p.has_dangling_comments(span) {
+        parts.push(text(" "));
+        parts.push(p.print_dangling_comments(span, true));
+    }
+    parts.push(text(";"));
+    array(parts)
+}
+
+fn print_switch<'a>(p: &mut Prettier<'a>, stmt: &SwitchStatement<'_>) -> Doc<'a> {
+    let discriminant = print_without_parens(p, &stmt.discriminant);
+    let mut parts = vec![
+        group(vec![
+            text("switch ("),
+            indent(vec![softline(), discriminant]),
+            softline(),
+            text(")"),
+        ]),
+        text(" {"),
+    ];
+    let mut cases = vec![];
+    for (index, case) in stmt.cases.iter().enumerate() {
+        let mut case_parts = vec![];
+        match &case.test {
+            Some(test) => {
+                case_parts.push(text("case "));
+                case_parts.push(print_without_parens(p, test));
+                case_parts.push(text(":"));
+            }
+            None => case_parts.push(text("default:")),
+        }
+        if p.has_dangling_comments(case.span) {
+            case_parts.push(text(" "));
+            case_parts.push(p.print_dangling_comments(case.span, true));
+        }
+        let consequent: Vec<&Statement<'_>> = case
+            .consequent
+            .iter()
+            .filter(|stmt| !matches!(stmt, Statement::EmptyStatement(_)))
+            .collect();
+        if !consequent.is_empty() {
+            let is_single_block =
+                consequent.len() == 1 && matches!(consequent[0], Statement::BlockStatement(_));
+            let statements = array(print_statement_sequence(p, &case.consequent));
+            case_parts.push(if is_single_block {
+                array(vec![text(" "), statements])
+            } else {
+                indent(vec![hardline(), statements])
+            });
+        }
+        cases.push(p.with_comments(case.span, array(case_parts)));
+        if index + 1 < stmt.cases.len() && p.is_next_line_empty(case.span.end) {
+            cases.push(text(""));
+        }
+    }
+    if !cases.is_empty() {
+        parts.push(indent(vec![hardline(), array(join(&hardline(), cases))]));
+    }
+    parts.push(hardline());
+    parts.push(text("}"));
+    array(parts)
+}
+
+fn print_try<'a>(p: &mut Prettier<'a>, stmt: &TryStatement<'_>) -> Doc<'a> {
+    let mut parts = vec![text("try "), print_block(p, stmt.block.span, &[], &stmt.block.body)];
+    if let Some(handler) = &stmt.handler {
+        parts.push(text(" "));
+        let mut handler_parts = vec![];
+        if let Some(param) = &handler.param {
+            handler_parts.push(text("catch ("));
+            handler_parts.push(param.format(p));
+            handler_parts.push(text(") "));
+        } else {
+            handler_parts.push(text("catch "));
+        }
+        handler_parts.push(print_block(p, handler.body.span, &[], &handler.body.body));
+        parts.push(p.with_comments(handler.span, array(handler_parts)));
+    }
+    if let Some(finalizer) = &stmt.finalizer {
+        parts.push(text(" finally "));
+        parts.push(print_block(p, finalizer.span, &[], &finalizer.body));
+    }
+    array(parts)
+}
+
+impl<'a> Format<'a> for Declaration<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        match self {
+            Self::VariableDeclaration(decl) => {
+                let mut parts = vec![];
+                if decl.modifiers.contains(ModifierKind::Declare) {
+                    parts.push(text("declare "));
+                }
+                parts.push(print_variable_declaration(p, decl, false));
+                parts.push(text(";"));
+                array(parts)
+            }
+            Self::FunctionDeclaration(func) => print_function(p, func),
+            Self::ClassDeclaration(class) => print_class(p, class),
+            Self::TSTypeAliasDeclaration(decl) => decl.format(p),
+            Self::TSInterfaceDeclaration(decl) => decl.format(p),
+            Self::TSEnumDeclaration(decl) => decl.format(p),
+            Self::TSModuleDeclaration(decl) => decl.format(p),
+            Self::TSImportEqualsDeclaration(decl) => decl.format(p),
+        }
+    }
+}
+
+/// Declarators after the first one are indented, each on its own line if any is initialized
+fn print_variable_declaration<'a>(
+    p: &mut Prettier<'a>,
+    decl: &VariableDeclaration<'_>,
+    in_for: bool,
+) -> Doc<'a> {
+    let kind = match decl.kind {
+        VariableDeclarationKind::Var => "var",
+        VariableDeclarationKind::Const => "const",
+        VariableDeclarationKind::Let => "let",
+        VariableDeclarationKind::Using => "using",
+        VariableDeclarationKind::AwaitUsing => "await using",
+    };
+    let has_value = decl.declarations.iter().any(|declarator| declarator.init.is_some());
+    let mut parts = vec![text(kind)];
+    let mut rest = vec![];
+    for (index, declarator) in decl.declarations.iter().enumerate() {
+        let doc = print_variable_declarator(p, declarator);
+        let doc = p.with_comments(declarator.span, doc);
+        if index == 0 {
+            parts.push(text(" "));
+            parts.push(doc);
+        } else {
+            rest.push(text(","));
+            rest.push(if has_value && !in_for { hardline() } else { line() });
+            rest.push(doc);
+        }
+    }
+    if !rest.is_empty() {
+        parts.push(indent(rest));
+    }
+    group(parts)
+}
+
+fn print_variable_declarator<'a>(
+    p: &mut Prettier<'a>,
+    declarator: &VariableDeclarator<'_>,
+) -> Doc<'a> {
+    let mut id = vec![declarator.id.format(p)];
+    if declarator.definite {
+        id.push(text("!"));
+    }
+    let left = if is_complex_destructuring_pattern(&declarator.id) {
+        AssignmentLeft::ComplexDestructuring
+    } else {
+        AssignmentLeft::Other
+    };
+    print_assignment(p, array(id), text(" ="), declarator.init.as_ref(), left)
+}
+
+/// An object pattern with more than two properties, some of which are not shorthand
+fn is_complex_destructuring_pattern(pattern: &BindingPattern<'_>) -> bool {
+    let BindingPatternKind::ObjectPattern(object) = &pattern.kind else { return false };
+    object.properties.len() > 2
+        && object.properties.iter().any(|property| {
+            !property.shorthand
+                || !matches!(property.value.kind, BindingPatternKind::BindingIdentifier(_))
+        })
+}
+
+fn print_function<'a>(p: &mut Prettier<'a>, func: &Function<'_>) -> Doc<'a> {
+    let mut parts = vec![];
+    if func.modifiers.contains(ModifierKind::Declare) {
+        parts.push(text("declare "));
+    }
+    if func.r#async {
+        parts.push(text("async "));
+    }
+    parts.push(text(if func.generator { "function* " } else { "function " }));
+    if let Some(id) = &func.id {
+        parts.push(text(p.source(id.span)));
+    }
+    parts.push(print_function_signature(p, func));
+    match &func.body {
+        Some(body) => {
+            parts.push(text(" "));
+            parts.push(print_block(p, body.span, &body.directives, &body.statements));
+        }
+        None => parts.push(text(";")),
+    }
+    array(parts)
+}
+
+/// Type parameters, parameters and return type of functions and methods
+fn print_function_signature<'a>(p: &mut Prettier<'a>, func: &Function<'_>) -> Doc<'a> {
+    let mut parts = vec![];
+    if let Some(type_parameters) = &func.type_parameters {
+        parts.push(print_type_parameters(p, type_parameters));
+    }
+    parts.push(group(vec![print_params(p, &func.params)]));
+    if let Some(return_type) = &func.return_type {
+        parts.push(print_type_annotation(p, return_type));
+    }
+    group(parts)
+}
+
+fn print_params<'a>(p: &mut Prettier<'a>, params: &FormalParameters<'_>) -> Doc<'a> {
+    if params.items.is_empty() && params.rest.is_none() {
+        return array(vec![text("("), p.print_dangling_comments(params.span, true), text(")")]);
+    }
+    let mut printed = vec![];
+    for param in &params.items {
+        let mut parts = vec![];
+        if !param.decorators.is_empty() {
+            parts.push(print_decorators(p, &param.decorators, param.pattern.span().start));
+        }
+        if let Some(accessibility) = param.accessibility {
+            parts.push(text(print_accessibility(accessibility)));
+        }
+        if param.r#override {
+            parts.push(text("override "));
+        }
+        if param.readonly {
+            parts.push(text("readonly "));
+        }
+        parts.push(print_binding_pattern(p, &param.pattern, true));
+        printed.push(p.with_comments(param.span, array(parts)));
+    }
+    if let Some(rest) = &params.rest {
+        let argument = print_binding_pattern(p, &rest.argument, true);
+        printed.push(p.with_comments(rest.span, array(vec![text("..."), argument])));
+    }
+    if should_hug_the_only_parameter(params) {
+        return array(vec![text("("), array(printed), text(")")]);
+    }
+    let trailing_comma = if p.options.trailing_comma.is_all() && params.rest.is_none() {
+        if_break(text(","), text(""))
+    } else {
+        text("")
+    };
+    array(vec![
+        text("("),
+        indent(vec![softline(), array(join(&array(vec![text(","), line()]), printed))]),
+        trailing_comma,
+        softline(),
+        text(")"),
+    ])
+}
+
+/// A single object or array pattern parameter is printed without breaking the parentheses,
+/// e.g. `function f({ a, b }) {}`
+fn should_hug_the_only_parameter(params: &FormalParameters<'_>) -> bool {
+    if params.items.len() != 1 || params.rest.is_some() {
+        return false;
+    }
+    let param = &params.items[0];
+    param.decorators.is_empty()
+        && param.accessibility.is_none()
+        && !param.readonly
+        && !param.r#override
+        && match &param.pattern.kind {
+            BindingPatternKind::ObjectPattern(_) | BindingPatternKind::ArrayPattern(_) => true,
+            BindingPatternKind::AssignmentPattern(assign) => {
+                matches!(assign.left.kind, BindingPatternKind::ObjectPattern(_))
+                    && matches!(&assign.right, Expression::ObjectExpression(object) if object.properties.is_empty())
+            }
+            BindingPatternKind::BindingIdentifier(_) => false,
+        }
+}
+
+fn print_accessibility(accessibility: TSAccessibility) -> &'static str {
+    match accessibility {
+        TSAccessibility::Private => "private ",
+        TSAccessibility::Protected => "protected ",
+        TSAccessibility::Public => "public ",
+    }
+}
+
+/// Decorators are printed as written, on their own lines if the first one was
+fn print_decorators<'a>(p: &mut Prettier<'a>, decorators: &[Decorator<'_>], next: u32) -> Doc<'a> {
+    let Some(last) = decorators.last() else { return text("") };
+    let separator = if p.has_newline_between(last.span.end, next) { hardline() } else { text(" ") };
+    let printed = decorators.iter().map(|decorator| print_verbatim(p, decorator.span)).collect();
+    array(vec![array(join(&separator, printed)), separator])
+}
+
+fn print_arrow<'a>(p: &mut Prettier<'a>, arrow: &ArrowExpression<'_>) -> Doc<'a> {
+    let mut signature = vec![];
+    if arrow.r#async {
+        signature.push(text("async "));
+    }
+    if let Some(type_parameters) = &arrow.type_parameters {
+        signature.push(print_arrow_type_parameters(p, type_parameters));
+    }
+    signature.push(group(vec![print_params(p, &arrow.params)]));
+    if let Some(return_type) = &arrow.return_type {
+        signature.push(print_type_annotation(p, return_type));
+    }
+    let mut parts = vec![group(signature), text(" =>")];
+
+    let body = match arrow.body.statements.first() {
+        Some(Statement::ExpressionStatement(stmt)) if arrow.expression => &stmt.expression,
+        _ => {
+            let body = &arrow.body;
+            parts.push(text(" "));
+            parts.push(print_block(p, body.span, &body.directives, &body.statements));
+            return group(parts);
+        }
+    };
+    let inner = body.without_parenthesized();
+    // Bodies which break by themselves stay on the same line as the arrow
+    if matches!(
+        inner,
+        Expression::ArrayExpression(_)
+            | Expression::ObjectExpression(_)
+            | Expression::JSXElement(_)
+            | Expression::JSXFragment(_)
+            | Expression::TemplateLiteral(_)
+            | Expression::TaggedTemplateExpression(_)
+            | Expression::ArrowExpression(_)
+    ) {
+        let body = if matches!(inner, Expression::ObjectExpression(_)) {
+            body.format(p)
+        } else {
+            print_without_parens(p, body)
+        };
+        parts.push(text(" "));
+        parts.push(body);
+        return group(parts);
+    }
+    if let Expression::SequenceExpression(sequence) = inner {
+        let doc = print_sequence(p, sequence);
+        let doc = p.with_comments(body.span(), doc);
+        parts.push(group(vec![text(" ("), indent(vec![softline(), doc]), softline(), text(")")]));
+        return group(parts);
+    }
+    let is_conditional = matches!(inner, Expression::ConditionalExpression(_));
+    let body_doc = if is_binaryish(inner) {
+        let doc = print_binaryish(p, inner, BinaryishParent::NoIndent);
+        p.with_comments(inner.span(), doc)
+    } else {
+        print_without_parens(p, body)
+    };
+    let body_doc = if is_conditional {
+        array(vec![if_break(text(""), text("(")), body_doc, if_break(text(""), text(")"))])
+    } else {
+        body_doc
+    };
+    parts.push(group(vec![indent(vec![line(), body_doc])]));
+    group(parts)
+}
+
+fn print_class<'a>(p: &mut Prettier<'a>, class: &Class<'_>) -> Doc<'a> {
+    let mut parts = vec![];
+    if !class.decorators.is_empty() {
+        let next = class.id.as_ref().map_or(class.body.span.start, |id| id.span.start);
+        parts.push(print_decorators(p, &class.decorators, next));
+    }
+    if class.is_declare() {
+        parts.push(text("declare "));
+    }
+    if class.modifiers.contains(ModifierKind::Abstract) {
+        parts.push(text("abstract "));
+    }
+    parts.push(text("class"));
+    if let Some(id) = &class.id {
+        parts.push(text(" "));
+        parts.push(text(p.source(id.span)));
+    }
+    if let Some(type_parameters) = &class.type_parameters {
+        parts.push(print_type_parameters(p, type_parameters));
+    }
+    if let Some(super_class) = &class.super_class {
+        parts.push(text(" extends "));
+        parts.push(super_class.format(p));
+        if let Some(type_parameters) = &class.super_type_parameters {
+            parts.push(print_type_arguments(p, type_parameters));
+        }
+    }
+    if let Some(implements) = &class.implements {
+        let printed = implements
+            .iter()
+            .map(|item| {
+                let mut parts = vec![print_type_name(p, &item.expression)];
+                if let Some(type_parameters) = &item.type_parameters {
+                    parts.push(print_type_arguments(p, type_parameters));
+                }
+                p.with_comments(item.span, array(parts))
+            })
+            .collect();
+        parts.push(text(" implements "));
+        parts.push(array(join(&text(", "), printed)));
+    }
+    parts.push(text(" "));
+    parts.push(print_class_body(p, &class.body));
+    array(parts)
+}
+
+fn print_class_body<'a>(p: &mut Prettier<'a>, body: &ClassBody<'_>) -> Doc<'a> {
+    if body.body.is_empty() {
+        if p.has_dangling_comments(body.span) {
+            let dangling = p.print_dangling_comments(body.span, false);
+            return array(vec![text("{"), dangling, hardline(), text("}")]);
+        }
+        return text("{}");
+    }
+    let mut parts = vec![];
+    for (index, element) in body.body.iter().enumerate() {
+        let doc = print_class_element(p, element);
+        parts.push(p.with_comments(element.span(), doc));
+        if index + 1 < body.body.len() {
+            parts.push(hardline());
+            if p.is_next_line_empty(element.span().end) {
+                parts.push(hardline());
+            }
+        }
+    }
+    array(vec![text("{"), indent(vec![hardline(), array(parts)]), hardline(), text("}")])
+}
+
+fn print_class_element<'a>(p: &mut Prettier<'a>, element: &ClassElement<'_>) -> Doc<'a> {
+    match element {
+        ClassElement::StaticBlock(block) => {
+            array(vec![text("static "), print_block(p, block.span, &[], &block.body)])
+        }
+        ClassElement::MethodDefinition(method) => print_method_definition(p, method, false),
+        ClassElement::PropertyDefinition(property) => print_property_definition(p, property, false),
+        ClassElement::AccessorProperty(property) => {
+            let mut parts = vec![];
+            if !property.decorators.is_empty() {
+                parts.push(print_decorators(p, &property.decorators, property.key.span().start));
+            }
+            if property.r#static {
+                parts.push(text("static "));
+            }
+            parts.push(text("accessor "));
+            parts.push(print_property_key(p, &property.key, property.computed, false));
+            let doc = print_assignment(
+                p,
+                array(parts),
+                text(" ="),
+                property.value.as_ref(),
+                AssignmentLeft::Other,
+            );
+            array(vec![doc, text(";")])
+        }
+        ClassElement::TSAbstractMethodDefinition(method) => {
+            print_method_definition(p, &method.method_definition, true)
+        }
+        ClassElement::TSAbstractPropertyDefinition(property) => {
+            print_property_definition(p, &property.property_definition, true)
+        }
+        ClassElement::TSIndexSignature(signature) => {
+            array(vec![print_index_signature(p, signature), text(";")])
+        }
+    }
+}
+
+fn print_method_definition<'a>(
+    p: &mut Prettier<'a>,
+    method: &MethodDefinition<'_>,
+    is_abstract: bool,
+) -> Doc<'a> {
+    let mut parts = vec![];
+    if !method.decorators.is_empty() {
+        parts.push(print_decorators(p, &method.decorators, method.key.span().start));
+    }
+    if let Some(accessibility) = method.accessibility {
+        parts.push(text(print_accessibility(accessibility)));
+    }
+    if method.r#static {
+        parts.push(text("static "));
+    }
+    if is_abstract {
+        parts.push(text("abstract "));
+    }
+    if method.r#override {
+        parts.push(text("override "));
+    }
+    let accessor = match method.kind {
+        MethodDefinitionKind::Get => "get ",
+        MethodDefinitionKind::Set => "set ",
+        MethodDefinitionKind::Constructor | MethodDefinitionKind::Method => "",
+    };
+    let mut key = vec![print_property_key(p, &method.key, method.computed, false)];
+    if method.optional {
+        key.push(text("?"));
+    }
+    parts.push(print_method(p, accessor, array(key), &method.value));
+    array(parts)
+}
+
+fn print_property_definition<'a>(
+    p: &mut Prettier<'a>,
+    property: &PropertyDefinition<'_>,
+    is_abstract: bool,
+) -> Doc<'a> {
+    let mut parts = vec![];
+    if !property.decorators.is_empty() {
+        parts.push(print_decorators(p, &property.decorators, property.key.span().start));
+    }
+    if property.declare {
+        parts.push(text("declare "));
+    }
+    if let Some(accessibility) = property.accessibility {
+        parts.push(text(print_accessibility(accessibility)));
+    }
+    if property.r#static {
+        parts.push(text("static "));
+    }
+    if is_abstract {
+        parts.push(text("abstract "));
+    }
+    if property.r#override {
+        parts.push(text("override "));
+    }
+    if property.readonly {
+        parts.push(text("readonly "));
+    }
+    parts.push(print_property_key(p, &property.key, property.computed, false));
+    if property.optional {
+        parts.push(text("?"));
+    }
+    if property.definite {
+        parts.push(text("!"));
+    }
+    if let Some(type_annotation) = &property.type_annotation {
+        parts.push(print_type_annotation(p, type_annotation));
+    }
+    let doc = print_assignment(
+        p,
+        array(parts),
+        text(" ="),
+        property.value.as_ref(),
+        AssignmentLeft::Other,
+    );
+    array(vec![doc, text(";")])
+}
+
+/// Methods of classes and objects, `accessor` being `get `, `set ` or nothing
+fn print_method<'a>(
+    p: &mut Prettier<'a>,
+    accessor: &'static str,
+    key: Doc<'a>,
+    func: &Function<'_>,
+) -> Doc<'a> {
+    let mut parts = vec![];
+    if func.r#async {
+        parts.push(text("async "));
+    }
+    parts.push(text(accessor));
+    if func.generator {
+        parts.push(text("*"));
+    }
+    parts.push(key);
+    parts.push(print_function_signature(p, func));
+    match &func.body {
+        Some(body) => {
+            parts.push(text(" "));
+            parts.push(print_block(p, body.span, &body.directives, &body.statements));
+        }
+        None => parts.push(text(";")),
+    }
+    array(parts)
+}
+
+fn print_module_declaration<'a>(p: &mut Prettier<'a>, decl: &ModuleDeclaration<'_>) -> Doc<'a> {
+    match decl {
+        ModuleDeclaration::ImportDeclaration(decl) => print_import_declaration(p, decl),
+        ModuleDeclaration::ExportAllDeclaration(decl) => {
+            let mut parts = vec![text("export")];
+            if decl.export_kind.is_type() {
+                parts.push(text(" type"));
+            }
+            parts.push(text(" *"));
+            if let Some(exported) = &decl.exported {
+                parts.push(text(" as "));
+                parts.push(print_module_export_name(p, exported));
+            }
+            parts.push(text(" from "));
+            parts.push(print_string(p, decl.source.span));
+            parts.push(print_with_clause(p, decl.with_clause.as_ref()));
+            parts.push(text(";"));
+            array(parts)
+        }
+        ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+            let declaration = match &decl.declaration {
+                ExportDefaultDeclarationKind::Expression(expr) => {
+                    array(vec![expr.format(p), text(";")])
+                }
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => print_function(p, func),
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => print_class(p, class),
+                ExportDefaultDeclarationKind::TSInterfaceDeclaration(decl) => decl.format(p),
+                ExportDefaultDeclarationKind::TSEnumDeclaration(decl) => decl.format(p),
+            };
+            array(vec![text("export default "), declaration])
+        }
+        ModuleDeclaration::ExportNamedDeclaration(decl) => {
+            if let Some(declaration) = &decl.declaration {
+                return array(vec![text("export "), declaration.format(p)]);
+            }
+            let mut parts = vec![text("export")];
+            if decl.export_kind.is_type() {
+                parts.push(text(" type"));
+            }
+            parts.push(text(" "));
+            if decl.specifiers.is_empty() {
+                parts.push(text("{}"));
+            } else {
+                let specifiers = decl
+                    .specifiers
+                    .iter()
+                    .map(|specifier| {
+                        let mut parts = vec![];
+                        if specifier.export_kind.is_type() {
+                            parts.push(text("type "));
+                        }
+                        parts.push(print_module_export_name(p, &specifier.local));
+                        if p.source(specifier.local.span()) != p.source(specifier.exported.span()) {
+                            parts.push(text(" as "));
+                            parts.push(print_module_export_name(p, &specifier.exported));
+                        }
+                        p.with_comments(specifier.span, array(parts))
+                    })
+                    .collect();
+                parts.push(print_module_specifiers(p, vec![], specifiers));
+            }
+            if let Some(source) = &decl.source {
+                parts.push(text(" from "));
+                parts.push(print_string(p, source.span));
+            }
+            parts.push(print_with_clause(p, decl.with_clause.as_ref()));
+            parts.push(text(";"));
+            array(parts)
+        }
+        ModuleDeclaration::TSExportAssignment(decl) => {
+            array(vec![text("export = "), decl.expression.format(p), text(";")])
+        }
+        ModuleDeclaration::TSNamespaceExportDeclaration(decl) => {
+            array(vec![text("export as namespace "), text(p.source(decl.id.span)), text(";")])
+        }
+    }
+}
+
+fn print_import_declaration<'a>(p: &mut Prettier<'a>, decl: &ImportDeclaration<'_>) -> Doc<'a> {
+    let mut parts = vec![text("import")];
+    if decl.import_kind.is_type() {
+        parts.push(text(" type"));
+    }
+    if decl.specifiers.is_empty() {
+        // `import {} from "a"`, unlike `import "a"`, does not evaluate the module
+        if p.source(Span::new(decl.span.start, decl.source.span.start)).contains('{') {
+            parts.push(text(" {} from"));
+        }
+    } else {
+        let mut standalone = vec![];
+        let mut grouped = vec![];
+        for specifier in &decl.specifiers {
+            match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                    let mut parts = vec![];
+                    if specifier.import_kind.is_type() {
+                        parts.push(text("type "));
+                    }
+                    parts.push(print_module_export_name(p, &specifier.imported));
+                    if p.source(specifier.imported.span()) != p.source(specifier.local.span) {
+                        parts.push(text(" as "));
+                        parts.push(text(p.source(specifier.local.span)));
+                    }
+                    grouped.push(p.with_comments(specifier.span, array(parts)));
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                    standalone.push(text(p.source(specifier.local.span)));
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                    standalone
+                        .push(array(vec![text("* as "), text(p.source(specifier.local.span))]));
+                }
+            }
+        }
+        parts.push(text(" "));
+        parts.push(print_module_specifiers(p, standalone, grouped));
+        parts.push(text(" from"));
+    }
+    parts.push(text(" "));
+    parts.push(print_string(p, decl.source.span));
+    parts.push(print_with_clause(p, decl.with_clause.as_ref()));
+    parts.push(text(";"));
+    array(parts)
+}
+
+/// `a, * as b` followed by `{ c, d }`, broken over multiple lines if too long
+fn print_module_specifiers<'a>(
+    p: &Prettier<'a>,
+    standalone: Vec<Doc<'a>>,
+    grouped: Vec<Doc<'a>>,
+) -> Doc<'a> {
+    let has_standalone = !standalone.is_empty();
+    let mut parts = join(&text(", "), standalone);
+    if !grouped.is_empty() {
+        if has_standalone {
+            parts.push(text(", "));
+        }
+        if grouped.len() > 1 || has_standalone {
+            let trailing_comma = if p.options.trailing_comma.is_es5() { "," } else { "" };
+            parts.push(group(vec![
+                text("{"),
+                indent(vec![line(), array(join(&array(vec![text(","), line()]), grouped))]),
+                if_break(text(trailing_comma), text("")),
+                line(),
+                text("}"),
+            ]));
+        } else {
+            parts.push(array(vec![text("{ "), array(grouped), text(" }")]));
+        }
+    }
+    array(parts)
+}
+
+fn print_module_export_name<'a>(p: &Prettier<'a>, name: &ModuleExportName) -> Doc<'a> {
+    match name {
+        ModuleExportName::Identifier(ident) => text(p.source(ident.span)),
+        ModuleExportName::StringLiteral(literal) => print_string(p, literal.span),
+    }
+}
+
+fn print_with_clause<'a>(p: &Prettier<'a>, with_clause: Option<&WithClause<'_>>) -> Doc<'a> {
+    let Some(with_clause) = with_clause else { return text("") };
+    let keyword = text(p.source(with_clause.attributes_keyword.span));
+    if with_clause.with_entries.is_empty() {
+        return array(vec![text(" "), keyword, text(" {}")]);
+    }
+    let attributes = with_clause
+        .with_entries
+        .iter()
+        .map(|attribute| {
+            let key = match &attribute.key {
+                ImportAttributeKey::Identifier(ident) => text(p.source(ident.span)),
+                ImportAttributeKey::StringLiteral(literal) => print_string(p, literal.span),
+            };
+            array(vec![key, text(": "), print_string(p, attribute.value.span)])
+        })
+        .collect();
+    array(vec![text(" "), keyword, text(" { "), array(join(&text(", "), attributes)), text(" }")])
+}
+
+impl<'a> Format<'a> for BindingPattern<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        print_binding_pattern(p, self, false)
+    }
+}
+
+fn print_binding_pattern<'a>(
+    p: &mut Prettier<'a>,
+    pattern: &BindingPattern<'_>,
+    in_params: bool,
+) -> Doc<'a> {
+    let doc = match &pattern.kind {
+        BindingPatternKind::BindingIdentifier(ident) => {
+            p.with_comments(ident.span, text(p.source(ident.span)))
+        }
+        BindingPatternKind::ObjectPattern(object) => {
+            let mut members = vec![];
+            for property in &object.properties {
+                let doc = if property.shorthand {
+                    print_binding_pattern(p, &property.value, in_params)
+                } else {
+                    let key = print_property_key(p, &property.key, property.computed, true);
+                    let value = print_binding_pattern(p, &property.value, in_params);
+                    array(vec![key, text(": "), value])
+                };
+                members.push((p.with_comments(property.span, doc), property.span));
+            }
+            if let Some(rest) = &object.rest {
+                let argument = print_binding_pattern(p, &rest.argument, in_params);
+                members.push((array(vec![text("..."), argument]), rest.span));
+            }
+            // Nested object patterns are expanded, except in parameters
+            let should_break = !in_params
+                && object.properties.iter().any(|property| {
+                    matches!(&property.value.kind, BindingPatternKind::ObjectPattern(object) if !object.properties.is_empty())
+                });
+            let doc =
+                print_object_like(p, object.span, members, object.rest.is_none(), should_break);
+            p.with_comments(object.span, doc)
+        }
+        BindingPatternKind::ArrayPattern(array_pattern) => {
+            let mut items = vec![];
+            for element in &array_pattern.elements {
+                items.push(
+                    element.as_ref().map(|element| {
+                        (print_binding_pattern(p, element, in_params), element.span())
+                    }),
+                );
+            }
+            if let Some(rest) = &array_pattern.rest {
+                let argument = print_binding_pattern(p, &rest.argument, in_params);
+                items.push(Some((array(vec![text("..."), argument]), rest.span)));
+            }
+            let can_have_trailing_comma = array_pattern.rest.is_none();
+            let doc = print_array_like(
+                p,
+                array_pattern.span,
+                items,
+                can_have_trailing_comma,
+                false,
+                false,
+            );
+            p.with_comments(array_pattern.span, doc)
+        }
+        BindingPatternKind::AssignmentPattern(assign) => {
+            let left = print_binding_pattern(p, &assign.left, in_params);
+            let right = print_without_parens(p, &assign.right);
+            p.with_comments(assign.span, array(vec![left, text(" = "), right]))
+        }
+    };
+    let mut parts = vec![doc];
+    if pattern.optional {
+        parts.push(text("?"));
+    }
+    if let Some(type_annotation) = &pattern.type_annotation {
+        parts.push(print_type_annotation(p, type_annotation));
+    }
+    array(parts)
+}
+
+fn print_assignment_target<'a>(p: &mut Prettier<'a>, target: &AssignmentTarget<'_>) -> Doc<'a> {
+    match target {
+        AssignmentTarget::SimpleAssignmentTarget(target) => {
+            print_simple_assignment_target(p, target)
+        }
+        AssignmentTarget::AssignmentTargetPattern(
+            AssignmentTargetPattern::ArrayAssignmentTarget(target),
+        ) => {
+            let mut items = vec![];
+            for element in &target.elements {
+                items.push(element.as_ref().map(|element| {
+                    (print_assignment_target_maybe_default(p, element), maybe_default_span(element))
+                }));
+            }
+            if let Some(rest) = &target.rest {
+                let doc = array(vec![text("..."), print_assignment_target(p, rest)]);
+                items.push(Some((doc, rest.span())));
+            }
+            let can_have_trailing_comma = target.rest.is_none();
+            print_array_like(p, target.span, items, can_have_trailing_comma, false, false)
+        }
+        AssignmentTarget::AssignmentTargetPattern(
+            AssignmentTargetPattern::ObjectAssignmentTarget(target),
+        ) => {
+            let mut members = vec![];
+            for property in &target.properties {
+                let (doc, span) = match property {
+                    AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) => {
+                        let mut parts = vec![text(p.source(ident.binding.span))];
+                        if let Some(init) = &ident.init {
+                            parts.push(text(" = "));
+                            parts.push(print_without_parens(p, init));
+                        }
+                        (array(parts), ident.span)
+                    }
+                    AssignmentTargetProperty::AssignmentTargetPropertyProperty(property) => {
+                        let key = print_property_key(p, &property.name, false, true);
+                        let value = print_assignment_target_maybe_default(p, &property.binding);
+                        (array(vec![key, text(": "), value]), property.span)
+                    }
+                };
+                members.push((p.with_comments(span, doc), span));
+            }
+            if let Some(rest) = &target.rest {
+                let doc = array(vec![text("..."), print_assignment_target(p, rest)]);
+                members.push((doc, rest.span()));
+            }
+            print_object_like(p, target.span, members, target.rest.is_none(), false)
+        }
+    }
+}
+
+fn print_assignment_target_maybe_default<'a>(
+    p: &mut Prettier<'a>,
+    target: &AssignmentTargetMaybeDefault<'_>,
+) -> Doc<'a> {
+    match target {
+        AssignmentTargetMaybeDefault::AssignmentTarget(target) => {
+            print_assignment_target(p, target)
+        }
+        AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => {
+            let binding = print_assignment_target(p, &target.binding);
+            let init = print_without_parens(p, &target.init);
+            array(vec![binding, text(" = "), init])
+        }
+    }
+}
+
+fn maybe_default_span(target: &AssignmentTargetMaybeDefault<'_>) -> Span {
+    match target {
+        AssignmentTargetMaybeDefault::AssignmentTarget(target) => target.span(),
+        AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => target.span,
+    }
+}
+
+fn print_simple_assignment_target<'a>(
+    p: &mut Prettier<'a>,
+    target: &SimpleAssignmentTarget<'_>,
+) -> Doc<'a> {
+    let doc = match target {
+        SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => text(p.source(ident.span)),
+        SimpleAssignmentTarget::MemberAssignmentTarget(member) => {
+            print_member_expression(p, member)
+        }
+        SimpleAssignmentTarget::TSAsExpression(expr) => {
+            print_type_cast(p, &expr.expression, " as ", &expr.type_annotation)
+        }
+        SimpleAssignmentTarget::TSSatisfiesExpression(expr) => {
+            print_type_cast(p, &expr.expression, " satisfies ", &expr.type_annotation)
+        }
+        SimpleAssignmentTarget::TSNonNullExpression(expr) => {
+            array(vec![expr.expression.format(p), text("!")])
+        }
+        SimpleAssignmentTarget::TSTypeAssertion(expr) => print_type_assertion(p, expr),
+    };
+    p.with_comments(target.span(), doc)
+}
+
+fn print_type_cast<'a>(
+    p: &mut Prettier<'a>,
+    expr: &Expression<'_>,
+    operator: &'static str,
+    type_annotation: &TSType<'_>,
+) -> Doc<'a> {
+    let expr = expr.format(p);
+    array(vec![expr, text(operator), type_annotation.format(p)])
+}
+
+fn print_type_assertion<'a>(p: &mut Prettier<'a>, expr: &TSTypeAssertion<'_>) -> Doc<'a> {
+    let type_annotation = expr.type_annotation.format(p);
+    array(vec![text("<"), type_annotation, text(">"), expr.expression.format(p)])
+}
+
+impl<'a> Format<'a> for Expression<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let doc = print_expression(p, self);
+        p.with_comments(self.span(), doc)
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn print_expression<'a>(p: &mut Prettier<'a>, expr: &Expression<'_>) -> Doc<'a> {
+    match expr {
+        Expression::BooleanLiteral(literal) => text(if literal.value { "true" } else { "false" }),
+        Expression::NullLiteral(_) => text("null"),
+        Expression::NumberLiteral(literal) => text(literal::print_number(p.source(literal.span))),
+        Expression::BigintLiteral(literal) => text(p.source(literal.span).to_ascii_lowercase()),
+        Expression::RegExpLiteral(literal) => text(literal::print_regexp(p.source(literal.span))),
+        Expression::StringLiteral(literal) => print_string(p, literal.span),
+        Expression::TemplateLiteral(literal) => print_template_literal(p, literal),
+        Expression::Identifier(ident) => text(p.source(ident.span)),
+        Expression::MetaProperty(meta) => text(p.source(meta.span)),
+        Expression::Super(_) => text("super"),
+        Expression::ThisExpression(_) => text("this"),
+        Expression::ErrorExpression(_) => text(""),
+        Expression::ArrayExpression(array_expr) => print_array_expression(p, array_expr),
+        Expression::ObjectExpression(object) => print_object_expression(p, object),
+        Expression::ArrowExpression(arrow) => print_arrow(p, arrow),
+        Expression::FunctionExpression(func) => print_function(p, func),
+        Expression::ClassExpression(class) => print_class(p, class),
+        Expression::AssignmentExpression(assign) => {
+            let left = print_assignment_target(p, &assign.left);
+            let operator = text(format!(" {}", assign.operator.as_str()));
+            let left_kind = if is_complex_destructuring_target(&assign.left) {
+                AssignmentLeft::ComplexDestructuring
+            } else {
+                AssignmentLeft::Other
+            };
+            print_assignment(p, left, operator, Some(&assign.right), left_kind)
+        }
+        Expression::AwaitExpression(expr) => array(vec![text("await "), expr.argument.format(p)]),
+        Expression::BinaryExpression(_) | Expression::LogicalExpression(_) => {
+            print_binaryish(p, expr, BinaryishParent::Other)
+        }
+        Expression::PrivateInExpression(expr) => {
+            let left = text(p.source(expr.left.span));
+            array(vec![left, text(" in "), expr.right.format(p)])
+        }
+        Expression::CallExpression(call) => call::print_call_expression(p, call),
+        Expression::NewExpression(new) => call::print_new_expression(p, new),
+        Expression::ImportExpression(import) => call::print_import_expression(p, import),
+        Expression::ChainExpression(chain) => match &chain.expression {
+            ChainElement::CallExpression(call) => call::print_call_expression(p, call),
+            ChainElement::MemberExpression(member) => print_member_expression(p, member),
+        },
+        Expression::MemberExpression(member) => print_member_expression(p, member),
+        Expression::ConditionalExpression(conditional) => print_conditional(p, conditional, false),
+        Expression::ParenthesizedExpression(paren) => {
+            if can_remove_parens(&paren.expression) {
+                paren.expression.format(p)
+            } else {
+                array(vec![text("("), paren.expression.format(p), text(")")])
+            }
+        }
+        Expression::SequenceExpression(sequence) => print_sequence(p, sequence),
+        Expression::TaggedTemplateExpression(expr) => {
+            let mut parts = vec![expr.tag.format(p)];
+            if let Some(type_parameters) = &expr.type_parameters {
+                parts.push(print_type_arguments(p, type_parameters));
+            }
+            parts.push(print_template_literal(p, &expr.quasi));
+            array(parts)
+        }
+        Expression::UnaryExpression(expr) => {
+            let operator = expr.operator.as_str();
+            let mut parts = vec![text(operator)];
+            if expr.operator.is_keyword() {
+                parts.push(text(" "));
+            }
+            let argument = expr.argument.format(p);
+            // `- -a` and `+ ++a` must not become `--a` and `+++a`
+            let needs_parens = match &expr.argument {
+                Expression::UnaryExpression(argument) => {
+                    !expr.operator.is_keyword() && argument.operator.as_str() == operator
+                }
+                Expression::UpdateExpression(argument) => {
+                    argument.prefix && argument.operator.as_str().starts_with(operator)
+                }
+                _ => false,
+            };
+            if needs_parens {
+                parts.extend([text("("), argument, text(")")]);
+            } else {
+                parts.push(argument);
+            }
+            array(parts)
+        }
+        Expression::UpdateExpression(expr) => {
+            let operator = text(expr.operator.as_str());
+            let argument = print_simple_assignment_target(p, &expr.argument);
+            if expr.prefix {
+                array(vec![operator, argument])
+            } else {
+                array(vec![argument, operator])
+            }
+        }
+        Expression::YieldExpression(expr) => {
+            let mut parts = vec![text(if expr.delegate { "yield*" } else { "yield" })];
+            if let Some(argument) = &expr.argument {
+                parts.push(text(" "));
+                parts.push(argument.format(p));
+            }
+            array(parts)
+        }
+        Expression::JSXElement(element) => {
+            let doc = jsx::print_jsx_element(p, element);
+            jsx::wrap_jsx(doc)
+        }
+        Expression::JSXFragment(fragment) => {
+            let doc = jsx::print_jsx_fragment(p, fragment);
+            jsx::wrap_jsx(doc)
+        }
+        Expression::TSAsExpression(expr) => {
+            print_type_cast(p, &expr.expression, " as ", &expr.type_annotation)
+        }
+        Expression::TSSatisfiesExpression(expr) => {
+            print_type_cast(p, &expr.expression, " satisfies ", &expr.type_annotation)
+        }
+        Expression::TSTypeAssertion(expr) => print_type_assertion(p, expr),
+        Expression::TSNonNullExpression(expr) => array(vec![expr.expression.format(p), text("!")]),
+        Expression::TSInstantiationExpression(expr) => {
+            let expression = expr.expression.format(p);
+            array(vec![expression, print_type_arguments(p, &expr.type_parameters)])
+        }
+    }
+}
+
+/// JSX elements are not wrapped in parentheses in statements, arguments and conditionals
+fn print_expression_no_wrap<'a>(p: &mut Prettier<'a>, expr: &Expression<'_>) -> Doc<'a> {
+    let doc = match strip_parens(expr) {
+        Expression::JSXElement(element) => jsx::print_jsx_element(p, element),
+        Expression::JSXFragment(fragment) => jsx::print_jsx_fragment(p, fragment),
+        _ => return expr.format(p),
+    };
+    p.with_comments(strip_parens(expr).span(), doc)
+}
+
+/// `expr` without the parentheses around it, in positions where any expression other than
+/// a sequence is unambiguous, e.g. the right side of assignments and the arguments of calls
+fn strip_parens<'e, 'b>(expr: &'e Expression<'b>) -> &'e Expression<'b> {
+    match expr {
+        Expression::ParenthesizedExpression(paren)
+            if !matches!(paren.expression, Expression::SequenceExpression(_)) =>
+        {
+            strip_parens(&paren.expression)
+        }
+        _ => expr,
+    }
+}
+
+fn print_without_parens<'a>(p: &mut Prettier<'a>, expr: &Expression<'_>) -> Doc<'a> {
+    strip_parens(expr).format(p)
+}
+
+/// Parentheses which are never needed around `expr`, wherever it is
+fn can_remove_parens(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::Identifier(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::NumberLiteral(_)
+        | Expression::BigintLiteral(_)
+        | Expression::RegExpLiteral(_)
+        | Expression::TemplateLiteral(_)
+        | Expression::ThisExpression(_)
+        | Expression::Super(_)
+        | Expression::MetaProperty(_)
+        | Expression::ArrayExpression(_)
+        | Expression::JSXElement(_)
+        | Expression::JSXFragment(_)
+        | Expression::ParenthesizedExpression(_) => true,
+        Expression::MemberExpression(member) => {
+            !member.optional()
+                && matches!(
+                    member.object(),
+                    Expression::Identifier(_)
+                        | Expression::ThisExpression(_)
+                        | Expression::MemberExpression(_)
+                )
+                && can_remove_parens(member.object())
+        }
+        _ => false,
+    }
+}
+
+fn is_complex_destructuring_target(target: &AssignmentTarget<'_>) -> bool {
+    let AssignmentTarget::AssignmentTargetPattern(AssignmentTargetPattern::ObjectAssignmentTarget(
+        object,
+    )) = target
+    else {
+        return false;
+    };
+    object.properties.len() > 2
+        && object.properties.iter().any(|property| {
+            !matches!(
+                property,
+                AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident)
+                    if ident.init.is_none()
+            )
+        })
+}
+
+fn print_member_expression<'a>(p: &mut Prettier<'a>, member: &MemberExpression<'_>) -> Doc<'a> {
+    let object = print_member_object(p, member.object());
+    array(vec![object, print_member_lookup(p, member)])
+}
+
+/// Number literals need parentheses to be the object of member expressions, e.g. `(1).toFixed()`
+fn print_member_object<'a>(p: &mut Prettier<'a>, object: &Expression<'_>) -> Doc<'a> {
+    match object.without_parenthesized() {
+        number @ Expression::NumberLiteral(_) => {
+            array(vec![text("("), number.format(p), text(")")])
+        }
+        _ => object.format(p),
+    }
+}
+
+fn print_member_lookup<'a>(p: &mut Prettier<'a>, member: &MemberExpression<'_>) -> Doc<'a> {
+    match member {
+        MemberExpression::StaticMemberExpression(expr) => {
+            let property = text(p.source(expr.property.span));
+            array(vec![text(if expr.optional { "?." } else { "." }), property])
+        }
+        MemberExpression::PrivateFieldExpression(expr) => {
+            let field = text(p.source(expr.field.span));
+            array(vec![text(if expr.optional { "?." } else { "." }), field])
+        }
+        MemberExpression::ComputedMemberExpression(expr) => {
+            let open = text(if expr.optional { "?.[" } else { "[" });
+            let property = strip_parens(&expr.expression);
+            let doc = property.format(p);
+            if matches!(property, Expression::NumberLiteral(_) | Expression::StringLiteral(_)) {
+                array(vec![open, doc, text("]")])
+            } else {
+                group(vec![open, indent(vec![softline(), doc]), softline(), text("]")])
+            }
+        }
+    }
+}
+
+fn print_sequence<'a>(p: &mut Prettier<'a>, sequence: &SequenceExpression<'_>) -> Doc<'a> {
+    let mut parts = vec![];
+    let mut rest = vec![];
+    for (index, expr) in sequence.expressions.iter().enumerate() {
+        let doc = expr.format(p);
+        if index == 0 {
+            parts.push(doc);
+        } else {
+            rest.extend([text(","), line(), doc]);
+        }
+    }
+    parts.push(indent(rest));
+    group(parts)
+}
+
+/// Conditionals in the alternate of another conditional are chained at the same indentation,
+/// and conditionals with JSX branches wrap them in parentheses when broken
+fn print_conditional<'a>(
+    p: &mut Prettier<'a>,
+    expr: &ConditionalExpression<'_>,
+    in_alternate: bool,
+) -> Doc<'a> {
+    let test = strip_parens(&expr.test);
+    let test_doc = if is_binaryish(test) {
+        let doc = print_binaryish(p, test, BinaryishParent::NoIndent);
+        p.with_comments(test.span(), doc)
+    } else {
+        test.format(p)
+    };
+    let consequent = strip_parens(&expr.consequent);
+    let alternate = strip_parens(&expr.alternate);
+    let is_jsx = |expr: &Expression<'_>| {
+        matches!(expr, Expression::JSXElement(_) | Expression::JSXFragment(_))
+    };
+    if is_jsx(consequent) || is_jsx(alternate) {
+        let is_nil = |expr: &Expression<'_>| expr.is_null() || expr.is_specific_id("undefined");
+        let wrap = |doc: Doc<'a>| {
+            array(vec![
+                if_break(text("("), text("")),
+                indent(vec![softline(), doc]),
+                softline(),
+                if_break(text(")"), text("")),
+            ])
+        };
+        let consequent_doc = print_expression_no_wrap(p, consequent);
+        let consequent_doc = if is_nil(consequent) { consequent_doc } else { wrap(consequent_doc) };
+        let alternate_doc = match alternate {
+            Expression::ConditionalExpression(alternate) => {
+                let doc = print_conditional(p, alternate, true);
+                p.with_comments(alternate.span, doc)
+            }
+            _ if is_nil(alternate) => alternate.format(p),
+            _ => wrap(print_expression_no_wrap(p, alternate)),
+        };
+        return group(vec![test_doc, text(" ? "), consequent_doc, text(" : "), alternate_doc]);
+    }
+    let consequent_doc = print_expression_no_wrap(p, consequent);
+    let alternate_doc = match alternate {
+        Expression::ConditionalExpression(alternate) => {
+            let doc = print_conditional(p, alternate, true);
+            p.with_comments(alternate.span, doc)
+        }
+        _ => print_expression_no_wrap(p, alternate),
+    };
+    let parts = vec![line(), text("? "), consequent_doc, line(), text(": "), alternate_doc];
+    if in_alternate {
+        array(vec![test_doc, array(parts)])
+    } else {
+        group(vec![test_doc, indent(parts)])
+    }
+}
+
+fn print_object_expression<'a>(p: &mut Prettier<'a>, object: &ObjectExpression<'_>) -> Doc<'a> {
+    // Objects written over multiple lines stay expanded
+    let should_break = object
+        .properties
+        .first()
+        .map_or(false, |first| p.has_newline_between(object.span.start, first.span().start));
+    let mut members = vec![];
+    for property in &object.properties {
+        let doc = match property {
+            ObjectPropertyKind::ObjectProperty(property) => {
+                let doc = print_object_property(p, property);
+                p.with_comments(property.span, doc)
+            }
+            ObjectPropertyKind::SpreadProperty(spread) => {
+                let argument = print_without_parens(p, &spread.argument);
+                p.with_comments(spread.span, array(vec![text("..."), argument]))
+            }
+        };
+        members.push((doc, property.span()));
+    }
+    print_object_like(p, object.span, members, true, should_break)
+}
+
+fn print_object_property<'a>(p: &mut Prettier<'a>, property: &ObjectProperty<'_>) -> Doc<'a> {
+    if property.shorthand {
+        return property.value.format(p);
+    }
+    let accessor = match property.kind {
+        PropertyKind::Get => "get ",
+        PropertyKind::Set => "set ",
+        PropertyKind::Init => "",
+    };
+    if let Expression::FunctionExpression(func) = &property.value {
+        if property.method || !accessor.is_empty() {
+            let key = print_property_key(p, &property.key, property.computed, true);
+            return print_method(p, accessor, key, func);
+        }
+    }
+    let key = print_property_key(p, &property.key, property.computed, true);
+    // Values after keys shorter than the indentation are not moved to the next line
+    let is_short_key = !property.computed
+        && matches!(&key, Doc::Str(key) if key.chars().count() < p.options.tab_width + 3);
+    let left = if is_short_key { AssignmentLeft::ShortKey } else { AssignmentLeft::Other };
+    print_assignment(p, key, text(":"), Some(&property.value), left)
+}
+
+/// Members of objects and object patterns, separated by commas and kept on one line if they fit,
+/// keeping single blank lines between them
+fn print_object_like<'a>(
+    p: &mut Prettier<'a>,
+    span: Span,
+    members: Vec<(Doc<'a>, Span)>,
+    can_have_trailing_comma: bool,
+    should_break: bool,
+) -> Doc<'a> {
+    if members.is_empty() {
+        if !p.has_dangling_comments(span) {
+            return text("{}");
+        }
+        let dangling = p.print_dangling_comments(span, false);
+        return group(vec![text("{"), dangling, softline(), text("}")]);
+    }
+    let len = members.len();
+    let mut parts = vec![];
+    for (index, (doc, member_span)) in members.into_iter().enumerate() {
+        parts.push(group(vec![doc]));
+        if index + 1 < len {
+            parts.push(text(","));
+            parts.push(line());
+            if p.is_next_line_empty(member_span.end) {
+                parts.push(softline());
+            }
+        }
+    }
+    let trailing_comma = if can_have_trailing_comma && p.options.trailing_comma.is_es5() {
+        if_break(text(","), text(""))
+    } else {
+        text("")
+    };
+    group_with_break(
+        vec![text("{"), indent(vec![line(), array(parts)]), trailing_comma, line(), text("}")],
+        should_break,
+    )
+}
+
+fn print_array_expression<'a>(p: &mut Prettier<'a>, expr: &ArrayExpression<'_>) -> Doc<'a> {
+    let mut items = vec![];
+    for element in &expr.elements {
+        let item = match element {
+            ArrayExpressionElement::SpreadElement(spread) => {
+                let argument = print_without_parens(p, &spread.argument);
+                Some(p.with_comments(spread.span, array(vec![text("..."), argument])))
+            }
+            ArrayExpressionElement::Expression(expr) => {
+                Some(print_expression_no_wrap(p, strip_parens(expr)))
+            }
+            ArrayExpressionElement::Elision(_) => None,
+        };
+        items.push(item.map(|doc| (doc, element.span())));
+    }
+    // Matrices of objects or arrays are expanded
+    let should_break = expr.elements.len() > 1
+        && (expr.elements.iter().all(|element| {
+            matches!(element, ArrayExpressionElement::Expression(Expression::ObjectExpression(object)) if object.properties.len() > 1)
+        }) || expr.elements.iter().all(|element| {
+            matches!(element, ArrayExpressionElement::Expression(Expression::ArrayExpression(array)) if array.elements.len() > 1)
+        }));
+    // Arrays of numbers are filled
+    let is_concise = expr.elements.len() > 1
+        && expr.elements.iter().all(|element| match element {
+            ArrayExpressionElement::Expression(Expression::NumberLiteral(_)) => true,
+            ArrayExpressionElement::Expression(Expression::UnaryExpression(unary)) => {
+                matches!(unary.argument, Expression::NumberLiteral(_))
+                    && matches!(unary.operator.as_str(), "+" | "-")
+            }
+            _ => false,
+        });
+    print_array_like(p, expr.span, items, true, should_break, is_concise)
+}
+
+/// Elements of arrays and array patterns, `None` being holes
+fn print_array_like<'a>(
+    p: &mut Prettier<'a>,
+    span: Span,
+    items: Vec<Option<(Doc<'a>, Span)>>,
+    can_have_trailing_comma: bool,
+    should_break: bool,
+    is_concise: bool,
+) -> Doc<'a> {
+    if items.is_empty() {
+        if !p.has_dangling_comments(span) {
+            return text("[]");
+        }
+        let dangling = p.print_dangling_comments(span, false);
+        return group(vec![text("["), dangling, softline(), text("]")]);
+    }
+    let group_id = p.next_group_id();
+    // A trailing hole needs a comma to be kept, `[,].length` is 1
+    let trailing_comma = if matches!(items.last(), Some(None)) {
+        text(",")
+    } else if can_have_trailing_comma && p.options.trailing_comma.is_es5() {
+        if is_concise {
+            if_break_with_id(text(","), text(""), group_id)
+        } else {
+            if_break(text(","), text(""))
+        }
+    } else {
+        text("")
+    };
+    let len = items.len();
+    let mut parts = vec![];
+    for (index, item) in items.into_iter().enumerate() {
+        let is_last = index + 1 == len;
+        let (doc, end) = match item {
+            Some((doc, span)) => (doc, Some(span.end)),
+            None => (text(""), None),
+        };
+        let is_next_line_empty = end.map_or(false, |end| p.is_next_line_empty(end));
+        if is_concise {
+            parts.push(array(vec![doc, if is_last { trailing_comma.clone() } else { text(",") }]));
+            if !is_last {
+                parts.push(if is_next_line_empty {
+                    array(vec![hardline(), hardline()])
+                } else {
+                    line()
+                });
+            }
+        } else {
+            parts.push(group(vec![doc]));
+            if !is_last {
+                parts.push(text(","));
+                parts.push(line());
+                if is_next_line_empty {
+                    parts.push(softline());
+                }
+            }
+        }
+    }
+    let items = if is_concise {
+        indent(vec![softline(), fill(parts)])
+    } else {
+        indent(vec![softline(), array(parts), trailing_comma])
+    };
+    Doc::Group {
+        contents: vec![text("["), items, softline(), text("]")],
+        should_break,
+        id: Some(group_id),
+    }
+}
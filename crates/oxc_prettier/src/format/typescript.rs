@@ -0,0 +1,770 @@
+//! TypeScript types and declarations
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/language-js/print/typescript.js)
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_span::{GetSpan, Span};
+
+use super::{
+    literal::{print_number, print_property_key, print_string, print_template_literal},
+    print_block, print_params, print_text_lines, print_verbatim, Format,
+};
+use crate::{
+    doc::{
+        array, group, group_with_break, hardline, if_break, indent, join, line, softline, text, Doc,
+    },
+    Prettier,
+};
+
+impl<'a> Format<'a> for TSType<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let doc = print_type(p, self);
+        p.with_comments(self.span(), doc)
+    }
+}
+
+pub(super) fn print_type_annotation<'a>(
+    p: &mut Prettier<'a>,
+    annotation: &TSTypeAnnotation<'_>,
+) -> Doc<'a> {
+    array(vec![text(": "), annotation.type_annotation.format(p)])
+}
+
+#[allow(clippy::too_many_lines)]
+fn print_type<'a>(p: &mut Prettier<'a>, ty: &TSType<'_>) -> Doc<'a> {
+    match ty {
+        TSType::TSAnyKeyword(_) => text("any"),
+        TSType::TSBigIntKeyword(_) => text("bigint"),
+        TSType::TSBooleanKeyword(_) => text("boolean"),
+        TSType::TSNeverKeyword(_) => text("never"),
+        TSType::TSNullKeyword(_) => text("null"),
+        TSType::TSNumberKeyword(_) => text("number"),
+        TSType::TSObjectKeyword(_) => text("object"),
+        TSType::TSStringKeyword(_) => text("string"),
+        TSType::TSSymbolKeyword(_) => text("symbol"),
+        TSType::TSThisKeyword(_) => text("this"),
+        TSType::TSUndefinedKeyword(_) => text("undefined"),
+        TSType::TSUnknownKeyword(_) => text("unknown"),
+        TSType::TSVoidKeyword(_) => text("void"),
+        TSType::TSArrayType(ty) => {
+            let element = print_wrapped(p, &ty.element_type, binds_looser_than_postfix);
+            array(vec![element, text("[]")])
+        }
+        TSType::TSConditionalType(ty) => {
+            let check = print_wrapped(p, &ty.check_type, |ty| {
+                is_function_like(ty) || matches!(ty, TSType::TSConditionalType(_))
+            });
+            let extends =
+                print_wrapped(p, &ty.extends_type, |ty| matches!(ty, TSType::TSConditionalType(_)));
+            let true_type = ty.true_type.format(p);
+            let false_type = ty.false_type.format(p);
+            group(vec![
+                check,
+                text(" extends "),
+                extends,
+                indent(vec![line(), text("? "), true_type, line(), text(": "), false_type]),
+            ])
+        }
+        TSType::TSConstructorType(ty) => {
+            let mut parts = vec![];
+            if ty.r#abstract {
+                parts.push(text("abstract "));
+            }
+            parts.push(text("new "));
+            parts.push(print_function_type(
+                p,
+                ty.type_parameters.as_deref(),
+                &ty.params,
+                &ty.return_type,
+            ));
+            array(parts)
+        }
+        TSType::TSFunctionType(ty) => {
+            print_function_type(p, ty.type_parameters.as_deref(), &ty.params, &ty.return_type)
+        }
+        TSType::TSImportType(ty) => {
+            let mut parts = vec![];
+            if ty.is_type_of {
+                parts.push(text("typeof "));
+            }
+            parts.push(text("import("));
+            parts.push(ty.parameter.format(p));
+            parts.push(text(")"));
+            if let Some(qualifier) = &ty.qualifier {
+                parts.push(text("."));
+                parts.push(print_type_name(p, qualifier));
+            }
+            if let Some(type_parameters) = &ty.type_parameters {
+                parts.push(print_type_arguments(p, type_parameters));
+            }
+            array(parts)
+        }
+        TSType::TSIndexedAccessType(ty) => {
+            let object = print_wrapped(p, &ty.object_type, binds_looser_than_postfix);
+            let index = ty.index_type.format(p);
+            array(vec![object, text("["), index, text("]")])
+        }
+        TSType::TSInferType(ty) => {
+            array(vec![text("infer "), print_type_parameter(p, &ty.type_parameter)])
+        }
+        TSType::TSIntersectionType(ty) => {
+            let types = ty
+                .types
+                .iter()
+                .map(|ty| {
+                    print_wrapped(p, ty, |ty| {
+                        is_function_like(ty)
+                            || matches!(ty, TSType::TSUnionType(_) | TSType::TSConditionalType(_))
+                    })
+                })
+                .collect();
+            group(vec![indent(join(&array(vec![text(" &"), line()]), types))])
+        }
+        TSType::TSLiteralType(ty) => print_literal_type(p, &ty.literal),
+        TSType::TSMappedType(ty) => print_mapped_type(p, ty),
+        TSType::TSQualifiedName(name) => print_qualified_name(p, name),
+        TSType::TSTemplateLiteralType(ty) => {
+            let mut parts = vec![text("`")];
+            for (index, quasi) in ty.quasis.iter().enumerate() {
+                parts.push(print_text_lines(p.source(quasi.span)));
+                if let Some(ty) = ty.types.get(index) {
+                    parts.extend([text("${"), ty.format(p), text("}")]);
+                }
+            }
+            parts.push(text("`"));
+            array(parts)
+        }
+        TSType::TSTupleType(ty) => print_tuple_type(p, ty),
+        TSType::TSTypeLiteral(ty) => {
+            let should_break = ty
+                .members
+                .first()
+                .map_or(false, |first| p.has_newline_between(ty.span.start, first.span().start));
+            print_signatures(p, ty.span, &ty.members, should_break)
+        }
+        TSType::TSTypeOperatorType(ty) => {
+            let operator = match ty.operator {
+                TSTypeOperator::Keyof => "keyof ",
+                TSTypeOperator::Unique => "unique ",
+                TSTypeOperator::Readonly => "readonly ",
+            };
+            let argument = print_wrapped(p, &ty.type_annotation, |ty| {
+                is_function_like(ty)
+                    || matches!(
+                        ty,
+                        TSType::TSUnionType(_)
+                            | TSType::TSIntersectionType(_)
+                            | TSType::TSConditionalType(_)
+                    )
+            });
+            array(vec![text(operator), argument])
+        }
+        TSType::TSTypePredicate(ty) => {
+            let mut parts = vec![];
+            if ty.asserts {
+                parts.push(text("asserts "));
+            }
+            parts.push(match &ty.parameter_name {
+                TSTypePredicateName::Identifier(ident) => text(p.source(ident.span)),
+                TSTypePredicateName::This(_) => text("this"),
+            });
+            if let Some(annotation) = &ty.type_annotation {
+                parts.push(text(" is "));
+                parts.push(annotation.type_annotation.format(p));
+            }
+            array(parts)
+        }
+        TSType::TSTypeQuery(ty) => {
+            let mut parts = vec![text("typeof "), print_type_name(p, &ty.expr_name)];
+            if let Some(type_parameters) = &ty.type_parameters {
+                parts.push(print_type_arguments(p, type_parameters));
+            }
+            array(parts)
+        }
+        TSType::TSTypeReference(ty) => {
+            let mut parts = vec![print_type_name(p, &ty.type_name)];
+            if let Some(type_parameters) = &ty.type_parameters {
+                parts.push(print_type_arguments(p, type_parameters));
+            }
+            array(parts)
+        }
+        TSType::TSUnionType(ty) => print_union_type(p, ty, softline()),
+        TSType::JSDocNullableType(_) | TSType::JSDocUnknownType(_) => print_verbatim(p, ty.span()),
+    }
+}
+
+/// `ty`, in parentheses if `needs_parens`, as the parser drops the parentheses around types
+fn print_wrapped<'a>(
+    p: &mut Prettier<'a>,
+    ty: &TSType<'_>,
+    needs_parens: impl Fn(&TSType<'_>) -> bool,
+) -> Doc<'a> {
+    let doc = ty.format(p);
+    if needs_parens(ty) {
+        array(vec![text("("), doc, text(")")])
+    } else {
+        doc
+    }
+}
+
+fn is_function_like(ty: &TSType<'_>) -> bool {
+    matches!(ty, TSType::TSFunctionType(_) | TSType::TSConstructorType(_))
+}
+
+/// Types which need parentheses before `[]` and `[K]`, e.g. `(A | B)[]` and `(keyof T)[K]`
+fn binds_looser_than_postfix(ty: &TSType<'_>) -> bool {
+    is_function_like(ty)
+        || matches!(
+            ty,
+            TSType::TSUnionType(_)
+                | TSType::TSIntersectionType(_)
+                | TSType::TSConditionalType(_)
+                | TSType::TSTypeOperatorType(_)
+                | TSType::TSInferType(_)
+        )
+}
+
+/// `A | B`, broken with each member on its own line after `| `, `leading` being the line
+/// before the first member when it breaks
+pub(super) fn print_union_type<'a>(
+    p: &mut Prettier<'a>,
+    ty: &TSUnionType<'_>,
+    leading: Doc<'a>,
+) -> Doc<'a> {
+    let types: Vec<Doc<'a>> = ty
+        .types
+        .iter()
+        .map(|ty| {
+            let doc = print_wrapped(p, ty, |ty| {
+                is_function_like(ty)
+                    || matches!(ty, TSType::TSUnionType(_) | TSType::TSConditionalType(_))
+                    || matches!(ty, TSType::TSInferType(infer) if infer.type_parameter.constraint.is_some())
+            });
+            p.with_comments(ty.span(), doc)
+        })
+        .collect();
+    if types.len() == 1 {
+        return array(types);
+    }
+    group(vec![indent(vec![
+        leading,
+        if_break(text("| "), text("")),
+        array(join(&array(vec![line(), text("| ")]), types)),
+    ])])
+}
+
+/// `<T>(a: A) => R` of function and constructor types
+fn print_function_type<'a>(
+    p: &mut Prettier<'a>,
+    type_parameters: Option<&TSTypeParameterDeclaration<'_>>,
+    params: &FormalParameters<'_>,
+    return_type: &TSTypeAnnotation<'_>,
+) -> Doc<'a> {
+    let mut parts = vec![];
+    if let Some(type_parameters) = type_parameters {
+        parts.push(print_type_parameters(p, type_parameters));
+    }
+    parts.push(group(vec![print_params(p, params)]));
+    parts.push(text(" => "));
+    parts.push(return_type.type_annotation.format(p));
+    group(parts)
+}
+
+fn print_literal_type<'a>(p: &mut Prettier<'a>, literal: &TSLiteral<'_>) -> Doc<'a> {
+    match literal {
+        TSLiteral::BooleanLiteral(literal) => text(if literal.value { "true" } else { "false" }),
+        TSLiteral::NullLiteral(_) => text("null"),
+        TSLiteral::NumberLiteral(literal) => text(print_number(p.source(literal.span))),
+        TSLiteral::BigintLiteral(literal) => text(p.source(literal.span).to_ascii_lowercase()),
+        TSLiteral::RegExpLiteral(literal) => text(p.source(literal.span)),
+        TSLiteral::StringLiteral(literal) => print_string(p, literal.span),
+        TSLiteral::TemplateLiteral(literal) => print_template_literal(p, literal),
+        TSLiteral::UnaryExpression(expr) => {
+            array(vec![text(expr.operator.as_str()), expr.argument.format(p)])
+        }
+    }
+}
+
+/// `{ readonly [K in T as N]?: U }`
+fn print_mapped_type<'a>(p: &mut Prettier<'a>, ty: &TSMappedType<'_>) -> Doc<'a> {
+    let mut parts = vec![];
+    parts.push(text(match ty.readonly {
+        TSMappedTypeModifierOperator::True => "readonly ",
+        TSMappedTypeModifierOperator::Plus => "+readonly ",
+        TSMappedTypeModifierOperator::Minus => "-readonly ",
+        TSMappedTypeModifierOperator::None => "",
+    }));
+    let parameter = &ty.type_parameter;
+    parts.push(text("["));
+    parts.push(text(p.source(parameter.name.span)));
+    if let Some(constraint) = &parameter.constraint {
+        parts.push(text(" in "));
+        parts.push(constraint.format(p));
+    }
+    if let Some(name_type) = &ty.name_type {
+        parts.push(text(" as "));
+        parts.push(name_type.format(p));
+    }
+    parts.push(text("]"));
+    parts.push(text(match ty.optional {
+        TSMappedTypeModifierOperator::True => "?",
+        TSMappedTypeModifierOperator::Plus => "+?",
+        TSMappedTypeModifierOperator::Minus => "-?",
+        TSMappedTypeModifierOperator::None => "",
+    }));
+    parts.push(text(": "));
+    parts.push(ty.type_annotation.format(p));
+    let should_break = p.has_newline_between(ty.span.start, parameter.span.start);
+    group_with_break(
+        vec![
+            text("{"),
+            indent(vec![line(), group(parts), if_break(text(";"), text(""))]),
+            line(),
+            text("}"),
+        ],
+        should_break,
+    )
+}
+
+fn print_tuple_type<'a>(p: &mut Prettier<'a>, ty: &TSTupleType<'_>) -> Doc<'a> {
+    if ty.element_types.is_empty() {
+        if p.has_dangling_comments(ty.span) {
+            let dangling = p.print_dangling_comments(ty.span, false);
+            return group(vec![text("["), dangling, softline(), text("]")]);
+        }
+        return text("[]");
+    }
+    let elements = ty
+        .element_types
+        .iter()
+        .map(|element| match element {
+            TSTupleElement::TSType(ty) => ty.format(p),
+            TSTupleElement::TSOptionalType(ty) => {
+                let doc = print_wrapped(p, &ty.type_annotation, binds_looser_than_postfix);
+                array(vec![doc, text("?")])
+            }
+            TSTupleElement::TSRestType(ty) => {
+                array(vec![text("..."), ty.type_annotation.format(p)])
+            }
+            TSTupleElement::TSNamedTupleMember(member) => {
+                let mut parts = vec![];
+                if member.rest {
+                    parts.push(text("..."));
+                }
+                parts.push(text(p.source(member.label.span)));
+                if member.optional {
+                    parts.push(text("?"));
+                }
+                parts.push(text(": "));
+                parts.push(member.element_type.format(p));
+                array(parts)
+            }
+        })
+        .collect();
+    let trailing_comma =
+        if p.options.trailing_comma.is_es5() { if_break(text(","), text("")) } else { text("") };
+    group(vec![
+        text("["),
+        indent(vec![softline(), array(join(&array(vec![text(","), line()]), elements))]),
+        trailing_comma,
+        softline(),
+        text("]"),
+    ])
+}
+
+pub(super) fn print_type_name<'a>(p: &mut Prettier<'a>, name: &TSTypeName<'_>) -> Doc<'a> {
+    match name {
+        TSTypeName::IdentifierName(ident) => text(p.source(ident.span)),
+        TSTypeName::QualifiedName(name) => print_qualified_name(p, name),
+    }
+}
+
+fn print_qualified_name<'a>(p: &mut Prettier<'a>, name: &TSQualifiedName<'_>) -> Doc<'a> {
+    array(vec![print_type_name(p, &name.left), text("."), text(p.source(name.right.span))])
+}
+
+/// `<T extends U = V>` of declarations
+pub(super) fn print_type_parameters<'a>(
+    p: &mut Prettier<'a>,
+    declaration: &TSTypeParameterDeclaration<'_>,
+) -> Doc<'a> {
+    let params = declaration.params.iter().map(|param| print_type_parameter(p, param)).collect();
+    print_type_list(p, params)
+}
+
+/// Same as [`print_type_parameters`], keeping the comma of `<T,>() => {}`, which would be
+/// parsed as a JSX element without it in TSX
+pub(super) fn print_arrow_type_parameters<'a>(
+    p: &mut Prettier<'a>,
+    declaration: &TSTypeParameterDeclaration<'_>,
+) -> Doc<'a> {
+    let source = p.source(declaration.span);
+    if let [param] = declaration.params.as_slice() {
+        if param.constraint.is_none() && source[..source.len() - 1].trim_end().ends_with(',') {
+            return array(vec![text("<"), print_type_parameter(p, param), text(",>")]);
+        }
+    }
+    print_type_parameters(p, declaration)
+}
+
+fn print_type_parameter<'a>(p: &mut Prettier<'a>, param: &TSTypeParameter<'_>) -> Doc<'a> {
+    let mut parts = vec![];
+    if param.r#const {
+        parts.push(text("const "));
+    }
+    if param.r#in {
+        parts.push(text("in "));
+    }
+    if param.out {
+        parts.push(text("out "));
+    }
+    parts.push(text(p.source(param.name.span)));
+    if let Some(constraint) = &param.constraint {
+        parts.push(text(" extends "));
+        parts.push(constraint.format(p));
+    }
+    if let Some(default) = &param.default {
+        parts.push(text(" = "));
+        parts.push(default.format(p));
+    }
+    p.with_comments(param.span, array(parts))
+}
+
+/// `<A, B>` of references, calls and `extends` clauses
+pub(super) fn print_type_arguments<'a>(
+    p: &mut Prettier<'a>,
+    instantiation: &TSTypeParameterInstantiation<'_>,
+) -> Doc<'a> {
+    let params: Vec<Doc<'a>> = instantiation.params.iter().map(|ty| ty.format(p)).collect();
+    // A single object type is hugged, e.g. `Foo<{ a: string }>`
+    if let [TSType::TSTypeLiteral(_) | TSType::TSMappedType(_)] = instantiation.params.as_slice() {
+        return array(vec![text("<"), array(params), text(">")]);
+    }
+    print_type_list(p, params)
+}
+
+fn print_type_list<'a>(p: &Prettier<'a>, params: Vec<Doc<'a>>) -> Doc<'a> {
+    let trailing_comma =
+        if p.options.trailing_comma.is_all() { if_break(text(","), text("")) } else { text("") };
+    group(vec![
+        text("<"),
+        indent(vec![softline(), array(join(&array(vec![text(","), line()]), params))]),
+        trailing_comma,
+        softline(),
+        text(">"),
+    ])
+}
+
+/// The members of interfaces and object types, separated by `;`
+fn print_signatures<'a>(
+    p: &mut Prettier<'a>,
+    span: Span,
+    members: &[TSSignature<'_>],
+    should_break: bool,
+) -> Doc<'a> {
+    if members.is_empty() {
+        if !p.has_dangling_comments(span) {
+            return text("{}");
+        }
+        let dangling = p.print_dangling_comments(span, false);
+        return group(vec![text("{"), dangling, softline(), text("}")]);
+    }
+    let mut parts = vec![];
+    for (index, member) in members.iter().enumerate() {
+        let doc = print_signature(p, member);
+        parts.push(p.with_comments(member.span(), doc));
+        if index + 1 < members.len() {
+            parts.push(text(";"));
+            parts.push(line());
+            if p.is_next_line_empty(member.span().end) {
+                parts.push(softline());
+            }
+        }
+    }
+    group_with_break(
+        vec![
+            text("{"),
+            indent(vec![line(), array(parts), if_break(text(";"), text(""))]),
+            line(),
+            text("}"),
+        ],
+        should_break,
+    )
+}
+
+fn print_signature<'a>(p: &mut Prettier<'a>, signature: &TSSignature<'_>) -> Doc<'a> {
+    match signature {
+        TSSignature::TSIndexSignature(signature) => print_index_signature(p, signature),
+        TSSignature::TSPropertySignature(signature) => {
+            let mut parts = vec![];
+            if signature.readonly {
+                parts.push(text("readonly "));
+            }
+            parts.push(print_property_key(p, &signature.key, signature.computed, true));
+            if signature.optional {
+                parts.push(text("?"));
+            }
+            if let Some(annotation) = &signature.type_annotation {
+                parts.push(print_type_annotation(p, annotation));
+            }
+            array(parts)
+        }
+        TSSignature::TSCallSignatureDeclaration(signature) => print_signature_parameters(
+            p,
+            signature.type_parameters.as_deref(),
+            &signature.params,
+            signature.return_type.as_deref(),
+        ),
+        TSSignature::TSConstructSignatureDeclaration(signature) => {
+            let signature = print_signature_parameters(
+                p,
+                signature.type_parameters.as_deref(),
+                &signature.params,
+                signature.return_type.as_deref(),
+            );
+            array(vec![text("new "), signature])
+        }
+        TSSignature::TSMethodSignature(signature) => {
+            let mut parts = vec![text(match signature.kind {
+                TSMethodSignatureKind::Method => "",
+                TSMethodSignatureKind::Get => "get ",
+                TSMethodSignatureKind::Set => "set ",
+            })];
+            parts.push(print_property_key(p, &signature.key, signature.computed, true));
+            if signature.optional {
+                parts.push(text("?"));
+            }
+            parts.push(print_signature_parameters(
+                p,
+                signature.type_parameters.as_deref(),
+                &signature.params,
+                signature.return_type.as_deref(),
+            ));
+            array(parts)
+        }
+    }
+}
+
+/// `<T>(a: A): R` of call, construct and method signatures
+fn print_signature_parameters<'a>(
+    p: &mut Prettier<'a>,
+    type_parameters: Option<&TSTypeParameterDeclaration<'_>>,
+    params: &FormalParameters<'_>,
+    return_type: Option<&TSTypeAnnotation<'_>>,
+) -> Doc<'a> {
+    let mut parts = vec![];
+    if let Some(type_parameters) = type_parameters {
+        parts.push(print_type_parameters(p, type_parameters));
+    }
+    parts.push(group(vec![print_params(p, params)]));
+    if let Some(return_type) = return_type {
+        parts.push(print_type_annotation(p, return_type));
+    }
+    group(parts)
+}
+
+/// `[key: string]: T`, with the modifiers before it which the AST does not keep,
+/// e.g. `readonly` and `static`
+pub(super) fn print_index_signature<'a>(
+    p: &mut Prettier<'a>,
+    signature: &TSIndexSignature<'_>,
+) -> Doc<'a> {
+    let mut parts = vec![];
+    let source = p.source(signature.span);
+    for modifier in source[..source.find('[').unwrap_or(0)].split_whitespace() {
+        parts.push(text(modifier));
+        parts.push(text(" "));
+    }
+    let parameters = signature
+        .parameters
+        .iter()
+        .map(|parameter| {
+            let name = p.source(parameter.span);
+            let name = name[..name.find(':').unwrap_or(name.len())].trim_end();
+            array(vec![text(name), print_type_annotation(p, &parameter.type_annotation)])
+        })
+        .collect();
+    parts.push(text("["));
+    parts.push(array(join(&text(", "), parameters)));
+    parts.push(text("]"));
+    parts.push(print_type_annotation(p, &signature.type_annotation));
+    array(parts)
+}
+
+impl<'a> Format<'a> for TSTypeAliasDeclaration<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let mut parts = vec![];
+        if self.modifiers.contains(ModifierKind::Declare) {
+            parts.push(text("declare "));
+        }
+        parts.push(text("type "));
+        parts.push(text(p.source(self.id.span)));
+        if let Some(type_parameters) = &self.type_parameters {
+            parts.push(print_type_parameters(p, type_parameters));
+        }
+        parts.push(text(" ="));
+        match &self.type_annotation {
+            TSType::TSUnionType(union) if union.types.len() > 1 => {
+                let doc = print_union_type(p, union, line());
+                parts.push(p.with_comments(union.span, doc));
+            }
+            ty => {
+                parts.push(text(" "));
+                parts.push(ty.format(p));
+            }
+        }
+        parts.push(text(";"));
+        group(parts)
+    }
+}
+
+impl<'a> Format<'a> for TSInterfaceDeclaration<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let mut parts = vec![];
+        if self.modifiers.contains(ModifierKind::Declare) {
+            parts.push(text("declare "));
+        }
+        parts.push(text("interface "));
+        parts.push(text(p.source(self.id.span)));
+        if let Some(type_parameters) = &self.type_parameters {
+            parts.push(print_type_parameters(p, type_parameters));
+        }
+        if let Some(extends) = &self.extends {
+            let extends = extends
+                .iter()
+                .map(|heritage| {
+                    let mut parts = vec![heritage.expression.format(p)];
+                    if let Some(type_parameters) = &heritage.type_parameters {
+                        parts.push(print_type_arguments(p, type_parameters));
+                    }
+                    array(parts)
+                })
+                .collect();
+            parts.push(text(" extends "));
+            parts.push(array(join(&text(", "), extends)));
+        }
+        parts.push(text(" "));
+        // Interfaces are always expanded
+        parts.push(print_signatures(p, self.body.span, &self.body.body, true));
+        array(parts)
+    }
+}
+
+impl<'a> Format<'a> for TSEnumDeclaration<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let mut parts = vec![];
+        if self.modifiers.contains(ModifierKind::Declare) {
+            parts.push(text("declare "));
+        }
+        if self.modifiers.contains(ModifierKind::Const) {
+            parts.push(text("const "));
+        }
+        parts.push(text("enum "));
+        parts.push(text(p.source(self.id.span)));
+        parts.push(text(" "));
+        let body = &self.body;
+        if body.members.is_empty() {
+            if p.has_dangling_comments(body.span) {
+                let dangling = p.print_dangling_comments(body.span, false);
+                parts.extend([text("{"), dangling, hardline(), text("}")]);
+            } else {
+                parts.push(text("{}"));
+            }
+            return array(parts);
+        }
+        let mut members = vec![];
+        for (index, member) in body.members.iter().enumerate() {
+            let mut doc = vec![match &member.id {
+                TSEnumMemberName::Identifier(ident) => text(p.source(ident.span)),
+                TSEnumMemberName::StringLiteral(literal) => print_string(p, literal.span),
+                TSEnumMemberName::ComputedPropertyName(expr) => {
+                    array(vec![text("["), expr.format(p), text("]")])
+                }
+                TSEnumMemberName::NumberLiteral(literal) => {
+                    text(print_number(p.source(literal.span)))
+                }
+            }];
+            if let Some(initializer) = &member.initializer {
+                doc.push(text(" = "));
+                doc.push(initializer.format(p));
+            }
+            members.push(p.with_comments(member.span, array(doc)));
+            if index + 1 < body.members.len() {
+                members.push(text(","));
+                members.push(hardline());
+                if p.is_next_line_empty(member.span.end) {
+                    members.push(hardline());
+                }
+            } else if p.options.trailing_comma.is_es5() {
+                members.push(text(","));
+            }
+        }
+        parts.extend([text("{"), indent(vec![hardline(), array(members)]), hardline(), text("}")]);
+        array(parts)
+    }
+}
+
+impl<'a> Format<'a> for TSModuleDeclaration<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let mut parts = vec![];
+        if self.modifiers.contains(ModifierKind::Declare) {
+            parts.push(text("declare "));
+        }
+        // `declare global {}` has no keyword
+        let head = p.source(Span::new(self.span.start, self.id.span().start));
+        if head.contains("namespace") {
+            parts.push(text("namespace "));
+        } else if head.contains("module") {
+            parts.push(text("module "));
+        }
+        parts.push(print_module_name(p, &self.id));
+        // `namespace A.B.C {}`
+        let mut body = &self.body;
+        while let TSModuleDeclarationBody::TSModuleDeclaration(inner) = body {
+            parts.push(text("."));
+            parts.push(print_module_name(p, &inner.id));
+            body = &inner.body;
+        }
+        if let TSModuleDeclarationBody::TSModuleBlock(block) = body {
+            // `declare module "a";` has no body
+            if p.source(block.span).starts_with('{') {
+                parts.push(text(" "));
+                parts.push(print_block(p, block.span, &[], &block.body));
+            } else {
+                parts.push(text(";"));
+            }
+        }
+        array(parts)
+    }
+}
+
+fn print_module_name<'a>(p: &Prettier<'a>, name: &TSModuleDeclarationName) -> Doc<'a> {
+    match name {
+        TSModuleDeclarationName::Identifier(ident) => text(p.source(ident.span)),
+        TSModuleDeclarationName::StringLiteral(literal) => print_string(p, literal.span),
+    }
+}
+
+impl<'a> Format<'a> for TSImportEqualsDeclaration<'_> {
+    fn format(&self, p: &mut Prettier<'a>) -> Doc<'a> {
+        let mut parts = vec![];
+        if self.is_export {
+            parts.push(text("export "));
+        }
+        parts.push(text("import "));
+        if self.import_kind.is_type() {
+            parts.push(text("type "));
+        }
+        parts.push(text(p.source(self.id.span)));
+        parts.push(text(" = "));
+        parts.push(match &*self.module_reference {
+            TSModuleReference::TypeName(name) => print_type_name(p, name),
+            TSModuleReference::ExternalModuleReference(reference) => {
+                array(vec![text("require("), print_string(p, reference.expression.span), text(")")])
+            }
+        });
+        parts.push(text(";"));
+        array(parts)
+    }
+}
@@ -0,0 +1,190 @@
+//! Oxc Prettier
+//!
+//! Formats JavaScript, TypeScript and JSX the same way as [Prettier](https://prettier.io):
+//! the AST and its attached comments are converted into a [`Doc`](doc::Doc) describing
+//! the possible layouts, which is printed by breaking the groups not fitting in the print width.
+//! TypeScript types are printed as written.
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/tree/main/src/language-js)
+
+mod comments;
+mod doc;
+mod format;
+mod printer;
+
+use std::collections::HashSet;
+
+use oxc_ast::{ast::Program, CommentAttachments, Trivias};
+use oxc_span::Span;
+
+pub use crate::format::Format;
+use crate::{
+    doc::{hardline, Doc, GroupId},
+    printer::Printer,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct PrettierOptions {
+    /// Width of the line which the printer will try to fit the code in
+    pub print_width: usize,
+    /// Number of spaces per indentation level, also the width of a tab
+    pub tab_width: usize,
+    pub use_tabs: bool,
+    /// Prefer single quotes over double quotes, except in JSX attributes
+    pub single_quote: bool,
+    pub trailing_comma: TrailingComma,
+}
+
+impl Default for PrettierOptions {
+    fn default() -> Self {
+        Self {
+            print_width: 80,
+            tab_width: 2,
+            use_tabs: false,
+            single_quote: false,
+            trailing_comma: TrailingComma::All,
+        }
+    }
+}
+
+/// Trailing commas in lists broken over multiple lines
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum TrailingComma {
+    /// Wherever possible, including function parameters and arguments
+    #[default]
+    All,
+    /// Where valid in ES5, i.e. arrays and objects
+    Es5,
+    None,
+}
+
+impl TrailingComma {
+    pub fn is_all(self) -> bool {
+        self == Self::All
+    }
+
+    pub fn is_es5(self) -> bool {
+        matches!(self, Self::All | Self::Es5)
+    }
+}
+
+pub struct Prettier<'a> {
+    source_text: &'a str,
+
+    trivias: &'a Trivias,
+
+    options: PrettierOptions,
+
+    comments: CommentAttachments,
+
+    /// Start of the comments already printed, a comment attached to a node may be printed by
+    /// an enclosing node with the same span, or as part of the source text printed as is
+    printed_comments: HashSet<u32>,
+
+    next_group_id: GroupId,
+}
+
+impl<'a> Prettier<'a> {
+    pub fn new(source_text: &'a str, trivias: &'a Trivias, options: PrettierOptions) -> Self {
+        Self {
+            source_text,
+            trivias,
+            options,
+            comments: CommentAttachments::default(),
+            printed_comments: HashSet::new(),
+            next_group_id: 0,
+        }
+    }
+
+    pub fn build<'p>(mut self, program: &'p Program<'p>) -> String {
+        self.comments = CommentAttachments::new(self.source_text, self.trivias, program);
+        let mut doc = program.format(&mut self);
+        if let Some(leftover) = self.print_leftover_comments() {
+            doc = Doc::Array(vec![doc, leftover, hardline()]);
+        }
+        doc::propagate_breaks(&mut doc);
+        Printer::new(&self.options).print(&doc)
+    }
+
+    /// Source text of `span`
+    fn source(&self, span: Span) -> &'a str {
+        span.source_text(self.source_text)
+    }
+
+    fn next_group_id(&mut self) -> GroupId {
+        self.next_group_id += 1;
+        self.next_group_id
+    }
+}
+
+/// Lookups of the whitespace in the source text around nodes and comments
+impl<'a> Prettier<'a> {
+    /// Whether there is a line break after `offset`, with only spaces and tabs before it
+    fn has_newline(&self, offset: u32) -> bool {
+        let rest = &self.source_text[offset as usize..];
+        rest.trim_start_matches([' ', '\t']).starts_with(['\n', '\r', '\u{2028}', '\u{2029}'])
+    }
+
+    /// Whether there is a line break before `offset`, with only spaces and tabs after it
+    fn has_newline_backwards(&self, offset: u32) -> bool {
+        let before = &self.source_text[..offset as usize];
+        before.trim_end_matches([' ', '\t']).ends_with(['\n', '\r', '\u{2028}', '\u{2029}'])
+    }
+
+    /// Whether there is a line break between `start` and `end`
+    fn has_newline_between(&self, start: u32, end: u32) -> bool {
+        self.source_text
+            .get(start as usize..end as usize)
+            .map_or(false, |text| text.contains(['\n', '\r', '\u{2028}', '\u{2029}']))
+    }
+
+    /// Whether the line after the line break at `offset` is empty
+    fn is_next_line_empty_after_newline(&self, offset: u32) -> bool {
+        let rest = self.source_text[offset as usize..].trim_start_matches([' ', '\t']);
+        let Some(rest) = strip_newline(rest) else { return false };
+        rest.trim_start_matches([' ', '\t']).starts_with(['\n', '\r'])
+    }
+
+    /// Whether the line before the line of `offset` is empty
+    fn is_previous_line_empty(&self, offset: u32) -> bool {
+        let before = self.source_text[..offset as usize].trim_end_matches([' ', '\t']);
+        let Some(before) = before.strip_suffix('\n').or_else(|| before.strip_suffix('\r')) else {
+            return false;
+        };
+        let before = before.strip_suffix('\r').unwrap_or(before);
+        before.trim_end_matches([' ', '\t']).ends_with(['\n', '\r'])
+    }
+
+    /// Whether there is an empty line after the node or comment ending at `offset`,
+    /// skipping the punctuators and comments on the rest of its line,
+    /// same as `isNextLineEmpty` of Prettier
+    fn is_next_line_empty(&self, offset: u32) -> bool {
+        let mut rest = &self.source_text[offset as usize..];
+        loop {
+            let before = rest.len();
+            rest = rest.trim_start_matches([',', ';', ' ', '\t']);
+            if let Some(comment) = rest.strip_prefix("/*") {
+                if let Some(end) = comment.find("*/") {
+                    if !comment[..end].contains('\n') {
+                        rest = &comment[end + 2..];
+                    }
+                }
+            }
+            if rest.len() == before {
+                break;
+            }
+        }
+        if rest.starts_with("//") {
+            rest = rest.find(['\n', '\r']).map_or("", |end| &rest[end..]);
+        }
+        let Some(rest) = strip_newline(rest) else { return false };
+        rest.trim_start_matches([' ', '\t']).starts_with(['\n', '\r'])
+    }
+}
+
+fn strip_newline(text: &str) -> Option<&str> {
+    text.strip_prefix("\r\n")
+        .or_else(|| text.strip_prefix('\n'))
+        .or_else(|| text.strip_prefix('\r'))
+        .or_else(|| text.strip_prefix('\u{2028}'))
+        .or_else(|| text.strip_prefix('\u{2029}'))
+}
@@ -0,0 +1,394 @@
+//! Prints a [`Doc`] into a string, fitting groups within the print width.
+//!
+//! Code adapted from [Prettier](https://github.com/prettier/prettier/blob/main/src/document/printer.js)
+
+use std::collections::HashMap;
+
+use crate::{
+    doc::{Doc, GroupId},
+    PrettierOptions,
+};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Mode {
+    Break,
+    Flat,
+}
+
+#[derive(Clone, Copy)]
+enum Item<'d, 'a> {
+    Doc(&'d Doc<'a>),
+    Docs(&'d [Doc<'a>]),
+    /// The remaining contents and separators of a fill
+    Fill(&'d [Doc<'a>]),
+}
+
+#[derive(Clone, Copy)]
+struct Command<'d, 'a> {
+    /// Indentation level
+    indent: usize,
+    mode: Mode,
+    item: Item<'d, 'a>,
+}
+
+impl<'d, 'a> Command<'d, 'a> {
+    fn new(indent: usize, mode: Mode, doc: &'d Doc<'a>) -> Self {
+        Self { indent, mode, item: Item::Doc(doc) }
+    }
+}
+
+pub struct Printer<'o> {
+    options: &'o PrettierOptions,
+    out: String,
+    /// Width of the current line
+    pos: usize,
+    /// Modes of the printed groups with an id
+    group_modes: HashMap<GroupId, Mode>,
+}
+
+impl<'o> Printer<'o> {
+    pub fn new(options: &'o PrettierOptions) -> Self {
+        Self { options, out: String::new(), pos: 0, group_modes: HashMap::new() }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    pub fn print(mut self, doc: &Doc<'_>) -> String {
+        let width = self.options.print_width;
+        let mut cmds = vec![Command::new(0, Mode::Break, doc)];
+        let mut line_suffixes: Vec<Command> = vec![];
+        // Set when a hard line is printed in a flat group, so that the groups after it are
+        // measured again from the new line instead of being printed flat
+        let mut should_remeasure = false;
+
+        while let Some(cmd) = cmds.pop() {
+            let Command { indent, mode, item } = cmd;
+            let doc = match item {
+                Item::Doc(doc) => doc,
+                Item::Docs(docs) => {
+                    cmds.extend(docs.iter().rev().map(|doc| Command::new(indent, mode, doc)));
+                    continue;
+                }
+                Item::Fill(parts) => {
+                    self.print_fill(cmd, parts, &mut cmds);
+                    continue;
+                }
+            };
+            match doc {
+                Doc::Str(s) => {
+                    self.out.push_str(s);
+                    self.pos += str_width(s);
+                }
+                Doc::Array(docs) | Doc::Indent(docs) => {
+                    let indent = if matches!(doc, Doc::Indent(_)) { indent + 1 } else { indent };
+                    cmds.extend(docs.iter().rev().map(|doc| Command::new(indent, mode, doc)));
+                }
+                Doc::Group { contents, should_break, id } => {
+                    let mode = if mode == Mode::Flat && !should_remeasure {
+                        if *should_break {
+                            Mode::Break
+                        } else {
+                            Mode::Flat
+                        }
+                    } else {
+                        should_remeasure = false;
+                        let rest = width.saturating_sub(self.pos);
+                        let next =
+                            vec![Command { indent, mode: Mode::Flat, item: Item::Docs(contents) }];
+                        if !should_break && fits(next, &cmds, rest, &self.group_modes, false) {
+                            Mode::Flat
+                        } else {
+                            Mode::Break
+                        }
+                    };
+                    if let Some(id) = id {
+                        self.group_modes.insert(*id, mode);
+                    }
+                    cmds.extend(contents.iter().rev().map(|doc| Command::new(indent, mode, doc)));
+                }
+                Doc::ConditionalGroup(states) => {
+                    if states.is_empty() {
+                        continue;
+                    }
+                    if mode == Mode::Flat && !should_remeasure {
+                        cmds.push(Command::new(indent, Mode::Flat, &states[0]));
+                        continue;
+                    }
+                    should_remeasure = false;
+                    let rest = width.saturating_sub(self.pos);
+                    let state = states.iter().find(|state| {
+                        let next = vec![Command::new(indent, Mode::Flat, state)];
+                        fits(next, &cmds, rest, &self.group_modes, false)
+                    });
+                    cmds.push(state.map_or_else(
+                        || Command::new(indent, Mode::Break, &states[states.len() - 1]),
+                        |state| Command::new(indent, Mode::Flat, state),
+                    ));
+                }
+                Doc::Fill(parts) => self.print_fill(cmd, parts, &mut cmds),
+                Doc::IfBreak { break_contents, flat_contents, group_id } => {
+                    let group_mode = group_mode(&self.group_modes, *group_id, mode);
+                    let contents =
+                        if group_mode == Mode::Break { break_contents } else { flat_contents };
+                    cmds.push(Command::new(indent, mode, contents));
+                }
+                Doc::LineSuffix(docs) => {
+                    line_suffixes.push(Command { indent, mode, item: Item::Docs(docs) });
+                }
+                Doc::BreakParent => {}
+                Doc::Line(line) => {
+                    if mode == Mode::Flat && !line.hard {
+                        if !line.soft {
+                            self.out.push(' ');
+                            self.pos += 1;
+                        }
+                        continue;
+                    }
+                    if mode == Mode::Flat {
+                        should_remeasure = true;
+                    }
+                    if !line_suffixes.is_empty() {
+                        cmds.push(cmd);
+                        cmds.extend(std::mem::take(&mut line_suffixes).into_iter().rev());
+                        continue;
+                    }
+                    // The whitespace before a literal line is part of a template literal or comment
+                    if line.literal {
+                        self.out.push('\n');
+                        self.pos = 0;
+                    } else {
+                        self.trim();
+                        self.out.push('\n');
+                        self.print_indent(indent);
+                    }
+                }
+            }
+
+            if cmds.is_empty() && !line_suffixes.is_empty() {
+                cmds.extend(std::mem::take(&mut line_suffixes).into_iter().rev());
+            }
+        }
+        self.out
+    }
+
+    /// Print the first content of a fill and the separator after it,
+    /// broken only if the content or the next content do not fit on the line
+    fn print_fill<'d, 'a>(
+        &self,
+        cmd: Command<'d, 'a>,
+        parts: &'d [Doc<'a>],
+        cmds: &mut Vec<Command<'d, 'a>>,
+    ) {
+        let Command { indent, mode, .. } = cmd;
+        let rest = self.options.print_width.saturating_sub(self.pos);
+        let Some(content) = parts.first() else { return };
+        let content_flat = Command::new(indent, Mode::Flat, content);
+        let content_break = Command::new(indent, Mode::Break, content);
+        let content_fits = fits(vec![content_flat], &[], rest, &self.group_modes, true);
+        let Some(separator) = parts.get(1) else {
+            cmds.push(if content_fits { content_flat } else { content_break });
+            return;
+        };
+        let separator_flat = Command::new(indent, Mode::Flat, separator);
+        let separator_break = Command::new(indent, Mode::Break, separator);
+        let Some(next_content) = parts.get(2) else {
+            if content_fits {
+                cmds.extend([separator_flat, content_flat]);
+            } else {
+                cmds.extend([separator_break, content_break]);
+            }
+            return;
+        };
+        let remaining = Command { indent, mode, item: Item::Fill(&parts[2..]) };
+        let next =
+            vec![Command::new(indent, Mode::Flat, next_content), separator_flat, content_flat];
+        if fits(next, &[], rest, &self.group_modes, true) {
+            cmds.extend([remaining, separator_flat, content_flat]);
+        } else if content_fits {
+            cmds.extend([remaining, separator_break, content_flat]);
+        } else {
+            cmds.extend([remaining, separator_break, content_break]);
+        }
+    }
+
+    fn print_indent(&mut self, indent: usize) {
+        if self.options.use_tabs {
+            self.out.extend(std::iter::repeat('\t').take(indent));
+        } else {
+            self.out.extend(std::iter::repeat(' ').take(indent * self.options.tab_width));
+        }
+        self.pos = indent * self.options.tab_width;
+    }
+
+    /// Remove the trailing whitespace of the current line
+    fn trim(&mut self) {
+        let len = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(len);
+    }
+}
+
+/// Whether the commands in `next` fit in the `width` remaining on the current line,
+/// up to the first line break, which may be in the `rest` commands after them.
+///
+/// The `rest` commands are measured in their own modes, so a line in a broken group ends the line.
+/// With `must_be_flat`, broken groups do not fit.
+fn fits<'d, 'a>(
+    mut next: Vec<Command<'d, 'a>>,
+    rest: &[Command<'d, 'a>],
+    width: usize,
+    group_modes: &HashMap<GroupId, Mode>,
+    must_be_flat: bool,
+) -> bool {
+    #[allow(clippy::cast_possible_wrap)]
+    let mut width = width as isize;
+    let mut rest_index = rest.len();
+    while width >= 0 {
+        let Some(Command { indent, mode, item }) = next.pop() else {
+            if rest_index == 0 {
+                return true;
+            }
+            rest_index -= 1;
+            next.push(rest[rest_index]);
+            continue;
+        };
+        let doc = match item {
+            Item::Doc(doc) => doc,
+            Item::Docs(docs) | Item::Fill(docs) => {
+                next.extend(docs.iter().rev().map(|doc| Command::new(indent, mode, doc)));
+                continue;
+            }
+        };
+        match doc {
+            #[allow(clippy::cast_possible_wrap)]
+            Doc::Str(s) => width -= str_width(s) as isize,
+            Doc::Array(docs) | Doc::Indent(docs) | Doc::Fill(docs) => {
+                next.extend(docs.iter().rev().map(|doc| Command::new(indent, mode, doc)));
+            }
+            Doc::Group { contents, should_break, .. } => {
+                if must_be_flat && *should_break {
+                    return false;
+                }
+                let mode = if *should_break { Mode::Break } else { mode };
+                next.extend(contents.iter().rev().map(|doc| Command::new(indent, mode, doc)));
+            }
+            Doc::ConditionalGroup(states) => {
+                let state = if mode == Mode::Break { states.last() } else { states.first() };
+                if let Some(state) = state {
+                    next.push(Command::new(indent, mode, state));
+                }
+            }
+            Doc::IfBreak { break_contents, flat_contents, group_id } => {
+                let group_mode = group_mode(group_modes, *group_id, mode);
+                let contents =
+                    if group_mode == Mode::Break { break_contents } else { flat_contents };
+                next.push(Command::new(indent, mode, contents));
+            }
+            Doc::Line(line) => {
+                if mode == Mode::Break || line.hard {
+                    return true;
+                }
+                if !line.soft {
+                    width -= 1;
+                }
+            }
+            Doc::LineSuffix(_) | Doc::BreakParent => {}
+        }
+    }
+    false
+}
+
+/// Mode of the group with `group_id`, which is flat if it is not printed yet,
+/// or `mode` of the enclosing group
+fn group_mode(group_modes: &HashMap<GroupId, Mode>, group_id: Option<GroupId>, mode: Mode) -> Mode {
+    group_id.map_or(mode, |id| group_modes.get(&id).copied().unwrap_or(Mode::Flat))
+}
+
+fn str_width(s: &str) -> usize {
+    s.chars().count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::Printer;
+    use crate::{doc::*, PrettierOptions};
+
+    fn print(doc: &Doc<'_>, print_width: usize) -> String {
+        let options = PrettierOptions { print_width, ..PrettierOptions::default() };
+        Printer::new(&options).print(doc)
+    }
+
+    #[test]
+    fn groups() {
+        let call = |args: Vec<&'static str>| {
+            let args = args.into_iter().map(text).collect();
+            group(vec![
+                text("f("),
+                indent(vec![softline(), array(join(&array(vec![text(","), line()]), args))]),
+                softline(),
+                text(")"),
+            ])
+        };
+        let doc = call(vec!["a", "b", "c"]);
+        assert_eq!(print(&doc, 80), "f(a, b, c)");
+        assert_eq!(print(&doc, 8), "f(\n  a,\n  b,\n  c\n)");
+
+        // An outer group breaks first
+        let doc = group(vec![text("x = "), call(vec!["aaaa", "bbbb"]), line(), text("y")]);
+        assert_eq!(print(&doc, 18), "x = f(aaaa, bbbb)\ny");
+
+        let mut doc = group(vec![text("a"), line(), text("b"), hardline(), text("c")]);
+        propagate_breaks(&mut doc);
+        assert_eq!(print(&doc, 80), "a\nb\nc");
+    }
+
+    #[test]
+    fn conditional_groups() {
+        let doc = conditional_group(vec![
+            text("aaaaaaaaaa"),
+            array(vec![text("bbbbb")]),
+            indent(vec![text("c"), hardline(), text("d")]),
+        ]);
+        assert_eq!(print(&doc, 80), "aaaaaaaaaa");
+        assert_eq!(print(&doc, 6), "bbbbb");
+        assert_eq!(print(&doc, 3), "c\n  d");
+    }
+
+    #[test]
+    fn fills() {
+        let words = ["aaa", "bbb", "ccc", "ddd"].into_iter().map(text).collect();
+        let doc = fill(join(&line(), words));
+        assert_eq!(print(&doc, 80), "aaa bbb ccc ddd");
+        assert_eq!(print(&doc, 8), "aaa bbb\nccc ddd");
+    }
+
+    #[test]
+    fn line_suffixes() {
+        let doc = array(vec![
+            text("a;"),
+            line_suffix(vec![text(" // c")]),
+            hardline(),
+            text("b;"),
+            line_suffix(vec![text(" // d")]),
+        ]);
+        assert_eq!(print(&doc, 80), "a; // c\nb; // d");
+    }
+
+    #[test]
+    fn indents_if_break() {
+        let doc = |id| {
+            group(vec![
+                text("a ="),
+                group_with_id(vec![indent(vec![line()])], id),
+                indent_if_break(vec![group(vec![text("b"), line(), text("c")])], id),
+            ])
+        };
+        assert_eq!(print(&doc(0), 80), "a = b c");
+        assert_eq!(print(&doc(0), 5), "a = b\nc");
+        assert_eq!(print(&doc(0), 3), "a =\n  b\n  c");
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        let doc = indent(vec![text("a"), hardline(), hardline(), text("b ")]);
+        assert_eq!(print(&doc, 80), "a\n\n  b ");
+    }
+}
@@ -0,0 +1,178 @@
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_prettier::{Prettier, PrettierOptions, TrailingComma};
+use oxc_span::SourceType;
+
+fn format(source_text: &str, source_type: SourceType, options: PrettierOptions) -> String {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    assert!(ret.errors.is_empty(), "failed to parse {source_text}");
+    let program = allocator.alloc(ret.program);
+    Prettier::new(source_text, &ret.trivias, options).build(program)
+}
+
+fn test(source_text: &str, expected: &str) {
+    test_options(source_text, expected, PrettierOptions::default());
+}
+
+fn test_options(source_text: &str, expected: &str, options: PrettierOptions) {
+    let printed = format(source_text, SourceType::default(), options);
+    assert_eq!(expected, printed, "for source {source_text}");
+    // Formatting is idempotent
+    let reprinted = format(&printed, SourceType::default(), options);
+    assert_eq!(printed, reprinted, "for formatted source {printed}");
+}
+
+fn test_jsx(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_jsx(true);
+    let printed = format(source_text, source_type, PrettierOptions::default());
+    assert_eq!(expected, printed, "for source {source_text}");
+}
+
+fn test_ts(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_typescript(true);
+    let printed = format(source_text, source_type, PrettierOptions::default());
+    assert_eq!(expected, printed, "for source {source_text}");
+    let reprinted = format(&printed, source_type, PrettierOptions::default());
+    assert_eq!(printed, reprinted, "for formatted source {printed}");
+}
+
+#[test]
+fn statements() {
+    test("let a = 1\nif (a) b()\nelse { c() }", "let a = 1;\nif (a) b();\nelse {\n  c();\n}\n");
+    test("for (;;) {}", "for (;;) {}\n");
+    test("function foo ( a , b ) { return a+b }", "function foo(a, b) {\n  return a + b;\n}\n");
+    test("a\n\n\n\nb", "a;\n\nb;\n");
+    test("", "");
+}
+
+#[test]
+fn quotes() {
+    test("'a'", "\"a\";\n");
+    test("'a\"b'", "'a\"b';\n");
+    test("x = \"\\d\"", "x = \"d\";\n");
+    test_options(
+        "\"a\"",
+        "'a';\n",
+        PrettierOptions { single_quote: true, ..PrettierOptions::default() },
+    );
+    test("x = { 'a': 1, 'b-c': 2 }", "x = { a: 1, \"b-c\": 2 };\n");
+}
+
+#[test]
+fn numbers() {
+    test("x = 0XAB + 1.50E+10 + .5", "x = 0xab + 1.5e10 + 0.5;\n");
+}
+
+#[test]
+fn wrapping() {
+    test(
+        "const value = someFunction(argumentNumberOne, argumentNumberTwo, argumentNumberThree);",
+        "const value = someFunction(\n  argumentNumberOne,\n  argumentNumberTwo,\n  argumentNumberThree,\n);\n",
+    );
+    test(
+        "const result = firstCondition && secondCondition || thirdCondition && fourthCondition;",
+        "const result =\n  (firstCondition && secondCondition) || (thirdCondition && fourthCondition);\n",
+    );
+    test("foo(function () { return 1 })", "foo(function () {\n  return 1;\n});\n");
+    test("useEffect(() => { a() }, [b])", "useEffect(() => {\n  a();\n}, [b]);\n");
+}
+
+#[test]
+fn trailing_commas() {
+    let source_text =
+        "const object = { propertyNumberOne: 1, propertyNumberTwo: 2, propertyNumberThree: 3 };";
+    test(
+        source_text,
+        "const object = {\n  propertyNumberOne: 1,\n  propertyNumberTwo: 2,\n  propertyNumberThree: 3,\n};\n",
+    );
+    test_options(
+        source_text,
+        "const object = {\n  propertyNumberOne: 1,\n  propertyNumberTwo: 2,\n  propertyNumberThree: 3\n};\n",
+        PrettierOptions { trailing_comma: TrailingComma::None, ..PrettierOptions::default() },
+    );
+    test_options(
+        "function foo(parameterNumberOne, parameterNumberTwo, parameterNumberThree, four) {}",
+        "function foo(\n  parameterNumberOne,\n  parameterNumberTwo,\n  parameterNumberThree,\n  four\n) {}\n",
+        PrettierOptions { trailing_comma: TrailingComma::Es5, ..PrettierOptions::default() },
+    );
+}
+
+#[test]
+fn objects() {
+    test("x = {}", "x = {};\n");
+    // Objects broken in the source after `{` stay broken
+    test("x = {\n  a: 1 }", "x = {\n  a: 1,\n};\n");
+    test("x = { a, b: [1, 2] }", "x = { a, b: [1, 2] };\n");
+}
+
+#[test]
+fn comments() {
+    test("// a\nlet a = 1; // b\n/* c */", "// a\nlet a = 1; // b\n/* c */\n");
+    test("function foo() {\n  // a\n}", "function foo() {\n  // a\n}\n");
+}
+
+#[test]
+fn indentation() {
+    test_options(
+        "if (a) { b }",
+        "if (a) {\n\tb;\n}\n",
+        PrettierOptions { use_tabs: true, ..PrettierOptions::default() },
+    );
+    test_options(
+        "if (a) { b }",
+        "if (a) {\n    b;\n}\n",
+        PrettierOptions { tab_width: 4, ..PrettierOptions::default() },
+    );
+}
+
+#[test]
+fn jsx() {
+    test_jsx("<div className='a'>text</div>", "<div className=\"a\">text</div>;\n");
+    test_jsx("<br/>", "<br />;\n");
+    test_jsx(
+        "const a = <div><span>a</span></div>",
+        "const a = (\n  <div>\n    <span>a</span>\n  </div>\n);\n",
+    );
+    test_jsx("<a>{b}</a>", "<a>{b}</a>;\n");
+}
+
+#[test]
+fn typescript() {
+    test_ts("let a:Array<string|number>=[]", "let a: Array<string | number> = [];\n");
+    test_ts("type A=(B|C)[]", "type A = (B | C)[];\n");
+    test_ts(
+        "type A<T>=T extends string?'s':never",
+        "type A<T> = T extends string ? \"s\" : never;\n",
+    );
+    test_ts(
+        "type A = 'aaaaaaaaaaaaaaaaaaaa' | 'bbbbbbbbbbbbbbbbbbbbbbbb' | 'cccccccccccccccccccccccccc'",
+        "type A =\n  | \"aaaaaaaaaaaaaaaaaaaa\"\n  | \"bbbbbbbbbbbbbbbbbbbbbbbb\"\n  | \"cccccccccccccccccccccccccc\";\n",
+    );
+    test_ts(
+        "interface A<T> extends B { readonly a?: T, b(x: number): void }",
+        "interface A<T> extends B {\n  readonly a?: T;\n  b(x: number): void;\n}\n",
+    );
+    test_ts("const enum E { A = 1, B }", "const enum E {\n  A = 1,\n  B,\n}\n");
+    test_ts("namespace A.B { export const c = 1 }", "namespace A.B {\n  export const c = 1;\n}\n");
+    test_ts("declare module 'a';", "declare module \"a\";\n");
+    test_ts("declare function f<T,>(a:T):T", "declare function f<T>(a: T): T;\n");
+    test_ts("let f = <T,>(a:T) => a", "let f = <T,>(a: T) => a;\n");
+    test_ts(
+        "abstract class A<T> extends B<T> implements C<T> { abstract a:number; protected abstract b():void; [key:string]:any }",
+        "abstract class A<T> extends B<T> implements C<T> {\n  abstract a: number;\n  protected abstract b(): void;\n  [key: string]: any;\n}\n",
+    );
+    test_ts(
+        "let a = b as unknown as {x:[number,string?]}",
+        "let a = b as unknown as { x: [number, string?] };\n",
+    );
+    test_ts(
+        "type M = {readonly [K in keyof T]?: T[K]}",
+        "type M = { readonly [K in keyof T]?: T[K] };\n",
+    );
+    test_ts("let f: (a: string) => void = g<number>", "let f: (a: string) => void = g<number>;\n");
+    test_ts(
+        "import fs = require('fs'); export = fs;",
+        "import fs = require(\"fs\");\nexport = fs;\n",
+    );
+}
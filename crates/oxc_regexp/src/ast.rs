@@ -0,0 +1,205 @@
+//! Regular expression pattern AST
+//!
+//! Spans are byte offsets into the pattern, moved by [crate::PatternParser::with_span_offset].
+//! Without the `u` or `v` flag, a pattern is a sequence of UTF-16 code units,
+//! so a character outside the Basic Multilingual Plane is two [Character]s with the same span.
+
+use oxc_span::{GetSpan, Span};
+
+/// The whole pattern, e.g. `a|b` of `/a|b/`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub span: Span,
+    pub alternatives: Vec<Alternative>,
+}
+
+/// One of the alternatives separated by `|`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternative {
+    pub span: Span,
+    pub elements: Vec<Element>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Element {
+    Assertion(Box<Assertion>),
+    Quantifier(Box<Quantifier>),
+    Group(Box<Group>),
+    CapturingGroup(Box<CapturingGroup>),
+    CharacterClass(Box<CharacterClass>),
+    CharacterSet(CharacterSet),
+    Character(Character),
+    Backreference(Backreference),
+}
+
+impl GetSpan for Element {
+    fn span(&self) -> Span {
+        match self {
+            Self::Assertion(e) => e.span,
+            Self::Quantifier(e) => e.span,
+            Self::Group(e) => e.span,
+            Self::CapturingGroup(e) => e.span,
+            Self::CharacterClass(e) => e.span,
+            Self::CharacterSet(e) => e.span,
+            Self::Character(e) => e.span,
+            Self::Backreference(e) => e.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssertionKind {
+    /// `^`
+    Start,
+    /// `$`
+    End,
+    /// `\b`
+    WordBoundary,
+    /// `\B`
+    NotWordBoundary,
+    /// `(?=...)`
+    Lookahead,
+    /// `(?!...)`
+    NegativeLookahead,
+    /// `(?<=...)`
+    Lookbehind,
+    /// `(?<!...)`
+    NegativeLookbehind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assertion {
+    pub span: Span,
+    pub kind: AssertionKind,
+    /// The alternatives of a lookaround, empty for the other assertions
+    pub alternatives: Vec<Alternative>,
+}
+
+/// `a*`, `a+?`, `a{2,}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quantifier {
+    pub span: Span,
+    pub min: u32,
+    /// `None` for no upper bound
+    pub max: Option<u32>,
+    /// `false` for the lazy `a*?`
+    pub greedy: bool,
+    pub element: Element,
+}
+
+/// `(?:...)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub span: Span,
+    pub alternatives: Vec<Alternative>,
+}
+
+/// `(...)` and `(?<name>...)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturingGroup {
+    pub span: Span,
+    /// Starts from 1, in the order of the opening parentheses
+    pub index: u32,
+    pub name: Option<String>,
+    pub alternatives: Vec<Alternative>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharacterClassKind {
+    /// `[ab]`
+    Union,
+    /// `[\w&&\d]`, only with the `v` flag
+    Intersection,
+    /// `[\w--\d]`, only with the `v` flag
+    Subtraction,
+}
+
+/// `[...]` and `[^...]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterClass {
+    pub span: Span,
+    pub negate: bool,
+    pub kind: CharacterClassKind,
+    pub elements: Vec<ClassElement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassElement {
+    Character(Character),
+    CharacterSet(CharacterSet),
+    Range(CharacterRange),
+    /// A nested class, only with the `v` flag
+    Class(Box<CharacterClass>),
+    /// `\q{abc|def}`, only with the `v` flag
+    Strings(ClassStrings),
+}
+
+impl GetSpan for ClassElement {
+    fn span(&self) -> Span {
+        match self {
+            Self::Character(e) => e.span,
+            Self::CharacterSet(e) => e.span,
+            Self::Range(e) => e.span,
+            Self::Class(e) => e.span,
+            Self::Strings(e) => e.span,
+        }
+    }
+}
+
+/// `a-z` in a character class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterRange {
+    pub span: Span,
+    pub min: Character,
+    pub max: Character,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassStrings {
+    pub span: Span,
+    pub strings: Vec<Vec<Character>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CharacterSetKind {
+    /// `.`
+    Any,
+    /// `\d` and `\D`
+    Digit,
+    /// `\s` and `\S`
+    Space,
+    /// `\w` and `\W`
+    Word,
+    /// `\p{Letter}` and `\P{Script=Greek}`, only with the `u` or `v` flag
+    Property { name: String, value: Option<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterSet {
+    pub span: Span,
+    pub kind: CharacterSetKind,
+    /// `\D`, `\S`, `\W` and `\P{...}`
+    pub negate: bool,
+}
+
+/// A literal or escaped character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Character {
+    pub span: Span,
+    /// Code point, or UTF-16 code unit without the `u` or `v` flag
+    pub value: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    /// `\1`
+    Index(u32),
+    /// `\k<name>`
+    Name(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backreference {
+    pub span: Span,
+    pub reference: Reference,
+}
@@ -0,0 +1,24 @@
+//! # Oxc RegExp
+//!
+//! Parser and validator for regular expression patterns and flags,
+//! following [ECMAScript Regular Expressions](https://tc39.es/ecma262/#sec-regexp-regular-expression-objects)
+//! including the web compatibility grammar of [Annex B](https://tc39.es/ecma262/#sec-regular-expressions-patterns)
+//! for patterns without the `u` or `v` flag.
+//!
+//! The AST is modeled after [regexpp](https://github.com/eslint-community/regexpp),
+//! which is used by the ESLint rules inspecting regular expressions.
+//!
+//! ```rust
+//! let flags = parse_flags("u")?;
+//! let pattern = PatternParser::new("(?<year>\\d{4})-\\k<year>", flags).parse()?;
+//! ```
+
+pub mod ast;
+mod diagnostics;
+mod parser;
+pub mod visit;
+
+pub use crate::{
+    diagnostics::{InvalidFlags, InvalidPattern},
+    parser::{parse_flags, PatternParser},
+};
@@ -0,0 +1,1342 @@
+//! Recursive Descent Parser for [Regular Expression Patterns](https://tc39.es/ecma262/#sec-patterns)
+
+use oxc_ast::ast::RegExpFlags;
+use oxc_span::Span;
+use oxc_syntax::identifier::{is_identifier_part, is_identifier_start_all};
+
+use crate::{
+    ast::{
+        Alternative, Assertion, AssertionKind, Backreference, CapturingGroup, Character,
+        CharacterClass, CharacterClassKind, CharacterRange, CharacterSet, CharacterSetKind,
+        ClassElement, ClassStrings, Element, Group, Pattern, Quantifier, Reference,
+    },
+    diagnostics::{InvalidFlags, InvalidPattern},
+};
+
+type Result<T> = std::result::Result<T, InvalidPattern>;
+
+/// Parse the flags of a regular expression, e.g. `gu` of `/a/gu`.
+///
+/// # Errors
+///
+/// * an unknown flag
+/// * a duplicate flag
+/// * both the `u` and `v` flags
+pub fn parse_flags(flags: &str) -> std::result::Result<RegExpFlags, InvalidFlags> {
+    let mut result = RegExpFlags::empty();
+    for (i, c) in flags.char_indices() {
+        #[allow(clippy::cast_possible_truncation)]
+        let span = Span::new(i as u32, (i + c.len_utf8()) as u32);
+        let flag = RegExpFlags::try_from(c).map_err(|_| InvalidFlags("Unknown flag", span))?;
+        if result.contains(flag) {
+            return Err(InvalidFlags("Duplicate flag", span));
+        }
+        result |= flag;
+    }
+    if result.contains(RegExpFlags::U | RegExpFlags::V) {
+        #[allow(clippy::cast_possible_truncation)]
+        let span = Span::new(0, flags.len() as u32);
+        return Err(InvalidFlags("The `u` and `v` flags cannot be used together", span));
+    }
+    Ok(result)
+}
+
+pub struct PatternParser<'a> {
+    source_text: &'a str,
+    flags: RegExpFlags,
+    span_offset: u32,
+}
+
+impl<'a> PatternParser<'a> {
+    /// `source_text` is the pattern between the slashes of a literal,
+    /// or the value of the string passed to the `RegExp` constructor.
+    pub fn new(source_text: &'a str, flags: RegExpFlags) -> Self {
+        Self { source_text, flags, span_offset: 0 }
+    }
+
+    /// Offset added to all spans, e.g. the start of the pattern in the source text
+    #[must_use]
+    pub fn with_span_offset(mut self, offset: u32) -> Self {
+        self.span_offset = offset;
+        self
+    }
+
+    /// # Errors
+    ///
+    /// The first syntax error of the pattern, with a message in the wording of V8
+    pub fn parse(self) -> Result<Pattern> {
+        ParserImpl::new(self.source_text, self.flags, self.span_offset).parse()
+    }
+}
+
+/// A code point, or a UTF-16 code unit without the `u` or `v` flag
+#[derive(Debug, Clone, Copy)]
+struct Unit {
+    value: u32,
+    start: u32,
+    end: u32,
+}
+
+struct ParserImpl {
+    units: Vec<Unit>,
+
+    pos: usize,
+
+    span_offset: u32,
+
+    /// `u` or `v` flag
+    unicode: bool,
+
+    /// `v` flag
+    unicode_sets: bool,
+
+    /// `\k` is a named reference with the `u` or `v` flag, or when the pattern has a group name
+    named_groups: bool,
+
+    /// Total number of capturing groups, for telling backreferences from legacy octal escapes
+    group_count: u32,
+
+    /// All group names, for checking named references before their groups
+    group_names: Vec<String>,
+
+    /// Index of the last capturing group parsed
+    group_index: u32,
+
+    /// Names of the groups parsed so far
+    parsed_group_names: Vec<String>,
+}
+
+impl ParserImpl {
+    fn new(source_text: &str, flags: RegExpFlags, span_offset: u32) -> Self {
+        let unicode = flags.intersects(RegExpFlags::U | RegExpFlags::V);
+        let mut units = vec![];
+        for (i, c) in source_text.char_indices() {
+            #[allow(clippy::cast_possible_truncation)]
+            let (start, end) = (i as u32, (i + c.len_utf8()) as u32);
+            if unicode {
+                units.push(Unit { value: u32::from(c), start, end });
+            } else {
+                let mut buf = [0; 2];
+                for value in c.encode_utf16(&mut buf) {
+                    units.push(Unit { value: u32::from(*value), start, end });
+                }
+            }
+        }
+        let mut parser = Self {
+            units,
+            pos: 0,
+            span_offset,
+            unicode,
+            unicode_sets: flags.contains(RegExpFlags::V),
+            named_groups: false,
+            group_count: 0,
+            group_names: vec![],
+            group_index: 0,
+            parsed_group_names: vec![],
+        };
+        parser.count_groups();
+        parser.named_groups = unicode || !parser.group_names.is_empty();
+        parser
+    }
+
+    fn parse(mut self) -> Result<Pattern> {
+        let start = self.offset();
+        let alternatives = self.parse_disjunction()?;
+        if !self.at_end() {
+            // `parse_disjunction` only stops before `)`
+            let start = self.offset();
+            self.pos += 1;
+            return Err(self.error("Unmatched ')'", start));
+        }
+        Ok(Pattern { span: self.span_from(start), alternatives })
+    }
+
+    /// Collect the number of capturing groups and their names before parsing,
+    /// since backreferences may come before the groups they refer to.
+    fn count_groups(&mut self) {
+        let mut class_depth = 0;
+        while !self.at_end() {
+            match self.peek_char() {
+                Some('\\') => self.pos += 1,
+                Some('[') if class_depth == 0 || self.unicode_sets => class_depth += 1,
+                Some(']') if class_depth > 0 => class_depth -= 1,
+                Some('(') if class_depth == 0 => {
+                    if !self.at_str(1, "?") {
+                        self.group_count += 1;
+                    } else if self.at_str(1, "?<")
+                        && !self.at_str(1, "?<=")
+                        && !self.at_str(1, "?<!")
+                    {
+                        self.group_count += 1;
+                        self.pos += 3;
+                        let start = self.offset();
+                        if let Ok(name) = self.parse_group_name(start, "") {
+                            self.group_names.push(name);
+                        }
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+        self.pos = 0;
+    }
+
+    /// Disjunction :: Alternative | Alternative `|` Disjunction
+    fn parse_disjunction(&mut self) -> Result<Vec<Alternative>> {
+        let mut alternatives = vec![self.parse_alternative()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_alternative()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn parse_alternative(&mut self) -> Result<Alternative> {
+        let start = self.offset();
+        let mut elements = vec![];
+        while !self.at_end() && !self.at('|') && !self.at(')') {
+            elements.push(self.parse_term()?);
+        }
+        Ok(Alternative { span: self.span_from(start), elements })
+    }
+
+    /// Term :: Assertion | Atom Quantifier?
+    fn parse_term(&mut self) -> Result<Element> {
+        let start = self.offset();
+        let assertion = if self.eat('^') {
+            Some(AssertionKind::Start)
+        } else if self.eat('$') {
+            Some(AssertionKind::End)
+        } else if self.eat_str("\\b") {
+            Some(AssertionKind::WordBoundary)
+        } else if self.eat_str("\\B") {
+            Some(AssertionKind::NotWordBoundary)
+        } else {
+            None
+        };
+        if let Some(kind) = assertion {
+            let element = self.assertion(start, kind, vec![]);
+            return self.parse_quantifier(start, element, false);
+        }
+
+        let lookaround = if self.eat_str("(?=") {
+            Some(AssertionKind::Lookahead)
+        } else if self.eat_str("(?!") {
+            Some(AssertionKind::NegativeLookahead)
+        } else if self.eat_str("(?<=") {
+            Some(AssertionKind::Lookbehind)
+        } else if self.eat_str("(?<!") {
+            Some(AssertionKind::NegativeLookbehind)
+        } else {
+            None
+        };
+        if let Some(kind) = lookaround {
+            let alternatives = self.parse_disjunction()?;
+            self.expect_group_end(start)?;
+            let element = self.assertion(start, kind, alternatives);
+            // Annex B: QuantifiableAssertion
+            let quantifiable = !self.unicode
+                && matches!(kind, AssertionKind::Lookahead | AssertionKind::NegativeLookahead);
+            return self.parse_quantifier(start, element, quantifiable);
+        }
+
+        let element = self.parse_atom()?;
+        self.parse_quantifier(start, element, true)
+    }
+
+    fn assertion(
+        &self,
+        start: u32,
+        kind: AssertionKind,
+        alternatives: Vec<Alternative>,
+    ) -> Element {
+        Element::Assertion(Box::new(Assertion { span: self.span_from(start), kind, alternatives }))
+    }
+
+    /// Quantifier :: `*` | `+` | `?` | `{` DecimalDigits `}` ... followed by an optional `?`
+    fn parse_quantifier(
+        &mut self,
+        start: u32,
+        element: Element,
+        quantifiable: bool,
+    ) -> Result<Element> {
+        let quantifier_start = self.offset();
+        let (min, max) = if self.eat('*') {
+            (0, None)
+        } else if self.eat('+') {
+            (1, None)
+        } else if self.eat('?') {
+            (0, Some(1))
+        } else if let Some(bounds) = self.eat_braced_quantifier()? {
+            bounds
+        } else {
+            return Ok(element);
+        };
+        if !quantifiable {
+            return Err(self.error("Nothing to repeat", quantifier_start));
+        }
+        let greedy = !self.eat('?');
+        let span = self.span_from(start);
+        Ok(Element::Quantifier(Box::new(Quantifier { span, min, max, greedy, element })))
+    }
+
+    /// `{n}`, `{n,}` and `{n,m}`, which are literal characters when incomplete without the `u` flag
+    fn eat_braced_quantifier(&mut self) -> Result<Option<(u32, Option<u32>)>> {
+        let checkpoint = self.pos;
+        let start = self.offset();
+        if !self.eat('{') {
+            return Ok(None);
+        }
+        if let Some(min) = self.eat_decimal_digits() {
+            let max = if self.eat(',') { self.eat_decimal_digits() } else { Some(min) };
+            if self.eat('}') {
+                if matches!(max, Some(max) if max < min) {
+                    return Err(self.error("numbers out of order in {} quantifier", start));
+                }
+                return Ok(Some((min, max)));
+            }
+        }
+        if self.unicode {
+            return Err(self.error("Incomplete quantifier", start));
+        }
+        self.pos = checkpoint;
+        Ok(None)
+    }
+
+    /// Atom :: PatternCharacter | `.` | `\` AtomEscape | CharacterClass | `(` GroupSpecifier? Disjunction `)`
+    ///   | `(?:` Disjunction `)`
+    fn parse_atom(&mut self) -> Result<Element> {
+        let start = self.offset();
+        match self.peek_char() {
+            Some('.') => {
+                self.pos += 1;
+                let span = self.span_from(start);
+                Ok(Element::CharacterSet(CharacterSet {
+                    span,
+                    kind: CharacterSetKind::Any,
+                    negate: false,
+                }))
+            }
+            Some('(') => self.parse_group(),
+            Some('[') => Ok(Element::CharacterClass(Box::new(self.parse_class()?))),
+            Some('\\') => self.parse_atom_escape(),
+            Some('*' | '+' | '?') => {
+                self.pos += 1;
+                Err(self.error("Nothing to repeat", start))
+            }
+            Some('{' | '}' | ']') if self.unicode => {
+                self.pos += 1;
+                Err(self.error("Lone quantifier brackets", start))
+            }
+            Some('{') if self.eat_braced_quantifier()?.is_some() => {
+                Err(self.error("Nothing to repeat", start))
+            }
+            // Annex B: ExtendedPatternCharacter allows `]`, `{` and `}`
+            _ => Ok(Element::Character(self.eat_character())),
+        }
+    }
+
+    fn parse_group(&mut self) -> Result<Element> {
+        let start = self.offset();
+        self.pos += 1;
+        if self.eat_str("?:") {
+            let alternatives = self.parse_disjunction()?;
+            self.expect_group_end(start)?;
+            let span = self.span_from(start);
+            return Ok(Element::Group(Box::new(Group { span, alternatives })));
+        }
+        let name = if self.eat_str("?<") {
+            let name = self.parse_group_name(start, "Invalid capture group name")?;
+            if self.parsed_group_names.contains(&name) {
+                return Err(self.error("Duplicate capture group name", start));
+            }
+            self.parsed_group_names.push(name.clone());
+            Some(name)
+        } else if self.eat('?') {
+            return Err(self.error("Invalid group", start));
+        } else {
+            None
+        };
+        self.group_index += 1;
+        let index = self.group_index;
+        let alternatives = self.parse_disjunction()?;
+        self.expect_group_end(start)?;
+        let span = self.span_from(start);
+        Ok(Element::CapturingGroup(Box::new(CapturingGroup { span, index, name, alternatives })))
+    }
+
+    fn expect_group_end(&mut self, start: u32) -> Result<()> {
+        if self.eat(')') {
+            Ok(())
+        } else {
+            Err(self.error("Unterminated group", start))
+        }
+    }
+
+    /// GroupName :: `<` RegExpIdentifierName `>`, after the `<`
+    fn parse_group_name(&mut self, start: u32, message: &'static str) -> Result<String> {
+        let mut name = String::new();
+        loop {
+            if self.eat('>') && !name.is_empty() {
+                return Ok(name);
+            }
+            let c = self.eat_group_name_char().ok_or_else(|| self.error(message, start))?;
+            let valid =
+                if name.is_empty() { is_identifier_start_all(c) } else { is_identifier_part(c) };
+            if !valid {
+                return Err(self.error(message, start));
+            }
+            name.push(c);
+        }
+    }
+
+    /// RegExpIdentifierStart and RegExpIdentifierPart, which may be escaped or a surrogate pair
+    fn eat_group_name_char(&mut self) -> Option<char> {
+        if self.eat_str("\\u") {
+            return self.eat_unicode_escape(true).and_then(char::from_u32);
+        }
+        let unit = *self.units.get(self.pos)?;
+        self.pos += 1;
+        let mut value = unit.value;
+        if is_lead_surrogate(value) {
+            if let Some(trail) = self.units.get(self.pos).filter(|u| is_trail_surrogate(u.value)) {
+                value = combine_surrogates(value, trail.value);
+                self.pos += 1;
+            }
+        }
+        char::from_u32(value)
+    }
+
+    /// AtomEscape :: DecimalEscape | CharacterClassEscape | CharacterEscape | `k` GroupName
+    fn parse_atom_escape(&mut self) -> Result<Element> {
+        let start = self.offset();
+        self.pos += 1;
+        if self.at_end() {
+            return Err(self.error("\\ at end of pattern", start));
+        }
+
+        if matches!(self.peek_char(), Some('1'..='9')) {
+            let checkpoint = self.pos;
+            if let Some(index) = self.eat_decimal_digits() {
+                if index <= self.group_count {
+                    let span = self.span_from(start);
+                    let reference = Reference::Index(index);
+                    return Ok(Element::Backreference(Backreference { span, reference }));
+                }
+            }
+            if self.unicode {
+                return Err(self.error("Invalid escape", start));
+            }
+            // Annex B: a legacy octal escape or an identity escape
+            self.pos = checkpoint;
+        }
+
+        if self.named_groups && self.eat('k') {
+            if !self.eat('<') {
+                return Err(self.error("Invalid named reference", start));
+            }
+            let name = self.parse_group_name(start, "Invalid named reference")?;
+            if !self.group_names.contains(&name) {
+                return Err(self.error("Invalid named capture referenced", start));
+            }
+            let span = self.span_from(start);
+            let reference = Reference::Name(name);
+            return Ok(Element::Backreference(Backreference { span, reference }));
+        }
+
+        if let Some(set) = self.eat_character_class_escape(start)? {
+            return Ok(Element::CharacterSet(set));
+        }
+
+        let value = self.parse_character_escape(start, false)?;
+        Ok(Element::Character(Character { span: self.span_from(start), value }))
+    }
+
+    /// CharacterClassEscape :: `d` | `D` | `s` | `S` | `w` | `W` | `p{` ... `}` | `P{` ... `}`,
+    /// after the `\`
+    fn eat_character_class_escape(&mut self, start: u32) -> Result<Option<CharacterSet>> {
+        let (kind, negate) = match self.peek_char() {
+            Some(c @ ('d' | 'D')) => (CharacterSetKind::Digit, c == 'D'),
+            Some(c @ ('s' | 'S')) => (CharacterSetKind::Space, c == 'S'),
+            Some(c @ ('w' | 'W')) => (CharacterSetKind::Word, c == 'W'),
+            Some(c @ ('p' | 'P')) if self.unicode => {
+                self.pos += 1;
+                let kind = self.parse_unicode_property(start)?;
+                let span = self.span_from(start);
+                return Ok(Some(CharacterSet { span, kind, negate: c == 'P' }));
+            }
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+        Ok(Some(CharacterSet { span: self.span_from(start), kind, negate }))
+    }
+
+    /// `{` UnicodePropertyName `=` UnicodePropertyValue `}`
+    /// and `{` LoneUnicodePropertyNameOrValue `}`
+    fn parse_unicode_property(&mut self, start: u32) -> Result<CharacterSetKind> {
+        if !self.eat('{') {
+            return Err(self.error("Invalid property name", start));
+        }
+        let name = self.eat_property_chars();
+        let value = if self.eat('=') { Some(self.eat_property_chars()) } else { None };
+        if !self.eat('}') {
+            return Err(self.error("Invalid property name", start));
+        }
+        let valid = match &value {
+            Some(value) => is_valid_property_value(&name, value),
+            None => {
+                is_general_category_value(&name)
+                    || is_binary_property(&name)
+                    || (self.unicode_sets && is_string_property(&name))
+            }
+        };
+        if !valid {
+            return Err(self.error("Invalid property name", start));
+        }
+        Ok(CharacterSetKind::Property { name, value })
+    }
+
+    fn eat_property_chars(&mut self) -> String {
+        let mut s = String::new();
+        while let Some(c) = self.peek_char().filter(|c| c.is_ascii_alphanumeric() || *c == '_') {
+            s.push(c);
+            self.pos += 1;
+        }
+        s
+    }
+
+    /// CharacterEscape, and the escapes of Annex B without the `u` flag, after the `\`.
+    /// Returns the value of the escaped character.
+    fn parse_character_escape(&mut self, start: u32, in_class: bool) -> Result<u32> {
+        let checkpoint = self.pos;
+        let c = self.peek_char();
+        self.pos += 1;
+        let value = match c {
+            Some('f') => 0x0C,
+            Some('n') => 0x0A,
+            Some('r') => 0x0D,
+            Some('t') => 0x09,
+            Some('v') => 0x0B,
+            Some('c') => {
+                let letter = self.peek_char().filter(|c| {
+                    c.is_ascii_alphabetic()
+                        // Annex B: ClassControlLetter
+                        || (!self.unicode && in_class && (c.is_ascii_digit() || *c == '_'))
+                });
+                if let Some(letter) = letter {
+                    self.pos += 1;
+                    return Ok(u32::from(letter) % 32);
+                }
+                if self.unicode {
+                    return Err(self.error("Invalid unicode escape", start));
+                }
+                // Annex B: `\c` is a literal backslash followed by `c`
+                self.pos = checkpoint;
+                return Ok(u32::from('\\'));
+            }
+            Some('0') if !matches!(self.peek_char(), Some('0'..='9')) => 0,
+            Some('0'..='7') if !self.unicode => {
+                self.pos = checkpoint;
+                self.eat_legacy_octal()
+            }
+            Some('0') => return Err(self.error("Invalid decimal escape", start)),
+            Some('1'..='9') if self.unicode => {
+                return Err(self.error("Invalid class escape", start))
+            }
+            Some('x') => match self.eat_fixed_hex(2) {
+                Some(value) => value,
+                None if self.unicode => return Err(self.error("Invalid escape", start)),
+                None => u32::from('x'),
+            },
+            Some('u') => match self.eat_unicode_escape(self.unicode) {
+                Some(value) => value,
+                None if self.unicode => return Err(self.error("Invalid Unicode escape", start)),
+                None => u32::from('u'),
+            },
+            Some('-') if self.unicode && in_class => u32::from('-'),
+            Some(c) if self.unicode => {
+                if !is_syntax_character(c) && c != '/' {
+                    return Err(self.error("Invalid escape", start));
+                }
+                u32::from(c)
+            }
+            // Annex B: IdentityEscape is any character except `c`, and `k` with group names
+            Some('k') if self.named_groups => return Err(self.error("Invalid escape", start)),
+            _ => self.units[checkpoint].value,
+        };
+        Ok(value)
+    }
+
+    /// Annex B: LegacyOctalEscapeSequence
+    fn eat_legacy_octal(&mut self) -> u32 {
+        let mut value = 0;
+        let max_len = if matches!(self.peek_char(), Some('0'..='3')) { 3 } else { 2 };
+        for _ in 0..max_len {
+            match self.peek_char().and_then(|c| c.to_digit(8)) {
+                Some(digit) => {
+                    value = value * 8 + digit;
+                    self.pos += 1;
+                }
+                None => break,
+            }
+        }
+        value
+    }
+
+    /// After `\u`: `XXXX`, and with `unicode`, `{X...}` and a surrogate pair `XXXX\uXXXX`
+    fn eat_unicode_escape(&mut self, unicode: bool) -> Option<u32> {
+        let checkpoint = self.pos;
+        if unicode && self.eat('{') {
+            let mut value: u32 = 0;
+            let mut len = 0;
+            while let Some(digit) = self.peek_char().and_then(|c| c.to_digit(16)) {
+                value = value.saturating_mul(16).saturating_add(digit);
+                len += 1;
+                self.pos += 1;
+            }
+            if len > 0 && value <= 0x10_FFFF && self.eat('}') {
+                return Some(value);
+            }
+            self.pos = checkpoint;
+            return None;
+        }
+        let lead = self.eat_fixed_hex(4)?;
+        if unicode && is_lead_surrogate(lead) {
+            let checkpoint = self.pos;
+            if self.eat_str("\\u") {
+                if let Some(trail) =
+                    self.eat_fixed_hex(4).filter(|trail| is_trail_surrogate(*trail))
+                {
+                    return Some(combine_surrogates(lead, trail));
+                }
+            }
+            self.pos = checkpoint;
+        }
+        Some(lead)
+    }
+
+    fn eat_fixed_hex(&mut self, len: usize) -> Option<u32> {
+        let mut value = 0;
+        for i in 0..len {
+            let digit = self
+                .units
+                .get(self.pos + i)
+                .and_then(|unit| char::from_u32(unit.value).and_then(|c| c.to_digit(16)))?;
+            value = value * 16 + digit;
+        }
+        self.pos += len;
+        Some(value)
+    }
+
+    fn eat_decimal_digits(&mut self) -> Option<u32> {
+        let mut value: Option<u32> = None;
+        while let Some(digit) = self.peek_char().and_then(|c| c.to_digit(10)) {
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+            self.pos += 1;
+        }
+        value
+    }
+
+    /// CharacterClass :: `[` `^`? ClassContents `]`
+    fn parse_class(&mut self) -> Result<CharacterClass> {
+        if self.unicode_sets {
+            return self.parse_class_set();
+        }
+        let start = self.offset();
+        self.pos += 1;
+        let negate = self.eat('^');
+        let mut elements = vec![];
+        loop {
+            if self.eat(']') {
+                break;
+            }
+            if self.at_end() {
+                return Err(self.error("Unterminated character class", start));
+            }
+            let range_start = self.offset();
+            let min = self.parse_class_atom()?;
+            if !self.at('-') || self.at_str(1, "]") || self.pos + 1 >= self.units.len() {
+                elements.push(min);
+                continue;
+            }
+            let dash = self.eat_character();
+            let max = self.parse_class_atom()?;
+            match (min, max) {
+                (ClassElement::Character(min), ClassElement::Character(max)) => {
+                    if min.value > max.value {
+                        return Err(
+                            self.error("Range out of order in character class", range_start)
+                        );
+                    }
+                    let span = self.span_from(range_start);
+                    elements.push(ClassElement::Range(CharacterRange { span, min, max }));
+                }
+                _ if self.unicode => {
+                    return Err(self.error("Invalid character class", range_start));
+                }
+                // Annex B: a range with a class escape such as `[\d-a]` is a union
+                (min, max) => {
+                    elements.extend([min, ClassElement::Character(dash), max]);
+                }
+            }
+        }
+        let span = self.span_from(start);
+        Ok(CharacterClass { span, negate, kind: CharacterClassKind::Union, elements })
+    }
+
+    /// ClassAtom :: `-` | ClassAtomNoDash
+    fn parse_class_atom(&mut self) -> Result<ClassElement> {
+        let start = self.offset();
+        if !self.eat('\\') {
+            return Ok(ClassElement::Character(self.eat_character()));
+        }
+        if self.at_end() {
+            return Err(self.error("\\ at end of pattern", start));
+        }
+        if let Some(set) = self.eat_character_class_escape(start)? {
+            return Ok(ClassElement::CharacterSet(set));
+        }
+        let value = if self.eat('b') { 0x08 } else { self.parse_character_escape(start, true)? };
+        Ok(ClassElement::Character(Character { span: self.span_from(start), value }))
+    }
+
+    /// ClassSetExpression of the `v` flag :: ClassUnion | ClassIntersection | ClassSubtraction
+    fn parse_class_set(&mut self) -> Result<CharacterClass> {
+        let start = self.offset();
+        self.pos += 1;
+        let negate = self.eat('^');
+        let mut kind = CharacterClassKind::Union;
+        let mut elements = vec![];
+        if !self.at(']') && !self.at_end() {
+            let first = self.parse_class_set_range_or_operand()?;
+            let operator = if self.at_str(0, "&&") {
+                Some(("&&", CharacterClassKind::Intersection))
+            } else if self.at_str(0, "--") {
+                Some(("--", CharacterClassKind::Subtraction))
+            } else {
+                None
+            };
+            if let Some((operator, operator_kind)) = operator {
+                kind = operator_kind;
+                if matches!(first, ClassElement::Range(_)) {
+                    return Err(self.error("Invalid set operation in character class", start));
+                }
+                elements.push(first);
+                while self.eat_str(operator) {
+                    if self.at('&') {
+                        return Err(self.error("Invalid character in character class", start));
+                    }
+                    elements.push(self.parse_class_set_operand()?);
+                }
+                if !self.at(']') && !self.at_end() {
+                    return Err(self.error("Invalid set operation in character class", start));
+                }
+            } else {
+                elements.push(first);
+                while !self.at(']') && !self.at_end() {
+                    if self.at_str(0, "&&") || self.at_str(0, "--") {
+                        return Err(self.error("Invalid set operation in character class", start));
+                    }
+                    elements.push(self.parse_class_set_range_or_operand()?);
+                }
+            }
+        }
+        if !self.eat(']') {
+            return Err(self.error("Unterminated character class", start));
+        }
+        let class = CharacterClass { span: self.span_from(start), negate, kind, elements };
+        if negate && may_contain_strings(&class) {
+            return Err(self.error("Negated character class may contain strings", start));
+        }
+        Ok(class)
+    }
+
+    /// ClassSetRange :: ClassSetCharacter `-` ClassSetCharacter, or a ClassSetOperand
+    fn parse_class_set_range_or_operand(&mut self) -> Result<ClassElement> {
+        let start = self.offset();
+        let operand = self.parse_class_set_operand()?;
+        if let ClassElement::Character(min) = operand {
+            if self.at('-') && !self.at_str(0, "--") {
+                self.pos += 1;
+                return match self.parse_class_set_operand()? {
+                    ClassElement::Character(max) => {
+                        if min.value > max.value {
+                            return Err(self.error("Range out of order in character class", start));
+                        }
+                        let span = self.span_from(start);
+                        Ok(ClassElement::Range(CharacterRange { span, min, max }))
+                    }
+                    _ => Err(self.error("Invalid character class", start)),
+                };
+            }
+        }
+        Ok(operand)
+    }
+
+    /// ClassSetOperand :: NestedClass | ClassStringDisjunction | ClassSetCharacter
+    fn parse_class_set_operand(&mut self) -> Result<ClassElement> {
+        let start = self.offset();
+        if self.at('[') {
+            return Ok(ClassElement::Class(Box::new(self.parse_class_set()?)));
+        }
+        if self.eat_str("\\q{") {
+            let mut strings = vec![vec![]];
+            loop {
+                if self.eat('}') {
+                    break;
+                }
+                if self.at_end() {
+                    return Err(self.error("Unterminated character class", start));
+                }
+                if self.eat('|') {
+                    strings.push(vec![]);
+                    continue;
+                }
+                let character = self.parse_class_set_character()?;
+                strings.last_mut().unwrap().push(character);
+            }
+            let span = self.span_from(start);
+            return Ok(ClassElement::Strings(ClassStrings { span, strings }));
+        }
+        if self.at('\\') {
+            let checkpoint = self.pos;
+            self.pos += 1;
+            if let Some(set) = self.eat_character_class_escape(start)? {
+                return Ok(ClassElement::CharacterSet(set));
+            }
+            self.pos = checkpoint;
+        }
+        Ok(ClassElement::Character(self.parse_class_set_character()?))
+    }
+
+    /// ClassSetCharacter, which may be escaped
+    fn parse_class_set_character(&mut self) -> Result<Character> {
+        let start = self.offset();
+        if self.eat('\\') {
+            if self.at_end() {
+                return Err(self.error("\\ at end of pattern", start));
+            }
+            let value = if self.eat('b') {
+                0x08
+            } else if let Some(c) =
+                self.peek_char().filter(|c| is_class_set_reserved_punctuator(*c))
+            {
+                self.pos += 1;
+                u32::from(c)
+            } else {
+                self.parse_character_escape(start, true)?
+            };
+            return Ok(Character { span: self.span_from(start), value });
+        }
+        let c = self.peek_char();
+        let double_punctuator = c.map_or(false, |c| {
+            is_class_set_reserved_double_punctuator(c)
+                && self.units.get(self.pos + 1).map_or(false, |unit| unit.value == u32::from(c))
+        });
+        if double_punctuator || c.map_or(false, is_class_set_syntax_character) {
+            self.pos += 1;
+            return Err(self.error("Invalid character in character class", start));
+        }
+        Ok(self.eat_character())
+    }
+
+    fn eat_character(&mut self) -> Character {
+        let start = self.offset();
+        let value = self.units[self.pos].value;
+        self.pos += 1;
+        Character { span: self.span_from(start), value }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.units.len()
+    }
+
+    /// The current unit as a character, `None` at the end or for a lone surrogate
+    fn peek_char(&self) -> Option<char> {
+        self.units.get(self.pos).and_then(|unit| char::from_u32(unit.value))
+    }
+
+    fn at(&self, c: char) -> bool {
+        self.peek_char() == Some(c)
+    }
+
+    /// Whether the units from `pos + n` start with the ASCII string `s`
+    fn at_str(&self, n: usize, s: &str) -> bool {
+        s.bytes().enumerate().all(|(i, b)| {
+            self.units.get(self.pos + n + i).map_or(false, |unit| unit.value == u32::from(b))
+        })
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.at(c) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        if self.at_str(0, s) {
+            self.pos += s.len();
+            return true;
+        }
+        false
+    }
+
+    /// Start of the current unit
+    fn offset(&self) -> u32 {
+        self.units.get(self.pos).map_or_else(|| self.prev_end(), |unit| unit.start)
+    }
+
+    fn prev_end(&self) -> u32 {
+        self.units[..self.pos].last().map_or(0, |unit| unit.end)
+    }
+
+    fn span_from(&self, start: u32) -> Span {
+        Span::new(start + self.span_offset, self.prev_end().max(start) + self.span_offset)
+    }
+
+    fn error(&self, message: &'static str, start: u32) -> InvalidPattern {
+        InvalidPattern(message, self.span_from(start))
+    }
+}
+
+const fn is_lead_surrogate(value: u32) -> bool {
+    matches!(value, 0xD800..=0xDBFF)
+}
+
+const fn is_trail_surrogate(value: u32) -> bool {
+    matches!(value, 0xDC00..=0xDFFF)
+}
+
+const fn combine_surrogates(lead: u32, trail: u32) -> u32 {
+    (lead - 0xD800) * 0x400 + (trail - 0xDC00) + 0x10000
+}
+
+/// SyntaxCharacter
+const fn is_syntax_character(c: char) -> bool {
+    matches!(c, '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|')
+}
+
+/// ClassSetSyntaxCharacter
+const fn is_class_set_syntax_character(c: char) -> bool {
+    matches!(c, '(' | ')' | '[' | ']' | '{' | '}' | '/' | '-' | '\\' | '|')
+}
+
+/// ClassSetReservedDoublePunctuator, when the character is repeated
+const fn is_class_set_reserved_double_punctuator(c: char) -> bool {
+    matches!(
+        c,
+        '&' | '!'
+            | '#'
+            | '$'
+            | '%'
+            | '*'
+            | '+'
+            | ','
+            | '.'
+            | ':'
+            | ';'
+            | '<'
+            | '='
+            | '>'
+            | '?'
+            | '@'
+            | '^'
+            | '`'
+            | '~'
+    )
+}
+
+/// ClassSetReservedPunctuator
+const fn is_class_set_reserved_punctuator(c: char) -> bool {
+    matches!(c, '&' | '-' | '!' | '#' | '%' | ',' | ':' | ';' | '<' | '=' | '>' | '@' | '`' | '~')
+}
+
+/// Whether a class of the `v` flag may match strings longer than one character
+fn may_contain_strings(class: &CharacterClass) -> bool {
+    let element_may_contain_strings = |element: &ClassElement| match element {
+        ClassElement::Strings(strings) => strings.strings.iter().any(|s| s.len() != 1),
+        ClassElement::CharacterSet(CharacterSet {
+            kind: CharacterSetKind::Property { name, value: None },
+            ..
+        }) => is_string_property(name),
+        ClassElement::Class(class) => may_contain_strings(class),
+        _ => false,
+    };
+    match class.kind {
+        CharacterClassKind::Union => class.elements.iter().any(element_may_contain_strings),
+        CharacterClassKind::Intersection => class.elements.iter().all(element_may_contain_strings),
+        CharacterClassKind::Subtraction => {
+            class.elements.first().map_or(false, element_may_contain_strings)
+        }
+    }
+}
+
+/// Scripts are not checked against the Unicode Character Database,
+/// any name is accepted as the value of `Script` and `Script_Extensions`.
+fn is_valid_property_value(name: &str, value: &str) -> bool {
+    match name {
+        "General_Category" | "gc" => is_general_category_value(value),
+        "Script" | "sc" | "Script_Extensions" | "scx" => {
+            !value.is_empty() && value.chars().all(|c| c.is_ascii_alphabetic() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Values of the `General_Category` property, including aliases
+fn is_general_category_value(value: &str) -> bool {
+    matches!(
+        value,
+        "C" | "Other"
+            | "Cc"
+            | "Control"
+            | "cntrl"
+            | "Cf"
+            | "Format"
+            | "Cn"
+            | "Unassigned"
+            | "Co"
+            | "Private_Use"
+            | "Cs"
+            | "Surrogate"
+            | "L"
+            | "Letter"
+            | "LC"
+            | "Cased_Letter"
+            | "Ll"
+            | "Lowercase_Letter"
+            | "Lm"
+            | "Modifier_Letter"
+            | "Lo"
+            | "Other_Letter"
+            | "Lt"
+            | "Titlecase_Letter"
+            | "Lu"
+            | "Uppercase_Letter"
+            | "M"
+            | "Mark"
+            | "Combining_Mark"
+            | "Mc"
+            | "Spacing_Mark"
+            | "Me"
+            | "Enclosing_Mark"
+            | "Mn"
+            | "Nonspacing_Mark"
+            | "N"
+            | "Number"
+            | "Nd"
+            | "Decimal_Number"
+            | "digit"
+            | "Nl"
+            | "Letter_Number"
+            | "No"
+            | "Other_Number"
+            | "P"
+            | "Punctuation"
+            | "punct"
+            | "Pc"
+            | "Connector_Punctuation"
+            | "Pd"
+            | "Dash_Punctuation"
+            | "Pe"
+            | "Close_Punctuation"
+            | "Pf"
+            | "Final_Punctuation"
+            | "Pi"
+            | "Initial_Punctuation"
+            | "Po"
+            | "Other_Punctuation"
+            | "Ps"
+            | "Open_Punctuation"
+            | "S"
+            | "Symbol"
+            | "Sc"
+            | "Currency_Symbol"
+            | "Sk"
+            | "Modifier_Symbol"
+            | "Sm"
+            | "Math_Symbol"
+            | "So"
+            | "Other_Symbol"
+            | "Z"
+            | "Separator"
+            | "Zl"
+            | "Line_Separator"
+            | "Zp"
+            | "Paragraph_Separator"
+            | "Zs"
+            | "Space_Separator"
+    )
+}
+
+/// [Binary Unicode property aliases](https://tc39.es/ecma262/#table-binary-unicode-properties)
+#[allow(clippy::too_many_lines)]
+fn is_binary_property(name: &str) -> bool {
+    matches!(
+        name,
+        "ASCII"
+            | "ASCII_Hex_Digit"
+            | "AHex"
+            | "Alphabetic"
+            | "Alpha"
+            | "Any"
+            | "Assigned"
+            | "Bidi_Control"
+            | "Bidi_C"
+            | "Bidi_Mirrored"
+            | "Bidi_M"
+            | "Case_Ignorable"
+            | "CI"
+            | "Cased"
+            | "Changes_When_Casefolded"
+            | "CWCF"
+            | "Changes_When_Casemapped"
+            | "CWCM"
+            | "Changes_When_Lowercased"
+            | "CWL"
+            | "Changes_When_NFKC_Casefolded"
+            | "CWKCF"
+            | "Changes_When_Titlecased"
+            | "CWT"
+            | "Changes_When_Uppercased"
+            | "CWU"
+            | "Dash"
+            | "Default_Ignorable_Code_Point"
+            | "DI"
+            | "Deprecated"
+            | "Dep"
+            | "Diacritic"
+            | "Dia"
+            | "Emoji"
+            | "Emoji_Component"
+            | "EComp"
+            | "Emoji_Modifier"
+            | "EMod"
+            | "Emoji_Modifier_Base"
+            | "EBase"
+            | "Emoji_Presentation"
+            | "EPres"
+            | "Extended_Pictographic"
+            | "ExtPict"
+            | "Extender"
+            | "Ext"
+            | "Grapheme_Base"
+            | "Gr_Base"
+            | "Grapheme_Extend"
+            | "Gr_Ext"
+            | "Hex_Digit"
+            | "Hex"
+            | "IDS_Binary_Operator"
+            | "IDSB"
+            | "IDS_Trinary_Operator"
+            | "IDST"
+            | "ID_Continue"
+            | "IDC"
+            | "ID_Start"
+            | "IDS"
+            | "Ideographic"
+            | "Ideo"
+            | "Join_Control"
+            | "Join_C"
+            | "Logical_Order_Exception"
+            | "LOE"
+            | "Lowercase"
+            | "Lower"
+            | "Math"
+            | "Noncharacter_Code_Point"
+            | "NChar"
+            | "Pattern_Syntax"
+            | "Pat_Syn"
+            | "Pattern_White_Space"
+            | "Pat_WS"
+            | "Quotation_Mark"
+            | "QMark"
+            | "Radical"
+            | "Regional_Indicator"
+            | "RI"
+            | "Sentence_Terminal"
+            | "STerm"
+            | "Soft_Dotted"
+            | "SD"
+            | "Terminal_Punctuation"
+            | "Term"
+            | "Unified_Ideograph"
+            | "UIdeo"
+            | "Uppercase"
+            | "Upper"
+            | "Variation_Selector"
+            | "VS"
+            | "White_Space"
+            | "space"
+            | "XID_Continue"
+            | "XIDC"
+            | "XID_Start"
+            | "XIDS"
+    )
+}
+
+/// [Binary Unicode properties of strings](https://tc39.es/ecma262/#table-binary-unicode-properties-of-strings),
+/// only with the `v` flag
+fn is_string_property(name: &str) -> bool {
+    matches!(
+        name,
+        "Basic_Emoji"
+            | "Emoji_Keycap_Sequence"
+            | "RGI_Emoji_Modifier_Sequence"
+            | "RGI_Emoji_Flag_Sequence"
+            | "RGI_Emoji_Tag_Sequence"
+            | "RGI_Emoji_ZWJ_Sequence"
+            | "RGI_Emoji"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_ast::ast::RegExpFlags;
+    use oxc_span::Span;
+
+    use super::{parse_flags, PatternParser};
+    use crate::ast::{CharacterClassKind, Element, Reference};
+
+    fn parse(pattern: &str, flags: &str) -> Result<crate::ast::Pattern, &'static str> {
+        let flags = parse_flags(flags).unwrap();
+        PatternParser::new(pattern, flags).parse().map_err(|error| error.0)
+    }
+
+    #[test]
+    fn valid() {
+        let pass = [
+            ("", ""),
+            ("a|b|", ""),
+            ("^abc$", "m"),
+            ("a*?b+c?d{2}e{2,}f{2,3}?", ""),
+            ("(a)(?:b)(?<name>c)\\1\\k<name>", ""),
+            ("\\k<name>(?<name>a)", ""),
+            ("(?=a)(?!b)(?<=c)(?<!d)", ""),
+            ("(?=a)*", ""),
+            ("[a-z\\d\\-_]", ""),
+            ("[^\\b]", ""),
+            ("\\x41\\u0041\\u{41}\\cJ\\0\\t", "u"),
+            ("\\uD83D\\uDE00", "u"),
+            ("\\p{L}\\P{Script=Greek}\\p{gc=Lu}\\p{ASCII_Hex_Digit}", "u"),
+            ("(?<\\u{1d4d1}>a)", ""),
+            ("(?<π>a)\\k<π>", "u"),
+            // Annex B
+            ("]{}", ""),
+            ("a{", ""),
+            ("a{1", ""),
+            ("\\1", ""),
+            ("\\8\\377\\08", ""),
+            ("\\c\\a\\k\\p{", ""),
+            ("[\\c1\\d-a]", ""),
+            ("\\u{1F600}", ""),
+            // `v` flag
+            ("[\\p{L}--[a-z]]", "v"),
+            ("[[a-z]&&[aeiou]]", "v"),
+            ("[\\q{abc|d}a-z]", "v"),
+            ("[\\p{RGI_Emoji}]", "v"),
+            ("[^\\q{a|b}]", "v"),
+            ("[\\&\\-]", "v"),
+        ];
+        for (pattern, flags) in pass {
+            assert!(parse(pattern, flags).is_ok(), "/{pattern}/{flags} should be valid");
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let fail = [
+            ("(", "", "Unterminated group"),
+            ("(?:a", "", "Unterminated group"),
+            ("a)", "", "Unmatched ')'"),
+            ("(?a)", "", "Invalid group"),
+            ("*", "", "Nothing to repeat"),
+            ("a**", "", "Nothing to repeat"),
+            ("^*", "", "Nothing to repeat"),
+            ("(?<=a)?", "", "Nothing to repeat"),
+            ("(?=a)*", "u", "Nothing to repeat"),
+            ("{1}", "", "Nothing to repeat"),
+            ("a{2,1}", "", "numbers out of order in {} quantifier"),
+            ("{", "u", "Lone quantifier brackets"),
+            ("]", "u", "Lone quantifier brackets"),
+            ("a{1", "u", "Incomplete quantifier"),
+            ("[a", "", "Unterminated character class"),
+            ("[z-a]", "", "Range out of order in character class"),
+            ("[\\d-a]", "u", "Invalid character class"),
+            ("\\", "", "\\ at end of pattern"),
+            ("\\a", "u", "Invalid escape"),
+            ("\\1", "u", "Invalid escape"),
+            ("\\u{110000}", "u", "Invalid Unicode escape"),
+            ("\\c", "u", "Invalid unicode escape"),
+            ("\\00", "u", "Invalid decimal escape"),
+            ("(?<1>a)", "", "Invalid capture group name"),
+            ("(?<a>a)(?<a>b)", "", "Duplicate capture group name"),
+            ("\\k", "u", "Invalid named reference"),
+            ("(?<a>a)\\k<b>", "", "Invalid named capture referenced"),
+            ("\\p{Unknown}", "u", "Invalid property name"),
+            ("\\p{RGI_Emoji}", "u", "Invalid property name"),
+            ("[a&&b--c]", "v", "Invalid set operation in character class"),
+            ("[a-z&&b]", "v", "Invalid set operation in character class"),
+            ("[(]", "v", "Invalid character in character class"),
+            ("[a!!b]", "v", "Invalid character in character class"),
+            ("[^\\q{ab}]", "v", "Negated character class may contain strings"),
+        ];
+        for (pattern, flags, message) in fail {
+            assert_eq!(parse(pattern, flags), Err(message), "/{pattern}/{flags}");
+        }
+    }
+
+    #[test]
+    fn flags() {
+        assert_eq!(parse_flags("dgimsuy").unwrap().bits(), 0b0111_1111);
+        assert_eq!(parse_flags("v").unwrap(), RegExpFlags::V);
+        assert_eq!(parse_flags("gx").unwrap_err().0, "Unknown flag");
+        assert_eq!(parse_flags("gig").unwrap_err().1, Span::new(2, 3));
+        assert!(parse_flags("uv").is_err());
+    }
+
+    #[test]
+    fn ast() {
+        let pattern = parse("(?<a>x)\\k<a>[a-c]", "").unwrap();
+        let elements = &pattern.alternatives[0].elements;
+        assert_eq!(elements.len(), 3);
+        match &elements[0] {
+            Element::CapturingGroup(group) => {
+                assert_eq!(group.index, 1);
+                assert_eq!(group.name.as_deref(), Some("a"));
+                assert_eq!(group.span, Span::new(0, 7));
+            }
+            element => panic!("{element:?}"),
+        }
+        match &elements[1] {
+            Element::Backreference(reference) => {
+                assert_eq!(reference.reference, Reference::Name("a".into()));
+            }
+            element => panic!("{element:?}"),
+        }
+        match &elements[2] {
+            Element::CharacterClass(class) => {
+                assert_eq!(class.kind, CharacterClassKind::Union);
+                assert_eq!(class.span, Span::new(12, 17));
+            }
+            element => panic!("{element:?}"),
+        }
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        let pattern = parse("😀", "").unwrap();
+        assert_eq!(pattern.alternatives[0].elements.len(), 2);
+        let pattern = parse("😀", "u").unwrap();
+        assert_eq!(pattern.alternatives[0].elements.len(), 1);
+    }
+
+    #[test]
+    fn span_offset() {
+        let error = PatternParser::new("a)", RegExpFlags::empty()).with_span_offset(1).parse();
+        assert_eq!(error.unwrap_err().1, Span::new(2, 3));
+    }
+}
@@ -0,0 +1,78 @@
+//! Visitor Pattern for the regular expression pattern AST
+//!
+//! The `walk_*` functions visit the children of a node, for visitors which override a method
+//! and still want to visit the children.
+
+use crate::ast::{
+    Alternative, Assertion, Backreference, CapturingGroup, CharacterClass, ClassElement, Element,
+    Group, Pattern, Quantifier,
+};
+
+pub trait Visit: Sized {
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        walk_alternatives(self, &pattern.alternatives);
+    }
+
+    fn visit_alternative(&mut self, alternative: &Alternative) {
+        walk_alternative(self, alternative);
+    }
+
+    fn visit_element(&mut self, element: &Element) {
+        walk_element(self, element);
+    }
+
+    fn visit_assertion(&mut self, assertion: &Assertion) {
+        walk_alternatives(self, &assertion.alternatives);
+    }
+
+    fn visit_quantifier(&mut self, quantifier: &Quantifier) {
+        self.visit_element(&quantifier.element);
+    }
+
+    fn visit_group(&mut self, group: &Group) {
+        walk_alternatives(self, &group.alternatives);
+    }
+
+    fn visit_capturing_group(&mut self, group: &CapturingGroup) {
+        walk_alternatives(self, &group.alternatives);
+    }
+
+    fn visit_character_class(&mut self, class: &CharacterClass) {
+        walk_character_class(self, class);
+    }
+
+    fn visit_backreference(&mut self, _backreference: &Backreference) {}
+}
+
+pub fn walk_alternatives<V: Visit>(visitor: &mut V, alternatives: &[Alternative]) {
+    for alternative in alternatives {
+        visitor.visit_alternative(alternative);
+    }
+}
+
+pub fn walk_alternative<V: Visit>(visitor: &mut V, alternative: &Alternative) {
+    for element in &alternative.elements {
+        visitor.visit_element(element);
+    }
+}
+
+pub fn walk_element<V: Visit>(visitor: &mut V, element: &Element) {
+    match element {
+        Element::Assertion(assertion) => visitor.visit_assertion(assertion),
+        Element::Quantifier(quantifier) => visitor.visit_quantifier(quantifier),
+        Element::Group(group) => visitor.visit_group(group),
+        Element::CapturingGroup(group) => visitor.visit_capturing_group(group),
+        Element::CharacterClass(class) => visitor.visit_character_class(class),
+        Element::Backreference(backreference) => visitor.visit_backreference(backreference),
+        Element::CharacterSet(_) | Element::Character(_) => {}
+    }
+}
+
+/// Visits the classes nested in the class, which only the `v` flag allows
+pub fn walk_character_class<V: Visit>(visitor: &mut V, class: &CharacterClass) {
+    for element in &class.elements {
+        if let ClassElement::Class(class) = element {
+            visitor.visit_character_class(class);
+        }
+    }
+}
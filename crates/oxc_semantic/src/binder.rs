@@ -15,7 +15,9 @@ impl<'a> Binder for VariableDeclarator<'a> {
     fn bind(&self, builder: &mut SemanticBuilder) {
         let current_scope_id = builder.current_scope_id;
         let (includes, excludes) = match self.kind {
-            VariableDeclarationKind::Const => (
+            VariableDeclarationKind::Const
+            | VariableDeclarationKind::Using
+            | VariableDeclarationKind::AwaitUsing => (
                 SymbolFlags::BlockScopedVariable | SymbolFlags::ConstVariable,
                 SymbolFlags::BlockScopedVariableExcludes,
             ),
@@ -220,7 +222,10 @@ impl<'a> Binder for TSInterfaceDeclaration<'a> {
 impl<'a> Binder for TSEnumDeclaration<'a> {
     fn bind(&self, builder: &mut SemanticBuilder) {
         let is_const = self.modifiers.contains(ModifierKind::Const);
-        let includes = if is_const { SymbolFlags::ConstEnum } else { SymbolFlags::RegularEnum };
+        let mut includes = if is_const { SymbolFlags::ConstEnum } else { SymbolFlags::RegularEnum };
+        if self.modifiers.contains(ModifierKind::Declare) {
+            includes |= SymbolFlags::Ambient;
+        }
         let excludes = if is_const {
             SymbolFlags::ConstEnumExcludes
         } else {
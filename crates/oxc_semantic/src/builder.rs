@@ -1,18 +1,23 @@
 //! Semantic Builder
 
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{OnceCell, RefCell},
+    rc::Rc,
+};
 
+use bitflags::bitflags;
 use itertools::Itertools;
 #[allow(clippy::wildcard_imports)]
 use oxc_ast::{ast::*, AstKind, Trivias, Visit};
 use oxc_diagnostics::Error;
-use oxc_span::{Atom, SourceType, Span};
-use oxc_syntax::{module_record::ModuleRecord, operator::AssignmentOperator};
+use oxc_span::{Atom, GetSpan, SourceType, Span};
+use oxc_syntax::{es_target::EsTarget, module_record::ModuleRecord, operator::AssignmentOperator};
 use rustc_hash::FxHashMap;
 
 use crate::{
     binder::Binder,
-    checker::{EarlyErrorJavaScript, EarlyErrorTypeScript},
+    checker::{EarlyErrorJavaScript, EarlyErrorTypeScript, EsTargetChecker},
+    control_flow::ControlFlowGraph,
     diagnostics::Redeclaration,
     jsdoc::JSDocBuilder,
     module_record::ModuleRecordBuilder,
@@ -25,14 +30,34 @@ use crate::{
 
 pub struct LabeledScope<'a> {
     name: &'a str,
+    node_id: AstNodeId,
+    /// The function containing the labeled statement, labels cannot be used across functions
+    function_id: Option<AstNodeId>,
     used: bool,
-    parent: usize,
+    parent: Option<usize>,
 }
 
-struct UnusedLabels<'a> {
+struct Labels<'a> {
     scopes: Vec<LabeledScope<'a>>,
-    curr_scope: usize,
-    labels: Vec<AstNodeId>,
+    curr_scope: Option<usize>,
+    unused: Vec<AstNodeId>,
+    /// Labeled `break` and `continue` statements to their labeled statements
+    targets: FxHashMap<AstNodeId, AstNodeId>,
+}
+
+bitflags! {
+    /// The optional passes of [`SemanticBuilder`], enabled by its `with_*` methods
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct BuilderOptions: u8 {
+        /// Build the module record of the import and export statements
+        const ModuleRecord     = 1 << 0;
+        /// Report early errors
+        const SyntaxErrors     = 1 << 1;
+        /// Report TypeScript code which cannot be transpiled one file at a time
+        const IsolatedModules  = 1 << 2;
+        /// Report assignments to `const` bindings in TypeScript
+        const ConstAssignments = 1 << 3;
+    }
 }
 
 pub struct SemanticBuilder<'a> {
@@ -63,13 +88,15 @@ pub struct SemanticBuilder<'a> {
     pub scope: ScopeTree,
     pub symbols: SymbolTable,
 
-    with_module_record_builder: bool,
     pub module_record_builder: ModuleRecordBuilder,
-    unused_labels: UnusedLabels<'a>,
+    labels: Labels<'a>,
 
     jsdoc: JSDocBuilder<'a>,
 
-    check_syntax_error: bool,
+    options: BuilderOptions,
+
+    /// Syntax newer than the target is reported as an error
+    target: EsTarget,
 }
 
 pub struct SemanticBuilderReturn<'a> {
@@ -97,11 +124,16 @@ impl<'a> SemanticBuilder<'a> {
             nodes: AstNodes::default(),
             scope,
             symbols: SymbolTable::default(),
-            with_module_record_builder: false,
             module_record_builder: ModuleRecordBuilder::default(),
-            unused_labels: UnusedLabels { scopes: vec![], curr_scope: 0, labels: vec![] },
+            labels: Labels {
+                scopes: vec![],
+                curr_scope: None,
+                unused: vec![],
+                targets: FxHashMap::default(),
+            },
             jsdoc: JSDocBuilder::new(source_text, &trivias),
-            check_syntax_error: false,
+            options: BuilderOptions::empty(),
+            target: EsTarget::default(),
         }
     }
 
@@ -115,26 +147,59 @@ impl<'a> SemanticBuilder<'a> {
 
     #[must_use]
     pub fn with_module_record_builder(mut self, yes: bool) -> Self {
-        self.with_module_record_builder = yes;
+        self.options.set(BuilderOptions::ModuleRecord, yes);
         self
     }
 
     #[must_use]
     pub fn with_check_syntax_error(mut self, yes: bool) -> Self {
-        self.check_syntax_error = yes;
+        self.options.set(BuilderOptions::SyntaxErrors, yes);
+        self
+    }
+
+    /// Report the errors of `tsc --isolatedModules`, e.g. re-exporting a type without `export type`.
+    #[must_use]
+    pub fn with_isolated_modules(mut self, yes: bool) -> Self {
+        self.options.set(BuilderOptions::IsolatedModules, yes);
+        self
+    }
+
+    /// Report assignments to `const` bindings in TypeScript, which `tsc` reports as type errors.
+    /// Off by default, as the errors stop the linter from running, whose `no-const-assign`
+    /// rule reports them in every language.
+    #[must_use]
+    pub fn with_const_assignments(mut self, yes: bool) -> Self {
+        self.options.set(BuilderOptions::ConstAssignments, yes);
+        self
+    }
+
+    /// Report syntax that is not supported by the `target` edition, e.g. optional chaining under ES2018.
+    #[must_use]
+    pub fn with_target(mut self, target: EsTarget) -> Self {
+        self.target = target;
         self
     }
 
     pub fn build(mut self, program: &'a Program<'a>) -> SemanticBuilderReturn<'a> {
         // First AST pass
-        if !self.source_type.is_typescript_definition() {
-            self.visit_program(program);
+        self.visit_program(program);
+
+        if self.source_type.is_typescript() && !self.source_type.is_typescript_definition() {
+            // References are only resolved once their scopes have been left
+            if self.options.contains(BuilderOptions::ConstAssignments) {
+                EarlyErrorTypeScript::check_const_assignments(&self);
+            }
+            if self.options.contains(BuilderOptions::IsolatedModules) {
+                EarlyErrorTypeScript::check_isolated_modules(program, &self);
+            }
         }
 
         // Second partial AST pass on top level import / export statements
-        let module_record = if self.with_module_record_builder {
+        let module_record = if self.options.contains(BuilderOptions::ModuleRecord) {
             self.module_record_builder.visit(program);
-            if self.check_syntax_error {
+            if self.options.contains(BuilderOptions::SyntaxErrors)
+                && !self.source_type.is_typescript_definition()
+            {
                 EarlyErrorJavaScript::check_module_record(&self);
             }
             self.module_record_builder.build()
@@ -142,16 +207,21 @@ impl<'a> SemanticBuilder<'a> {
             ModuleRecord::default()
         };
 
+        let cfg = ControlFlowGraph::new(&self.nodes);
+
         let semantic = Semantic {
             source_text: self.source_text,
             source_type: self.source_type,
             trivias: self.trivias,
+            comment_attachments: OnceCell::new(),
             nodes: self.nodes,
             scopes: self.scope,
             symbols: self.symbols,
             module_record,
             jsdoc: self.jsdoc.build(),
-            unused_labels: self.unused_labels.labels,
+            unused_labels: self.labels.unused,
+            label_targets: self.labels.targets,
+            cfg,
         };
         SemanticBuilderReturn { semantic, errors: self.errors.into_inner() }
     }
@@ -161,12 +231,15 @@ impl<'a> SemanticBuilder<'a> {
             source_text: self.source_text,
             source_type: self.source_type,
             trivias: self.trivias,
+            comment_attachments: OnceCell::new(),
             nodes: self.nodes,
             scopes: self.scope,
             symbols: self.symbols,
             module_record: ModuleRecord::default(),
             jsdoc: self.jsdoc.build(),
-            unused_labels: self.unused_labels.labels,
+            unused_labels: self.labels.unused,
+            label_targets: self.labels.targets,
+            cfg: ControlFlowGraph::default(),
         }
     }
 
@@ -196,7 +269,8 @@ impl<'a> SemanticBuilder<'a> {
         fn is_strict(directives: &[Directive]) -> bool {
             directives.iter().any(|d| d.directive == "use strict")
         }
-        if let Some(flags) = ScopeTree::scope_flags_from_ast_kind(kind) {
+        let flags = ScopeTree::scope_flags_from_ast_kind(kind);
+        if let Some(flags) = flags {
             self.enter_scope(flags);
         }
         let strict_mode = match kind {
@@ -204,6 +278,8 @@ impl<'a> SemanticBuilder<'a> {
             AstKind::Function(func) => {
                 func.body.as_ref().is_some_and(|body| is_strict(&body.directives))
             }
+            // All parts of a class are strict mode code, including the scope of a class expression
+            AstKind::Class(_) => flags.is_some(),
             _ => false,
         };
         if strict_mode {
@@ -232,6 +308,25 @@ impl<'a> SemanticBuilder<'a> {
         }
     }
 
+    /// Resolve the label of the current `break` or `continue` statement to the enclosing labeled
+    /// statement of the same function. Undefined labels are reported by the checker.
+    fn resolve_label(&mut self, label: &LabelIdentifier) {
+        let function_id = self.function_stack.last().copied();
+        let mut curr_scope = self.labels.curr_scope;
+        while let Some(scope_index) = curr_scope {
+            let scope = &mut self.labels.scopes[scope_index];
+            if scope.function_id != function_id {
+                return;
+            }
+            if scope.name == label.name.as_str() {
+                scope.used = true;
+                self.labels.targets.insert(self.current_node_id, scope.node_id);
+                return;
+            }
+            curr_scope = scope.parent;
+        }
+    }
+
     /// Declares a `Symbol` for the node, adds it to symbol table, and binds it to the scope.
     ///
     /// includes: the `SymbolFlags` that node has in addition to its declaration type (eg: export, ambient, etc.)
@@ -292,7 +387,7 @@ impl<'a> SemanticBuilder<'a> {
         // let includes = includes | self.current_symbol_flags;
         let symbol_id =
             self.symbols.create_symbol(span, name.clone(), includes, self.current_scope_id);
-        if includes.is_variable() {
+        if includes.is_variable() || includes.contains(SymbolFlags::Import) {
             self.scope.add_binding(scope_id, name.clone(), symbol_id);
         }
         symbol_id
@@ -351,15 +446,12 @@ impl<'a> SemanticBuilder<'a> {
 
     pub fn enter_scope(&mut self, flags: ScopeFlags) {
         let mut flags = flags;
-        // Inherit strict mode for functions
+        // Inherit strict mode from the enclosing scope, and from the enclosing class
         // https://tc39.es/ecma262/#sec-strict-mode-code
-        let mut strict_mode = self.scope.root_flags().is_strict_mode();
         let parent_scope_id = self.current_scope_id;
         let parent_scope_flags = self.scope.get_flags(parent_scope_id);
-
-        if !strict_mode && parent_scope_flags.is_function() && parent_scope_flags.is_strict_mode() {
-            strict_mode = true;
-        }
+        let strict_mode = parent_scope_flags.is_strict_mode()
+            || self.current_node_flags.contains(NodeFlags::Class);
 
         // inherit flags for non-function scopes
         if !flags.contains(ScopeFlags::Function) {
@@ -423,16 +515,29 @@ impl<'a> Visit<'a> for SemanticBuilder<'a> {
 
         // create new self.current_node_id
         self.create_ast_node(kind);
+        if matches!(kind, AstKind::Program(_))
+            || ScopeTree::scope_flags_from_ast_kind(kind).is_some()
+        {
+            self.scope.set_node_id(self.current_scope_id, self.current_node_id);
+        }
 
         self.enter_kind(kind);
     }
 
     fn leave_node(&mut self, kind: AstKind<'a>) {
-        if self.check_syntax_error {
+        if self.options.contains(BuilderOptions::SyntaxErrors) {
             let node = self.nodes.get_node(self.current_node_id);
-            EarlyErrorJavaScript::run(node, self);
+            // Definition files only contain ambient declarations,
+            // the checks for values and runtime semantics do not apply to them.
+            if !self.source_type.is_typescript_definition() {
+                EarlyErrorJavaScript::run(node, self);
+            }
             EarlyErrorTypeScript::run(node, self);
         }
+        if self.target < EsTarget::ESNext && !self.source_type.is_typescript_definition() {
+            let node = self.nodes.get_node(self.current_node_id);
+            EsTargetChecker::run(node, self.target, self);
+        }
         self.leave_kind(kind);
         self.pop_ast_node();
         self.try_leave_scope(kind);
@@ -502,30 +607,18 @@ impl<'a> SemanticBuilder<'a> {
                 self.reference_jsx_element_name(elem);
             }
             AstKind::LabeledStatement(stmt) => {
-                self.unused_labels.scopes.push(LabeledScope {
+                self.labels.scopes.push(LabeledScope {
                     name: stmt.label.name.as_str(),
+                    node_id: self.current_node_id,
+                    function_id: self.function_stack.last().copied(),
                     used: false,
-                    parent: self.unused_labels.curr_scope,
+                    parent: self.labels.curr_scope,
                 });
-                self.unused_labels.curr_scope = self.unused_labels.scopes.len() - 1;
-            }
-            AstKind::ContinueStatement(stmt) => {
-                if let Some(label) = &stmt.label {
-                    let scope =
-                        self.unused_labels.scopes.iter_mut().rev().find(|x| x.name == label.name);
-                    if let Some(scope) = scope {
-                        scope.used = true;
-                    }
-                }
+                self.labels.curr_scope = Some(self.labels.scopes.len() - 1);
             }
-            AstKind::BreakStatement(stmt) => {
-                if let Some(label) = &stmt.label {
-                    let scope =
-                        self.unused_labels.scopes.iter_mut().rev().find(|x| x.name == label.name);
-                    if let Some(scope) = scope {
-                        scope.used = true;
-                    }
-                }
+            AstKind::ContinueStatement(ContinueStatement { label: Some(label), .. })
+            | AstKind::BreakStatement(BreakStatement { label: Some(label), .. }) => {
+                self.resolve_label(label);
             }
             AstKind::YieldExpression(_) => {
                 self.set_function_node_flag(NodeFlags::HasYield);
@@ -544,11 +637,13 @@ impl<'a> SemanticBuilder<'a> {
                 self.current_symbol_flags -= Self::symbol_flag_from_module_declaration(decl);
             }
             AstKind::LabeledStatement(_) => {
-                let scope = &self.unused_labels.scopes[self.unused_labels.curr_scope];
-                if !scope.used {
-                    self.unused_labels.labels.push(self.current_node_id);
+                if let Some(curr_scope) = self.labels.curr_scope {
+                    let scope = &self.labels.scopes[curr_scope];
+                    if !scope.used {
+                        self.labels.unused.push(scope.node_id);
+                    }
+                    self.labels.curr_scope = scope.parent;
                 }
-                self.unused_labels.curr_scope = scope.parent;
             }
             AstKind::Function(_) | AstKind::ArrowExpression(_) => {
                 self.function_stack.pop();
@@ -613,7 +708,20 @@ impl<'a> SemanticBuilder<'a> {
                     };
                     break;
                 }
-                (_, AstKind::SimpleAssignmentTarget(_) | AstKind::AssignmentTarget(_)) => {
+                // `b` in `({ a = b } = c)`, defaults of shorthand properties are children of the
+                // pattern
+                (
+                    _,
+                    AstKind::AssignmentTarget(AssignmentTarget::AssignmentTargetPattern(
+                        AssignmentTargetPattern::ObjectAssignmentTarget(target),
+                    )),
+                ) if is_shorthand_default(target, curr.kind().span()) => {
+                    flags |= ReferenceFlag::Read;
+                    break;
+                }
+                (_, AstKind::SimpleAssignmentTarget(_) | AstKind::AssignmentTarget(_))
+                // `a` in `[a = b] = c`
+                | (AstKind::AssignmentTarget(_), AstKind::AssignmentTargetWithDefault(_)) => {
                     flags |= ReferenceFlag::write();
                     // continue up tree
                 }
@@ -675,3 +783,13 @@ impl<'a> SemanticBuilder<'a> {
         }
     }
 }
+
+/// Whether `span` is the default value of a shorthand property, e.g. `b` in `({ a = b } = c)`
+fn is_shorthand_default(target: &ObjectAssignmentTarget, span: Span) -> bool {
+    target.properties.iter().any(|property| match property {
+        AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(property) => {
+            property.init.as_ref().is_some_and(|init| init.span() == span)
+        }
+        AssignmentTargetProperty::AssignmentTargetPropertyProperty(_) => false,
+    })
+}
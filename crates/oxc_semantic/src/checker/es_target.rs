@@ -0,0 +1,346 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstKind};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_span::{GetSpan, Span};
+use oxc_syntax::{
+    es_target::EsTarget,
+    operator::{AssignmentOperator, BinaryOperator, LogicalOperator},
+    NumberBase,
+};
+
+use crate::{builder::SemanticBuilder, AstNode};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("{0} are not available in the configured target {1}")]
+#[diagnostic(help("{0} require {2} or later"))]
+struct UnsupportedSyntax(&'static str, EsTarget, EsTarget, #[label] Span);
+
+/// Reports syntax newer than the configured [EsTarget]
+pub struct EsTargetChecker;
+
+impl EsTargetChecker {
+    pub fn run<'a>(node: &AstNode<'a>, target: EsTarget, ctx: &SemanticBuilder<'a>) {
+        if let Some((feature, required, span)) = required_target(node, ctx) {
+            if required > target {
+                ctx.error(UnsupportedSyntax(feature, target, required, span));
+            }
+        }
+    }
+}
+
+/// The syntax feature of the node and the first edition that supports it
+fn required_target<'a>(
+    node: &AstNode<'a>,
+    ctx: &SemanticBuilder<'a>,
+) -> Option<(&'static str, EsTarget, Span)> {
+    let kind = node.kind();
+    statement_target(kind)
+        .or_else(|| function_target(kind, ctx))
+        .or_else(|| class_target(node, ctx))
+        .or_else(|| literal_target(kind))
+        .or_else(|| object_target(node, ctx))
+        .or_else(|| operator_target(kind))
+        .or_else(|| match kind {
+            AstKind::ModuleDeclaration(decl) => module_declaration_target(decl),
+            _ => None,
+        })
+}
+
+fn statement_target(kind: AstKind) -> Option<(&'static str, EsTarget, Span)> {
+    let feature = match kind {
+        AstKind::VariableDeclaration(decl) => match decl.kind {
+            VariableDeclarationKind::Var => return None,
+            VariableDeclarationKind::Let | VariableDeclarationKind::Const => {
+                ("Lexical declarations", EsTarget::ES2015, decl.span)
+            }
+            VariableDeclarationKind::Using | VariableDeclarationKind::AwaitUsing => {
+                ("Using declarations", EsTarget::ESNext, decl.span)
+            }
+        },
+        AstKind::ForOfStatement(stmt) if stmt.r#await => {
+            ("for await...of loops", EsTarget::ES2018, stmt.span)
+        }
+        AstKind::ForOfStatement(stmt) => ("for...of loops", EsTarget::ES2015, stmt.span),
+        AstKind::CatchClause(clause) if clause.param.is_none() => {
+            ("Optional catch bindings", EsTarget::ES2019, clause.span)
+        }
+        _ => return None,
+    };
+    Some(feature)
+}
+
+fn function_target(kind: AstKind, ctx: &SemanticBuilder) -> Option<(&'static str, EsTarget, Span)> {
+    let feature = match kind {
+        AstKind::Function(func) => match (func.r#async, func.generator) {
+            (true, true) => ("Async generators", EsTarget::ES2018, func.span),
+            (true, false) => ("Async functions", EsTarget::ES2017, func.span),
+            (false, true) => ("Generators", EsTarget::ES2015, func.span),
+            (false, false) => return None,
+        },
+        AstKind::ArrowExpression(arrow) if arrow.r#async => {
+            ("Async functions", EsTarget::ES2017, arrow.span)
+        }
+        AstKind::ArrowExpression(arrow) => ("Arrow functions", EsTarget::ES2015, arrow.span),
+        AstKind::AwaitExpression(expr) if ctx.function_stack.is_empty() => {
+            ("Top-level await expressions", EsTarget::ES2022, expr.span)
+        }
+        _ => return None,
+    };
+    Some(feature)
+}
+
+fn class_target<'a>(
+    node: &AstNode<'a>,
+    ctx: &SemanticBuilder<'a>,
+) -> Option<(&'static str, EsTarget, Span)> {
+    let feature = match node.kind() {
+        AstKind::Class(class) => ("Classes", EsTarget::ES2015, class.span),
+        AstKind::PropertyDefinition(def) if !def.declare => {
+            ("Class fields", EsTarget::ES2022, def.span)
+        }
+        AstKind::MethodDefinition(def) if matches!(def.key, PropertyKey::PrivateIdentifier(_)) => {
+            ("Private methods", EsTarget::ES2022, def.span)
+        }
+        // Private names of declarations are reported by their class field or private method
+        AstKind::PrivateIdentifier(ident)
+            if !matches!(ctx.nodes.parent_kind(node.id()), Some(AstKind::PropertyKey(_))) =>
+        {
+            ("Private names", EsTarget::ES2022, ident.span)
+        }
+        AstKind::StaticBlock(block) => ("Class static blocks", EsTarget::ES2022, block.span),
+        AstKind::Decorator(decorator) => ("Decorators", EsTarget::ESNext, decorator.span),
+        _ => return None,
+    };
+    Some(feature)
+}
+
+fn literal_target(kind: AstKind) -> Option<(&'static str, EsTarget, Span)> {
+    match kind {
+        AstKind::TemplateLiteral(lit) => Some(("Template literals", EsTarget::ES2015, lit.span)),
+        AstKind::BigintLiteral(lit) => Some(("BigInt literals", EsTarget::ES2020, lit.span)),
+        AstKind::NumberLiteral(lit) => number_literal_target(lit),
+        AstKind::RegExpLiteral(lit) => regexp_literal_target(lit),
+        _ => None,
+    }
+}
+
+fn object_target<'a>(
+    node: &AstNode<'a>,
+    ctx: &SemanticBuilder<'a>,
+) -> Option<(&'static str, EsTarget, Span)> {
+    let parent_kind = || ctx.nodes.parent_kind(node.id());
+    let feature = match node.kind() {
+        AstKind::ObjectProperty(prop) if prop.computed => {
+            ("Computed property names", EsTarget::ES2015, prop.key.span())
+        }
+        AstKind::ObjectProperty(prop) if prop.shorthand => {
+            ("Shorthand properties", EsTarget::ES2015, prop.span)
+        }
+        AstKind::ObjectProperty(prop) if prop.method => {
+            ("Shorthand methods", EsTarget::ES2015, prop.span)
+        }
+        AstKind::SpreadElement(elem)
+            if matches!(parent_kind(), Some(AstKind::ObjectExpression(_))) =>
+        {
+            ("Object spread properties", EsTarget::ES2018, elem.span)
+        }
+        AstKind::SpreadElement(elem) => ("Spread elements", EsTarget::ES2015, elem.span),
+        AstKind::RestElement(elem) if matches!(parent_kind(), Some(AstKind::ObjectPattern(_))) => {
+            ("Object rest properties", EsTarget::ES2018, elem.span)
+        }
+        AstKind::RestElement(elem) => ("Rest elements", EsTarget::ES2015, elem.span),
+        AstKind::ArrayPattern(pat) => ("Destructuring patterns", EsTarget::ES2015, pat.span),
+        AstKind::ObjectPattern(pat) => ("Destructuring patterns", EsTarget::ES2015, pat.span),
+        AstKind::AssignmentPattern(pat) => ("Default values", EsTarget::ES2015, pat.span),
+        _ => return None,
+    };
+    Some(feature)
+}
+
+fn operator_target(kind: AstKind) -> Option<(&'static str, EsTarget, Span)> {
+    let feature = match kind {
+        AstKind::BinaryExpression(expr) if expr.operator == BinaryOperator::Exponential => {
+            ("Exponentiation operators", EsTarget::ES2016, expr.span)
+        }
+        AstKind::LogicalExpression(expr) if expr.operator == LogicalOperator::Coalesce => {
+            ("Nullish coalescing operators", EsTarget::ES2020, expr.span)
+        }
+        AstKind::AssignmentExpression(expr) if expr.operator == AssignmentOperator::Exponential => {
+            ("Exponentiation operators", EsTarget::ES2016, expr.span)
+        }
+        AstKind::AssignmentExpression(expr) if expr.operator.is_logical_operator() => {
+            ("Logical assignment operators", EsTarget::ES2021, expr.span)
+        }
+        AstKind::MemberExpression(expr) if expr.optional() => {
+            ("Optional chains", EsTarget::ES2020, expr.span())
+        }
+        AstKind::CallExpression(expr) if expr.optional => {
+            ("Optional chains", EsTarget::ES2020, expr.span)
+        }
+        AstKind::MetaProperty(prop) if prop.meta.name == "import" => {
+            ("import.meta properties", EsTarget::ES2020, prop.span)
+        }
+        AstKind::MetaProperty(prop) => ("new.target properties", EsTarget::ES2015, prop.span),
+        _ => return None,
+    };
+    Some(feature)
+}
+
+fn number_literal_target(lit: &NumberLiteral) -> Option<(&'static str, EsTarget, Span)> {
+    if lit.raw.contains('_') {
+        return Some(("Numeric separators", EsTarget::ES2021, lit.span));
+    }
+    // Legacy octal literals such as `017` are also `NumberBase::Octal`
+    let prefixed = matches!(lit.raw.as_bytes().get(1), Some(b'b' | b'B' | b'o' | b'O'));
+    if matches!(lit.base, NumberBase::Binary | NumberBase::Octal) && prefixed {
+        return Some(("Binary and octal literals", EsTarget::ES2015, lit.span));
+    }
+    None
+}
+
+fn regexp_literal_target(lit: &RegExpLiteral) -> Option<(&'static str, EsTarget, Span)> {
+    let flags = lit.regex.flags;
+    let feature = if flags.contains(RegExpFlags::V) {
+        ("Regular expression 'v' flags", EsTarget::ES2024)
+    } else if flags.contains(RegExpFlags::D) {
+        ("Regular expression 'd' flags", EsTarget::ES2022)
+    } else if flags.contains(RegExpFlags::S) {
+        ("Regular expression 's' flags", EsTarget::ES2018)
+    } else if flags.intersects(RegExpFlags::U | RegExpFlags::Y) {
+        ("Regular expression 'u' and 'y' flags", EsTarget::ES2015)
+    } else {
+        return None;
+    };
+    Some((feature.0, feature.1, lit.span))
+}
+
+fn module_declaration_target(decl: &ModuleDeclaration) -> Option<(&'static str, EsTarget, Span)> {
+    let with_clause = match decl {
+        ModuleDeclaration::ImportDeclaration(decl) => decl.with_clause.as_ref(),
+        ModuleDeclaration::ExportAllDeclaration(decl) => {
+            if decl.exported.is_some() && decl.with_clause.is_none() {
+                return Some(("Namespace re-exports", EsTarget::ES2020, decl.span));
+            }
+            decl.with_clause.as_ref()
+        }
+        ModuleDeclaration::ExportNamedDeclaration(decl) => decl.with_clause.as_ref(),
+        ModuleDeclaration::ExportDefaultDeclaration(_) => None,
+        _ => return None,
+    };
+    Some(with_clause.map_or_else(
+        || ("Modules", EsTarget::ES2015, decl.span()),
+        |clause| ("Import attributes", EsTarget::ESNext, clause.span),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+    use oxc_syntax::es_target::EsTarget;
+
+    use crate::SemanticBuilder;
+
+    fn errors(source_text: &str, target: EsTarget) -> Vec<String> {
+        let source_type = SourceType::default().with_module(true);
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        assert!(ret.errors.is_empty(), "{source_text}");
+        let program = allocator.alloc(ret.program);
+        let semantic_ret =
+            SemanticBuilder::new(source_text, source_type).with_target(target).build(program);
+        semantic_ret.errors.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn supported() {
+        let cases = [
+            ("a?.b; a?.(); a ?? b; 10n; import.meta", EsTarget::ES2020),
+            ("async function f() { await x } a **= 2", EsTarget::ES2017),
+            ("const { a, ...rest } = { ...b }", EsTarget::ES2018),
+            ("try {} catch {}", EsTarget::ES2019),
+            ("a ||= b; 1_000", EsTarget::ES2021),
+            ("class A { #a = 1; static {} m() { return #a in this } }", EsTarget::ES2022),
+            ("/[\\p{L}--a]/v", EsTarget::ES2024),
+            ("using x = y", EsTarget::ESNext),
+        ];
+        for (source_text, target) in cases {
+            assert_eq!(errors(source_text, target), Vec::<String>::new(), "{source_text}");
+        }
+        assert!(errors("var a = function () { return this.b; }; 0xff", EsTarget::ES5).is_empty());
+    }
+
+    #[test]
+    fn unsupported() {
+        let cases = [
+            (
+                "a?.b",
+                EsTarget::ES2018,
+                "Optional chains are not available in the configured target ES2018",
+            ),
+            (
+                "a ?? b",
+                EsTarget::ES2019,
+                "Nullish coalescing operators are not available in the configured target ES2019",
+            ),
+            (
+                "a ** b",
+                EsTarget::ES2015,
+                "Exponentiation operators are not available in the configured target ES2015",
+            ),
+            (
+                "async () => {}",
+                EsTarget::ES2016,
+                "Async functions are not available in the configured target ES2016",
+            ),
+            (
+                "({ ...a })",
+                EsTarget::ES2017,
+                "Object spread properties are not available in the configured target ES2017",
+            ),
+            (
+                "try {} catch {}",
+                EsTarget::ES2018,
+                "Optional catch bindings are not available in the configured target ES2018",
+            ),
+            (
+                "a &&= b",
+                EsTarget::ES2020,
+                "Logical assignment operators are not available in the configured target ES2020",
+            ),
+            (
+                "class A { static {} }",
+                EsTarget::ES2021,
+                "Class static blocks are not available in the configured target ES2021",
+            ),
+            (
+                "await x",
+                EsTarget::ES2021,
+                "Top-level await expressions are not available in the configured target ES2021",
+            ),
+            (
+                "/a/d",
+                EsTarget::ES2021,
+                "Regular expression 'd' flags are not available in the configured target ES2021",
+            ),
+            (
+                "let a = `b`",
+                EsTarget::ES5,
+                "Lexical declarations are not available in the configured target ES5",
+            ),
+            (
+                "using x = y",
+                EsTarget::ES2024,
+                "Using declarations are not available in the configured target ES2024",
+            ),
+        ];
+        for (source_text, target, message) in cases {
+            assert!(errors(source_text, target).iter().any(|e| e == message), "{source_text}");
+        }
+        assert_eq!(errors("let a = `b`; a => a", EsTarget::ES5).len(), 3);
+    }
+}
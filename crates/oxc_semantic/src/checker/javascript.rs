@@ -8,6 +8,7 @@ use oxc_diagnostics::{
     miette::{self, Diagnostic},
     thiserror::{self, Error},
 };
+use oxc_regexp::PatternParser;
 use oxc_span::{Atom, GetSpan, ModuleKind, Span};
 use oxc_syntax::{
     module_record::ExportLocalName,
@@ -56,6 +57,9 @@ impl EarlyErrorJavaScript {
             AstKind::BreakStatement(stmt) => check_break_statement(stmt, node, ctx),
             AstKind::ContinueStatement(stmt) => check_continue_statement(stmt, node, ctx),
             AstKind::LabeledStatement(stmt) => check_labeled_statement(stmt, node, ctx),
+            AstKind::VariableDeclaration(decl) if decl.kind.is_using() => {
+                check_using_declaration(decl, node, ctx);
+            }
             AstKind::ForInStatement(stmt) => check_for_statement_left(&stmt.left, true, node, ctx),
             AstKind::ForOfStatement(stmt) => check_for_statement_left(&stmt.left, false, node, ctx),
 
@@ -281,22 +285,7 @@ fn check_private_identifier<'a>(
         return;
     }
 
-    // Find enclosing classes
-    let mut classes = vec![];
-    for node_id in ctx.nodes.ancestors(node.id()).skip(1) {
-        let kind = ctx.nodes.kind(node_id);
-        if let AstKind::Class(class) = kind {
-            classes.push(class);
-        }
-        // stop lookup when the class is a heritage, e.g.
-        // `class C extends class extends class { x = this.#foo; } {} { #foo }`
-        // `class C extends function() { x = this.#foo; } { #foo }`
-        if matches!(kind, AstKind::ClassHeritage(_)) {
-            break;
-        }
-    }
-
-    if classes.is_empty() {
+    if ctx.nodes.private_name_classes(node.id()).next().is_none() {
         #[derive(Debug, Error, Diagnostic)]
         #[error("Private identifier '#{0}' is not allowed outside class bodies")]
         #[diagnostic()]
@@ -304,23 +293,7 @@ fn check_private_identifier<'a>(
         return ctx.error(PrivateNotInClass(ident.name.clone(), ident.span));
     };
 
-    // Check private identifier declarations in class.
-    // This implementations does a simple lookup for private identifier declarations inside a class.
-    // Performance can be improved by storing private identifiers for each class inside a lookup table,
-    // but there are not many private identifiers in the wild so we should be good fow now.
-    let found_private_ident = classes.iter().any(|class| {
-        class.body.body.iter().any(|def| {
-            // let key = match def {
-            // ClassElement::PropertyDefinition(def) => &def.key,
-            // ClassElement::MethodDefinition(def) => &def.key,
-            // _ => return false,
-            // };
-            matches!(def.property_key(), Some(PropertyKey::PrivateIdentifier(prop_ident))
-                if prop_ident.name == ident.name)
-        })
-    });
-
-    if !found_private_ident {
+    if ctx.nodes.private_member_declaration(node.id(), &ident.name).is_none() {
         #[derive(Debug, Error, Diagnostic)]
         #[error("Private field '{0}' must be declared in an enclosing class")]
         #[diagnostic()]
@@ -597,6 +570,12 @@ fn check_regexp_literal(lit: &RegExpLiteral, ctx: &SemanticBuilder<'_>) {
     let flags = lit.regex.flags;
     if flags.contains(RegExpFlags::U | RegExpFlags::V) {
         ctx.error(RegExpFlagUAndV(lit.span));
+        return;
+    }
+    // +1 for the opening `/`
+    let parser = PatternParser::new(lit.regex.pattern.as_str(), flags);
+    if let Err(error) = parser.with_span_offset(lit.span.start + 1).parse() {
+        ctx.error(error);
     }
 }
 
@@ -764,6 +743,35 @@ fn check_labeled_statement<'a>(
     }
 }
 
+fn check_using_declaration<'a>(
+    decl: &VariableDeclaration,
+    node: &AstNode<'a>,
+    ctx: &SemanticBuilder<'a>,
+) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("`{0}` declarations are not allowed at the top level of a script")]
+    #[diagnostic()]
+    struct UsingDeclarationAtTopLevelOfScript(String, #[label] Span);
+
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("`{0}` declarations are not allowed directly in a `case` or `default` clause")]
+    #[diagnostic(help("Wrap this declaration in a block statement"))]
+    struct UsingDeclarationInCaseClause(String, #[label] Span);
+
+    match ctx.nodes.parent_kind(node.id()) {
+        // It is ambiguous between script and module for `TypeScript`, see `check_module_declaration`
+        Some(AstKind::Program(_))
+            if ctx.source_type.is_script() && !ctx.source_type.is_typescript() =>
+        {
+            ctx.error(UsingDeclarationAtTopLevelOfScript(decl.kind.to_string(), decl.span));
+        }
+        Some(AstKind::SwitchCase(_)) => {
+            ctx.error(UsingDeclarationInCaseClause(decl.kind.to_string(), decl.span));
+        }
+        _ => {}
+    }
+}
+
 fn check_for_statement_left<'a>(
     left: &ForStatementLeft,
     is_for_in: bool,
@@ -780,8 +788,17 @@ fn check_for_statement_left<'a>(
     #[diagnostic()]
     struct UnexpectedInitializerInForLoopHead(&'static str, #[label] Span);
 
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("The left-hand side of a `for...in` statement cannot be a `{0}` declaration")]
+    #[diagnostic()]
+    struct UsingDeclarationInForIn(String, #[label] Span);
+
     let ForStatementLeft::VariableDeclaration(decl) = left else { return };
 
+    if is_for_in && decl.kind.is_using() {
+        return ctx.error(UsingDeclarationInForIn(decl.kind.to_string(), decl.span));
+    }
+
     // initializer is not allowed for for-in / for-of
     if decl.declarations.len() > 1 {
         return ctx.error(MultipleDeclarationInForLoopHead(
@@ -1011,6 +1028,11 @@ fn check_binary_expression(binary_expr: &BinaryExpression, ctx: &SemanticBuilder
     #[diagnostic(help("Wrap {0} expression in parentheses to enforce operator precedence"))]
     struct UnexpectedExponential(&'static str, #[label] Span);
 
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("Unexpected private name '#{0}' in an operand of '{1}'")]
+    #[diagnostic(help("Wrap the `in` expression in parentheses"))]
+    struct UnexpectedPrivateIn(Atom, &'static str, #[label] Span);
+
     if binary_expr.operator == BinaryOperator::Exponential {
         match binary_expr.left {
             // async () => await 5 ** 6
@@ -1025,6 +1047,29 @@ fn check_binary_expression(binary_expr: &BinaryExpression, ctx: &SemanticBuilder
             _ => {}
         }
     }
+
+    // `#x in obj` is a RelationalExpression, which is only the left operand of relational operators
+    // and an operand of the operators with a lower precedence, e.g. `1 + #x in obj` is an error
+    let operator = binary_expr.operator;
+    let is_shift_or_arithmetic = operator.is_arithmetic()
+        || matches!(
+            operator,
+            BinaryOperator::ShiftLeft
+                | BinaryOperator::ShiftRight
+                | BinaryOperator::ShiftRightZeroFill
+        );
+    let is_relational = operator.is_relational() || operator.is_compare();
+    for (operand, is_left) in [(&binary_expr.left, true), (&binary_expr.right, false)] {
+        if let Expression::PrivateInExpression(expr) = operand {
+            if is_shift_or_arithmetic || (is_relational && !is_left) {
+                ctx.error(UnexpectedPrivateIn(
+                    expr.left.name.clone(),
+                    operator.as_str(),
+                    expr.span,
+                ));
+            }
+        }
+    }
 }
 
 fn check_logical_expression(logical_expr: &LogicalExpression, ctx: &SemanticBuilder<'_>) {
@@ -1141,3 +1186,78 @@ fn check_yield_expression<'a>(
         ctx.error(AwaitOrYieldInParameter("yield", expr.span));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use crate::SemanticBuilder;
+
+    fn errors(source_text: &str) -> Vec<String> {
+        let source_type = SourceType::default().with_module(true);
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        assert!(ret.errors.is_empty(), "{source_text}");
+        let program = allocator.alloc(ret.program);
+        let semantic_ret = SemanticBuilder::new(source_text, source_type)
+            .with_check_syntax_error(true)
+            .build(program);
+        semantic_ret.errors.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn private_names() {
+        let pass = [
+            "class C { #x; m() { this.#x; } }",
+            "class C { #x() {} m() { return #x in this; } }",
+            "class C { static #x; m() { () => C.#x; } }",
+            "class C { #x; m() { class D { n() { this.#x; } } } }",
+            "class C { #x; m() { return #x in a + b; } }",
+            "class C { #x; m() { return #x in a in b; } }",
+            "class C { #x; m() { return #x in a === true; } }",
+            "class C { #x; m() { return #x in a && #x in b; } }",
+            "class C { #x; m() { return 1 + (#x in a); } }",
+        ];
+        for source_text in pass {
+            assert_eq!(errors(source_text), Vec::<String>::new(), "{source_text}");
+        }
+
+        let fail = [
+            ("this.#x", "Private identifier '#x' is not allowed outside class bodies"),
+            ("#x in a", "Private identifier '#x' is not allowed outside class bodies"),
+            (
+                "class C { m() { this.#x; } }",
+                "Private field 'x' must be declared in an enclosing class",
+            ),
+            (
+                "class C { m() { #x in this; } }",
+                "Private field 'x' must be declared in an enclosing class",
+            ),
+            (
+                "class C extends (class { m() { this.#x; } }) { #x }",
+                "Private field 'x' must be declared in an enclosing class",
+            ),
+            (
+                "class C { #x; m() { 1 + #x in a; } }",
+                "Unexpected private name '#x' in an operand of '+'",
+            ),
+            (
+                "class C { #x; m() { a << #x in b; } }",
+                "Unexpected private name '#x' in an operand of '<<'",
+            ),
+            (
+                "class C { #x; m() { a < #x in b; } }",
+                "Unexpected private name '#x' in an operand of '<'",
+            ),
+            (
+                "class C { #x; m() { a in #x in b; } }",
+                "Unexpected private name '#x' in an operand of 'in'",
+            ),
+        ];
+        for (source_text, error) in fail {
+            assert_eq!(errors(source_text), [error], "{source_text}");
+        }
+    }
+}
@@ -1,5 +1,7 @@
+mod es_target;
 mod javascript;
 mod typescript;
 
+pub use es_target::EsTargetChecker;
 pub use javascript::EarlyErrorJavaScript;
 pub use typescript::EarlyErrorTypeScript;
@@ -1,10 +1,12 @@
 #[allow(clippy::wildcard_imports)]
-use oxc_ast::{ast::*, AstKind};
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstKind};
 use oxc_diagnostics::{
     miette::{self, Diagnostic},
-    thiserror::Error,
+    thiserror::{self, Error},
 };
-use oxc_span::{GetSpan, Span};
+use oxc_span::{Atom, GetSpan, Span};
+use oxc_syntax::symbol::SymbolFlags;
+use rustc_hash::FxHashSet;
 
 use crate::{builder::SemanticBuilder, AstNode};
 
@@ -14,13 +16,114 @@ impl EarlyErrorTypeScript {
     pub fn run<'a>(node: &AstNode<'a>, ctx: &SemanticBuilder<'a>) {
         let kind = node.kind();
 
-        // should be removed when add more matches.
-        #[allow(clippy::single_match)]
         match kind {
             AstKind::SimpleAssignmentTarget(target) => check_simple_assignment_target(target, ctx),
+            AstKind::TSTypeParameter(param) if param.r#const => {
+                check_const_type_parameter(param, node, ctx);
+            }
             _ => {}
         }
     }
+
+    /// Assignments to `const` bindings. They are only runtime errors in JavaScript, which are
+    /// left to the `no-const-assign` lint rule.
+    pub fn check_const_assignments(ctx: &SemanticBuilder<'_>) {
+        #[derive(Debug, Error, Diagnostic)]
+        #[error("Cannot assign to '{0}' because it is a constant.")]
+        #[diagnostic()]
+        struct ConstAssignment(
+            Atom,
+            #[label("`{0}` is declared as a constant here")] Span,
+            #[label("It can not be assigned here")] Span,
+        );
+
+        for symbol_id in ctx.symbols.iter() {
+            if !ctx.symbols.get_flag(symbol_id).is_const_variable() {
+                continue;
+            }
+            for &reference_id in ctx.symbols.get_resolved_reference_ids(symbol_id) {
+                let reference = ctx.symbols.get_reference(reference_id);
+                if reference.is_write() {
+                    ctx.error(ConstAssignment(
+                        ctx.symbols.get_name(symbol_id).clone(),
+                        ctx.symbols.get_span(symbol_id),
+                        reference.span(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Errors of `tsc --isolatedModules`, i.e. code which cannot be transpiled one file at a time
+    pub fn check_isolated_modules<'a>(program: &Program<'a>, ctx: &SemanticBuilder<'a>) {
+        check_type_reexports(program, ctx);
+        check_ambient_const_enum_access(ctx);
+    }
+}
+
+/// `export { T }` is removed only if `T` is known to be a type, which a transpiler cannot know
+/// for imports, so re-exporting a type requires `export type`.
+fn check_type_reexports<'a>(program: &Program<'a>, ctx: &SemanticBuilder<'a>) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("Re-exporting a type when 'isolatedModules' is enabled requires using 'export type'.")]
+    #[diagnostic(help("Use `export type {{ {0} }}` instead"))]
+    struct TypeReexport(Atom, #[label] Span);
+
+    let mut type_imports = FxHashSet::default();
+    for stmt in &program.body {
+        let Statement::ModuleDeclaration(decl) = stmt else { continue };
+        let ModuleDeclaration::ImportDeclaration(decl) = &**decl else { continue };
+        if decl.import_kind.is_type() {
+            decl.bound_names(&mut |ident| {
+                type_imports.insert(ident.name.clone());
+            });
+        }
+    }
+
+    let root_scope_id = ctx.scope.root_scope_id();
+    for stmt in &program.body {
+        let Statement::ModuleDeclaration(decl) = stmt else { continue };
+        let ModuleDeclaration::ExportNamedDeclaration(decl) = &**decl else { continue };
+        if decl.source.is_some() || decl.export_kind.is_type() {
+            continue;
+        }
+        for specifier in decl.specifiers.iter().filter(|s| !s.export_kind.is_type()) {
+            let name = specifier.local.name();
+            let is_type = type_imports.contains(name)
+                || ctx.scope.get_binding(root_scope_id, name).is_some_and(|symbol_id| {
+                    let flags = ctx.symbols.get_flag(symbol_id);
+                    flags.intersects(
+                        SymbolFlags::TypeAlias
+                            | SymbolFlags::Interface
+                            | SymbolFlags::NameSpaceModule,
+                    ) && !flags.intersects(
+                        SymbolFlags::Value | SymbolFlags::Function | SymbolFlags::ImportBinding,
+                    )
+                });
+            if is_type {
+                ctx.error(TypeReexport(name.clone(), specifier.span));
+            }
+        }
+    }
+}
+
+/// Accessing a `declare const enum` requires inlining its members from the declaration, which may
+/// be in another file.
+fn check_ambient_const_enum_access(ctx: &SemanticBuilder<'_>) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("Cannot access ambient const enums when 'isolatedModules' is enabled.")]
+    #[diagnostic()]
+    struct AmbientConstEnumAccess(#[label] Span);
+
+    for symbol_id in ctx.symbols.iter() {
+        if !ctx.symbols.get_flag(symbol_id).contains(SymbolFlags::ConstEnum | SymbolFlags::Ambient)
+        {
+            continue;
+        }
+        for &reference_id in ctx.symbols.get_resolved_reference_ids(symbol_id) {
+            ctx.error(AmbientConstEnumAccess(ctx.symbols.get_reference(reference_id).span()));
+        }
+    }
 }
 
 fn check_simple_assignment_target<'a>(
@@ -43,3 +146,113 @@ fn check_simple_assignment_target<'a>(
         }
     }
 }
+
+fn check_const_type_parameter<'a>(
+    param: &TSTypeParameter<'a>,
+    node: &AstNode<'a>,
+    ctx: &SemanticBuilder<'a>,
+) {
+    #[derive(Debug, Error, Diagnostic)]
+    #[error("'const' modifier can only appear on a type parameter of a function, method or class")]
+    #[diagnostic()]
+    struct ConstTypeParameter(#[label] Span);
+
+    // TSTypeParameter -> TSTypeParameterDeclaration -> the owner of the type parameters
+    let owner = ctx.nodes.parent_id(node.id()).and_then(|id| ctx.nodes.parent_kind(id));
+    if matches!(
+        owner,
+        Some(AstKind::TSInterfaceDeclaration(_) | AstKind::TSTypeAliasDeclaration(_))
+    ) {
+        ctx.error(ConstTypeParameter(param.span));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use crate::SemanticBuilder;
+
+    fn build_errors(source_text: &str, isolated_modules: bool) -> Vec<String> {
+        let source_type = SourceType::default().with_module(true).with_typescript(true);
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        assert!(ret.errors.is_empty(), "{source_text}");
+        let program = allocator.alloc(ret.program);
+        let semantic_ret = SemanticBuilder::new(source_text, source_type)
+            .with_check_syntax_error(!isolated_modules)
+            .with_const_assignments(!isolated_modules)
+            .with_isolated_modules(isolated_modules)
+            .build(program);
+        semantic_ret.errors.iter().map(ToString::to_string).collect()
+    }
+
+    fn errors(source_text: &str) -> Vec<String> {
+        build_errors(source_text, true)
+    }
+
+    #[test]
+    fn const_assignments() {
+        let pass = [
+            "let a = 1; a = 2;",
+            "const a = 1; { let a; a = 2; }",
+            "const a = { b: 1 }; a.b = 2;",
+            "for (const a of b) { a; }",
+        ];
+        for source_text in pass {
+            assert_eq!(build_errors(source_text, false), Vec::<String>::new(), "{source_text}");
+        }
+
+        let fail = [
+            ("const a = 1; a = 2;", 1),
+            ("const a = 1; a++; [a] = [1]; ({ a } = {});", 3),
+            ("const a = 1; function f() { a += 1; }", 1),
+            ("for (const a of b) { a = 1; }", 1),
+        ];
+        for (source_text, count) in fail {
+            let errors = build_errors(source_text, false);
+            assert_eq!(errors.len(), count, "{source_text}");
+            for error in errors {
+                assert_eq!(error, "Cannot assign to 'a' because it is a constant.");
+            }
+        }
+        // Opt-in, which `errors` does not
+        assert_eq!(errors("const a = 1; a = 2;"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn isolated_modules() {
+        let pass = [
+            "type T = 1; export type { T }",
+            "interface I {} export { type I }",
+            "import type { T } from 'a'; export type { T }",
+            "import { T } from 'a'; export { T }",
+            "class C {} interface C {} export { C }",
+            "namespace N { export const a = 1 }\nexport { N }",
+            "export type { T } from 'a'",
+            "const enum E { A } E.A",
+            "declare const enum E { A } let e: E",
+        ];
+        for source_text in pass {
+            assert_eq!(errors(source_text), Vec::<String>::new(), "{source_text}");
+        }
+
+        let reexport =
+            "Re-exporting a type when 'isolatedModules' is enabled requires using 'export type'.";
+        let fail = [
+            ("type T = 1; export { T }", reexport),
+            ("interface I {} export { I as J }", reexport),
+            ("import type { T } from 'a'; export { T }", reexport),
+            ("namespace N { export type T = 1 }\nexport { N }", reexport),
+            (
+                "declare const enum E { A } E.A",
+                "Cannot access ambient const enums when 'isolatedModules' is enabled.",
+            ),
+        ];
+        for (source_text, error) in fail {
+            assert_eq!(errors(source_text), [error], "{source_text}");
+        }
+    }
+}
@@ -0,0 +1,381 @@
+//! Partial evaluation of constant expressions, e.g. `1 + 2`, `"a" + 1` or `typeof 1`
+//!
+//! Follows the abstract operations of the specification, such as `ToNumber` and `ToString`,
+//! for primitive values. Expressions whose value depends on objects are not evaluated.
+
+use num_bigint::BigInt;
+use oxc_ast::ast::{Expression, UnaryExpression};
+use oxc_syntax::operator::{BinaryOperator, LogicalOperator, UnaryOperator};
+
+use crate::Semantic;
+
+/// The primitive value of a constant expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstantValue {
+    Number(f64),
+    BigInt(BigInt),
+    String(String),
+    Boolean(bool),
+    Null,
+    Undefined,
+}
+
+impl ConstantValue {
+    /// [ToBoolean](https://tc39.es/ecma262/#sec-toboolean)
+    pub fn to_boolean(&self) -> bool {
+        match self {
+            Self::Number(n) => *n != 0.0 && !n.is_nan(),
+            Self::BigInt(n) => *n != BigInt::default(),
+            Self::String(s) => !s.is_empty(),
+            Self::Boolean(b) => *b,
+            Self::Null | Self::Undefined => false,
+        }
+    }
+
+    /// [ToNumber](https://tc39.es/ecma262/#sec-tonumber), `None` for BigInts, which throw
+    pub fn to_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::BigInt(_) => None,
+            Self::String(s) => Some(string_to_number(s)),
+            Self::Boolean(b) => Some(f64::from(u8::from(*b))),
+            Self::Null => Some(0.0),
+            Self::Undefined => Some(f64::NAN),
+        }
+    }
+
+    /// [ToString](https://tc39.es/ecma262/#sec-tostring)
+    pub fn to_js_string(&self) -> String {
+        match self {
+            Self::Number(n) => ryu_js::Buffer::new().format(*n).to_string(),
+            Self::BigInt(n) => n.to_string(),
+            Self::String(s) => s.clone(),
+            Self::Boolean(b) => b.to_string(),
+            Self::Null => "null".to_string(),
+            Self::Undefined => "undefined".to_string(),
+        }
+    }
+
+    /// The result of `typeof`
+    pub fn type_of(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "number",
+            Self::BigInt(_) => "bigint",
+            Self::String(_) => "string",
+            Self::Boolean(_) => "boolean",
+            Self::Null => "object",
+            Self::Undefined => "undefined",
+        }
+    }
+
+    /// The value of `operator value` for the operators which only read primitives, i.e. `-`, `+`,
+    /// `~`, `!` and `void`
+    pub fn unary(operator: UnaryOperator, value: &Self) -> Option<Self> {
+        match operator {
+            UnaryOperator::Void => Some(Self::Undefined),
+            UnaryOperator::LogicalNot => Some(Self::Boolean(!value.to_boolean())),
+            UnaryOperator::UnaryNegation => match value {
+                Self::BigInt(n) => Some(Self::BigInt(-n)),
+                value => Some(Self::Number(-value.to_number()?)),
+            },
+            UnaryOperator::UnaryPlus => Some(Self::Number(value.to_number()?)),
+            UnaryOperator::BitwiseNot => {
+                Some(Self::Number(f64::from(!to_int32(value.to_number()?))))
+            }
+            UnaryOperator::Typeof | UnaryOperator::Delete => None,
+        }
+    }
+
+    /// The value of `left operator right`, `None` for operators on objects, and for operations on
+    /// BigInts which may throw
+    pub fn binary(operator: BinaryOperator, left: &Self, right: &Self) -> Option<Self> {
+        evaluate_binary(operator, left, right)
+    }
+
+    /// [IsStrictlyEqual](https://tc39.es/ecma262/#sec-isstrictlyequal)
+    #[allow(clippy::float_cmp)]
+    fn strict_equals(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) => a == b,
+            _ => self == other,
+        }
+    }
+
+    /// [IsLooselyEqual](https://tc39.es/ecma262/#sec-islooselyequal), `None` for comparisons
+    /// of BigInts with other types
+    fn loose_equals(&self, other: &Self) -> Option<bool> {
+        match (self, other) {
+            (Self::Null | Self::Undefined, Self::Null | Self::Undefined) => Some(true),
+            (Self::Null | Self::Undefined, _) | (_, Self::Null | Self::Undefined) => Some(false),
+            _ if std::mem::discriminant(self) == std::mem::discriminant(other) => {
+                Some(self.strict_equals(other))
+            }
+            (Self::BigInt(_), _) | (_, Self::BigInt(_)) => None,
+            _ => Some(
+                Self::Number(self.to_number()?).strict_equals(&Self::Number(other.to_number()?)),
+            ),
+        }
+    }
+}
+
+/// [StringToNumber](https://tc39.es/ecma262/#sec-stringtonumber)
+fn string_to_number(s: &str) -> f64 {
+    let s = s.trim_matches(|c: char| c.is_whitespace() || c == '\u{feff}');
+    let radix = match s.get(..2) {
+        Some("0x" | "0X") => 16,
+        Some("0o" | "0O") => 8,
+        Some("0b" | "0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        let digits = &s[2..];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return f64::NAN;
+        }
+        return digits.chars().fold(0.0, |n, c| {
+            n.mul_add(f64::from(radix), f64::from(c.to_digit(radix).unwrap_or_default()))
+        });
+    }
+    match s {
+        "" => 0.0,
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        // Rust also parses `inf` and `NaN`
+        _ if s
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-')) =>
+        {
+            s.parse().unwrap_or(f64::NAN)
+        }
+        _ => f64::NAN,
+    }
+}
+
+/// [ToInt32](https://tc39.es/ecma262/#sec-toint32)
+#[allow(clippy::cast_possible_wrap)]
+fn to_int32(n: f64) -> i32 {
+    to_uint32(n) as i32
+}
+
+/// [ToUint32](https://tc39.es/ecma262/#sec-touint32)
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_uint32(n: f64) -> u32 {
+    if !n.is_finite() {
+        return 0;
+    }
+    let n = n.trunc() % 4_294_967_296.0;
+    (if n < 0.0 { n + 4_294_967_296.0 } else { n }) as u32
+}
+
+impl<'a> Semantic<'a> {
+    /// The value of the expression if it is a constant primitive, e.g. `3` for `1 + 2`.
+    ///
+    /// The value does not tell whether evaluating the expression has side effects, e.g. `void f()`
+    /// is `undefined`, see [`Semantic::side_effects`].
+    pub fn evaluate(&self, expr: &Expression<'a>) -> Option<ConstantValue> {
+        match expr {
+            Expression::BooleanLiteral(lit) => Some(ConstantValue::Boolean(lit.value)),
+            Expression::NullLiteral(_) => Some(ConstantValue::Null),
+            Expression::NumberLiteral(lit) => Some(ConstantValue::Number(lit.value)),
+            Expression::BigintLiteral(lit) => Some(ConstantValue::BigInt(lit.value.clone())),
+            Expression::StringLiteral(lit) => Some(ConstantValue::String(lit.value.to_string())),
+            Expression::TemplateLiteral(lit) => {
+                let mut value = String::new();
+                for (i, quasi) in lit.quasis.iter().enumerate() {
+                    value.push_str(quasi.value.cooked.as_ref()?);
+                    if let Some(expr) = lit.expressions.get(i) {
+                        value.push_str(&self.evaluate(expr)?.to_js_string());
+                    }
+                }
+                Some(ConstantValue::String(value))
+            }
+            Expression::Identifier(ident) if self.is_reference_to_global_variable(ident) => {
+                match ident.name.as_str() {
+                    "undefined" => Some(ConstantValue::Undefined),
+                    "NaN" => Some(ConstantValue::Number(f64::NAN)),
+                    "Infinity" => Some(ConstantValue::Number(f64::INFINITY)),
+                    _ => None,
+                }
+            }
+            Expression::UnaryExpression(expr) => self.evaluate_unary(expr),
+            Expression::BinaryExpression(expr) => {
+                let left = self.evaluate(&expr.left)?;
+                let right = self.evaluate(&expr.right)?;
+                evaluate_binary(expr.operator, &left, &right)
+            }
+            Expression::LogicalExpression(expr) => {
+                let left = self.evaluate(&expr.left)?;
+                let short_circuit = match expr.operator {
+                    LogicalOperator::And => !left.to_boolean(),
+                    LogicalOperator::Or => left.to_boolean(),
+                    LogicalOperator::Coalesce => {
+                        !matches!(left, ConstantValue::Null | ConstantValue::Undefined)
+                    }
+                };
+                if short_circuit {
+                    Some(left)
+                } else {
+                    self.evaluate(&expr.right)
+                }
+            }
+            Expression::ConditionalExpression(expr) => {
+                if self.evaluate_boolean(&expr.test)? {
+                    self.evaluate(&expr.consequent)
+                } else {
+                    self.evaluate(&expr.alternate)
+                }
+            }
+            Expression::SequenceExpression(expr) => self.evaluate(expr.expressions.last()?),
+            Expression::ParenthesizedExpression(expr) => self.evaluate(&expr.expression),
+            Expression::TSAsExpression(expr) => self.evaluate(&expr.expression),
+            Expression::TSSatisfiesExpression(expr) => self.evaluate(&expr.expression),
+            Expression::TSTypeAssertion(expr) => self.evaluate(&expr.expression),
+            Expression::TSNonNullExpression(expr) => self.evaluate(&expr.expression),
+            _ => None,
+        }
+    }
+
+    /// The value of the expression converted to a boolean, including for objects and functions,
+    /// which are always truthy
+    pub fn evaluate_boolean(&self, expr: &Expression<'a>) -> Option<bool> {
+        match expr.get_inner_expression() {
+            Expression::ArrayExpression(_)
+            | Expression::ObjectExpression(_)
+            | Expression::RegExpLiteral(_)
+            | Expression::FunctionExpression(_)
+            | Expression::ArrowExpression(_)
+            | Expression::ClassExpression(_) => Some(true),
+            _ => self.evaluate(expr).map(|value| value.to_boolean()),
+        }
+    }
+
+    /// The result of `typeof` on the expression, including for objects and functions
+    pub fn evaluate_typeof(&self, expr: &Expression<'a>) -> Option<&'static str> {
+        match expr.get_inner_expression() {
+            Expression::ArrayExpression(_)
+            | Expression::ObjectExpression(_)
+            | Expression::RegExpLiteral(_) => Some("object"),
+            Expression::FunctionExpression(_)
+            | Expression::ArrowExpression(_)
+            | Expression::ClassExpression(_) => Some("function"),
+            _ => self.evaluate(expr).map(|value| value.type_of()),
+        }
+    }
+
+    fn evaluate_unary(&self, expr: &UnaryExpression<'a>) -> Option<ConstantValue> {
+        match expr.operator {
+            UnaryOperator::Void => Some(ConstantValue::Undefined),
+            UnaryOperator::Typeof => {
+                self.evaluate_typeof(&expr.argument).map(|t| ConstantValue::String(t.to_string()))
+            }
+            UnaryOperator::LogicalNot => {
+                self.evaluate_boolean(&expr.argument).map(|b| ConstantValue::Boolean(!b))
+            }
+            UnaryOperator::UnaryNegation | UnaryOperator::UnaryPlus | UnaryOperator::BitwiseNot => {
+                ConstantValue::unary(expr.operator, &self.evaluate(&expr.argument)?)
+            }
+            UnaryOperator::Delete => None,
+        }
+    }
+}
+
+/// `None` for operators on objects, and for operations on BigInts which may throw
+#[allow(clippy::float_cmp)]
+fn evaluate_binary(
+    operator: BinaryOperator,
+    left: &ConstantValue,
+    right: &ConstantValue,
+) -> Option<ConstantValue> {
+    use ConstantValue::{BigInt, Boolean, Number, String};
+    let value = match operator {
+        BinaryOperator::Equality => Boolean(left.loose_equals(right)?),
+        BinaryOperator::Inequality => Boolean(!left.loose_equals(right)?),
+        BinaryOperator::StrictEquality => Boolean(left.strict_equals(right)),
+        BinaryOperator::StrictInequality => Boolean(!left.strict_equals(right)),
+        BinaryOperator::Addition => match (left, right) {
+            (String(_), _) | (_, String(_)) => String(left.to_js_string() + &right.to_js_string()),
+            (BigInt(a), BigInt(b)) => BigInt(a + b),
+            _ => Number(left.to_number()? + right.to_number()?),
+        },
+        BinaryOperator::Subtraction | BinaryOperator::Multiplication => match (left, right) {
+            (BigInt(a), BigInt(b)) if operator == BinaryOperator::Subtraction => BigInt(a - b),
+            (BigInt(a), BigInt(b)) => BigInt(a * b),
+            _ => {
+                let (a, b) = (left.to_number()?, right.to_number()?);
+                Number(if operator == BinaryOperator::Subtraction { a - b } else { a * b })
+            }
+        },
+        BinaryOperator::Division => Number(left.to_number()? / right.to_number()?),
+        BinaryOperator::Remainder => Number(left.to_number()? % right.to_number()?),
+        BinaryOperator::Exponential => {
+            let (base, exponent) = (left.to_number()?, right.to_number()?);
+            // Unlike `powf`, `1 ** NaN` and `1 ** Infinity` are `NaN`
+            if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+                Number(f64::NAN)
+            } else {
+                Number(base.powf(exponent))
+            }
+        }
+        BinaryOperator::LessThan
+        | BinaryOperator::LessEqualThan
+        | BinaryOperator::GreaterThan
+        | BinaryOperator::GreaterEqualThan => {
+            let ordering = match (left, right) {
+                // Strings are compared by UTF-16 code units
+                (String(a), String(b)) => Some(a.encode_utf16().cmp(b.encode_utf16())),
+                (BigInt(_), _) | (_, BigInt(_)) => return None,
+                _ => left.to_number()?.partial_cmp(&right.to_number()?),
+            };
+            Boolean(ordering.is_some_and(|ordering| match operator {
+                BinaryOperator::LessThan => ordering.is_lt(),
+                BinaryOperator::LessEqualThan => ordering.is_le(),
+                BinaryOperator::GreaterThan => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        BinaryOperator::BitwiseOR | BinaryOperator::BitwiseXOR | BinaryOperator::BitwiseAnd => {
+            let (a, b) = (to_int32(left.to_number()?), to_int32(right.to_number()?));
+            Number(f64::from(match operator {
+                BinaryOperator::BitwiseOR => a | b,
+                BinaryOperator::BitwiseXOR => a ^ b,
+                _ => a & b,
+            }))
+        }
+        BinaryOperator::ShiftLeft => {
+            let shift = to_uint32(right.to_number()?) & 31;
+            Number(f64::from(to_int32(left.to_number()?).wrapping_shl(shift)))
+        }
+        BinaryOperator::ShiftRight => {
+            let shift = to_uint32(right.to_number()?) & 31;
+            Number(f64::from(to_int32(left.to_number()?) >> shift))
+        }
+        BinaryOperator::ShiftRightZeroFill => {
+            let shift = to_uint32(right.to_number()?) & 31;
+            Number(f64::from(to_uint32(left.to_number()?) >> shift))
+        }
+        BinaryOperator::In | BinaryOperator::Instanceof => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{string_to_number, to_int32, ConstantValue};
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn conversions() {
+        assert_eq!(string_to_number(" 12 "), 12.0);
+        assert_eq!(string_to_number(""), 0.0);
+        assert_eq!(string_to_number("0x1F"), 31.0);
+        assert_eq!(string_to_number("-Infinity"), f64::NEG_INFINITY);
+        assert!(string_to_number("inf").is_nan());
+        assert!(string_to_number("1a").is_nan());
+        assert_eq!(to_int32(4_294_967_297.0), 1);
+        assert_eq!(to_int32(-1.5), -1);
+        assert_eq!(to_int32(2_147_483_648.0), -2_147_483_648);
+        assert_eq!(ConstantValue::Number(-0.0).to_js_string(), "0");
+        assert_eq!(ConstantValue::Number(1e21).to_js_string(), "1e+21");
+        assert_eq!(ConstantValue::Number(0.1).to_js_string(), "0.1");
+    }
+}
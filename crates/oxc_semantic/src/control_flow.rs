@@ -0,0 +1,564 @@
+//! Control flow graph
+//!
+//! Each function, arrow function and the program itself get their own graph of basic blocks.
+//! Blocks contain the ids of the statements executed in them, in order.
+//! Expressions are not split into blocks, so short-circuiting operators and conditional
+//! expressions do not create branches.
+//!
+//! `break`, `continue` and `return` jump straight to their target, skipping enclosing `finally`
+//! blocks, so code after a `try` statement with a `finally` block is considered reachable if the
+//! `finally` block is.
+
+use std::ops::Range;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstKind};
+use oxc_index::{define_index_type, IndexVec};
+use oxc_span::Atom;
+use rustc_hash::FxHashMap;
+
+use crate::node::{AstNodeId, AstNodes};
+
+define_index_type! {
+    pub struct BasicBlockId = usize;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeType {
+    /// Sequential flow, e.g. into the block after an `if` statement
+    Normal,
+    /// Taken when the test of an `if` statement or loop is truthy
+    True,
+    /// Taken when the test of an `if` statement or loop is falsy
+    False,
+    /// `break`, `continue` and `return`
+    Jump,
+    /// From the end of a loop body back to its test
+    Backedge,
+    /// From code which may throw to a `catch` or `finally` block, or to the exit of the function
+    Exception,
+}
+
+/// A sequence of statements which are executed one after another
+#[derive(Debug, Default)]
+pub struct BasicBlock {
+    statements: Vec<AstNodeId>,
+    successors: Vec<(BasicBlockId, EdgeType)>,
+    predecessors: Vec<BasicBlockId>,
+    reachable: bool,
+}
+
+impl BasicBlock {
+    /// Statements in execution order. Compound statements such as `if` are in the block which
+    /// evaluates their test, while the statements they contain are in other blocks.
+    pub fn statements(&self) -> &[AstNodeId] {
+        &self.statements
+    }
+
+    pub fn successors(&self) -> &[(BasicBlockId, EdgeType)] {
+        &self.successors
+    }
+
+    pub fn predecessors(&self) -> &[BasicBlockId] {
+        &self.predecessors
+    }
+
+    /// Whether the block can be reached from the entry of its function
+    pub fn is_reachable(&self) -> bool {
+        self.reachable
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FunctionBlocks {
+    entry: BasicBlockId,
+    exit: BasicBlockId,
+    /// All blocks of the function, including `entry` and `exit`
+    blocks: Range<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct ControlFlowGraph {
+    basic_blocks: IndexVec<BasicBlockId, BasicBlock>,
+    /// Keyed by the ids of `Program`, `Function` and `ArrowExpression` nodes
+    functions: FxHashMap<AstNodeId, FunctionBlocks>,
+    /// The block of each statement
+    statement_blocks: FxHashMap<AstNodeId, BasicBlockId>,
+}
+
+impl ControlFlowGraph {
+    /// Build the graphs of the program and all functions in `nodes`
+    pub fn new(nodes: &AstNodes<'_>) -> Self {
+        let mut builder = ControlFlowBuilder::new(nodes);
+        for node in nodes.iter() {
+            let statements = match node.kind() {
+                AstKind::Program(program) => &program.body,
+                AstKind::Function(Function { body: Some(body), .. }) => &body.statements,
+                AstKind::ArrowExpression(arrow) => &arrow.body.statements,
+                _ => continue,
+            };
+            builder.build_function(node.id(), statements);
+        }
+        let mut cfg = builder.cfg;
+        cfg.mark_reachable();
+        cfg
+    }
+
+    pub fn basic_block(&self, id: BasicBlockId) -> &BasicBlock {
+        &self.basic_blocks[id]
+    }
+
+    pub fn basic_blocks(&self) -> impl Iterator<Item = (BasicBlockId, &BasicBlock)> + '_ {
+        self.basic_blocks.iter_enumerated()
+    }
+
+    /// The first block of a `Program`, `Function` or `ArrowExpression` node
+    pub fn function_entry(&self, function_id: AstNodeId) -> Option<BasicBlockId> {
+        self.functions.get(&function_id).map(|function| function.entry)
+    }
+
+    /// The block reached by returning or throwing out of a `Program`, `Function` or
+    /// `ArrowExpression` node. It contains no statements.
+    pub fn function_exit(&self, function_id: AstNodeId) -> Option<BasicBlockId> {
+        self.functions.get(&function_id).map(|function| function.exit)
+    }
+
+    /// All blocks of a `Program`, `Function` or `ArrowExpression` node, including its entry and
+    /// exit, but not the blocks of nested functions
+    pub fn function_blocks(
+        &self,
+        function_id: AstNodeId,
+    ) -> Option<impl Iterator<Item = BasicBlockId>> {
+        let function = self.functions.get(&function_id)?;
+        Some(function.blocks.clone().map(BasicBlockId::new))
+    }
+
+    /// The block containing a statement, `None` for other nodes
+    pub fn statement_block(&self, statement_id: AstNodeId) -> Option<BasicBlockId> {
+        self.statement_blocks.get(&statement_id).copied()
+    }
+
+    /// Whether a statement can be executed. Nodes which are not statements are always reachable,
+    /// check their closest enclosing statement instead.
+    pub fn is_reachable(&self, statement_id: AstNodeId) -> bool {
+        self.statement_block(statement_id).map_or(true, |id| self.basic_blocks[id].reachable)
+    }
+
+    /// Number of linearly independent paths through a function, `edges - blocks + 2` counting
+    /// only reachable blocks
+    pub fn cyclomatic_complexity(&self, function_id: AstNodeId) -> Option<usize> {
+        let function = self.functions.get(&function_id)?;
+        let blocks = self.basic_blocks.raw[function.blocks.clone()]
+            .iter()
+            .filter(|block| block.reachable)
+            .collect::<Vec<_>>();
+        let edges = blocks.iter().map(|block| block.successors.len()).sum::<usize>();
+        Some(edges + 2 - blocks.len())
+    }
+
+    fn mark_reachable(&mut self) {
+        let mut stack = self.functions.values().map(|function| function.entry).collect::<Vec<_>>();
+        while let Some(id) = stack.pop() {
+            let block = &mut self.basic_blocks[id];
+            if block.reachable {
+                continue;
+            }
+            block.reachable = true;
+            stack.extend(block.successors.iter().map(|(successor, _)| *successor));
+        }
+    }
+}
+
+/// Where `break` and `continue` statements jump to
+struct JumpTarget<'a> {
+    labels: Vec<&'a Atom>,
+    break_block: BasicBlockId,
+    /// `None` for labeled statements which are not loops
+    continue_block: Option<BasicBlockId>,
+    /// Loops and `switch` statements are the targets of `break` statements without a label
+    unlabeled: bool,
+}
+
+struct ControlFlowBuilder<'n, 'a> {
+    nodes: &'n AstNodes<'a>,
+    cfg: ControlFlowGraph,
+    current: BasicBlockId,
+    exit: BasicBlockId,
+    jump_targets: Vec<JumpTarget<'a>>,
+    /// Labels of the labeled statements directly enclosing the statement being built
+    pending_labels: Vec<&'a Atom>,
+    /// Blocks which thrown exceptions go to, innermost last
+    exception_targets: Vec<BasicBlockId>,
+}
+
+impl<'n, 'a> ControlFlowBuilder<'n, 'a> {
+    fn new(nodes: &'n AstNodes<'a>) -> Self {
+        Self {
+            nodes,
+            cfg: ControlFlowGraph::default(),
+            current: BasicBlockId::new(0),
+            exit: BasicBlockId::new(0),
+            jump_targets: vec![],
+            pending_labels: vec![],
+            exception_targets: vec![],
+        }
+    }
+
+    fn build_function(&mut self, function_id: AstNodeId, statements: &'a [Statement<'a>]) {
+        let start = self.cfg.basic_blocks.len();
+        let entry = self.new_block();
+        self.exit = self.new_block();
+        self.current = entry;
+        self.build_statements(statements);
+        self.add_edge(self.current, self.exit, EdgeType::Normal);
+        let blocks = start..self.cfg.basic_blocks.len();
+        self.cfg.functions.insert(function_id, FunctionBlocks { entry, exit: self.exit, blocks });
+    }
+
+    fn new_block(&mut self) -> BasicBlockId {
+        self.cfg.basic_blocks.push(BasicBlock::default())
+    }
+
+    fn add_edge(&mut self, from: BasicBlockId, to: BasicBlockId, edge_type: EdgeType) {
+        let successors = &mut self.cfg.basic_blocks[from].successors;
+        if !successors.iter().any(|(id, _)| *id == to) {
+            successors.push((to, edge_type));
+            self.cfg.basic_blocks[to].predecessors.push(from);
+        }
+    }
+
+    /// Continue building in a new block which is only reachable if something jumps to it
+    fn start_unreachable_block(&mut self) {
+        self.current = self.new_block();
+    }
+
+    fn add_statement(&mut self, statement: &'a Statement<'a>) {
+        if let Some(id) = self.nodes.get_node_id(statement_kind(statement)) {
+            self.cfg.basic_blocks[self.current].statements.push(id);
+            self.cfg.statement_blocks.insert(id, self.current);
+        }
+    }
+
+    fn build_statements(&mut self, statements: &'a [Statement<'a>]) {
+        for statement in statements {
+            self.build_statement(statement);
+        }
+    }
+
+    fn build_statement(&mut self, statement: &'a Statement<'a>) {
+        self.add_statement(statement);
+        match statement {
+            Statement::BlockStatement(block) => self.build_statements(&block.body),
+            Statement::IfStatement(stmt) => self.build_if_statement(stmt),
+            Statement::WhileStatement(stmt) => {
+                let labels = std::mem::take(&mut self.pending_labels);
+                let test = self.new_block();
+                self.add_edge(self.current, test, EdgeType::Normal);
+                let may_exit = !is_always_truthy(&stmt.test);
+                self.build_loop(labels, test, test, may_exit, &stmt.body);
+            }
+            Statement::DoWhileStatement(stmt) => self.build_do_while_statement(stmt),
+            Statement::ForStatement(stmt) => {
+                let labels = std::mem::take(&mut self.pending_labels);
+                let test = self.new_block();
+                self.add_edge(self.current, test, EdgeType::Normal);
+                let update = self.new_block();
+                self.add_edge(update, test, EdgeType::Backedge);
+                // `for (;;)` only exits by jumping out of it
+                let may_exit = stmt.test.as_ref().is_some_and(|test| !is_always_truthy(test));
+                self.build_loop(labels, test, update, may_exit, &stmt.body);
+            }
+            Statement::ForInStatement(stmt) => self.build_for_each(&stmt.body),
+            Statement::ForOfStatement(stmt) => self.build_for_each(&stmt.body),
+            Statement::SwitchStatement(stmt) => self.build_switch_statement(stmt),
+            Statement::TryStatement(stmt) => self.build_try_statement(stmt),
+            Statement::LabeledStatement(stmt) => {
+                self.pending_labels.push(&stmt.label.name);
+                if matches!(
+                    stmt.body,
+                    Statement::LabeledStatement(_)
+                        | Statement::WhileStatement(_)
+                        | Statement::DoWhileStatement(_)
+                        | Statement::ForStatement(_)
+                        | Statement::ForInStatement(_)
+                        | Statement::ForOfStatement(_)
+                        | Statement::SwitchStatement(_)
+                ) {
+                    self.build_statement(&stmt.body);
+                } else {
+                    let labels = std::mem::take(&mut self.pending_labels);
+                    let after = self.new_block();
+                    self.jump_targets.push(JumpTarget {
+                        labels,
+                        break_block: after,
+                        continue_block: None,
+                        unlabeled: false,
+                    });
+                    self.build_statement(&stmt.body);
+                    self.jump_targets.pop();
+                    self.add_edge(self.current, after, EdgeType::Normal);
+                    self.current = after;
+                }
+            }
+            Statement::WithStatement(stmt) => self.build_statement(&stmt.body),
+            Statement::BreakStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| &label.name);
+                let target = self
+                    .jump_targets
+                    .iter()
+                    .rev()
+                    .find(|target| {
+                        label.map_or(target.unlabeled, |label| target.labels.contains(&label))
+                    })
+                    .map(|target| target.break_block);
+                if let Some(target) = target {
+                    self.add_edge(self.current, target, EdgeType::Jump);
+                }
+                self.start_unreachable_block();
+            }
+            Statement::ContinueStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| &label.name);
+                let target = self
+                    .jump_targets
+                    .iter()
+                    .rev()
+                    .filter(|target| label.map_or(true, |label| target.labels.contains(&label)))
+                    .find_map(|target| target.continue_block);
+                if let Some(target) = target {
+                    self.add_edge(self.current, target, EdgeType::Jump);
+                }
+                self.start_unreachable_block();
+            }
+            Statement::ReturnStatement(_) => {
+                self.add_edge(self.current, self.exit, EdgeType::Jump);
+                self.start_unreachable_block();
+            }
+            Statement::ThrowStatement(_) => {
+                let target = self.exception_targets.last().copied().unwrap_or(self.exit);
+                self.add_edge(self.current, target, EdgeType::Exception);
+                self.start_unreachable_block();
+            }
+            _ => {}
+        }
+    }
+
+    fn build_if_statement(&mut self, stmt: &'a IfStatement<'a>) {
+        let test = self.current;
+        let consequent = self.new_block();
+        self.add_edge(test, consequent, EdgeType::True);
+        self.current = consequent;
+        self.build_statement(&stmt.consequent);
+        let consequent_end = self.current;
+
+        let alternate_end = stmt.alternate.as_ref().map(|alternate| {
+            let block = self.new_block();
+            self.add_edge(test, block, EdgeType::False);
+            self.current = block;
+            self.build_statement(alternate);
+            self.current
+        });
+
+        let after = self.new_block();
+        self.add_edge(consequent_end, after, EdgeType::Normal);
+        match alternate_end {
+            Some(alternate_end) => self.add_edge(alternate_end, after, EdgeType::Normal),
+            None => self.add_edge(test, after, EdgeType::False),
+        }
+        self.current = after;
+    }
+
+    /// Build the body of a loop whose test is evaluated in `test`, where `continue` statements
+    /// jump to `update`. `update` must already flow into `test`.
+    fn build_loop(
+        &mut self,
+        labels: Vec<&'a Atom>,
+        test: BasicBlockId,
+        update: BasicBlockId,
+        may_exit: bool,
+        body: &'a Statement<'a>,
+    ) {
+        let body_block = self.new_block();
+        self.add_edge(test, body_block, EdgeType::True);
+        let after = self.new_block();
+        if may_exit {
+            self.add_edge(test, after, EdgeType::False);
+        }
+        self.jump_targets.push(JumpTarget {
+            labels,
+            break_block: after,
+            continue_block: Some(update),
+            unlabeled: true,
+        });
+        self.current = body_block;
+        self.build_statement(body);
+        self.jump_targets.pop();
+        let edge_type = if update == test { EdgeType::Backedge } else { EdgeType::Normal };
+        self.add_edge(self.current, update, edge_type);
+        self.current = after;
+    }
+
+    fn build_do_while_statement(&mut self, stmt: &'a DoWhileStatement<'a>) {
+        let labels = std::mem::take(&mut self.pending_labels);
+        let body = self.new_block();
+        self.add_edge(self.current, body, EdgeType::Normal);
+        let test = self.new_block();
+        let after = self.new_block();
+        self.jump_targets.push(JumpTarget {
+            labels,
+            break_block: after,
+            continue_block: Some(test),
+            unlabeled: true,
+        });
+        self.current = body;
+        self.build_statement(&stmt.body);
+        self.jump_targets.pop();
+        self.add_edge(self.current, test, EdgeType::Normal);
+        self.add_edge(test, body, EdgeType::Backedge);
+        if !is_always_truthy(&stmt.test) {
+            self.add_edge(test, after, EdgeType::False);
+        }
+        self.current = after;
+    }
+
+    /// `for in` and `for of` statements, which loop while there are keys or values left
+    fn build_for_each(&mut self, body: &'a Statement<'a>) {
+        let labels = std::mem::take(&mut self.pending_labels);
+        let test = self.new_block();
+        self.add_edge(self.current, test, EdgeType::Normal);
+        self.build_loop(labels, test, test, true, body);
+    }
+
+    fn build_switch_statement(&mut self, stmt: &'a SwitchStatement<'a>) {
+        let labels = std::mem::take(&mut self.pending_labels);
+        let discriminant = self.current;
+        let after = self.new_block();
+        self.jump_targets.push(JumpTarget {
+            labels,
+            break_block: after,
+            continue_block: None,
+            unlabeled: true,
+        });
+        let mut fallthrough = None;
+        for case in &stmt.cases {
+            let block = self.new_block();
+            self.add_edge(discriminant, block, EdgeType::Normal);
+            if let Some(previous) = fallthrough {
+                self.add_edge(previous, block, EdgeType::Normal);
+            }
+            self.current = block;
+            self.build_statements(&case.consequent);
+            fallthrough = Some(self.current);
+        }
+        self.jump_targets.pop();
+        if let Some(previous) = fallthrough {
+            self.add_edge(previous, after, EdgeType::Normal);
+        }
+        if !stmt.cases.iter().any(|case| case.test.is_none()) {
+            self.add_edge(discriminant, after, EdgeType::Normal);
+        }
+        self.current = after;
+    }
+
+    fn build_try_statement(&mut self, stmt: &'a TryStatement<'a>) {
+        let finalizer = stmt.finalizer.as_ref().map(|_| self.new_block());
+        let handler = stmt.handler.as_ref().map(|_| self.new_block());
+        let exception_target = handler.or(finalizer);
+
+        let block = self.new_block();
+        self.add_edge(self.current, block, EdgeType::Normal);
+        self.current = block;
+        self.build_protected(exception_target, |builder| {
+            builder.build_statements(&stmt.block.body);
+        });
+        let block_end = self.current;
+
+        let handler_end = stmt.handler.as_ref().zip(handler).map(|(clause, handler)| {
+            self.current = handler;
+            self.build_protected(finalizer, |builder| builder.build_statements(&clause.body.body));
+            self.current
+        });
+
+        let after = self.new_block();
+        let ends = [Some(block_end), handler_end].into_iter().flatten();
+        if let Some(finalizer) = finalizer {
+            for end in ends {
+                self.add_edge(end, finalizer, EdgeType::Normal);
+            }
+            self.current = finalizer;
+            if let Some(block) = &stmt.finalizer {
+                self.build_statements(&block.body);
+            }
+            self.add_edge(self.current, after, EdgeType::Normal);
+            // Rethrow exceptions which were not caught
+            let target = self.exception_targets.last().copied().unwrap_or(self.exit);
+            self.add_edge(self.current, target, EdgeType::Exception);
+        } else {
+            for end in ends {
+                self.add_edge(end, after, EdgeType::Normal);
+            }
+        }
+        self.current = after;
+    }
+
+    /// Build code where any statement may throw to `exception_target`
+    fn build_protected(
+        &mut self,
+        exception_target: Option<BasicBlockId>,
+        build: impl FnOnce(&mut Self),
+    ) {
+        let Some(target) = exception_target else {
+            build(self);
+            return;
+        };
+        let first = self.current;
+        let start = self.cfg.basic_blocks.len();
+        self.exception_targets.push(target);
+        build(self);
+        self.exception_targets.pop();
+        self.add_edge(first, target, EdgeType::Exception);
+        for id in start..self.cfg.basic_blocks.len() {
+            self.add_edge(BasicBlockId::new(id), target, EdgeType::Exception);
+        }
+    }
+}
+
+fn is_always_truthy(expr: &Expression) -> bool {
+    matches!(expr, Expression::BooleanLiteral(lit) if lit.value)
+}
+
+fn statement_kind<'a>(statement: &'a Statement<'a>) -> AstKind<'a> {
+    match statement {
+        Statement::BlockStatement(stmt) => AstKind::BlockStatement(stmt),
+        Statement::BreakStatement(stmt) => AstKind::BreakStatement(stmt),
+        Statement::ContinueStatement(stmt) => AstKind::ContinueStatement(stmt),
+        Statement::DebuggerStatement(stmt) => AstKind::DebuggerStatement(stmt),
+        Statement::DoWhileStatement(stmt) => AstKind::DoWhileStatement(stmt),
+        Statement::EmptyStatement(stmt) => AstKind::EmptyStatement(stmt),
+        Statement::ErrorStatement(stmt) => AstKind::ErrorStatement(stmt),
+        Statement::ExpressionStatement(stmt) => AstKind::ExpressionStatement(stmt),
+        Statement::ForInStatement(stmt) => AstKind::ForInStatement(stmt),
+        Statement::ForOfStatement(stmt) => AstKind::ForOfStatement(stmt),
+        Statement::ForStatement(stmt) => AstKind::ForStatement(stmt),
+        Statement::IfStatement(stmt) => AstKind::IfStatement(stmt),
+        Statement::LabeledStatement(stmt) => AstKind::LabeledStatement(stmt),
+        Statement::ReturnStatement(stmt) => AstKind::ReturnStatement(stmt),
+        Statement::SwitchStatement(stmt) => AstKind::SwitchStatement(stmt),
+        Statement::ThrowStatement(stmt) => AstKind::ThrowStatement(stmt),
+        Statement::TryStatement(stmt) => AstKind::TryStatement(stmt),
+        Statement::WhileStatement(stmt) => AstKind::WhileStatement(stmt),
+        Statement::WithStatement(stmt) => AstKind::WithStatement(stmt),
+        Statement::ModuleDeclaration(decl) => AstKind::ModuleDeclaration(decl),
+        Statement::Declaration(decl) => match decl {
+            Declaration::VariableDeclaration(decl) => AstKind::VariableDeclaration(decl),
+            Declaration::FunctionDeclaration(func) => AstKind::Function(func),
+            Declaration::ClassDeclaration(class) => AstKind::Class(class),
+            Declaration::TSTypeAliasDeclaration(decl) => AstKind::TSTypeAliasDeclaration(decl),
+            Declaration::TSInterfaceDeclaration(decl) => AstKind::TSInterfaceDeclaration(decl),
+            Declaration::TSEnumDeclaration(decl) => AstKind::TSEnumDeclaration(decl),
+            Declaration::TSModuleDeclaration(decl) => AstKind::TSModuleDeclaration(decl),
+            Declaration::TSImportEqualsDeclaration(decl) => {
+                AstKind::TSImportEqualsDeclaration(decl)
+            }
+        },
+    }
+}
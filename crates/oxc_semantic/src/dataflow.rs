@@ -0,0 +1,169 @@
+//! Dataflow analysis over the control flow graph of a function
+//!
+//! An [`Analysis`] describes how a value of its [`Lattice`] changes by executing statements and
+//! following edges, and [`ControlFlowGraph::analyze`] computes the value at the start and end of
+//! every block by iterating until a fixed point is reached.
+//!
+//! Blocks contain compound statements such as `if` and `while` in the block which evaluates
+//! their test, the statements they contain are in other blocks. So transfer functions should
+//! only look at the parts of a compound statement which are not statements themselves.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::{BuildHasher, Hash},
+};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    control_flow::{BasicBlockId, ControlFlowGraph, EdgeType},
+    node::AstNodeId,
+};
+
+/// The values of an analysis, ordered from `bottom`, the value of blocks which have not been
+/// reached yet, upwards by `join`
+pub trait Lattice: Clone {
+    fn bottom() -> Self;
+
+    /// Merge the value flowing in from another edge, returns whether `self` changed
+    fn join(&mut self, other: &Self) -> bool;
+}
+
+/// Sets grow by union, e.g. for "may" analyses such as reaching definitions
+impl<T: Clone + Eq + Hash, S: BuildHasher + Clone + Default> Lattice for HashSet<T, S> {
+    fn bottom() -> Self {
+        Self::default()
+    }
+
+    fn join(&mut self, other: &Self) -> bool {
+        let len = self.len();
+        self.extend(other.iter().cloned());
+        self.len() != len
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// From the entry of the function along edges, e.g. definite assignment
+    Forward,
+    /// From the exit of the function against edges, e.g. liveness
+    Backward,
+}
+
+/// A dataflow problem, solved by [`ControlFlowGraph::analyze`]
+pub trait Analysis {
+    type Domain: Lattice;
+
+    const DIRECTION: Direction;
+
+    /// The value at the entry of the function for forward analyses, or at its exit for backward
+    /// analyses
+    fn boundary(&self) -> Self::Domain;
+
+    /// Apply the effect of a statement. Statements are visited in execution order for forward
+    /// analyses and in reverse for backward analyses.
+    fn transfer_statement(&self, state: &mut Self::Domain, statement_id: AstNodeId);
+
+    /// Apply the effect of following an edge, e.g. narrowing `x` to non-null on the `True` edge
+    /// of `if (x)`. `from` is the block the edge starts from in the CFG, regardless of direction.
+    fn transfer_edge(&self, _state: &mut Self::Domain, _from: BasicBlockId, _edge: EdgeType) {}
+}
+
+/// The values of an analysis at the start and end of each block of a function
+#[derive(Debug)]
+pub struct DataflowResults<D> {
+    /// Values at the start of each block in execution order
+    entry: FxHashMap<BasicBlockId, D>,
+    /// Values at the end of each block in execution order
+    exit: FxHashMap<BasicBlockId, D>,
+}
+
+impl<D> DataflowResults<D> {
+    /// The value before the first statement of the block, `None` if the block is unreachable
+    pub fn entry(&self, block_id: BasicBlockId) -> Option<&D> {
+        self.entry.get(&block_id)
+    }
+
+    /// The value after the last statement of the block, `None` if the block is unreachable
+    pub fn exit(&self, block_id: BasicBlockId) -> Option<&D> {
+        self.exit.get(&block_id)
+    }
+}
+
+impl ControlFlowGraph {
+    /// Run the analysis on a `Program`, `Function` or `ArrowExpression` node until a fixed point
+    /// is reached. Unreachable blocks are skipped.
+    pub fn analyze<A: Analysis>(
+        &self,
+        function_id: AstNodeId,
+        analysis: &A,
+    ) -> Option<DataflowResults<A::Domain>> {
+        let forward = A::DIRECTION == Direction::Forward;
+        let boundary_block = if forward {
+            self.function_entry(function_id)?
+        } else {
+            self.function_exit(function_id)?
+        };
+        // Unreachable blocks can only flow into reachable blocks, which would make them see the
+        // effects of code which never runs
+        let blocks = self
+            .function_blocks(function_id)?
+            .filter(|&block_id| self.basic_block(block_id).is_reachable())
+            .collect::<Vec<_>>();
+
+        // Values flowing into and out of each block, in the direction of the analysis
+        let mut inputs = blocks
+            .iter()
+            .map(|&block_id| (block_id, A::Domain::bottom()))
+            .collect::<FxHashMap<_, _>>();
+        let mut outputs = inputs.clone();
+        inputs.insert(boundary_block, analysis.boundary());
+
+        // Every block is transferred at least once, then again whenever its input changes
+        let mut worklist = blocks.iter().copied().collect::<VecDeque<_>>();
+        if !forward {
+            worklist.make_contiguous().reverse();
+        }
+        let mut queued = blocks.iter().copied().collect::<FxHashSet<_>>();
+        while let Some(block_id) = worklist.pop_front() {
+            queued.remove(&block_id);
+            let block = self.basic_block(block_id);
+            let mut state = inputs[&block_id].clone();
+            if forward {
+                for &statement_id in block.statements() {
+                    analysis.transfer_statement(&mut state, statement_id);
+                }
+            } else {
+                for &statement_id in block.statements().iter().rev() {
+                    analysis.transfer_statement(&mut state, statement_id);
+                }
+            }
+
+            let edges = if forward {
+                block.successors().iter().map(|&(to, edge)| (to, block_id, edge)).collect()
+            } else {
+                block
+                    .predecessors()
+                    .iter()
+                    .filter_map(|&from| {
+                        let successors = self.basic_block(from).successors();
+                        let &(_, edge) = successors.iter().find(|(to, _)| *to == block_id)?;
+                        Some((from, from, edge))
+                    })
+                    .collect::<Vec<_>>()
+            };
+            for (next, from, edge) in edges {
+                let mut value = state.clone();
+                analysis.transfer_edge(&mut value, from, edge);
+                let Some(input) = inputs.get_mut(&next) else { continue };
+                if input.join(&value) && queued.insert(next) {
+                    worklist.push_back(next);
+                }
+            }
+            outputs.insert(block_id, state);
+        }
+
+        let (entry, exit) = if forward { (inputs, outputs) } else { (outputs, inputs) };
+        Some(DataflowResults { entry, exit })
+    }
+}
@@ -0,0 +1,341 @@
+//! Global variables of the environments code may run in, see [`Semantic::global_variable`].
+//!
+//! [Globals](https://github.com/sindresorhus/globals/blob/main/globals.json)
+//! Each global is given a value of true or false.
+//! A value of true indicates that the variable may be overwritten.
+//! A value of false indicates that the variable should be considered read-only.
+
+use bitflags::bitflags;
+use phf::{phf_map, Map};
+
+use crate::{Reference, ReferenceId, Semantic};
+
+bitflags! {
+    /// Environments whose global variables are defined, e.g. `Environment::Builtin | Environment::Node`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Environment: u8 {
+        /// ECMAScript intrinsics, defined in all environments
+        const Builtin = 1 << 0;
+        /// Browser globals, e.g. `window` and `document`
+        const Browser = 1 << 1;
+        /// Node.js globals, e.g. `process` and `require`
+        const Node    = 1 << 2;
+    }
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self::Builtin
+    }
+}
+
+impl Environment {
+    /// Code running in browsers
+    pub const BROWSER: Self = Self::Builtin.union(Self::Browser);
+    /// Code running in Node.js
+    pub const NODE: Self = Self::Builtin.union(Self::Node);
+
+    /// The environment of an ESLint `env` name, e.g. `browser`, `node` or `es2021`
+    pub fn from_env_name(name: &str) -> Option<Self> {
+        match name {
+            "browser" => Some(Self::Browser),
+            "node" => Some(Self::Node),
+            "builtin" => Some(Self::Builtin),
+            _ if name.strip_prefix("es").is_some_and(|version| version.parse::<u16>().is_ok()) => {
+                Some(Self::Builtin)
+            }
+            _ => None,
+        }
+    }
+
+    /// The globals of a single environment
+    fn globals(self) -> &'static Map<&'static str, bool> {
+        match self {
+            Self::Browser => &BROWSER,
+            Self::Node => &NODE,
+            _ => &BUILTINS,
+        }
+    }
+}
+
+/// A global variable defined by an environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlobalVariable {
+    /// The environment defining the global, a single flag
+    pub environment: Environment,
+    /// Whether the global may be overwritten
+    pub writable: bool,
+}
+
+/// The global variable `name` defined by one of the `environments`
+pub fn global_variable(name: &str, environments: Environment) -> Option<GlobalVariable> {
+    environments.iter().find_map(|environment| {
+        let writable = *environment.globals().get(name)?;
+        Some(GlobalVariable { environment, writable })
+    })
+}
+
+impl<'a> Semantic<'a> {
+    /// The global variable the reference refers to, `None` if the reference is resolved to a
+    /// declaration or the global is not defined by any of the `environments`
+    pub fn global_variable(
+        &self,
+        reference: &Reference,
+        environments: Environment,
+    ) -> Option<GlobalVariable> {
+        if reference.symbol_id().is_some() {
+            return None;
+        }
+        global_variable(reference.name(), environments)
+    }
+
+    /// References to undeclared variables which are not defined by any of the `environments`,
+    /// in source order
+    pub fn undefined_references(&self, environments: Environment) -> Vec<ReferenceId> {
+        let mut references = self
+            .scopes()
+            .root_unresolved_references()
+            .iter()
+            .filter(|(name, _)| global_variable(name, environments).is_none())
+            .flat_map(|(_, reference_ids)| reference_ids.iter().copied())
+            .collect::<Vec<_>>();
+        references.sort_unstable_by_key(|&reference_id| {
+            self.symbols().get_reference(reference_id).span().start
+        });
+        references
+    }
+}
+
+/// ECMAScript intrinsics
+pub const BUILTINS: Map<&'static str, bool> = phf_map! {
+    "AggregateError" => false,
+    "Array" => false,
+    "ArrayBuffer" => false,
+    "Atomics" => false,
+    "BigInt" => false,
+    "BigInt64Array" => false,
+    "BigUint64Array" => false,
+    "Boolean" => false,
+    "constructor" => false,
+    "DataView" => false,
+    "Date" => false,
+    "decodeURI" => false,
+    "decodeURIComponent" => false,
+    "encodeURI" => false,
+    "encodeURIComponent" => false,
+    "Error" => false,
+    "escape" => false,
+    "eval" => false,
+    "EvalError" => false,
+    "FinalizationRegistry" => false,
+    "Float32Array" => false,
+    "Float64Array" => false,
+    "Function" => false,
+    "globalThis" => false,
+    "hasOwnProperty" => false,
+    "Infinity" => false,
+    "Int16Array" => false,
+    "Int32Array" => false,
+    "Int8Array" => false,
+    "Intl" => false,
+    "isFinite" => false,
+    "isNaN" => false,
+    "isPrototypeOf" => false,
+    "JSON" => false,
+    "Map" => false,
+    "Math" => false,
+    "NaN" => false,
+    "Number" => false,
+    "Object" => false,
+    "parseFloat" => false,
+    "parseInt" => false,
+    "Promise" => false,
+    "propertyIsEnumerable" => false,
+    "Proxy" => false,
+    "RangeError" => false,
+    "ReferenceError" => false,
+    "Reflect" => false,
+    "RegExp" => false,
+    "Set" => false,
+    "SharedArrayBuffer" => false,
+    "String" => false,
+    "Symbol" => false,
+    "SyntaxError" => false,
+    "toLocaleString" => false,
+    "toString" => false,
+    "TypeError" => false,
+    "Uint16Array" => false,
+    "Uint32Array" => false,
+    "Uint8Array" => false,
+    "Uint8ClampedArray" => false,
+    "undefined" => false,
+    "unescape" => false,
+    "URIError" => false,
+    "valueOf" => false,
+    "WeakMap" => false,
+    "WeakRef" => false,
+    "WeakSet" => false
+};
+
+/// Browser globals
+pub const BROWSER: Map<&'static str, bool> = phf_map! {
+    "AbortController" => false,
+    "AbortSignal" => false,
+    "addEventListener" => false,
+    "alert" => false,
+    "atob" => false,
+    "Blob" => false,
+    "blur" => false,
+    "btoa" => false,
+    "BroadcastChannel" => false,
+    "caches" => false,
+    "cancelAnimationFrame" => false,
+    "cancelIdleCallback" => false,
+    "clearInterval" => false,
+    "clearTimeout" => false,
+    "close" => false,
+    "confirm" => false,
+    "console" => false,
+    "Credential" => false,
+    "crypto" => false,
+    "CustomEvent" => false,
+    "customElements" => false,
+    "devicePixelRatio" => false,
+    "document" => false,
+    "Document" => false,
+    "DOMException" => false,
+    "DOMParser" => false,
+    "Element" => false,
+    "Event" => false,
+    "EventSource" => false,
+    "EventTarget" => false,
+    "fetch" => false,
+    "File" => false,
+    "FileReader" => false,
+    "focus" => false,
+    "FormData" => false,
+    "frames" => false,
+    "getComputedStyle" => false,
+    "getSelection" => false,
+    "Headers" => false,
+    "history" => false,
+    "HTMLElement" => false,
+    "Image" => false,
+    "indexedDB" => false,
+    "innerHeight" => false,
+    "innerWidth" => false,
+    "IntersectionObserver" => false,
+    "KeyboardEvent" => false,
+    "localStorage" => false,
+    "location" => true,
+    "matchMedia" => false,
+    "MessageChannel" => false,
+    "MouseEvent" => false,
+    "MutationObserver" => false,
+    "name" => false,
+    "navigator" => false,
+    "Node" => false,
+    "Notification" => false,
+    "onerror" => true,
+    "onload" => true,
+    "onmessage" => true,
+    "open" => false,
+    "opener" => false,
+    "parent" => false,
+    "performance" => false,
+    "postMessage" => false,
+    "print" => false,
+    "prompt" => false,
+    "queueMicrotask" => false,
+    "removeEventListener" => false,
+    "Request" => false,
+    "requestAnimationFrame" => false,
+    "requestIdleCallback" => false,
+    "ResizeObserver" => false,
+    "Response" => false,
+    "screen" => false,
+    "scroll" => false,
+    "scrollTo" => false,
+    "scrollX" => false,
+    "scrollY" => false,
+    "self" => false,
+    "sessionStorage" => false,
+    "setInterval" => false,
+    "setTimeout" => false,
+    "status" => false,
+    "structuredClone" => false,
+    "TextDecoder" => false,
+    "TextEncoder" => false,
+    "top" => false,
+    "URL" => false,
+    "URLSearchParams" => false,
+    "WebSocket" => false,
+    "window" => false,
+    "Worker" => false,
+    "XMLHttpRequest" => false
+};
+
+/// Node.js globals, including the CommonJS module wrapper variables
+pub const NODE: Map<&'static str, bool> = phf_map! {
+    "__dirname" => false,
+    "__filename" => false,
+    "AbortController" => false,
+    "AbortSignal" => false,
+    "atob" => false,
+    "Blob" => false,
+    "BroadcastChannel" => false,
+    "btoa" => false,
+    "Buffer" => false,
+    "clearImmediate" => false,
+    "clearInterval" => false,
+    "clearTimeout" => false,
+    "console" => false,
+    "crypto" => false,
+    "DOMException" => false,
+    "Event" => false,
+    "EventTarget" => false,
+    "exports" => true,
+    "fetch" => false,
+    "FormData" => false,
+    "global" => false,
+    "Headers" => false,
+    "MessageChannel" => false,
+    "module" => false,
+    "performance" => false,
+    "process" => false,
+    "queueMicrotask" => false,
+    "Request" => false,
+    "require" => false,
+    "Response" => false,
+    "setImmediate" => false,
+    "setInterval" => false,
+    "setTimeout" => false,
+    "structuredClone" => false,
+    "TextDecoder" => false,
+    "TextEncoder" => false,
+    "URL" => false,
+    "URLSearchParams" => false,
+    "WebAssembly" => false
+};
+
+#[cfg(test)]
+mod test {
+    use super::{global_variable, Environment, GlobalVariable};
+
+    #[test]
+    fn global_variables() {
+        let builtin = GlobalVariable { environment: Environment::Builtin, writable: false };
+        assert_eq!(global_variable("Math", Environment::default()), Some(builtin));
+        assert_eq!(global_variable("Math", Environment::NODE), Some(builtin));
+        assert_eq!(global_variable("window", Environment::default()), None);
+        assert_eq!(
+            global_variable("window", Environment::BROWSER),
+            Some(GlobalVariable { environment: Environment::Browser, writable: false })
+        );
+        assert_eq!(global_variable("require", Environment::BROWSER), None);
+        assert_eq!(
+            global_variable("exports", Environment::NODE | Environment::Browser),
+            Some(GlobalVariable { environment: Environment::Node, writable: true })
+        );
+    }
+}
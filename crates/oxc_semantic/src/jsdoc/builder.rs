@@ -37,7 +37,7 @@ impl<'a> JSDocBuilder<'a> {
 
     /// Find the jsdoc doc in frontend this span, a.k.a leading comment
     fn find_jsdoc_comment(&self, span: Span) -> Option<&'a str> {
-        let (start, comment) = self.trivias.comments().range(..span.start).next()?;
+        let (start, comment) = self.trivias.comments().range(..span.start).next_back()?;
 
         if comment.is_single_line() {
             return None;
@@ -142,4 +142,39 @@ mod test {
             test_jsdoc(source_text, "function foo() {}");
         }
     }
+    #[test]
+    fn symbol_jsdoc() {
+        let source_text = "
+            /**
+             * Adds one
+             * @deprecated
+             */
+            export const add = (a) => a + 1;
+            /** Greets */
+            function greet(name) {}
+            let b;
+        ";
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let semantic = SemanticBuilder::new(source_text, source_type)
+            .with_trivias(ret.trivias)
+            .build(program)
+            .semantic;
+        let jsdoc = |name: &str| {
+            let symbol_id =
+                semantic.symbols().iter().find(|id| *semantic.symbols().get_name(*id) == name)?;
+            semantic.symbol_jsdoc(symbol_id)
+        };
+
+        let add = jsdoc("add").unwrap();
+        assert_eq!(add.description(), "Adds one");
+        assert!(add.tags()[0].is_deprecated());
+        assert_eq!(jsdoc("greet").unwrap().description(), "Greets");
+        // The comment of a function is not the comment of its parameters
+        assert!(jsdoc("a").is_none());
+        assert!(jsdoc("name").is_none());
+        assert!(jsdoc("b").is_none());
+    }
 }
@@ -6,7 +6,7 @@ pub use builder::JSDocBuilder;
 use oxc_span::{GetSpan, Span};
 
 use self::parser::JSDocParser;
-pub use self::parser::JSDocTag;
+pub use self::parser::{JSDocTag, JSDocTagKind, Param, ParamType, ParamTypeKind};
 use crate::AstNode;
 
 mod parser;
@@ -50,4 +50,16 @@ impl<'a> JSDocComment<'a> {
     pub fn tags<'b>(&'b self) -> &'b Vec<JSDocTag<'a>> {
         self.tags.get_or_init(|| JSDocParser::new(self.comment).parse())
     }
+
+    /// The text before the first tag, without the leading `*` of each line
+    pub fn description(&self) -> String {
+        self.comment
+            .lines()
+            .map(|line| line.trim().trim_start_matches('*').trim())
+            .take_while(|line| !line.starts_with('@'))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
 }
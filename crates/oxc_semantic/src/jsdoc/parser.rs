@@ -8,7 +8,7 @@ pub enum ParamTypeKind {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ParamType<'a> {
-    value: &'a str,
+    pub value: &'a str,
 }
 
 impl<'a> ParamType<'a> {
@@ -37,14 +37,18 @@ impl FromStr for ParamTypeKind {
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Param<'a> {
-    name: &'a str,
-    r#type: Option<ParamType<'a>>,
+    pub name: &'a str,
+    pub r#type: Option<ParamType<'a>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JSDocTagKind<'a> {
     Deprecated,
     Param(Param<'a>),
+    /// `@returns` or `@return`, with the type if there is one
+    Returns(Option<ParamType<'a>>),
+    /// Any other tag, e.g. `example` for `@example`
+    Other(&'a str),
 }
 
 impl<'a> FromStr for JSDocTagKind<'a> {
@@ -54,6 +58,7 @@ impl<'a> FromStr for JSDocTagKind<'a> {
         match s {
             "deprecated" => Ok(Self::Deprecated),
             "param" => Ok(Self::Param(Param::default())),
+            "returns" | "return" => Ok(Self::Returns(None)),
             _ => Err(()),
         }
     }
@@ -127,11 +132,15 @@ impl<'a> JSDocParser<'a> {
 
         while let Some(c) = comment.chars().nth(self.current) {
             match c {
-                '@' => {
+                // Tags start a line, an `@` elsewhere is part of a description, e.g. an email address
+                '@' if self.current == 0
+                    || comment
+                        .chars()
+                        .nth(self.current - 1)
+                        .is_some_and(|c| c.is_whitespace() || c == '*') =>
+                {
                     self.current += 1;
-                    let Some(tag) = self.parse_tag(comment) else { break };
-                    self.current += tag.description.len();
-                    tags.push(tag);
+                    tags.push(self.parse_tag(comment));
                 }
                 _ => {
                     self.current += 1;
@@ -142,34 +151,41 @@ impl<'a> JSDocParser<'a> {
         tags
     }
 
-    fn parse_tag(&mut self, comment: &'a str) -> Option<JSDocTag<'a>> {
+    fn parse_tag(&mut self, comment: &'a str) -> JSDocTag<'a> {
         let tag = self.take_until(comment, |c| c == ' ' || c == '\n');
-        JSDocTagKind::from_str(tag).map_or(None, |kind| match kind {
-            JSDocTagKind::Deprecated => Some(self.parse_deprecated_tag(comment)),
-            JSDocTagKind::Param { .. } => Some(self.parse_param_tag(comment)),
-        })
+        match JSDocTagKind::from_str(tag).unwrap_or(JSDocTagKind::Other(tag)) {
+            JSDocTagKind::Param { .. } => self.parse_param_tag(comment),
+            JSDocTagKind::Returns(_) => {
+                self.skip_whitespace(comment);
+                let r#type = self.parse_type(comment);
+                let description = self.take_until(comment, |c| c == '\n' || c == '*');
+                JSDocTag { kind: JSDocTagKind::Returns(r#type), description }
+            }
+            kind => {
+                self.skip_whitespace(comment);
+                let description = self.take_until(comment, |c| c == '\n' || c == '*');
+                JSDocTag { kind, description }
+            }
+        }
     }
 
-    fn parse_deprecated_tag(&mut self, comment: &'a str) -> JSDocTag<'a> {
+    fn parse_type(&mut self, comment: &'a str) -> Option<ParamType<'a>> {
+        if !self.at('{') {
+            return None;
+        }
+        // If we hit a space, then treat it as the end of the type annotation.
+        let type_annotation = self.take_until(comment, |c| c == '}' || c == ' ');
+        if self.at('}') {
+            self.skip_whitespace(comment);
+        }
         self.skip_whitespace(comment);
-        let description = self.take_until(comment, |c| c == '\n' || c == '*');
-        JSDocTag { kind: JSDocTagKind::Deprecated, description }
+        Some(ParamType { value: type_annotation })
     }
 
     fn parse_param_tag(&mut self, comment: &'a str) -> JSDocTag<'a> {
         self.skip_whitespace(comment);
 
-        let mut r#type = None;
-
-        if self.at('{') {
-            // If we hit a space, then treat it as the end of the type annotation.
-            let type_annotation = self.take_until(comment, |c| c == '}' || c == ' ');
-            r#type = Some(ParamType { value: type_annotation });
-            if self.at('}') {
-                self.skip_whitespace(comment);
-            }
-            self.skip_whitespace(comment);
-        }
+        let r#type = self.parse_type(comment);
 
         let name = self.take_until(comment, |c| c == ' ' || c == '\n');
 
@@ -303,4 +319,27 @@ mod test {
             ]
         );
     }
+    #[test]
+    fn parses_other_tags() {
+        let source = r#"/**
+        * Mail me@example.com
+        * @returns {number} the sum
+        * @example add(1, 2)
+        * @deprecated
+        */
+       "#;
+
+        let tags = JSDocParser::new(source).parse();
+        assert_eq!(
+            tags,
+            vec![
+                JSDocTag {
+                    kind: JSDocTagKind::Returns(Some(ParamType { value: "number" })),
+                    description: "the sum"
+                },
+                JSDocTag { kind: JSDocTagKind::Other("example"), description: "add(1, 2)" },
+                JSDocTag { kind: JSDocTagKind::Deprecated, description: "" },
+            ]
+        );
+    }
 }
@@ -1,30 +1,47 @@
 mod binder;
 mod builder;
 mod checker;
+mod constant_evaluation;
+mod control_flow;
+mod dataflow;
 mod diagnostics;
+pub mod globals;
 mod jsdoc;
 mod module_record;
 mod node;
 mod reference;
 mod scope;
+mod side_effects;
+mod snapshot;
 mod symbol;
 
-use std::rc::Rc;
+use std::{cell::OnceCell, collections::BTreeMap, rc::Rc};
 
 pub use builder::{SemanticBuilder, SemanticBuilderReturn};
-pub use jsdoc::{JSDoc, JSDocComment, JSDocTag};
-use oxc_ast::{ast::IdentifierReference, AstKind, Trivias};
-use oxc_span::SourceType;
+pub use jsdoc::{JSDoc, JSDocComment, JSDocTag, JSDocTagKind, Param, ParamType, ParamTypeKind};
+use oxc_ast::{
+    ast::{AssignmentTarget, Directive, IdentifierReference},
+    AstKind, CommentAttachments, Trivias,
+};
+use oxc_span::{GetSpan, SourceType, Span};
 pub use oxc_syntax::{
     module_record::ModuleRecord,
     scope::{ScopeFlags, ScopeId},
     symbol::{SymbolFlags, SymbolId},
 };
+use rustc_hash::{FxHashMap, FxHashSet};
 
 pub use crate::{
+    constant_evaluation::ConstantValue,
+    control_flow::{BasicBlock, BasicBlockId, ControlFlowGraph, EdgeType},
+    dataflow::{Analysis, DataflowResults, Direction, Lattice},
+    globals::{Environment, GlobalVariable},
+    module_record::{ExportUsageGraph, UnusedExport},
     node::{AstNode, AstNodeId, AstNodes, NodeFlags},
-    reference::{Reference, ReferenceFlag, ReferenceId},
-    scope::ScopeTree,
+    reference::{Capture, Reference, ReferenceFlag, ReferenceId, SymbolOccurrence},
+    scope::{ScopeKind, ScopeTree},
+    side_effects::SideEffects,
+    snapshot::SemanticSnapshot,
     symbol::SymbolTable,
 };
 
@@ -41,11 +58,18 @@ pub struct Semantic<'a> {
 
     trivias: Rc<Trivias>,
 
+    /// Built on first use
+    comment_attachments: OnceCell<CommentAttachments>,
+
     module_record: ModuleRecord,
 
     jsdoc: JSDoc<'a>,
 
     unused_labels: Vec<AstNodeId>,
+
+    label_targets: FxHashMap<AstNodeId, AstNodeId>,
+
+    cfg: ControlFlowGraph,
 }
 
 impl<'a> Semantic<'a> {
@@ -73,6 +97,19 @@ impl<'a> Semantic<'a> {
         &self.trivias
     }
 
+    /// Leading, trailing and dangling comments of the nodes
+    pub fn comment_attachments(&self) -> &CommentAttachments {
+        self.comment_attachments.get_or_init(|| {
+            let program = self.nodes.iter().find_map(|node| match node.kind() {
+                AstKind::Program(program) => Some(program),
+                _ => None,
+            });
+            program.map_or_else(CommentAttachments::default, |program| {
+                CommentAttachments::new(self.source_text, &self.trivias, program)
+            })
+        })
+    }
+
     pub fn jsdoc(&self) -> &JSDoc<'a> {
         &self.jsdoc
     }
@@ -85,10 +122,32 @@ impl<'a> Semantic<'a> {
         &self.symbols
     }
 
+    /// Control flow graphs of the program and its functions
+    pub fn cfg(&self) -> &ControlFlowGraph {
+        &self.cfg
+    }
+
     pub fn unused_labels(&self) -> &Vec<AstNodeId> {
         &self.unused_labels
     }
 
+    /// The labeled statement which the labeled `break` or `continue` statement jumps to
+    pub fn label_target(&self, node_id: AstNodeId) -> Option<AstNodeId> {
+        self.label_targets.get(&node_id).copied()
+    }
+
+    /// The `break` and `continue` statements which jump to the labeled statement, in source order
+    pub fn label_jumps(&self, label_id: AstNodeId) -> Vec<AstNodeId> {
+        let mut jumps = self
+            .label_targets
+            .iter()
+            .filter(|(_, target)| **target == label_id)
+            .map(|(jump, _)| *jump)
+            .collect::<Vec<_>>();
+        jumps.sort_unstable();
+        jumps
+    }
+
     pub fn is_unresolved_reference(&self, node_id: AstNodeId) -> bool {
         let reference_node = self.nodes.get_node(node_id);
         let AstKind::IdentifierReference(id) = reference_node.kind() else {
@@ -97,6 +156,37 @@ impl<'a> Semantic<'a> {
         self.scopes().root_unresolved_references().contains_key(&id.name)
     }
 
+    /// The kind of node which creates the scope, `None` for scopes which were not created from
+    /// the AST
+    pub fn scope_kind(&self, scope_id: ScopeId) -> Option<ScopeKind> {
+        let node_id = self.scopes.get_node_id(scope_id)?;
+        ScopeKind::from_ast_kind(self.nodes.kind(node_id), self.source_type)
+    }
+
+    /// Span of the node which creates the scope
+    pub fn scope_span(&self, scope_id: ScopeId) -> Option<Span> {
+        self.scopes.get_node_id(scope_id).map(|node_id| self.nodes.kind(node_id).span())
+    }
+
+    /// Whether the node is strict mode code, i.e. it is in a module, in a class, or in the scope
+    /// of a `"use strict"` directive
+    pub fn is_strict_mode(&self, node_id: AstNodeId) -> bool {
+        let node = self.nodes.get_node(node_id);
+        node.strict_mode(self.scopes.get_flags(node.scope_id()))
+    }
+
+    /// The directive prologue of the program or function which creates the scope, e.g.
+    /// `"use strict"`. Empty for other scopes.
+    pub fn scope_directives(&self, scope_id: ScopeId) -> &'a [Directive] {
+        let Some(node_id) = self.scopes.get_node_id(scope_id) else { return &[] };
+        match self.nodes.kind(node_id) {
+            AstKind::Program(program) => &program.directives,
+            AstKind::Function(func) => func.body.as_ref().map_or(&[], |body| &body.directives),
+            AstKind::ArrowExpression(arrow) => &arrow.body.directives,
+            _ => &[],
+        }
+    }
+
     /// Find which scope a symbol is declared in
     pub fn symbol_scope(&self, symbol_id: SymbolId) -> ScopeId {
         self.symbols.get_scope_id(symbol_id)
@@ -114,11 +204,242 @@ impl<'a> Semantic<'a> {
         self.nodes.get_node(self.symbols.get_declaration(symbol_id))
     }
 
+    /// The JSDoc comment of the declaration of a symbol, e.g. `/** doc */` of `a` in
+    /// `/** doc */ export const a = 1`
+    pub fn symbol_jsdoc(&self, symbol_id: SymbolId) -> Option<JSDocComment<'a>> {
+        for node in self.nodes.iter_parents(self.symbols.get_declaration(symbol_id)) {
+            if let Some(jsdoc) = self.jsdoc.get_by_node(node) {
+                return Some(jsdoc);
+            }
+            // The comment is in front of the whole statement of `const a = 1` and `export function a() {}`
+            let Some(parent) = self.nodes.parent_kind(node.id()) else { break };
+            if !matches!(
+                (node.kind(), parent),
+                (AstKind::VariableDeclarator(_), AstKind::VariableDeclaration(_))
+                    | (
+                        AstKind::VariableDeclaration(_) | AstKind::Function(_) | AstKind::Class(_),
+                        AstKind::ModuleDeclaration(_)
+                    )
+            ) {
+                break;
+            }
+        }
+        None
+    }
+
+    /// The symbol shadowed by `symbol_id`, i.e. the symbol of the same name in the closest
+    /// enclosing scope
+    pub fn shadowed_symbol(&self, symbol_id: SymbolId) -> Option<SymbolId> {
+        shadowed_symbol(&self.scopes, &self.symbols, symbol_id)
+    }
+
+    /// Symbols declared in nested scopes which shadow `symbol_id`
+    pub fn shadowing_symbols(&self, symbol_id: SymbolId) -> impl Iterator<Item = SymbolId> + '_ {
+        let (scopes, symbols) = (&self.scopes, &self.symbols);
+        symbols
+            .iter()
+            .filter(move |other| shadowed_symbol(scopes, symbols, *other) == Some(symbol_id))
+    }
+
+    /// The symbol declared or referenced by the identifier at `offset`, e.g. at the cursor of an
+    /// editor
+    pub fn symbol_at_offset(&self, offset: u32) -> Option<SymbolId> {
+        // A cursor right after an identifier is on the identifier as well
+        [Some(offset), offset.checked_sub(1)].into_iter().flatten().find_map(|offset| {
+            let node = self.nodes.node_at_offset(offset)?;
+            // References of JSX element names are made by the `JSXElementName` around the
+            // identifier
+            self.nodes.ancestors(node.id()).find_map(|node_id| {
+                if let Some(reference_id) = self.symbols.get_node_reference_id(node_id) {
+                    let reference = self.symbols.get_reference(reference_id);
+                    let span = reference.span();
+                    return (span.start <= offset && offset < span.end)
+                        .then_some(reference.symbol_id());
+                }
+                match self.nodes.kind(node_id) {
+                    AstKind::BindingIdentifier(ident) => Some(
+                        self.scopes
+                            .ancestors(self.nodes.get_node(node_id).scope_id())
+                            .find_map(|scope_id| self.scopes.get_binding(scope_id, &ident.name)),
+                    ),
+                    _ => None,
+                }
+            })
+        })?
+    }
+
+    /// The declaration and all references of the symbol in source order, including references
+    /// in nested scopes, e.g. for finding all references and renaming in editors
+    pub fn symbol_occurrences(&self, symbol_id: SymbolId) -> Vec<SymbolOccurrence> {
+        let span = self.symbols.get_span(symbol_id);
+        let declaration = self.nodes.iter().find(
+            |node| matches!(node.kind(), AstKind::BindingIdentifier(ident) if ident.span == span),
+        );
+        let mut occurrences = vec![SymbolOccurrence {
+            span,
+            reference_id: None,
+            is_shorthand: declaration.is_some_and(|node| self.is_shorthand(node.id(), span)),
+        }];
+        occurrences.extend(self.symbols.get_resolved_reference_ids(symbol_id).iter().map(
+            |&reference_id| {
+                let reference = self.symbols.get_reference(reference_id);
+                SymbolOccurrence {
+                    span: reference.span(),
+                    reference_id: Some(reference_id),
+                    is_shorthand: self.is_shorthand(reference.node_id(), reference.span()),
+                }
+            },
+        ));
+        occurrences.sort_unstable_by_key(|occurrence| occurrence.span.start);
+        occurrences
+    }
+
+    /// Whether the identifier node with `span` is both the key and the value of a property
+    fn is_shorthand(&self, node_id: AstNodeId, span: Span) -> bool {
+        let is_shorthand_binding = |kind: Option<AstKind<'a>>| match kind {
+            Some(AstKind::ObjectPattern(pattern)) => pattern
+                .properties
+                .iter()
+                .any(|property| property.shorthand && property.key.span() == span),
+            _ => false,
+        };
+        let mut parents = self.nodes.ancestor_kinds(node_id);
+        match parents.next() {
+            Some(AstKind::ObjectProperty(property)) => property.shorthand,
+            // `a` in `({ a } = b)`, the targets of other properties are wrapped in a
+            // `SimpleAssignmentTarget`
+            Some(AstKind::AssignmentTarget(AssignmentTarget::AssignmentTargetPattern(_))) => true,
+            // `a` in `const { a = 1 } = b`
+            Some(AstKind::AssignmentPattern(_)) => is_shorthand_binding(parents.next()),
+            parent => is_shorthand_binding(parent),
+        }
+    }
+
+    /// Whether a reference is called, e.g. `foo` in `foo()`, `(foo)()` and `new foo()`
+    pub fn is_call_reference(&self, reference: &Reference) -> bool {
+        let parent = self
+            .nodes
+            .iter_parents(reference.node_id())
+            .skip(1)
+            .find(|node| !matches!(node.kind(), AstKind::ParenthesizedExpression(_)));
+        // Arguments are wrapped in `Argument` nodes, so the reference must be the callee
+        matches!(
+            parent.map(AstNode::kind),
+            Some(AstKind::CallExpression(_) | AstKind::NewExpression(_))
+        )
+    }
+
+    /// The bindings of enclosing scopes which are referenced in the function or arrow function,
+    /// including in its nested functions, in the order of their declaration
+    pub fn closure_captures(&self, function_id: AstNodeId) -> Vec<Capture> {
+        let scope_id = self.nodes.get_node(function_id).scope_id();
+        if self.scopes.get_node_id(scope_id) != Some(function_id)
+            || !self.scopes.get_flags(scope_id).is_function()
+        {
+            return vec![];
+        }
+        let inner_scopes = self.scopes.descendants_of(scope_id).collect::<FxHashSet<_>>();
+        let mut captures = BTreeMap::<SymbolId, bool>::new();
+        for reference in &self.symbols.references {
+            let Some(symbol_id) = reference.symbol_id() else { continue };
+            if inner_scopes.contains(&self.symbols.get_scope_id(symbol_id))
+                || !inner_scopes.contains(&self.nodes.get_node(reference.node_id()).scope_id())
+            {
+                continue;
+            }
+            *captures.entry(symbol_id).or_default() |= reference.is_write();
+        }
+        captures
+            .into_iter()
+            .map(|(symbol_id, is_mutated)| Capture { symbol_id, is_mutated })
+            .collect()
+    }
+
+    /// The temporal dead zone of a `let`, `const` or `class` binding, which spans from the start
+    /// of its scope to the end of its initialization, e.g. the whole of `let a = a + 1;` or
+    /// `class A extends A`.
+    /// `None` for other symbols, which are initialized when their scope is entered.
+    pub fn symbol_tdz(&self, symbol_id: SymbolId) -> Option<Span> {
+        let flags = self.symbols.get_flag(symbol_id);
+        if !flags.intersects(SymbolFlags::BlockScopedVariable | SymbolFlags::Class) {
+            return None;
+        }
+        let declaration = self.symbols.get_declaration(symbol_id);
+        let end = match self.nodes.kind(declaration) {
+            AstKind::VariableDeclarator(declarator) => {
+                // `for (let a of a)` evaluates the right side before the binding is initialized
+                let statement = self.nodes.ancestors(declaration).skip(1).nth(1);
+                match statement.map(|node_id| self.nodes.kind(node_id)) {
+                    Some(AstKind::ForInStatement(stmt)) => stmt.right.span().end,
+                    Some(AstKind::ForOfStatement(stmt)) => stmt.right.span().end,
+                    _ => declarator.span.end,
+                }
+            }
+            AstKind::Class(class) => class.body.span.start,
+            _ => return None,
+        };
+        let start = self.scope_span(self.symbols.get_scope_id(symbol_id))?.start;
+        Some(Span::new(start, end))
+    }
+
+    /// Whether the reference appears before the declaration of its symbol, e.g. `a` in
+    /// `a(); function a() {}`
+    pub fn is_reference_before_declaration(&self, reference: &Reference) -> bool {
+        reference
+            .symbol_id()
+            .is_some_and(|symbol_id| reference.span().end <= self.symbols.get_span(symbol_id).start)
+    }
+
+    /// Whether evaluating the reference always throws a `ReferenceError`, because it is in the
+    /// temporal dead zone of its symbol and is evaluated along with the declaration.
+    /// References in functions and in the values of instance fields are not, as they may be
+    /// evaluated after the binding is initialized.
+    pub fn is_reference_before_initialization(&self, reference: &Reference) -> bool {
+        let Some(symbol_id) = reference.symbol_id() else { return false };
+        let Some(tdz) = self.symbol_tdz(symbol_id) else { return false };
+        let span = reference.span();
+        if span.start < tdz.start || span.end > tdz.end {
+            return false;
+        }
+        let scope_node_id = self.scopes.get_node_id(self.symbols.get_scope_id(symbol_id));
+        for node_id in self.nodes.ancestors(reference.node_id()).skip(1) {
+            if Some(node_id) == scope_node_id {
+                break;
+            }
+            match self.nodes.kind(node_id) {
+                AstKind::Function(_) | AstKind::ArrowExpression(_) => return false,
+                AstKind::PropertyDefinition(prop)
+                    if !prop.r#static
+                        && prop.value.as_ref().is_some_and(|value| {
+                            let value = value.span();
+                            value.start <= span.start && span.end <= value.end
+                        }) =>
+                {
+                    return false;
+                }
+                _ => {}
+            }
+        }
+        true
+    }
+
     pub fn is_reference_to_global_variable(&self, ident: &IdentifierReference) -> bool {
         self.scopes().root_unresolved_references().contains_key(&ident.name)
     }
 }
 
+fn shadowed_symbol(
+    scopes: &ScopeTree,
+    symbols: &SymbolTable,
+    symbol_id: SymbolId,
+) -> Option<SymbolId> {
+    let name = symbols.get_name(symbol_id);
+    scopes
+        .ancestors(symbols.get_scope_id(symbol_id))
+        .skip(1)
+        .find_map(|scope_id| scopes.get_binding(scope_id, name))
+}
+
 #[cfg(test)]
 mod tests {
     use oxc_allocator::Allocator;
@@ -306,4 +627,47 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_comment_attachments() {
+        use oxc_ast::CommentPosition;
+
+        let source = "
+            // leading
+            let a = 1; // trailing
+            function foo(/* dangling */) {}
+            foo(/* argument */ a);
+        ";
+        let allocator = Allocator::default();
+        let source_type = SourceType::default();
+        let ret = oxc_parser::Parser::new(&allocator, source, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let semantic =
+            SemanticBuilder::new(source, source_type).with_trivias(ret.trivias).build(program);
+        let attachments = semantic.semantic.comment_attachments();
+
+        let comments = attachments
+            .iter()
+            .flat_map(|(node, comments)| {
+                comments.iter().map(move |comment| {
+                    (node.source_text(source), comment.span.source_text(source), comment.position)
+                })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            comments,
+            vec![
+                ("let a = 1;", " leading", CommentPosition::Leading),
+                ("let a = 1;", " trailing", CommentPosition::Trailing),
+                ("(/* dangling */)", " dangling ", CommentPosition::Dangling),
+                ("a", " argument ", CommentPosition::Leading),
+            ]
+        );
+
+        let span = attachments.iter().next().unwrap().0;
+        assert_eq!(span.source_text(source), "let a = 1;");
+        assert_eq!(attachments.leading(span).count(), 1);
+        assert_eq!(attachments.trailing(span).count(), 1);
+        assert_eq!(attachments.dangling(span).count(), 0);
+    }
 }
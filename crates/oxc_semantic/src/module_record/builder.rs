@@ -79,6 +79,20 @@ impl ModuleRecordBuilder {
             .push(name_span.span());
     }
 
+    fn add_import_attributes(&mut self, module_request: &NameSpan, with_clause: &WithClause) {
+        let attributes = with_clause
+            .with_entries
+            .iter()
+            .map(|attribute| ImportAttributeEntry {
+                key: NameSpan::new(attribute.key.as_atom(), attribute.key.span()),
+                value: NameSpan::new(attribute.value.value.clone(), attribute.value.span),
+            })
+            .collect();
+        self.module_record
+            .import_attributes
+            .push(ModuleRequestAttributes { module_request: module_request.clone(), attributes });
+    }
+
     fn add_import_entry(&mut self, entry: ImportEntry) {
         self.module_record.import_entries.push(entry);
     }
@@ -123,11 +137,10 @@ impl ModuleRecordBuilder {
                     ExportLocalName::Name(name) => Some(name),
                     _ => None,
                 };
-                let found_import_entry = self
-                    .module_record
-                    .import_entries
-                    .iter()
-                    .find(|import_entry| Some(&import_entry.local_name) == local_name);
+                let found_import_entry =
+                    self.module_record.import_entries.iter().find(|import_entry| {
+                        local_name.is_some_and(|name| import_entry.local_name.name() == name.name())
+                    });
                 match found_import_entry {
                     // i. If ee.[[LocalName]] is not an element of importedBoundNames, then
                     None => {
@@ -164,6 +177,7 @@ impl ModuleRecordBuilder {
                                         ImportImportName::NamespaceObject => unreachable!(),
                                     },
                                     export_name: ee.export_name.clone(),
+                                    span: ee.span,
                                     ..ExportEntry::default()
                                 };
                                 self.append_indirect_export_entry(export_entry);
@@ -233,11 +247,19 @@ impl ModuleRecordBuilder {
             });
         }
         self.add_module_request(&module_request);
+        if let Some(with_clause) = &decl.with_clause {
+            self.add_import_attributes(&module_request, with_clause);
+        }
     }
 
     fn visit_export_all_declaration(&mut self, decl: &ExportAllDeclaration) {
+        // `export type * from "mod"` only re-exports types
+        if decl.export_kind.is_type() {
+            return;
+        }
         let module_request = NameSpan::new(decl.source.value.clone(), decl.source.span);
         let export_entry = ExportEntry {
+            span: decl.span,
             module_request: Some(module_request.clone()),
             import_name: decl
                 .exported
@@ -256,6 +278,9 @@ impl ModuleRecordBuilder {
             self.add_export_binding(exported_name.name().clone(), exported_name.span());
         }
         self.add_module_request(&module_request);
+        if let Some(with_clause) = &decl.with_clause {
+            self.add_import_attributes(&module_request, with_clause);
+        }
     }
 
     fn visit_export_default_declaration(&mut self, decl: &ExportDefaultDeclaration) {
@@ -274,6 +299,7 @@ impl ModuleRecordBuilder {
             | ExportDefaultDeclarationKind::TSEnumDeclaration(_) => return,
         };
         let export_entry = ExportEntry {
+            span: decl.span,
             export_name: ExportExportName::Default(exported_name.span()),
             local_name: id
                 .as_ref()
@@ -299,11 +325,16 @@ impl ModuleRecordBuilder {
 
         if let Some(module_request) = &module_request {
             self.add_module_request(module_request);
+            if let Some(with_clause) = &decl.with_clause {
+                self.add_import_attributes(module_request, with_clause);
+            }
         }
 
+        let span = decl.span;
         if let Some(decl) = &decl.declaration {
             decl.bound_names(&mut |ident| {
                 let export_entry = ExportEntry {
+                    span,
                     module_request: module_request.clone(),
                     export_name: ExportExportName::Name(NameSpan::new(
                         ident.name.clone(),
@@ -322,6 +353,7 @@ impl ModuleRecordBuilder {
 
         for specifier in &decl.specifiers {
             let export_entry = ExportEntry {
+                span: specifier.span,
                 module_request: module_request.clone(),
                 export_name: ExportExportName::Name(NameSpan::new(
                     specifier.exported.name().clone(),
@@ -0,0 +1,232 @@
+use std::hash::{BuildHasherDefault, Hash};
+
+use indexmap::IndexMap;
+use oxc_span::{Atom, Span};
+#[allow(clippy::wildcard_imports)]
+use oxc_syntax::module_record::*;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+
+type FxIndexMap<K, V> = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
+
+/// Which exports of the modules of a project are imported by other modules.
+///
+/// A single module cannot tell whether its exports are used, so the module records of all
+/// modules are added to the graph, with a function resolving module requests to modules.
+/// Modules are identified by `K`, e.g. their paths.
+#[derive(Debug)]
+pub struct ExportUsageGraph<K> {
+    modules: FxIndexMap<K, ModuleExports<K>>,
+    /// Imports of all modules, re-exports are only uses if they are used themselves
+    imports: Vec<(K, Usage)>,
+    /// Modules whose exports are all used, e.g. the entry points of a library
+    entries: Vec<K>,
+}
+
+/// An export which is neither imported nor re-exported by a used export of another module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedExport<K> {
+    pub module: K,
+    /// `default` for the default export
+    pub name: Atom,
+    /// Span of the export entry
+    pub span: Span,
+}
+
+#[derive(Debug)]
+struct ModuleExports<K> {
+    /// Exported names, in source order
+    exports: FxIndexMap<Atom, Span>,
+    /// `export { a as b } from "mod"` and `export * as b from "mod"`, keyed by the exported name
+    reexports: FxHashMap<Atom, (K, Usage)>,
+    /// `export * from "mod"`
+    star_reexports: Vec<K>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Usage {
+    Name(Atom),
+    /// All exports, e.g. by `import * as ns from "mod"`
+    All,
+}
+
+impl<K> Default for ExportUsageGraph<K> {
+    fn default() -> Self {
+        Self { modules: FxIndexMap::default(), imports: vec![], entries: vec![] }
+    }
+}
+
+impl<K: Clone + Eq + Hash> ExportUsageGraph<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a module, `resolve` returns the module of a module request, or `None` for modules
+    /// outside of the project, e.g. dependencies
+    pub fn add_module<F>(&mut self, module: K, record: &ModuleRecord, resolve: F)
+    where
+        F: Fn(&str) -> Option<K>,
+    {
+        let resolve_entry = |entry: &ExportEntry| {
+            entry.module_request.as_ref().and_then(|request| resolve(request.name().as_str()))
+        };
+        let mut exports = ModuleExports {
+            exports: FxIndexMap::default(),
+            reexports: FxHashMap::default(),
+            star_reexports: vec![],
+        };
+        for entry in record.local_export_entries.iter().chain(&record.indirect_export_entries) {
+            let name = match &entry.export_name {
+                ExportExportName::Name(name) => name.name().clone(),
+                ExportExportName::Default(_) => Atom::from("default"),
+                ExportExportName::Null => continue,
+            };
+            exports.exports.insert(name.clone(), entry.span);
+            let Some(target) = resolve_entry(entry) else { continue };
+            // `export { a } from "mod"` records the imported name `a` as its local name
+            let usage = match (&entry.import_name, &entry.local_name) {
+                (ExportImportName::All | ExportImportName::AllButDefault, _) => Usage::All,
+                (ExportImportName::Name(import_name), _)
+                | (_, ExportLocalName::Name(import_name)) => {
+                    Usage::Name(import_name.name().clone())
+                }
+                _ => continue,
+            };
+            exports.reexports.insert(name, (target, usage));
+        }
+        exports.exports.sort_by(|_, a, _, b| a.start.cmp(&b.start));
+        exports.star_reexports =
+            record.star_export_entries.iter().filter_map(resolve_entry).collect();
+
+        for entry in &record.import_entries {
+            let Some(target) = resolve(entry.module_request.name().as_str()) else { continue };
+            let usage = match &entry.import_name {
+                ImportImportName::Name(name) => Usage::Name(name.name().clone()),
+                ImportImportName::Default(_) => Usage::Name(Atom::from("default")),
+                ImportImportName::NamespaceObject => Usage::All,
+            };
+            self.imports.push((target, usage));
+        }
+        self.modules.insert(module, exports);
+    }
+
+    /// Mark all exports of the module as used, e.g. for the entry points of a library
+    pub fn add_entry(&mut self, module: K) {
+        self.entries.push(module);
+    }
+
+    /// The unused exports of all modules, in the order the modules were added
+    pub fn unused_exports(&self) -> Vec<UnusedExport<K>> {
+        let used = self.used_exports();
+        let mut unused = vec![];
+        for (module, exports) in &self.modules {
+            if used.contains(&(module, Usage::All)) {
+                continue;
+            }
+            for (name, span) in &exports.exports {
+                if !used.contains(&(module, Usage::Name(name.clone()))) {
+                    unused.push(UnusedExport {
+                        module: module.clone(),
+                        name: name.clone(),
+                        span: *span,
+                    });
+                }
+            }
+        }
+        unused
+    }
+
+    /// Uses of the exports of modules, following re-exports from the imports and entries
+    fn used_exports(&self) -> FxHashSet<(&K, Usage)> {
+        let mut used = FxHashSet::default();
+        let mut stack = self
+            .imports
+            .iter()
+            .map(|(module, usage)| (module, usage.clone()))
+            .chain(self.entries.iter().map(|module| (module, Usage::All)))
+            .collect::<Vec<_>>();
+        while let Some((module, usage)) = stack.pop() {
+            let Some((module, exports)) = self.modules.get_key_value(module) else { continue };
+            if !used.insert((module, usage.clone())) {
+                continue;
+            }
+            match usage {
+                Usage::Name(name) => {
+                    if let Some((target, usage)) = exports.reexports.get(&name) {
+                        stack.push((target, usage.clone()));
+                    } else if !exports.exports.contains_key(&name) && name != "default" {
+                        // `export *` does not re-export the default export
+                        let targets = exports.star_reexports.iter();
+                        stack.extend(targets.map(|target| (target, Usage::Name(name.clone()))));
+                    }
+                }
+                Usage::All => {
+                    for (target, usage) in exports.reexports.values() {
+                        stack.push((target, usage.clone()));
+                    }
+                    stack.extend(exports.star_reexports.iter().map(|target| (target, Usage::All)));
+                }
+            }
+        }
+        used
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+    use oxc_syntax::module_record::ModuleRecord;
+
+    use super::ExportUsageGraph;
+    use crate::SemanticBuilder;
+
+    fn build(source_text: &str) -> ModuleRecord {
+        let source_type = SourceType::default().with_module(true);
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let semantic_ret = SemanticBuilder::new(source_text, source_type)
+            .with_trivias(ret.trivias)
+            .with_module_record_builder(true)
+            .build(program);
+        semantic_ret.semantic.module_record
+    }
+
+    #[test]
+    fn unused_exports() {
+        let modules = [
+            ("main", "import { a, c } from './a'; import d from './d'; import 'side-effect';"),
+            (
+                "a",
+                "export const a = 1, b = 2; export { c, unused } from './c'; export * from './s'",
+            ),
+            ("c", "export const c = 1, unused = 2, never = 3;"),
+            ("d", "export default 1; export const e = 1;"),
+            ("s", "export const s = 1;"),
+            ("lib", "export * as ns from './c'; export const f = 1;"),
+        ];
+        let mut graph = ExportUsageGraph::new();
+        for (name, source_text) in modules {
+            let resolve = |request: &str| {
+                let request = request.strip_prefix("./")?;
+                modules.iter().find(|(name, _)| *name == request).map(|(name, _)| *name)
+            };
+            graph.add_module(name, &build(source_text), resolve);
+        }
+        let unused = |graph: &ExportUsageGraph<&str>| {
+            graph
+                .unused_exports()
+                .into_iter()
+                .map(|export| format!("{}.{}", export.module, export.name))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            unused(&graph),
+            ["a.b", "a.unused", "c.unused", "c.never", "d.e", "s.s", "lib.ns", "lib.f"]
+        );
+
+        graph.add_entry("lib");
+        assert_eq!(unused(&graph), ["a.b", "a.unused", "d.e", "s.s"]);
+    }
+}
@@ -1,6 +1,8 @@
 mod builder;
+mod export_usage;
 
 pub use builder::ModuleRecordBuilder;
+pub use export_usage::{ExportUsageGraph, UnusedExport};
 
 #[cfg(test)]
 mod module_record_tests {
@@ -90,6 +92,7 @@ mod module_record_tests {
         // ExportFromClause : *
         let module_record = build("export * from 'mod'");
         let export_entry = ExportEntry {
+            span: Span::new(0, 19),
             module_request: Some(NameSpan::new("mod".into(), Span::new(14, 19))),
             import_name: ExportImportName::AllButDefault,
             ..ExportEntry::default()
@@ -104,6 +107,7 @@ mod module_record_tests {
         // ExportFromClause : * as ModuleExportName
         let module_record = build("export * as ns from 'mod'");
         let export_entry = ExportEntry {
+            span: Span::new(0, 25),
             module_request: Some(NameSpan::new("mod".into(), Span::new(20, 25))),
             import_name: ExportImportName::All,
             export_name: ExportExportName::Name(NameSpan::new("ns".into(), Span::new(12, 14))),
@@ -119,6 +123,7 @@ mod module_record_tests {
         // ExportSpecifier : ModuleExportName
         let module_record = build("export { x }");
         let export_entry = ExportEntry {
+            span: Span::new(9, 10),
             export_name: ExportExportName::Name(NameSpan::new("x".into(), Span::new(9, 10))),
             local_name: ExportLocalName::Name(NameSpan::new("x".into(), Span::new(9, 10))),
             ..ExportEntry::default()
@@ -133,6 +138,7 @@ mod module_record_tests {
         // ExportSpecifier : ModuleExportName as ModuleExportName
         let module_record = build("export { x as v }");
         let export_entry = ExportEntry {
+            span: Span::new(9, 15),
             export_name: ExportExportName::Name(NameSpan::new("v".into(), Span::new(14, 15))),
             local_name: ExportLocalName::Name(NameSpan::new("x".into(), Span::new(9, 10))),
             ..ExportEntry::default()
@@ -147,6 +153,7 @@ mod module_record_tests {
         // ExportSpecifier : ModuleExportName
         let module_record = build("export { x } from 'mod'");
         let export_entry = ExportEntry {
+            span: Span::new(9, 10),
             module_request: Some(NameSpan::new("mod".into(), Span::new(18, 23))),
             export_name: ExportExportName::Name(NameSpan::new("x".into(), Span::new(9, 10))),
             local_name: ExportLocalName::Name(NameSpan::new("x".into(), Span::new(9, 10))),
@@ -162,6 +169,7 @@ mod module_record_tests {
         // ExportSpecifier : ModuleExportName as ModuleExportName
         let module_record = build("export { x as v } from 'mod'");
         let export_entry = ExportEntry {
+            span: Span::new(9, 15),
             module_request: Some(NameSpan::new("mod".into(), Span::new(23, 28))),
             export_name: ExportExportName::Name(NameSpan::new("v".into(), Span::new(14, 15))),
             local_name: ExportLocalName::Name(NameSpan::new("x".into(), Span::new(9, 10))),
@@ -176,6 +184,7 @@ mod module_record_tests {
         // ExportDeclaration : export VariableStatement
         let module_record = build("export var v");
         let export_entry = ExportEntry {
+            span: Span::new(0, 12),
             export_name: ExportExportName::Name(NameSpan::new("v".into(), Span::new(11, 12))),
             local_name: ExportLocalName::Name(NameSpan::new("v".into(), Span::new(11, 12))),
             ..ExportEntry::default()
@@ -189,6 +198,7 @@ mod module_record_tests {
         // ExportDeclaration : export default HoistableDeclaration
         let module_record = build("export default function f() {}");
         let export_entry = ExportEntry {
+            span: Span::new(0, 30),
             export_name: ExportExportName::Default(Span::new(7, 14)),
             local_name: ExportLocalName::Name(NameSpan::new("f".into(), Span::new(24, 25))),
             ..ExportEntry::default()
@@ -202,6 +212,7 @@ mod module_record_tests {
         // ExportDeclaration : export default HoistableDeclaration
         let module_record = build("export default function() {}");
         let export_entry = ExportEntry {
+            span: Span::new(0, 28),
             export_name: ExportExportName::Default(Span::new(7, 14)),
             local_name: ExportLocalName::Default(Span::new(7, 14)),
             ..ExportEntry::default()
@@ -215,6 +226,7 @@ mod module_record_tests {
         // ExportDeclaration : export default HoistableDeclaration
         let module_record = build("export default 42");
         let export_entry = ExportEntry {
+            span: Span::new(0, 17),
             export_name: ExportExportName::Default(Span::new(7, 14)),
             local_name: ExportLocalName::Default(Span::new(7, 14)),
             ..ExportEntry::default()
@@ -227,6 +239,7 @@ mod module_record_tests {
     fn export_named_default() {
         let module_record = build("export { default }");
         let export_entry = ExportEntry {
+            span: Span::new(9, 16),
             export_name: ExportExportName::Name(NameSpan::new("default".into(), Span::new(9, 16))),
             local_name: ExportLocalName::Name(NameSpan::new("default".into(), Span::new(9, 16))),
             ..ExportEntry::default()
@@ -234,4 +247,19 @@ mod module_record_tests {
         assert_eq!(module_record.local_export_entries.len(), 1);
         assert_eq!(module_record.local_export_entries[0], export_entry);
     }
+
+    #[test]
+    fn reexport_import() {
+        let module_record = build("import { a } from 'mod'; export { a as b }");
+        let export_entry = ExportEntry {
+            span: Span::new(34, 40),
+            module_request: Some(NameSpan::new("mod".into(), Span::new(18, 23))),
+            import_name: ExportImportName::Name(NameSpan::new("a".into(), Span::new(9, 10))),
+            export_name: ExportExportName::Name(NameSpan::new("b".into(), Span::new(39, 40))),
+            ..ExportEntry::default()
+        };
+        assert!(module_record.local_export_entries.is_empty());
+        assert_eq!(module_record.indirect_export_entries.len(), 1);
+        assert_eq!(module_record.indirect_export_entries[0], export_entry);
+    }
 }
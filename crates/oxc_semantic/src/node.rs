@@ -1,12 +1,23 @@
 #![allow(non_upper_case_globals)] // for bitflags
 
+use std::mem::Discriminant;
+
 use bitflags::bitflags;
-use oxc_ast::AstKind;
+use oxc_ast::{
+    ast::{Class, ClassElement, PropertyKey},
+    AstKind,
+};
 use oxc_index::{define_index_type, IndexVec};
+use oxc_span::GetSpan;
+use rustc_hash::FxHashMap;
 
 use crate::scope::{ScopeFlags, ScopeId};
 
 define_index_type! {
+    /// Id of an AST node, assigned in visitation order while building the semantic model.
+    ///
+    /// Building the same program always produces the same ids, so side tables
+    /// can be keyed by `AstNodeId` instead of holding references into the AST.
     pub struct AstNodeId = usize;
 }
 
@@ -87,6 +98,10 @@ impl<'a> AstNode<'a> {
 pub struct AstNodes<'a> {
     nodes: IndexVec<AstNodeId, AstNode<'a>>,
     parent_ids: IndexVec<AstNodeId, Option<AstNodeId>>,
+    /// Children of each node in visitation order
+    child_ids: IndexVec<AstNodeId, Vec<AstNodeId>>,
+    /// Reverse lookup from a node's kind and address to its id
+    node_ids: FxHashMap<(Discriminant<AstKind<'a>>, usize), AstNodeId>,
 }
 
 impl<'a> AstNodes<'a> {
@@ -96,8 +111,8 @@ impl<'a> AstNodes<'a> {
 
     /// Walk up the AST, iterating over each parent node.
     ///
-    /// The first node produced by this iterator is the first parent of the node
-    /// pointed to by `node_id`. The last node will usually be a `Program`.
+    /// The first node produced by this iterator is the node pointed to by `node_id`
+    /// itself, followed by its parent. The last node will usually be a `Program`.
     pub fn iter_parents(&self, node_id: AstNodeId) -> impl Iterator<Item = &AstNode<'a>> + '_ {
         let curr = Some(self.get_node(node_id));
         AstNodeParentIter { curr, nodes: self }
@@ -123,26 +138,119 @@ impl<'a> AstNodes<'a> {
         &self.nodes[ast_node_id]
     }
 
+    /// The id of an AST node, looked up by its kind and address, so nodes which share a span
+    /// such as `a` in `a;` and the `ExpressionStatement` are told apart.
+    ///
+    /// Returns `None` if the node was not visited by the semantic builder.
+    pub fn get_node_id(&self, kind: AstKind<'a>) -> Option<AstNodeId> {
+        self.node_ids.get(&Self::node_key(kind)?).copied()
+    }
+
     pub fn get_node_mut(&mut self, ast_node_id: AstNodeId) -> &mut AstNode<'a> {
         &mut self.nodes[ast_node_id]
     }
 
     /// Walk up the AST, iterating over each parent node.
     ///
-    /// The first node produced by this iterator is the first parent of the node
-    /// pointed to by `node_id`. The last node will usually be a `Program`.
+    /// The first node produced by this iterator is the node pointed to by `ast_node_id`
+    /// itself, followed by its parent. The last node will usually be a `Program`.
     pub fn ancestors(&self, ast_node_id: AstNodeId) -> impl Iterator<Item = AstNodeId> + '_ {
         let parent_ids = &self.parent_ids;
         std::iter::successors(Some(ast_node_id), |node_id| parent_ids[*node_id])
     }
 
+    /// Kinds of the ancestors of the node, from its parent up to the `Program`.
+    pub fn ancestor_kinds(&self, ast_node_id: AstNodeId) -> impl Iterator<Item = AstKind<'a>> + '_ {
+        self.ancestors(ast_node_id).skip(1).map(|node_id| self.kind(node_id))
+    }
+
+    /// The closest ancestor of the node whose kind matches `predicate`, e.g. the
+    /// `describe(...)` call a test is inside of.
+    pub fn find_ancestor<F>(&self, ast_node_id: AstNodeId, predicate: F) -> Option<&AstNode<'a>>
+    where
+        F: Fn(AstKind<'a>) -> bool,
+    {
+        self.ancestors(ast_node_id)
+            .skip(1)
+            .map(|node_id| self.get_node(node_id))
+            .find(|node| predicate(node.kind()))
+    }
+
+    /// The classes whose private names can be used at the node, innermost first.
+    ///
+    /// The `extends` clause of a class is outside of its body, so only the private names of
+    /// the classes around the class can be used there.
+    pub fn private_name_classes(
+        &self,
+        ast_node_id: AstNodeId,
+    ) -> impl Iterator<Item = &'a Class<'a>> + '_ {
+        let mut in_heritage = false;
+        self.ancestor_kinds(ast_node_id).filter_map(move |kind| match kind {
+            AstKind::ClassHeritage(_) => {
+                in_heritage = true;
+                None
+            }
+            AstKind::Class(class) => {
+                let is_heritage_owner = std::mem::take(&mut in_heritage);
+                (!is_heritage_owner).then_some(class)
+            }
+            _ => None,
+        })
+    }
+
+    /// The class element declaring the private name `#name` used at the node,
+    /// e.g. `#x = 1` for `this.#x`
+    pub fn private_member_declaration(
+        &self,
+        ast_node_id: AstNodeId,
+        name: &str,
+    ) -> Option<&'a ClassElement<'a>> {
+        self.private_name_classes(ast_node_id).find_map(|class| {
+            class.body.body.iter().find(|element| {
+                matches!(element.property_key(), Some(PropertyKey::PrivateIdentifier(ident))
+                    if ident.name == name)
+            })
+        })
+    }
+
+    /// The innermost node whose span contains the byte `offset`, which is the node a
+    /// cursor at `offset` is on. Use [`AstNodes::ancestors`] for the enclosing nodes.
+    ///
+    /// Spans are half open, so a cursor right after `foo` in `foo;` is on the
+    /// `ExpressionStatement` and not on `foo`.
+    pub fn node_at_offset(&self, offset: u32) -> Option<&AstNode<'a>> {
+        let contains = |node_id: &AstNodeId| {
+            let span = self.kind(*node_id).span();
+            span.start <= offset && offset < span.end
+        };
+        // Descend from the root through the children containing the offset. Siblings such as
+        // the key and the value of `{ a }` can share a span, in which case the last one wins.
+        let root_id = self.nodes.indices().next().filter(contains)?;
+        let node_id = std::iter::successors(Some(root_id), |node_id| {
+            self.child_ids[*node_id].iter().rev().find(|child_id| contains(child_id)).copied()
+        })
+        .last()?;
+        Some(self.get_node(node_id))
+    }
+
     pub fn add_node(&mut self, node: AstNode<'a>, parent_id: Option<AstNodeId>) -> AstNodeId {
         let mut node = node;
         let ast_node_id = self.parent_ids.push(parent_id);
+        self.child_ids.push(vec![]);
+        if let Some(parent_id) = parent_id {
+            self.child_ids[parent_id].push(ast_node_id);
+        }
         node.id = ast_node_id;
+        if let Some(key) = Self::node_key(node.kind) {
+            self.node_ids.insert(key, ast_node_id);
+        }
         self.nodes.push(node);
         ast_node_id
     }
+
+    fn node_key(kind: AstKind<'a>) -> Option<(Discriminant<AstKind<'a>>, usize)> {
+        Some((std::mem::discriminant(&kind), kind.address()?))
+    }
 }
 
 #[derive(Debug)]
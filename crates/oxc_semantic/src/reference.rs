@@ -41,6 +41,10 @@ impl Reference {
         self.symbol_id
     }
 
+    pub fn flag(&self) -> ReferenceFlag {
+        self.flag
+    }
+
     pub(crate) fn set_symbol_id(&mut self, symbol_id: SymbolId) {
         self.symbol_id = Some(symbol_id);
     }
@@ -58,6 +62,25 @@ impl Reference {
     }
 }
 
+/// A binding of an enclosing scope which is referenced in a function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capture {
+    pub symbol_id: SymbolId,
+    /// Whether the function writes to the binding, rather than only reading it
+    pub is_mutated: bool,
+}
+
+/// The declaration or a reference of a symbol, see [`crate::Semantic::symbol_occurrences`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolOccurrence {
+    pub span: Span,
+    /// `None` for the declaration
+    pub reference_id: Option<ReferenceId>,
+    /// Whether the identifier is both the key and the value of a property, e.g. `a` in `{ a }`
+    /// and `const { a } = b`, which has to be expanded to `{ a: renamed }` when renaming it
+    pub is_shorthand: bool,
+}
+
 bitflags! {
     #[derive(Debug, Clone, Copy, Eq, PartialEq)]
     pub struct ReferenceFlag: u8 {
@@ -5,21 +5,66 @@ use oxc_ast::{ast::ClassType, AstKind};
 use oxc_index::IndexVec;
 use oxc_span::{Atom, SourceType};
 pub use oxc_syntax::scope::{ScopeFlags, ScopeId};
-use rustc_hash::{FxHashMap, FxHasher};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 
-use crate::{reference::ReferenceId, symbol::SymbolId};
+use crate::{node::AstNodeId, reference::ReferenceId, symbol::SymbolId};
 
 type FxIndexMap<K, V> = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
 
 type Bindings = FxIndexMap<Atom, SymbolId>;
 type UnresolvedReferences = FxHashMap<Atom, Vec<ReferenceId>>;
 
+/// The kind of node which creates a scope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    /// The top level of a script
+    Global,
+    /// The top level of an ES module
+    Module,
+    /// Functions and arrow functions, including their parameters
+    Function,
+    /// Block statements, `for` loops and `switch` statements
+    Block,
+    /// Class expressions, which bind the name of the class
+    Class,
+    Catch,
+    ClassStaticBlock,
+    TsModule,
+    TsEnum,
+    TsTypeParameter,
+}
+
+impl ScopeKind {
+    pub fn from_ast_kind(kind: AstKind, source_type: SourceType) -> Option<Self> {
+        match kind {
+            AstKind::Program(_) if source_type.is_module() => Some(Self::Module),
+            AstKind::Program(_) => Some(Self::Global),
+            AstKind::Function(_) | AstKind::ArrowExpression(_) => Some(Self::Function),
+            AstKind::BlockStatement(_)
+            | AstKind::ForStatement(_)
+            | AstKind::ForInStatement(_)
+            | AstKind::ForOfStatement(_)
+            | AstKind::SwitchStatement(_) => Some(Self::Block),
+            AstKind::Class(_) => Some(Self::Class),
+            AstKind::CatchClause(_) => Some(Self::Catch),
+            AstKind::StaticBlock(_) => Some(Self::ClassStaticBlock),
+            AstKind::TSModuleBlock(_) => Some(Self::TsModule),
+            AstKind::TSEnumBody(_) => Some(Self::TsEnum),
+            AstKind::TSTypeParameter(_) => Some(Self::TsTypeParameter),
+            _ => None,
+        }
+    }
+}
+
 /// Scope Tree
 ///
 /// `SoA` (Struct of Arrays) for memory efficiency.
 #[derive(Debug, Default)]
 pub struct ScopeTree {
     parent_ids: IndexVec<ScopeId, Option<ScopeId>>,
+    child_ids: IndexVec<ScopeId, Vec<ScopeId>>,
+    /// The node which creates each scope
+    node_ids: IndexVec<ScopeId, Option<AstNodeId>>,
     flags: IndexVec<ScopeId, ScopeFlags>,
     bindings: IndexVec<ScopeId, Bindings>,
     unresolved_references: IndexVec<ScopeId, UnresolvedReferences>,
@@ -50,6 +95,27 @@ impl ScopeTree {
         self.parent_ids.iter_enumerated().map(|(scope_id, _)| scope_id)
     }
 
+    /// `scope_id` and the scopes nested in it, depth first in source order
+    pub fn descendants_of(&self, scope_id: ScopeId) -> impl Iterator<Item = ScopeId> + '_ {
+        let mut stack = vec![scope_id];
+        std::iter::from_fn(move || {
+            let scope_id = stack.pop()?;
+            stack.extend(self.child_ids[scope_id].iter().rev());
+            Some(scope_id)
+        })
+    }
+
+    /// Scopes directly nested in `scope_id`, in source order
+    pub fn get_child_ids(&self, scope_id: ScopeId) -> &[ScopeId] {
+        &self.child_ids[scope_id]
+    }
+
+    /// The node which creates the scope, e.g. a `Function` or `BlockStatement`.
+    /// `None` for scopes which were not created from the AST.
+    pub fn get_node_id(&self, scope_id: ScopeId) -> Option<AstNodeId> {
+        self.node_ids[scope_id]
+    }
+
     pub fn root_scope_id(&self) -> ScopeId {
         ScopeId::new(0)
     }
@@ -87,6 +153,19 @@ impl ScopeTree {
         &self.bindings[scope_id]
     }
 
+    /// Bindings which are visible in `scope_id`, from the innermost scope outwards.
+    /// Shadowed bindings are skipped.
+    pub fn visible_bindings(
+        &self,
+        scope_id: ScopeId,
+    ) -> impl Iterator<Item = (&Atom, SymbolId)> + '_ {
+        let mut names = FxHashSet::default();
+        self.ancestors(scope_id)
+            .flat_map(|scope_id| self.bindings[scope_id].iter())
+            .filter(move |(name, _)| names.insert(*name))
+            .map(|(name, symbol_id)| (name, *symbol_id))
+    }
+
     pub fn iter_bindings(&self) -> impl Iterator<Item = (ScopeId, SymbolId, Atom)> + '_ {
         self.bindings.iter_enumerated().flat_map(|(scope_id, bindings)| {
             bindings.iter().map(move |(name, symbol_id)| (scope_id, *symbol_id, name.clone()))
@@ -99,12 +178,21 @@ impl ScopeTree {
 
     pub(crate) fn add_scope(&mut self, parent_id: Option<ScopeId>, flags: ScopeFlags) -> ScopeId {
         let scope_id = self.parent_ids.push(parent_id);
+        _ = self.child_ids.push(vec![]);
+        _ = self.node_ids.push(None);
+        if let Some(parent_id) = parent_id {
+            self.child_ids[parent_id].push(scope_id);
+        }
         _ = self.flags.push(flags);
         _ = self.bindings.push(Bindings::default());
         _ = self.unresolved_references.push(UnresolvedReferences::default());
         scope_id
     }
 
+    pub(crate) fn set_node_id(&mut self, scope_id: ScopeId, node_id: AstNodeId) {
+        self.node_ids[scope_id] = Some(node_id);
+    }
+
     pub(crate) fn add_binding(&mut self, scope_id: ScopeId, name: Atom, symbol_id: SymbolId) {
         self.bindings[scope_id].insert(name, symbol_id);
     }
@@ -127,6 +215,10 @@ impl ScopeTree {
         self.unresolved_references[scope_id].entry(name).or_default().extend(reference_ids);
     }
 
+    pub(crate) fn get_unresolved_references(&self, scope_id: ScopeId) -> &UnresolvedReferences {
+        &self.unresolved_references[scope_id]
+    }
+
     pub(crate) fn unresolved_references_mut(
         &mut self,
         scope_id: ScopeId,
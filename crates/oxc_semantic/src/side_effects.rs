@@ -0,0 +1,282 @@
+//! Conservative side effect analysis of expressions
+//!
+//! Shared by dead code elimination and lint rules such as `no-unused-expressions`, which need to
+//! know whether an expression can be removed when its value is unused.
+
+use bitflags::bitflags;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_syntax::operator::{BinaryOperator, UnaryOperator};
+
+use crate::{
+    globals::{global_variable, Environment},
+    Semantic,
+};
+
+bitflags! {
+    /// The effects which evaluating an expression may have, see [`Semantic::side_effects`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SideEffects: u8 {
+        /// May change state outside of the expression, e.g. by calling a function or assigning
+        /// to a variable
+        const Mutates      = 1 << 0;
+        /// May throw, e.g. by reading an undeclared variable or a property of `null`
+        const MayThrow     = 1 << 1;
+        /// May read a global variable
+        const ReadsGlobal  = 1 << 2;
+        /// May write to a global variable
+        const WritesGlobal = 1 << 3;
+    }
+}
+
+impl SideEffects {
+    /// The expression can be removed if its value is unused
+    pub fn is_pure(self) -> bool {
+        !self.intersects(Self::Mutates | Self::MayThrow)
+    }
+}
+
+impl<'a> Semantic<'a> {
+    /// The side effects of evaluating the expression.
+    ///
+    /// Calls are assumed to do anything, unless they are annotated with `/* #__PURE__ */`, in
+    /// which case only their arguments are evaluated.
+    /// Conversions of operands to primitives are assumed to be pure, e.g. `a + b` only has the
+    /// side effects of `a` and `b`.
+    pub fn side_effects(&self, expr: &Expression<'a>) -> SideEffects {
+        match expr {
+            Expression::BooleanLiteral(_)
+            | Expression::NullLiteral(_)
+            | Expression::NumberLiteral(_)
+            | Expression::BigintLiteral(_)
+            | Expression::RegExpLiteral(_)
+            | Expression::StringLiteral(_)
+            | Expression::ThisExpression(_)
+            | Expression::Super(_)
+            | Expression::MetaProperty(_)
+            | Expression::FunctionExpression(_)
+            | Expression::ArrowExpression(_) => SideEffects::empty(),
+            Expression::TemplateLiteral(lit) => self.all_side_effects(&lit.expressions),
+            Expression::Identifier(ident) => self.identifier_side_effects(ident),
+            Expression::ArrayExpression(array) => self.array_side_effects(array),
+            Expression::ObjectExpression(object) => self.object_side_effects(object),
+            Expression::UnaryExpression(expr) => match (expr.operator, &expr.argument) {
+                (UnaryOperator::Delete, argument) => {
+                    SideEffects::Mutates | self.side_effects(argument)
+                }
+                // `typeof` does not throw for undeclared variables
+                (UnaryOperator::Typeof, Expression::Identifier(ident)) => {
+                    self.identifier_side_effects(ident) - SideEffects::MayThrow
+                }
+                (_, argument) => self.side_effects(argument),
+            },
+            Expression::BinaryExpression(expr) => {
+                let effects = self.side_effects(&expr.left) | self.side_effects(&expr.right);
+                // Throws if the right side is not an object or a constructor
+                if matches!(expr.operator, BinaryOperator::In | BinaryOperator::Instanceof) {
+                    effects | SideEffects::MayThrow
+                } else {
+                    effects
+                }
+            }
+            Expression::PrivateInExpression(expr) => {
+                SideEffects::MayThrow | self.side_effects(&expr.right)
+            }
+            Expression::LogicalExpression(expr) => {
+                self.side_effects(&expr.left) | self.side_effects(&expr.right)
+            }
+            Expression::ConditionalExpression(expr) => {
+                self.side_effects(&expr.test)
+                    | self.side_effects(&expr.consequent)
+                    | self.side_effects(&expr.alternate)
+            }
+            Expression::SequenceExpression(expr) => self.all_side_effects(&expr.expressions),
+            Expression::ParenthesizedExpression(expr) => self.side_effects(&expr.expression),
+            Expression::TSAsExpression(expr) => self.side_effects(&expr.expression),
+            Expression::TSSatisfiesExpression(expr) => self.side_effects(&expr.expression),
+            Expression::TSTypeAssertion(expr) => self.side_effects(&expr.expression),
+            Expression::TSNonNullExpression(expr) => self.side_effects(&expr.expression),
+            Expression::TSInstantiationExpression(expr) => self.side_effects(&expr.expression),
+            Expression::MemberExpression(expr) => self.member_side_effects(expr),
+            Expression::ChainExpression(_)
+            | Expression::CallExpression(_)
+            | Expression::NewExpression(_) => self.call_side_effects(expr),
+            Expression::ClassExpression(class) => self.class_side_effects(class),
+            Expression::AssignmentExpression(expr) => {
+                SideEffects::Mutates
+                    | self.assignment_target_side_effects(&expr.left)
+                    | self.side_effects(&expr.right)
+            }
+            Expression::UpdateExpression(expr) => {
+                SideEffects::Mutates | self.simple_assignment_target_side_effects(&expr.argument)
+            }
+            Expression::AwaitExpression(_)
+            | Expression::YieldExpression(_)
+            | Expression::ImportExpression(_)
+            | Expression::TaggedTemplateExpression(_)
+            | Expression::JSXElement(_)
+            | Expression::JSXFragment(_)
+            | Expression::ErrorExpression(_) => SideEffects::all(),
+        }
+    }
+
+    fn all_side_effects(&self, exprs: &[Expression<'a>]) -> SideEffects {
+        exprs.iter().fold(SideEffects::empty(), |effects, expr| effects | self.side_effects(expr))
+    }
+
+    /// Reading an undeclared global which is not an intrinsic is `ReadsGlobal | MayThrow`
+    /// wherever it is evaluated, e.g. `bar` in `/* #__PURE__ */ new Foo(bar)`
+    fn identifier_side_effects(&self, ident: &IdentifierReference) -> SideEffects {
+        if !self.is_reference_to_global_variable(ident) {
+            return SideEffects::empty();
+        }
+        // Reading ECMAScript intrinsics never throws, they are defined in all environments
+        if global_variable(&ident.name, Environment::Builtin).is_some() {
+            SideEffects::ReadsGlobal
+        } else {
+            // Throws if the global is not defined
+            SideEffects::ReadsGlobal | SideEffects::MayThrow
+        }
+    }
+
+    fn array_side_effects(&self, array: &ArrayExpression<'a>) -> SideEffects {
+        array.elements.iter().fold(SideEffects::empty(), |effects, element| {
+            effects
+                | match element {
+                    ArrayExpressionElement::SpreadElement(spread) => {
+                        self.spread_side_effects(spread)
+                    }
+                    ArrayExpressionElement::Expression(expr) => self.side_effects(expr),
+                    ArrayExpressionElement::Elision(_) => SideEffects::empty(),
+                }
+        })
+    }
+
+    fn object_side_effects(&self, object: &ObjectExpression<'a>) -> SideEffects {
+        object.properties.iter().fold(SideEffects::empty(), |effects, property| {
+            effects
+                | match property {
+                    ObjectPropertyKind::ObjectProperty(property) => {
+                        self.property_key_side_effects(&property.key)
+                            | self.side_effects(&property.value)
+                    }
+                    ObjectPropertyKind::SpreadProperty(spread) => self.spread_side_effects(spread),
+                }
+        })
+    }
+
+    /// Spreading runs the iterator of arrays and the getters of objects
+    fn spread_side_effects(&self, spread: &SpreadElement<'a>) -> SideEffects {
+        SideEffects::all() | self.side_effects(&spread.argument)
+    }
+
+    fn property_key_side_effects(&self, key: &PropertyKey<'a>) -> SideEffects {
+        match key {
+            PropertyKey::Expression(expr) => self.side_effects(expr),
+            PropertyKey::Identifier(_) | PropertyKey::PrivateIdentifier(_) => SideEffects::empty(),
+        }
+    }
+
+    /// Reading a property throws on `null` and `undefined`, getters are assumed to be pure
+    fn member_side_effects(&self, expr: &MemberExpression<'a>) -> SideEffects {
+        let effects = SideEffects::MayThrow | self.side_effects(expr.object());
+        match expr {
+            MemberExpression::ComputedMemberExpression(expr) => {
+                effects | self.side_effects(&expr.expression)
+            }
+            MemberExpression::StaticMemberExpression(_)
+            | MemberExpression::PrivateFieldExpression(_) => effects,
+        }
+    }
+
+    /// Calls, `new` expressions and optional chains
+    fn call_side_effects(&self, expr: &Expression<'a>) -> SideEffects {
+        let (pure, arguments) = match expr {
+            Expression::CallExpression(call) => (call.pure, &call.arguments),
+            Expression::NewExpression(expr) => (expr.pure, &expr.arguments),
+            Expression::ChainExpression(expr) => match &expr.expression {
+                ChainElement::CallExpression(call) => (call.pure, &call.arguments),
+                ChainElement::MemberExpression(expr) => return self.member_side_effects(expr),
+            },
+            _ => unreachable!(),
+        };
+        if !pure {
+            return SideEffects::all();
+        }
+        arguments.iter().fold(SideEffects::empty(), |effects, argument| {
+            effects
+                | match argument {
+                    Argument::SpreadElement(spread) => self.spread_side_effects(spread),
+                    Argument::Expression(expr) => self.side_effects(expr),
+                }
+        })
+    }
+
+    /// Evaluating a class evaluates its heritage, computed keys and static initializers
+    fn class_side_effects(&self, class: &Class<'a>) -> SideEffects {
+        if !class.decorators.is_empty() {
+            return SideEffects::all();
+        }
+        let mut effects = class.super_class.as_ref().map_or(SideEffects::empty(), |expr| {
+            // Throws if the super class is not a constructor
+            SideEffects::MayThrow | self.side_effects(expr)
+        });
+        for element in &class.body.body {
+            effects |= match element {
+                ClassElement::StaticBlock(_) => return SideEffects::all(),
+                ClassElement::MethodDefinition(method) => {
+                    self.property_key_side_effects(&method.key)
+                }
+                ClassElement::PropertyDefinition(prop) => {
+                    let value = prop.value.as_ref().filter(|_| prop.r#static);
+                    self.property_key_side_effects(&prop.key)
+                        | value.map_or(SideEffects::empty(), |value| self.side_effects(value))
+                }
+                ClassElement::AccessorProperty(prop) => {
+                    let value = prop.value.as_ref().filter(|_| prop.r#static);
+                    self.property_key_side_effects(&prop.key)
+                        | value.map_or(SideEffects::empty(), |value| self.side_effects(value))
+                }
+                ClassElement::TSAbstractMethodDefinition(_)
+                | ClassElement::TSAbstractPropertyDefinition(_)
+                | ClassElement::TSIndexSignature(_) => SideEffects::empty(),
+            };
+        }
+        effects
+    }
+
+    /// The side effects of evaluating the target of an assignment, besides the assignment itself
+    fn assignment_target_side_effects(&self, target: &AssignmentTarget<'a>) -> SideEffects {
+        match target {
+            AssignmentTarget::SimpleAssignmentTarget(target) => {
+                self.simple_assignment_target_side_effects(target)
+            }
+            // Destructuring runs iterators and getters
+            AssignmentTarget::AssignmentTargetPattern(_) => SideEffects::all(),
+        }
+    }
+
+    fn simple_assignment_target_side_effects(
+        &self,
+        target: &SimpleAssignmentTarget<'a>,
+    ) -> SideEffects {
+        match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                if self.is_reference_to_global_variable(ident) {
+                    SideEffects::ReadsGlobal | SideEffects::WritesGlobal | SideEffects::MayThrow
+                } else {
+                    SideEffects::empty()
+                }
+            }
+            SimpleAssignmentTarget::MemberAssignmentTarget(expr) => self.member_side_effects(expr),
+            SimpleAssignmentTarget::TSAsExpression(expr) => self.side_effects(&expr.expression),
+            SimpleAssignmentTarget::TSSatisfiesExpression(expr) => {
+                self.side_effects(&expr.expression)
+            }
+            SimpleAssignmentTarget::TSNonNullExpression(expr) => {
+                self.side_effects(&expr.expression)
+            }
+            SimpleAssignmentTarget::TSTypeAssertion(expr) => self.side_effects(&expr.expression),
+        }
+    }
+}
@@ -0,0 +1,588 @@
+//! Compact binary snapshots of semantic results
+//!
+//! [`Semantic::snapshot`] encodes the symbol table, scope tree and module record of a file,
+//! keyed by a SHA-256 hash of its source, so project-wide analyses and language servers can cache them
+//! on disk and skip running [`crate::SemanticBuilder`] on files which have not changed.
+//!
+//! `AstNodeId`s are stored as they are, they only point to the right nodes if the AST is parsed
+//! again from the same source.
+
+use oxc_span::{Atom, SourceType, Span};
+use oxc_syntax::module_record::{
+    ExportEntry, ExportExportName, ExportImportName, ExportLocalName, ImportAttributeEntry,
+    ImportEntry, ImportImportName, ModuleRecord, ModuleRequestAttributes, NameSpan,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    node::AstNodeId,
+    reference::{Reference, ReferenceFlag, ReferenceId},
+    scope::{ScopeFlags, ScopeId, ScopeTree},
+    symbol::{SymbolFlags, SymbolId, SymbolTable},
+    Semantic,
+};
+
+const MAGIC: &[u8; 4] = b"OXCS";
+
+/// Bumped whenever the encoding or the semantic analysis changes, which invalidates all snapshots
+const VERSION: u64 = 2;
+
+/// The semantic results of a file decoded from a snapshot
+#[derive(Debug)]
+pub struct SemanticSnapshot {
+    pub symbols: SymbolTable,
+    pub scopes: ScopeTree,
+    pub module_record: ModuleRecord,
+}
+
+impl SemanticSnapshot {
+    /// The hash a snapshot is keyed by. A cryptographic hash, as a collision would make a cache
+    /// return the symbols of another file.
+    pub fn source_hash(source_text: &str, source_type: SourceType) -> [u8; 32] {
+        let source_type = format!("{source_type:?}");
+        let mut hasher = Sha256::new();
+        hasher.update(VERSION.to_le_bytes());
+        hasher.update((source_type.len() as u64).to_le_bytes());
+        hasher.update(source_type);
+        hasher.update(source_text);
+        hasher.finalize().into()
+    }
+
+    /// Decode a snapshot created by [`Semantic::snapshot`].
+    /// Returns `None` if the snapshot was created from a different source or by a different
+    /// version of oxc, or is malformed.
+    pub fn decode(bytes: &[u8], source_text: &str, source_type: SourceType) -> Option<Self> {
+        let mut reader = Reader { bytes: bytes.strip_prefix(MAGIC)? };
+        if reader.u64()? != VERSION || reader.hash()? != Self::source_hash(source_text, source_type)
+        {
+            return None;
+        }
+        let lens =
+            Lens { symbols: reader.usize()?, references: reader.usize()?, scopes: reader.usize()? };
+
+        let symbols = reader.symbols(&lens)?;
+        let scopes = reader.scopes(&lens)?;
+        let module_record = reader.module_record()?;
+        reader.bytes.is_empty().then_some(Self { symbols, scopes, module_record })
+    }
+}
+
+impl<'a> Semantic<'a> {
+    /// Encode the symbol table, scope tree and module record, see [`SemanticSnapshot`]
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut writer = Writer { bytes: MAGIC.to_vec() };
+        writer.u64(VERSION);
+        writer.bytes.extend(SemanticSnapshot::source_hash(self.source_text, self.source_type));
+        writer.usize(self.symbols.len());
+        writer.usize(self.symbols.references.len());
+        writer.usize(self.scopes.len());
+
+        writer.symbols(&self.symbols);
+        writer.scopes(&self.scopes);
+        writer.module_record(&self.module_record);
+        writer.bytes
+    }
+}
+
+/// Integers are encoded as LEB128, ids as their index and optional ids as their index plus one
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    #[allow(clippy::cast_possible_truncation)]
+    fn u64(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    fn option_usize(&mut self, value: Option<usize>) {
+        self.usize(value.map_or(0, |value| value + 1));
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.bytes.push(u8::from(value));
+    }
+
+    fn str(&mut self, value: &str) {
+        self.usize(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    fn span(&mut self, span: Span) {
+        self.u64(u64::from(span.start));
+        self.u64(u64::from(span.end));
+    }
+
+    fn name_span(&mut self, name_span: &NameSpan) {
+        self.str(name_span.name());
+        self.span(name_span.span());
+    }
+
+    fn seq<T>(&mut self, items: &[T], mut write: impl FnMut(&mut Self, &T)) {
+        self.usize(items.len());
+        for item in items {
+            write(self, item);
+        }
+    }
+
+    fn symbols(&mut self, symbols: &SymbolTable) {
+        for symbol_id in symbols.iter() {
+            self.span(symbols.get_span(symbol_id));
+            self.str(symbols.get_name(symbol_id));
+            self.u64(u64::from(symbols.get_flag(symbol_id).bits()));
+            self.usize(symbols.get_scope_id(symbol_id).index());
+            self.usize(symbols.get_declaration(symbol_id).index());
+            self.seq(symbols.get_resolved_reference_ids(symbol_id), |writer, reference_id| {
+                writer.usize(reference_id.index());
+            });
+        }
+        for reference in &symbols.references {
+            self.span(reference.span());
+            self.str(reference.name());
+            self.usize(reference.node_id().index());
+            self.option_usize(reference.symbol_id().map(SymbolId::index));
+            self.u64(u64::from(reference.flag().bits()));
+        }
+    }
+
+    fn scopes(&mut self, scopes: &ScopeTree) {
+        for scope_id in scopes.descendants() {
+            self.option_usize(scopes.get_parent_id(scope_id).map(ScopeId::index));
+            self.option_usize(scopes.get_node_id(scope_id).map(AstNodeId::index));
+            self.u64(u64::from(scopes.get_flags(scope_id).bits()));
+            let bindings = scopes.get_bindings(scope_id);
+            self.usize(bindings.len());
+            for (name, symbol_id) in bindings {
+                self.str(name);
+                self.usize(symbol_id.index());
+            }
+            let unresolved_references = scopes.get_unresolved_references(scope_id);
+            self.usize(unresolved_references.len());
+            for (name, reference_ids) in unresolved_references {
+                self.str(name);
+                self.seq(reference_ids, |writer, reference_id| writer.usize(reference_id.index()));
+            }
+        }
+    }
+
+    fn module_record(&mut self, module_record: &ModuleRecord) {
+        self.usize(module_record.module_requests.len());
+        for (name, spans) in &module_record.module_requests {
+            self.str(name);
+            self.seq(spans, |writer, span| writer.span(*span));
+        }
+        self.seq(&module_record.import_attributes, |writer, request| {
+            writer.name_span(&request.module_request);
+            writer.seq(&request.attributes, |writer, attribute| {
+                writer.name_span(&attribute.key);
+                writer.name_span(&attribute.value);
+            });
+        });
+        self.seq(&module_record.import_entries, |writer, entry| {
+            writer.name_span(&entry.module_request);
+            match &entry.import_name {
+                ImportImportName::Name(name_span) => {
+                    writer.usize(0);
+                    writer.name_span(name_span);
+                }
+                ImportImportName::NamespaceObject => writer.usize(1),
+                ImportImportName::Default(span) => {
+                    writer.usize(2);
+                    writer.span(*span);
+                }
+            }
+            writer.name_span(&entry.local_name);
+        });
+        self.seq(&module_record.local_export_entries, Self::export_entry);
+        self.seq(&module_record.indirect_export_entries, Self::export_entry);
+        self.seq(&module_record.star_export_entries, Self::export_entry);
+        self.usize(module_record.exported_bindings.len());
+        for (name, span) in &module_record.exported_bindings {
+            self.str(name);
+            self.span(*span);
+        }
+        self.seq(&module_record.exported_bindings_duplicated, Self::name_span);
+        self.bool(module_record.export_default.is_some());
+        if let Some(span) = module_record.export_default {
+            self.span(span);
+        }
+        self.seq(&module_record.export_default_duplicated, |writer, span| writer.span(*span));
+    }
+
+    fn export_entry(&mut self, entry: &ExportEntry) {
+        self.span(entry.span);
+        self.bool(entry.module_request.is_some());
+        if let Some(module_request) = &entry.module_request {
+            self.name_span(module_request);
+        }
+        match &entry.import_name {
+            ExportImportName::Name(name_span) => {
+                self.usize(0);
+                self.name_span(name_span);
+            }
+            ExportImportName::All => self.usize(1),
+            ExportImportName::AllButDefault => self.usize(2),
+            ExportImportName::Null => self.usize(3),
+        }
+        match &entry.export_name {
+            ExportExportName::Name(name_span) => {
+                self.usize(0);
+                self.name_span(name_span);
+            }
+            ExportExportName::Default(span) => {
+                self.usize(1);
+                self.span(*span);
+            }
+            ExportExportName::Null => self.usize(2),
+        }
+        match &entry.local_name {
+            ExportLocalName::Name(name_span) => {
+                self.usize(0);
+                self.name_span(name_span);
+            }
+            ExportLocalName::Default(span) => {
+                self.usize(1);
+                self.span(*span);
+            }
+            ExportLocalName::Null => self.usize(2),
+        }
+    }
+}
+
+/// The number of symbols, references and scopes, which ids are checked against
+struct Lens {
+    symbols: usize,
+    references: usize,
+    scopes: usize,
+}
+
+/// Reads what [`Writer`] wrote, returning `None` as soon as anything is out of place
+struct Reader<'b> {
+    bytes: &'b [u8],
+}
+
+impl<'b> Reader<'b> {
+    fn u8(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.bytes.split_first()?;
+        self.bytes = rest;
+        Some(byte)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn usize(&mut self) -> Option<usize> {
+        usize::try_from(self.u64()?).ok()
+    }
+
+    /// The 32 bytes of [`SemanticSnapshot::source_hash`]
+    fn hash(&mut self) -> Option<[u8; 32]> {
+        if self.bytes.len() < 32 {
+            return None;
+        }
+        let (hash, rest) = self.bytes.split_at(32);
+        self.bytes = rest;
+        hash.try_into().ok()
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        u32::try_from(self.u64()?).ok()
+    }
+
+    /// An index which is less than `len`
+    fn index(&mut self, len: usize) -> Option<usize> {
+        self.usize().filter(|&index| index < len)
+    }
+
+    /// An optional index, `Some(None)` if it is absent
+    #[allow(clippy::option_option)]
+    fn option_index(&mut self, len: usize) -> Option<Option<usize>> {
+        match self.usize()? {
+            0 => Some(None),
+            index => (index - 1 < len).then_some(Some(index - 1)),
+        }
+    }
+
+    fn bool(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn str(&mut self) -> Option<&'b str> {
+        let len = self.usize()?;
+        if len > self.bytes.len() {
+            return None;
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        std::str::from_utf8(bytes).ok()
+    }
+
+    fn atom(&mut self) -> Option<Atom> {
+        self.str().map(Atom::from)
+    }
+
+    fn span(&mut self) -> Option<Span> {
+        Some(Span::new(self.u32()?, self.u32()?))
+    }
+
+    fn name_span(&mut self) -> Option<NameSpan> {
+        Some(NameSpan::new(self.atom()?, self.span()?))
+    }
+
+    fn seq<T>(&mut self, mut read: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let len = self.usize()?;
+        // Every item takes at least one byte, so a malformed length can't allocate too much
+        let mut items = Vec::with_capacity(len.min(self.bytes.len()));
+        for _ in 0..len {
+            items.push(read(self)?);
+        }
+        Some(items)
+    }
+
+    fn symbols(&mut self, lens: &Lens) -> Option<SymbolTable> {
+        let mut symbols = SymbolTable::default();
+        for _ in 0..lens.symbols {
+            let span = self.span()?;
+            let name = self.atom()?;
+            let flag = SymbolFlags::from_bits(self.u32()?)?;
+            let scope_id = ScopeId::new(self.index(lens.scopes)?);
+            let symbol_id = symbols.create_symbol(span, name, flag, scope_id);
+            symbols.add_declaration(AstNodeId::new(self.usize()?));
+            symbols.resolved_references[symbol_id] =
+                self.seq(|reader| reader.index(lens.references).map(ReferenceId::new))?;
+        }
+        for _ in 0..lens.references {
+            let span = self.span()?;
+            let name = self.atom()?;
+            let node_id = AstNodeId::new(self.usize()?);
+            let symbol_id = self.option_index(lens.symbols)?.map(SymbolId::new);
+            let flag = ReferenceFlag::from_bits(u8::try_from(self.u64()?).ok()?)?;
+            let mut reference = Reference::new(span, name, node_id, flag);
+            if let Some(symbol_id) = symbol_id {
+                reference.set_symbol_id(symbol_id);
+            }
+            symbols.create_reference(reference);
+        }
+        Some(symbols)
+    }
+
+    fn scopes(&mut self, lens: &Lens) -> Option<ScopeTree> {
+        let mut scopes = ScopeTree::default();
+        for index in 0..lens.scopes {
+            // Parents are created before their children, which keeps children in source order
+            let parent_id = self.option_index(index)?.map(ScopeId::new);
+            let node_id = self.option_index(usize::MAX)?.map(AstNodeId::new);
+            let flags = ScopeFlags::from_bits(u16::try_from(self.u64()?).ok()?)?;
+            let scope_id = scopes.add_scope(parent_id, flags);
+            if let Some(node_id) = node_id {
+                scopes.set_node_id(scope_id, node_id);
+            }
+            for _ in 0..self.usize()? {
+                let name = self.atom()?;
+                let symbol_id = SymbolId::new(self.index(lens.symbols)?);
+                scopes.add_binding(scope_id, name, symbol_id);
+            }
+            for _ in 0..self.usize()? {
+                let name = self.atom()?;
+                let reference_ids =
+                    self.seq(|reader| reader.index(lens.references).map(ReferenceId::new))?;
+                scopes.extend_unresolved_reference(scope_id, name, reference_ids);
+            }
+        }
+        Some(scopes)
+    }
+
+    fn module_record(&mut self) -> Option<ModuleRecord> {
+        let mut module_record = ModuleRecord::default();
+        for _ in 0..self.usize()? {
+            let name = self.atom()?;
+            let spans = self.seq(Self::span)?;
+            module_record.module_requests.insert(name, spans);
+        }
+        module_record.import_attributes = self.seq(|reader| {
+            let module_request = reader.name_span()?;
+            let attributes = reader.seq(|reader| {
+                Some(ImportAttributeEntry { key: reader.name_span()?, value: reader.name_span()? })
+            })?;
+            Some(ModuleRequestAttributes { module_request, attributes })
+        })?;
+        module_record.import_entries = self.seq(|reader| {
+            let module_request = reader.name_span()?;
+            let import_name = match reader.usize()? {
+                0 => ImportImportName::Name(reader.name_span()?),
+                1 => ImportImportName::NamespaceObject,
+                2 => ImportImportName::Default(reader.span()?),
+                _ => return None,
+            };
+            Some(ImportEntry { module_request, import_name, local_name: reader.name_span()? })
+        })?;
+        module_record.local_export_entries = self.seq(Self::export_entry)?;
+        module_record.indirect_export_entries = self.seq(Self::export_entry)?;
+        module_record.star_export_entries = self.seq(Self::export_entry)?;
+        for _ in 0..self.usize()? {
+            let name = self.atom()?;
+            let span = self.span()?;
+            module_record.exported_bindings.insert(name, span);
+        }
+        module_record.exported_bindings_duplicated = self.seq(Self::name_span)?;
+        module_record.export_default = if self.bool()? { Some(self.span()?) } else { None };
+        module_record.export_default_duplicated = self.seq(Self::span)?;
+        Some(module_record)
+    }
+
+    fn export_entry(&mut self) -> Option<ExportEntry> {
+        let span = self.span()?;
+        let module_request = if self.bool()? { Some(self.name_span()?) } else { None };
+        let import_name = match self.usize()? {
+            0 => ExportImportName::Name(self.name_span()?),
+            1 => ExportImportName::All,
+            2 => ExportImportName::AllButDefault,
+            3 => ExportImportName::Null,
+            _ => return None,
+        };
+        let export_name = match self.usize()? {
+            0 => ExportExportName::Name(self.name_span()?),
+            1 => ExportExportName::Default(self.span()?),
+            2 => ExportExportName::Null,
+            _ => return None,
+        };
+        let local_name = match self.usize()? {
+            0 => ExportLocalName::Name(self.name_span()?),
+            1 => ExportLocalName::Default(self.span()?),
+            2 => ExportLocalName::Null,
+            _ => return None,
+        };
+        Some(ExportEntry { span, module_request, import_name, export_name, local_name })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::SemanticSnapshot;
+    use crate::{Semantic, SemanticBuilder};
+
+    const SOURCE: &str = "
+        import a, { b as c } from 'mod' with { type: 'json' };
+        export * from 'other';
+        export { d as e } from 'other';
+        export default function f(g, { h = a }) {
+            try { c } catch (i) { return g + h + i + j }
+        }
+        let k = 1;
+        k++;
+        export { k };
+    ";
+
+    fn build(allocator: &Allocator) -> Semantic<'_> {
+        let source_type = SourceType::default().with_module(true);
+        let ret = Parser::new(allocator, SOURCE, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        SemanticBuilder::new(SOURCE, source_type)
+            .with_trivias(ret.trivias)
+            .with_module_record_builder(true)
+            .build(program)
+            .semantic
+    }
+
+    fn round_trip(semantic: &Semantic) -> SemanticSnapshot {
+        let source_type = SourceType::default().with_module(true);
+        SemanticSnapshot::decode(&semantic.snapshot(), SOURCE, source_type)
+            .expect("snapshot is valid")
+    }
+
+    #[test]
+    fn symbols() {
+        let allocator = Allocator::default();
+        let semantic = build(&allocator);
+        let snapshot = round_trip(&semantic);
+        let symbols = semantic.symbols();
+        assert_eq!(snapshot.symbols.spans, symbols.spans);
+        assert_eq!(snapshot.symbols.names, symbols.names);
+        assert_eq!(snapshot.symbols.flags, symbols.flags);
+        assert_eq!(snapshot.symbols.scope_ids, symbols.scope_ids);
+        assert_eq!(snapshot.symbols.declarations, symbols.declarations);
+        assert_eq!(snapshot.symbols.resolved_references, symbols.resolved_references);
+        assert_eq!(snapshot.symbols.references.len(), symbols.references.len());
+        for (reference, decoded) in symbols.references.iter().zip(&snapshot.symbols.references) {
+            assert_eq!(decoded.span(), reference.span());
+            assert_eq!(decoded.name(), reference.name());
+            assert_eq!(decoded.symbol_id(), reference.symbol_id());
+            assert_eq!(decoded.flag(), reference.flag());
+        }
+    }
+
+    #[test]
+    fn scopes() {
+        let allocator = Allocator::default();
+        let semantic = build(&allocator);
+        let snapshot = round_trip(&semantic);
+        let scopes = semantic.scopes();
+        assert_eq!(snapshot.scopes.len(), scopes.len());
+        for scope_id in scopes.descendants() {
+            assert_eq!(snapshot.scopes.get_parent_id(scope_id), scopes.get_parent_id(scope_id));
+            assert_eq!(snapshot.scopes.get_child_ids(scope_id), scopes.get_child_ids(scope_id));
+            assert_eq!(snapshot.scopes.get_node_id(scope_id), scopes.get_node_id(scope_id));
+            assert_eq!(snapshot.scopes.get_flags(scope_id), scopes.get_flags(scope_id));
+            assert_eq!(snapshot.scopes.get_bindings(scope_id), scopes.get_bindings(scope_id));
+        }
+        assert_eq!(
+            snapshot.scopes.root_unresolved_references(),
+            scopes.root_unresolved_references()
+        );
+    }
+
+    #[test]
+    fn module_record() {
+        let allocator = Allocator::default();
+        let semantic = build(&allocator);
+        let snapshot = round_trip(&semantic);
+        let module_record = semantic.module_record();
+        assert_eq!(snapshot.module_record.module_requests, module_record.module_requests);
+        assert_eq!(snapshot.module_record.import_attributes, module_record.import_attributes);
+        assert_eq!(snapshot.module_record.import_entries, module_record.import_entries);
+        assert_eq!(snapshot.module_record.local_export_entries, module_record.local_export_entries);
+        assert_eq!(
+            snapshot.module_record.indirect_export_entries,
+            module_record.indirect_export_entries
+        );
+        assert_eq!(snapshot.module_record.star_export_entries, module_record.star_export_entries);
+        assert_eq!(snapshot.module_record.exported_bindings, module_record.exported_bindings);
+        assert_eq!(snapshot.module_record.export_default, module_record.export_default);
+    }
+
+    #[test]
+    fn cache_miss() {
+        let allocator = Allocator::default();
+        let bytes = build(&allocator).snapshot();
+        let source_type = SourceType::default().with_module(true);
+        // A different source, source type or a truncated snapshot is a cache miss
+        assert!(SemanticSnapshot::decode(&bytes, "let k = 2;", source_type).is_none());
+        assert!(SemanticSnapshot::decode(&bytes, SOURCE, SourceType::default()).is_none());
+        assert!(SemanticSnapshot::decode(&bytes[..bytes.len() - 1], SOURCE, source_type).is_none());
+    }
+}
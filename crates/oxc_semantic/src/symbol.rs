@@ -4,6 +4,7 @@ pub use oxc_syntax::{
     scope::ScopeId,
     symbol::{SymbolFlags, SymbolId},
 };
+use rustc_hash::FxHashMap;
 
 use crate::{
     node::AstNodeId,
@@ -23,6 +24,8 @@ pub struct SymbolTable {
     pub declarations: IndexVec<SymbolId, AstNodeId>,
     pub resolved_references: IndexVec<SymbolId, Vec<ReferenceId>>,
     pub references: IndexVec<ReferenceId, Reference>,
+    /// The reference made by each identifier node
+    pub node_reference_ids: FxHashMap<AstNodeId, ReferenceId>,
 }
 
 impl SymbolTable {
@@ -85,7 +88,15 @@ impl SymbolTable {
     }
 
     pub fn create_reference(&mut self, reference: Reference) -> ReferenceId {
-        self.references.push(reference)
+        let node_id = reference.node_id();
+        let reference_id = self.references.push(reference);
+        self.node_reference_ids.insert(node_id, reference_id);
+        reference_id
+    }
+
+    /// The reference made by the identifier node, e.g. `a` in `a + 1`
+    pub fn get_node_reference_id(&self, node_id: AstNodeId) -> Option<ReferenceId> {
+        self.node_reference_ids.get(&node_id).copied()
     }
 
     pub fn get_reference(&self, reference_id: ReferenceId) -> &Reference {
@@ -0,0 +1,191 @@
+pub mod util;
+use oxc_ast::{ast::Expression, AstKind};
+use oxc_semantic::{Analysis, AstNodeId, Direction, Lattice, Semantic};
+use oxc_span::Atom;
+use rustc_hash::FxHashSet;
+use util::SemanticTester;
+
+/// Whether the call of `name` is reachable
+fn is_call_reachable(semantic: &Semantic, name: &str) -> bool {
+    let nodes = semantic.nodes();
+    let call = nodes
+        .iter()
+        .find(|node| {
+            matches!(node.kind(), AstKind::CallExpression(call) if call.callee.is_specific_id(name))
+        })
+        .unwrap_or_else(|| panic!("no call of {name}"));
+    let statement = nodes.parent_id(call.id()).unwrap();
+    semantic.cfg().is_reachable(statement)
+}
+
+fn function_id(semantic: &Semantic) -> AstNodeId {
+    semantic.nodes().iter().find(|node| matches!(node.kind(), AstKind::Function(_))).unwrap().id()
+}
+
+#[test]
+fn test_unreachable() {
+    let cases = [
+        ("function f() { a(); return; b(); }", true, false),
+        ("function f() { if (x) { return; } else { a(); throw x; } b(); }", true, false),
+        ("function f() { if (x) { return; } a(); b(); }", true, true),
+        ("while (true) { a(); } b();", true, false),
+        ("while (x) { a(); } b();", true, true),
+        ("for (;;) { a(); break; } b();", true, true),
+        ("for (;;) { continue; a(); } b();", false, false),
+        ("do { a(); } while (true); b();", true, false),
+        ("outer: for (;;) { for (;;) { break outer; } a(); } b();", false, true),
+        ("outer: for (x of y) { for (;;) { continue outer; } a(); } b();", false, true),
+        ("block: { break block; a(); } b();", false, true),
+        ("switch (x) { case 1: a(); break; default: throw x; } b();", true, true),
+        (
+            "function f() { switch (x) { case 1: return; default: a(); throw x; } b(); }",
+            true,
+            false,
+        ),
+        ("try { a(); throw x; } catch { } b();", true, true),
+        ("try { throw x; } finally { a(); } b();", true, true),
+        ("function f() { try { return; } catch { a(); return; } b(); }", true, false),
+    ];
+    for (source, a, b) in cases {
+        let tester = SemanticTester::js(source);
+        let semantic = tester.build();
+        assert_eq!(is_call_reachable(&semantic, "a"), a, "a() in {source}");
+        assert_eq!(is_call_reachable(&semantic, "b"), b, "b() in {source}");
+    }
+}
+
+#[test]
+fn test_functions() {
+    // Each function has its own graph
+    let tester = SemanticTester::js("throw x; function f() { a(); } const g = () => { b(); };");
+    let semantic = tester.build();
+    assert!(is_call_reachable(&semantic, "a"));
+    assert!(is_call_reachable(&semantic, "b"));
+
+    let cfg = semantic.cfg();
+    let f = function_id(&semantic);
+    let entry = cfg.function_entry(f).unwrap();
+    let exit = cfg.function_exit(f).unwrap();
+    assert_eq!(cfg.basic_block(entry).statements().len(), 1);
+    assert!(cfg.basic_block(exit).is_reachable());
+}
+
+#[test]
+fn test_cyclomatic_complexity() {
+    let cases = [
+        ("function f() { a(); }", 1),
+        ("function f() { if (x) a(); }", 2),
+        ("function f() { if (x) a(); else if (y) b(); }", 3),
+        ("function f() { while (x) { if (y) break; } }", 3),
+        ("function f() { switch (x) { case 1: a(); case 2: b(); } }", 3),
+    ];
+    for (source, expected) in cases {
+        let tester = SemanticTester::js(source);
+        let semantic = tester.build();
+        let complexity = semantic.cfg().cyclomatic_complexity(function_id(&semantic));
+        assert_eq!(complexity, Some(expected), "{source}");
+    }
+}
+
+/// The names of the functions called by expression statements
+struct CalledFunctions<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+}
+
+impl<'s, 'a> CalledFunctions<'s, 'a> {
+    fn callee(&self, statement_id: AstNodeId) -> Option<Atom> {
+        let AstKind::ExpressionStatement(stmt) = self.semantic.nodes().kind(statement_id) else {
+            return None;
+        };
+        let Expression::CallExpression(call) = &stmt.expression else { return None };
+        let Expression::Identifier(ident) = &call.callee else { return None };
+        Some(ident.name.clone())
+    }
+}
+
+/// Functions which may have been called
+impl<'s, 'a> Analysis for CalledFunctions<'s, 'a> {
+    type Domain = FxHashSet<Atom>;
+
+    const DIRECTION: Direction = Direction::Forward;
+
+    fn boundary(&self) -> Self::Domain {
+        FxHashSet::default()
+    }
+
+    fn transfer_statement(&self, state: &mut Self::Domain, statement_id: AstNodeId) {
+        state.extend(self.callee(statement_id));
+    }
+}
+
+/// Functions which will be called on every path, `None` until a path is known
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MustCall(Option<FxHashSet<Atom>>);
+
+impl Lattice for MustCall {
+    fn bottom() -> Self {
+        Self(None)
+    }
+
+    fn join(&mut self, other: &Self) -> bool {
+        let Some(other) = &other.0 else { return false };
+        let Some(names) = &mut self.0 else {
+            self.0 = Some(other.clone());
+            return true;
+        };
+        let len = names.len();
+        names.retain(|name| other.contains(name));
+        names.len() != len
+    }
+}
+
+struct MustCallFunctions<'s, 'a>(CalledFunctions<'s, 'a>);
+
+impl<'s, 'a> Analysis for MustCallFunctions<'s, 'a> {
+    type Domain = MustCall;
+
+    const DIRECTION: Direction = Direction::Backward;
+
+    fn boundary(&self) -> Self::Domain {
+        MustCall(Some(FxHashSet::default()))
+    }
+
+    fn transfer_statement(&self, state: &mut Self::Domain, statement_id: AstNodeId) {
+        if let Some(names) = &mut state.0 {
+            names.extend(self.0.callee(statement_id));
+        }
+    }
+}
+
+fn sorted(names: &FxHashSet<Atom>) -> Vec<&str> {
+    let mut names = names.iter().map(Atom::as_str).collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+#[test]
+fn test_dataflow() {
+    let source = "function f() {
+        a();
+        if (x) { b(); } else { b(); c(); }
+        while (y) { d(); }
+        return;
+        e();
+    }";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let cfg = semantic.cfg();
+    let f = function_id(&semantic);
+    let entry = cfg.function_entry(f).unwrap();
+    let exit = cfg.function_exit(f).unwrap();
+
+    let may = cfg.analyze(f, &CalledFunctions { semantic: &semantic }).unwrap();
+    assert_eq!(sorted(may.entry(entry).unwrap()), Vec::<&str>::new());
+    assert_eq!(sorted(may.exit(entry).unwrap()), ["a"]);
+    assert_eq!(sorted(may.entry(exit).unwrap()), ["a", "b", "c", "d"]);
+
+    let must = cfg.analyze(f, &MustCallFunctions(CalledFunctions { semantic: &semantic })).unwrap();
+    assert_eq!(must.exit(exit), Some(&MustCall(Some(FxHashSet::default()))));
+    let called = must.entry(entry).unwrap().0.as_ref().unwrap();
+    assert_eq!(sorted(called), ["a", "b"]);
+}
@@ -0,0 +1,52 @@
+pub mod util;
+use oxc_ast::AstKind;
+use oxc_semantic::ConstantValue;
+use util::SemanticTester;
+
+#[test]
+fn test_evaluate() {
+    let number = ConstantValue::Number;
+    let string = |s: &str| ConstantValue::String(s.to_string());
+    let cases = [
+        ("1 + 2 * 3", Some(number(7.0))),
+        ("'a' + 1 + null", Some(string("a1null"))),
+        ("`${1}-${true}`", Some(string("1-true"))),
+        ("typeof (() => {})", Some(string("function"))),
+        ("typeof null", Some(string("object"))),
+        ("!![]", Some(ConstantValue::Boolean(true))),
+        ("1 == '1' && null == undefined", Some(ConstantValue::Boolean(true))),
+        ("NaN === NaN", Some(ConstantValue::Boolean(false))),
+        ("'b' > 'a'", Some(ConstantValue::Boolean(true))),
+        ("-1 >>> 28", Some(number(15.0))),
+        ("~5", Some(number(-6.0))),
+        ("1 << 3 | 1", Some(number(9.0))),
+        ("1 ** NaN", Some(number(f64::NAN))),
+        ("0 || 'x'", Some(string("x"))),
+        ("null ?? 2", Some(number(2.0))),
+        ("(x, 1)", Some(number(1.0))),
+        ("void f()", Some(ConstantValue::Undefined)),
+        ("2n + 3n", Some(ConstantValue::BigInt(5.into()))),
+        ("1n + 1", None),
+        ("x + 1", None),
+        ("let undefined = 1; undefined", None),
+    ];
+    for (source, expected) in cases {
+        let tester = SemanticTester::js(source);
+        let semantic = tester.build();
+        let expr = semantic
+            .nodes()
+            .iter()
+            .find_map(|node| match node.kind() {
+                AstKind::ExpressionStatement(stmt) => Some(&stmt.expression),
+                _ => None,
+            })
+            .unwrap();
+        let value = semantic.evaluate(expr);
+        match (&value, &expected) {
+            (Some(ConstantValue::Number(a)), Some(ConstantValue::Number(b))) if a.is_nan() => {
+                assert!(b.is_nan(), "{source}");
+            }
+            _ => assert_eq!(value, expected, "{source}"),
+        }
+    }
+}
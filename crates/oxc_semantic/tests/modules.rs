@@ -1,4 +1,4 @@
-mod util;
+pub mod util;
 
 #[allow(clippy::wildcard_imports)]
 use util::*;
@@ -31,3 +31,50 @@ fn test_exports() {
     // FIXME: failing
     // test.has_some_symbol("defaultExport").is_exported().test();
 }
+
+#[test]
+fn test_import_attributes() {
+    let test = SemanticTester::js(
+        r#"
+        import data from "./data.json" with { type: "json" };
+        import legacy from "./legacy.json" assert { type: "json" };
+        import plain from "./plain.js";
+        export * from "./reexport.json" with { type: "json" };
+        "#,
+    )
+    .with_module_record_builder(true);
+    let semantic = test.build();
+    let module_record = semantic.module_record();
+
+    let requests = module_record
+        .import_attributes
+        .iter()
+        .map(|request| request.module_request.name().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(requests, ["./data.json", "./legacy.json", "./reexport.json"]);
+
+    let attributes = &module_record.import_attributes[0].attributes;
+    assert_eq!(attributes.len(), 1);
+    assert_eq!(attributes[0].key.name().as_str(), "type");
+    assert_eq!(attributes[0].value.name().as_str(), "json");
+
+    let plain = module_record.module_requests["./plain.js"][0];
+    assert!(module_record.import_attributes_of(plain).is_none());
+}
+
+#[test]
+fn test_export_type_star() {
+    let test = SemanticTester::ts(
+        "
+        export type * from './types';
+        export type * as ns from './types';
+        export * from './values';
+        ",
+    );
+    let semantic = test.build();
+    let module_record = semantic.module_record();
+    assert_eq!(module_record.star_export_entries.len(), 1);
+    assert!(module_record.indirect_export_entries.is_empty());
+    assert!(!module_record.exported_bindings.contains_key("ns"));
+    assert!(!module_record.module_requests.contains_key("./types"));
+}
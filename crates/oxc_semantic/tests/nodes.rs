@@ -0,0 +1,146 @@
+pub mod util;
+use oxc_ast::AstKind;
+use oxc_span::GetSpan;
+use util::SemanticTester;
+
+#[test]
+fn test_ancestors() {
+    let tester = SemanticTester::js("describe('a', () => { it('b', () => { x = foo(); }); });");
+    let semantic = tester.build();
+    let nodes = semantic.nodes();
+    let call = nodes
+        .iter()
+        .find(|node| {
+            matches!(node.kind(), AstKind::CallExpression(call) if call.callee.is_specific_id("foo"))
+        })
+        .unwrap();
+
+    assert!(matches!(nodes.parent_kind(call.id()), Some(AstKind::AssignmentExpression(_))));
+    assert!(matches!(nodes.ancestor_kinds(call.id()).last(), Some(AstKind::Program(_))));
+
+    let is_call_of = |name: &'static str| move |kind: AstKind| matches!(kind, AstKind::CallExpression(call) if call.callee.is_specific_id(name));
+    let it = nodes.find_ancestor(call.id(), is_call_of("it")).unwrap();
+    let describe = nodes.find_ancestor(call.id(), is_call_of("describe")).unwrap();
+    assert_eq!(nodes.find_ancestor(it.id(), is_call_of("describe")).unwrap().id(), describe.id());
+    assert!(nodes.find_ancestor(describe.id(), is_call_of("describe")).is_none());
+}
+
+#[test]
+fn test_node_ids() {
+    let source = "function foo(a) { return a + 1; }";
+    let tester = SemanticTester::js(source);
+    let first = tester.build();
+    let second = tester.build();
+
+    // Ids are assigned in visitation order, so they are the same across builds
+    let kinds = |semantic: &oxc_semantic::Semantic| {
+        semantic
+            .nodes()
+            .iter()
+            .map(|node| (node.id(), node.kind().debug_name().into_owned()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(kinds(&first), kinds(&second));
+
+    let nodes = first.nodes();
+    for node in nodes.iter() {
+        assert_eq!(nodes.get_node_id(node.kind()), Some(node.id()));
+    }
+
+    // Nodes are looked up by identity, so the same nodes of another build are not found
+    for node in second.nodes().iter() {
+        assert_eq!(nodes.get_node_id(node.kind()), None);
+    }
+}
+
+#[test]
+fn test_node_at_offset() {
+    let source = "let a = foo(bar.baz, { qux });";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let nodes = semantic.nodes();
+    let offset_of = |text: &str| u32::try_from(source.find(text).unwrap()).unwrap();
+
+    let property = nodes.node_at_offset(offset_of("baz")).unwrap();
+    assert!(matches!(property.kind(), AstKind::IdentifierName(ident) if ident.name == "baz"));
+    assert!(matches!(nodes.parent_kind(property.id()), Some(AstKind::MemberExpression(_))));
+
+    let callee = nodes.node_at_offset(offset_of("foo") + 2).unwrap();
+    assert!(matches!(callee.kind(), AstKind::IdentifierReference(ident) if ident.name == "foo"));
+
+    let call = nodes.node_at_offset(offset_of("(")).unwrap();
+    assert!(matches!(call.kind(), AstKind::CallExpression(_)));
+
+    // The key and the value of a shorthand property share a span
+    let qux = nodes.node_at_offset(offset_of("qux")).unwrap();
+    assert!(matches!(qux.kind(), AstKind::IdentifierReference(ident) if ident.name == "qux"));
+
+    assert!(nodes.node_at_offset(u32::try_from(source.len()).unwrap()).is_none());
+}
+
+#[test]
+fn test_private_member_declaration() {
+    let source = "
+        class Outer {
+            #foo = 1;
+            m() {
+                class Inner extends (this.#foo, Object) {
+                    #bar() {}
+                    n() { this.#bar(); this.#foo; }
+                }
+            }
+        }
+    ";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let nodes = semantic.nodes();
+    let declarations = nodes
+        .iter()
+        .filter_map(|node| match node.kind() {
+            AstKind::PrivateIdentifier(ident)
+                if !matches!(nodes.parent_kind(node.id()), Some(AstKind::PropertyKey(_))) =>
+            {
+                let element = nodes.private_member_declaration(node.id(), &ident.name)?;
+                Some((ident.name.as_str(), element.method_definition_kind().is_some()))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    // The `extends` clause of `Inner` can only use the private names of `Outer`
+    assert_eq!(declarations, [("foo", false), ("bar", true), ("foo", false)]);
+}
+
+#[test]
+fn test_label_targets() {
+    let source =
+        "a: { function f() { a: { break a; } } b: for (;;) { c: { continue b; } } break a; }";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let nodes = semantic.nodes();
+    let text = |node_id| {
+        let span = nodes.kind(node_id).span();
+        &source[span.start as usize..span.end as usize]
+    };
+
+    let jumps = nodes
+        .iter()
+        .filter(|node| {
+            matches!(node.kind(), AstKind::BreakStatement(_) | AstKind::ContinueStatement(_))
+        })
+        .map(|node| (text(node.id()), text(semantic.label_target(node.id()).unwrap())))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        jumps,
+        [
+            ("break a;", "a: { break a; }"),
+            ("continue b;", "b: for (;;) { c: { continue b; } }"),
+            ("break a;", source),
+        ]
+    );
+
+    let outer = nodes.iter().find(|node| matches!(node.kind(), AstKind::LabeledStatement(_)));
+    let outer = outer.unwrap().id();
+    assert_eq!(semantic.label_jumps(outer).len(), 1);
+    let unused = semantic.unused_labels().iter().map(|id| text(*id)).collect::<Vec<_>>();
+    assert_eq!(unused, ["c: { continue b; }"]);
+}
@@ -0,0 +1,81 @@
+pub mod util;
+use oxc_ast::AstKind;
+use oxc_semantic::ScopeKind;
+use oxc_span::{Atom, SourceType};
+use util::SemanticTester;
+
+#[test]
+fn test_scope_tree() {
+    let source = "let a; function foo(b) { try {} catch (e) { let c; } } for (let i of x) {}";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let scopes = semantic.scopes();
+    let root = scopes.root_scope_id();
+
+    let kinds = scopes
+        .descendants_of(root)
+        .map(|scope_id| semantic.scope_kind(scope_id).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            ScopeKind::Module,
+            ScopeKind::Function,
+            ScopeKind::Block,
+            ScopeKind::Catch,
+            ScopeKind::Block,
+            ScopeKind::Block,
+        ]
+    );
+
+    let children = scopes.get_child_ids(root);
+    assert_eq!(children.len(), 2);
+    let function = children[0];
+    let span = semantic.scope_span(function).unwrap();
+    assert_eq!(
+        &source[span.start as usize..span.end as usize],
+        "function foo(b) { try {} catch (e) { let c; } }"
+    );
+
+    let names =
+        |scope_id| scopes.get_bindings(scope_id).keys().map(Atom::as_str).collect::<Vec<_>>();
+    assert_eq!(names(root), ["a", "foo"]);
+    assert_eq!(names(function), ["b"]);
+
+    // The body of the catch clause shares its scope, so `c` is declared next to `e`
+    let catch_scope = scopes.descendants_of(function).last().unwrap();
+    let visible =
+        scopes.visible_bindings(catch_scope).map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+    assert_eq!(visible, ["e", "c", "b", "a", "foo"]);
+}
+
+#[test]
+fn test_strict_mode() {
+    let source = "function sloppy() { { a; } } function strict() { 'use strict'; { { b; } } } \
+                  let c = class { m() { d; } };";
+    let tester = SemanticTester::new(source, SourceType::default());
+    let semantic = tester.build();
+    let nodes = semantic.nodes();
+    let is_strict = |name: &str| {
+        let node = nodes
+            .iter()
+            .find(|node| matches!(node.kind(), AstKind::IdentifierReference(ident) if ident.name == name))
+            .unwrap();
+        semantic.is_strict_mode(node.id())
+    };
+    assert!(!is_strict("a"));
+    assert!(is_strict("b"));
+    assert!(is_strict("d"));
+
+    let scopes = semantic.scopes();
+    let root = scopes.root_scope_id();
+    assert!(!scopes.get_flags(root).is_strict_mode());
+    let strict = scopes.get_child_ids(root)[1];
+    assert!(scopes
+        .descendants_of(strict)
+        .all(|scope_id| scopes.get_flags(scope_id).is_strict_mode()));
+    let directives = semantic.scope_directives(strict);
+    assert_eq!(directives.len(), 1);
+    assert_eq!(directives[0].directive, "use strict");
+    assert!(semantic.scope_directives(root).is_empty());
+}
@@ -0,0 +1,47 @@
+pub mod util;
+use oxc_ast::AstKind;
+use oxc_semantic::SideEffects;
+use util::SemanticTester;
+
+#[test]
+fn test_side_effects() {
+    // Reading an undeclared global throws if it is not defined, unless it is an intrinsic
+    let global_read = SideEffects::ReadsGlobal | SideEffects::MayThrow;
+    let cases = [
+        ("1 + 2", SideEffects::empty()),
+        ("x", global_read),
+        ("Math", SideEffects::ReadsGlobal),
+        ("typeof x", SideEffects::ReadsGlobal),
+        ("void undefined", SideEffects::ReadsGlobal),
+        ("let a; a.b", SideEffects::MayThrow),
+        ("let a; `${a}` + (a, 1)", SideEffects::empty()),
+        ("let a; a in {}", SideEffects::MayThrow),
+        ("foo()", SideEffects::all()),
+        ("/* #__PURE__ */ foo(1)", SideEffects::empty()),
+        ("/* @__PURE__ */ new Foo(bar)", global_read),
+        ("/* @__PURE__ */ new Foo(Math)", SideEffects::ReadsGlobal),
+        ("x = 1", global_read | SideEffects::Mutates | SideEffects::WritesGlobal),
+        ("let a; a = [1, ...b]", SideEffects::all()),
+        ("let a; a++", SideEffects::Mutates),
+        ("(class { static x = 1; m() {} })", SideEffects::empty()),
+        ("(class { static x = foo(); })", SideEffects::all()),
+    ];
+    for (source, expected) in cases {
+        let tester = SemanticTester::js(source);
+        let semantic = tester.build();
+        let expr = semantic
+            .nodes()
+            .iter()
+            .find_map(|node| match node.kind() {
+                AstKind::ExpressionStatement(stmt) => Some(&stmt.expression),
+                _ => None,
+            })
+            .unwrap();
+        let effects = semantic.side_effects(expr);
+        assert_eq!(effects, expected, "{source}");
+        assert_eq!(
+            effects.is_pure(),
+            !expected.intersects(SideEffects::MayThrow | SideEffects::Mutates)
+        );
+    }
+}
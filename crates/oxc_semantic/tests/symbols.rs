@@ -1,5 +1,7 @@
-mod util;
-use oxc_semantic::SymbolFlags;
+pub mod util;
+use oxc_ast::AstKind;
+use oxc_semantic::{Environment, GlobalVariable, SymbolFlags};
+use oxc_span::SourceType;
 use util::SemanticTester;
 
 #[test]
@@ -35,6 +37,21 @@ fn test_var_simple() {
         .test();
 }
 
+#[test]
+fn test_using_declaration() {
+    SemanticTester::js("{ using x = foo(); x.bar(); }")
+        .has_some_symbol("x")
+        .contains_flags(SymbolFlags::BlockScopedVariable | SymbolFlags::ConstVariable)
+        .has_number_of_reads(1)
+        .test();
+
+    SemanticTester::js("async function f() { for (await using y of z) { y; } }")
+        .has_some_symbol("y")
+        .contains_flags(SymbolFlags::BlockScopedVariable | SymbolFlags::ConstVariable)
+        .has_number_of_reads(1)
+        .test();
+}
+
 #[test]
 fn test_var_read_write() {
     SemanticTester::js("let x; x += 1")
@@ -87,3 +104,243 @@ fn test_types_simple() {
         .has_number_of_references(1)
         .test();
 }
+
+#[test]
+fn test_definition_file() {
+    SemanticTester::new(
+        "
+        declare global {
+            interface Window { foo: string }
+        }
+        export function overload(a: string): void;
+        export function overload(a: number): void;
+        export declare const value: number;
+        declare class Foo {
+            constructor(a: string);
+            constructor(a: number);
+            method(): void;
+            method(a: string): void;
+        }
+        ",
+        SourceType::default().with_module(true).with_typescript_definition(true),
+    )
+    .has_root_symbol("value")
+    .contains_flags(SymbolFlags::ConstVariable)
+    .is_exported()
+    .test();
+}
+
+#[test]
+fn test_shadowing() {
+    let tester = SemanticTester::js(
+        "let a; function foo(a) { { let a; } } function bar() { a(); (a)(); new a(); a; }",
+    );
+    let semantic = tester.build();
+    let symbols = semantic.symbols();
+    let a = symbols.iter().filter(|id| *symbols.get_name(*id) == "a").collect::<Vec<_>>();
+    let [top, param, block] = a[..] else { panic!("expected 3 symbols named a, found {a:?}") };
+
+    assert_eq!(semantic.shadowed_symbol(top), None);
+    assert_eq!(semantic.shadowed_symbol(param), Some(top));
+    assert_eq!(semantic.shadowed_symbol(block), Some(param));
+    assert_eq!(semantic.shadowing_symbols(top).collect::<Vec<_>>(), [param]);
+
+    let calls = semantic.symbol_references(top).filter(|r| semantic.is_call_reference(r)).count();
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn test_use_before_define() {
+    // (source, is before declaration, is before initialization) of the references to `a`
+    let cases: &[(&str, &[bool], &[bool])] = &[
+        ("a; let a = 1;", &[true], &[true]),
+        ("let a = a;", &[false], &[true]),
+        ("let a; a;", &[false], &[false]),
+        ("for (const a of a) {}", &[false], &[true]),
+        ("class a extends a {}", &[false], &[true]),
+        ("if (x) { typeof a; } const a = 1;", &[true], &[true]),
+        ("function f() { a; } let a;", &[true], &[false]),
+        ("class A { x = a; static y = a; } let a;", &[true, true], &[false, true]),
+        ("a(); function a() {}", &[true], &[false]),
+        ("a; var a;", &[true], &[false]),
+    ];
+    for &(source, before_declaration, before_initialization) in cases {
+        let tester = SemanticTester::js(source);
+        let semantic = tester.build();
+        let symbols = semantic.symbols();
+        let a = symbols.iter().find(|id| *symbols.get_name(*id) == "a").unwrap();
+        let references = semantic.symbol_references(a).collect::<Vec<_>>();
+        let declaration = references
+            .iter()
+            .map(|r| semantic.is_reference_before_declaration(r))
+            .collect::<Vec<_>>();
+        assert_eq!(declaration, before_declaration, "{source}");
+        let initialization = references
+            .iter()
+            .map(|r| semantic.is_reference_before_initialization(r))
+            .collect::<Vec<_>>();
+        assert_eq!(initialization, before_initialization, "{source}");
+    }
+
+    let tester = SemanticTester::js("let b; { let a = 1; }");
+    let semantic = tester.build();
+    let a = semantic.symbols().iter().last().unwrap();
+    assert_eq!(semantic.symbol_tdz(a), Some(oxc_span::Span::new(7, 18)));
+    assert_eq!(
+        semantic.symbol_tdz(semantic.symbols().iter().next().unwrap()),
+        Some(oxc_span::Span::new(0, 5))
+    );
+}
+
+#[test]
+fn test_closure_captures() {
+    let source = "let a = 1, b = 2, unused;
+        for (let i = 0; i < 3; i++) { fns.push(() => { b = a + i; const c = 1; return c; }); }
+        function f() { let x; return function g() { x++; y; }; }
+        let y;";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let captures = |function_id| {
+        semantic
+            .closure_captures(function_id)
+            .into_iter()
+            .map(|capture| {
+                (semantic.symbols().get_name(capture.symbol_id).as_str(), capture.is_mutated)
+            })
+            .collect::<Vec<_>>()
+    };
+    let functions = semantic
+        .nodes()
+        .iter()
+        .filter(|node| matches!(node.kind(), AstKind::Function(_) | AstKind::ArrowExpression(_)))
+        .map(oxc_semantic::AstNode::id)
+        .collect::<Vec<_>>();
+    let [arrow, f, g] = functions[..] else { panic!("expected 3 functions") };
+
+    assert_eq!(captures(arrow), [("a", false), ("b", true), ("i", false)]);
+    assert_eq!(captures(f), [("y", false)]);
+    assert_eq!(captures(g), [("x", true), ("y", false)]);
+}
+
+#[test]
+fn test_undefined_references() {
+    let source =
+        "let a = Math.max(x, 1); window.foo = process.env; require('a'); { let window; window; }";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let undefined = |environments| {
+        semantic
+            .undefined_references(environments)
+            .into_iter()
+            .map(|reference_id| semantic.symbols().get_reference(reference_id).name().as_str())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(undefined(Environment::default()), ["x", "window", "process", "require"]);
+    assert_eq!(undefined(Environment::BROWSER), ["x", "process", "require"]);
+    assert_eq!(undefined(Environment::NODE), ["x", "window"]);
+
+    let reference = semantic
+        .symbols()
+        .references
+        .iter()
+        .find(|reference| reference.name().as_str() == "Math")
+        .expect("Math is referenced");
+    assert_eq!(
+        semantic.global_variable(reference, Environment::NODE),
+        Some(GlobalVariable { environment: Environment::Builtin, writable: false })
+    );
+
+    assert_eq!(Environment::from_env_name("browser"), Some(Environment::Browser));
+    assert_eq!(Environment::from_env_name("es2021"), Some(Environment::Builtin));
+    assert_eq!(Environment::from_env_name("jest"), None);
+}
+
+#[test]
+#[allow(clippy::cast_possible_truncation)]
+fn test_symbol_at_offset() {
+    let source = "const Foo = 1; <Foo.Bar />; Foo;";
+    let tester = SemanticTester::js(source).with_jsx(true);
+    let semantic = tester.build();
+    let offsets = source.match_indices("Foo").map(|(offset, _)| offset as u32).collect::<Vec<_>>();
+
+    let symbol_id = semantic.symbol_at_offset(offsets[0]).expect("`Foo` is declared");
+    assert_eq!(semantic.symbol_at_offset(offsets[1] + 1), Some(symbol_id));
+    // Right after `Foo;`
+    assert_eq!(semantic.symbol_at_offset(offsets[2] + 3), Some(symbol_id));
+    assert_eq!(semantic.symbol_at_offset(source.find("Bar").unwrap() as u32), None);
+    assert_eq!(semantic.symbol_at_offset(source.find('1').unwrap() as u32), None);
+}
+
+#[test]
+#[allow(clippy::cast_possible_truncation)]
+fn test_symbol_occurrences() {
+    let source = "let { a } = obj;
+        function f() { return { a, b: a }; }
+        ({ a } = obj);
+        { let a; a; }";
+    let tester = SemanticTester::js(source);
+    let semantic = tester.build();
+    let offsets = |name: &str| {
+        source.match_indices(name).map(|(offset, _)| offset as u32).collect::<Vec<_>>()
+    };
+    let a = offsets("a");
+
+    let symbol_id = semantic.symbol_at_offset(a[0]).expect("`a` is declared");
+    for &offset in &a[..4] {
+        assert_eq!(semantic.symbol_at_offset(offset), Some(symbol_id));
+    }
+    assert_ne!(semantic.symbol_at_offset(a[4]), Some(symbol_id));
+    assert_eq!(semantic.symbol_at_offset(offsets("obj")[0]), None);
+
+    let occurrences = semantic
+        .symbol_occurrences(symbol_id)
+        .into_iter()
+        .map(|occurrence| {
+            (occurrence.span.start, occurrence.reference_id.is_some(), occurrence.is_shorthand)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        occurrences,
+        [(a[0], false, true), (a[1], true, true), (a[2], true, false), (a[3], true, true)]
+    );
+}
+
+#[test]
+fn test_destructuring_defaults_read_write() {
+    SemanticTester::js("let a, b; ({ a = b } = {});")
+        .has_some_symbol("b")
+        .has_number_of_reads(1)
+        .has_number_of_writes(0)
+        .test();
+
+    SemanticTester::js("let a, b; ({ a = b } = {});")
+        .has_some_symbol("a")
+        .has_number_of_reads(0)
+        .has_number_of_writes(1)
+        .test();
+
+    SemanticTester::js("let a, b; ({ x: a = b } = {});")
+        .has_some_symbol("b")
+        .has_number_of_reads(1)
+        .has_number_of_writes(0)
+        .test();
+
+    SemanticTester::js("let a; [a = 1] = [];")
+        .has_some_symbol("a")
+        .has_number_of_reads(0)
+        .has_number_of_writes(1)
+        .test();
+
+    SemanticTester::js("let a, b; for ({ a = b } of []);")
+        .has_some_symbol("b")
+        .has_number_of_reads(1)
+        .has_number_of_writes(0)
+        .test();
+
+    SemanticTester::js("try {} catch ({ a, b: [c = a, ...d], ...e }) { a; c; d; e; }")
+        .has_some_symbol("a")
+        .has_number_of_reads(2)
+        .has_number_of_writes(0)
+        .test();
+}
@@ -1,3 +1,6 @@
+//! Helpers of the integration tests, which declare this module `pub` as each test only uses
+//! some of the helpers.
+
 use std::sync::Arc;
 
 use itertools::Itertools;
@@ -22,7 +25,6 @@ impl SemanticTester {
     /// Create a new tester for a TypeScript test case.
     ///
     /// Use [`SemanticTester::js`] for JavaScript test cases.
-    #[allow(dead_code)]
     pub fn ts(source_text: &'static str) -> Self {
         Self::new(source_text, SourceType::default().with_module(true).with_typescript(true))
     }
@@ -44,27 +46,31 @@ impl SemanticTester {
     }
 
     /// Set the [`SourceType`] to TypeScript (or JavaScript, using `false`)
-    #[allow(dead_code)]
+    #[must_use]
     pub fn with_typescript(mut self, yes: bool) -> Self {
         self.source_type = SourceType::default().with_typescript(yes);
         self
     }
 
     /// Mark the [`SourceType`] as JSX
-    #[allow(dead_code)]
+    #[must_use]
     pub fn with_jsx(mut self, yes: bool) -> Self {
         self.source_type = self.source_type.with_jsx(yes);
         self
     }
 
     /// Set [`SemanticBuilder`]'s `with_module_record_builder` option
-    #[allow(dead_code)]
+    #[must_use]
     pub fn with_module_record_builder(mut self, yes: bool) -> Self {
         self.use_module_record_builder = yes;
         self
     }
 
     /// Parse the source text and produce a new [`Semantic`]
+    ///
+    /// # Panics
+    ///
+    /// * The source text has syntax or semantic errors
     #[allow(unstable_name_collisions)]
     pub fn build(&self) -> Semantic<'_> {
         let parse =
@@ -109,8 +115,7 @@ impl SemanticTester {
     ///
     /// ## Fails
     /// If no symbol with the given name exists at the top-level scope.
-    #[allow(dead_code)]
-    pub fn has_root_symbol(&self, name: &str) -> SymbolTester {
+    pub fn has_root_symbol(&self, name: &str) -> SymbolTester<'_> {
         SymbolTester::new_at_root(self, self.build(), name)
     }
 
@@ -120,7 +125,7 @@ impl SemanticTester {
     /// 1. No symbol with the given name exists,
     /// 2. More than one symbol with the given name exists, so a symbol cannot
     ///    be uniquely obtained.
-    pub fn has_some_symbol(&self, name: &str) -> SymbolTester {
+    pub fn has_some_symbol(&self, name: &str) -> SymbolTester<'_> {
         SymbolTester::new_unique(self, self.build(), name)
     }
 
@@ -152,7 +157,6 @@ pub struct SymbolTester<'a> {
 }
 
 impl<'a> SymbolTester<'a> {
-    #[allow(dead_code)]
     pub(super) fn new_at_root(
         parent: &'a SemanticTester,
         semantic: Semantic<'a>,
@@ -183,6 +187,7 @@ impl<'a> SymbolTester<'a> {
     }
 
     /// Checks if the resolved symbol contains all flags in `flags`, using [`SymbolFlags::contains()`]
+    #[must_use]
     pub fn contains_flags(mut self, flags: SymbolFlags) -> Self {
         self.test_result = match self.test_result {
             Ok(symbol_id) => {
@@ -203,6 +208,7 @@ impl<'a> SymbolTester<'a> {
         self
     }
 
+    #[must_use]
     pub fn intersects_flags(mut self, flags: SymbolFlags) -> Self {
         self.test_result = match self.test_result {
             Ok(symbol_id) => {
@@ -223,19 +229,22 @@ impl<'a> SymbolTester<'a> {
         self
     }
 
+    #[must_use]
     pub fn has_number_of_reads(self, ref_count: usize) -> Self {
         self.has_number_of_references_where(ref_count, Reference::is_read)
     }
 
-    #[allow(dead_code)]
+    #[must_use]
     pub fn has_number_of_writes(self, ref_count: usize) -> Self {
         self.has_number_of_references_where(ref_count, Reference::is_write)
     }
 
+    #[must_use]
     pub fn has_number_of_references(self, ref_count: usize) -> Self {
         self.has_number_of_references_where(ref_count, |_| true)
     }
 
+    #[must_use]
     pub fn has_number_of_references_where<F>(mut self, ref_count: usize, filter: F) -> Self
     where
         F: FnMut(&Reference) -> bool,
@@ -262,6 +271,7 @@ impl<'a> SymbolTester<'a> {
     }
 
     #[allow(clippy::wrong_self_convention)]
+    #[must_use]
     pub fn is_exported(mut self) -> Self {
         self.test_result = match self.test_result {
             Ok(symbol_id) => {
@@ -279,8 +289,11 @@ impl<'a> SymbolTester<'a> {
         self
     }
 
-    /// Complete the test case. Will panic if any of the previously applied
-    /// assertions failed.
+    /// Complete the test case.
+    ///
+    /// # Panics
+    ///
+    /// * Any of the previously applied assertions failed
     pub fn test(self) {
         let res: Result<_, _> = self.into();
 
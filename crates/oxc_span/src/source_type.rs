@@ -1,11 +1,11 @@
 use std::path::Path;
 
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Source Type for JavaScript vs TypeScript / Script vs Module / JSX
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "camelCase"))]
 pub struct SourceType {
     /// JavaScript or TypeScript, default JavaScript
     language: Language,
@@ -23,7 +23,7 @@ pub struct SourceType {
 
 /// JavaScript or TypeScript
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "camelCase"))]
 pub enum Language {
     JavaScript,
     #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
@@ -34,7 +34,7 @@ pub enum Language {
 
 /// Script or Module
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "camelCase"))]
 pub enum ModuleKind {
     Script,
     Module,
@@ -42,7 +42,7 @@ pub enum ModuleKind {
 
 /// JSX for JavaScript and TypeScript
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "camelCase"))]
 pub enum LanguageVariant {
     Standard,
     Jsx,
@@ -138,6 +138,15 @@ impl SourceType {
         self
     }
 
+    /// Disable JSX, the counterpart of [`SourceType::with_jsx`]
+    #[must_use]
+    pub fn with_standard(mut self, yes: bool) -> Self {
+        if yes {
+            self.variant = LanguageVariant::Standard;
+        }
+        self
+    }
+
     #[must_use]
     pub fn with_always_strict(mut self, yes: bool) -> Self {
         self.always_strict = yes;
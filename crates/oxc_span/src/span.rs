@@ -2,7 +2,10 @@ use std::hash::{Hash, Hasher};
 
 use miette::{SourceOffset, SourceSpan};
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+/// An empty span, for nodes which are synthesized instead of parsed from source text
+pub const SPAN: Span = Span::new(0, 0);
 
 /// Newtype for working with text ranges
 ///
@@ -10,7 +13,7 @@ use serde::Serialize;
 /// Utility methods can be copied from the `text-size` crate if they are needed.
 /// NOTE: `u32` is sufficient for "all" reasonable programs. Larger than u32 is a 4GB JS file.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Span {
     pub start: u32,
     pub end: u32,
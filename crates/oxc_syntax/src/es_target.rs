@@ -0,0 +1,94 @@
+//! ECMAScript editions for targeting the syntax of an output
+
+use std::{fmt, str::FromStr};
+
+/// The edition of ECMAScript that a program is expected to run on.
+///
+/// Editions are ordered, so `EsTarget::ES2018 < EsTarget::ES2020`.
+/// `ESNext` includes syntax beyond the latest edition, such as stage 3 proposals.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EsTarget {
+    ES5,
+    ES2015,
+    ES2016,
+    ES2017,
+    ES2018,
+    ES2019,
+    ES2020,
+    ES2021,
+    ES2022,
+    ES2023,
+    ES2024,
+    #[default]
+    ESNext,
+}
+
+impl EsTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ES5 => "ES5",
+            Self::ES2015 => "ES2015",
+            Self::ES2016 => "ES2016",
+            Self::ES2017 => "ES2017",
+            Self::ES2018 => "ES2018",
+            Self::ES2019 => "ES2019",
+            Self::ES2020 => "ES2020",
+            Self::ES2021 => "ES2021",
+            Self::ES2022 => "ES2022",
+            Self::ES2023 => "ES2023",
+            Self::ES2024 => "ES2024",
+            Self::ESNext => "ESNext",
+        }
+    }
+}
+
+impl FromStr for EsTarget {
+    type Err = String;
+
+    /// Parse a target case-insensitively, e.g. `es2018`, `ES6` or `esnext`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "es5" => Ok(Self::ES5),
+            "es6" | "es2015" => Ok(Self::ES2015),
+            "es7" | "es2016" => Ok(Self::ES2016),
+            "es8" | "es2017" => Ok(Self::ES2017),
+            "es9" | "es2018" => Ok(Self::ES2018),
+            "es10" | "es2019" => Ok(Self::ES2019),
+            "es11" | "es2020" => Ok(Self::ES2020),
+            "es12" | "es2021" => Ok(Self::ES2021),
+            "es13" | "es2022" => Ok(Self::ES2022),
+            "es14" | "es2023" => Ok(Self::ES2023),
+            "es15" | "es2024" => Ok(Self::ES2024),
+            "esnext" => Ok(Self::ESNext),
+            _ => Err(format!("Invalid target '{s}', expected es5, es2015 ... es2024 or esnext")),
+        }
+    }
+}
+
+impl fmt::Display for EsTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EsTarget;
+
+    #[test]
+    fn from_str() {
+        assert_eq!("es5".parse(), Ok(EsTarget::ES5));
+        assert_eq!("ES6".parse(), Ok(EsTarget::ES2015));
+        assert_eq!("es2018".parse(), Ok(EsTarget::ES2018));
+        assert_eq!("ESNext".parse(), Ok(EsTarget::ESNext));
+        assert!("es3".parse::<EsTarget>().is_err());
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(EsTarget::ES5 < EsTarget::ES2015);
+        assert!(EsTarget::ES2018 < EsTarget::ES2020);
+        assert!(EsTarget::ES2024 < EsTarget::ESNext);
+        assert_eq!(EsTarget::default(), EsTarget::ESNext);
+    }
+}
@@ -0,0 +1,243 @@
+//! Decoding of escape sequences in string literals and template elements
+//!
+//! See [String Literals](https://tc39.es/ecma262/#sec-literals-string-literals)
+//! and [Template Literal Lexical Components](https://tc39.es/ecma262/#sec-template-literal-lexical-components)
+
+use std::{iter::Peekable, str::CharIndices};
+
+use oxc_span::Span;
+
+use crate::identifier::{CR, FF, LF, LS, PS, TAB, VT};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum EscapeContext {
+    /// Text between the quotes of a string literal, where legacy octal escapes such as `\012` are allowed
+    String,
+    /// Raw text of a template element, where `\1` is invalid and line terminators are normalized to `\n`
+    Template,
+}
+
+/// An escape sequence without a value, e.g. `\xZ`, `\u{110000}`, or `\1` in a template.
+/// The span is relative to the start of the raw text.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct InvalidEscape(pub Span);
+
+/// Decode the escape sequences of `raw` into UTF-16 code units, the value seen by JavaScript.
+///
+/// Surrogate pairs such as `😀` form a single character,
+/// and lone surrogates such as `\uD800` are kept, which a Rust string cannot hold.
+///
+/// # Errors
+///
+/// Returns the first invalid escape sequence
+pub fn cook_utf16(raw: &str, context: EscapeContext) -> Result<Vec<u16>, InvalidEscape> {
+    let mut units = Vec::with_capacity(raw.len());
+    let mut chars = raw.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '\\' => cook_escape(raw, start, &mut chars, context, &mut units)?,
+            CR if context == EscapeContext::Template => {
+                chars.next_if(|(_, c)| *c == LF);
+                units.push(LF as u16);
+            }
+            c => push_char(&mut units, c),
+        }
+    }
+    Ok(units)
+}
+
+/// Decode the escape sequences of `raw` into a string,
+/// lone surrogates are replaced by `U+FFFD`.
+///
+/// # Errors
+///
+/// Returns the first invalid escape sequence
+pub fn cook(raw: &str, context: EscapeContext) -> Result<String, InvalidEscape> {
+    cook_utf16(raw, context).map(|units| {
+        char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect()
+    })
+}
+
+/// Static Semantics: `IsStringWellFormedUnicode`, whether there are no lone surrogates
+pub fn is_well_formed_unicode(units: &[u16]) -> bool {
+    char::decode_utf16(units.iter().copied()).all(|c| c.is_ok())
+}
+
+fn push_char(units: &mut Vec<u16>, c: char) {
+    let mut buffer = [0; 2];
+    units.extend_from_slice(c.encode_utf16(&mut buffer));
+}
+
+/// Decode the escape sequence after the `\` at `start`
+#[allow(clippy::cast_possible_truncation)]
+fn cook_escape(
+    raw: &str,
+    start: usize,
+    chars: &mut Peekable<CharIndices>,
+    context: EscapeContext,
+    units: &mut Vec<u16>,
+) -> Result<(), InvalidEscape> {
+    let invalid = |chars: &mut Peekable<CharIndices>| {
+        let end = chars.peek().map_or(raw.len(), |(end, _)| *end);
+        InvalidEscape(Span::new(start as u32, end as u32))
+    };
+    let Some((_, c)) = chars.next() else {
+        return Err(invalid(chars));
+    };
+    match c {
+        // LineContinuation
+        LF | LS | PS => {}
+        CR => {
+            chars.next_if(|(_, c)| *c == LF);
+        }
+        'b' => units.push(0x8),
+        'f' => units.push(FF as u16),
+        'n' => units.push(LF as u16),
+        'r' => units.push(CR as u16),
+        't' => units.push(TAB as u16),
+        'v' => units.push(VT as u16),
+        'x' => {
+            let value = hex_digits(chars, 2).ok_or_else(|| invalid(chars))?;
+            units.push(value as u16);
+        }
+        'u' if chars.next_if(|(_, c)| *c == '{').is_some() => {
+            let mut value = None;
+            while let Some((_, digit)) = chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                let next = value.unwrap_or(0) * 16 + digit.to_digit(16).unwrap();
+                if next > 0x0010_FFFF {
+                    return Err(invalid(chars));
+                }
+                value = Some(next);
+            }
+            match value {
+                Some(value) if chars.next_if(|(_, c)| *c == '}').is_some() => {
+                    // Code points in the surrogate range are lone surrogates
+                    match char::from_u32(value) {
+                        Some(c) => push_char(units, c),
+                        None => units.push(value as u16),
+                    }
+                }
+                _ => return Err(invalid(chars)),
+            }
+        }
+        // Both halves of a surrogate pair are pushed separately
+        'u' => {
+            let value = hex_digits(chars, 4).ok_or_else(|| invalid(chars))?;
+            units.push(value as u16);
+        }
+        // 0 [lookahead ∉ DecimalDigit]
+        '0' if !chars.peek().map_or(false, |(_, c)| c.is_ascii_digit()) => units.push(0),
+        // NotEscapeSequence
+        '0'..='9' if context == EscapeContext::Template => return Err(invalid(chars)),
+        // LegacyOctalEscapeSequence
+        '0'..='7' => {
+            let mut value = c.to_digit(8).unwrap();
+            let max_digits = if c <= '3' { 2 } else { 1 };
+            for _ in 0..max_digits {
+                match chars.next_if(|(_, c)| matches!(c, '0'..='7')) {
+                    Some((_, digit)) => value = value * 8 + digit.to_digit(8).unwrap(),
+                    None => break,
+                }
+            }
+            units.push(value as u16);
+        }
+        // NonOctalDecimalEscapeSequence `\8` and `\9`, and NonEscapeCharacter
+        c => push_char(units, c),
+    }
+    Ok(())
+}
+
+fn hex_digits(chars: &mut Peekable<CharIndices>, count: usize) -> Option<u32> {
+    (0..count).try_fold(0, |value, _| {
+        let (_, c) = chars.next_if(|(_, c)| c.is_ascii_hexdigit())?;
+        Some((value << 4) | c.to_digit(16)?)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::Span;
+
+    use super::{cook, cook_utf16, is_well_formed_unicode, EscapeContext, InvalidEscape};
+
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().collect()
+    }
+
+    #[test]
+    fn escapes() {
+        let cases = [
+            (r"plain text", "plain text"),
+            (r"\b\f\n\r\t\v\0", "\u{8}\u{c}\n\r\t\u{b}\0"),
+            (r#"\'\"\\"#, r#"'"\"#),
+            (r"\x41B\u{43}\u{000044}", "ABCD"),
+            (r"\u{1F600} 😀", "😀 😀"),
+            (
+                "line \\\ncontinuation \\\r\nand \\\u{2028}separators",
+                "line continuation and separators",
+            ),
+            (r"\a\c", "ac"),
+            ("非 ASCII", "非 ASCII"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(cook(raw, EscapeContext::String), Ok(expected.to_string()), "{raw}");
+            assert_eq!(cook(raw, EscapeContext::Template), Ok(expected.to_string()), "{raw}");
+        }
+    }
+
+    #[test]
+    fn legacy_octal() {
+        let cases = [
+            (r"\8\9", "89"),
+            (r"\1", "\u{1}"),
+            (r"\101", "A"),
+            (r"\377", "ÿ"),
+            (r"\400", "\u{20}0"),
+            (r"\08", "\08"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(cook(raw, EscapeContext::String), Ok(expected.to_string()), "{raw}");
+        }
+        assert_eq!(cook(r"a\1", EscapeContext::Template), Err(InvalidEscape(Span::new(1, 3))));
+        assert_eq!(cook(r"\08", EscapeContext::Template), Err(InvalidEscape(Span::new(0, 2))));
+        assert_eq!(cook(r"\9", EscapeContext::Template), Err(InvalidEscape(Span::new(0, 2))));
+    }
+
+    #[test]
+    fn template_line_terminators() {
+        assert_eq!(cook("a\r\nb\rc", EscapeContext::Template), Ok("a\nb\nc".to_string()));
+    }
+
+    #[test]
+    fn lone_surrogates() {
+        let units = cook_utf16(r"\uD800", EscapeContext::String).unwrap();
+        assert_eq!(units, vec![0xD800]);
+        assert!(!is_well_formed_unicode(&units));
+        assert_eq!(cook_utf16(r"\u{DC00}a", EscapeContext::String).unwrap(), vec![0xDC00, 0x61]);
+        assert_eq!(cook(r"\uD800", EscapeContext::String), Ok("\u{FFFD}".to_string()));
+
+        let units = cook_utf16(r"😀", EscapeContext::String).unwrap();
+        assert_eq!(units, utf16("😀"));
+        assert!(is_well_formed_unicode(&units));
+        // An escaped backslash is not the start of an escape
+        assert!(is_well_formed_unicode(&cook_utf16(r"\\uD800", EscapeContext::String).unwrap()));
+    }
+
+    #[test]
+    fn invalid() {
+        let cases = [
+            (r"\x", Span::new(0, 2)),
+            (r"\x4", Span::new(0, 3)),
+            (r"ab\xZZ", Span::new(2, 4)),
+            (r"\u12", Span::new(0, 4)),
+            (r"\u{}", Span::new(0, 3)),
+            (r"\u{110000}", Span::new(0, 9)),
+            (r"\u{41", Span::new(0, 5)),
+            (r"\", Span::new(0, 1)),
+        ];
+        for (raw, span) in cases {
+            assert_eq!(cook_utf16(raw, EscapeContext::String), Err(InvalidEscape(span)), "{raw}");
+            assert_eq!(cook_utf16(raw, EscapeContext::Template), Err(InvalidEscape(span)), "{raw}");
+        }
+    }
+}
@@ -14,6 +14,10 @@ pub struct ModuleRecord {
     /// Keyed by FromClause, valued by all node occurrences
     pub module_requests: FxHashMap<Atom, Vec<Span>>,
 
+    /// [Import Attributes](https://tc39.es/proposal-import-attributes) of the module requests
+    /// with a `with { ... }` (or legacy `assert { ... }`) clause, in source order
+    pub import_attributes: Vec<ModuleRequestAttributes>,
+
     /// A List of ImportEntry records derived from the code of this module
     pub import_entries: Vec<ImportEntry>,
 
@@ -38,6 +42,16 @@ pub struct ModuleRecord {
     pub export_default_duplicated: Vec<Span>,
 }
 
+impl ModuleRecord {
+    /// Import attributes of the module request at `span`, i.e. the span of the ModuleSpecifier
+    pub fn import_attributes_of(&self, span: Span) -> Option<&[ImportAttributeEntry]> {
+        self.import_attributes
+            .iter()
+            .find(|request| request.module_request.span() == span)
+            .map(|request| request.attributes.as_slice())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NameSpan {
     name: Atom,
@@ -71,6 +85,25 @@ pub struct ImportEntry {
     pub local_name: NameSpan,
 }
 
+/// The attributes of a module request, e.g. `import json from "./foo.json" with { type: "json" }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleRequestAttributes {
+    /// String value of the ModuleSpecifier the attributes apply to
+    pub module_request: NameSpan,
+
+    pub attributes: Vec<ImportAttributeEntry>,
+}
+
+/// [`ImportAttribute`](https://tc39.es/proposal-import-attributes/#importattribute-record)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportAttributeEntry {
+    /// `type` of `type: "json"`
+    pub key: NameSpan,
+
+    /// `"json"` of `type: "json"`
+    pub value: NameSpan,
+}
+
 /// `ImportName` For `ImportEntry`
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ImportImportName {
@@ -0,0 +1,179 @@
+//! Values of numeric literals
+//!
+//! See [Numeric Literals](https://tc39.es/ecma262/#sec-literals-numeric-literals)
+
+use std::borrow::Cow;
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum NumberBase {
+    Float,
+    #[default]
+    Decimal,
+    Binary,
+    Octal,
+    Hex,
+}
+
+impl NumberBase {
+    pub fn is_base_10(&self) -> bool {
+        matches!(self, Self::Float | Self::Decimal)
+    }
+
+    pub fn radix(self) -> u32 {
+        match self {
+            Self::Float | Self::Decimal => 10,
+            Self::Binary => 2,
+            Self::Octal => 8,
+            Self::Hex => 16,
+        }
+    }
+}
+
+/// Parse the value of a number literal as written in source text,
+/// e.g. `0b101`, `0x1F`, `017` (legacy octal), `089`, `1_000` or `1.5e-3`.
+///
+/// The base is `Float` for literals with a fraction, same as `NumberLiteral::base`.
+/// Returns `None` for text which is not a number literal, including BigInt literals.
+pub fn parse_number(raw: &str) -> Option<(f64, NumberBase)> {
+    let text = without_separators(raw);
+    let (base, digits) = split_base(&text);
+    let value = match base {
+        NumberBase::Decimal | NumberBase::Float => {
+            if !text.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+                return None;
+            }
+            text.parse::<f64>().ok()?
+        }
+        _ => parse_radix(digits, base.radix())?,
+    };
+    let base = match base {
+        // `1.5e3` is an integer
+        NumberBase::Decimal | NumberBase::Float if text.contains(['e', 'E']) => {
+            if value.fract() == 0.0 {
+                NumberBase::Decimal
+            } else {
+                NumberBase::Float
+            }
+        }
+        base => base,
+    };
+    Some((value, base))
+}
+
+/// The digits of a BigInt literal without the base prefix, separators and `n` suffix,
+/// e.g. `0x1F_FFn` is `("1FFF", NumberBase::Hex)`.
+///
+/// Returns `None` for text which is not a BigInt literal.
+pub fn parse_bigint_digits(raw: &str) -> Option<(String, NumberBase)> {
+    let text = without_separators(raw.strip_suffix('n')?);
+    let (base, digits) = split_base(&text);
+    match base {
+        NumberBase::Float => return None,
+        // Legacy octal and leading zeros are not allowed
+        NumberBase::Decimal if digits.len() > 1 && digits.starts_with('0') => return None,
+        NumberBase::Octal if !text[1..].starts_with(['o', 'O']) => return None,
+        _ => {}
+    }
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(base.radix())) {
+        return None;
+    }
+    Some((digits.to_string(), base))
+}
+
+fn without_separators(raw: &str) -> Cow<'_, str> {
+    if raw.contains('_') {
+        Cow::Owned(raw.replace('_', ""))
+    } else {
+        Cow::Borrowed(raw)
+    }
+}
+
+/// Split `text` into its base and the digits after the prefix
+fn split_base(text: &str) -> (NumberBase, &str) {
+    let bytes = text.as_bytes();
+    let base = match bytes {
+        [b'0', b'b' | b'B', ..] => NumberBase::Binary,
+        [b'0', b'o' | b'O', ..] => NumberBase::Octal,
+        [b'0', b'x' | b'X', ..] => NumberBase::Hex,
+        // LegacyOctalIntegerLiteral, otherwise NonOctalDecimalIntegerLiteral such as `089`
+        [b'0', rest @ ..] if !rest.is_empty() && rest.iter().all(|b| matches!(b, b'0'..=b'7')) => {
+            return (NumberBase::Octal, &text[1..]);
+        }
+        _ if text.contains(['.', 'e', 'E']) => return (NumberBase::Float, text),
+        _ => return (NumberBase::Decimal, text),
+    };
+    (base, &text[2..])
+}
+
+fn parse_radix(digits: &str, radix: u32) -> Option<f64> {
+    if digits.is_empty() {
+        return None;
+    }
+    digits.chars().try_fold(0_f64, |value, c| {
+        let digit = c.to_digit(radix)?;
+        Some(value.mul_add(f64::from(radix), f64::from(digit)))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_bigint_digits, parse_number, NumberBase};
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn numbers() {
+        let cases = [
+            ("0", 0.0, NumberBase::Decimal),
+            ("123", 123.0, NumberBase::Decimal),
+            ("1_000_000", 1_000_000.0, NumberBase::Decimal),
+            ("1.5", 1.5, NumberBase::Float),
+            (".5", 0.5, NumberBase::Float),
+            ("1.", 1.0, NumberBase::Float),
+            ("1e3", 1000.0, NumberBase::Decimal),
+            ("1.5e3", 1500.0, NumberBase::Decimal),
+            ("1.5E-3", 0.0015, NumberBase::Float),
+            ("0b1010", 10.0, NumberBase::Binary),
+            ("0B1_0", 2.0, NumberBase::Binary),
+            ("0o17", 15.0, NumberBase::Octal),
+            ("017", 15.0, NumberBase::Octal),
+            ("089", 89.0, NumberBase::Decimal),
+            ("08.5", 8.5, NumberBase::Float),
+            ("0xFF", 255.0, NumberBase::Hex),
+            ("0Xdead_beef", 3_735_928_559.0, NumberBase::Hex),
+        ];
+        for (raw, value, base) in cases {
+            assert_eq!(parse_number(raw), Some((value, base)), "{raw}");
+        }
+        // Precision is lost the same way as in JavaScript
+        assert_eq!(
+            parse_number("9007199254740993"),
+            Some((9_007_199_254_740_992.0, NumberBase::Decimal))
+        );
+    }
+
+    #[test]
+    fn invalid_numbers() {
+        for raw in ["", "0x", "0b2", "0o8", "1n", "inf", "NaN", "+1", "1e", "0xG"] {
+            assert_eq!(parse_number(raw), None, "{raw}");
+        }
+    }
+
+    #[test]
+    fn bigints() {
+        let cases = [
+            ("0n", "0", NumberBase::Decimal),
+            ("123n", "123", NumberBase::Decimal),
+            ("9007199254740993n", "9007199254740993", NumberBase::Decimal),
+            ("1_000n", "1000", NumberBase::Decimal),
+            ("0b101n", "101", NumberBase::Binary),
+            ("0o17n", "17", NumberBase::Octal),
+            ("0x1F_FFn", "1FFF", NumberBase::Hex),
+        ];
+        for (raw, digits, base) in cases {
+            assert_eq!(parse_bigint_digits(raw), Some((digits.to_string(), base)), "{raw}");
+        }
+        for raw in ["123", "1.5n", "1e3n", "017n", "01n", "0xn", "0b2n"] {
+            assert_eq!(parse_bigint_digits(raw), None, "{raw}");
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Code which runs when a class is constructed, before the body of its constructor
+
+use std::mem;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+
+use crate::{shorthands::Shorthands, temporaries::Temporaries};
+
+/// Runs `initializers` at the start of the constructor of `class`, or after `super()` in derived
+/// classes, adding a constructor if there is none
+pub fn add_initializers<'a>(
+    ast: &AstBuilder<'a>,
+    temporaries: &mut Temporaries<'a>,
+    class: &mut Class<'a>,
+    initializers: std::vec::Vec<Expression<'a>>,
+) {
+    let is_derived = class.super_class.is_some();
+    let constructor = class.body.body.iter_mut().find_map(|element| match element {
+        ClassElement::MethodDefinition(def) if def.kind == MethodDefinitionKind::Constructor => {
+            Some(def)
+        }
+        _ => None,
+    });
+    let Some(constructor) = constructor else {
+        let constructor = create_constructor(ast, temporaries, is_derived, initializers);
+        class.body.body.insert(0, constructor);
+        return;
+    };
+    let Some(body) = &mut constructor.value.body else { return };
+    let index = if is_derived {
+        // `super(...);` at the top level of the constructor
+        let index = body.statements.iter().position(|stmt| match stmt {
+            Statement::ExpressionStatement(stmt) => is_super_call(&stmt.expression),
+            _ => false,
+        });
+        let Some(index) = index else {
+            SuperCall { ast: AstBuilder::new(ast.allocator), initializers }
+                .visit_function_body(body);
+            return;
+        };
+        index + 1
+    } else {
+        0
+    };
+    for (offset, expr) in initializers.into_iter().enumerate() {
+        body.statements.insert(index + offset, ast.expression_statement(SPAN, expr));
+    }
+}
+
+/// `constructor() { initializers }`, or
+/// `constructor(..._args) { super(..._args); initializers }` in derived classes
+fn create_constructor<'a>(
+    ast: &AstBuilder<'a>,
+    temporaries: &mut Temporaries<'a>,
+    is_derived: bool,
+    initializers: std::vec::Vec<Expression<'a>>,
+) -> ClassElement<'a> {
+    let mut statements = ast.new_vec_with_capacity(initializers.len() + 1);
+    let mut rest = None;
+    if is_derived {
+        let args = temporaries.create_unscoped("args");
+        let id = BindingIdentifier { span: SPAN, name: args.clone() };
+        let id = ast.binding_pattern(ast.binding_identifier(id), None, false);
+        rest = Some(ast.rest_element(SPAN, id));
+        let spread = ast.spread_element(SPAN, ast.identifier_reference_expression(&args));
+        let arguments = ast.new_vec_single(Argument::SpreadElement(spread));
+        let call = ast.call_expression(SPAN, ast.super_(SPAN), arguments, false, None, false);
+        statements.push(ast.expression_statement(SPAN, call));
+    }
+    statements.extend(initializers.into_iter().map(|expr| ast.expression_statement(SPAN, expr)));
+    let kind = FormalParameterKind::UniqueFormalParameters;
+    let params = ast.formal_parameters(SPAN, kind, ast.new_vec(), rest);
+    let body = ast.function_body(SPAN, ast.new_vec(), statements);
+    let value = ast.function(
+        FunctionType::FunctionExpression,
+        SPAN,
+        None,
+        false,
+        false,
+        false,
+        params,
+        Some(body),
+        None,
+        None,
+        Modifiers::empty(),
+    );
+    let key = IdentifierName { span: SPAN, name: Atom::from("constructor") };
+    let key = PropertyKey::Identifier(ast.alloc(key));
+    ast.method_definition(MethodDefinitionKind::Constructor, key, false, false, value)
+}
+
+fn is_super_call(expr: &Expression) -> bool {
+    matches!(expr, Expression::CallExpression(call) if matches!(call.callee, Expression::Super(_)))
+}
+
+/// `super()` -> `(super(), initializers, this)` for the first `super()` in a constructor, which
+/// is not an expression statement
+struct SuperCall<'a> {
+    ast: AstBuilder<'a>,
+    initializers: std::vec::Vec<Expression<'a>>,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for SuperCall<'a> {
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        self.visit_expression_match(expr);
+        if self.initializers.is_empty() || !is_super_call(expr) {
+            return;
+        }
+        let call = mem::replace(expr, self.ast.null_literal_expression());
+        let mut expressions = self.ast.new_vec_with_capacity(self.initializers.len() + 2);
+        expressions.push(call);
+        expressions.extend(self.initializers.drain(..));
+        expressions.push(self.ast.this_expression(SPAN));
+        let sequence = self.ast.sequence_expression(SPAN, expressions);
+        *expr = self.ast.parenthesized_expression(SPAN, sequence);
+    }
+
+    // `super()` can only be called in arrow functions nested in the constructor
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, _class: &'b mut Class<'a>) {}
+}
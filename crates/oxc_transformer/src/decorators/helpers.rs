@@ -0,0 +1,610 @@
+//! Functions which apply decorators at runtime, declared at the start of the program when they
+//! are used
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{BinaryOperator, LogicalOperator, UpdateOperator};
+
+use crate::shorthands::Shorthands;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Helper {
+    /// Applies the decorators of the 2023 proposal to a class and its elements
+    ApplyDecs,
+    /// Applies legacy decorators to a class or to a class element
+    Decorate,
+    /// Turns a legacy parameter decorator into a method decorator
+    Param,
+    /// A legacy decorator defining metadata with `Reflect.metadata`
+    Metadata,
+}
+
+impl Helper {
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::ApplyDecs => "applyDecs",
+            Self::Decorate => "decorate",
+            Self::Param => "param",
+            Self::Metadata => "metadata",
+        }
+    }
+
+    pub(super) fn declaration<'a>(self, ast: &AstBuilder<'a>, name: Atom) -> Statement<'a> {
+        let (params, body) = match self {
+            Self::ApplyDecs => (&["targetClass", "memberDecs", "classDecs"][..], apply_decs(ast)),
+            Self::Decorate => (&["decorators", "target", "key", "descriptor"][..], decorate(ast)),
+            Self::Param => (&["index", "decorator"][..], param(ast)),
+            Self::Metadata => (&["key", "value"][..], metadata(ast)),
+        };
+        ast.function_declaration_of(name, params, body)
+    }
+}
+
+/// ```javascript
+/// var metadataSymbol = Symbol.metadata || Symbol.for("Symbol.metadata");
+/// var metadata = Object.create(targetClass[metadataSymbol] || null);
+/// var protoInitializers = [], staticInitializers = [], classInitializers = [],
+///     elementValues = [];
+/// function decorate(decorator, value, kind, name, isStatic, isPrivate, access, initializers) {
+///     var done = false;
+///     var context = {
+///         kind: kind, name: name, metadata: metadata,
+///         addInitializer: (initializer) => {
+///             if (done) throw new TypeError("...");
+///             initializers.push(initializer);
+///         },
+///     };
+///     if (kind !== "class") {
+///         context.static = isStatic; context.private = isPrivate; context.access = access;
+///     }
+///     var result = decorator(value, context);
+///     done = true;
+///     var type = kind === "accessor" ? "object" : "function";
+///     if (result !== void 0 && typeof result !== type) throw new TypeError("...");
+///     return result;
+/// }
+/// function run(initializers, receiver) {
+///     initializers.forEach((initializer) => initializer.call(receiver));
+/// }
+/// memberDecs.forEach((member) => {
+///     var decorators = member[0], kind = member[1], name = member[2], isStatic = member[3],
+///         isPrivate = member[4];
+///     var target = isStatic ? targetClass : targetClass.prototype;
+///     var initializers = isStatic ? staticInitializers : protoInitializers;
+///     var access = member[5] || {
+///         has: (object) => name in object,
+///         get: (object) => object[name],
+///         set: (object, value) => { object[name] = value; },
+///     };
+///     var key = kind === "getter" ? "get" : kind === "setter" ? "set" : "value";
+///     var descriptor = kind === "field" ? void 0
+///         : member[6] || Object.getOwnPropertyDescriptor(target, name);
+///     var values = [];
+///     for (var i = decorators.length - 1; i >= 0; i--) {
+///         var value = kind === "field" ? void 0
+///             : kind === "accessor" ? { get: descriptor.get, set: descriptor.set }
+///             : descriptor[key];
+///         var result = decorate(decorators[i], value, kind, name, isStatic, isPrivate, access,
+///             initializers);
+///         if (result) {
+///             if (kind === "field") {
+///                 values.push(result);
+///             } else if (kind === "accessor") {
+///                 if (result.get) descriptor.get = result.get;
+///                 if (result.set) descriptor.set = result.set;
+///                 if (result.init) values.push(result.init);
+///             } else {
+///                 descriptor[key] = result;
+///             }
+///         }
+///     }
+///     if (descriptor && !isPrivate) Object.defineProperty(target, name, descriptor);
+///     if (kind === "field" || kind === "accessor") {
+///         elementValues.push((instance, value) => values.reduce(
+///             (value, initializer) => initializer.call(instance, value), value));
+///     }
+///     if (descriptor && isPrivate) elementValues.push(descriptor);
+/// });
+/// run(staticInitializers, targetClass);
+/// Object.defineProperty(targetClass, metadataSymbol,
+///     { value: metadata, writable: true, enumerable: true, configurable: true });
+/// var newClass = targetClass;
+/// for (var i = classDecs.length - 1; i >= 0; i--) {
+///     newClass = decorate(classDecs[i], newClass, "class", targetClass.name, false, false,
+///         void 0, classInitializers) || newClass;
+/// }
+/// return [newClass, (instance) => run(protoInitializers, instance),
+///     () => run(classInitializers, newClass)].concat(elementValues);
+/// ```
+#[allow(clippy::too_many_lines)]
+fn apply_decs<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let symbol = ast.ident("Symbol");
+    let metadata_symbol = ast.logical(
+        ast.member(symbol, "metadata"),
+        LogicalOperator::Or,
+        ast.call(ast.member(ast.ident("Symbol"), "for"), [ast.string("Symbol.metadata")]),
+    );
+    let parent_metadata = ast.computed(ast.ident("targetClass"), ast.ident("metadataSymbol"));
+    let parent_metadata =
+        ast.logical(parent_metadata, LogicalOperator::Or, ast.null_literal_expression());
+    let metadata = ast.call(ast.member(ast.ident("Object"), "create"), [parent_metadata]);
+    let lists = ["protoInitializers", "staticInitializers", "classInitializers", "elementValues"]
+        .map(|name| (name, Some(ast.array([]))));
+
+    let kind_is = |kind: &str| {
+        ast.binary(ast.ident("kind"), BinaryOperator::StrictEquality, ast.string(kind))
+    };
+    let run = |initializers: &str, receiver: Expression<'a>| {
+        ast.call(ast.ident("run"), [ast.ident(initializers), receiver])
+    };
+
+    // function decorate(decorator, value, kind, name, isStatic, isPrivate, access, initializers)
+    let add_initializer = ast.arrow(
+        &["initializer"],
+        ast.statements([
+            ast.if_else(
+                ast.ident("done"),
+                ast.throw_type_error("Cannot add initializers after decoration has completed"),
+                None,
+            ),
+            ast.statement(
+                ast.call(ast.member(ast.ident("initializers"), "push"), [ast.ident("initializer")]),
+            ),
+        ]),
+    );
+    let context = ast.object([
+        ("kind", ast.ident("kind")),
+        ("name", ast.ident("name")),
+        ("metadata", ast.ident("metadata")),
+        ("addInitializer", add_initializer),
+    ]);
+    let element_context = ast.statements(
+        [("static", "isStatic"), ("private", "isPrivate"), ("access", "access")].map(
+            |(property, value)| {
+                let property = ast.member(ast.ident("context"), property);
+                ast.statement(ast.assign(property, ast.ident(value)))
+            },
+        ),
+    );
+    let result_type =
+        ast.conditional(kind_is("accessor"), ast.string("object"), ast.string("function"));
+    let is_invalid = ast.logical(
+        ast.binary(ast.ident("result"), BinaryOperator::StrictInequality, ast.void_0()),
+        LogicalOperator::And,
+        ast.binary(
+            ast.type_of(ast.ident("result")),
+            BinaryOperator::StrictInequality,
+            ast.ident("type"),
+        ),
+    );
+    let decorate = ast.function_declaration_of(
+        Atom::from("decorate"),
+        &["decorator", "value", "kind", "name", "isStatic", "isPrivate", "access", "initializers"],
+        ast.statements([
+            ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [("done", Some(ast.boolean_literal_expression(false)))],
+            ),
+            ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [("context", Some(context))],
+            ),
+            ast.if_else(
+                ast.binary(
+                    ast.ident("kind"),
+                    BinaryOperator::StrictInequality,
+                    ast.string("class"),
+                ),
+                ast.block_of(element_context),
+                None,
+            ),
+            ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [(
+                    "result",
+                    Some(
+                        ast.call(
+                            ast.ident("decorator"),
+                            [ast.ident("value"), ast.ident("context")],
+                        ),
+                    ),
+                )],
+            ),
+            ast.statement(ast.assign(ast.ident("done"), ast.boolean_literal_expression(true))),
+            ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [("type", Some(result_type))],
+            ),
+            ast.if_else(
+                is_invalid,
+                ast.throw_type_error("A decorator returned a value of the wrong type"),
+                None,
+            ),
+            ast.return_statement(SPAN, Some(ast.ident("result"))),
+        ]),
+    );
+
+    // function run(initializers, receiver)
+    let call_initializer = ast.arrow_returning(
+        &["initializer"],
+        ast.call(ast.member(ast.ident("initializer"), "call"), [ast.ident("receiver")]),
+    );
+    let run_declaration = ast.function_declaration_of(
+        Atom::from("run"),
+        &["initializers", "receiver"],
+        ast.statements([ast.statement(
+            ast.call(ast.member(ast.ident("initializers"), "forEach"), [call_initializer]),
+        )]),
+    );
+
+    // memberDecs.forEach((member) => { ... })
+    let member_fields = ["decorators", "kind", "name", "isStatic", "isPrivate"]
+        .into_iter()
+        .zip(0..)
+        .map(|(name, index)| (name, Some(ast.index(ast.ident("member"), index))));
+    let is_static = || ast.ident("isStatic");
+    let target = ast.conditional(
+        is_static(),
+        ast.ident("targetClass"),
+        ast.member(ast.ident("targetClass"), "prototype"),
+    );
+    let initializers = ast.conditional(
+        is_static(),
+        ast.ident("staticInitializers"),
+        ast.ident("protoInitializers"),
+    );
+    let property = || ast.computed(ast.ident("object"), ast.ident("name"));
+    let public_access = ast.object([
+        (
+            "has",
+            ast.arrow_returning(
+                &["object"],
+                ast.binary(ast.ident("name"), BinaryOperator::In, ast.ident("object")),
+            ),
+        ),
+        ("get", ast.arrow_returning(&["object"], property())),
+        (
+            "set",
+            ast.arrow(
+                &["object", "value"],
+                ast.statements([ast.statement(ast.assign(property(), ast.ident("value")))]),
+            ),
+        ),
+    ]);
+    let access = ast.logical(ast.index(ast.ident("member"), 5), LogicalOperator::Or, public_access);
+    let key = ast.conditional(
+        kind_is("getter"),
+        ast.string("get"),
+        ast.conditional(kind_is("setter"), ast.string("set"), ast.string("value")),
+    );
+    let descriptor = ast.conditional(
+        kind_is("field"),
+        ast.void_0(),
+        ast.logical(
+            ast.index(ast.ident("member"), 6),
+            LogicalOperator::Or,
+            ast.call(
+                ast.member(ast.ident("Object"), "getOwnPropertyDescriptor"),
+                [ast.ident("target"), ast.ident("name")],
+            ),
+        ),
+    );
+    let descriptor_value = || ast.computed(ast.ident("descriptor"), ast.ident("key"));
+    let descriptor_accessor = |name: &str| ast.member(ast.ident("descriptor"), name);
+    let accessor =
+        ast.object([("get", descriptor_accessor("get")), ("set", descriptor_accessor("set"))]);
+    let value = ast.conditional(
+        kind_is("field"),
+        ast.void_0(),
+        ast.conditional(kind_is("accessor"), accessor, descriptor_value()),
+    );
+    let result = ast.call(
+        ast.ident("decorate"),
+        [
+            ast.computed(ast.ident("decorators"), ast.ident("i")),
+            ast.ident("value"),
+            ast.ident("kind"),
+            ast.ident("name"),
+            ast.ident("isStatic"),
+            ast.ident("isPrivate"),
+            ast.ident("access"),
+            ast.ident("initializers"),
+        ],
+    );
+    let push_value = |value: Expression<'a>| {
+        ast.statement(ast.call(ast.member(ast.ident("values"), "push"), [value]))
+    };
+    let result_accessor = |name: &str| ast.member(ast.ident("result"), name);
+    let save_accessor = ast.statements(["get", "set"].map(|name| {
+        ast.if_else(
+            result_accessor(name),
+            ast.statement(ast.assign(descriptor_accessor(name), result_accessor(name))),
+            None,
+        )
+    }));
+    let mut save_accessor = save_accessor;
+    save_accessor.push(ast.if_else(
+        result_accessor("init"),
+        push_value(result_accessor("init")),
+        None,
+    ));
+    let save_result =
+        ast.if_else(
+            kind_is("field"),
+            ast.block_of(ast.statements([push_value(ast.ident("result"))])),
+            Some(ast.if_else(
+                kind_is("accessor"),
+                ast.block_of(save_accessor),
+                Some(ast.block_of(ast.statements([
+                    ast.statement(ast.assign(descriptor_value(), ast.ident("result"))),
+                ]))),
+            )),
+        );
+    let apply_member_decorators = for_each_reversed(
+        ast,
+        "decorators",
+        ast.block_of(ast.statements([
+            ast.var_declaration_statement(VariableDeclarationKind::Var, [("value", Some(value))]),
+            ast.var_declaration_statement(VariableDeclarationKind::Var, [("result", Some(result))]),
+            ast.if_else(ast.ident("result"), ast.block_of(ast.statements([save_result])), None),
+        ])),
+    );
+    let define = ast.call(
+        ast.member(ast.ident("Object"), "defineProperty"),
+        [ast.ident("target"), ast.ident("name"), ast.ident("descriptor")],
+    );
+    let initialize_field = ast.arrow_returning(
+        &["value", "initializer"],
+        ast.call(
+            ast.member(ast.ident("initializer"), "call"),
+            [ast.ident("instance"), ast.ident("value")],
+        ),
+    );
+    let initialize_field = ast.arrow_returning(
+        &["instance", "value"],
+        ast.call(ast.member(ast.ident("values"), "reduce"), [initialize_field, ast.ident("value")]),
+    );
+    let add_field_initializer = ast.if_else(
+        ast.logical(kind_is("field"), LogicalOperator::Or, kind_is("accessor")),
+        ast.block_of(ast.statements([ast.statement(
+            ast.call(ast.member(ast.ident("elementValues"), "push"), [initialize_field]),
+        )])),
+        None,
+    );
+    let add_private_descriptor = ast.if_else(
+        ast.logical(ast.ident("descriptor"), LogicalOperator::And, ast.ident("isPrivate")),
+        ast.statement(
+            ast.call(ast.member(ast.ident("elementValues"), "push"), [ast.ident("descriptor")]),
+        ),
+        None,
+    );
+    let decorate_member = ast.arrow(
+        &["member"],
+        ast.statements([
+            ast.var_declaration_statement(VariableDeclarationKind::Var, member_fields),
+            ast.var_declaration_statement(VariableDeclarationKind::Var, [("target", Some(target))]),
+            ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [("initializers", Some(initializers))],
+            ),
+            ast.var_declaration_statement(VariableDeclarationKind::Var, [("access", Some(access))]),
+            ast.var_declaration_statement(VariableDeclarationKind::Var, [("key", Some(key))]),
+            ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [("descriptor", Some(descriptor))],
+            ),
+            ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [("values", Some(ast.array([])))],
+            ),
+            apply_member_decorators,
+            ast.if_else(
+                ast.logical(
+                    ast.ident("descriptor"),
+                    LogicalOperator::And,
+                    ast.not(ast.ident("isPrivate")),
+                ),
+                ast.statement(define),
+                None,
+            ),
+            add_field_initializer,
+            add_private_descriptor,
+        ]),
+    );
+
+    // The class
+    let define_metadata = ast.call(
+        ast.member(ast.ident("Object"), "defineProperty"),
+        [
+            ast.ident("targetClass"),
+            ast.ident("metadataSymbol"),
+            ast.object([
+                ("value", ast.ident("metadata")),
+                ("writable", ast.boolean_literal_expression(true)),
+                ("enumerable", ast.boolean_literal_expression(true)),
+                ("configurable", ast.boolean_literal_expression(true)),
+            ]),
+        ],
+    );
+    let decorate_class = ast.call(
+        ast.ident("decorate"),
+        [
+            ast.computed(ast.ident("classDecs"), ast.ident("i")),
+            ast.ident("newClass"),
+            ast.string("class"),
+            ast.member(ast.ident("targetClass"), "name"),
+            ast.boolean_literal_expression(false),
+            ast.boolean_literal_expression(false),
+            ast.void_0(),
+            ast.ident("classInitializers"),
+        ],
+    );
+    let decorate_class = ast.logical(decorate_class, LogicalOperator::Or, ast.ident("newClass"));
+    let apply_class_decorators = for_each_reversed(
+        ast,
+        "classDecs",
+        ast.block_of(
+            ast.statements([ast.statement(ast.assign(ast.ident("newClass"), decorate_class))]),
+        ),
+    );
+    let result = ast.array([
+        ast.ident("newClass"),
+        ast.arrow_returning(&["instance"], run("protoInitializers", ast.ident("instance"))),
+        ast.arrow_returning(&[], run("classInitializers", ast.ident("newClass"))),
+    ]);
+    let result = ast.call(ast.member(result, "concat"), [ast.ident("elementValues")]);
+
+    ast.statements([
+        ast.var_declaration_statement(
+            VariableDeclarationKind::Var,
+            [("metadataSymbol", Some(metadata_symbol))],
+        ),
+        ast.var_declaration_statement(VariableDeclarationKind::Var, [("metadata", Some(metadata))]),
+        ast.var_declaration_statement(VariableDeclarationKind::Var, lists),
+        decorate,
+        run_declaration,
+        ast.statement(ast.call(ast.member(ast.ident("memberDecs"), "forEach"), [decorate_member])),
+        ast.statement(run("staticInitializers", ast.ident("targetClass"))),
+        ast.statement(define_metadata),
+        ast.var_declaration_statement(
+            VariableDeclarationKind::Var,
+            [("newClass", Some(ast.ident("targetClass")))],
+        ),
+        apply_class_decorators,
+        ast.return_statement(SPAN, Some(result)),
+    ])
+}
+
+/// ```javascript
+/// var isClass = arguments.length < 3;
+/// if (descriptor === null) descriptor = Object.getOwnPropertyDescriptor(target, key);
+/// var result = isClass ? target : descriptor;
+/// for (var i = decorators.length - 1; i >= 0; i--) {
+///     if (decorators[i]) {
+///         result = (isClass ? decorators[i](result) : decorators[i](target, key, result))
+///             || result;
+///     }
+/// }
+/// if (!isClass && result) Object.defineProperty(target, key, result);
+/// return result;
+/// ```
+fn decorate<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let is_class = ast.binary(
+        ast.member(ast.ident("arguments"), "length"),
+        BinaryOperator::LessThan,
+        ast.number(3.0),
+    );
+    let get_descriptor = ast.call(
+        ast.member(ast.ident("Object"), "getOwnPropertyDescriptor"),
+        [ast.ident("target"), ast.ident("key")],
+    );
+    let decorator = || ast.computed(ast.ident("decorators"), ast.ident("i"));
+    let result = ast.conditional(
+        ast.ident("isClass"),
+        ast.call(decorator(), [ast.ident("result")]),
+        ast.call(decorator(), [ast.ident("target"), ast.ident("key"), ast.ident("result")]),
+    );
+    let result = ast.logical(
+        ast.parenthesized_expression(SPAN, result),
+        LogicalOperator::Or,
+        ast.ident("result"),
+    );
+    let apply = ast.if_else(
+        decorator(),
+        ast.block_of(ast.statements([ast.statement(ast.assign(ast.ident("result"), result))])),
+        None,
+    );
+    let apply_all = for_each_reversed(ast, "decorators", ast.block_of(ast.statements([apply])));
+    let define = ast.call(
+        ast.member(ast.ident("Object"), "defineProperty"),
+        [ast.ident("target"), ast.ident("key"), ast.ident("result")],
+    );
+    ast.statements([
+        ast.var_declaration_statement(VariableDeclarationKind::Var, [("isClass", Some(is_class))]),
+        ast.if_else(
+            ast.binary(
+                ast.ident("descriptor"),
+                BinaryOperator::StrictEquality,
+                ast.null_literal_expression(),
+            ),
+            ast.statement(ast.assign(ast.ident("descriptor"), get_descriptor)),
+            None,
+        ),
+        ast.var_declaration_statement(
+            VariableDeclarationKind::Var,
+            [(
+                "result",
+                Some(ast.conditional(
+                    ast.ident("isClass"),
+                    ast.ident("target"),
+                    ast.ident("descriptor"),
+                )),
+            )],
+        ),
+        apply_all,
+        ast.if_else(
+            ast.logical(ast.not(ast.ident("isClass")), LogicalOperator::And, ast.ident("result")),
+            ast.statement(define),
+            None,
+        ),
+        ast.return_statement(SPAN, Some(ast.ident("result"))),
+    ])
+}
+
+/// ```javascript
+/// return (target, key) => decorator(target, key, index);
+/// ```
+fn param<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let call = ast
+        .call(ast.ident("decorator"), [ast.ident("target"), ast.ident("key"), ast.ident("index")]);
+    let decorator = ast.arrow_returning(&["target", "key"], call);
+    ast.statements([ast.return_statement(SPAN, Some(decorator))])
+}
+
+/// ```javascript
+/// if (typeof Reflect === "object" && typeof Reflect.metadata === "function") {
+///     return Reflect.metadata(key, value);
+/// }
+/// ```
+fn metadata<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let has_reflect = ast.binary(
+        ast.type_of(ast.ident("Reflect")),
+        BinaryOperator::StrictEquality,
+        ast.string("object"),
+    );
+    let has_metadata = ast.binary(
+        ast.type_of(ast.member(ast.ident("Reflect"), "metadata")),
+        BinaryOperator::StrictEquality,
+        ast.string("function"),
+    );
+    let define = ast
+        .call(ast.member(ast.ident("Reflect"), "metadata"), [ast.ident("key"), ast.ident("value")]);
+    ast.statements([ast.if_else(
+        ast.logical(has_reflect, LogicalOperator::And, has_metadata),
+        ast.block_of(ast.statements([ast.return_statement(SPAN, Some(define))])),
+        None,
+    )])
+}
+
+/// `for (var i = array.length - 1; i >= 0; i--) body`
+fn for_each_reversed<'a>(ast: &AstBuilder<'a>, array: &str, body: Statement<'a>) -> Statement<'a> {
+    let last_decorator = ast.binary(
+        ast.member(ast.ident(array), "length"),
+        BinaryOperator::Subtraction,
+        ast.number(1.0),
+    );
+    let init = ast.var_declaration_of(VariableDeclarationKind::Var, [("i", Some(last_decorator))]);
+    let test = ast.binary(ast.ident("i"), BinaryOperator::GreaterEqualThan, ast.number(0.0));
+    let update = ast.update_expression(
+        SPAN,
+        UpdateOperator::Decrement,
+        false,
+        SimpleAssignmentTarget::AssignmentTargetIdentifier(
+            ast.alloc(IdentifierReference { span: SPAN, name: Atom::from("i") }),
+        ),
+    );
+    let init = ForStatementInit::VariableDeclaration(init);
+    ast.for_statement(SPAN, Some(init), Some(test), Some(update), body)
+}
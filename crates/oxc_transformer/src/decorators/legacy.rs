@@ -0,0 +1,264 @@
+//! Legacy decorators, which are applied after the class is defined
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_syntax::operator::BinaryOperator;
+
+use super::{helpers::Helper, Decorators, LoweredClass};
+use crate::shorthands::Shorthands;
+
+impl<'a> Decorators<'a> {
+    /// `class A { @b m() {} }` -> `class A { m() {} } _decorate([b], A.prototype, "m", null);`
+    pub(super) fn lower_legacy(&mut self, class: &mut Class<'a>) {
+        let (name, binding) = self.class_binding(class);
+        let mut instance = vec![];
+        let mut statics = vec![];
+        let mut class_decorators = self.take_decorators(&mut class.decorators);
+        let mut parameter_decorators = vec![];
+        let mut parameter_types = None;
+
+        for element in class.body.body.iter_mut() {
+            let (decorators, is_static, key, descriptor) = match element {
+                ClassElement::MethodDefinition(def) => {
+                    let mut decorators = self.take_decorators(&mut def.decorators);
+                    let parameters = self.parameter_decorators(&mut def.value.params);
+                    if def.kind == MethodDefinitionKind::Constructor {
+                        parameter_decorators = parameters;
+                        if self.options.emit_decorator_metadata {
+                            let types = self.parameter_types(&def.value.params);
+                            parameter_types = Some(self.metadata("design:paramtypes", types));
+                        }
+                        continue;
+                    }
+                    decorators.extend(parameters);
+                    if decorators.is_empty() {
+                        continue;
+                    }
+                    if self.options.emit_decorator_metadata {
+                        decorators.extend(self.method_metadata(def));
+                    }
+                    (decorators, def.r#static, &mut def.key, self.ast.null_literal_expression())
+                }
+                ClassElement::PropertyDefinition(def) => {
+                    let mut decorators = self.take_decorators(&mut def.decorators);
+                    if decorators.is_empty() {
+                        continue;
+                    }
+                    if self.options.emit_decorator_metadata {
+                        let ty = self.serialize_type(def.type_annotation.as_deref());
+                        decorators.push(self.metadata("design:type", ty));
+                    }
+                    (decorators, def.r#static, &mut def.key, self.ast.void_0())
+                }
+                ClassElement::AccessorProperty(def) => {
+                    let decorators = self.take_decorators(&mut def.decorators);
+                    if decorators.is_empty() {
+                        continue;
+                    }
+                    (decorators, def.r#static, &mut def.key, self.ast.void_0())
+                }
+                _ => continue,
+            };
+            if matches!(key, PropertyKey::PrivateIdentifier(_)) {
+                continue;
+            }
+            let key = self.element_name(key);
+            let target = if is_static {
+                self.ast.ident(&name)
+            } else {
+                self.ast.member(self.ast.ident(&name), "prototype")
+            };
+            let decorate = self.helper(Helper::Decorate);
+            let decorate =
+                self.ast.call(decorate, [self.ast.array(decorators), target, key, descriptor]);
+            if is_static {
+                statics.push(decorate);
+            } else {
+                instance.push(decorate);
+            }
+        }
+
+        // Parameter decorators of the constructor decorate the class
+        if !class_decorators.is_empty() || !parameter_decorators.is_empty() {
+            class_decorators.extend(parameter_decorators);
+            class_decorators.extend(parameter_types);
+            let decorate = self.helper(Helper::Decorate);
+            let decorate =
+                self.ast.call(decorate, [self.ast.array(class_decorators), self.ast.ident(&name)]);
+            statics.push(self.ast.assign(self.ast.ident(&name), decorate));
+        }
+
+        instance.extend(statics);
+        let value = binding.clone();
+        self.lowered = Some(LoweredClass { before: vec![], binding, after: instance, value });
+    }
+
+    /// `m(@a b) {}` -> `_param(0, a)`, removing the decorators of the parameters
+    fn parameter_decorators(
+        &mut self,
+        params: &mut FormalParameters<'a>,
+    ) -> std::vec::Vec<Expression<'a>> {
+        let mut decorators = vec![];
+        let params = params.items.iter_mut().filter(|param| !is_this_parameter(param));
+        for (index, param) in params.enumerate() {
+            for decorator in self.take_decorators(&mut param.decorators) {
+                let param = self.helper(Helper::Param);
+                #[allow(clippy::cast_precision_loss)]
+                let index = self.ast.number(index as f64);
+                decorators.push(self.ast.call(param, [index, decorator]));
+            }
+        }
+        decorators
+    }
+
+    /// `_metadata("design:type", Function)`, the types of the parameters and the return type
+    /// of a method or the type of an accessor
+    fn method_metadata(&mut self, def: &MethodDefinition<'a>) -> std::vec::Vec<Expression<'a>> {
+        let func = &def.value;
+        match def.kind {
+            MethodDefinitionKind::Get => {
+                let ty = self.serialize_type(func.return_type.as_deref());
+                vec![self.metadata("design:type", ty)]
+            }
+            MethodDefinitionKind::Set => {
+                let param = func.params.items.iter().find(|param| !is_this_parameter(param));
+                let ty =
+                    self.serialize_type(param.and_then(|p| p.pattern.type_annotation.as_deref()));
+                let types = self.parameter_types(&func.params);
+                vec![self.metadata("design:type", ty), self.metadata("design:paramtypes", types)]
+            }
+            _ => {
+                let return_type = match &func.return_type {
+                    Some(annotation) => self.serialize_type(Some(annotation)),
+                    None if func.r#async => self.ast.ident("Promise"),
+                    None => self.ast.void_0(),
+                };
+                vec![
+                    self.metadata("design:type", self.ast.ident("Function")),
+                    self.metadata("design:paramtypes", self.parameter_types(&func.params)),
+                    self.metadata("design:returntype", return_type),
+                ]
+            }
+        }
+    }
+
+    /// `[String, Number]`, with the element type of rest parameters
+    fn parameter_types(&self, params: &FormalParameters<'a>) -> Expression<'a> {
+        let mut types: std::vec::Vec<_> = params
+            .items
+            .iter()
+            .filter(|param| !is_this_parameter(param))
+            .map(|param| self.serialize_type(param.pattern.type_annotation.as_deref()))
+            .collect();
+        if let Some(rest) = &params.rest {
+            let ty = match rest.argument.type_annotation.as_deref() {
+                Some(TSTypeAnnotation { type_annotation: TSType::TSArrayType(array), .. }) => {
+                    self.serialize(&array.element_type)
+                }
+                _ => self.ast.ident("Object"),
+            };
+            types.push(ty);
+        }
+        self.ast.array(types)
+    }
+
+    /// `_metadata(key, value)`
+    fn metadata(&mut self, key: &str, value: Expression<'a>) -> Expression<'a> {
+        let metadata = self.helper(Helper::Metadata);
+        self.ast.call(metadata, [self.ast.string(key), value])
+    }
+
+    /// The constructor describing the values of a type annotation, like TypeScript without type
+    /// information
+    fn serialize_type(&self, annotation: Option<&TSTypeAnnotation<'a>>) -> Expression<'a> {
+        annotation.map_or_else(|| self.ast.ident("Object"), |a| self.serialize(&a.type_annotation))
+    }
+
+    fn serialize(&self, ty: &TSType<'a>) -> Expression<'a> {
+        match serialized_type(ty) {
+            SerializedType::Void => self.ast.void_0(),
+            SerializedType::Global(name) => self.ast.ident(name),
+            // `typeof A === "undefined" ? Object : A`, as `A` can be a type
+            SerializedType::Reference(name) => {
+                let test = self.ast.binary(
+                    self.ast.type_of(self.ast.ident(&TSTypeName::get_first_name(name).name)),
+                    BinaryOperator::StrictEquality,
+                    self.ast.string("undefined"),
+                );
+                self.ast.conditional(test, self.ast.ident("Object"), self.type_name(name))
+            }
+        }
+    }
+
+    fn type_name(&self, name: &TSTypeName<'a>) -> Expression<'a> {
+        match name {
+            TSTypeName::IdentifierName(ident) => self.ast.ident(&ident.name),
+            TSTypeName::QualifiedName(name) => {
+                self.ast.member(self.type_name(&name.left), &name.right.name)
+            }
+        }
+    }
+}
+
+enum SerializedType<'t, 'a> {
+    /// `void 0`
+    Void,
+    /// A global constructor, e.g. `String`
+    Global(&'static str),
+    /// A class or an enum
+    Reference(&'t TSTypeName<'a>),
+}
+
+fn serialized_type<'t, 'a>(ty: &'t TSType<'a>) -> SerializedType<'t, 'a> {
+    match ty {
+        TSType::TSVoidKeyword(_)
+        | TSType::TSUndefinedKeyword(_)
+        | TSType::TSNullKeyword(_)
+        | TSType::TSNeverKeyword(_) => SerializedType::Void,
+        TSType::TSNumberKeyword(_) => SerializedType::Global("Number"),
+        TSType::TSStringKeyword(_) | TSType::TSTemplateLiteralType(_) => {
+            SerializedType::Global("String")
+        }
+        TSType::TSBooleanKeyword(_) | TSType::TSTypePredicate(_) => {
+            SerializedType::Global("Boolean")
+        }
+        TSType::TSBigIntKeyword(_) => SerializedType::Global("BigInt"),
+        TSType::TSSymbolKeyword(_) => SerializedType::Global("Symbol"),
+        TSType::TSArrayType(_) | TSType::TSTupleType(_) => SerializedType::Global("Array"),
+        TSType::TSFunctionType(_) | TSType::TSConstructorType(_) => {
+            SerializedType::Global("Function")
+        }
+        TSType::TSLiteralType(ty) => match &ty.literal {
+            TSLiteral::BooleanLiteral(_) => SerializedType::Global("Boolean"),
+            TSLiteral::NullLiteral(_) => SerializedType::Void,
+            TSLiteral::NumberLiteral(_) | TSLiteral::UnaryExpression(_) => {
+                SerializedType::Global("Number")
+            }
+            TSLiteral::BigintLiteral(_) => SerializedType::Global("BigInt"),
+            TSLiteral::StringLiteral(_) | TSLiteral::TemplateLiteral(_) => {
+                SerializedType::Global("String")
+            }
+            TSLiteral::RegExpLiteral(_) => SerializedType::Global("RegExp"),
+        },
+        TSType::TSTypeOperatorType(ty) => serialized_type(&ty.type_annotation),
+        TSType::TSTypeReference(ty) => SerializedType::Reference(&ty.type_name),
+        // `A | null` is `A`, and members with different types are an `Object`
+        TSType::TSUnionType(union) => {
+            let mut result = SerializedType::Void;
+            for ty in union.types.iter().map(serialized_type) {
+                match (&result, ty) {
+                    (_, SerializedType::Void) => {}
+                    (SerializedType::Void, ty) => result = ty,
+                    (SerializedType::Global(a), SerializedType::Global(b)) if *a == b => {}
+                    _ => return SerializedType::Global("Object"),
+                }
+            }
+            result
+        }
+        _ => SerializedType::Global("Object"),
+    }
+}
+
+fn is_this_parameter(param: &FormalParameter) -> bool {
+    matches!(&param.pattern.kind, BindingPatternKind::BindingIdentifier(ident) if ident.name == "this")
+}
@@ -0,0 +1,316 @@
+//! Decorators
+//!
+//! * Legacy, as TypeScript's `experimentalDecorators`:
+//!   `@a class A { @b m() {} }` ->
+//!   `class A { m() {} } _decorate([b], A.prototype, "m", null); A = _decorate([a], A);`
+//! * The 2023 proposal:
+//!   `@a class A { @b m() {} @c x = 1; }` ->
+//!   `class A { static { [_A, _initProto, _initClass, _init_x] = _applyDecs(this,
+//!   [[[b], "method", "m", false, false], [[c], "field", "x", false, false]], [a]); }
+//!   constructor() { _initProto(this); } m() {} x = _init_x(this, 1); }
+//!   A = _A; _initClass();`
+//!
+//! Private methods and accessors are replaced by getters and setters calling the decorated
+//! functions, `@b #m() {}` -> `get #m() { return _call_m.value; }`.
+
+mod helpers;
+mod legacy;
+mod version_2023;
+
+use std::mem;
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::Error,
+};
+use oxc_span::{Atom, Span, SPAN};
+
+use self::helpers::Helper;
+use crate::{
+    shorthands::Shorthands, symbols::Symbols, temporaries::Temporaries, TransformContext,
+    TransformPass,
+};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Decorated private methods which use `super` can not be lowered")]
+#[diagnostic(help("Use a public method, or a method of the class without decorators"))]
+struct PrivateMethodSuper(#[label] Span);
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DecoratorsOptions {
+    pub version: DecoratorsVersion,
+
+    /// Describe the types of decorated elements with `Reflect.metadata`, as TypeScript's
+    /// `emitDecoratorMetadata`. Only used by legacy decorators.
+    pub emit_decorator_metadata: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DecoratorsVersion {
+    /// TypeScript's `experimentalDecorators`
+    Legacy,
+    /// <https://github.com/tc39/proposal-decorators>
+    #[default]
+    Version2023,
+}
+
+/// The parts of a decorated class which are evaluated around it
+struct LoweredClass<'a> {
+    /// Evaluated before the class, e.g. `_dec = a()`
+    before: std::vec::Vec<Expression<'a>>,
+    /// The variable a class expression is assigned to, which the caller declares
+    binding: Option<Atom>,
+    /// Evaluated after the class, e.g. `A = _decorate([a], A)`
+    after: std::vec::Vec<Expression<'a>>,
+    /// The variable holding the decorated class, which is the value of a class expression
+    value: Option<Atom>,
+}
+
+/// Applies decorators to classes and class elements at runtime, with helpers declared at the
+/// start of the program
+pub struct Decorators<'a> {
+    ast: AstBuilder<'a>,
+    options: DecoratorsOptions,
+    temporaries: Temporaries<'a>,
+    /// The class which was just visited, which is spread into the statement or expression
+    /// containing it
+    lowered: Option<LoweredClass<'a>>,
+    /// The helpers used, in the order of their first use
+    helpers: std::vec::Vec<(Helper, Atom)>,
+    /// Resolves the references to decorated classes in their bodies
+    symbols: Symbols,
+    /// The decorators which can not be applied
+    errors: std::vec::Vec<oxc_diagnostics::Error>,
+    /// Whether a class was decorated, which adds bindings
+    decorated: bool,
+}
+
+impl<'a> Decorators<'a> {
+    pub fn new(allocator: &'a Allocator, options: DecoratorsOptions) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            options,
+            temporaries: Temporaries::new(allocator),
+            lowered: None,
+            helpers: vec![],
+            symbols: Symbols::default(),
+            errors: vec![],
+            decorated: false,
+        }
+    }
+
+    /// A reference to `helper`, which is declared at the start of the program
+    fn helper(&mut self, helper: Helper) -> Expression<'a> {
+        if let Some((_, name)) = self.helpers.iter().find(|(h, _)| *h == helper) {
+            return self.ast.ident(name);
+        }
+        let name = self.temporaries.create_unscoped(helper.name());
+        self.helpers.push((helper, name.clone()));
+        self.ast.ident(&name)
+    }
+
+    /// The name of `class` after it is defined, naming anonymous class declarations, and the
+    /// variable a class expression is assigned to
+    fn class_binding(&mut self, class: &mut Class<'a>) -> (Atom, Option<Atom>) {
+        match &class.id {
+            Some(id) if class.is_declaration() => (id.name.clone(), None),
+            id => {
+                let base = id.as_ref().map_or("Class", |id| id.name.as_str());
+                let name = self.temporaries.create_unscoped(base);
+                if class.is_declaration() {
+                    class.id = Some(BindingIdentifier { span: SPAN, name: name.clone() });
+                    return (name, None);
+                }
+                (name.clone(), Some(name))
+            }
+        }
+    }
+
+    /// The name of an element as a value, e.g. `"a"` for `a() {}` and `"#a"` for `#a() {}`.
+    ///
+    /// Computed keys are stored in a temporary when they are defined, `[a]() {}` ->
+    /// `[_computedKey = a]() {}`.
+    fn element_name(&mut self, key: &mut PropertyKey<'a>) -> Expression<'a> {
+        match key {
+            PropertyKey::Identifier(ident) => self.ast.string(&ident.name),
+            PropertyKey::PrivateIdentifier(ident) => self.ast.string(&format!("#{}", ident.name)),
+            PropertyKey::Expression(expr) => {
+                if let Some(reference) = self.temporaries.reference(expr) {
+                    return reference;
+                }
+                let expr = mem::replace(expr, self.ast.null_literal_expression());
+                let (value, reference) = self.temporaries.memoize(expr, "computedKey");
+                *key = PropertyKey::Expression(value);
+                reference
+            }
+        }
+    }
+
+    /// The expressions of `decorators`, which are removed
+    fn take_decorators(
+        &self,
+        decorators: &mut Vec<'a, Decorator<'a>>,
+    ) -> std::vec::Vec<Expression<'a>> {
+        mem::replace(decorators, self.ast.new_vec()).into_iter().map(|d| d.expression).collect()
+    }
+
+    /// `(_A = class {}, after, _A)`
+    fn transform_class_expression(
+        &mut self,
+        class: Expression<'a>,
+        lowered: LoweredClass<'a>,
+    ) -> Expression<'a> {
+        let mut expressions = self.ast.new_vec();
+        expressions.extend(lowered.before);
+        match lowered.binding {
+            Some(binding) => {
+                self.temporaries.declare(binding.clone());
+                expressions.push(self.ast.assign(self.ast.ident(&binding), class));
+            }
+            None => expressions.push(class),
+        }
+        expressions.extend(lowered.after);
+        if let Some(value) = lowered.value {
+            expressions.push(self.ast.ident(&value));
+        }
+        let expr = self.ast.sequence_expression(SPAN, expressions);
+        self.ast.parenthesized_expression(SPAN, expr)
+    }
+}
+
+/// Whether `class` or any of its elements or their parameters are decorated
+fn has_decorators(class: &Class) -> bool {
+    !class.decorators.is_empty()
+        || class.body.body.iter().any(|element| match element {
+            ClassElement::MethodDefinition(def) => {
+                !def.decorators.is_empty()
+                    || def.value.params.items.iter().any(|param| !param.decorators.is_empty())
+            }
+            ClassElement::PropertyDefinition(def) => !def.decorators.is_empty(),
+            ClassElement::AccessorProperty(def) => !def.decorators.is_empty(),
+            _ => false,
+        })
+}
+
+impl<'a> TransformPass<'a> for Decorators<'a> {
+    fn name(&self) -> &'static str {
+        "decorators"
+    }
+
+    /// The class decorators of the 2023 proposal replace the class which its body references
+    fn needs_semantic(&self) -> bool {
+        self.options.version == DecoratorsVersion::Version2023
+    }
+
+    fn invalidates_scopes(&self) -> bool {
+        self.decorated
+    }
+
+    fn transform(&mut self, program: &mut Program<'a>, ctx: &TransformContext<'a>) {
+        if let Some(symbols) = ctx.symbols() {
+            self.symbols = Symbols::new(symbols);
+        }
+        self.visit_program(program);
+        for error in self.errors.drain(..) {
+            ctx.error(error);
+        }
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Decorators<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        self.temporaries.enter_scope();
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
+        for directive in program.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut program.body);
+        for (index, (helper, name)) in mem::take(&mut self.helpers).into_iter().enumerate() {
+            program.body.insert(index, helper.declaration(&self.ast, name));
+        }
+        self.temporaries.exit_scope(&mut program.body);
+    }
+
+    fn visit_statements(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        let mut index = 0;
+        while index < stmts.len() {
+            self.visit_statement(&mut stmts[index]);
+            // The statement declared a class
+            if let Some(lowered) = self.lowered.take() {
+                for expr in lowered.before {
+                    stmts.insert(index, self.ast.statement(expr));
+                    index += 1;
+                }
+                for expr in lowered.after {
+                    index += 1;
+                    stmts.insert(index, self.ast.statement(expr));
+                }
+            }
+            index += 1;
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &'b mut FunctionBody<'a>) {
+        self.temporaries.enter_scope();
+        for directive in body.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut body.statements);
+        self.temporaries.exit_scope(&mut body.statements);
+    }
+
+    fn visit_formal_parameters(&mut self, params: &'b mut FormalParameters<'a>) {
+        self.temporaries.enter_scope();
+        for param in params.items.iter_mut() {
+            self.visit_formal_parameter(param);
+        }
+        if let Some(rest) = &mut params.rest {
+            self.visit_rest_element(rest);
+        }
+        self.temporaries.exit_parameters_scope(params);
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.temporaries.expand_arrow_body(expr);
+    }
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        for decorator in class.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        if let Some(id) = &mut class.id {
+            self.visit_binding_identifier(id);
+        }
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        self.visit_class_body(&mut class.body);
+        if has_decorators(class) {
+            self.decorated = true;
+            match self.options.version {
+                DecoratorsVersion::Legacy => self.lower_legacy(class),
+                DecoratorsVersion::Version2023 => self.lower_2023(class),
+            }
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        self.visit_expression_match(expr);
+        if let Expression::ClassExpression(_) = expr {
+            let Some(lowered) = self.lowered.take() else { return };
+            if lowered.before.is_empty() && lowered.after.is_empty() && lowered.value.is_none() {
+                return;
+            }
+            let class = mem::replace(expr, self.ast.null_literal_expression());
+            *expr = self.transform_class_expression(class, lowered);
+        }
+    }
+}
@@ -0,0 +1,527 @@
+//! Decorators of the 2023 proposal, which are applied in a static block at the start of the
+//! class
+
+use std::mem;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, VisitMut};
+use oxc_semantic::SymbolId;
+use oxc_span::{Atom, Span, SPAN};
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator};
+
+use super::{helpers::Helper, Decorators, LoweredClass, PrivateMethodSuper};
+use crate::{constructor, shorthands::Shorthands, symbols::Symbols};
+
+impl<'a> Decorators<'a> {
+    /// Replaces decorated fields with calls of their initializers, lowers decorated accessors
+    /// to a getter and a setter of a private field, replaces decorated private methods with
+    /// getters and setters calling the decorated functions, and applies the decorators with
+    /// `_applyDecs`
+    #[allow(clippy::too_many_lines)]
+    pub(super) fn lower_2023(&mut self, class: &mut Class<'a>) {
+        let mut before = vec![];
+        let mut member_decs = vec![];
+        // The initializers of fields and accessors, and the decorated private methods and
+        // accessors, in the order `_applyDecs` returns them
+        let mut element_values = vec![];
+        let mut has_instance_decorators = false;
+        let elements = mem::replace(&mut class.body.body, self.ast.new_vec());
+        for element in elements {
+            match element {
+                ClassElement::MethodDefinition(mut def) if !def.decorators.is_empty() => {
+                    let kind = match def.kind {
+                        MethodDefinitionKind::Get => "getter",
+                        MethodDefinitionKind::Set => "setter",
+                        _ => "method",
+                    };
+                    let PropertyKey::PrivateIdentifier(ident) = &def.key else {
+                        let decorators = self.decorator_list(&mut def.decorators, &mut before);
+                        let name = self.element_name(&mut def.key);
+                        member_decs.push(self.member_dec(
+                            decorators,
+                            kind,
+                            name,
+                            def.r#static,
+                            vec![],
+                        ));
+                        has_instance_decorators |= !def.r#static;
+                        class.body.body.push(ClassElement::MethodDefinition(def));
+                        continue;
+                    };
+                    // The method is moved out of the class
+                    let private = ident.name.clone();
+                    if let Some(span) = super_reference(&mut def.value) {
+                        self.take_decorators(&mut def.decorators);
+                        self.errors.push(PrivateMethodSuper(span).into());
+                        class.body.body.push(ClassElement::MethodDefinition(def));
+                        continue;
+                    }
+                    let decorators = self.decorator_list(&mut def.decorators, &mut before);
+                    let call = self.temporaries.create(&format!("call_{private}"));
+                    let name = self.element_name(&mut def.key);
+                    let def = def.unbox();
+                    let descriptor_key = match def.kind {
+                        MethodDefinitionKind::Get => "get",
+                        MethodDefinitionKind::Set => "set",
+                        _ => "value",
+                    };
+                    let descriptor = self
+                        .ast
+                        .object([(descriptor_key, self.ast.function_expression(def.value))]);
+                    let access = self.private_access(&private, kind);
+                    member_decs.push(self.member_dec(
+                        decorators,
+                        kind,
+                        name,
+                        def.r#static,
+                        vec![access, descriptor],
+                    ));
+                    element_values.push(call.clone());
+                    has_instance_decorators |= !def.r#static;
+                    class.body.body.push(self.private_method(
+                        def.kind,
+                        &private,
+                        def.r#static,
+                        &call,
+                    ));
+                }
+                ClassElement::PropertyDefinition(mut def) if !def.decorators.is_empty() => {
+                    let decorators = self.decorator_list(&mut def.decorators, &mut before);
+                    let access = match &def.key {
+                        PropertyKey::PrivateIdentifier(ident) => {
+                            vec![self.private_access(&ident.name, "field")]
+                        }
+                        _ => vec![],
+                    };
+                    let initializer = self.temporaries.create(&initializer_base(&def.key));
+                    let name = self.element_name(&mut def.key);
+                    def.value = Some(self.initialize(&initializer, def.value.take()));
+                    element_values.push(initializer);
+                    member_decs.push(self.member_dec(
+                        decorators,
+                        "field",
+                        name,
+                        def.r#static,
+                        access,
+                    ));
+                    has_instance_decorators |= !def.r#static;
+                    class.body.body.push(ClassElement::PropertyDefinition(def));
+                }
+                ClassElement::AccessorProperty(mut def) if !def.decorators.is_empty() => {
+                    let decorators = self.decorator_list(&mut def.decorators, &mut before);
+                    let initializer = self.temporaries.create(&initializer_base(&def.key));
+                    let private = match &def.key {
+                        PropertyKey::PrivateIdentifier(ident) => Some(ident.name.clone()),
+                        _ => None,
+                    };
+                    let base = private.clone().or_else(|| def.key.static_name());
+                    let storage = self
+                        .temporaries
+                        .create_unscoped(base.as_ref().map_or("accessor", Atom::as_str));
+                    let name = self.element_name(&mut def.key);
+                    let mut def = def.unbox();
+                    let value = self.initialize(&initializer, def.value.take());
+                    element_values.push(initializer);
+                    has_instance_decorators |= !def.r#static;
+                    if let Some(private) = private {
+                        let call = self.temporaries.create(&format!("call_{private}"));
+                        let access = self.private_access(&private, "accessor");
+                        let descriptor = self.storage_accessors(&storage);
+                        member_decs.push(self.member_dec(
+                            decorators,
+                            "accessor",
+                            name,
+                            def.r#static,
+                            vec![access, descriptor],
+                        ));
+                        element_values.push(call.clone());
+                        class.body.body.extend([
+                            self.storage_field(&storage, def.r#static, value),
+                            self.private_method(
+                                MethodDefinitionKind::Get,
+                                &private,
+                                def.r#static,
+                                &call,
+                            ),
+                            self.private_method(
+                                MethodDefinitionKind::Set,
+                                &private,
+                                def.r#static,
+                                &call,
+                            ),
+                        ]);
+                        continue;
+                    }
+                    let setter_key = match &def.key {
+                        PropertyKey::Identifier(ident) => {
+                            PropertyKey::Identifier(self.ast.alloc((**ident).clone()))
+                        }
+                        _ => PropertyKey::Expression(
+                            self.temporaries.reference(&name).expect("names can be copied"),
+                        ),
+                    };
+                    member_decs.push(self.member_dec(
+                        decorators,
+                        "accessor",
+                        name,
+                        def.r#static,
+                        vec![],
+                    ));
+                    let elements = self.lower_accessor(def, &storage, value, setter_key);
+                    class.body.body.extend(elements);
+                }
+                element => class.body.body.push(element),
+            }
+        }
+
+        let class_decorators = self.decorator_list(&mut class.decorators, &mut before);
+        let new_class = (!class_decorators.is_empty()).then(|| {
+            let base = class.id.as_ref().map_or("Class", |id| id.name.as_str()).to_string();
+            self.temporaries.create(&base)
+        });
+        let init_class = new_class.is_some().then(|| self.temporaries.create("initClass"));
+        let init_proto = has_instance_decorators.then(|| self.temporaries.create("initProto"));
+
+        // The class binding in the body is the class which the decorators return, also in the
+        // private methods moved to the descriptors after the access objects
+        if let (Some(new_class), Some(id)) = (&new_class, &class.id) {
+            if let Some(symbol) = self.symbols.binding(id.span) {
+                let mut references =
+                    ClassReferences { symbols: &self.symbols, symbol, name: new_class.clone() };
+                references.visit_class_body(&mut class.body);
+                for member_dec in &mut member_decs {
+                    if let Expression::ArrayExpression(array) = member_dec {
+                        for element in array.elements.iter_mut().skip(6) {
+                            references.visit_array_expression_element(element);
+                        }
+                    }
+                }
+            }
+        }
+
+        // static { [_A, _initProto, _initClass, _init_a] = _applyDecs(this, [...], [...]); }
+        let apply_decs = self.helper(Helper::ApplyDecs);
+        let apply_decs = self.ast.call(
+            apply_decs,
+            [
+                self.ast.this_expression(SPAN),
+                self.ast.array(member_decs),
+                self.ast.array(class_decorators),
+            ],
+        );
+        let mut targets = [new_class.clone(), init_proto.clone(), init_class.clone()]
+            .into_iter()
+            .chain(element_values.into_iter().map(Some))
+            .collect::<std::vec::Vec<_>>();
+        while targets.last().is_some_and(Option::is_none) {
+            targets.pop();
+        }
+        let apply_decs =
+            if targets.is_empty() { apply_decs } else { self.destructure(targets, apply_decs) };
+        let body = self.ast.new_vec_single(self.ast.statement(apply_decs));
+        class.body.body.insert(0, self.ast.static_block(SPAN, body));
+
+        // Initializers added by the decorators of methods run before the fields are defined
+        if let Some(init_proto) = init_proto {
+            let init = self.ast.call(self.ast.ident(&init_proto), [self.ast.this_expression(SPAN)]);
+            let field = class.body.body.iter_mut().find_map(|element| match element {
+                ClassElement::PropertyDefinition(def) if !def.r#static => Some(def),
+                _ => None,
+            });
+            if let Some(field) = field {
+                let value = field.value.take().unwrap_or_else(|| self.ast.void_0());
+                let mut expressions = self.ast.new_vec_with_capacity(2);
+                expressions.push(init);
+                expressions.push(value);
+                let sequence = self.ast.sequence_expression(SPAN, expressions);
+                field.value = Some(self.ast.parenthesized_expression(SPAN, sequence));
+            } else {
+                constructor::add_initializers(&self.ast, &mut self.temporaries, class, vec![init]);
+            }
+        }
+
+        let mut after = vec![];
+        let mut value = None;
+        if let (Some(new_class), Some(init_class)) = (new_class, init_class) {
+            if class.is_declaration() {
+                let (name, _) = self.class_binding(class);
+                after.push(self.ast.assign(self.ast.ident(&name), self.ast.ident(&new_class)));
+            } else {
+                value = Some(new_class);
+            }
+            after.push(self.ast.call(self.ast.ident(&init_class), []));
+        }
+        self.lowered = Some(LoweredClass { before, binding: None, after, value });
+    }
+
+    /// The expressions of `decorators`, which are evaluated before the class unless they are
+    /// identifiers or static member expressions
+    fn decorator_list(
+        &mut self,
+        decorators: &mut oxc_allocator::Vec<'a, Decorator<'a>>,
+        before: &mut std::vec::Vec<Expression<'a>>,
+    ) -> std::vec::Vec<Expression<'a>> {
+        let decorators = self.take_decorators(decorators);
+        decorators
+            .into_iter()
+            .map(|expr| {
+                if is_static_reference(&expr) {
+                    return expr;
+                }
+                let name = self.temporaries.create("dec");
+                before.push(self.ast.assign(self.ast.ident(&name), expr));
+                self.ast.ident(&name)
+            })
+            .collect()
+    }
+
+    /// `[decorators, kind, name, isStatic, isPrivate, access, descriptor]`, where `private` is
+    /// `[access]` for private fields and `[access, descriptor]` for the other private elements,
+    /// whose functions are decorated instead of the properties of the class
+    fn member_dec(
+        &self,
+        decorators: std::vec::Vec<Expression<'a>>,
+        kind: &str,
+        name: Expression<'a>,
+        is_static: bool,
+        private: std::vec::Vec<Expression<'a>>,
+    ) -> Expression<'a> {
+        let mut elements = vec![
+            self.ast.array(decorators),
+            self.ast.string(kind),
+            name,
+            self.ast.boolean_literal_expression(is_static),
+            self.ast.boolean_literal_expression(!private.is_empty()),
+        ];
+        elements.extend(private);
+        self.ast.array(elements)
+    }
+
+    /// `{ has: (object) => #a in object, get: (object) => object.#a,
+    /// set: (object, value) => { object.#a = value; } }`, without `get` for setters and without
+    /// `set` for methods and getters
+    fn private_access(&self, name: &Atom, kind: &str) -> Expression<'a> {
+        let field = || PrivateIdentifier { span: SPAN, name: name.clone() };
+        let member =
+            || self.ast.private_field_expression(SPAN, self.ast.ident("object"), field(), false);
+        let has = Expression::PrivateInExpression(self.ast.alloc(PrivateInExpression {
+            span: SPAN,
+            left: field(),
+            operator: BinaryOperator::In,
+            right: self.ast.ident("object"),
+        }));
+        let mut access = vec![("has", self.ast.arrow_returning(&["object"], has))];
+        if kind != "setter" {
+            access.push(("get", self.ast.arrow_returning(&["object"], member())));
+        }
+        if !matches!(kind, "method" | "getter") {
+            let set = self.ast.statement(self.ast.assign(member(), self.ast.ident("value")));
+            access.push(("set", self.ast.arrow(&["object", "value"], self.ast.statements([set]))));
+        }
+        self.ast.object(access)
+    }
+
+    /// `_init_a(this, value)`
+    fn initialize(&self, initializer: &Atom, value: Option<Expression<'a>>) -> Expression<'a> {
+        let mut arguments = vec![self.ast.this_expression(SPAN)];
+        arguments.extend(value);
+        self.ast.call(self.ast.ident(initializer), arguments)
+    }
+
+    /// `[_A, , _initClass] = value`
+    fn destructure(
+        &self,
+        targets: std::vec::Vec<Option<Atom>>,
+        value: Expression<'a>,
+    ) -> Expression<'a> {
+        let mut elements = self.ast.new_vec_with_capacity(targets.len());
+        elements.extend(targets.into_iter().map(|target| {
+            target.map(|name| {
+                let ident = self.ast.alloc(IdentifierReference { span: SPAN, name });
+                AssignmentTargetMaybeDefault::AssignmentTarget(
+                    AssignmentTarget::SimpleAssignmentTarget(
+                        SimpleAssignmentTarget::AssignmentTargetIdentifier(ident),
+                    ),
+                )
+            })
+        }));
+        let pattern =
+            ArrayAssignmentTarget { span: SPAN, elements, rest: None, trailing_comma: None };
+        let target = AssignmentTarget::AssignmentTargetPattern(
+            AssignmentTargetPattern::ArrayAssignmentTarget(self.ast.alloc(pattern)),
+        );
+        self.ast.assignment_expression(SPAN, AssignmentOperator::Assign, target, value)
+    }
+
+    /// `accessor a = value` ->
+    /// `#a = value; get a() { return this.#a; } set a(value) { this.#a = value; }`
+    fn lower_accessor(
+        &self,
+        def: AccessorProperty<'a>,
+        storage: &Atom,
+        value: Expression<'a>,
+        setter_key: PropertyKey<'a>,
+    ) -> [ClassElement<'a>; 3] {
+        let (computed, is_static) = (def.computed, def.r#static);
+        let property = self.storage_field(storage, is_static, value);
+        let r#type = FunctionType::FunctionExpression;
+        let get = self.ast.statements([self.get_storage(storage)]);
+        let get = self.ast.function_of(r#type, None, &[], get);
+        let get = self.ast.method_definition(
+            MethodDefinitionKind::Get,
+            def.key,
+            computed,
+            is_static,
+            get,
+        );
+        let set = self.ast.statements([self.set_storage(storage)]);
+        let set = self.ast.function_of(r#type, None, &["value"], set);
+        let set = self.ast.method_definition(
+            MethodDefinitionKind::Set,
+            setter_key,
+            computed,
+            is_static,
+            set,
+        );
+        [property, get, set]
+    }
+
+    /// `#a = value`, which stores the value of an accessor
+    fn storage_field(
+        &self,
+        storage: &Atom,
+        is_static: bool,
+        value: Expression<'a>,
+    ) -> ClassElement<'a> {
+        let key = PrivateIdentifier { span: SPAN, name: storage.clone() };
+        let key = PropertyKey::PrivateIdentifier(self.ast.alloc(key));
+        self.ast.property_definition(key, false, is_static, Some(value))
+    }
+
+    /// `{ get: function () { return this.#a; }, set: function (value) { this.#a = value; } }`,
+    /// the functions which the decorators of a private accessor decorate
+    fn storage_accessors(&self, storage: &Atom) -> Expression<'a> {
+        let r#type = FunctionType::FunctionExpression;
+        let get = self.ast.statements([self.get_storage(storage)]);
+        let get = self.ast.function_expression(self.ast.function_of(r#type, None, &[], get));
+        let set = self.ast.statements([self.set_storage(storage)]);
+        let set = self.ast.function_expression(self.ast.function_of(r#type, None, &["value"], set));
+        self.ast.object([("get", get), ("set", set)])
+    }
+
+    /// `return this.#a;`
+    fn get_storage(&self, storage: &Atom) -> Statement<'a> {
+        self.ast.return_statement(SPAN, Some(self.this_storage(storage)))
+    }
+
+    /// `this.#a = value;`
+    fn set_storage(&self, storage: &Atom) -> Statement<'a> {
+        self.ast.statement(self.ast.assign(self.this_storage(storage), self.ast.ident("value")))
+    }
+
+    fn this_storage(&self, storage: &Atom) -> Expression<'a> {
+        let field = PrivateIdentifier { span: SPAN, name: storage.clone() };
+        self.ast.private_field_expression(SPAN, self.ast.this_expression(SPAN), field, false)
+    }
+
+    /// A private method, getter or setter which calls the function its decorators returned,
+    /// `get #a() { return _call_a.value; }` for methods, `get #a() { return
+    /// _call_a.get.call(this); }` for getters and `set #a(value) { _call_a.set.call(this,
+    /// value); }` for setters
+    fn private_method(
+        &self,
+        kind: MethodDefinitionKind,
+        name: &Atom,
+        is_static: bool,
+        call: &Atom,
+    ) -> ClassElement<'a> {
+        let this = || self.ast.this_expression(SPAN);
+        let decorated = |key| self.ast.member(self.ast.ident(call), key);
+        let (kind, params, body) = match kind {
+            MethodDefinitionKind::Get => {
+                let get = self.ast.call(self.ast.member(decorated("get"), "call"), [this()]);
+                (MethodDefinitionKind::Get, &[][..], self.ast.return_statement(SPAN, Some(get)))
+            }
+            MethodDefinitionKind::Set => {
+                let set = self.ast.member(decorated("set"), "call");
+                let set = self.ast.call(set, [this(), self.ast.ident("value")]);
+                (MethodDefinitionKind::Set, &["value"][..], self.ast.statement(set))
+            }
+            _ => {
+                let value = decorated("value");
+                (MethodDefinitionKind::Get, &[][..], self.ast.return_statement(SPAN, Some(value)))
+            }
+        };
+        let r#type = FunctionType::FunctionExpression;
+        let value = self.ast.function_of(r#type, None, params, self.ast.statements([body]));
+        let key = PrivateIdentifier { span: SPAN, name: name.clone() };
+        let key = PropertyKey::PrivateIdentifier(self.ast.alloc(key));
+        self.ast.method_definition(kind, key, false, is_static, value)
+    }
+}
+
+/// `init_a` for `a`, `#a` and `accessor a`
+fn initializer_base(key: &PropertyKey) -> String {
+    match key {
+        PropertyKey::PrivateIdentifier(ident) => format!("init_{}", ident.name),
+        key => key
+            .static_name()
+            .map_or_else(|| "init_computedKey".into(), |name| format!("init_{name}")),
+    }
+}
+
+/// `a` or `a.b.c`, which can be evaluated in the class
+fn is_static_reference(expr: &Expression) -> bool {
+    match expr {
+        Expression::Identifier(_) => true,
+        Expression::MemberExpression(member) => match &**member {
+            MemberExpression::StaticMemberExpression(member) => is_static_reference(&member.object),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The first `super` in `func`, which can not be used once the function is moved out of the
+/// class
+fn super_reference(func: &mut Function) -> Option<Span> {
+    let mut reference = SuperReference(None);
+    reference.visit_formal_parameters(&mut func.params);
+    if let Some(body) = &mut func.body {
+        reference.visit_function_body(body);
+    }
+    reference.0
+}
+
+struct SuperReference(Option<Span>);
+
+impl<'a, 'b> VisitMut<'a, 'b> for SuperReference {
+    fn visit_super(&mut self, expr: &'b mut Super) {
+        self.0.get_or_insert(expr.span);
+    }
+
+    // Functions and the bodies of classes have their own `super`
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+    }
+}
+
+/// Replaces the references to a decorated class in its body with the class which its
+/// decorators returned
+struct ClassReferences<'s> {
+    symbols: &'s Symbols,
+    symbol: SymbolId,
+    name: Atom,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for ClassReferences<'_> {
+    fn visit_identifier_reference(&mut self, ident: &'b mut IdentifierReference) {
+        if self.symbols.reference(ident.span) == Some(self.symbol) {
+            ident.name = self.name.clone();
+        }
+    }
+}
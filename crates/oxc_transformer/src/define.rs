@@ -0,0 +1,457 @@
+//! Compile-time constants, as esbuild's `--define`
+//!
+//! * `process.env.NODE_ENV` -> `"production"`
+//! * `if (__DEV__) { check(); } else { f(); }` -> `f();`, with `__DEV__` defined as `false`
+//!
+//! Global identifiers and the member expressions of them are replaced with JSON values or other
+//! identifiers. The branches which the replaced values make dead are removed afterwards, from
+//! `if` statements, conditional expressions and logical expressions whose tests are constant.
+
+use std::{fmt, mem, str::FromStr};
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::{
+    identifier::{is_identifier_part, is_identifier_start_all},
+    operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+};
+
+use crate::{
+    pipeline::{TransformContext, TransformPass},
+    shorthands::Shorthands,
+    symbols::Symbols,
+};
+
+/// Options of [`Define`]
+#[derive(Debug, Default, Clone)]
+pub struct DefineOptions {
+    /// Global identifiers or member expressions of them, e.g. `process.env.NODE_ENV` or
+    /// `import.meta.env.DEV`, with the values which replace them
+    pub replacements: Vec<(String, DefineValue)>,
+}
+
+/// A value which replaces a defined expression, which is parsed from JSON or an identifier as
+/// with esbuild, e.g. `"\"production\""`, `false` or `globalThis.window`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefineValue {
+    Json(serde_json::Value),
+    /// An identifier or a member expression of it, e.g. `globalThis.window`
+    Identifier(String),
+}
+
+/// A value which is neither JSON nor an identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDefineValue(pub String);
+
+impl fmt::Display for InvalidDefineValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid define value `{}`, which must be JSON or an identifier", self.0)
+    }
+}
+
+impl std::error::Error for InvalidDefineValue {}
+
+impl FromStr for DefineValue {
+    type Err = InvalidDefineValue;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(value) = serde_json::from_str(s) {
+            return Ok(Self::Json(value));
+        }
+        if is_identifier_path(s) {
+            return Ok(Self::Identifier(s.to_string()));
+        }
+        Err(InvalidDefineValue(s.to_string()))
+    }
+}
+
+/// Whether `s` is an identifier or a member expression of it, e.g. `a.b.c`
+fn is_identifier_path(s: &str) -> bool {
+    s.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(is_identifier_start_all) && chars.all(is_identifier_part)
+    })
+}
+
+/// Replaces defined expressions with their values and removes the dead branches
+pub struct Define<'a> {
+    ast: AstBuilder<'a>,
+    /// The parts of the defined expressions, e.g. `["process", "env", "NODE_ENV"]`, with their
+    /// values
+    replacements: Vec<(Vec<String>, DefineValue)>,
+    /// The references which resolve to bindings are not global
+    symbols: Symbols,
+}
+
+impl<'a> Define<'a> {
+    pub fn new(allocator: &'a Allocator, options: DefineOptions) -> Self {
+        let replacements = options
+            .replacements
+            .into_iter()
+            .map(|(key, value)| (key.split('.').map(str::to_string).collect(), value))
+            .collect();
+        Self { ast: AstBuilder::new(allocator), replacements, symbols: Symbols::default() }
+    }
+
+    fn replacement(&self, expr: &Expression<'a>) -> Option<Expression<'a>> {
+        let (_, value) =
+            self.replacements.iter().find(|(parts, _)| self.is_defined(expr, parts))?;
+        Some(match value {
+            DefineValue::Json(value) => self.json(value),
+            DefineValue::Identifier(path) => {
+                let mut parts = path.split('.');
+                let root = self.ast.ident(parts.next().unwrap_or_default());
+                parts.fold(root, |object, name| self.ast.member(object, name))
+            }
+        })
+    }
+
+    /// Whether `expr` is the expression with `parts`, e.g. `process.env.NODE_ENV` or
+    /// `process.env["NODE_ENV"]`
+    fn is_defined(&self, expr: &Expression<'a>, parts: &[String]) -> bool {
+        let Some((last, object)) = parts.split_last() else { return false };
+        match expr {
+            Expression::Identifier(ident) => {
+                object.is_empty() && ident.name == last.as_str() && is_global(ident, &self.symbols)
+            }
+            Expression::MetaProperty(meta) => {
+                object.len() == 1
+                    && meta.meta.name == object[0].as_str()
+                    && meta.property.name == last.as_str()
+            }
+            Expression::MemberExpression(member) => {
+                let name = match &**member {
+                    MemberExpression::StaticMemberExpression(member) => &member.property.name,
+                    MemberExpression::ComputedMemberExpression(member) => {
+                        let Expression::StringLiteral(lit) = &member.expression else {
+                            return false;
+                        };
+                        &lit.value
+                    }
+                    MemberExpression::PrivateFieldExpression(_) => return false,
+                };
+                !member.optional()
+                    && *name == last.as_str()
+                    && self.is_defined(member.object(), object)
+            }
+            _ => false,
+        }
+    }
+
+    fn json(&self, value: &serde_json::Value) -> Expression<'a> {
+        match value {
+            serde_json::Value::Null => self.ast.null_literal_expression(),
+            serde_json::Value::Bool(value) => self.ast.boolean_literal_expression(*value),
+            serde_json::Value::Number(number) => {
+                let value = number.as_f64().unwrap_or(f64::NAN);
+                if value.is_sign_negative() {
+                    let negation = self.ast.unary_expression(
+                        SPAN,
+                        UnaryOperator::UnaryNegation,
+                        self.ast.number(-value),
+                    );
+                    self.ast.parenthesized_expression(SPAN, negation)
+                } else {
+                    self.ast.number(value)
+                }
+            }
+            serde_json::Value::String(value) => self.ast.string(value),
+            serde_json::Value::Array(elements) => {
+                self.ast.array(elements.iter().map(|element| self.json(element)))
+            }
+            serde_json::Value::Object(properties) => {
+                let mut object = self.ast.new_vec_with_capacity(properties.len());
+                object.extend(properties.iter().map(|(name, value)| {
+                    let key = if is_identifier_path(name) && !name.contains('.') {
+                        let key = IdentifierName { span: SPAN, name: Atom::from(name.as_str()) };
+                        PropertyKey::Identifier(self.ast.alloc(key))
+                    } else {
+                        PropertyKey::Expression(self.ast.string(name))
+                    };
+                    ObjectPropertyKind::ObjectProperty(self.ast.object_property(
+                        SPAN,
+                        PropertyKind::Init,
+                        key,
+                        self.json(value),
+                        None,
+                        false,
+                        false,
+                        false,
+                    ))
+                }));
+                // `({ a: 1 }).b`, as a statement can not start with an object
+                let object = self.ast.object_expression(SPAN, object, None);
+                self.ast.parenthesized_expression(SPAN, object)
+            }
+        }
+    }
+
+    /// `true ? a : b` -> `a`, `false && a` -> `false`, `null ?? a` -> `a`
+    fn remove_dead_branch(&self, expr: &mut Expression<'a>) {
+        let replacement = match expr {
+            Expression::ConditionalExpression(conditional) => {
+                let Some(test) = evaluate(&conditional.test, &self.symbols) else { return };
+                if test.is_truthy() {
+                    mem::replace(&mut conditional.consequent, self.ast.void_0())
+                } else {
+                    mem::replace(&mut conditional.alternate, self.ast.void_0())
+                }
+            }
+            Expression::LogicalExpression(logical) => {
+                let Some(left) = evaluate(&logical.left, &self.symbols) else { return };
+                let short_circuits = match logical.operator {
+                    LogicalOperator::And => !left.is_truthy(),
+                    LogicalOperator::Or => left.is_truthy(),
+                    LogicalOperator::Coalesce => !left.is_nullish(),
+                };
+                if short_circuits {
+                    left.expression(&self.ast)
+                } else {
+                    mem::replace(&mut logical.right, self.ast.void_0())
+                }
+            }
+            _ => return,
+        };
+        *expr = replacement;
+    }
+
+    /// `if (true) { a; } else { b; }` -> `a;`, or nothing for `if (false) a;`
+    ///
+    /// The `var`s declared in the removed branch are kept, as they are hoisted.
+    fn remove_dead_if(&self, stmt: &mut Statement<'a>) -> Option<Vec<Statement<'a>>> {
+        let Statement::IfStatement(if_stmt) = stmt else { return None };
+        let test = evaluate(&if_stmt.test, &self.symbols)?;
+        let consequent = mem::replace(&mut if_stmt.consequent, self.ast.empty_statement(SPAN));
+        let alternate = if_stmt.alternate.take();
+        let (taken, removed) = if test.is_truthy() {
+            (Some(consequent), alternate)
+        } else {
+            (alternate, Some(consequent))
+        };
+        let mut hoisted = VarNames { names: vec![] };
+        if let Some(mut removed) = removed {
+            hoisted.visit_statement(&mut removed);
+        }
+        let mut stmts = vec![];
+        if !hoisted.names.is_empty() {
+            stmts.push(self.ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                hoisted.names.iter().map(|name| (name.as_str(), None)),
+            ));
+        }
+        match taken {
+            // Blocks are kept for their lexical declarations
+            Some(Statement::BlockStatement(block)) if !block.body.iter().any(is_lexical) => {
+                stmts.extend(block.unbox().body);
+            }
+            Some(taken) => stmts.push(taken),
+            None => {}
+        }
+        Some(stmts)
+    }
+}
+
+impl<'a> TransformPass<'a> for Define<'a> {
+    fn name(&self) -> &'static str {
+        "define"
+    }
+
+    /// Only the references which do not resolve to a binding in their scope are replaced
+    fn needs_semantic(&self) -> bool {
+        true
+    }
+
+    fn transform(&mut self, program: &mut Program<'a>, ctx: &TransformContext<'a>) {
+        if let Some(symbols) = ctx.symbols() {
+            self.symbols = Symbols::new(symbols);
+        }
+        self.visit_program(program);
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Define<'a> {
+    fn visit_statements(&mut self, stmts: &'b mut oxc_allocator::Vec<'a, Statement<'a>>) {
+        let mut visited = self.ast.new_vec_with_capacity(stmts.len());
+        for mut stmt in stmts.drain(..) {
+            self.visit_statement_match(&mut stmt);
+            match self.remove_dead_if(&mut stmt) {
+                Some(replacement) => visited.extend(replacement),
+                None => visited.push(stmt),
+            }
+        }
+        *stmts = visited;
+    }
+
+    fn visit_statement(&mut self, stmt: &'b mut Statement<'a>) {
+        self.visit_statement_match(stmt);
+        if let Some(mut replacement) = self.remove_dead_if(stmt) {
+            *stmt = match replacement.len() {
+                0 => self.ast.empty_statement(SPAN),
+                1 => replacement.remove(0),
+                _ => self.ast.block_of(self.ast.statements(replacement)),
+            };
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        if let Some(replacement) = self.replacement(expr) {
+            *expr = replacement;
+            return;
+        }
+        self.visit_expression_match(expr);
+        self.remove_dead_branch(expr);
+    }
+}
+
+/// A value of an expression which is known at compile time
+enum Constant {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+}
+
+impl Constant {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Self::Undefined | Self::Null => false,
+            Self::Boolean(value) => *value,
+            Self::Number(value) => *value != 0.0 && !value.is_nan(),
+            Self::String(value) => !value.is_empty(),
+        }
+    }
+
+    fn is_nullish(&self) -> bool {
+        matches!(self, Self::Undefined | Self::Null)
+    }
+
+    fn type_of(&self) -> &'static str {
+        match self {
+            Self::Undefined => "undefined",
+            Self::Null => "object",
+            Self::Boolean(_) => "boolean",
+            Self::Number(_) => "number",
+            Self::String(_) => "string",
+        }
+    }
+
+    /// `===`, or `None` for `==` between values of different types other than `null` and
+    /// `undefined`
+    #[allow(clippy::float_cmp)]
+    fn equals(&self, other: &Self, strict: bool) -> Option<bool> {
+        Some(match (self, other) {
+            (Self::Undefined | Self::Null, Self::Undefined | Self::Null) => {
+                !strict || self.type_of() == other.type_of()
+            }
+            (Self::Boolean(a), Self::Boolean(b)) => a == b,
+            (Self::Number(a), Self::Number(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a == b,
+            _ if strict || self.is_nullish() || other.is_nullish() => false,
+            _ => return None,
+        })
+    }
+
+    fn expression<'a>(&self, ast: &AstBuilder<'a>) -> Expression<'a> {
+        match self {
+            Self::Undefined => ast.void_0(),
+            Self::Null => ast.null_literal_expression(),
+            Self::Boolean(value) => ast.boolean_literal_expression(*value),
+            Self::Number(value) if value.is_sign_negative() => {
+                let negation =
+                    ast.unary_expression(SPAN, UnaryOperator::UnaryNegation, ast.number(-value));
+                ast.parenthesized_expression(SPAN, negation)
+            }
+            Self::Number(value) => ast.number(*value),
+            Self::String(value) => ast.string(value),
+        }
+    }
+}
+
+/// Whether the reference does not resolve to a binding, which is the case for all the references
+/// without the symbols of the program
+fn is_global(ident: &IdentifierReference, symbols: &Symbols) -> bool {
+    symbols.reference(ident.span).is_none()
+}
+
+/// The value of `expr` if it is a literal or an operation on literals without side effects
+fn evaluate(expr: &Expression, symbols: &Symbols) -> Option<Constant> {
+    match expr {
+        Expression::BooleanLiteral(lit) => Some(Constant::Boolean(lit.value)),
+        Expression::NullLiteral(_) => Some(Constant::Null),
+        Expression::NumberLiteral(lit) => Some(Constant::Number(lit.value)),
+        Expression::StringLiteral(lit) => Some(Constant::String(lit.value.to_string())),
+        Expression::Identifier(ident) if ident.name == "undefined" => {
+            is_global(ident, symbols).then_some(Constant::Undefined)
+        }
+        Expression::ParenthesizedExpression(expr) => evaluate(&expr.expression, symbols),
+        Expression::UnaryExpression(unary) => {
+            let argument = evaluate(&unary.argument, symbols)?;
+            match unary.operator {
+                UnaryOperator::LogicalNot => Some(Constant::Boolean(!argument.is_truthy())),
+                UnaryOperator::Void => Some(Constant::Undefined),
+                UnaryOperator::Typeof => Some(Constant::String(argument.type_of().to_string())),
+                _ => None,
+            }
+        }
+        Expression::BinaryExpression(binary) => {
+            let left = evaluate(&binary.left, symbols)?;
+            let right = evaluate(&binary.right, symbols)?;
+            let equals = match binary.operator {
+                BinaryOperator::StrictEquality => left.equals(&right, true)?,
+                BinaryOperator::StrictInequality => !left.equals(&right, true)?,
+                BinaryOperator::Equality => left.equals(&right, false)?,
+                BinaryOperator::Inequality => !left.equals(&right, false)?,
+                _ => return None,
+            };
+            Some(Constant::Boolean(equals))
+        }
+        Expression::LogicalExpression(logical) => {
+            let left = evaluate(&logical.left, symbols)?;
+            let short_circuits = match logical.operator {
+                LogicalOperator::And => !left.is_truthy(),
+                LogicalOperator::Or => left.is_truthy(),
+                LogicalOperator::Coalesce => !left.is_nullish(),
+            };
+            if short_circuits {
+                Some(left)
+            } else {
+                evaluate(&logical.right, symbols)
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_lexical(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+            decl.kind != VariableDeclarationKind::Var
+        }
+        Statement::Declaration(_) => true,
+        _ => false,
+    }
+}
+
+/// Collects the names declared with `var`, which are hoisted out of blocks
+struct VarNames {
+    names: Vec<Atom>,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for VarNames {
+    fn visit_variable_declaration(&mut self, decl: &'b mut VariableDeclaration<'a>) {
+        if decl.kind == VariableDeclarationKind::Var {
+            for declarator in &decl.declarations {
+                declarator.id.bound_names(&mut |ident| self.names.push(ident.name.clone()));
+            }
+        }
+    }
+
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &'b mut ArrowExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &'b mut Class<'a>) {}
+}
@@ -0,0 +1,1122 @@
+//! The state machine of a generator, after regenerator's `emit.js`
+//!
+//! The statements of the generator are exploded into a listing, which is split into the cases
+//! of a `switch` at the marked locations. Statements which do not contain `yield`, `return`,
+//! `break` or `continue` are copied to the listing as they are.
+
+use std::{collections::HashSet, mem};
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::{
+    operator::{AssignmentOperator, BinaryOperator, LogicalOperator},
+    NumberBase,
+};
+
+use super::hoist;
+use crate::shorthands::Shorthands;
+
+/// The raw text of the number literals of locations, which are replaced once the listing is
+/// complete
+const LOC: &str = "<loc>";
+
+/// A location in the listing
+#[derive(Debug, Clone, Copy)]
+struct Loc(usize);
+
+/// The statements `break` and `continue` can leave
+enum Leap {
+    Loop { label: Option<Atom>, break_loc: Loc, continue_loc: Loc },
+    Switch { break_loc: Loc },
+    Labeled { label: Atom, break_loc: Loc },
+}
+
+/// The locations of a `try` statement, its handlers and the code after it
+struct TryEntry {
+    start: Loc,
+    catch: Option<Loc>,
+    finally: Option<Loc>,
+    after: Loc,
+}
+
+/// The state machine of a generator
+pub(super) struct StateMachine<'a> {
+    /// `while (1) switch (_context.prev = _context.next) { ... }`
+    pub body: Statement<'a>,
+    /// `[[tryLoc, catchLoc, finallyLoc, afterLoc], ...]`
+    pub try_locs: Option<Expression<'a>>,
+    /// The names of the declarations the machine assigns to, which are declared with `var`
+    pub hoisted: std::vec::Vec<Atom>,
+}
+
+pub(super) struct Emitter<'a> {
+    ast: AstBuilder<'a>,
+    /// The name of the parameter with the context of the generator, e.g. `_context`
+    context: Atom,
+    listing: std::vec::Vec<Statement<'a>>,
+    /// The indices of the listing at which cases start
+    marked: HashSet<usize>,
+    /// The index of each location in the listing, once it is marked
+    locs: std::vec::Vec<Option<usize>>,
+    /// The number of temporaries, which are properties of the context
+    temps: usize,
+    try_entries: std::vec::Vec<TryEntry>,
+    leaps: std::vec::Vec<Leap>,
+    hoisted: std::vec::Vec<Atom>,
+}
+
+impl<'a> Emitter<'a> {
+    pub fn new(allocator: &'a Allocator, context: Atom) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            context,
+            listing: vec![],
+            marked: HashSet::from([0]),
+            locs: vec![],
+            temps: 0,
+            try_entries: vec![],
+            leaps: vec![],
+            hoisted: vec![],
+        }
+    }
+
+    /// Splits the listing into cases
+    pub fn finish(mut self) -> StateMachine<'a> {
+        let mut resolver = ResolveLocs { locs: &self.locs };
+        for stmt in &mut self.listing {
+            resolver.visit_statement(stmt);
+        }
+        let end = self.listing.len();
+        let mut cases = self.ast.new_vec();
+        let mut case: Option<(usize, Vec<'a, Statement<'a>>)> = None;
+        let mut completed = false;
+        for (index, stmt) in mem::take(&mut self.listing).into_iter().enumerate() {
+            if self.marked.contains(&index) {
+                cases.extend(case.take().map(|(loc, body)| self.case(loc, body)));
+                case = Some((index, self.ast.new_vec()));
+                completed = false;
+            }
+            // Statements after a completion can only be reached through a later case
+            if let (Some((_, body)), false) = (&mut case, completed) {
+                completed = matches!(
+                    stmt,
+                    Statement::ReturnStatement(_)
+                        | Statement::ThrowStatement(_)
+                        | Statement::BreakStatement(_)
+                        | Statement::ContinueStatement(_)
+                );
+                body.push(stmt);
+            }
+        }
+        cases.extend(case.take().map(|(loc, body)| self.case(loc, body)));
+        cases.push(self.case(end, self.ast.new_vec()));
+        let stop = self.ast.call(self.context("stop"), []);
+        cases.push(self.ast.switch_case(
+            SPAN,
+            Some(self.ast.string("end")),
+            self.ast.new_vec_single(self.ast.return_statement(SPAN, Some(stop))),
+        ));
+
+        // `_context.prev = _context.next`
+        let discriminant = self.ast.assign(self.context("prev"), self.context("next"));
+        let switch = self.ast.switch_statement(SPAN, discriminant, cases);
+        let body = self.ast.while_statement(SPAN, self.ast.number(1.0), switch);
+
+        let try_locs = (!self.try_entries.is_empty()).then(|| {
+            let entries = self.try_entries.iter().map(|entry| self.try_locs(entry));
+            self.ast.array(entries)
+        });
+        StateMachine { body, try_locs, hoisted: self.hoisted }
+    }
+
+    fn case(&self, loc: usize, body: Vec<'a, Statement<'a>>) -> SwitchCase<'a> {
+        self.ast.switch_case(SPAN, Some(self.index(loc)), body)
+    }
+
+    /// `[tryLoc, catchLoc, finallyLoc, afterLoc]`, where the locations after the last of the
+    /// try statement are left out
+    fn try_locs(&self, entry: &TryEntry) -> Expression<'a> {
+        let resolve = |loc: Loc| ArrayExpressionElement::Expression(self.index(self.resolve(loc)));
+        let mut elements = self.ast.new_vec_single(resolve(entry.start));
+        match (entry.catch, entry.finally) {
+            (Some(catch_loc), None) => elements.push(resolve(catch_loc)),
+            (catch_loc, Some(finally_loc)) => {
+                elements.push(catch_loc.map_or(ArrayExpressionElement::Elision(SPAN), resolve));
+                elements.push(resolve(finally_loc));
+                elements.push(resolve(entry.after));
+            }
+            (None, None) => {}
+        }
+        self.ast.array_expression(SPAN, elements, None)
+    }
+
+    fn resolve(&self, loc: Loc) -> usize {
+        self.locs[loc.0].expect("locations are marked before the listing is complete")
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn index(&self, index: usize) -> Expression<'a> {
+        self.ast.number(index as f64)
+    }
+
+    /* ---------- Locations ---------- */
+
+    fn loc(&mut self) -> Loc {
+        self.locs.push(None);
+        Loc(self.locs.len() - 1)
+    }
+
+    /// Starts a new case at `loc`
+    fn mark(&mut self, loc: Loc) {
+        let index = self.listing.len();
+        self.locs[loc.0] = Some(index);
+        self.marked.insert(index);
+    }
+
+    /// The location of the next statement, which does not start a new case
+    fn current_loc(&mut self) -> Loc {
+        self.locs.push(Some(self.listing.len()));
+        Loc(self.locs.len() - 1)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn loc_expr(&self, loc: Loc) -> Expression<'a> {
+        let literal = NumberLiteral::new(SPAN, loc.0 as f64, LOC, NumberBase::Decimal);
+        self.ast.literal_number_expression(literal)
+    }
+
+    /* ---------- Emitting ---------- */
+
+    /// `_context.name`
+    fn context(&self, name: &str) -> Expression<'a> {
+        self.ast.member(self.ast.ident(&self.context), name)
+    }
+
+    fn make_temp(&mut self) -> String {
+        let name = format!("t{}", self.temps);
+        self.temps += 1;
+        name
+    }
+
+    /// Whether `expr` is a temporary, which is assigned only where its value is used
+    fn is_temp(&self, expr: &Expression<'a>) -> bool {
+        let Expression::MemberExpression(member) = expr else { return false };
+        let MemberExpression::StaticMemberExpression(member) = &**member else { return false };
+        matches!(&member.object, Expression::Identifier(ident) if ident.name == self.context)
+            && member.property.name.starts_with('t')
+            && member.property.name[1..].bytes().all(|b| b.is_ascii_digit())
+    }
+
+    fn emit(&mut self, stmt: Statement<'a>) {
+        self.listing.push(stmt);
+    }
+
+    fn emit_expression(&mut self, expr: Expression<'a>) {
+        self.emit(hoist::statement(&self.ast, expr));
+    }
+
+    fn emit_assign(&mut self, target: Expression<'a>, value: Expression<'a>) {
+        self.emit_expression(self.ast.assign(target, value));
+    }
+
+    /// `_context.next = loc; break;`
+    fn jump(&mut self, loc: Loc) {
+        self.jump_to(self.loc_expr(loc));
+    }
+
+    fn jump_to(&mut self, loc: Expression<'a>) {
+        self.emit_assign(self.context("next"), loc);
+        self.emit(self.ast.break_statement(SPAN, None));
+    }
+
+    fn jump_if(&mut self, test: Expression<'a>, loc: Loc) {
+        let next = self.ast.assign(self.context("next"), self.loc_expr(loc));
+        let jump =
+            self.ast.statements([self.ast.statement(next), self.ast.break_statement(SPAN, None)]);
+        self.emit(self.ast.if_else(test, self.ast.block_of(jump), None));
+    }
+
+    fn jump_if_not(&mut self, test: Expression<'a>, loc: Loc) {
+        let test = match test {
+            Expression::Identifier(_)
+            | Expression::MemberExpression(_)
+            | Expression::CallExpression(_)
+            | Expression::ParenthesizedExpression(_) => self.ast.not(test),
+            test => self.ast.not(self.ast.parenthesized_expression(SPAN, test)),
+        };
+        self.jump_if(test, loc);
+    }
+
+    /// `return _context.abrupt(type, argument)`
+    fn abrupt(&mut self, r#type: &str, argument: Option<Expression<'a>>) {
+        let mut arguments = vec![self.ast.string(r#type)];
+        arguments.extend(argument);
+        let abrupt = self.ast.call(self.context("abrupt"), arguments);
+        self.emit(self.ast.return_statement(SPAN, Some(abrupt)));
+    }
+
+    /// `_context.prev = loc`, the location exceptions are dispatched from
+    fn update_context_prev_loc(&mut self, loc: Loc) {
+        self.emit_assign(self.context("prev"), self.loc_expr(loc));
+    }
+
+    fn hoist(&mut self, name: Atom) {
+        self.hoisted.push(name);
+    }
+
+    /* ---------- Statements ---------- */
+
+    /// Explodes a list of statements, in which function declarations are hoisted
+    pub fn explode_statements<I: IntoIterator<Item = Statement<'a>>>(&mut self, stmts: I) {
+        let (functions, stmts): (std::vec::Vec<_>, std::vec::Vec<_>) =
+            stmts.into_iter().partition(|stmt| {
+                matches!(stmt, Statement::Declaration(Declaration::FunctionDeclaration(_)))
+            });
+        for stmt in functions.into_iter().chain(stmts) {
+            self.explode_statement(stmt, None);
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn explode_statement(&mut self, mut stmt: Statement<'a>, label: Option<Atom>) {
+        match stmt {
+            // Declarations of the listing are hoisted, as the cases are run by different calls
+            Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+                self.explode_declaration(decl.unbox());
+                return;
+            }
+            Statement::Declaration(Declaration::FunctionDeclaration(mut func)) => {
+                // `function a() {}` -> `a = function a() {}`
+                let Some(id) = &func.id else { return };
+                let name = id.name.clone();
+                func.r#type = FunctionType::FunctionExpression;
+                self.hoist(name.clone());
+                self.emit_assign(self.ast.ident(&name), Expression::FunctionExpression(func));
+                return;
+            }
+            Statement::Declaration(Declaration::ClassDeclaration(mut class)) => {
+                // `class A {}` -> `A = class A {}`
+                let Some(id) = &class.id else { return };
+                let name = id.name.clone();
+                class.r#type = ClassType::ClassExpression;
+                self.hoist(name.clone());
+                self.emit_assign(self.ast.ident(&name), self.ast.class_expression(class));
+                return;
+            }
+            Statement::EmptyStatement(_) => return,
+            _ => {}
+        }
+        if !contains_leap(&mut stmt) {
+            self.emit(stmt);
+            return;
+        }
+        match stmt {
+            Statement::BlockStatement(block) => self.explode_statements(block.unbox().body),
+            Statement::ExpressionStatement(stmt) => {
+                self.explode_expression(stmt.unbox().expression, true);
+            }
+            Statement::LabeledStatement(stmt) => {
+                let stmt = stmt.unbox();
+                let after = self.loc();
+                let label = stmt.label.name;
+                self.leaps.push(Leap::Labeled { label: label.clone(), break_loc: after });
+                self.explode_statement(stmt.body, Some(label));
+                self.leaps.pop();
+                self.mark(after);
+            }
+            Statement::WhileStatement(stmt) => {
+                let stmt = stmt.unbox();
+                let before = self.loc();
+                let after = self.loc();
+                self.mark(before);
+                let test = self.explode(stmt.test);
+                self.jump_if_not(test, after);
+                self.explode_loop_body(stmt.body, label, after, before);
+                self.jump(before);
+                self.mark(after);
+            }
+            Statement::DoWhileStatement(stmt) => {
+                let stmt = stmt.unbox();
+                let first = self.loc();
+                let test_loc = self.loc();
+                let after = self.loc();
+                self.mark(first);
+                self.explode_loop_body(stmt.body, label, after, test_loc);
+                self.mark(test_loc);
+                let test = self.explode(stmt.test);
+                self.jump_if(test, first);
+                self.mark(after);
+            }
+            Statement::ForStatement(stmt) => {
+                let stmt = stmt.unbox();
+                let head = self.loc();
+                let update_loc = self.loc();
+                let after = self.loc();
+                match stmt.init {
+                    Some(ForStatementInit::VariableDeclaration(decl)) => {
+                        self.explode_declaration(decl.unbox());
+                    }
+                    Some(ForStatementInit::Expression(init)) => {
+                        self.explode_expression(init, true);
+                    }
+                    None => {}
+                }
+                self.mark(head);
+                if let Some(test) = stmt.test {
+                    let test = self.explode(test);
+                    self.jump_if_not(test, after);
+                }
+                self.explode_loop_body(stmt.body, label, after, update_loc);
+                self.mark(update_loc);
+                if let Some(update) = stmt.update {
+                    self.explode_expression(update, true);
+                }
+                self.jump(head);
+                self.mark(after);
+            }
+            Statement::ForInStatement(stmt) => {
+                // `for (a in b) c` ->
+                // `_context.t0 = _context.keys(b); while (!(_context.t1 = _context.t0()).done) {
+                // a = _context.t1.value; c }`
+                let stmt = stmt.unbox();
+                let head = self.loc();
+                let after = self.loc();
+                let right = self.explode(stmt.right);
+                let keys = self.make_temp();
+                self.emit_assign(self.context(&keys), self.ast.call(self.context("keys"), [right]));
+                self.mark(head);
+                let step = self.make_temp();
+                let next = self.ast.call(self.context(&keys), []);
+                self.explode_for_each(stmt.left, stmt.body, label, &step, next, head, after);
+            }
+            Statement::ForOfStatement(stmt) => {
+                // `for (a of b) c` ->
+                // `_context.t0 = _context.values(b); while (!(_context.t1 = _context.t0.next()).done) {
+                // a = _context.t1.value; c }`
+                let stmt = stmt.unbox();
+                let head = self.loc();
+                let after = self.loc();
+                let right = self.explode(stmt.right);
+                let iterator = self.make_temp();
+                let values = self.ast.call(self.context("values"), [right]);
+                self.emit_assign(self.context(&iterator), values);
+                self.mark(head);
+                let step = self.make_temp();
+                let next = self.ast.call(self.ast.member(self.context(&iterator), "next"), []);
+                self.explode_for_each(stmt.left, stmt.body, label, &step, next, head, after);
+            }
+            Statement::BreakStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| &label.name);
+                let loc = self.break_loc(label);
+                self.abrupt("break", Some(self.loc_expr(loc)));
+            }
+            Statement::ContinueStatement(stmt) => {
+                let label = stmt.label.as_ref().map(|label| &label.name);
+                let loc = self.continue_loc(label);
+                self.abrupt("continue", Some(self.loc_expr(loc)));
+            }
+            Statement::IfStatement(stmt) => {
+                let stmt = stmt.unbox();
+                let else_loc = stmt.alternate.is_some().then(|| self.loc());
+                let after = self.loc();
+                let test = self.explode(stmt.test);
+                self.jump_if_not(test, else_loc.unwrap_or(after));
+                self.explode_statement(stmt.consequent, None);
+                if let (Some(else_loc), Some(alternate)) = (else_loc, stmt.alternate) {
+                    self.jump(after);
+                    self.mark(else_loc);
+                    self.explode_statement(alternate, None);
+                }
+                self.mark(after);
+            }
+            Statement::ReturnStatement(stmt) => {
+                let argument = stmt.unbox().argument.map(|argument| self.explode(argument));
+                self.abrupt("return", argument);
+            }
+            Statement::ThrowStatement(stmt) => {
+                let argument = self.explode(stmt.unbox().argument);
+                self.emit(self.ast.throw_statement(SPAN, argument));
+            }
+            Statement::SwitchStatement(stmt) => self.explode_switch(stmt.unbox()),
+            Statement::TryStatement(stmt) => self.explode_try(stmt.unbox()),
+            // Rejected before, e.g. `with`
+            stmt => self.emit(stmt),
+        }
+    }
+
+    fn explode_loop_body(
+        &mut self,
+        body: Statement<'a>,
+        label: Option<Atom>,
+        break_loc: Loc,
+        continue_loc: Loc,
+    ) {
+        self.leaps.push(Leap::Loop { label, break_loc, continue_loc });
+        self.explode_statement(body, None);
+        self.leaps.pop();
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn explode_for_each(
+        &mut self,
+        left: ForStatementLeft<'a>,
+        body: Statement<'a>,
+        label: Option<Atom>,
+        step: &str,
+        next: Expression<'a>,
+        head: Loc,
+        after: Loc,
+    ) {
+        // `(_context.t1 = next).done`
+        let step_expr = self.ast.assign(self.context(step), next);
+        let done = self.ast.member(self.ast.parenthesized_expression(SPAN, step_expr), "done");
+        self.jump_if(done, after);
+        let value = self.ast.member(self.context(step), "value");
+        let assignment = match left {
+            ForStatementLeft::VariableDeclaration(mut decl) => {
+                let declarator = decl.declarations.pop().expect("for-in and for-of declare once");
+                hoist::bound_names(&declarator.id, &mut self.hoisted);
+                hoist::assignment(&self.ast, declarator.id, value)
+            }
+            ForStatementLeft::AssignmentTarget(target) => {
+                self.ast.assignment_expression(SPAN, AssignmentOperator::Assign, target, value)
+            }
+        };
+        self.emit_expression(assignment);
+        self.explode_loop_body(body, label, after, head);
+        self.jump(head);
+        self.mark(after);
+    }
+
+    /// `let a = 1, b` -> `a = 1; b = void 0`, where `a` and `b` are hoisted
+    fn explode_declaration(&mut self, decl: VariableDeclaration<'a>) {
+        for declarator in decl.declarations {
+            let mut names = vec![];
+            hoist::bound_names(&declarator.id, &mut names);
+            match declarator.init {
+                Some(init) => {
+                    let assignment = hoist::assignment(&self.ast, declarator.id, init);
+                    self.explode_expression(assignment, true);
+                }
+                // `let a` resets `a`, e.g. in loops, `var a` does not
+                None if decl.kind != VariableDeclarationKind::Var => {
+                    for name in &names {
+                        self.emit_assign(self.ast.ident(name), self.ast.void_0());
+                    }
+                }
+                None => {}
+            }
+            self.hoisted.extend(names);
+        }
+    }
+
+    fn explode_switch(&mut self, stmt: SwitchStatement<'a>) {
+        // `switch (a) { case b: c; default: d }` ->
+        // `_context.t0 = a; _context.next = _context.t0 === b ? 1 : 2; break; case 1: c; case 2: d`
+        let discriminant = self.make_temp();
+        let value = self.explode(stmt.discriminant);
+        self.emit_assign(self.context(&discriminant), value);
+        let after = self.loc();
+        let case_locs = stmt.cases.iter().map(|_| self.loc()).collect::<std::vec::Vec<_>>();
+        let default_loc =
+            stmt.cases.iter().position(|case| case.test.is_none()).map_or(after, |i| case_locs[i]);
+        let mut cases = std::vec::Vec::with_capacity(stmt.cases.len());
+        let mut condition = self.loc_expr(default_loc);
+        for (case, loc) in stmt.cases.into_iter().zip(&case_locs).rev() {
+            if let Some(test) = case.test {
+                let test = self.ast.binary(
+                    self.context(&discriminant),
+                    BinaryOperator::StrictEquality,
+                    test,
+                );
+                condition = self.ast.conditional(test, self.loc_expr(*loc), condition);
+            }
+            cases.push(case.consequent);
+        }
+        let condition = self.explode(condition);
+        self.jump_to(condition);
+        self.leaps.push(Leap::Switch { break_loc: after });
+        for (consequent, loc) in cases.into_iter().rev().zip(case_locs) {
+            self.mark(loc);
+            self.explode_statements(consequent);
+        }
+        self.leaps.pop();
+        self.mark(after);
+    }
+
+    fn explode_try(&mut self, stmt: TryStatement<'a>) {
+        let after = self.loc();
+        let catch_loc = stmt.handler.is_some().then(|| self.loc());
+        let finally_loc = stmt.finalizer.is_some().then(|| self.loc());
+        let try_loc = self.current_loc();
+        self.update_context_prev_loc(try_loc);
+        self.try_entries.push(TryEntry {
+            start: try_loc,
+            catch: catch_loc,
+            finally: finally_loc,
+            after,
+        });
+        self.explode_statements(stmt.block.unbox().body);
+        if let (Some(catch_loc), Some(handler)) = (catch_loc, stmt.handler) {
+            self.jump(finally_loc.unwrap_or(after));
+            self.mark(catch_loc);
+            self.update_context_prev_loc(catch_loc);
+            // `e = _context.catch(tryLoc)`, where `e` is hoisted
+            let thrown = self.ast.call(self.context("catch"), [self.loc_expr(try_loc)]);
+            let handler = handler.unbox();
+            match handler.param {
+                Some(param) => {
+                    hoist::bound_names(&param, &mut self.hoisted);
+                    self.emit_expression(hoist::assignment(&self.ast, param, thrown));
+                }
+                None => self.emit_expression(thrown),
+            }
+            self.explode_statements(handler.body.unbox().body);
+        }
+        if let (Some(finally_loc), Some(finalizer)) = (finally_loc, stmt.finalizer) {
+            self.mark(finally_loc);
+            self.update_context_prev_loc(finally_loc);
+            self.explode_statements(finalizer.unbox().body);
+            let finish = self.ast.call(self.context("finish"), [self.loc_expr(finally_loc)]);
+            self.emit(self.ast.return_statement(SPAN, Some(finish)));
+        }
+        self.mark(after);
+    }
+
+    fn break_loc(&self, label: Option<&Atom>) -> Loc {
+        self.leaps
+            .iter()
+            .rev()
+            .find_map(|leap| match (leap, label) {
+                (Leap::Loop { break_loc, .. } | Leap::Switch { break_loc }, None) => {
+                    Some(*break_loc)
+                }
+                (
+                    Leap::Loop { label: Some(name), break_loc, .. }
+                    | Leap::Labeled { label: name, break_loc },
+                    Some(label),
+                ) if name == label => Some(*break_loc),
+                _ => None,
+            })
+            .expect("`break` has a target")
+    }
+
+    fn continue_loc(&self, label: Option<&Atom>) -> Loc {
+        self.leaps
+            .iter()
+            .rev()
+            .find_map(|leap| match leap {
+                Leap::Loop { label: name, continue_loc, .. }
+                    if label.is_none() || name.as_ref() == label =>
+                {
+                    Some(*continue_loc)
+                }
+                _ => None,
+            })
+            .expect("`continue` has a target")
+    }
+
+    /* ---------- Expressions ---------- */
+
+    fn explode(&mut self, expr: Expression<'a>) -> Expression<'a> {
+        self.explode_expression(expr, false).expect("the result is used")
+    }
+
+    /// Explodes `expr` and stores its result in a temporary, unless it can not change before it
+    /// is used, e.g. as a literal
+    fn explode_via_temp(&mut self, expr: Expression<'a>) -> Expression<'a> {
+        let result = self.explode(expr);
+        if matches!(
+            result,
+            Expression::BooleanLiteral(_)
+                | Expression::NullLiteral(_)
+                | Expression::NumberLiteral(_)
+                | Expression::BigintLiteral(_)
+                | Expression::StringLiteral(_)
+                | Expression::ThisExpression(_)
+        ) || self.is_temp(&result)
+        {
+            return result;
+        }
+        let temp = self.make_temp();
+        self.emit_assign(self.context(&temp), result);
+        self.context(&temp)
+    }
+
+    /// Explodes the operands of an expression in order, where the results which could change
+    /// before the last `yield` of the other operands are stored in temporaries
+    fn explode_operands(&mut self, mut operands: std::vec::Vec<&mut Expression<'a>>) {
+        let yields =
+            operands.iter_mut().map(|expr| contains_yield(expr)).collect::<std::vec::Vec<_>>();
+        let last_yield = yields.iter().rposition(|yields| *yields);
+        for (index, operand) in operands.into_iter().enumerate() {
+            let expr = mem::replace(operand, self.ast.void_0());
+            *operand = if last_yield.is_some_and(|last| index < last) {
+                self.explode_via_temp(expr)
+            } else {
+                self.explode(expr)
+            };
+        }
+    }
+
+    fn finish_expression(
+        &mut self,
+        expr: Expression<'a>,
+        ignore_result: bool,
+    ) -> Option<Expression<'a>> {
+        if ignore_result {
+            self.emit_expression(expr);
+            return None;
+        }
+        Some(expr)
+    }
+
+    /// Explodes `expr`, returning its result unless `ignore_result`
+    #[allow(clippy::too_many_lines)]
+    fn explode_expression(
+        &mut self,
+        mut expr: Expression<'a>,
+        ignore_result: bool,
+    ) -> Option<Expression<'a>> {
+        if !contains_yield(&mut expr) {
+            return self.finish_expression(expr, ignore_result);
+        }
+        match expr {
+            Expression::ParenthesizedExpression(paren) => {
+                let paren = paren.unbox();
+                let result = self.explode_expression(paren.expression, ignore_result)?;
+                if matches!(result, Expression::Identifier(_) | Expression::MemberExpression(_)) {
+                    return Some(result);
+                }
+                Some(self.ast.parenthesized_expression(paren.span, result))
+            }
+            Expression::MemberExpression(mut member) => {
+                self.explode_member(&mut member, false);
+                self.finish_expression(Expression::MemberExpression(member), ignore_result)
+            }
+            Expression::CallExpression(mut call) => {
+                let arguments_yield = call.arguments.iter_mut().any(|argument| match argument {
+                    Argument::Expression(expr) => contains_yield(expr),
+                    Argument::SpreadElement(spread) => contains_yield(&mut spread.argument),
+                });
+                let member_callee = matches!(call.callee, Expression::MemberExpression(_));
+                if arguments_yield && member_callee {
+                    // `a.b(yield)` -> `_context.t0 = a; _context.t0.b.call(_context.t0, _context.sent)`
+                    let Expression::MemberExpression(member) = &mut call.callee else {
+                        unreachable!()
+                    };
+                    let this = self.explode_member(member, true).expect("the object is spilled");
+                    let callee = mem::replace(&mut call.callee, self.ast.void_0());
+                    call.callee = self.ast.member(callee, "call");
+                    call.arguments.insert(0, Argument::Expression(self.context(&this)));
+                } else if arguments_yield {
+                    let callee = mem::replace(&mut call.callee, self.ast.void_0());
+                    call.callee = self.explode_via_temp(callee);
+                } else {
+                    let callee = mem::replace(&mut call.callee, self.ast.void_0());
+                    call.callee = self.explode(callee);
+                }
+                if !member_callee && matches!(call.callee, Expression::MemberExpression(_)) {
+                    // `(0, _context.sent)()`, which is not called with the context as `this`
+                    let callee = mem::replace(&mut call.callee, self.ast.void_0());
+                    let mut sequence = self.ast.new_vec_single(self.ast.number(0.0));
+                    sequence.push(callee);
+                    let sequence = self.ast.sequence_expression(SPAN, sequence);
+                    call.callee = self.ast.parenthesized_expression(SPAN, sequence);
+                }
+                let operands = call.arguments.iter_mut().map(argument_expression).collect();
+                self.explode_operands(operands);
+                self.finish_expression(Expression::CallExpression(call), ignore_result)
+            }
+            Expression::NewExpression(mut new) => {
+                let new_mut = &mut *new;
+                let mut operands = vec![&mut new_mut.callee];
+                operands.extend(new_mut.arguments.iter_mut().map(argument_expression));
+                self.explode_operands(operands);
+                self.finish_expression(Expression::NewExpression(new), ignore_result)
+            }
+            Expression::ArrayExpression(mut array) => {
+                let operands = array
+                    .elements
+                    .iter_mut()
+                    .filter_map(|element| match element {
+                        ArrayExpressionElement::Expression(expr) => Some(expr),
+                        ArrayExpressionElement::SpreadElement(spread) => Some(&mut spread.argument),
+                        ArrayExpressionElement::Elision(_) => None,
+                    })
+                    .collect();
+                self.explode_operands(operands);
+                self.finish_expression(Expression::ArrayExpression(array), ignore_result)
+            }
+            Expression::ObjectExpression(mut object) => {
+                let mut operands = vec![];
+                for property in object.properties.iter_mut() {
+                    match property {
+                        // Methods and accessors are kept as they are
+                        ObjectPropertyKind::ObjectProperty(property)
+                            if property.kind == PropertyKind::Init && !property.method =>
+                        {
+                            // `{ a }` -> `{ a: _context.t0 }`
+                            let property = &mut **property;
+                            property.shorthand = false;
+                            if let PropertyKey::Expression(key) = &mut property.key {
+                                operands.push(key);
+                            }
+                            operands.push(&mut property.value);
+                        }
+                        ObjectPropertyKind::ObjectProperty(_) => {}
+                        ObjectPropertyKind::SpreadProperty(spread) => {
+                            operands.push(&mut spread.argument);
+                        }
+                    }
+                }
+                self.explode_operands(operands);
+                self.finish_expression(Expression::ObjectExpression(object), ignore_result)
+            }
+            Expression::TemplateLiteral(mut template) => {
+                self.explode_operands(template.expressions.iter_mut().collect());
+                self.finish_expression(Expression::TemplateLiteral(template), ignore_result)
+            }
+            Expression::BinaryExpression(mut binary) => {
+                let binary_mut = &mut *binary;
+                self.explode_operands(vec![&mut binary_mut.left, &mut binary_mut.right]);
+                self.finish_expression(Expression::BinaryExpression(binary), ignore_result)
+            }
+            Expression::UnaryExpression(mut unary) => {
+                let argument = mem::replace(&mut unary.argument, self.ast.void_0());
+                unary.argument = self.explode(argument);
+                self.finish_expression(Expression::UnaryExpression(unary), ignore_result)
+            }
+            Expression::UpdateExpression(mut update) => {
+                if let SimpleAssignmentTarget::MemberAssignmentTarget(member) = &mut update.argument
+                {
+                    self.explode_member(member, false);
+                }
+                self.finish_expression(Expression::UpdateExpression(update), ignore_result)
+            }
+            Expression::SequenceExpression(sequence) => {
+                let mut expressions = sequence.unbox().expressions;
+                let last = expressions.pop()?;
+                for expr in expressions {
+                    self.explode_expression(expr, true);
+                }
+                self.explode_expression(last, ignore_result)
+            }
+            Expression::LogicalExpression(logical) => {
+                // `a && b` -> `_context.t0 = a; if (!_context.t0) { goto after; }
+                // _context.t0 = b; after:`
+                let logical = logical.unbox();
+                let after = self.loc();
+                let result = (!ignore_result).then(|| self.make_temp());
+                let mut left = self.explode(logical.left);
+                if let Some(result) = &result {
+                    self.emit_assign(self.context(result), left);
+                    left = self.context(result);
+                }
+                match logical.operator {
+                    LogicalOperator::And => self.jump_if_not(left, after),
+                    LogicalOperator::Or => self.jump_if(left, after),
+                    LogicalOperator::Coalesce => {
+                        let null = self.ast.null_literal_expression();
+                        let test = self.ast.binary(left, BinaryOperator::Inequality, null);
+                        self.jump_if(test, after);
+                    }
+                }
+                self.explode_into(logical.right, result.as_deref());
+                self.mark(after);
+                result.map(|result| self.context(&result))
+            }
+            Expression::ConditionalExpression(conditional) => {
+                let conditional = conditional.unbox();
+                let else_loc = self.loc();
+                let after = self.loc();
+                let result = (!ignore_result).then(|| self.make_temp());
+                let test = self.explode(conditional.test);
+                self.jump_if_not(test, else_loc);
+                self.explode_into(conditional.consequent, result.as_deref());
+                self.jump(after);
+                self.mark(else_loc);
+                self.explode_into(conditional.alternate, result.as_deref());
+                self.mark(after);
+                result.map(|result| self.context(&result))
+            }
+            Expression::AssignmentExpression(mut assignment) => {
+                let right_yields = contains_yield(&mut assignment.right);
+                if assignment.operator == AssignmentOperator::Assign {
+                    if let AssignmentTarget::SimpleAssignmentTarget(
+                        SimpleAssignmentTarget::MemberAssignmentTarget(member),
+                    ) = &mut assignment.left
+                    {
+                        self.explode_member(member, right_yields);
+                    }
+                    let right = mem::replace(&mut assignment.right, self.ast.void_0());
+                    assignment.right = self.explode(right);
+                    return self.finish_expression(
+                        Expression::AssignmentExpression(assignment),
+                        ignore_result,
+                    );
+                }
+                let AssignmentTarget::SimpleAssignmentTarget(target) = &mut assignment.left else {
+                    return self.finish_expression(
+                        Expression::AssignmentExpression(assignment),
+                        ignore_result,
+                    );
+                };
+                if !right_yields {
+                    if let SimpleAssignmentTarget::MemberAssignmentTarget(member) = target {
+                        self.explode_member(member, false);
+                    }
+                    return self.finish_expression(
+                        Expression::AssignmentExpression(assignment),
+                        ignore_result,
+                    );
+                }
+                // `a += yield` -> `_context.t0 = a; a = _context.t0 += _context.sent`
+                let value = self.make_temp();
+                let current = match target {
+                    SimpleAssignmentTarget::MemberAssignmentTarget(member) => {
+                        self.explode_member(member, true);
+                        Expression::MemberExpression(self.ast.alloc(self.copy_member(member)))
+                    }
+                    SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                        self.ast.ident(&ident.name)
+                    }
+                    // Rejected before
+                    _ => {
+                        return self.finish_expression(
+                            Expression::AssignmentExpression(assignment),
+                            ignore_result,
+                        )
+                    }
+                };
+                self.emit_assign(self.context(&value), current);
+                let right = mem::replace(&mut assignment.right, self.ast.void_0());
+                let right = self.explode(right);
+                let operator = mem::replace(&mut assignment.operator, AssignmentOperator::Assign);
+                let compound = self.ast.assignment_expression(
+                    SPAN,
+                    operator,
+                    AssignmentTarget::SimpleAssignmentTarget(
+                        SimpleAssignmentTarget::MemberAssignmentTarget(
+                            self.ast.alloc(self.temp_member(&value)),
+                        ),
+                    ),
+                    right,
+                );
+                assignment.right = compound;
+                self.finish_expression(Expression::AssignmentExpression(assignment), ignore_result)
+            }
+            Expression::YieldExpression(yield_expr) => {
+                let yield_expr = yield_expr.unbox();
+                let after = self.loc();
+                let argument = yield_expr.argument.map(|argument| self.explode(argument));
+                if yield_expr.delegate {
+                    // `yield* a` -> `return _context.delegateYield(a, "t0", after)`
+                    let result = self.make_temp();
+                    let argument = argument.unwrap_or_else(|| self.ast.void_0());
+                    let delegate = self.ast.call(
+                        self.context("delegateYield"),
+                        [argument, self.ast.string(&result), self.loc_expr(after)],
+                    );
+                    self.emit(self.ast.return_statement(SPAN, Some(delegate)));
+                    self.mark(after);
+                    return (!ignore_result).then(|| self.context(&result));
+                }
+                // `yield a` -> `_context.next = after; return a;`
+                self.emit_assign(self.context("next"), self.loc_expr(after));
+                self.emit(self.ast.return_statement(SPAN, argument));
+                self.mark(after);
+                (!ignore_result).then(|| self.context("sent"))
+            }
+            // Rejected before
+            expr => self.finish_expression(expr, ignore_result),
+        }
+    }
+
+    /// Explodes `expr` into the temporary `result`, or ignores its result
+    fn explode_into(&mut self, expr: Expression<'a>, result: Option<&str>) {
+        match result {
+            Some(result) => {
+                let value = self.explode(expr);
+                self.emit_assign(self.context(result), value);
+            }
+            None => {
+                self.explode_expression(expr, true);
+            }
+        }
+    }
+
+    /// Explodes the object and key of `member`, which are stored in temporaries when `spill`,
+    /// returning the temporary of the object
+    fn explode_member(&mut self, member: &mut MemberExpression<'a>, spill: bool) -> Option<String> {
+        let (object, mut key) = match member {
+            MemberExpression::ComputedMemberExpression(member) => {
+                (&mut member.object, Some(&mut member.expression))
+            }
+            MemberExpression::StaticMemberExpression(member) => (&mut member.object, None),
+            MemberExpression::PrivateFieldExpression(member) => (&mut member.object, None),
+        };
+        let key_yields = key.as_mut().is_some_and(|key| contains_yield(key));
+        let value = mem::replace(object, self.ast.void_0());
+        let value = self.explode(value);
+        let temp = if spill || key_yields {
+            let temp = self.make_temp();
+            self.emit_assign(self.context(&temp), value);
+            *object = self.context(&temp);
+            Some(temp)
+        } else {
+            *object = value;
+            None
+        };
+        if let Some(key) = key {
+            let value = mem::replace(key, self.ast.void_0());
+            let value = self.explode(value);
+            *key = if spill
+                && !matches!(value, Expression::StringLiteral(_) | Expression::NumberLiteral(_))
+            {
+                let temp = self.make_temp();
+                self.emit_assign(self.context(&temp), value);
+                self.context(&temp)
+            } else {
+                value
+            };
+        }
+        temp
+    }
+
+    /// `_context.name`, as a member expression
+    fn temp_member(&self, name: &str) -> MemberExpression<'a> {
+        let property = IdentifierName { span: SPAN, name: Atom::from(name) };
+        let object = self.ast.ident(&self.context);
+        MemberExpression::StaticMemberExpression(StaticMemberExpression {
+            span: SPAN,
+            object,
+            property,
+            optional: false,
+        })
+    }
+
+    /// A copy of `member` after [`Emitter::explode_member`] stored its object and key in
+    /// temporaries
+    fn copy_member(&self, member: &MemberExpression<'a>) -> MemberExpression<'a> {
+        match member {
+            MemberExpression::ComputedMemberExpression(member) => {
+                MemberExpression::ComputedMemberExpression(ComputedMemberExpression {
+                    span: SPAN,
+                    object: self.copy(&member.object),
+                    expression: self.copy(&member.expression),
+                    optional: false,
+                })
+            }
+            MemberExpression::StaticMemberExpression(member) => {
+                MemberExpression::StaticMemberExpression(StaticMemberExpression {
+                    span: SPAN,
+                    object: self.copy(&member.object),
+                    property: member.property.clone(),
+                    optional: false,
+                })
+            }
+            MemberExpression::PrivateFieldExpression(member) => {
+                MemberExpression::PrivateFieldExpression(PrivateFieldExpression {
+                    span: SPAN,
+                    object: self.copy(&member.object),
+                    field: member.field.clone(),
+                    optional: false,
+                })
+            }
+        }
+    }
+
+    /// A copy of a temporary or of a string or number literal
+    fn copy(&self, expr: &Expression<'a>) -> Expression<'a> {
+        match expr {
+            Expression::StringLiteral(literal) => self.ast.string(&literal.value),
+            Expression::NumberLiteral(literal) => self.ast.literal_number_expression(
+                NumberLiteral::new(SPAN, literal.value, literal.raw, literal.base),
+            ),
+            Expression::MemberExpression(member) => match &**member {
+                MemberExpression::StaticMemberExpression(member) => {
+                    self.context(&member.property.name)
+                }
+                _ => unreachable!("only temporaries are copied"),
+            },
+            _ => unreachable!("only temporaries are copied"),
+        }
+    }
+}
+
+/// Finds `yield` and the statements which leave the current statement
+#[derive(Default)]
+pub(super) struct Leaps {
+    yields: bool,
+    jumps: bool,
+}
+
+impl Leaps {
+    /// Whether the code visited by `visit` yields or leaves the current statement, in which case
+    /// the statement is exploded and the bindings declared in it are hoisted
+    pub fn find<F: FnOnce(&mut Self)>(visit: F) -> bool {
+        let mut leaps = Self::default();
+        visit(&mut leaps);
+        leaps.yields || leaps.jumps
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Leaps {
+    fn visit_yield_expression(&mut self, _expr: &'b mut YieldExpression<'a>) {
+        self.yields = true;
+    }
+
+    fn visit_return_statement(&mut self, _stmt: &'b mut ReturnStatement<'a>) {
+        self.jumps = true;
+    }
+
+    fn visit_break_statement(&mut self, _stmt: &'b mut BreakStatement) {
+        self.jumps = true;
+    }
+
+    fn visit_continue_statement(&mut self, _stmt: &'b mut ContinueStatement) {
+        self.jumps = true;
+    }
+
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &'b mut ArrowExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &'b mut Class<'a>) {}
+}
+
+fn contains_yield(expr: &mut Expression) -> bool {
+    let mut leaps = Leaps::default();
+    leaps.visit_expression(expr);
+    leaps.yields
+}
+
+fn contains_leap(stmt: &mut Statement) -> bool {
+    Leaps::find(|leaps| leaps.visit_statement(stmt))
+}
+
+fn argument_expression<'r, 'a>(argument: &'r mut Argument<'a>) -> &'r mut Expression<'a> {
+    match argument {
+        Argument::Expression(expr) => expr,
+        Argument::SpreadElement(spread) => &mut spread.argument,
+    }
+}
+
+/// Replaces the number literals of locations with the indices of the listing
+struct ResolveLocs<'l> {
+    locs: &'l [Option<usize>],
+}
+
+impl<'a, 'b, 'l> VisitMut<'a, 'b> for ResolveLocs<'l> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+    fn visit_number_literal(&mut self, lit: &'b mut NumberLiteral<'a>) {
+        if lit.raw == LOC {
+            let index = self.locs[lit.value as usize].expect("locations are marked");
+            lit.value = index as f64;
+            lit.raw = "";
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Declarations of generators, which are hoisted out of the state machine as `var`s
+
+use std::mem;
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::AssignmentOperator;
+
+/// Replaces the `var` declarations of a generator with assignments, collecting their names
+pub(super) struct Hoist<'a> {
+    ast: AstBuilder<'a>,
+    pub names: std::vec::Vec<Atom>,
+}
+
+impl<'a> Hoist<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self { ast: AstBuilder::new(allocator), names: vec![] }
+    }
+
+    /// `var a = 1, b, c = 2` -> `a = 1, c = 2`, or `None` without initializers
+    fn assignments(&mut self, decl: &mut VariableDeclaration<'a>) -> Option<Expression<'a>> {
+        let mut expressions = self.ast.new_vec();
+        for declarator in mem::replace(&mut decl.declarations, self.ast.new_vec()) {
+            bound_names(&declarator.id, &mut self.names);
+            if let Some(init) = declarator.init {
+                expressions.push(assignment(&self.ast, declarator.id, init));
+            }
+        }
+        match expressions.len() {
+            0 => None,
+            1 => expressions.pop(),
+            _ => Some(self.ast.sequence_expression(SPAN, expressions)),
+        }
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Hoist<'a> {
+    fn visit_statement(&mut self, stmt: &'b mut Statement<'a>) {
+        let Statement::Declaration(Declaration::VariableDeclaration(decl)) = stmt else {
+            self.visit_statement_match(stmt);
+            return;
+        };
+        if decl.kind != VariableDeclarationKind::Var {
+            return;
+        }
+        let span = decl.span;
+        *stmt = match self.assignments(decl) {
+            Some(expr) => statement(&self.ast, expr),
+            None => self.ast.empty_statement(span),
+        };
+    }
+
+    fn visit_for_statement_init(&mut self, init: &'b mut ForStatementInit<'a>) {
+        let ForStatementInit::VariableDeclaration(decl) = init else { return };
+        if decl.kind == VariableDeclarationKind::Var {
+            // `for (var a; ...)` is replaced with `for (a = void 0; ...)`, which is the same
+            let expr = self.assignments(decl).unwrap_or_else(|| self.ast.void_0());
+            *init = ForStatementInit::Expression(expr);
+        }
+    }
+
+    fn visit_for_statement_left(&mut self, left: &'b mut ForStatementLeft<'a>) {
+        let ForStatementLeft::VariableDeclaration(decl) = left else { return };
+        if decl.kind != VariableDeclarationKind::Var {
+            return;
+        }
+        if let Some(declarator) = decl.declarations.pop() {
+            bound_names(&declarator.id, &mut self.names);
+            *left = ForStatementLeft::AssignmentTarget(assignment_target(&self.ast, declarator.id));
+        }
+    }
+
+    // Functions and classes have their own scopes, which `var` does not leave
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &'b mut ArrowExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &'b mut Class<'a>) {}
+}
+
+/// Adds the names declared by `pattern` to `names`
+pub(super) fn bound_names(pattern: &BindingPattern, names: &mut std::vec::Vec<Atom>) {
+    pattern.bound_names(&mut |ident| names.push(ident.name.clone()));
+}
+
+/// `{ a, b: [c = 1] }` -> `({ a, b: [c = 1] } = value)`
+pub(super) fn assignment<'a>(
+    ast: &AstBuilder<'a>,
+    pattern: BindingPattern<'a>,
+    value: Expression<'a>,
+) -> Expression<'a> {
+    let target = assignment_target(ast, pattern);
+    ast.assignment_expression(SPAN, AssignmentOperator::Assign, target, value)
+}
+
+/// An expression statement of `expr`, which is parenthesized when it starts with an object
+/// pattern and would be read as a block otherwise
+pub(super) fn statement<'a>(ast: &AstBuilder<'a>, expr: Expression<'a>) -> Statement<'a> {
+    let expr = if starts_with_object_pattern(&expr) {
+        ast.parenthesized_expression(SPAN, expr)
+    } else {
+        expr
+    };
+    ast.expression_statement(SPAN, expr)
+}
+
+fn starts_with_object_pattern(expr: &Expression) -> bool {
+    match expr {
+        Expression::AssignmentExpression(assignment) => matches!(
+            assignment.left,
+            AssignmentTarget::AssignmentTargetPattern(
+                AssignmentTargetPattern::ObjectAssignmentTarget(_)
+            )
+        ),
+        Expression::SequenceExpression(sequence) => {
+            sequence.expressions.first().is_some_and(starts_with_object_pattern)
+        }
+        _ => false,
+    }
+}
+
+/// The assignment target which assigns to the names bound by `pattern`
+pub(super) fn assignment_target<'a>(
+    ast: &AstBuilder<'a>,
+    pattern: BindingPattern<'a>,
+) -> AssignmentTarget<'a> {
+    match pattern.kind {
+        BindingPatternKind::BindingIdentifier(ident) => {
+            let ident = IdentifierReference { span: ident.span, name: ident.name.clone() };
+            AssignmentTarget::SimpleAssignmentTarget(
+                SimpleAssignmentTarget::AssignmentTargetIdentifier(ast.alloc(ident)),
+            )
+        }
+        BindingPatternKind::ObjectPattern(object) => {
+            let object = object.unbox();
+            let mut properties = ast.new_vec_with_capacity(object.properties.len());
+            properties.extend(
+                object
+                    .properties
+                    .into_iter()
+                    .map(|property| assignment_target_property(ast, property)),
+            );
+            let rest = object.rest.map(|rest| assignment_target(ast, rest.unbox().argument));
+            let target = ObjectAssignmentTarget { span: object.span, properties, rest };
+            AssignmentTarget::AssignmentTargetPattern(
+                AssignmentTargetPattern::ObjectAssignmentTarget(ast.alloc(target)),
+            )
+        }
+        BindingPatternKind::ArrayPattern(array) => {
+            let array = array.unbox();
+            let mut elements = ast.new_vec_with_capacity(array.elements.len());
+            elements.extend(array.elements.into_iter().map(|element| {
+                element.map(|element| assignment_target_maybe_default(ast, element))
+            }));
+            let rest = array.rest.map(|rest| assignment_target(ast, rest.unbox().argument));
+            let target =
+                ArrayAssignmentTarget { span: array.span, elements, rest, trailing_comma: None };
+            AssignmentTarget::AssignmentTargetPattern(
+                AssignmentTargetPattern::ArrayAssignmentTarget(ast.alloc(target)),
+            )
+        }
+        // Only nested patterns have defaults
+        BindingPatternKind::AssignmentPattern(assignment) => {
+            assignment_target(ast, assignment.unbox().left)
+        }
+    }
+}
+
+fn assignment_target_maybe_default<'a>(
+    ast: &AstBuilder<'a>,
+    pattern: BindingPattern<'a>,
+) -> AssignmentTargetMaybeDefault<'a> {
+    match pattern.kind {
+        BindingPatternKind::AssignmentPattern(assignment) => {
+            let assignment = assignment.unbox();
+            let target = AssignmentTargetWithDefault {
+                span: assignment.span,
+                binding: assignment_target(ast, assignment.left),
+                init: assignment.right,
+            };
+            AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(ast.alloc(target))
+        }
+        _ => AssignmentTargetMaybeDefault::AssignmentTarget(assignment_target(ast, pattern)),
+    }
+}
+
+fn assignment_target_property<'a>(
+    ast: &AstBuilder<'a>,
+    property: BindingProperty<'a>,
+) -> AssignmentTargetProperty<'a> {
+    // `{ a }` and `{ a = 1 }`
+    let shorthand = property.shorthand
+        && match &property.value.kind {
+            BindingPatternKind::BindingIdentifier(_) => true,
+            BindingPatternKind::AssignmentPattern(assignment) => {
+                matches!(assignment.left.kind, BindingPatternKind::BindingIdentifier(_))
+            }
+            _ => false,
+        };
+    if !shorthand {
+        let binding = assignment_target_maybe_default(ast, property.value);
+        let target =
+            AssignmentTargetPropertyProperty { span: property.span, name: property.key, binding };
+        return AssignmentTargetProperty::AssignmentTargetPropertyProperty(ast.alloc(target));
+    }
+    let (ident, init) = match property.value.kind {
+        BindingPatternKind::AssignmentPattern(assignment) => {
+            let assignment = assignment.unbox();
+            let BindingPatternKind::BindingIdentifier(ident) = assignment.left.kind else {
+                unreachable!()
+            };
+            (ident, Some(assignment.right))
+        }
+        BindingPatternKind::BindingIdentifier(ident) => (ident, None),
+        _ => unreachable!(),
+    };
+    let binding = IdentifierReference { span: ident.span, name: ident.name.clone() };
+    let target = AssignmentTargetPropertyIdentifier { span: property.span, binding, init };
+    AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ast.alloc(target))
+}
@@ -0,0 +1,380 @@
+//! ES2015: generators
+//!
+//! * `function* f(a) { var b = yield a; return b; }` ->
+//!   `function f(a) { var b; return _regenerator(function (_context) { while (1) switch
+//!   (_context.prev = _context.next) { case 0: _context.next = 2; return a; case 2: b =
+//!   _context.sent; return _context.abrupt("return", b); case 4: case "end": return
+//!   _context.stop(); } }, this); }`
+//!
+//! The body of the generator becomes a state machine, after regenerator, which the
+//! `_regenerator` helper resumes. The declarations of the body are hoisted out of the machine
+//! as `var`s, including `let`, `const` and the parameters of `catch` clauses, which are renamed
+//! if they would shadow another binding.
+//!
+//! Async generators, generators referencing `super`, generators with `yield` in patterns,
+//! optional chains, tagged templates or `with` statements, and generators whose closures capture
+//! the bindings of loop iterations are kept as they are, which is reported as an error.
+
+mod emit;
+mod hoist;
+mod runtime;
+mod scopes;
+
+use std::mem;
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_diagnostics::{
+    miette::{self, Diagnostic},
+    thiserror::{self, Error},
+};
+use oxc_span::{Atom, Span, SPAN};
+
+use self::{emit::Emitter, hoist::Hoist, scopes::BlockScopes};
+use crate::{shorthands::Shorthands, temporaries::Temporaries, TransformContext, TransformPass};
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Async generators can not be lowered to ES5")]
+#[diagnostic()]
+struct AsyncGenerator(#[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Generators with {0} can not be lowered to ES5")]
+#[diagnostic()]
+struct UnsupportedGenerator(&'static str, #[label] Span);
+
+#[derive(Debug, Error, Diagnostic)]
+#[error(
+    "Generators whose closures capture the bindings of loop iterations can not be lowered to ES5"
+)]
+#[diagnostic(help("Declare the binding with `var`, or outside of the loop"))]
+struct CapturedIterationBinding(#[label("The iterations would share this binding")] Span);
+
+/// Runs generators as state machines with the `_regenerator` helper.
+///
+/// Async functions are lowered to generators by [`crate::Es2017`] first.
+pub struct Es2015<'a> {
+    ast: AstBuilder<'a>,
+    temporaries: Temporaries<'a>,
+    /// The names of the parameter with the context of the generators and of the copy of their
+    /// `arguments`, which nested generators shadow
+    names: Option<(Atom, Atom)>,
+    /// The name of `_regenerator` once it is used
+    helper: Option<Atom>,
+    /// The generators which can not be lowered
+    errors: std::vec::Vec<oxc_diagnostics::Error>,
+}
+
+impl<'a> Es2015<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            temporaries: Temporaries::new(allocator),
+            names: None,
+            helper: None,
+            errors: vec![],
+        }
+    }
+
+    fn names(&mut self) -> (Atom, Atom) {
+        if self.names.is_none() {
+            let context = self.temporaries.create_unscoped("context");
+            let arguments = self.temporaries.create_unscoped("arguments");
+            self.names = Some((context, arguments));
+        }
+        self.names.clone().expect("the names are created")
+    }
+
+    fn helper(&mut self) -> Expression<'a> {
+        let temporaries = &mut self.temporaries;
+        let name = self.helper.get_or_insert_with(|| temporaries.create_unscoped("regenerator"));
+        self.ast.ident(name)
+    }
+
+    /// `function* f() { body }` ->
+    /// `function f() { var hoisted; return _regenerator(function (_context) { machine }, this); }`
+    fn lower(&mut self, body: &mut FunctionBody<'a>) {
+        let (context, arguments) = self.names();
+        let statements = mem::replace(&mut body.statements, self.ast.new_vec());
+        // Function declarations are hoisted out of the machine as they are
+        let (functions, mut statements): (Vec<_>, Vec<_>) =
+            statements.into_iter().partition(|stmt| {
+                matches!(stmt, Statement::Declaration(Declaration::FunctionDeclaration(_)))
+            });
+
+        let mut hoist = Hoist::new(self.ast.allocator);
+        let mut replace_arguments = ReplaceArguments { name: arguments.clone(), used: false };
+        for stmt in &mut statements {
+            hoist.visit_statement(stmt);
+            replace_arguments.visit_statement(stmt);
+        }
+        let mut emitter = Emitter::new(self.ast.allocator, context.clone());
+        emitter.explode_statements(statements);
+        let machine = emitter.finish();
+
+        let mut names = hoist.names;
+        names.extend(machine.hoisted);
+        let mut declarations = vec![];
+        for name in &names {
+            if !declarations.iter().any(|(declared, _)| declared == name) {
+                declarations.push((name.clone(), None));
+            }
+        }
+        if replace_arguments.used {
+            declarations.push((arguments, Some(self.ast.ident("arguments"))));
+        }
+        if !declarations.is_empty() {
+            let declarations =
+                declarations.iter_mut().map(|(name, init)| (name.as_str(), init.take()));
+            body.statements.push(
+                self.ast.var_declaration_statement(VariableDeclarationKind::Var, declarations),
+            );
+        }
+        body.statements.extend(functions);
+
+        let inner = self.ast.function_of(
+            FunctionType::FunctionExpression,
+            None,
+            &[context.as_str()],
+            self.ast.statements([machine.body]),
+        );
+        let mut arguments =
+            vec![Expression::FunctionExpression(inner), self.ast.this_expression(SPAN)];
+        arguments.extend(machine.try_locs);
+        let helper = self.helper();
+        let call = self.ast.call(helper, arguments);
+        body.statements.push(self.ast.return_statement(SPAN, Some(call)));
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Es2015<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
+        for directive in program.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut program.body);
+        if let Some(name) = self.helper.take() {
+            program.body.insert(0, runtime::helper_declaration(&self.ast, name));
+        }
+    }
+
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        // Nested generators are lowered first
+        if let Some(ident) = &mut func.id {
+            self.visit_binding_identifier(ident);
+        }
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+        if !func.generator {
+            return;
+        }
+        let Some(body) = &mut func.body else { return };
+        if func.r#async {
+            self.errors.push(AsyncGenerator(func.span).into());
+            return;
+        }
+        if let Some((syntax, span)) = unsupported(body) {
+            self.errors.push(UnsupportedGenerator(syntax, span).into());
+            return;
+        }
+        let scopes = BlockScopes::collect(&mut func.params, body);
+        if let Some(span) = scopes.captured() {
+            self.errors.push(CapturedIterationBinding(span).into());
+            return;
+        }
+        scopes.rename(self.ast.allocator, &mut func.params, body, &mut self.temporaries);
+        func.generator = false;
+        self.lower(body);
+    }
+}
+
+impl<'a> TransformPass<'a> for Es2015<'a> {
+    fn name(&self) -> &'static str {
+        "es2015"
+    }
+
+    fn transform(&mut self, program: &mut Program<'a>, ctx: &TransformContext<'a>) {
+        self.visit_program(program);
+        for error in self.errors.drain(..) {
+            ctx.error(error);
+        }
+    }
+}
+
+/// The first syntax of the generator with `body` which the state machine does not support
+fn unsupported(body: &mut FunctionBody) -> Option<(&'static str, Span)> {
+    let mut analysis = Analysis { unsupported: None, context: None };
+    analysis.visit_function_body(body);
+    analysis.unsupported
+}
+
+/// Finds the syntax of a generator which the state machine does not support
+struct Analysis {
+    unsupported: Option<(&'static str, Span)>,
+    /// The syntax being visited which `yield` can not be lowered in
+    context: Option<&'static str>,
+}
+
+impl Analysis {
+    fn unsupported_yield<F: FnOnce(&mut Self)>(&mut self, context: &'static str, visit: F) {
+        let outer = self.context.replace(context);
+        visit(self);
+        self.context = outer;
+    }
+
+    fn report(&mut self, syntax: &'static str, span: Span) {
+        self.unsupported.get_or_insert((syntax, span));
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Analysis {
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        let context = match expr {
+            Expression::ChainExpression(_) => Some("`yield` in optional chains"),
+            Expression::TaggedTemplateExpression(_) => Some("`yield` in tagged templates"),
+            Expression::PrivateInExpression(_) => Some("`yield` in `#a in b`"),
+            Expression::ImportExpression(_) => Some("`yield` in `import()`"),
+            Expression::AwaitExpression(_) => Some("`yield` in `await`"),
+            Expression::JSXElement(_) | Expression::JSXFragment(_) => Some("`yield` in JSX"),
+            Expression::TSAsExpression(_)
+            | Expression::TSSatisfiesExpression(_)
+            | Expression::TSTypeAssertion(_)
+            | Expression::TSNonNullExpression(_)
+            | Expression::TSInstantiationExpression(_) => Some("`yield` in TypeScript expressions"),
+            // `a ||= yield` only evaluates `yield` when `a` is falsy
+            Expression::AssignmentExpression(assignment)
+                if assignment.operator.is_logical_operator() =>
+            {
+                Some("`yield` in logical assignments")
+            }
+            _ => None,
+        };
+        match context {
+            Some(context) => {
+                self.unsupported_yield(context, |analysis| analysis.visit_expression_match(expr));
+            }
+            None => self.visit_expression_match(expr),
+        }
+    }
+
+    fn visit_yield_expression(&mut self, expr: &'b mut YieldExpression<'a>) {
+        if let Some(context) = self.context {
+            self.report(context, expr.span);
+        }
+        if let Some(argument) = &mut expr.argument {
+            self.visit_expression(argument);
+        }
+    }
+
+    fn visit_super(&mut self, expr: &'b mut Super) {
+        self.report("`super`", expr.span);
+    }
+
+    fn visit_with_statement(&mut self, stmt: &'b mut WithStatement<'a>) {
+        self.report("`with` statements", stmt.span);
+    }
+
+    fn visit_binding_pattern(&mut self, pat: &'b mut BindingPattern<'a>) {
+        self.unsupported_yield("`yield` in patterns", |analysis| match &mut pat.kind {
+            BindingPatternKind::BindingIdentifier(ident) => {
+                analysis.visit_binding_identifier(ident);
+            }
+            BindingPatternKind::ObjectPattern(pat) => analysis.visit_object_pattern(pat),
+            BindingPatternKind::ArrayPattern(pat) => analysis.visit_array_pattern(pat),
+            BindingPatternKind::AssignmentPattern(pat) => analysis.visit_assignment_pattern(pat),
+        });
+    }
+
+    fn visit_assignment_target_pattern(&mut self, pat: &'b mut AssignmentTargetPattern<'a>) {
+        self.unsupported_yield("`yield` in patterns", |analysis| match pat {
+            AssignmentTargetPattern::ArrayAssignmentTarget(target) => {
+                analysis.visit_array_assignment_target(target);
+            }
+            AssignmentTargetPattern::ObjectAssignmentTarget(target) => {
+                analysis.visit_object_assignment_target(target);
+            }
+        });
+    }
+
+    fn visit_for_statement_left(&mut self, left: &'b mut ForStatementLeft<'a>) {
+        self.unsupported_yield("`yield` in the heads of `for-in` and `for-of`", |analysis| {
+            match left {
+                ForStatementLeft::VariableDeclaration(decl) => {
+                    analysis.visit_variable_declaration(decl);
+                }
+                ForStatementLeft::AssignmentTarget(target) => {
+                    analysis.visit_assignment_target(target);
+                }
+            }
+        });
+    }
+
+    fn visit_object_property(&mut self, prop: &'b mut ObjectProperty<'a>) {
+        // The keys of methods and accessors are kept as they are
+        if prop.kind == PropertyKind::Init && !prop.method {
+            self.visit_property_key(&mut prop.key);
+        } else {
+            self.unsupported_yield("`yield` in the keys of methods and accessors", |analysis| {
+                analysis.visit_property_key(&mut prop.key);
+            });
+        }
+        self.visit_expression(&mut prop.value);
+    }
+
+    // Functions have their own `super`, arrow functions do not
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        self.unsupported_yield("`yield` in classes", |analysis| {
+            if let Some(super_class) = &mut class.super_class {
+                analysis.visit_class_heritage(super_class);
+            }
+            for element in class.body.body.iter_mut() {
+                match element {
+                    ClassElement::MethodDefinition(def) => {
+                        analysis.visit_property_key(&mut def.key);
+                    }
+                    ClassElement::PropertyDefinition(def) => {
+                        analysis.visit_property_key(&mut def.key);
+                    }
+                    ClassElement::AccessorProperty(def) => {
+                        analysis.visit_property_key(&mut def.key);
+                    }
+                    _ => {}
+                }
+            }
+        });
+    }
+}
+
+/// Replaces `arguments` with a copy of the `arguments` of the generator, as the machine is
+/// another function
+struct ReplaceArguments {
+    name: Atom,
+    used: bool,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for ReplaceArguments {
+    fn visit_identifier_reference(&mut self, ident: &'b mut IdentifierReference) {
+        if ident.name == "arguments" {
+            ident.name = self.name.clone();
+            self.used = true;
+        }
+    }
+
+    // Functions have their own `arguments`, arrow functions do not
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+    }
+}
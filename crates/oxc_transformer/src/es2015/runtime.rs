@@ -0,0 +1,1039 @@
+//! `_regenerator`, which runs the state machines of generators
+//!
+//! ```javascript
+//! function _regenerator(innerFn, self, tryLocs) {
+//!     var ContinueSentinel = {};
+//!     var state = "suspendedStart";
+//!     var tryEntries = [{ tryLoc: "root" }];
+//!     if (tryLocs) {
+//!         tryLocs.forEach(function (locs) {
+//!             tryEntries.push({ tryLoc: locs[0], catchLoc: locs[1], finallyLoc: locs[2], afterLoc: locs[3] });
+//!         });
+//!     }
+//!     tryEntries.forEach(reset);
+//!     function reset(entry) {
+//!         entry.completion = { type: "normal" };
+//!     }
+//!     function values(iterable) {
+//!         var method = typeof Symbol === "function" && iterable[Symbol.iterator];
+//!         if (method) {
+//!             return method.call(iterable);
+//!         }
+//!         if (typeof iterable.next === "function") {
+//!             return iterable;
+//!         }
+//!         var index = -1;
+//!         return {
+//!             next: function () {
+//!                 index++;
+//!                 return index < iterable.length ? { value: iterable[index], done: false } : { value: void 0, done: true };
+//!             }
+//!         };
+//!     }
+//!     function complete(record, afterLoc) {
+//!         if (record.type === "throw") {
+//!             throw record.arg;
+//!         }
+//!         if (record.type === "break" || record.type === "continue") {
+//!             context.next = record.arg;
+//!         } else if (record.type === "return") {
+//!             context.rval = context.arg = record.arg;
+//!             context.method = "return";
+//!             context.next = "end";
+//!         } else if (afterLoc) {
+//!             context.next = afterLoc;
+//!         }
+//!         return ContinueSentinel;
+//!     }
+//!     function abrupt(type, arg) {
+//!         for (var i = tryEntries.length - 1; i >= 0; i--) {
+//!             var entry = tryEntries[i];
+//!             if (entry.tryLoc <= context.prev && context.prev < entry.finallyLoc) {
+//!                 // Jumps inside the try statement do not run its finally block
+//!                 if ((type === "break" || type === "continue") && entry.tryLoc <= arg && arg <= entry.finallyLoc) {
+//!                     break;
+//!                 }
+//!                 entry.completion = { type: type, arg: arg };
+//!                 context.method = "next";
+//!                 context.next = entry.finallyLoc;
+//!                 return ContinueSentinel;
+//!             }
+//!         }
+//!         return complete({ type: type, arg: arg });
+//!     }
+//!     function dispatchException(exception) {
+//!         if (context.done) {
+//!             throw exception;
+//!         }
+//!         for (var i = tryEntries.length - 1; i >= 0; i--) {
+//!             var entry = tryEntries[i];
+//!             var caught = context.prev < entry.catchLoc;
+//!             if (entry.tryLoc === "root" || entry.tryLoc <= context.prev && (caught || context.prev < entry.finallyLoc)) {
+//!                 entry.completion = { type: "throw", arg: exception };
+//!                 context.next = entry.tryLoc === "root" ? "end" : caught ? entry.catchLoc : entry.finallyLoc;
+//!                 if (caught) {
+//!                     context.method = "next";
+//!                     context.arg = void 0;
+//!                 }
+//!                 return;
+//!             }
+//!         }
+//!     }
+//!     function invokeDelegate(delegate) {
+//!         var iterator = delegate.iterator;
+//!         var method = iterator[context.method];
+//!         if (method === void 0) {
+//!             context.delegate = null;
+//!             if (context.method === "return") {
+//!                 return ContinueSentinel;
+//!             }
+//!             if (context.method === "throw" && iterator["return"]) {
+//!                 iterator["return"]();
+//!             }
+//!             context.arg = new TypeError("The iterator does not provide a '" + context.method + "' method");
+//!             context.method = "throw";
+//!             return ContinueSentinel;
+//!         }
+//!         try {
+//!             var info = method.call(iterator, context.arg);
+//!         } catch (error) {
+//!             context.delegate = null;
+//!             context.method = "throw";
+//!             context.arg = error;
+//!             return ContinueSentinel;
+//!         }
+//!         if (!info.done) {
+//!             return info;
+//!         }
+//!         context.delegate = null;
+//!         context[delegate.resultName] = info.value;
+//!         context.next = delegate.nextLoc;
+//!         if (context.method === "return") {
+//!             context.arg = info.value;
+//!         } else {
+//!             context.method = "next";
+//!             context.arg = void 0;
+//!         }
+//!         return ContinueSentinel;
+//!     }
+//!     function invoke(method, arg) {
+//!         if (state === "executing") {
+//!             throw new TypeError("Generator is already running");
+//!         }
+//!         if (state === "suspendedStart" && method !== "next") {
+//!             state = "completed";
+//!         }
+//!         if (state === "completed") {
+//!             if (method === "throw") {
+//!                 throw arg;
+//!             }
+//!             return { value: method === "return" ? arg : void 0, done: true };
+//!         }
+//!         context.method = method;
+//!         context.arg = arg;
+//!         while (true) {
+//!             if (context.delegate) {
+//!                 var result = invokeDelegate(context.delegate);
+//!                 if (result !== ContinueSentinel) {
+//!                     return result;
+//!                 }
+//!             }
+//!             if (context.method === "next") {
+//!                 context.sent = context.arg;
+//!             } else if (context.method === "throw") {
+//!                 dispatchException(context.arg);
+//!             } else {
+//!                 abrupt("return", context.arg);
+//!             }
+//!             state = "executing";
+//!             try {
+//!                 var value = innerFn.call(self, context);
+//!             } catch (error) {
+//!                 state = "completed";
+//!                 context.method = "throw";
+//!                 context.arg = error;
+//!                 continue;
+//!             }
+//!             state = context.done ? "completed" : "suspendedYield";
+//!             if (value !== ContinueSentinel) {
+//!                 return { value: value, done: context.done };
+//!             }
+//!         }
+//!     }
+//!     var context = {
+//!         prev: 0,
+//!         next: 0,
+//!         sent: void 0,
+//!         rval: void 0,
+//!         done: false,
+//!         delegate: null,
+//!         method: "next",
+//!         arg: void 0,
+//!         abrupt: abrupt,
+//!         stop: function () {
+//!             context.done = true;
+//!             var root = tryEntries[0].completion;
+//!             if (root.type === "throw") {
+//!                 throw root.arg;
+//!             }
+//!             return context.rval;
+//!         },
+//!         finish: function (finallyLoc) {
+//!             for (var i = tryEntries.length - 1; i >= 0; i--) {
+//!                 var entry = tryEntries[i];
+//!                 if (entry.finallyLoc === finallyLoc) {
+//!                     var record = entry.completion;
+//!                     reset(entry);
+//!                     return complete(record, entry.afterLoc);
+//!                 }
+//!             }
+//!         },
+//!         catch: function (tryLoc) {
+//!             for (var i = tryEntries.length - 1; i >= 0; i--) {
+//!                 var entry = tryEntries[i];
+//!                 if (entry.tryLoc === tryLoc) {
+//!                     var thrown = entry.completion.arg;
+//!                     reset(entry);
+//!                     return thrown;
+//!                 }
+//!             }
+//!         },
+//!         delegateYield: function (iterable, resultName, nextLoc) {
+//!             context.delegate = { iterator: values(iterable), resultName: resultName, nextLoc: nextLoc };
+//!             if (context.method === "next") {
+//!                 context.arg = void 0;
+//!             }
+//!             return ContinueSentinel;
+//!         },
+//!         keys: function (object) {
+//!             object = Object(object);
+//!             var keys = [];
+//!             for (var key in object) {
+//!                 keys.push(key);
+//!             }
+//!             keys.reverse();
+//!             return function () {
+//!                 while (keys.length) {
+//!                     var key = keys.pop();
+//!                     if (key in object) {
+//!                         return { value: key, done: false };
+//!                     }
+//!                 }
+//!                 return { value: void 0, done: true };
+//!             };
+//!         },
+//!         values: values
+//!     };
+//!     var generator = {
+//!         next: function (arg) { return invoke("next", arg); },
+//!         throw: function (arg) { return invoke("throw", arg); },
+//!         return: function (arg) { return invoke("return", arg); }
+//!     };
+//!     if (typeof Symbol === "function" && Symbol.iterator) {
+//!         generator[Symbol.iterator] = function () { return this; };
+//!     }
+//!     return generator;
+//! }
+//! ```
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{BinaryOperator, LogicalOperator, UnaryOperator, UpdateOperator};
+
+use crate::shorthands::Shorthands;
+
+#[allow(clippy::too_many_lines)]
+pub(super) fn helper_declaration<'a>(ast: &AstBuilder<'a>, name: Atom) -> Statement<'a> {
+    let body = ast.statements([
+        var(ast, "ContinueSentinel", ast.object([])),
+        var(ast, "state", ast.string("suspendedStart")),
+        var(ast, "tryEntries", ast.array([ast.object([("tryLoc", ast.string("root"))])])),
+        if_(
+            ast,
+            ast.ident("tryLocs"),
+            [ast.statement(call_method(
+                ast,
+                ast.ident("tryLocs"),
+                "forEach",
+                [function(
+                    ast,
+                    &["locs"],
+                    [ast.statement(call_method(
+                        ast,
+                        ast.ident("tryEntries"),
+                        "push",
+                        [ast.object([
+                            ("tryLoc", ast.index(ast.ident("locs"), 0)),
+                            ("catchLoc", ast.index(ast.ident("locs"), 1)),
+                            ("finallyLoc", ast.index(ast.ident("locs"), 2)),
+                            ("afterLoc", ast.index(ast.ident("locs"), 3)),
+                        ])],
+                    ))],
+                )],
+            ))],
+        ),
+        ast.statement(call_method(ast, ast.ident("tryEntries"), "forEach", [ast.ident("reset")])),
+        declaration(
+            ast,
+            "reset",
+            &["entry"],
+            [ast.statement(ast.assign(
+                ast.member(ast.ident("entry"), "completion"),
+                ast.object([("type", ast.string("normal"))]),
+            ))],
+        ),
+        values(ast),
+        complete(ast),
+        abrupt(ast),
+        dispatch_exception(ast),
+        invoke_delegate(ast),
+        invoke(ast),
+        var(ast, CONTEXT, context_object(ast)),
+        var(
+            ast,
+            "generator",
+            ast.object(["next", "throw", "return"].map(|method| {
+                let invoke = ast.call(ast.ident("invoke"), [ast.string(method), ast.ident("arg")]);
+                (method, function(ast, &["arg"], [ret(ast, invoke)]))
+            })),
+        ),
+        if_(
+            ast,
+            ast.logical(
+                symbol_is_function(ast),
+                LogicalOperator::And,
+                ast.member(ast.ident("Symbol"), "iterator"),
+            ),
+            [ast.statement(ast.assign(
+                ast.computed(ast.ident("generator"), ast.member(ast.ident("Symbol"), "iterator")),
+                function(ast, &[], [ret(ast, ast.this_expression(SPAN))]),
+            ))],
+        ),
+        ret(ast, ast.ident("generator")),
+    ]);
+    ast.function_declaration_of(name, &["innerFn", "self", "tryLocs"], body)
+}
+
+/// `function values(iterable) { ... }`
+fn values<'a>(ast: &AstBuilder<'a>) -> Statement<'a> {
+    let method = ast.logical(
+        symbol_is_function(ast),
+        LogicalOperator::And,
+        ast.computed(ast.ident("iterable"), ast.member(ast.ident("Symbol"), "iterator")),
+    );
+    let index = || ast.ident("index");
+    let next = function(
+        ast,
+        &[],
+        [
+            ast.statement(update(ast, UpdateOperator::Increment, "index")),
+            ret(
+                ast,
+                ast.conditional(
+                    ast.binary(
+                        index(),
+                        BinaryOperator::LessThan,
+                        ast.member(ast.ident("iterable"), "length"),
+                    ),
+                    ast.object([
+                        ("value", ast.computed(ast.ident("iterable"), index())),
+                        ("done", ast.boolean_literal_expression(false)),
+                    ]),
+                    done(ast),
+                ),
+            ),
+        ],
+    );
+    declaration(
+        ast,
+        "values",
+        &["iterable"],
+        [
+            var(ast, "method", method),
+            if_(
+                ast,
+                ast.ident("method"),
+                [ret(ast, call_method(ast, ast.ident("method"), "call", [ast.ident("iterable")]))],
+            ),
+            if_(
+                ast,
+                strict_equal(
+                    ast,
+                    ast.type_of(ast.member(ast.ident("iterable"), "next")),
+                    ast.string("function"),
+                ),
+                [ret(ast, ast.ident("iterable"))],
+            ),
+            var(
+                ast,
+                "index",
+                ast.unary_expression(SPAN, UnaryOperator::UnaryNegation, ast.number(1.0)),
+            ),
+            ret(ast, ast.object([("next", next)])),
+        ],
+    )
+}
+
+/// `function complete(record, afterLoc) { ... }`
+fn complete<'a>(ast: &AstBuilder<'a>) -> Statement<'a> {
+    let record_type = || ast.member(ast.ident("record"), "type");
+    let record_arg = || ast.member(ast.ident("record"), "arg");
+    let is_jump = ast.logical(
+        strict_equal(ast, record_type(), ast.string("break")),
+        LogicalOperator::Or,
+        strict_equal(ast, record_type(), ast.string("continue")),
+    );
+    let after = if_(ast, ast.ident("afterLoc"), [set_context(ast, "next", ast.ident("afterLoc"))]);
+    let is_return = if_else(
+        ast,
+        strict_equal(ast, record_type(), ast.string("return")),
+        [
+            ast.statement(
+                ast.assign(context(ast, "rval"), ast.assign(context(ast, "arg"), record_arg())),
+            ),
+            set_context(ast, "method", ast.string("return")),
+            set_context(ast, "next", ast.string("end")),
+        ],
+        after,
+    );
+    declaration(
+        ast,
+        "complete",
+        &["record", "afterLoc"],
+        [
+            if_(
+                ast,
+                strict_equal(ast, record_type(), ast.string("throw")),
+                [ast.throw_statement(SPAN, record_arg())],
+            ),
+            if_else(ast, is_jump, [set_context(ast, "next", record_arg())], is_return),
+            ret(ast, ast.ident("ContinueSentinel")),
+        ],
+    )
+}
+
+/// `function abrupt(type, arg) { ... }`
+fn abrupt<'a>(ast: &AstBuilder<'a>) -> Statement<'a> {
+    let entry = |name| ast.member(ast.ident("entry"), name);
+    let in_try = |value: Expression<'a>, end: Expression<'a>| {
+        ast.logical(
+            ast.binary(entry("tryLoc"), BinaryOperator::LessEqualThan, value),
+            LogicalOperator::And,
+            end,
+        )
+    };
+    let is_jump = ast.logical(
+        strict_equal(ast, ast.ident("type"), ast.string("break")),
+        LogicalOperator::Or,
+        strict_equal(ast, ast.ident("type"), ast.string("continue")),
+    );
+    let jumps_inside = ast.logical(
+        ast.parenthesized_expression(SPAN, is_jump),
+        LogicalOperator::And,
+        in_try(
+            ast.ident("arg"),
+            ast.binary(ast.ident("arg"), BinaryOperator::LessEqualThan, entry("finallyLoc")),
+        ),
+    );
+    let record = || ast.object([("type", ast.ident("type")), ("arg", ast.ident("arg"))]);
+    let in_finally = in_try(
+        context(ast, "prev"),
+        ast.binary(context(ast, "prev"), BinaryOperator::LessThan, entry("finallyLoc")),
+    );
+    declaration(
+        ast,
+        "abrupt",
+        &["type", "arg"],
+        [
+            for_each_entry(
+                ast,
+                [if_(
+                    ast,
+                    in_finally,
+                    [
+                        if_(ast, jumps_inside, [ast.break_statement(SPAN, None)]),
+                        ast.statement(ast.assign(entry("completion"), record())),
+                        set_context(ast, "method", ast.string("next")),
+                        set_context(ast, "next", entry("finallyLoc")),
+                        ret(ast, ast.ident("ContinueSentinel")),
+                    ],
+                )],
+            ),
+            ret(ast, ast.call(ast.ident("complete"), [record()])),
+        ],
+    )
+}
+
+/// `function dispatchException(exception) { ... }`
+fn dispatch_exception<'a>(ast: &AstBuilder<'a>) -> Statement<'a> {
+    let entry = |name| ast.member(ast.ident("entry"), name);
+    let is_root = || strict_equal(ast, entry("tryLoc"), ast.string("root"));
+    let handled = ast.logical(
+        is_root(),
+        LogicalOperator::Or,
+        ast.logical(
+            ast.binary(entry("tryLoc"), BinaryOperator::LessEqualThan, context(ast, "prev")),
+            LogicalOperator::And,
+            ast.parenthesized_expression(
+                SPAN,
+                ast.logical(
+                    ast.ident("caught"),
+                    LogicalOperator::Or,
+                    ast.binary(context(ast, "prev"), BinaryOperator::LessThan, entry("finallyLoc")),
+                ),
+            ),
+        ),
+    );
+    let next = ast.conditional(
+        is_root(),
+        ast.string("end"),
+        ast.conditional(ast.ident("caught"), entry("catchLoc"), entry("finallyLoc")),
+    );
+    declaration(
+        ast,
+        "dispatchException",
+        &["exception"],
+        [
+            if_(ast, context(ast, "done"), [ast.throw_statement(SPAN, ast.ident("exception"))]),
+            for_each_entry(
+                ast,
+                [
+                    var(
+                        ast,
+                        "caught",
+                        ast.binary(
+                            context(ast, "prev"),
+                            BinaryOperator::LessThan,
+                            entry("catchLoc"),
+                        ),
+                    ),
+                    if_(
+                        ast,
+                        handled,
+                        [
+                            ast.statement(ast.assign(
+                                entry("completion"),
+                                ast.object([
+                                    ("type", ast.string("throw")),
+                                    ("arg", ast.ident("exception")),
+                                ]),
+                            )),
+                            set_context(ast, "next", next),
+                            if_(
+                                ast,
+                                ast.ident("caught"),
+                                [
+                                    set_context(ast, "method", ast.string("next")),
+                                    set_context(ast, "arg", ast.void_0()),
+                                ],
+                            ),
+                            ast.return_statement(SPAN, None),
+                        ],
+                    ),
+                ],
+            ),
+        ],
+    )
+}
+
+/// `function invokeDelegate(delegate) { ... }`
+fn invoke_delegate<'a>(ast: &AstBuilder<'a>) -> Statement<'a> {
+    let iterator = || ast.ident("iterator");
+    let method = || context(ast, "method");
+    let sentinel = || ret(ast, ast.ident("ContinueSentinel"));
+    let message = ast.binary(
+        ast.binary(
+            ast.string("The iterator does not provide a '"),
+            BinaryOperator::Addition,
+            method(),
+        ),
+        BinaryOperator::Addition,
+        ast.string("' method"),
+    );
+    let missing = if_(
+        ast,
+        strict_equal(ast, ast.ident("method"), ast.void_0()),
+        [
+            set_context(ast, "delegate", ast.null_literal_expression()),
+            if_(ast, strict_equal(ast, method(), ast.string("return")), [sentinel()]),
+            if_(
+                ast,
+                ast.logical(
+                    strict_equal(ast, method(), ast.string("throw")),
+                    LogicalOperator::And,
+                    ast.computed(iterator(), ast.string("return")),
+                ),
+                [ast.statement(ast.call(ast.computed(iterator(), ast.string("return")), []))],
+            ),
+            set_context(ast, "arg", new_type_error(ast, message)),
+            set_context(ast, "method", ast.string("throw")),
+            sentinel(),
+        ],
+    );
+    let call = try_catch(
+        ast,
+        [var(
+            ast,
+            "info",
+            call_method(ast, ast.ident("method"), "call", [iterator(), context(ast, "arg")]),
+        )],
+        [
+            set_context(ast, "delegate", ast.null_literal_expression()),
+            set_context(ast, "method", ast.string("throw")),
+            set_context(ast, "arg", ast.ident("error")),
+            sentinel(),
+        ],
+    );
+    let info_value = || ast.member(ast.ident("info"), "value");
+    declaration(
+        ast,
+        "invokeDelegate",
+        &["delegate"],
+        [
+            var(ast, "iterator", ast.member(ast.ident("delegate"), "iterator")),
+            var(ast, "method", ast.computed(iterator(), method())),
+            missing,
+            call,
+            if_(ast, ast.not(ast.member(ast.ident("info"), "done")), [ret(ast, ast.ident("info"))]),
+            set_context(ast, "delegate", ast.null_literal_expression()),
+            ast.statement(ast.assign(
+                ast.computed(ast.ident(CONTEXT), ast.member(ast.ident("delegate"), "resultName")),
+                info_value(),
+            )),
+            set_context(ast, "next", ast.member(ast.ident("delegate"), "nextLoc")),
+            if_else(
+                ast,
+                strict_equal(ast, method(), ast.string("return")),
+                [set_context(ast, "arg", info_value())],
+                block(
+                    ast,
+                    [
+                        set_context(ast, "method", ast.string("next")),
+                        set_context(ast, "arg", ast.void_0()),
+                    ],
+                ),
+            ),
+            sentinel(),
+        ],
+    )
+}
+
+/// `function invoke(method, arg) { ... }`
+#[allow(clippy::too_many_lines)]
+fn invoke<'a>(ast: &AstBuilder<'a>) -> Statement<'a> {
+    let state = || ast.ident("state");
+    let set_state = |value: Expression<'a>| ast.statement(ast.assign(state(), value));
+    let method = || ast.ident("method");
+    let sentinel = || ast.ident("ContinueSentinel");
+    let completed = if_(
+        ast,
+        strict_equal(ast, state(), ast.string("completed")),
+        [
+            if_(
+                ast,
+                strict_equal(ast, method(), ast.string("throw")),
+                [ast.throw_statement(SPAN, ast.ident("arg"))],
+            ),
+            ret(
+                ast,
+                ast.object([
+                    (
+                        "value",
+                        ast.conditional(
+                            strict_equal(ast, method(), ast.string("return")),
+                            ast.ident("arg"),
+                            ast.void_0(),
+                        ),
+                    ),
+                    ("done", ast.boolean_literal_expression(true)),
+                ]),
+            ),
+        ],
+    );
+    let delegate = if_(
+        ast,
+        context(ast, "delegate"),
+        [
+            var(ast, "result", ast.call(ast.ident("invokeDelegate"), [context(ast, "delegate")])),
+            if_(
+                ast,
+                ast.binary(ast.ident("result"), BinaryOperator::StrictInequality, sentinel()),
+                [ret(ast, ast.ident("result"))],
+            ),
+        ],
+    );
+    let resume = if_else(
+        ast,
+        strict_equal(ast, context(ast, "method"), ast.string("next")),
+        [set_context(ast, "sent", context(ast, "arg"))],
+        if_else(
+            ast,
+            strict_equal(ast, context(ast, "method"), ast.string("throw")),
+            [ast.statement(ast.call(ast.ident("dispatchException"), [context(ast, "arg")]))],
+            block(
+                ast,
+                [ast.statement(
+                    ast.call(ast.ident("abrupt"), [ast.string("return"), context(ast, "arg")]),
+                )],
+            ),
+        ),
+    );
+    let run = try_catch(
+        ast,
+        [var(
+            ast,
+            "value",
+            call_method(ast, ast.ident("innerFn"), "call", [ast.ident("self"), ast.ident(CONTEXT)]),
+        )],
+        [
+            set_state(ast.string("completed")),
+            set_context(ast, "method", ast.string("throw")),
+            set_context(ast, "arg", ast.ident("error")),
+            ast.continue_statement(SPAN, None),
+        ],
+    );
+    let loop_body = ast.statements([
+        delegate,
+        resume,
+        set_state(ast.string("executing")),
+        run,
+        set_state(ast.conditional(
+            context(ast, "done"),
+            ast.string("completed"),
+            ast.string("suspendedYield"),
+        )),
+        if_(
+            ast,
+            ast.binary(ast.ident("value"), BinaryOperator::StrictInequality, sentinel()),
+            [ret(ast, ast.object([("value", ast.ident("value")), ("done", context(ast, "done"))]))],
+        ),
+    ]);
+    declaration(
+        ast,
+        "invoke",
+        &["method", "arg"],
+        [
+            if_(
+                ast,
+                strict_equal(ast, state(), ast.string("executing")),
+                [ast.throw_statement(
+                    SPAN,
+                    new_type_error(ast, ast.string("Generator is already running")),
+                )],
+            ),
+            if_(
+                ast,
+                ast.logical(
+                    strict_equal(ast, state(), ast.string("suspendedStart")),
+                    LogicalOperator::And,
+                    ast.binary(method(), BinaryOperator::StrictInequality, ast.string("next")),
+                ),
+                [set_state(ast.string("completed"))],
+            ),
+            completed,
+            set_context(ast, "method", method()),
+            set_context(ast, "arg", ast.ident("arg")),
+            ast.while_statement(
+                SPAN,
+                ast.boolean_literal_expression(true),
+                ast.block_of(loop_body),
+            ),
+        ],
+    )
+}
+
+/// `{ prev: 0, next: 0, ..., values: values }`
+fn context_object<'a>(ast: &AstBuilder<'a>) -> Expression<'a> {
+    let entry = |name| ast.member(ast.ident("entry"), name);
+    let root = || ast.ident("root");
+    let stop = function(
+        ast,
+        &[],
+        [
+            set_context(ast, "done", ast.boolean_literal_expression(true)),
+            var(ast, "root", ast.member(ast.index(ast.ident("tryEntries"), 0), "completion")),
+            if_(
+                ast,
+                strict_equal(ast, ast.member(root(), "type"), ast.string("throw")),
+                [ast.throw_statement(SPAN, ast.member(root(), "arg"))],
+            ),
+            ret(ast, context(ast, "rval")),
+        ],
+    );
+    let finish = function(
+        ast,
+        &["finallyLoc"],
+        [for_each_entry(
+            ast,
+            [if_(
+                ast,
+                strict_equal(ast, entry("finallyLoc"), ast.ident("finallyLoc")),
+                [
+                    var(ast, "record", entry("completion")),
+                    ast.statement(ast.call(ast.ident("reset"), [ast.ident("entry")])),
+                    ret(
+                        ast,
+                        ast.call(ast.ident("complete"), [ast.ident("record"), entry("afterLoc")]),
+                    ),
+                ],
+            )],
+        )],
+    );
+    let catch = function(
+        ast,
+        &["tryLoc"],
+        [for_each_entry(
+            ast,
+            [if_(
+                ast,
+                strict_equal(ast, entry("tryLoc"), ast.ident("tryLoc")),
+                [
+                    var(ast, "thrown", ast.member(entry("completion"), "arg")),
+                    ast.statement(ast.call(ast.ident("reset"), [ast.ident("entry")])),
+                    ret(ast, ast.ident("thrown")),
+                ],
+            )],
+        )],
+    );
+    let delegate_yield = function(
+        ast,
+        &["iterable", "resultName", "nextLoc"],
+        [
+            set_context(
+                ast,
+                "delegate",
+                ast.object([
+                    ("iterator", ast.call(ast.ident("values"), [ast.ident("iterable")])),
+                    ("resultName", ast.ident("resultName")),
+                    ("nextLoc", ast.ident("nextLoc")),
+                ]),
+            ),
+            if_(
+                ast,
+                strict_equal(ast, context(ast, "method"), ast.string("next")),
+                [set_context(ast, "arg", ast.void_0())],
+            ),
+            ret(ast, ast.ident("ContinueSentinel")),
+        ],
+    );
+    ast.object([
+        ("prev", ast.number(0.0)),
+        ("next", ast.number(0.0)),
+        ("sent", ast.void_0()),
+        ("rval", ast.void_0()),
+        ("done", ast.boolean_literal_expression(false)),
+        ("delegate", ast.null_literal_expression()),
+        ("method", ast.string("next")),
+        ("arg", ast.void_0()),
+        ("abrupt", ast.ident("abrupt")),
+        ("stop", stop),
+        ("finish", finish),
+        ("catch", catch),
+        ("delegateYield", delegate_yield),
+        ("keys", keys(ast)),
+        ("values", ast.ident("values")),
+    ])
+}
+
+/// `function (object) { ... }`, which iterates the keys of `object` in `for-in` loops
+fn keys<'a>(ast: &AstBuilder<'a>) -> Expression<'a> {
+    let keys = || ast.ident("keys");
+    let key = ast.var_declaration_of(VariableDeclarationKind::Var, [("key", None)]);
+    let collect = ast.for_in_statement(
+        SPAN,
+        ForStatementLeft::VariableDeclaration(key),
+        ast.ident("object"),
+        ast.statement(call_method(ast, keys(), "push", [ast.ident("key")])),
+    );
+    let next = function(
+        ast,
+        &[],
+        [
+            ast.while_statement(
+                SPAN,
+                ast.member(keys(), "length"),
+                block(
+                    ast,
+                    [
+                        var(ast, "key", call_method(ast, keys(), "pop", [])),
+                        if_(
+                            ast,
+                            ast.binary(ast.ident("key"), BinaryOperator::In, ast.ident("object")),
+                            [ret(
+                                ast,
+                                ast.object([
+                                    ("value", ast.ident("key")),
+                                    ("done", ast.boolean_literal_expression(false)),
+                                ]),
+                            )],
+                        ),
+                    ],
+                ),
+            ),
+            ret(ast, done(ast)),
+        ],
+    );
+    function(
+        ast,
+        &["object"],
+        [
+            ast.statement(
+                ast.assign(
+                    ast.ident("object"),
+                    ast.call(ast.ident("Object"), [ast.ident("object")]),
+                ),
+            ),
+            var(ast, "keys", ast.array([])),
+            collect,
+            ast.statement(call_method(ast, keys(), "reverse", [])),
+            ret(ast, next),
+        ],
+    )
+}
+
+/// The name of the context in the helper
+const CONTEXT: &str = "context";
+
+/// `context.name`
+fn context<'a>(ast: &AstBuilder<'a>, name: &str) -> Expression<'a> {
+    ast.member(ast.ident(CONTEXT), name)
+}
+
+/// `context.name = value;`
+fn set_context<'a>(ast: &AstBuilder<'a>, name: &str, value: Expression<'a>) -> Statement<'a> {
+    ast.statement(ast.assign(context(ast, name), value))
+}
+
+/// `object.name(arguments)`
+fn call_method<'a, I>(
+    ast: &AstBuilder<'a>,
+    object: Expression<'a>,
+    name: &str,
+    arguments: I,
+) -> Expression<'a>
+where
+    I: IntoIterator<Item = Expression<'a>>,
+{
+    ast.call(ast.member(object, name), arguments)
+}
+
+/// `{ value: void 0, done: true }`
+fn done<'a>(ast: &AstBuilder<'a>) -> Expression<'a> {
+    ast.object([("value", ast.void_0()), ("done", ast.boolean_literal_expression(true))])
+}
+
+fn update<'a>(ast: &AstBuilder<'a>, operator: UpdateOperator, name: &str) -> Expression<'a> {
+    let ident = IdentifierReference { span: SPAN, name: Atom::from(name) };
+    let target = SimpleAssignmentTarget::AssignmentTargetIdentifier(ast.alloc(ident));
+    ast.update_expression(SPAN, operator, false, target)
+}
+
+fn strict_equal<'a>(
+    ast: &AstBuilder<'a>,
+    left: Expression<'a>,
+    right: Expression<'a>,
+) -> Expression<'a> {
+    ast.binary(left, BinaryOperator::StrictEquality, right)
+}
+
+/// `typeof Symbol === "function"`
+fn symbol_is_function<'a>(ast: &AstBuilder<'a>) -> Expression<'a> {
+    strict_equal(ast, ast.type_of(ast.ident("Symbol")), ast.string("function"))
+}
+
+fn new_type_error<'a>(ast: &AstBuilder<'a>, message: Expression<'a>) -> Expression<'a> {
+    let arguments = ast.new_vec_single(Argument::Expression(message));
+    ast.new_expression(SPAN, ast.ident("TypeError"), arguments, None, false)
+}
+
+/// `function (params) { body }`
+fn function<'a, I>(ast: &AstBuilder<'a>, params: &[&str], body: I) -> Expression<'a>
+where
+    I: IntoIterator<Item = Statement<'a>>,
+{
+    let body = ast.statements(body);
+    Expression::FunctionExpression(ast.function_of(
+        FunctionType::FunctionExpression,
+        None,
+        params,
+        body,
+    ))
+}
+
+/// `function name(params) { body }`
+fn declaration<'a, I>(ast: &AstBuilder<'a>, name: &str, params: &[&str], body: I) -> Statement<'a>
+where
+    I: IntoIterator<Item = Statement<'a>>,
+{
+    ast.function_declaration_of(Atom::from(name), params, ast.statements(body))
+}
+
+fn block<'a, I>(ast: &AstBuilder<'a>, statements: I) -> Statement<'a>
+where
+    I: IntoIterator<Item = Statement<'a>>,
+{
+    ast.block_of(ast.statements(statements))
+}
+
+fn var<'a>(ast: &AstBuilder<'a>, name: &str, init: Expression<'a>) -> Statement<'a> {
+    ast.var_declaration_statement(VariableDeclarationKind::Var, [(name, Some(init))])
+}
+
+fn ret<'a>(ast: &AstBuilder<'a>, argument: Expression<'a>) -> Statement<'a> {
+    ast.return_statement(SPAN, Some(argument))
+}
+
+fn if_<'a, I>(ast: &AstBuilder<'a>, test: Expression<'a>, consequent: I) -> Statement<'a>
+where
+    I: IntoIterator<Item = Statement<'a>>,
+{
+    ast.if_else(test, block(ast, consequent), None)
+}
+
+fn if_else<'a, I>(
+    ast: &AstBuilder<'a>,
+    test: Expression<'a>,
+    consequent: I,
+    alternate: Statement<'a>,
+) -> Statement<'a>
+where
+    I: IntoIterator<Item = Statement<'a>>,
+{
+    ast.if_else(test, block(ast, consequent), Some(alternate))
+}
+
+/// `try { block } catch (error) { handler }`
+fn try_catch<'a, I, J>(ast: &AstBuilder<'a>, block: I, handler: J) -> Statement<'a>
+where
+    I: IntoIterator<Item = Statement<'a>>,
+    J: IntoIterator<Item = Statement<'a>>,
+{
+    let error = BindingIdentifier { span: SPAN, name: Atom::from("error") };
+    let error = ast.binding_pattern(ast.binding_identifier(error), None, false);
+    let handler = ast.catch_clause(SPAN, Some(error), ast.block(SPAN, ast.statements(handler)));
+    ast.try_statement(SPAN, ast.block(SPAN, ast.statements(block)), Some(handler), None)
+}
+
+/// `for (var i = tryEntries.length - 1; i >= 0; i--) { var entry = tryEntries[i]; body }`
+fn for_each_entry<'a, I>(ast: &AstBuilder<'a>, body: I) -> Statement<'a>
+where
+    I: IntoIterator<Item = Statement<'a>>,
+{
+    let last_index = ast.binary(
+        ast.member(ast.ident("tryEntries"), "length"),
+        BinaryOperator::Subtraction,
+        ast.number(1.0),
+    );
+    let init = ast.var_declaration_of(VariableDeclarationKind::Var, [("i", Some(last_index))]);
+    let test = ast.binary(ast.ident("i"), BinaryOperator::GreaterEqualThan, ast.number(0.0));
+    let entry = var(ast, "entry", ast.computed(ast.ident("tryEntries"), ast.ident("i")));
+    let statements = ast.statements(std::iter::once(entry).chain(body));
+    ast.for_statement(
+        SPAN,
+        Some(ForStatementInit::VariableDeclaration(init)),
+        Some(test),
+        Some(update(ast, UpdateOperator::Decrement, "i")),
+        ast.block_of(statements),
+    )
+}
@@ -0,0 +1,466 @@
+//! The block scopes of generators, whose bindings are hoisted out of the state machine as `var`s
+//!
+//! * `function* f() { { let a = 1; yield a; } return a; }` ->
+//!   `function* f() { { let _a = 1; yield _a; } return a; }`, before the declarations are hoisted
+
+use std::collections::{HashMap, HashSet};
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, VisitMut};
+use oxc_span::{Atom, Span};
+
+use super::emit::Leaps;
+use crate::temporaries::Temporaries;
+
+/// The bindings of a scope, and the names they are renamed to
+struct Frame {
+    names: std::vec::Vec<(Atom, Option<Atom>)>,
+    /// The number of the functions inside the generator the scope is in
+    depth: usize,
+    /// Whether each iteration of a loop has its own bindings, which are shared once hoisted
+    per_iteration: bool,
+}
+
+/// Resolves the references of a generator to the scopes of its body, first to find the block
+/// scoped bindings which are in conflict with other names once they are hoisted, then to rename
+/// them.
+pub(super) struct BlockScopes<'r, 'a> {
+    frames: std::vec::Vec<Frame>,
+    depth: usize,
+    loops: usize,
+    /// The number of the declarations of each name which is declared in the generator, and not in
+    /// a function inside it
+    declarations: HashMap<Atom, usize>,
+    /// The names which are declared in the blocks of the generator
+    block_scoped: HashSet<Atom>,
+    /// The names which are referenced but not declared in the generator
+    free: HashSet<Atom>,
+    /// A reference of a closure to a binding of a loop iteration
+    captured: Option<Span>,
+    rename: Option<Rename<'r, 'a>>,
+}
+
+/// The names to rename, and the temporaries they are renamed to
+struct Rename<'r, 'a> {
+    ast: AstBuilder<'a>,
+    conflicts: HashSet<Atom>,
+    temporaries: &'r mut Temporaries<'a>,
+}
+
+impl<'r, 'a> BlockScopes<'r, 'a> {
+    /// Finds the block scoped bindings of the generator with `params` and `body`
+    pub fn collect(params: &mut FormalParameters<'a>, body: &mut FunctionBody<'a>) -> Self {
+        let mut scopes = Self::new(None);
+        scopes.visit_generator(params, body);
+        scopes
+    }
+
+    /// A reference of a closure to a `let` or `const` binding of an iteration of a loop, which
+    /// the iterations would share once it is hoisted
+    pub fn captured(&self) -> Option<Span> {
+        self.captured
+    }
+
+    /// Renames the block scoped bindings which would shadow another binding or a reference once
+    /// they are hoisted
+    pub fn rename(
+        self,
+        allocator: &'a Allocator,
+        params: &mut FormalParameters<'a>,
+        body: &mut FunctionBody<'a>,
+        temporaries: &'r mut Temporaries<'a>,
+    ) {
+        let conflicts: HashSet<Atom> = self
+            .block_scoped
+            .iter()
+            .filter(|name| self.declarations[*name] > 1 || self.free.contains(*name))
+            .cloned()
+            .collect();
+        if conflicts.is_empty() {
+            return;
+        }
+        let rename = Rename { ast: AstBuilder::new(allocator), conflicts, temporaries };
+        Self::new(Some(rename)).visit_generator(params, body);
+    }
+
+    fn new(rename: Option<Rename<'r, 'a>>) -> Self {
+        Self {
+            frames: vec![],
+            depth: 0,
+            loops: 0,
+            declarations: HashMap::new(),
+            block_scoped: HashSet::new(),
+            free: HashSet::new(),
+            captured: None,
+            rename,
+        }
+    }
+
+    fn visit_generator(&mut self, params: &mut FormalParameters<'a>, body: &mut FunctionBody<'a>) {
+        let names = function_scoped_names(params, body);
+        self.push(names, false);
+        self.visit_formal_parameters(params);
+        self.visit_function_body(body);
+        self.frames.pop();
+    }
+
+    /// Enters a scope declaring `names`, which the iterations of a loop have their own copies of
+    /// if `per_iteration`
+    fn push(&mut self, names: std::vec::Vec<Atom>, per_iteration: bool) {
+        let hoisted = self.depth == 0 && !self.frames.is_empty();
+        if self.depth == 0 {
+            for name in &names {
+                *self.declarations.entry(name.clone()).or_default() += 1;
+                if hoisted {
+                    self.block_scoped.insert(name.clone());
+                }
+            }
+        }
+        let names = names
+            .into_iter()
+            .map(|name| {
+                let renamed = match &mut self.rename {
+                    Some(rename) if hoisted && rename.conflicts.contains(&name) => {
+                        Some(rename.temporaries.create_unscoped(&name))
+                    }
+                    _ => None,
+                };
+                (name, renamed)
+            })
+            .collect();
+        let per_iteration = hoisted && per_iteration;
+        self.frames.push(Frame { names, depth: self.depth, per_iteration });
+    }
+
+    /// The new name of `name` if the binding it resolves to is renamed
+    fn resolve(&mut self, name: &Atom, span: Span) -> Option<Atom> {
+        let Some(frame) =
+            self.frames.iter().rev().find(|frame| frame.names.iter().any(|(n, _)| n == name))
+        else {
+            self.free.insert(name.clone());
+            return None;
+        };
+        if frame.per_iteration && frame.depth < self.depth && self.captured.is_none() {
+            self.captured = Some(span);
+        }
+        frame.names.iter().find(|(n, _)| n == name).and_then(|(_, renamed)| renamed.clone())
+    }
+
+    fn visit_loop_body(&mut self, body: &mut Statement<'a>) {
+        self.loops += 1;
+        self.visit_statement(body);
+        self.loops -= 1;
+    }
+
+    /// `for (let a; ...)`, `for (let a in b)` and `for (let a of b)`, whose bindings are
+    /// hoisted if the loop is
+    fn lexical_head<F: FnOnce(&mut Self)>(
+        &mut self,
+        names: std::vec::Vec<Atom>,
+        leaps: bool,
+        visit: F,
+    ) {
+        if names.is_empty() {
+            visit(self);
+            return;
+        }
+        self.push(names, leaps);
+        visit(self);
+        self.frames.pop();
+    }
+}
+
+impl<'r, 'a, 'b> VisitMut<'a, 'b> for BlockScopes<'r, 'a> {
+    fn visit_identifier_reference(&mut self, ident: &'b mut IdentifierReference) {
+        if let Some(name) = self.resolve(&ident.name, ident.span) {
+            ident.name = name;
+        }
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &'b mut BindingIdentifier) {
+        if let Some(name) = self.resolve(&ident.name, ident.span) {
+            ident.name = name;
+        }
+    }
+
+    fn visit_object_property(&mut self, prop: &'b mut ObjectProperty<'a>) {
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+        // `{ a }` -> `{ a: _a }`
+        if let (PropertyKey::Identifier(key), Expression::Identifier(value)) =
+            (&prop.key, &prop.value)
+        {
+            prop.shorthand &= key.name == value.name;
+        }
+    }
+
+    fn visit_binding_property(&mut self, prop: &'b mut BindingProperty<'a>) {
+        self.visit_property_key(&mut prop.key);
+        self.visit_binding_pattern(&mut prop.value);
+        let value = match &prop.value.kind {
+            BindingPatternKind::AssignmentPattern(pattern) => &pattern.left.kind,
+            kind => kind,
+        };
+        if let (PropertyKey::Identifier(key), BindingPatternKind::BindingIdentifier(value)) =
+            (&prop.key, value)
+        {
+            prop.shorthand &= key.name == value.name;
+        }
+    }
+
+    fn visit_assignment_target_property(&mut self, property: &'b mut AssignmentTargetProperty<'a>) {
+        let AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) = property else {
+            if let AssignmentTargetProperty::AssignmentTargetPropertyProperty(property) = property {
+                self.visit_assignment_target_property_property(property);
+            }
+            return;
+        };
+        if let Some(init) = &mut ident.init {
+            self.visit_expression(init);
+        }
+        let Some(name) = self.resolve(&ident.binding.name, ident.binding.span) else { return };
+        // `({ a } = b)` -> `({ a: _a } = b)`
+        let Some(Rename { ast, .. }) = &self.rename else { return };
+        let key = IdentifierName { span: ident.binding.span, name: ident.binding.name.clone() };
+        let binding = IdentifierReference { span: ident.binding.span, name };
+        let binding = AssignmentTarget::SimpleAssignmentTarget(
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ast.alloc(binding)),
+        );
+        let binding = match ident.init.take() {
+            Some(init) => AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(
+                ast.alloc(AssignmentTargetWithDefault { span: ident.span, binding, init }),
+            ),
+            None => AssignmentTargetMaybeDefault::AssignmentTarget(binding),
+        };
+        let target = AssignmentTargetPropertyProperty {
+            span: ident.span,
+            name: PropertyKey::Identifier(ast.alloc(key)),
+            binding,
+        };
+        *property = AssignmentTargetProperty::AssignmentTargetPropertyProperty(ast.alloc(target));
+    }
+
+    fn visit_block_statement(&mut self, stmt: &'b mut BlockStatement<'a>) {
+        let leaps = self.loops > 0 && Leaps::find(|leaps| leaps.visit_block_statement(stmt));
+        self.push(lexically_scoped_names(&stmt.body), leaps);
+        self.visit_statements(&mut stmt.body);
+        self.frames.pop();
+    }
+
+    fn visit_for_statement(&mut self, stmt: &'b mut ForStatement<'a>) {
+        let mut names = vec![];
+        if let Some(ForStatementInit::VariableDeclaration(decl)) = &stmt.init {
+            if decl.kind != VariableDeclarationKind::Var {
+                decl.bound_names(&mut |ident| names.push(ident.name.clone()));
+            }
+        }
+        let leaps = !names.is_empty() && Leaps::find(|leaps| leaps.visit_for_statement(stmt));
+        self.lexical_head(names, leaps, |scopes| {
+            if let Some(init) = &mut stmt.init {
+                scopes.visit_for_statement_init(init);
+            }
+            if let Some(test) = &mut stmt.test {
+                scopes.visit_expression(test);
+            }
+            if let Some(update) = &mut stmt.update {
+                scopes.visit_expression(update);
+            }
+            scopes.visit_loop_body(&mut stmt.body);
+        });
+    }
+
+    fn visit_for_in_statement(&mut self, stmt: &'b mut ForInStatement<'a>) {
+        let names = for_statement_left_names(&stmt.left);
+        let leaps = !names.is_empty() && Leaps::find(|leaps| leaps.visit_for_in_statement(stmt));
+        self.lexical_head(names, leaps, |scopes| {
+            scopes.visit_for_statement_left(&mut stmt.left);
+            scopes.visit_expression(&mut stmt.right);
+            scopes.visit_loop_body(&mut stmt.body);
+        });
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &'b mut ForOfStatement<'a>) {
+        let names = for_statement_left_names(&stmt.left);
+        let leaps = !names.is_empty() && Leaps::find(|leaps| leaps.visit_for_of_statement(stmt));
+        self.lexical_head(names, leaps, |scopes| {
+            scopes.visit_for_statement_left(&mut stmt.left);
+            scopes.visit_expression(&mut stmt.right);
+            scopes.visit_loop_body(&mut stmt.body);
+        });
+    }
+
+    fn visit_while_statement(&mut self, stmt: &'b mut WhileStatement<'a>) {
+        self.visit_expression(&mut stmt.test);
+        self.visit_loop_body(&mut stmt.body);
+    }
+
+    fn visit_do_while_statement(&mut self, stmt: &'b mut DoWhileStatement<'a>) {
+        self.visit_loop_body(&mut stmt.body);
+        self.visit_expression(&mut stmt.test);
+    }
+
+    fn visit_switch_statement(&mut self, stmt: &'b mut SwitchStatement<'a>) {
+        self.visit_expression(&mut stmt.discriminant);
+        let mut names = vec![];
+        for case in &stmt.cases {
+            names.extend(lexically_scoped_names(&case.consequent));
+        }
+        let leaps = self.loops > 0 && Leaps::find(|leaps| leaps.visit_switch_statement(stmt));
+        self.push(names, leaps);
+        for case in stmt.cases.iter_mut() {
+            self.visit_switch_case(case);
+        }
+        self.frames.pop();
+    }
+
+    fn visit_try_statement(&mut self, stmt: &'b mut TryStatement<'a>) {
+        let leaps = self.loops > 0 && Leaps::find(|leaps| leaps.visit_try_statement(stmt));
+        self.visit_block_statement(&mut stmt.block);
+        if let Some(handler) = &mut stmt.handler {
+            // The parameter and the declarations of the body are in the same scope
+            let mut names = vec![];
+            if let Some(param) = &handler.param {
+                param.bound_names(&mut |ident| names.push(ident.name.clone()));
+            }
+            names.extend(lexically_scoped_names(&handler.body.body));
+            self.push(names, leaps);
+            self.visit_catch_clause(handler);
+            self.frames.pop();
+        }
+        if let Some(finalizer) = &mut stmt.finalizer {
+            self.visit_finally_clause(finalizer);
+        }
+    }
+
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        // The name of a function declaration is declared in the outer scope
+        if func.r#type == FunctionType::FunctionDeclaration {
+            if let Some(ident) = &mut func.id {
+                self.visit_binding_identifier(ident);
+            }
+        }
+        self.depth += 1;
+        let Some(body) = &mut func.body else {
+            self.depth -= 1;
+            return;
+        };
+        let mut names = function_scoped_names(&func.params, body);
+        if func.r#type != FunctionType::FunctionDeclaration {
+            names.extend(func.id.as_ref().map(|ident| ident.name.clone()));
+        }
+        self.push(names, false);
+        if func.r#type != FunctionType::FunctionDeclaration {
+            if let Some(ident) = &mut func.id {
+                self.visit_binding_identifier(ident);
+            }
+        }
+        self.visit_formal_parameters(&mut func.params);
+        self.visit_function_body(body);
+        self.frames.pop();
+        self.depth -= 1;
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        self.depth += 1;
+        self.push(function_scoped_names(&expr.params, &mut expr.body), false);
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.frames.pop();
+        self.depth -= 1;
+    }
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        // The name of a class expression is only declared in its body
+        let expression = class.r#type == ClassType::ClassExpression;
+        if expression {
+            self.push(class.id.iter().map(|ident| ident.name.clone()).collect(), false);
+        }
+        if let Some(id) = &mut class.id {
+            self.visit_binding_identifier(id);
+        }
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        self.visit_class_body(&mut class.body);
+        if expression {
+            self.frames.pop();
+        }
+    }
+
+    fn visit_static_block(&mut self, block: &'b mut StaticBlock<'a>) {
+        self.depth += 1;
+        let mut names = var_names(&mut block.body);
+        names.extend(lexically_scoped_names(&block.body));
+        self.push(names, false);
+        self.visit_statements(&mut block.body);
+        self.frames.pop();
+        self.depth -= 1;
+    }
+}
+
+/// The names declared by `let`, `const`, functions and classes in `stmts`
+fn lexically_scoped_names(stmts: &[Statement]) -> std::vec::Vec<Atom> {
+    let mut names = vec![];
+    for stmt in stmts {
+        let Statement::Declaration(decl) = stmt else { continue };
+        match decl {
+            Declaration::VariableDeclaration(decl) if decl.kind == VariableDeclarationKind::Var => {
+            }
+            Declaration::VariableDeclaration(_)
+            | Declaration::FunctionDeclaration(_)
+            | Declaration::ClassDeclaration(_) => {
+                decl.bound_names(&mut |ident| names.push(ident.name.clone()));
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// The names of the parameters and of the declarations in the body of a function
+fn function_scoped_names(
+    params: &FormalParameters,
+    body: &mut FunctionBody,
+) -> std::vec::Vec<Atom> {
+    let mut names = vec![];
+    params.bound_names(&mut |ident| names.push(ident.name.clone()));
+    names.extend(var_names(&mut body.statements));
+    names.extend(lexically_scoped_names(&body.statements));
+    names
+}
+
+fn for_statement_left_names(left: &ForStatementLeft) -> std::vec::Vec<Atom> {
+    let mut names = vec![];
+    if let ForStatementLeft::VariableDeclaration(decl) = left {
+        if decl.kind != VariableDeclarationKind::Var {
+            decl.bound_names(&mut |ident| names.push(ident.name.clone()));
+        }
+    }
+    names
+}
+
+/// The names declared by `var` in `stmts`, outside of the functions in them
+fn var_names<'a>(stmts: &mut oxc_allocator::Vec<'a, Statement<'a>>) -> std::vec::Vec<Atom> {
+    let mut collector = VarNames { names: vec![] };
+    collector.visit_statements(stmts);
+    collector.names
+}
+
+struct VarNames {
+    names: std::vec::Vec<Atom>,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for VarNames {
+    fn visit_variable_declaration(&mut self, decl: &'b mut VariableDeclaration<'a>) {
+        if decl.kind == VariableDeclarationKind::Var {
+            decl.bound_names(&mut |ident| self.names.push(ident.name.clone()));
+        }
+    }
+
+    fn visit_expression(&mut self, _expr: &'b mut Expression<'a>) {}
+
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, _class: &'b mut Class<'a>) {}
+}
@@ -0,0 +1,489 @@
+//! ES2017: async functions
+//!
+//! * `async function f(a) { await a; }` ->
+//!   `function f(a) { return _asyncToGenerator(this, arguments, function* () { yield a; }); }`
+//! * `async (a) => await a` ->
+//!   `(a) => _asyncToGenerator(this, void 0, function* () { return yield a; })`
+//! * `async m() { return super.m(); }` ->
+//!   `m() { var _superprop_getM = () => super.m;
+//!   return _asyncToGenerator(this, arguments, function* () { return _superprop_getM().call(this); }); }`
+//!
+//! Async generators, async functions with `for await` loops and async functions updating
+//! properties of `super` with other operators than `=` are kept as they are.
+
+use std::{collections::HashMap, mem};
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::{Atom, Span, SPAN};
+use oxc_syntax::operator::AssignmentOperator;
+
+use crate::{shorthands::Shorthands, temporaries::Temporaries};
+
+/// Runs async functions as generators, which yield the awaited values, with the
+/// `_asyncToGenerator` helper.
+///
+/// Generators are only lowered further together with [`crate::Es2015`].
+pub struct Es2017<'a> {
+    ast: AstBuilder<'a>,
+    temporaries: Temporaries<'a>,
+    /// Whether the functions being visited are lowered, innermost last, in which case `await`
+    /// becomes `yield`
+    functions: std::vec::Vec<bool>,
+    /// The name of `_asyncToGenerator` once it is used
+    helper: Option<Atom>,
+}
+
+impl<'a> Es2017<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            temporaries: Temporaries::new(allocator),
+            functions: vec![],
+            helper: None,
+        }
+    }
+
+    /// Replaces the properties of `super` in `body`, which can not be referenced in the
+    /// generator, with calls of arrow functions declared in the returned statement
+    fn replace_super_properties(&mut self, body: &mut FunctionBody<'a>) -> Option<Statement<'a>> {
+        let mut super_properties = SuperProperties {
+            ast: AstBuilder::new(self.ast.allocator),
+            temporaries: &mut self.temporaries,
+            accessors: HashMap::new(),
+            declarations: vec![],
+        };
+        super_properties.visit_function_body(body);
+        let accessors = super_properties.declarations;
+        if accessors.is_empty() {
+            return None;
+        }
+        let declarations = accessors
+            .iter()
+            .map(|(name, accessor)| (name.as_str(), Some(self.accessor_function(accessor))))
+            .collect::<std::vec::Vec<_>>();
+        Some(self.ast.var_declaration_statement(VariableDeclarationKind::Var, declarations))
+    }
+
+    /// `{ body }` -> `{ declarations; return _asyncToGenerator(this, args, function* () { body }); }`
+    fn wrap_body(
+        &mut self,
+        span: Span,
+        body: &mut FunctionBody<'a>,
+        declarations: Option<Statement<'a>>,
+        args: Expression<'a>,
+    ) {
+        let statements = mem::replace(&mut body.statements, self.ast.new_vec());
+        let mut generator =
+            self.ast.function_of(FunctionType::FunctionExpression, None, &[], statements);
+        generator.generator = true;
+        generator.span = span;
+        let helper = self.helper();
+        let call = self.ast.call(
+            helper,
+            [self.ast.this_expression(SPAN), args, Expression::FunctionExpression(generator)],
+        );
+        body.statements.extend(declarations);
+        body.statements.push(self.ast.return_statement(SPAN, Some(call)));
+    }
+
+    fn helper(&mut self) -> Expression<'a> {
+        let temporaries = &mut self.temporaries;
+        let name =
+            self.helper.get_or_insert_with(|| temporaries.create_unscoped("asyncToGenerator"));
+        self.ast.ident(name)
+    }
+
+    /// `() => super.a`, `(_prop) => super[_prop]` or `(_value) => super.a = _value`
+    fn accessor_function(&self, accessor: &SuperAccessor) -> Expression<'a> {
+        let object = self.ast.super_(SPAN);
+        let member = match &accessor.property {
+            Some(name) => self.ast.member(object, name),
+            None => self.ast.computed(object, self.ast.ident("_prop")),
+        };
+        let mut params = std::vec::Vec::with_capacity(2);
+        if accessor.property.is_none() {
+            params.push("_prop");
+        }
+        if accessor.set {
+            params.push("_value");
+            let assignment = self.ast.assign(member, self.ast.ident("_value"));
+            return self.ast.arrow_returning(&params, assignment);
+        }
+        self.ast.arrow_returning(&params, member)
+    }
+
+    /// ```javascript
+    /// function _asyncToGenerator(thisArg, args, generator) {
+    ///     return new Promise(function (resolve, reject) {
+    ///         var iterator = generator.apply(thisArg, args);
+    ///         function step(method, value) {
+    ///             try {
+    ///                 var result = iterator[method](value);
+    ///             } catch (error) {
+    ///                 reject(error);
+    ///                 return;
+    ///             }
+    ///             if (result.done) {
+    ///                 resolve(result.value);
+    ///             } else {
+    ///                 Promise.resolve(result.value).then(next, rejected);
+    ///             }
+    ///         }
+    ///         function next(value) { step("next", value); }
+    ///         function rejected(error) { step("throw", error); }
+    ///         next();
+    ///     });
+    /// }
+    /// ```
+    fn helper_declaration(&self, name: Atom) -> Statement<'a> {
+        let ast = &self.ast;
+        let iterator = ast.call(
+            ast.member(ast.ident("generator"), "apply"),
+            [ast.ident("thisArg"), ast.ident("args")],
+        );
+        let iterator = ast.var_declaration_statement(
+            VariableDeclarationKind::Var,
+            [("iterator", Some(iterator))],
+        );
+
+        let result = ast
+            .call(ast.computed(ast.ident("iterator"), ast.ident("method")), [ast.ident("value")]);
+        let result =
+            ast.var_declaration_statement(VariableDeclarationKind::Var, [("result", Some(result))]);
+        let reject = ast.statements([
+            ast.statement(ast.call(ast.ident("reject"), [ast.ident("error")])),
+            ast.return_statement(SPAN, None),
+        ]);
+        let error = BindingIdentifier { span: SPAN, name: Atom::from("error") };
+        let error = ast.binding_pattern(ast.binding_identifier(error), None, false);
+        let handler = ast.catch_clause(SPAN, Some(error), ast.block(SPAN, reject));
+        let try_statement =
+            ast.try_statement(SPAN, ast.block(SPAN, ast.statements([result])), Some(handler), None);
+        let value = || ast.member(ast.ident("result"), "value");
+        let resolve = ast.call(ast.ident("resolve"), [value()]);
+        let then = ast.call(ast.member(ast.ident("Promise"), "resolve"), [value()]);
+        let then = ast.call(ast.member(then, "then"), [ast.ident("next"), ast.ident("rejected")]);
+        let done = ast.if_else(
+            ast.member(ast.ident("result"), "done"),
+            ast.block_of(ast.statements([ast.statement(resolve)])),
+            Some(ast.block_of(ast.statements([ast.statement(then)]))),
+        );
+        let step = ast.function_declaration_of(
+            Atom::from("step"),
+            &["method", "value"],
+            ast.statements([try_statement, done]),
+        );
+
+        let resume = |name: &str, method: &str, param: &str| {
+            let step = ast.call(ast.ident("step"), [ast.string(method), ast.ident(param)]);
+            ast.function_declaration_of(
+                Atom::from(name),
+                &[param],
+                ast.statements([ast.statement(step)]),
+            )
+        };
+        let executor = ast.function_of(
+            FunctionType::FunctionExpression,
+            None,
+            &["resolve", "reject"],
+            ast.statements([
+                iterator,
+                step,
+                resume("next", "next", "value"),
+                resume("rejected", "throw", "error"),
+                ast.statement(ast.call(ast.ident("next"), [])),
+            ]),
+        );
+        let arguments =
+            ast.new_vec_single(Argument::Expression(Expression::FunctionExpression(executor)));
+        let promise = ast.new_expression(SPAN, ast.ident("Promise"), arguments, None, false);
+        ast.function_declaration_of(
+            name,
+            &["thisArg", "args", "generator"],
+            ast.statements([ast.return_statement(SPAN, Some(promise))]),
+        )
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Es2017<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
+        for directive in program.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut program.body);
+        if let Some(name) = self.helper.take() {
+            program.body.insert(0, self.helper_declaration(name));
+        }
+    }
+
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        let lower = func.r#async
+            && !func.generator
+            && func.body.as_mut().is_some_and(|body| can_lower(body).is_some());
+        let declarations = match &mut func.body {
+            Some(body) if lower => self.replace_super_properties(body),
+            _ => None,
+        };
+        self.functions.push(lower);
+        if let Some(ident) = &mut func.id {
+            self.visit_binding_identifier(ident);
+        }
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+        self.functions.pop();
+        if let (true, Some(body)) = (lower, &mut func.body) {
+            func.r#async = false;
+            let arguments = self.ast.ident("arguments");
+            self.wrap_body(func.span, body, declarations, arguments);
+        }
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        let uses_arguments = if expr.r#async { can_lower(&mut expr.body) } else { None };
+        let lower = uses_arguments.is_some();
+        let declarations = if lower { self.replace_super_properties(&mut expr.body) } else { None };
+        self.functions.push(lower);
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.functions.pop();
+        if !lower {
+            return;
+        }
+        // `async () => a` -> `() => _asyncToGenerator(this, void 0, function* () { return a; })`
+        if expr.expression {
+            if let Some(Statement::ExpressionStatement(stmt)) = expr.body.statements.pop() {
+                let stmt = stmt.unbox();
+                let argument = Some(stmt.expression);
+                expr.body.statements.push(self.ast.return_statement(stmt.span, argument));
+            }
+        }
+        expr.r#async = false;
+        // Arrow functions reference the `arguments` of the function containing them
+        let arguments = if uses_arguments == Some(true) {
+            self.ast.ident("arguments")
+        } else {
+            self.ast.void_0()
+        };
+        let has_declarations = declarations.is_some();
+        self.wrap_body(expr.span, &mut expr.body, declarations, arguments);
+        if !has_declarations {
+            // `{ return call; }` -> `call`
+            if let Some(Statement::ReturnStatement(stmt)) = expr.body.statements.pop() {
+                let call = stmt.unbox().argument.expect("the call is returned");
+                expr.body.statements.push(self.ast.statement(call));
+            }
+        }
+        expr.expression = !has_declarations;
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        self.visit_expression_match(expr);
+        if !self.functions.last().copied().unwrap_or_default() {
+            return;
+        }
+        // `await a` -> `(yield a)`
+        if let Expression::AwaitExpression(await_expr) = expr {
+            let span = await_expr.span;
+            let argument = mem::replace(&mut await_expr.argument, self.ast.void_0());
+            let yield_expr = self.ast.yield_expression(span, false, Some(argument));
+            *expr = self.ast.parenthesized_expression(span, yield_expr);
+        }
+    }
+}
+
+/// Whether the async function with `body` can be lowered, returning whether it references the
+/// `arguments` of the function containing it, which only matters for arrow functions
+fn can_lower(body: &mut FunctionBody) -> Option<bool> {
+    let mut analysis = Analysis { unsupported: false, uses_arguments: false };
+    analysis.visit_function_body(body);
+    (!analysis.unsupported).then_some(analysis.uses_arguments)
+}
+
+/// Finds the syntax of an async function which is not supported in generators
+struct Analysis {
+    unsupported: bool,
+    uses_arguments: bool,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Analysis {
+    fn visit_for_of_statement(&mut self, stmt: &'b mut ForOfStatement<'a>) {
+        self.unsupported |= stmt.r#await;
+        self.visit_for_statement_left(&mut stmt.left);
+        self.visit_expression(&mut stmt.right);
+        self.visit_statement(&mut stmt.body);
+    }
+
+    fn visit_assignment_expression(&mut self, expr: &'b mut AssignmentExpression<'a>) {
+        // `super.a = b` is supported, `super.a += b` is not
+        if expr.operator == AssignmentOperator::Assign {
+            if let AssignmentTarget::SimpleAssignmentTarget(
+                SimpleAssignmentTarget::MemberAssignmentTarget(member),
+            ) = &mut expr.left
+            {
+                self.visit_member_expression(member);
+                self.visit_expression(&mut expr.right);
+                return;
+            }
+        }
+        self.visit_assignment_target(&mut expr.left);
+        self.visit_expression(&mut expr.right);
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &'b mut SimpleAssignmentTarget<'a>) {
+        if let SimpleAssignmentTarget::MemberAssignmentTarget(member) = target {
+            self.unsupported |= matches!(member.object(), Expression::Super(_));
+            self.visit_member_expression(member);
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &'b mut IdentifierReference) {
+        self.uses_arguments |= ident.name == "arguments";
+    }
+
+    // Functions have their own `arguments` and `super`, arrow functions do not
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+    }
+}
+
+/// An arrow function reading or writing a property of `super`
+struct SuperAccessor {
+    /// The name of the property, or `None` for computed properties, which are parameters
+    property: Option<Atom>,
+    set: bool,
+}
+
+/// Replaces `super.a` with `_superprop_getA()`, `super.a(b)` with
+/// `_superprop_getA().call(this, b)` and `super.a = b` with `_superprop_setA(b)`
+struct SuperProperties<'a, 't> {
+    ast: AstBuilder<'a>,
+    temporaries: &'t mut Temporaries<'a>,
+    /// The names of the accessors created so far, by the name of their property and whether
+    /// they write it
+    accessors: HashMap<(Option<Atom>, bool), Atom>,
+    declarations: std::vec::Vec<(Atom, SuperAccessor)>,
+}
+
+impl<'a, 't> SuperProperties<'a, 't> {
+    /// A call of the accessor of the property of `super` in `member`, which writes `value`
+    fn access(
+        &mut self,
+        member: &mut MemberExpression<'a>,
+        value: Option<Expression<'a>>,
+    ) -> Option<Expression<'a>> {
+        let (property, key) = match member {
+            MemberExpression::StaticMemberExpression(member) => {
+                (Some(member.property.name.clone()), None)
+            }
+            MemberExpression::ComputedMemberExpression(member) => {
+                let key = mem::replace(&mut member.expression, self.ast.void_0());
+                (None, Some(key))
+            }
+            MemberExpression::PrivateFieldExpression(_) => return None,
+        };
+        let set = value.is_some();
+        let name = self.accessor(property, set);
+        Some(self.ast.call(self.ast.ident(&name), key.into_iter().chain(value)))
+    }
+
+    fn accessor(&mut self, property: Option<Atom>, set: bool) -> Atom {
+        let key = (property.clone(), set);
+        if let Some(name) = self.accessors.get(&key) {
+            return name.clone();
+        }
+        let mut base = String::from(if set { "superprop_set" } else { "superprop_get" });
+        if let Some(property) = &property {
+            let mut chars = property.chars();
+            base.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            base.push_str(chars.as_str());
+        }
+        let name = self.temporaries.create_unscoped(&base);
+        self.accessors.insert(key, name.clone());
+        self.declarations.push((name.clone(), SuperAccessor { property, set }));
+        name
+    }
+
+    /// Whether `expr` references an accessor which reads a property
+    fn is_accessor(&self, expr: &Expression<'a>) -> bool {
+        let Expression::Identifier(ident) = expr else { return false };
+        self.accessors.iter().any(|((_, set), name)| !set && *name == ident.name)
+    }
+}
+
+impl<'a, 'b, 't> VisitMut<'a, 'b> for SuperProperties<'a, 't> {
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        let replacement = match expr {
+            Expression::MemberExpression(member) if is_super_property(member) => {
+                self.visit_member_expression(member);
+                self.access(member, None)
+            }
+            Expression::CallExpression(call) => {
+                for argument in call.arguments.iter_mut() {
+                    self.visit_argument(argument);
+                }
+                self.visit_expression(&mut call.callee);
+                let Expression::CallExpression(get) = &call.callee else { return };
+                if !self.is_accessor(&get.callee) {
+                    return;
+                }
+                // `_superprop_getA()(b)` -> `_superprop_getA().call(this, b)`
+                let get = mem::replace(&mut call.callee, self.ast.void_0());
+                call.callee = self.ast.member(get, "call");
+                call.arguments.insert(0, Argument::Expression(self.ast.this_expression(SPAN)));
+                None
+            }
+            Expression::AssignmentExpression(assignment)
+                if assignment.operator == AssignmentOperator::Assign =>
+            {
+                let assignment = &mut **assignment;
+                let AssignmentTarget::SimpleAssignmentTarget(
+                    SimpleAssignmentTarget::MemberAssignmentTarget(member),
+                ) = &mut assignment.left
+                else {
+                    self.visit_assignment_expression(assignment);
+                    return;
+                };
+                if !is_super_property(member) {
+                    self.visit_assignment_expression(assignment);
+                    return;
+                }
+                self.visit_member_expression(member);
+                self.visit_expression(&mut assignment.right);
+                let value = mem::replace(&mut assignment.right, self.ast.void_0());
+                self.access(member, Some(value))
+            }
+            _ => {
+                self.visit_expression_match(expr);
+                None
+            }
+        };
+        if let Some(replacement) = replacement {
+            *expr = replacement;
+        }
+    }
+
+    // Functions have their own `super`, arrow functions do not
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+    }
+}
+
+fn is_super_property(member: &MemberExpression) -> bool {
+    matches!(member.object(), Expression::Super(_))
+}
@@ -0,0 +1,253 @@
+//! ES2020: optional chaining and nullish coalescing
+//!
+//! * `a?.b` -> `(a === null || a === void 0 ? void 0 : a.b)`
+//! * `a ?? b` -> `(a !== null && a !== void 0 ? a : b)`
+
+use std::mem;
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::SPAN;
+use oxc_syntax::operator::{BinaryOperator, LogicalOperator, UnaryOperator};
+
+use crate::temporaries::Temporaries;
+
+/// Lowers optional chains and `??` to conditional expressions, memoizing the checked values
+/// which are not identifiers or `this` in temporaries named `_ref`
+pub struct Es2020<'a> {
+    ast: AstBuilder<'a>,
+    temporaries: Temporaries<'a>,
+}
+
+impl<'a> Es2020<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self { ast: AstBuilder::new(allocator), temporaries: Temporaries::new(allocator) }
+    }
+
+    /// `a?.b.c` -> `(a === null || a === void 0 ? void 0 : a.b.c)`, or with `delete` in front
+    /// `(a === null || a === void 0 ? true : delete a.b.c)`
+    fn transform_chain_expression(
+        &mut self,
+        chain: ChainExpression<'a>,
+        is_delete: bool,
+    ) -> Expression<'a> {
+        let expr = match chain.expression {
+            ChainElement::CallExpression(call) => Expression::CallExpression(call),
+            ChainElement::MemberExpression(member) => Expression::MemberExpression(member),
+        };
+        let mut checks = Vec::new();
+        let mut expr = self.transform_chain_link(expr, &mut checks);
+        let short_circuit = if is_delete {
+            expr = self.ast.unary_expression(chain.span, UnaryOperator::Delete, expr);
+            self.ast.boolean_literal_expression(true)
+        } else {
+            self.ast.void_0()
+        };
+        let or = |left, right| self.ast.logical_expression(SPAN, left, LogicalOperator::Or, right);
+        let Some(test) = checks.into_iter().reduce(or) else {
+            return expr;
+        };
+        let expr = self.ast.conditional_expression(chain.span, test, short_circuit, expr);
+        self.ast.parenthesized_expression(chain.span, expr)
+    }
+
+    /// Removes the optional flags of the links in the chain `expr`, adding the checks of the
+    /// values they short-circuit on to `checks` in evaluation order
+    fn transform_chain_link(
+        &mut self,
+        expr: Expression<'a>,
+        checks: &mut Vec<Expression<'a>>,
+    ) -> Expression<'a> {
+        match expr {
+            Expression::MemberExpression(mut member) => {
+                self.transform_member_object(&mut member, checks);
+                Expression::MemberExpression(member)
+            }
+            Expression::CallExpression(mut call) => {
+                let callee = mem::replace(&mut call.callee, self.ast.null_literal_expression());
+                call.callee = match callee {
+                    // `a.b?.()` -> `... ? void 0 : _ref.call(a)`, checking `_ref = a.b`
+                    Expression::MemberExpression(mut member) if call.optional => {
+                        self.transform_member_object(&mut member, checks);
+                        let (object, _) = member_object(&mut member);
+                        let this = if matches!(object, Expression::Super(_)) {
+                            self.ast.this_expression(SPAN)
+                        } else {
+                            let value = mem::replace(object, self.ast.null_literal_expression());
+                            let (value, this) = self.temporaries.memoize(value, "ref");
+                            *object = value;
+                            this
+                        };
+                        call.arguments.insert(0, Argument::Expression(this));
+                        let callee = self.check(Expression::MemberExpression(member), checks);
+                        self.ast.member_expression_by_name(callee, "call")
+                    }
+                    callee if call.optional => {
+                        let callee = self.transform_chain_link(callee, checks);
+                        self.check(callee, checks)
+                    }
+                    callee => self.transform_chain_link(callee, checks),
+                };
+                call.optional = false;
+                Expression::CallExpression(call)
+            }
+            expr => expr,
+        }
+    }
+
+    fn transform_member_object(
+        &mut self,
+        member: &mut MemberExpression<'a>,
+        checks: &mut Vec<Expression<'a>>,
+    ) {
+        let (object, optional) = member_object(member);
+        let value = mem::replace(object, self.ast.null_literal_expression());
+        let mut value = self.transform_chain_link(value, checks);
+        if mem::take(optional) {
+            value = self.check(value, checks);
+        }
+        *object = value;
+    }
+
+    /// Adds `(_ref = expr) === null || _ref === void 0` to `checks`, returns `_ref`
+    fn check(&mut self, expr: Expression<'a>, checks: &mut Vec<Expression<'a>>) -> Expression<'a> {
+        let (value, reference) = self.temporaries.memoize(expr, "ref");
+        let null = self.ast.null_literal_expression();
+        let is_null = self.ast.binary_expression(SPAN, value, BinaryOperator::StrictEquality, null);
+        let is_undefined = self.ast.binary_expression(
+            SPAN,
+            self.reference(&reference),
+            BinaryOperator::StrictEquality,
+            self.ast.void_0(),
+        );
+        checks.push(self.ast.logical_expression(SPAN, is_null, LogicalOperator::Or, is_undefined));
+        reference
+    }
+
+    /// `a ?? b` -> `(a !== null && a !== void 0 ? a : b)`
+    fn transform_nullish_coalescing(&mut self, expr: LogicalExpression<'a>) -> Expression<'a> {
+        let (value, reference) = self.temporaries.memoize(expr.left, "ref");
+        let null = self.ast.null_literal_expression();
+        let is_not_null =
+            self.ast.binary_expression(SPAN, value, BinaryOperator::StrictInequality, null);
+        let is_not_undefined = self.ast.binary_expression(
+            SPAN,
+            self.reference(&reference),
+            BinaryOperator::StrictInequality,
+            self.ast.void_0(),
+        );
+        let test =
+            self.ast.logical_expression(SPAN, is_not_null, LogicalOperator::And, is_not_undefined);
+        let span = expr.span;
+        let expr = self.ast.conditional_expression(span, test, reference, expr.right);
+        self.ast.parenthesized_expression(span, expr)
+    }
+
+    /// A copy of a reference returned by [`Temporaries::memoize`]
+    fn reference(&self, reference: &Expression<'a>) -> Expression<'a> {
+        self.temporaries.reference(reference).expect("references can be copied")
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Es2020<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        self.temporaries.enter_scope();
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
+        for directive in program.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut program.body);
+        self.temporaries.exit_scope(&mut program.body);
+    }
+
+    fn visit_function_body(&mut self, body: &'b mut FunctionBody<'a>) {
+        self.temporaries.enter_scope();
+        for directive in body.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut body.statements);
+        self.temporaries.exit_scope(&mut body.statements);
+    }
+
+    fn visit_formal_parameters(&mut self, params: &'b mut FormalParameters<'a>) {
+        self.temporaries.enter_scope();
+        for param in params.items.iter_mut() {
+            self.visit_formal_parameter(param);
+        }
+        if let Some(rest) = &mut params.rest {
+            self.visit_rest_element(rest);
+        }
+        self.temporaries.exit_parameters_scope(params);
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.temporaries.expand_arrow_body(expr);
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        // `delete a?.b` is transformed as a whole
+        if let Expression::UnaryExpression(unary) = expr {
+            if unary.operator == UnaryOperator::Delete {
+                if let Expression::ChainExpression(chain) = &mut unary.argument {
+                    self.visit_chain_expression(chain);
+                    let Expression::UnaryExpression(unary) =
+                        mem::replace(expr, self.ast.null_literal_expression())
+                    else {
+                        unreachable!()
+                    };
+                    let Expression::ChainExpression(chain) = unary.unbox().argument else {
+                        unreachable!()
+                    };
+                    *expr = self.transform_chain_expression(chain.unbox(), true);
+                    return;
+                }
+            }
+        }
+
+        self.visit_expression_match(expr);
+        match expr {
+            Expression::ChainExpression(_) => {
+                let Expression::ChainExpression(chain) =
+                    mem::replace(expr, self.ast.null_literal_expression())
+                else {
+                    unreachable!()
+                };
+                *expr = self.transform_chain_expression(chain.unbox(), false);
+            }
+            // `(a?.b).c` keeps one pair of parentheses
+            Expression::ParenthesizedExpression(paren)
+                if matches!(paren.expression, Expression::ParenthesizedExpression(_)) =>
+            {
+                *expr = mem::replace(&mut paren.expression, self.ast.null_literal_expression());
+            }
+            Expression::LogicalExpression(logical)
+                if logical.operator == LogicalOperator::Coalesce =>
+            {
+                let Expression::LogicalExpression(logical) =
+                    mem::replace(expr, self.ast.null_literal_expression())
+                else {
+                    unreachable!()
+                };
+                *expr = self.transform_nullish_coalescing(logical.unbox());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The object of `member` and whether it is accessed optionally
+fn member_object<'a, 'm>(
+    member: &'m mut MemberExpression<'a>,
+) -> (&'m mut Expression<'a>, &'m mut bool) {
+    match member {
+        MemberExpression::ComputedMemberExpression(expr) => (&mut expr.object, &mut expr.optional),
+        MemberExpression::StaticMemberExpression(expr) => (&mut expr.object, &mut expr.optional),
+        MemberExpression::PrivateFieldExpression(expr) => (&mut expr.object, &mut expr.optional),
+    }
+}
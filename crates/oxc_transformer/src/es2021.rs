@@ -0,0 +1,193 @@
+//! ES2021: logical assignment operators and numeric separators
+//!
+//! * `a ||= b` -> `a || (a = b)`
+//! * `1_000` -> `1000`
+
+use std::mem;
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{AssignmentOperator, LogicalOperator};
+
+use crate::temporaries::Temporaries;
+
+/// Lowers logical assignments to logical expressions, memoizing the objects and computed keys of
+/// member targets in temporaries named `_obj` and `_key`.
+///
+/// `a ??= b` is lowered to `a ?? (a = b)`, which is left to [`crate::Es2020`].
+pub struct Es2021<'a> {
+    ast: AstBuilder<'a>,
+    temporaries: Temporaries<'a>,
+}
+
+impl<'a> Es2021<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self { ast: AstBuilder::new(allocator), temporaries: Temporaries::new(allocator) }
+    }
+
+    /// `a.b ||= c` -> `a.b || (a.b = c)`, `a()[b()] ||= c` ->
+    /// `(_obj = a())[(_key = b())] || (_obj[_key] = c)`
+    ///
+    /// The logical expression binds tighter than the assignment it replaces, so it is not
+    /// parenthesized.
+    fn transform_logical_assignment(&mut self, expr: AssignmentExpression<'a>) -> Expression<'a> {
+        let operator = match expr.operator {
+            AssignmentOperator::LogicalOr => LogicalOperator::Or,
+            AssignmentOperator::LogicalAnd => LogicalOperator::And,
+            AssignmentOperator::LogicalNullish => LogicalOperator::Coalesce,
+            _ => unreachable!(),
+        };
+        let AssignmentTarget::SimpleAssignmentTarget(target) = expr.left else { unreachable!() };
+        let (read, write) = match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                let read = self.ast.identifier_expression((*ident).clone());
+                (read, SimpleAssignmentTarget::AssignmentTargetIdentifier(ident))
+            }
+            SimpleAssignmentTarget::MemberAssignmentTarget(member) => {
+                let (read, write) = self.transform_member(member.unbox());
+                (read, SimpleAssignmentTarget::MemberAssignmentTarget(self.ast.alloc(write)))
+            }
+            _ => unreachable!(),
+        };
+        let assignment = self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(write),
+            expr.right,
+        );
+        let assignment = self.ast.parenthesized_expression(SPAN, assignment);
+        self.ast.logical_expression(expr.span, read, operator, assignment)
+    }
+
+    /// The member expression to read and the member expression to assign, which evaluate the
+    /// object and computed key of `member` only once
+    fn transform_member(
+        &mut self,
+        member: MemberExpression<'a>,
+    ) -> (Expression<'a>, MemberExpression<'a>) {
+        match member {
+            MemberExpression::ComputedMemberExpression(expr) => {
+                let (object, object_ref) = self.temporaries.memoize(expr.object, "obj");
+                let (key, key_ref) = self.temporaries.memoize(expr.expression, "key");
+                let read = self.ast.computed_member_expression(expr.span, object, key, false);
+                let write = ComputedMemberExpression {
+                    span: expr.span,
+                    object: object_ref,
+                    expression: key_ref,
+                    optional: false,
+                };
+                (read, MemberExpression::ComputedMemberExpression(write))
+            }
+            MemberExpression::StaticMemberExpression(expr) => {
+                let (object, object_ref) = self.temporaries.memoize(expr.object, "obj");
+                let read = self.ast.static_member_expression(
+                    expr.span,
+                    object,
+                    expr.property.clone(),
+                    false,
+                );
+                let write = StaticMemberExpression {
+                    span: expr.span,
+                    object: object_ref,
+                    property: expr.property,
+                    optional: false,
+                };
+                (read, MemberExpression::StaticMemberExpression(write))
+            }
+            MemberExpression::PrivateFieldExpression(expr) => {
+                let (object, object_ref) = self.temporaries.memoize(expr.object, "obj");
+                let read =
+                    self.ast.private_field_expression(expr.span, object, expr.field.clone(), false);
+                let write = PrivateFieldExpression {
+                    span: expr.span,
+                    object: object_ref,
+                    field: expr.field,
+                    optional: false,
+                };
+                (read, MemberExpression::PrivateFieldExpression(write))
+            }
+        }
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Es2021<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        self.temporaries.enter_scope();
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
+        for directive in program.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut program.body);
+        self.temporaries.exit_scope(&mut program.body);
+    }
+
+    fn visit_function_body(&mut self, body: &'b mut FunctionBody<'a>) {
+        self.temporaries.enter_scope();
+        for directive in body.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut body.statements);
+        self.temporaries.exit_scope(&mut body.statements);
+    }
+
+    fn visit_formal_parameters(&mut self, params: &'b mut FormalParameters<'a>) {
+        self.temporaries.enter_scope();
+        for param in params.items.iter_mut() {
+            self.visit_formal_parameter(param);
+        }
+        if let Some(rest) = &mut params.rest {
+            self.visit_rest_element(rest);
+        }
+        self.temporaries.exit_parameters_scope(params);
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.temporaries.expand_arrow_body(expr);
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        self.visit_expression_match(expr);
+        let Expression::AssignmentExpression(assignment) = expr else { return };
+        let is_logical = matches!(
+            assignment.operator,
+            AssignmentOperator::LogicalOr
+                | AssignmentOperator::LogicalAnd
+                | AssignmentOperator::LogicalNullish
+        );
+        // Only identifiers and member expressions can be targets of logical assignments
+        let is_lowered = matches!(
+            assignment.left,
+            AssignmentTarget::SimpleAssignmentTarget(
+                SimpleAssignmentTarget::AssignmentTargetIdentifier(_)
+                    | SimpleAssignmentTarget::MemberAssignmentTarget(_)
+            )
+        );
+        if is_logical && is_lowered {
+            let Expression::AssignmentExpression(assignment) =
+                mem::replace(expr, self.ast.null_literal_expression())
+            else {
+                unreachable!()
+            };
+            *expr = self.transform_logical_assignment(assignment.unbox());
+        }
+    }
+
+    fn visit_number_literal(&mut self, lit: &'b mut NumberLiteral<'a>) {
+        if lit.raw.contains('_') {
+            lit.raw = self.ast.new_str(&lit.raw.replace('_', ""));
+        }
+    }
+
+    fn visit_bigint_literal(&mut self, lit: &'b mut BigintLiteral) {
+        if lit.raw.contains('_') {
+            lit.raw = Atom::from(lit.raw.replace('_', ""));
+        }
+    }
+}
@@ -0,0 +1,1234 @@
+//! ES2022: class fields, private methods and static blocks
+//!
+//! * `class A { a = 1; }` ->
+//!   `class A { constructor() { Object.defineProperty(this, "a", { value: 1, ... }); } }`
+//! * `class A { #a = 1; f() { return this.#a; } }` ->
+//!   `var _a = new WeakMap(); class A { constructor() { _a.set(this, 1); }
+//!   f() { return _classPrivateFieldGet(this, _a); } }`
+//! * `class A { static a = 1; static { f(); } }` ->
+//!   `class A {} Object.defineProperty(A, "a", { value: 1, ... }); (() => { f(); })();`
+//! * `class A { [f()] = 1; [g()]() {} }` ->
+//!   `var _key; class A { constructor() { ... } [(_key = f(), g())]() {} }`
+//!
+//! `this`, `super` and `new.target` in the initializers and static blocks moved out of the class
+//! are replaced with what they refer to. Private accessors are kept as they are.
+
+use std::{collections::HashMap, mem};
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{
+    AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator, UpdateOperator,
+};
+
+use crate::{constructor, shorthands::Shorthands, temporaries::Temporaries};
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClassPropertiesOptions {
+    /// Assign public fields, i.e. `this.a = 1`, instead of defining them with
+    /// `Object.defineProperty`, which is smaller but calls the setters of the super classes
+    pub loose: bool,
+}
+
+/// Functions checking that an object has a private name, declared at the start of the program
+/// when they are used
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
+enum Helper {
+    PrivateFieldGet,
+    PrivateFieldSet,
+    PrivateFieldDestructureSet,
+    PrivateMethodGet,
+}
+
+impl Helper {
+    fn name(self) -> &'static str {
+        match self {
+            Self::PrivateFieldGet => "classPrivateFieldGet",
+            Self::PrivateFieldSet => "classPrivateFieldSet",
+            Self::PrivateFieldDestructureSet => "classPrivateFieldDestructureSet",
+            Self::PrivateMethodGet => "classPrivateMethodGet",
+        }
+    }
+}
+
+/// Where the values of a private name are stored
+#[derive(Clone)]
+struct PrivateName {
+    /// The `WeakMap` from the objects to the values of a field, or the `WeakSet` of the objects
+    /// which have a method
+    storage: Atom,
+    /// The function of a method
+    method: Option<Atom>,
+}
+
+/// The parts of a lowered class which are evaluated around it
+struct LoweredClass<'a> {
+    /// Variables initialized before the class, e.g. `_a = new WeakMap()`
+    before: std::vec::Vec<(Atom, Expression<'a>)>,
+    /// The name of the class in `after`, which the caller declares for class expressions
+    binding: Option<Atom>,
+    /// The computed keys of fields and the static initializers, evaluated after the class
+    after: std::vec::Vec<Expression<'a>>,
+}
+
+/// Lowers class fields to definitions in the constructor, private names to `WeakMap`s and
+/// `WeakSet`s and static initializers to expressions after the class.
+///
+/// Optional chains ending in private fields are only lowered correctly together with
+/// [`crate::Es2020`].
+pub struct Es2022<'a> {
+    ast: AstBuilder<'a>,
+    options: ClassPropertiesOptions,
+    temporaries: Temporaries<'a>,
+    /// The private names of the classes being visited, innermost last, which are `None` for
+    /// private accessors
+    private_names: std::vec::Vec<HashMap<Atom, Option<PrivateName>>>,
+    /// The class which was just visited, which is spread into the statement or expression
+    /// containing it
+    lowered: Option<LoweredClass<'a>>,
+    /// The helpers used, in the order of their first use
+    helpers: std::vec::Vec<(Helper, Atom)>,
+    /// Whether the assignment targets being visited are in a pattern or on the left of a `for-in`
+    /// or `for-of` statement
+    destructuring: bool,
+}
+
+impl<'a> Es2022<'a> {
+    pub fn new(allocator: &'a Allocator, options: ClassPropertiesOptions) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            options,
+            temporaries: Temporaries::new(allocator),
+            private_names: vec![],
+            lowered: None,
+            helpers: vec![],
+            destructuring: false,
+        }
+    }
+
+    /// Lowers the fields, private methods and static blocks of `class` after its elements were
+    /// visited, leaving the code around it in `self.lowered`
+    fn lower_class(
+        &mut self,
+        class: &mut Class<'a>,
+        names: &HashMap<Atom, Option<PrivateName>>,
+        mut before: std::vec::Vec<(Atom, Expression<'a>)>,
+    ) {
+        let has_statics = class.body.body.iter().any(|element| match element {
+            ClassElement::StaticBlock(_) => true,
+            ClassElement::PropertyDefinition(def) => def.r#static,
+            ClassElement::MethodDefinition(def) => {
+                def.r#static && private_method(names, &def.key).is_some()
+            }
+            _ => false,
+        });
+        let binding = has_statics.then(|| match &class.id {
+            Some(id) if class.is_declaration() => id.name.clone(),
+            id => {
+                self.temporaries.create_unscoped(id.as_ref().map_or("Class", |id| id.name.as_str()))
+            }
+        });
+        if let (Some(binding), None) = (&binding, &class.id) {
+            if class.is_declaration() {
+                class.id = Some(BindingIdentifier { span: SPAN, name: binding.clone() });
+            }
+        }
+        let class_reference = |ast: &AstBuilder<'a>| {
+            let binding = binding.as_ref().expect("classes with static elements have a binding");
+            ast.identifier_reference_expression(binding)
+        };
+
+        let mut method_brands = vec![];
+        let mut initializers = vec![];
+        // The computed keys of fields, which are evaluated with the next computed key of the
+        // class or after it, in the order of the elements
+        let mut keys = vec![];
+        let mut static_method_brands = vec![];
+        let mut statics = vec![];
+        let elements = mem::replace(&mut class.body.body, self.ast.new_vec());
+        for element in elements {
+            match element {
+                ClassElement::PropertyDefinition(def) => {
+                    let def = def.unbox();
+                    let mut value = def.value.unwrap_or_else(|| self.ast.void_0());
+                    let class_binding = def.r#static.then(|| binding.clone()).flatten();
+                    self.initializer(class_binding).visit_expression(&mut value);
+                    if def.r#static {
+                        let class = class_reference(&self.ast);
+                        let field = self.field(names, class, def.key, def.computed, value);
+                        statics.push(field.with_key(&mut keys));
+                    } else {
+                        let this = self.ast.this_expression(SPAN);
+                        let field = self.field(names, this, def.key, def.computed, value);
+                        initializers.push(field.with_key(&mut keys));
+                    }
+                }
+                ClassElement::MethodDefinition(mut def) => {
+                    let Some((storage, method)) = private_method(names, &def.key) else {
+                        if def.computed {
+                            self.evaluate_keys(&mut keys, &mut def.key);
+                        }
+                        class.body.body.push(ClassElement::MethodDefinition(def));
+                        continue;
+                    };
+                    let def = def.unbox();
+                    let object = if def.r#static {
+                        class_reference(&self.ast)
+                    } else {
+                        self.ast.this_expression(SPAN)
+                    };
+                    let add = self.ast.member_expression_by_name(self.identifier(&storage), "add");
+                    let add = self.ast.call_expression_with_arguments(add, [object]);
+                    if def.r#static {
+                        static_method_brands.push(add);
+                    } else {
+                        method_brands.push(add);
+                    }
+                    before.push((method, self.ast.function_expression(def.value)));
+                }
+                ClassElement::StaticBlock(block) => {
+                    let mut body = block.unbox().body;
+                    let mut initializer = self.initializer(binding.clone());
+                    for stmt in body.iter_mut() {
+                        initializer.visit_statement(stmt);
+                    }
+                    statics.push(self.immediately_invoked(body));
+                }
+                ClassElement::AccessorProperty(mut def) if def.computed => {
+                    self.evaluate_keys(&mut keys, &mut def.key);
+                    class.body.body.push(ClassElement::AccessorProperty(def));
+                }
+                element => class.body.body.push(element),
+            }
+        }
+
+        method_brands.extend(initializers);
+        if !method_brands.is_empty() {
+            constructor::add_initializers(&self.ast, &mut self.temporaries, class, method_brands);
+        }
+        let mut after = keys
+            .into_iter()
+            .map(|(name, key)| self.assign(&name, key))
+            .collect::<std::vec::Vec<_>>();
+        after.extend(static_method_brands);
+        after.extend(statics);
+        self.lowered = Some(LoweredClass { before, binding, after });
+    }
+
+    /// Evaluates the computed keys of the fields before the computed `key` of the element after
+    /// them: `[a]` -> `[(_key = f(), a)]`
+    fn evaluate_keys(
+        &self,
+        keys: &mut std::vec::Vec<(Atom, Expression<'a>)>,
+        key: &mut PropertyKey<'a>,
+    ) {
+        let PropertyKey::Expression(key) = key else { return };
+        if keys.is_empty() {
+            return;
+        }
+        let mut expressions = self.ast.new_vec_with_capacity(keys.len() + 1);
+        expressions.extend(keys.drain(..).map(|(name, key)| self.assign(&name, key)));
+        expressions.push(mem::replace(key, self.ast.null_literal_expression()));
+        let sequence = self.ast.sequence_expression(SPAN, expressions);
+        *key = self.ast.parenthesized_expression(SPAN, sequence);
+    }
+
+    /// Replaces `new.target` in an initializer, and `this` and `super` in a static initializer
+    /// of the class `binding`
+    fn initializer(&mut self, binding: Option<Atom>) -> Initializer<'a, '_> {
+        Initializer {
+            ast: AstBuilder::new(self.ast.allocator),
+            temporaries: &mut self.temporaries,
+            class: binding,
+        }
+    }
+
+    /// The definition of the field `key` of `object`, whose computed key is evaluated with
+    /// the class into a temporary
+    fn field(
+        &mut self,
+        names: &HashMap<Atom, Option<PrivateName>>,
+        object: Expression<'a>,
+        key: PropertyKey<'a>,
+        computed: bool,
+        value: Expression<'a>,
+    ) -> Field<'a> {
+        let key = match key {
+            PropertyKey::PrivateIdentifier(ident) => {
+                let storage = names
+                    .get(&ident.name)
+                    .and_then(Option::as_ref)
+                    .map(|name| name.storage.clone())
+                    .expect("private fields are collected before they are lowered");
+                let set = self.ast.member_expression_by_name(self.identifier(&storage), "set");
+                let set = self.ast.call_expression_with_arguments(set, [object, value]);
+                return Field { definition: set, key: None };
+            }
+            PropertyKey::Expression(expr)
+                if computed
+                    && !matches!(
+                        expr,
+                        Expression::StringLiteral(_) | Expression::NumberLiteral(_)
+                    ) =>
+            {
+                let name = self.temporaries.create("key");
+                let definition = self.public_field(
+                    object,
+                    PropertyKey::Expression(self.identifier(&name)),
+                    value,
+                );
+                return Field { definition, key: Some((name, expr)) };
+            }
+            key => key,
+        };
+        Field { definition: self.public_field(object, key, value), key: None }
+    }
+
+    /// `Object.defineProperty(object, "a", { value, writable: true, enumerable: true,
+    /// configurable: true })`, or `object.a = value` in loose mode
+    fn public_field(
+        &self,
+        object: Expression<'a>,
+        key: PropertyKey<'a>,
+        value: Expression<'a>,
+    ) -> Expression<'a> {
+        if self.options.loose {
+            let member = match key {
+                PropertyKey::Identifier(ident) => {
+                    MemberExpression::StaticMemberExpression(StaticMemberExpression {
+                        span: SPAN,
+                        object,
+                        property: ident.unbox(),
+                        optional: false,
+                    })
+                }
+                PropertyKey::Expression(expression) => {
+                    MemberExpression::ComputedMemberExpression(ComputedMemberExpression {
+                        span: SPAN,
+                        object,
+                        expression,
+                        optional: false,
+                    })
+                }
+                PropertyKey::PrivateIdentifier(_) => unreachable!(),
+            };
+            let target = SimpleAssignmentTarget::MemberAssignmentTarget(self.ast.alloc(member));
+            return self.ast.assignment_expression(
+                SPAN,
+                AssignmentOperator::Assign,
+                AssignmentTarget::SimpleAssignmentTarget(target),
+                value,
+            );
+        }
+        let key = match key {
+            PropertyKey::Identifier(ident) => self.ast.string_literal_expression(&ident.name),
+            PropertyKey::Expression(expr) => expr,
+            PropertyKey::PrivateIdentifier(_) => unreachable!(),
+        };
+        let mut properties = self.ast.new_vec_with_capacity(4);
+        properties.push(self.property("value", value));
+        for name in ["writable", "enumerable", "configurable"] {
+            properties.push(self.property(name, self.ast.boolean_literal_expression(true)));
+        }
+        let descriptor = self.ast.object_expression(SPAN, properties, None);
+        let define =
+            self.ast.member_expression_by_name(self.identifier("Object"), "defineProperty");
+        self.ast.call_expression_with_arguments(define, [object, key, descriptor])
+    }
+
+    /// `(() => { body })()`
+    fn immediately_invoked(&self, body: Vec<'a, Statement<'a>>) -> Expression<'a> {
+        let kind = FormalParameterKind::ArrowFormalParameters;
+        let params = self.ast.formal_parameters(SPAN, kind, self.ast.new_vec(), None);
+        let body = self.ast.function_body(SPAN, self.ast.new_vec(), body);
+        let arrow = self.ast.arrow_expression(SPAN, false, false, false, params, body, None, None);
+        let arrow = self.ast.parenthesized_expression(SPAN, arrow);
+        self.ast.call_expression_with_arguments(arrow, [])
+    }
+
+    /// `(_a = new WeakMap(), _A = class A {}, statics, _A)`
+    fn transform_class_expression(
+        &mut self,
+        class: Expression<'a>,
+        lowered: LoweredClass<'a>,
+    ) -> Expression<'a> {
+        let mut expressions = self.ast.new_vec();
+        for (name, init) in lowered.before {
+            self.temporaries.declare(name.clone());
+            expressions.push(self.assign(&name, init));
+        }
+        if lowered.after.is_empty() {
+            expressions.push(class);
+        } else {
+            // Classes with only computed keys after them have no binding yet
+            let binding =
+                lowered.binding.unwrap_or_else(|| self.temporaries.create_unscoped("Class"));
+            self.temporaries.declare(binding.clone());
+            expressions.push(self.assign(&binding, class));
+            expressions.extend(lowered.after);
+            expressions.push(self.identifier(&binding));
+        }
+        let expr = self.ast.sequence_expression(SPAN, expressions);
+        self.ast.parenthesized_expression(SPAN, expr)
+    }
+
+    /// The private name accessed by `expr`, if it is lowered
+    fn private_name(&self, member: &MemberExpression<'a>) -> Option<PrivateName> {
+        let MemberExpression::PrivateFieldExpression(expr) = member else { return None };
+        self.resolve(&expr.field.name)
+    }
+
+    fn resolve(&self, name: &Atom) -> Option<PrivateName> {
+        self.private_names.iter().rev().find_map(|names| names.get(name)).cloned().flatten()
+    }
+
+    /// `a.#b` -> `_classPrivateFieldGet(a, _b)`, `a.#c` -> `_classPrivateMethodGet(a, _c, _c2)`
+    fn private_get(&mut self, name: &PrivateName, object: Expression<'a>) -> Expression<'a> {
+        let storage = self.identifier(&name.storage);
+        match &name.method {
+            None => {
+                let get = self.helper(Helper::PrivateFieldGet);
+                self.ast.call_expression_with_arguments(get, [object, storage])
+            }
+            Some(method) => {
+                let get = self.helper(Helper::PrivateMethodGet);
+                let method = self.identifier(method);
+                self.ast.call_expression_with_arguments(get, [object, storage, method])
+            }
+        }
+    }
+
+    /// `a?.#b` -> `(_obj = a) === null || _obj === void 0 ? void 0 : _classPrivateFieldGet(...)`
+    fn private_field_get(&mut self, expr: PrivateFieldExpression<'a>) -> Expression<'a> {
+        let name = self.resolve(&expr.field.name).expect("lowered private names are resolved");
+        if !expr.optional {
+            return self.private_get(&name, expr.object);
+        }
+        let (value, reference) = self.temporaries.memoize(expr.object, "obj");
+        let copy = self.temporaries.reference(&reference).expect("references can be copied");
+        let null = self.ast.null_literal_expression();
+        let is_null = self.ast.binary_expression(SPAN, value, BinaryOperator::StrictEquality, null);
+        let undefined = self.ast.void_0();
+        let is_undefined =
+            self.ast.binary_expression(SPAN, copy, BinaryOperator::StrictEquality, undefined);
+        let test = self.ast.logical_expression(SPAN, is_null, LogicalOperator::Or, is_undefined);
+        let get = self.private_get(&name, reference);
+        let expr = self.ast.conditional_expression(expr.span, test, self.ast.void_0(), get);
+        self.ast.parenthesized_expression(SPAN, expr)
+    }
+
+    /// `a.#b = c` -> `_classPrivateFieldSet(a, _b, c)`
+    fn private_set(
+        &mut self,
+        name: &PrivateName,
+        object: Expression<'a>,
+        value: Expression<'a>,
+    ) -> Expression<'a> {
+        let set = self.helper(Helper::PrivateFieldSet);
+        let storage = self.identifier(&name.storage);
+        self.ast.call_expression_with_arguments(set, [object, storage, value])
+    }
+
+    /// `a.#b += c` -> `_classPrivateFieldSet(_obj = a, _b, _classPrivateFieldGet(_obj, _b) + c)`,
+    /// `a.#b ||= c` -> `_classPrivateFieldGet(_obj = a, _b) || _classPrivateFieldSet(_obj, _b, c)`
+    fn transform_private_assignment(
+        &mut self,
+        name: &PrivateName,
+        expr: AssignmentExpression<'a>,
+    ) -> Expression<'a> {
+        let AssignmentTarget::SimpleAssignmentTarget(target) = expr.left else { unreachable!() };
+        let object = private_target_object(target);
+        if expr.operator == AssignmentOperator::Assign {
+            return self.private_set(name, object, expr.right);
+        }
+        let (object, reference) = self.temporaries.memoize(object, "obj");
+        if let Some(operator) = logical_operator(expr.operator) {
+            let get = self.private_get(name, object);
+            let set = self.private_set(name, reference, expr.right);
+            return self.ast.logical_expression(expr.span, get, operator, set);
+        }
+        let get = self.private_get(name, reference);
+        let right = binary_operand(&self.ast, expr.right);
+        let value = self.ast.binary_expression(SPAN, get, binary_operator(expr.operator), right);
+        self.private_set(name, object, value)
+    }
+
+    /// `++a.#b` ->
+    /// `_classPrivateFieldSet(a, _b, (_value = _classPrivateFieldGet(a, _b), ++_value))`,
+    /// `a.#b++` -> `(_classPrivateFieldSet(a, _b, (_value = ..., _old = _value++, _value)), _old)`
+    fn transform_private_update(
+        &mut self,
+        name: &PrivateName,
+        expr: UpdateExpression<'a>,
+    ) -> Expression<'a> {
+        let object = private_target_object(expr.argument);
+        let (object, reference) = self.temporaries.memoize(object, "obj");
+        let get = self.private_get(name, reference);
+        let value = self.temporaries.create("value");
+        let target = SimpleAssignmentTarget::AssignmentTargetIdentifier(
+            self.ast.alloc(IdentifierReference { span: SPAN, name: value.clone() }),
+        );
+        let update = self.ast.update_expression(SPAN, expr.operator, expr.prefix, target);
+        let mut expressions = self.ast.new_vec_with_capacity(3);
+        expressions.push(self.assign(&value, get));
+        let old = if expr.prefix {
+            expressions.push(update);
+            None
+        } else {
+            let old = self.temporaries.create("old");
+            expressions.push(self.assign(&old, update));
+            expressions.push(self.identifier(&value));
+            Some(old)
+        };
+        let sequence = self.ast.sequence_expression(SPAN, expressions);
+        let new_value = self.ast.parenthesized_expression(SPAN, sequence);
+        let set = self.private_set(name, object, new_value);
+        let Some(old) = old else { return set };
+        let mut expressions = self.ast.new_vec_with_capacity(2);
+        expressions.push(set);
+        expressions.push(self.identifier(&old));
+        let sequence = self.ast.sequence_expression(expr.span, expressions);
+        self.ast.parenthesized_expression(expr.span, sequence)
+    }
+
+    /// `[a.#b] = c` -> `[_classPrivateFieldDestructureSet(a, _b).value] = c`
+    fn transform_destructured_target(&mut self, target: &mut SimpleAssignmentTarget<'a>) {
+        let SimpleAssignmentTarget::MemberAssignmentTarget(member) = target else { return };
+        let Some(name) = self.private_name(member) else { return };
+        let MemberExpression::PrivateFieldExpression(member) = &mut **member else { return };
+        let object = mem::replace(&mut member.object, self.ast.null_literal_expression());
+        let set = self.helper(Helper::PrivateFieldDestructureSet);
+        let set = self.ast.call(set, [object, self.identifier(&name.storage)]);
+        let Expression::MemberExpression(value) = self.ast.member(set, "value") else {
+            unreachable!()
+        };
+        *target = SimpleAssignmentTarget::MemberAssignmentTarget(value);
+    }
+
+    /// A reference to `helper`, which is declared at the start of the program
+    fn helper(&mut self, helper: Helper) -> Expression<'a> {
+        if let Some((_, name)) = self.helpers.iter().find(|(h, _)| *h == helper) {
+            return self.identifier(&name.clone());
+        }
+        let name = self.temporaries.create_unscoped(helper.name());
+        self.helpers.push((helper, name.clone()));
+        self.identifier(&name)
+    }
+
+    /// ```javascript
+    /// function _classPrivateFieldGet(receiver, privateMap) {
+    ///     if (!privateMap.has(receiver)) throw new TypeError("Cannot read private member ...");
+    ///     return privateMap.get(receiver);
+    /// }
+    /// ```
+    ///
+    /// `_classPrivateFieldDestructureSet` returns
+    /// `{ set value(value) { privateMap.set(receiver, value); } }` instead.
+    fn helper_declaration(&self, helper: Helper, name: Atom) -> Statement<'a> {
+        let (params, message): (&[&str], _) = match helper {
+            Helper::PrivateFieldGet => (&["receiver", "privateMap"], "read"),
+            Helper::PrivateFieldSet => (&["receiver", "privateMap", "value"], "write"),
+            Helper::PrivateFieldDestructureSet => (&["receiver", "privateMap"], "write"),
+            Helper::PrivateMethodGet => (&["receiver", "privateSet", "fn"], "read"),
+        };
+        let receiver = || self.identifier("receiver");
+        let storage = || self.identifier(params[1]);
+
+        let mut statements = self.ast.new_vec_with_capacity(3);
+        let has = self.ast.member_expression_by_name(storage(), "has");
+        let has = self.ast.call_expression_with_arguments(has, [receiver()]);
+        let message = format!(
+            "Cannot {message} private member {} an object whose class did not declare it",
+            if message == "read" { "from" } else { "to" }
+        );
+        let message = self.ast.string_literal_expression(&message);
+        let arguments = self.ast.new_vec_single(Argument::Expression(message));
+        let error =
+            self.ast.new_expression(SPAN, self.identifier("TypeError"), arguments, None, false);
+        statements.push(self.ast.if_statement(
+            SPAN,
+            self.ast.unary_expression(SPAN, UnaryOperator::LogicalNot, has),
+            self.ast.throw_statement(SPAN, error),
+            None,
+        ));
+        let result = match helper {
+            Helper::PrivateFieldGet => {
+                let get = self.ast.member_expression_by_name(storage(), "get");
+                self.ast.call_expression_with_arguments(get, [receiver()])
+            }
+            Helper::PrivateFieldSet => {
+                let set = self.ast.member_expression_by_name(storage(), "set");
+                let set = self
+                    .ast
+                    .call_expression_with_arguments(set, [receiver(), self.identifier("value")]);
+                statements.push(self.ast.expression_statement(SPAN, set));
+                self.identifier("value")
+            }
+            Helper::PrivateFieldDestructureSet => {
+                let set = self.ast.member(storage(), "set");
+                let set = self.ast.call(set, [receiver(), self.identifier("value")]);
+                let body = self.ast.new_vec_single(self.ast.statement(set));
+                let setter =
+                    self.ast.function_of(FunctionType::FunctionExpression, None, &["value"], body);
+                let key = IdentifierName { span: SPAN, name: Atom::from("value") };
+                let property = self.ast.object_property(
+                    SPAN,
+                    PropertyKind::Set,
+                    PropertyKey::Identifier(self.ast.alloc(key)),
+                    self.ast.function_expression(setter),
+                    None,
+                    false,
+                    false,
+                    false,
+                );
+                let properties =
+                    self.ast.new_vec_single(ObjectPropertyKind::ObjectProperty(property));
+                self.ast.object_expression(SPAN, properties, None)
+            }
+            Helper::PrivateMethodGet => self.identifier("fn"),
+        };
+        statements.push(self.ast.return_statement(SPAN, Some(result)));
+
+        let mut items = self.ast.new_vec_with_capacity(params.len());
+        items.extend(params.iter().map(|param| {
+            let id = BindingIdentifier { span: SPAN, name: Atom::from(*param) };
+            let pattern = self.ast.binding_pattern(self.ast.binding_identifier(id), None, false);
+            self.ast.formal_parameter(SPAN, pattern, None, false, false, self.ast.new_vec())
+        }));
+        let kind = FormalParameterKind::FormalParameter;
+        let params = self.ast.formal_parameters(SPAN, kind, items, None);
+        let body = self.ast.function_body(SPAN, self.ast.new_vec(), statements);
+        let function = self.ast.function(
+            FunctionType::FunctionDeclaration,
+            SPAN,
+            Some(BindingIdentifier { span: SPAN, name }),
+            false,
+            false,
+            false,
+            params,
+            Some(body),
+            None,
+            None,
+            Modifiers::empty(),
+        );
+        self.ast.function_declaration(function)
+    }
+
+    /// `var _a = new WeakMap(), _b = function () {};`
+    fn var_declaration(
+        &self,
+        declarations: std::vec::Vec<(Atom, Expression<'a>)>,
+    ) -> Statement<'a> {
+        let kind = VariableDeclarationKind::Var;
+        let mut declarators = self.ast.new_vec_with_capacity(declarations.len());
+        declarators.extend(declarations.into_iter().map(|(name, init)| {
+            let id = self.ast.binding_identifier(BindingIdentifier { span: SPAN, name });
+            let id = self.ast.binding_pattern(id, None, false);
+            self.ast.variable_declarator(SPAN, kind, id, Some(init), false)
+        }));
+        let decl = self.ast.variable_declaration(SPAN, kind, declarators, Modifiers::empty());
+        Statement::Declaration(Declaration::VariableDeclaration(decl))
+    }
+
+    /// `/* #__PURE__ */ new WeakMap()`, which bundlers can remove with the unused class
+    fn new_instance(&self, constructor: &str) -> Expression<'a> {
+        let arguments = self.ast.new_vec();
+        self.ast.new_expression(SPAN, self.identifier(constructor), arguments, None, true)
+    }
+
+    /// `name = value`
+    fn assign(&self, name: &Atom, value: Expression<'a>) -> Expression<'a> {
+        let target = SimpleAssignmentTarget::AssignmentTargetIdentifier(
+            self.ast.alloc(IdentifierReference { span: SPAN, name: name.clone() }),
+        );
+        self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            value,
+        )
+    }
+
+    /// `name: value`
+    fn property(&self, name: &str, value: Expression<'a>) -> ObjectPropertyKind<'a> {
+        let key = IdentifierName { span: SPAN, name: Atom::from(name) };
+        ObjectPropertyKind::ObjectProperty(self.ast.object_property(
+            SPAN,
+            PropertyKind::Init,
+            PropertyKey::Identifier(self.ast.alloc(key)),
+            value,
+            None,
+            false,
+            false,
+            false,
+        ))
+    }
+
+    fn identifier(&self, name: &str) -> Expression<'a> {
+        self.ast.identifier_reference_expression(name)
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Es2022<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        self.temporaries.enter_scope();
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
+        for directive in program.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut program.body);
+        for (index, (helper, name)) in mem::take(&mut self.helpers).into_iter().enumerate() {
+            program.body.insert(index, self.helper_declaration(helper, name));
+        }
+        self.temporaries.exit_scope(&mut program.body);
+    }
+
+    fn visit_statements(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        let mut index = 0;
+        while index < stmts.len() {
+            self.visit_statement(&mut stmts[index]);
+            // The statement declared a class
+            if let Some(lowered) = self.lowered.take() {
+                if !lowered.before.is_empty() {
+                    stmts.insert(index, self.var_declaration(lowered.before));
+                    index += 1;
+                }
+                for expr in lowered.after {
+                    index += 1;
+                    stmts.insert(index, self.ast.expression_statement(SPAN, expr));
+                }
+            }
+            index += 1;
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &'b mut FunctionBody<'a>) {
+        self.temporaries.enter_scope();
+        for directive in body.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut body.statements);
+        self.temporaries.exit_scope(&mut body.statements);
+    }
+
+    fn visit_formal_parameters(&mut self, params: &'b mut FormalParameters<'a>) {
+        self.temporaries.enter_scope();
+        for param in params.items.iter_mut() {
+            self.visit_formal_parameter(param);
+        }
+        if let Some(rest) = &mut params.rest {
+            self.visit_rest_element(rest);
+        }
+        self.temporaries.exit_parameters_scope(params);
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.temporaries.expand_arrow_body(expr);
+    }
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        let mut names = HashMap::new();
+        let mut before = vec![];
+        for element in &class.body.body {
+            let (key, storage) = match element {
+                ClassElement::PropertyDefinition(def) => (&def.key, Some("WeakMap")),
+                ClassElement::MethodDefinition(def) => {
+                    (&def.key, (def.kind == MethodDefinitionKind::Method).then_some("WeakSet"))
+                }
+                _ => continue,
+            };
+            let PropertyKey::PrivateIdentifier(ident) = key else { continue };
+            let Some(constructor) = storage else {
+                names.insert(ident.name.clone(), None);
+                continue;
+            };
+            let storage = self.temporaries.create_unscoped(&ident.name);
+            let method =
+                (constructor == "WeakSet").then(|| self.temporaries.create_unscoped(&ident.name));
+            before.push((storage.clone(), self.new_instance(constructor)));
+            names.insert(ident.name.clone(), Some(PrivateName { storage, method }));
+        }
+
+        for decorator in class.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        // The heritage is outside of the private names of the class
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        self.private_names.push(names);
+        self.visit_class_body(&mut class.body);
+        let names = self.private_names.pop().unwrap_or_default();
+
+        let is_lowered = class.body.body.iter().any(|element| match element {
+            ClassElement::StaticBlock(_) | ClassElement::PropertyDefinition(_) => true,
+            ClassElement::MethodDefinition(def) => private_method(&names, &def.key).is_some(),
+            _ => false,
+        });
+        if is_lowered {
+            self.lower_class(class, &names, before);
+        }
+    }
+
+    fn visit_call_expression(&mut self, call: &'b mut CallExpression<'a>) {
+        for arg in call.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        let Expression::MemberExpression(member) = &mut call.callee else {
+            self.visit_expression(&mut call.callee);
+            return;
+        };
+        let Some(name) = self.private_name(member) else {
+            self.visit_expression(&mut call.callee);
+            return;
+        };
+        // `a.#b(c)` -> `_classPrivateFieldGet(_obj = a, _b).call(_obj, c)`
+        if let MemberExpression::PrivateFieldExpression(expr) = &mut **member {
+            self.visit_expression(&mut expr.object);
+        }
+        let Expression::MemberExpression(member) =
+            mem::replace(&mut call.callee, self.ast.null_literal_expression())
+        else {
+            unreachable!()
+        };
+        let MemberExpression::PrivateFieldExpression(expr) = member.unbox() else { unreachable!() };
+        let (object, this) = self.temporaries.memoize(expr.object, "obj");
+        let function = self.private_get(&name, object);
+        call.callee = self.ast.member_expression_by_name(function, "call");
+        call.arguments.insert(0, Argument::Expression(this));
+    }
+
+    fn visit_assignment_target(&mut self, target: &'b mut AssignmentTarget<'a>) {
+        match target {
+            AssignmentTarget::SimpleAssignmentTarget(target) => {
+                self.visit_simple_assignment_target(target);
+                if self.destructuring {
+                    self.transform_destructured_target(target);
+                }
+            }
+            AssignmentTarget::AssignmentTargetPattern(pattern) => {
+                let destructuring = mem::replace(&mut self.destructuring, true);
+                self.visit_assignment_target_pattern(pattern);
+                self.destructuring = destructuring;
+            }
+        }
+    }
+
+    fn visit_for_statement_left(&mut self, left: &'b mut ForStatementLeft<'a>) {
+        match left {
+            ForStatementLeft::VariableDeclaration(decl) => self.visit_variable_declaration(decl),
+            ForStatementLeft::AssignmentTarget(target) => {
+                let destructuring = mem::replace(&mut self.destructuring, true);
+                self.visit_assignment_target(target);
+                self.destructuring = destructuring;
+            }
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        // The expressions in patterns are not assigned to
+        let destructuring = mem::replace(&mut self.destructuring, false);
+        self.visit_expression_match(expr);
+        self.destructuring = destructuring;
+        match expr {
+            Expression::MemberExpression(member) if self.private_name(member).is_some() => {
+                let Expression::MemberExpression(member) =
+                    mem::replace(expr, self.ast.null_literal_expression())
+                else {
+                    unreachable!()
+                };
+                let MemberExpression::PrivateFieldExpression(member) = member.unbox() else {
+                    unreachable!()
+                };
+                *expr = self.private_field_get(member);
+            }
+            // `a?.#b` ends an optional chain
+            Expression::ChainExpression(chain)
+                if matches!(
+                    &chain.expression,
+                    ChainElement::MemberExpression(member) if self.private_name(member).is_some()
+                ) =>
+            {
+                let Expression::ChainExpression(chain) =
+                    mem::replace(expr, self.ast.null_literal_expression())
+                else {
+                    unreachable!()
+                };
+                let ChainElement::MemberExpression(member) = chain.unbox().expression else {
+                    unreachable!()
+                };
+                let MemberExpression::PrivateFieldExpression(mut member) = member.unbox() else {
+                    unreachable!()
+                };
+                // The links before keep short-circuiting
+                let object = mem::replace(&mut member.object, self.ast.null_literal_expression());
+                member.object = match object {
+                    Expression::MemberExpression(object) => {
+                        self.ast.chain_expression(SPAN, ChainElement::MemberExpression(object))
+                    }
+                    Expression::CallExpression(object) => {
+                        self.ast.chain_expression(SPAN, ChainElement::CallExpression(object))
+                    }
+                    object => object,
+                };
+                *expr = self.private_field_get(member);
+            }
+            Expression::AssignmentExpression(assignment) => {
+                let AssignmentTarget::SimpleAssignmentTarget(
+                    SimpleAssignmentTarget::MemberAssignmentTarget(member),
+                ) = &assignment.left
+                else {
+                    return;
+                };
+                let Some(name) = self.private_name(member) else { return };
+                let Expression::AssignmentExpression(assignment) =
+                    mem::replace(expr, self.ast.null_literal_expression())
+                else {
+                    unreachable!()
+                };
+                *expr = self.transform_private_assignment(&name, assignment.unbox());
+            }
+            Expression::UpdateExpression(update) => {
+                let SimpleAssignmentTarget::MemberAssignmentTarget(member) = &update.argument
+                else {
+                    return;
+                };
+                let Some(name) = self.private_name(member) else { return };
+                let Expression::UpdateExpression(update) =
+                    mem::replace(expr, self.ast.null_literal_expression())
+                else {
+                    unreachable!()
+                };
+                *expr = self.transform_private_update(&name, update.unbox());
+            }
+            // `#a in b` -> `_a.has(b)`
+            Expression::PrivateInExpression(private_in) => {
+                let Some(name) = self.resolve(&private_in.left.name) else { return };
+                let Expression::PrivateInExpression(private_in) =
+                    mem::replace(expr, self.ast.null_literal_expression())
+                else {
+                    unreachable!()
+                };
+                let has = self.ast.member_expression_by_name(self.identifier(&name.storage), "has");
+                let private_in = private_in.unbox();
+                let arguments = self.ast.new_vec_single(Argument::Expression(private_in.right));
+                *expr =
+                    self.ast.call_expression(private_in.span, has, arguments, false, None, false);
+            }
+            Expression::ClassExpression(_) => {
+                let Some(lowered) = self.lowered.take() else { return };
+                if lowered.before.is_empty() && lowered.after.is_empty() {
+                    return;
+                }
+                let class = mem::replace(expr, self.ast.null_literal_expression());
+                *expr = self.transform_class_expression(class, lowered);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The definition of a field, and the computed key it uses
+struct Field<'a> {
+    definition: Expression<'a>,
+    key: Option<(Atom, Expression<'a>)>,
+}
+
+impl<'a> Field<'a> {
+    /// Leaves the computed key to be evaluated in the order of the elements of the class
+    fn with_key(self, before: &mut std::vec::Vec<(Atom, Expression<'a>)>) -> Expression<'a> {
+        before.extend(self.key);
+        self.definition
+    }
+}
+
+/// The storage and function of the private method `key`
+fn private_method(
+    names: &HashMap<Atom, Option<PrivateName>>,
+    key: &PropertyKey,
+) -> Option<(Atom, Atom)> {
+    let PropertyKey::PrivateIdentifier(ident) = key else { return None };
+    let name = names.get(&ident.name)?.as_ref()?;
+    Some((name.storage.clone(), name.method.clone()?))
+}
+
+/// The object of the private field `target`
+fn private_target_object(target: SimpleAssignmentTarget<'_>) -> Expression<'_> {
+    let SimpleAssignmentTarget::MemberAssignmentTarget(member) = target else { unreachable!() };
+    let MemberExpression::PrivateFieldExpression(expr) = member.unbox() else { unreachable!() };
+    expr.object
+}
+
+/// Whether `member` is `super.a` or `super[a]`
+fn is_super_member(member: &MemberExpression) -> bool {
+    matches!(member.object(), Expression::Super(_))
+}
+
+/// The right side of a compound assignment as the right operand of a binary expression
+fn binary_operand<'a>(ast: &AstBuilder<'a>, expr: Expression<'a>) -> Expression<'a> {
+    if matches!(
+        expr,
+        Expression::AssignmentExpression(_)
+            | Expression::ArrowExpression(_)
+            | Expression::BinaryExpression(_)
+            | Expression::ConditionalExpression(_)
+            | Expression::LogicalExpression(_)
+            | Expression::SequenceExpression(_)
+            | Expression::YieldExpression(_)
+    ) {
+        ast.parenthesized_expression(SPAN, expr)
+    } else {
+        expr
+    }
+}
+
+fn logical_operator(operator: AssignmentOperator) -> Option<LogicalOperator> {
+    match operator {
+        AssignmentOperator::LogicalOr => Some(LogicalOperator::Or),
+        AssignmentOperator::LogicalAnd => Some(LogicalOperator::And),
+        AssignmentOperator::LogicalNullish => Some(LogicalOperator::Coalesce),
+        _ => None,
+    }
+}
+
+fn binary_operator(operator: AssignmentOperator) -> BinaryOperator {
+    match operator {
+        AssignmentOperator::Addition => BinaryOperator::Addition,
+        AssignmentOperator::Subtraction => BinaryOperator::Subtraction,
+        AssignmentOperator::Multiplication => BinaryOperator::Multiplication,
+        AssignmentOperator::Division => BinaryOperator::Division,
+        AssignmentOperator::Remainder => BinaryOperator::Remainder,
+        AssignmentOperator::Exponential => BinaryOperator::Exponential,
+        AssignmentOperator::ShiftLeft => BinaryOperator::ShiftLeft,
+        AssignmentOperator::ShiftRight => BinaryOperator::ShiftRight,
+        AssignmentOperator::ShiftRightZeroFill => BinaryOperator::ShiftRightZeroFill,
+        AssignmentOperator::BitwiseOR => BinaryOperator::BitwiseOR,
+        AssignmentOperator::BitwiseXOR => BinaryOperator::BitwiseXOR,
+        AssignmentOperator::BitwiseAnd => BinaryOperator::BitwiseAnd,
+        AssignmentOperator::Assign
+        | AssignmentOperator::LogicalOr
+        | AssignmentOperator::LogicalAnd
+        | AssignmentOperator::LogicalNullish => unreachable!(),
+    }
+}
+
+/// Replaces what refers to the context of an initializer or a static block, which is moved out of
+/// the class: `new.target`, which is `undefined` in them, and in static elements `this` and
+/// `super`, which are the class and its prototype. `arguments` is an early error in them.
+struct Initializer<'a, 't> {
+    ast: AstBuilder<'a>,
+    temporaries: &'t mut Temporaries<'a>,
+    /// The binding of the class of a static element
+    class: Option<Atom>,
+}
+
+impl<'a> Initializer<'a, '_> {
+    /// The key of `super.a` or `super[a]`
+    fn super_key(&self, member: &mut MemberExpression<'a>) -> Expression<'a> {
+        match member {
+            MemberExpression::StaticMemberExpression(member) => {
+                self.ast.string(&member.property.name)
+            }
+            MemberExpression::ComputedMemberExpression(member) => {
+                mem::replace(&mut member.expression, self.ast.null_literal_expression())
+            }
+            MemberExpression::PrivateFieldExpression(_) => unreachable!("`super.#a` is invalid"),
+        }
+    }
+
+    /// `Object.getPrototypeOf(A)`
+    fn prototype(&self, class: &Atom) -> Expression<'a> {
+        let get_prototype_of = self.ast.member(self.ast.ident("Object"), "getPrototypeOf");
+        self.ast.call(get_prototype_of, [self.ast.ident(class)])
+    }
+
+    /// `super[key]` -> `Reflect.get(Object.getPrototypeOf(A), key, A)`
+    fn super_get(&self, class: &Atom, key: Expression<'a>) -> Expression<'a> {
+        let get = self.ast.member(self.ast.ident("Reflect"), "get");
+        self.ast.call(get, [self.prototype(class), key, self.ast.ident(class)])
+    }
+
+    /// `(_value = value, Reflect.set(Object.getPrototypeOf(A), key, _value, A), _value)`
+    fn super_set(
+        &mut self,
+        class: &Atom,
+        key: Expression<'a>,
+        value: Expression<'a>,
+    ) -> Expression<'a> {
+        let name = self.temporaries.create("value");
+        let set = self.ast.member(self.ast.ident("Reflect"), "set");
+        let set = self
+            .ast
+            .call(set, [self.prototype(class), key, self.ast.ident(&name), self.ast.ident(class)]);
+        let mut expressions = self.ast.new_vec_with_capacity(3);
+        expressions.push(self.ast.assign(self.ast.ident(&name), value));
+        expressions.push(set);
+        expressions.push(self.ast.ident(&name));
+        let sequence = self.ast.sequence_expression(SPAN, expressions);
+        self.ast.parenthesized_expression(SPAN, sequence)
+    }
+
+    /// `super.a += b` -> `super.a = super.a + b`, `super.a ||= b` -> `super.a || (super.a = b)`,
+    /// evaluating the key once
+    fn super_assignment(
+        &mut self,
+        class: &Atom,
+        key: Expression<'a>,
+        operator: AssignmentOperator,
+        value: Expression<'a>,
+    ) -> Expression<'a> {
+        if operator == AssignmentOperator::Assign {
+            return self.super_set(class, key, value);
+        }
+        let (key, reference) = self.temporaries.memoize(key, "key");
+        let get = self.super_get(class, key);
+        if let Some(operator) = logical_operator(operator) {
+            let set = self.super_set(class, reference, value);
+            return self.ast.logical(get, operator, set);
+        }
+        let value =
+            self.ast.binary(get, binary_operator(operator), binary_operand(&self.ast, value));
+        self.super_set(class, reference, value)
+    }
+
+    /// `++super.a` -> `(_value2 = super.a, super.a = ++_value2)`,
+    /// `super.a++` -> `(_value2 = super.a, super.a = (_old = _value2++, _value2), _old)`
+    fn super_update(
+        &mut self,
+        class: &Atom,
+        key: Expression<'a>,
+        operator: UpdateOperator,
+        prefix: bool,
+    ) -> Expression<'a> {
+        let (key, reference) = self.temporaries.memoize(key, "key");
+        let value = self.temporaries.create("value");
+        let target = SimpleAssignmentTarget::AssignmentTargetIdentifier(
+            self.ast.alloc(IdentifierReference { span: SPAN, name: value.clone() }),
+        );
+        let update = self.ast.update_expression(SPAN, operator, prefix, target);
+        let mut expressions = self.ast.new_vec_with_capacity(3);
+        expressions.push(self.ast.assign(self.ast.ident(&value), self.super_get(class, key)));
+        if prefix {
+            expressions.push(self.super_set(class, reference, update));
+        } else {
+            let old = self.temporaries.create("old");
+            let mut new_value = self.ast.new_vec_with_capacity(2);
+            new_value.push(self.ast.assign(self.ast.ident(&old), update));
+            new_value.push(self.ast.ident(&value));
+            let new_value = self.ast.sequence_expression(SPAN, new_value);
+            let new_value = self.ast.parenthesized_expression(SPAN, new_value);
+            expressions.push(self.super_set(class, reference, new_value));
+            expressions.push(self.ast.ident(&old));
+        }
+        let sequence = self.ast.sequence_expression(SPAN, expressions);
+        self.ast.parenthesized_expression(SPAN, sequence)
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for Initializer<'a, '_> {
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        match expr {
+            Expression::ThisExpression(this) => {
+                if let Some(name) = self.class.clone() {
+                    *expr = self
+                        .ast
+                        .identifier_expression(IdentifierReference { span: this.span, name });
+                }
+                return;
+            }
+            Expression::MetaProperty(meta) if meta.meta.name == "new" => {
+                *expr = self.ast.void_0();
+                return;
+            }
+            _ => {}
+        }
+        self.visit_expression_match(expr);
+        let Some(class) = self.class.clone() else { return };
+        match expr {
+            Expression::MemberExpression(member) if is_super_member(member) => {
+                let key = self.super_key(member);
+                *expr = self.super_get(&class, key);
+            }
+            Expression::AssignmentExpression(assignment) => {
+                let AssignmentTarget::SimpleAssignmentTarget(
+                    SimpleAssignmentTarget::MemberAssignmentTarget(member),
+                ) = &mut assignment.left
+                else {
+                    return;
+                };
+                if !is_super_member(member) {
+                    return;
+                }
+                let key = self.super_key(member);
+                let value = mem::replace(&mut assignment.right, self.ast.null_literal_expression());
+                *expr = self.super_assignment(&class, key, assignment.operator, value);
+            }
+            Expression::UpdateExpression(update) => {
+                let SimpleAssignmentTarget::MemberAssignmentTarget(member) = &mut update.argument
+                else {
+                    return;
+                };
+                if !is_super_member(member) {
+                    return;
+                }
+                let key = self.super_key(member);
+                *expr = self.super_update(&class, key, update.operator, update.prefix);
+            }
+            _ => {}
+        }
+    }
+
+    /// `super.a(b)` -> `Reflect.get(Object.getPrototypeOf(A), "a", A).call(A, b)`
+    fn visit_call_expression(&mut self, call: &'b mut CallExpression<'a>) {
+        for arg in call.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        let class = self.class.clone();
+        let (Expression::MemberExpression(member), Some(class)) = (&mut call.callee, class) else {
+            self.visit_expression(&mut call.callee);
+            return;
+        };
+        if !is_super_member(member) {
+            self.visit_expression(&mut call.callee);
+            return;
+        }
+        if let MemberExpression::ComputedMemberExpression(member) = &mut **member {
+            self.visit_expression(&mut member.expression);
+        }
+        let key = self.super_key(member);
+        call.callee = self.ast.member(self.super_get(&class, key), "call");
+        call.arguments.insert(0, Argument::Expression(self.ast.ident(&class)));
+    }
+
+    // Functions have their own `this`, `super` and `new.target`, and so do the elements of
+    // classes except for their computed keys
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        for element in class.body.body.iter_mut() {
+            match element {
+                ClassElement::MethodDefinition(def) if def.computed => {
+                    self.visit_property_key(&mut def.key);
+                }
+                ClassElement::PropertyDefinition(def) if def.computed => {
+                    self.visit_property_key(&mut def.key);
+                }
+                ClassElement::AccessorProperty(def) if def.computed => {
+                    self.visit_property_key(&mut def.key);
+                }
+                _ => {}
+            }
+        }
+    }
+}
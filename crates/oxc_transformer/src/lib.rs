@@ -0,0 +1,229 @@
+//! Transformer / Transpiler
+//!
+//! Passes which rewrite the AST in place so it can be printed as JavaScript that runs in the
+//! target environment, e.g. with `oxc_codegen`.
+
+mod constructor;
+mod decorators;
+mod define;
+mod es2015;
+mod es2017;
+mod es2020;
+mod es2021;
+mod es2022;
+mod modules;
+mod pipeline;
+mod react_jsx;
+mod shorthands;
+mod symbols;
+mod temporaries;
+mod typescript;
+
+use oxc_allocator::Allocator;
+use oxc_ast::{ast::Program, VisitMut};
+use oxc_diagnostics::Error;
+use oxc_span::SourceType;
+
+pub use crate::{
+    decorators::{Decorators, DecoratorsOptions, DecoratorsVersion},
+    define::{Define, DefineOptions, DefineValue, InvalidDefineValue},
+    es2015::Es2015,
+    es2017::Es2017,
+    es2020::Es2020,
+    es2021::Es2021,
+    es2022::{ClassPropertiesOptions, Es2022},
+    modules::{CommonJs, DynamicImport, EsModule, ModuleFormat},
+    pipeline::{TransformContext, TransformPass},
+    react_jsx::{ReactJsx, ReactJsxOptions, ReactJsxRuntime},
+    typescript::{
+        ConstEnumLookup, ConstEnumValue, ImportsNotUsedAsValues, TypeScript, TypeScriptOptions,
+    },
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct TransformOptions {
+    /// Path of the transformed file, which development builds of JSX reference in `__source`
+    pub filename: String,
+
+    pub typescript: TypeScriptOptions,
+
+    pub react_jsx: ReactJsxOptions,
+
+    pub class_properties: ClassPropertiesOptions,
+
+    pub decorators: DecoratorsOptions,
+
+    pub define: DefineOptions,
+
+    /// Syntax newer than the target is lowered
+    pub target: TransformTarget,
+
+    /// Imports and exports are converted to this format
+    pub module: ModuleFormat,
+
+    /// How `import()` is lowered when modules become CommonJS
+    pub dynamic_import: DynamicImport,
+}
+
+/// The ECMAScript version the output has to run on
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TransformTarget {
+    ES5,
+    ES2015,
+    ES2016,
+    ES2017,
+    ES2018,
+    ES2019,
+    ES2020,
+    ES2021,
+    ES2022,
+    #[default]
+    ESNext,
+}
+
+/// Runs the passes registered on it in order, see [`TransformPass`]
+pub struct Transformer<'a> {
+    ctx: TransformContext<'a>,
+    passes: Vec<Box<dyn TransformPass<'a> + 'a>>,
+}
+
+impl<'a> Transformer<'a> {
+    /// The built-in passes needed by `options`
+    pub fn new(
+        allocator: &'a Allocator,
+        source_text: &'a str,
+        source_type: SourceType,
+        options: TransformOptions,
+    ) -> Self {
+        let mut transformer = Self::empty(allocator, source_text, source_type);
+        // First, as metadata of legacy decorators is read from type annotations
+        transformer.push(Decorators::new(allocator, options.decorators));
+        if source_type.is_typescript() {
+            let jsx = source_type.is_jsx().then_some(&options.react_jsx);
+            transformer.push(TypeScript::new(allocator, options.typescript, jsx));
+        }
+        if !options.define.replacements.is_empty() {
+            transformer.push(Define::new(allocator, options.define));
+        }
+        if options.module == ModuleFormat::EsModule {
+            transformer.push(EsModule::new(allocator));
+        }
+        if source_type.is_jsx() {
+            transformer.push(ReactJsx::new(
+                allocator,
+                source_text,
+                source_type,
+                &options.filename,
+                options.react_jsx,
+            ));
+        }
+        if options.target < TransformTarget::ES2021 {
+            transformer.push(Es2021::new(allocator));
+        }
+        if options.target < TransformTarget::ES2020 {
+            transformer.push(Es2020::new(allocator));
+        }
+        // Async functions become generators, which are lowered next
+        if options.target < TransformTarget::ES2017 {
+            transformer.push(Es2017::new(allocator));
+        }
+        if options.target < TransformTarget::ES2015 {
+            transformer.push(Es2015::new(allocator));
+        }
+        // Last, as the other passes can leave private fields, e.g. `a.#b ||= c` ->
+        // `a.#b || (a.#b = c)`
+        if options.target < TransformTarget::ES2022 {
+            transformer.push(Es2022::new(allocator, options.class_properties));
+        }
+        // After the passes which import helpers or runtimes, e.g. the automatic JSX runtime
+        if options.module == ModuleFormat::CommonJs {
+            transformer.push(CommonJs::new(allocator, options.dynamic_import));
+        }
+        transformer
+    }
+
+    /// Without any passes, for embedders which register their own
+    pub fn empty(allocator: &'a Allocator, source_text: &'a str, source_type: SourceType) -> Self {
+        Self { ctx: TransformContext::new(allocator, source_text, source_type), passes: vec![] }
+    }
+
+    /// Names of the registered passes, in the order they run
+    pub fn pass_names(&self) -> std::vec::Vec<&'static str> {
+        self.passes.iter().map(|pass| pass.name()).collect()
+    }
+
+    /// Runs `pass` after the registered passes
+    #[must_use]
+    pub fn with_pass<P: TransformPass<'a> + 'a>(mut self, pass: P) -> Self {
+        self.push(pass);
+        self
+    }
+
+    /// Runs `pass` before the pass named `name`, or after the registered passes if there is none,
+    /// e.g. as the built-in pass is not needed by the options
+    #[must_use]
+    pub fn with_pass_before<P: TransformPass<'a> + 'a>(mut self, name: &str, pass: P) -> Self {
+        let index = self.position(name).unwrap_or(self.passes.len());
+        self.passes.insert(index, Box::new(pass));
+        self
+    }
+
+    /// Runs `pass` after the pass named `name`, or after the registered passes if there is none
+    #[must_use]
+    pub fn with_pass_after<P: TransformPass<'a> + 'a>(mut self, name: &str, pass: P) -> Self {
+        let index = self.position(name).map_or(self.passes.len(), |index| index + 1);
+        self.passes.insert(index, Box::new(pass));
+        self
+    }
+
+    fn push<P: TransformPass<'a> + 'a>(&mut self, pass: P) {
+        self.passes.push(Box::new(pass));
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.passes.iter().position(|pass| pass.name() == name)
+    }
+
+    /// # Errors
+    ///
+    /// The errors reported by the passes, e.g. for syntax which can not be lowered to the target.
+    /// The program is transformed by all the passes regardless.
+    pub fn build<'b>(mut self, program: &'b mut Program<'a>) -> Result<(), std::vec::Vec<Error>> {
+        for pass in &mut self.passes {
+            self.ctx.run(pass.as_mut(), program);
+        }
+        let errors = self.ctx.into_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// The built-in passes, which only read the options they were created with
+macro_rules! impl_transform_pass {
+    ($($ty:ident => $name:literal,)*) => {
+        $(
+            impl<'a> TransformPass<'a> for $ty<'a> {
+                fn name(&self) -> &'static str {
+                    $name
+                }
+
+                fn transform(&mut self, program: &mut Program<'a>, _ctx: &TransformContext<'a>) {
+                    self.visit_program(program);
+                }
+            }
+        )*
+    };
+}
+
+impl_transform_pass! {
+    EsModule => "es_module",
+    ReactJsx => "react_jsx",
+    Es2021 => "es2021",
+    Es2020 => "es2020",
+    Es2017 => "es2017",
+    Es2022 => "es2022",
+    CommonJs => "commonjs",
+}
@@ -0,0 +1,504 @@
+use std::{collections::HashMap, iter, mem};
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::identifier::{is_identifier_part, is_identifier_start_all};
+
+use super::{helpers::Helper, module_base, DynamicImport, ModuleReferences, RewriteReference};
+use crate::{shorthands::Shorthands, temporaries::Temporaries};
+
+/// A module which is imported or re-exported
+#[allow(clippy::struct_excessive_bools)]
+struct Source {
+    specifier: Atom,
+    /// The variable holding the module, if its exports are referenced
+    binding: Option<Atom>,
+    /// Whether the default export is referenced
+    default: bool,
+    /// Whether other named exports are referenced
+    named: bool,
+    /// Whether the namespace is referenced, `import * as a from "m"`
+    namespace: bool,
+    /// `export * from "m"`
+    star: bool,
+}
+
+impl Source {
+    /// The helper which wraps `require`: modules whose default export is referenced may be
+    /// CommonJS, where the default export is the module itself
+    fn interop(&self) -> Option<Helper> {
+        if self.namespace || (self.default && self.named) {
+            Some(Helper::InteropRequireWildcard)
+        } else if self.default {
+            Some(Helper::InteropRequireDefault)
+        } else {
+            None
+        }
+    }
+}
+
+/// An export of a required module, or its namespace if `name` is `None`
+#[derive(Clone)]
+struct Import {
+    source: usize,
+    name: Option<Atom>,
+}
+
+enum Export {
+    /// A binding of the module scope
+    Local(Atom),
+    Import(Import),
+}
+
+/// Turns imports into `require` and exports into getters on `exports`, which read the
+/// bindings of the module
+pub struct CommonJs<'a> {
+    ast: AstBuilder<'a>,
+    temporaries: Temporaries<'a>,
+    /// The modules which are required, in the order they are imported
+    sources: Vec<Source>,
+    /// The imported bindings by their local names
+    imports: HashMap<Atom, Import>,
+    /// The exported names with their values
+    exports: Vec<(Atom, Export)>,
+    /// The helpers used, in the order of their first use
+    helpers: Vec<(Helper, Atom)>,
+    dynamic_import: DynamicImport,
+}
+
+impl<'a> CommonJs<'a> {
+    pub fn new(allocator: &'a Allocator, dynamic_import: DynamicImport) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            temporaries: Temporaries::new(allocator),
+            sources: vec![],
+            imports: HashMap::new(),
+            exports: vec![],
+            helpers: vec![],
+            dynamic_import,
+        }
+    }
+
+    /// A reference to `helper`, which is declared at the start of the program
+    fn helper(&mut self, helper: Helper) -> Expression<'a> {
+        if let Some((_, name)) = self.helpers.iter().find(|(h, _)| *h == helper) {
+            return self.ast.ident(name);
+        }
+        let name = self.temporaries.create_unscoped(helper.name());
+        self.helpers.push((helper, name.clone()));
+        self.ast.ident(&name)
+    }
+
+    /// The index of the module `specifier` in [`CommonJs::sources`]
+    fn source(&mut self, specifier: &Atom) -> usize {
+        if let Some(index) = self.sources.iter().position(|s| s.specifier == *specifier) {
+            return index;
+        }
+        self.sources.push(Source {
+            specifier: specifier.clone(),
+            binding: None,
+            default: false,
+            named: false,
+            namespace: false,
+            star: false,
+        });
+        self.sources.len() - 1
+    }
+
+    /// Records an import of `name` from the module `source`
+    fn import(&mut self, source: usize, name: Option<&Atom>) -> Import {
+        let source_ref = &mut self.sources[source];
+        match name {
+            None => source_ref.namespace = true,
+            Some(name) if *name == "default" => source_ref.default = true,
+            Some(_) => source_ref.named = true,
+        }
+        Import { source, name: name.cloned() }
+    }
+
+    /// Records the imports and exports of `decl`, returning the statement which replaces it
+    fn transform_module_declaration(
+        &mut self,
+        decl: ModuleDeclaration<'a>,
+    ) -> Option<Statement<'a>> {
+        match decl {
+            ModuleDeclaration::ImportDeclaration(decl) => {
+                let source = self.source(&decl.source.value);
+                for specifier in &decl.specifiers {
+                    let (local, import) = match specifier {
+                        ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                            (&specifier.local, self.import(source, Some(specifier.imported.name())))
+                        }
+                        ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                            (&specifier.local, self.import(source, Some(&Atom::from("default"))))
+                        }
+                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                            (&specifier.local, self.import(source, None))
+                        }
+                    };
+                    self.imports.insert(local.name.clone(), import);
+                }
+                None
+            }
+            ModuleDeclaration::ExportAllDeclaration(decl) => {
+                let source = self.source(&decl.source.value);
+                match &decl.exported {
+                    Some(exported) => {
+                        let import = self.import(source, None);
+                        self.exports.push((exported.name().clone(), Export::Import(import)));
+                    }
+                    None => self.sources[source].star = true,
+                }
+                None
+            }
+            ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                let decl = decl.unbox();
+                if let Some(declaration) = decl.declaration {
+                    declaration.bound_names(&mut |ident| {
+                        self.exports.push((ident.name.clone(), Export::Local(ident.name.clone())));
+                    });
+                    return Some(Statement::Declaration(declaration));
+                }
+                let source = decl.source.as_ref().map(|source| self.source(&source.value));
+                for specifier in &decl.specifiers {
+                    let export = source.map_or_else(
+                        || Export::Local(specifier.local.name().clone()),
+                        |source| Export::Import(self.import(source, Some(specifier.local.name()))),
+                    );
+                    self.exports.push((specifier.exported.name().clone(), export));
+                }
+                None
+            }
+            ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+                let (name, stmt) = match decl.unbox().declaration {
+                    ExportDefaultDeclarationKind::Expression(expr) => {
+                        let name = self.temporaries.create_unscoped("default");
+                        let stmt = self.ast.var_declaration_statement(
+                            VariableDeclarationKind::Var,
+                            [(name.as_str(), Some(expr))],
+                        );
+                        (name, stmt)
+                    }
+                    ExportDefaultDeclarationKind::FunctionDeclaration(mut func) => {
+                        let name = self.default_name(&mut func.id);
+                        (name, self.ast.function_declaration(func))
+                    }
+                    ExportDefaultDeclarationKind::ClassDeclaration(mut class) => {
+                        let name = self.default_name(&mut class.id);
+                        (name, self.ast.class_declaration(class))
+                    }
+                    ExportDefaultDeclarationKind::TSInterfaceDeclaration(_)
+                    | ExportDefaultDeclarationKind::TSEnumDeclaration(_) => return None,
+                };
+                self.exports.push((Atom::from("default"), Export::Local(name)));
+                Some(stmt)
+            }
+            // `export = a` -> `module.exports = a`
+            ModuleDeclaration::TSExportAssignment(decl) => {
+                let target = self.ast.member(self.ast.ident("module"), "exports");
+                let expr = self.ast.assign(target, decl.unbox().expression);
+                Some(self.ast.statement(expr))
+            }
+            ModuleDeclaration::TSNamespaceExportDeclaration(_) => None,
+        }
+    }
+
+    /// The name of a default exported function or class, naming anonymous ones
+    fn default_name(&mut self, id: &mut Option<BindingIdentifier>) -> Atom {
+        id.get_or_insert_with(|| BindingIdentifier {
+            span: SPAN,
+            name: self.temporaries.create_unscoped("default"),
+        })
+        .name
+        .clone()
+    }
+
+    /// `(0, _m.name)`, which does not call the function with the module as `this`
+    fn callee(&self, import: &Import) -> Expression<'a> {
+        let mut expressions = self.ast.new_vec_with_capacity(2);
+        expressions.push(self.ast.number(0.0));
+        expressions.push(self.import_expression(import));
+        let sequence = self.ast.sequence_expression(SPAN, expressions);
+        self.ast.parenthesized_expression(SPAN, sequence)
+    }
+
+    /// `_m.name`, or `_m` for the namespace
+    fn import_expression(&self, import: &Import) -> Expression<'a> {
+        let binding = self.sources[import.source].binding.as_ref().expect("the module is bound");
+        let module = self.ast.ident(binding);
+        match &import.name {
+            Some(name) if is_identifier_name(name) => self.ast.member(module, name),
+            Some(name) => self.ast.computed(module, self.ast.string(name)),
+            None => module,
+        }
+    }
+
+    /// `var _m = _interopRequireDefault(require("m"));`, `_exportStar(require("m"), exports);`
+    /// or `require("m");` for each required module
+    fn require_statements(&mut self) -> std::vec::Vec<Statement<'a>> {
+        let mut stmts = vec![];
+        for index in 0..self.sources.len() {
+            let source = &self.sources[index];
+            let require =
+                self.ast.call(self.ast.ident("require"), [self.ast.string(&source.specifier)]);
+            let interop = source.interop();
+            let star = source.star;
+            let Some(binding) = source.binding.clone() else {
+                if star {
+                    let helper = self.helper(Helper::ExportStar);
+                    let call = self.ast.call(helper, [require, self.ast.ident("exports")]);
+                    stmts.push(self.ast.statement(call));
+                } else {
+                    stmts.push(self.ast.statement(require));
+                }
+                continue;
+            };
+            let init = match interop {
+                Some(interop) => {
+                    let helper = self.helper(interop);
+                    self.ast.call(helper, [require])
+                }
+                None => require,
+            };
+            stmts.push(self.ast.var_declaration_statement(
+                VariableDeclarationKind::Var,
+                [(binding.as_str(), Some(init))],
+            ));
+            if star {
+                let helper = self.helper(Helper::ExportStar);
+                let call =
+                    self.ast.call(helper, [self.ast.ident(&binding), self.ast.ident("exports")]);
+                stmts.push(self.ast.statement(call));
+            }
+        }
+        stmts
+    }
+
+    /// `Object.defineProperty(exports, "__esModule", { value: true });` and
+    /// `_export(exports, { name: function () { return value; } });`
+    fn export_statements(&mut self) -> std::vec::Vec<Statement<'a>> {
+        if self.exports.is_empty() && !self.sources.iter().any(|source| source.star) {
+            return vec![];
+        }
+        let descriptor = self.ast.object([("value", self.ast.boolean_literal_expression(true))]);
+        let es_module = self.ast.call(
+            self.ast.member(self.ast.ident("Object"), "defineProperty"),
+            [self.ast.ident("exports"), self.ast.string("__esModule"), descriptor],
+        );
+        let mut stmts = vec![self.ast.statement(es_module)];
+        if self.exports.is_empty() {
+            return stmts;
+        }
+        let mut properties = self.ast.new_vec_with_capacity(self.exports.len());
+        for (name, export) in mem::take(&mut self.exports) {
+            let value = match export {
+                Export::Local(local) => match self.imports.get(&local) {
+                    Some(import) => self.import_expression(import),
+                    None => self.ast.ident(&local),
+                },
+                Export::Import(import) => self.import_expression(&import),
+            };
+            let body = self.ast.statements([self.ast.return_statement(SPAN, Some(value))]);
+            let getter = self.ast.function_of(FunctionType::FunctionExpression, None, &[], body);
+            properties.push(ObjectPropertyKind::ObjectProperty(self.ast.object_property(
+                SPAN,
+                PropertyKind::Init,
+                self.property_key(&name),
+                Expression::FunctionExpression(getter),
+                None,
+                false,
+                false,
+                false,
+            )));
+        }
+        let getters = self.ast.object_expression(SPAN, properties, None);
+        let helper = self.helper(Helper::Export);
+        stmts.push(self.ast.statement(self.ast.call(helper, [self.ast.ident("exports"), getters])));
+        stmts
+    }
+
+    /// `name` or `"name"` if it is not an identifier
+    fn property_key(&self, name: &Atom) -> PropertyKey<'a> {
+        if is_identifier_name(name) {
+            PropertyKey::Identifier(
+                self.ast.alloc(IdentifierName { span: SPAN, name: name.clone() }),
+            )
+        } else {
+            PropertyKey::Expression(self.ast.string(name))
+        }
+    }
+
+    /// `import.meta.url` -> `require("url").pathToFileURL(__filename).href`
+    fn import_meta_url(&self) -> Expression<'a> {
+        let url = self.ast.call(self.ast.ident("require"), [self.ast.string("url")]);
+        let url =
+            self.ast.call(self.ast.member(url, "pathToFileURL"), [self.ast.ident("__filename")]);
+        self.ast.member(url, "href")
+    }
+
+    /// `import("m")` -> `Promise.resolve().then(function () { return
+    /// _interopRequireWildcard(require("m")); })`
+    ///
+    /// Other specifiers are converted to strings when `import()` is evaluated, as
+    /// `Promise.resolve("".concat(m)).then(function (specifier) { ... })`.
+    fn require_import(&mut self, import: ImportExpression<'a>) -> Expression<'a> {
+        let ImportExpression { source, arguments, .. } = import;
+        // Options which are not constant are still evaluated after the specifier, as ignored
+        // arguments of `Promise.resolve` or before the promise of a string specifier
+        let mut options = arguments.into_iter().next().filter(|options| !is_constant(options));
+        let helper = self.helper(Helper::InteropRequireWildcard);
+        let resolve = self.ast.member(self.ast.ident("Promise"), "resolve");
+        let (specifier, promise, params) = if let Expression::StringLiteral(_) = source {
+            let promise = self.ast.call(resolve, []);
+            (source, promise, &[][..])
+        } else {
+            let specifier = self.ast.call(self.ast.member(self.ast.string(""), "concat"), [source]);
+            let promise = self.ast.call(resolve, iter::once(specifier).chain(options.take()));
+            (self.ast.ident("specifier"), promise, &["specifier"][..])
+        };
+        let require = self.ast.call(self.ast.ident("require"), [specifier]);
+        let namespace = self.ast.call(helper, [require]);
+        let body = self.ast.statements([self.ast.return_statement(SPAN, Some(namespace))]);
+        let callback = self.ast.function_of(FunctionType::FunctionExpression, None, params, body);
+        let callback = Expression::FunctionExpression(callback);
+        let then = self.ast.call(self.ast.member(promise, "then"), [callback]);
+        match options {
+            Some(options) => {
+                let mut expressions = self.ast.new_vec_with_capacity(2);
+                expressions.push(options);
+                expressions.push(then);
+                let sequence = self.ast.sequence_expression(SPAN, expressions);
+                self.ast.parenthesized_expression(SPAN, sequence)
+            }
+            None => then,
+        }
+    }
+
+    /// `import("m", options)` -> `(0, _runtime.name)("m", options)`
+    fn runtime_import(
+        &mut self,
+        import: ImportExpression<'a>,
+        source: &Atom,
+        name: &Atom,
+    ) -> Expression<'a> {
+        let index = self.source(source);
+        let import_ref = self.import(index, Some(name));
+        let runtime = &mut self.sources[index];
+        if runtime.binding.is_none() {
+            runtime.binding = Some(self.temporaries.create_unscoped(&module_base(source)));
+        }
+        let callee = self.callee(&import_ref);
+        self.ast.call(callee, iter::once(import.source).chain(import.arguments))
+    }
+}
+
+impl<'a> RewriteReference<'a> for CommonJs<'a> {
+    fn rewrite(&mut self, expr: &mut Expression<'a>, callee: bool) -> bool {
+        *expr = match expr {
+            Expression::Identifier(ident) => {
+                let Some(import) = self.imports.get(&ident.name) else { return false };
+                // `a()` -> `(0, _m.a)()`
+                if callee && import.name.is_some() {
+                    self.callee(import)
+                } else {
+                    self.import_expression(import)
+                }
+            }
+            // `this` is `undefined` in the module scope of ES modules
+            Expression::ThisExpression(_) => self.ast.void_0(),
+            Expression::MetaProperty(_) => {
+                let meta = self.ast.object([
+                    ("url", self.import_meta_url()),
+                    ("filename", self.ast.ident("__filename")),
+                    ("dirname", self.ast.ident("__dirname")),
+                ]);
+                self.ast.parenthesized_expression(SPAN, meta)
+            }
+            Expression::MemberExpression(member) => match member.static_property_name() {
+                Some("url") => self.import_meta_url(),
+                Some("filename") => self.ast.ident("__filename"),
+                Some("dirname") => self.ast.ident("__dirname"),
+                _ => return false,
+            },
+            _ => return false,
+        };
+        true
+    }
+
+    fn rewrite_import(&mut self, expr: &mut Expression<'a>) {
+        if self.dynamic_import == DynamicImport::Preserve {
+            return;
+        }
+        let Expression::ImportExpression(import) = mem::replace(expr, self.ast.void_0()) else {
+            unreachable!()
+        };
+        let import = import.unbox();
+        *expr = match self.dynamic_import.clone() {
+            DynamicImport::Runtime { source, name } => {
+                self.runtime_import(import, &source.into(), &name.into())
+            }
+            _ => self.require_import(import),
+        };
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for CommonJs<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        let body = mem::replace(&mut program.body, self.ast.new_vec());
+        for stmt in body {
+            let stmt = match stmt {
+                Statement::ModuleDeclaration(decl) => {
+                    self.transform_module_declaration(decl.unbox())
+                }
+                stmt => Some(stmt),
+            };
+            program.body.extend(stmt);
+        }
+        for source in &mut self.sources {
+            if source.default || source.named || source.namespace {
+                source.binding =
+                    Some(self.temporaries.create_unscoped(&module_base(&source.specifier)));
+            }
+        }
+
+        let names = self.imports.keys().cloned().collect();
+        let mut references = ModuleReferences::new(self, names);
+        for stmt in program.body.iter_mut() {
+            references.visit_statement(stmt);
+        }
+
+        let mut prelude = self.export_statements();
+        prelude.extend(self.require_statements());
+        for (index, stmt) in prelude.into_iter().enumerate() {
+            program.body.insert(index, stmt);
+        }
+        for (index, (helper, name)) in mem::take(&mut self.helpers).into_iter().enumerate() {
+            program.body.insert(index, helper.declaration(&self.ast, name));
+        }
+        if !program.directives.iter().any(|directive| directive.directive == "use strict") {
+            let use_strict = StringLiteral { span: SPAN, value: Atom::from("use strict") };
+            let directive = self.ast.directive(SPAN, use_strict, Atom::from("use strict"));
+            program.directives.insert(0, directive);
+        }
+    }
+}
+
+/// Whether evaluating the options of `import()` has no effects, e.g. `{ with: { type: "json" } }`
+fn is_constant(expr: &Expression) -> bool {
+    match expr {
+        Expression::StringLiteral(_) => true,
+        Expression::ObjectExpression(object) => object.properties.iter().all(|property| {
+            matches!(property, ObjectPropertyKind::ObjectProperty(property)
+                if !property.computed && is_constant(&property.value))
+        }),
+        _ => false,
+    }
+}
+
+fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(is_identifier_start_all) && chars.all(is_identifier_part)
+}
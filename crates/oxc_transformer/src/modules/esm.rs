@@ -0,0 +1,583 @@
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::AssignmentOperator;
+
+use super::{ModuleReferences, RewriteReference, VarNames};
+use crate::{shorthands::Shorthands, temporaries::Temporaries};
+
+/// A statement of the module scope which exports a value, see [`export_assignment`]
+enum ExportAssignment {
+    /// `Object.defineProperty(exports, "__esModule", { value: true })`
+    Marker,
+    /// `module.exports = a`
+    Default,
+    /// `exports.name = a`
+    Named(Atom),
+}
+
+/// Turns `require` and the assignments to `exports` in the module scope into imports and
+/// exports. `this` of the module scope is `exports`.
+///
+/// `require` is created with `createRequire` if it is still referenced afterwards, and `module`
+/// and `exports` are declared as the default export if they are, in which case no other
+/// exports are created.
+pub struct EsModule<'a> {
+    ast: AstBuilder<'a>,
+    temporaries: Temporaries<'a>,
+    /// The number of references to `require` which are left
+    require_references: usize,
+    /// The number of references to `module` and `exports` which are left
+    module_references: usize,
+    /// Whether `this` of the module scope becomes `exports`, which it is in CommonJS
+    rewrites_this: bool,
+}
+
+impl<'a> EsModule<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            temporaries: Temporaries::new(allocator),
+            require_references: 0,
+            module_references: 0,
+            rewrites_this: false,
+        }
+    }
+
+    /// `import a from "m";`, `import { a, b as c } from "m";` or `import { b as c } from "m";`
+    /// for `const a = require("m")`, `const { a, b: c } = require("m")` or
+    /// `const c = require("m").b`, one for each declarator
+    fn transform_requires(
+        &self,
+        decl: &VariableDeclaration<'a>,
+        unique: &dyn Fn(&Atom) -> bool,
+    ) -> Option<std::vec::Vec<Statement<'a>>> {
+        let mut imports = vec![];
+        for declarator in &decl.declarations {
+            let init = declarator.init.as_ref()?;
+            let (source, imported) = match init {
+                Expression::MemberExpression(member) => {
+                    let name = member.static_property_name()?;
+                    (required_module(member.object())?, Some(Atom::from(name)))
+                }
+                init => (required_module(init)?, None),
+            };
+            let mut specifiers = self.ast.new_vec();
+            match (&declarator.id.kind, imported) {
+                (BindingPatternKind::BindingIdentifier(ident), None) => {
+                    if !unique(&ident.name) {
+                        return None;
+                    }
+                    specifiers.push(ImportDeclarationSpecifier::ImportDefaultSpecifier(
+                        ImportDefaultSpecifier { span: SPAN, local: (**ident).clone() },
+                    ));
+                }
+                (BindingPatternKind::BindingIdentifier(ident), Some(imported)) => {
+                    if !unique(&ident.name) {
+                        return None;
+                    }
+                    specifiers.push(import_specifier(imported, (**ident).clone()));
+                }
+                (BindingPatternKind::ObjectPattern(pat), None) if pat.rest.is_none() => {
+                    for property in &pat.properties {
+                        let BindingPatternKind::BindingIdentifier(ident) = &property.value.kind
+                        else {
+                            return None;
+                        };
+                        let imported = property.key.static_name()?;
+                        if property.computed || !unique(&ident.name) {
+                            return None;
+                        }
+                        specifiers.push(import_specifier(imported, (**ident).clone()));
+                    }
+                }
+                _ => return None,
+            }
+            let source = StringLiteral { span: SPAN, value: source.clone() };
+            let decl = self.ast.import_declaration(
+                SPAN,
+                specifiers,
+                source,
+                None,
+                ImportOrExportKind::Value,
+            );
+            imports.push(self.ast.module_declaration(ModuleDeclaration::ImportDeclaration(decl)));
+        }
+        Some(imports)
+    }
+
+    /// `exports.a = b;` -> `export const a = b;`, or `export { b as a };` if `b` is a binding
+    /// of the module scope which is never assigned
+    fn transform_named_export(
+        &mut self,
+        name: &Atom,
+        value: Expression<'a>,
+        is_constant: &dyn Fn(&Atom) -> bool,
+    ) -> std::vec::Vec<Statement<'a>> {
+        if let Expression::Identifier(ident) = &value {
+            if is_constant(&ident.name) {
+                return vec![self.export_specifier(ident.name.clone(), name.clone())];
+            }
+        }
+        // The name is free if it is not referenced anywhere
+        let local = if !is_reserved_word(name) && self.temporaries.reserve(name.clone()) {
+            name.clone()
+        } else {
+            self.temporaries.create_unscoped(name)
+        };
+        let decl = self
+            .ast
+            .var_declaration_of(VariableDeclarationKind::Const, [(local.as_str(), Some(value))]);
+        if local == *name {
+            let decl = self.ast.export_named_declaration(
+                SPAN,
+                Some(Declaration::VariableDeclaration(decl)),
+                self.ast.new_vec(),
+                None,
+                None,
+                ImportOrExportKind::Value,
+            );
+            return vec![self
+                .ast
+                .module_declaration(ModuleDeclaration::ExportNamedDeclaration(decl))];
+        }
+        vec![
+            Statement::Declaration(Declaration::VariableDeclaration(decl)),
+            self.export_specifier(local, name.clone()),
+        ]
+    }
+
+    /// `export { local as exported };`
+    fn export_specifier(&self, local: Atom, exported: Atom) -> Statement<'a> {
+        let specifier = ExportSpecifier {
+            span: SPAN,
+            local: ModuleExportName::Identifier(IdentifierName { span: SPAN, name: local }),
+            exported: ModuleExportName::Identifier(IdentifierName { span: SPAN, name: exported }),
+            export_kind: ImportOrExportKind::Value,
+        };
+        let decl = self.ast.export_named_declaration(
+            SPAN,
+            None,
+            self.ast.new_vec_single(specifier),
+            None,
+            None,
+            ImportOrExportKind::Value,
+        );
+        self.ast.module_declaration(ModuleDeclaration::ExportNamedDeclaration(decl))
+    }
+
+    /// `export default value;`
+    fn export_default(&self, value: Expression<'a>) -> Statement<'a> {
+        let decl = self.ast.export_default_declaration(
+            SPAN,
+            ExportDefaultDeclarationKind::Expression(value),
+            ModuleExportName::Identifier(IdentifierName {
+                span: SPAN,
+                name: Atom::from("default"),
+            }),
+        );
+        self.ast.module_declaration(ModuleDeclaration::ExportDefaultDeclaration(decl))
+    }
+
+    /// Whether the assignments to `exports` can all be turned into exports
+    fn can_transform_exports(&self, stmts: &[Statement<'a>], assigned: &HashSet<Atom>) -> bool {
+        if assigned.contains("module") || assigned.contains("exports") {
+            return false;
+        }
+        let mut count = 0;
+        let mut default = false;
+        let mut names = HashSet::new();
+        for stmt in stmts {
+            match export_assignment(stmt) {
+                Some(ExportAssignment::Marker) => {}
+                Some(ExportAssignment::Default) if !default => default = true,
+                Some(ExportAssignment::Named(name)) if !names.contains(&name) => {
+                    names.insert(name);
+                }
+                Some(_) => return false,
+                None => continue,
+            }
+            count += 1;
+        }
+        // The statements are the only references, and the default export is not combined with
+        // named exports, which would be its properties
+        count == self.module_references && (!default || names.is_empty())
+    }
+
+    /// `import { createRequire as _createRequire } from "module";
+    /// const require = _createRequire(import.meta.url);`
+    fn create_require(&mut self) -> [Statement<'a>; 2] {
+        let local = self.temporaries.create_unscoped("createRequire");
+        let specifier = import_specifier(
+            Atom::from("createRequire"),
+            BindingIdentifier { span: SPAN, name: local.clone() },
+        );
+        let source = StringLiteral { span: SPAN, value: Atom::from("module") };
+        let decl = self.ast.import_declaration(
+            SPAN,
+            self.ast.new_vec_single(specifier),
+            source,
+            None,
+            ImportOrExportKind::Value,
+        );
+        let url = self.ast.member(import_meta(&self.ast), "url");
+        let require = self.ast.call(self.ast.ident(&local), [url]);
+        [
+            self.ast.module_declaration(ModuleDeclaration::ImportDeclaration(decl)),
+            self.ast.var_declaration_statement(
+                VariableDeclarationKind::Const,
+                [("require", Some(require))],
+            ),
+        ]
+    }
+}
+
+impl<'a> RewriteReference<'a> for EsModule<'a> {
+    fn rewrite(&mut self, expr: &mut Expression<'a>, _callee: bool) -> bool {
+        if matches!(expr, Expression::ThisExpression(_)) {
+            if !self.rewrites_this {
+                return false;
+            }
+            // `this` is `undefined` in the module scope of ES modules
+            self.module_references += 1;
+            *expr = self.ast.ident("exports");
+            return true;
+        }
+        let Expression::Identifier(ident) = expr else { return false };
+        let property = match ident.name.as_str() {
+            "require" => {
+                self.require_references += 1;
+                return true;
+            }
+            "module" | "exports" => {
+                self.module_references += 1;
+                return true;
+            }
+            // Node.js 20.11
+            "__filename" => "filename",
+            "__dirname" => "dirname",
+            _ => return false,
+        };
+        *expr = self.ast.member(import_meta(&self.ast), property);
+        true
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for EsModule<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        let declared = module_scope_names(&mut program.body);
+        let mut assigned = AssignedNames { names: HashSet::new() };
+        assigned.visit_program(program);
+        let assigned = assigned.names;
+
+        let names = ["require", "module", "exports", "__filename", "__dirname"]
+            .into_iter()
+            .map(Atom::from)
+            .filter(|name| !declared.contains_key(name))
+            .collect::<HashSet<_>>();
+        let converts_requires = names.contains("require");
+        let converts_exports = names.contains("module") && names.contains("exports");
+        self.rewrites_this = converts_exports;
+        let mut references = ModuleReferences::new(self, names);
+        for stmt in program.body.iter_mut() {
+            references.visit_statement(stmt);
+        }
+
+        let transforms_exports =
+            converts_exports && self.can_transform_exports(&program.body, &assigned);
+        let unique = |name: &Atom| declared.get(name) == Some(&1) && !assigned.contains(name);
+        let body = mem::replace(&mut program.body, self.ast.new_vec());
+        let mut imports: Vec<'a, Statement<'a>> = self.ast.new_vec();
+        // Imports are evaluated before the module body, so only the `require`s which no other
+        // statement is evaluated before become imports
+        let mut leading = true;
+        for stmt in body {
+            if converts_requires && leading {
+                if let Some(source) = required_statement(&stmt) {
+                    let source = StringLiteral { span: SPAN, value: source.clone() };
+                    let decl = self.ast.import_declaration(
+                        SPAN,
+                        self.ast.new_vec(),
+                        source,
+                        None,
+                        ImportOrExportKind::Value,
+                    );
+                    imports.push(
+                        self.ast.module_declaration(ModuleDeclaration::ImportDeclaration(decl)),
+                    );
+                    self.require_references -= 1;
+                    continue;
+                }
+                if let Statement::Declaration(Declaration::VariableDeclaration(decl)) = &stmt {
+                    if let Some(stmts) = self.transform_requires(decl, &unique) {
+                        self.require_references -= stmts.len();
+                        imports.extend(stmts);
+                        continue;
+                    }
+                }
+            }
+            // Function declarations are hoisted and the marker has no effects
+            leading &= matches!(stmt, Statement::Declaration(Declaration::FunctionDeclaration(_)))
+                || matches!(export_assignment(&stmt), Some(ExportAssignment::Marker));
+            if transforms_exports {
+                if let Some(assignment) = export_assignment(&stmt) {
+                    self.module_references -= 1;
+                    match (assignment, assigned_value(stmt)) {
+                        (ExportAssignment::Default, Some(value)) => {
+                            program.body.push(self.export_default(value));
+                        }
+                        (ExportAssignment::Named(name), Some(value)) => {
+                            let stmts = self.transform_named_export(&name, value, &unique);
+                            program.body.extend(stmts);
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+            }
+            program.body.push(stmt);
+        }
+
+        if self.module_references > 0 {
+            // `var module = { exports: {} }, exports = module.exports;` ...
+            // `export default module.exports;`
+            let exports = self.ast.object([("exports", self.ast.object([]))]);
+            program.body.insert(
+                0,
+                self.ast.var_declaration_statement(
+                    VariableDeclarationKind::Var,
+                    [
+                        ("module", Some(exports)),
+                        ("exports", Some(self.ast.member(self.ast.ident("module"), "exports"))),
+                    ],
+                ),
+            );
+            let exports = self.ast.member(self.ast.ident("module"), "exports");
+            program.body.push(self.export_default(exports));
+        }
+        if self.require_references > 0 {
+            for (index, stmt) in self.create_require().into_iter().enumerate() {
+                imports.insert(index, stmt);
+            }
+        }
+        for (index, stmt) in imports.into_iter().enumerate() {
+            program.body.insert(index, stmt);
+        }
+    }
+}
+
+/// Whether `name` can not be the name of a variable in ES modules
+fn is_reserved_word(name: &str) -> bool {
+    matches!(
+        name,
+        "await"
+            | "break"
+            | "case"
+            | "catch"
+            | "class"
+            | "const"
+            | "continue"
+            | "debugger"
+            | "default"
+            | "delete"
+            | "do"
+            | "else"
+            | "enum"
+            | "export"
+            | "extends"
+            | "false"
+            | "finally"
+            | "for"
+            | "function"
+            | "if"
+            | "implements"
+            | "import"
+            | "in"
+            | "instanceof"
+            | "interface"
+            | "let"
+            | "new"
+            | "null"
+            | "package"
+            | "private"
+            | "protected"
+            | "public"
+            | "return"
+            | "static"
+            | "super"
+            | "switch"
+            | "this"
+            | "throw"
+            | "true"
+            | "try"
+            | "typeof"
+            | "var"
+            | "void"
+            | "while"
+            | "with"
+            | "yield"
+            | "arguments"
+            | "eval"
+    )
+}
+
+/// `import.meta`
+fn import_meta<'a>(ast: &AstBuilder<'a>) -> Expression<'a> {
+    let meta = IdentifierName { span: SPAN, name: Atom::from("import") };
+    let property = IdentifierName { span: SPAN, name: Atom::from("meta") };
+    ast.meta_property(SPAN, meta, property)
+}
+
+fn import_specifier(imported: Atom, local: BindingIdentifier) -> ImportDeclarationSpecifier {
+    ImportDeclarationSpecifier::ImportSpecifier(ImportSpecifier {
+        span: SPAN,
+        imported: ModuleExportName::Identifier(IdentifierName { span: SPAN, name: imported }),
+        local,
+        import_kind: ImportOrExportKind::Value,
+    })
+}
+
+/// `"m"` of `require("m")`
+fn required_module<'e>(expr: &'e Expression) -> Option<&'e Atom> {
+    let Expression::CallExpression(call) = expr else { return None };
+    match (&call.callee, call.arguments.as_slice()) {
+        (Expression::Identifier(ident), [Argument::Expression(Expression::StringLiteral(lit))])
+            if ident.name == "require" && !call.optional =>
+        {
+            Some(&lit.value)
+        }
+        _ => None,
+    }
+}
+
+/// `"m"` of `require("m");`
+fn required_statement<'s>(stmt: &'s Statement) -> Option<&'s Atom> {
+    match stmt {
+        Statement::ExpressionStatement(stmt) => required_module(&stmt.expression),
+        _ => None,
+    }
+}
+
+/// `a` of `exports.name = a;`
+fn assigned_value(stmt: Statement) -> Option<Expression> {
+    let Statement::ExpressionStatement(stmt) = stmt else { return None };
+    match stmt.unbox().expression {
+        Expression::AssignmentExpression(expr) => Some(expr.unbox().right),
+        _ => None,
+    }
+}
+
+/// Whether `expr` is `exports` or `module.exports`
+fn is_exports(expr: &Expression) -> bool {
+    match expr {
+        Expression::Identifier(ident) => ident.name == "exports",
+        Expression::MemberExpression(member) => {
+            matches!(member.object(), Expression::Identifier(ident) if ident.name == "module")
+                && member.static_property_name() == Some("exports")
+        }
+        _ => false,
+    }
+}
+
+/// Classifies `exports.name = a;`, `module.exports = a;` and the `__esModule` marker
+fn export_assignment(stmt: &Statement) -> Option<ExportAssignment> {
+    let Statement::ExpressionStatement(stmt) = stmt else { return None };
+    match &stmt.expression {
+        Expression::AssignmentExpression(expr) if expr.operator == AssignmentOperator::Assign => {
+            let AssignmentTarget::SimpleAssignmentTarget(
+                SimpleAssignmentTarget::MemberAssignmentTarget(member),
+            ) = &expr.left
+            else {
+                return None;
+            };
+            let name = member.static_property_name()?;
+            if matches!(member.object(), Expression::Identifier(ident) if ident.name == "module")
+                && name == "exports"
+            {
+                return Some(ExportAssignment::Default);
+            }
+            let is_static = matches!(&**member, MemberExpression::StaticMemberExpression(_));
+            if !is_static || !is_exports(member.object()) {
+                return None;
+            }
+            if name == "__esModule" {
+                return Some(ExportAssignment::Marker);
+            }
+            Some(ExportAssignment::Named(Atom::from(name)))
+        }
+        // `Object.defineProperty(exports, "__esModule", { value: true })`
+        Expression::CallExpression(call) => {
+            let Expression::MemberExpression(callee) = &call.callee else { return None };
+            let is_define = matches!(callee.object(), Expression::Identifier(ident) if ident.name == "Object")
+                && callee.static_property_name() == Some("defineProperty");
+            match call.arguments.as_slice() {
+                [Argument::Expression(target), Argument::Expression(Expression::StringLiteral(name)), Argument::Expression(_)]
+                    if is_define && is_exports(target) && name.value == "__esModule" =>
+                {
+                    Some(ExportAssignment::Marker)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The number of declarations of each name of the module scope
+fn module_scope_names(stmts: &mut Vec<Statement>) -> HashMap<Atom, usize> {
+    let mut names = HashMap::new();
+    let mut declare =
+        |ident: &BindingIdentifier| *names.entry(ident.name.clone()).or_insert(0) += 1;
+    let mut vars = VarNames { names: vec![] };
+    for stmt in stmts.iter_mut() {
+        match stmt {
+            Statement::Declaration(decl) => decl.bound_names(&mut declare),
+            Statement::ModuleDeclaration(decl) => match &**decl {
+                ModuleDeclaration::ImportDeclaration(decl) => decl.bound_names(&mut declare),
+                ModuleDeclaration::ExportNamedDeclaration(decl) => decl.bound_names(&mut declare),
+                _ => {}
+            },
+            stmt => vars.visit_statement(stmt),
+        }
+    }
+    for name in vars.names {
+        declare(&BindingIdentifier { span: SPAN, name });
+    }
+    names
+}
+
+/// Collects the names of the identifiers which are assigned
+struct AssignedNames {
+    names: HashSet<Atom>,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for AssignedNames {
+    fn visit_simple_assignment_target(&mut self, target: &'b mut SimpleAssignmentTarget<'a>) {
+        match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                self.names.insert(ident.name.clone());
+            }
+            SimpleAssignmentTarget::MemberAssignmentTarget(expr) => {
+                self.visit_member_expression(expr);
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        ident: &'b mut AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.names.insert(ident.binding.name.clone());
+        if let Some(expr) = &mut ident.init {
+            self.visit_expression(expr);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Functions which define the exports of CommonJS modules and read the exports of required
+//! modules as ES modules, declared at the start of the program when they are used
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{BinaryOperator, LogicalOperator};
+
+use crate::shorthands::Shorthands;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Helper {
+    /// Defines getters of exports
+    Export,
+    /// Defines getters of the exports of another module, `export * from "m"`
+    ExportStar,
+    /// Wraps a CommonJS module as the default export of a namespace
+    InteropRequireDefault,
+    /// Copies the exports of a CommonJS module to a namespace, with the module as its default
+    /// export
+    InteropRequireWildcard,
+}
+
+impl Helper {
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Export => "export",
+            Self::ExportStar => "exportStar",
+            Self::InteropRequireDefault => "interopRequireDefault",
+            Self::InteropRequireWildcard => "interopRequireWildcard",
+        }
+    }
+
+    pub(super) fn declaration<'a>(self, ast: &AstBuilder<'a>, name: Atom) -> Statement<'a> {
+        let (params, body) = match self {
+            Self::Export => (&["target", "all"][..], export(ast)),
+            Self::ExportStar => (&["from", "to"][..], export_star(ast)),
+            Self::InteropRequireDefault => (&["obj"][..], interop_require_default(ast)),
+            Self::InteropRequireWildcard => (&["obj"][..], interop_require_wildcard(ast)),
+        };
+        ast.function_declaration_of(name, params, body)
+    }
+}
+
+/// `Object.defineProperty(target, key, { enumerable: true, get: get })`
+fn define_getter<'a>(
+    ast: &AstBuilder<'a>,
+    target: &str,
+    key: &str,
+    get: Expression<'a>,
+) -> Statement<'a> {
+    let descriptor =
+        ast.object([("enumerable", ast.boolean_literal_expression(true)), ("get", get)]);
+    ast.statement(ast.call(
+        ast.member(ast.ident("Object"), "defineProperty"),
+        [ast.ident(target), ast.ident(key), descriptor],
+    ))
+}
+
+/// `Object.prototype.hasOwnProperty.call(object, key)`
+fn has_own_property<'a>(ast: &AstBuilder<'a>, object: &str, key: &str) -> Expression<'a> {
+    let prototype = ast.member(ast.ident("Object"), "prototype");
+    ast.call(
+        ast.member(ast.member(prototype, "hasOwnProperty"), "call"),
+        [ast.ident(object), ast.ident(key)],
+    )
+}
+
+/// `key !== "default"`
+fn is_not_default<'a>(ast: &AstBuilder<'a>, key: &str) -> Expression<'a> {
+    ast.binary(ast.ident(key), BinaryOperator::StrictInequality, ast.string("default"))
+}
+
+/// ```javascript
+/// for (var name in all) Object.defineProperty(target, name, { enumerable: true, get: all[name] });
+/// ```
+fn export<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let name = ast.var_declaration_of(VariableDeclarationKind::Var, [("name", None)]);
+    let get = ast.computed(ast.ident("all"), ast.ident("name"));
+    ast.statements([ast.for_in_statement(
+        SPAN,
+        ForStatementLeft::VariableDeclaration(name),
+        ast.ident("all"),
+        define_getter(ast, "target", "name", get),
+    )])
+}
+
+/// ```javascript
+/// Object.keys(from).forEach(function (key) {
+///     if (key !== "default" && !Object.prototype.hasOwnProperty.call(to, key)) {
+///         Object.defineProperty(to, key, { enumerable: true, get: function () { return from[key]; } });
+///     }
+/// });
+/// return from;
+/// ```
+fn export_star<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let get = ast.return_statement(SPAN, Some(ast.computed(ast.ident("from"), ast.ident("key"))));
+    let get = ast.function_of(FunctionType::FunctionExpression, None, &[], ast.statements([get]));
+    let test = ast.logical(
+        is_not_default(ast, "key"),
+        LogicalOperator::And,
+        ast.not(has_own_property(ast, "to", "key")),
+    );
+    let define = ast.if_else(
+        test,
+        ast.block_of(ast.statements([define_getter(
+            ast,
+            "to",
+            "key",
+            Expression::FunctionExpression(get),
+        )])),
+        None,
+    );
+    let callback =
+        ast.function_of(FunctionType::FunctionExpression, None, &["key"], ast.statements([define]));
+    let keys = ast.call(ast.member(ast.ident("Object"), "keys"), [ast.ident("from")]);
+    ast.statements([
+        ast.statement(
+            ast.call(ast.member(keys, "forEach"), [Expression::FunctionExpression(callback)]),
+        ),
+        ast.return_statement(SPAN, Some(ast.ident("from"))),
+    ])
+}
+
+/// `obj && obj.__esModule`
+fn is_es_module<'a>(ast: &AstBuilder<'a>) -> Expression<'a> {
+    ast.logical(ast.ident("obj"), LogicalOperator::And, ast.member(ast.ident("obj"), "__esModule"))
+}
+
+/// ```javascript
+/// return obj && obj.__esModule ? obj : { default: obj };
+/// ```
+fn interop_require_default<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let namespace = ast.object([("default", ast.ident("obj"))]);
+    let result = ast.conditional(is_es_module(ast), ast.ident("obj"), namespace);
+    ast.statements([ast.return_statement(SPAN, Some(result))])
+}
+
+/// ```javascript
+/// if (obj && obj.__esModule) return obj;
+/// var namespace = {};
+/// if (obj != null) {
+///     for (var key in obj) {
+///         if (key !== "default" && Object.prototype.hasOwnProperty.call(obj, key)) {
+///             Object.defineProperty(namespace, key, Object.getOwnPropertyDescriptor(obj, key));
+///         }
+///     }
+/// }
+/// namespace.default = obj;
+/// return namespace;
+/// ```
+fn interop_require_wildcard<'a>(ast: &AstBuilder<'a>) -> Vec<'a, Statement<'a>> {
+    let obj = || ast.ident("obj");
+    let descriptor = ast.call(
+        ast.member(ast.ident("Object"), "getOwnPropertyDescriptor"),
+        [obj(), ast.ident("key")],
+    );
+    let copy = ast.statement(ast.call(
+        ast.member(ast.ident("Object"), "defineProperty"),
+        [ast.ident("namespace"), ast.ident("key"), descriptor],
+    ));
+    let test = ast.logical(
+        is_not_default(ast, "key"),
+        LogicalOperator::And,
+        has_own_property(ast, "obj", "key"),
+    );
+    let key = ast.var_declaration_of(VariableDeclarationKind::Var, [("key", None)]);
+    let copy_all = ast.for_in_statement(
+        SPAN,
+        ForStatementLeft::VariableDeclaration(key),
+        obj(),
+        ast.block_of(ast.statements([ast.if_else(
+            test,
+            ast.block_of(ast.statements([copy])),
+            None,
+        )])),
+    );
+    ast.statements([
+        ast.if_else(is_es_module(ast), ast.return_statement(SPAN, Some(obj())), None),
+        ast.var_declaration_statement(
+            VariableDeclarationKind::Var,
+            [("namespace", Some(ast.object([])))],
+        ),
+        ast.if_else(
+            ast.binary(obj(), BinaryOperator::Inequality, ast.null_literal_expression()),
+            ast.block_of(ast.statements([copy_all])),
+            None,
+        ),
+        ast.statement(ast.assign(ast.member(ast.ident("namespace"), "default"), obj())),
+        ast.return_statement(SPAN, Some(ast.ident("namespace"))),
+    ])
+}
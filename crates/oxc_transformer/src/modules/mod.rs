@@ -0,0 +1,391 @@
+//! Module formats
+//!
+//! * ES modules to CommonJS, as Babel's `transform-modules-commonjs`:
+//!   `import a, { b } from "m"; export const c = a(b);` ->
+//!   `"use strict"; Object.defineProperty(exports, "__esModule", { value: true });
+//!   _export(exports, { c: function () { return c; } }); var _m = _interopRequireWildcard(
+//!   require("m")); const c = (0, _m.default)(_m.b);`
+//! * CommonJS to ES modules, on a best-effort basis:
+//!   `const a = require("a"); exports.b = a;` -> `import a from "a"; export const b = a;`
+//!
+//! `import()` becomes `require` in a promise callback, or a call to a function of a runtime, see
+//! [`DynamicImport`].
+//!
+//! Exports are getters on `exports`, so they are live bindings, and imported bindings are read
+//! from the required module whenever they are referenced.
+//!
+//! CommonJS is only turned into imports and exports where the module scope uses `require`,
+//! `module` and `exports` in ways which imports and exports express, e.g. not conditionally.
+//! The others are kept as they are, with `require` created by `createRequire` and `module` and
+//! `exports` declared as the default export.
+
+mod commonjs;
+mod esm;
+mod helpers;
+
+use std::collections::HashSet;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, VisitMut};
+use oxc_span::{Atom, SPAN};
+
+pub use self::{commonjs::CommonJs, esm::EsModule};
+
+/// The module format of the output
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleFormat {
+    /// Imports and exports are kept as they are
+    #[default]
+    Preserve,
+    /// ES modules become CommonJS, see [`CommonJs`]
+    CommonJs,
+    /// CommonJS becomes ES modules, see [`EsModule`]
+    EsModule,
+}
+
+/// How `import()` is lowered when ES modules become CommonJS
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum DynamicImport {
+    /// `import("m")` -> `Promise.resolve().then(function () { return
+    /// _interopRequireWildcard(require("m")); })`
+    ///
+    /// Import attributes are evaluated and otherwise ignored, as `require` loads JSON modules by
+    /// their extension.
+    #[default]
+    Require,
+    /// `import()` is kept, which CommonJS modules of Node.js support
+    Preserve,
+    /// `import("m", { with: { type: "json" } })` -> `(0, _runtime.load)("m", { with: { type:
+    /// "json" } })`, where the function `name` is required from the module `source` and returns
+    /// a promise of the namespace
+    Runtime { source: String, name: String },
+}
+
+/// Rewrites the references to the module scope which [`ModuleReferences`] finds
+trait RewriteReference<'a> {
+    /// Rewrites `expr`, which is an identifier referencing the module scope, `this` outside of
+    /// functions and classes, `import.meta` or a property of it. `callee` is whether `expr` is
+    /// called, including as the tag of a template.
+    ///
+    /// The children of `expr` are visited unless it is rewritten.
+    fn rewrite(&mut self, expr: &mut Expression<'a>, callee: bool) -> bool;
+
+    /// Rewrites `import()`, after its arguments are visited
+    fn rewrite_import(&mut self, _expr: &mut Expression<'a>) {}
+}
+
+/// Finds the references to the names of the module scope, which the functions, blocks and
+/// classes around them do not shadow
+struct ModuleReferences<'r, R> {
+    rewriter: &'r mut R,
+    scopes: Scopes,
+    /// The number of functions and class bodies being visited, which have their own `this`
+    depth: usize,
+}
+
+impl<'r, R> ModuleReferences<'r, R> {
+    /// Finds the references to `names` with `rewriter`
+    fn new(rewriter: &'r mut R, names: HashSet<Atom>) -> Self {
+        Self { rewriter, scopes: Scopes::new(names), depth: 0 }
+    }
+
+    /// Whether `expr` is a reference to the module scope
+    fn is_reference(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Identifier(ident) => self.scopes.is_module_binding(&ident.name),
+            Expression::ThisExpression(_) => self.depth == 0,
+            Expression::MetaProperty(meta) => meta.meta.name == "import",
+            Expression::MemberExpression(member) => match &**member {
+                MemberExpression::StaticMemberExpression(member) => {
+                    matches!(&member.object, Expression::MetaProperty(meta) if meta.meta.name == "import")
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl<'a, 'b, 'r, R: RewriteReference<'a>> VisitMut<'a, 'b> for ModuleReferences<'r, R> {
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        if let Expression::ImportExpression(import) = expr {
+            self.visit_import_expression(import);
+            self.rewriter.rewrite_import(expr);
+            return;
+        }
+        if !(self.is_reference(expr) && self.rewriter.rewrite(expr, false)) {
+            self.visit_expression_match(expr);
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &'b mut CallExpression<'a>) {
+        for arg in expr.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        if !(self.is_reference(&expr.callee) && self.rewriter.rewrite(&mut expr.callee, true)) {
+            self.visit_expression_match(&mut expr.callee);
+        }
+    }
+
+    fn visit_tagged_template_expression(&mut self, expr: &'b mut TaggedTemplateExpression<'a>) {
+        if !(self.is_reference(&expr.tag) && self.rewriter.rewrite(&mut expr.tag, true)) {
+            self.visit_expression_match(&mut expr.tag);
+        }
+        self.visit_template_literal(&mut expr.quasi);
+    }
+
+    fn visit_object_property(&mut self, prop: &'b mut ObjectProperty<'a>) {
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+        // `{ a }` -> `{ a: _m.a }`
+        prop.shorthand &= matches!(prop.value, Expression::Identifier(_));
+    }
+
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        self.scopes.enter();
+        if func.is_expression() {
+            if let Some(id) = &func.id {
+                self.scopes.declare(id);
+            }
+        }
+        if let Some(body) = &mut func.body {
+            self.scopes.declare_function(&func.params, body);
+        }
+        self.depth += 1;
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+        self.depth -= 1;
+        self.scopes.exit();
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        self.scopes.enter();
+        self.scopes.declare_function(&expr.params, &mut expr.body);
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+        self.scopes.exit();
+    }
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        self.scopes.enter();
+        if let Some(id) = &class.id {
+            self.scopes.declare(id);
+        }
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        self.depth += 1;
+        self.visit_class_body(&mut class.body);
+        self.depth -= 1;
+        self.scopes.exit();
+    }
+
+    fn visit_static_block(&mut self, block: &'b mut StaticBlock<'a>) {
+        self.scopes.enter();
+        let mut vars = VarNames { names: vec![] };
+        for stmt in block.body.iter_mut() {
+            vars.visit_statement(stmt);
+        }
+        for name in vars.names {
+            self.scopes.declare(&BindingIdentifier { span: SPAN, name });
+        }
+        self.scopes.declare_lexical(&block.body);
+        self.visit_statements(&mut block.body);
+        self.scopes.exit();
+    }
+
+    fn visit_block_statement(&mut self, stmt: &'b mut BlockStatement<'a>) {
+        self.scopes.enter();
+        self.scopes.declare_lexical(&stmt.body);
+        self.visit_statements(&mut stmt.body);
+        self.scopes.exit();
+    }
+
+    fn visit_switch_statement(&mut self, stmt: &'b mut SwitchStatement<'a>) {
+        self.visit_expression(&mut stmt.discriminant);
+        self.scopes.enter();
+        for case in &stmt.cases {
+            self.scopes.declare_lexical(&case.consequent);
+        }
+        for case in stmt.cases.iter_mut() {
+            self.visit_switch_case(case);
+        }
+        self.scopes.exit();
+    }
+
+    fn visit_for_statement(&mut self, stmt: &'b mut ForStatement<'a>) {
+        self.scopes.enter();
+        if let Some(ForStatementInit::VariableDeclaration(decl)) = &stmt.init {
+            self.scopes.declare_lexical_declaration(decl);
+        }
+        if let Some(init) = &mut stmt.init {
+            self.visit_for_statement_init(init);
+        }
+        if let Some(test) = &mut stmt.test {
+            self.visit_expression(test);
+        }
+        if let Some(update) = &mut stmt.update {
+            self.visit_expression(update);
+        }
+        self.visit_statement(&mut stmt.body);
+        self.scopes.exit();
+    }
+
+    fn visit_for_in_statement(&mut self, stmt: &'b mut ForInStatement<'a>) {
+        self.scopes.enter();
+        if let ForStatementLeft::VariableDeclaration(decl) = &stmt.left {
+            self.scopes.declare_lexical_declaration(decl);
+        }
+        self.visit_for_statement_left(&mut stmt.left);
+        self.visit_expression(&mut stmt.right);
+        self.visit_statement(&mut stmt.body);
+        self.scopes.exit();
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &'b mut ForOfStatement<'a>) {
+        self.scopes.enter();
+        if let ForStatementLeft::VariableDeclaration(decl) = &stmt.left {
+            self.scopes.declare_lexical_declaration(decl);
+        }
+        self.visit_for_statement_left(&mut stmt.left);
+        self.visit_expression(&mut stmt.right);
+        self.visit_statement(&mut stmt.body);
+        self.scopes.exit();
+    }
+
+    fn visit_catch_clause(&mut self, clause: &'b mut CatchClause<'a>) {
+        self.scopes.enter();
+        if let Some(param) = &clause.param {
+            param.bound_names(&mut |ident| self.scopes.declare(ident));
+        }
+        self.scopes.declare_lexical(&clause.body.body);
+        if let Some(param) = &mut clause.param {
+            self.visit_binding_pattern(param);
+        }
+        self.visit_statements(&mut clause.body.body);
+        self.scopes.exit();
+    }
+}
+
+/// The names declared in the functions, blocks and classes being visited, which shadow the
+/// names of the module scope
+struct Scopes {
+    /// The names of the module scope which are looked up, the others are not collected
+    names: HashSet<Atom>,
+    /// The names of each scope, innermost last
+    scopes: Vec<Vec<Atom>>,
+}
+
+impl Scopes {
+    fn new(names: HashSet<Atom>) -> Self {
+        Self { names, scopes: vec![] }
+    }
+
+    /// Whether `name` is one of the names looked up and references the module scope
+    fn is_module_binding(&self, name: &Atom) -> bool {
+        self.names.contains(name) && !self.scopes.iter().any(|scope| scope.contains(name))
+    }
+
+    fn enter(&mut self) {
+        self.scopes.push(vec![]);
+    }
+
+    fn exit(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, ident: &BindingIdentifier) {
+        if self.names.contains(&ident.name) {
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.push(ident.name.clone());
+            }
+        }
+    }
+
+    /// Declares the parameters of a function and the `var`s and lexical declarations of its
+    /// body
+    fn declare_function<'a>(&mut self, params: &FormalParameters<'a>, body: &mut FunctionBody<'a>) {
+        params.bound_names(&mut |ident| self.declare(ident));
+        let mut vars = VarNames { names: vec![] };
+        for stmt in body.statements.iter_mut() {
+            vars.visit_statement(stmt);
+        }
+        for name in vars.names {
+            self.declare(&BindingIdentifier { span: SPAN, name });
+        }
+        self.declare_lexical(&body.statements);
+    }
+
+    /// Declares the names of a `let` or `const` declaration
+    fn declare_lexical_declaration(&mut self, decl: &VariableDeclaration) {
+        if decl.kind != VariableDeclarationKind::Var {
+            decl.bound_names(&mut |ident| self.declare(ident));
+        }
+    }
+
+    /// Declares the `let`, `const`, class and function declarations of `stmts`
+    fn declare_lexical(&mut self, stmts: &[Statement]) {
+        for stmt in stmts {
+            if let Statement::Declaration(decl) = stmt {
+                match decl {
+                    Declaration::VariableDeclaration(decl) => {
+                        self.declare_lexical_declaration(decl);
+                    }
+                    decl => decl.bound_names(&mut |ident| self.declare(ident)),
+                }
+            }
+        }
+    }
+}
+
+/// Collects the names declared with `var`, which are hoisted out of blocks
+struct VarNames {
+    names: Vec<Atom>,
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for VarNames {
+    fn visit_variable_declaration(&mut self, decl: &'b mut VariableDeclaration<'a>) {
+        if decl.kind == VariableDeclarationKind::Var {
+            decl.bound_names(&mut |ident| self.names.push(ident.name.clone()));
+        }
+    }
+
+    fn visit_function(&mut self, _func: &'b mut Function<'a>) {}
+
+    fn visit_arrow_expression(&mut self, _expr: &'b mut ArrowExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &'b mut Class<'a>) {}
+}
+
+/// The base of the name of the variable holding a required module, e.g. `reactJsxRuntime` for
+/// `react/jsx-runtime`
+pub fn module_base(source: &str) -> String {
+    let mut base = String::new();
+    let parts = source.split(|c: char| !c.is_ascii_alphanumeric()).filter(|part| !part.is_empty());
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            base.push(if base.is_empty() { first } else { first.to_ascii_uppercase() });
+            base.extend(chars);
+        }
+    }
+    if base.is_empty() {
+        base.push_str("module");
+    }
+    base
+}
+
+#[cfg(test)]
+mod test {
+    use super::module_base;
+
+    #[test]
+    fn module_bases() {
+        assert_eq!(module_base("react"), "react");
+        assert_eq!(module_base("react/jsx-runtime"), "reactJsxRuntime");
+        assert_eq!(module_base("@emotion/react/jsx-dev-runtime"), "emotionReactJsxDevRuntime");
+        assert_eq!(module_base("./"), "module");
+    }
+}
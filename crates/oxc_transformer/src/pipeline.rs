@@ -0,0 +1,111 @@
+//! Pass manager of the [`Transformer`](crate::Transformer)
+//!
+//! The built-in passes and the passes of embedders implement [`TransformPass`], and run in the
+//! order they are registered on the same program. The symbols and scopes of the program are shared
+//! through the [`TransformContext`], and only rebuilt when a pass needs them after a pass before it
+//! has invalidated them. Errors, e.g. of syntax which can not be lowered, are collected in the
+//! context too.
+
+use std::cell::RefCell;
+
+use oxc_allocator::Allocator;
+use oxc_ast::{ast::Program, AstBuilder};
+use oxc_diagnostics::Error;
+use oxc_semantic::{ScopeTree, SemanticBuilder, SymbolTable};
+use oxc_span::SourceType;
+
+/// A pass which rewrites the program in place
+pub trait TransformPass<'a> {
+    /// Name which other passes are registered relative to, e.g.
+    /// [`Transformer::with_pass_before`](crate::Transformer::with_pass_before)
+    fn name(&self) -> &'static str;
+
+    /// Whether [`TransformContext::symbols`] and [`TransformContext::scopes`] have to be up to date
+    /// for the pass
+    fn needs_semantic(&self) -> bool {
+        false
+    }
+
+    /// Whether the pass adds, removes or moves bindings, references or scopes, after which the
+    /// symbols and scopes are rebuilt for the passes which need them
+    fn invalidates_scopes(&self) -> bool {
+        true
+    }
+
+    fn transform(&mut self, program: &mut Program<'a>, ctx: &TransformContext<'a>);
+}
+
+/// The data shared by the passes of a [`Transformer`](crate::Transformer)
+pub struct TransformContext<'a> {
+    allocator: &'a Allocator,
+
+    source_text: &'a str,
+
+    source_type: SourceType,
+
+    /// `None` until a pass needs them, and after a pass invalidates them
+    semantic: Option<(SymbolTable, ScopeTree)>,
+
+    errors: RefCell<Vec<Error>>,
+}
+
+impl<'a> TransformContext<'a> {
+    pub fn new(allocator: &'a Allocator, source_text: &'a str, source_type: SourceType) -> Self {
+        Self { allocator, source_text, source_type, semantic: None, errors: RefCell::default() }
+    }
+
+    /// The arena of the program, which new nodes are allocated in
+    pub fn allocator(&self) -> &'a Allocator {
+        self.allocator
+    }
+
+    pub fn ast(&self) -> AstBuilder<'a> {
+        AstBuilder::new(self.allocator)
+    }
+
+    pub fn source_text(&self) -> &'a str {
+        self.source_text
+    }
+
+    pub fn source_type(&self) -> SourceType {
+        self.source_type
+    }
+
+    /// Up to date for the passes which declare [`TransformPass::needs_semantic`]
+    pub fn symbols(&self) -> Option<&SymbolTable> {
+        self.semantic.as_ref().map(|(symbols, _)| symbols)
+    }
+
+    /// Up to date for the passes which declare [`TransformPass::needs_semantic`]
+    pub fn scopes(&self) -> Option<&ScopeTree> {
+        self.semantic.as_ref().map(|(_, scopes)| scopes)
+    }
+
+    /// Reports an error, after which the program is still transformed by the other passes
+    pub fn error<T: Into<Error>>(&self, error: T) {
+        self.errors.borrow_mut().push(error.into());
+    }
+
+    pub(crate) fn into_errors(self) -> Vec<Error> {
+        self.errors.into_inner()
+    }
+
+    /// Runs `pass`, rebuilding the symbols and scopes before it if it needs them
+    pub(crate) fn run(&mut self, pass: &mut dyn TransformPass<'a>, program: &mut Program<'a>) {
+        if pass.needs_semantic() && self.semantic.is_none() {
+            self.build_semantic(program);
+        }
+        pass.transform(program, self);
+        if pass.invalidates_scopes() {
+            self.semantic = None;
+        }
+    }
+
+    fn build_semantic(&mut self, program: &Program<'a>) {
+        // SAFETY: The semantic only borrows the program while it is built. The symbol table and
+        // the scope tree, which are kept, own their data.
+        let program = unsafe { &*(program as *const Program<'a>) };
+        let semantic = SemanticBuilder::new(self.source_text, self.source_type).build(program);
+        self.semantic = Some(semantic.semantic.into_symbol_table_and_scope_tree());
+    }
+}
@@ -0,0 +1,844 @@
+//! [JSX runtimes](https://react.dev/blog/2020/09/22/introducing-the-new-jsx-transform)
+//!
+//! * classic: `<div a="b">{c}</div>` -> `React.createElement("div", { a: "b" }, c)`
+//! * automatic: `<div a="b">{c}</div>` -> `_jsx("div", { a: "b", children: c })`, with
+//!   `import { jsx as _jsx } from "react/jsx-runtime"`
+
+use std::mem;
+
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::{Atom, SourceType, Span, SPAN};
+
+use crate::{modules::module_base, temporaries::Temporaries};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReactJsxRuntime {
+    /// Calls `pragma`, which must be in scope
+    #[default]
+    Classic,
+    /// Calls `jsx` and `jsxs`, which are imported from `{import_source}/jsx-runtime`
+    Automatic,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReactJsxOptions {
+    /// Default `Classic`
+    pub runtime: ReactJsxRuntime,
+
+    /// Pass `__self` and `__source` for the warnings of React's development build, and call
+    /// `jsxDEV` from `{import_source}/jsx-dev-runtime` with the automatic runtime
+    ///
+    /// Default `false`
+    pub development: bool,
+
+    /// The function called to create elements with the classic runtime
+    ///
+    /// Default `React.createElement`
+    pub pragma: String,
+
+    /// The component passed to `pragma` for fragments
+    ///
+    /// Default `React.Fragment`
+    pub pragma_frag: String,
+
+    /// The module which the automatic runtime is imported from
+    ///
+    /// Default `react`
+    pub import_source: String,
+
+    /// Annotate the created elements with `/* #__PURE__ */`, so bundlers can remove the unused
+    /// ones
+    ///
+    /// Default `true`, except with the classic runtime and a custom `pragma`, which may not be
+    /// pure, as in Babel
+    pub pure: Option<bool>,
+}
+
+const DEFAULT_PRAGMA: &str = "React.createElement";
+
+impl Default for ReactJsxOptions {
+    fn default() -> Self {
+        Self {
+            runtime: ReactJsxRuntime::default(),
+            development: false,
+            pragma: DEFAULT_PRAGMA.into(),
+            pragma_frag: "React.Fragment".into(),
+            import_source: "react".into(),
+            pure: None,
+        }
+    }
+}
+
+/// Functions and components imported by the automatic runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuntimeImport {
+    Jsx,
+    Jsxs,
+    JsxDev,
+    Fragment,
+    /// For `key` after a spread attribute, see [`has_key_after_spread`]
+    CreateElement,
+}
+
+impl RuntimeImport {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Jsx => "jsx",
+            Self::Jsxs => "jsxs",
+            Self::JsxDev => "jsxDEV",
+            Self::Fragment => "Fragment",
+            Self::CreateElement => "createElement",
+        }
+    }
+}
+
+/// Replaces JSX elements and fragments with calls to the runtime, matching Babel's output.
+///
+/// Attributes keep their order relative to spread attributes, so `key` in `<a {...b} key="c" />`
+/// overrides a `key` of `b`. The created nodes keep the spans of the JSX they are created from,
+/// so source maps point to the JSX. The bindings of the runtime skip the names of the program,
+/// `_jsx2` if `_jsx` is declared.
+#[allow(clippy::struct_excessive_bools)]
+pub struct ReactJsx<'a> {
+    ast: AstBuilder<'a>,
+    temporaries: Temporaries<'a>,
+    options: ReactJsxOptions,
+    source_text: &'a str,
+    /// Referenced by `__source` in development
+    filename: String,
+    /// Modules import the automatic runtime, scripts `require` it
+    is_module: bool,
+    /// Byte offsets of the start of each line, only computed in development
+    line_offsets: Vec<u32>,
+
+    /// Imports of the automatic runtime, in order of first use, with their binding in modules
+    /// and the binding of their module in scripts
+    imports: Vec<(RuntimeImport, Atom)>,
+    /// `_jsxFileName` if `__source` references it
+    filename_binding: Option<Atom>,
+    /// Whether `__self` can be `this`, which is not the case before `super()` is called
+    this_allowed: bool,
+    in_derived_class: bool,
+}
+
+impl<'a> ReactJsx<'a> {
+    pub fn new(
+        allocator: &'a Allocator,
+        source_text: &'a str,
+        source_type: SourceType,
+        filename: &str,
+        options: ReactJsxOptions,
+    ) -> Self {
+        let line_offsets = if options.development { line_offsets(source_text) } else { vec![] };
+        Self {
+            ast: AstBuilder::new(allocator),
+            temporaries: Temporaries::new(allocator),
+            options,
+            source_text,
+            filename: filename.to_string(),
+            is_module: source_type.is_module(),
+            line_offsets,
+            imports: vec![],
+            filename_binding: None,
+            this_allowed: true,
+            in_derived_class: false,
+        }
+    }
+
+    fn is_automatic(&self) -> bool {
+        self.options.runtime == ReactJsxRuntime::Automatic
+    }
+
+    /// Whether the created elements are annotated with `/* #__PURE__ */`
+    fn is_pure(&self) -> bool {
+        self.options
+            .pure
+            .unwrap_or_else(|| self.is_automatic() || self.options.pragma == DEFAULT_PRAGMA)
+    }
+
+    fn transform_element(&mut self, elem: JSXElement<'a>) -> Expression<'a> {
+        let opening_element = elem.opening_element.unbox();
+        let element_type = self.element_type(opening_element.name);
+        let attributes = opening_element.attributes;
+        let children = self.children(elem.children);
+
+        if self.is_automatic() && !has_key_after_spread(&attributes) {
+            let source = Some(opening_element.span);
+            return self.jsx(elem.span, element_type, attributes, children, source);
+        }
+
+        let mut properties = self.ast.new_vec_with_capacity(attributes.len());
+        for attribute in attributes {
+            self.accumulate_attribute(&mut properties, attribute);
+        }
+        if self.options.development {
+            if self.this_allowed {
+                properties.push(self.property("__self", self.ast.this_expression(SPAN)));
+            }
+            let source = self.source(opening_element.span);
+            properties.push(self.property("__source", source));
+        }
+        let props = self.create_element_props(opening_element.span, properties);
+        let callee = if self.is_automatic() {
+            self.runtime_callee(RuntimeImport::CreateElement)
+        } else {
+            self.pragma_expression(&self.options.pragma, elem.span)
+        };
+        self.create_element(elem.span, callee, element_type, props, children)
+    }
+
+    fn transform_fragment(&mut self, fragment: JSXFragment<'a>) -> Expression<'a> {
+        let children = self.children(fragment.children);
+        if self.is_automatic() {
+            let element_type = self.runtime_reference(RuntimeImport::Fragment);
+            let attributes = self.ast.new_vec();
+            return self.jsx(fragment.span, element_type, attributes, children, None);
+        }
+        let element_type = self.pragma_expression(&self.options.pragma_frag, fragment.span);
+        let props = self.ast.null_literal_expression();
+        let callee = self.pragma_expression(&self.options.pragma, fragment.span);
+        self.create_element(fragment.span, callee, element_type, props, children)
+    }
+
+    /// `createElement(type, props, ...children)`
+    fn create_element(
+        &self,
+        span: Span,
+        callee: Expression<'a>,
+        element_type: Expression<'a>,
+        props: Expression<'a>,
+        children: Vec<Argument<'a>>,
+    ) -> Expression<'a> {
+        let mut arguments = self.ast.new_vec_with_capacity(children.len() + 2);
+        arguments.push(Argument::Expression(element_type));
+        arguments.push(Argument::Expression(props));
+        arguments.extend(children);
+        self.ast.call_expression(span, callee, arguments, false, None, self.is_pure())
+    }
+
+    /// `null` without props, the argument of a single spread, otherwise an object
+    fn create_element_props(
+        &self,
+        span: Span,
+        mut properties: oxc_allocator::Vec<'a, ObjectPropertyKind<'a>>,
+    ) -> Expression<'a> {
+        if properties.is_empty() {
+            return self.ast.null_literal_expression();
+        }
+        // `<A {...b} />` -> `createElement(A, b)`
+        if let [ObjectPropertyKind::SpreadProperty(spread)] = properties.as_mut_slice() {
+            if !matches!(spread.argument, Expression::ObjectExpression(_)) {
+                return mem::replace(&mut spread.argument, self.ast.null_literal_expression());
+            }
+        }
+        self.ast.object_expression(span, properties, None)
+    }
+
+    /// `jsx(type, { ...props, children }, key)`, or in development
+    /// `jsxDEV(type, { ...props, children }, key, isStaticChildren, source, self)`.
+    ///
+    /// `source` is the span of the opening element, fragments have neither `source` nor `self`.
+    fn jsx(
+        &mut self,
+        span: Span,
+        element_type: Expression<'a>,
+        attributes: oxc_allocator::Vec<'a, JSXAttributeItem<'a>>,
+        children: Vec<Argument<'a>>,
+        source: Option<Span>,
+    ) -> Expression<'a> {
+        let mut key = None;
+        let mut properties = self.ast.new_vec_with_capacity(attributes.len() + 1);
+        for attribute in attributes {
+            match attribute {
+                JSXAttributeItem::Attribute(attribute) if is_key(&attribute) => {
+                    let attribute = attribute.unbox();
+                    key = Some(self.attribute_value(attribute.span, attribute.value));
+                }
+                attribute => self.accumulate_attribute(&mut properties, attribute),
+            }
+        }
+
+        let is_static_children = children.len() > 1;
+        let children = match children.len() {
+            0 => None,
+            1 => children.into_iter().next().map(|child| match child {
+                Argument::Expression(expr) => expr,
+                // `{...a}` is always in an array
+                Argument::SpreadElement(spread) => {
+                    let element = ArrayExpressionElement::SpreadElement(spread);
+                    self.ast.array_expression(SPAN, self.ast.new_vec_single(element), None)
+                }
+            }),
+            _ => {
+                let mut elements = self.ast.new_vec_with_capacity(children.len());
+                elements.extend(children.into_iter().map(|child| match child {
+                    Argument::Expression(expr) => ArrayExpressionElement::Expression(expr),
+                    Argument::SpreadElement(spread) => {
+                        ArrayExpressionElement::SpreadElement(spread)
+                    }
+                }));
+                Some(self.ast.array_expression(SPAN, elements, None))
+            }
+        };
+        if let Some(children) = children {
+            properties.push(self.property("children", children));
+        }
+
+        let props_span = source.unwrap_or(span);
+        let mut arguments = self.ast.new_vec_with_capacity(6);
+        arguments.push(Argument::Expression(element_type));
+        arguments
+            .push(Argument::Expression(self.ast.object_expression(props_span, properties, None)));
+        let callee = if self.options.development {
+            let key = key.unwrap_or_else(|| self.ast.void_0());
+            let is_static_children = self.ast.boolean_literal_expression(is_static_children);
+            arguments.push(Argument::Expression(key));
+            arguments.push(Argument::Expression(is_static_children));
+            if let Some(source) = source {
+                let source = self.source(source);
+                arguments.push(Argument::Expression(source));
+                if self.this_allowed {
+                    arguments.push(Argument::Expression(self.ast.this_expression(SPAN)));
+                }
+            }
+            self.runtime_callee(RuntimeImport::JsxDev)
+        } else {
+            if let Some(key) = key {
+                arguments.push(Argument::Expression(key));
+            }
+            let import = if is_static_children { RuntimeImport::Jsxs } else { RuntimeImport::Jsx };
+            self.runtime_callee(import)
+        };
+        self.ast.call_expression(span, callee, arguments, false, None, self.is_pure())
+    }
+
+    /// `React.createElement` -> `React.createElement`, as a member expression
+    fn pragma_expression(&self, pragma: &str, span: Span) -> Expression<'a> {
+        let mut parts = pragma.split('.');
+        let object = match parts.next() {
+            Some("this") => self.ast.this_expression(span),
+            name => self.identifier(span, name.unwrap_or_default()),
+        };
+        parts.fold(object, |object, property| {
+            let property = IdentifierName { span, name: Atom::from(property) };
+            self.ast.static_member_expression(span, object, property, false)
+        })
+    }
+
+    /// `_jsx` in modules, `_reactJsxRuntime.jsx` in scripts
+    fn runtime_reference(&mut self, import: RuntimeImport) -> Expression<'a> {
+        let binding = self.runtime_binding(import);
+        let reference = self.ast.identifier_reference_expression(&binding);
+        if self.is_module {
+            reference
+        } else {
+            self.ast.member_expression_by_name(reference, import.name())
+        }
+    }
+
+    /// The binding of `import` in modules, or of its module in scripts, created on first use
+    fn runtime_binding(&mut self, import: RuntimeImport) -> Atom {
+        if let Some((_, binding)) = self.imports.iter().find(|(i, _)| *i == import) {
+            return binding.clone();
+        }
+        let binding = if self.is_module {
+            self.temporaries.create_unscoped(import.name())
+        } else {
+            let source = self.runtime_source(import);
+            let module = self.imports.iter().find(|(i, _)| self.runtime_source(*i) == source);
+            match module {
+                Some((_, binding)) => binding.clone(),
+                None => self.temporaries.create_unscoped(&module_base(&source)),
+            }
+        };
+        self.imports.push((import, binding.clone()));
+        binding
+    }
+
+    /// `(0, _reactJsxRuntime.jsx)` in scripts, so the module is not passed as `this`
+    fn runtime_callee(&mut self, import: RuntimeImport) -> Expression<'a> {
+        let reference = self.runtime_reference(import);
+        if self.is_module {
+            return reference;
+        }
+        let mut expressions = self.ast.new_vec_with_capacity(2);
+        expressions.push(self.ast.number_literal_expression(0.0));
+        expressions.push(reference);
+        let sequence = self.ast.sequence_expression(SPAN, expressions);
+        self.ast.parenthesized_expression(SPAN, sequence)
+    }
+
+    fn runtime_source(&self, import: RuntimeImport) -> String {
+        let import_source = &self.options.import_source;
+        match import {
+            RuntimeImport::CreateElement => import_source.clone(),
+            _ if self.options.development => format!("{import_source}/jsx-dev-runtime"),
+            _ => format!("{import_source}/jsx-runtime"),
+        }
+    }
+
+    /// `var _jsxFileName = "filename"` and the imports of the runtime, before the other
+    /// statements
+    fn insert_runtime(&self, program: &mut Program<'a>) {
+        let mut stmts = self.ast.new_vec();
+        if let Some(binding) = &self.filename_binding {
+            let init = self.ast.string_literal_expression(&self.filename);
+            stmts.push(self.variable_declaration(binding, init));
+        }
+
+        let mut sources: Vec<(String, Vec<&(RuntimeImport, Atom)>)> = vec![];
+        for import in &self.imports {
+            let source = self.runtime_source(import.0);
+            match sources.iter_mut().find(|(s, _)| *s == source) {
+                Some((_, imports)) => imports.push(import),
+                None => sources.push((source, vec![import])),
+            }
+        }
+        for (source, imports) in sources {
+            let stmt = if self.is_module {
+                // `import { jsx as _jsx } from "react/jsx-runtime"`
+                let mut specifiers = self.ast.new_vec_with_capacity(imports.len());
+                specifiers.extend(imports.iter().map(|(import, binding)| {
+                    ImportDeclarationSpecifier::ImportSpecifier(ImportSpecifier {
+                        span: SPAN,
+                        imported: ModuleExportName::Identifier(IdentifierName {
+                            span: SPAN,
+                            name: Atom::from(import.name()),
+                        }),
+                        local: BindingIdentifier { span: SPAN, name: binding.clone() },
+                        import_kind: ImportOrExportKind::Value,
+                    })
+                }));
+                let source = StringLiteral { span: SPAN, value: Atom::from(source) };
+                let decl = self.ast.import_declaration(
+                    SPAN,
+                    specifiers,
+                    source,
+                    None,
+                    ImportOrExportKind::Value,
+                );
+                self.ast.module_declaration(ModuleDeclaration::ImportDeclaration(decl))
+            } else {
+                // `var _reactJsxRuntime = require("react/jsx-runtime")`
+                let callee = self.ast.identifier_reference_expression("require");
+                let init = self.ast.call_expression_with_arguments(
+                    callee,
+                    [self.ast.string_literal_expression(&source)],
+                );
+                self.variable_declaration(&imports[0].1, init)
+            };
+            stmts.push(stmt);
+        }
+
+        if !stmts.is_empty() {
+            let body = mem::replace(&mut program.body, stmts);
+            program.body.extend(body);
+        }
+    }
+
+    /// `var name = init`
+    fn variable_declaration(&self, name: &str, init: Expression<'a>) -> Statement<'a> {
+        let id = BindingIdentifier { span: SPAN, name: Atom::from(name) };
+        let id = self.ast.binding_pattern(self.ast.binding_identifier(id), None, false);
+        let kind = VariableDeclarationKind::Var;
+        let declarator = self.ast.variable_declarator(SPAN, kind, id, Some(init), false);
+        let decl = self.ast.variable_declaration(
+            SPAN,
+            kind,
+            self.ast.new_vec_single(declarator),
+            Modifiers::empty(),
+        );
+        Statement::Declaration(Declaration::VariableDeclaration(decl))
+    }
+
+    /// `{ fileName: _jsxFileName, lineNumber: 1, columnNumber: 1 }` for the start of `span`
+    fn source(&mut self, span: Span) -> Expression<'a> {
+        let binding = match &self.filename_binding {
+            Some(binding) => binding.clone(),
+            None => self.temporaries.create_unscoped("jsxFileName"),
+        };
+        let (line, column) = self.position(span.start);
+        let file_name = self.ast.identifier_reference_expression(&binding);
+        self.filename_binding = Some(binding);
+        let line = self.ast.number_literal_expression(f64::from(line));
+        let column = self.ast.number_literal_expression(f64::from(column));
+        let mut properties = self.ast.new_vec_with_capacity(3);
+        properties.push(self.property("fileName", file_name));
+        properties.push(self.property("lineNumber", line));
+        properties.push(self.property("columnNumber", column));
+        self.ast.object_expression(SPAN, properties, None)
+    }
+
+    /// The 1-based line and column of `offset`, with the column in UTF-16 code units
+    #[allow(clippy::cast_possible_truncation)]
+    fn position(&self, offset: u32) -> (u32, u32) {
+        let line = self.line_offsets.partition_point(|&start| start <= offset);
+        let start = self.line_offsets[line - 1] as usize;
+        let end = (offset as usize).min(self.source_text.len());
+        let column = self.source_text.get(start..end).map_or(0, |text| text.encode_utf16().count());
+        (line as u32, column as u32 + 1)
+    }
+
+    /// Intrinsic elements such as `div` and `my-element` are strings, components are references
+    fn element_type(&self, name: JSXElementName<'a>) -> Expression<'a> {
+        match name {
+            JSXElementName::Identifier(ident) if ident.name == "this" => {
+                self.ast.this_expression(ident.span)
+            }
+            JSXElementName::Identifier(ident) => {
+                if ident.name.starts_with(|c: char| c.is_ascii_lowercase())
+                    || ident.name.contains('-')
+                {
+                    self.string_literal(ident.span, ident.name)
+                } else {
+                    self.identifier(ident.span, &ident.name)
+                }
+            }
+            JSXElementName::NamespacedName(name) => self.namespaced_name(&name),
+            JSXElementName::MemberExpression(expr) => self.member_expression(expr.unbox()),
+        }
+    }
+
+    /// `<a.b.c />` -> `a.b.c`
+    fn member_expression(&self, expr: JSXMemberExpression<'a>) -> Expression<'a> {
+        let object = match expr.object {
+            JSXMemberExpressionObject::Identifier(ident) if ident.name == "this" => {
+                self.ast.this_expression(ident.span)
+            }
+            JSXMemberExpressionObject::Identifier(ident) => {
+                self.identifier(ident.span, &ident.name)
+            }
+            JSXMemberExpressionObject::MemberExpression(expr) => {
+                self.member_expression(expr.unbox())
+            }
+        };
+        let property = IdentifierName { span: expr.property.span, name: expr.property.name };
+        self.ast.static_member_expression(expr.span, object, property, false)
+    }
+
+    /// Attributes become properties in source order, the properties of spread object literals
+    /// are inlined
+    fn accumulate_attribute(
+        &mut self,
+        properties: &mut oxc_allocator::Vec<'a, ObjectPropertyKind<'a>>,
+        attribute: JSXAttributeItem<'a>,
+    ) {
+        match attribute {
+            JSXAttributeItem::Attribute(attribute) => {
+                let attribute = attribute.unbox();
+                let key = self.property_key(attribute.name);
+                let value = self.attribute_value(attribute.span, attribute.value);
+                properties.push(ObjectPropertyKind::ObjectProperty(self.ast.object_property(
+                    attribute.span,
+                    PropertyKind::Init,
+                    key,
+                    value,
+                    None,
+                    false,
+                    false,
+                    false,
+                )));
+            }
+            JSXAttributeItem::SpreadAttribute(attribute) => {
+                let attribute = attribute.unbox();
+                match attribute.argument {
+                    // `{...{ a: 1 }}` -> `a: 1`
+                    Expression::ObjectExpression(object) if !has_proto(&object) => {
+                        properties.extend(object.unbox().properties);
+                    }
+                    argument => {
+                        let spread = self.ast.spread_element(attribute.span, argument);
+                        properties.push(ObjectPropertyKind::SpreadProperty(spread));
+                    }
+                }
+            }
+        }
+    }
+
+    /// `className` is an identifier, `aria-label` and `xlink:href` are strings
+    fn property_key(&self, name: JSXAttributeName<'a>) -> PropertyKey<'a> {
+        match name {
+            JSXAttributeName::Identifier(ident) if ident.name.contains('-') => {
+                PropertyKey::Expression(self.string_literal(ident.span, ident.name))
+            }
+            JSXAttributeName::Identifier(ident) => PropertyKey::Identifier(
+                self.ast.alloc(IdentifierName { span: ident.span, name: ident.name }),
+            ),
+            JSXAttributeName::NamespacedName(name) => {
+                PropertyKey::Expression(self.namespaced_name(&name))
+            }
+        }
+    }
+
+    fn attribute_value(
+        &mut self,
+        span: Span,
+        value: Option<JSXAttributeValue<'a>>,
+    ) -> Expression<'a> {
+        match value {
+            // `<input disabled />`
+            None => self.ast.literal_boolean_expression(BooleanLiteral { span, value: true }),
+            Some(JSXAttributeValue::StringLiteral(mut literal)) => {
+                if literal.value.contains('\n') {
+                    literal.value = Atom::from(collapse_line_breaks(&literal.value));
+                }
+                self.ast.literal_string_expression(literal)
+            }
+            Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                match container.expression {
+                    JSXExpression::Expression(expr) => expr,
+                    // `a={}` is a syntax error
+                    JSXExpression::EmptyExpression(_) => self.ast.void_0(),
+                }
+            }
+            Some(JSXAttributeValue::Element(elem)) => self.transform_element(elem.unbox()),
+            Some(JSXAttributeValue::Fragment(fragment)) => {
+                self.transform_fragment(fragment.unbox())
+            }
+        }
+    }
+
+    fn children(&mut self, children: oxc_allocator::Vec<'a, JSXChild<'a>>) -> Vec<Argument<'a>> {
+        children.into_iter().filter_map(|child| self.child(child)).collect()
+    }
+
+    /// Children are passed as arguments, except empty expressions and text which is only
+    /// whitespace
+    fn child(&mut self, child: JSXChild<'a>) -> Option<Argument<'a>> {
+        let expr = match child {
+            JSXChild::Text(text) => {
+                let value = clean_text(&text.value)?;
+                self.string_literal(text.span, Atom::from(value))
+            }
+            JSXChild::Element(elem) => self.transform_element(elem.unbox()),
+            JSXChild::Fragment(fragment) => self.transform_fragment(fragment.unbox()),
+            JSXChild::ExpressionContainer(container) => match container.expression {
+                JSXExpression::Expression(expr) => expr,
+                JSXExpression::EmptyExpression(_) => return None,
+            },
+            // `{...children}`
+            JSXChild::Spread(spread) => {
+                let spread = self.ast.spread_element(spread.span, spread.expression);
+                return Some(Argument::SpreadElement(spread));
+            }
+        };
+        Some(Argument::Expression(expr))
+    }
+
+    /// `<a:b />` -> `"a:b"`
+    fn namespaced_name(&self, name: &JSXNamespacedName) -> Expression<'a> {
+        let value = format!("{}:{}", name.namespace.name, name.property.name);
+        self.string_literal(name.span, Atom::from(value))
+    }
+
+    /// `name: value`
+    fn property(&self, name: &str, value: Expression<'a>) -> ObjectPropertyKind<'a> {
+        let key = IdentifierName { span: SPAN, name: Atom::from(name) };
+        ObjectPropertyKind::ObjectProperty(self.ast.object_property(
+            SPAN,
+            PropertyKind::Init,
+            PropertyKey::Identifier(self.ast.alloc(key)),
+            value,
+            None,
+            false,
+            false,
+            false,
+        ))
+    }
+
+    fn identifier(&self, span: Span, name: &str) -> Expression<'a> {
+        self.ast.identifier_expression(IdentifierReference { span, name: Atom::from(name) })
+    }
+
+    fn string_literal(&self, span: Span, value: Atom) -> Expression<'a> {
+        self.ast.literal_string_expression(StringLiteral { span, value })
+    }
+
+    /// Visit `func`, where `__self` is `this` if `this_allowed`
+    fn visit_function_with_this(&mut self, func: &mut Function<'a>, this_allowed: bool) {
+        let this_allowed = mem::replace(&mut self.this_allowed, this_allowed);
+        if let Some(ident) = &mut func.id {
+            self.visit_binding_identifier(ident);
+        }
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+        self.this_allowed = this_allowed;
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for ReactJsx<'a> {
+    fn visit_program(&mut self, program: &'b mut Program<'a>) {
+        self.temporaries.reserve_names(program);
+        if let Some(hashbang) = &mut program.hashbang {
+            self.visit_hashbang(hashbang);
+        }
+        for directive in program.directives.iter_mut() {
+            self.visit_directive(directive);
+        }
+        self.visit_statements(&mut program.body);
+        self.insert_runtime(program);
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        // JSX in attributes and children is transformed first
+        self.visit_expression_match(expr);
+        if matches!(expr, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
+            *expr = match mem::replace(expr, self.ast.null_literal_expression()) {
+                Expression::JSXElement(elem) => self.transform_element(elem.unbox()),
+                Expression::JSXFragment(fragment) => self.transform_fragment(fragment.unbox()),
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        self.visit_function_with_this(func, true);
+    }
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        for decorator in class.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        if let Some(id) = &mut class.id {
+            self.visit_binding_identifier(id);
+        }
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        let in_derived_class =
+            mem::replace(&mut self.in_derived_class, class.super_class.is_some());
+        self.visit_class_body(&mut class.body);
+        self.in_derived_class = in_derived_class;
+    }
+
+    fn visit_method_definition(&mut self, def: &'b mut MethodDefinition<'a>) {
+        for decorator in def.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        self.visit_property_key(&mut def.key);
+        // `this` can't be used before `super()` in the constructor of a derived class
+        let this_allowed =
+            !(def.kind == MethodDefinitionKind::Constructor && self.in_derived_class);
+        self.visit_function_with_this(&mut def.value, this_allowed);
+    }
+
+    fn visit_ts_module_block(&mut self, block: &'b mut TSModuleBlock<'a>) {
+        let this_allowed = mem::replace(&mut self.this_allowed, false);
+        self.visit_statements(&mut block.body);
+        self.this_allowed = this_allowed;
+    }
+}
+
+fn is_key(attribute: &JSXAttribute) -> bool {
+    matches!(&attribute.name, JSXAttributeName::Identifier(ident) if ident.name == "key")
+}
+
+/// `<div {...a} key="b" />`, where `key` must override `a.key` so `createElement` is called
+fn has_key_after_spread(attributes: &[JSXAttributeItem]) -> bool {
+    attributes
+        .iter()
+        .skip_while(|attribute| !matches!(attribute, JSXAttributeItem::SpreadAttribute(_)))
+        .any(|attribute| {
+            matches!(attribute, JSXAttributeItem::Attribute(attribute) if is_key(attribute))
+        })
+}
+
+/// `{ __proto__: a }` sets the prototype, so it is not inlined from a spread
+fn has_proto(object: &ObjectExpression) -> bool {
+    object.properties.iter().any(|property| match property {
+        ObjectPropertyKind::ObjectProperty(property) => {
+            !property.computed
+                && !property.shorthand
+                && property.key.is_specific_static_name("__proto__")
+        }
+        ObjectPropertyKind::SpreadProperty(_) => false,
+    })
+}
+
+/// Line breaks followed by whitespace in attribute strings are collapsed to a space
+fn collapse_line_breaks(value: &str) -> String {
+    let mut collapsed = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' && chars.next_if(|c| c.is_whitespace()).is_some() {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            collapsed.push(' ');
+        } else {
+            collapsed.push(c);
+        }
+    }
+    collapsed
+}
+
+/// Collapse the whitespace of text the same way as React: lines are trimmed, lines which are
+/// only whitespace are removed and the remaining lines are joined with a space. `None` if
+/// nothing is left.
+fn clean_text(text: &str) -> Option<String> {
+    let lines: Vec<&str> =
+        text.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).collect();
+    let last_line = lines.len() - 1;
+    let last_non_empty_line =
+        lines.iter().rposition(|line| line.contains(|c: char| c != ' ' && c != '\t')).unwrap_or(0);
+
+    let mut value = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.replace('\t', " ");
+        let mut line = line.as_str();
+        if i != 0 {
+            line = line.trim_start_matches(' ');
+        }
+        if i != last_line {
+            line = line.trim_end_matches(' ');
+        }
+        if !line.is_empty() {
+            value.push_str(line);
+            if i != last_non_empty_line {
+                value.push(' ');
+            }
+        }
+    }
+    (!value.is_empty()).then_some(value)
+}
+
+/// Byte offsets of the start of each line, lines are ended by `\n`, `\r\n`, `\r`, `<LS>` and
+/// `<PS>`
+#[allow(clippy::cast_possible_truncation)]
+fn line_offsets(source_text: &str) -> Vec<u32> {
+    let mut offsets = vec![0];
+    let mut chars = source_text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\r' if chars.next_if(|(_, c)| *c == '\n').is_some() => {
+                offsets.push(index as u32 + 2);
+            }
+            '\r' | '\n' | '\u{2028}' | '\u{2029}' => offsets.push((index + c.len_utf8()) as u32),
+            _ => {}
+        }
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod test {
+    use super::{clean_text, collapse_line_breaks};
+
+    #[test]
+    fn text() {
+        assert_eq!(clean_text("a b"), Some("a b".into()));
+        assert_eq!(clean_text(" a "), Some(" a ".into()));
+        assert_eq!(clean_text(" "), Some(" ".into()));
+        assert_eq!(clean_text("\n  a\n  b  \n"), Some("a b".into()));
+        assert_eq!(clean_text("a\r\n\tb"), Some("a b".into()));
+        assert_eq!(clean_text("\n   \n"), None);
+    }
+
+    #[test]
+    fn attribute_strings() {
+        assert_eq!(collapse_line_breaks("a\n    b"), "a b");
+        assert_eq!(collapse_line_breaks("a\n\n  b\n"), "a b\n");
+    }
+}
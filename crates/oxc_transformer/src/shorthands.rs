@@ -0,0 +1,437 @@
+//! Shorthands for building the code the passes insert, e.g. their helpers
+
+use oxc_allocator::Vec;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator};
+
+/// Shorthands for the code created by the passes
+pub trait Shorthands<'a> {
+    fn ident(&self, name: &str) -> Expression<'a>;
+
+    fn string(&self, value: &str) -> Expression<'a>;
+
+    fn number(&self, value: f64) -> Expression<'a>;
+
+    /// `object.name`
+    fn member(&self, object: Expression<'a>, name: &str) -> Expression<'a>;
+
+    /// `object[expression]`
+    fn computed(&self, object: Expression<'a>, expression: Expression<'a>) -> Expression<'a>;
+
+    /// `object[index]`
+    fn index(&self, object: Expression<'a>, index: u32) -> Expression<'a>;
+
+    fn call<I>(&self, callee: Expression<'a>, arguments: I) -> Expression<'a>
+    where
+        I: IntoIterator<Item = Expression<'a>>;
+
+    fn array<I>(&self, elements: I) -> Expression<'a>
+    where
+        I: IntoIterator<Item = Expression<'a>>;
+
+    /// `{ name: value, ... }`
+    fn object<'n, I>(&self, properties: I) -> Expression<'a>
+    where
+        I: IntoIterator<Item = (&'n str, Expression<'a>)>;
+
+    /// `target = value`, where `target` is an identifier or a member expression
+    fn assign(&self, target: Expression<'a>, value: Expression<'a>) -> Expression<'a>;
+
+    fn binary(
+        &self,
+        left: Expression<'a>,
+        operator: BinaryOperator,
+        right: Expression<'a>,
+    ) -> Expression<'a>;
+
+    fn logical(
+        &self,
+        left: Expression<'a>,
+        operator: LogicalOperator,
+        right: Expression<'a>,
+    ) -> Expression<'a>;
+
+    fn conditional(
+        &self,
+        test: Expression<'a>,
+        consequent: Expression<'a>,
+        alternate: Expression<'a>,
+    ) -> Expression<'a>;
+
+    fn not(&self, argument: Expression<'a>) -> Expression<'a>;
+
+    fn type_of(&self, argument: Expression<'a>) -> Expression<'a>;
+
+    /// `(params) => { body }`
+    fn arrow(&self, params: &[&str], body: Vec<'a, Statement<'a>>) -> Expression<'a>;
+
+    /// `(params) => expression`
+    fn arrow_returning(&self, params: &[&str], expression: Expression<'a>) -> Expression<'a>;
+
+    /// `function name(params) { body }`, where the parameters of methods are unique
+    fn function_of(
+        &self,
+        r#type: FunctionType,
+        name: Option<Atom>,
+        params: &[&str],
+        body: Vec<'a, Statement<'a>>,
+    ) -> oxc_allocator::Box<'a, Function<'a>>;
+
+    /// `function name(params) { body }`
+    fn function_declaration_of(
+        &self,
+        name: Atom,
+        params: &[&str],
+        body: Vec<'a, Statement<'a>>,
+    ) -> Statement<'a>;
+
+    /// `key(params) { body }`, or a getter, setter or constructor by `kind`
+    fn method_definition(
+        &self,
+        kind: MethodDefinitionKind,
+        key: PropertyKey<'a>,
+        computed: bool,
+        is_static: bool,
+        value: oxc_allocator::Box<'a, Function<'a>>,
+    ) -> ClassElement<'a>;
+
+    /// `key = value;`
+    fn property_definition(
+        &self,
+        key: PropertyKey<'a>,
+        computed: bool,
+        is_static: bool,
+        value: Option<Expression<'a>>,
+    ) -> ClassElement<'a>;
+
+    fn statement(&self, expression: Expression<'a>) -> Statement<'a>;
+
+    fn statements<I>(&self, statements: I) -> Vec<'a, Statement<'a>>
+    where
+        I: IntoIterator<Item = Statement<'a>>;
+
+    fn block_of(&self, statements: Vec<'a, Statement<'a>>) -> Statement<'a>;
+
+    fn if_else(
+        &self,
+        test: Expression<'a>,
+        consequent: Statement<'a>,
+        alternate: Option<Statement<'a>>,
+    ) -> Statement<'a>;
+
+    /// `throw new TypeError(message)`
+    fn throw_type_error(&self, message: &str) -> Statement<'a>;
+
+    /// `var name = init, ...`
+    fn var_declaration_of<'n, I>(
+        &self,
+        kind: VariableDeclarationKind,
+        declarations: I,
+    ) -> oxc_allocator::Box<'a, VariableDeclaration<'a>>
+    where
+        I: IntoIterator<Item = (&'n str, Option<Expression<'a>>)>;
+
+    /// `var name = init, ...;`
+    fn var_declaration_statement<'n, I>(
+        &self,
+        kind: VariableDeclarationKind,
+        declarations: I,
+    ) -> Statement<'a>
+    where
+        I: IntoIterator<Item = (&'n str, Option<Expression<'a>>)>;
+}
+
+impl<'a> Shorthands<'a> for AstBuilder<'a> {
+    fn ident(&self, name: &str) -> Expression<'a> {
+        self.identifier_reference_expression(name)
+    }
+
+    fn string(&self, value: &str) -> Expression<'a> {
+        self.string_literal_expression(value)
+    }
+
+    fn number(&self, value: f64) -> Expression<'a> {
+        self.number_literal_expression(value)
+    }
+
+    fn member(&self, object: Expression<'a>, name: &str) -> Expression<'a> {
+        self.member_expression_by_name(object, name)
+    }
+
+    fn computed(&self, object: Expression<'a>, expression: Expression<'a>) -> Expression<'a> {
+        self.computed_member_expression(SPAN, object, expression, false)
+    }
+
+    fn index(&self, object: Expression<'a>, index: u32) -> Expression<'a> {
+        self.computed(object, self.number(f64::from(index)))
+    }
+
+    fn call<I>(&self, callee: Expression<'a>, arguments: I) -> Expression<'a>
+    where
+        I: IntoIterator<Item = Expression<'a>>,
+    {
+        self.call_expression_with_arguments(callee, arguments)
+    }
+
+    fn array<I>(&self, elements: I) -> Expression<'a>
+    where
+        I: IntoIterator<Item = Expression<'a>>,
+    {
+        let mut array = self.new_vec();
+        array.extend(elements.into_iter().map(ArrayExpressionElement::Expression));
+        self.array_expression(SPAN, array, None)
+    }
+
+    fn object<'n, I>(&self, properties: I) -> Expression<'a>
+    where
+        I: IntoIterator<Item = (&'n str, Expression<'a>)>,
+    {
+        let mut object = self.new_vec();
+        object.extend(properties.into_iter().map(|(name, value)| {
+            let key = IdentifierName { span: SPAN, name: Atom::from(name) };
+            ObjectPropertyKind::ObjectProperty(self.object_property(
+                SPAN,
+                PropertyKind::Init,
+                PropertyKey::Identifier(self.alloc(key)),
+                value,
+                None,
+                false,
+                false,
+                false,
+            ))
+        }));
+        self.object_expression(SPAN, object, None)
+    }
+
+    fn assign(&self, target: Expression<'a>, value: Expression<'a>) -> Expression<'a> {
+        let target = match target {
+            Expression::Identifier(ident) => {
+                SimpleAssignmentTarget::AssignmentTargetIdentifier(ident)
+            }
+            Expression::MemberExpression(member) => {
+                SimpleAssignmentTarget::MemberAssignmentTarget(member)
+            }
+            _ => unreachable!("only identifiers and members are assigned"),
+        };
+        self.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            value,
+        )
+    }
+
+    fn binary(
+        &self,
+        left: Expression<'a>,
+        operator: BinaryOperator,
+        right: Expression<'a>,
+    ) -> Expression<'a> {
+        self.binary_expression(SPAN, left, operator, right)
+    }
+
+    fn logical(
+        &self,
+        left: Expression<'a>,
+        operator: LogicalOperator,
+        right: Expression<'a>,
+    ) -> Expression<'a> {
+        self.logical_expression(SPAN, left, operator, right)
+    }
+
+    fn conditional(
+        &self,
+        test: Expression<'a>,
+        consequent: Expression<'a>,
+        alternate: Expression<'a>,
+    ) -> Expression<'a> {
+        self.conditional_expression(SPAN, test, consequent, alternate)
+    }
+
+    fn not(&self, argument: Expression<'a>) -> Expression<'a> {
+        self.unary_expression(SPAN, UnaryOperator::LogicalNot, argument)
+    }
+
+    fn type_of(&self, argument: Expression<'a>) -> Expression<'a> {
+        self.unary_expression(SPAN, UnaryOperator::Typeof, argument)
+    }
+
+    fn arrow(&self, params: &[&str], body: Vec<'a, Statement<'a>>) -> Expression<'a> {
+        let params = formal_parameters(self, FormalParameterKind::ArrowFormalParameters, params);
+        let body = self.function_body(SPAN, self.new_vec(), body);
+        self.arrow_expression(SPAN, false, false, false, params, body, None, None)
+    }
+
+    fn arrow_returning(&self, params: &[&str], expression: Expression<'a>) -> Expression<'a> {
+        let params = formal_parameters(self, FormalParameterKind::ArrowFormalParameters, params);
+        let body = self.new_vec_single(self.statement(expression));
+        let body = self.function_body(SPAN, self.new_vec(), body);
+        self.arrow_expression(SPAN, true, false, false, params, body, None, None)
+    }
+
+    fn function_of(
+        &self,
+        r#type: FunctionType,
+        name: Option<Atom>,
+        params: &[&str],
+        body: Vec<'a, Statement<'a>>,
+    ) -> oxc_allocator::Box<'a, Function<'a>> {
+        let kind = if name.is_some() {
+            FormalParameterKind::FormalParameter
+        } else {
+            FormalParameterKind::UniqueFormalParameters
+        };
+        let params = formal_parameters(self, kind, params);
+        let body = self.function_body(SPAN, self.new_vec(), body);
+        self.function(
+            r#type,
+            SPAN,
+            name.map(|name| BindingIdentifier { span: SPAN, name }),
+            false,
+            false,
+            false,
+            params,
+            Some(body),
+            None,
+            None,
+            Modifiers::empty(),
+        )
+    }
+
+    fn function_declaration_of(
+        &self,
+        name: Atom,
+        params: &[&str],
+        body: Vec<'a, Statement<'a>>,
+    ) -> Statement<'a> {
+        self.function_declaration(self.function_of(
+            FunctionType::FunctionDeclaration,
+            Some(name),
+            params,
+            body,
+        ))
+    }
+
+    fn method_definition(
+        &self,
+        kind: MethodDefinitionKind,
+        key: PropertyKey<'a>,
+        computed: bool,
+        is_static: bool,
+        value: oxc_allocator::Box<'a, Function<'a>>,
+    ) -> ClassElement<'a> {
+        ClassElement::MethodDefinition(self.alloc(MethodDefinition {
+            span: SPAN,
+            key,
+            value,
+            kind,
+            computed,
+            r#static: is_static,
+            r#override: false,
+            optional: false,
+            accessibility: None,
+            decorators: self.new_vec(),
+        }))
+    }
+
+    fn property_definition(
+        &self,
+        key: PropertyKey<'a>,
+        computed: bool,
+        is_static: bool,
+        value: Option<Expression<'a>>,
+    ) -> ClassElement<'a> {
+        ClassElement::PropertyDefinition(self.alloc(PropertyDefinition {
+            span: SPAN,
+            key,
+            value,
+            computed,
+            r#static: is_static,
+            declare: false,
+            r#override: false,
+            optional: false,
+            definite: false,
+            readonly: false,
+            type_annotation: None,
+            accessibility: None,
+            decorators: self.new_vec(),
+        }))
+    }
+
+    fn statement(&self, expression: Expression<'a>) -> Statement<'a> {
+        self.expression_statement(SPAN, expression)
+    }
+
+    fn statements<I>(&self, statements: I) -> Vec<'a, Statement<'a>>
+    where
+        I: IntoIterator<Item = Statement<'a>>,
+    {
+        let mut vec = self.new_vec();
+        vec.extend(statements);
+        vec
+    }
+
+    fn block_of(&self, statements: Vec<'a, Statement<'a>>) -> Statement<'a> {
+        Statement::BlockStatement(self.block(SPAN, statements))
+    }
+
+    fn if_else(
+        &self,
+        test: Expression<'a>,
+        consequent: Statement<'a>,
+        alternate: Option<Statement<'a>>,
+    ) -> Statement<'a> {
+        self.if_statement(SPAN, test, consequent, alternate)
+    }
+
+    fn throw_type_error(&self, message: &str) -> Statement<'a> {
+        let arguments = self.new_vec_single(Argument::Expression(self.string(message)));
+        let error = self.new_expression(SPAN, self.ident("TypeError"), arguments, None, false);
+        self.throw_statement(SPAN, error)
+    }
+
+    fn var_declaration_of<'n, I>(
+        &self,
+        kind: VariableDeclarationKind,
+        declarations: I,
+    ) -> oxc_allocator::Box<'a, VariableDeclaration<'a>>
+    where
+        I: IntoIterator<Item = (&'n str, Option<Expression<'a>>)>,
+    {
+        let mut declarators = self.new_vec();
+        declarators.extend(declarations.into_iter().map(|(name, init)| {
+            let id = BindingIdentifier { span: SPAN, name: Atom::from(name) };
+            let id = self.binding_pattern(self.binding_identifier(id), None, false);
+            self.variable_declarator(SPAN, kind, id, init, false)
+        }));
+        self.variable_declaration(SPAN, kind, declarators, Modifiers::empty())
+    }
+
+    fn var_declaration_statement<'n, I>(
+        &self,
+        kind: VariableDeclarationKind,
+        declarations: I,
+    ) -> Statement<'a>
+    where
+        I: IntoIterator<Item = (&'n str, Option<Expression<'a>>)>,
+    {
+        let declaration = self.var_declaration_of(kind, declarations);
+        Statement::Declaration(Declaration::VariableDeclaration(declaration))
+    }
+}
+
+fn formal_parameters<'a>(
+    ast: &AstBuilder<'a>,
+    kind: FormalParameterKind,
+    names: &[&str],
+) -> oxc_allocator::Box<'a, FormalParameters<'a>> {
+    let mut items = ast.new_vec_with_capacity(names.len());
+    items.extend(names.iter().map(|name| {
+        let id = BindingIdentifier { span: SPAN, name: Atom::from(*name) };
+        let pattern = ast.binding_pattern(ast.binding_identifier(id), None, false);
+        ast.formal_parameter(SPAN, pattern, None, false, false, ast.new_vec())
+    }));
+    ast.formal_parameters(SPAN, kind, items, None)
+}
@@ -0,0 +1,41 @@
+//! The symbols of the identifiers of the program, for the passes which need the semantic
+
+use std::collections::HashMap;
+
+use oxc_semantic::{SymbolId, SymbolTable};
+use oxc_span::{Span, SPAN};
+
+/// The symbols of the bindings and the resolved references of the program, by the starts of
+/// their spans, as spans hash to nothing
+#[derive(Default)]
+pub struct Symbols {
+    bindings: HashMap<u32, SymbolId>,
+    references: HashMap<u32, SymbolId>,
+}
+
+impl Symbols {
+    pub fn new(symbols: &SymbolTable) -> Self {
+        let bindings = symbols
+            .spans
+            .iter_enumerated()
+            .filter(|(_, span)| **span != SPAN)
+            .map(|(symbol_id, span)| (span.start, symbol_id))
+            .collect();
+        let references = symbols
+            .references
+            .iter()
+            .filter(|reference| reference.span() != SPAN)
+            .filter_map(|reference| Some((reference.span().start, reference.symbol_id()?)))
+            .collect();
+        Self { bindings, references }
+    }
+
+    /// `None` for the identifiers which passes created
+    pub fn binding(&self, span: Span) -> Option<SymbolId> {
+        (span != SPAN).then(|| self.bindings.get(&span.start).copied()).flatten()
+    }
+
+    pub fn reference(&self, span: Span) -> Option<SymbolId> {
+        (span != SPAN).then(|| self.references.get(&span.start).copied()).flatten()
+    }
+}
@@ -0,0 +1,220 @@
+//! Temporary variables for values which are referenced more than once
+
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::AssignmentOperator;
+
+/// Creates temporary variables, which are declared with `var` at the start of the function
+/// they are used in.
+///
+/// Names are `_{base}`, `_{base}2`, ... skipping the names of the identifiers in the program,
+/// including the temporaries of the passes which ran before.
+pub struct Temporaries<'a> {
+    ast: AstBuilder<'a>,
+    /// The names of the identifiers in the program and of the temporaries created so far
+    names: HashSet<Atom>,
+    /// The next suffix of each base
+    suffixes: HashMap<String, usize>,
+    /// The temporaries of each function being visited, innermost last
+    scopes: std::vec::Vec<std::vec::Vec<Atom>>,
+    /// The addresses of the parameters of setters, which have exactly one parameter
+    setters: HashSet<usize>,
+}
+
+impl<'a> Temporaries<'a> {
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self {
+            ast: AstBuilder::new(allocator),
+            names: HashSet::new(),
+            suffixes: HashMap::new(),
+            scopes: vec![],
+            setters: HashSet::new(),
+        }
+    }
+
+    /// Collects the names temporaries have to avoid, before `program` is visited
+    pub fn reserve_names(&mut self, program: &mut Program<'a>) {
+        NameCollector { names: &mut self.names, setters: &mut self.setters }.visit_program(program);
+    }
+
+    pub fn enter_scope(&mut self) {
+        self.scopes.push(vec![]);
+    }
+
+    /// Declares the temporaries created since the matching [`Temporaries::enter_scope`] at the
+    /// start of `stmts`
+    pub fn exit_scope(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let names = self.scopes.pop().unwrap_or_default();
+        if names.is_empty() {
+            return;
+        }
+        let kind = VariableDeclarationKind::Var;
+        let mut declarations = self.ast.new_vec_with_capacity(names.len());
+        declarations.extend(names.into_iter().map(|name| {
+            let id = self.ast.binding_identifier(BindingIdentifier { span: SPAN, name });
+            let id = self.ast.binding_pattern(id, None, false);
+            self.ast.variable_declarator(SPAN, kind, id, None, false)
+        }));
+        let decl = self.ast.variable_declaration(SPAN, kind, declarations, Modifiers::empty());
+        stmts.insert(0, Statement::Declaration(Declaration::VariableDeclaration(decl)));
+    }
+
+    /// Declares the temporaries created since the matching [`Temporaries::enter_scope`] as
+    /// parameters after `params`, `(a = b?.c) => {}` -> `(a = _ref?.c, _ref = void 0) => {}`,
+    /// as the parameters can not see the `var`s of the body.
+    ///
+    /// A parameter after a default value does not change the `length` of the function. The
+    /// temporaries of setters and of parameters with a rest parameter are declared in the
+    /// enclosing scope instead.
+    pub fn exit_parameters_scope(&mut self, params: &mut FormalParameters<'a>) {
+        let names = self.scopes.pop().unwrap_or_default();
+        let is_setter = self.setters.contains(&(std::ptr::addr_of!(*params) as usize));
+        if params.rest.is_some() || is_setter {
+            for name in names {
+                self.declare(name);
+            }
+            return;
+        }
+        params.items.extend(names.into_iter().map(|name| {
+            let id = self.ast.binding_identifier(BindingIdentifier { span: SPAN, name });
+            let id = self.ast.binding_pattern(id, None, false);
+            let pattern = self.ast.assignment_pattern(SPAN, id, self.ast.void_0());
+            self.ast.formal_parameter(SPAN, pattern, None, false, false, self.ast.new_vec())
+        }));
+    }
+
+    /// `() => a` -> `() => { var _ref; return a; }` if temporaries were declared in the body
+    pub fn expand_arrow_body(&self, expr: &mut ArrowExpression<'a>) {
+        if !expr.expression || expr.body.statements.len() < 2 {
+            return;
+        }
+        expr.expression = false;
+        if let Some(stmt) = expr.body.statements.last_mut() {
+            if let Statement::ExpressionStatement(expr_stmt) = stmt {
+                let span = expr_stmt.span;
+                let argument =
+                    mem::replace(&mut expr_stmt.expression, self.ast.null_literal_expression());
+                *stmt = self.ast.return_statement(span, Some(argument));
+            }
+        }
+    }
+
+    /// A new temporary in the current scope
+    pub fn create(&mut self, base: &str) -> Atom {
+        let name = self.create_unscoped(base);
+        self.declare(name.clone());
+        name
+    }
+
+    /// A new name, which the caller declares
+    pub fn create_unscoped(&mut self, base: &str) -> Atom {
+        let suffix = self.suffixes.entry(base.to_string()).or_insert(1);
+        loop {
+            let name = if *suffix == 1 { format!("_{base}") } else { format!("_{base}{suffix}") };
+            *suffix += 1;
+            let name = Atom::from(name);
+            if self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    /// Reserves `name` for the caller, if it is not a name of the program or a temporary yet
+    pub fn reserve(&mut self, name: Atom) -> bool {
+        self.names.insert(name)
+    }
+
+    /// Declares a name from [`Temporaries::create_unscoped`] in the current scope
+    pub fn declare(&mut self, name: Atom) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push(name);
+        }
+    }
+
+    /// Evaluate `expr` once and reference its value again: returns the expression to evaluate
+    /// and a reference, which can be copied with [`Temporaries::reference`].
+    ///
+    /// `a` -> `(a, a)`, `a.b` -> `((_ref = a.b), _ref)`
+    pub fn memoize(
+        &mut self,
+        expr: Expression<'a>,
+        base: &str,
+    ) -> (Expression<'a>, Expression<'a>) {
+        if let Some(reference) = self.reference(&expr) {
+            return (expr, reference);
+        }
+        let name = self.create(base);
+        let target = SimpleAssignmentTarget::AssignmentTargetIdentifier(
+            self.ast.alloc(IdentifierReference { span: SPAN, name: name.clone() }),
+        );
+        let assignment = self.ast.assignment_expression(
+            SPAN,
+            AssignmentOperator::Assign,
+            AssignmentTarget::SimpleAssignmentTarget(target),
+            expr,
+        );
+        let value = self.ast.parenthesized_expression(SPAN, assignment);
+        (value, self.ast.identifier_reference_expression(&name))
+    }
+
+    /// A copy of `expr` if it can be evaluated again without side effects, i.e. an identifier,
+    /// `this`, `super` or a string or number literal
+    pub fn reference(&self, expr: &Expression<'a>) -> Option<Expression<'a>> {
+        match expr {
+            Expression::Identifier(ident) => {
+                Some(self.ast.identifier_expression((**ident).clone()))
+            }
+            Expression::ThisExpression(this) => Some(self.ast.this_expression(this.span)),
+            Expression::Super(sup) => Some(self.ast.super_(sup.span)),
+            Expression::StringLiteral(lit) => {
+                Some(self.ast.literal_string_expression((**lit).clone()))
+            }
+            Expression::NumberLiteral(lit) => {
+                Some(self.ast.literal_number_expression((**lit).clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+struct NameCollector<'n> {
+    names: &'n mut HashSet<Atom>,
+    setters: &'n mut HashSet<usize>,
+}
+
+impl<'a, 'b, 'n> VisitMut<'a, 'b> for NameCollector<'n> {
+    fn visit_binding_identifier(&mut self, ident: &'b mut BindingIdentifier) {
+        self.names.insert(ident.name.clone());
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &'b mut IdentifierReference) {
+        self.names.insert(ident.name.clone());
+    }
+
+    fn visit_method_definition(&mut self, def: &'b mut MethodDefinition<'a>) {
+        if def.kind == MethodDefinitionKind::Set {
+            self.setters.insert(std::ptr::addr_of!(*def.value.params) as usize);
+        }
+        for decorator in def.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        self.visit_property_key(&mut def.key);
+        self.visit_function(&mut def.value);
+    }
+
+    fn visit_object_property(&mut self, prop: &'b mut ObjectProperty<'a>) {
+        if let (PropertyKind::Set, Expression::FunctionExpression(func)) = (prop.kind, &prop.value)
+        {
+            self.setters.insert(std::ptr::addr_of!(*func.params) as usize);
+        }
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+    }
+}
@@ -0,0 +1,361 @@
+//! `enum E { A, B = "b" }` ->
+//! `var E; (function (E) { E[E["A"] = 0] = "A"; E["B"] = "b"; })(E || (E = {}));`
+//!
+//! The members of const enums are inlined where they are referenced, `E.A` -> `0`, and the
+//! declarations of const enums are removed.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use oxc_allocator::{Box, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_semantic::{ConstantValue, ScopeTree, SymbolFlags, SymbolId, SymbolTable};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::{LogicalOperator, UnaryOperator};
+
+use super::TypeScript;
+use crate::{shorthands::Shorthands, symbols::Symbols};
+
+/// The value of a constant enum member
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstEnumValue {
+    Number(f64),
+    String(String),
+}
+
+impl ConstEnumValue {
+    fn from_constant(value: ConstantValue) -> Option<Self> {
+        match value {
+            ConstantValue::Number(n) => Some(Self::Number(n)),
+            ConstantValue::String(s) => Some(Self::String(s)),
+            _ => None,
+        }
+    }
+
+    fn to_constant(&self) -> ConstantValue {
+        match self {
+            Self::Number(n) => ConstantValue::Number(*n),
+            Self::String(s) => ConstantValue::String(s.clone()),
+        }
+    }
+
+    /// `-1` is a negation, and `NaN` and `Infinity` are the globals
+    fn to_expression<'a>(&self, ast: &AstBuilder<'a>) -> Expression<'a> {
+        let n = match self {
+            Self::String(s) => return ast.string(s),
+            Self::Number(n) => *n,
+        };
+        let magnitude = if n.is_nan() {
+            ast.ident("NaN")
+        } else if n.is_infinite() {
+            ast.ident("Infinity")
+        } else {
+            ast.number(n.abs())
+        };
+        if n.is_sign_negative() && !n.is_nan() {
+            ast.unary_expression(SPAN, UnaryOperator::UnaryNegation, magnitude)
+        } else {
+            magnitude
+        }
+    }
+}
+
+/// Looks up the members of const enums declared in other files, for bundlers and other
+/// embedders which know the declarations of the imported modules
+pub trait ConstEnumLookup: fmt::Debug + Send + Sync {
+    /// The value of the member `member` of the const enum exported as `name` from the module
+    /// `source`, as it is written in the import, e.g. `"./enums"`
+    fn lookup(&self, source: &str, name: &str, member: &str) -> Option<ConstEnumValue>;
+}
+
+/// The values of the constant members of the enums of the program
+#[derive(Default)]
+pub(super) struct Enums {
+    /// The members of each enum, including the members of merged declarations
+    values: HashMap<SymbolId, HashMap<Atom, ConstEnumValue>>,
+    /// The symbols of the enum declarations, by the start of their spans
+    declarations: HashMap<u32, SymbolId>,
+    const_enums: HashSet<SymbolId>,
+}
+
+/// Evaluates the members of all enums in the order they are declared, as the initializers of
+/// later members read the earlier ones
+pub(super) struct EnumValues<'s> {
+    symbols: &'s SymbolTable,
+    scopes: &'s ScopeTree,
+    spans: &'s Symbols,
+    pub(super) enums: Enums,
+}
+
+impl<'s> EnumValues<'s> {
+    pub(super) fn new(symbols: &'s SymbolTable, scopes: &'s ScopeTree, spans: &'s Symbols) -> Self {
+        Self { symbols, scopes, spans, enums: Enums::default() }
+    }
+
+    fn symbol(&self, decl: &TSEnumDeclaration) -> Option<SymbolId> {
+        self.spans.binding(decl.id.span).or_else(|| {
+            // A declaration merged into an earlier one has the symbol of the earlier one, which is
+            // bound in the scope around the scope of its members
+            decl.body.members.iter().find_map(|member| {
+                let member = self.spans.binding(member.span)?;
+                let scope_id = self.scopes.get_parent_id(self.symbols.get_scope_id(member))?;
+                self.scopes.get_binding(scope_id, &decl.id.name)
+            })
+        })
+    }
+
+    /// The value of an initializer, where `members` are the earlier members of the enum
+    fn evaluate(
+        &self,
+        expr: &Expression,
+        symbol_id: SymbolId,
+        members: &HashMap<Atom, ConstEnumValue>,
+    ) -> Option<ConstantValue> {
+        match expr {
+            Expression::NumberLiteral(lit) => Some(ConstantValue::Number(lit.value)),
+            Expression::StringLiteral(lit) => Some(ConstantValue::String(lit.value.to_string())),
+            Expression::TemplateLiteral(lit) if lit.expressions.is_empty() => {
+                Some(ConstantValue::String(lit.quasis.first()?.value.cooked.as_ref()?.to_string()))
+            }
+            Expression::Identifier(ident) => match members.get(&ident.name) {
+                Some(value) => Some(value.to_constant()),
+                None if self.spans.reference(ident.span).is_none() => match ident.name.as_str() {
+                    "NaN" => Some(ConstantValue::Number(f64::NAN)),
+                    "Infinity" => Some(ConstantValue::Number(f64::INFINITY)),
+                    _ => None,
+                },
+                None => None,
+            },
+            // `E.A` and `E["A"]`, of this enum or another one
+            Expression::MemberExpression(member) => {
+                let (object, name) = enum_member_expression(member)?;
+                let enum_id = self.spans.reference(object.span)?;
+                let members =
+                    if enum_id == symbol_id { members } else { self.enums.values.get(&enum_id)? };
+                members.get(name).map(ConstEnumValue::to_constant)
+            }
+            Expression::UnaryExpression(expr) => match expr.operator {
+                UnaryOperator::UnaryNegation
+                | UnaryOperator::UnaryPlus
+                | UnaryOperator::BitwiseNot => ConstantValue::unary(
+                    expr.operator,
+                    &self.evaluate(&expr.argument, symbol_id, members)?,
+                ),
+                _ => None,
+            },
+            Expression::BinaryExpression(expr) => ConstantValue::binary(
+                expr.operator,
+                &self.evaluate(&expr.left, symbol_id, members)?,
+                &self.evaluate(&expr.right, symbol_id, members)?,
+            ),
+            Expression::ParenthesizedExpression(expr) => {
+                self.evaluate(&expr.expression, symbol_id, members)
+            }
+            Expression::TSAsExpression(expr) => self.evaluate(&expr.expression, symbol_id, members),
+            Expression::TSSatisfiesExpression(expr) => {
+                self.evaluate(&expr.expression, symbol_id, members)
+            }
+            Expression::TSTypeAssertion(expr) => {
+                self.evaluate(&expr.expression, symbol_id, members)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'b, 's> VisitMut<'a, 'b> for EnumValues<'s> {
+    fn visit_enum(&mut self, decl: &'b mut TSEnumDeclaration<'a>) {
+        let Some(symbol_id) = self.symbol(decl) else { return };
+        self.enums.declarations.insert(decl.span.start, symbol_id);
+        if self.symbols.get_flag(symbol_id).contains(SymbolFlags::ConstEnum) {
+            self.enums.const_enums.insert(symbol_id);
+        }
+        let mut members = self.enums.values.remove(&symbol_id).unwrap_or_default();
+        // Members without an initializer are one more than the member before them
+        let mut next = Some(0.0);
+        for member in decl.body.members.iter() {
+            let Some(name) = enum_member_name(&member.id) else { continue };
+            let value = member.initializer.as_ref().map_or_else(
+                || next.map(ConstEnumValue::Number),
+                |init| {
+                    self.evaluate(init, symbol_id, &members).and_then(ConstEnumValue::from_constant)
+                },
+            );
+            next = match &value {
+                Some(ConstEnumValue::Number(n)) => Some(n + 1.0),
+                _ => None,
+            };
+            if let Some(value) = value {
+                members.insert(name, value);
+            }
+        }
+        self.enums.values.insert(symbol_id, members);
+    }
+}
+
+impl<'a> TypeScript<'a> {
+    /// The function lowering an enum, called with the enum object, which `export` is the
+    /// namespace exporting the enum
+    pub(super) fn lower_enum(
+        &mut self,
+        decl: Box<'a, TSEnumDeclaration<'a>>,
+        export: Option<&Atom>,
+    ) -> Statement<'a> {
+        let decl = decl.unbox();
+        let name = decl.id.name;
+        let values = self
+            .enums
+            .declarations
+            .get(&decl.span.start)
+            .and_then(|symbol_id| self.enums.values.get(symbol_id))
+            .cloned()
+            .unwrap_or_default();
+        let mut names = values.keys().cloned().collect::<HashSet<_>>();
+        names.extend(decl.body.members.iter().filter_map(|member| enum_member_name(&member.id)));
+
+        let mut body = self.ast.new_vec_with_capacity(decl.body.members.len());
+        for member in decl.body.members {
+            let Some(member_name) = enum_member_name(&member.id) else { continue };
+            let value = match (values.get(&member_name), member.initializer) {
+                (Some(value), _) => value.to_expression(&self.ast),
+                (None, Some(mut init)) => {
+                    self.visit_expression(&mut init);
+                    EnumMemberReferences { ast: &self.ast, name: &name, members: &names }
+                        .visit_expression(&mut init);
+                    init
+                }
+                // After a member which is not a number
+                (None, None) => self.ast.void_0(),
+            };
+            let is_string = matches!(values.get(&member_name), Some(ConstEnumValue::String(_)));
+            let member = self.ast.computed(self.ast.ident(&name), self.ast.string(&member_name));
+            let mut assign = self.ast.assign(member, value);
+            // Numbers are mapped back to the names of their members
+            if !is_string {
+                let reverse = self.ast.computed(self.ast.ident(&name), assign);
+                assign = self.ast.assign(reverse, self.ast.string(&member_name));
+            }
+            body.push(self.ast.statement(assign));
+        }
+        self.namespace_iife(&name, body, export)
+    }
+
+    /// `E.A` -> `0`, where `E` is a const enum of the program or an imported const enum which
+    /// [`ConstEnumLookup`] knows
+    pub(super) fn inline_const_enum_member(&self, expr: &Expression<'a>) -> Option<Expression<'a>> {
+        let Expression::MemberExpression(member) = expr else { return None };
+        let (object, name) = enum_member_expression(member)?;
+        let symbol_id = self.symbols.reference(object.span)?;
+        let value = if self.enums.const_enums.contains(&symbol_id) {
+            self.enums.values.get(&symbol_id)?.get(name)?.clone()
+        } else {
+            let (source, imported) = self.imports.get(&symbol_id)?;
+            self.options.const_enums.as_ref()?.lookup(source, imported, name)?
+        };
+        Some(value.to_expression(&self.ast))
+    }
+
+    /// `(function (name) { body })(name || (name = {}))`, or
+    /// `(function (name) { body })(name = N.name || (N.name = {}))` where `export` is `N`
+    pub(super) fn namespace_iife(
+        &self,
+        name: &Atom,
+        body: Vec<'a, Statement<'a>>,
+        export: Option<&Atom>,
+    ) -> Statement<'a> {
+        let ast = &self.ast;
+        let function =
+            ast.function_of(FunctionType::FunctionExpression, None, &[name.as_str()], body);
+        let callee = ast.parenthesized_expression(SPAN, Expression::FunctionExpression(function));
+        let target = || {
+            export
+                .map_or_else(|| ast.ident(name), |namespace| ast.member(ast.ident(namespace), name))
+        };
+        let object = ast.parenthesized_expression(SPAN, ast.assign(target(), ast.object([])));
+        let mut argument = ast.logical(target(), LogicalOperator::Or, object);
+        if export.is_some() {
+            argument = ast.assign(ast.ident(name), argument);
+        }
+        ast.statement(ast.call(callee, [argument]))
+    }
+}
+
+/// Rewrites the references to the members of an enum in the initializers which are not
+/// constant, `A` -> `E.A`. Functions in the initializers which shadow the members are not
+/// looked into.
+struct EnumMemberReferences<'n, 'a> {
+    ast: &'n AstBuilder<'a>,
+    name: &'n Atom,
+    members: &'n HashSet<Atom>,
+}
+
+impl<'n, 'a, 'b> VisitMut<'a, 'b> for EnumMemberReferences<'n, 'a> {
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        match expr {
+            Expression::Identifier(ident) if self.members.contains(&ident.name) => {
+                *expr = self.ast.member(self.ast.ident(self.name), &ident.name);
+            }
+            _ => self.visit_expression_match(expr),
+        }
+    }
+}
+
+/// The name of a member, `None` for computed names, which are invalid
+fn enum_member_name(name: &TSEnumMemberName) -> Option<Atom> {
+    match name {
+        TSEnumMemberName::Identifier(ident) => Some(ident.name.clone()),
+        TSEnumMemberName::StringLiteral(lit) => Some(lit.value.clone()),
+        TSEnumMemberName::NumberLiteral(lit) => Some(Atom::from(lit.value.to_string())),
+        TSEnumMemberName::ComputedPropertyName(_) => None,
+    }
+}
+
+/// The enum and the member name of `E.A` and `E["A"]`
+fn enum_member_expression<'e>(
+    member: &'e MemberExpression,
+) -> Option<(&'e IdentifierReference, &'e str)> {
+    let (object, name) = match member {
+        MemberExpression::StaticMemberExpression(member) => {
+            (&member.object, member.property.name.as_str())
+        }
+        MemberExpression::ComputedMemberExpression(member) => match &member.expression {
+            Expression::StringLiteral(lit) => (&member.object, lit.value.as_str()),
+            _ => return None,
+        },
+        MemberExpression::PrivateFieldExpression(_) => return None,
+    };
+    match object {
+        Expression::Identifier(ident) => Some((ident, name)),
+        _ => None,
+    }
+}
+
+/// `import { E } from "m"` -> `E` is `("m", "E")`, for [`ConstEnumLookup`]
+pub(super) fn import_names(
+    program: &Program,
+    symbols: &Symbols,
+) -> HashMap<SymbolId, (Atom, Atom)> {
+    let mut imports = HashMap::new();
+    for stmt in &program.body {
+        let Statement::ModuleDeclaration(decl) = stmt else { continue };
+        let ModuleDeclaration::ImportDeclaration(decl) = &**decl else { continue };
+        for specifier in decl.specifiers.iter() {
+            let (local, imported) = match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+                    (&specifier.local, specifier.imported.name().clone())
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => {
+                    (&specifier.local, Atom::from("default"))
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => continue,
+            };
+            if let Some(symbol_id) = symbols.binding(local.span) {
+                imports.insert(symbol_id, (decl.source.value.clone(), imported));
+            }
+        }
+    }
+    imports
+}
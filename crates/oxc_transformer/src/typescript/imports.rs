@@ -0,0 +1,169 @@
+//! Import elision, `import { A, b } from "m"; b(A);` -> `import { b } from "m"; b();`, where
+//! `A` is only used as a type
+//!
+//! As tsc does, the imports which no value references after the types are removed are removed
+//! too, as they may be types which the imported module does not export at runtime. With
+//! `verbatimModuleSyntax`, only the imports marked `type` are removed.
+
+use std::{collections::HashSet, mem};
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, VisitMut};
+use oxc_semantic::SymbolId;
+use oxc_span::{Atom, SPAN};
+
+use super::TypeScript;
+use crate::symbols::Symbols;
+
+/// What is left of the imports which are not used as values, tsc's `importsNotUsedAsValues`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImportsNotUsedAsValues {
+    /// Default, removes the imports
+    #[default]
+    Remove,
+    /// Keeps the modules of the imports for their side effects, `import "m"`
+    Preserve,
+    /// Emits the same as `Preserve`, as the imports which should be `import type` are reported
+    /// by the type checker
+    Error,
+}
+
+/// The roots of the pragmas of the classic JSX runtime, e.g. `React` of `React.createElement`,
+/// which JSX references once it is lowered
+pub(super) struct JsxPragmas {
+    pub(super) element: Atom,
+    pub(super) fragment: Atom,
+}
+
+impl<'a> TypeScript<'a> {
+    /// Removes the imports marked `type`, and the imports which no value references unless the
+    /// syntax of the module is verbatim
+    pub(super) fn elide_imports(&self, program: &mut Program<'a>) {
+        let references = (!self.options.verbatim_module_syntax).then(|| {
+            let mut references = ValueReferences::new(&self.symbols);
+            references.visit_program(program);
+            references.finish(self.jsx_pragmas.as_ref())
+        });
+        let preserve = self.options.verbatim_module_syntax
+            || self.options.imports_not_used_as_values != ImportsNotUsedAsValues::Remove;
+
+        let body = mem::replace(&mut program.body, self.ast.new_vec());
+        for mut stmt in body {
+            if let Statement::ModuleDeclaration(decl) = &mut stmt {
+                if let ModuleDeclaration::ImportDeclaration(decl) = &mut **decl {
+                    // `import "m"` is only imported for its side effects
+                    if !decl.specifiers.is_empty() {
+                        decl.specifiers.retain(|specifier| {
+                            is_value_import(specifier, references.as_ref(), &self.symbols)
+                        });
+                        if decl.specifiers.is_empty() && !preserve {
+                            continue;
+                        }
+                    }
+                }
+            }
+            program.body.push(stmt);
+        }
+    }
+}
+
+fn is_value_import(
+    specifier: &ImportDeclarationSpecifier,
+    references: Option<&ValueReferences>,
+    symbols: &Symbols,
+) -> bool {
+    let local = match specifier {
+        ImportDeclarationSpecifier::ImportSpecifier(specifier) => {
+            if specifier.import_kind.is_type() {
+                return false;
+            }
+            &specifier.local
+        }
+        ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => &specifier.local,
+        ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => &specifier.local,
+    };
+    references.map_or(true, |references| {
+        symbols.binding(local.span).is_some_and(|symbol_id| references.symbols.contains(&symbol_id))
+            || references.names.contains(&local.name)
+    })
+}
+
+/// The bindings which values reference, once the types are removed
+struct ValueReferences<'s> {
+    spans: &'s Symbols,
+    symbols: HashSet<SymbolId>,
+    /// The module scope bindings which are referenced by name, by `export { a }` and by the
+    /// identifiers which passes created
+    names: HashSet<Atom>,
+    has_jsx_elements: bool,
+    has_jsx_fragments: bool,
+}
+
+impl<'s> ValueReferences<'s> {
+    fn new(spans: &'s Symbols) -> Self {
+        Self {
+            spans,
+            symbols: HashSet::new(),
+            names: HashSet::new(),
+            has_jsx_elements: false,
+            has_jsx_fragments: false,
+        }
+    }
+
+    /// Adds the pragmas which the JSX of the program references
+    fn finish(mut self, pragmas: Option<&JsxPragmas>) -> Self {
+        if let Some(pragmas) = pragmas {
+            if self.has_jsx_elements || self.has_jsx_fragments {
+                self.names.insert(pragmas.element.clone());
+            }
+            if self.has_jsx_fragments {
+                self.names.insert(pragmas.fragment.clone());
+            }
+        }
+        self
+    }
+}
+
+impl<'a, 'b, 's> VisitMut<'a, 'b> for ValueReferences<'s> {
+    fn visit_identifier_reference(&mut self, ident: &'b mut IdentifierReference) {
+        match self.spans.reference(ident.span) {
+            Some(symbol_id) => {
+                self.symbols.insert(symbol_id);
+            }
+            // Created by a pass before, e.g. the metadata of legacy decorators
+            None if ident.span == SPAN => {
+                self.names.insert(ident.name.clone());
+            }
+            None => {}
+        }
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &'b mut ExportNamedDeclaration<'a>) {
+        if decl.source.is_none() {
+            self.names
+                .extend(decl.specifiers.iter().map(|specifier| specifier.local.name().clone()));
+        }
+        if let Some(decl) = &mut decl.declaration {
+            self.visit_declaration(decl);
+        }
+    }
+
+    fn visit_jsx_element_name(&mut self, name: &'b mut JSXElementName<'a>) {
+        self.has_jsx_elements = true;
+        let ident = match &*name {
+            JSXElementName::Identifier(ident) => ident,
+            JSXElementName::MemberExpression(expr) => expr.get_object_identifier(),
+            JSXElementName::NamespacedName(_) => return,
+        };
+        if let Some(symbol_id) = self.spans.reference(ident.span) {
+            self.symbols.insert(symbol_id);
+        }
+    }
+
+    fn visit_jsx_fragment(&mut self, elem: &'b mut JSXFragment<'a>) {
+        self.has_jsx_fragments = true;
+        for child in elem.children.iter_mut() {
+            self.visit_jsx_child(child);
+        }
+    }
+}
@@ -0,0 +1,580 @@
+//! Remove TypeScript syntax which only exists at compile time, and lower the syntax with runtime
+//! semantics which JavaScript does not have
+//!
+//! Enums and namespaces become functions which add their members to objects, see [`enums`] and
+//! [`namespace`]. Parameter properties become assignments in the constructor, and the imports
+//! which are only used as types are removed, see [`imports`].
+
+mod enums;
+mod imports;
+mod namespace;
+
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    sync::Arc,
+};
+
+use oxc_allocator::{Allocator, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder, VisitMut};
+use oxc_semantic::SymbolId;
+use oxc_span::{Atom, SPAN};
+
+use self::{
+    enums::{import_names, EnumValues, Enums},
+    imports::JsxPragmas,
+};
+pub use self::{
+    enums::{ConstEnumLookup, ConstEnumValue},
+    imports::ImportsNotUsedAsValues,
+};
+use crate::{
+    constructor,
+    pipeline::{TransformContext, TransformPass},
+    react_jsx::{ReactJsxOptions, ReactJsxRuntime},
+    shorthands::Shorthands,
+    symbols::Symbols,
+    temporaries::Temporaries,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct TypeScriptOptions {
+    /// Looks up the members of const enums imported from other files, which are inlined like the
+    /// members of the const enums of the file
+    pub const_enums: Option<Arc<dyn ConstEnumLookup>>,
+
+    /// Only the imports and exports marked `type` are removed, tsc's `verbatimModuleSyntax`
+    pub verbatim_module_syntax: bool,
+
+    pub imports_not_used_as_values: ImportsNotUsedAsValues,
+}
+
+/// Strips type annotations, type assertions, interfaces and type aliases, type-only imports and
+/// exports, and `declare`d declarations, leaving JavaScript
+pub struct TypeScript<'a> {
+    ast: AstBuilder<'a>,
+    options: TypeScriptOptions,
+    symbols: Symbols,
+    enums: Enums,
+    /// Imported bindings as their modules and imported names, for [`ConstEnumLookup`]
+    imports: HashMap<SymbolId, (Atom, Atom)>,
+    /// The exported variables of the namespaces, which are properties of the namespaces
+    namespace_exports: HashMap<SymbolId, Atom>,
+    /// The names the namespaces export, by the symbols which merged declarations share, for the
+    /// references from the other declarations
+    namespace_members: HashMap<SymbolId, HashSet<Atom>>,
+    /// The symbols of the namespaces declared in the statements being visited, innermost last
+    namespace_symbols: std::vec::Vec<HashMap<Atom, SymbolId>>,
+    /// The namespaces whose bodies are being visited, innermost last
+    namespaces: std::vec::Vec<(Atom, Option<SymbolId>)>,
+    /// The namespace whose body is visited next
+    namespace: Option<Atom>,
+    /// `None` unless JSX is lowered with the classic runtime
+    jsx_pragmas: Option<JsxPragmas>,
+}
+
+impl<'a> TypeScript<'a> {
+    /// `jsx` are the options JSX is lowered with, which keep the imports of the pragmas of the
+    /// classic runtime
+    pub fn new(
+        allocator: &'a Allocator,
+        options: TypeScriptOptions,
+        jsx: Option<&ReactJsxOptions>,
+    ) -> Self {
+        let jsx_pragmas = jsx.filter(|jsx| jsx.runtime == ReactJsxRuntime::Classic).map(|jsx| {
+            let root = |pragma: &str| Atom::from(pragma.split('.').next().unwrap_or_default());
+            JsxPragmas { element: root(&jsx.pragma), fragment: root(&jsx.pragma_frag) }
+        });
+        Self {
+            ast: AstBuilder::new(allocator),
+            options,
+            symbols: Symbols::default(),
+            enums: Enums::default(),
+            imports: HashMap::new(),
+            namespace_exports: HashMap::new(),
+            namespace_members: HashMap::new(),
+            namespace_symbols: vec![],
+            namespaces: vec![],
+            namespace: None,
+            jsx_pragmas,
+        }
+    }
+
+    /// `constructor(public a) {}` -> `constructor(a) { this.a = a; }`, after `super()` in derived
+    /// classes, also for `readonly` and `override`
+    fn lower_parameter_properties(&self, class: &mut Class<'a>) {
+        let constructor = class.body.body.iter_mut().find_map(|element| match element {
+            ClassElement::MethodDefinition(def)
+                if def.kind == MethodDefinitionKind::Constructor =>
+            {
+                Some(def)
+            }
+            _ => None,
+        });
+        let Some(constructor) = constructor else { return };
+        let mut assignments = vec![];
+        for param in constructor.value.params.items.iter_mut() {
+            let accessibility = param.accessibility.take();
+            let readonly = mem::take(&mut param.readonly);
+            let r#override = mem::take(&mut param.r#override);
+            if accessibility.is_none() && !readonly && !r#override {
+                continue;
+            }
+            let name = match &param.pattern.kind {
+                BindingPatternKind::BindingIdentifier(ident) => &ident.name,
+                // `constructor(public a = 1)`
+                BindingPatternKind::AssignmentPattern(pat) => match &pat.left.kind {
+                    BindingPatternKind::BindingIdentifier(ident) => &ident.name,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let ast = &self.ast;
+            let this = ast.this_expression(SPAN);
+            assignments.push(ast.assign(ast.member(this, name), ast.ident(name)));
+        }
+        if !assignments.is_empty() {
+            // The class has a constructor, so no temporaries are created for a new one
+            let mut temporaries = Temporaries::new(self.ast.allocator);
+            constructor::add_initializers(&self.ast, &mut temporaries, class, assignments);
+        }
+    }
+
+    /// `a as T`, `a satisfies T`, `a!`, `<T>a` and `a<T>` -> `a`
+    fn strip_expression(&self, expr: &mut Expression<'a>) {
+        loop {
+            let inner = match expr {
+                Expression::TSAsExpression(expr) => &mut expr.expression,
+                Expression::TSSatisfiesExpression(expr) => &mut expr.expression,
+                Expression::TSNonNullExpression(expr) => &mut expr.expression,
+                Expression::TSTypeAssertion(expr) => &mut expr.expression,
+                Expression::TSInstantiationExpression(expr) => &mut expr.expression,
+                _ => return,
+            };
+            *expr = mem::replace(inner, self.ast.null_literal_expression());
+        }
+    }
+
+    /// `a` -> `N.a`, where `a` is exported from the namespace `N`, also by another declaration
+    /// of `N` when `a` is unresolved
+    fn namespace_export(&self, ident: &IdentifierReference) -> Option<Expression<'a>> {
+        let namespace = match self.symbols.reference(ident.span) {
+            Some(symbol_id) => self.namespace_exports.get(&symbol_id)?,
+            None if ident.span != SPAN => {
+                let (namespace, _) = self.namespaces.iter().rev().find(|(_, symbol_id)| {
+                    symbol_id
+                        .and_then(|symbol_id| self.namespace_members.get(&symbol_id))
+                        .is_some_and(|names| names.contains(&ident.name))
+                })?;
+                namespace
+            }
+            None => return None,
+        };
+        Some(self.ast.member(self.ast.ident(namespace), &ident.name))
+    }
+
+    /// Lowers the enums and namespaces of `stmt`, and visits the others
+    fn lower_statement(
+        &mut self,
+        stmt: Statement<'a>,
+        namespace: Option<&Atom>,
+        declared: &mut HashSet<Atom>,
+        stmts: &mut Vec<'a, Statement<'a>>,
+    ) {
+        let mut stmt = match stmt {
+            Statement::Declaration(decl) if is_enum_or_namespace(&decl) => {
+                self.lower_declaration(decl, namespace, false, declared, stmts);
+                return;
+            }
+            Statement::ModuleDeclaration(mut module_decl) => {
+                if let ModuleDeclaration::ExportNamedDeclaration(export) = &mut *module_decl {
+                    if let Some(namespace) = namespace {
+                        if let Some(decl) = export.declaration.take() {
+                            self.lower_namespace_export(decl, namespace, declared, stmts);
+                            return;
+                        }
+                    } else if export.declaration.as_ref().is_some_and(is_enum_or_namespace) {
+                        // `export enum E {}` -> `export var E; (function (E) {})(E || (E = {}));`
+                        let decl = export.declaration.take();
+                        let name = decl.as_ref().and_then(declaration_name);
+                        if let Some(name) = name.filter(|name| declared.insert(name.clone())) {
+                            let binding = self
+                                .ast
+                                .var_declaration_of(VariableDeclarationKind::Var, [(&*name, None)]);
+                            export.declaration = Some(Declaration::VariableDeclaration(binding));
+                            stmts.push(Statement::ModuleDeclaration(module_decl));
+                        }
+                        if let Some(decl) = decl {
+                            self.lower_declaration(decl, None, false, declared, stmts);
+                        }
+                        return;
+                    }
+                }
+                Statement::ModuleDeclaration(module_decl)
+            }
+            stmt => stmt,
+        };
+        self.visit_statement(&mut stmt);
+        stmts.push(stmt);
+    }
+
+    /// Lowers an enum or a namespace in the body of `namespace`, which it is exported from if
+    /// `export` is set, and declares its name unless a declaration before it in the same
+    /// statements did
+    fn lower_declaration(
+        &mut self,
+        decl: Declaration<'a>,
+        namespace: Option<&Atom>,
+        export: bool,
+        declared: &mut HashSet<Atom>,
+        stmts: &mut Vec<'a, Statement<'a>>,
+    ) {
+        let Some(name) = declaration_name(&decl) else { return };
+        let export = namespace.filter(|_| export);
+        let lowered = match decl {
+            Declaration::TSEnumDeclaration(decl) => self.lower_enum(decl, export),
+            Declaration::TSModuleDeclaration(decl) => self.lower_namespace(decl, export),
+            _ => return,
+        };
+        if declared.insert(name.clone()) {
+            // Block scoped in namespaces, as tsc declares them
+            let kind = if namespace.is_some() {
+                VariableDeclarationKind::Let
+            } else {
+                VariableDeclarationKind::Var
+            };
+            stmts.push(self.ast.var_declaration_statement(kind, [(&*name, None)]));
+        }
+        stmts.push(lowered);
+    }
+}
+
+impl<'a> TransformPass<'a> for TypeScript<'a> {
+    fn name(&self) -> &'static str {
+        "typescript"
+    }
+
+    /// Enums and namespaces are lowered with the symbols their members and exports reference
+    fn needs_semantic(&self) -> bool {
+        true
+    }
+
+    fn transform(&mut self, program: &mut Program<'a>, ctx: &TransformContext<'a>) {
+        let (Some(symbols), Some(scopes)) = (ctx.symbols(), ctx.scopes()) else {
+            self.visit_program(program);
+            return;
+        };
+        self.symbols = Symbols::new(symbols);
+        let mut values = EnumValues::new(symbols, scopes, &self.symbols);
+        values.visit_program(program);
+        self.enums = values.enums;
+        self.imports = import_names(program, &self.symbols);
+        self.visit_program(program);
+        // After const enums are inlined, which removes the references to their imports
+        self.elide_imports(program);
+    }
+}
+
+impl<'a, 'b> VisitMut<'a, 'b> for TypeScript<'a> {
+    fn visit_statements(&mut self, stmts: &'b mut Vec<'a, Statement<'a>>) {
+        // The namespace which `stmts` are the body of
+        let namespace = self.namespace.take();
+        stmts.retain(|stmt| !is_type_only_statement(stmt));
+        let mut declared = declared_names(stmts);
+        let symbols = self.collect_namespace_members(stmts);
+        self.namespace_symbols.push(symbols);
+        let mut lowered = self.ast.new_vec_with_capacity(stmts.len());
+        for stmt in mem::replace(stmts, self.ast.new_vec()) {
+            self.lower_statement(stmt, namespace.as_ref(), &mut declared, &mut lowered);
+        }
+        self.namespace_symbols.pop();
+        *stmts = lowered;
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &'b mut ExportNamedDeclaration<'a>) {
+        decl.specifiers.retain(|specifier| specifier.export_kind.is_value());
+        if let Some(decl) = &mut decl.declaration {
+            self.visit_declaration(decl);
+        }
+    }
+
+    fn visit_variable_declarator(&mut self, declarator: &'b mut VariableDeclarator<'a>) {
+        declarator.definite = false;
+        self.visit_binding_pattern(&mut declarator.id);
+        if let Some(init) = &mut declarator.init {
+            self.visit_expression(init);
+        }
+    }
+
+    fn visit_function(&mut self, func: &'b mut Function<'a>) {
+        func.type_parameters = None;
+        func.return_type = None;
+        if func.params.this_parameter().is_some() {
+            func.params.items.remove(0);
+        }
+        if let Some(ident) = &mut func.id {
+            self.visit_binding_identifier(ident);
+        }
+        self.visit_formal_parameters(&mut func.params);
+        if let Some(body) = &mut func.body {
+            self.visit_function_body(body);
+        }
+    }
+
+    fn visit_arrow_expression(&mut self, expr: &'b mut ArrowExpression<'a>) {
+        expr.type_parameters = None;
+        expr.return_type = None;
+        self.visit_formal_parameters(&mut expr.params);
+        self.visit_function_body(&mut expr.body);
+    }
+
+    fn visit_class(&mut self, class: &'b mut Class<'a>) {
+        class.type_parameters = None;
+        class.super_type_parameters = None;
+        class.implements = None;
+        for decorator in class.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        if let Some(id) = &mut class.id {
+            self.visit_binding_identifier(id);
+        }
+        if let Some(super_class) = &mut class.super_class {
+            self.visit_class_heritage(super_class);
+        }
+        self.visit_class_body(&mut class.body);
+        self.lower_parameter_properties(class);
+    }
+
+    fn visit_class_body(&mut self, body: &'b mut ClassBody<'a>) {
+        body.body.retain(|elem| match elem {
+            // Overloads
+            ClassElement::MethodDefinition(def) => def.value.body.is_some(),
+            ClassElement::PropertyDefinition(def) => !def.declare,
+            ClassElement::StaticBlock(_) | ClassElement::AccessorProperty(_) => true,
+            ClassElement::TSAbstractMethodDefinition(_)
+            | ClassElement::TSAbstractPropertyDefinition(_)
+            | ClassElement::TSIndexSignature(_) => false,
+        });
+        for elem in body.body.iter_mut() {
+            self.visit_class_element(elem);
+        }
+    }
+
+    fn visit_method_definition(&mut self, def: &'b mut MethodDefinition<'a>) {
+        def.r#override = false;
+        def.optional = false;
+        def.accessibility = None;
+        for decorator in def.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        self.visit_property_key(&mut def.key);
+        self.visit_function(&mut def.value);
+    }
+
+    fn visit_property_definition(&mut self, def: &'b mut PropertyDefinition<'a>) {
+        def.r#override = false;
+        def.optional = false;
+        def.definite = false;
+        def.readonly = false;
+        def.type_annotation = None;
+        def.accessibility = None;
+        for decorator in def.decorators.iter_mut() {
+            self.visit_decorator(decorator);
+        }
+        self.visit_property_key(&mut def.key);
+        if let Some(value) = &mut def.value {
+            self.visit_expression(value);
+        }
+    }
+
+    fn visit_expression(&mut self, expr: &'b mut Expression<'a>) {
+        self.strip_expression(expr);
+        let rewritten = match expr {
+            Expression::Identifier(ident) => self.namespace_export(ident),
+            Expression::MemberExpression(_) => self.inline_const_enum_member(expr),
+            _ => None,
+        };
+        match rewritten {
+            Some(rewritten) => *expr = rewritten,
+            None => self.visit_expression_match(expr),
+        }
+    }
+
+    fn visit_object_property(&mut self, prop: &'b mut ObjectProperty<'a>) {
+        self.visit_property_key(&mut prop.key);
+        self.visit_expression(&mut prop.value);
+        // `{ a }` -> `{ a: N.a }`
+        prop.shorthand &= matches!(prop.value, Expression::Identifier(_));
+    }
+
+    fn visit_call_expression(&mut self, expr: &'b mut CallExpression<'a>) {
+        expr.type_parameters = None;
+        for arg in expr.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+        self.visit_expression(&mut expr.callee);
+    }
+
+    fn visit_new_expression(&mut self, expr: &'b mut NewExpression<'a>) {
+        expr.type_parameters = None;
+        self.visit_expression(&mut expr.callee);
+        for arg in expr.arguments.iter_mut() {
+            self.visit_argument(arg);
+        }
+    }
+
+    fn visit_tagged_template_expression(&mut self, expr: &'b mut TaggedTemplateExpression<'a>) {
+        expr.type_parameters = None;
+        self.visit_expression(&mut expr.tag);
+        self.visit_template_literal(&mut expr.quasi);
+    }
+
+    /// `a! = b` -> `a = b`
+    fn visit_simple_assignment_target(&mut self, target: &'b mut SimpleAssignmentTarget<'a>) {
+        let expr = match target {
+            SimpleAssignmentTarget::TSAsExpression(expr) => Some(&mut expr.expression),
+            SimpleAssignmentTarget::TSSatisfiesExpression(expr) => Some(&mut expr.expression),
+            SimpleAssignmentTarget::TSNonNullExpression(expr) => Some(&mut expr.expression),
+            SimpleAssignmentTarget::TSTypeAssertion(expr) => Some(&mut expr.expression),
+            _ => None,
+        };
+        if let Some(expr) = expr {
+            self.strip_expression(expr);
+            match mem::replace(expr, self.ast.null_literal_expression()) {
+                Expression::Identifier(ident) => {
+                    *target = SimpleAssignmentTarget::AssignmentTargetIdentifier(ident);
+                }
+                Expression::MemberExpression(expr) => {
+                    *target = SimpleAssignmentTarget::MemberAssignmentTarget(expr);
+                }
+                // e.g. a parenthesized expression, which is printed as it is
+                inner => *expr = inner,
+            }
+        }
+
+        // `a = 1` -> `N.a = 1`
+        if let SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) = target {
+            if let Some(Expression::MemberExpression(expr)) = self.namespace_export(ident) {
+                *target = SimpleAssignmentTarget::MemberAssignmentTarget(expr);
+            }
+        }
+
+        match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                self.visit_identifier_reference(ident);
+            }
+            SimpleAssignmentTarget::MemberAssignmentTarget(expr) => {
+                self.visit_member_expression(expr);
+            }
+            SimpleAssignmentTarget::TSAsExpression(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+            SimpleAssignmentTarget::TSSatisfiesExpression(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+            SimpleAssignmentTarget::TSNonNullExpression(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+            SimpleAssignmentTarget::TSTypeAssertion(expr) => {
+                self.visit_expression(&mut expr.expression);
+            }
+        }
+    }
+
+    fn visit_binding_pattern(&mut self, pat: &'b mut BindingPattern<'a>) {
+        pat.type_annotation = None;
+        pat.optional = false;
+        match &mut pat.kind {
+            BindingPatternKind::BindingIdentifier(ident) => {
+                self.visit_binding_identifier(ident);
+            }
+            BindingPatternKind::ObjectPattern(pat) => self.visit_object_pattern(pat),
+            BindingPatternKind::ArrayPattern(pat) => self.visit_array_pattern(pat),
+            BindingPatternKind::AssignmentPattern(pat) => self.visit_assignment_pattern(pat),
+        }
+    }
+}
+
+/// Statements which are removed entirely, see [`is_type_only_declaration`]
+fn is_type_only_statement(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::Declaration(decl) => is_type_only_declaration(decl),
+        Statement::ModuleDeclaration(decl) => match &**decl {
+            ModuleDeclaration::ImportDeclaration(decl) => decl.import_kind.is_type(),
+            ModuleDeclaration::ExportAllDeclaration(decl) => decl.export_kind.is_type(),
+            ModuleDeclaration::ExportDefaultDeclaration(decl) => {
+                decl.declaration.is_typescript_syntax()
+            }
+            ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                decl.export_kind.is_type()
+                    || decl.declaration.as_ref().is_some_and(is_type_only_declaration)
+                    // `export { type A }`, but not `export {}` which marks a module
+                    || (!decl.specifiers.is_empty()
+                        && decl.specifiers.iter().all(|specifier| specifier.export_kind.is_type()))
+            }
+            ModuleDeclaration::TSNamespaceExportDeclaration(_) => true,
+            ModuleDeclaration::TSExportAssignment(_) => false,
+        },
+        _ => false,
+    }
+}
+
+/// Types, `declare`d declarations, overloads and const enums, whose members are inlined
+fn is_type_only_declaration(decl: &Declaration) -> bool {
+    match decl {
+        Declaration::VariableDeclaration(decl) => decl.modifiers.contains(ModifierKind::Declare),
+        Declaration::FunctionDeclaration(func) => func.is_typescript_syntax(),
+        Declaration::ClassDeclaration(class) => class.is_declare(),
+        Declaration::TSTypeAliasDeclaration(_) | Declaration::TSInterfaceDeclaration(_) => true,
+        Declaration::TSEnumDeclaration(decl) => {
+            decl.modifiers.contains(ModifierKind::Declare)
+                || decl.modifiers.contains(ModifierKind::Const)
+        }
+        Declaration::TSModuleDeclaration(decl) => is_type_only_module(decl),
+        Declaration::TSImportEqualsDeclaration(decl) => decl.import_kind.is_type(),
+    }
+}
+
+/// `declare module "a" {}`, `declare global {}` and namespaces which only contain types
+fn is_type_only_module(decl: &TSModuleDeclaration) -> bool {
+    decl.modifiers.contains(ModifierKind::Declare)
+        || match &decl.body {
+            TSModuleDeclarationBody::TSModuleDeclaration(decl) => is_type_only_module(decl),
+            TSModuleDeclarationBody::TSModuleBlock(block) => {
+                block.body.iter().all(is_type_only_statement)
+            }
+        }
+}
+
+fn is_enum_or_namespace(decl: &Declaration) -> bool {
+    matches!(decl, Declaration::TSEnumDeclaration(_) | Declaration::TSModuleDeclaration(_))
+}
+
+fn declaration_name(decl: &Declaration) -> Option<Atom> {
+    match decl {
+        Declaration::TSEnumDeclaration(decl) => Some(decl.id.name.clone()),
+        Declaration::TSModuleDeclaration(decl) => Some(decl.id.name().clone()),
+        Declaration::FunctionDeclaration(func) => func.id.as_ref().map(|id| id.name.clone()),
+        Declaration::ClassDeclaration(class) => class.id.as_ref().map(|id| id.name.clone()),
+        _ => None,
+    }
+}
+
+/// The functions and classes of `stmts`, which enums and namespaces of the same name are merged
+/// into instead of declaring their names
+fn declared_names(stmts: &[Statement]) -> HashSet<Atom> {
+    stmts
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Statement::Declaration(decl) => Some(decl),
+            Statement::ModuleDeclaration(decl) => match &**decl {
+                ModuleDeclaration::ExportNamedDeclaration(decl) => decl.declaration.as_ref(),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|decl| !is_enum_or_namespace(decl))
+        .filter_map(declaration_name)
+        .collect()
+}
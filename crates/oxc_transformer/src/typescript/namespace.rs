@@ -0,0 +1,209 @@
+//! `namespace N { export const a = 1; }` ->
+//! `var N; (function (N) { N.a = 1; })(N || (N = {}));`
+//!
+//! Exported variables become properties of the namespace, and so do the references to them.
+//! Declarations merged into an earlier declaration of the same name add to its object, and the
+//! references to the names exported by the other declarations become properties too:
+//! `namespace N { export const a = 1; } namespace N { f(a); }` -> `... f(N.a); ...`
+
+use std::collections::{HashMap, HashSet};
+
+use oxc_allocator::{Box, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, VisitMut};
+use oxc_semantic::SymbolId;
+use oxc_span::Atom;
+
+use super::{is_enum_or_namespace, TypeScript};
+use crate::shorthands::Shorthands;
+
+impl<'a> TypeScript<'a> {
+    /// The function lowering a namespace, called with the namespace object, which `export` is the
+    /// namespace exporting the namespace
+    pub(super) fn lower_namespace(
+        &mut self,
+        decl: Box<'a, TSModuleDeclaration<'a>>,
+        export: Option<&Atom>,
+    ) -> Statement<'a> {
+        let decl = decl.unbox();
+        let name = decl.id.name().clone();
+        let symbol_id = self
+            .namespace_symbols
+            .last()
+            .and_then(|symbols| symbols.get(&name).copied())
+            .or_else(|| self.symbols.binding(decl.span));
+        self.namespaces.push((name.clone(), symbol_id));
+        let body = match decl.body {
+            TSModuleDeclarationBody::TSModuleBlock(block) => {
+                let mut body = block.unbox().body;
+                self.export_variables(&body, &name);
+                self.namespace = Some(name.clone());
+                self.visit_statements(&mut body);
+                body
+            }
+            // `namespace A.B {}`, where `B` is exported from `A`
+            TSModuleDeclarationBody::TSModuleDeclaration(inner) => {
+                let inner_name = inner.id.name().clone();
+                let inner = self.lower_namespace(inner, Some(&name));
+                let binding = self.ast.var_declaration_statement(
+                    VariableDeclarationKind::Let,
+                    [(&*inner_name, None)],
+                );
+                self.ast.statements([binding, inner])
+            }
+        };
+        self.namespaces.pop();
+        self.namespace_iife(&name, body, export)
+    }
+
+    /// Records the names which the namespaces declared by `stmts` export, and returns the
+    /// symbols of the namespaces, which the declarations merged with the first one share
+    pub(super) fn collect_namespace_members(
+        &mut self,
+        stmts: &Vec<'a, Statement<'a>>,
+    ) -> HashMap<Atom, SymbolId> {
+        let mut symbols = HashMap::new();
+        for stmt in stmts.iter() {
+            let decl = match stmt {
+                Statement::Declaration(Declaration::TSModuleDeclaration(decl)) => decl,
+                Statement::ModuleDeclaration(decl) => match &**decl {
+                    ModuleDeclaration::ExportNamedDeclaration(export) => {
+                        match &export.declaration {
+                            Some(Declaration::TSModuleDeclaration(decl)) => decl,
+                            _ => continue,
+                        }
+                    }
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let name = decl.id.name();
+            let symbol_id = symbols.get(name).copied().or_else(|| self.symbols.binding(decl.span));
+            let Some(symbol_id) = symbol_id else { continue };
+            symbols.insert(name.clone(), symbol_id);
+            let members = self.namespace_members.entry(symbol_id).or_default();
+            match &decl.body {
+                TSModuleDeclarationBody::TSModuleBlock(block) => {
+                    for stmt in &block.body {
+                        exported_names(stmt, &mut |name| {
+                            members.insert(name.clone());
+                        });
+                    }
+                }
+                // `namespace A.B {}` exports `B` from `A`
+                TSModuleDeclarationBody::TSModuleDeclaration(inner) => {
+                    members.insert(inner.id.name().clone());
+                }
+            }
+        }
+        symbols
+    }
+
+    /// Makes the references to the variables which `stmts` export properties of `namespace`
+    fn export_variables(&mut self, stmts: &Vec<'a, Statement<'a>>, namespace: &Atom) {
+        for stmt in stmts.iter() {
+            let Statement::ModuleDeclaration(decl) = stmt else { continue };
+            let ModuleDeclaration::ExportNamedDeclaration(decl) = &**decl else { continue };
+            let Some(Declaration::VariableDeclaration(decl)) = &decl.declaration else { continue };
+            decl.bound_names(&mut |ident| {
+                if let Some(symbol_id) = self.symbols.binding(ident.span) {
+                    self.namespace_exports.insert(symbol_id, namespace.clone());
+                }
+            });
+        }
+    }
+
+    /// `export function f() {}` -> `function f() {} N.f = f;`, where `namespace` is `N`
+    pub(super) fn lower_namespace_export(
+        &mut self,
+        decl: Declaration<'a>,
+        namespace: &Atom,
+        declared: &mut HashSet<Atom>,
+        stmts: &mut Vec<'a, Statement<'a>>,
+    ) {
+        if matches!(decl, Declaration::VariableDeclaration(_)) {
+            self.lower_exported_variables(decl, namespace, stmts);
+            return;
+        }
+        if is_enum_or_namespace(&decl) {
+            self.lower_declaration(decl, Some(namespace), true, declared, stmts);
+            return;
+        }
+        let name = match &decl {
+            Declaration::FunctionDeclaration(func) => func.id.as_ref().map(|id| id.name.clone()),
+            Declaration::ClassDeclaration(class) => class.id.as_ref().map(|id| id.name.clone()),
+            _ => None,
+        };
+        let mut stmt = Statement::Declaration(decl);
+        self.visit_statement(&mut stmt);
+        stmts.push(stmt);
+        if let Some(name) = name {
+            let ast = &self.ast;
+            let export = ast.assign(ast.member(ast.ident(namespace), &name), ast.ident(&name));
+            stmts.push(ast.statement(export));
+        }
+    }
+
+    /// `export const a = 1, b = 2;` -> `N.a = 1; N.b = 2;`, and
+    /// `export const { a } = b;` -> `const { a } = b; N.a = a;`
+    fn lower_exported_variables(
+        &mut self,
+        decl: Declaration<'a>,
+        namespace: &Atom,
+        stmts: &mut Vec<'a, Statement<'a>>,
+    ) {
+        let Declaration::VariableDeclaration(mut decl) = decl else { return };
+        let is_destructuring = decl.declarations.iter().any(|declarator| {
+            !matches!(declarator.id.kind, BindingPatternKind::BindingIdentifier(_))
+        });
+        if is_destructuring {
+            let mut names = vec![];
+            decl.bound_names(&mut |ident| names.push(ident.name.clone()));
+            let mut stmt = Statement::Declaration(Declaration::VariableDeclaration(decl));
+            self.visit_statement(&mut stmt);
+            stmts.push(stmt);
+            for name in names {
+                let ast = &self.ast;
+                let export = ast.assign(ast.member(ast.ident(namespace), &name), ast.ident(&name));
+                stmts.push(ast.statement(export));
+            }
+            return;
+        }
+        for declarator in decl.declarations.iter_mut() {
+            let (BindingPatternKind::BindingIdentifier(ident), Some(init)) =
+                (&declarator.id.kind, &mut declarator.init)
+            else {
+                continue;
+            };
+            self.visit_expression(init);
+            let init = std::mem::replace(init, self.ast.void_0());
+            let ast = &self.ast;
+            let export = ast.assign(ast.member(ast.ident(namespace), &ident.name), init);
+            stmts.push(ast.statement(export));
+        }
+    }
+}
+
+/// The names of the values which `stmt` exports from a namespace
+fn exported_names(stmt: &Statement, f: &mut impl FnMut(&Atom)) {
+    let Statement::ModuleDeclaration(decl) = stmt else { return };
+    let ModuleDeclaration::ExportNamedDeclaration(decl) = &**decl else { return };
+    match &decl.declaration {
+        Some(Declaration::VariableDeclaration(decl)) => {
+            decl.bound_names(&mut |ident| f(&ident.name));
+        }
+        Some(Declaration::FunctionDeclaration(func)) => {
+            if let Some(id) = &func.id {
+                f(&id.name);
+            }
+        }
+        Some(Declaration::ClassDeclaration(class)) => {
+            if let Some(id) = &class.id {
+                f(&id.name);
+            }
+        }
+        Some(Declaration::TSEnumDeclaration(decl)) => f(&decl.id.name),
+        Some(Declaration::TSModuleDeclaration(decl)) => f(decl.id.name()),
+        _ => {}
+    }
+}
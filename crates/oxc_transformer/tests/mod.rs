@@ -0,0 +1,1813 @@
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{Program, Statement};
+use oxc_codegen::{Codegen, CodegenOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use oxc_transformer::{
+    ClassPropertiesOptions, ConstEnumLookup, ConstEnumValue, DecoratorsOptions, DecoratorsVersion,
+    DefineOptions, DefineValue, DynamicImport, ImportsNotUsedAsValues, ModuleFormat,
+    ReactJsxOptions, ReactJsxRuntime, TransformContext, TransformOptions, TransformPass,
+    TransformTarget, Transformer, TypeScriptOptions,
+};
+
+fn print(source_text: &str, source_type: SourceType, options: TransformOptions) -> String {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    assert!(ret.errors.is_empty(), "failed to parse {source_text}");
+    let mut program = ret.program;
+    let ret = Transformer::new(&allocator, source_text, source_type, options).build(&mut program);
+    assert!(ret.is_ok(), "failed to transform {source_text}");
+    Codegen::new(source_text.len(), CodegenOptions::default()).build(&program)
+}
+
+/// Strips the TypeScript `source_text` and compares it with the JavaScript `expected`
+fn test(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let printed =
+        print(source_text, source_type.with_typescript(true), TransformOptions::default());
+    assert_eq!(
+        printed,
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+/// Transforms the JSX in `source_text` and compares it with the JavaScript `expected`
+fn test_jsx(source_text: &str, expected: &str, options: TransformOptions) {
+    test_jsx_with_source_type(
+        source_text,
+        expected,
+        options,
+        SourceType::default().with_module(true),
+    );
+}
+
+fn test_jsx_with_source_type(
+    source_text: &str,
+    expected: &str,
+    options: TransformOptions,
+    source_type: SourceType,
+) {
+    let printed = print(source_text, source_type.with_jsx(true), options);
+    assert_eq!(
+        printed,
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+#[test]
+fn annotations() {
+    test("let a: number = 1, b!: string;", "let a = 1, b;");
+    test(
+        "function f<T>(this: Window, a: T, b?: string, ...c: T[]): T { return a; }",
+        "function f(a, b, ...c) { return a; }",
+    );
+    test("const f = <T,>(a: T): T => a;", "const f = (a) => a;");
+    test("const { a }: { a: number } = b;", "const { a } = b;");
+    test("try {} catch (e: unknown) {}", "try {} catch (e) {}");
+}
+
+#[test]
+fn expressions() {
+    test("a as unknown as string;", "a;");
+    test("a satisfies T;", "a;");
+    test("a!.b!;", "a.b;");
+    test("f<string>(a);", "f(a);");
+    test("new A<string>();", "new A();");
+    test("a! = 1; b.c! += 2;", "a = 1; b.c += 2;");
+}
+
+#[test]
+fn declarations() {
+    test("type A = string; interface B { a: A } let c;", "let c;");
+    test("declare const a: number; declare function f(): void; declare class C {}", "");
+    test("declare module 'a' { export const b: number; }", "");
+    test("namespace A { export type B = string; }", "");
+    test("function f(a: string): void; function f(a: any) {}", "function f(a) {}");
+}
+
+#[test]
+fn modules() {
+    test("import type { A } from 'a'; import { b } from 'b'; b;", "import { b } from 'b'; b;");
+    test("export type { A } from 'a'; export type * from 'b';", "");
+    test("export interface A {} export type B = A; export const c = 1;", "export const c = 1;");
+    test("export default interface A {}", "");
+    test("export {};", "export {};");
+}
+
+#[test]
+fn classes() {
+    test(
+        "class A<T> extends B<T> implements C, D {
+            declare a: string;
+            private readonly b?: T;
+            c!: number;
+            [key: string]: unknown;
+            public m(): void;
+            public m(a?: T): void {}
+        }",
+        "class A extends B {
+            b;
+            c;
+            m(a) {}
+        }",
+    );
+    test(
+        "abstract class A { abstract a: string; abstract m(): void; n() {} }",
+        "class A { n() {} }",
+    );
+}
+
+#[test]
+fn enums() {
+    test(
+        "enum E { A, B = 3, C, D = B * 2, F = -1 }",
+        r#"var E;
+        (function (E) {
+            E[E["A"] = 0] = "A";
+            E[E["B"] = 3] = "B";
+            E[E["C"] = 4] = "C";
+            E[E["D"] = 6] = "D";
+            E[E["F"] = -1] = "F";
+        })(E || (E = {}));"#,
+    );
+    test(
+        "enum S { A = 'a', B = `b`, C = A + B }",
+        r#"var S;
+        (function (S) {
+            S["A"] = "a";
+            S["B"] = "b";
+            S["C"] = "ab";
+        })(S || (S = {}));"#,
+    );
+    // Members after a member which is not constant reference the enum
+    test(
+        "enum E { A = f(), B = A, C }",
+        r#"var E;
+        (function (E) {
+            E[E["A"] = f()] = "A";
+            E[E["B"] = E.A] = "B";
+            E[E["C"] = void 0] = "C";
+        })(E || (E = {}));"#,
+    );
+    test(
+        "enum E { A } enum E { B = A + 1 }",
+        r#"var E;
+        (function (E) { E[E["A"] = 0] = "A"; })(E || (E = {}));
+        (function (E) { E[E["B"] = 1] = "B"; })(E || (E = {}));"#,
+    );
+    test(
+        "export enum E { A }",
+        r#"export var E;
+        (function (E) { E[E["A"] = 0] = "A"; })(E || (E = {}));"#,
+    );
+}
+
+#[derive(Debug)]
+struct Enums;
+
+impl ConstEnumLookup for Enums {
+    fn lookup(&self, source: &str, name: &str, member: &str) -> Option<ConstEnumValue> {
+        match (source, name, member) {
+            ("./enums", "E", "A") => Some(ConstEnumValue::Number(1.0)),
+            ("./enums", "default", "B") => Some(ConstEnumValue::String("b".to_string())),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn const_enums() {
+    test(
+        "const enum E { A = 1, B = 'b', C = A << 2 } let a = [E.A, E['B'], E.C];",
+        r#"let a = [1, "b", 4];"#,
+    );
+    test("declare const enum E { A = -1 } f(E.A);", "f(-1);");
+    test("namespace N { export const enum E { A } }", "");
+
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions {
+        typescript: TypeScriptOptions {
+            const_enums: Some(Arc::new(Enums)),
+            ..TypeScriptOptions::default()
+        },
+        ..TransformOptions::default()
+    };
+    let source_text = "import { E } from './enums'; import D from './enums'; f(E.A, D.B, E.C);";
+    assert_eq!(
+        print(source_text, source_type.with_typescript(true), options),
+        print(
+            r#"import { E } from './enums'; f(1, "b", E.C);"#,
+            source_type,
+            TransformOptions::default()
+        )
+    );
+}
+
+#[test]
+fn namespaces() {
+    test(
+        "namespace N { export const a = 1; export const { b } = c; export function f() { return a + b; } }",
+        "var N;
+        (function (N) {
+            N.a = 1;
+            const { b } = c;
+            N.b = b;
+            function f() { return N.a + N.b; }
+            N.f = f;
+        })(N || (N = {}));",
+    );
+    test(
+        "namespace N { export let a = 1; a = 2; f({ a }); }",
+        "var N;
+        (function (N) {
+            N.a = 1;
+            N.a = 2;
+            f({ a: N.a });
+        })(N || (N = {}));",
+    );
+    test(
+        "namespace A.B { export class C {} }",
+        "var A;
+        (function (A) {
+            let B;
+            (function (B) {
+                class C {}
+                B.C = C;
+            })(B = A.B || (A.B = {}));
+        })(A || (A = {}));",
+    );
+    test(
+        "namespace N { export enum E { A } }",
+        r#"var N;
+        (function (N) {
+            let E;
+            (function (E) { E[E["A"] = 0] = "A"; })(E = N.E || (N.E = {}));
+        })(N || (N = {}));"#,
+    );
+    // Merged into the function and the namespace before them
+    test(
+        "function f() {}
+        namespace f { export const a = 1; }
+        namespace f { export const b = 2; }",
+        "function f() {}
+        (function (f) { f.a = 1; })(f || (f = {}));
+        (function (f) { f.b = 2; })(f || (f = {}));",
+    );
+    // The exports of the other declarations of the namespace, before and after
+    test(
+        "namespace N { export const a = 1; }
+        namespace N { f(a, b); }
+        namespace N { export function b() { return a; } }",
+        "var N;
+        (function (N) { N.a = 1; })(N || (N = {}));
+        (function (N) { f(N.a, N.b); })(N || (N = {}));
+        (function (N) {
+            function b() { return N.a; }
+            N.b = b;
+        })(N || (N = {}));",
+    );
+    // Shadowed by a local
+    test(
+        "namespace N { export const a = 1; }
+        namespace N { const a = 2; f(a); }",
+        "var N;
+        (function (N) { N.a = 1; })(N || (N = {}));
+        (function (N) { const a = 2; f(a); })(N || (N = {}));",
+    );
+}
+
+#[test]
+fn parameter_properties() {
+    test(
+        "class A { constructor(public a: string, private readonly b = 1, c?: number) { f(); } }",
+        "class A { constructor(a, b = 1, c) { this.a = a; this.b = b; f(); } }",
+    );
+    test(
+        "class A extends B { constructor(readonly a) { f(); super(); g(); } }",
+        "class A extends B { constructor(a) { f(); super(); this.a = a; g(); } }",
+    );
+    test(
+        "class A extends B { constructor(override a, protected override readonly b) { super(); } }",
+        "class A extends B { constructor(a, b) { super(); this.a = a; this.b = b; } }",
+    );
+}
+
+fn test_imports(source_text: &str, expected: &str, options: TypeScriptOptions) {
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions { typescript: options, ..TransformOptions::default() };
+    assert_eq!(
+        print(source_text, source_type.with_typescript(true), options),
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+#[test]
+fn import_elision() {
+    test("import { A, b } from 'm'; let c: A = b;", "import { b } from 'm'; let c = b;");
+    test("import A, * as B from 'm'; let c: B.C<A>; declare const d: typeof A;", "let c;");
+    test("import { type A, b, c } from 'm'; export { b };", "import { b } from 'm'; export { b };");
+    // Shadowed by the parameter
+    test("import { a } from 'm'; function f(a) { return a; }", "function f(a) { return a; }");
+    test(
+        "import { a } from 'm'; namespace N { export const b = a; }",
+        "import { a } from 'm'; var N; (function (N) { N.b = a; })(N || (N = {}));",
+    );
+    test("import 'm';", "import 'm';");
+
+    let preserve = TypeScriptOptions {
+        imports_not_used_as_values: ImportsNotUsedAsValues::Preserve,
+        ..TypeScriptOptions::default()
+    };
+    test_imports(
+        "import { A } from 'a'; import { type B, c } from 'b'; import type { D } from 'd'; c;",
+        "import 'a'; import { c } from 'b'; c;",
+        preserve,
+    );
+    let verbatim =
+        TypeScriptOptions { verbatim_module_syntax: true, ..TypeScriptOptions::default() };
+    test_imports(
+        "import { A } from 'a'; import b, { type C } from 'b'; import { type D } from 'd'; \
+        import type { E } from 'e';",
+        "import { A } from 'a'; import b from 'b'; import 'd';",
+        verbatim,
+    );
+}
+
+#[test]
+fn import_elision_jsx() {
+    let test = |source_text: &str, expected: &str, runtime: ReactJsxRuntime| {
+        let source_type = SourceType::default().with_module(true).with_jsx(true);
+        let options = || TransformOptions {
+            react_jsx: ReactJsxOptions { runtime, ..ReactJsxOptions::default() },
+            ..TransformOptions::default()
+        };
+        assert_eq!(
+            print(source_text, source_type.with_typescript(true), options()),
+            print(expected, source_type, options()),
+            "for source {source_text}"
+        );
+    };
+    let source_text = "import React from 'react'; import { A, B } from 'a'; let c: B = <A.B />;";
+    // The classic runtime calls `React.createElement`
+    test(
+        source_text,
+        "import React from 'react'; import { A } from 'a'; let c = <A.B />;",
+        ReactJsxRuntime::Classic,
+    );
+    test(source_text, "import { A } from 'a'; let c = <A.B />;", ReactJsxRuntime::Automatic);
+}
+
+#[test]
+fn javascript() {
+    // Nothing is stripped from JavaScript
+    let source_text = "let a = 1;";
+    assert_eq!(
+        print(source_text, SourceType::default(), TransformOptions::default()),
+        "let a = 1;\n"
+    );
+}
+
+#[test]
+fn jsx_elements() {
+    let test = |source_text, expected| test_jsx(source_text, expected, TransformOptions::default());
+    test("<div />;", "/* #__PURE__ */ React.createElement('div', null);");
+    test("<my-element />;", "/* #__PURE__ */ React.createElement('my-element', null);");
+    test("<A />;", "/* #__PURE__ */ React.createElement(A, null);");
+    test("<a.b.C />;", "/* #__PURE__ */ React.createElement(a.b.C, null);");
+    test("<this.A />;", "/* #__PURE__ */ React.createElement(this.A, null);");
+    test("<a:b />;", "/* #__PURE__ */ React.createElement('a:b', null);");
+    test("<></>;", "/* #__PURE__ */ React.createElement(React.Fragment, null);");
+}
+
+#[test]
+fn jsx_attributes() {
+    let test = |source_text, expected| test_jsx(source_text, expected, TransformOptions::default());
+    test(
+        "<input disabled type='text' value={a} aria-label='b' xlink:href='c' />;",
+        "/* #__PURE__ */ React.createElement('input', {
+            disabled: true,
+            type: 'text',
+            value: a,
+            'aria-label': 'b',
+            'xlink:href': 'c',
+        });",
+    );
+    // The `key` after the spread overrides a `key` in `b`
+    test(
+        "<A a='1' {...b} key='c' />;",
+        "/* #__PURE__ */ React.createElement(A, { a: '1', ...b, key: 'c' });",
+    );
+    test("<A a=<b /> />;", "/* #__PURE__ */ React.createElement(A, { a: /* #__PURE__ */ React.createElement('b', null) });");
+    test("<A {...b} />;", "/* #__PURE__ */ React.createElement(A, b);");
+    test("<A {...{ a: 1 }} b />;", "/* #__PURE__ */ React.createElement(A, { a: 1, b: true });");
+    test(
+        "<A {...{ __proto__: a }} />;",
+        "/* #__PURE__ */ React.createElement(A, { ...{ __proto__: a } });",
+    );
+    test("<A a='b\n    c' />;", "/* #__PURE__ */ React.createElement(A, { a: 'b c' });");
+}
+
+#[test]
+fn jsx_children() {
+    let test = |source_text, expected| test_jsx(source_text, expected, TransformOptions::default());
+    test("<a>text {b} </a>;", "/* #__PURE__ */ React.createElement('a', null, 'text ', b, ' ');");
+    test(
+        "<a>
+            first
+            second
+            <b>{/* comment */}</b>
+            {...c}
+        </a>;",
+        "/* #__PURE__ */ React.createElement('a', null, 'first second', /* #__PURE__ */ React.createElement('b', null), ...c);",
+    );
+    test(
+        "<><A>{<b />}</A></>;",
+        "/* #__PURE__ */ React.createElement(
+            React.Fragment,
+            null,
+            /* #__PURE__ */ React.createElement(A, null, /* #__PURE__ */ React.createElement('b', null)),
+        );",
+    );
+    test("f(() => <a />);", "f(() => /* #__PURE__ */ React.createElement('a', null));");
+}
+
+#[test]
+fn jsx_pragma() {
+    let options = TransformOptions {
+        react_jsx: ReactJsxOptions {
+            pragma: "h".into(),
+            pragma_frag: "Fragment".into(),
+            ..ReactJsxOptions::default()
+        },
+        ..TransformOptions::default()
+    };
+    test_jsx("<><a /></>;", "h(Fragment, null, h('a', null));", options.clone());
+
+    let mut options = options;
+    options.react_jsx.pure = Some(true);
+    test_jsx(
+        "<><a /></>;",
+        "/* #__PURE__ */ h(Fragment, null, /* #__PURE__ */ h('a', null));",
+        options,
+    );
+}
+
+#[test]
+fn jsx_pure() {
+    let options = TransformOptions {
+        react_jsx: ReactJsxOptions { pure: Some(false), ..ReactJsxOptions::default() },
+        ..TransformOptions::default()
+    };
+    test_jsx(
+        "<a><b /></a>;",
+        "React.createElement('a', null, React.createElement('b', null));",
+        options,
+    );
+}
+
+fn automatic(development: bool) -> TransformOptions {
+    TransformOptions {
+        filename: "test.js".into(),
+        react_jsx: ReactJsxOptions {
+            runtime: ReactJsxRuntime::Automatic,
+            development,
+            ..ReactJsxOptions::default()
+        },
+        ..TransformOptions::default()
+    }
+}
+
+#[test]
+fn jsx_automatic() {
+    let test = |source_text, expected| test_jsx(source_text, expected, automatic(false));
+    test(
+        "<div />;",
+        "import { jsx as _jsx } from 'react/jsx-runtime'; /* #__PURE__ */ _jsx('div', {});",
+    );
+    test(
+        "<div a='b' key='c'>d</div>;",
+        "import { jsx as _jsx } from 'react/jsx-runtime';
+        /* #__PURE__ */ _jsx('div', { a: 'b', children: 'd' }, 'c');",
+    );
+    test(
+        "<a><b />{c}</a>;",
+        "import { jsx as _jsx, jsxs as _jsxs } from 'react/jsx-runtime';
+        /* #__PURE__ */ _jsxs('a', { children: [/* #__PURE__ */ _jsx('b', {}), c] });",
+    );
+    test(
+        "<>a</>;",
+        "import { Fragment as _Fragment, jsx as _jsx } from 'react/jsx-runtime';
+        /* #__PURE__ */ _jsx(_Fragment, { children: 'a' });",
+    );
+    // `createElement` is called so the `key` overrides the `key` of `a`
+    test(
+        "<div {...a} key='b' />;",
+        "import { createElement as _createElement } from 'react';
+        /* #__PURE__ */ _createElement('div', { ...a, key: 'b' });",
+    );
+    test_jsx_with_source_type(
+        "<div />;",
+        "var _reactJsxRuntime = require('react/jsx-runtime');
+        /* #__PURE__ */ (0, _reactJsxRuntime.jsx)('div', {});",
+        automatic(false),
+        SourceType::default(),
+    );
+    // The bindings of the runtime do not shadow the names of the program
+    test(
+        "const _jsx = 1; <div />;",
+        "import { jsx as _jsx2 } from 'react/jsx-runtime';
+        const _jsx = 1;
+        /* #__PURE__ */ _jsx2('div', {});",
+    );
+    test_jsx_with_source_type(
+        "var _reactJsxRuntime; <div />;",
+        "var _reactJsxRuntime2 = require('react/jsx-runtime');
+        var _reactJsxRuntime;
+        /* #__PURE__ */ (0, _reactJsxRuntime2.jsx)('div', {});",
+        automatic(false),
+        SourceType::default(),
+    );
+}
+
+#[test]
+fn jsx_development() {
+    let test = |source_text, expected| test_jsx(source_text, expected, automatic(true));
+    test(
+        "<div key='a' />;",
+        "var _jsxFileName = 'test.js';
+        import { jsxDEV as _jsxDEV } from 'react/jsx-dev-runtime';
+        /* #__PURE__ */ _jsxDEV(
+            'div',
+            {},
+            'a',
+            false,
+            { fileName: _jsxFileName, lineNumber: 1, columnNumber: 1 },
+            this,
+        );",
+    );
+    test(
+        "<>\n  <a />\n  <b />\n</>;",
+        "var _jsxFileName = 'test.js';
+        import { jsxDEV as _jsxDEV, Fragment as _Fragment } from 'react/jsx-dev-runtime';
+        /* #__PURE__ */ _jsxDEV(_Fragment, {
+            children: [
+                /* #__PURE__ */ _jsxDEV(
+                    'a',
+                    {},
+                    void 0,
+                    false,
+                    { fileName: _jsxFileName, lineNumber: 2, columnNumber: 3 },
+                    this,
+                ),
+                /* #__PURE__ */ _jsxDEV(
+                    'b',
+                    {},
+                    void 0,
+                    false,
+                    { fileName: _jsxFileName, lineNumber: 3, columnNumber: 3 },
+                    this,
+                )
+            ],
+        }, void 0, true);",
+    );
+    // `this` can't be used before `super()`
+    test(
+        "class A extends B { constructor() { super(<a />); } }",
+        "var _jsxFileName = 'test.js';
+        import { jsxDEV as _jsxDEV } from 'react/jsx-dev-runtime';
+        class A extends B {
+            constructor() {
+                super(/* #__PURE__ */ _jsxDEV(
+                    'a',
+                    {},
+                    void 0,
+                    false,
+                    { fileName: _jsxFileName, lineNumber: 1, columnNumber: 43 },
+                ));
+            }
+        }",
+    );
+
+    let mut options =
+        TransformOptions { filename: "test.js".into(), ..TransformOptions::default() };
+    options.react_jsx.development = true;
+    test_jsx(
+        "<div />;",
+        "var _jsxFileName = 'test.js';
+        /* #__PURE__ */ React.createElement('div', {
+            __self: this,
+            __source: { fileName: _jsxFileName, lineNumber: 1, columnNumber: 1 },
+        });",
+        options,
+    );
+}
+
+/// Lowers the JavaScript `source_text` to ES2019 and compares it with `expected`
+fn test_es2019(source_text: &str, expected: &str) {
+    test_target(source_text, expected, TransformTarget::ES2019);
+}
+
+fn test_target(source_text: &str, expected: &str, target: TransformTarget) {
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions { target, ..TransformOptions::default() };
+    assert_eq!(
+        print(source_text, source_type, options),
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+#[test]
+fn optional_chaining() {
+    test_es2019("a?.b;", "(a === null || a === void 0 ? void 0 : a.b);");
+    test_es2019("a?.b.c();", "(a === null || a === void 0 ? void 0 : a.b.c());");
+    test_es2019("a?.();", "(a === null || a === void 0 ? void 0 : a());");
+    test_es2019(
+        "a.b?.c;",
+        "var _ref; ((_ref = a.b) === null || _ref === void 0 ? void 0 : _ref.c);",
+    );
+    test_es2019(
+        "a?.b?.[c];",
+        "var _ref;
+        (a === null || a === void 0 || (_ref = a.b) === null || _ref === void 0
+            ? void 0
+            : _ref[c]);",
+    );
+    // `this` of the call is the object of the callee
+    test_es2019(
+        "a.b?.();",
+        "var _ref; ((_ref = a.b) === null || _ref === void 0 ? void 0 : _ref.call(a));",
+    );
+    test_es2019("delete a?.b;", "(a === null || a === void 0 ? true : delete a.b);");
+    test_es2019("(a?.b).c;", "(a === null || a === void 0 ? void 0 : a.b).c;");
+    // Temporaries are declared in the function they are used in, and skip names in the source
+    test_es2019(
+        "function f(_ref) { return a.b?.c; }",
+        "function f(_ref) {
+            var _ref2;
+            return ((_ref2 = a.b) === null || _ref2 === void 0 ? void 0 : _ref2.c);
+        }",
+    );
+    test_es2019(
+        "const f = () => a.b?.c;",
+        "const f = () => {
+            var _ref;
+            return ((_ref = a.b) === null || _ref === void 0 ? void 0 : _ref.c);
+        };",
+    );
+    // The parameters can not see the `var`s of the body
+    test_es2019(
+        "function f(x = a.b?.c) {}",
+        "function f(x = ((_ref = a.b) === null || _ref === void 0 ? void 0 : _ref.c), _ref = void 0) {}",
+    );
+    test_es2019(
+        "({ set x(x = a.b?.c) {} });",
+        "var _ref; ({ set x(x = ((_ref = a.b) === null || _ref === void 0 ? void 0 : _ref.c)) {} });",
+    );
+}
+
+#[test]
+fn nullish_coalescing() {
+    test_es2019("a ?? b;", "(a !== null && a !== void 0 ? a : b);");
+    test_es2019("f() ?? b;", "var _ref; ((_ref = f()) !== null && _ref !== void 0 ? _ref : b);");
+    test_es2019(
+        "a ?? b ?? c;",
+        "var _ref;
+        ((_ref = (a !== null && a !== void 0 ? a : b)) !== null && _ref !== void 0 ? _ref : c);",
+    );
+}
+
+#[test]
+fn logical_assignment() {
+    test_es2019("a ||= b;", "a || (a = b);");
+    test_es2019("a.b &&= c;", "a.b && (a.b = c);");
+    test_es2019(
+        "a()[b()] ||= c;",
+        "var _obj, _key; (_obj = a())[(_key = b())] || (_obj[_key] = c);",
+    );
+    test_es2019("a ??= b;", "(a !== null && a !== void 0 ? a : (a = b));");
+    // `??` is supported by ES2020
+    test_target("a ??= b;", "a ?? (a = b);", TransformTarget::ES2020);
+    test_target("a ??= b;", "a ??= b;", TransformTarget::ES2021);
+}
+
+#[test]
+fn numeric_separators() {
+    test_es2019("1_000_000;", "1000000;");
+    test_es2019("0x1_F;", "0x1F;");
+    test_es2019("1_000n;", "1000n;");
+}
+
+/// Lowers the classes in `source_text` to ES2021 and compares them with `expected`
+fn test_classes(source_text: &str, expected: &str, loose: bool) {
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions {
+        class_properties: ClassPropertiesOptions { loose },
+        target: TransformTarget::ES2021,
+        ..TransformOptions::default()
+    };
+    assert_eq!(
+        print(source_text, source_type, options),
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+const PRIVATE_FIELD_GET: &str = "function _classPrivateFieldGet(receiver, privateMap) {
+    if (!privateMap.has(receiver))
+        throw new TypeError('Cannot read private member from an object \
+            whose class did not declare it');
+    return privateMap.get(receiver);
+}";
+
+const PRIVATE_FIELD_SET: &str = "function _classPrivateFieldSet(receiver, privateMap, value) {
+    if (!privateMap.has(receiver))
+        throw new TypeError('Cannot write private member to an object \
+            whose class did not declare it');
+    privateMap.set(receiver, value);
+    return value;
+}";
+
+const PRIVATE_FIELD_DESTRUCTURE_SET: &str = "function _classPrivateFieldDestructureSet(receiver,
+    privateMap) {
+    if (!privateMap.has(receiver))
+        throw new TypeError('Cannot write private member to an object \
+            whose class did not declare it');
+    return { set value(value) { privateMap.set(receiver, value); } };
+}";
+
+const PRIVATE_METHOD_GET: &str = "function _classPrivateMethodGet(receiver, privateSet, fn) {
+    if (!privateSet.has(receiver))
+        throw new TypeError('Cannot read private member from an object \
+            whose class did not declare it');
+    return fn;
+}";
+
+#[test]
+fn class_fields() {
+    test_classes(
+        "class A { a = 1; b; }",
+        "class A {
+            constructor() {
+                Object.defineProperty(this, 'a', {
+                    value: 1, writable: true, enumerable: true, configurable: true
+                });
+                Object.defineProperty(this, 'b', {
+                    value: void 0, writable: true, enumerable: true, configurable: true
+                });
+            }
+        }",
+        false,
+    );
+    test_classes(
+        "class A { a = 1; [f()] = 2; }",
+        "var _key; class A { constructor() { this.a = 1; this[_key] = 2; } } _key = f();",
+        true,
+    );
+    // Computed keys are evaluated in the order of the elements, after the heritage
+    test_classes(
+        "class A extends (g()) { [f()] = 1; [h()]() {} static [k()] = 2; }",
+        "var _key, _key2;
+        class A extends (g()) {
+            constructor(..._args) { super(..._args); this[_key] = 1; }
+            [(_key = f(), h())]() {}
+        }
+        _key2 = k();
+        A[_key2] = 2;",
+        true,
+    );
+    test_classes(
+        "const B = class { [f()] = 1; };",
+        "var _key, _Class;
+        const B = (_Class = class { constructor() { this[_key] = 1; } }, _key = f(), _Class);",
+        true,
+    );
+    test_classes(
+        "class A { a = 1; constructor(b) { this.b = b; } }",
+        "class A { constructor(b) { this.a = 1; this.b = b; } }",
+        true,
+    );
+    // Fields are initialized after `super()` in derived classes
+    test_classes(
+        "class B extends A { a = 1; }",
+        "class B extends A { constructor(..._args) { super(..._args); this.a = 1; } }",
+        true,
+    );
+    test_classes(
+        "class B extends A { a = 1; constructor() { if (x) super(); } }",
+        "class B extends A { constructor() { if (x) (super(), this.a = 1, this); } }",
+        true,
+    );
+}
+
+#[test]
+fn class_private_fields() {
+    test_classes(
+        "class A { #x = 1; f(o) { this.#x += o.#x; return o.#x; } }",
+        &format!(
+            "{PRIVATE_FIELD_GET} {PRIVATE_FIELD_SET}
+            var _x = /* #__PURE__ */ new WeakMap();
+            class A {{
+                constructor() {{ _x.set(this, 1); }}
+                f(o) {{
+                    _classPrivateFieldSet(
+                        this,
+                        _x,
+                        _classPrivateFieldGet(this, _x) + _classPrivateFieldGet(o, _x),
+                    );
+                    return _classPrivateFieldGet(o, _x);
+                }}
+            }}"
+        ),
+        true,
+    );
+    test_classes(
+        "class A { #x; f() { this.#x = 1; } static is(o) { return #x in o; } }",
+        &format!(
+            "{PRIVATE_FIELD_SET}
+            var _x = /* #__PURE__ */ new WeakMap();
+            class A {{
+                constructor() {{ _x.set(this, void 0); }}
+                f() {{ _classPrivateFieldSet(this, _x, 1); }}
+                static is(o) {{ return _x.has(o); }}
+            }}"
+        ),
+        true,
+    );
+}
+
+#[test]
+fn class_private_destructuring() {
+    test_classes(
+        "class A { #x; f(o) { [this.#x, a = this.#x] = o; ({ y: this.#x } = o); for (this.#x of o); } }",
+        &format!(
+            "{PRIVATE_FIELD_DESTRUCTURE_SET} {PRIVATE_FIELD_GET}
+            var _x = /* #__PURE__ */ new WeakMap();
+            class A {{
+                constructor() {{ _x.set(this, void 0); }}
+                f(o) {{
+                    [_classPrivateFieldDestructureSet(this, _x).value,
+                        a = _classPrivateFieldGet(this, _x)] = o;
+                    ({{ y: _classPrivateFieldDestructureSet(this, _x).value }} = o);
+                    for (_classPrivateFieldDestructureSet(this, _x).value of o);
+                }}
+            }}"
+        ),
+        true,
+    );
+}
+
+#[test]
+fn class_private_methods() {
+    test_classes(
+        "class A { #m() { return 1; } f() { return this.#m(); } }",
+        &format!(
+            "{PRIVATE_METHOD_GET}
+            var _m = /* #__PURE__ */ new WeakSet(), _m2 = function () {{ return 1; }};
+            class A {{
+                constructor() {{ _m.add(this); }}
+                f() {{ return _classPrivateMethodGet(this, _m, _m2).call(this); }}
+            }}"
+        ),
+        true,
+    );
+}
+
+#[test]
+fn class_static_elements() {
+    test_classes(
+        "class A { static a = this.b; static { this.c(); } }",
+        "class A {} A.a = A.b; (() => { A.c(); })();",
+        true,
+    );
+    test_classes(
+        "const B = class { static a = 1; };",
+        "var _Class; const B = (_Class = class {}, _Class.a = 1, _Class);",
+        true,
+    );
+    // `super` is the prototype of the class, and `new.target` is undefined
+    test_classes(
+        "class B extends A { static a = super.a; static { super.f(1); super[g()] += new.target; } }",
+        "var _key, _value;
+        class B extends A {}
+        B.a = Reflect.get(Object.getPrototypeOf(B), 'a', B);
+        (() => {
+            Reflect.get(Object.getPrototypeOf(B), 'f', B).call(B, 1);
+            (_value = Reflect.get(Object.getPrototypeOf(B), (_key = g()), B) + void 0,
+                Reflect.set(Object.getPrototypeOf(B), _key, _value, B), _value);
+        })();",
+        true,
+    );
+    test_classes(
+        "class A { a = new.target; }",
+        "class A { constructor() { this.a = void 0; } }",
+        true,
+    );
+    // Classes are not lowered for ES2022
+    test_target("class A { static a = 1; }", "class A { static a = 1; }", TransformTarget::ES2022);
+}
+
+/// Lowers the decorators in the TypeScript `source_text` and compares them with `expected`
+fn test_decorators(source_text: &str, expected: &str, decorators: DecoratorsOptions) {
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions { decorators, ..TransformOptions::default() };
+    assert_eq!(
+        print(source_text, source_type.with_typescript(true), options),
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+fn legacy(emit_decorator_metadata: bool) -> DecoratorsOptions {
+    DecoratorsOptions { version: DecoratorsVersion::Legacy, emit_decorator_metadata }
+}
+
+const DECORATE: &str = "function _decorate(decorators, target, key, descriptor) {
+    var isClass = arguments.length < 3;
+    if (descriptor === null) {
+        descriptor = Object.getOwnPropertyDescriptor(target, key);
+    }
+    var result = isClass ? target : descriptor;
+    for (var i = decorators.length - 1; i >= 0; i--) {
+        if (decorators[i]) {
+            result = (isClass ? decorators[i](result) : decorators[i](target, key, result)) || result;
+        }
+    }
+    if (!isClass && result) {
+        Object.defineProperty(target, key, result);
+    }
+    return result;
+}";
+
+const PARAM: &str = "function _param(index, decorator) {
+    return (target, key) => decorator(target, key, index);
+}";
+
+const METADATA: &str = "function _metadata(key, value) {
+    if (typeof Reflect === 'object' && typeof Reflect.metadata === 'function') {
+        return Reflect.metadata(key, value);
+    }
+}";
+
+#[test]
+fn legacy_decorators() {
+    test_decorators(
+        "@dec class A { @m method() {} @p prop = 1; @s static x; }",
+        &format!(
+            "{DECORATE}
+            class A {{ method() {{}} prop = 1; static x; }}
+            _decorate([m], A.prototype, 'method', null);
+            _decorate([p], A.prototype, 'prop', void 0);
+            _decorate([s], A, 'x', void 0);
+            A = _decorate([dec], A);"
+        ),
+        legacy(false),
+    );
+    test_decorators(
+        "const B = @dec class A {};",
+        &format!(
+            "var _A; {DECORATE} const B = (_A = class A {{}}, _A = _decorate([dec], _A), _A);"
+        ),
+        legacy(false),
+    );
+    test_decorators(
+        "export default @dec class {}",
+        &format!("{DECORATE} export default class _Class {{}} _Class = _decorate([dec], _Class);"),
+        legacy(false),
+    );
+}
+
+#[test]
+fn legacy_decorator_metadata() {
+    test_decorators(
+        "class A {
+            constructor(@inject a: string, b: Foo) {}
+            @m async method(a: number, ...b: string[]): Promise<void> {}
+            @p prop: Bar;
+        }",
+        &format!(
+            "{PARAM} {METADATA} {DECORATE}
+            class A {{ constructor(a, b) {{}} async method(a, ...b) {{}} prop; }}
+            _decorate([
+                m,
+                _metadata('design:type', Function),
+                _metadata('design:paramtypes', [Number, String]),
+                _metadata('design:returntype', typeof Promise === 'undefined' ? Object : Promise)
+            ], A.prototype, 'method', null);
+            _decorate([
+                p,
+                _metadata('design:type', typeof Bar === 'undefined' ? Object : Bar)
+            ], A.prototype, 'prop', void 0);
+            A = _decorate([
+                _param(0, inject),
+                _metadata('design:paramtypes', [String, typeof Foo === 'undefined' ? Object : Foo])
+            ], A);"
+        ),
+        legacy(true),
+    );
+}
+
+const APPLY_DECS: &str = "function _applyDecs(targetClass, memberDecs, classDecs) {
+    var metadataSymbol = Symbol.metadata || Symbol.for('Symbol.metadata');
+    var metadata = Object.create(targetClass[metadataSymbol] || null);
+    var protoInitializers = [], staticInitializers = [], classInitializers = [],
+        elementValues = [];
+    function decorate(decorator, value, kind, name, isStatic, isPrivate, access, initializers) {
+        var done = false;
+        var context = {
+            kind: kind,
+            name: name,
+            metadata: metadata,
+            addInitializer: (initializer) => {
+                if (done) {
+                    throw new TypeError('Cannot add initializers after decoration has completed');
+                }
+                initializers.push(initializer);
+            }
+        };
+        if (kind !== 'class') {
+            context.static = isStatic;
+            context.private = isPrivate;
+            context.access = access;
+        }
+        var result = decorator(value, context);
+        done = true;
+        var type = kind === 'accessor' ? 'object' : 'function';
+        if (result !== void 0 && typeof result !== type) {
+            throw new TypeError('A decorator returned a value of the wrong type');
+        }
+        return result;
+    }
+    function run(initializers, receiver) {
+        initializers.forEach((initializer) => initializer.call(receiver));
+    }
+    memberDecs.forEach((member) => {
+        var decorators = member[0], kind = member[1], name = member[2], isStatic = member[3],
+            isPrivate = member[4];
+        var target = isStatic ? targetClass : targetClass.prototype;
+        var initializers = isStatic ? staticInitializers : protoInitializers;
+        var access = member[5] || {
+            has: (object) => name in object,
+            get: (object) => object[name],
+            set: (object, value) => {
+                object[name] = value;
+            }
+        };
+        var key = kind === 'getter' ? 'get' : kind === 'setter' ? 'set' : 'value';
+        var descriptor =
+            kind === 'field' ? void 0 : member[6] || Object.getOwnPropertyDescriptor(target, name);
+        var values = [];
+        for (var i = decorators.length - 1; i >= 0; i--) {
+            var value = kind === 'field'
+                ? void 0
+                : kind === 'accessor' ? { get: descriptor.get, set: descriptor.set } : descriptor[key];
+            var result =
+                decorate(decorators[i], value, kind, name, isStatic, isPrivate, access, initializers);
+            if (result) {
+                if (kind === 'field') {
+                    values.push(result);
+                } else if (kind === 'accessor') {
+                    if (result.get) {
+                        descriptor.get = result.get;
+                    }
+                    if (result.set) {
+                        descriptor.set = result.set;
+                    }
+                    if (result.init) {
+                        values.push(result.init);
+                    }
+                } else {
+                    descriptor[key] = result;
+                }
+            }
+        }
+        if (descriptor && !isPrivate) {
+            Object.defineProperty(target, name, descriptor);
+        }
+        if (kind === 'field' || kind === 'accessor') {
+            elementValues.push((instance, value) =>
+                values.reduce((value, initializer) => initializer.call(instance, value), value));
+        }
+        if (descriptor && isPrivate) {
+            elementValues.push(descriptor);
+        }
+    });
+    run(staticInitializers, targetClass);
+    Object.defineProperty(targetClass, metadataSymbol, {
+        value: metadata,
+        writable: true,
+        enumerable: true,
+        configurable: true
+    });
+    var newClass = targetClass;
+    for (var i = classDecs.length - 1; i >= 0; i--) {
+        newClass = decorate(classDecs[i], newClass, 'class', targetClass.name, false, false,
+            void 0, classInitializers) || newClass;
+    }
+    return [
+        newClass,
+        (instance) => run(protoInitializers, instance),
+        () => run(classInitializers, newClass)
+    ].concat(elementValues);
+}";
+
+#[test]
+fn decorators() {
+    let options = DecoratorsOptions::default();
+    test_decorators(
+        "@dec class A {
+            @m method() {}
+            @f field = 1;
+            @a accessor x = 2;
+            @s static y;
+            #p = 3;
+            @g get z() { return 1; }
+        }",
+        &format!(
+            "var _init_field, _init_x, _init_y, _A, _initClass, _initProto;
+            {APPLY_DECS}
+            class A {{
+                static {{
+                    [_A, _initProto, _initClass, _init_field, _init_x, _init_y] = _applyDecs(this, [
+                        [[m], 'method', 'method', false, false],
+                        [[f], 'field', 'field', false, false],
+                        [[a], 'accessor', 'x', false, false],
+                        [[s], 'field', 'y', true, false],
+                        [[g], 'getter', 'z', false, false]
+                    ], [dec]);
+                }}
+                method() {{}}
+                field = (_initProto(this), _init_field(this, 1));
+                #_x = _init_x(this, 2);
+                get x() {{ return this.#_x; }}
+                set x(value) {{ this.#_x = value; }}
+                static y = _init_y(this);
+                #p = 3;
+                get z() {{ return 1; }}
+            }}
+            A = _A;
+            _initClass();"
+        ),
+        options,
+    );
+    test_decorators(
+        "const B = @dec class { @f a; };",
+        &format!(
+            "var _init_a, _Class, _initClass, _initProto;
+            {APPLY_DECS}
+            const B = (class {{
+                static {{
+                    [_Class, _initProto, _initClass, _init_a] =
+                        _applyDecs(this, [[[f], 'field', 'a', false, false]], [dec]);
+                }}
+                a = (_initProto(this), _init_a(this));
+            }}, _initClass(), _Class);"
+        ),
+        options,
+    );
+}
+
+#[test]
+fn private_and_computed_decorators() {
+    test_decorators(
+        "class A { @(foo()) m() {} @f #x = 1; }",
+        &format!(
+            "var _dec, _init_x, _initProto;
+            {APPLY_DECS}
+            _dec = foo();
+            class A {{
+                static {{
+                    [, _initProto, , _init_x] = _applyDecs(this, [
+                        [[_dec], 'method', 'm', false, false],
+                        [[f], 'field', '#x', false, true, {{
+                            has: (object) => #x in object,
+                            get: (object) => object.#x,
+                            set: (object, value) => {{ object.#x = value; }}
+                        }}]
+                    ], []);
+                }}
+                m() {{}}
+                #x = (_initProto(this), _init_x(this, 1));
+            }}"
+        ),
+        DecoratorsOptions::default(),
+    );
+}
+
+#[test]
+fn private_method_decorators() {
+    test_decorators(
+        "@dec class A {
+            @m #m() { return A; }
+            @g get #g() { return 1; }
+            @a accessor #a = 1;
+            static b = A;
+        }",
+        &format!(
+            "var _call_m, _call_g, _init_a, _call_a, _A, _initClass, _initProto;
+            {APPLY_DECS}
+            class A {{
+                static {{
+                    [_A, _initProto, _initClass, _call_m, _call_g, _init_a, _call_a] =
+                        _applyDecs(this, [
+                            [[m], 'method', '#m', false, true, {{
+                                has: (object) => #m in object,
+                                get: (object) => object.#m
+                            }}, {{ value: function() {{ return _A; }} }}],
+                            [[g], 'getter', '#g', false, true, {{
+                                has: (object) => #g in object,
+                                get: (object) => object.#g
+                            }}, {{ get: function() {{ return 1; }} }}],
+                            [[a], 'accessor', '#a', false, true, {{
+                                has: (object) => #a in object,
+                                get: (object) => object.#a,
+                                set: (object, value) => {{ object.#a = value; }}
+                            }}, {{
+                                get: function() {{ return this.#_a; }},
+                                set: function(value) {{ this.#_a = value; }}
+                            }}]
+                        ], [dec]);
+                }}
+                get #m() {{ return _call_m.value; }}
+                get #g() {{ return _call_g.get.call(this); }}
+                #_a = (_initProto(this), _init_a(this, 1));
+                get #a() {{ return _call_a.get.call(this); }}
+                set #a(value) {{ _call_a.set.call(this, value); }}
+                static b = _A;
+            }}
+            A = _A;
+            _initClass();"
+        ),
+        DecoratorsOptions::default(),
+    );
+}
+
+#[test]
+fn private_method_decorators_with_super() {
+    assert_eq!(
+        transform_errors("class A extends B { @m #m() { super.m(); } }", TransformTarget::ESNext),
+        ["Decorated private methods which use `super` can not be lowered"]
+    );
+}
+
+/// Lowers `source_text` for `target` and compares it with `expected`, after the declarations of
+/// the `helpers` which the lowered code calls
+fn test_lowered(source_text: &str, expected: &str, target: TransformTarget, helpers: &[&str]) {
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions { target, ..TransformOptions::default() };
+    let printed = print(source_text, source_type, options);
+    let mut lowered = printed.as_str();
+    for helper in helpers {
+        let declaration = format!("function {helper} (");
+        assert!(lowered.starts_with(&declaration), "for source {source_text}: {printed}");
+        lowered = lowered.split_once("\n}\n").expect("helpers are declared first").1;
+    }
+    assert_eq!(
+        lowered,
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+#[test]
+fn async_functions() {
+    let test = |source_text, expected| {
+        test_lowered(source_text, expected, TransformTarget::ES2016, &["_asyncToGenerator"]);
+    };
+    test(
+        "async function f(a) { return await a; }",
+        "function f(a) {
+            return _asyncToGenerator(this, arguments, function* () { return (yield a); });
+        }",
+    );
+    test(
+        "const f = async (a) => { await a; };",
+        "const f = (a) => _asyncToGenerator(this, void 0, function* () { (yield a); });",
+    );
+    test(
+        "class B extends A { async m() { return await super.m(); } }",
+        "class B extends A {
+            m() {
+                var _superprop_getM = () => super.m;
+                return _asyncToGenerator(this, arguments, function* () {
+                    return (yield _superprop_getM().call(this));
+                });
+            }
+        }",
+    );
+    // Async functions are not lowered for ES2017
+    test_target("async function f() {}", "async function f() {}", TransformTarget::ES2017);
+}
+
+fn test_generator(source_text: &str, expected: &str) {
+    test_lowered(source_text, expected, TransformTarget::ES5, &["_regenerator"]);
+}
+
+#[test]
+fn generators() {
+    test_generator(
+        "function* f(a) { for (let i = 0; i < a; i++) yield i; }",
+        "function f(a) {
+            var i;
+            return _regenerator(function (_context) {
+                while (1) switch (_context.prev = _context.next) {
+                    case 0:
+                        i = 0;
+                    case 1:
+                        if (!(i < a)) { _context.next = 7; break; }
+                        _context.next = 4;
+                        return i;
+                    case 4:
+                        i++;
+                        _context.next = 1;
+                        break;
+                    case 7:
+                    case 'end':
+                        return _context.stop();
+                }
+            }, this);
+        }",
+    );
+}
+
+#[test]
+fn generators_try_statements() {
+    test_generator(
+        "function* f() { try { yield 1; } catch (e) { g(e); } finally { yield* h(); } }",
+        "function f() {
+            var e;
+            return _regenerator(function (_context) {
+                while (1) switch (_context.prev = _context.next) {
+                    case 0:
+                        _context.prev = 0;
+                        _context.next = 3;
+                        return 1;
+                    case 3:
+                        _context.next = 8;
+                        break;
+                    case 5:
+                        _context.prev = 5;
+                        e = _context.catch(0);
+                        g(e);
+                    case 8:
+                        _context.prev = 8;
+                        return _context.delegateYield(h(), 't0', 10);
+                    case 10:
+                        return _context.finish(8);
+                    case 11:
+                    case 'end':
+                        return _context.stop();
+                }
+            }, this, [[0, 5, 8, 11]]);
+        }",
+    );
+}
+
+/// Async functions become generators first
+#[test]
+fn generators_async_functions() {
+    test_lowered(
+        "async function f() { await g(); }",
+        "function f() {
+            return _asyncToGenerator(this, arguments, function () {
+                return _regenerator(function (_context) {
+                    while (1) switch (_context.prev = _context.next) {
+                        case 0:
+                            _context.next = 2;
+                            return g();
+                        case 2:
+                        case 'end':
+                            return _context.stop();
+                    }
+                }, this);
+            });
+        }",
+        TransformTarget::ES5,
+        &["_regenerator", "_asyncToGenerator"],
+    );
+}
+
+#[test]
+fn generators_target() {
+    test_target(
+        "function* f() { yield 1; }",
+        "function* f() { yield 1; }",
+        TransformTarget::ES2015,
+    );
+}
+
+/// Block scoped bindings are renamed when they would shadow other names once hoisted
+#[test]
+fn generators_block_scoped_bindings() {
+    test_generator(
+        "function* f(e) { { let a = 1; yield { a }; } try { yield; } catch (e) { g(e); } return [a, e]; }",
+        "function f(e) {
+            var _a, _e;
+            return _regenerator(function (_context) {
+                while (1) switch (_context.prev = _context.next) {
+                    case 0:
+                        _a = 1;
+                        _context.next = 3;
+                        return { a: _a };
+                    case 3:
+                        _context.prev = 3;
+                        _context.next = 6;
+                        return;
+                    case 6:
+                        _context.next = 11;
+                        break;
+                    case 8:
+                        _context.prev = 8;
+                        _e = _context.catch(3);
+                        g(_e);
+                    case 11:
+                        return _context.abrupt('return', [a, e]);
+                    case 12:
+                    case 'end':
+                        return _context.stop();
+                }
+            }, this, [[3, 8]]);
+        }",
+    );
+}
+
+/// The messages of the errors of transforming the module `source_text`
+fn transform_errors(source_text: &str, target: TransformTarget) -> std::vec::Vec<String> {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let mut program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let options = TransformOptions { target, ..TransformOptions::default() };
+    let ret = Transformer::new(&allocator, source_text, source_type, options).build(&mut program);
+    ret.err().unwrap_or_default().iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn unsupported_generators() {
+    let test = |source_text, expected: &str| {
+        assert_eq!(
+            transform_errors(source_text, TransformTarget::ES5),
+            [expected],
+            "for source {source_text}"
+        );
+    };
+    test("async function* f() {}", "Async generators can not be lowered to ES5");
+    test("function* f() { super.a; }", "Generators with `super` can not be lowered to ES5");
+    test(
+        "function* f() { let [a = yield] = b; }",
+        "Generators with `yield` in patterns can not be lowered to ES5",
+    );
+    test(
+        "function* f() { for (let i = 0; i < 3; i++) { g(() => i); yield; } }",
+        "Generators whose closures capture the bindings of loop iterations can not be lowered to ES5",
+    );
+    // The loop is not part of the state machine without `yield`
+    assert!(transform_errors(
+        "function* f() { for (let i = 0; i < 3; i++) { g(() => i); } yield; }",
+        TransformTarget::ES5
+    )
+    .is_empty());
+    assert!(transform_errors("async function* f() {}", TransformTarget::ES2015).is_empty());
+}
+
+/// Runs the generators lowered to ES5 with Node.js, and compares what they log with the original
+/// generators. Skipped if Node.js is not installed.
+#[test]
+fn generators_runtime() {
+    let run = |source_text: &str| {
+        let output = std::process::Command::new("node").arg("-e").arg(source_text).output().ok()?;
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        Some(String::from_utf8(output.stdout).unwrap())
+    };
+    let source_text = r#"
+    function* range(n) { for (let i = 0; i < n; i++) yield i; }
+    function* keys(object) { for (const key in object) yield key; }
+    function* delegate() { const result = yield* range(2); yield* keys({ a: 1, b: 2 }); return result; }
+    function* guarded(log) {
+        try { yield 1; yield 2; } catch (e) { log.push("caught " + e); yield 3; } finally { log.push("finally"); }
+    }
+    function* sent() { const a = yield "first"; const b = yield a + 1; return [a, b, arguments.length]; }
+    function* shadowed(e) { { let e = 1; yield e; } try { throw 2; } catch (e) { yield e; } return e; }
+    const log = [];
+    log.push([...range(3)].join());
+    log.push([...delegate()].join());
+    const it = guarded(log);
+    log.push(it.next().value, it.throw("error").value, JSON.stringify(it.next()));
+    const early = guarded(log);
+    early.next();
+    log.push(JSON.stringify(early.return(4)));
+    const s = sent(1, 2);
+    log.push(s.next().value, s.next(10).value, JSON.stringify(s.next(20)));
+    log.push([...shadowed(0)].join(), JSON.stringify(shadowed(0).next()));
+    console.log(log.join("\n"));"#;
+    let options = TransformOptions { target: TransformTarget::ES5, ..TransformOptions::default() };
+    let lowered = print(source_text, SourceType::default(), options);
+    assert!(!lowered.contains("function*"));
+    let Some(expected) = run(source_text) else { return };
+    assert_eq!(run(&lowered).as_deref(), Some(expected.as_str()));
+}
+
+fn test_define(source_text: &str, expected: &str, replacements: &[(&str, &str)]) {
+    let source_type = SourceType::default().with_module(true);
+    let replacements = replacements
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), value.parse().unwrap()))
+        .collect();
+    let options =
+        TransformOptions { define: DefineOptions { replacements }, ..TransformOptions::default() };
+    assert_eq!(
+        print(source_text, source_type, options),
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+#[test]
+fn define() {
+    let production = [("process.env.NODE_ENV", "\"production\""), ("__DEV__", "false")];
+    test_define(
+        "if (process.env.NODE_ENV !== 'production') { warn(); } else { run(); }",
+        "run();",
+        &production,
+    );
+    test_define(
+        "const a = __DEV__ ? check() : 1, b = __DEV__ && check(), c = process.env['NODE_ENV'];",
+        "const a = 1, b = false, c = 'production';",
+        &production,
+    );
+    // `var`s in removed branches are hoisted
+    test_define("if (__DEV__) { var a = f(); } g();", "var a; g();", &production);
+    // Declared names are not global
+    test_define(
+        "function f(__DEV__, process) { return __DEV__ || process.env.NODE_ENV; }",
+        "function f(__DEV__, process) { return __DEV__ || process.env.NODE_ENV; }",
+        &production,
+    );
+    // Bindings only shadow the globals in their scopes
+    test_define(
+        "function f(__DEV__) { return __DEV__; } const g = () => __DEV__;
+        { let undefined = 1; } if (__DEV__ === undefined) a();",
+        "function f(__DEV__) { return __DEV__; } const g = () => false; { let undefined = 1; }",
+        &production,
+    );
+    // Assignments are kept
+    test_define("process.env.NODE_ENV = 'test';", "process.env.NODE_ENV = 'test';", &production);
+    test_define(
+        "global.a = import.meta.env.MODE + process.env.B;",
+        "globalThis.a = 'development' + ({ B: 1, 'c-d': [null] }).B;",
+        &[
+            ("global", "globalThis"),
+            ("import.meta.env.MODE", "\"development\""),
+            ("process.env", "{ \"B\": 1, \"c-d\": [null] }"),
+        ],
+    );
+    assert!("a b".parse::<DefineValue>().is_err());
+}
+
+fn test_module(source_text: &str, expected: &str, module: ModuleFormat) {
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions { module, ..TransformOptions::default() };
+    assert_eq!(
+        print(source_text, source_type, options),
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+#[test]
+fn commonjs() {
+    test_module(
+        "import a, { b } from 'm'; export const c = a(b);",
+        r#"
+        "use strict";
+        function _export(target, all) {
+            for (var name in all) Object.defineProperty(target, name, { enumerable: true, get: all[name] });
+        }
+        function _interopRequireWildcard(obj) {
+            if (obj && obj.__esModule) return obj;
+            var namespace = {};
+            if (obj != null) {
+                for (var key in obj) {
+                    if (key !== "default" && Object.prototype.hasOwnProperty.call(obj, key)) {
+                        Object.defineProperty(namespace, key, Object.getOwnPropertyDescriptor(obj, key));
+                    }
+                }
+            }
+            namespace.default = obj;
+            return namespace;
+        }
+        Object.defineProperty(exports, "__esModule", { value: true });
+        _export(exports, { c: function() { return c; } });
+        var _m = _interopRequireWildcard(require("m"));
+        const c = (0, _m.default)(_m.b);
+        "#,
+        ModuleFormat::CommonJs,
+    );
+    test_module(
+        "import { a } from 'm'; function f(a) { return a; } f({ a }); this;",
+        r#"
+        "use strict";
+        var _m = require("m");
+        function f(a) { return a; }
+        f({ a: _m.a });
+        void 0;
+        "#,
+        ModuleFormat::CommonJs,
+    );
+    test_module(
+        "export { a as b } from 'm'; export default function () {}",
+        r#"
+        "use strict";
+        function _export(target, all) {
+            for (var name in all) Object.defineProperty(target, name, { enumerable: true, get: all[name] });
+        }
+        Object.defineProperty(exports, "__esModule", { value: true });
+        _export(exports, { b: function() { return _m.a; }, default: function() { return _default; } });
+        var _m = require("m");
+        function _default() {}
+        "#,
+        ModuleFormat::CommonJs,
+    );
+}
+
+#[test]
+fn es_module() {
+    test_module(
+        "const a = require('a'); const { b, c: d } = require('b'); require('c'); exports.e = a(b, d);",
+        "import a from 'a'; import { b, c as d } from 'b'; import 'c'; export const e = a(b, d);",
+        ModuleFormat::EsModule,
+    );
+    test_module(
+        "module.exports = function () {}; console.log(__filename);",
+        "export default function() {}; console.log(import.meta.filename);",
+        ModuleFormat::EsModule,
+    );
+    // Conditional exports are kept
+    test_module(
+        "if (a) { exports.b = 1; } function f() { return require('c'); }",
+        r#"
+        import { createRequire as _createRequire } from "module";
+        const require = _createRequire(import.meta.url);
+        var module = { exports: {} }, exports = module.exports;
+        if (a) { exports.b = 1; }
+        function f() { return require('c'); }
+        export default module.exports;
+        "#,
+        ModuleFormat::EsModule,
+    );
+    // `require`s after other statements are evaluated in order
+    test_module(
+        "const a = require('a'); function f() {} a(); const b = require('b'); exports.c = b;",
+        r#"
+        import { createRequire as _createRequire } from "module";
+        const require = _createRequire(import.meta.url);
+        import a from 'a';
+        function f() {}
+        a();
+        const b = require('b');
+        export { b as c };
+        "#,
+        ModuleFormat::EsModule,
+    );
+    // `this` of the module scope is `exports`
+    test_module(
+        "this.a = 1; function f() { return this; }",
+        "export const a = 1; function f() { return this; }",
+        ModuleFormat::EsModule,
+    );
+    test_module(
+        "console.log(this);",
+        "
+        var module = { exports: {} }, exports = module.exports;
+        console.log(exports);
+        export default module.exports;
+        ",
+        ModuleFormat::EsModule,
+    );
+}
+
+fn test_dynamic_import(source_text: &str, expected: &str, dynamic_import: DynamicImport) {
+    let source_type = SourceType::default().with_module(true);
+    let options = TransformOptions {
+        module: ModuleFormat::CommonJs,
+        dynamic_import,
+        ..TransformOptions::default()
+    };
+    assert_eq!(
+        print(source_text, source_type, options),
+        print(expected, source_type, TransformOptions::default()),
+        "for source {source_text}"
+    );
+}
+
+#[test]
+fn dynamic_import() {
+    test_dynamic_import(
+        "import('a', { with: { type: 'json' } }); import(b, c);",
+        r#"
+        "use strict";
+        function _interopRequireWildcard(obj) {
+            if (obj && obj.__esModule) return obj;
+            var namespace = {};
+            if (obj != null) {
+                for (var key in obj) {
+                    if (key !== "default" && Object.prototype.hasOwnProperty.call(obj, key)) {
+                        Object.defineProperty(namespace, key, Object.getOwnPropertyDescriptor(obj, key));
+                    }
+                }
+            }
+            namespace.default = obj;
+            return namespace;
+        }
+        Promise.resolve().then(function() { return _interopRequireWildcard(require('a')); });
+        Promise.resolve(''.concat(b), c).then(function(specifier) {
+            return _interopRequireWildcard(require(specifier));
+        });
+        "#,
+        DynamicImport::Require,
+    );
+    test_dynamic_import(
+        "import { a } from 'a'; import(a);",
+        "'use strict'; var _a = require('a'); import(_a.a);",
+        DynamicImport::Preserve,
+    );
+    test_dynamic_import(
+        "import('a', { with: { type: 'json' } });",
+        r#"
+        "use strict";
+        var _runtime = require("runtime");
+        (0, _runtime.load)('a', { with: { type: 'json' } });
+        "#,
+        DynamicImport::Runtime { source: "runtime".to_string(), name: "load".to_string() },
+    );
+}
+
+/// Removes top level `debugger` statements
+struct RemoveDebugger;
+
+impl<'a> TransformPass<'a> for RemoveDebugger {
+    fn name(&self) -> &'static str {
+        "remove_debugger"
+    }
+
+    fn invalidates_scopes(&self) -> bool {
+        false
+    }
+
+    fn transform(&mut self, program: &mut Program<'a>, _ctx: &TransformContext<'a>) {
+        program.body.retain(|stmt| !matches!(stmt, Statement::DebuggerStatement(_)));
+    }
+}
+
+/// Records the names of the symbols when it runs
+struct CollectSymbols(Rc<RefCell<Vec<String>>>);
+
+impl<'a> TransformPass<'a> for CollectSymbols {
+    fn name(&self) -> &'static str {
+        "collect_symbols"
+    }
+
+    fn needs_semantic(&self) -> bool {
+        true
+    }
+
+    fn invalidates_scopes(&self) -> bool {
+        false
+    }
+
+    fn transform(&mut self, _program: &mut Program<'a>, ctx: &TransformContext<'a>) {
+        let symbols = ctx.symbols().expect("symbols are built for the pass");
+        self.0.borrow_mut().extend(symbols.names.iter().map(ToString::to_string));
+    }
+}
+
+#[test]
+fn custom_passes() {
+    let allocator = Allocator::default();
+    let source_text = "let a: A; type A = string; debugger;";
+    let source_type = SourceType::default().with_module(true).with_typescript(true);
+    let mut program = Parser::new(&allocator, source_text, source_type).parse().program;
+    let before = Rc::new(RefCell::new(vec![]));
+    let after = Rc::new(RefCell::new(vec![]));
+    let transformer =
+        Transformer::new(&allocator, source_text, source_type, TransformOptions::default())
+            .with_pass(RemoveDebugger)
+            .with_pass_before("typescript", CollectSymbols(Rc::clone(&before)))
+            .with_pass_after("typescript", CollectSymbols(Rc::clone(&after)));
+    assert_eq!(
+        transformer.pass_names(),
+        ["decorators", "collect_symbols", "typescript", "collect_symbols", "remove_debugger"]
+    );
+    assert!(transformer.build(&mut program).is_ok());
+    assert_eq!(*before.borrow(), ["a", "A"]);
+    // Rebuilt, as stripping types invalidates the scopes
+    assert_eq!(*after.borrow(), ["a"]);
+    assert_eq!(
+        Codegen::new(source_text.len(), CodegenOptions::default()).build(&program),
+        "let a;\n"
+    );
+}
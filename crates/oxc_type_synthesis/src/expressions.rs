@@ -327,6 +327,8 @@ pub(crate) fn synthesize_expression<T: FSResolver>(
             );
             return TypeId::ERROR_TYPE;
         }
+        // The syntax error of the missing expression has been reported by the parser
+        ast::Expression::ErrorExpression(_) => return TypeId::ERROR_TYPE,
         ast::Expression::JSXElement(item) => {
             checking_data.raise_unimplemented_error(
                 "JSXElement expression",
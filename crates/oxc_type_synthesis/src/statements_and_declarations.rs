@@ -71,7 +71,7 @@ pub(crate) fn hoist_statements<T: FSResolver>(
             Statement::Declaration(declaration) => match declaration {
                 ast::Declaration::VariableDeclaration(declaration) => {
                     let is_declare = declaration.modifiers.contains(ast::ModifierKind::Declare);
-                    let is_const = matches!(declaration.kind, ast::VariableDeclarationKind::Const);
+                    let is_const = declaration.kind.is_const() || declaration.kind.is_using();
 
                     for declaration in declaration.declarations.iter() {
                         let ty = declaration.id.type_annotation.as_ref().map(|ta| {
@@ -212,7 +212,7 @@ pub(crate) fn synthesize_statement<T: FSResolver>(
             "continue statement",
             oxc_span_to_source_map_span(item.span),
         ),
-        ast::Statement::EmptyStatement(_) => {}
+        ast::Statement::EmptyStatement(_) | ast::Statement::ErrorStatement(_) => {}
         ast::Statement::DebuggerStatement(_) => {}
         ast::Statement::DoWhileStatement(item) => checking_data.raise_unimplemented_error(
             "do while statement",
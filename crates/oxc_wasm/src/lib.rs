@@ -4,10 +4,10 @@ use std::{cell::RefCell, collections::BTreeMap, rc::Rc, sync::Arc};
 
 use oxc_allocator::Allocator;
 use oxc_ast_lower::AstLower;
+use oxc_codegen::{Codegen, CodegenOptions};
 use oxc_diagnostics::Error;
-use oxc_formatter::{Formatter, FormatterOptions};
 use oxc_linter::{LintContext, Linter};
-use oxc_minifier::{CompressOptions, Compressor, ManglerBuilder, Printer, PrinterOptions};
+use oxc_minifier::{LicenseComments, Minifier, MinifierOptions};
 use oxc_parser::{Parser, ParserReturn};
 use oxc_query::{schema, Adapter, SCHEMA_TEXT};
 use oxc_semantic::{SemanticBuilder, SemanticBuilderReturn};
@@ -199,8 +199,12 @@ impl Oxc {
         }
 
         if run_options.format() {
-            let formatter_options = FormatterOptions { indentation: formatter_options.indentation };
-            let printed = Formatter::new(source_text.len(), formatter_options).build(program);
+            let codegen_options = CodegenOptions {
+                indentation: formatter_options.indentation,
+                typescript: source_type.is_typescript(),
+                ..CodegenOptions::default()
+            };
+            let printed = Codegen::new(source_text.len(), codegen_options).build(program);
             self.formatted_text = printed;
         }
 
@@ -210,19 +214,13 @@ impl Oxc {
         }
 
         if run_options.minify() {
-            let ast_lower_ret = AstLower::new(&allocator, source_text, source_type).build(program);
-            let hir = allocator.alloc(ast_lower_ret.program);
-            let semantic = ast_lower_ret.semantic;
-
-            let mut printer = Printer::new(self.source_text.len(), PrinterOptions);
-            let _semantic =
-                Compressor::new(&allocator, semantic, CompressOptions::default()).build(hir);
-            if minifier_options.mangle() {
-                let mangler = ManglerBuilder::new(source_text, source_type).build(hir);
-                printer.with_mangler(mangler);
-            }
-
-            self.minified_text = printer.build(hir);
+            let options = MinifierOptions {
+                mangle: minifier_options.mangle(),
+                compress: minifier_options.compress_options()?,
+                license_comments: LicenseComments::None,
+                ..MinifierOptions::default()
+            };
+            self.minified_text = Minifier::new(source_text, source_type, options).build();
         }
 
         if run_options.type_check() {
@@ -238,6 +236,26 @@ impl Oxc {
         self.diagnostics.borrow_mut().extend(diagnostics);
     }
 
+    /// Returns the nodes matching an [esquery](https://github.com/estools/esquery) selector,
+    /// e.g. `CallExpression[callee.name="require"]`, in source order
+    ///
+    /// # Errors
+    /// The selector is invalid
+    #[wasm_bindgen(js_name = querySelector)]
+    pub fn query_selector(
+        &self,
+        parser_options: &OxcParserOptions,
+        selector: &str,
+    ) -> Result<Vec<JsValue>, JsError> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path("test.tsx").unwrap_or_default();
+        let ret = Parser::new(&allocator, &self.source_text, source_type)
+            .allow_return_outside_function(parser_options.allow_return_outside_function)
+            .parse();
+        let nodes = ret.program.query(selector)?;
+        Ok(nodes.iter().map(|node| node.serialize(&self.serializer).unwrap()).collect())
+    }
+
     /// # Errors
     /// Will return `Err` only if a serde wasm bindgen serialization error occurs.
     #[wasm_bindgen]
@@ -253,10 +271,11 @@ impl Oxc {
             return "'test.tsx' source type invalid, this should never happen.\nPlease open an issue at https://github.com/web-infra-dev/oxc".to_string().serialize(&self.serializer);
         };
 
-        let ParserReturn { errors: parse_errors, panicked, program: returned_program, trivias } =
-            Parser::new(&allocator, source_text, source_type)
-                .allow_return_outside_function(parser_options.allow_return_outside_function)
-                .parse();
+        let ParserReturn {
+            errors: parse_errors, panicked, program: returned_program, trivias, ..
+        } = Parser::new(&allocator, source_text, source_type)
+            .allow_return_outside_function(parser_options.allow_return_outside_function)
+            .parse();
 
         let allocated_program = allocator.alloc(returned_program);
 
@@ -287,9 +306,10 @@ impl Oxc {
         let arc_adapter = Arc::from(&adapter);
 
         let Ok(arguments): Result<BTreeMap<Arc<str>, TransparentValue>, _> =
-            serde_json::from_str(query_arguments) else {
-                return "Query arguments is not valid json string, this should never happen.\nPlease open an issue at https://github.com/web-infra-dev/oxc".serialize(&self.serializer)
-            };
+            serde_json::from_str(query_arguments)
+        else {
+            return "Query arguments is not valid json string, this should never happen.\nPlease open an issue at https://github.com/web-infra-dev/oxc".serialize(&self.serializer);
+        };
 
         execute_query(schema(), arc_adapter, query, arguments).map_or_else(
             |e| e.to_string().serialize(&self.serializer),
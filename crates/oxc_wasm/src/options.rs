@@ -1,3 +1,4 @@
+use oxc_minifier::CompressOptions;
 use wasm_bindgen::prelude::*;
 
 #[allow(clippy::struct_excessive_bools)]
@@ -122,9 +123,10 @@ impl OxcFormatterOptions {
 }
 
 #[wasm_bindgen]
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone)]
 pub struct OxcMinifierOptions {
     mangle: bool,
+    compress: JsValue,
 }
 
 #[wasm_bindgen]
@@ -135,7 +137,7 @@ impl OxcMinifierOptions {
     }
 
     #[wasm_bindgen(getter)]
-    pub fn mangle(self) -> bool {
+    pub fn mangle(&self) -> bool {
         self.mangle
     }
 
@@ -143,6 +145,28 @@ impl OxcMinifierOptions {
     pub fn set_mangle(&mut self, yes: bool) {
         self.mangle = yes;
     }
+
+    /// The terser compress options, for example `{ sequences: false, drop_console: true }`
+    #[wasm_bindgen(getter)]
+    pub fn compress(&self) -> JsValue {
+        self.compress.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_compress(&mut self, options: JsValue) {
+        self.compress = options;
+    }
+}
+
+impl OxcMinifierOptions {
+    /// # Errors
+    /// Serde deserialization error of the compress options
+    pub fn compress_options(&self) -> Result<CompressOptions, serde_wasm_bindgen::Error> {
+        if self.compress.is_undefined() || self.compress.is_null() {
+            return Ok(CompressOptions::default());
+        }
+        serde_wasm_bindgen::from_value(self.compress.clone())
+    }
 }
 
 #[wasm_bindgen]
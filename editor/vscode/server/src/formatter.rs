@@ -0,0 +1,43 @@
+use std::path::Path;
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_prettier::{Prettier, PrettierOptions};
+use oxc_span::SourceType;
+use ropey::Rope;
+use tower_lsp::lsp_types::{Position, Range, TextEdit};
+
+#[derive(Debug, Default)]
+pub struct ServerFormatter {
+    options: PrettierOptions,
+}
+
+impl ServerFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Edits replacing the whole document with its formatted code, none if it is formatted
+    /// already, or `None` if it cannot be parsed
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn run(&self, path: &Path, source_text: &str) -> Option<Vec<TextEdit>> {
+        let source_type = SourceType::from_path(path).ok()?;
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        if !ret.errors.is_empty() {
+            return None;
+        }
+        let program = allocator.alloc(ret.program);
+        let formatted = Prettier::new(source_text, &ret.trivias, self.options).build(program);
+        if formatted == source_text {
+            return Some(vec![]);
+        }
+
+        // Positions count UTF-16 code units in the line
+        let rope = Rope::from_str(source_text);
+        let last_line = rope.len_lines() - 1;
+        let end_character = rope.line(last_line).chars().map(char::len_utf16).sum::<usize>();
+        let end = Position::new(last_line as u32, end_character as u32);
+        Some(vec![TextEdit { range: Range::new(Position::new(0, 0), end), new_text: formatted }])
+    }
+}
@@ -17,9 +17,9 @@ use oxc_diagnostics::{
     Error, Severity,
 };
 use oxc_linter::{LintContext, Linter, RuleCategory, RULES};
-use oxc_parser::Parser;
+use oxc_parser::{detect_module_kind, Parser};
 use oxc_semantic::SemanticBuilder;
-use oxc_span::{SourceType, VALID_EXTENSIONS};
+use oxc_span::{ModuleKind, SourceType, VALID_EXTENSIONS};
 use ropey::Rope;
 use tower_lsp::lsp_types::{self, Position, Range, Url};
 
@@ -220,13 +220,20 @@ impl IsolatedLintHandler {
         let source_text =
             fs::read_to_string(path).unwrap_or_else(|_| panic!("Failed to read {path:?}"));
         let allocator = Allocator::default();
-        let source_type =
+        let mut source_type =
             SourceType::from_path(path).unwrap_or_else(|_| panic!("Incorrect {path:?}"));
+        // `.js` and `.jsx` files may be either CommonJS scripts or ES modules
+        if path.extension().map_or(false, |ext| ext == "js" || ext == "jsx") {
+            source_type = source_type
+                .with_script(detect_module_kind(&source_text, source_type) == ModuleKind::Script);
+        }
+        // Recover from syntax errors so the rest of a file in the middle of an edit is still linted
         let ret = Parser::new(&allocator, &source_text, source_type)
             .allow_return_outside_function(true)
+            .recover_from_errors(true)
             .parse();
 
-        if !ret.errors.is_empty() {
+        if ret.panicked {
             let reports = ret
                 .errors
                 .into_iter()
@@ -236,6 +243,12 @@ impl IsolatedLintHandler {
             return Some(Self::wrap_diagnostics(path, &source_text, reports));
         };
 
+        let mut all_reports = ret
+            .errors
+            .into_iter()
+            .map(|diagnostic| ErrorReport { error: diagnostic, fixed_content: None })
+            .collect::<Vec<_>>();
+
         let program = allocator.alloc(ret.program);
         let semantic_ret = SemanticBuilder::new(&source_text, source_type)
             .with_trivias(ret.trivias)
@@ -246,44 +259,41 @@ impl IsolatedLintHandler {
             let reports = semantic_ret
                 .errors
                 .into_iter()
-                .map(|diagnostic| ErrorReport { error: diagnostic, fixed_content: None })
-                .collect();
-            return Some(Self::wrap_diagnostics(path, &source_text, reports));
+                .map(|diagnostic| ErrorReport { error: diagnostic, fixed_content: None });
+            all_reports.extend(reports);
+            return Some(Self::wrap_diagnostics(path, &source_text, all_reports));
         };
 
         let lint_ctx = LintContext::new(&Rc::new(semantic_ret.semantic));
         let result = linter.run(lint_ctx);
 
-        if result.is_empty() {
+        if result.is_empty() && all_reports.is_empty() {
             return None;
         }
 
         if linter.has_fix() {
-            let reports = result
-                .into_iter()
-                .map(|msg| {
-                    let fixed_content = msg.fix.map(|f| FixedContent {
-                        code: f.content.to_string(),
-                        range: Range {
-                            start: offset_to_position(f.span.start as usize, &source_text)
-                                .unwrap_or_default(),
-                            end: offset_to_position(f.span.end as usize, &source_text)
-                                .unwrap_or_default(),
-                        },
-                    });
-
-                    ErrorReport { error: msg.error, fixed_content }
-                })
-                .collect::<Vec<ErrorReport>>();
+            let reports = result.into_iter().map(|msg| {
+                let fixed_content = msg.fix.map(|f| FixedContent {
+                    code: f.content.to_string(),
+                    range: Range {
+                        start: offset_to_position(f.span.start as usize, &source_text)
+                            .unwrap_or_default(),
+                        end: offset_to_position(f.span.end as usize, &source_text)
+                            .unwrap_or_default(),
+                    },
+                });
 
-            return Some(Self::wrap_diagnostics(path, &source_text, reports));
+                ErrorReport { error: msg.error, fixed_content }
+            });
+            all_reports.extend(reports);
+            return Some(Self::wrap_diagnostics(path, &source_text, all_reports));
         }
 
         let errors = result
             .into_iter()
-            .map(|diagnostic| ErrorReport { error: diagnostic.error, fixed_content: None })
-            .collect();
-        Some(Self::wrap_diagnostics(path, &source_text, errors))
+            .map(|diagnostic| ErrorReport { error: diagnostic.error, fixed_content: None });
+        all_reports.extend(errors);
+        Some(Self::wrap_diagnostics(path, &source_text, all_reports))
     }
 
     fn wrap_diagnostics(
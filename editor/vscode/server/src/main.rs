@@ -1,7 +1,10 @@
+mod formatter;
 mod linter;
 mod options;
+mod selection;
 mod walk;
 
+use crate::formatter::ServerFormatter;
 use crate::linter::{DiagnosticReport, ServerLinter};
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -14,9 +17,11 @@ use tower_lsp::jsonrpc::{Error, ErrorCode, Result};
 use tower_lsp::lsp_types::{
     CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
     CodeActionProviderCapability, CodeActionResponse, Diagnostic, DidChangeTextDocumentParams,
-    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializeResult,
-    InitializedParams, MessageType, ServerCapabilities, ServerInfo, TextDocumentSyncCapability,
-    TextDocumentSyncKind, TextEdit, Url, WorkDoneProgressOptions, WorkspaceEdit,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    DocumentFormattingParams, InitializeParams, InitializeResult, InitializedParams, MessageType,
+    OneOf, SelectionRange, SelectionRangeParams, SelectionRangeProviderCapability,
+    ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit,
+    Url, WorkDoneProgressOptions, WorkspaceEdit,
 };
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
@@ -25,7 +30,10 @@ struct Backend {
     client: Client,
     root_uri: OnceCell<Option<Url>>,
     server_linter: ServerLinter,
+    server_formatter: ServerFormatter,
     diagnostics_report_map: DashMap<String, Vec<DiagnosticReport>>,
+    /// Contents of the open documents, which may not be saved yet
+    document_map: DashMap<String, String>,
 }
 
 #[tower_lsp::async_trait]
@@ -49,6 +57,8 @@ impl LanguageServer for Backend {
                         resolve_provider: None,
                     },
                 )),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
                 ..ServerCapabilities::default()
             },
         })
@@ -79,13 +89,40 @@ impl LanguageServer for Backend {
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // The whole document is sent with `TextDocumentSyncKind::FULL`
+        if let Some(change) = params.content_changes.into_iter().last() {
+            self.document_map.insert(params.text_document.uri.to_string(), change.text);
+        }
         self.handle_file_update(params.text_document.uri).await;
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.document_map.insert(params.text_document.uri.to_string(), params.text_document.text);
         self.handle_file_update(params.text_document.uri).await;
     }
 
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        // The saved file is read again when the document is not open
+        self.document_map.remove(&params.text_document.uri.to_string());
+    }
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let Some((path, source_text)) = self.read_document(&params.text_document.uri) else {
+            return Ok(None);
+        };
+        Ok(self.server_formatter.run(&path, &source_text))
+    }
+
+    async fn selection_range(
+        &self,
+        params: SelectionRangeParams,
+    ) -> Result<Option<Vec<SelectionRange>>> {
+        let Some((path, source_text)) = self.read_document(&params.text_document.uri) else {
+            return Ok(None);
+        };
+        Ok(selection::selection_ranges(&path, &source_text, &params.positions))
+    }
+
     async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
         let uri = params.text_document.uri;
 
@@ -140,6 +177,16 @@ impl Backend {
         })
     }
 
+    /// The path and the contents of a document, which may not be saved yet
+    fn read_document(&self, uri: &Url) -> Option<(PathBuf, String)> {
+        let path = uri.to_file_path().ok()?;
+        let source_text = match self.document_map.get(&uri.to_string()) {
+            Some(source_text) => source_text.clone(),
+            None => std::fs::read_to_string(&path).ok()?,
+        };
+        Some((path, source_text))
+    }
+
     #[allow(clippy::ptr_arg)]
     async fn publish_all_diagnostics(&self, result: &Vec<(PathBuf, Vec<Diagnostic>)>) {
         join_all(result.iter().map(|(path, diagnostics)| {
@@ -183,7 +230,9 @@ async fn main() {
         client,
         root_uri: OnceCell::new(),
         server_linter,
+        server_formatter: ServerFormatter::new(),
         diagnostics_report_map,
+        document_map: DashMap::new(),
     })
     .finish();
 
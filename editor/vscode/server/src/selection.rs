@@ -0,0 +1,80 @@
+use std::path::Path;
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_semantic::SemanticBuilder;
+use oxc_span::{GetSpan, SourceType, Span};
+use ropey::Rope;
+use tower_lsp::lsp_types::{Position, Range, SelectionRange};
+
+/// Selection ranges at each of `positions`, from the innermost node at the position up to the
+/// whole program, or `None` if the document cannot be parsed
+pub fn selection_ranges(
+    path: &Path,
+    source_text: &str,
+    positions: &[Position],
+) -> Option<Vec<SelectionRange>> {
+    let source_type = SourceType::from_path(path).ok()?;
+    let allocator = Allocator::default();
+    // Recover from syntax errors so ranges can be selected in the middle of an edit
+    let ret = Parser::new(&allocator, source_text, source_type)
+        .allow_return_outside_function(true)
+        .recover_from_errors(true)
+        .parse();
+    if ret.panicked {
+        return None;
+    }
+    let program = allocator.alloc(ret.program);
+    let semantic = SemanticBuilder::new(source_text, source_type).build(program).semantic;
+    let nodes = semantic.nodes();
+
+    let rope = Rope::from_str(source_text);
+    let ranges = positions
+        .iter()
+        .map(|&position| {
+            let empty = SelectionRange { range: Range::new(position, position), parent: None };
+            let Some(node) =
+                position_to_offset(&rope, position).and_then(|offset| nodes.node_at_offset(offset))
+            else {
+                return empty;
+            };
+            let mut spans = nodes
+                .ancestors(node.id())
+                .map(|node_id| nodes.kind(node_id).span())
+                .collect::<Vec<_>>();
+            // Nested nodes can share a span, such as the `Program` and its only statement in `a;`
+            spans.dedup();
+            spans
+                .into_iter()
+                .rev()
+                .fold(None, |parent, span| {
+                    Some(SelectionRange {
+                        range: span_to_range(&rope, span),
+                        parent: parent.map(Box::new),
+                    })
+                })
+                .unwrap_or(empty)
+        })
+        .collect();
+    Some(ranges)
+}
+
+/// Positions count UTF-16 code units in the line
+fn position_to_offset(rope: &Rope, position: Position) -> Option<u32> {
+    let line = rope.get_line(position.line as usize)?;
+    let character = (position.character as usize).min(line.len_utf16_cu());
+    let char_idx = rope.line_to_char(position.line as usize) + line.utf16_cu_to_char(character);
+    u32::try_from(rope.char_to_byte(char_idx)).ok()
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn offset_to_position(rope: &Rope, offset: u32) -> Position {
+    let char_idx = rope.byte_to_char(offset as usize);
+    let line = rope.char_to_line(char_idx);
+    let character = rope.line(line).char_to_utf16_cu(char_idx - rope.line_to_char(line));
+    Position::new(line as u32, character as u32)
+}
+
+fn span_to_range(rope: &Rope, span: Span) -> Range {
+    Range::new(offset_to_position(rope, span.start), offset_to_position(rope, span.end))
+}
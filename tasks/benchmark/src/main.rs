@@ -53,6 +53,7 @@ pub fn main() -> Result<(), String> {
     }
 
     bench_parser(&mut criterion, &files);
+    bench_trivia(&mut criterion);
     bench_semantic(&mut criterion, &files);
     bench_minifier(&mut criterion, &files);
     drop(criterion);
@@ -82,6 +83,40 @@ fn bench_parser(criterion: &mut Criterion, files: &[&TestFile]) {
     group.finish();
 }
 
+/// Source text dominated by the parts of the lexer scanned a word at a time:
+/// indentation, comments and string literals, as in unminified bundles
+fn trivia_source() -> String {
+    let mut source = String::new();
+    for i in 0..20_000 {
+        source.push_str(
+            "/**\n * A documentation comment which is long enough to span a few words.\n */\n",
+        );
+        source.push_str("        // A line comment following the indentation of nested code\n");
+        source.push_str(&format!(
+            "        const s{i} = \"a string literal with some text in it and an \\n escape\";\n"
+        ));
+    }
+    source
+}
+
+fn bench_trivia(criterion: &mut Criterion) {
+    let source_text = trivia_source();
+    let mut group = criterion.benchmark_group("parser");
+    group.throughput(Throughput::Bytes(source_text.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::from_parameter("trivia"),
+        &source_text,
+        |b, source_text| {
+            b.iter_with_large_drop(|| {
+                let allocator = Allocator::default();
+                _ = Parser::new(&allocator, source_text, SourceType::default()).parse();
+                allocator
+            });
+        },
+    );
+    group.finish();
+}
+
 fn bench_minifier(criterion: &mut Criterion, files: &[&TestFile]) {
     let mut group = criterion.benchmark_group("minifier");
     for file in files {
@@ -92,7 +127,9 @@ fn bench_minifier(criterion: &mut Criterion, files: &[&TestFile]) {
             |b, source_text| {
                 let source_type = SourceType::from_path(&file.file_name).unwrap();
                 let options = MinifierOptions::default();
-                b.iter_with_large_drop(|| Minifier::new(source_text, source_type, options).build());
+                b.iter_with_large_drop(|| {
+                    Minifier::new(source_text, source_type, options.clone()).build()
+                });
             },
         );
     }
@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use oxc_allocator::Allocator;
-use oxc_formatter::{Formatter, FormatterOptions};
+use oxc_codegen::{Codegen, CodegenOptions};
 use oxc_parser::Parser;
 use oxc_span::SourceType;
 
@@ -11,11 +11,11 @@ use crate::{
     test262::{Test262Case, TestFlag},
 };
 
-pub struct FormatterTest262Case {
+pub struct CodegenTest262Case {
     base: Test262Case,
 }
 
-impl Case for FormatterTest262Case {
+impl Case for CodegenTest262Case {
     fn new(path: PathBuf, code: String) -> Self {
         Self { base: Test262Case::new(path, code) }
     }
@@ -40,17 +40,17 @@ impl Case for FormatterTest262Case {
         let source_text = self.base.code();
         let is_module = self.base.meta().flags.contains(&TestFlag::Module);
         let source_type = SourceType::default().with_module(is_module);
-        let formatter_options = FormatterOptions::default();
-        let result = get_result(source_text, source_type, formatter_options);
+        let codegen_options = CodegenOptions::default();
+        let result = get_result(source_text, source_type, codegen_options);
         self.base.set_result(result);
     }
 }
 
-pub struct FormatterBabelCase {
+pub struct CodegenBabelCase {
     base: BabelCase,
 }
 
-impl Case for FormatterBabelCase {
+impl Case for CodegenBabelCase {
     fn new(path: PathBuf, code: String) -> Self {
         Self { base: BabelCase::new(path, code) }
     }
@@ -74,18 +74,18 @@ impl Case for FormatterBabelCase {
     fn run(&mut self) {
         let source_text = self.base.code();
         let source_type = self.base.source_type();
-        let formatter_options = FormatterOptions::default();
-        let result = get_result(source_text, source_type, formatter_options);
+        let codegen_options = CodegenOptions::default();
+        let result = get_result(source_text, source_type, codegen_options);
         self.base.set_result(result);
     }
 }
 
-fn get_result(source_text: &str, source_type: SourceType, options: FormatterOptions) -> TestResult {
+fn get_result(source_text: &str, source_type: SourceType, options: CodegenOptions) -> TestResult {
     let allocator = Allocator::default();
     let program1 = Parser::new(&allocator, source_text, source_type).parse().program;
-    let source_text1 = Formatter::new(source_text.len(), options).build(&program1);
+    let source_text1 = Codegen::new(source_text.len(), options).build(&program1);
     let program2 = Parser::new(&allocator, &source_text1, source_type).parse().program;
-    let source_text2 = Formatter::new(source_text1.len(), options).build(&program2);
+    let source_text2 = Codegen::new(source_text1.len(), options).build(&program2);
     if source_text1 == source_text2 {
         TestResult::Passed
     } else {